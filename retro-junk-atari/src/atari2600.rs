@@ -0,0 +1,132 @@
+//! Atari 2600 ROM analyzer.
+//!
+//! Supports:
+//! - 2600 ROMs (.a26, .bin)
+//!
+//! 2600 cartridges have no header. The console sees 4 KB of cartridge space
+//! at a time, so larger games switch banks, and the scheme mostly follows
+//! from the ROM size. Detection checks the size and that the reset vector
+//! in the last two bytes points into cartridge space (address line A12 set).
+
+use retro_junk_core::ReadSeek;
+use std::io::SeekFrom;
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+
+/// ROM sizes found on cartridges, in bytes.
+const ROM_SIZES: &[u64] = &[
+    2 * 1024,
+    4 * 1024,
+    8 * 1024,
+    12 * 1024,
+    16 * 1024,
+    32 * 1024,
+    64 * 1024,
+];
+
+/// Most common bank-switching scheme for a ROM size.
+fn bankswitch_scheme(size: u64) -> Option<&'static str> {
+    match size / 1024 {
+        2 | 4 => Some("None"),
+        8 => Some("F8"),
+        12 => Some("FA (CBS RAM+)"),
+        16 => Some("F6"),
+        32 => Some("F4"),
+        64 => Some("EF"),
+        _ => None,
+    }
+}
+
+/// Smallest cartridge size that holds `file_size` bytes.
+fn expected_rom_size(file_size: u64) -> Option<u64> {
+    ROM_SIZES.iter().copied().find(|&s| s >= file_size)
+}
+
+/// Read the 6502 reset vector from the last bank.
+fn read_reset_vector(reader: &mut dyn ReadSeek) -> Result<u16, AnalysisError> {
+    reader.seek(SeekFrom::End(-4))?;
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+/// Whether the ROM was made for the Superchip's 128 bytes of extra RAM.
+/// Its write and read ports take the first 256 bytes of every bank, so
+/// dumps hold the same filler byte there.
+fn has_superchip(data: &[u8]) -> bool {
+    if data.len() < 8 * 1024 {
+        return false;
+    }
+    data.chunks(4 * 1024).all(|bank| {
+        let fill = bank[0];
+        bank[..256].iter().all(|&b| b == fill) && bank[256..].iter().any(|&b| b != fill)
+    })
+}
+
+/// Analyzer for Atari 2600 ROMs.
+#[derive(Debug, Default)]
+pub struct Atari2600Analyzer;
+
+impl RomAnalyzer for Atari2600Analyzer {
+    fn analyze(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        if file_size < ROM_SIZES[0] {
+            return Err(AnalysisError::TooSmall {
+                expected: ROM_SIZES[0],
+                actual: file_size,
+            });
+        }
+
+        let reset = read_reset_vector(reader)?;
+        let mut id = RomIdentification::new().with_platform(Platform::Atari2600);
+        id.file_size = Some(file_size);
+        id.expected_size = expected_rom_size(file_size);
+        id.extra
+            .insert("reset_vector".into(), format!("${:04X}", reset));
+        if let Some(scheme) = bankswitch_scheme(file_size) {
+            id.extra.insert("bankswitch".into(), scheme.into());
+        }
+
+        if !options.quick && file_size <= 64 * 1024 {
+            let mut data = vec![0u8; file_size as usize];
+            reader.read_exact(&mut data)?;
+            if has_superchip(&data) {
+                id.extra.insert("superchip".into(), "Yes".into());
+            }
+        }
+
+        id.attach_raw_header(reader, file_size - 6, 6, options)?;
+        Ok(id)
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::Atari2600
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["a26", "bin"]
+    }
+
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        let Ok(file_size) = retro_junk_core::util::file_size(reader) else {
+            return false;
+        };
+        if !ROM_SIZES.contains(&file_size) {
+            return false;
+        }
+        read_reset_vector(reader).is_ok_and(|reset| reset & 0x1000 != 0)
+    }
+
+    fn dat_names(&self) -> &'static [&'static str] {
+        &["Atari - 2600"]
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/atari2600_tests.rs"]
+mod tests;
@@ -0,0 +1,152 @@
+//! Atari 5200 ROM analyzer.
+//!
+//! Supports:
+//! - 5200 ROMs (.a52, .bin)
+//!
+//! Cartridges end at $BFFF, and the last 24 bytes are read by the BIOS:
+//!
+//! | Address       | Contents                                          |
+//! |---------------|---------------------------------------------------|
+//! | $BFE8–$BFFB   | Title, 20 characters in ANTIC screen codes        |
+//! | $BFFC–$BFFD   | Copyright year, two screen-code digits            |
+//! | $BFFE–$BFFF   | Start address (little-endian)                     |
+//!
+//! A cartridge that sets $BFFD to $FF skips the title screen and jumps
+//! straight to its start address (diagnostic and some homebrew carts).
+
+use retro_junk_core::ReadSeek;
+use std::io::SeekFrom;
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+
+/// Size of the trailer the BIOS reads.
+const TRAILER_SIZE: u64 = 24;
+
+/// ROM sizes found on cartridges, in bytes.
+const ROM_SIZES: &[u64] = &[4 * 1024, 8 * 1024, 16 * 1024, 32 * 1024, 40 * 1024];
+
+/// Cartridge trailer at $BFE8–$BFFF.
+struct Trailer {
+    title: String,
+    year: Option<String>,
+    skip_title: bool,
+    start_address: u16,
+}
+
+/// Convert an ANTIC screen code to ASCII. The top two bits select a color in
+/// the text modes titles use, so they're ignored.
+fn screen_code_char(code: u8) -> char {
+    ((code & 0x3F) + 0x20) as char
+}
+
+fn parse_trailer(reader: &mut dyn ReadSeek) -> Result<Trailer, AnalysisError> {
+    reader.seek(SeekFrom::End(-(TRAILER_SIZE as i64)))?;
+    let mut buf = [0u8; TRAILER_SIZE as usize];
+    reader.read_exact(&mut buf)?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    let title: String = buf[..20].iter().map(|&b| screen_code_char(b)).collect();
+    let skip_title = buf[21] == 0xFF;
+    let year = if skip_title {
+        None
+    } else {
+        let digits: String = buf[20..22].iter().map(|&b| screen_code_char(b)).collect();
+        digits
+            .chars()
+            .all(|c| c.is_ascii_digit())
+            .then(|| format!("19{}", digits))
+    };
+
+    Ok(Trailer {
+        title: title.trim().to_string(),
+        year,
+        skip_title,
+        start_address: u16::from_le_bytes([buf[22], buf[23]]),
+    })
+}
+
+/// Whether `address` is inside the cartridge window ($4000–$BFFF).
+fn in_cartridge_space(address: u16) -> bool {
+    (0x4000..0xC000).contains(&address)
+}
+
+/// Analyzer for Atari 5200 ROMs.
+#[derive(Debug, Default)]
+pub struct Atari5200Analyzer;
+
+impl RomAnalyzer for Atari5200Analyzer {
+    fn analyze(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        if file_size < ROM_SIZES[0] {
+            return Err(AnalysisError::TooSmall {
+                expected: ROM_SIZES[0],
+                actual: file_size,
+            });
+        }
+
+        let trailer = parse_trailer(reader)?;
+        let mut id = RomIdentification::new().with_platform(Platform::Atari5200);
+        id.file_size = Some(file_size);
+        id.expected_size = ROM_SIZES.iter().copied().find(|&s| s >= file_size);
+        if !trailer.skip_title && !trailer.title.is_empty() {
+            id.internal_name = Some(trailer.title);
+        }
+        if let Some(year) = trailer.year {
+            id.extra.insert("copyright_year".into(), year);
+        }
+        if trailer.skip_title {
+            id.extra.insert("title_screen".into(), "Skipped".into());
+        }
+        id.extra.insert(
+            "start_address".into(),
+            format!("${:04X}", trailer.start_address),
+        );
+        if !in_cartridge_space(trailer.start_address) {
+            id.extra.insert(
+                "start_address_status".into(),
+                "INVALID (outside $4000-$BFFF)".into(),
+            );
+        }
+
+        id.attach_raw_header(
+            reader,
+            file_size - TRAILER_SIZE,
+            TRAILER_SIZE as usize,
+            options,
+        )?;
+        Ok(id)
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::Atari5200
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["a52", "bin"]
+    }
+
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        let Ok(file_size) = retro_junk_core::util::file_size(reader) else {
+            return false;
+        };
+        if !ROM_SIZES.contains(&file_size) {
+            return false;
+        }
+        // The year digits are the most distinctive part of the trailer
+        parse_trailer(reader).is_ok_and(|t| {
+            in_cartridge_space(t.start_address) && (t.skip_title || t.year.is_some())
+        })
+    }
+
+    fn dat_names(&self) -> &'static [&'static str] {
+        &["Atari - 5200"]
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/atari5200_tests.rs"]
+mod tests;
@@ -0,0 +1,234 @@
+//! Atari 7800 ROM analyzer.
+//!
+//! Supports:
+//! - A78 ROMs with the 128-byte header (.a78)
+//! - Headerless ROMs (.bin)
+//!
+//! The A78 header is added by dumpers and emulators; it isn't part of the
+//! cartridge. It records the title, the ROM size, the bank-switching and
+//! sound hardware the cartridge has, the controllers, and the TV system.
+//! No-Intro hashes ROMs without it.
+//!
+//! | Offset  | Size | Field                                         |
+//! |---------|------|-----------------------------------------------|
+//! | 0x00    | 1    | Header version                                |
+//! | 0x01    | 16   | `ATARI7800`, zero-padded                      |
+//! | 0x11    | 32   | Title, zero-padded                            |
+//! | 0x31    | 4    | ROM size without the header (big-endian)      |
+//! | 0x35    | 2    | Cartridge type flags (big-endian)             |
+//! | 0x37    | 1    | Port 1 controller                             |
+//! | 0x38    | 1    | Port 2 controller                             |
+//! | 0x39    | 1    | TV system (bit 0: PAL)                        |
+//! | 0x3A    | 1    | Save device                                   |
+//! | 0x3F    | 1    | Expansion module (1: XM)                      |
+//! | 0x64    | 28   | `ACTUAL CART DATA STARTS HERE`                |
+
+use retro_junk_core::ReadSeek;
+use std::io::SeekFrom;
+
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, Platform, Region, RomAnalyzer, RomIdentification,
+};
+
+/// Size of the A78 header.
+pub const A78_HEADER_SIZE: u64 = 128;
+
+/// Magic at offset 0x01.
+pub(crate) const A78_MAGIC: &[u8] = b"ATARI7800";
+
+/// Smallest 7800 cartridge.
+const MIN_ROM_SIZE: u64 = 16 * 1024;
+
+/// Cartridge type flag bits and what they mean.
+const CART_TYPE_FLAGS: &[(u16, &str)] = &[
+    (1 << 0, "POKEY at $4000"),
+    (1 << 1, "SuperGame banking"),
+    (1 << 2, "RAM at $4000"),
+    (1 << 3, "ROM at $4000"),
+    (1 << 4, "Bank 6 at $4000"),
+    (1 << 5, "Banked RAM"),
+    (1 << 6, "POKEY at $0450"),
+    (1 << 7, "Mirrored RAM at $4000"),
+    (1 << 8, "Activision banking"),
+    (1 << 9, "Absolute banking"),
+    (1 << 10, "POKEY at $0440"),
+    (1 << 11, "YM2151 at $0460"),
+];
+
+/// Parsed A78 header.
+struct A78Header {
+    version: u8,
+    title: String,
+    rom_size: u32,
+    cart_type: u16,
+    controllers: [u8; 2],
+    pal: bool,
+    save_device: u8,
+    expansion: u8,
+}
+
+/// Whether `buf` starts with an A78 header.
+fn has_a78_magic(buf: &[u8]) -> bool {
+    buf.get(1..1 + A78_MAGIC.len()) == Some(A78_MAGIC)
+}
+
+fn parse_header(buf: &[u8; A78_HEADER_SIZE as usize]) -> A78Header {
+    A78Header {
+        version: buf[0x00],
+        title: retro_junk_core::util::read_ascii(&buf[0x11..0x31])
+            .trim()
+            .to_string(),
+        rom_size: u32::from_be_bytes([buf[0x31], buf[0x32], buf[0x33], buf[0x34]]),
+        cart_type: u16::from_be_bytes([buf[0x35], buf[0x36]]),
+        controllers: [buf[0x37], buf[0x38]],
+        pal: buf[0x39] & 0x01 != 0,
+        save_device: buf[0x3A],
+        expansion: buf[0x3F],
+    }
+}
+
+/// Read the A78 header if the file has one. The reader is left at the start.
+fn read_header(reader: &mut dyn ReadSeek) -> Result<Option<A78Header>, AnalysisError> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut buf = [0u8; A78_HEADER_SIZE as usize];
+    let found = reader.read_exact(&mut buf).is_ok() && has_a78_magic(&buf);
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(found.then(|| parse_header(&buf)))
+}
+
+fn controller_name(code: u8) -> String {
+    match code {
+        0 => "None".into(),
+        1 => "Joystick".into(),
+        2 => "Light gun".into(),
+        3 => "Paddle".into(),
+        4 => "Trak-Ball".into(),
+        5 => "2600 joystick".into(),
+        6 => "2600 driving controller".into(),
+        7 => "2600 keypad".into(),
+        8 => "ST mouse".into(),
+        9 => "Amiga mouse".into(),
+        10 => "AtariVox/SaveKey".into(),
+        11 => "SNES2Atari".into(),
+        other => format!("Unknown ({})", other),
+    }
+}
+
+fn save_device_name(code: u8) -> Option<&'static str> {
+    match code {
+        1 => Some("High Score Cartridge"),
+        2 => Some("SaveKey/AtariVox"),
+        _ => None,
+    }
+}
+
+/// Convert a parsed header into a RomIdentification.
+fn to_identification(header: &A78Header, file_size: u64) -> RomIdentification {
+    let mut id = RomIdentification::new().with_platform(Platform::Atari7800);
+    if !header.title.is_empty() {
+        id.internal_name = Some(header.title.clone());
+    }
+    id.regions.push(if header.pal {
+        Region::Europe
+    } else {
+        Region::Usa
+    });
+    id.file_size = Some(file_size);
+    id.expected_size = Some(header.rom_size as u64 + A78_HEADER_SIZE);
+
+    id.extra
+        .insert("header_version".into(), header.version.to_string());
+    id.extra.insert(
+        "tv_system".into(),
+        if header.pal { "PAL" } else { "NTSC" }.into(),
+    );
+    let hardware: Vec<&str> = CART_TYPE_FLAGS
+        .iter()
+        .filter(|(bit, _)| header.cart_type & bit != 0)
+        .map(|(_, name)| *name)
+        .collect();
+    if !hardware.is_empty() {
+        id.extra.insert("cart_type".into(), hardware.join(", "));
+    }
+    id.extra.insert(
+        "controller_1".into(),
+        controller_name(header.controllers[0]),
+    );
+    id.extra.insert(
+        "controller_2".into(),
+        controller_name(header.controllers[1]),
+    );
+    if let Some(save) = save_device_name(header.save_device) {
+        id.extra.insert("save_device".into(), save.into());
+    }
+    if header.expansion == 1 {
+        id.extra.insert("expansion".into(), "XM".into());
+    }
+    id
+}
+
+/// Analyzer for Atari 7800 ROMs.
+#[derive(Debug, Default)]
+pub struct Atari7800Analyzer;
+
+impl RomAnalyzer for Atari7800Analyzer {
+    fn analyze(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        match read_header(reader)? {
+            Some(header) => {
+                let mut id = to_identification(&header, file_size);
+                id.attach_raw_header(reader, 0, A78_HEADER_SIZE as usize, options)?;
+                Ok(id)
+            }
+            None => {
+                if file_size < MIN_ROM_SIZE {
+                    return Err(AnalysisError::TooSmall {
+                        expected: MIN_ROM_SIZE,
+                        actual: file_size,
+                    });
+                }
+                // A headerless dump carries nothing but its data
+                let mut id = RomIdentification::new().with_platform(Platform::Atari7800);
+                id.file_size = Some(file_size);
+                id.extra.insert("header".into(), "None".into());
+                Ok(id)
+            }
+        }
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::Atari7800
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["a78", "bin"]
+    }
+
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        read_header(reader).is_ok_and(|h| h.is_some())
+    }
+
+    fn dat_names(&self) -> &'static [&'static str] {
+        &["Atari - 7800"]
+    }
+
+    fn dat_header_size(
+        &self,
+        reader: &mut dyn ReadSeek,
+        _file_size: u64,
+    ) -> Result<u64, AnalysisError> {
+        Ok(if read_header(reader)?.is_some() {
+            A78_HEADER_SIZE
+        } else {
+            0
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/atari7800_tests.rs"]
+mod tests;
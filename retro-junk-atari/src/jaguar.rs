@@ -0,0 +1,133 @@
+//! Atari Jaguar ROM analyzer.
+//!
+//! Supports:
+//! - Cartridge ROMs (.j64, .jag, .rom)
+//!
+//! Cartridges are mapped at $800000. The first 1 KB holds the encrypted
+//! boot data the BIOS checks, followed by a small header the BIOS uses to
+//! configure the cartridge bus and start the game:
+//!
+//! | Offset  | Size | Field                                         |
+//! |---------|------|-----------------------------------------------|
+//! | 0x400   | 4    | ROM bus configuration (big-endian)            |
+//! | 0x404   | 4    | Start address (big-endian, usually $802000)   |
+//!
+//! Jaguar cartridges have no title, serial, or region, so identification
+//! relies on DAT hashes.
+
+use retro_junk_core::ReadSeek;
+use std::io::SeekFrom;
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+
+/// Offset of the bus configuration and start address.
+const HEADER_OFFSET: u64 = 0x400;
+
+/// Address cartridge ROM is mapped at.
+const CART_BASE: u32 = 0x80_0000;
+
+/// End of the 6 MB cartridge window.
+const CART_END: u32 = 0xE0_0000;
+
+/// Cartridge sizes, in bytes.
+const ROM_SIZES: &[u64] = &[
+    1024 * 1024,
+    2 * 1024 * 1024,
+    4 * 1024 * 1024,
+    6 * 1024 * 1024,
+];
+
+struct JaguarHeader {
+    bus_config: u32,
+    start_address: u32,
+}
+
+fn parse_header(reader: &mut dyn ReadSeek) -> Result<JaguarHeader, AnalysisError> {
+    reader.seek(SeekFrom::Start(HEADER_OFFSET))?;
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::UnexpectedEof {
+            AnalysisError::TooSmall {
+                expected: HEADER_OFFSET + 8,
+                actual: 0,
+            }
+        } else {
+            AnalysisError::io_at(e, HEADER_OFFSET)
+        }
+    })?;
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(JaguarHeader {
+        bus_config: u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]),
+        start_address: u32::from_be_bytes([buf[4], buf[5], buf[6], buf[7]]),
+    })
+}
+
+/// Whether the header looks like a cartridge's: every byte of the bus
+/// configuration is the same, and the game starts inside cartridge space.
+fn is_cartridge_header(header: &JaguarHeader) -> bool {
+    let config = header.bus_config.to_be_bytes();
+    config[0] != 0
+        && config.iter().all(|&b| b == config[0])
+        && (CART_BASE..CART_END).contains(&header.start_address)
+}
+
+/// Analyzer for Atari Jaguar ROMs.
+#[derive(Debug, Default)]
+pub struct JaguarAnalyzer;
+
+impl RomAnalyzer for JaguarAnalyzer {
+    fn analyze(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        if file_size < HEADER_OFFSET + 8 {
+            return Err(AnalysisError::TooSmall {
+                expected: HEADER_OFFSET + 8,
+                actual: file_size,
+            });
+        }
+
+        let header = parse_header(reader)?;
+        if !is_cartridge_header(&header) {
+            return Err(AnalysisError::invalid_format(format!(
+                "no Jaguar cartridge header at 0x400 (start address ${:06X})",
+                header.start_address
+            )));
+        }
+
+        let mut id = RomIdentification::new().with_platform(Platform::Jaguar);
+        id.file_size = Some(file_size);
+        id.expected_size = ROM_SIZES.iter().copied().find(|&s| s >= file_size);
+        id.extra
+            .insert("bus_config".into(), format!("{:08X}", header.bus_config));
+        id.extra.insert(
+            "start_address".into(),
+            format!("${:06X}", header.start_address),
+        );
+
+        id.attach_raw_header(reader, HEADER_OFFSET, 8, options)?;
+        Ok(id)
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::Jaguar
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["j64", "jag", "rom"]
+    }
+
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        parse_header(reader).is_ok_and(|h| is_cartridge_header(&h))
+    }
+
+    fn dat_names(&self) -> &'static [&'static str] {
+        &["Atari - Jaguar"]
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/jaguar_tests.rs"]
+mod tests;
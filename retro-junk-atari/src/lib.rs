@@ -0,0 +1,23 @@
+//! Atari console ROM analyzers.
+//!
+//! This crate provides ROM analysis implementations for Atari consoles:
+//!
+//! - 2600
+//! - 5200
+//! - 7800 (A78 header)
+//! - Lynx (LNX header)
+//! - Jaguar
+
+pub mod atari2600;
+pub mod atari5200;
+pub mod atari7800;
+pub mod jaguar;
+pub mod lynx;
+#[cfg(any(test, feature = "testkit"))]
+pub mod synthetic;
+
+pub use atari2600::Atari2600Analyzer;
+pub use atari5200::Atari5200Analyzer;
+pub use atari7800::Atari7800Analyzer;
+pub use jaguar::JaguarAnalyzer;
+pub use lynx::LynxAnalyzer;
@@ -0,0 +1,192 @@
+//! Atari Lynx ROM analyzer.
+//!
+//! Supports:
+//! - LNX ROMs with the 64-byte Handy header (.lnx)
+//! - Headerless ROMs (.lyx)
+//!
+//! Lynx cartridges are read through a page counter, so the LNX header
+//! records the page size of each of the two banks rather than a ROM size.
+//! Each bank has 256 pages. No-Intro hashes ROMs without the header.
+//!
+//! | Offset  | Size | Field                                         |
+//! |---------|------|-----------------------------------------------|
+//! | 0x00    | 4    | `LYNX`                                        |
+//! | 0x04    | 2    | Bank 0 page size (little-endian)              |
+//! | 0x06    | 2    | Bank 1 page size (little-endian)              |
+//! | 0x08    | 2    | Header version (little-endian)                |
+//! | 0x0A    | 32   | Cartridge name, zero-padded                   |
+//! | 0x2A    | 16   | Manufacturer, zero-padded                     |
+//! | 0x3A    | 1    | Screen rotation (1: left, 2: right)           |
+//! | 0x3B    | 1    | AUDIN pin used as a bank select               |
+//! | 0x3C    | 1    | EEPROM type (bits 0–2)                        |
+
+use retro_junk_core::ReadSeek;
+use std::io::SeekFrom;
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+
+/// Size of the LNX header.
+pub const LNX_HEADER_SIZE: u64 = 64;
+
+/// Magic at offset 0.
+pub(crate) const LNX_MAGIC: &[u8; 4] = b"LYNX";
+
+/// Smallest Lynx cartridge.
+const MIN_ROM_SIZE: u64 = 64 * 1024;
+
+/// Parsed LNX header.
+struct LnxHeader {
+    bank0_page_size: u16,
+    bank1_page_size: u16,
+    version: u16,
+    name: String,
+    manufacturer: String,
+    rotation: u8,
+    audin: bool,
+    eeprom: u8,
+}
+
+impl LnxHeader {
+    /// ROM size described by the two banks.
+    fn rom_size(&self) -> u64 {
+        (self.bank0_page_size as u64 + self.bank1_page_size as u64) * 256
+    }
+}
+
+fn parse_header(buf: &[u8; LNX_HEADER_SIZE as usize]) -> LnxHeader {
+    LnxHeader {
+        bank0_page_size: u16::from_le_bytes([buf[0x04], buf[0x05]]),
+        bank1_page_size: u16::from_le_bytes([buf[0x06], buf[0x07]]),
+        version: u16::from_le_bytes([buf[0x08], buf[0x09]]),
+        name: retro_junk_core::util::read_ascii(&buf[0x0A..0x2A])
+            .trim()
+            .to_string(),
+        manufacturer: retro_junk_core::util::read_ascii(&buf[0x2A..0x3A])
+            .trim()
+            .to_string(),
+        rotation: buf[0x3A],
+        audin: buf[0x3B] != 0,
+        eeprom: buf[0x3C],
+    }
+}
+
+/// Read the LNX header if the file has one. The reader is left at the start.
+fn read_header(reader: &mut dyn ReadSeek) -> Result<Option<LnxHeader>, AnalysisError> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut buf = [0u8; LNX_HEADER_SIZE as usize];
+    let found = reader.read_exact(&mut buf).is_ok() && &buf[..4] == LNX_MAGIC;
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(found.then(|| parse_header(&buf)))
+}
+
+fn eeprom_name(code: u8) -> Option<&'static str> {
+    match code & 0x07 {
+        1 => Some("93C46 (128 bytes)"),
+        2 => Some("93C56 (256 bytes)"),
+        3 => Some("93C66 (512 bytes)"),
+        4 => Some("93C76 (1 KB)"),
+        5 => Some("93C86 (2 KB)"),
+        _ => None,
+    }
+}
+
+/// Convert a parsed header into a RomIdentification.
+fn to_identification(header: &LnxHeader, file_size: u64) -> RomIdentification {
+    let mut id = RomIdentification::new().with_platform(Platform::Lynx);
+    if !header.name.is_empty() {
+        id.internal_name = Some(header.name.clone());
+    }
+    if !header.manufacturer.is_empty() {
+        id.maker_code = Some(header.manufacturer.clone());
+    }
+    id.file_size = Some(file_size);
+    id.expected_size = Some(header.rom_size() + LNX_HEADER_SIZE);
+
+    id.extra
+        .insert("header_version".into(), header.version.to_string());
+    id.extra.insert(
+        "bank_page_sizes".into(),
+        format!("{} / {}", header.bank0_page_size, header.bank1_page_size),
+    );
+    match header.rotation {
+        1 => {
+            id.extra.insert("rotation".into(), "Left".into());
+        }
+        2 => {
+            id.extra.insert("rotation".into(), "Right".into());
+        }
+        _ => {}
+    }
+    if header.audin {
+        id.extra.insert("audin_banking".into(), "Yes".into());
+    }
+    if let Some(eeprom) = eeprom_name(header.eeprom) {
+        id.extra.insert("eeprom".into(), eeprom.into());
+    }
+    id
+}
+
+/// Analyzer for Atari Lynx ROMs.
+#[derive(Debug, Default)]
+pub struct LynxAnalyzer;
+
+impl RomAnalyzer for LynxAnalyzer {
+    fn analyze(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        match read_header(reader)? {
+            Some(header) => {
+                let mut id = to_identification(&header, file_size);
+                id.attach_raw_header(reader, 0, LNX_HEADER_SIZE as usize, options)?;
+                Ok(id)
+            }
+            None => {
+                if file_size < MIN_ROM_SIZE {
+                    return Err(AnalysisError::TooSmall {
+                        expected: MIN_ROM_SIZE,
+                        actual: file_size,
+                    });
+                }
+                let mut id = RomIdentification::new().with_platform(Platform::Lynx);
+                id.file_size = Some(file_size);
+                id.extra.insert("header".into(), "None".into());
+                Ok(id)
+            }
+        }
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::Lynx
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["lnx", "lyx"]
+    }
+
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        read_header(reader).is_ok_and(|h| h.is_some())
+    }
+
+    fn dat_names(&self) -> &'static [&'static str] {
+        &["Atari - Lynx"]
+    }
+
+    fn dat_header_size(
+        &self,
+        reader: &mut dyn ReadSeek,
+        _file_size: u64,
+    ) -> Result<u64, AnalysisError> {
+        Ok(if read_header(reader)?.is_some() {
+            LNX_HEADER_SIZE
+        } else {
+            0
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/lynx_tests.rs"]
+mod tests;
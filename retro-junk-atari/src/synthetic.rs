@@ -0,0 +1,75 @@
+//! Synthetic ROM images for Atari platforms.
+//!
+//! Each builder produces the smallest image its analyzer fully accepts.
+
+use retro_junk_core::{Platform, SyntheticRom};
+
+use crate::atari7800::{A78_HEADER_SIZE, A78_MAGIC};
+use crate::lynx::{LNX_HEADER_SIZE, LNX_MAGIC};
+
+/// The synthetic image for `platform`, if this crate can build one.
+pub fn synthesize(platform: Platform) -> Option<SyntheticRom> {
+    match platform {
+        Platform::Atari2600 => Some(atari2600()),
+        Platform::Atari5200 => Some(atari5200()),
+        Platform::Atari7800 => Some(atari7800()),
+        Platform::Lynx => Some(lynx()),
+        Platform::Jaguar => Some(jaguar()),
+        _ => None,
+    }
+}
+
+/// 4 KB cartridge of NOPs whose reset vector points at $F000.
+pub fn atari2600() -> SyntheticRom {
+    let mut rom = vec![0xEAu8; 4096];
+    rom[4092..4094].copy_from_slice(&0xF000u16.to_le_bytes());
+    SyntheticRom::new(Platform::Atari2600, "synthetic.a26", rom)
+}
+
+/// 32 KB cartridge with a BIOS title trailer.
+pub fn atari5200() -> SyntheticRom {
+    let size = 32 * 1024;
+    let mut rom = vec![0u8; size];
+    let trailer = size - 24;
+    // Title and year are ANTIC screen codes (ASCII - 0x20)
+    let text = format!("{:^20}94", "SYNTHETIC");
+    for (dst, b) in rom[trailer..trailer + 22].iter_mut().zip(text.bytes()) {
+        *dst = b - 0x20;
+    }
+    rom[trailer + 22..].copy_from_slice(&0x4000u16.to_le_bytes()); // start vector
+    SyntheticRom::new(Platform::Atari5200, "synthetic.a52", rom)
+}
+
+/// A78 file holding a 16 KB NTSC cartridge.
+pub fn atari7800() -> SyntheticRom {
+    let rom_size: u32 = 16 * 1024;
+    let mut data = vec![0u8; A78_HEADER_SIZE as usize + rom_size as usize];
+    data[0] = 3; // header version
+    data[1..10].copy_from_slice(A78_MAGIC);
+    data[0x11..0x1A].copy_from_slice(b"SYNTHETIC");
+    data[0x31..0x35].copy_from_slice(&rom_size.to_be_bytes());
+    data[0x37] = 1; // controller 1: joystick
+    data[0x38] = 1; // controller 2: joystick
+    data[0x64..0x80].copy_from_slice(b"ACTUAL CART DATA STARTS HERE");
+    SyntheticRom::new(Platform::Atari7800, "synthetic.a78", data)
+}
+
+/// LNX file holding a 128 KB cartridge in bank 0.
+pub fn lynx() -> SyntheticRom {
+    let rom_size = 128 * 1024;
+    let mut data = vec![0u8; LNX_HEADER_SIZE as usize + rom_size];
+    data[0..4].copy_from_slice(LNX_MAGIC);
+    data[4..6].copy_from_slice(&512u16.to_le_bytes()); // bank 0 page size
+    data[8..10].copy_from_slice(&1u16.to_le_bytes()); // header version
+    data[0x0A..0x13].copy_from_slice(b"SYNTHETIC");
+    data[0x2A..0x2E].copy_from_slice(b"NONE");
+    SyntheticRom::new(Platform::Lynx, "synthetic.lnx", data)
+}
+
+/// 1 MB cartridge whose start address lies in cartridge space.
+pub fn jaguar() -> SyntheticRom {
+    let mut rom = vec![0xFFu8; 1024 * 1024];
+    rom[0x400..0x404].copy_from_slice(&0x0404_0404u32.to_be_bytes()); // bus config
+    rom[0x404..0x408].copy_from_slice(&0x0080_2000u32.to_be_bytes()); // start address
+    SyntheticRom::new(Platform::Jaguar, "synthetic.j64", rom)
+}
@@ -0,0 +1,55 @@
+use super::*;
+use std::io::Cursor;
+
+/// Build a 2600 ROM of `size` bytes with the reset vector at `reset`.
+fn make_rom(size: usize, reset: u16) -> Vec<u8> {
+    let mut rom = vec![0xEAu8; size];
+    rom[size - 4..size - 2].copy_from_slice(&reset.to_le_bytes());
+    rom
+}
+
+#[test]
+fn test_can_handle_valid() {
+    let rom = make_rom(4096, 0xF000);
+    assert!(Atari2600Analyzer.can_handle(&mut Cursor::new(rom)));
+}
+
+#[test]
+fn test_can_handle_rejects_vector_outside_cartridge() {
+    let rom = make_rom(4096, 0x0080);
+    assert!(!Atari2600Analyzer.can_handle(&mut Cursor::new(rom)));
+}
+
+#[test]
+fn test_can_handle_rejects_odd_size() {
+    let rom = make_rom(5000, 0xF000);
+    assert!(!Atari2600Analyzer.can_handle(&mut Cursor::new(rom)));
+}
+
+#[test]
+fn test_analyze_reports_bankswitching() {
+    let rom = make_rom(16 * 1024, 0xF000);
+    let id = Atari2600Analyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::Atari2600));
+    assert_eq!(id.extra.get("bankswitch").unwrap(), "F6");
+    assert_eq!(id.extra.get("reset_vector").unwrap(), "$F000");
+    assert_eq!(id.expected_size, Some(16 * 1024));
+    assert!(!id.extra.contains_key("superchip"));
+}
+
+#[test]
+fn test_analyze_detects_superchip() {
+    let mut rom = make_rom(8 * 1024, 0xF000);
+    for bank in rom.chunks_mut(4096) {
+        bank[..256].fill(0xFF);
+    }
+    // A blank ROM isn't taken for one
+    let blank = vec![0xFFu8; 8 * 1024];
+    assert!(!has_superchip(&blank));
+    let id = Atari2600Analyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.extra.get("superchip").unwrap(), "Yes");
+}
@@ -0,0 +1,57 @@
+use super::*;
+use std::io::Cursor;
+
+/// Encode ASCII text as ANTIC screen codes.
+fn screen_codes(text: &str) -> Vec<u8> {
+    text.bytes().map(|b| b - 0x20).collect()
+}
+
+/// Build a 5200 ROM of `size` bytes with a BIOS trailer.
+fn make_rom(size: usize, title: &str, year: &str) -> Vec<u8> {
+    let mut rom = vec![0u8; size];
+    let trailer = size - 24;
+    let padded = format!("{:^20}", title);
+    rom[trailer..trailer + 20].copy_from_slice(&screen_codes(&padded));
+    rom[trailer + 20..trailer + 22].copy_from_slice(&screen_codes(year));
+    rom[trailer + 22..].copy_from_slice(&0x4000u16.to_le_bytes());
+    rom
+}
+
+#[test]
+fn test_can_handle_valid() {
+    let rom = make_rom(32 * 1024, "SUPER BREAKOUT", "82");
+    assert!(Atari5200Analyzer.can_handle(&mut Cursor::new(rom)));
+}
+
+#[test]
+fn test_can_handle_rejects_missing_trailer() {
+    let rom = vec![0u8; 32 * 1024];
+    assert!(!Atari5200Analyzer.can_handle(&mut Cursor::new(rom)));
+}
+
+#[test]
+fn test_analyze_reads_title_and_year() {
+    let mut rom = make_rom(16 * 1024, "PAC-MAN", "83");
+    // Color bits in the title don't change the characters
+    let trailer = rom.len() - 24;
+    rom[trailer + 6] |= 0x80;
+    let id = Atari5200Analyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.internal_name.as_deref(), Some("PAC-MAN"));
+    assert_eq!(id.extra.get("copyright_year").unwrap(), "1983");
+    assert_eq!(id.extra.get("start_address").unwrap(), "$4000");
+}
+
+#[test]
+fn test_analyze_skipped_title_screen() {
+    let mut rom = make_rom(8 * 1024, "", "00");
+    let len = rom.len();
+    rom[len - 3] = 0xFF;
+    let id = Atari5200Analyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.internal_name, None);
+    assert_eq!(id.extra.get("title_screen").unwrap(), "Skipped");
+    assert!(!id.extra.contains_key("copyright_year"));
+}
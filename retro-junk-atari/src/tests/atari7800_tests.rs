@@ -0,0 +1,90 @@
+use super::*;
+use std::io::Cursor;
+
+const ROM_SIZE: usize = 48 * 1024;
+
+/// Build an A78 file with a 48 KB ROM.
+fn make_a78() -> Vec<u8> {
+    let mut data = vec![0u8; A78_HEADER_SIZE as usize + ROM_SIZE];
+    data[0] = 3;
+    data[1..10].copy_from_slice(A78_MAGIC);
+    data[0x11..0x11 + 13].copy_from_slice(b"Food Fight   ");
+    data[0x31..0x35].copy_from_slice(&(ROM_SIZE as u32).to_be_bytes());
+    data[0x35..0x37].copy_from_slice(&0x0003u16.to_be_bytes());
+    data[0x37] = 1;
+    data[0x38] = 1;
+    data[0x39] = 0;
+    data[0x3A] = 1;
+    data[0x64..0x80].copy_from_slice(b"ACTUAL CART DATA STARTS HERE");
+    data
+}
+
+#[test]
+fn test_can_handle_a78() {
+    assert!(Atari7800Analyzer.can_handle(&mut Cursor::new(make_a78())));
+}
+
+#[test]
+fn test_can_handle_rejects_headerless() {
+    let data = vec![0u8; ROM_SIZE];
+    assert!(!Atari7800Analyzer.can_handle(&mut Cursor::new(data)));
+}
+
+#[test]
+fn test_analyze_a78_header() {
+    let id = Atari7800Analyzer
+        .analyze(&mut Cursor::new(make_a78()), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.internal_name.as_deref(), Some("Food Fight"));
+    assert_eq!(id.regions, vec![Region::Usa]);
+    assert_eq!(id.expected_size, Some(A78_HEADER_SIZE + ROM_SIZE as u64));
+    assert_eq!(id.file_size, id.expected_size);
+    assert_eq!(
+        id.extra.get("cart_type").unwrap(),
+        "POKEY at $4000, SuperGame banking"
+    );
+    assert_eq!(id.extra.get("controller_1").unwrap(), "Joystick");
+    assert_eq!(id.extra.get("save_device").unwrap(), "High Score Cartridge");
+    assert_eq!(id.extra.get("tv_system").unwrap(), "NTSC");
+}
+
+#[test]
+fn test_analyze_pal() {
+    let mut data = make_a78();
+    data[0x39] = 1;
+    let id = Atari7800Analyzer
+        .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.regions, vec![Region::Europe]);
+    assert_eq!(id.extra.get("tv_system").unwrap(), "PAL");
+}
+
+#[test]
+fn test_dat_header_size() {
+    let size = make_a78().len() as u64;
+    assert_eq!(
+        Atari7800Analyzer
+            .dat_header_size(&mut Cursor::new(make_a78()), size)
+            .unwrap(),
+        A78_HEADER_SIZE
+    );
+    let headerless = vec![0u8; ROM_SIZE];
+    assert_eq!(
+        Atari7800Analyzer
+            .dat_header_size(&mut Cursor::new(headerless), ROM_SIZE as u64)
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_analyze_headerless() {
+    let id = Atari7800Analyzer
+        .analyze(
+            &mut Cursor::new(vec![0u8; ROM_SIZE]),
+            &AnalysisOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(id.internal_name, None);
+    assert_eq!(id.extra.get("header").unwrap(), "None");
+}
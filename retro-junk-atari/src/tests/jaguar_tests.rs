@@ -0,0 +1,50 @@
+use super::*;
+use std::io::Cursor;
+
+/// Build a 1 MB Jaguar cartridge image.
+fn make_rom() -> Vec<u8> {
+    let mut rom = vec![0xFFu8; 1024 * 1024];
+    rom[0x400..0x404].copy_from_slice(&0x0404_0404u32.to_be_bytes());
+    rom[0x404..0x408].copy_from_slice(&0x0080_2000u32.to_be_bytes());
+    rom
+}
+
+#[test]
+fn test_can_handle_valid() {
+    assert!(JaguarAnalyzer.can_handle(&mut Cursor::new(make_rom())));
+}
+
+#[test]
+fn test_can_handle_rejects_start_outside_cartridge() {
+    let mut rom = make_rom();
+    rom[0x404..0x408].copy_from_slice(&0x0000_4000u32.to_be_bytes());
+    assert!(!JaguarAnalyzer.can_handle(&mut Cursor::new(rom)));
+}
+
+#[test]
+fn test_analyze_header() {
+    let id = JaguarAnalyzer
+        .analyze(&mut Cursor::new(make_rom()), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::Jaguar));
+    assert_eq!(id.extra.get("bus_config").unwrap(), "04040404");
+    assert_eq!(id.extra.get("start_address").unwrap(), "$802000");
+    assert_eq!(id.expected_size, Some(1024 * 1024));
+}
+
+#[test]
+fn test_analyze_rejects_non_cartridge() {
+    let rom = vec![0u8; 4096];
+    let err = JaguarAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap_err();
+    assert!(matches!(err, AnalysisError::InvalidFormat(_)));
+}
+
+#[test]
+fn test_too_small() {
+    let err = JaguarAnalyzer
+        .analyze(&mut Cursor::new(vec![0u8; 16]), &AnalysisOptions::default())
+        .unwrap_err();
+    assert!(matches!(err, AnalysisError::TooSmall { .. }));
+}
@@ -0,0 +1,63 @@
+use super::*;
+use std::io::Cursor;
+
+const ROM_SIZE: usize = 256 * 1024;
+
+/// Build an LNX file with a 256 KB ROM in bank 0.
+fn make_lnx() -> Vec<u8> {
+    let mut data = vec![0u8; LNX_HEADER_SIZE as usize + ROM_SIZE];
+    data[0..4].copy_from_slice(LNX_MAGIC);
+    data[4..6].copy_from_slice(&1024u16.to_le_bytes());
+    data[8..10].copy_from_slice(&1u16.to_le_bytes());
+    data[0x0A..0x0A + 11].copy_from_slice(b"Gates of Zendocon".split_at(11).0);
+    data[0x2A..0x2A + 4].copy_from_slice(b"Epyx");
+    data[0x3A] = 1;
+    data[0x3C] = 1;
+    data
+}
+
+#[test]
+fn test_can_handle_lnx() {
+    assert!(LynxAnalyzer.can_handle(&mut Cursor::new(make_lnx())));
+}
+
+#[test]
+fn test_can_handle_rejects_headerless() {
+    assert!(!LynxAnalyzer.can_handle(&mut Cursor::new(vec![0u8; ROM_SIZE])));
+}
+
+#[test]
+fn test_analyze_lnx_header() {
+    let id = LynxAnalyzer
+        .analyze(&mut Cursor::new(make_lnx()), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::Lynx));
+    assert_eq!(id.internal_name.as_deref(), Some("Gates of Ze"));
+    assert_eq!(id.maker_code.as_deref(), Some("Epyx"));
+    assert_eq!(id.expected_size, Some(LNX_HEADER_SIZE + ROM_SIZE as u64));
+    assert_eq!(id.file_size, id.expected_size);
+    assert_eq!(id.extra.get("rotation").unwrap(), "Left");
+    assert_eq!(id.extra.get("eeprom").unwrap(), "93C46 (128 bytes)");
+}
+
+#[test]
+fn test_truncated_rom_reports_expected_size() {
+    let mut data = make_lnx();
+    data.truncate(LNX_HEADER_SIZE as usize + ROM_SIZE / 2);
+    let id = LynxAnalyzer
+        .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert!(id.file_size < id.expected_size);
+}
+
+#[test]
+fn test_dat_header_size() {
+    let data = make_lnx();
+    let size = data.len() as u64;
+    assert_eq!(
+        LynxAnalyzer
+            .dat_header_size(&mut Cursor::new(data), size)
+            .unwrap(),
+        LNX_HEADER_SIZE
+    );
+}
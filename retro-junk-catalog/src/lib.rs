@@ -1,15 +1,21 @@
-//! Game catalog data model types, YAML I/O, and No-Intro name parsing.
+//! Game catalog data model types, YAML I/O, No-Intro name parsing, and
+//! title slugs.
 //!
 //! This crate defines the persistent data model for the game catalog without
 //! any database dependencies. Consumers can use these types directly for
 //! serialization, display, or passing to `retro-junk-db` for persistence.
 
 pub mod name_parser;
+pub mod slug;
 pub mod types;
 pub mod yaml;
 
 pub use name_parser::{
-    DumpStatus, ParsedDatName, parse_dat_name, region_slug_to_display, region_to_slug,
+    DEFAULT_REGION_PRIORITY, DumpStatus, Modification, ParsedDatName, Translation, parse_dat_name,
+    region_slug_to_display, region_to_slug,
 };
+pub use slug::{fold_diacritics, slugify, strip_article, title_key};
 pub use types::*;
-pub use yaml::{load_catalog, load_companies, load_overrides, load_platforms};
+pub use yaml::{
+    load_catalog, load_companies, load_overrides, load_platforms, load_resolution_rules,
+};
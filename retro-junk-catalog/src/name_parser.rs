@@ -7,6 +7,14 @@
 //!
 //! This parser extracts the base title, regions, revision, languages, flags,
 //! and status information from these names.
+//!
+//! Older GoodTools-style names are understood too, so files named by those
+//! tools can be classified before hashing:
+//! ```text
+//! Game Name (JUE) (V1.1) [h1C] [t1] [T+Eng1.0_Author] [a1] [b2]
+//! ```
+//! Single-letter country codes become No-Intro region names, and the dump
+//! tags are recorded as [`Modification`]s, a [`Translation`], or the status.
 
 /// Parsed components of a No-Intro/Redump filename.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -29,6 +37,75 @@ pub struct ParsedDatName {
     pub status: DumpStatus,
     /// Version string if present (e.g., "v1.0", "v1.1").
     pub version: Option<String>,
+    /// GoodTools modification tags: [h] hack, [t] trainer, [f] fixed, [p] pirate.
+    pub modifications: Vec<Modification>,
+    /// GoodTools fan translation tag ([T+Eng], [T-Fre_Author]).
+    pub translation: Option<Translation>,
+    /// Whether the name used any GoodTools-only tag or country code.
+    pub goodtools: bool,
+}
+
+impl ParsedDatName {
+    /// Rebuild the name in No-Intro style: title, regions, languages,
+    /// revision/version, and flags. GoodTools-only tags (modifications,
+    /// translations) have no No-Intro equivalent and are dropped.
+    ///
+    /// ```
+    /// use retro_junk_catalog::name_parser::parse_dat_name;
+    ///
+    /// let parsed = parse_dat_name("Super Mario Bros. 3 (UE) (PRG1) [!]");
+    /// assert_eq!(parsed.to_no_intro_name(), "Super Mario Bros. 3 (USA, Europe) (Rev 1)");
+    /// ```
+    pub fn to_no_intro_name(&self) -> String {
+        let mut name = self.title.clone();
+        if !self.regions.is_empty() {
+            name.push_str(&format!(" ({})", self.regions.join(", ")));
+        }
+        if !self.languages.is_empty() {
+            name.push_str(&format!(" ({})", self.languages.join(",")));
+        }
+        if let Some(ref version) = self.version {
+            // GoodTools writes "V1.1"; No-Intro uses "v1.1"
+            name.push_str(&format!(" (v{})", &version[1..]));
+        }
+        if let Some(ref revision) = self.revision {
+            name.push_str(&format!(" ({revision})"));
+        }
+        for flag in self.flags.iter().filter(|f| !f.starts_with('[')) {
+            name.push_str(&format!(" ({flag})"));
+        }
+        if let Some(n) = self.disc_number {
+            match self.disc_label {
+                Some(ref label) => name.push_str(&format!(" (Disc {n} - {label})")),
+                None => name.push_str(&format!(" (Disc {n})")),
+            }
+        }
+        name
+    }
+}
+
+/// A GoodTools tag marking a modified (non-original) dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modification {
+    /// [h] hacked, e.g. [h1], [h1C], [hI].
+    Hack,
+    /// [t] trainer added, e.g. [t1].
+    Trainer,
+    /// [f] fixed to run on emulators or copiers, e.g. [f1].
+    Fixed,
+    /// [p] pirate release.
+    Pirate,
+}
+
+/// A GoodTools fan translation tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translation {
+    /// Target language code as written (e.g., "Eng", "Fre", "Por").
+    pub language: String,
+    /// `[T+...]` is the newest translation; `[T-...]` an outdated one.
+    pub latest: bool,
+    /// Version and author text after the language (e.g., "1.0_Author").
+    pub details: Option<String>,
 }
 
 /// Dump verification status from bracketed tags.
@@ -73,6 +150,9 @@ pub fn parse_dat_name(name: &str) -> ParsedDatName {
         disc_label: None,
         status: DumpStatus::Verified,
         version: None,
+        modifications: Vec::new(),
+        translation: None,
+        goodtools: false,
     };
 
     let (title, tags) = extract_title_and_tags(name);
@@ -214,6 +294,28 @@ fn classify_paren_tag(content: &str, result: &mut ParsedDatName) {
         return;
     }
 
+    // GoodTools country codes: "U", "J", "E", "JUE", "Sw", "HK", ...
+    if let Some(regions) = goodtools_regions(trimmed) {
+        for region in regions {
+            if !result.regions.iter().any(|r| r == region) {
+                result.regions.push(region.to_string());
+            }
+        }
+        result.goodtools = true;
+        return;
+    }
+
+    // GoodNES PRG revisions: "PRG0" is the original, "PRG1" the first revision
+    if let Some(n) = trimmed.strip_prefix("PRG")
+        && let Ok(n) = n.parse::<u32>()
+    {
+        if n > 0 {
+            result.revision = Some(format!("Rev {n}"));
+        }
+        result.goodtools = true;
+        return;
+    }
+
     // Revision: "Rev A", "Rev 1", "Rev 1.1"
     if let Some(rev) = trimmed.strip_prefix("Rev ") {
         result.revision = Some(format!("Rev {rev}"));
@@ -281,17 +383,138 @@ fn looks_like_language_list(s: &str) -> bool {
     })
 }
 
+/// GoodTools country codes that are not plain single letters.
+const GOODTOOLS_MULTI_LETTER_CODES: &[(&str, &[&str])] = &[
+    ("Ch", &["China"]),
+    ("FC", &["Canada"]),
+    ("FN", &["Finland"]),
+    ("GR", &["Greece"]),
+    ("HK", &["Hong Kong"]),
+    ("NL", &["Netherlands"]),
+    ("Sw", &["Sweden"]),
+    ("UK", &["United Kingdom"]),
+    ("1", &["Japan", "Korea"]),
+    ("4", &["USA", "Brazil"]),
+];
+
+/// Single-letter GoodTools country codes, which may be combined ("JUE").
+fn goodtools_letter_region(c: char) -> Option<&'static str> {
+    Some(match c {
+        'A' => "Australia",
+        'B' => "Brazil",
+        'C' => "China",
+        'E' => "Europe",
+        'F' => "France",
+        'G' => "Germany",
+        'I' => "Italy",
+        'J' => "Japan",
+        'K' => "Korea",
+        'S' => "Spain",
+        'U' => "USA",
+        'W' => "World",
+        _ => return None,
+    })
+}
+
+/// Map a GoodTools country tag to No-Intro region names.
+fn goodtools_regions(s: &str) -> Option<Vec<&'static str>> {
+    if let Some((_, regions)) = GOODTOOLS_MULTI_LETTER_CODES.iter().find(|(c, _)| *c == s) {
+        return Some(regions.to_vec());
+    }
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => goodtools_letter_region(c).map(|r| vec![r]),
+        // Combined codes ("JU", "UE", "JUEB") only use the major regions, so
+        // No-Intro tags like "(SGB)" aren't mistaken for country lists
+        (Some(_), Some(_)) if s.len() <= 4 && s.chars().all(|c| "JUEBAKW".contains(c)) => {
+            s.chars().map(goodtools_letter_region).collect()
+        }
+        _ => None,
+    }
+}
+
+/// Match a GoodTools dump tag: `letter` alone or followed by a number
+/// (`[b]`, `[b1]`). Returns the number, if any.
+fn numbered_tag(content: &str, letter: char) -> Option<Option<u32>> {
+    let rest = content.strip_prefix(letter)?;
+    if rest.is_empty() {
+        return Some(None);
+    }
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() {
+        return None;
+    }
+    Some(digits.parse().ok())
+}
+
 /// Classify a bracketed tag and update the result accordingly.
 fn classify_bracket_tag(content: &str, result: &mut ParsedDatName) {
-    match content.trim() {
+    let trimmed = content.trim();
+    match trimmed {
         "!" => result.status = DumpStatus::Verified,
         "b" => result.status = DumpStatus::BadDump,
         "o" => result.status = DumpStatus::Overdump,
-        _ => {
-            // Unknown bracket tags are stored as flags
-            result.flags.push(format!("[{}]", content.trim()));
+        _ => classify_goodtools_tag(trimmed, result),
+    }
+}
+
+/// Classify the GoodTools-only bracket tags. Unknown tags are stored as flags.
+fn classify_goodtools_tag(tag: &str, result: &mut ParsedDatName) {
+    // Translations: "T+Eng", "T-Fre_Author", "T+Eng1.0_Author"
+    if let Some(rest) = tag.strip_prefix("T+").or_else(|| tag.strip_prefix("T-")) {
+        let split = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let (language, details) = rest.split_at(split);
+        if !language.is_empty() {
+            let details = details.trim_start_matches(['_', ' ']);
+            result.translation = Some(Translation {
+                language: language.to_string(),
+                latest: tag.starts_with("T+"),
+                details: (!details.is_empty()).then(|| details.to_string()),
+            });
+            result.goodtools = true;
+            return;
         }
     }
+
+    // Hacks carry an optional number and suffix: "h", "h1", "h1C", "hI", "hFFE"
+    if let Some(rest) = tag.strip_prefix('h')
+        && rest
+            .chars()
+            .next()
+            .is_none_or(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+    {
+        result.modifications.push(Modification::Hack);
+        result.goodtools = true;
+        return;
+    }
+
+    if numbered_tag(tag, 'b').is_some() || tag == "x" {
+        // "x" is GoodTools' bad-checksum tag
+        result.status = DumpStatus::BadDump;
+    } else if numbered_tag(tag, 'o').is_some() {
+        result.status = DumpStatus::Overdump;
+    } else if let Some(n) = numbered_tag(tag, 'a') {
+        // Alternate good dumps map to No-Intro's "(Alt N)"
+        result.flags.push(match n {
+            Some(n) => format!("Alt {n}"),
+            None => "Alt".to_string(),
+        });
+    } else if numbered_tag(tag, 't').is_some() {
+        result.modifications.push(Modification::Trainer);
+    } else if numbered_tag(tag, 'f').is_some() {
+        result.modifications.push(Modification::Fixed);
+    } else if numbered_tag(tag, 'p').is_some() {
+        result.modifications.push(Modification::Pirate);
+    } else if tag == "c" {
+        // Checksum verified
+        result.status = DumpStatus::Verified;
+    } else {
+        result.flags.push(format!("[{tag}]"));
+        return;
+    }
+    result.goodtools = true;
 }
 
 /// Map a catalog region slug back to its Redump display format.
@@ -346,6 +569,10 @@ pub fn region_slug_to_display(slug: &str) -> String {
     }
 }
 
+/// Region slugs in the default order of preference, used when picking one
+/// release of a game over another.
+pub const DEFAULT_REGION_PRIORITY: &[&str] = &["usa", "world", "europe", "japan"];
+
 /// Map a No-Intro/Redump region string to a lowercase slug used in the catalog.
 ///
 /// Returns the canonical region slug (e.g., "usa", "japan", "europe").
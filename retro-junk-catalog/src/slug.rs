@@ -0,0 +1,238 @@
+//! Slugs and comparison keys for game titles.
+//!
+//! Catalog IDs are built from slugs of DAT titles, and titles from different
+//! sources are compared to find the same game. Both need the same view of a
+//! title:
+//!
+//! - Latin letters with diacritics fold to ASCII (`Pokémon` → `pokemon`).
+//! - Fullwidth ASCII folds to ASCII (`ＦＦ` → `ff`).
+//! - Other scripts (kana, kanji, hangul, Cyrillic) pass through unchanged
+//!   rather than being dropped or romanized, so Japanese-only titles still
+//!   get distinct slugs.
+//! - Leading articles (`The Legend of Zelda`) and the No-Intro trailing form
+//!   (`Legend of Zelda, The`) are stripped for comparison keys. English
+//!   articles are always recognized; other languages' articles only for
+//!   regions that use them, so `Die Hard` stays intact in a USA release.
+
+use crate::name_parser::region_to_slug;
+
+/// Articles recognized in every region.
+const ENGLISH_ARTICLES: &[&str] = &["the", "a", "an"];
+
+/// Articles recognized for regions where the language is used.
+const LANGUAGE_ARTICLES: &[(&str, &[&str])] = &[
+    ("fr", &["le", "la", "les", "l'"]),
+    ("de", &["der", "die", "das"]),
+    ("es", &["el", "la", "los", "las"]),
+    ("it", &["il", "lo", "la", "gli", "le", "l'"]),
+    ("nl", &["de", "het"]),
+    ("pt", &["o", "a", "os", "as"]),
+];
+
+/// Languages whose articles are stripped for a region slug.
+fn region_languages(region: &str) -> &'static [&'static str] {
+    match region_to_slug(region) {
+        "france" => &["fr"],
+        "canada" => &["fr"],
+        "germany" => &["de"],
+        "spain" | "latin-america" => &["es"],
+        "italy" => &["it"],
+        "netherlands" => &["nl"],
+        "portugal" | "brazil" => &["pt"],
+        _ => &[],
+    }
+}
+
+/// ASCII replacement for a Latin letter with a diacritic, if it has one.
+fn fold_char(c: char) -> Option<&'static str> {
+    Some(match c {
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ð' | 'Ď' | 'Đ' => "D",
+        'ð' | 'ď' | 'đ' => "d",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ĥ' | 'Ħ' => "H",
+        'ĥ' | 'ħ' => "h",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'Ĵ' => "J",
+        'ĵ' => "j",
+        'Ķ' => "K",
+        'ķ' => "k",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' => "N",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ŕ' | 'Ŗ' | 'Ř' => "R",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' => "S",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'Ţ' | 'Ť' | 'Ŧ' => "T",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'Þ' => "TH",
+        'þ' => "th",
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'Ŵ' => "W",
+        'ŵ' => "w",
+        'Ý' | 'Ŷ' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        '\u{2018}' | '\u{2019}' | '\u{02BC}' => "'",
+        _ => return None,
+    })
+}
+
+/// Fold Latin diacritics and fullwidth ASCII to plain ASCII.
+///
+/// Characters from other scripts are left as they are.
+pub fn fold_diacritics(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if let Some(folded) = fold_char(c) {
+            out.push_str(folded);
+        } else if ('\u{FF01}'..='\u{FF5E}').contains(&c) {
+            // Fullwidth forms sit at a fixed offset from ASCII
+            out.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c));
+        } else if c == '\u{3000}' {
+            out.push(' ');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Convert a string to a URL-friendly slug (lowercase, hyphens, no trailing hyphen).
+///
+/// Diacritics fold to ASCII and non-Latin letters pass through, so
+/// `Pokémon Stadium` becomes `pokemon-stadium` and `ドラゴンクエスト` keeps
+/// its kana.
+pub fn slugify(s: &str) -> String {
+    let folded = fold_diacritics(s);
+    let mut result = String::with_capacity(folded.len());
+    let mut last_was_separator = false;
+
+    for c in folded.chars() {
+        if c.is_ascii_alphanumeric() {
+            result.push(c.to_ascii_lowercase());
+            last_was_separator = false;
+        } else if !c.is_ascii() && c.is_alphanumeric() {
+            result.extend(c.to_lowercase());
+            last_was_separator = false;
+        } else if !last_was_separator && !result.is_empty() {
+            result.push('-');
+            last_was_separator = true;
+        }
+    }
+
+    // Trim trailing separator
+    if result.ends_with('-') {
+        result.pop();
+    }
+
+    result
+}
+
+/// The articles recognized for `region` (a display name or slug).
+fn articles_for(region: Option<&str>) -> Vec<&'static str> {
+    let mut articles = ENGLISH_ARTICLES.to_vec();
+    if let Some(region) = region {
+        for lang in region_languages(region) {
+            if let Some((_, list)) = LANGUAGE_ARTICLES.iter().find(|(l, _)| l == lang) {
+                articles.extend_from_slice(list);
+            }
+        }
+    }
+    articles
+}
+
+/// Strip a leading article, or a No-Intro style trailing one.
+///
+/// `The Legend of Zelda` and `Legend of Zelda, The` both become
+/// `Legend of Zelda`; a trailing article before a ` - ` subtitle is removed
+/// too. `region` enables articles beyond English (e.g. `Le`, `Die`).
+/// Diacritics are folded in the result.
+pub fn strip_article(title: &str, region: Option<&str>) -> String {
+    let articles = articles_for(region);
+    let folded = fold_diacritics(title);
+
+    // No-Intro moves articles behind the main title: "Name, The - Subtitle"
+    for article in &articles {
+        let suffix = format!(", {}", article);
+        if let Some(at) = folded.len().checked_sub(suffix.len())
+            && folded
+                .get(at..)
+                .is_some_and(|s| s.eq_ignore_ascii_case(&suffix))
+        {
+            return folded[..at].to_string();
+        }
+        for sep in [" - ", ": "] {
+            if let Some(at) = find_ignore_ascii_case(&folded, &format!("{}{}", suffix, sep)) {
+                return format!("{}{}", &folded[..at], &folded[at + suffix.len()..]);
+            }
+        }
+    }
+
+    for article in &articles {
+        let prefix = if article.ends_with('\'') {
+            article.to_string()
+        } else {
+            format!("{} ", article)
+        };
+        if folded
+            .get(..prefix.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(&prefix))
+        {
+            let rest = folded[prefix.len()..].trim_start();
+            if !rest.is_empty() {
+                return rest.to_string();
+            }
+        }
+    }
+
+    folded
+}
+
+/// Byte offset of the first ASCII-case-insensitive match of `needle`.
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack.char_indices().map(|(i, _)| i).find(|&i| {
+        haystack
+            .get(i..i + needle.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(needle))
+    })
+}
+
+/// Comparison key for a title: articles stripped, diacritics folded,
+/// lowercased, `&` read as `and`, and punctuation collapsed to single spaces.
+///
+/// Titles that differ only in those respects get the same key, e.g.
+/// `The Legend of Zelda: A Link to the Past` and
+/// `Legend of Zelda, The - A Link to the Past`.
+pub fn title_key(title: &str, region: Option<&str>) -> String {
+    let stripped = strip_article(title, region).replace('&', " and ");
+    let mut key = String::with_capacity(stripped.len());
+    for word in stripped
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        if !key.is_empty() {
+            key.push(' ');
+        }
+        key.extend(word.chars().flat_map(char::to_lowercase));
+    }
+    key
+}
@@ -100,6 +100,11 @@ pub enum WorkRelationship {
     Port,
     Remaster,
     Dlc,
+    /// The same game on another platform (e.g. Aladdin on SNES and Genesis).
+    Sibling,
+    /// Reviewed as a possible sibling and judged to be a different game.
+    #[serde(rename = "not_sibling")]
+    NotSibling,
 }
 
 // ── Release ─────────────────────────────────────────────────────────────────
@@ -278,3 +283,59 @@ pub struct Override {
     pub override_value: String,
     pub reason: String,
 }
+
+// ── Resolution Rules ────────────────────────────────────────────────────────
+
+/// How a [`ResolutionRule`] picks between the two values of a disagreement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionStrategy {
+    /// Take the value reported by the rule's `source`.
+    PreferSource,
+    /// Take the longer value (by character count).
+    Longest,
+    /// Take the shorter value (by character count).
+    Shortest,
+    /// Keep the value that was already in the catalog (source A).
+    PreferExisting,
+    /// Take the newly imported value (source B).
+    PreferIncoming,
+}
+
+/// A rule for resolving disagreements without manual review, loaded from YAML.
+///
+/// A rule matches disagreements on `field`, optionally narrowed by entity type
+/// and platform. Rules are applied in order; the first one that can decide a
+/// disagreement wins.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionRule {
+    pub field: String,
+    #[serde(default)]
+    pub entity_type: Option<String>,
+    #[serde(default)]
+    pub platform_id: Option<String>,
+    pub strategy: ResolutionStrategy,
+    /// Source name to prefer (required for `prefer_source`).
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl ResolutionRule {
+    /// Short description used in the resolution audit trail.
+    pub fn describe(&self) -> String {
+        if let Some(reason) = &self.reason {
+            return reason.clone();
+        }
+        match self.strategy {
+            ResolutionStrategy::PreferSource => {
+                format!("prefer {}", self.source.as_deref().unwrap_or("?"))
+            }
+            ResolutionStrategy::Longest => "longest value".to_string(),
+            ResolutionStrategy::Shortest => "shortest value".to_string(),
+            ResolutionStrategy::PreferExisting => "prefer existing".to_string(),
+            ResolutionStrategy::PreferIncoming => "prefer incoming".to_string(),
+        }
+    }
+}
@@ -1,9 +1,9 @@
 //! YAML loading for human-curated catalog data.
 //!
-//! Loads platform definitions, company profiles, and data overrides
-//! from the `catalog/` directory.
+//! Loads platform definitions, company profiles, data overrides, and
+//! disagreement resolution rules from the `catalog/` directory.
 
-use crate::types::{CatalogPlatform, Company, Override};
+use crate::types::{CatalogPlatform, Company, Override, ResolutionRule};
 use std::path::Path;
 use thiserror::Error;
 
@@ -44,6 +44,44 @@ pub fn load_companies(dir: &Path) -> Result<Vec<Company>, YamlError> {
 ///
 /// Each `.yaml` file should contain a YAML sequence (list) of `Override` entries.
 pub fn load_overrides(dir: &Path) -> Result<Vec<Override>, YamlError> {
+    load_yaml_lists(dir)
+}
+
+/// Load all disagreement auto-resolution rules from YAML files in a directory.
+///
+/// Each `.yaml` file should contain a YAML sequence (list) of `ResolutionRule`
+/// entries. Files are read in name order, and rules keep their order within a
+/// file, so earlier rules take precedence.
+pub fn load_resolution_rules(dir: &Path) -> Result<Vec<ResolutionRule>, YamlError> {
+    load_yaml_lists(dir)
+}
+
+/// Load all catalog data from the standard directory layout.
+///
+/// Expected structure:
+/// ```text
+/// catalog_dir/
+///   platforms/
+///     nes.yaml
+///     snes.yaml
+///     ...
+///   companies/
+///     nintendo.yaml
+///     capcom.yaml
+///     ...
+///   overrides/
+///     psx-serials.yaml
+///     ...
+/// ```
+pub fn load_catalog(catalog_dir: &Path) -> Result<CatalogData, YamlError> {
+    let platforms = load_platforms(&catalog_dir.join("platforms"))?;
+    let companies = load_companies(&catalog_dir.join("companies"))?;
+    let overrides = load_overrides(&catalog_dir.join("overrides"))?;
+    Ok((platforms, companies, overrides))
+}
+
+/// Generic helper: load all YAML files in a directory, each containing a list of `T`.
+fn load_yaml_lists<T: serde::de::DeserializeOwned>(dir: &Path) -> Result<Vec<T>, YamlError> {
     if !dir.exists() {
         return Ok(Vec::new());
     }
@@ -72,41 +110,16 @@ pub fn load_overrides(dir: &Path) -> Result<Vec<Override>, YamlError> {
             path: path.display().to_string(),
             source: e,
         })?;
-        let overrides: Vec<Override> =
-            serde_yml::from_str(&contents).map_err(|e| YamlError::Parse {
-                path: path.display().to_string(),
-                source: e,
-            })?;
-        all.extend(overrides);
+        let items: Vec<T> = serde_yml::from_str(&contents).map_err(|e| YamlError::Parse {
+            path: path.display().to_string(),
+            source: e,
+        })?;
+        all.extend(items);
     }
 
     Ok(all)
 }
 
-/// Load all catalog data from the standard directory layout.
-///
-/// Expected structure:
-/// ```text
-/// catalog_dir/
-///   platforms/
-///     nes.yaml
-///     snes.yaml
-///     ...
-///   companies/
-///     nintendo.yaml
-///     capcom.yaml
-///     ...
-///   overrides/
-///     psx-serials.yaml
-///     ...
-/// ```
-pub fn load_catalog(catalog_dir: &Path) -> Result<CatalogData, YamlError> {
-    let platforms = load_platforms(&catalog_dir.join("platforms"))?;
-    let companies = load_companies(&catalog_dir.join("companies"))?;
-    let overrides = load_overrides(&catalog_dir.join("overrides"))?;
-    Ok((platforms, companies, overrides))
-}
-
 /// Generic helper: load all YAML files in a directory, each containing a single `T`.
 fn load_yaml_dir<T: serde::de::DeserializeOwned>(dir: &Path) -> Result<Vec<T>, YamlError> {
     if !dir.exists() {
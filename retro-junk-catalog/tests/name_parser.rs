@@ -1,4 +1,6 @@
-use retro_junk_catalog::name_parser::{DumpStatus, parse_dat_name, region_to_slug};
+use retro_junk_catalog::name_parser::{
+    DumpStatus, Modification, Translation, parse_dat_name, region_to_slug,
+};
 
 #[test]
 fn simple_usa_game() {
@@ -177,3 +179,84 @@ fn demo_flag() {
     let p = parse_dat_name("Game (USA) (Demo)");
     assert!(p.flags.contains(&"Demo".to_string()));
 }
+
+#[test]
+fn goodtools_country_codes() {
+    let p = parse_dat_name("Super Mario Bros. 3 (UE) [!]");
+    assert_eq!(p.title, "Super Mario Bros. 3");
+    assert_eq!(p.regions, vec!["USA", "Europe"]);
+    assert_eq!(p.status, DumpStatus::Verified);
+    assert!(p.goodtools);
+
+    assert_eq!(parse_dat_name("Game (J)").regions, vec!["Japan"]);
+    assert_eq!(parse_dat_name("Game (Sw)").regions, vec!["Sweden"]);
+    assert_eq!(
+        parse_dat_name("Game (JUE)").regions,
+        vec!["Japan", "USA", "Europe"]
+    );
+    // No-Intro tags made of capital letters are not country codes
+    let p = parse_dat_name("Game (USA) (SGB)");
+    assert_eq!(p.regions, vec!["USA"]);
+    assert_eq!(p.flags, vec!["SGB"]);
+    assert!(!p.goodtools);
+}
+
+#[test]
+fn goodtools_dump_tags() {
+    let p = parse_dat_name("Game (U) [b2]");
+    assert_eq!(p.status, DumpStatus::BadDump);
+
+    let p = parse_dat_name("Game (U) [o1]");
+    assert_eq!(p.status, DumpStatus::Overdump);
+
+    let p = parse_dat_name("Game (U) [h1C] [t1] [f2] [p1]");
+    assert_eq!(
+        p.modifications,
+        vec![
+            Modification::Hack,
+            Modification::Trainer,
+            Modification::Fixed,
+            Modification::Pirate,
+        ]
+    );
+    assert!(p.flags.is_empty());
+
+    let p = parse_dat_name("Game (U) [a1]");
+    assert_eq!(p.flags, vec!["Alt 1"]);
+}
+
+#[test]
+fn goodtools_translation() {
+    let p = parse_dat_name("Seiken Densetsu 3 (J) [T+Eng1.0_Neill Corlett]");
+    assert_eq!(p.regions, vec!["Japan"]);
+    assert_eq!(
+        p.translation,
+        Some(Translation {
+            language: "Eng".to_string(),
+            latest: true,
+            details: Some("1.0_Neill Corlett".to_string()),
+        })
+    );
+
+    let p = parse_dat_name("Game (J) [T-Fre]");
+    let t = p.translation.unwrap();
+    assert_eq!(t.language, "Fre");
+    assert!(!t.latest);
+    assert!(t.details.is_none());
+}
+
+#[test]
+fn goodtools_to_no_intro_name() {
+    let p = parse_dat_name("Super Mario Bros. 3 (U) (PRG1) [!]");
+    assert_eq!(p.to_no_intro_name(), "Super Mario Bros. 3 (USA) (Rev 1)");
+
+    let p = parse_dat_name("Legend of Zelda, The (U) (PRG0) [h1]");
+    assert_eq!(p.revision, None);
+    assert_eq!(p.to_no_intro_name(), "Legend of Zelda, The (USA)");
+
+    let p = parse_dat_name("Tetris (JUE) (V1.1) [a1]");
+    assert_eq!(
+        p.to_no_intro_name(),
+        "Tetris (Japan, USA, Europe) (v1.1) (Alt 1)"
+    );
+}
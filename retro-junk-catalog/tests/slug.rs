@@ -0,0 +1,82 @@
+use retro_junk_catalog::{fold_diacritics, slugify, strip_article, title_key};
+
+#[test]
+fn slugify_ascii_titles() {
+    assert_eq!(slugify("Super Mario Bros."), "super-mario-bros");
+    assert_eq!(slugify("  Hello -- World!! "), "hello-world");
+    assert_eq!(slugify("Rev 1.1"), "rev-1-1");
+}
+
+#[test]
+fn slugify_folds_diacritics() {
+    assert_eq!(slugify("Pokémon Stadium"), "pokemon-stadium");
+    assert_eq!(slugify("Ōkami"), "okami");
+    assert_eq!(slugify("Straße"), "strasse");
+    assert_eq!(slugify("Æon Flux"), "aeon-flux");
+}
+
+#[test]
+fn slugify_passes_other_scripts_through() {
+    assert_eq!(slugify("ドラゴンクエスト III"), "ドラゴンクエスト-iii");
+    assert_eq!(slugify("Тетрис"), "тетрис");
+    assert_ne!(slugify("ゼルダの伝説"), slugify("メトロイド"));
+}
+
+#[test]
+fn fold_handles_fullwidth_and_quotes() {
+    assert_eq!(fold_diacritics("ＦＦ　ＩＩＩ"), "FF III");
+    assert_eq!(fold_diacritics("Kirby\u{2019}s"), "Kirby's");
+    assert_eq!(fold_diacritics("ゼルダ"), "ゼルダ");
+}
+
+#[test]
+fn strip_leading_and_trailing_articles() {
+    assert_eq!(
+        strip_article("The Legend of Zelda", None),
+        "Legend of Zelda"
+    );
+    assert_eq!(
+        strip_article("Legend of Zelda, The", None),
+        "Legend of Zelda"
+    );
+    assert_eq!(
+        strip_article("Legend of Zelda, The - A Link to the Past", None),
+        "Legend of Zelda - A Link to the Past"
+    );
+    assert_eq!(strip_article("Bug's Life, A", None), "Bug's Life");
+    // A lone article is a title, not an article
+    assert_eq!(strip_article("The", None), "The");
+    // Words that merely start with an article are untouched
+    assert_eq!(strip_article("Theme Park", None), "Theme Park");
+}
+
+#[test]
+fn strip_article_is_region_aware() {
+    assert_eq!(strip_article("Die Hard", Some("USA")), "Die Hard");
+    assert_eq!(strip_article("Die Siedler", Some("Germany")), "Siedler");
+    assert_eq!(strip_article("L'Aventure", Some("france")), "Aventure");
+    assert_eq!(
+        strip_article("Aventures de Tintin, Les", Some("France")),
+        "Aventures de Tintin"
+    );
+    assert_eq!(strip_article("Les Aventures", None), "Les Aventures");
+}
+
+#[test]
+fn title_keys_match_across_spellings() {
+    let key = title_key("The Legend of Zelda: A Link to the Past", None);
+    assert_eq!(key, "legend of zelda a link to the past");
+    assert_eq!(
+        title_key("Legend of Zelda, The - A Link to the Past", None),
+        key
+    );
+    assert_eq!(
+        title_key("Pokémon - Red Version", None),
+        title_key("Pokemon: Red Version", None)
+    );
+    assert_eq!(
+        title_key("Ratchet & Clank", None),
+        title_key("Ratchet and Clank", None)
+    );
+    assert_eq!(title_key("ゼルダの伝説", None), "ゼルダの伝説");
+}
@@ -1,4 +1,6 @@
-use retro_junk_catalog::{load_companies, load_overrides, load_platforms};
+use retro_junk_catalog::{
+    ResolutionStrategy, load_companies, load_overrides, load_platforms, load_resolution_rules,
+};
 use std::fs;
 use std::path::Path;
 use tempfile::TempDir;
@@ -96,6 +98,39 @@ fn load_overrides_from_yaml() {
     assert_eq!(overrides[0].platform_id.as_deref(), Some("ps1"));
 }
 
+#[test]
+fn load_resolution_rules_in_file_order() {
+    let tmp = TempDir::new().unwrap();
+    let dir = tmp.path().join("resolutions");
+    fs::create_dir(&dir).unwrap();
+
+    write_yaml(
+        &dir,
+        "10-dates.yaml",
+        r#"
+- field: release_date
+  strategy: prefer_source
+  source: dat-import
+"#,
+    );
+    write_yaml(
+        &dir,
+        "20-text.yaml",
+        r#"
+- field: description
+  strategy: longest
+  reason: "Longer synopses are more complete"
+"#,
+    );
+
+    let rules = load_resolution_rules(&dir).unwrap();
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].strategy, ResolutionStrategy::PreferSource);
+    assert_eq!(rules[0].describe(), "prefer dat-import");
+    assert_eq!(rules[1].strategy, ResolutionStrategy::Longest);
+    assert_eq!(rules[1].describe(), "Longer synopses are more complete");
+}
+
 #[test]
 fn missing_dir_returns_empty() {
     let tmp = TempDir::new().unwrap();
@@ -4,7 +4,14 @@ use std::path::PathBuf;
 
 use clap::{Args, Parser, Subcommand};
 
-use retro_junk_lib::Platform;
+use retro_junk_frontend::AssetType;
+use retro_junk_lib::analysis_output::OutputFormat;
+use retro_junk_lib::chd_verify::ChdVerifyMode;
+use retro_junk_lib::notify::NotifyFormat;
+use retro_junk_lib::rename::{ConflictStrategy, LinkRename};
+use retro_junk_lib::sidecar::SidecarFormat;
+use retro_junk_lib::{Language, Platform};
+use retro_junk_scraper::AssetNaming;
 
 #[derive(Parser)]
 #[command(name = "retro-junk")]
@@ -26,6 +33,29 @@ pub(crate) struct Cli {
     #[arg(long, global = true)]
     pub logfile: Option<PathBuf>,
 
+    /// ES-DE es_systems.xml to read folder names from (default: the bundled
+    /// and custom_systems files from ES-DE's standard locations)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub es_systems: Option<PathBuf>,
+
+    /// How many folder levels below the library path to search for console
+    /// folders (default: 2, e.g. roms/nintendo/snes)
+    #[arg(long, global = true, value_name = "N")]
+    pub scan_depth: Option<usize>,
+
+    /// Ignore symlinked (and junctioned) ROMs and folders instead of following them
+    #[arg(long, global = true)]
+    pub skip_symlinks: bool,
+
+    /// POST a summary to this URL when analyze, verify, scrape, catalog
+    /// verify, or catalog dumper finishes a run
+    #[arg(long, global = true, value_name = "URL")]
+    pub notify: Option<String>,
+
+    /// Notification payload: webhook (JSON summary), ntfy, or discord
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "webhook")]
+    pub notify_format: NotifyFormat,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -46,15 +76,149 @@ pub(crate) struct RomFilterArgs {
 pub(crate) enum Commands {
     /// Analyze ROMs in a directory structure
     Analyze {
+        /// Analyze one file instead of the library: a path (or an extracted
+        /// game folder), an http(s) URL, an SMB share
+        /// (smb://host/share/path or a UNC path), or "-" for stdin
+        #[arg(value_name = "FILE")]
+        input: Option<String>,
+
         /// Quick mode: read as little data as possible (useful for network shares)
         #[arg(short, long)]
         quick: bool,
 
         #[command(flatten)]
         roms: RomFilterArgs,
+
+        /// Write a <rom>.retro-junk.toml sidecar next to each ROM
+        /// (pass "json" for JSON)
+        #[arg(long, value_name = "FORMAT", num_args = 0..=1, default_missing_value = "toml")]
+        sidecar: Option<SidecarFormat>,
+
+        /// Print the raw header bytes of each ROM as a hex dump, for
+        /// reporting misdetections
+        #[arg(long)]
+        dump_header: bool,
+
+        /// Check CHD images against the SHA1 stored in their headers: "fast"
+        /// matches it against the DAT, "deep" also decompresses the image to
+        /// detect corruption
+        #[arg(long, value_name = "MODE", num_args = 0..=1, default_missing_value = "fast")]
+        verify_chd: Option<ChdVerifyMode>,
+
+        /// Use DAT files from this directory instead of the cache (for
+        /// --verify-chd)
+        #[arg(long)]
+        dat_dir: Option<PathBuf>,
+
+        /// Output format: text, json (one array), or ndjson (one object per
+        /// line). JSON goes to stdout; warnings still go to stderr
+        #[arg(long, value_name = "FORMAT", default_value = "text")]
+        format: OutputFormat,
+
+        /// Cap memory, seeks, and table sizes per file so a corrupt or
+        /// hostile file fails fast (for untrusted URLs and stdin). Checksums
+        /// of cartridges over 64 MB are skipped
+        #[arg(long)]
+        hardened: bool,
+
+        /// Language for region names in text output: en, fr, de, es, or ja
+        #[arg(long, value_name = "CODE", default_value = "en")]
+        display_language: Language,
+    },
+
+    /// Summarize trimmed, truncated, and oversized ROMs per console
+    ///
+    /// Reads only headers (no hashing), so it's fast enough to sweep the whole
+    /// library before deciding what to repair.
+    AuditSizes {
+        #[command(flatten)]
+        roms: RomFilterArgs,
+
+        /// List every file whose size doesn't match its header
+        #[arg(long)]
+        list: bool,
+    },
+
+    /// Check every ROM against its DAT: verified, bad dump, overdump, or unknown
+    ///
+    /// Hashes each file and reports its status without renaming or changing
+    /// anything. Single-ROM .zip and .7z archives are checked by their contents.
+    ///
+    /// With --smdb, checks the whole library folder against an EverDrive-style
+    /// .smdb listing instead: each file is hashed as stored and reported as in
+    /// place, misplaced, unknown, or missing.
+    Verify {
+        /// Verify one file instead of the library: a path, an http(s) URL,
+        /// or an SMB share (smb://host/share/path or a UNC path)
+        #[arg(value_name = "FILE", conflicts_with = "smdb")]
+        input: Option<String>,
+
+        #[command(flatten)]
+        roms: RomFilterArgs,
+
+        /// Use DAT files from this directory instead of the cache
+        #[arg(long)]
+        dat_dir: Option<PathBuf>,
+
+        /// Only list files that aren't verified
+        #[arg(long)]
+        problems_only: bool,
+
+        /// Write every file's status to a .csv or .json report
+        #[arg(long, value_name = "PATH")]
+        report: Option<PathBuf>,
+
+        /// Write a _retro-junk-report.txt into each console folder listing
+        /// every file's status and the DAT versions used
+        #[arg(long, conflicts_with_all = ["input", "smdb"])]
+        folder_report: bool,
+
+        /// Check against this .smdb listing instead of DATs
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["dat_dir", "report"])]
+        smdb: Option<PathBuf>,
+
+        /// Move misplaced files to the paths the SMDB lists (with --smdb)
+        #[arg(long = "move", requires = "smdb")]
+        move_files: bool,
+    },
+
+    /// Keep one release of each game (1G1R) and set the others aside
+    ///
+    /// Groups each console folder's games by title (and by the DAT's
+    /// parent/clone data when available) and picks one release by region,
+    /// language and revision. Only reports unless --move is given, which
+    /// moves the other releases into a _duplicates subfolder.
+    #[command(name = "1g1r")]
+    OneG1r {
+        /// Console names or aliases (e.g., snes,n64,ps1,gc,gg)
+        #[arg(short, long, value_delimiter = ',')]
+        consoles: Option<Vec<Platform>>,
+
+        /// Use DAT files from this directory instead of the cache
+        #[arg(long)]
+        dat_dir: Option<PathBuf>,
+
+        /// Preferred regions, most preferred first
+        #[arg(long, value_delimiter = ',', default_value = "USA,World,Europe,Japan")]
+        regions: Vec<String>,
+
+        /// Preferred languages, most preferred first
+        #[arg(long, value_delimiter = ',', default_value = "En,Ja")]
+        languages: Vec<String>,
+
+        /// Prefer the original release over later revisions
+        #[arg(long)]
+        original_revision: bool,
+
+        /// Move the releases that aren't kept into _duplicates
+        #[arg(long = "move")]
+        move_duplicates: bool,
     },
 
     /// Rename ROM files to NoIntro canonical names
+    ///
+    /// Files listed in a console folder's rename-overrides.toml are pinned to
+    /// the DAT game name or CRC32 given there instead of being matched.
     Rename {
         /// Show planned renames without executing
         #[arg(short = 'n', long)]
@@ -78,6 +242,65 @@ pub(crate) enum Commands {
         /// Don't rename media files alongside ROMs
         #[arg(long)]
         no_media: bool,
+
+        /// Write the full plan to a .csv or .json file (requires --dry-run)
+        #[arg(long, value_name = "PATH", requires = "dry_run")]
+        export: Option<PathBuf>,
+
+        /// How to handle several files matching the same DAT entry:
+        /// skip, keep-first, keep-best, or dedup-suffix
+        #[arg(long, value_name = "STRATEGY", default_value = "skip")]
+        on_conflict: ConflictStrategy,
+
+        /// Write a _retro-junk-report.txt into each console folder listing
+        /// every file's status and the DAT versions used
+        #[arg(long, conflicts_with = "dry_run")]
+        report: bool,
+
+        /// Record the DAT match in a <rom>.retro-junk.toml sidecar next to each ROM
+        /// (pass "json" for JSON)
+        #[arg(long, value_name = "FORMAT", num_args = 0..=1, default_missing_value = "toml")]
+        sidecar: Option<SidecarFormat>,
+
+        /// Split .zip/.7z archives holding several ROMs into individual,
+        /// canonically named files (the archive is removed afterwards)
+        #[arg(long)]
+        split_archives: bool,
+
+        /// Also match .zip/.7z files holding a single ROM by the ROM inside,
+        /// and rename the archive to the DAT name
+        #[arg(long)]
+        archives: bool,
+
+        /// For symlinked ROMs, rename the link itself ("link") or the file it
+        /// points to, re-pointing the link ("target")
+        #[arg(long, value_name = "MODE", default_value = "link")]
+        symlinks: LinkRename,
+
+        /// Adjust names for FAT32/exFAT SD cards (no ':', '?', trailing dots,
+        /// 255-byte limit); canonical names are kept in canonical-names.toml
+        #[arg(long)]
+        sanitize: bool,
+
+        /// Move loose discs of a multi-disc game into a new <game>.m3u
+        /// folder and write its playlist
+        #[arg(long)]
+        create_m3u: bool,
+
+        /// Extensions of files sharing a ROM's name that are renamed with it
+        /// (default: ips,bps,ups,sav,srm,cht,png)
+        #[arg(long, value_name = "EXTS", value_delimiter = ',')]
+        companions: Option<Vec<String>>,
+
+        /// Don't rename saves, patches, or other companion files
+        #[arg(long, conflicts_with = "companions")]
+        no_companions: bool,
+
+        /// Keep files that are removed or rewritten (split archives, old
+        /// playlists, CUE sheets) in .retro-junk-trash/ so `trash restore`
+        /// can bring them back
+        #[arg(long, conflicts_with = "dry_run")]
+        trash: bool,
     },
 
     /// [Experimental] Repair trimmed/truncated ROMs by padding to match DAT checksums
@@ -90,6 +313,16 @@ pub(crate) enum Commands {
         #[arg(long)]
         no_backup: bool,
 
+        /// Copy each ROM into .retro-junk-trash/ before repairing it, so
+        /// `trash restore` can bring the original back
+        #[arg(long, conflicts_with = "dry_run")]
+        trash: bool,
+
+        /// Remove 512-byte copier headers from SNES/Genesis ROMs that match
+        /// the DAT without them
+        #[arg(long)]
+        strip_headers: bool,
+
         #[command(flatten)]
         roms: RomFilterArgs,
 
@@ -98,12 +331,43 @@ pub(crate) enum Commands {
         dat_dir: Option<PathBuf>,
     },
 
+    /// Dump a DVD from an optical drive to an ISO and verify it against Redump
+    ///
+    /// Reads the 2048-byte data sectors the OS exposes for the drive, which is
+    /// what Redump lists for DVD-based systems (PS2 DVDs, Xbox, GameCube, Wii).
+    /// Raw 2352-byte CD reads and CUE/BIN output are not supported, so CDs
+    /// (PS1, Saturn, Sega CD, PC Engine CD, PS2 CDs) are refused, as are
+    /// cartridge systems.
+    DumpDvd {
+        /// Console the disc belongs to (e.g., ps2, xbox)
+        system: Platform,
+
+        /// Optical drive device (e.g., /dev/sr0)
+        device: PathBuf,
+
+        /// Output image path
+        output: PathBuf,
+
+        /// Read attempts per sector before zero-filling it
+        #[arg(long, default_value_t = 3)]
+        retries: u32,
+
+        /// Use DAT files from this directory instead of the cache
+        #[arg(long)]
+        dat_dir: Option<PathBuf>,
+
+        /// Replace the output file if it exists
+        #[arg(long)]
+        overwrite: bool,
+    },
+
     /// Scrape game metadata and media from ScreenScraper.fr
     Scrape {
         #[command(flatten)]
         roms: RomFilterArgs,
 
-        /// Media types to download (e.g., covers,screenshots,videos,marquees)
+        /// Media types to download (e.g., covers,backcovers,3dboxes,screenshots,
+        /// titlescreens,marquees,videos,fanart,physicalmedia,labels)
         #[arg(long, value_delimiter = ',')]
         media_types: Option<Vec<String>>,
 
@@ -125,6 +389,17 @@ pub(crate) enum Commands {
         #[arg(long, default_value = "us")]
         region: String,
 
+        /// Region preference for one media type, overriding --region and the
+        /// ROM-detected region (e.g., covers=jp). Repeatable.
+        #[arg(long = "media-region", value_name = "TYPE=REGION")]
+        media_regions: Vec<String>,
+
+        /// Name media files after the ROM (rom, as ES-DE expects), its slug
+        /// (slug), or the game's serial (serial). `sync` and `rename` only
+        /// recognize ROM-named media
+        #[arg(long, value_name = "NAMING", default_value = "rom")]
+        asset_naming: AssetNaming,
+
         /// Language for descriptions: "match" derives from ROM region (default), or a code like "en", "ja", "fr"
         #[arg(long, default_value = "match")]
         language: String,
@@ -160,6 +435,104 @@ pub(crate) enum Commands {
         /// Maximum concurrent API threads (default: server-granted max)
         #[arg(long)]
         threads: Option<usize>,
+
+        /// Threads for miximage generation, separate from the API threads
+        /// (default: one per CPU)
+        #[arg(long, value_name = "N")]
+        image_threads: Option<usize>,
+
+        /// Record scraped metadata in a <rom>.retro-junk.toml sidecar next to each ROM
+        /// (pass "json" for JSON)
+        #[arg(long, value_name = "FORMAT", num_args = 0..=1, default_missing_value = "toml")]
+        sidecar: Option<SidecarFormat>,
+
+        /// Serve Prometheus metrics, including the API requests used today,
+        /// at http://<ADDR>/metrics while scraping (e.g. 127.0.0.1:9898)
+        #[arg(long, value_name = "ADDR")]
+        metrics: Option<String>,
+    },
+
+    /// Prune gamelist entries and media for ROMs no longer on disk
+    ///
+    /// Removes gamelist.xml entries whose ROM is gone and media files that
+    /// don't match any ROM. Does nothing for a missing or empty ROM folder.
+    Sync {
+        /// Show what would be pruned without changing anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Console names or aliases (e.g., snes,n64,ps1,gc,gg)
+        #[arg(short, long, value_delimiter = ',')]
+        consoles: Option<Vec<Platform>>,
+
+        /// Directory for metadata files (default: <root>-metadata)
+        #[arg(long)]
+        metadata_dir: Option<PathBuf>,
+
+        /// Directory for media files (default: <root>-media)
+        #[arg(long)]
+        media_dir: Option<PathBuf>,
+    },
+
+    /// Copy games onto a device's SD card in the layout it expects
+    ///
+    /// A device profile sets the folder for each system, the formats the
+    /// device loads (the preferred one wins when a game exists in several),
+    /// and the file-name rules of its card. Files already on the card with
+    /// the same size are skipped, so re-running an export tops a card up.
+    Export {
+        /// Device profile (mister, everdrive-n8, everdrive-gba, megasd, usb-loader-gx, anbernic)
+        #[arg(long)]
+        profile: String,
+
+        /// Root of the device's card
+        target: PathBuf,
+
+        #[command(flatten)]
+        roms: RomFilterArgs,
+
+        /// Only export games whose name contains this text
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Show what would be copied without copying anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+
+    /// Join a file that was split for a FAT32 card
+    ///
+    /// Takes the first piece (`Game.iso.part0` or `Game.wbfs`), checks that
+    /// no piece was truncated, writes the joined file, and checks its CRC32
+    /// against the DAT of the platform it's detected as.
+    Join {
+        /// First piece of the split file
+        part: PathBuf,
+
+        /// Where to write the joined file (defaults to the name without the
+        /// piece suffix, next to the pieces)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Use DAT files from this directory instead of the cache
+        #[arg(long)]
+        dat_dir: Option<PathBuf>,
+    },
+
+    /// Check the library layout against what ES-DE expects
+    ///
+    /// Reports console folders ES-DE won't recognize as systems, and the
+    /// system name scraped gamelists and media are written under.
+    Doctor {
+        /// Console names or aliases (e.g., snes,n64,ps1,gc,gg)
+        #[arg(short, long, value_delimiter = ',')]
+        consoles: Option<Vec<Platform>>,
+    },
+
+    /// List or restore files kept by `rename --trash` and `repair --trash`
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
     },
 
     /// Manage cached DAT files
@@ -188,6 +561,15 @@ pub(crate) enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+pub(crate) enum TrashAction {
+    /// List the files in each console folder's trash
+    List,
+
+    /// Put trashed files back where they came from
+    Restore,
+}
+
 #[derive(Subcommand)]
 pub(crate) enum CacheAction {
     /// List cached DAT files
@@ -248,6 +630,132 @@ pub(crate) enum SettingsAction {
     },
 }
 
+#[derive(Subcommand)]
+pub(crate) enum AssetsAction {
+    /// Check asset files against their recorded hashes
+    Verify {
+        /// Only verify assets for this system (e.g., nes, snes)
+        #[arg(long)]
+        system: Option<String>,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+
+    /// Find asset files the catalog doesn't reference, and rows whose files are missing
+    Prune {
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Directory for catalog media assets
+        #[arg(long)]
+        asset_dir: Option<PathBuf>,
+
+        /// Delete orphaned files and the rows of missing files
+        #[arg(long)]
+        delete: bool,
+
+        /// Re-download missing files from their source URLs
+        #[arg(long)]
+        redownload: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum CompaniesAction {
+    /// List company names from enrichment that matched no known company
+    Review {
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Maximum number of names to show
+        #[arg(long, default_value = "50")]
+        limit: u32,
+    },
+
+    /// Approve names as aliases of their suggested company, or as new companies
+    Approve {
+        /// Staged name IDs (e.g., 3,7,12)
+        #[arg(value_delimiter = ',', required_unless_present = "all_suggested")]
+        ids: Vec<i64>,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Approve every name that has a suggested company
+        #[arg(long, conflicts_with = "ids")]
+        all_suggested: bool,
+    },
+
+    /// Merge a name into an existing company as an alias
+    Merge {
+        /// Staged name ID
+        id: i64,
+
+        /// Company ID to merge into (e.g., nintendo)
+        company: String,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+
+    /// Reject names so enrichment ignores them
+    Reject {
+        /// Staged name IDs (e.g., 3,7,12)
+        #[arg(value_delimiter = ',', required = true)]
+        ids: Vec<i64>,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum SiblingsAction {
+    /// List works with the same title on different platforms
+    Review {
+        /// Only show groups including these systems (e.g., snes,genesis)
+        #[arg(value_delimiter = ',')]
+        systems: Vec<String>,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Maximum number of groups to show
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+
+    /// Link works as the same game on different platforms
+    Link {
+        /// Work IDs (e.g., snes:aladdin,genesis:aladdin)
+        #[arg(value_delimiter = ',', num_args = 1.., required = true)]
+        works: Vec<String>,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+
+    /// Mark works as different games so they are no longer reported
+    Reject {
+        /// Work IDs (e.g., snes:aladdin,genesis:aladdin)
+        #[arg(value_delimiter = ',', num_args = 1.., required = true)]
+        works: Vec<String>,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+}
+
 #[derive(Subcommand)]
 pub(crate) enum CatalogAction {
     /// Import DAT files into the catalog database
@@ -318,6 +826,17 @@ pub(crate) enum CatalogAction {
         #[arg(long, default_value = "us")]
         region: String,
 
+        /// Region preference for one asset type, overriding --region
+        /// (e.g., box-front=jp). Repeatable.
+        #[arg(long = "asset-region", value_name = "TYPE=REGION", value_parser = parse_asset_region)]
+        asset_regions: Vec<(String, String)>,
+
+        /// File assets in one folder per type, named after the release's DAT
+        /// name (rom), slug (slug), or serial (serial), instead of one folder
+        /// per release
+        #[arg(long, value_name = "NAMING")]
+        asset_naming: Option<AssetNaming>,
+
         /// Preferred language for descriptions (default: en)
         #[arg(long, default_value = "en")]
         language: String,
@@ -329,6 +848,10 @@ pub(crate) enum CatalogAction {
         /// Skip automatic work reconciliation after enrichment
         #[arg(long)]
         no_reconcile: bool,
+
+        /// Look releases up and show what would change, without writing or downloading anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
     },
 
     /// Scan a ROM folder and add matched files to collection
@@ -348,6 +871,106 @@ pub(crate) enum CatalogAction {
         user_id: String,
     },
 
+    /// Watch a cartridge dumper folder and add verified dumps to the collection
+    Dumper {
+        /// System being dumped (e.g., snes, gb, genesis)
+        system: String,
+
+        /// Folder or mounted drive the dumper writes to
+        folder: PathBuf,
+
+        /// Console folder for verified dumps (default: <library>/<system>)
+        #[arg(long)]
+        dest: Option<PathBuf>,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// User ID for collection entries
+        #[arg(long, default_value = "default")]
+        user_id: String,
+
+        /// Seconds between folder checks
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+
+        /// Process the dumps currently in the folder and exit
+        #[arg(long, conflicts_with = "daemon")]
+        once: bool,
+
+        /// Run unattended (as a systemd unit, Windows scheduled task, or
+        /// service): keep a state file, append to a rotating log, and finish
+        /// the current dump before stopping on Ctrl-C or SIGTERM
+        #[arg(long)]
+        daemon: bool,
+
+        /// State file for --daemon (default: dumper-<system>.toml in the
+        /// cache directory)
+        #[arg(long, value_name = "PATH", requires = "daemon")]
+        state_file: Option<PathBuf>,
+
+        /// Rotate the --daemon log once it grows past this many megabytes
+        #[arg(long, value_name = "MB", default_value_t = 10, requires = "daemon")]
+        log_max_size: u64,
+
+        /// Rotated --daemon logs to keep
+        #[arg(long, value_name = "N", default_value_t = 5, requires = "daemon")]
+        log_keep: usize,
+
+        /// Serve Prometheus metrics at http://<ADDR>/metrics (e.g.
+        /// 127.0.0.1:9898)
+        #[arg(long, value_name = "ADDR", conflicts_with = "once")]
+        metrics: Option<String>,
+    },
+
+    /// Import emulator screenshots as release assets
+    Screenshots {
+        /// System the captures belong to (e.g., nes, snes, n64)
+        system: String,
+
+        /// Folder containing capture images
+        folder: PathBuf,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Directory for catalog media assets
+        #[arg(long)]
+        asset_dir: Option<PathBuf>,
+
+        /// Register images as title screens instead of screenshots
+        #[arg(long)]
+        title_screen: bool,
+
+        /// Manual mapping from image stem to release ID (STEM=RELEASE_ID)
+        #[arg(long = "map", value_name = "STEM=RELEASE_ID")]
+        mappings: Vec<String>,
+
+        /// Replace existing asset files
+        #[arg(long)]
+        overwrite: bool,
+    },
+
+    /// Manage downloaded and imported media assets
+    Assets {
+        #[command(subcommand)]
+        action: AssetsAction,
+    },
+
+    /// Review company names staged during enrichment
+    Companies {
+        #[command(subcommand)]
+        action: CompaniesAction,
+    },
+
+    /// Review works that appear to be the same game on different platforms
+    Siblings {
+        #[command(subcommand)]
+        action: SiblingsAction,
+    },
+
     /// Re-verify collection entries against files on disk
     Verify {
         /// System to verify (e.g., nes, snes, n64)
@@ -362,6 +985,45 @@ pub(crate) enum CatalogAction {
         user_id: String,
     },
 
+    /// Re-analyze collection files to fill in serials and release dates
+    Refresh {
+        /// System to refresh (e.g., nes, snes, n64)
+        system: String,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+
+    /// Export a renamed listing of a system's media as an .smdb file
+    Export {
+        /// System to export (e.g., nes, snes, n64)
+        system: String,
+
+        /// File to write the listing to
+        output: PathBuf,
+
+        /// Naming convention: no-intro, emumovies, or everdrive
+        #[arg(long, default_value = "no-intro")]
+        naming: String,
+
+        /// Folder to place every file under (e.g., "NES")
+        #[arg(long)]
+        folder: Option<String>,
+
+        /// Only export media in your collection
+        #[arg(long)]
+        owned: bool,
+
+        /// User ID whose collection to export with --owned
+        #[arg(long, default_value = "default")]
+        user_id: String,
+
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+
     /// List unresolved disagreements between data sources
     Disagreements {
         /// Path to the catalog database file
@@ -403,6 +1065,21 @@ pub(crate) enum CatalogAction {
         custom: Option<String>,
     },
 
+    /// Resolve disagreements automatically using catalog/resolutions/ rules
+    AutoResolve {
+        /// Path to the catalog database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Path to catalog YAML data directory (default: ./catalog)
+        #[arg(long)]
+        catalog_dir: Option<PathBuf>,
+
+        /// Show what would be resolved without making changes
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+
     /// Analyze media asset coverage gaps
     Gaps {
         /// System to analyze (e.g., nes, snes)
@@ -420,6 +1097,14 @@ pub(crate) enum CatalogAction {
         #[arg(long)]
         missing: Option<String>,
 
+        /// List works with no owned release instead of asset gaps
+        #[arg(long, conflicts_with_all = ["missing", "collection_only"])]
+        works: bool,
+
+        /// User ID whose collection to check for --works
+        #[arg(long, default_value = "default")]
+        user_id: String,
+
         /// Maximum releases to list
         #[arg(long, default_value = "50")]
         limit: u32,
@@ -434,7 +1119,8 @@ pub(crate) enum CatalogAction {
         #[arg(long, short = 't')]
         r#type: Option<String>,
 
-        /// Filter by platform short name (e.g., nes, snes, psx)
+        /// Filter by platform short name (e.g., nes, snes, psx), or a family
+        /// such as gb-family when listing platforms, releases, or media
         #[arg(long)]
         platform: Option<String>,
 
@@ -495,6 +1181,22 @@ pub(crate) enum CatalogAction {
         /// Path to the catalog database file
         #[arg(long)]
         db: Option<PathBuf>,
+
+        /// Show collection snapshots recorded by each scan and verify
+        #[arg(long)]
+        history: bool,
+
+        /// Only show history for this system (e.g., nes, snes, n64)
+        #[arg(long, requires = "history")]
+        system: Option<String>,
+
+        /// Write the history to a CSV file
+        #[arg(long, value_name = "PATH", requires = "history")]
+        csv: Option<PathBuf>,
+
+        /// User ID whose collection to count
+        #[arg(long, default_value = "default")]
+        user_id: String,
     },
 
     /// Clear enrichment status for releases (screenscraper_id and scraper_not_found)
@@ -526,3 +1228,27 @@ pub(crate) enum CatalogAction {
         confirm: bool,
     },
 }
+
+/// Parse an `--asset-region TYPE=REGION` pair. Unknown types and regions are
+/// rejected here, since enrichment would quietly ignore the type or fetch US
+/// media for the region.
+fn parse_asset_region(value: &str) -> Result<(String, String), String> {
+    let (asset_type, region) = value
+        .split_once('=')
+        .map(|(t, r)| (t.trim(), r.trim().to_lowercase()))
+        .filter(|(t, r)| !t.is_empty() && !r.is_empty())
+        .ok_or("expected TYPE=REGION (e.g., box-front=jp)")?;
+    if AssetType::from_catalog_name(asset_type).is_none() {
+        let names: Vec<&str> = AssetType::ALL.iter().map(|t| t.catalog_name()).collect();
+        return Err(format!(
+            "unknown asset type '{asset_type}'; expected one of {}",
+            names.join(", ")
+        ));
+    }
+    if retro_junk_import::known_catalog_region(&region).is_none() {
+        return Err(format!(
+            "unknown region '{region}'; expected us, eu, jp, au, kr, cn, tw, br, or wor"
+        ));
+    }
+    Ok((asset_type.to_string(), region))
+}
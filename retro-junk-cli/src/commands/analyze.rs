@@ -1,37 +1,72 @@
 use std::collections::HashSet;
-use std::fs;
+use std::io::SeekFrom;
 use std::path::{Path, PathBuf};
 
 use log::Level;
 use owo_colors::OwoColorize;
 use owo_colors::Stream::Stdout;
 
+use retro_junk_dat::cache;
+use retro_junk_dat::matcher::DatIndex;
+use retro_junk_lib::analysis_output::{AnalysisRecord, OutputFormat, render_records};
+use retro_junk_lib::archive;
+use retro_junk_lib::chd_verify::{self, ChdVerifyMode};
 use retro_junk_lib::display::{HARDWARE_KEYS, SizeVerdict, compute_size_verdict, prettify_key};
-use retro_junk_lib::{AnalysisContext, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+use retro_junk_lib::dump_check::{self, DumpWarning};
+use retro_junk_lib::notify::{NotifyTarget, RunSummary};
+use retro_junk_lib::remote::Source;
+use retro_junk_lib::scanner::ScanOptions;
+use retro_junk_lib::sidecar::{self, SidecarFormat, SidecarHashes};
+use retro_junk_lib::{
+    AnalysisContext, AnalysisError, AnalysisErrorKind, AnalysisOptions, AnalyzePathError,
+    DatSource, Language, LimitedReader, ParseLimits, Platform, ReadSeek, RomAnalyzer,
+    RomIdentification,
+};
 
 use crate::CliError;
+use crate::commands::send_notification;
 use crate::scan_folders;
 
 /// Run the analyze command.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn run_analyze(
     ctx: &AnalysisContext,
     quick: bool,
     consoles: Option<Vec<Platform>>,
     limit: Option<usize>,
     library_path: PathBuf,
+    sidecar: Option<SidecarFormat>,
+    dump_header: bool,
+    verify_chd: Option<ChdVerifyMode>,
+    dat_dir: Option<PathBuf>,
+    format: OutputFormat,
+    hardened: bool,
+    display_language: Language,
+    notify: Option<&NotifyTarget>,
 ) -> Result<(), CliError> {
     let root_path = library_path;
+    let mut summary = RunSummary::new("analyze");
+    let mut records = RecordSink::new(format, display_language);
 
     log::info!("Analyzing ROMs in: {}", root_path.display());
     if quick {
         log::info!("Quick mode enabled");
     }
+    if hardened {
+        log::info!("Hardened parsing enabled");
+    }
     if let Some(n) = limit {
         log::info!("Limit: {} games per console", n);
     }
+    if let Some(mode) = verify_chd {
+        log::info!("Verifying CHDs ({} mode)", mode);
+    }
     crate::log_blank();
 
-    let options = AnalysisOptions::new().quick(quick);
+    let options = AnalysisOptions::new()
+        .quick(quick)
+        .include_raw_header(dump_header)
+        .limits(parse_limits(hardened));
 
     let scan = match scan_folders(ctx, &root_path, &consoles) {
         Some(s) => s,
@@ -49,8 +84,25 @@ pub(crate) fn run_analyze(
             cf.folder_name.if_supports_color(Stdout, |t| t.cyan()),
         );
 
-        analyze_folder(&cf.path, console.analyzer.as_ref(), &options, limit);
+        let chd_check = verify_chd.map(|mode| ChdCheck {
+            mode,
+            index: load_disk_index(console.analyzer.as_ref(), dat_dir.as_deref()),
+        });
+
+        analyze_folder(
+            &cf.path,
+            console.analyzer.as_ref(),
+            &options,
+            &ctx.scan_options().archives(true),
+            limit,
+            sidecar,
+            chd_check.as_ref(),
+            &mut summary,
+            &mut records,
+        );
     }
+    records.finish();
+    send_notification(notify, summary);
 
     if scan.matches.is_empty() {
         log::info!(
@@ -71,18 +123,183 @@ pub(crate) fn run_analyze(
     Ok(())
 }
 
+/// Analyze a single file from a local path, URL, SMB share, or stdin.
+///
+/// The platform is detected as for a local file (see
+/// [`AnalysisContext::analyze_reader`]), among `consoles` when given. Remote
+/// sources are read
+/// on demand, so only the regions the analyzer touches are transferred. A
+/// local folder is analyzed as an extracted game.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_analyze_input(
+    ctx: &AnalysisContext,
+    input: &str,
+    quick: bool,
+    consoles: Option<Vec<Platform>>,
+    dump_header: bool,
+    format: OutputFormat,
+    hardened: bool,
+    display_language: Language,
+) -> Result<(), CliError> {
+    let mut records = RecordSink::new(format, display_language);
+    if Path::new(input).is_dir() {
+        return analyze_input_dir(ctx, Path::new(input), quick, consoles, hardened, records);
+    }
+
+    let source = Source::parse(input);
+    let name = source.file_name();
+    if source.is_remote() {
+        log::info!("Reading from {}", source);
+    }
+    let mut reader = source
+        .open()
+        .map_err(|e| CliError::other(format!("Failed to open {}: {}", source, e)))?;
+
+    let options = AnalysisOptions {
+        file_path: source.local_path(),
+        ..AnalysisOptions::new()
+            .quick(quick)
+            .include_raw_header(dump_header)
+            .limits(parse_limits(hardened))
+    };
+
+    let (platform, info) = match ctx.analyze_reader(
+        reader.as_mut(),
+        Path::new(&name),
+        consoles.as_deref(),
+        &options,
+    ) {
+        Ok(result) => result,
+        Err(AnalyzePathError::NotRecognized(_)) => {
+            log::warn!(
+                "  {}: {} Not recognized",
+                name,
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+            );
+            return Ok(());
+        }
+        Err(AnalyzePathError::Analysis(e)) => {
+            return Err(CliError::analysis(format!(
+                "{}: {} ({})",
+                name,
+                failure_label(&e),
+                e
+            )));
+        }
+        Err(e) => return Err(CliError::other(format!("{}: {}", name, e))),
+    };
+    if let Some(console) = ctx.get_by_platform(platform) {
+        log::info!(
+            "{} {}",
+            "Analyzed as".if_supports_color(Stdout, |t| t.bold()),
+            console.metadata.platform_name,
+        );
+    }
+    log_analysis_lines(&format_analysis(&name, &info, "", display_language));
+
+    let path = source.local_path().unwrap_or_else(|| PathBuf::from(input));
+    records.push(AnalysisRecord::identified(path, platform, info));
+    records.finish();
+    Ok(())
+}
+
+/// Analyze an extracted game folder given as the input.
+fn analyze_input_dir(
+    ctx: &AnalysisContext,
+    dir: &Path,
+    quick: bool,
+    consoles: Option<Vec<Platform>>,
+    hardened: bool,
+    mut records: RecordSink,
+) -> Result<(), CliError> {
+    let name = dir.display().to_string();
+    let options = AnalysisOptions::new()
+        .quick(quick)
+        .limits(parse_limits(hardened));
+    let (platform, info) = match ctx.analyze_path_with(dir, &options) {
+        Ok((platform, info)) if consoles.as_ref().is_none_or(|c| c.contains(&platform)) => {
+            (platform, info)
+        }
+        Err(AnalyzePathError::Analysis(e)) => {
+            return Err(CliError::analysis(format!(
+                "{}: {} ({})",
+                name,
+                failure_label(&e),
+                e
+            )));
+        }
+        _ => {
+            log::warn!(
+                "  {}: {} Not recognized",
+                name,
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+            );
+            return Ok(());
+        }
+    };
+    log_analysis_lines(&format_analysis(&name, &info, "", records.display_language));
+
+    records.push(AnalysisRecord::identified(
+        dir.to_path_buf(),
+        platform,
+        info,
+    ));
+    records.finish();
+    Ok(())
+}
+
+/// Where `--format json|ndjson` records go. NDJSON lines are printed as
+/// each file is analyzed; a JSON array is printed by [`finish`](Self::finish).
+/// Text output names regions in `display_language`.
+struct RecordSink {
+    format: OutputFormat,
+    display_language: Language,
+    records: Vec<AnalysisRecord>,
+}
+
+impl RecordSink {
+    fn new(format: OutputFormat, display_language: Language) -> Self {
+        Self {
+            format,
+            display_language,
+            records: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, record: AnalysisRecord) {
+        match self.format {
+            OutputFormat::Text => {}
+            OutputFormat::Json => self.records.push(record),
+            OutputFormat::Ndjson => println!("{}", record.to_json_line()),
+        }
+    }
+
+    fn finish(self) {
+        if self.format == OutputFormat::Json {
+            println!("{}", render_records(self.format, &self.records));
+        }
+    }
+}
+
 /// Analyze all ROM files in a folder.
+#[allow(clippy::too_many_arguments)]
 fn analyze_folder(
     folder: &Path,
     analyzer: &dyn RomAnalyzer,
     options: &AnalysisOptions,
+    scan_options: &ScanOptions,
     limit: Option<usize>,
+    sidecar: Option<SidecarFormat>,
+    chd_check: Option<&ChdCheck>,
+    summary: &mut RunSummary,
+    records: &mut RecordSink,
 ) {
     use retro_junk_lib::scanner::{self, GameEntry};
 
     let extensions = scanner::extension_set(analyzer.file_extensions());
 
-    let mut game_entries = match scanner::scan_game_entries(folder, &extensions) {
+    let mut game_entries = match scanner::scan_game_entries_with(folder, &extensions, scan_options)
+    {
         Ok(entries) => entries,
         Err(e) => {
             log::warn!(
@@ -103,7 +320,18 @@ fn analyze_folder(
         match entry {
             GameEntry::SingleFile(path) => {
                 any_output = true;
-                analyze_and_print(path, analyzer, options, "");
+                summary.count("files analyzed", 1);
+                let problems = analyze_and_print(
+                    path,
+                    analyzer,
+                    options,
+                    &extensions,
+                    "",
+                    sidecar,
+                    chd_check,
+                    records,
+                );
+                summary.problems.extend(problems);
             }
             GameEntry::MultiDisc { name, files } => {
                 any_output = true;
@@ -112,12 +340,71 @@ fn analyze_folder(
                     format!("{}:", name).if_supports_color(Stdout, |t| t.bold()),
                 );
                 for path in files {
-                    analyze_and_print(path, analyzer, options, "  ");
+                    summary.count("files analyzed", 1);
+                    let problems = analyze_and_print(
+                        path,
+                        analyzer,
+                        options,
+                        &extensions,
+                        "  ",
+                        sidecar,
+                        chd_check,
+                        records,
+                    );
+                    summary.problems.extend(problems);
                 }
             }
         }
     }
 
+    // Compilation archives are listed entry by entry
+    let archives = archive::find_archives(folder).unwrap_or_default();
+    for path in archives
+        .iter()
+        .filter(|p| archive::is_multi_game(p, &extensions))
+    {
+        any_output = true;
+        summary
+            .problems
+            .extend(analyze_archive(path, analyzer, options, records));
+    }
+
+    // Extracted games (PS3 JB folders) are read from the files inside them
+    for dir in scanner::find_game_dirs(folder, analyzer, scan_options) {
+        any_output = true;
+        summary.count("folders analyzed", 1);
+        summary
+            .problems
+            .extend(analyze_game_dir(&dir, analyzer, options, records));
+    }
+
+    // Music rips are scanned apart from the ROMs and read by analyze_music
+    let music_extensions = scanner::extension_set(analyzer.music_extensions());
+    if !music_extensions.is_empty() {
+        let rip_options = scan_options.clone().archives(false);
+        let rips = scanner::scan_game_entries_with(folder, &music_extensions, &rip_options)
+            .unwrap_or_default();
+        for path in rips
+            .iter()
+            .flat_map(GameEntry::all_files)
+            .take(limit.unwrap_or(usize::MAX))
+        {
+            any_output = true;
+            summary.count("music rips analyzed", 1);
+            let problems = analyze_and_print(
+                path,
+                analyzer,
+                options,
+                &music_extensions,
+                "",
+                sidecar,
+                chd_check,
+                records,
+            );
+            summary.problems.extend(problems);
+        }
+    }
+
     if !any_output {
         log::info!(
             "  {}",
@@ -127,22 +414,27 @@ fn analyze_folder(
     crate::log_blank();
 }
 
-/// Analyze a single file and print its results.
+/// Analyze a single file and print its results. A single-ROM archive is
+/// analyzed through to the ROM inside.
+///
+/// Returns the problems found (analysis failures and bad-dump warnings) for
+/// the run's notification.
+#[allow(clippy::too_many_arguments)]
 fn analyze_and_print(
-    path: &PathBuf,
+    path: &Path,
     analyzer: &dyn RomAnalyzer,
     options: &AnalysisOptions,
+    extensions: &HashSet<String>,
     indent: &str,
-) {
+    sidecar: Option<SidecarFormat>,
+    chd_check: Option<&ChdCheck>,
+    records: &mut RecordSink,
+) -> Vec<String> {
     let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
+    let mut problems = Vec::new();
 
-    let file_options = AnalysisOptions {
-        file_path: Some(path.clone()),
-        ..options.clone()
-    };
-
-    let mut file = match fs::File::open(path) {
-        Ok(f) => f,
+    let opened = match archive::open_rom(path, extensions) {
+        Ok(o) => o,
         Err(e) => {
             log::warn!(
                 "  {}{} Error opening {}: {}",
@@ -151,36 +443,443 @@ fn analyze_and_print(
                 file_name,
                 e,
             );
-            return;
+            records.push(AnalysisRecord::failed(
+                path.to_path_buf(),
+                analyzer.platform(),
+                e.to_string(),
+            ));
+            return vec![format!("{}: {}", path.display(), e)];
         }
     };
+    let display_name = match &opened.entry {
+        Some(entry) => format!("{} ({})", file_name, entry.file_name()),
+        None => file_name.to_string(),
+    };
+    let file_options = AnalysisOptions {
+        file_path: Some(opened.analysis_path.clone()),
+        ..options.clone()
+    };
+    let entry_name = opened.entry.as_ref().map(|e| e.name.clone());
+    let mut file = opened.reader;
 
-    match analyzer.analyze(&mut file, &file_options) {
+    let is_music_rip = opened
+        .analysis_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| {
+            analyzer
+                .music_extensions()
+                .iter()
+                .any(|e| e.eq_ignore_ascii_case(ext))
+        });
+    let mut limited = LimitedReader::new(file.as_mut(), &file_options.limits);
+    let result = if is_music_rip {
+        analyzer.analyze_music(&mut limited, &file_options)
+    } else {
+        analyzer.analyze(&mut limited, &file_options)
+    };
+    match result {
         Ok(info) => {
-            let lines = format_analysis(file_name, &info, indent);
-            let has_warnings = lines.iter().any(|(level, _)| *level <= Level::Warn);
-            for (i, (level, msg)) in lines.iter().enumerate() {
-                // Promote header to warn if this file has warnings (visible in quiet mode)
-                let effective_level = if i == 0 && has_warnings {
-                    Level::Warn
-                } else {
-                    *level
-                };
-                log::log!(effective_level, "{}", msg);
+            let mut lines = format_analysis(&display_name, &info, indent, records.display_language);
+            let mut record =
+                AnalysisRecord::identified(path.to_path_buf(), analyzer.platform(), info.clone());
+            // Bad-dump heuristics read the whole file, so skip them in quick
+            // mode; they don't apply to music rips
+            if !options.quick && !is_music_rip {
+                for warning in dump_warnings(&opened.analysis_path, file.as_mut(), analyzer, &info)
+                {
+                    problems.push(format!("{}: {}", path.display(), warning));
+                    record.warnings.push(warning.to_string());
+                    lines.push((
+                        Level::Warn,
+                        format!(
+                            "    {}{} {}",
+                            indent,
+                            "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                            warning,
+                        ),
+                    ));
+                }
+            }
+            if let Some(check) = chd_check
+                && path
+                    .extension()
+                    .is_some_and(|e| e.eq_ignore_ascii_case("chd"))
+            {
+                lines.extend(chd_lines(&mut file, check, indent));
+            }
+            log_analysis_lines(&lines);
+            record.entry = entry_name;
+            records.push(record);
+            if options.include_raw_header && info.raw_header.is_none() {
+                log::info!(
+                    "    {}{}",
+                    indent,
+                    "Raw header not available for this format"
+                        .if_supports_color(Stdout, |t| t.dimmed()),
+                );
+            }
+            if let Some(format) = sidecar
+                && let Err(e) =
+                    write_sidecar(path, file.as_mut(), analyzer, info, options.quick, format)
+            {
+                log::warn!(
+                    "  {}{} Failed to write sidecar: {}",
+                    indent,
+                    "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                    e,
+                );
             }
         }
         Err(e) => {
             log::warn!(
-                "  {}{}: {} Analysis failed ({})",
+                "  {}{}: {} {} ({})",
                 indent,
-                file_name,
+                display_name,
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                failure_label(&e),
+                e,
+            );
+            problems.push(format!("{}: {} ({})", path.display(), failure_label(&e), e));
+            let mut record = AnalysisRecord::failed(
+                path.to_path_buf(),
+                analyzer.platform(),
+                format!("{} ({})", failure_label(&e), e),
+            );
+            record.entry = entry_name;
+            records.push(record);
+        }
+    }
+    problems
+}
+
+/// Limits for `--hardened`, or the defaults.
+fn parse_limits(hardened: bool) -> ParseLimits {
+    if hardened {
+        ParseLimits::hardened()
+    } else {
+        ParseLimits::default()
+    }
+}
+
+/// Short description of why analysis failed, from the error's kind.
+fn failure_label(e: &AnalysisError) -> &'static str {
+    match e.kind() {
+        AnalysisErrorKind::NotThisFormat => "Not recognized",
+        AnalysisErrorKind::Corrupt => "Damaged file",
+        AnalysisErrorKind::Unsupported => "Unsupported format",
+        AnalysisErrorKind::Io => "Read error",
+        AnalysisErrorKind::Limit => "Over parse limits",
+        AnalysisErrorKind::Other => "Analysis failed",
+    }
+}
+
+/// CHD verification settings for one console folder.
+struct ChdCheck {
+    mode: ChdVerifyMode,
+    /// DAT index for identifying CHDs by header SHA1, if one could be loaded.
+    index: Option<DatIndex>,
+}
+
+/// Load the console's DATs for CHD identification. Failures are logged and
+/// the CHDs are still checked for corruption.
+fn load_disk_index(analyzer: &dyn RomAnalyzer, dat_dir: Option<&Path>) -> Option<DatIndex> {
+    if !analyzer.has_dat_support() {
+        return None;
+    }
+    match cache::load_dats(
+        analyzer.short_name(),
+        analyzer.dat_names(),
+        analyzer.dat_download_ids(),
+        dat_dir,
+        analyzer.dat_source(),
+    ) {
+        Ok(dats) => Some(DatIndex::from_dats(dats)),
+        Err(e) => {
+            log::warn!(
+                "  {} Couldn't load DATs, CHDs won't be identified: {}",
                 "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
                 e,
             );
+            None
         }
     }
 }
 
+/// Verify a CHD against its header SHA1 and format the result lines.
+fn chd_lines(file: &mut impl ReadSeek, check: &ChdCheck, indent: &str) -> Vec<(Level, String)> {
+    let result = match chd_verify::verify_chd(file, check.mode, check.index.as_ref(), &|_, _| {}) {
+        Ok(r) => r,
+        Err(e) => {
+            return vec![(
+                Level::Warn,
+                format!(
+                    "    {}{} CHD verification failed: {}",
+                    indent,
+                    "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                    e,
+                ),
+            )];
+        }
+    };
+
+    let mut lines = vec![(
+        Level::Info,
+        format!(
+            "    {}CHD SHA1:     {} {}",
+            indent,
+            result.header.sha1,
+            format!("(v{})", result.header.version).if_supports_color(Stdout, |t| t.dimmed()),
+        ),
+    )];
+
+    if let Some(ref index) = check.index {
+        let line = match &result.dat_match {
+            Some(m) => format!(
+                "    {}{} DAT match: {}",
+                indent,
+                "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                index.games[m.game_index].name,
+            ),
+            None => format!(
+                "    {}{}",
+                indent,
+                "No DAT disk entry has this SHA1".if_supports_color(Stdout, |t| t.dimmed()),
+            ),
+        };
+        lines.push((Level::Info, line));
+    }
+
+    let fail = |msg: String| {
+        (
+            Level::Warn,
+            format!(
+                "    {}{} {}",
+                indent,
+                "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                msg,
+            ),
+        )
+    };
+    if result.metadata_consistent == Some(false) {
+        lines.push(fail("Header SHA1 doesn't match the image metadata".into()));
+    }
+    if let Some(hunk) = result.bad_hunk {
+        let msg = if result.header.has_parent {
+            format!(
+                "Hunk {} couldn't be read (parent CHDs aren't supported)",
+                hunk
+            )
+        } else {
+            format!("Corrupt: hunk {} failed to decompress", hunk)
+        };
+        lines.push(fail(msg));
+    } else if let Some(ref derived) = result.derived_raw_sha1 {
+        if derived != result.header.expected_raw_sha1() {
+            lines.push(fail(format!(
+                "Corrupt: data hashes to {}, header expects {}",
+                derived,
+                result.header.expected_raw_sha1(),
+            )));
+        } else {
+            lines.push((
+                Level::Info,
+                format!(
+                    "    {}{} Data intact",
+                    indent,
+                    "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                ),
+            ));
+        }
+    }
+    lines
+}
+
+/// Print lines from [`format_analysis`], promoting the header line to warn
+/// if any line is a warning (so it stays visible in quiet mode).
+fn log_analysis_lines(lines: &[(Level, String)]) {
+    let has_warnings = lines.iter().any(|(level, _)| *level <= Level::Warn);
+    for (i, (level, msg)) in lines.iter().enumerate() {
+        let effective_level = if i == 0 && has_warnings {
+            Level::Warn
+        } else {
+            *level
+        };
+        log::log!(effective_level, "{}", msg);
+    }
+}
+
+/// Analyze each ROM inside a multi-game archive and print them under the
+/// archive's name. Returns the entries that failed to analyze.
+fn analyze_archive(
+    path: &Path,
+    analyzer: &dyn RomAnalyzer,
+    options: &AnalysisOptions,
+    records: &mut RecordSink,
+) -> Vec<String> {
+    let archive_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
+    let results = match archive::analyze_entries(path, analyzer, options) {
+        Ok(r) => r,
+        Err(e) => {
+            log::warn!(
+                "  {}: {} Error reading archive ({})",
+                archive_name,
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                e,
+            );
+            records.push(AnalysisRecord::failed(
+                path.to_path_buf(),
+                analyzer.platform(),
+                e.to_string(),
+            ));
+            return vec![format!("{}: {}", path.display(), e)];
+        }
+    };
+
+    log::info!(
+        "  {} {}",
+        format!("{}:", archive_name).if_supports_color(Stdout, |t| t.bold()),
+        format!("({} ROMs)", results.len()).if_supports_color(Stdout, |t| t.dimmed()),
+    );
+    let mut problems = Vec::new();
+    for analysis in &results {
+        match &analysis.result {
+            Ok(info) => {
+                log_analysis_lines(&format_analysis(
+                    analysis.entry.file_name(),
+                    info,
+                    "  ",
+                    records.display_language,
+                ));
+                records.push(
+                    AnalysisRecord::identified(
+                        path.to_path_buf(),
+                        analyzer.platform(),
+                        info.clone(),
+                    )
+                    .with_entry(&analysis.entry.name),
+                );
+            }
+            Err(e) => {
+                log::warn!(
+                    "    {}: {} {} ({})",
+                    analysis.entry.file_name(),
+                    "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                    failure_label(e),
+                    e,
+                );
+                problems.push(format!(
+                    "{}: {} ({})",
+                    path.join(&analysis.entry.name).display(),
+                    failure_label(e),
+                    e
+                ));
+                records.push(
+                    AnalysisRecord::failed(
+                        path.to_path_buf(),
+                        analyzer.platform(),
+                        format!("{} ({})", failure_label(e), e),
+                    )
+                    .with_entry(&analysis.entry.name),
+                );
+            }
+        }
+    }
+    problems
+}
+
+/// Analyze an extracted game folder and print its results. Returns the
+/// problem if it failed to analyze.
+fn analyze_game_dir(
+    dir: &Path,
+    analyzer: &dyn RomAnalyzer,
+    options: &AnalysisOptions,
+    records: &mut RecordSink,
+) -> Option<String> {
+    let dir_name = dir.file_name().and_then(|n| n.to_str()).unwrap_or("?");
+    let dir_options = AnalysisOptions {
+        file_path: Some(dir.to_path_buf()),
+        ..options.clone()
+    };
+    match analyzer.analyze_dir(dir, &dir_options) {
+        Ok(info) => {
+            log_analysis_lines(&format_analysis(
+                &format!("{}/", dir_name),
+                &info,
+                "",
+                records.display_language,
+            ));
+            records.push(AnalysisRecord::identified(
+                dir.to_path_buf(),
+                analyzer.platform(),
+                info,
+            ));
+            None
+        }
+        Err(e) => {
+            log::warn!(
+                "  {}/: {} {} ({})",
+                dir_name,
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                failure_label(&e),
+                e,
+            );
+            records.push(AnalysisRecord::failed(
+                dir.to_path_buf(),
+                analyzer.platform(),
+                format!("{} ({})", failure_label(&e), e),
+            ));
+            Some(format!("{}: {} ({})", dir.display(), failure_label(&e), e))
+        }
+    }
+}
+
+/// Run the bad-dump heuristics on an analyzed file. Failures are logged at
+/// debug level and yield no warnings.
+fn dump_warnings(
+    path: &Path,
+    file: &mut dyn ReadSeek,
+    analyzer: &dyn RomAnalyzer,
+    info: &RomIdentification,
+) -> Vec<DumpWarning> {
+    let sector_size = if analyzer.dat_source() == DatSource::Redump {
+        dump_check::disc_sector_size(path)
+    } else {
+        None
+    };
+    dump_check::check_dump(file, info.expected_size, sector_size).unwrap_or_else(|e| {
+        log::debug!("Bad-dump check failed for {}: {}", path.display(), e);
+        Vec::new()
+    })
+}
+
+/// Record the identification (and hashes, unless in quick mode) in the
+/// ROM's sidecar file.
+fn write_sidecar(
+    path: &Path,
+    file: &mut dyn ReadSeek,
+    analyzer: &dyn RomAnalyzer,
+    info: RomIdentification,
+    quick: bool,
+    format: SidecarFormat,
+) -> Result<(), String> {
+    let hashes = if quick {
+        None
+    } else {
+        file.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+        let hashes = retro_junk_lib::hasher::compute_crc32_sha1(file, analyzer, Some(path))
+            .map_err(|e| e.to_string())?;
+        Some(SidecarHashes::from(&hashes))
+    };
+    sidecar::update_sidecar(path, format, |s| {
+        s.identification = Some(info);
+        if hashes.is_some() {
+            s.hashes = hashes;
+        }
+    })
+    .map_err(|e| e.to_string())
+}
+
 /// Format a byte size as a human-readable string.
 pub(crate) use retro_junk_lib::util::format_bytes;
 
@@ -222,6 +921,7 @@ fn format_analysis(
     file_name: &str,
     info: &RomIdentification,
     indent: &str,
+    display_language: Language,
 ) -> Vec<(Level, String)> {
     let mut lines: Vec<(Level, String)> = Vec::new();
     let mut shown_keys: HashSet<&str> = HashSet::new();
@@ -259,6 +959,24 @@ fn format_analysis(
             ),
         ));
     }
+    if !info.titles.is_empty() {
+        lines.push((
+            Level::Info,
+            format!(
+                "    {}{}",
+                indent,
+                "Titles:".if_supports_color(Stdout, |t| t.cyan()),
+            ),
+        ));
+        let mut titles: Vec<_> = info.titles.iter().collect();
+        titles.sort();
+        for (language, title) in titles {
+            lines.push((
+                Level::Info,
+                format!("      {}{}  {}", indent, language.code(), title),
+            ));
+        }
+    }
     if let Some(ref maker) = info.maker_code {
         lines.push((
             Level::Info,
@@ -281,6 +999,17 @@ fn format_analysis(
             ),
         ));
     }
+    if let Some(date) = info.build_date {
+        lines.push((
+            Level::Info,
+            format!(
+                "    {}{}    {}",
+                indent,
+                "Built:".if_supports_color(Stdout, |t| t.cyan()),
+                date,
+            ),
+        ));
+    }
 
     // (b) Format line (composed as single string)
     if let Some(format_val) = info.extra.get("format") {
@@ -410,7 +1139,11 @@ fn format_analysis(
 
     // (f) Region
     if !info.regions.is_empty() {
-        let region_str: Vec<_> = info.regions.iter().map(|r| r.name()).collect();
+        let region_str: Vec<_> = info
+            .regions
+            .iter()
+            .map(|r| r.localized_name(display_language))
+            .collect();
         lines.push((
             Level::Info,
             format!(
@@ -453,5 +1186,42 @@ fn format_analysis(
         }
     }
 
+    // (e) Raw header dump (--dump-header)
+    if let Some(ref raw) = info.raw_header {
+        lines.push((
+            Level::Info,
+            format!(
+                "    {}{}",
+                indent,
+                format!("Raw header (0x{:X}):", raw.offset)
+                    .if_supports_color(Stdout, |t| t.bright_magenta()),
+            ),
+        ));
+        for (i, row) in raw.bytes().chunks(16).enumerate() {
+            let hex: Vec<String> = row.iter().map(|b| format!("{:02x}", b)).collect();
+            let ascii: String = row
+                .iter()
+                .map(|&b| {
+                    if (0x20..0x7F).contains(&b) {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            lines.push((
+                Level::Info,
+                format!(
+                    "      {}{} {:<47}  {}",
+                    indent,
+                    format!("{:08X}", raw.offset + i as u64 * 16)
+                        .if_supports_color(Stdout, |t| t.dimmed()),
+                    hex.join(" "),
+                    ascii,
+                ),
+            ));
+        }
+    }
+
     lines
 }
@@ -0,0 +1,138 @@
+use std::path::PathBuf;
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_lib::size_audit::{self, SizeAudit};
+use retro_junk_lib::{AnalysisContext, Platform};
+
+use crate::CliError;
+
+/// Run the audit-sizes command.
+pub(crate) fn run_audit_sizes(
+    ctx: &AnalysisContext,
+    consoles: Option<Vec<Platform>>,
+    limit: Option<usize>,
+    library_path: PathBuf,
+    list: bool,
+) -> Result<(), CliError> {
+    let root_path = library_path;
+
+    log::info!("Auditing ROM sizes in: {}", root_path.display());
+    if let Some(n) = limit {
+        log::info!("Limit: {} games per console", n);
+    }
+    crate::log_blank();
+
+    let scan = match crate::scan_folders(ctx, &root_path, &consoles) {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    let mut rows: Vec<(String, SizeAudit)> = Vec::new();
+    for cf in &scan.matches {
+        let console = ctx.get_by_platform(cf.platform).ok_or_else(|| {
+            CliError::unknown_system(format!("No analyzer for platform {:?}", cf.platform))
+        })?;
+        match size_audit::audit_folder(&cf.path, console.analyzer.as_ref(), limit) {
+            Ok(audit) if audit.total() > 0 => rows.push((
+                format!("{} ({})", console.metadata.short_name, cf.folder_name),
+                audit,
+            )),
+            Ok(_) => {}
+            Err(e) => log::warn!(
+                "  {} Error reading {}: {}",
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                cf.folder_name,
+                e,
+            ),
+        }
+    }
+
+    if rows.is_empty() {
+        log::info!("No ROM files found.");
+        return Ok(());
+    }
+
+    // Worst consoles first, so repairs can be prioritized
+    rows.sort_by_key(|(_, a)| std::cmp::Reverse(a.problems.len()));
+
+    log::info!(
+        "  {:<28} {:>7} {:>7} {:>8} {:>10} {:>7} {:>10} {:>8} {:>7}",
+        "Console".if_supports_color(Stdout, |t| t.dimmed()),
+        "Files".if_supports_color(Stdout, |t| t.dimmed()),
+        "OK".if_supports_color(Stdout, |t| t.dimmed()),
+        "Trimmed".if_supports_color(Stdout, |t| t.dimmed()),
+        "Truncated".if_supports_color(Stdout, |t| t.dimmed()),
+        "Copier".if_supports_color(Stdout, |t| t.dimmed()),
+        "Oversized".if_supports_color(Stdout, |t| t.dimmed()),
+        "Unknown".if_supports_color(Stdout, |t| t.dimmed()),
+        "Failed".if_supports_color(Stdout, |t| t.dimmed()),
+    );
+    let mut totals = SizeAudit::default();
+    for (label, audit) in &rows {
+        print_row(label, audit);
+        totals.merge(audit.clone());
+    }
+    print_row("Total", &totals);
+    crate::log_blank();
+
+    if list {
+        for (label, audit) in &rows {
+            if audit.problems.is_empty() {
+                continue;
+            }
+            log::info!("{}", label.if_supports_color(Stdout, |t| t.bold()));
+            for problem in &audit.problems {
+                let name = problem
+                    .path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("?");
+                log::info!("  {}: {}", name, problem.verdict.description());
+            }
+            crate::log_blank();
+        }
+    }
+
+    let problems = totals.problems.len();
+    if problems == 0 {
+        log::info!(
+            "{} All {} files match their header sizes.",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            totals.ok,
+        );
+    } else {
+        log::info!(
+            "{} {} of {} files don't match their header sizes.{}",
+            "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+            problems,
+            totals.total(),
+            if list { "" } else { " Use --list to see them." },
+        );
+    }
+
+    Ok(())
+}
+
+fn print_row(label: &str, audit: &SizeAudit) {
+    let count = |n: usize| {
+        if n == 0 {
+            "-".to_string()
+        } else {
+            n.to_string()
+        }
+    };
+    log::info!(
+        "  {:<28} {:>7} {:>7} {:>8} {:>10} {:>7} {:>10} {:>8} {:>7}",
+        label,
+        audit.total(),
+        count(audit.ok),
+        count(audit.trimmed),
+        count(audit.truncated),
+        count(audit.copier_header),
+        count(audit.oversized),
+        count(audit.unknown),
+        count(audit.failed),
+    );
+}
@@ -0,0 +1,169 @@
+use std::path::PathBuf;
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+use retro_junk_lib::Platform;
+
+use crate::CliError;
+
+use super::{default_catalog_asset_dir, default_catalog_db_path, open_existing_catalog};
+
+/// Verify catalog asset files against their recorded hashes.
+pub(crate) fn run_catalog_assets_verify(
+    system: Option<String>,
+    db_path: Option<PathBuf>,
+    quiet: bool,
+) -> Result<(), CliError> {
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+
+    if !db_path.exists() {
+        log::warn!("No catalog database found at {}", db_path.display());
+        log::info!("Run 'retro-junk catalog import all' first.");
+        return Ok(());
+    }
+
+    let platform: Option<Platform> = system
+        .as_deref()
+        .map(|s| {
+            s.parse().map_err(|_| {
+                CliError::unknown_system(format!(
+                    "Unknown system '{}'. Use a short name like 'nes', 'snes', 'n64'.",
+                    s
+                ))
+            })
+        })
+        .transpose()?;
+
+    let conn = retro_junk_db::open_database(&db_path)
+        .map_err(|e| CliError::database(format!("Failed to open catalog database: {}", e)))?;
+
+    log::info!(
+        "{}",
+        "Verifying media assets".if_supports_color(Stdout, |t| t.bold()),
+    );
+
+    let result = retro_junk_import::verify_assets(&conn, platform.map(|p| p.short_name()))
+        .map_err(|e| CliError::database(format!("Asset verification failed: {}", e)))?;
+
+    if !quiet {
+        for problem in &result.problems {
+            let label = if problem.missing {
+                "missing"
+            } else {
+                "changed"
+            };
+            log::info!(
+                "  {} {} {} ({})",
+                "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                problem.release_id.as_deref().unwrap_or("-"),
+                problem.asset_type,
+                format!("{}: {}", label, problem.path.display())
+                    .if_supports_color(Stdout, |t| t.dimmed()),
+            );
+        }
+    }
+
+    let stats = &result.stats;
+    crate::log_blank();
+    log::info!(
+        "{}",
+        "Verification complete".if_supports_color(Stdout, |t| t.bold()),
+    );
+    log::info!("  Checked:        {:>6}", stats.checked);
+    log::info!("  Verified:       {:>6}", stats.verified);
+    log::info!("  Hash recorded:  {:>6}", stats.hashed);
+    log::info!("  Missing:        {:>6}", stats.missing);
+    log::info!("  Changed:        {:>6}", stats.mismatched);
+    if stats.errors > 0 {
+        log::info!("  Errors:         {:>6}", stats.errors);
+    }
+
+    Ok(())
+}
+
+/// Reconcile the asset directory with the catalog's asset rows.
+pub(crate) fn run_catalog_assets_prune(
+    db_path: Option<PathBuf>,
+    asset_dir: Option<PathBuf>,
+    delete: bool,
+    redownload: bool,
+    quiet: bool,
+) -> Result<(), CliError> {
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+    let asset_dir = asset_dir.unwrap_or_else(default_catalog_asset_dir);
+
+    let mut plan = retro_junk_import::plan_asset_prune(&conn, &asset_dir)
+        .map_err(|e| CliError::database(format!("Failed to list assets: {}", e)))?;
+
+    if plan.is_empty() {
+        log::info!(
+            "{} Asset directory and catalog agree ({})",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            asset_dir.display(),
+        );
+        return Ok(());
+    }
+
+    if !quiet {
+        for path in &plan.orphaned_files {
+            log::info!(
+                "  {} {} {}",
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                path.display(),
+                "(not in catalog)".if_supports_color(Stdout, |t| t.dimmed()),
+            );
+        }
+        for asset in &plan.missing {
+            log::info!(
+                "  {} {} {} {}",
+                "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                asset.release_id.as_deref().unwrap_or("-"),
+                asset.asset_type,
+                format!("(missing: {})", asset.file_path.as_deref().unwrap_or(""))
+                    .if_supports_color(Stdout, |t| t.dimmed()),
+            );
+        }
+        crate::log_blank();
+    }
+    log::info!("  Orphaned files:  {:>6}", plan.orphaned_files.len());
+    log::info!("  Missing files:   {:>6}", plan.missing.len());
+
+    if redownload && !plan.missing.is_empty() {
+        let total = plan.missing.len();
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| CliError::runtime(format!("Failed to create tokio runtime: {}", e)))?;
+        plan.missing = rt.block_on(async {
+            let (client, _) = crate::commands::scrape::connect_screenscraper(None, quiet).await?;
+            retro_junk_import::redownload_assets(&client, &conn, &plan.missing)
+                .await
+                .map_err(|e| CliError::database(format!("Failed to update assets: {}", e)))
+        })?;
+        log::info!("  Re-downloaded:   {:>6}", total - plan.missing.len(),);
+    }
+
+    if delete {
+        let stats = retro_junk_import::apply_asset_prune(&conn, &plan)
+            .map_err(|e| CliError::database(format!("Failed to prune assets: {}", e)))?;
+        log::info!("  Files deleted:   {:>6}", stats.files_deleted);
+        log::info!("  Rows removed:    {:>6}", stats.rows_deleted);
+        if stats.errors > 0 {
+            log::info!("  Errors:          {:>6}", stats.errors);
+        }
+    } else if !plan.is_empty() {
+        crate::log_blank();
+        log::info!(
+            "Use {} to remove them{}.",
+            "--delete".if_supports_color(Stdout, |t| t.bold()),
+            if redownload || plan.missing.is_empty() {
+                ""
+            } else {
+                ", or --redownload to restore missing files"
+            },
+        );
+    }
+
+    Ok(())
+}
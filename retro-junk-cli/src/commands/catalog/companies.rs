@@ -0,0 +1,217 @@
+use std::path::PathBuf;
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use crate::CliError;
+
+use super::{default_catalog_db_path, open_existing_catalog};
+
+/// List company names staged during enrichment.
+pub(crate) fn run_catalog_companies_review(
+    db_path: Option<PathBuf>,
+    limit: u32,
+) -> Result<(), CliError> {
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+
+    let total = retro_junk_db::count_pending_company_aliases(&conn)
+        .map_err(|e| CliError::database(format!("Failed to count staged names: {}", e)))?;
+    if total == 0 {
+        log::info!("No company names awaiting review.");
+        return Ok(());
+    }
+
+    let pending = retro_junk_db::list_pending_company_aliases(&conn, limit)
+        .map_err(|e| CliError::database(format!("Failed to list staged names: {}", e)))?;
+
+    log::info!(
+        "{}",
+        format!("{} company name(s) awaiting review:", total)
+            .if_supports_color(Stdout, |t| t.bold()),
+    );
+    crate::log_blank();
+
+    for p in &pending {
+        log::info!(
+            "  #{} {} [{}] seen {}x, {} release(s) waiting",
+            format!("{}", p.id).if_supports_color(Stdout, |t| t.bold()),
+            p.alias.if_supports_color(Stdout, |t| t.cyan()),
+            p.source,
+            p.occurrences,
+            p.release_count,
+        );
+        match &p.suggested_company_id {
+            Some(company_id) => {
+                let name = retro_junk_db::get_company_name(&conn, company_id)
+                    .ok()
+                    .flatten()
+                    .unwrap_or_else(|| company_id.clone());
+                log::info!(
+                    "    {} looks like {} ({})",
+                    "\u{25B6}".if_supports_color(Stdout, |t| t.green()),
+                    name,
+                    company_id.if_supports_color(Stdout, |t| t.dimmed()),
+                );
+            }
+            None => {
+                log::info!(
+                    "    {} no similar company; approving creates a new one",
+                    "\u{25B6}".if_supports_color(Stdout, |t| t.yellow()),
+                );
+            }
+        }
+    }
+
+    if (pending.len() as i64) < total {
+        crate::log_blank();
+        log::info!(
+            "  ... and {} more (use --limit to show more)",
+            total - pending.len() as i64,
+        );
+    }
+
+    crate::log_blank();
+    log::info!(
+        "Review with: retro-junk catalog companies approve <ids> | merge <id> <company> | reject <ids>"
+    );
+
+    Ok(())
+}
+
+/// Approve staged names as aliases of their suggested company, or as new companies.
+pub(crate) fn run_catalog_companies_approve(
+    ids: Vec<i64>,
+    db_path: Option<PathBuf>,
+    all_suggested: bool,
+) -> Result<(), CliError> {
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+
+    let ids = if all_suggested {
+        retro_junk_db::list_pending_company_aliases(&conn, u32::MAX)
+            .map_err(|e| CliError::database(format!("Failed to list staged names: {}", e)))?
+            .into_iter()
+            .filter(|p| p.suggested_company_id.is_some())
+            .map(|p| p.id)
+            .collect()
+    } else {
+        ids
+    };
+
+    if ids.is_empty() {
+        log::info!("No staged names with a suggested company.");
+        return Ok(());
+    }
+
+    for id in ids {
+        approve_one(&conn, id, None);
+    }
+
+    Ok(())
+}
+
+/// Merge a staged name into an existing company.
+pub(crate) fn run_catalog_companies_merge(
+    id: i64,
+    company: String,
+    db_path: Option<PathBuf>,
+) -> Result<(), CliError> {
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+
+    approve_one(&conn, id, Some(&company));
+    Ok(())
+}
+
+/// Reject staged names so enrichment ignores them.
+pub(crate) fn run_catalog_companies_reject(
+    ids: Vec<i64>,
+    db_path: Option<PathBuf>,
+) -> Result<(), CliError> {
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+
+    for id in ids {
+        let Some(pending) = pending_for_review(&conn, id) else {
+            continue;
+        };
+        match retro_junk_db::reject_pending_company_alias(&conn, id) {
+            Ok(()) => log::info!(
+                "  {} #{} {} rejected",
+                "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                id,
+                pending.alias,
+            ),
+            Err(e) => log::warn!(
+                "  {} #{}: {}",
+                "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                id,
+                e,
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// Approve one staged name, reporting the outcome. Failures are logged so a
+/// bad ID doesn't stop the rest of a batch.
+fn approve_one(conn: &retro_junk_db::Connection, id: i64, company_id: Option<&str>) {
+    let Some(pending) = pending_for_review(conn, id) else {
+        return;
+    };
+    match retro_junk_import::approve_company_alias(conn, id, company_id) {
+        Ok(approved) => log::info!(
+            "  {} #{} {} {} {}{}, {} release(s) linked",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            id,
+            pending.alias,
+            "\u{2192}".if_supports_color(Stdout, |t| t.dimmed()),
+            approved.company_id.if_supports_color(Stdout, |t| t.bold()),
+            if approved.company_created {
+                " (new company)"
+            } else {
+                ""
+            },
+            approved.releases_linked,
+        ),
+        Err(e) => log::warn!(
+            "  {} #{} {}: {}",
+            "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+            id,
+            pending.alias,
+            e,
+        ),
+    }
+}
+
+/// Fetch a staged name, warning if it doesn't exist or was already reviewed.
+fn pending_for_review(
+    conn: &retro_junk_db::Connection,
+    id: i64,
+) -> Option<retro_junk_db::PendingCompanyAlias> {
+    match retro_junk_db::get_pending_company_alias(conn, id) {
+        Ok(Some(p)) if p.status == "pending" => Some(p),
+        Ok(Some(p)) => {
+            log::warn!("  #{} {} was already {}.", id, p.alias, p.status);
+            None
+        }
+        Ok(None) => {
+            log::warn!("  #{} not found.", id);
+            None
+        }
+        Err(e) => {
+            log::warn!("  #{}: {}", id, e);
+            None
+        }
+    }
+}
@@ -1,11 +1,12 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use owo_colors::OwoColorize;
 use owo_colors::Stream::Stdout;
+use retro_junk_catalog::types::ResolutionRule;
 
 use crate::CliError;
 
-use super::default_catalog_db_path;
+use super::{default_catalog_db_path, default_catalog_dir, open_existing_catalog};
 
 /// List unresolved disagreements between data sources.
 pub(crate) fn run_catalog_disagreements(
@@ -159,3 +160,84 @@ pub(crate) fn run_catalog_resolve(
 
     Ok(())
 }
+
+/// Resolve disagreements using the rules in `catalog/resolutions/`.
+pub(crate) fn run_catalog_auto_resolve(
+    db_path: Option<PathBuf>,
+    catalog_dir: Option<PathBuf>,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+
+    let rules_dir = catalog_dir
+        .unwrap_or_else(default_catalog_dir)
+        .join("resolutions");
+    let rules = retro_junk_catalog::yaml::load_resolution_rules(&rules_dir)
+        .map_err(|e| CliError::config(format!("Failed to load resolution rules: {}", e)))?;
+    if rules.is_empty() {
+        log::warn!("No resolution rules found in {}", rules_dir.display());
+        return Ok(());
+    }
+
+    run_auto_resolve_on_conn(&conn, &rules, dry_run)
+}
+
+/// Run the resolution rules in `catalog_dir` after an import or enrichment.
+///
+/// Problems are reported as warnings so they never fail the surrounding command.
+pub(crate) fn auto_resolve_after_update(conn: &retro_junk_db::Connection, catalog_dir: &Path) {
+    let rules_dir = catalog_dir.join("resolutions");
+    match retro_junk_catalog::yaml::load_resolution_rules(&rules_dir) {
+        Ok(rules) if !rules.is_empty() => {
+            if let Err(e) = run_auto_resolve_on_conn(conn, &rules, false) {
+                log::warn!("Auto-resolution failed: {}", e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Failed to load resolution rules: {}", e),
+    }
+}
+
+/// Shared auto-resolution logic, usable from both standalone command and post-import.
+fn run_auto_resolve_on_conn(
+    conn: &retro_junk_db::Connection,
+    rules: &[ResolutionRule],
+    dry_run: bool,
+) -> Result<(), CliError> {
+    log::info!(
+        "\n{}",
+        "Auto-resolving disagreements...".if_supports_color(Stdout, |t| t.bold()),
+    );
+
+    let stats = retro_junk_import::auto_resolve_disagreements(conn, rules, dry_run)
+        .map_err(|e| CliError::database(format!("Auto-resolution failed: {}", e)))?;
+
+    if stats.resolved == 0 {
+        log::info!("  No disagreements matched the resolution rules.");
+    } else {
+        let by_field: Vec<String> = stats
+            .by_field
+            .iter()
+            .map(|(field, count)| format!("{}: {}", field, count))
+            .collect();
+        log::info!(
+            "  {} {} {} disagreement(s) ({})",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            if dry_run { "Would resolve" } else { "Resolved" },
+            stats.resolved,
+            by_field.join(", "),
+        );
+    }
+    if stats.skipped > 0 {
+        log::info!(
+            "  {} {} matched a rule but the field can't be updated; left for manual review",
+            "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+            stats.skipped,
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,327 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_import::dumper_ingest::{
+    DumpOutcome, DumperOptions, DumperState, DumperWatcher, ingest_dump,
+};
+use retro_junk_lib::AnalysisContext;
+use retro_junk_lib::metrics::{self, Metrics};
+use retro_junk_lib::notify::{NotifyTarget, RunSummary};
+
+use crate::CliError;
+
+use super::default_catalog_db_path;
+
+/// Where a daemon for `system` keeps its state file (`extension` "toml")
+/// or log ("log") unless told otherwise.
+pub(crate) fn default_daemon_path(system: &str, extension: &str) -> PathBuf {
+    retro_junk_dat::cache::cache_dir()
+        .unwrap_or_else(|_| PathBuf::from(".cache"))
+        .join("dumper")
+        .join(format!("{}.{}", system.to_lowercase(), extension))
+}
+
+/// Watch a cartridge dumper folder and add verified dumps to the collection.
+///
+/// As a daemon, the watcher saves a [`DumperState`] after every poll and
+/// stops between dumps when asked to (Ctrl-C, SIGTERM, or a Windows console
+/// close), recording a clean shutdown. With `notify`, every poll that
+/// processed dumps sends a summary listing the ones that need another try.
+/// With `metrics_addr`, counters are served for Prometheus at `/metrics`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_catalog_dumper(
+    ctx: &AnalysisContext,
+    system: String,
+    folder: PathBuf,
+    dest: Option<PathBuf>,
+    library_path_override: Option<PathBuf>,
+    db_path: Option<PathBuf>,
+    user_id: String,
+    interval: u64,
+    once: bool,
+    daemon: bool,
+    state_file: Option<PathBuf>,
+    metrics_addr: Option<String>,
+    notify: Option<&NotifyTarget>,
+) -> Result<(), CliError> {
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+
+    if !db_path.exists() {
+        log::warn!("No catalog database found at {}", db_path.display());
+        log::info!("Run 'retro-junk catalog import all' first.");
+        return Ok(());
+    }
+
+    if !folder.exists() {
+        return Err(CliError::other(format!(
+            "Dumper folder not found: {}",
+            folder.display()
+        )));
+    }
+
+    let console = ctx.get_by_short_name(&system).ok_or_else(|| {
+        CliError::unknown_system(format!(
+            "Unknown system '{}'. Use a short name like 'nes', 'snes', 'n64'.",
+            system
+        ))
+    })?;
+
+    let conn = retro_junk_db::open_database(&db_path)
+        .map_err(|e| CliError::database(format!("Failed to open catalog database: {}", e)))?;
+
+    let library_dir = dest.unwrap_or_else(|| {
+        retro_junk_lib::settings::resolve_library_path(library_path_override)
+            .join(console.metadata.short_name)
+    });
+    let options = DumperOptions {
+        library_dir,
+        user_id,
+    };
+
+    log::info!(
+        "{}",
+        format!(
+            "Watching {} for {} dumps",
+            folder.display(),
+            console.metadata.platform_name
+        )
+        .if_supports_color(Stdout, |t| t.bold()),
+    );
+    log::info!(
+        "{}",
+        format!("Verified dumps go to {}", options.library_dir.display())
+            .if_supports_color(Stdout, |t| t.dimmed()),
+    );
+    if !once {
+        log::info!(
+            "{}",
+            "Press Ctrl-C to stop".if_supports_color(Stdout, |t| t.dimmed()),
+        );
+    }
+    crate::log_blank();
+
+    let mut watcher = DumperWatcher::new(&folder, console.analyzer.as_ref());
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let mut daemon_state = None;
+    if daemon {
+        let state_path = state_file.unwrap_or_else(|| default_daemon_path(&system, "toml"));
+        let mut state = DumperState::load(&state_path)?;
+        if state.stopped_at.is_none()
+            && let Some(pid) = state.pid
+        {
+            log::warn!(
+                "{} The previous watcher (pid {}) did not shut down cleanly",
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                pid,
+            );
+        }
+        watcher.mark_handled(&state.handled);
+        state.pid = Some(std::process::id());
+        state.started_at = Some(chrono::Utc::now().to_rfc3339());
+        state.stopped_at = None;
+        state.save(&state_path)?;
+        log::info!(
+            "{}",
+            format!("State saved to {}", state_path.display())
+                .if_supports_color(Stdout, |t| t.dimmed()),
+        );
+
+        let flag = Arc::clone(&stop);
+        ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst)).map_err(|e| {
+            CliError::other(format!("Failed to install the shutdown handler: {}", e))
+        })?;
+        daemon_state = Some((state, state_path));
+    }
+
+    let metrics = Metrics::new();
+    if let Some(addr) = &metrics_addr {
+        let bound = metrics::serve(addr.as_str(), Arc::clone(&metrics))
+            .map_err(|e| CliError::other(format!("Failed to serve metrics on {}: {}", addr, e)))?;
+        log::info!(
+            "{}",
+            format!("Metrics at http://{}/metrics", bound)
+                .if_supports_color(Stdout, |t| t.dimmed()),
+        );
+    }
+
+    // Prime the watcher so files already present are picked up on the next poll.
+    watcher.poll()?;
+
+    loop {
+        if !wait(Duration::from_secs(interval.max(1)), &stop) {
+            break;
+        }
+
+        let ready = match watcher.poll() {
+            Ok(r) => r,
+            Err(e) => {
+                // Dumper drives disappear when unplugged; keep waiting.
+                log::debug!("Cannot read {}: {}", folder.display(), e);
+                Vec::new()
+            }
+        };
+
+        let mut summary = RunSummary::new("catalog dumper");
+        let mut processed = 0;
+        for (i, dump) in ready.iter().enumerate() {
+            if stop.load(Ordering::SeqCst) {
+                // Left for the next run
+                for skipped in &ready[i..] {
+                    watcher.release(skipped);
+                }
+                break;
+            }
+            let name = dump
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let size = std::fs::metadata(dump).map(|m| m.len()).unwrap_or(0);
+            let outcome = ingest_dump(
+                &conn,
+                dump,
+                console.analyzer.as_ref(),
+                console.metadata.platform,
+                &options,
+            );
+            if let Some((state, _)) = &mut daemon_state {
+                state.record(&outcome);
+            }
+            processed += 1;
+            match outcome {
+                Ok(DumpOutcome::Added {
+                    title,
+                    target,
+                    already_owned,
+                }) => {
+                    summary.count("dumps added", 1);
+                    metrics.record_analyzed(size);
+                    metrics.record_added();
+                    log::info!(
+                        "  {} {} -> {}{}",
+                        "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                        name,
+                        title.if_supports_color(Stdout, |t| t.bold()),
+                        if already_owned {
+                            " (already in collection)"
+                        } else {
+                            ""
+                        },
+                    );
+                    log::info!(
+                        "    {}",
+                        target.display().if_supports_color(Stdout, |t| t.dimmed()),
+                    );
+                }
+                Ok(DumpOutcome::Unverified {
+                    crc32,
+                    header_title,
+                    ..
+                }) => {
+                    summary.count("unverified", 1);
+                    metrics.record_analyzed(size);
+                    metrics.record_unmatched();
+                    summary.problem(format!("{} did not verify (CRC32: {})", name, crc32));
+                    log::warn!(
+                        "  {} {} did not verify (CRC32: {}{})",
+                        "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                        name,
+                        crc32,
+                        header_title
+                            .map(|t| format!(", header title: {}", t.trim()))
+                            .unwrap_or_default(),
+                    );
+                    log::info!(
+                        "    {}",
+                        "Clean the cartridge contacts and dump again."
+                            .if_supports_color(Stdout, |t| t.dimmed()),
+                    );
+                }
+                Ok(DumpOutcome::Conflict { title, target }) => {
+                    summary.count("conflicts", 1);
+                    metrics.record_analyzed(size);
+                    metrics.record_failed();
+                    summary.problem(format!(
+                        "{} verified as {}, but a different file is already at {}",
+                        name,
+                        title,
+                        target.display()
+                    ));
+                    log::warn!(
+                        "  {} {} verified as {}, but a different file is already at {}",
+                        "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                        name,
+                        title.if_supports_color(Stdout, |t| t.bold()),
+                        target.display(),
+                    );
+                }
+                Err(e) => {
+                    summary.count("failed", 1);
+                    metrics.record_failed();
+                    summary.problem(format!("{}: {}", name, e));
+                    log::warn!(
+                        "  {} {}: {}",
+                        "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                        name,
+                        e,
+                    );
+                }
+            }
+        }
+        if processed > 0 {
+            crate::commands::send_notification(notify, summary);
+        }
+        metrics.mark_poll();
+
+        if let Some((state, state_path)) = &mut daemon_state {
+            state.last_poll = Some(chrono::Utc::now().to_rfc3339());
+            state.handled = watcher.handled();
+            if let Err(e) = state.save(state_path) {
+                log::warn!(
+                    "{} Failed to save {}: {}",
+                    "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                    state_path.display(),
+                    e,
+                );
+            }
+        }
+
+        if once || stop.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+
+    if let Some((mut state, state_path)) = daemon_state {
+        state.handled = watcher.handled();
+        state.stopped_at = Some(chrono::Utc::now().to_rfc3339());
+        state.save(&state_path)?;
+        log::info!(
+            "Stopped ({} added, {} unverified, {} conflicts, {} failed since the state file was created)",
+            state.added,
+            state.unverified,
+            state.conflicts,
+            state.failed,
+        );
+    }
+
+    Ok(())
+}
+
+/// Sleep for `duration`, waking early if `stop` is set. Returns false when
+/// stopped.
+fn wait(duration: Duration, stop: &AtomicBool) -> bool {
+    let deadline = Instant::now() + duration;
+    while !stop.load(Ordering::SeqCst) {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return true;
+        }
+        std::thread::sleep(left.min(Duration::from_millis(250)));
+    }
+    false
+}
@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use owo_colors::OwoColorize;
 use owo_colors::Stream::Stdout;
-use retro_junk_lib::Platform;
+use retro_junk_lib::{AnalysisContext, Platform};
 
 use crate::CliError;
 use crate::commands::scrape::connect_screenscraper;
@@ -12,6 +12,7 @@ use super::default_catalog_db_path;
 /// Enrich catalog releases with ScreenScraper metadata.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn run_catalog_enrich(
+    ctx: &AnalysisContext,
     systems: Vec<String>,
     db_path: Option<PathBuf>,
     limit: Option<u32>,
@@ -19,9 +20,12 @@ pub(crate) fn run_catalog_enrich(
     download_assets: bool,
     asset_dir: Option<PathBuf>,
     region: String,
+    asset_regions: Vec<(String, String)>,
+    asset_naming: Option<retro_junk_scraper::AssetNaming>,
     language: String,
     threads: Option<usize>,
     no_reconcile: bool,
+    dry_run: bool,
     quiet: bool,
 ) -> Result<(), CliError> {
     use retro_junk_import::scraper_import::{self, EnrichEvent, EnrichOptions};
@@ -69,16 +73,38 @@ pub(crate) fn run_catalog_enrich(
 
     let reconcile_platform_ids = platform_ids.clone();
 
+    // Cached DATs only: enrichment shouldn't stall on DAT downloads
+    let dat_indexes = platform_ids
+        .iter()
+        .filter_map(|id| ctx.get_by_short_name(id))
+        .filter_map(|console| {
+            retro_junk_lib::one_g1r::load_cached_index(console.analyzer.as_ref())
+                .map(|index| (console.metadata.platform, index))
+        })
+        .collect();
+
     let options = EnrichOptions {
         platform_ids,
         limit,
         skip_existing: !force,
         download_assets,
         asset_dir,
+        asset_naming,
         preferred_region: region,
+        asset_regions: asset_regions.into_iter().collect(),
         preferred_language: language,
+        dry_run,
+        dat_indexes,
     };
 
+    if dry_run {
+        log::info!(
+            "{}",
+            "Dry run: looking releases up without writing or downloading anything"
+                .if_supports_color(Stdout, |t| t.dimmed()),
+        );
+    }
+
     let rt = tokio::runtime::Runtime::new()
         .map_err(|e| CliError::runtime(format!("Failed to create tokio runtime: {}", e)))?;
 
@@ -121,6 +147,39 @@ pub(crate) fn run_catalog_enrich(
                             ss_name,
                         );
                     }
+                    EnrichEvent::ReleaseWouldChange {
+                        ref title,
+                        ref ss_name,
+                        ref method,
+                        ref changes,
+                        ..
+                    } => {
+                        log::info!(
+                            "  {} {} (via {}, SS: \"{}\")",
+                            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                            title.if_supports_color(Stdout, |t| t.bold()),
+                            method,
+                            ss_name,
+                        );
+                        if changes.is_empty() {
+                            log::info!(
+                                "      {}",
+                                "no changes".if_supports_color(Stdout, |t| t.dimmed()),
+                            );
+                        }
+                        for change in changes {
+                            log::info!(
+                                "      {}: {} \u{2192} {}",
+                                change.field,
+                                change
+                                    .old
+                                    .as_deref()
+                                    .unwrap_or("\u{2014}")
+                                    .if_supports_color(Stdout, |t| t.dimmed()),
+                                preview(&change.new, 60),
+                            );
+                        }
+                    }
                     EnrichEvent::ReleaseNotFound { ref title, .. } => {
                         log::info!(
                             "  {} {}",
@@ -151,14 +210,23 @@ pub(crate) fn run_catalog_enrich(
                         crate::log_blank();
                         log::info!(
                             "{}",
-                            "Enrichment complete".if_supports_color(Stdout, |t| t.bold()),
+                            if dry_run {
+                                "Dry run complete (nothing written)"
+                            } else {
+                                "Enrichment complete"
+                            }
+                            .if_supports_color(Stdout, |t| t.bold()),
                         );
                         log::info!("  Processed:     {:>6}", stats.releases_processed);
-                        log::info!("  Enriched:      {:>6}", stats.releases_enriched);
+                        if dry_run {
+                            log::info!("  Would enrich:  {:>6}", stats.releases_enriched);
+                        } else {
+                            log::info!("  Enriched:      {:>6}", stats.releases_enriched);
+                        }
                         log::info!("  Not found:     {:>6}", stats.releases_not_found);
                         log::info!("  Skipped:       {:>6}", stats.releases_skipped);
                         log::info!("  Assets:        {:>6}", stats.assets_downloaded);
-                        log::info!("  Companies:     {:>6} (new)", stats.companies_created);
+                        log::info!("  Companies:     {:>6} (staged)", stats.companies_staged);
                         log::info!("  Disagreements: {:>6}", stats.disagreements_found);
                         if stats.errors > 0 {
                             log::info!("  Errors:        {:>6}", stats.errors);
@@ -174,10 +242,25 @@ pub(crate) fn run_catalog_enrich(
         Ok::<(), CliError>(())
     })?;
 
+    if !dry_run {
+        super::disagreements::auto_resolve_after_update(&conn, &super::default_catalog_dir());
+    }
+
     // Auto-reconcile after enrichment
     if !no_reconcile {
-        super::reconcile::run_reconcile_on_conn(&conn, &reconcile_platform_ids, false)?;
+        super::reconcile::run_reconcile_on_conn(&conn, &reconcile_platform_ids, dry_run)?;
     }
 
     Ok(())
 }
+
+/// Flatten a value onto one line, cut to `max` characters.
+fn preview(value: &str, max: usize) -> String {
+    let flat = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= max {
+        flat
+    } else {
+        let cut: String = flat.chars().take(max.saturating_sub(3)).collect();
+        format!("{}...", cut)
+    }
+}
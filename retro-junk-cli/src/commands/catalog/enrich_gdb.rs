@@ -97,8 +97,11 @@ pub(crate) fn run_catalog_enrich_gdb(
                     stats.enriched,
                     stats.disagreements,
                 );
-                if stats.companies_created > 0 {
-                    log::info!("    {} new companies created", stats.companies_created,);
+                if stats.companies_staged > 0 {
+                    log::info!(
+                        "    {} unknown company names staged for review",
+                        stats.companies_staged,
+                    );
                 }
                 if stats.skipped_no_hash > 0 {
                     log::info!(
@@ -125,5 +128,7 @@ pub(crate) fn run_catalog_enrich_gdb(
         total_enriched,
     );
 
+    super::disagreements::auto_resolve_after_update(&conn, &super::default_catalog_dir());
+
     Ok(())
 }
@@ -0,0 +1,88 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_import::{ListingOptions, NAMING_CONVENTIONS};
+use retro_junk_lib::AnalysisContext;
+
+use crate::CliError;
+
+use super::{default_catalog_db_path, open_existing_catalog};
+
+/// Export a renamed listing of a system's media as an .smdb file.
+pub(crate) fn run_catalog_export(
+    ctx: &AnalysisContext,
+    system: String,
+    output: PathBuf,
+    naming: String,
+    folder: Option<String>,
+    owned_by: Option<String>,
+    db_path: Option<PathBuf>,
+) -> Result<(), CliError> {
+    let convention = retro_junk_import::find_convention(&naming).ok_or_else(|| {
+        let known: Vec<_> = NAMING_CONVENTIONS
+            .iter()
+            .map(|c| format!("{} ({})", c.id(), c.description()))
+            .collect();
+        CliError::other(format!(
+            "Unknown naming convention '{}'. Choose one of: {}",
+            naming,
+            known.join(", ")
+        ))
+    })?;
+
+    let console = ctx.get_by_short_name(&system).ok_or_else(|| {
+        CliError::unknown_system(format!(
+            "Unknown system '{}'. Use a short name like 'nes', 'snes', 'n64'.",
+            system
+        ))
+    })?;
+
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+
+    let options = ListingOptions {
+        convention,
+        extension: console
+            .analyzer
+            .file_extensions()
+            .first()
+            .copied()
+            .unwrap_or_default()
+            .to_string(),
+        folder,
+        owned_by,
+    };
+    let listing =
+        retro_junk_import::build_listing(&conn, console.metadata.platform.short_name(), &options)
+            .map_err(|e| CliError::database(format!("Failed to list media: {}", e)))?;
+
+    let mut out = std::io::BufWriter::new(std::fs::File::create(&output)?);
+    retro_junk_import::write_smdb(&mut out, &listing.entries)?;
+    out.flush()?;
+
+    log::info!(
+        "{} Wrote {} {} entries ({} naming) to {}",
+        "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+        listing.entries.len(),
+        console.metadata.short_name,
+        convention.id(),
+        output.display(),
+    );
+    if listing.duplicates > 0 {
+        log::info!(
+            "  Skipped {} media whose name was already used",
+            listing.duplicates
+        );
+    }
+    if listing.unnamed > 0 {
+        log::info!("  Skipped {} media with no DAT name", listing.unnamed);
+    }
+    log::info!("  The catalog has no SHA-256 hashes; that column is left empty.");
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use owo_colors::OwoColorize;
@@ -5,7 +6,7 @@ use owo_colors::Stream::Stdout;
 
 use crate::CliError;
 
-use super::default_catalog_db_path;
+use super::{default_catalog_db_path, open_existing_catalog};
 
 /// Analyze media asset coverage gaps.
 pub(crate) fn run_catalog_gaps(
@@ -13,18 +14,13 @@ pub(crate) fn run_catalog_gaps(
     db_path: Option<PathBuf>,
     collection_only: bool,
     missing: Option<String>,
+    user_id: &str,
     limit: u32,
 ) -> Result<(), CliError> {
     let db_path = db_path.unwrap_or_else(default_catalog_db_path);
-
-    if !db_path.exists() {
-        log::warn!("No catalog database found at {}", db_path.display());
-        log::info!("Run 'retro-junk catalog import all' first.");
+    let Some(conn) = open_existing_catalog(&db_path)? else {
         return Ok(());
-    }
-
-    let conn = retro_junk_db::open_database(&db_path)
-        .map_err(|e| CliError::database(format!("Failed to open catalog database: {}", e)))?;
+    };
 
     let scope = if collection_only {
         "collection"
@@ -97,6 +93,22 @@ pub(crate) fn run_catalog_gaps(
     log::info!("  Without any asset:    {:>6}", total - with_assets);
     log::info!("  Total assets:         {:>6}", asset_count);
 
+    match retro_junk_db::work_ownership_for_platform(&conn, &system, user_id) {
+        Ok(works) if !works.is_empty() => {
+            let owned = works.iter().filter(|w| w.is_owned()).count();
+            log::info!(
+                "  Works owned:          {:>6} of {} ({}%)",
+                owned,
+                works.len(),
+                percent(owned, works.len()),
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            log::error!("Failed to query work ownership: {}", e);
+        }
+    }
+
     // Asset counts by type
     match retro_junk_db::asset_counts_by_type(&conn, &system, collection_only) {
         Ok(counts) => {
@@ -147,8 +159,92 @@ pub(crate) fn run_catalog_gaps(
         "--missing <type>".if_supports_color(Stdout, |t| t.bold()),
     );
     log::info!(
-        "  Asset types: box-front, box-back, screenshot, title-screen, wheel, fanart, cart-front"
+        "  Asset types: box-front, box-back, screenshot, title-screen, wheel, fanart, cart-front, label"
+    );
+    log::info!(
+        "Use {} to list works you don't own any release of.",
+        "--works".if_supports_color(Stdout, |t| t.bold()),
     );
 
     Ok(())
 }
+
+/// List works with no owned release on a platform, and summarize which
+/// region each owned work is held in.
+pub(crate) fn run_catalog_work_gaps(
+    system: String,
+    db_path: Option<PathBuf>,
+    user_id: &str,
+    limit: u32,
+) -> Result<(), CliError> {
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+
+    let works = retro_junk_db::work_ownership_for_platform(&conn, &system, user_id)
+        .map_err(|e| CliError::database(format!("Failed to query works: {}", e)))?;
+    if works.is_empty() {
+        log::warn!("No works found for '{}' in the catalog.", system);
+        return Ok(());
+    }
+    let (owned, missing): (Vec<_>, Vec<_>) = works.iter().partition(|w| w.is_owned());
+
+    log::info!(
+        "{}",
+        format!("Works on {} with no owned release:", system)
+            .if_supports_color(Stdout, |t| t.bold()),
+    );
+    if missing.is_empty() {
+        log::info!("  No gaps found — you own every work in the catalog.");
+    } else {
+        for work in missing.iter().take(limit as usize) {
+            log::info!(
+                "  {} {} {}",
+                "\u{2022}".if_supports_color(Stdout, |t| t.dimmed()),
+                work.canonical_name,
+                format!("({} releases)", work.release_count)
+                    .if_supports_color(Stdout, |t| t.dimmed()),
+            );
+            log::debug!("    {}", work.work_id);
+        }
+        if missing.len() > limit as usize {
+            log::info!("  ... (showing first {}, use --limit to see more)", limit);
+        }
+    }
+
+    crate::log_blank();
+    log::info!(
+        "  Works owned:  {:>6} of {} ({}%)",
+        owned.len(),
+        works.len(),
+        percent(owned.len(), works.len()),
+    );
+
+    // Which region each owned work is best represented by
+    let mut by_region: BTreeMap<&str, usize> = BTreeMap::new();
+    for work in &owned {
+        if let Some(region) = work.best_owned_region(retro_junk_catalog::DEFAULT_REGION_PRIORITY) {
+            *by_region.entry(region).or_default() += 1;
+        }
+    }
+    if !by_region.is_empty() {
+        log::info!(
+            "{}",
+            "  Owned works by best region:".if_supports_color(Stdout, |t| t.bold()),
+        );
+        for (region, count) in &by_region {
+            log::info!("    {:<20} {:>6}", region, count);
+        }
+    }
+
+    Ok(())
+}
+
+fn percent(part: usize, total: usize) -> u32 {
+    if total > 0 {
+        (part as f64 / total as f64 * 100.0) as u32
+    } else {
+        0
+    }
+}
@@ -137,7 +137,7 @@ pub(crate) fn run_catalog_import(
             let stats = match import_dat(
                 &conn,
                 dat,
-                console.metadata.platform,
+                console.analyzer.as_ref(),
                 source_str,
                 Some(&progress),
             ) {
@@ -216,6 +216,10 @@ pub(crate) fn run_catalog_import(
         0
     };
 
+    if catalog_dir.exists() {
+        super::disagreements::auto_resolve_after_update(&conn, &catalog_dir);
+    }
+
     crate::log_blank();
     log::info!(
         "{}",
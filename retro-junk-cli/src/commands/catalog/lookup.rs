@@ -4,6 +4,8 @@ use std::path::PathBuf;
 use owo_colors::OwoColorize;
 use owo_colors::Stream::Stdout;
 
+use retro_junk_lib::{AnalysisContext, Platform, PlatformFamily};
+
 use crate::CliError;
 
 use super::{default_catalog_db_path, format_file_size, truncate_str};
@@ -17,6 +19,7 @@ const PREFIX_MEDIA: &str = "med-";
 /// Entry point for `catalog lookup`.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn run_catalog_lookup(
+    ctx: &AnalysisContext,
     query: Option<String>,
     platform: Option<String>,
     entity_type: Option<String>,
@@ -97,6 +100,7 @@ pub(crate) fn run_catalog_lookup(
             );
         } else if let Some(ref s) = serial {
             lookup_by_serial(
+                ctx,
                 &conn,
                 s,
                 platform.as_deref(),
@@ -203,6 +207,7 @@ fn lookup_by_hash<F>(
 // ── Serial Lookup ───────────────────────────────────────────────────────────
 
 fn lookup_by_serial(
+    ctx: &AnalysisContext,
     conn: &retro_junk_db::Connection,
     serial: &str,
     platform_filter: Option<&str>,
@@ -212,23 +217,25 @@ fn lookup_by_serial(
     let mut release_ids: HashSet<String> = HashSet::new();
     let mut releases: Vec<retro_junk_catalog::types::Release> = Vec::new();
 
-    // Search release serials
-    if let Ok(found) = retro_junk_db::find_release_by_serial(conn, serial) {
-        for r in found {
-            if release_ids.insert(r.id.clone()) {
-                releases.push(r);
+    for form in serial_forms(ctx, serial, platform_filter) {
+        // Search release serials
+        if let Ok(found) = retro_junk_db::find_release_by_serial(conn, &form) {
+            for r in found {
+                if release_ids.insert(r.id.clone()) {
+                    releases.push(r);
+                }
             }
         }
-    }
 
-    // Search media serials → resolve parent release
-    if let Ok(media_hits) = retro_junk_db::find_media_by_serial(conn, serial) {
-        for m in &media_hits {
-            if !release_ids.contains(&m.release_id)
-                && let Ok(Some(r)) = retro_junk_db::get_release_by_id(conn, &m.release_id)
-            {
-                release_ids.insert(r.id.clone());
-                releases.push(r);
+        // Search media serials → resolve parent release
+        if let Ok(media_hits) = retro_junk_db::find_media_by_serial(conn, &form) {
+            for m in &media_hits {
+                if !release_ids.contains(&m.release_id)
+                    && let Ok(Some(r)) = retro_junk_db::get_release_by_id(conn, &m.release_id)
+                {
+                    release_ids.insert(r.id.clone());
+                    releases.push(r);
+                }
             }
         }
     }
@@ -272,6 +279,22 @@ fn lookup_by_serial(
     }
 }
 
+/// The serial as typed, then in each platform's canonical form (only the
+/// filtered platform's when there is one), without repeats.
+fn serial_forms(ctx: &AnalysisContext, serial: &str, platform_filter: Option<&str>) -> Vec<String> {
+    let mut forms = vec![serial.trim().to_string()];
+    for console in ctx.consoles() {
+        if platform_filter.is_some_and(|p| p != console.metadata.short_name) {
+            continue;
+        }
+        let form = console.analyzer.normalize_serial(serial);
+        if !forms.contains(&form) {
+            forms.push(form);
+        }
+    }
+    forms
+}
+
 // ── ID Lookup ───────────────────────────────────────────────────────────────
 
 fn dispatch_id_lookup(conn: &retro_junk_db::Connection, q: &str) {
@@ -459,7 +482,8 @@ fn dispatch_listing(
 ) -> Result<(), CliError> {
     match entity_type {
         None | Some("platforms" | "platform") => {
-            list_platforms(conn, manufacturer, group);
+            let family = platform.and_then(|p| p.parse::<PlatformFamily>().ok());
+            list_platforms(conn, manufacturer, family, group);
         }
         Some("works" | "work") => {
             log::info!(
@@ -468,7 +492,9 @@ fn dispatch_listing(
         }
         Some("releases" | "release") => {
             if let Some(pid) = platform {
-                list_releases_for_platform(conn, pid, limit, offset);
+                for id in expand_platform_filter(conn, pid) {
+                    list_releases_for_platform(conn, &id, limit, offset);
+                }
             } else {
                 log::info!(
                     "Listing releases requires --platform. Try: catalog lookup --type releases --platform nes"
@@ -477,7 +503,9 @@ fn dispatch_listing(
         }
         Some("media") => {
             if let Some(pid) = platform {
-                list_media_for_platform(conn, pid, limit, offset);
+                for id in expand_platform_filter(conn, pid) {
+                    list_media_for_platform(conn, &id, limit, offset);
+                }
             } else {
                 log::info!(
                     "Listing media requires --platform. Try: catalog lookup --type media --platform nes"
@@ -499,6 +527,7 @@ fn dispatch_listing(
 fn list_platforms(
     conn: &retro_junk_db::Connection,
     manufacturer_filter: Option<&str>,
+    family_filter: Option<PlatformFamily>,
     group: bool,
 ) {
     let platforms = match retro_junk_db::list_platforms(conn) {
@@ -525,10 +554,13 @@ fn list_platforms(
                 .map(|mf| p.manufacturer.to_lowercase().contains(&mf.to_lowercase()))
                 .unwrap_or(true)
         })
+        .filter(|p| family_filter.is_none_or(|f| row_family(p) == Some(f)))
         .collect();
 
     if filtered.is_empty() {
-        if let Some(mf) = manufacturer_filter {
+        if let Some(f) = family_filter {
+            log::info!("No platforms found in the {}.", f);
+        } else if let Some(mf) = manufacturer_filter {
             log::info!("No platforms found for manufacturer \"{}\".", mf);
         } else {
             log::info!("No platforms in the catalog.");
@@ -594,6 +626,29 @@ fn print_platform_table_rows(
     }
 }
 
+/// The core platform family of a catalog platform, if any.
+fn row_family(p: &retro_junk_db::PlatformRow) -> Option<PlatformFamily> {
+    p.core_platform
+        .as_deref()?
+        .parse::<Platform>()
+        .ok()?
+        .family()
+}
+
+/// Expand a `--platform` value naming a family (e.g., `gb-family`) into the
+/// catalog IDs of its members. Other values are returned unchanged.
+fn expand_platform_filter(conn: &retro_junk_db::Connection, platform: &str) -> Vec<String> {
+    let Ok(family) = platform.parse::<PlatformFamily>() else {
+        return vec![platform.to_string()];
+    };
+    retro_junk_db::list_platforms(conn)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|p| row_family(p) == Some(family))
+        .map(|p| p.id)
+        .collect()
+}
+
 // ── Release / media listing for a platform ──────────────────────────────────
 
 fn list_releases_for_platform(
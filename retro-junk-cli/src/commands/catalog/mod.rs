@@ -1,26 +1,58 @@
+pub(crate) mod assets;
+pub(crate) mod companies;
 pub(crate) mod disagreements;
+pub(crate) mod dumper;
 pub(crate) mod enrich;
 pub(crate) mod enrich_gdb;
+pub(crate) mod export;
 pub(crate) mod gaps;
 pub(crate) mod import;
 pub(crate) mod lookup;
 pub(crate) mod reconcile;
+pub(crate) mod refresh;
 pub(crate) mod reset;
 pub(crate) mod scan;
+pub(crate) mod screenshots;
+pub(crate) mod siblings;
 pub(crate) mod stats;
 pub(crate) mod unenrich;
 pub(crate) mod verify;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use retro_junk_lib::util::format_bytes;
 
+use crate::CliError;
+
 pub(crate) fn default_catalog_db_path() -> PathBuf {
     retro_junk_dat::cache::cache_dir()
         .unwrap_or_else(|_| PathBuf::from(".cache"))
         .join("catalog.db")
 }
 
+/// Open an existing catalog database, or explain how to create one if it's
+/// missing.
+pub(crate) fn open_existing_catalog(
+    db_path: &Path,
+) -> Result<Option<retro_junk_db::Connection>, CliError> {
+    if !db_path.exists() {
+        log::warn!("No catalog database found at {}", db_path.display());
+        log::info!("Run 'retro-junk catalog import all' first.");
+        return Ok(None);
+    }
+
+    retro_junk_db::open_database(db_path)
+        .map(Some)
+        .map_err(|e| CliError::database(format!("Failed to open catalog database: {}", e)))
+}
+
+/// Default directory for catalog media assets.
+pub(crate) fn default_catalog_asset_dir() -> PathBuf {
+    retro_junk_dat::cache::cache_dir()
+        .unwrap_or_else(|_| PathBuf::from(".cache"))
+        .join("assets")
+}
+
 /// Default path for catalog YAML data.
 pub(crate) fn default_catalog_dir() -> PathBuf {
     // Look for catalog/ relative to the current directory
@@ -50,6 +50,7 @@ pub(crate) fn run_reconcile_on_conn(
 
     if result.stats.groups_found == 0 {
         log::info!("  No duplicate works found.");
+        report_sibling_candidates(conn, systems);
         return Ok(());
     }
 
@@ -110,5 +111,23 @@ pub(crate) fn run_reconcile_on_conn(
         log::info!("  Media moved:      {:>6}", result.stats.media_moved);
     }
 
+    report_sibling_candidates(conn, systems);
     Ok(())
 }
+
+/// Mention unreviewed cross-platform siblings. These are never merged, so
+/// they are only counted here and listed by `catalog siblings review`.
+fn report_sibling_candidates(conn: &retro_junk_db::Connection, systems: &[String]) {
+    match retro_junk_import::reconcile::find_cross_platform_siblings(conn, systems) {
+        Ok(groups) if !groups.is_empty() => {
+            crate::log_blank();
+            log::info!(
+                "  {} {} title(s) appear on more than one platform; review with: retro-junk catalog siblings review",
+                "\u{25B6}".if_supports_color(Stdout, |t| t.cyan()),
+                groups.len(),
+            );
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("  Failed to check cross-platform siblings: {}", e),
+    }
+}
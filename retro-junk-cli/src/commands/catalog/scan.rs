@@ -96,6 +96,9 @@ pub(crate) fn run_catalog_scan(
             log::info!("  Matched:       {:>6}", stats.matched);
             log::info!("  Already owned: {:>6}", stats.already_owned);
             log::info!("  Unmatched:     {:>6}", stats.unmatched);
+            if stats.release_dates_filled > 0 {
+                log::info!("  Dates filled:  {:>6}", stats.release_dates_filled);
+            }
             if stats.errors > 0 {
                 log::info!("  Errors:        {:>6}", stats.errors);
             }
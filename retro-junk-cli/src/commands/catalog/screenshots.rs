@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+use retro_junk_frontend::AssetType;
+use retro_junk_lib::Platform;
+
+use crate::CliError;
+
+use super::{default_catalog_asset_dir, default_catalog_db_path};
+
+/// Import emulator screenshots and register them as release assets.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_catalog_screenshots(
+    system: String,
+    folder: PathBuf,
+    db_path: Option<PathBuf>,
+    asset_dir: Option<PathBuf>,
+    title_screen: bool,
+    mappings: Vec<String>,
+    overwrite: bool,
+    quiet: bool,
+) -> Result<(), CliError> {
+    use retro_junk_import::screenshot_import::ScreenshotOptions;
+
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+
+    if !db_path.exists() {
+        log::warn!("No catalog database found at {}", db_path.display());
+        log::info!("Run 'retro-junk catalog import all' first.");
+        return Ok(());
+    }
+
+    if !folder.exists() {
+        return Err(CliError::other(format!(
+            "Screenshot folder not found: {}",
+            folder.display()
+        )));
+    }
+
+    let platform: Platform = system.parse().map_err(|_| {
+        CliError::unknown_system(format!(
+            "Unknown system '{}'. Use a short name like 'nes', 'snes', 'n64'.",
+            system
+        ))
+    })?;
+
+    let mut mapping_table = HashMap::new();
+    for m in &mappings {
+        let (stem, release_id) = m.split_once('=').ok_or_else(|| {
+            CliError::other(format!(
+                "Invalid mapping '{}'. Expected STEM=RELEASE_ID.",
+                m
+            ))
+        })?;
+        mapping_table.insert(stem.trim().to_string(), release_id.trim().to_string());
+    }
+
+    let conn = retro_junk_db::open_database(&db_path)
+        .map_err(|e| CliError::database(format!("Failed to open catalog database: {}", e)))?;
+
+    let options = ScreenshotOptions {
+        asset_dir: asset_dir.unwrap_or_else(default_catalog_asset_dir),
+        asset_type: if title_screen {
+            AssetType::TitleScreen
+        } else {
+            AssetType::Screenshot
+        }
+        .catalog_name()
+        .to_string(),
+        mappings: mapping_table,
+        overwrite,
+    };
+
+    log::info!(
+        "{}",
+        format!(
+            "Importing {} screenshots from {}",
+            platform.short_name(),
+            folder.display()
+        )
+        .if_supports_color(Stdout, |t| t.bold()),
+    );
+
+    let result = retro_junk_import::ingest_screenshots(&conn, &folder, platform, &options)
+        .map_err(|e| CliError::other(format!("Screenshot import failed: {}", e)))?;
+
+    let stats = &result.stats;
+    crate::log_blank();
+    log::info!(
+        "{}",
+        "Screenshot import complete".if_supports_color(Stdout, |t| t.bold()),
+    );
+    log::info!("  Images found:  {:>6}", stats.images_found);
+    log::info!("  Imported:      {:>6}", stats.imported);
+    log::info!("  Already set:   {:>6}", stats.skipped_existing);
+    log::info!("  Unmatched:     {:>6}", stats.unmatched);
+    if stats.errors > 0 {
+        log::info!("  Errors:        {:>6}", stats.errors);
+    }
+
+    if !result.unmatched.is_empty() && !quiet {
+        crate::log_blank();
+        log::info!(
+            "{}",
+            format!("{} unmatched images:", result.unmatched.len())
+                .if_supports_color(Stdout, |t| t.dimmed()),
+        );
+        for path in &result.unmatched {
+            log::info!(
+                "  {}",
+                path.file_name()
+                    .map(|n| n.to_string_lossy())
+                    .unwrap_or_default(),
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+use retro_junk_import::reconcile::{find_cross_platform_siblings, link_siblings, reject_siblings};
+
+use crate::CliError;
+
+use super::{default_catalog_db_path, open_existing_catalog};
+
+/// List works that share a title across platforms and haven't been reviewed.
+pub(crate) fn run_catalog_siblings_review(
+    systems: Vec<String>,
+    db_path: Option<PathBuf>,
+    limit: usize,
+) -> Result<(), CliError> {
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+
+    let groups = find_cross_platform_siblings(&conn, &systems)
+        .map_err(|e| CliError::database(format!("Failed to find siblings: {}", e)))?;
+    if groups.is_empty() {
+        log::info!("No cross-platform siblings awaiting review.");
+        return Ok(());
+    }
+
+    log::info!(
+        "{}",
+        format!(
+            "{} title(s) appear on more than one platform:",
+            groups.len()
+        )
+        .if_supports_color(Stdout, |t| t.bold()),
+    );
+
+    for group in groups.iter().take(limit) {
+        crate::log_blank();
+        log::info!(
+            "  {}",
+            group.title_key.if_supports_color(Stdout, |t| t.cyan())
+        );
+        for work in &group.works {
+            log::info!(
+                "    {} {:<10} {} ({})",
+                "\u{25B6}".if_supports_color(Stdout, |t| t.dimmed()),
+                work.platform_id,
+                work.canonical_name,
+                work.work_id.if_supports_color(Stdout, |t| t.dimmed()),
+            );
+        }
+    }
+
+    if groups.len() > limit {
+        crate::log_blank();
+        log::info!(
+            "  ... and {} more (use --limit to show more)",
+            groups.len() - limit,
+        );
+    }
+
+    crate::log_blank();
+    log::info!("Review with: retro-junk catalog siblings link <works> | reject <works>");
+
+    Ok(())
+}
+
+/// Record works as the same game on different platforms.
+pub(crate) fn run_catalog_siblings_link(
+    works: Vec<String>,
+    db_path: Option<PathBuf>,
+) -> Result<(), CliError> {
+    record(works, db_path, true)
+}
+
+/// Record works as different games so they stop being reported.
+pub(crate) fn run_catalog_siblings_reject(
+    works: Vec<String>,
+    db_path: Option<PathBuf>,
+) -> Result<(), CliError> {
+    record(works, db_path, false)
+}
+
+fn record(works: Vec<String>, db_path: Option<PathBuf>, link: bool) -> Result<(), CliError> {
+    if works.len() < 2 {
+        return Err(CliError::other("Give at least two work IDs"));
+    }
+
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+
+    let result = if link {
+        link_siblings(&conn, &works)
+    } else {
+        reject_siblings(&conn, &works)
+    };
+    let pairs = result.map_err(|e| CliError::database(e.to_string()))?;
+
+    log::info!(
+        "  {} {} {} ({} pair(s))",
+        "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+        works.join(", "),
+        if link {
+            "linked as siblings"
+        } else {
+            "marked as different games"
+        },
+        pairs,
+    );
+    Ok(())
+}
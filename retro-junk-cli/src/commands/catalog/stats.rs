@@ -1,23 +1,18 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use owo_colors::OwoColorize;
 use owo_colors::Stream::Stdout;
+use retro_junk_lib::Platform;
 
 use crate::CliError;
 
-use super::default_catalog_db_path;
+use super::{default_catalog_db_path, open_existing_catalog};
 
-pub(crate) fn run_catalog_stats(db_path: Option<PathBuf>) -> Result<(), CliError> {
+pub(crate) fn run_catalog_stats(db_path: Option<PathBuf>, user_id: &str) -> Result<(), CliError> {
     let db_path = db_path.unwrap_or_else(default_catalog_db_path);
-
-    if !db_path.exists() {
-        log::warn!("No catalog database found at {}", db_path.display());
-        log::info!("Run 'retro-junk catalog import all' to create one.");
+    let Some(conn) = open_existing_catalog(&db_path)? else {
         return Ok(());
-    }
-
-    let conn = retro_junk_db::open_database(&db_path)
-        .map_err(|e| CliError::database(format!("Failed to open catalog database: {}", e)))?;
+    };
 
     let stats = retro_junk_db::catalog_stats(&conn)
         .map_err(|e| CliError::database(format!("Failed to query catalog stats: {}", e)))?;
@@ -35,10 +30,141 @@ pub(crate) fn run_catalog_stats(db_path: Option<PathBuf>) -> Result<(), CliError
     log::info!("  Media entries:  {:>8}", stats.media);
     log::info!("  Assets:         {:>8}", stats.assets);
     log::info!("  Owned (coll.):  {:>8}", stats.collection_owned);
+    log::info!("  Owned works:    {:>8}", stats.works_owned);
     log::info!(
         "  Disagreements:  {:>8} (unresolved)",
         stats.unresolved_disagreements,
     );
 
+    let counts = retro_junk_db::work_counts_by_platform(&conn, user_id)
+        .map_err(|e| CliError::database(format!("Failed to query work counts: {}", e)))?;
+    let owned: Vec<_> = counts.iter().filter(|c| c.owned_works > 0).collect();
+    if !owned.is_empty() {
+        crate::log_blank();
+        log::info!(
+            "{}",
+            "  Works owned by platform:".if_supports_color(Stdout, |t| t.bold()),
+        );
+        for c in owned {
+            log::info!(
+                "    {:<12} {:>6} of {:<6} ({}%)",
+                c.platform_id,
+                c.owned_works,
+                c.works,
+                c.owned_works * 100 / c.works.max(1),
+            );
+        }
+    }
+
     Ok(())
 }
+
+/// Show (and optionally export) the collection snapshots recorded by scans
+/// and verifies.
+pub(crate) fn run_catalog_stats_history(
+    db_path: Option<PathBuf>,
+    system: Option<String>,
+    csv: Option<PathBuf>,
+    user_id: &str,
+) -> Result<(), CliError> {
+    let platform_id = system
+        .map(|s| {
+            s.parse::<Platform>().map(|p| p.short_name()).map_err(|_| {
+                CliError::unknown_system(format!(
+                    "Unknown system '{}'. Use a short name like 'nes', 'snes', 'n64'.",
+                    s
+                ))
+            })
+        })
+        .transpose()?;
+
+    let db_path = db_path.unwrap_or_else(default_catalog_db_path);
+    let Some(conn) = open_existing_catalog(&db_path)? else {
+        return Ok(());
+    };
+
+    let history = retro_junk_db::collection_history(&conn, platform_id, user_id)
+        .map_err(|e| CliError::database(format!("Failed to query collection history: {}", e)))?;
+
+    if let Some(path) = &csv {
+        write_history_csv(path, &history)
+            .map_err(|e| CliError::other(format!("Failed to write {}: {}", path.display(), e)))?;
+    }
+
+    if history.is_empty() {
+        log::info!("No collection snapshots recorded yet.");
+        log::info!("Snapshots are taken by 'retro-junk catalog scan' and 'catalog verify'.");
+        return Ok(());
+    }
+
+    log::info!(
+        "{}",
+        "Collection History".if_supports_color(Stdout, |t| t.bold()),
+    );
+    log::info!(
+        "  {:<19}  {:<10}  {:<6}  {:>7}  {:>7}  {:>8}  {:>7}",
+        "Recorded",
+        "System",
+        "Source",
+        "Owned",
+        "Works",
+        "Verified",
+        "Assets",
+    );
+    for snap in &history {
+        log::info!(
+            "  {:<19}  {:<10}  {:<6}  {:>7}  {:>7}  {:>8}  {:>7}",
+            display_timestamp(&snap.recorded_at),
+            snap.platform_id,
+            snap.source,
+            snap.owned,
+            snap.owned_works,
+            snap.verified,
+            snap.assets,
+        );
+    }
+
+    if let Some(path) = &csv {
+        crate::log_blank();
+        log::info!(
+            "{} Wrote {} snapshot(s) to {}",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            history.len(),
+            path.display(),
+        );
+    }
+
+    Ok(())
+}
+fn write_history_csv(
+    path: &Path,
+    history: &[retro_junk_db::CollectionSnapshot],
+) -> std::io::Result<()> {
+    let mut out = csv::Writer::from_path(path)?;
+    out.write_record([
+        "recorded_at",
+        "system",
+        "source",
+        "owned",
+        "owned_works",
+        "verified",
+        "assets",
+    ])?;
+    for snap in history {
+        out.write_record([
+            snap.recorded_at.clone(),
+            snap.platform_id.clone(),
+            snap.source.clone(),
+            snap.owned.to_string(),
+            snap.owned_works.to_string(),
+            snap.verified.to_string(),
+            snap.assets.to_string(),
+        ])?;
+    }
+    out.flush()
+}
+
+/// Trim an RFC 3339 timestamp to "YYYY-MM-DD HH:MM:SS".
+fn display_timestamp(ts: &str) -> String {
+    ts.get(..19).unwrap_or(ts).replacen('T', " ", 1)
+}
@@ -4,6 +4,7 @@ use owo_colors::OwoColorize;
 use owo_colors::Stream::Stdout;
 
 use retro_junk_lib::AnalysisContext;
+use retro_junk_lib::notify::{NotifyTarget, RunSummary};
 
 use crate::CliError;
 
@@ -16,6 +17,7 @@ pub(crate) fn run_catalog_verify(
     db_path: Option<PathBuf>,
     user_id: String,
     _quiet: bool,
+    notify: Option<&NotifyTarget>,
 ) -> Result<(), CliError> {
     let db_path = db_path.unwrap_or_else(default_catalog_db_path);
 
@@ -44,6 +46,7 @@ pub(crate) fn run_catalog_verify(
         .if_supports_color(Stdout, |t| t.bold()),
     );
 
+    let mut summary = RunSummary::new("catalog verify");
     let stats = retro_junk_import::verify_collection(
         &conn,
         console.analyzer.as_ref(),
@@ -66,5 +69,13 @@ pub(crate) fn run_catalog_verify(
         log::info!("  Errors:         {:>6}", stats.errors);
     }
 
+    summary.count("checked", stats.checked);
+    summary.count("verified", stats.verified);
+    summary.count("missing", stats.missing);
+    summary.count("hash mismatch", stats.hash_mismatch);
+    summary.count("errors", stats.errors);
+    summary.problems = stats.problems;
+    crate::commands::send_notification(notify, summary);
+
     Ok(())
 }
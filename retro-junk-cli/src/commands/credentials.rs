@@ -145,6 +145,42 @@ pub(crate) fn run_credentials_show() -> Result<(), CliError> {
         }
     }
 
+    // HTTP client settings ([client] section)
+    let client = retro_junk_scraper::ClientConfig::load();
+    let soft_name = creds
+        .as_ref()
+        .map(|c| c.soft_name.as_str())
+        .unwrap_or("retro-junk");
+    crate::log_blank();
+    log::info!("{}", "Client".if_supports_color(Stdout, |t| t.bold()));
+    let settings = [
+        ("user_agent", client.user_agent_for(soft_name)),
+        ("api_timeout", format!("{}s", client.api_timeout.as_secs())),
+        (
+            "connect_timeout",
+            format!("{}s", client.connect_timeout.as_secs()),
+        ),
+        (
+            "media_timeout",
+            format!("{}s", client.media_timeout.as_secs()),
+        ),
+        ("max_retries", client.max_retries.to_string()),
+        (
+            "max_media_bytes",
+            client
+                .max_media_bytes
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "unlimited".to_string()),
+        ),
+    ];
+    for (name, value) in &settings {
+        log::info!(
+            "  {} {}",
+            format!("{}:", name).if_supports_color(Stdout, |t| t.cyan()),
+            value,
+        );
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_frontend::esde_names;
+use retro_junk_lib::{AnalysisContext, Platform, PlatformFamily};
+
+use crate::CliError;
+
+/// Run the doctor command.
+pub(crate) fn run_doctor(
+    ctx: &AnalysisContext,
+    consoles: Option<Vec<Platform>>,
+    library_path: PathBuf,
+) -> Result<(), CliError> {
+    log::info!("Checking library: {}", library_path.display());
+    crate::log_blank();
+
+    let scan = match crate::scan_folders(ctx, &library_path, &consoles) {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    log::info!(
+        "{}",
+        "ES-DE folders".if_supports_color(Stdout, |t| t.bold())
+    );
+    let mut checked: Vec<&PathBuf> = Vec::new();
+    let mut problems = 0usize;
+    for cf in &scan.matches {
+        // Family folders are matched once per member console
+        if checked.contains(&&cf.path) {
+            continue;
+        }
+        checked.push(&cf.path);
+
+        let folder = cf.folder_name.as_str();
+        if let Some(system) = ctx.frontend_system_name(folder) {
+            ok(&format!("{} (ES-DE system '{}')", folder, system));
+        } else if let Ok(family) = folder.parse::<PlatformFamily>() {
+            problems += 1;
+            warn(&format!(
+                "{}: ES-DE has no combined system for the {}; it needs a custom system entry",
+                folder, family,
+            ));
+        } else if cf.platform.is_custom() {
+            log::info!(
+                "  {} {}: custom platform, no ES-DE mapping",
+                "\u{2014}".if_supports_color(Stdout, |t| t.dimmed()),
+                folder,
+            );
+        } else if esde_names::system_names(cf.platform).contains(&folder) {
+            ok(folder);
+        } else {
+            problems += 1;
+            let expected = esde_names::output_system_name(cf.platform, folder);
+            if expected == folder {
+                warn(&format!(
+                    "{}: ES-DE system names are lowercase ('{}')",
+                    folder,
+                    folder.to_lowercase(),
+                ));
+            } else {
+                warn(&format!(
+                    "{}: ES-DE expects '{}'; gamelists and media are written there, \
+                     but ES-DE won't find the ROMs until the folder is renamed",
+                    folder, expected,
+                ));
+            }
+        }
+    }
+    crate::log_blank();
+
+    if problems == 0 {
+        log::info!(
+            "{} All {} console folders match ES-DE system names.",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            checked.len(),
+        );
+    } else {
+        log::info!(
+            "{} {} of {} console folders need attention.",
+            "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+            problems,
+            checked.len(),
+        );
+    }
+
+    Ok(())
+}
+
+fn ok(message: &str) {
+    log::info!(
+        "  {} {}",
+        "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+        message,
+    );
+}
+
+fn warn(message: &str) {
+    log::warn!(
+        "  {} {}",
+        "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+        message,
+    );
+}
@@ -0,0 +1,136 @@
+use std::path::PathBuf;
+
+use indicatif::{ProgressBar, ProgressStyle};
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_lib::dump::{DumpOptions, DumpProgress, check_dumpable, dump_dvd};
+use retro_junk_lib::{AnalysisContext, Platform};
+
+use crate::CliError;
+
+/// Run the dump-dvd command.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_dump_dvd(
+    ctx: &AnalysisContext,
+    system: Platform,
+    device: PathBuf,
+    output: PathBuf,
+    retries: u32,
+    dat_dir: Option<PathBuf>,
+    overwrite: bool,
+    quiet: bool,
+) -> Result<(), CliError> {
+    let console = ctx.get_by_platform(system).ok_or_else(|| {
+        CliError::unknown_system(format!("No analyzer for platform {:?}", system))
+    })?;
+    check_dumpable(console.analyzer.as_ref(), &device)
+        .map_err(|e| CliError::other(format!("Can't dump this disc: {}", e)))?;
+
+    if output.exists() && !overwrite {
+        return Err(CliError::other(format!(
+            "Output file already exists: {} (use --overwrite to replace it)",
+            output.display()
+        )));
+    }
+
+    log::info!(
+        "Dumping {} disc from {} to {}",
+        console.metadata.platform_name,
+        device.display().if_supports_color(Stdout, |t| t.cyan()),
+        output.display().if_supports_color(Stdout, |t| t.cyan()),
+    );
+    crate::log_blank();
+
+    let pb = if quiet {
+        ProgressBar::hidden()
+    } else {
+        let pb = ProgressBar::new(0);
+        pb.set_style(
+            ProgressStyle::with_template(
+                "  {msg} [{bar:30.cyan/dim}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})",
+            )
+            .expect("static pattern"),
+        );
+        pb
+    };
+
+    let progress_callback = |progress: DumpProgress| match progress {
+        DumpProgress::Reading {
+            bytes_done,
+            bytes_total,
+        } => {
+            pb.set_message("Reading");
+            pb.set_length(bytes_total);
+            pb.set_position(bytes_done);
+        }
+        DumpProgress::BadSector { sector } => {
+            pb.suspend(|| {
+                log::warn!(
+                    "  {} Unreadable sector {} (zero-filled)",
+                    "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                    sector,
+                );
+            });
+        }
+        DumpProgress::Verifying {
+            bytes_done,
+            bytes_total,
+        } => {
+            pb.set_message("Hashing");
+            pb.set_length(bytes_total);
+            pb.set_position(bytes_done);
+        }
+    };
+
+    let options = DumpOptions { retries, dat_dir };
+    let result = dump_dvd(
+        &device,
+        &output,
+        console.analyzer.as_ref(),
+        &options,
+        &progress_callback,
+    );
+    pb.finish_and_clear();
+    let summary = result.map_err(|e| CliError::other(format!("Dump failed: {}", e)))?;
+
+    log::info!(
+        "  {} Wrote {} bytes",
+        "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+        summary.bytes_written,
+    );
+    log::info!("  CRC32: {}", summary.hashes.crc32);
+    if let Some(ref sha1) = summary.hashes.sha1 {
+        log::info!("  SHA1:  {}", sha1);
+    }
+    if !summary.bad_sectors.is_empty() {
+        log::warn!(
+            "  {} {} unreadable sectors were zero-filled",
+            "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+            summary.bad_sectors.len(),
+        );
+    }
+
+    crate::log_blank();
+    match summary.matched_game {
+        Some(ref name) => log::info!(
+            "{} Verified: {}",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            name.if_supports_color(Stdout, |t| t.bold()),
+        ),
+        None if !console.analyzer.has_dat_support() => log::info!(
+            "{}",
+            format!(
+                "No DAT support for {}; skipped verification",
+                console.metadata.platform_name
+            )
+            .if_supports_color(Stdout, |t| t.dimmed()),
+        ),
+        None => log::warn!(
+            "{} No DAT match for this dump",
+            "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+        ),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_lib::device::{self, DeviceProfile, PROFILES};
+use retro_junk_lib::split::SplitFormat;
+use retro_junk_lib::util::format_bytes_approx;
+use retro_junk_lib::{AnalysisContext, Platform};
+
+use crate::CliError;
+
+/// Run the export command.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_export(
+    ctx: &AnalysisContext,
+    profile: &str,
+    target: &Path,
+    consoles: Option<Vec<Platform>>,
+    limit: Option<usize>,
+    name: Option<&str>,
+    dry_run: bool,
+    library_path: PathBuf,
+) -> Result<(), CliError> {
+    let profile = device::find_profile(profile).ok_or_else(|| {
+        let ids: Vec<_> = PROFILES.iter().map(|p| p.id).collect();
+        CliError::config(format!(
+            "Unknown device profile '{}' (expected one of: {})",
+            profile,
+            ids.join(", ")
+        ))
+    })?;
+
+    log::info!(
+        "Exporting to {} for {} ({})",
+        target.display(),
+        profile.name.if_supports_color(Stdout, |t| t.bold()),
+        profile.filesystem.display_name(),
+    );
+    if let Some(name) = name {
+        log::info!("Only games matching \"{}\"", name);
+    }
+    if dry_run {
+        log::info!(
+            "{}",
+            "Dry run: nothing will be copied".if_supports_color(Stdout, |t| t.yellow()),
+        );
+    }
+    crate::log_blank();
+
+    let scan = match crate::scan_folders(ctx, &library_path, &consoles) {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    let mut total_files = 0usize;
+    let mut total_bytes = 0u64;
+    for cf in &scan.matches {
+        let Some(system) = profile.system(cf.platform) else {
+            log::info!(
+                "  {} {}: not supported by {}",
+                "\u{2014}".if_supports_color(Stdout, |t| t.dimmed()),
+                cf.folder_name,
+                profile.name,
+            );
+            continue;
+        };
+        let Some(console) = ctx.get_by_platform(cf.platform) else {
+            continue;
+        };
+        let library_extensions: HashSet<String> =
+            retro_junk_lib::scanner::extension_set(console.analyzer.file_extensions());
+
+        let plan = match device::plan_export(
+            profile,
+            system,
+            &cf.path,
+            target,
+            &library_extensions,
+            name,
+            limit,
+        ) {
+            Ok(plan) => plan,
+            Err(e) => {
+                log::warn!(
+                    "  {} {}: {}",
+                    "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                    cf.folder_name,
+                    e,
+                );
+                continue;
+            }
+        };
+        if plan.files.is_empty() && plan.unsupported.is_empty() {
+            continue;
+        }
+
+        let pending = plan.files.iter().filter(|f| !f.up_to_date).count();
+        log::info!(
+            "{} {} {}",
+            cf.folder_name.if_supports_color(Stdout, |t| t.bold()),
+            "\u{2192}".if_supports_color(Stdout, |t| t.dimmed()),
+            profile.system_dir(Path::new(""), system).display(),
+        );
+        print_plan(profile, &plan);
+
+        if !dry_run && pending > 0 {
+            let result = device::apply_export(&plan, &|file, _| {
+                log::debug!("Copying {}", file.source.display());
+            });
+            if let Err(e) = result {
+                log::warn!(
+                    "  {} Error copying: {}",
+                    "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                    e,
+                );
+                continue;
+            }
+        }
+        total_files += pending;
+        total_bytes += plan.pending_bytes();
+    }
+
+    crate::log_blank();
+    if total_files == 0 {
+        log::info!(
+            "{} The device is up to date.",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+        );
+    } else {
+        log::info!(
+            "{} {} {} files ({}).",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            if dry_run { "Would copy" } else { "Copied" },
+            total_files,
+            format_bytes_approx(total_bytes),
+        );
+    }
+
+    Ok(())
+}
+
+fn print_plan(profile: &DeviceProfile, plan: &device::ExportPlan) {
+    for file in &plan.files {
+        let file_name = file
+            .dest
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if file.up_to_date {
+            log::debug!("  = {}", file_name);
+            continue;
+        }
+        let source_name = file
+            .source
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let file_name = match file.split {
+            Some(_) => format!(
+                "{} (split into {} pieces)",
+                file_name,
+                SplitFormat::part_count(file.size)
+            ),
+            None => file_name,
+        };
+        if source_name == file_name {
+            log::info!(
+                "  {} {}",
+                "+".if_supports_color(Stdout, |t| t.green()),
+                file_name
+            );
+        } else {
+            log::info!(
+                "  {} {} {} {}",
+                "+".if_supports_color(Stdout, |t| t.green()),
+                source_name,
+                "\u{2192}".if_supports_color(Stdout, |t| t.dimmed()),
+                file_name,
+            );
+        }
+    }
+    for path in &plan.unsupported {
+        log::info!(
+            "  {} {} (format not loaded by {})",
+            "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            profile.name,
+        );
+    }
+    for path in &plan.superseded {
+        log::debug!("  - {} (preferred format exported instead)", path.display());
+    }
+}
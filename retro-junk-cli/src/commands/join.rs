@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_lib::split::{self, JoinResult, SplitFormat};
+use retro_junk_lib::util::format_bytes_approx;
+use retro_junk_lib::{AnalysisContext, FileHashes, one_g1r};
+
+use crate::CliError;
+
+/// Run the join command.
+pub(crate) fn run_join(
+    ctx: &AnalysisContext,
+    part: &Path,
+    output: Option<&Path>,
+    dat_dir: Option<&Path>,
+) -> Result<(), CliError> {
+    let (format, parts) = split::find_parts(part).ok_or_else(|| {
+        CliError::other(format!(
+            "{} is not the first piece of a split file (expected .part0, or .wbfs with .wbf1 next to it)",
+            part.display()
+        ))
+    })?;
+
+    let output = match output {
+        Some(path) => path.to_path_buf(),
+        None => default_output(part, format),
+    };
+    log::info!(
+        "Joining {} pieces into {}",
+        parts.len(),
+        output.display().if_supports_color(Stdout, |t| t.bold()),
+    );
+    let result = split::join_parts(&parts, &output)
+        .map_err(|e| CliError::other(format!("Couldn't join into {}: {}", output.display(), e)))?;
+    log::info!(
+        "{} {} ({}), CRC32 {}",
+        "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+        output.display(),
+        format_bytes_approx(result.size),
+        result.crc32,
+    );
+
+    if format == SplitFormat::Wbfs {
+        // DATs list plain disc images, not WBFS containers
+        log::info!("  WBFS images aren't listed in DATs; CRC32 not checked");
+        return Ok(());
+    }
+    check_against_dat(ctx, &output, &result, dat_dir)
+}
+
+/// Look the joined file's CRC32 up in the DAT of each platform it's
+/// detected as. Fails if a DAT was loaded but lists no such dump.
+fn check_against_dat(
+    ctx: &AnalysisContext,
+    path: &Path,
+    result: &JoinResult,
+    dat_dir: Option<&Path>,
+) -> Result<(), CliError> {
+    let platforms = ctx.detect_platforms(&mut std::fs::File::open(path)?)?;
+    let hashes = FileHashes {
+        crc32: result.crc32.clone(),
+        sha1: None,
+        md5: None,
+        sha256: None,
+        data_size: result.size,
+    };
+
+    let mut checked = Vec::new();
+    for console in platforms.iter().filter_map(|&p| ctx.get_by_platform(p)) {
+        let index = match one_g1r::load_index(console.analyzer.as_ref(), dat_dir) {
+            Ok(Some(index)) => index,
+            Ok(None) => continue,
+            Err(e) => {
+                log::warn!(
+                    "  {} Couldn't load {} DATs: {}",
+                    "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                    console.metadata.platform_name,
+                    e,
+                );
+                continue;
+            }
+        };
+        if let Some(m) = index.match_by_hash(result.size, &hashes) {
+            log::info!(
+                "  {} Matches DAT: {}",
+                "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                index.games[m.game_index].name,
+            );
+            return Ok(());
+        }
+        checked.push(console.metadata.platform_name);
+    }
+
+    if checked.is_empty() {
+        log::warn!(
+            "  {} No DAT for this file's platform; CRC32 not checked",
+            "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+        );
+        return Ok(());
+    }
+    Err(CliError::other(format!(
+        "{} (CRC32 {}) isn't in the {} DAT; the pieces may be a bad copy",
+        path.display(),
+        result.crc32,
+        checked.join(" / "),
+    )))
+}
+
+/// `Game.iso.part0` joins to `Game.iso`. Split WBFS files keep their name,
+/// so the joined copy gets `.joined.wbfs` to avoid overwriting the first piece.
+fn default_output(part: &Path, format: SplitFormat) -> PathBuf {
+    match format {
+        SplitFormat::Parts => part.with_extension(""),
+        SplitFormat::Wbfs => part.with_extension("joined.wbfs"),
+    }
+}
@@ -1,8 +1,39 @@
 pub(crate) mod analyze;
+pub(crate) mod audit_sizes;
 pub(crate) mod cache;
 pub(crate) mod catalog;
 pub(crate) mod config;
 pub(crate) mod credentials;
+pub(crate) mod doctor;
+pub(crate) mod dump_dvd;
+pub(crate) mod export;
+pub(crate) mod join;
+pub(crate) mod one_g1r;
 pub(crate) mod rename;
 pub(crate) mod repair;
 pub(crate) mod scrape;
+pub(crate) mod sync;
+pub(crate) mod trash;
+pub(crate) mod verify;
+pub(crate) mod verify_smdb;
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_lib::notify::{self, NotifyTarget, RunSummary};
+
+/// Send a finished run's summary to the `--notify` URL, if one was given.
+/// A failed notification is reported but doesn't fail the run.
+pub(crate) fn send_notification(target: Option<&NotifyTarget>, mut summary: RunSummary) {
+    let Some(target) = target else {
+        return;
+    };
+    summary.finish();
+    if let Err(e) = notify::send(target, &summary) {
+        log::warn!(
+            "{} {}",
+            "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+            e,
+        );
+    }
+}
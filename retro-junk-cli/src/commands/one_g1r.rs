@@ -0,0 +1,224 @@
+use std::path::{Path, PathBuf};
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_dat::one_g1r::Preferences;
+use retro_junk_lib::notify::{NotifyTarget, RunSummary};
+use retro_junk_lib::one_g1r::{
+    DUPLICATES_DIR, FolderPlan, OneG1rOptions, load_index, move_duplicates, plan_folder,
+};
+use retro_junk_lib::scanner::GroupedEntry;
+use retro_junk_lib::{AnalysisContext, Platform};
+
+use crate::CliError;
+
+/// Run the 1g1r command.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_one_g1r(
+    ctx: &AnalysisContext,
+    consoles: Option<Vec<Platform>>,
+    library_path: PathBuf,
+    dat_dir: Option<PathBuf>,
+    preferences: Preferences,
+    move_files: bool,
+    notify: Option<&NotifyTarget>,
+) -> Result<(), CliError> {
+    let root_path = library_path;
+    let options = OneG1rOptions {
+        preferences,
+        symlinks: ctx.symlink_policy(),
+    };
+
+    log::info!(
+        "Selecting one release per game in: {}",
+        root_path.display().if_supports_color(Stdout, |t| t.cyan()),
+    );
+    log::info!(
+        "{}",
+        format!(
+            "Regions: {}  Languages: {}  Revision: {}",
+            options.preferences.regions.join(", "),
+            options.preferences.languages.join(", "),
+            if options.preferences.latest_revision {
+                "latest"
+            } else {
+                "original"
+            },
+        )
+        .if_supports_color(Stdout, |t| t.dimmed()),
+    );
+    if !move_files {
+        log::info!(
+            "{}",
+            "Report only: pass --move to set duplicates aside"
+                .if_supports_color(Stdout, |t| t.dimmed()),
+        );
+    }
+    crate::log_blank();
+
+    let scan = match crate::scan_folders(ctx, &root_path, &consoles) {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    let mut summary = RunSummary::new("1g1r");
+    let mut total_games = 0usize;
+    let mut total_duplicates = 0usize;
+    let mut total_moved = 0usize;
+    let mut total_errors = 0usize;
+
+    for cf in &scan.matches {
+        let console = ctx.get_by_platform(cf.platform).ok_or_else(|| {
+            CliError::unknown_system(format!("No analyzer for platform {:?}", cf.platform))
+        })?;
+
+        let index = match load_index(console.analyzer.as_ref(), dat_dir.as_deref()) {
+            Ok(index) => index,
+            Err(e) => {
+                crate::log_dat_error(
+                    console.metadata.platform_name,
+                    &cf.folder_name,
+                    console.metadata.short_name,
+                    &e,
+                );
+                None
+            }
+        };
+
+        let plan = match plan_folder(
+            &cf.path,
+            console.analyzer.as_ref(),
+            index.as_ref(),
+            &options,
+        ) {
+            Ok(plan) => plan,
+            Err(e) => {
+                log::warn!(
+                    "{} {}: {} Error: {}",
+                    console
+                        .metadata
+                        .platform_name
+                        .if_supports_color(Stdout, |t| t.bold()),
+                    format!("({})", cf.folder_name).if_supports_color(Stdout, |t| t.dimmed()),
+                    "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                    e,
+                );
+                summary.problem(format!("error: {}: {}", cf.path.display(), e));
+                crate::log_blank();
+                continue;
+            }
+        };
+
+        if plan.sets.is_empty() {
+            continue;
+        }
+
+        log::info!(
+            "{} {}{}",
+            console
+                .metadata
+                .platform_name
+                .if_supports_color(Stdout, |t| t.bold()),
+            format!("({})", cf.folder_name).if_supports_color(Stdout, |t| t.dimmed()),
+            if plan.used_dat {
+                String::new()
+            } else {
+                format!(
+                    " {}",
+                    "[by title, no DAT]".if_supports_color(Stdout, |t| t.dimmed())
+                )
+            },
+        );
+        print_plan(&cf.path, &plan);
+
+        total_games += plan.sets.len();
+        total_duplicates += plan.duplicate_count();
+
+        if move_files {
+            let moved = move_duplicates(&cf.path, &plan);
+            total_moved += moved.moved;
+            total_errors += moved.errors.len();
+            for (path, msg) in &moved.errors {
+                log::warn!(
+                    "  {} {}: {}",
+                    "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                    display_name(&cf.path, path),
+                    msg,
+                );
+                summary.problem(format!("error: {}: {}", path.display(), msg));
+            }
+        }
+        crate::log_blank();
+    }
+
+    if total_games == 0 {
+        log::info!(
+            "{}",
+            "No duplicate releases found.".if_supports_color(Stdout, |t| t.dimmed()),
+        );
+        crate::commands::send_notification(notify, summary);
+        return Ok(());
+    }
+
+    log::info!("{}", "Summary:".if_supports_color(Stdout, |t| t.bold()));
+    summary.count("games", total_games as u64);
+    summary.count("duplicates", total_duplicates as u64);
+    log::info!(
+        "  {} {} game{} with {} duplicate{}",
+        "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+        total_games,
+        if total_games == 1 { "" } else { "s" },
+        total_duplicates,
+        if total_duplicates == 1 { "" } else { "s" },
+    );
+    if move_files {
+        summary.count("moved", total_moved as u64);
+        log::info!(
+            "  {} {} moved into {}",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            total_moved,
+            DUPLICATES_DIR,
+        );
+    }
+    if total_errors > 0 {
+        summary.count("errors", total_errors as u64);
+        log::warn!(
+            "  {} {} could not be moved",
+            "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+            total_errors,
+        );
+    }
+
+    crate::commands::send_notification(notify, summary);
+    Ok(())
+}
+
+/// Print the kept and dropped releases of each game in a folder.
+fn print_plan(folder: &Path, plan: &FolderPlan) {
+    for set in &plan.sets {
+        log::info!(
+            "  {} {}",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            entry_name(folder, &set.keep),
+        );
+        for entry in &set.drop {
+            log::info!(
+                "    {} {}",
+                "\u{2718}".if_supports_color(Stdout, |t| t.yellow()),
+                entry_name(folder, entry).if_supports_color(Stdout, |t| t.dimmed()),
+            );
+        }
+    }
+}
+
+fn entry_name(folder: &Path, entry: &GroupedEntry) -> String {
+    display_name(folder, entry.entry.analysis_path())
+}
+
+fn display_name(folder: &Path, path: &Path) -> String {
+    path.strip_prefix(folder)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
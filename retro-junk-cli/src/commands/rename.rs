@@ -7,10 +7,13 @@ use owo_colors::OwoColorize;
 use owo_colors::Stream::Stdout;
 
 use retro_junk_lib::rename::{
-    M3uRenameJob, MediaRenamePlan, RenameOptions, RenamePlan, RenameProgress, SerialWarningKind,
-    execute_media_renames, execute_renames, format_match_method, plan_m3u_action,
-    plan_media_renames, plan_renames,
+    ConflictStrategy, LinkRename, M3uRenameJob, MediaRenamePlan, RenameOptions, RenamePlan,
+    RenameProgress, SerialWarningKind, execute_media_renames, execute_renames, format_match_method,
+    plan_m3u_action, plan_media_renames, plan_renames,
 };
+use retro_junk_lib::rename_export::{ExportFormat, plan_export_rows, write_plan_export};
+use retro_junk_lib::report::FolderReport;
+use retro_junk_lib::sidecar::{self, SidecarDatMatch, SidecarFormat};
 use retro_junk_lib::util::default_media_dir;
 use retro_junk_lib::{AnalysisContext, Platform};
 
@@ -28,13 +31,56 @@ pub(crate) fn run_rename(
     quiet: bool,
     media_dir_override: Option<PathBuf>,
     no_media: bool,
+    export: Option<PathBuf>,
+    conflict_strategy: ConflictStrategy,
+    write_report: bool,
+    sidecar_format: Option<SidecarFormat>,
+    split_archives: bool,
+    archives: bool,
+    link_rename: LinkRename,
+    sanitize_names: bool,
+    create_m3u: bool,
+    companions: Option<Vec<String>>,
+    no_companions: bool,
+    use_trash: bool,
 ) -> Result<(), CliError> {
     let root_path = library_path;
 
+    let export_format = match &export {
+        Some(path) => Some(ExportFormat::from_path(path).ok_or_else(|| {
+            CliError::other(format!(
+                "Unsupported export format for {}. Use a .csv or .json file.",
+                path.display()
+            ))
+        })?),
+        None => None,
+    };
+    let mut export_rows = Vec::new();
+
     let rename_options = RenameOptions {
         hash_mode,
         dat_dir,
         limit,
+        conflict_strategy,
+        split_archives,
+        archives,
+        symlinks: ctx.symlink_policy(),
+        link_rename,
+        sanitize_names,
+        create_m3u,
+        companion_extensions: if no_companions {
+            Vec::new()
+        } else {
+            match companions {
+                Some(exts) => exts
+                    .iter()
+                    .map(|e| e.trim().trim_start_matches('.').to_ascii_lowercase())
+                    .filter(|e| !e.is_empty())
+                    .collect(),
+                None => RenameOptions::default().companion_extensions,
+            }
+        },
+        use_trash,
     };
 
     log::info!(
@@ -53,6 +99,12 @@ pub(crate) fn run_rename(
             "Dry run: no files will be renamed".if_supports_color(Stdout, |t| t.dimmed()),
         );
     }
+    if conflict_strategy != ConflictStrategy::Skip {
+        log::info!(
+            "{}",
+            format!("Conflicts: {}", conflict_strategy).if_supports_color(Stdout, |t| t.dimmed()),
+        );
+    }
     if let Some(n) = limit {
         log::info!(
             "{}",
@@ -174,10 +226,15 @@ pub(crate) fn run_rename(
 
                 print_rename_plan(&plan);
 
+                if export_format.is_some() {
+                    export_rows.extend(plan_export_rows(console.metadata.short_name, &plan));
+                }
+
                 // Plan media renames if media dir exists
+                let media_system = crate::output_system_name(ctx, cf);
                 let media_plan = effective_media_dir
                     .as_ref()
-                    .map(|media_dir| plan_media_renames(&plan, media_dir, &cf.folder_name));
+                    .map(|media_dir| plan_media_renames(&plan, media_dir, media_system));
 
                 if let Some(ref mp) = media_plan {
                     if mp.has_actions() {
@@ -188,7 +245,8 @@ pub(crate) fn run_rename(
                 let has_work = !plan.renames.is_empty()
                     || !plan.m3u_jobs.is_empty()
                     || !plan.broken_cue_files.is_empty()
-                    || !plan.broken_m3u_files.is_empty();
+                    || !plan.broken_m3u_files.is_empty()
+                    || !plan.archive_splits.is_empty();
                 let has_media_work = media_plan.as_ref().is_some_and(|mp| mp.has_actions());
                 if !dry_run && (has_work || has_media_work) {
                     // Prompt for confirmation (raw print — user interaction)
@@ -210,6 +268,9 @@ pub(crate) fn run_rename(
                     if total_renames > 0 {
                         parts.push(format!("{} renames", total_renames));
                     }
+                    if !plan.companion_renames.is_empty() {
+                        parts.push(format!("{} companion files", plan.companion_renames.len()));
+                    }
                     if m3u_count > 0 {
                         parts.push(format!("{} m3u updates", m3u_count));
                     }
@@ -217,6 +278,9 @@ pub(crate) fn run_rename(
                         let total = cue_count + m3u_fix_count;
                         parts.push(format!("{} reference fixes", total));
                     }
+                    if !plan.archive_splits.is_empty() {
+                        parts.push(format!("{} archive splits", plan.archive_splits.len()));
+                    }
                     if has_media_work {
                         let media_count = media_plan.as_ref().unwrap().renames.len();
                         parts.push(format!("{} media renames", media_count));
@@ -228,6 +292,12 @@ pub(crate) fn run_rename(
                     std::io::stdin().read_line(&mut input)?;
 
                     if input.trim().eq_ignore_ascii_case("y") {
+                        // Sidecars are written at the current paths and move
+                        // with their ROMs during execution.
+                        if let Some(format) = sidecar_format {
+                            write_match_sidecars(&plan, format, &mut total_errors);
+                        }
+
                         // Execute ROM renames
                         let summary = execute_renames(&plan);
                         total_renamed += summary.renamed;
@@ -240,6 +310,13 @@ pub(crate) fn run_rename(
                             "\u{2714}".if_supports_color(Stdout, |t| t.green()),
                             summary.renamed,
                         );
+                        if summary.companions_renamed > 0 {
+                            log::info!(
+                                "  {} {} companion files renamed",
+                                "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                                summary.companions_renamed,
+                            );
+                        }
                         if summary.m3u_folders_renamed > 0 {
                             log::info!(
                                 "  {} {} m3u folders renamed",
@@ -247,6 +324,13 @@ pub(crate) fn run_rename(
                                 summary.m3u_folders_renamed,
                             );
                         }
+                        if summary.m3u_folders_created > 0 {
+                            log::info!(
+                                "  {} {} m3u folders created",
+                                "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                                summary.m3u_folders_created,
+                            );
+                        }
                         if summary.m3u_playlists_written > 0 {
                             log::info!(
                                 "  {} {} m3u playlists written",
@@ -269,6 +353,30 @@ pub(crate) fn run_rename(
                                 ref_fixes,
                             );
                         }
+                        if summary.archives_split > 0 {
+                            log::info!(
+                                "  {} {} archives split",
+                                "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                                summary.archives_split,
+                            );
+                        }
+                        if summary.canonical_names_recorded > 0 {
+                            log::info!(
+                                "  {} {} sanitized names recorded",
+                                "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                                summary.canonical_names_recorded,
+                            );
+                        }
+                        if let Some(trash) = plan.trash.as_ref().filter(|t| t.dir().is_dir()) {
+                            log::info!(
+                                "  {} Replaced files kept in {}",
+                                "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                                trash
+                                    .dir()
+                                    .display()
+                                    .if_supports_color(Stdout, |t| t.dimmed()),
+                            );
+                        }
 
                         // Execute media renames
                         if let Some(ref mp) = media_plan {
@@ -289,6 +397,9 @@ pub(crate) fn run_rename(
                         log::info!("  {}", "Skipped".if_supports_color(Stdout, |t| t.dimmed()));
                     }
                 } else {
+                    if !dry_run && let Some(format) = sidecar_format {
+                        write_match_sidecars(&plan, format, &mut total_errors);
+                    }
                     total_already_correct += plan.already_correct.len();
                     total_unmatched += plan.unmatched.len();
                     total_conflicts.extend(
@@ -297,6 +408,23 @@ pub(crate) fn run_rename(
                             .map(|(_, msg): &(PathBuf, String)| msg.clone()),
                     );
                 }
+
+                if write_report {
+                    let report = FolderReport::from_rename_plan(
+                        console.metadata.platform_name,
+                        &cf.path,
+                        &plan,
+                        chrono::Utc::now(),
+                    );
+                    match report.write() {
+                        Ok(path) => log::info!(
+                            "  {} Report written to {}",
+                            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                            path.display(),
+                        ),
+                        Err(e) => total_errors.push(format!("Failed to write report: {}", e)),
+                    }
+                }
             }
             Err(e) => {
                 pb.finish_and_clear();
@@ -331,6 +459,17 @@ pub(crate) fn run_rename(
         return Ok(());
     }
 
+    if let (Some(path), Some(format)) = (&export, export_format) {
+        write_plan_export(path, format, &export_rows)
+            .map_err(|e| CliError::other(format!("Failed to write {}: {}", path.display(), e)))?;
+        log::info!(
+            "Exported {} plan entries to {}",
+            export_rows.len(),
+            path.display().if_supports_color(Stdout, |t| t.cyan()),
+        );
+        crate::log_blank();
+    }
+
     // Print overall summary
     log::info!("{}", "Summary:".if_supports_color(Stdout, |t| t.bold()));
     if total_renamed > 0 {
@@ -406,6 +545,27 @@ pub(crate) fn print_rename_plan(plan: &RenamePlan) {
         );
     }
 
+    // Companion files following their ROMs
+    for companion in &plan.companion_renames {
+        let source_name = companion
+            .source
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("?");
+        let target_name = companion
+            .target
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("?");
+        log::info!(
+            "    {} {} {} {}",
+            "+".if_supports_color(Stdout, |t| t.dimmed()),
+            source_name.if_supports_color(Stdout, |t| t.dimmed()),
+            "\u{2192}".if_supports_color(Stdout, |t| t.dimmed()),
+            target_name.if_supports_color(Stdout, |t| t.dimmed()),
+        );
+    }
+
     // Already correct
     if !plan.already_correct.is_empty() {
         log::info!(
@@ -418,7 +578,14 @@ pub(crate) fn print_rename_plan(plan: &RenamePlan) {
     // Unmatched
     for uf in &plan.unmatched {
         let name = uf.file.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-        if let Some(ref crc) = uf.crc32 {
+        if let Some(ref reason) = uf.mismatch_reason {
+            log::warn!(
+                "  {} {} (can't match: {})",
+                "\u{2718}".if_supports_color(Stdout, |t| t.yellow()),
+                name.if_supports_color(Stdout, |t| t.dimmed()),
+                reason,
+            );
+        } else if let Some(ref crc) = uf.crc32 {
             log::warn!(
                 "  {} {} (no match, CRC32: {})",
                 "?".if_supports_color(Stdout, |t| t.yellow()),
@@ -513,6 +680,15 @@ pub(crate) fn print_rename_plan(plan: &RenamePlan) {
                     hash_suffix,
                 );
             }
+            SerialWarningKind::Malformed { full_serial } => {
+                log::warn!(
+                    "  {} {}: serial \"{}\" is not in this platform's serial format{}",
+                    "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                    file_name.if_supports_color(Stdout, |t| t.dimmed()),
+                    full_serial,
+                    hash_suffix,
+                );
+            }
             SerialWarningKind::Missing => {
                 log::warn!(
                     "  {} {}: no serial found (expected for this platform){}",
@@ -527,6 +703,67 @@ pub(crate) fn print_rename_plan(plan: &RenamePlan) {
     // M3U jobs (disc renames + folder rename + playlist)
     print_m3u_jobs(&plan.m3u_jobs);
 
+    // Multi-disc sets that could be gathered into a new .m3u folder
+    for set in &plan.loose_disc_sets {
+        let folder = set
+            .source_folder
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("?");
+        log::info!(
+            "  {} {} loose discs could become {} {}",
+            "\u{1F4C1}".if_supports_color(Stdout, |t| t.yellow()),
+            set.discs.len(),
+            folder.if_supports_color(Stdout, |t| t.bold()),
+            "(--create-m3u)".if_supports_color(Stdout, |t| t.dimmed()),
+        );
+    }
+
+    // Multi-game archives to split
+    for split in &plan.archive_splits {
+        let name = split
+            .archive
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("?");
+        log::info!(
+            "  {} {} {}",
+            "\u{1F4E6}".if_supports_color(Stdout, |t| t.cyan()),
+            name.if_supports_color(Stdout, |t| t.bold()),
+            format!(
+                "(split {} ROMs, {} matched)",
+                split.entries.len(),
+                split.matched()
+            )
+            .if_supports_color(Stdout, |t| t.dimmed()),
+        );
+        for entry in &split.entries {
+            let target = entry
+                .target
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("?");
+            if entry.game_name.is_some() {
+                log::info!(
+                    "      {} {} {}",
+                    entry
+                        .entry
+                        .file_name()
+                        .if_supports_color(Stdout, |t| t.dimmed()),
+                    "\u{2192}".if_supports_color(Stdout, |t| t.green()),
+                    target.if_supports_color(Stdout, |t| t.bold()),
+                );
+            } else {
+                log::warn!(
+                    "      {} {} {}",
+                    "?".if_supports_color(Stdout, |t| t.yellow()),
+                    target.if_supports_color(Stdout, |t| t.dimmed()),
+                    "(no match, extracted as-is)".if_supports_color(Stdout, |t| t.dimmed()),
+                );
+            }
+        }
+    }
+
     // Broken CUE files (non-M3U dirs only)
     for cue_path in &plan.broken_cue_files {
         let name = cue_path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
@@ -576,6 +813,20 @@ fn print_media_rename_plan(plan: &MediaRenamePlan) {
 /// Print M3U jobs: disc renames, folder rename, and playlist actions.
 pub(crate) fn print_m3u_jobs(jobs: &[M3uRenameJob]) {
     for job in jobs {
+        if job.create_folder {
+            let folder = job
+                .source_folder
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("?");
+            log::info!(
+                "  {} Create {} {}",
+                "\u{1F4C1}".if_supports_color(Stdout, |t| t.green()),
+                folder.if_supports_color(Stdout, |t| t.bold()),
+                "(folder)".if_supports_color(Stdout, |t| t.dimmed()),
+            );
+        }
+
         // Show disc renames
         for disc in &job.discs {
             let target = job.source_folder.join(&disc.target_filename);
@@ -638,3 +889,22 @@ pub(crate) fn print_m3u_jobs(jobs: &[M3uRenameJob]) {
         }
     }
 }
+
+/// Record each matched file's DAT entry in its sidecar.
+fn write_match_sidecars(plan: &RenamePlan, format: SidecarFormat, errors: &mut Vec<String>) {
+    for m in &plan.matched {
+        let dat_match = SidecarDatMatch {
+            game_name: m.game_name.clone(),
+            method: format_match_method(&m.matched_by).to_string(),
+        };
+        if let Err(e) = sidecar::update_sidecar(&m.source, format, |s| {
+            s.dat_match = Some(dat_match);
+        }) {
+            errors.push(format!(
+                "Failed to write sidecar for {}: {}",
+                m.source.display(),
+                e
+            ));
+        }
+    }
+}
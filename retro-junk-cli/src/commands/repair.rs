@@ -19,6 +19,8 @@ pub(crate) fn run_repair(
     ctx: &AnalysisContext,
     dry_run: bool,
     no_backup: bool,
+    use_trash: bool,
+    strip_headers: bool,
     consoles: Option<Vec<Platform>>,
     limit: Option<usize>,
     library_path: PathBuf,
@@ -31,6 +33,8 @@ pub(crate) fn run_repair(
         dat_dir,
         limit,
         create_backup: !no_backup,
+        use_trash,
+        strip_headers,
     };
 
     log::warn!(
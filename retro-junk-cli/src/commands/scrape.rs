@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -6,12 +7,20 @@ use log::LevelFilter;
 use owo_colors::OwoColorize;
 use owo_colors::Stream::Stdout;
 
+use retro_junk_lib::metrics::{self, Metrics};
+use retro_junk_lib::notify::{NotifyTarget, RunSummary};
+use retro_junk_lib::sidecar::{self, SidecarFormat, SidecarScrape};
 use retro_junk_lib::{AnalysisContext, Platform};
+use retro_junk_scraper::LogEntry;
 
 use crate::CliError;
+use crate::commands::send_notification;
 use crate::scan_folders;
 use crate::spinner;
 
+/// Spinner key for the image worker progress line.
+const IMAGE_SPINNER: usize = usize::MAX - 1;
+
 /// Try to enrich scraped games with cover_title from the catalog database.
 ///
 /// Looks up each game by title in the catalog DB. If a release with a
@@ -116,6 +125,8 @@ pub(crate) fn run_scrape(
     media_dir: Option<PathBuf>,
     _frontend: String,
     region: String,
+    media_regions: Vec<String>,
+    asset_naming: retro_junk_scraper::AssetNaming,
     language: String,
     language_fallback: String,
     force_full_hash: bool,
@@ -125,10 +136,15 @@ pub(crate) fn run_scrape(
     no_miximage: bool,
     force_redownload: bool,
     threads: Option<usize>,
+    image_threads: Option<usize>,
+    sidecar_format: Option<SidecarFormat>,
+    metrics_addr: Option<String>,
     library_path: PathBuf,
     quiet: bool,
+    notify: Option<&NotifyTarget>,
 ) -> Result<(), CliError> {
     let root_path = library_path;
+    let mut run_summary = RunSummary::new("scrape");
 
     // Build scrape options
     let mut options = retro_junk_scraper::ScrapeOptions::new(root_path.clone());
@@ -140,7 +156,9 @@ pub(crate) fn run_scrape(
     options.skip_existing = skip_existing;
     options.no_log = no_log;
     options.no_miximage = no_miximage;
+    options.image_threads = image_threads;
     options.force_redownload = force_redownload;
+    options.asset_naming = asset_naming;
     options.limit = limit;
 
     // Load miximage layout unless disabled
@@ -169,6 +187,16 @@ pub(crate) fn run_scrape(
     if let Some(ref types) = media_types {
         options.asset_selection = retro_junk_scraper::AssetSelection::from_names(types);
     }
+    let invalid = options
+        .asset_selection
+        .apply_region_overrides(&media_regions, retro_junk_import::known_catalog_region);
+    if !invalid.is_empty() {
+        return Err(CliError::other(format!(
+            "Invalid --media-region value(s): {}. Expected TYPE=REGION (e.g., covers=jp) \
+             with a region of us, eu, jp, au, kr, cn, tw, br, or wor.",
+            invalid.join(", ")
+        )));
+    }
 
     log::info!(
         "Scraping ROMs in: {}",
@@ -207,6 +235,18 @@ pub(crate) fn run_scrape(
 
     rt.block_on(async {
         let (client, max_workers) = connect_screenscraper(threads, quiet).await?;
+        if let Some(addr) = &metrics_addr {
+            let metrics = Metrics::new();
+            let bound = metrics::serve(addr.as_str(), Arc::clone(&metrics)).map_err(|e| {
+                CliError::other(format!("Failed to serve metrics on {}: {}", addr, e))
+            })?;
+            log::info!(
+                "{}",
+                format!("Metrics at http://{}/metrics", bound)
+                    .if_supports_color(Stdout, |t| t.dimmed()),
+            );
+            client.report_quota_to(metrics).await;
+        }
 
         let scan = match scan_folders(ctx, &root_path, &consoles) {
             Some(s) => s,
@@ -218,6 +258,10 @@ pub(crate) fn run_scrape(
         let mut total_media = 0usize;
         let mut total_errors = 0usize;
         let mut total_unidentified = 0usize;
+        // Games already written per ROM folder, so consoles sharing a family
+        // folder produce one combined gamelist instead of overwriting it.
+        let mut folder_games: HashMap<PathBuf, Vec<retro_junk_frontend::ScrapedGame>> =
+            HashMap::new();
 
         for cf in &scan.matches {
             let console = ctx.get_by_platform(cf.platform).ok_or_else(|| {
@@ -225,6 +269,7 @@ pub(crate) fn run_scrape(
             })?;
             let path = &cf.path;
             let folder_name = &cf.folder_name;
+            let system_name = crate::output_system_name(ctx, cf);
 
             // Check if this system has a ScreenScraper ID
             if retro_junk_scraper::screenscraper_system_id(cf.platform).is_none() {
@@ -245,9 +290,12 @@ pub(crate) fn run_scrape(
                 format!("({})", folder_name).if_supports_color(Stdout, |t| t.dimmed()),
             );
 
-            // Set up MultiProgress with N spinner slots
-            let mut pool = spinner::SpinnerPool::new(max_workers, quiet, true);
+            // Set up MultiProgress with a spinner slot per API worker, plus
+            // one for image work
+            let mut pool = spinner::SpinnerPool::new(max_workers + 1, quiet, true);
             let mut scan_total = 0usize;
+            let mut images_queued = 0usize;
+            let mut images_done = 0usize;
 
             let (event_tx, event_rx) =
                 tokio::sync::mpsc::unbounded_channel::<retro_junk_scraper::ScrapeEvent>();
@@ -255,9 +303,10 @@ pub(crate) fn run_scrape(
             let scrape_future = retro_junk_scraper::scrape_folder(
                 &client,
                 path,
-                console.analyzer.as_ref(),
+                ctx,
+                console.metadata.platform,
                 &options,
-                folder_name,
+                system_name,
                 max_workers,
                 event_tx,
             );
@@ -352,6 +401,27 @@ pub(crate) fn run_scrape(
                         );
                         pool.release(index);
                     }
+                    retro_junk_scraper::ScrapeEvent::ImageQueued { queued, .. } => {
+                        if images_queued == 0 {
+                            pool.claim(IMAGE_SPINNER, String::new());
+                        }
+                        images_queued = queued;
+                        pool.update(
+                            IMAGE_SPINNER,
+                            format!("Miximages [{}/{}]", images_done, images_queued),
+                        );
+                    }
+                    retro_junk_scraper::ScrapeEvent::ImageFinished {
+                        ref file,
+                        completed,
+                        ..
+                    } => {
+                        images_done = completed;
+                        pool.update(
+                            IMAGE_SPINNER,
+                            format!("Miximages [{}/{}] {}", images_done, images_queued, file),
+                        );
+                    }
                     retro_junk_scraper::ScrapeEvent::GameGrouped { .. } => {
                         // Grouped discs happen after the concurrent phase; no spinner
                     }
@@ -377,6 +447,17 @@ pub(crate) fn run_scrape(
                     total_media += summary.media_downloaded;
                     total_errors += summary.total_errors;
                     total_unidentified += summary.total_unidentified;
+                    for entry in result.log.entries() {
+                        match entry {
+                            LogEntry::Unidentified { file, .. } => {
+                                run_summary.problem(format!("{}/{}: unidentified", folder_name, file))
+                            }
+                            LogEntry::Error { file, message } => {
+                                run_summary.problem(format!("{}/{}: {}", folder_name, file, message))
+                            }
+                            _ => {}
+                        }
+                    }
 
                     let has_issues = summary.total_unidentified > 0 || summary.total_errors > 0;
 
@@ -388,12 +469,13 @@ pub(crate) fn run_scrape(
                     // Print per-system summary
                     if summary.total_success > 0 {
                         log::info!(
-                            "  {} {} games scraped (serial: {}, filename: {}, hash: {})",
+                            "  {} {} games scraped (serial: {}, filename: {}, hash: {}, parent: {})",
                             "\u{2714}".if_supports_color(Stdout, |t| t.green()),
                             summary.total_success,
                             summary.by_serial,
                             summary.by_filename,
                             summary.by_hash,
+                            summary.by_parent,
                         );
                     }
                     if summary.total_grouped > 0 {
@@ -493,12 +575,14 @@ pub(crate) fn run_scrape(
 
                     // Write metadata
                     if !games.is_empty() && !dry_run {
-                        let system_metadata_dir = options.metadata_dir.join(folder_name);
-                        let system_media_dir = options.media_dir.join(folder_name);
+                        let system_metadata_dir = options.metadata_dir.join(system_name);
+                        let system_media_dir = options.media_dir.join(system_name);
+                        let combined = folder_games.entry(path.clone()).or_default();
+                        combined.extend(games.iter().cloned());
 
                         use retro_junk_frontend::Frontend;
                         if let Err(e) = esde.write_metadata(
-                            &games,
+                            combined,
                             path,
                             &system_metadata_dir,
                             &system_media_dir,
@@ -515,6 +599,10 @@ pub(crate) fn run_scrape(
                                 system_metadata_dir.display(),
                             );
                         }
+
+                        if let Some(format) = sidecar_format {
+                            write_scrape_sidecars(&games, path, format);
+                        }
                     }
 
                     // Write scrape log
@@ -538,11 +626,17 @@ pub(crate) fn run_scrape(
                         e,
                     );
                     total_errors += 1;
+                    run_summary.problem(format!("{}: {}", folder_name, e));
                 }
             }
             crate::log_blank();
         }
 
+        run_summary.count("games scraped", total_games as u64);
+        run_summary.count("media files", total_media as u64);
+        run_summary.count("unidentified", total_unidentified as u64);
+        run_summary.count("errors", total_errors as u64);
+
         // Print overall summary
         if total_games > 0 || total_errors > 0 || total_unidentified > 0 {
             log::info!("{}", "Summary:".if_supports_color(Stdout, |t| t.bold()));
@@ -582,5 +676,44 @@ pub(crate) fn run_scrape(
         Ok::<(), CliError>(())
     })?;
 
+    // The notification client blocks, so it's sent outside the runtime
+    send_notification(notify, run_summary);
     Ok(())
 }
+
+/// Record scraped metadata in each ROM's sidecar.
+fn write_scrape_sidecars(
+    games: &[retro_junk_frontend::ScrapedGame],
+    rom_dir: &std::path::Path,
+    format: SidecarFormat,
+) {
+    for game in games {
+        let rom = rom_dir.join(&game.rom_filename);
+        if !rom.is_file() {
+            continue;
+        }
+        let scrape = SidecarScrape {
+            name: game.name.clone(),
+            description: game.description.clone(),
+            developer: game.developer.clone(),
+            publisher: game.publisher.clone(),
+            genre: game.genre.clone(),
+            players: game.players.clone(),
+            rating: game.rating,
+            release_date: game.release_date.clone(),
+            media: game
+                .assets
+                .iter()
+                .map(|(asset, path)| (asset.to_string().replace(' ', "_"), path.clone()))
+                .collect(),
+        };
+        if let Err(e) = sidecar::update_sidecar(&rom, format, |s| s.scrape = Some(scrape)) {
+            log::warn!(
+                "  {} Failed to write sidecar for {}: {}",
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                game.rom_filename,
+                e,
+            );
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_frontend::sync::{self, SyncPlan};
+use retro_junk_lib::{AnalysisContext, Platform};
+
+use crate::CliError;
+
+/// Run the sync command.
+pub(crate) fn run_sync(
+    ctx: &AnalysisContext,
+    dry_run: bool,
+    consoles: Option<Vec<Platform>>,
+    library_path: PathBuf,
+    metadata_dir: Option<PathBuf>,
+    media_dir: Option<PathBuf>,
+) -> Result<(), CliError> {
+    let metadata_dir =
+        metadata_dir.unwrap_or_else(|| retro_junk_lib::util::default_metadata_dir(&library_path));
+    let media_dir =
+        media_dir.unwrap_or_else(|| retro_junk_lib::util::default_media_dir(&library_path));
+
+    log::info!("Syncing metadata with: {}", library_path.display());
+    log::info!("  Metadata: {}", metadata_dir.display());
+    log::info!("  Media:    {}", media_dir.display());
+    if dry_run {
+        log::info!(
+            "{}",
+            "Dry run: nothing will be changed".if_supports_color(Stdout, |t| t.yellow()),
+        );
+    }
+    crate::log_blank();
+
+    let scan = match crate::scan_folders(ctx, &library_path, &consoles) {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    let mut checked: Vec<&PathBuf> = Vec::new();
+    let mut total_entries = 0usize;
+    let mut total_media = 0usize;
+    for cf in &scan.matches {
+        // Family folders are matched once per member console
+        if checked.contains(&&cf.path) {
+            continue;
+        }
+        checked.push(&cf.path);
+
+        let system_name = crate::output_system_name(ctx, cf);
+        let plan = match sync::plan_sync(
+            &cf.path,
+            &metadata_dir.join(system_name),
+            &media_dir.join(system_name),
+        ) {
+            Ok(plan) => plan,
+            Err(e) => {
+                log::warn!(
+                    "  {} {}: {}",
+                    "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                    cf.folder_name,
+                    e,
+                );
+                continue;
+            }
+        };
+        if plan.is_empty() {
+            continue;
+        }
+
+        log::info!(
+            "{} {}",
+            cf.folder_name.if_supports_color(Stdout, |t| t.bold()),
+            format!("({})", system_name).if_supports_color(Stdout, |t| t.dimmed()),
+        );
+        print_plan(&plan);
+
+        if !dry_run && let Err(e) = sync::apply_sync(&plan) {
+            log::warn!(
+                "  {} Error pruning: {}",
+                "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                e,
+            );
+            continue;
+        }
+        total_entries += plan.stale_entries.len();
+        total_media += plan.orphaned_media.len();
+    }
+
+    if total_entries == 0 && total_media == 0 {
+        log::info!(
+            "{} Metadata is in sync with the library.",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+        );
+    } else {
+        crate::log_blank();
+        log::info!(
+            "{} {} {} gamelist entries and {} media files.",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            if dry_run { "Would prune" } else { "Pruned" },
+            total_entries,
+            total_media,
+        );
+    }
+
+    Ok(())
+}
+
+fn print_plan(plan: &SyncPlan) {
+    for path in &plan.stale_entries {
+        log::info!(
+            "  {} gamelist entry {}",
+            "-".if_supports_color(Stdout, |t| t.red()),
+            path,
+        );
+    }
+    for path in &plan.orphaned_media {
+        log::info!(
+            "  {} {}",
+            "-".if_supports_color(Stdout, |t| t.red()),
+            path.display(),
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_lib::trash::Trash;
+
+use crate::CliError;
+
+/// How far below the library root to look for console folders' trash.
+const TRASH_SEARCH_DEPTH: usize = 3;
+
+/// List the files kept in each trash folder of the library.
+pub(crate) fn run_trash_list(library_path: &Path) -> Result<(), CliError> {
+    let trashes = Trash::find_all(library_path, TRASH_SEARCH_DEPTH);
+    if trashes.is_empty() {
+        log::info!(
+            "{}",
+            "Nothing in the trash.".if_supports_color(Stdout, |t| t.dimmed()),
+        );
+        return Ok(());
+    }
+
+    let mut total = 0;
+    for trash in &trashes {
+        let entries = trash.entries()?;
+        log::info!(
+            "{}",
+            trash
+                .dir()
+                .display()
+                .if_supports_color(Stdout, |t| t.bold()),
+        );
+        for entry in &entries {
+            log::info!(
+                "  {} {} {}",
+                entry.original.display(),
+                format!("({})", entry.reason).if_supports_color(Stdout, |t| t.dimmed()),
+                entry.trashed_at.if_supports_color(Stdout, |t| t.dimmed()),
+            );
+        }
+        total += entries.len();
+        crate::log_blank();
+    }
+    log::info!("Total: {} files", total);
+    Ok(())
+}
+
+/// Put every trashed file in the library back where it came from.
+pub(crate) fn run_trash_restore(library_path: &Path) -> Result<(), CliError> {
+    let trashes = Trash::find_all(library_path, TRASH_SEARCH_DEPTH);
+    if trashes.is_empty() {
+        log::info!(
+            "{}",
+            "Nothing in the trash.".if_supports_color(Stdout, |t| t.dimmed()),
+        );
+        return Ok(());
+    }
+
+    let mut restored = 0;
+    let mut errors = Vec::new();
+    for trash in &trashes {
+        let summary = trash.restore()?;
+        restored += summary.restored;
+        errors.extend(summary.errors);
+    }
+
+    log::info!(
+        "  {} {} files restored",
+        "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+        restored,
+    );
+    for error in &errors {
+        log::warn!(
+            "  {} {}",
+            "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+            error,
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,468 @@
+use std::path::{Path, PathBuf};
+
+use indicatif::{ProgressBar, ProgressStyle};
+use log::Level;
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_lib::context::AnalyzePathError;
+use retro_junk_lib::dat_verify::{
+    FolderVerification, VerifyOptions, VerifyProgress, VerifyStatus, verify_folder,
+    verify_report_rows, verify_source, write_verify_report,
+};
+use retro_junk_lib::notify::{NotifyTarget, RunSummary};
+use retro_junk_lib::remote::Source;
+use retro_junk_lib::rename_export::ExportFormat;
+use retro_junk_lib::report::FolderReport;
+use retro_junk_lib::{AnalysisContext, AnalysisOptions, Platform};
+
+use crate::CliError;
+
+const STATUSES: [VerifyStatus; 4] = [
+    VerifyStatus::Verified,
+    VerifyStatus::BadDump,
+    VerifyStatus::Overdump,
+    VerifyStatus::Unknown,
+];
+
+/// Run the verify command.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_verify(
+    ctx: &AnalysisContext,
+    consoles: Option<Vec<Platform>>,
+    limit: Option<usize>,
+    library_path: PathBuf,
+    dat_dir: Option<PathBuf>,
+    problems_only: bool,
+    report: Option<PathBuf>,
+    write_folder_report: bool,
+    quiet: bool,
+    notify: Option<&NotifyTarget>,
+) -> Result<(), CliError> {
+    let root_path = library_path;
+
+    let report_format = report_format(report.as_deref())?;
+
+    let options = VerifyOptions {
+        dat_dir,
+        limit,
+        symlinks: ctx.symlink_policy(),
+    };
+
+    log::info!(
+        "Verifying ROMs in: {}",
+        root_path.display().if_supports_color(Stdout, |t| t.cyan()),
+    );
+    if let Some(n) = limit {
+        log::info!(
+            "{}",
+            format!("Limit: {} ROMs per console", n).if_supports_color(Stdout, |t| t.dimmed()),
+        );
+    }
+    crate::log_blank();
+
+    let scan = match crate::scan_folders(ctx, &root_path, &consoles) {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    let mut summary = RunSummary::new("verify");
+    let mut report_rows = Vec::new();
+    let mut totals = [0usize; STATUSES.len()];
+    let mut total_errors = 0usize;
+    let mut found_any = false;
+
+    for cf in &scan.matches {
+        let console = ctx.get_by_platform(cf.platform).ok_or_else(|| {
+            CliError::unknown_system(format!("No analyzer for platform {:?}", cf.platform))
+        })?;
+
+        if !console.analyzer.has_dat_support() {
+            log::warn!(
+                "  {} Skipping \"{}\" — no DAT support yet",
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                cf.folder_name,
+            );
+            continue;
+        }
+
+        found_any = true;
+
+        let pb = spinner(quiet);
+        let progress_callback = |progress| show_progress(&pb, progress);
+
+        match verify_folder(
+            &cf.path,
+            console.analyzer.as_ref(),
+            &options,
+            &progress_callback,
+        ) {
+            Ok(verification) => {
+                pb.finish_and_clear();
+
+                let has_issues = verification.files.len()
+                    != verification.count(&VerifyStatus::Verified)
+                    || !verification.errors.is_empty();
+                let header_level = if has_issues { Level::Warn } else { Level::Info };
+                log::log!(
+                    header_level,
+                    "{} {}",
+                    console
+                        .metadata
+                        .platform_name
+                        .if_supports_color(Stdout, |t| t.bold()),
+                    format!("({})", cf.folder_name).if_supports_color(Stdout, |t| t.dimmed()),
+                );
+
+                print_verification(&cf.path, &verification, problems_only);
+
+                for (total, status) in totals.iter_mut().zip(&STATUSES) {
+                    *total += verification.count(status);
+                }
+                total_errors += verification.errors.len();
+                for file in &verification.files {
+                    if file.status != VerifyStatus::Verified {
+                        summary.problem(format!(
+                            "{}: {}",
+                            file.status.label().to_lowercase(),
+                            file.path.display()
+                        ));
+                    }
+                }
+                for (path, msg) in &verification.errors {
+                    summary.problem(format!("error: {}: {}", path.display(), msg));
+                }
+                if report_format.is_some() {
+                    report_rows.extend(verify_report_rows(
+                        console.metadata.short_name,
+                        &verification,
+                    ));
+                }
+                if write_folder_report {
+                    let folder_report = FolderReport::from_verification(
+                        console.metadata.platform_name,
+                        &cf.path,
+                        &verification,
+                        chrono::Utc::now(),
+                    );
+                    match folder_report.write() {
+                        Ok(path) => log::info!(
+                            "  {} Report written to {}",
+                            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                            path.display(),
+                        ),
+                        Err(e) => {
+                            log::warn!(
+                                "  {} Failed to write report: {}",
+                                "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                                e,
+                            );
+                            summary.problem(format!("Failed to write report: {}", e));
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                pb.finish_and_clear();
+                crate::log_dat_error(
+                    console.metadata.platform_name,
+                    &cf.folder_name,
+                    console.metadata.short_name,
+                    &e,
+                );
+            }
+        }
+        crate::log_blank();
+    }
+
+    if scan.matches.is_empty() || !found_any {
+        log::info!(
+            "{}",
+            "No console folders with DAT support found.".if_supports_color(Stdout, |t| t.dimmed()),
+        );
+        return Ok(());
+    }
+
+    if let (Some(path), Some(format)) = (&report, report_format) {
+        write_verify_report(path, format, &report_rows)
+            .map_err(|e| CliError::other(format!("Failed to write {}: {}", path.display(), e)))?;
+        log::info!(
+            "Wrote {} entries to {}",
+            report_rows.len(),
+            path.display().if_supports_color(Stdout, |t| t.cyan()),
+        );
+        crate::log_blank();
+    }
+
+    // Print overall summary
+    log::info!("{}", "Summary:".if_supports_color(Stdout, |t| t.bold()));
+    for (status, &count) in STATUSES.iter().zip(&totals) {
+        summary.count(&status.label().to_lowercase(), count as u64);
+        if count == 0 {
+            continue;
+        }
+        match status {
+            VerifyStatus::Verified => log::info!(
+                "  {} {} verified",
+                "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                count,
+            ),
+            VerifyStatus::BadDump => log::warn!(
+                "  {} {} bad dump{}",
+                "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                count,
+                if count == 1 { "" } else { "s" },
+            ),
+            VerifyStatus::Overdump => log::warn!(
+                "  {} {} overdump{}",
+                "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                count,
+                if count == 1 { "" } else { "s" },
+            ),
+            VerifyStatus::Unknown => log::warn!(
+                "  {} {} unknown",
+                "?".if_supports_color(Stdout, |t| t.yellow()),
+                count,
+            ),
+        }
+    }
+    if total_errors > 0 {
+        summary.count("errors", total_errors as u64);
+        log::warn!(
+            "  {} {} could not be read",
+            "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+            total_errors,
+        );
+    }
+
+    crate::commands::send_notification(notify, summary);
+    Ok(())
+}
+
+/// Verify one file given as a local path, an http(s) URL, or an SMB share.
+///
+/// The platform is detected as `analyze` detects it, among `consoles` when
+/// given. Remote files are read on demand rather than copied first.
+pub(crate) fn run_verify_input(
+    ctx: &AnalysisContext,
+    input: &str,
+    consoles: Option<Vec<Platform>>,
+    dat_dir: Option<PathBuf>,
+    report: Option<PathBuf>,
+    quiet: bool,
+    notify: Option<&NotifyTarget>,
+) -> Result<(), CliError> {
+    let report_format = report_format(report.as_deref())?;
+    let source = Source::parse(input);
+    if source == Source::Stdin {
+        return Err(CliError::other(
+            "verify can't read stdin; pass a path or URL instead",
+        ));
+    }
+    if source.is_remote() {
+        log::info!("Reading from {}", source);
+    }
+
+    let mut reader = source
+        .open()
+        .map_err(|e| CliError::other(format!("Failed to open {}: {}", source, e)))?;
+    let name = source.file_name();
+    let options = AnalysisOptions {
+        file_path: source.local_path(),
+        ..AnalysisOptions::new().quick(true)
+    };
+    let platform = match ctx.analyze_reader(
+        reader.as_mut(),
+        Path::new(&name),
+        consoles.as_deref(),
+        &options,
+    ) {
+        Ok((platform, _)) => platform,
+        Err(AnalyzePathError::NotRecognized(_)) => {
+            return Err(CliError::analysis(format!("{}: not recognized", name)));
+        }
+        Err(e) => return Err(CliError::analysis(format!("{}: {}", name, e))),
+    };
+    drop(reader);
+    let console = ctx.get_by_platform(platform).ok_or_else(|| {
+        CliError::unknown_system(format!("No analyzer for platform {:?}", platform))
+    })?;
+    if !console.analyzer.has_dat_support() {
+        return Err(CliError::other(format!(
+            "{} has no DAT support yet",
+            console.metadata.platform_name
+        )));
+    }
+
+    let options = VerifyOptions {
+        dat_dir,
+        limit: None,
+        symlinks: ctx.symlink_policy(),
+    };
+    let pb = spinner(quiet);
+    let verification = verify_source(&source, console.analyzer.as_ref(), &options, &|progress| {
+        show_progress(&pb, progress)
+    });
+    pb.finish_and_clear();
+    let verification = verification.map_err(|e| {
+        CliError::other(format!(
+            "Failed to load {} DATs: {}",
+            console.metadata.platform_name, e
+        ))
+    })?;
+
+    log::info!(
+        "{}",
+        console
+            .metadata
+            .platform_name
+            .if_supports_color(Stdout, |t| t.bold()),
+    );
+    print_verification(Path::new(""), &verification, false);
+
+    let mut summary = RunSummary::new("verify");
+    for status in &STATUSES {
+        summary.count(
+            &status.label().to_lowercase(),
+            verification.count(status) as u64,
+        );
+    }
+    for file in &verification.files {
+        if file.status != VerifyStatus::Verified {
+            summary.problem(format!(
+                "{}: {}",
+                file.status.label().to_lowercase(),
+                file.path.display()
+            ));
+        }
+    }
+    for (path, msg) in &verification.errors {
+        summary.problem(format!("error: {}: {}", path.display(), msg));
+    }
+    if let (Some(path), Some(format)) = (&report, report_format) {
+        let rows = verify_report_rows(console.metadata.short_name, &verification);
+        write_verify_report(path, format, &rows)
+            .map_err(|e| CliError::other(format!("Failed to write {}: {}", path.display(), e)))?;
+    }
+    crate::commands::send_notification(notify, summary);
+    Ok(())
+}
+
+/// The report format for `--report`'s file extension.
+fn report_format(report: Option<&Path>) -> Result<Option<ExportFormat>, CliError> {
+    report
+        .map(|path| {
+            ExportFormat::from_path(path).ok_or_else(|| {
+                CliError::other(format!(
+                    "Unsupported report format for {}. Use a .csv or .json file.",
+                    path.display()
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// A spinner for verification progress, hidden when `quiet`.
+fn spinner(quiet: bool) -> ProgressBar {
+    if quiet {
+        return ProgressBar::hidden();
+    }
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(
+        ProgressStyle::with_template("  {spinner:.cyan} {msg}")
+            .expect("static pattern")
+            .tick_chars("/-\\|"),
+    );
+    pb
+}
+
+fn show_progress(pb: &ProgressBar, progress: VerifyProgress) {
+    match progress {
+        VerifyProgress::Scanning { file_count } => {
+            pb.set_message(format!("Found {file_count} ROM files"));
+            pb.tick();
+        }
+        VerifyProgress::Hashing {
+            ref file_name,
+            file_index,
+            total,
+            bytes_done,
+            bytes_total,
+        } => {
+            let percent = (bytes_done * 100).checked_div(bytes_total).unwrap_or(0);
+            pb.set_message(format!(
+                "[{}/{}] Hashing {} ({}%)",
+                file_index + 1,
+                total,
+                file_name,
+                percent
+            ));
+            pb.tick();
+        }
+        VerifyProgress::Done => {
+            pb.finish_and_clear();
+        }
+    }
+}
+
+/// Print each file's status for a single console.
+fn print_verification(folder: &Path, verification: &FolderVerification, problems_only: bool) {
+    let display_name = |path: &Path| -> String {
+        path.strip_prefix(folder)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+
+    for file in &verification.files {
+        let name = display_name(&file.path);
+        match file.status {
+            VerifyStatus::Verified => {
+                if !problems_only {
+                    log::info!(
+                        "  {} {} {}",
+                        "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                        name,
+                        format!("[{}]", file.game_name.as_deref().unwrap_or("?"))
+                            .if_supports_color(Stdout, |t| t.dimmed()),
+                    );
+                }
+            }
+            ref status => {
+                let glyph = match status {
+                    VerifyStatus::BadDump => "\u{2718}",
+                    VerifyStatus::Overdump => "\u{26A0}",
+                    _ => "?",
+                };
+                let mut line = format!(
+                    "  {} {} {}",
+                    glyph.if_supports_color(Stdout, |t| t.yellow()),
+                    name,
+                    status.label().if_supports_color(Stdout, |t| t.yellow()),
+                );
+                if let Some(detail) = &file.detail {
+                    line.push_str(&format!(
+                        " {}",
+                        format!("({})", detail).if_supports_color(Stdout, |t| t.dimmed())
+                    ));
+                }
+                if !file.crc32.is_empty() {
+                    line.push_str(&format!(
+                        " {}",
+                        format!("CRC32 {}", file.crc32).if_supports_color(Stdout, |t| t.dimmed())
+                    ));
+                }
+                log::warn!("{}", line);
+            }
+        }
+    }
+
+    for (path, msg) in &verification.errors {
+        log::warn!(
+            "  {} {}: {}",
+            "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+            display_name(path).if_supports_color(Stdout, |t| t.dimmed()),
+            msg,
+        );
+    }
+}
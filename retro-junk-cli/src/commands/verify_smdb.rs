@@ -0,0 +1,164 @@
+use std::path::{Path, PathBuf};
+
+use indicatif::{ProgressBar, ProgressStyle};
+use owo_colors::OwoColorize;
+use owo_colors::Stream::Stdout;
+
+use retro_junk_lib::notify::{NotifyTarget, RunSummary};
+use retro_junk_lib::smdb::{Smdb, SmdbStatus, apply_moves, verify_against_smdb};
+
+use crate::CliError;
+
+/// Check a folder tree against an .smdb listing, optionally moving
+/// misplaced files to their listed paths.
+pub(crate) fn run_verify_smdb(
+    root_path: PathBuf,
+    smdb_path: &Path,
+    move_files: bool,
+    problems_only: bool,
+    quiet: bool,
+    notify: Option<&NotifyTarget>,
+) -> Result<(), CliError> {
+    let smdb = Smdb::load(smdb_path)
+        .map_err(|e| CliError::other(format!("Failed to read {}: {}", smdb_path.display(), e)))?;
+
+    log::info!(
+        "Checking {} against {} ({} entries)",
+        root_path.display().if_supports_color(Stdout, |t| t.cyan()),
+        smdb_path.display().if_supports_color(Stdout, |t| t.cyan()),
+        smdb.entries.len(),
+    );
+    crate::log_blank();
+
+    let pb = if quiet {
+        ProgressBar::hidden()
+    } else {
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(
+            ProgressStyle::with_template("  {spinner:.cyan} {msg}")
+                .expect("static pattern")
+                .tick_chars("/-\\|"),
+        );
+        pb
+    };
+    let verification = verify_against_smdb(&root_path, &smdb, &|index, total, path| {
+        pb.set_message(format!("[{}/{}] Hashing {}", index + 1, total, path));
+        pb.tick();
+    })
+    .map_err(|e| CliError::other(format!("Failed to scan {}: {}", root_path.display(), e)))?;
+    pb.finish_and_clear();
+
+    let mut summary = RunSummary::new("verify");
+    for file in &verification.files {
+        match &file.status {
+            SmdbStatus::InPlace => {
+                if !problems_only {
+                    log::info!(
+                        "  {} {}",
+                        "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+                        file.path,
+                    );
+                }
+            }
+            SmdbStatus::Misplaced { target } => {
+                log::warn!(
+                    "  {} {} {} {}",
+                    "\u{2192}".if_supports_color(Stdout, |t| t.yellow()),
+                    file.path,
+                    "should be".if_supports_color(Stdout, |t| t.dimmed()),
+                    target,
+                );
+                summary.problem(format!("misplaced: {} -> {}", file.path, target));
+            }
+            SmdbStatus::Duplicate { of } => {
+                log::warn!(
+                    "  {} {} {}",
+                    "\u{26A0}".if_supports_color(Stdout, |t| t.yellow()),
+                    file.path,
+                    format!("(copy of {})", of).if_supports_color(Stdout, |t| t.dimmed()),
+                );
+                summary.problem(format!("duplicate: {}", file.path));
+            }
+            SmdbStatus::Unknown => {
+                log::warn!(
+                    "  {} {} {}",
+                    "?".if_supports_color(Stdout, |t| t.yellow()),
+                    file.path,
+                    "not in SMDB".if_supports_color(Stdout, |t| t.yellow()),
+                );
+                summary.problem(format!("unknown: {}", file.path));
+            }
+        }
+    }
+    for path in &verification.missing {
+        log::warn!(
+            "  {} {} {}",
+            "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+            path,
+            "missing".if_supports_color(Stdout, |t| t.red()),
+        );
+        summary.problem(format!("missing: {}", path));
+    }
+    for (path, msg) in &verification.errors {
+        log::warn!(
+            "  {} {}: {}",
+            "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+            path.if_supports_color(Stdout, |t| t.dimmed()),
+            msg,
+        );
+        summary.problem(format!("error: {}: {}", path, msg));
+    }
+    crate::log_blank();
+
+    let misplaced = verification.count(&SmdbStatus::Misplaced {
+        target: String::new(),
+    });
+    if move_files && misplaced > 0 {
+        let moves = apply_moves(&root_path, &verification);
+        log::info!(
+            "{} Moved {} file{} into place",
+            "\u{2714}".if_supports_color(Stdout, |t| t.green()),
+            moves.moved,
+            if moves.moved == 1 { "" } else { "s" },
+        );
+        for (path, msg) in &moves.failed {
+            log::warn!(
+                "  {} Could not move {}: {}",
+                "\u{2718}".if_supports_color(Stdout, |t| t.red()),
+                path,
+                msg,
+            );
+        }
+        summary.count("moved", moves.moved as u64);
+        crate::log_blank();
+    }
+
+    log::info!("{}", "Summary:".if_supports_color(Stdout, |t| t.bold()));
+    let counts = [
+        ("in place", verification.count(&SmdbStatus::InPlace)),
+        ("misplaced", misplaced),
+        (
+            "duplicates",
+            verification.count(&SmdbStatus::Duplicate { of: String::new() }),
+        ),
+        ("unknown", verification.count(&SmdbStatus::Unknown)),
+        ("missing", verification.missing.len()),
+        ("errors", verification.errors.len()),
+    ];
+    for (label, count) in counts {
+        summary.count(label, count as u64);
+        if count > 0 {
+            log::info!("  {} {}", count, label);
+        }
+    }
+    if misplaced > 0 && !move_files {
+        log::info!(
+            "{}",
+            "Run with --move to move misplaced files to their SMDB paths."
+                .if_supports_color(Stdout, |t| t.dimmed()),
+        );
+    }
+
+    crate::commands::send_notification(notify, summary);
+    Ok(())
+}
@@ -11,7 +11,7 @@ pub(crate) use error::CliError;
 
 use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::SystemTime;
 
@@ -20,7 +20,11 @@ use log::LevelFilter;
 use owo_colors::OwoColorize;
 use owo_colors::Stream::Stdout;
 
-use retro_junk_lib::{AnalysisContext, FolderScanResult, Platform};
+use retro_junk_lib::custom_platforms;
+use retro_junk_lib::esde_systems::EsSystems;
+use retro_junk_lib::notify::NotifyTarget;
+use retro_junk_lib::scanner::SymlinkPolicy;
+use retro_junk_lib::{AnalysisContext, ConsoleFolder, FolderScanResult, Platform};
 
 use cli_types::*;
 
@@ -29,7 +33,82 @@ use cli_types::*;
 struct CliLogger {
     level: LevelFilter,
     verbose: bool,
-    logfile: Option<Mutex<fs::File>>,
+    logfile: Option<Mutex<LogFile>>,
+}
+
+/// The `--logfile` target. With a rotation limit, a file that grows past it
+/// is renamed to `<name>.1` (shifting older ones up to `<name>.<keep>`) and
+/// a fresh file is started.
+struct LogFile {
+    file: fs::File,
+    path: PathBuf,
+    rotation: Option<LogRotation>,
+    written: u64,
+}
+
+#[derive(Clone, Copy)]
+struct LogRotation {
+    max_bytes: u64,
+    keep: usize,
+}
+
+impl LogFile {
+    fn create(path: &Path, rotation: Option<LogRotation>) -> std::io::Result<Self> {
+        // A rotating log outlives one run, so it's appended to
+        let file = if rotation.is_some() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?
+        } else {
+            fs::File::create(path)?
+        };
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            file,
+            path: path.to_path_buf(),
+            rotation,
+            written,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) {
+        let len = line.len() as u64 + 1;
+        if let Some(rotation) = self.rotation
+            && self.written > 0
+            && self.written + len > rotation.max_bytes
+        {
+            self.rotate(rotation.keep);
+        }
+        if writeln!(self.file, "{}", line).is_ok() {
+            self.written += len;
+        }
+    }
+
+    fn rotate(&mut self, keep: usize) {
+        let numbered = |n: usize| {
+            let mut name = self.path.as_os_str().to_owned();
+            name.push(format!(".{}", n));
+            PathBuf::from(name)
+        };
+        if keep == 0 {
+            let _ = fs::remove_file(&self.path);
+        } else {
+            let _ = fs::remove_file(numbered(keep));
+            for n in (1..keep).rev() {
+                let _ = fs::rename(numbered(n), numbered(n + 1));
+            }
+            let _ = fs::rename(&self.path, numbered(1));
+        }
+        // If the new file can't be opened, keep writing to the old one
+        if let Ok(file) = fs::File::create(&self.path) {
+            self.file = file;
+            self.written = 0;
+        }
+    }
 }
 
 impl CliLogger {
@@ -74,8 +153,7 @@ impl log::Log for CliLogger {
             if let Some(ref file) = self.logfile {
                 let stripped = strip_ansi_escapes::strip(&formatted);
                 let text = String::from_utf8_lossy(&stripped);
-                let mut guard = file.lock().unwrap();
-                let _ = writeln!(guard, "{}", text);
+                file.lock().unwrap().write_line(&text);
             }
         } else {
             // Normal mode: no timestamps for terminal
@@ -90,15 +168,16 @@ impl log::Log for CliLogger {
                 let level = record.level();
                 let stripped = strip_ansi_escapes::strip(&msg);
                 let text = String::from_utf8_lossy(&stripped);
-                let mut guard = file.lock().unwrap();
-                let _ = writeln!(guard, "[{} {:5}] {}", ts, level, text);
+                file.lock()
+                    .unwrap()
+                    .write_line(&format!("[{} {:5}] {}", ts, level, text));
             }
         }
     }
 
     fn flush(&self) {
         if let Some(ref file) = self.logfile {
-            let _ = std::io::Write::flush(&mut *file.lock().unwrap());
+            let _ = file.lock().unwrap().file.flush();
         }
     }
 }
@@ -114,8 +193,13 @@ pub(crate) fn log_blank() {
 
 fn main() {
     let cli = Cli::parse();
-    let quiet = cli.quiet;
-    let verbose = cli.verbose;
+    // JSON output owns stdout, so only warnings (on stderr) are logged
+    let structured_output = matches!(
+        &cli.command,
+        Commands::Analyze { format, .. } if format.is_structured()
+    );
+    let quiet = cli.quiet || structured_output;
+    let verbose = cli.verbose && !structured_output;
 
     // Initialize logger
     let level = if quiet {
@@ -125,8 +209,32 @@ fn main() {
     } else {
         LevelFilter::Info
     };
-    let logfile = cli.logfile.map(|p| {
-        let file = fs::File::create(&p).unwrap_or_else(|e| {
+    // A daemon always logs to a rotating file, by default in the cache
+    // directory next to its state file
+    let (logfile, rotation) =
+        match &cli.command {
+            Commands::Catalog {
+                action:
+                    CatalogAction::Dumper {
+                        system,
+                        daemon: true,
+                        log_max_size,
+                        log_keep,
+                        ..
+                    },
+            } => (
+                Some(cli.logfile.clone().unwrap_or_else(|| {
+                    commands::catalog::dumper::default_daemon_path(system, "log")
+                })),
+                Some(LogRotation {
+                    max_bytes: (*log_max_size).max(1) * 1024 * 1024,
+                    keep: *log_keep,
+                }),
+            ),
+            _ => (cli.logfile.clone(), None),
+        };
+    let logfile = logfile.map(|p| {
+        let file = LogFile::create(&p, rotation).unwrap_or_else(|e| {
             eprintln!("Error: could not create logfile {}: {}", p.display(), e);
             std::process::exit(1);
         });
@@ -140,11 +248,27 @@ fn main() {
     log::set_boxed_logger(logger).expect("Failed to set logger");
     log::set_max_level(level);
 
-    let ctx = create_context();
+    let mut ctx = match create_context(cli.es_systems.as_deref()) {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            log::error!("{e}");
+            std::process::exit(1);
+        }
+    };
+    if let Some(depth) = cli.scan_depth {
+        ctx.set_scan_depth(depth);
+    }
+    if cli.skip_symlinks {
+        ctx.set_symlink_policy(SymlinkPolicy::Skip);
+    }
+    let notify = cli.notify.map(|url| NotifyTarget {
+        url,
+        format: cli.notify_format,
+    });
     let command = cli.command;
     let library_path = cli.library_path;
 
-    if let Err(e) = run(command, library_path, quiet, &ctx) {
+    if let Err(e) = run(command, library_path, quiet, &ctx, notify.as_ref()) {
         log::error!("{e}");
         std::process::exit(1);
     }
@@ -155,14 +279,22 @@ fn run(
     library_path_override: Option<PathBuf>,
     quiet: bool,
     ctx: &AnalysisContext,
+    notify: Option<&NotifyTarget>,
 ) -> Result<(), CliError> {
     // Commands that need the library path resolve it once here.
     let needs_library_path = matches!(
         command,
         Commands::Analyze { .. }
+            | Commands::AuditSizes { .. }
+            | Commands::Verify { .. }
+            | Commands::OneG1r { .. }
             | Commands::Rename { .. }
             | Commands::Repair { .. }
+            | Commands::Trash { .. }
             | Commands::Scrape { .. }
+            | Commands::Doctor { .. }
+            | Commands::Sync { .. }
+            | Commands::Export { .. }
     );
     let library_path = if needs_library_path {
         retro_junk_lib::settings::resolve_library_path(library_path_override.clone())
@@ -172,8 +304,138 @@ fn run(
     };
 
     match command {
-        Commands::Analyze { quick, roms } => {
-            commands::analyze::run_analyze(ctx, quick, roms.consoles, roms.limit, library_path)?;
+        Commands::Analyze {
+            input: Some(input),
+            quick,
+            roms,
+            dump_header,
+            format,
+            hardened,
+            display_language,
+            ..
+        } => {
+            commands::analyze::run_analyze_input(
+                ctx,
+                &input,
+                quick,
+                roms.consoles,
+                dump_header,
+                format,
+                hardened,
+                display_language,
+            )?;
+        }
+        Commands::Analyze {
+            input: None,
+            quick,
+            roms,
+            sidecar,
+            dump_header,
+            verify_chd,
+            dat_dir,
+            format,
+            hardened,
+            display_language,
+        } => {
+            commands::analyze::run_analyze(
+                ctx,
+                quick,
+                roms.consoles,
+                roms.limit,
+                library_path,
+                sidecar,
+                dump_header,
+                verify_chd,
+                dat_dir,
+                format,
+                hardened,
+                display_language,
+                notify,
+            )?;
+        }
+        Commands::AuditSizes { roms, list } => {
+            commands::audit_sizes::run_audit_sizes(
+                ctx,
+                roms.consoles,
+                roms.limit,
+                library_path,
+                list,
+            )?;
+        }
+        Commands::Verify {
+            problems_only,
+            smdb: Some(smdb),
+            move_files,
+            ..
+        } => {
+            commands::verify_smdb::run_verify_smdb(
+                library_path,
+                &smdb,
+                move_files,
+                problems_only,
+                quiet,
+                notify,
+            )?;
+        }
+        Commands::Verify {
+            input: Some(input),
+            roms,
+            dat_dir,
+            report,
+            ..
+        } => {
+            commands::verify::run_verify_input(
+                ctx,
+                &input,
+                roms.consoles,
+                dat_dir,
+                report,
+                quiet,
+                notify,
+            )?;
+        }
+        Commands::Verify {
+            roms,
+            dat_dir,
+            problems_only,
+            report,
+            folder_report,
+            ..
+        } => {
+            commands::verify::run_verify(
+                ctx,
+                roms.consoles,
+                roms.limit,
+                library_path,
+                dat_dir,
+                problems_only,
+                report,
+                folder_report,
+                quiet,
+                notify,
+            )?;
+        }
+        Commands::OneG1r {
+            consoles,
+            dat_dir,
+            regions,
+            languages,
+            original_revision,
+            move_duplicates,
+        } => {
+            commands::one_g1r::run_one_g1r(
+                ctx,
+                consoles,
+                library_path,
+                dat_dir,
+                retro_junk_dat::one_g1r::Preferences {
+                    regions,
+                    languages,
+                    latest_revision: !original_revision,
+                },
+                move_duplicates,
+                notify,
+            )?;
         }
         Commands::Rename {
             dry_run,
@@ -182,6 +444,18 @@ fn run(
             dat_dir,
             media_dir,
             no_media,
+            export,
+            on_conflict,
+            report,
+            sidecar,
+            split_archives,
+            archives,
+            symlinks,
+            sanitize,
+            create_m3u,
+            companions,
+            no_companions,
+            trash,
         } => {
             commands::rename::run_rename(
                 ctx,
@@ -194,11 +468,25 @@ fn run(
                 quiet,
                 media_dir,
                 no_media,
+                export,
+                on_conflict,
+                report,
+                sidecar,
+                split_archives,
+                archives,
+                symlinks,
+                sanitize,
+                create_m3u,
+                companions,
+                no_companions,
+                trash,
             )?;
         }
         Commands::Repair {
             dry_run,
             no_backup,
+            trash,
+            strip_headers,
             roms,
             dat_dir,
         } => {
@@ -206,6 +494,8 @@ fn run(
                 ctx,
                 dry_run,
                 no_backup,
+                trash,
+                strip_headers,
                 roms.consoles,
                 roms.limit,
                 library_path,
@@ -213,6 +503,18 @@ fn run(
                 quiet,
             )?;
         }
+        Commands::DumpDvd {
+            system,
+            device,
+            output,
+            retries,
+            dat_dir,
+            overwrite,
+        } => {
+            commands::dump_dvd::run_dump_dvd(
+                ctx, system, device, output, retries, dat_dir, overwrite, quiet,
+            )?;
+        }
         Commands::Scrape {
             roms,
             media_types,
@@ -220,6 +522,8 @@ fn run(
             media_dir,
             frontend,
             region,
+            media_regions,
+            asset_naming,
             language,
             language_fallback,
             force_full_hash,
@@ -229,6 +533,9 @@ fn run(
             no_miximage,
             force_redownload,
             threads,
+            image_threads,
+            sidecar,
+            metrics,
         } => {
             commands::scrape::run_scrape(
                 ctx,
@@ -239,6 +546,8 @@ fn run(
                 media_dir,
                 frontend,
                 region,
+                media_regions,
+                asset_naming,
                 language,
                 language_fallback,
                 force_full_hash,
@@ -248,10 +557,61 @@ fn run(
                 no_miximage,
                 force_redownload,
                 threads,
+                image_threads,
+                sidecar,
+                metrics,
                 library_path,
                 quiet,
+                notify,
+            )?;
+        }
+        Commands::Join {
+            part,
+            output,
+            dat_dir,
+        } => {
+            commands::join::run_join(ctx, &part, output.as_deref(), dat_dir.as_deref())?;
+        }
+        Commands::Doctor { consoles } => {
+            commands::doctor::run_doctor(ctx, consoles, library_path)?;
+        }
+        Commands::Sync {
+            dry_run,
+            consoles,
+            metadata_dir,
+            media_dir,
+        } => {
+            commands::sync::run_sync(
+                ctx,
+                dry_run,
+                consoles,
+                library_path,
+                metadata_dir,
+                media_dir,
+            )?;
+        }
+        Commands::Export {
+            profile,
+            target,
+            roms,
+            name,
+            dry_run,
+        } => {
+            commands::export::run_export(
+                ctx,
+                &profile,
+                &target,
+                roms.consoles,
+                roms.limit,
+                name.as_deref(),
+                dry_run,
+                library_path,
             )?;
         }
+        Commands::Trash { action } => match action {
+            TrashAction::List => commands::trash::run_trash_list(&library_path)?,
+            TrashAction::Restore => commands::trash::run_trash_restore(&library_path)?,
+        },
         Commands::Cache { action } => match action {
             CacheAction::List => commands::cache::run_cache_list()?,
             CacheAction::Clear => commands::cache::run_cache_clear()?,
@@ -307,11 +667,15 @@ fn run(
                 download_assets,
                 asset_dir,
                 region,
+                asset_regions,
+                asset_naming,
                 language,
                 threads,
                 no_reconcile,
+                dry_run,
             } => {
                 commands::catalog::enrich::run_catalog_enrich(
+                    ctx,
                     systems,
                     db,
                     limit,
@@ -319,9 +683,12 @@ fn run(
                     download_assets,
                     asset_dir,
                     region,
+                    asset_regions,
+                    asset_naming,
                     language,
                     threads,
                     no_reconcile,
+                    dry_run,
                     quiet,
                 )?;
             }
@@ -333,12 +700,134 @@ fn run(
             } => {
                 commands::catalog::scan::run_catalog_scan(ctx, system, folder, db, user_id, quiet)?;
             }
+            CatalogAction::Dumper {
+                system,
+                folder,
+                dest,
+                db,
+                user_id,
+                interval,
+                once,
+                daemon,
+                state_file,
+                log_max_size: _,
+                log_keep: _,
+                metrics,
+            } => {
+                commands::catalog::dumper::run_catalog_dumper(
+                    ctx,
+                    system,
+                    folder,
+                    dest,
+                    library_path_override,
+                    db,
+                    user_id,
+                    interval,
+                    once,
+                    daemon,
+                    state_file,
+                    metrics,
+                    notify,
+                )?;
+            }
+            CatalogAction::Screenshots {
+                system,
+                folder,
+                db,
+                asset_dir,
+                title_screen,
+                mappings,
+                overwrite,
+            } => {
+                commands::catalog::screenshots::run_catalog_screenshots(
+                    system,
+                    folder,
+                    db,
+                    asset_dir,
+                    title_screen,
+                    mappings,
+                    overwrite,
+                    quiet,
+                )?;
+            }
+            CatalogAction::Assets { action } => match action {
+                AssetsAction::Verify { system, db } => {
+                    commands::catalog::assets::run_catalog_assets_verify(system, db, quiet)?;
+                }
+                AssetsAction::Prune {
+                    db,
+                    asset_dir,
+                    delete,
+                    redownload,
+                } => {
+                    commands::catalog::assets::run_catalog_assets_prune(
+                        db, asset_dir, delete, redownload, quiet,
+                    )?;
+                }
+            },
+            CatalogAction::Companies { action } => match action {
+                CompaniesAction::Review { db, limit } => {
+                    commands::catalog::companies::run_catalog_companies_review(db, limit)?;
+                }
+                CompaniesAction::Approve {
+                    ids,
+                    db,
+                    all_suggested,
+                } => {
+                    commands::catalog::companies::run_catalog_companies_approve(
+                        ids,
+                        db,
+                        all_suggested,
+                    )?;
+                }
+                CompaniesAction::Merge { id, company, db } => {
+                    commands::catalog::companies::run_catalog_companies_merge(id, company, db)?;
+                }
+                CompaniesAction::Reject { ids, db } => {
+                    commands::catalog::companies::run_catalog_companies_reject(ids, db)?;
+                }
+            },
+            CatalogAction::Siblings { action } => match action {
+                SiblingsAction::Review { systems, db, limit } => {
+                    commands::catalog::siblings::run_catalog_siblings_review(systems, db, limit)?;
+                }
+                SiblingsAction::Link { works, db } => {
+                    commands::catalog::siblings::run_catalog_siblings_link(works, db)?;
+                }
+                SiblingsAction::Reject { works, db } => {
+                    commands::catalog::siblings::run_catalog_siblings_reject(works, db)?;
+                }
+            },
             CatalogAction::Verify {
                 system,
                 db,
                 user_id,
             } => {
-                commands::catalog::verify::run_catalog_verify(ctx, system, db, user_id, quiet)?;
+                commands::catalog::verify::run_catalog_verify(
+                    ctx, system, db, user_id, quiet, notify,
+                )?;
+            }
+            CatalogAction::Refresh { system, db } => {
+                commands::catalog::refresh::run_catalog_refresh(ctx, system, db)?;
+            }
+            CatalogAction::Export {
+                system,
+                output,
+                naming,
+                folder,
+                owned,
+                user_id,
+                db,
+            } => {
+                commands::catalog::export::run_catalog_export(
+                    ctx,
+                    system,
+                    output,
+                    naming,
+                    folder,
+                    owned.then_some(user_id),
+                    db,
+                )?;
             }
             CatalogAction::Disagreements {
                 db,
@@ -361,20 +850,38 @@ fn run(
                     id, db, source_a, source_b, custom,
                 )?;
             }
+            CatalogAction::AutoResolve {
+                db,
+                catalog_dir,
+                dry_run,
+            } => {
+                commands::catalog::disagreements::run_catalog_auto_resolve(
+                    db,
+                    catalog_dir,
+                    dry_run,
+                )?;
+            }
             CatalogAction::Gaps {
                 system,
                 db,
                 collection_only,
                 missing,
+                works,
+                user_id,
                 limit,
             } => {
-                commands::catalog::gaps::run_catalog_gaps(
-                    system,
-                    db,
-                    collection_only,
-                    missing,
-                    limit,
-                )?;
+                if works {
+                    commands::catalog::gaps::run_catalog_work_gaps(system, db, &user_id, limit)?;
+                } else {
+                    commands::catalog::gaps::run_catalog_gaps(
+                        system,
+                        db,
+                        collection_only,
+                        missing,
+                        &user_id,
+                        limit,
+                    )?;
+                }
             }
             CatalogAction::Lookup {
                 query,
@@ -391,6 +898,7 @@ fn run(
                 db,
             } => {
                 commands::catalog::lookup::run_catalog_lookup(
+                    ctx,
                     query,
                     platform,
                     r#type,
@@ -412,8 +920,18 @@ fn run(
             } => {
                 commands::catalog::reconcile::run_catalog_reconcile(systems, db, dry_run)?;
             }
-            CatalogAction::Stats { db } => {
-                commands::catalog::stats::run_catalog_stats(db)?;
+            CatalogAction::Stats {
+                db,
+                history,
+                system,
+                csv,
+                user_id,
+            } => {
+                if history {
+                    commands::catalog::stats::run_catalog_stats_history(db, system, csv, &user_id)?;
+                } else {
+                    commands::catalog::stats::run_catalog_stats(db, &user_id)?;
+                }
             }
             CatalogAction::Unenrich {
                 system,
@@ -433,8 +951,33 @@ fn run(
 }
 
 /// Create the analysis context with all registered consoles.
-fn create_context() -> AnalysisContext {
-    retro_junk_lib::create_default_context()
+///
+/// User-defined platforms from `platforms.toml` are registered after the
+/// built-in analyzers. Folder names from ES-DE's system definitions are added as aliases, so a
+/// library laid out for ES-DE is recognized as-is.
+fn create_context(es_systems: Option<&std::path::Path>) -> Result<AnalysisContext, CliError> {
+    let mut ctx = retro_junk_lib::create_default_context();
+    let platforms_path = custom_platforms::default_config_path();
+    let custom = custom_platforms::register_from_file(&mut ctx, &platforms_path)
+        .map_err(|e| CliError::config(e.to_string()))?;
+    if !custom.is_empty() {
+        log::debug!(
+            "Registered {} custom platforms from {}",
+            custom.len(),
+            platforms_path.display()
+        );
+    }
+
+    let systems = match es_systems {
+        Some(path) => EsSystems::load(path)
+            .map_err(|e| CliError::config(format!("Failed to read ES-DE systems: {}", e)))?,
+        None => EsSystems::discover(),
+    };
+    let added = systems.apply_to(&mut ctx);
+    if added > 0 {
+        log::debug!("Added {} folder aliases from ES-DE systems", added);
+    }
+    Ok(ctx)
 }
 
 /// Scan the root directory for console folders, logging unrecognized ones.
@@ -468,6 +1011,17 @@ pub(crate) fn scan_folders(
     }
 }
 
+/// The system name gamelists and media for a console folder are written under.
+///
+/// ES-DE keys these by system name, which can differ from the ROM folder
+/// name: custom `es_systems.xml` entries win, then ES-DE's standard names.
+pub(crate) fn output_system_name<'a>(ctx: &'a AnalysisContext, cf: &'a ConsoleFolder) -> &'a str {
+    ctx.frontend_system_name(&cf.folder_name)
+        .unwrap_or_else(|| {
+            retro_junk_frontend::esde_names::output_system_name(cf.platform, &cf.folder_name)
+        })
+}
+
 /// Log a DAT loading error with a `cache fetch` hint.
 pub(crate) fn log_dat_error(
     platform_name: &str,
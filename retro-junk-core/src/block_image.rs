@@ -0,0 +1,132 @@
+//! Seekable view of a block-compressed image.
+//!
+//! Compressed disc formats (CSO, ZSO, CHD and the like) store an image as
+//! fixed-size blocks that are each decompressed on their own. A
+//! [`BlockSource`] knows how to decode one block; [`BlockImage`] turns it
+//! into a `Read + Seek` over the uncompressed image, so analyzers and
+//! hashers written for plain images work on it unchanged. The most recently
+//! decoded block is kept, since sector-by-sector reads hit the same block
+//! many times in a row; sources where every block is expensive to get, like
+//! HTTP range requests, keep more with [`BlockImage::with_cache`].
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::util::seek_target;
+
+/// A block-compressed image that can decode any one block.
+pub trait BlockSource {
+    /// Uncompressed size of every block but possibly the last.
+    fn block_size(&self) -> usize;
+
+    /// Uncompressed size of the whole image.
+    fn image_len(&self) -> u64;
+
+    /// Decode block `index` into `buf`, replacing its contents. The block
+    /// must come out [`block_size`](Self::block_size) bytes long, except the
+    /// last one, which may stop at [`image_len`](Self::image_len).
+    fn read_block(&mut self, index: u64, buf: &mut Vec<u8>) -> io::Result<()>;
+}
+
+/// A `Read + Seek` adapter over a [`BlockSource`].
+pub struct BlockImage<S> {
+    source: S,
+    /// Decoded blocks by index.
+    blocks: HashMap<u64, Vec<u8>>,
+    /// Indices in `blocks`, least recently used first.
+    order: VecDeque<u64>,
+    max_blocks: usize,
+    pos: u64,
+}
+
+impl<S: BlockSource> BlockImage<S> {
+    /// An image that keeps only the most recently decoded block.
+    pub fn new(source: S) -> Self {
+        Self::with_cache(source, 1)
+    }
+
+    /// An image that keeps up to `max_blocks` decoded blocks, dropping the
+    /// least recently used one to make room.
+    pub fn with_cache(source: S, max_blocks: usize) -> Self {
+        Self {
+            source,
+            blocks: HashMap::new(),
+            order: VecDeque::new(),
+            max_blocks: max_blocks.max(1),
+            pos: 0,
+        }
+    }
+
+    /// Uncompressed size of the image.
+    pub fn len(&self) -> u64 {
+        self.source.image_len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    pub fn into_source(self) -> S {
+        self.source
+    }
+
+    /// Block `index`, decoded now unless it is cached.
+    fn load(&mut self, index: u64) -> io::Result<&[u8]> {
+        if let Some(at) = self.order.iter().position(|&i| i == index) {
+            self.order.remove(at);
+        } else {
+            // Reuse the evicted block's buffer when the cache is full
+            let mut buf = if self.order.len() >= self.max_blocks {
+                self.order
+                    .pop_front()
+                    .and_then(|evicted| self.blocks.remove(&evicted))
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            self.source.read_block(index, &mut buf)?;
+            self.blocks.insert(index, buf);
+        }
+        self.order.push_back(index);
+        Ok(&self.blocks[&index])
+    }
+}
+
+impl<S: BlockSource> Read for BlockImage<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() || self.pos >= self.source.image_len() {
+            return Ok(0);
+        }
+        let block_size = self.source.block_size() as u64;
+        let index = self.pos / block_size;
+        let offset = (self.pos % block_size) as usize;
+        let block = self.load(index)?;
+
+        let available = block.get(offset..).unwrap_or_default();
+        if available.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("block {index} decoded short of offset {}", self.pos),
+            ));
+        }
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<S: BlockSource> Seek for BlockImage<S> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.pos = seek_target(pos, self.pos, self.source.image_len())?;
+        Ok(self.pos)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/block_image_tests.rs"]
+mod tests;
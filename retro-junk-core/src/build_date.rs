@@ -0,0 +1,95 @@
+//! Build dates embedded in ROM and disc headers.
+//!
+//! Many headers record when the game was mastered: Genesis copyright lines
+//! ("(C)SEGA 1991.APR"), the GameCube apploader date, the Famicom Disk
+//! System manufacturing date, and the ISO 9660 volume creation date on PS1
+//! and PS2 discs. These are usually close to the release date, so they make a
+//! reasonable fallback when no database knows the real one.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A date from a ROM header. Headers vary in precision, so month and day are
+/// optional (a day is only present with a month).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BuildDate {
+    pub year: u16,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+}
+
+impl BuildDate {
+    /// Earliest and latest years accepted; anything outside is header garbage.
+    pub const MIN_YEAR: u16 = 1970;
+    pub const MAX_YEAR: u16 = 2099;
+
+    /// Build a date, rejecting out-of-range fields.
+    pub fn new(year: u16, month: Option<u8>, day: Option<u8>) -> Option<Self> {
+        if !(Self::MIN_YEAR..=Self::MAX_YEAR).contains(&year) {
+            return None;
+        }
+        if month.is_some_and(|m| !(1..=12).contains(&m)) {
+            return None;
+        }
+        if day.is_some_and(|d| month.is_none() || !(1..=31).contains(&d)) {
+            return None;
+        }
+        Some(Self { year, month, day })
+    }
+
+    /// Parse a numeric date: `YYYYMMDD`, `YYYYMM`, `YYYY`, or the same with
+    /// `/`, `-`, or `.` separators. Surrounding spaces and NULs are ignored.
+    ///
+    /// ```
+    /// use retro_junk_core::BuildDate;
+    ///
+    /// assert_eq!(BuildDate::parse("2001/09/14").unwrap().to_string(), "2001-09-14");
+    /// assert_eq!(BuildDate::parse("19981120").unwrap().to_string(), "1998-11-20");
+    /// assert!(BuildDate::parse("0000/00/00").is_none());
+    /// ```
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim_matches(|c: char| c.is_whitespace() || c == '\0');
+        let parts: Vec<&str> = if s.contains(['/', '-', '.']) {
+            s.split(['/', '-', '.']).collect()
+        } else {
+            if !s.is_ascii() {
+                return None;
+            }
+            match s.len() {
+                8 => vec![&s[..4], &s[4..6], &s[6..]],
+                6 => vec![&s[..4], &s[4..]],
+                4 => vec![s],
+                _ => return None,
+            }
+        };
+        if parts.is_empty() || parts.len() > 3 || parts[0].len() != 4 {
+            return None;
+        }
+        let field = |i: usize| -> Option<Option<u8>> {
+            match parts.get(i) {
+                Some(p) => p.parse().ok().map(Some),
+                None => Some(None),
+            }
+        };
+        Self::new(parts[0].parse().ok()?, field(1)?, field(2)?)
+    }
+}
+
+impl fmt::Display for BuildDate {
+    /// `YYYY`, `YYYY-MM`, or `YYYY-MM-DD`, depending on precision.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}", self.year)?;
+        if let Some(month) = self.month {
+            write!(f, "-{:02}", month)?;
+            if let Some(day) = self.day {
+                write!(f, "-{:02}", day)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/build_date_tests.rs"]
+mod tests;
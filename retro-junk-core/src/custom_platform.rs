@@ -0,0 +1,94 @@
+//! Platforms defined at runtime from user configuration.
+//!
+//! Built-in platforms are enum variants with static metadata. A custom
+//! platform is registered once at startup and referenced through
+//! [`Platform::Custom`], which indexes this registry. Definitions are leaked
+//! so they can hand out the same `&'static str` metadata as built-ins; the
+//! registry only grows, and only by the handful of entries in a config file.
+
+use std::sync::RwLock;
+
+use crate::Platform;
+use crate::disc::PartKind;
+
+/// Metadata for a user-defined platform.
+#[derive(Debug, Clone)]
+pub struct CustomPlatform {
+    /// Canonical short name (e.g., "vectrex"). Also the first alias.
+    pub short_name: &'static str,
+    pub display_name: &'static str,
+    pub manufacturer: &'static str,
+    /// Accepted names, lowercase, starting with `short_name`.
+    pub aliases: &'static [&'static str],
+    /// ScreenScraper system ID, if the platform can be scraped.
+    pub screenscraper_id: Option<u32>,
+    /// Tags that mark the parts of a multi-part game. Empty means "(Disc N)".
+    pub part_kinds: &'static [PartKind],
+}
+
+static REGISTRY: RwLock<Vec<&'static CustomPlatform>> = RwLock::new(Vec::new());
+
+/// User overrides of ScreenScraper system IDs, for any platform.
+static SCREENSCRAPER_OVERRIDES: RwLock<Vec<(Platform, u32)>> = RwLock::new(Vec::new());
+
+/// Register a custom platform and return its `Platform` value.
+///
+/// Registering a short name that already exists returns the existing
+/// platform unchanged.
+pub fn register(platform: CustomPlatform) -> Platform {
+    let mut registry = REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+    if let Some(i) = registry
+        .iter()
+        .position(|p| p.short_name == platform.short_name)
+    {
+        return Platform::Custom(i as u16);
+    }
+    registry.push(Box::leak(Box::new(platform)));
+    Platform::Custom((registry.len() - 1) as u16)
+}
+
+/// Look up a registered custom platform by index.
+pub fn get(index: u16) -> Option<&'static CustomPlatform> {
+    let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    registry.get(index as usize).copied()
+}
+
+/// All registered custom platforms, in registration order.
+pub fn all() -> Vec<Platform> {
+    let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    (0..registry.len() as u16).map(Platform::Custom).collect()
+}
+
+/// Override the ScreenScraper system ID used for `platform`.
+///
+/// Takes precedence over both the built-in mapping and a custom platform's
+/// own `screenscraper_id`. Setting the same platform again replaces it.
+pub fn set_screenscraper_id(platform: Platform, id: u32) {
+    let mut overrides = SCREENSCRAPER_OVERRIDES
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    match overrides.iter_mut().find(|(p, _)| *p == platform) {
+        Some(entry) => entry.1 = id,
+        None => overrides.push((platform, id)),
+    }
+}
+
+/// The user-configured ScreenScraper system ID for `platform`, if any.
+pub fn screenscraper_id_override(platform: Platform) -> Option<u32> {
+    let overrides = SCREENSCRAPER_OVERRIDES
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
+    overrides
+        .iter()
+        .find(|(p, _)| *p == platform)
+        .map(|(_, id)| *id)
+}
+
+/// Find a custom platform by short name or alias (already lowercased).
+pub(crate) fn find(name: &str) -> Option<Platform> {
+    let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    registry
+        .iter()
+        .position(|p| p.aliases.contains(&name))
+        .map(|i| Platform::Custom(i as u16))
+}
@@ -1,31 +1,202 @@
 //! Disc-related filename utilities.
 //!
 //! Functions for parsing "(Disc N)" tags from game filenames and grouping
-//! multi-disc entries. Used by both the rename and scraper systems.
+//! multi-disc entries. Used by both the rename and scraper systems. The
+//! [`iso9660`] submodule reads the filesystem on the discs themselves,
+//! [`cue`] parses the CUE sheets that describe their tracks, and [`cd`]
+//! holds the raw sector and CHD track layout of CD images.
+//!
+//! Optical media is tagged "(Disc N)", but floppy and tape games use other
+//! tags: "(Disk 2)", "(Side B)", TOSEC's "(Disk 1 of 3)". Each platform
+//! lists the [`PartKind`]s that count as parts of one game
+//! ([`part_kinds`]); the `*_part*` functions take that list, and the
+//! `*_disc*` ones are the optical-only shorthand.
+
+pub mod cd;
+pub mod cue;
+pub mod iso9660;
 
 use std::collections::HashMap;
 
+use crate::{Platform, custom_platform};
+
+/// A kind of numbered part a game can be split across.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartKind {
+    /// Optical discs: "(Disc 2)"
+    Disc,
+    /// Floppy disks: "(Disk 2)", "(Disk 2 of 3)", "(Disk 1 Side B)"
+    Disk,
+    /// Disk or tape sides: "(Side B)"
+    Side,
+    /// Cassettes: "(Tape 2)"
+    Tape,
+}
+
+impl PartKind {
+    /// Every kind, for names whose tags are already canonical (DAT names).
+    pub const ALL: &'static [PartKind] = &[
+        PartKind::Disc,
+        PartKind::Disk,
+        PartKind::Side,
+        PartKind::Tape,
+    ];
+
+    /// The word used in the tag.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Disc => "Disc",
+            Self::Disk => "Disk",
+            Self::Side => "Side",
+            Self::Tape => "Tape",
+        }
+    }
+
+    /// Parse a config name (`"disk"`), case-insensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|k| k.label().eq_ignore_ascii_case(name))
+    }
+}
+
+/// The kinds of part that make up one game on `platform`.
+///
+/// Disc-based and cartridge platforms only group "(Disc N)"; the Famicom
+/// Disk System (under NES) uses disks and sides. Custom platforms set their
+/// own with `multi_part` in `platforms.toml`.
+pub fn part_kinds(platform: Platform) -> &'static [PartKind] {
+    match platform {
+        Platform::Nes => &[PartKind::Disk, PartKind::Side],
+        Platform::Custom(i) => custom_platform::get(i)
+            .map(|p| p.part_kinds)
+            .filter(|k| !k.is_empty())
+            .unwrap_or(&[PartKind::Disc]),
+        _ => &[PartKind::Disc],
+    }
+}
+
+/// A part tag found in a name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartTag {
+    pub kind: PartKind,
+    /// Disc/disk/tape number, or the side for a bare "(Side B)" (A = 1).
+    pub number: u32,
+    /// Side of a numbered disk ("(Disk 1 Side B)"), A = 1.
+    pub side: Option<u32>,
+    /// Part count from "(Disk 1 of 3)".
+    pub total: Option<u32>,
+    /// Byte range of the tag in the name, including the parentheses.
+    start: usize,
+    end: usize,
+}
+
+impl PartTag {
+    /// Key that orders the parts of one game.
+    pub fn sort_key(&self) -> (u32, u32) {
+        (self.number, self.side.unwrap_or(0))
+    }
+
+    /// The tag text without parentheses, e.g. "Disk 1 Side B".
+    pub fn label(&self) -> String {
+        match (self.kind, self.side) {
+            (PartKind::Side, _) => format!("Side {}", side_letter(self.number)),
+            (kind, Some(side)) => format!(
+                "{} {} Side {}",
+                kind.label(),
+                self.number,
+                side_letter(side)
+            ),
+            (kind, None) => format!("{} {}", kind.label(), self.number),
+        }
+    }
+}
+
+fn side_letter(side: u32) -> char {
+    char::from_u32('A' as u32 + side.saturating_sub(1)).unwrap_or('?')
+}
+
+/// Parse a side: a letter (A = 1) or a number.
+fn parse_side(s: &str) -> Option<u32> {
+    match s.as_bytes() {
+        [c] if c.is_ascii_alphabetic() => Some((c.to_ascii_uppercase() - b'A') as u32 + 1),
+        _ => s.parse().ok(),
+    }
+}
+
+/// Parse the inside of a parenthesized tag as one of `kinds`.
+fn parse_part(
+    content: &str,
+    kinds: &[PartKind],
+) -> Option<(PartKind, u32, Option<u32>, Option<u32>)> {
+    let (word, rest) = content.split_once(' ')?;
+    let kind = kinds
+        .iter()
+        .copied()
+        .find(|k| k.label().eq_ignore_ascii_case(word))?;
+    let words: Vec<&str> = rest.split(' ').collect();
+    match (kind, words.as_slice()) {
+        (PartKind::Side, [side]) => Some((kind, parse_side(side)?, None, None)),
+        (PartKind::Side, _) => None,
+        (_, [n]) => Some((kind, n.parse().ok()?, None, None)),
+        (_, [n, of, total]) if of.eq_ignore_ascii_case("of") => {
+            Some((kind, n.parse().ok()?, None, Some(total.parse().ok()?)))
+        }
+        (PartKind::Disk, [n, side_word, side]) if side_word.eq_ignore_ascii_case("side") => {
+            Some((kind, n.parse().ok()?, Some(parse_side(side)?), None))
+        }
+        _ => None,
+    }
+}
+
+/// Find the first part tag of one of `kinds` in `name`.
+///
+/// Examples with `[Disk, Side]`:
+/// - `"Zelda no Densetsu (Japan) (Disk 1 Side B)"` → disk 1, side 2
+/// - `"Lemmings (1991)(Psygnosis)(Disk 2 of 2)"` → disk 2 of 2
+pub fn extract_part(name: &str, kinds: &[PartKind]) -> Option<PartTag> {
+    let mut from = 0;
+    while let Some(open) = name[from..].find('(').map(|i| from + i) {
+        let close = open + name[open..].find(')')?;
+        if let Some((kind, number, side, total)) = parse_part(&name[open + 1..close], kinds) {
+            return Some(PartTag {
+                kind,
+                number,
+                side,
+                total,
+                start: open,
+                end: close + 1,
+            });
+        }
+        from = close + 1;
+    }
+    None
+}
+
+/// Remove the first part tag of one of `kinds` from `name`, with the space
+/// before it.
+pub fn strip_part_tag(name: &str, kinds: &[PartKind]) -> String {
+    match extract_part(name, kinds) {
+        Some(tag) => {
+            let start = if name[..tag.start].ends_with(' ') {
+                tag.start - 1
+            } else {
+                tag.start
+            };
+            format!("{}{}", &name[..start], &name[tag.end..])
+        }
+        None => name.to_string(),
+    }
+}
+
 /// Remove " (Disc N)" from a game name, preserving other parenthesized tags.
 ///
 /// Examples:
 /// - `"Final Fantasy VII (Disc 1) (USA)"` → `"Final Fantasy VII (USA)"`
 /// - `"Crash Bandicoot (USA)"` → `"Crash Bandicoot (USA)"` (unchanged)
 pub fn strip_disc_tag(name: &str) -> String {
-    const PREFIX: &str = " (Disc ";
-    if let Some(start) = name.find(PREFIX) {
-        let after = &name[start + PREFIX.len()..];
-        // Find closing ')' after the digits
-        if let Some(close) = after.find(')') {
-            let digits = &after[..close];
-            if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
-                let mut result = String::with_capacity(name.len());
-                result.push_str(&name[..start]);
-                result.push_str(&after[close + 1..]);
-                return result;
-            }
-        }
-    }
-    name.to_string()
+    strip_part_tag(name, &[PartKind::Disc])
 }
 
 /// Extract disc number from a filename or game name for sorting.
@@ -34,11 +205,7 @@ pub fn strip_disc_tag(name: &str) -> String {
 /// - `"Final Fantasy VII (Disc 2) (USA).chd"` → `Some(2)`
 /// - `"Crash Bandicoot (USA).chd"` → `None`
 pub fn extract_disc_number(name: &str) -> Option<u32> {
-    const PREFIX: &str = "(Disc ";
-    let start = name.find(PREFIX)?;
-    let after = &name[start + PREFIX.len()..];
-    let close = after.find(')')?;
-    after[..close].parse().ok()
+    extract_part(name, &[PartKind::Disc]).map(|tag| tag.number)
 }
 
 /// Info about a group of entries belonging to the same multi-disc game.
@@ -57,21 +224,35 @@ pub struct DiscGroup {
 /// Only entries containing "(Disc N)" are considered. Groups with a single
 /// entry are excluded (a lone "Disc 1" with no other discs isn't a group).
 pub fn detect_disc_groups(entries: &[(usize, &str)]) -> Vec<DiscGroup> {
-    // Group by base name (disc tag stripped)
-    let mut groups: HashMap<String, Vec<(usize, u32)>> = HashMap::new();
+    detect_part_groups(entries, &[PartKind::Disc])
+}
+
+/// Like [`detect_disc_groups`], for any of `kinds`.
+///
+/// Entries only group when their part counts agree, so a two-disk and a
+/// three-disk release of the same game ("(Disk 1 of 2)", "(Disk 1 of 3)")
+/// stay separate.
+pub fn detect_part_groups(entries: &[(usize, &str)], kinds: &[PartKind]) -> Vec<DiscGroup> {
+    // Group by base name (part tag stripped), kind, and part count
+    type GroupKey = (String, PartKind, Option<u32>);
+    type Member = (usize, (u32, u32));
+    let mut groups: HashMap<GroupKey, Vec<Member>> = HashMap::new();
 
     for &(index, stem) in entries {
-        if let Some(disc_num) = extract_disc_number(stem) {
-            let base = strip_disc_tag(stem);
-            groups.entry(base).or_default().push((index, disc_num));
+        if let Some(tag) = extract_part(stem, kinds) {
+            let base = strip_part_tag(stem, kinds);
+            groups
+                .entry((base, tag.kind, tag.total))
+                .or_default()
+                .push((index, tag.sort_key()));
         }
     }
 
     let mut result: Vec<DiscGroup> = groups
         .into_iter()
         .filter(|(_, members)| members.len() >= 2)
-        .map(|(base_name, mut members)| {
-            members.sort_by_key(|&(_, disc_num)| disc_num);
+        .map(|((base_name, _, _), mut members)| {
+            members.sort_by_key(|&(_, key)| key);
             let primary_index = members[0].0;
             let member_indices = members.iter().map(|&(idx, _)| idx).collect();
             DiscGroup {
@@ -95,13 +276,18 @@ pub fn detect_disc_groups(entries: &[(usize, &str)]) -> Vec<DiscGroup> {
 ///   numbered discs). Otherwise, compute the longest common prefix trimmed to a clean
 ///   parenthesized-group boundary (handles scenario-named discs like "Leon Hen"/"Claire Hen").
 pub fn derive_base_game_name(names: &[&str]) -> String {
+    derive_base_name(names, &[PartKind::Disc])
+}
+
+/// Like [`derive_base_game_name`], stripping part tags of any of `kinds`.
+pub fn derive_base_name(names: &[&str], kinds: &[PartKind]) -> String {
     match names.len() {
         0 => String::new(),
-        1 => strip_disc_tag(names[0]),
+        1 => strip_part_tag(names[0], kinds),
         _ => {
-            let stripped = strip_disc_tag(names[0]);
+            let stripped = strip_part_tag(names[0], kinds);
             if stripped != names[0] {
-                // Fast path: numbered discs — strip_disc_tag handled it
+                // Fast path: numbered parts — strip_part_tag handled it
                 stripped
             } else {
                 // Scenario discs: find the longest common prefix across all names
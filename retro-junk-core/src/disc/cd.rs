@@ -0,0 +1,106 @@
+//! CD image layout shared by the disc analyzers.
+//!
+//! Raw BIN images store whole 2352-byte sectors, each starting with
+//! [`SYNC_PATTERN`]. CHD images store the same sectors as 2448-byte frames
+//! (the sector plus 96 bytes of subcode) and describe the tracks in text
+//! metadata, which [`parse_chd_track`] reads.
+
+/// Bytes of a raw sector: sync, header, user data, and EDC/ECC.
+pub const RAW_SECTOR_SIZE: u64 = 2352;
+
+/// Sync pattern that starts every raw data sector.
+pub const SYNC_PATTERN: [u8; 12] = [
+    0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00,
+];
+
+/// Magic bytes at the start of a CHD file.
+pub const CHD_MAGIC: &[u8; 8] = b"MComprHD";
+
+/// Bytes per frame in a CHD CD image: a raw sector plus 96 bytes of subcode.
+pub const CHD_FRAME_SIZE: u64 = 2448;
+
+/// CHD tracks are padded to a multiple of this many frames.
+const CHD_TRACK_PADDING: u64 = 4;
+
+/// A track described by a CHD's CD-ROM or GD-ROM metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChdTrack {
+    pub number: u32,
+    /// Track type, e.g. `MODE1_RAW` or `AUDIO`.
+    pub kind: String,
+    /// Frames stored for the track, including any stored pregap.
+    pub frames: u64,
+    /// Pregap frames stored ahead of the track's first sector.
+    pub stored_pregap: u64,
+    /// First frame of the track within the CHD.
+    pub chd_offset: u64,
+}
+
+impl ChdTrack {
+    pub fn is_data(&self) -> bool {
+        self.kind != "AUDIO"
+    }
+
+    /// Bytes of each frame that belong in the track's BIN file.
+    pub fn sector_size(&self) -> u64 {
+        match self.kind.as_str() {
+            "MODE1" | "MODE2_FORM1" => 2048,
+            "MODE2_FORM2" => 2324,
+            "MODE2" | "MODE2_FORM_MIX" => 2336,
+            _ => RAW_SECTOR_SIZE,
+        }
+    }
+
+    /// Where the user data starts within a frame.
+    pub fn data_offset(&self) -> u64 {
+        match self.kind.as_str() {
+            "MODE1_RAW" => 16,
+            "MODE2_RAW" => 24,
+            "MODE2" | "MODE2_FORM_MIX" => 8,
+            _ => 0,
+        }
+    }
+}
+
+/// Parse one CHD track metadata entry, e.g.
+/// `TRACK:1 TYPE:MODE1_RAW SUBTYPE:NONE FRAMES:1350 PREGAP:150 PGTYPE:MODE1_RAW`.
+///
+/// A `PGTYPE` starting with `V` means the pregap is stored in the image.
+/// The offset within the CHD is filled in by [`layout_chd_tracks`].
+pub fn parse_chd_track(text: &str) -> Option<ChdTrack> {
+    let field = |name: &str| {
+        let prefix = format!("{}:", name);
+        text.split_whitespace()
+            .find_map(|token| token.strip_prefix(&prefix))
+            .map(|v| v.trim_end_matches('\0'))
+    };
+    let stored_pregap = match field("PGTYPE") {
+        Some(pgtype) if pgtype.starts_with('V') => {
+            field("PREGAP").and_then(|p| p.parse().ok()).unwrap_or(0)
+        }
+        _ => 0,
+    };
+    Some(ChdTrack {
+        number: field("TRACK")?.parse().ok()?,
+        kind: field("TYPE")?.to_string(),
+        frames: field("FRAMES")?.parse().ok()?,
+        stored_pregap,
+        chd_offset: 0,
+    })
+}
+
+/// Sort tracks and work out where each starts, allowing for the padding
+/// CHD adds after every track.
+pub fn layout_chd_tracks(mut tracks: Vec<ChdTrack>) -> Vec<ChdTrack> {
+    tracks.sort_by_key(|t| t.number);
+    let mut offset = 0;
+    for track in &mut tracks {
+        track.chd_offset = offset;
+        offset += track.frames.next_multiple_of(CHD_TRACK_PADDING);
+    }
+    tracks
+}
+
+#[cfg(test)]
+#[path = "../tests/cd_tests.rs"]
+mod tests;
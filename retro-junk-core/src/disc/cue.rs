@@ -0,0 +1,174 @@
+//! CUE sheet parsing.
+//!
+//! A CUE sheet lists a disc image's track files (`FILE`), the tracks in each
+//! (`TRACK`), and where each track starts (`INDEX 01`). This is the one CUE
+//! parser: the analyzers read track layouts from it, and DAT matching,
+//! renaming and scanning follow its `FILE` entries to the track files.
+
+use std::io::{self, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::ReadSeek;
+
+/// Frames (sectors) per second of CD audio, for `mm:ss:ff` positions.
+const FRAMES_PER_SECOND: u64 = 75;
+
+/// A parsed CUE sheet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CueSheet {
+    pub files: Vec<CueFile>,
+}
+
+/// A `FILE` entry in a CUE sheet with its tracks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CueFile {
+    pub filename: String,
+    /// File type, e.g. `BINARY` or `WAVE`.
+    pub file_type: String,
+    pub tracks: Vec<CueTrack>,
+}
+
+/// A `TRACK` entry in a CUE sheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CueTrack {
+    pub number: u32,
+    /// Track mode, e.g. `AUDIO` or `MODE2/2352`.
+    pub mode: String,
+    /// Position of `INDEX 01` in frames from the start of the file.
+    pub start: u64,
+}
+
+impl CueTrack {
+    /// Whether this is a data track (any `MODEx/...` mode).
+    pub fn is_data(&self) -> bool {
+        self.mode.to_uppercase().starts_with("MODE")
+    }
+}
+
+impl CueSheet {
+    /// Names of the files the sheet references, in order.
+    pub fn filenames(&self) -> impl Iterator<Item = &str> {
+        self.files.iter().map(|f| f.filename.as_str())
+    }
+
+    /// Every track in the sheet, in order.
+    pub fn tracks(&self) -> impl Iterator<Item = &CueTrack> {
+        self.files.iter().flat_map(|f| &f.tracks)
+    }
+}
+
+/// Parse the `FILE`, `TRACK`, and `INDEX 01` lines of a CUE sheet.
+///
+/// Other lines (`REM`, `PREGAP`, ...) and lines that don't parse are
+/// skipped, as are `TRACK` lines before the first `FILE`.
+pub fn parse(text: &str) -> CueSheet {
+    let mut files: Vec<CueFile> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some((filename, file_type)) = parse_file_directive(line) {
+            files.push(CueFile {
+                filename,
+                file_type,
+                tracks: Vec::new(),
+            });
+            continue;
+        }
+        let mut words = line.split_whitespace();
+        match words.next().map(str::to_uppercase).as_deref() {
+            Some("TRACK") => {
+                let (Some(number), Some(mode), Some(file)) =
+                    (words.next(), words.next(), files.last_mut())
+                else {
+                    continue;
+                };
+                let Ok(number) = number.parse() else {
+                    continue;
+                };
+                file.tracks.push(CueTrack {
+                    number,
+                    mode: mode.to_string(),
+                    start: 0,
+                });
+            }
+            Some("INDEX") => {
+                if words.next().and_then(|n| n.parse::<u32>().ok()) != Some(1) {
+                    continue;
+                }
+                let start = words.next().and_then(parse_msf);
+                if let (Some(start), Some(track)) =
+                    (start, files.last_mut().and_then(|f| f.tracks.last_mut()))
+                {
+                    track.start = start;
+                }
+            }
+            _ => {}
+        }
+    }
+    CueSheet { files }
+}
+
+/// Parse a `FILE` line, returning (filename, file type).
+///
+/// Handles quoted and unquoted filenames and any case of the keyword:
+///   FILE "filename.bin" BINARY
+///   File filename.bin BINARY
+pub fn parse_file_directive(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if !line.get(..5)?.eq_ignore_ascii_case("FILE ") {
+        return None;
+    }
+    let rest = line[5..].trim_start();
+
+    if let Some(after_quote) = rest.strip_prefix('"') {
+        let (filename, remainder) = after_quote.split_once('"')?;
+        Some((filename.to_string(), remainder.trim().to_string()))
+    } else {
+        let (filename, remainder) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        Some((filename.to_string(), remainder.trim().to_string()))
+    }
+}
+
+/// Whether the first bytes of a file look like a CUE sheet: text with both
+/// `FILE` and `TRACK` directives.
+pub fn looks_like_sheet(head: &[u8]) -> bool {
+    let is_binary = head
+        .iter()
+        .any(|&b| b < 0x09 || (b > 0x0D && b < 0x20 && b != 0x1A));
+    if head.is_empty() || is_binary {
+        return false;
+    }
+    let text = String::from_utf8_lossy(head).to_uppercase();
+    text.contains("FILE ") && text.contains("TRACK ")
+}
+
+/// Check the start of `reader` with [`looks_like_sheet`], leaving it
+/// rewound.
+pub fn sniff(reader: &mut dyn ReadSeek) -> io::Result<bool> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut head = [0u8; 512];
+    let n = reader.read(&mut head)?;
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(looks_like_sheet(&head[..n]))
+}
+
+/// Convert `mm:ss:ff` to frames.
+pub fn parse_msf(msf: &str) -> Option<u64> {
+    let mut parts = msf.split(':').map(|p| p.parse::<u64>().ok());
+    let (m, s, f) = (parts.next()??, parts.next()??, parts.next()??);
+    Some((m * 60 + s) * FRAMES_PER_SECOND + f)
+}
+
+/// The files referenced by the CUE sheet at `cue_path`, resolved relative
+/// to it, in sheet order. The files aren't checked for existence.
+pub fn file_paths(cue_path: &Path) -> io::Result<Vec<PathBuf>> {
+    let text = std::fs::read_to_string(cue_path)?;
+    let dir = cue_path.parent().unwrap_or(Path::new(""));
+    Ok(parse(&text)
+        .filenames()
+        .map(|name| dir.join(name))
+        .collect())
+}
+
+#[cfg(test)]
+#[path = "../tests/cue_tests.rs"]
+mod tests;
@@ -0,0 +1,433 @@
+//! ISO 9660 filesystem reading, with Joliet names.
+//!
+//! Disc analyzers find their boot files (SYSTEM.CNF, 0.BIN, 1ST_READ.BIN)
+//! through the disc's filesystem. [`Iso9660`] reads it from any source of
+//! 2048-byte logical sectors, so the same code serves cooked ISOs, raw
+//! 2352-byte BINs and compressed containers: the caller only says how to
+//! fetch a sector. Every size and count taken from the disc is checked
+//! against [`ParseLimits`] before it's acted on.
+
+use crate::{AnalysisError, BuildDate, ParseLimits};
+
+/// Bytes of user data in a logical sector.
+pub const SECTOR_SIZE: usize = 2048;
+
+/// First volume descriptor sector.
+pub const VOLUME_DESCRIPTOR_START: u64 = 16;
+
+/// Volume descriptors read before giving up on a terminator.
+const MAX_VOLUME_DESCRIPTORS: u64 = 32;
+
+/// Directory levels [`Iso9660::walk`] descends; ISO 9660 allows 8.
+const MAX_DEPTH: usize = 16;
+
+/// Fixed part of a directory record, before the identifier.
+const DIR_RECORD_HEADER: usize = 33;
+
+/// Directory record flag for directories.
+const FLAG_DIRECTORY: u8 = 0x02;
+
+/// Volume descriptor types.
+const TYPE_PRIMARY: u8 = 1;
+const TYPE_SUPPLEMENTARY: u8 = 2;
+const TYPE_TERMINATOR: u8 = 255;
+
+/// Escape sequences marking a supplementary descriptor as Joliet (UCS-2
+/// levels 1 to 3).
+const JOLIET_ESCAPES: [&[u8; 3]; 3] = [b"%/@", b"%/C", b"%/E"];
+
+/// Identifiers of the UDF descriptor in the volume recognition sequence
+/// (ECMA-167 2/9.1 and 3/9.1): NSR02 for UDF up to 1.50, NSR03 after.
+const UDF_NSR_IDS: [&[u8; 5]; 2] = [b"NSR02", b"NSR03"];
+
+/// Fetches one 2048-byte logical sector by LBA.
+pub trait SectorSource {
+    fn read_sector(&mut self, lba: u64) -> Result<[u8; SECTOR_SIZE], AnalysisError>;
+}
+
+impl<F> SectorSource for F
+where
+    F: FnMut(u64) -> Result<[u8; SECTOR_SIZE], AnalysisError>,
+{
+    fn read_sector(&mut self, lba: u64) -> Result<[u8; SECTOR_SIZE], AnalysisError> {
+        self(lba)
+    }
+}
+
+/// Parsed Primary Volume Descriptor.
+#[derive(Debug, Clone)]
+pub struct PrimaryVolumeDescriptor {
+    /// System identifier (offset 8, 32 bytes). e.g. "PLAYSTATION"
+    pub system_identifier: String,
+    /// Volume identifier (offset 40, 32 bytes).
+    pub volume_identifier: String,
+    /// Volume space size in sectors (offset 80, LE u32).
+    pub volume_space_size: u32,
+    /// LBA of root directory extent (from root dir record at offset 156).
+    pub root_dir_extent_lba: u32,
+    /// Size of root directory data in bytes.
+    pub root_dir_data_length: u32,
+    /// Size of the path table in bytes (offset 132).
+    pub path_table_size: u32,
+    /// LBA of the little-endian path table (offset 140).
+    pub path_table_lba: u32,
+    /// Volume creation date (offset 813, "YYYYMMDDHHMMSScc" + timezone).
+    pub creation_date: Option<BuildDate>,
+}
+
+impl PrimaryVolumeDescriptor {
+    /// Parse a volume descriptor sector, checking its type and "CD001".
+    pub fn parse(sector: &[u8; SECTOR_SIZE]) -> Result<Self, AnalysisError> {
+        if sector[0] != TYPE_PRIMARY {
+            return Err(AnalysisError::invalid_format(format!(
+                "Expected PVD type 0x01, got 0x{:02X}",
+                sector[0]
+            )));
+        }
+        if &sector[1..6] != b"CD001" {
+            // The PVD is always the first volume descriptor
+            return Err(AnalysisError::invalid_magic(
+                VOLUME_DESCRIPTOR_START * SECTOR_SIZE as u64 + 1,
+                b"CD001",
+                &sector[1..6],
+            ));
+        }
+        let root = &sector[156..190];
+        Ok(Self {
+            system_identifier: read_str_a(&sector[8..40]),
+            volume_identifier: read_str_a(&sector[40..72]),
+            volume_space_size: le_u32(&sector[80..84]),
+            root_dir_extent_lba: le_u32(&root[2..6]),
+            root_dir_data_length: le_u32(&root[10..14]),
+            path_table_size: le_u32(&sector[132..136]),
+            path_table_lba: le_u32(&sector[140..144]),
+            // Only the date part of the creation timestamp is kept
+            creation_date: std::str::from_utf8(&sector[813..821])
+                .ok()
+                .and_then(BuildDate::parse),
+        })
+    }
+
+    /// Size in bytes of the whole volume stored with `sector_size`-byte
+    /// sectors: 2048 for an ISO, 2352 for a raw BIN.
+    pub fn image_size(&self, sector_size: u64) -> u64 {
+        u64::from(self.volume_space_size) * sector_size
+    }
+
+    /// The root directory as an entry.
+    pub fn root(&self) -> DirEntry {
+        DirEntry {
+            name: String::new(),
+            extent_lba: self.root_dir_extent_lba,
+            data_length: self.root_dir_data_length,
+            flags: FLAG_DIRECTORY,
+        }
+    }
+}
+
+/// A file or directory in a directory listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirEntry {
+    /// Name without the ";1" version suffix.
+    pub name: String,
+    pub extent_lba: u32,
+    pub data_length: u32,
+    pub flags: u8,
+}
+
+impl DirEntry {
+    pub fn is_dir(&self) -> bool {
+        self.flags & FLAG_DIRECTORY != 0
+    }
+
+    /// Whether `name` names this entry: case-insensitive, ignoring a
+    /// ";1" version suffix.
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.split(';').next().unwrap_or(name);
+        self.name.eq_ignore_ascii_case(name)
+    }
+}
+
+/// An entry of the path table: one directory, with its parent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathTableEntry {
+    pub name: String,
+    pub extent_lba: u32,
+    /// 1-based index of the parent entry; the root is its own parent.
+    pub parent: u16,
+}
+
+/// An ISO 9660 filesystem on a sector source.
+pub struct Iso9660<S> {
+    source: S,
+    pvd: PrimaryVolumeDescriptor,
+    /// Root of the Joliet tree, when the disc has one.
+    joliet_root: Option<DirEntry>,
+    limits: ParseLimits,
+}
+
+impl<S: SectorSource> Iso9660<S> {
+    /// Read the volume descriptors starting at sector 16.
+    ///
+    /// Fails if the first descriptor isn't a Primary Volume Descriptor. A
+    /// Joliet supplementary descriptor after it, if any, provides long
+    /// names for listings and lookups.
+    pub fn open(mut source: S, limits: &ParseLimits) -> Result<Self, AnalysisError> {
+        let pvd = PrimaryVolumeDescriptor::parse(&source.read_sector(VOLUME_DESCRIPTOR_START)?)?;
+        let mut joliet_root = None;
+        for lba in VOLUME_DESCRIPTOR_START + 1..VOLUME_DESCRIPTOR_START + MAX_VOLUME_DESCRIPTORS {
+            let Ok(sector) = source.read_sector(lba) else {
+                break;
+            };
+            if &sector[1..6] != b"CD001" || sector[0] == TYPE_TERMINATOR {
+                break;
+            }
+            if sector[0] == TYPE_SUPPLEMENTARY
+                && JOLIET_ESCAPES.iter().any(|e| &sector[88..91] == *e)
+            {
+                let root = &sector[156..190];
+                joliet_root = Some(DirEntry {
+                    name: String::new(),
+                    extent_lba: le_u32(&root[2..6]),
+                    data_length: le_u32(&root[10..14]),
+                    flags: FLAG_DIRECTORY,
+                });
+                break;
+            }
+        }
+        Ok(Self {
+            source,
+            pvd,
+            joliet_root,
+            limits: *limits,
+        })
+    }
+
+    /// Use an already-parsed PVD, without looking for a Joliet tree.
+    pub fn with_pvd(source: S, pvd: PrimaryVolumeDescriptor, limits: &ParseLimits) -> Self {
+        Self {
+            source,
+            pvd,
+            joliet_root: None,
+            limits: *limits,
+        }
+    }
+
+    pub fn pvd(&self) -> &PrimaryVolumeDescriptor {
+        &self.pvd
+    }
+
+    /// Whether names come from a Joliet tree.
+    pub fn is_joliet(&self) -> bool {
+        self.joliet_root.is_some()
+    }
+
+    /// The root directory.
+    pub fn root(&self) -> DirEntry {
+        self.joliet_root.clone().unwrap_or_else(|| self.pvd.root())
+    }
+
+    /// List a directory, without its "." and ".." entries.
+    pub fn read_dir(&mut self, dir: &DirEntry) -> Result<Vec<DirEntry>, AnalysisError> {
+        let sectors = u64::from(dir.data_length).div_ceil(SECTOR_SIZE as u64);
+        self.limits.check_entries(sectors, "ISO 9660 directory")?;
+        let joliet = self.is_joliet();
+
+        let mut entries = Vec::new();
+        for i in 0..sectors {
+            let sector = self.source.read_sector(u64::from(dir.extent_lba) + i)?;
+            let mut pos = 0;
+            // Records never span sectors; a zero length pads to the next one
+            while pos < SECTOR_SIZE && sector[pos] != 0 {
+                let len = sector[pos] as usize;
+                if pos + len > SECTOR_SIZE {
+                    break;
+                }
+                if let Some(entry) = parse_dir_record(&sector[pos..pos + len], joliet) {
+                    entries.push(entry);
+                    self.limits
+                        .check_entries(entries.len() as u64, "ISO 9660 directory")?;
+                }
+                pos += len;
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Find a file or directory by path ("SYSTEM.CNF", "/DATA/0.BIN",
+    /// "cdrom:\\SLUS_012.34;1"), case-insensitively.
+    pub fn lookup(&mut self, path: &str) -> Result<Option<DirEntry>, AnalysisError> {
+        let path = path.strip_prefix("cdrom0:").unwrap_or(path);
+        let path = path.strip_prefix("cdrom:").unwrap_or(path);
+        let mut current = self.root();
+        let components: Vec<&str> = path.split(['/', '\\']).filter(|c| !c.is_empty()).collect();
+        for (i, component) in components.iter().enumerate() {
+            if !current.is_dir() {
+                return Ok(None);
+            }
+            let Some(next) = self
+                .read_dir(&current)?
+                .into_iter()
+                .find(|e| e.matches(component))
+            else {
+                return Ok(None);
+            };
+            if i + 1 < components.len() && !next.is_dir() {
+                return Ok(None);
+            }
+            current = next;
+        }
+        Ok(Some(current))
+    }
+
+    /// Read a file's contents.
+    pub fn read_file(&mut self, entry: &DirEntry) -> Result<Vec<u8>, AnalysisError> {
+        let len = self
+            .limits
+            .check_alloc(u64::from(entry.data_length), "ISO 9660 file")?;
+        let mut data = Vec::with_capacity(len);
+        let mut lba = u64::from(entry.extent_lba);
+        while data.len() < len {
+            let sector = self.source.read_sector(lba)?;
+            let n = (len - data.len()).min(SECTOR_SIZE);
+            data.extend_from_slice(&sector[..n]);
+            lba += 1;
+        }
+        Ok(data)
+    }
+
+    /// Find a file by path and read it, failing if it's missing.
+    pub fn read_path(&mut self, path: &str) -> Result<Vec<u8>, AnalysisError> {
+        match self.lookup(path)? {
+            Some(entry) if !entry.is_dir() => self.read_file(&entry),
+            _ => Err(AnalysisError::other(format!(
+                "File '{path}' not found on disc"
+            ))),
+        }
+    }
+
+    /// Every file and directory, depth first, with "/"-separated paths.
+    pub fn walk(&mut self) -> Result<Vec<(String, DirEntry)>, AnalysisError> {
+        let mut out = Vec::new();
+        let root = self.root();
+        self.walk_into(&root, "", 0, &mut out)?;
+        Ok(out)
+    }
+
+    fn walk_into(
+        &mut self,
+        dir: &DirEntry,
+        prefix: &str,
+        depth: usize,
+        out: &mut Vec<(String, DirEntry)>,
+    ) -> Result<(), AnalysisError> {
+        if depth >= MAX_DEPTH {
+            return Ok(());
+        }
+        for entry in self.read_dir(dir)? {
+            let path = format!("{prefix}/{}", entry.name);
+            let is_dir = entry.is_dir() && entry.extent_lba != dir.extent_lba;
+            out.push((path.clone(), entry.clone()));
+            self.limits
+                .check_entries(out.len() as u64, "ISO 9660 filesystem")?;
+            if is_dir {
+                self.walk_into(&entry, &path, depth + 1, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the little-endian path table: every directory on the disc in
+    /// one read, without walking the tree. Names are the ISO 9660 ones.
+    pub fn path_table(&mut self) -> Result<Vec<PathTableEntry>, AnalysisError> {
+        let len = self
+            .limits
+            .check_alloc(u64::from(self.pvd.path_table_size), "ISO 9660 path table")?;
+        let table = self.read_file(&DirEntry {
+            name: String::new(),
+            extent_lba: self.pvd.path_table_lba,
+            data_length: len as u32,
+            flags: 0,
+        })?;
+
+        let mut entries = Vec::new();
+        let mut pos = 0;
+        while pos + 8 <= table.len() {
+            let name_len = table[pos] as usize;
+            if name_len == 0 || pos + 8 + name_len > table.len() {
+                break;
+            }
+            let name = &table[pos + 8..pos + 8 + name_len];
+            entries.push(PathTableEntry {
+                name: if name == [0] {
+                    String::new()
+                } else {
+                    String::from_utf8_lossy(name).into_owned()
+                },
+                extent_lba: le_u32(&table[pos + 2..pos + 6]),
+                parent: u16::from_le_bytes([table[pos + 6], table[pos + 7]]),
+            });
+            self.limits
+                .check_entries(entries.len() as u64, "ISO 9660 path table")?;
+            // Records are padded to an even length
+            pos += 8 + name_len + name_len % 2;
+        }
+        Ok(entries)
+    }
+}
+
+/// Parse a directory record, skipping "." and "..".
+fn parse_dir_record(record: &[u8], joliet: bool) -> Option<DirEntry> {
+    if record.len() < DIR_RECORD_HEADER {
+        return None;
+    }
+    let id_len = record[32] as usize;
+    let id = record.get(DIR_RECORD_HEADER..DIR_RECORD_HEADER + id_len)?;
+    if id.is_empty() || id == [0] || id == [1] {
+        return None;
+    }
+    let name = if joliet {
+        let units: Vec<u16> = id
+            .chunks_exact(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(id).into_owned()
+    };
+    let name = name.split(';').next().unwrap_or(&name);
+    // Files without an extension are recorded with a trailing dot
+    let name = name.strip_suffix('.').unwrap_or(name);
+    Some(DirEntry {
+        name: name.to_string(),
+        extent_lba: le_u32(&record[2..6]),
+        data_length: le_u32(&record[10..14]),
+        flags: record[25],
+    })
+}
+
+/// Whether the disc also carries a UDF filesystem, as DVD-ROMs mastered
+/// as UDF bridge discs do.
+///
+/// UDF's volume recognition sequence follows the ISO 9660 descriptors from
+/// sector 16 on, in sectors of the same shape: a type byte and a 5-byte
+/// identifier.
+pub fn has_udf<S: SectorSource>(source: &mut S) -> bool {
+    (VOLUME_DESCRIPTOR_START..VOLUME_DESCRIPTOR_START + MAX_VOLUME_DESCRIPTORS)
+        .map_while(|lba| source.read_sector(lba).ok())
+        .any(|sector| UDF_NSR_IDS.iter().any(|id| &sector[1..6] == *id))
+}
+
+/// Read a padded ISO 9660 string (strip trailing spaces).
+fn read_str_a(bytes: &[u8]) -> String {
+    let s = std::str::from_utf8(bytes).unwrap_or("");
+    s.trim_end().to_string()
+}
+
+fn le_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+#[cfg(test)]
+#[path = "../tests/iso9660_tests.rs"]
+mod tests;
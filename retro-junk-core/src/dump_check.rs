@@ -0,0 +1,201 @@
+//! Heuristic bad-dump detection.
+//!
+//! These checks look for the shapes corrupt dumps tend to have, independent
+//! of any platform's header format, so damaged files can be flagged even when
+//! no DAT lists them:
+//!
+//! - the file is entirely 0x00 or 0xFF (a cartridge that didn't read at all)
+//! - everything past the header's declared size is 0x00 or 0xFF (overdump
+//!   padding from a dumper that read the wrong size)
+//! - a long run of identical 1 KiB blocks (bus not switching banks)
+//! - a disc image whose size isn't a whole number of sectors
+
+use std::fmt;
+use std::io::SeekFrom;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::read_full;
+use crate::{AnalysisError, ReadSeek};
+
+/// Block size used for the repeated-block check.
+pub const BLOCK_SIZE: usize = 1024;
+
+/// Minimum run of identical blocks reported as suspicious (64 KiB). Runs of
+/// uniform fill bytes are ordinary padding and are not counted.
+pub const MIN_REPEATED_BLOCKS: u64 = 64;
+
+/// A structured bad-dump warning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DumpWarning {
+    /// The whole file is a single fill byte.
+    BlankFile { fill: u8 },
+    /// Everything past the expected size is a single fill byte.
+    BlankPadding { offset: u64, len: u64, fill: u8 },
+    /// `count` consecutive identical 1 KiB blocks starting at `offset`.
+    RepeatedBlocks { offset: u64, count: u64 },
+    /// A disc image ends `remainder` bytes into a sector.
+    TruncatedSector { sector_size: u64, remainder: u64 },
+}
+
+impl fmt::Display for DumpWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BlankFile { fill } => write!(f, "File is entirely 0x{:02X} bytes", fill),
+            Self::BlankPadding { offset, len, fill } => write!(
+                f,
+                "{} bytes past the expected size (from 0x{:X}) are all 0x{:02X}",
+                len, offset, fill
+            ),
+            Self::RepeatedBlocks { offset, count } => write!(
+                f,
+                "{} identical 1 KB blocks starting at 0x{:X}",
+                count, offset
+            ),
+            Self::TruncatedSector {
+                sector_size,
+                remainder,
+            } => write!(
+                f,
+                "Image ends {} bytes into a {}-byte sector",
+                remainder, sector_size
+            ),
+        }
+    }
+}
+
+/// Sector size of a raw disc image, by extension: 2048 for `.iso`, 2352 for
+/// `.bin`/`.img`. Compressed and container formats return `None`.
+///
+/// Only meaningful for disc platforms; `.bin` is also a cartridge extension.
+pub fn disc_sector_size(path: &Path) -> Option<u64> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    match ext.as_str() {
+        "iso" => Some(2048),
+        "bin" | "img" => Some(2352),
+        _ => None,
+    }
+}
+
+/// Run all heuristics over `reader`, reading it once from start to end.
+///
+/// `expected_size` is the size declared by the header, if known.
+/// `sector_size` enables the truncated-sector check for raw disc images.
+pub fn check_dump(
+    reader: &mut dyn ReadSeek,
+    expected_size: Option<u64>,
+    sector_size: Option<u64>,
+) -> Result<Vec<DumpWarning>, AnalysisError> {
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    let mut warnings = Vec::new();
+    if file_size == 0 {
+        return Ok(warnings);
+    }
+
+    // Only look for padding when the file is larger than declared
+    let padding_start = expected_size.filter(|&e| e > 0 && e < file_size);
+
+    let mut whole_fill = Fill::default();
+    let mut padding_fill = Fill::default();
+    let mut prev_block: Vec<u8> = Vec::new();
+    let mut run_start = 0u64;
+    let mut run_len = 0u64;
+    let mut longest: Option<(u64, u64)> = None;
+
+    let mut buf = vec![0u8; BLOCK_SIZE];
+    let mut offset = 0u64;
+    loop {
+        let n = read_full(reader, &mut buf)?;
+        if n == 0 {
+            break;
+        }
+        let block = &buf[..n];
+
+        whole_fill.feed(block);
+        if let Some(start) = padding_start {
+            let end = offset + n as u64;
+            if end > start {
+                let skip = start.saturating_sub(offset) as usize;
+                padding_fill.feed(&block[skip..]);
+            }
+        }
+
+        if n == BLOCK_SIZE && !is_uniform(block) && block == prev_block.as_slice() {
+            run_len += 1;
+        } else {
+            if run_len >= MIN_REPEATED_BLOCKS && longest.is_none_or(|(_, l)| run_len > l) {
+                longest = Some((run_start, run_len));
+            }
+            run_start = offset;
+            run_len = 1;
+            prev_block.clear();
+            prev_block.extend_from_slice(block);
+        }
+        offset += n as u64;
+    }
+    if run_len >= MIN_REPEATED_BLOCKS && longest.is_none_or(|(_, l)| run_len > l) {
+        longest = Some((run_start, run_len));
+    }
+
+    if let Some(fill) = whole_fill.value() {
+        warnings.push(DumpWarning::BlankFile { fill });
+    } else {
+        if let (Some(start), Some(fill)) = (padding_start, padding_fill.value()) {
+            warnings.push(DumpWarning::BlankPadding {
+                offset: start,
+                len: file_size - start,
+                fill,
+            });
+        }
+        if let Some((offset, count)) = longest {
+            warnings.push(DumpWarning::RepeatedBlocks { offset, count });
+        }
+    }
+
+    if let Some(sector_size) = sector_size.filter(|&s| s > 0) {
+        let remainder = file_size % sector_size;
+        if remainder != 0 {
+            warnings.push(DumpWarning::TruncatedSector {
+                sector_size,
+                remainder,
+            });
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// Tracks whether every byte seen so far is the same 0x00 or 0xFF.
+#[derive(Default)]
+struct Fill {
+    byte: Option<u8>,
+    mixed: bool,
+}
+
+impl Fill {
+    fn feed(&mut self, bytes: &[u8]) {
+        if self.mixed || bytes.is_empty() {
+            return;
+        }
+        let first = *self.byte.get_or_insert(bytes[0]);
+        if !(first == 0x00 || first == 0xFF) || bytes.iter().any(|&b| b != first) {
+            self.mixed = true;
+        }
+    }
+
+    fn value(&self) -> Option<u8> {
+        if self.mixed { None } else { self.byte }
+    }
+}
+
+fn is_uniform(block: &[u8]) -> bool {
+    block.iter().all(|&b| b == block[0])
+}
+
+#[cfg(test)]
+#[path = "tests/dump_check_tests.rs"]
+mod tests;
@@ -1,11 +1,35 @@
+use std::fmt;
+
 use thiserror::Error;
 
+use crate::limits::LimitExceeded;
+
 /// Errors that can occur during ROM analysis.
+///
+/// Each variant has a stable [`code`](Self::code) and a broad
+/// [`kind`](Self::kind), so callers can tell "not this platform" apart from
+/// "corrupt file" and "format we don't support yet" without matching on
+/// message text.
 #[derive(Debug, Error)]
 pub enum AnalysisError {
-    /// I/O error while reading the ROM
-    #[error("I/O error: {0}")]
-    Io(#[from] std::io::Error),
+    /// I/O error while reading the ROM, with the offset being read if known
+    #[error("{}", io_message(source, *offset))]
+    Io {
+        source: std::io::Error,
+        offset: Option<u64>,
+    },
+
+    /// The magic bytes at `offset` don't identify this format
+    #[error(
+        "Invalid magic at 0x{offset:X}: expected {}, found {}",
+        hex_bytes(expected),
+        hex_bytes(found)
+    )]
+    InvalidMagic {
+        offset: u64,
+        expected: Vec<u8>,
+        found: Vec<u8>,
+    },
 
     /// The ROM format is not recognized or is invalid
     #[error("Invalid ROM format: {0}")]
@@ -15,18 +39,27 @@ pub enum AnalysisError {
     #[error("Corrupted header: {0}")]
     CorruptedHeader(String),
 
+    /// The header ends before all of its fields could be read
+    #[error("Truncated header: expected {expected} bytes, got {actual}")]
+    TruncatedHeader { expected: u64, actual: u64 },
+
     /// The ROM is too small to contain valid data
     #[error("ROM too small: expected at least {expected} bytes, got {actual}")]
     TooSmall { expected: u64, actual: u64 },
 
-    /// Unsupported ROM variant or version
-    #[error("Unsupported variant: {0}")]
-    UnsupportedVariant(String),
+    /// A recognized format in a variant or container that isn't supported yet
+    #[error("Unsupported format: {0}")]
+    UnsupportedSubFormat(String),
 
     /// Checksum verification failed
     #[error("Checksum mismatch: expected {expected}, got {actual}")]
     ChecksumMismatch { expected: String, actual: String },
 
+    /// The file needs more than the configured
+    /// [`ParseLimits`](crate::ParseLimits) allow
+    #[error(transparent)]
+    LimitExceeded(#[from] LimitExceeded),
+
     /// Progress channel disconnected
     #[error("Progress channel disconnected")]
     ChannelDisconnected,
@@ -36,6 +69,68 @@ pub enum AnalysisError {
     Other(String),
 }
 
+/// Broad classes of analysis failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnalysisErrorKind {
+    /// The file isn't in the analyzer's format; another platform may claim it.
+    NotThisFormat,
+    /// The file is in the right format but damaged or incomplete.
+    Corrupt,
+    /// The format is recognized but this variant isn't handled yet.
+    Unsupported,
+    /// The file couldn't be read.
+    Io,
+    /// The file needs more memory, seeks or entries than allowed.
+    Limit,
+    /// Anything else.
+    Other,
+}
+
+impl AnalysisErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotThisFormat => "not_this_format",
+            Self::Corrupt => "corrupt",
+            Self::Unsupported => "unsupported",
+            Self::Io => "io",
+            Self::Limit => "limit",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for AnalysisErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<std::io::Error> for AnalysisError {
+    fn from(source: std::io::Error) -> Self {
+        match into_limit_exceeded(source) {
+            Ok(limit) => Self::LimitExceeded(limit),
+            Err(source) => Self::Io {
+                source,
+                offset: None,
+            },
+        }
+    }
+}
+
+/// Recover a [`LimitExceeded`] that a
+/// [`LimitedReader`](crate::LimitedReader) wrapped in an I/O error.
+fn into_limit_exceeded(source: std::io::Error) -> Result<LimitExceeded, std::io::Error> {
+    if source
+        .get_ref()
+        .is_some_and(|e| e.downcast_ref::<LimitExceeded>().is_some())
+    {
+        let inner = source.into_inner().expect("checked above");
+        Ok(*inner.downcast::<LimitExceeded>().expect("checked above"))
+    } else {
+        Err(source)
+    }
+}
+
 impl AnalysisError {
     pub fn invalid_format(msg: impl Into<String>) -> Self {
         Self::InvalidFormat(msg.into())
@@ -49,11 +144,90 @@ impl AnalysisError {
         Self::TooSmall { expected, actual }
     }
 
+    pub fn truncated_header(expected: u64, actual: u64) -> Self {
+        Self::TruncatedHeader { expected, actual }
+    }
+
+    pub fn invalid_magic(offset: u64, expected: &[u8], found: &[u8]) -> Self {
+        Self::InvalidMagic {
+            offset,
+            expected: expected.to_vec(),
+            found: found.to_vec(),
+        }
+    }
+
+    /// An I/O error that happened while reading at `offset`.
+    pub fn io_at(source: std::io::Error, offset: u64) -> Self {
+        match into_limit_exceeded(source) {
+            Ok(limit) => Self::LimitExceeded(limit),
+            Err(source) => Self::Io {
+                source,
+                offset: Some(offset),
+            },
+        }
+    }
+
     pub fn unsupported(msg: impl Into<String>) -> Self {
-        Self::UnsupportedVariant(msg.into())
+        Self::UnsupportedSubFormat(msg.into())
     }
 
     pub fn other(msg: impl Into<String>) -> Self {
         Self::Other(msg.into())
     }
+
+    /// Stable, machine-readable identifier for the variant.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Io { .. } => "io",
+            Self::InvalidMagic { .. } => "invalid_magic",
+            Self::InvalidFormat(_) => "invalid_format",
+            Self::CorruptedHeader(_) => "corrupted_header",
+            Self::TruncatedHeader { .. } => "truncated_header",
+            Self::TooSmall { .. } => "too_small",
+            Self::UnsupportedSubFormat(_) => "unsupported_sub_format",
+            Self::ChecksumMismatch { .. } => "checksum_mismatch",
+            Self::LimitExceeded(_) => "limit_exceeded",
+            Self::ChannelDisconnected => "channel_disconnected",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// The broad class of failure.
+    ///
+    /// Files too small for the format count as "not this format": analyzers
+    /// are tried on files of every platform, and a short file is far more
+    /// often another platform's than a damaged one.
+    pub fn kind(&self) -> AnalysisErrorKind {
+        match self {
+            Self::Io { .. } => AnalysisErrorKind::Io,
+            Self::InvalidMagic { .. } | Self::InvalidFormat(_) | Self::TooSmall { .. } => {
+                AnalysisErrorKind::NotThisFormat
+            }
+            Self::CorruptedHeader(_)
+            | Self::TruncatedHeader { .. }
+            | Self::ChecksumMismatch { .. } => AnalysisErrorKind::Corrupt,
+            Self::UnsupportedSubFormat(_) => AnalysisErrorKind::Unsupported,
+            Self::LimitExceeded(_) => AnalysisErrorKind::Limit,
+            Self::ChannelDisconnected | Self::Other(_) => AnalysisErrorKind::Other,
+        }
+    }
+}
+
+fn io_message(source: &std::io::Error, offset: Option<u64>) -> String {
+    match offset {
+        Some(offset) => format!("I/O error at 0x{:X}: {}", offset, source),
+        None => format!("I/O error: {}", source),
+    }
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
 }
+
+#[cfg(test)]
+#[path = "tests/error_tests.rs"]
+mod tests;
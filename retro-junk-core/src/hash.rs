@@ -0,0 +1,380 @@
+//! CRC32, SHA1, MD5 and SHA-256 digests of byte streams.
+//!
+//! This is the one implementation of the DAT hashes. The hasher in
+//! `retro-junk-lib`, DAT track matching and the platform crates' container
+//! hashing all feed their bytes through [`StreamHasher`] or [`hash_reader`].
+
+use std::io::{self, Read};
+use std::sync::Arc;
+use std::sync::mpsc::{self, SyncSender};
+use std::thread::{self, JoinHandle};
+
+use sha1::Digest;
+use sha1::digest::common::hazmat::{SerializableState, SerializedState};
+
+use crate::{FileHashes, HashAlgorithms};
+
+const CHUNK_SIZE: usize = 64 * 1024; // 64 KB
+
+/// Chunks buffered per worker thread before `update` waits for it to catch up.
+const QUEUE_DEPTH: usize = 4;
+
+/// Running CRC32, SHA1, MD5 and SHA-256 of data fed in pieces. Only the
+/// digests `algorithms` asks for are computed.
+pub struct StreamHasher {
+    crc: crc32fast::Hasher,
+    digests: Digests,
+    len: u64,
+}
+
+enum Digests {
+    Inline {
+        sha1: Option<sha1::Sha1>,
+        md5: Option<md5::Context>,
+        sha256: Option<sha2::Sha256>,
+    },
+    /// SHA1, MD5 and SHA-256 each on their own thread, for large inputs.
+    Threaded {
+        sha1: Option<DigestWorker<sha1::Sha1>>,
+        md5: Option<DigestWorker<md5::Context>>,
+        sha256: Option<DigestWorker<sha2::Sha256>>,
+    },
+}
+
+/// Saved progress of a [`StreamHasher`], for resuming a hash that was
+/// interrupted. Only CRC32 and SHA1 can be saved; the `md5` crate keeps its
+/// state private, and SHA-256 is only wanted alongside MD5.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashState {
+    /// Bytes hashed so far.
+    pub len: u64,
+    /// CRC32 of those bytes.
+    pub crc32: u32,
+    /// The `sha1` crate's serialized hasher state, when SHA1 is computed.
+    pub sha1: Option<Vec<u8>>,
+}
+
+impl StreamHasher {
+    pub fn new(algorithms: HashAlgorithms) -> Self {
+        Self {
+            crc: crc32fast::Hasher::new(),
+            digests: Digests::Inline {
+                sha1: algorithms.sha1().then(sha1::Sha1::new),
+                md5: algorithms.md5().then(md5::Context::new),
+                sha256: algorithms
+                    .sha256()
+                    .then(<sha2::Sha256 as sha2::Digest>::new),
+            },
+            len: 0,
+        }
+    }
+
+    /// A hasher that runs SHA1, MD5 and SHA-256 on worker threads, so
+    /// hashing them all takes about as long as the slowest alone. CRC32 is cheap enough to
+    /// stay on the calling thread. Worth it only for inputs of a few MB and
+    /// up, fed in large pieces.
+    pub fn threaded(algorithms: HashAlgorithms) -> Self {
+        Self {
+            crc: crc32fast::Hasher::new(),
+            digests: Digests::Threaded {
+                sha1: algorithms
+                    .sha1()
+                    .then(|| DigestWorker::spawn(sha1::Sha1::new())),
+                md5: algorithms
+                    .md5()
+                    .then(|| DigestWorker::spawn(md5::Context::new())),
+                sha256: algorithms
+                    .sha256()
+                    .then(|| DigestWorker::spawn(<sha2::Sha256 as sha2::Digest>::new())),
+            },
+            len: 0,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.crc.update(data);
+        match &mut self.digests {
+            Digests::Inline { sha1, md5, sha256 } => {
+                if let Some(s) = sha1 {
+                    StreamDigest::update(s, data);
+                }
+                if let Some(m) = md5 {
+                    m.consume(data);
+                }
+                if let Some(s) = sha256 {
+                    StreamDigest::update(s, data);
+                }
+            }
+            Digests::Threaded { sha1, md5, sha256 } => {
+                let chunk = Arc::new(data.to_vec());
+                if let Some(w) = sha1 {
+                    w.send(Job::Chunk(Arc::clone(&chunk)));
+                }
+                if let Some(w) = md5 {
+                    w.send(Job::Chunk(Arc::clone(&chunk)));
+                }
+                if let Some(w) = sha256 {
+                    w.send(Job::Chunk(chunk));
+                }
+            }
+        }
+        self.len += data.len() as u64;
+    }
+
+    /// Hash everything `reader` yields.
+    pub fn update_reader(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        loop {
+            let n = read_full(reader, &mut buf)?;
+            if n == 0 {
+                return Ok(());
+            }
+            self.update(&buf[..n]);
+        }
+    }
+
+    /// Number of bytes hashed so far.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Save the progress so far, or `None` when MD5 or SHA-256 is being
+    /// computed.
+    pub fn state(&self) -> Option<HashState> {
+        let sha1 = match &self.digests {
+            Digests::Inline { md5: Some(_), .. }
+            | Digests::Threaded { md5: Some(_), .. }
+            | Digests::Inline {
+                sha256: Some(_), ..
+            }
+            | Digests::Threaded {
+                sha256: Some(_), ..
+            } => {
+                return None;
+            }
+            Digests::Inline { sha1, .. } => sha1.as_ref().map(StreamDigest::save),
+            Digests::Threaded { sha1, .. } => sha1.as_ref().map(DigestWorker::save),
+        };
+        let sha1 = match sha1 {
+            Some(saved) => Some(saved?),
+            None => None,
+        };
+        Some(HashState {
+            len: self.len,
+            crc32: self.crc.clone().finalize(),
+            sha1,
+        })
+    }
+
+    /// Continue from a saved [`HashState`] instead of from the start.
+    ///
+    /// Returns false, leaving the hasher as it was, if it has already been
+    /// fed data or `state` doesn't hold the digests it computes.
+    pub fn resume(&mut self, state: &HashState) -> bool {
+        if !self.is_empty() {
+            return false;
+        }
+        let (sha1_slot, unsaveable_wanted) = match &self.digests {
+            Digests::Inline { sha1, md5, sha256 } => {
+                (sha1.is_some(), md5.is_some() || sha256.is_some())
+            }
+            Digests::Threaded { sha1, md5, sha256 } => {
+                (sha1.is_some(), md5.is_some() || sha256.is_some())
+            }
+        };
+        if unsaveable_wanted || sha1_slot != state.sha1.is_some() {
+            return false;
+        }
+        let sha1 = match state.sha1.as_deref().map(load_sha1) {
+            Some(None) => return false,
+            loaded => loaded.flatten(),
+        };
+        match (&mut self.digests, sha1) {
+            (Digests::Inline { sha1, .. }, Some(loaded)) => *sha1 = Some(loaded),
+            (Digests::Threaded { sha1: Some(w), .. }, Some(loaded)) => w.send(Job::Restore(loaded)),
+            _ => {}
+        }
+        self.crc = crc32fast::Hasher::new_with_initial_len(state.crc32, state.len);
+        self.len = state.len;
+        true
+    }
+
+    pub fn finish(self) -> FileHashes {
+        let (sha1, md5, sha256) = match self.digests {
+            Digests::Inline { sha1, md5, sha256 } => (
+                sha1.map(StreamDigest::finish_hex),
+                md5.map(StreamDigest::finish_hex),
+                sha256.map(StreamDigest::finish_hex),
+            ),
+            Digests::Threaded { sha1, md5, sha256 } => (
+                sha1.map(DigestWorker::finish),
+                md5.map(DigestWorker::finish),
+                sha256.map(DigestWorker::finish),
+            ),
+        };
+        FileHashes {
+            crc32: format!("{:08x}", self.crc.finalize()),
+            sha1,
+            md5,
+            sha256,
+            data_size: self.len,
+        }
+    }
+}
+
+fn load_sha1(bytes: &[u8]) -> Option<sha1::Sha1> {
+    let state = SerializedState::<sha1::Sha1>::try_from(bytes).ok()?;
+    sha1::Sha1::deserialize(&state).ok()
+}
+
+/// A digest [`StreamHasher`] can run inline or on a worker thread.
+trait StreamDigest: Send + 'static {
+    fn update(&mut self, data: &[u8]);
+    fn finish_hex(self) -> String;
+    /// Serialized state, if the digest supports saving it.
+    fn save(&self) -> Option<Vec<u8>>;
+}
+
+impl StreamDigest for sha1::Sha1 {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
+
+    fn finish_hex(self) -> String {
+        hex(&self.finalize())
+    }
+
+    fn save(&self) -> Option<Vec<u8>> {
+        Some(self.serialize().to_vec())
+    }
+}
+
+impl StreamDigest for sha2::Sha256 {
+    fn update(&mut self, data: &[u8]) {
+        sha2::Digest::update(self, data);
+    }
+
+    fn finish_hex(self) -> String {
+        hex(&sha2::Digest::finalize(self))
+    }
+
+    fn save(&self) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+impl StreamDigest for md5::Context {
+    fn update(&mut self, data: &[u8]) {
+        self.consume(data);
+    }
+
+    fn finish_hex(self) -> String {
+        format!("{:x}", self.compute())
+    }
+
+    fn save(&self) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// Work sent to a [`DigestWorker`], processed in order.
+enum Job<D> {
+    Chunk(Arc<Vec<u8>>),
+    /// Reply with the digest's state once every earlier chunk is hashed.
+    Save(SyncSender<Option<Vec<u8>>>),
+    /// Replace the digest with a restored one.
+    Restore(D),
+}
+
+/// A digest running on its own thread, fed chunks over a bounded channel.
+struct DigestWorker<D> {
+    tx: SyncSender<Job<D>>,
+    handle: JoinHandle<String>,
+}
+
+impl<D: StreamDigest> DigestWorker<D> {
+    fn spawn(mut digest: D) -> Self {
+        let (tx, rx) = mpsc::sync_channel::<Job<D>>(QUEUE_DEPTH);
+        let handle = thread::spawn(move || {
+            for job in rx {
+                match job {
+                    Job::Chunk(chunk) => digest.update(&chunk),
+                    Job::Save(reply) => {
+                        let _ = reply.send(digest.save());
+                    }
+                    Job::Restore(restored) => digest = restored,
+                }
+            }
+            digest.finish_hex()
+        });
+        Self { tx, handle }
+    }
+
+    fn send(&self, job: Job<D>) {
+        // A closed channel means the worker panicked; `finish` reports it
+        let _ = self.tx.send(job);
+    }
+
+    /// The digest's state after every chunk sent so far, or `None` if it
+    /// can't be saved or the worker has died.
+    fn save(&self) -> Option<Vec<u8>> {
+        let (reply, rx) = mpsc::sync_channel(1);
+        self.send(Job::Save(reply));
+        rx.recv().ok().flatten()
+    }
+
+    /// The hex digest, once every chunk has been hashed.
+    fn finish(self) -> String {
+        drop(self.tx);
+        self.handle
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    }
+}
+
+/// Lowercase hex of a digest.
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode hex in either case, or `None` if `s` isn't whole bytes of hex.
+pub fn unhex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Hash everything `reader` yields. Wrap the reader in [`Read::take`] to
+/// hash only a leading part of it.
+pub fn hash_reader(reader: &mut dyn Read, algorithms: HashAlgorithms) -> io::Result<FileHashes> {
+    let mut hasher = StreamHasher::new(algorithms);
+    hasher.update_reader(reader)?;
+    Ok(hasher.finish())
+}
+
+/// Fill `buf` as far as the reader allows, returning the bytes read (less
+/// than `buf.len()` only at end of stream).
+pub fn read_full(reader: &mut dyn Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+#[path = "tests/hash_tests.rs"]
+mod tests;
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+
+/// Languages that ROM headers store titles in.
+///
+/// Serialized as ISO 639-1 codes (the same codes ScreenScraper uses), so a
+/// map of titles reads as `{"en": "...", "ja": "..."}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Language {
+    #[serde(rename = "ja")]
+    Japanese,
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "it")]
+    Italian,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "nl")]
+    Dutch,
+    #[serde(rename = "pt")]
+    Portuguese,
+    #[serde(rename = "ru")]
+    Russian,
+    #[serde(rename = "ko")]
+    Korean,
+    #[serde(rename = "zh")]
+    Chinese,
+}
+
+impl Language {
+    /// All language variants.
+    pub const ALL: &[Language] = &[
+        Self::Japanese,
+        Self::English,
+        Self::French,
+        Self::German,
+        Self::Italian,
+        Self::Spanish,
+        Self::Dutch,
+        Self::Portuguese,
+        Self::Russian,
+        Self::Korean,
+        Self::Chinese,
+    ];
+
+    /// Languages names can be displayed in; see [`Language::localized_name`]
+    /// and [`crate::Region::localized_name`].
+    pub const DISPLAY: &[Language] = &[
+        Self::English,
+        Self::French,
+        Self::German,
+        Self::Spanish,
+        Self::Japanese,
+    ];
+
+    /// ISO 639-1 code (e.g., "en", "ja").
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Japanese => "ja",
+            Self::English => "en",
+            Self::French => "fr",
+            Self::German => "de",
+            Self::Italian => "it",
+            Self::Spanish => "es",
+            Self::Dutch => "nl",
+            Self::Portuguese => "pt",
+            Self::Russian => "ru",
+            Self::Korean => "ko",
+            Self::Chinese => "zh",
+        }
+    }
+
+    /// English name of the language.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Japanese => "Japanese",
+            Self::English => "English",
+            Self::French => "French",
+            Self::German => "German",
+            Self::Italian => "Italian",
+            Self::Spanish => "Spanish",
+            Self::Dutch => "Dutch",
+            Self::Portuguese => "Portuguese",
+            Self::Russian => "Russian",
+            Self::Korean => "Korean",
+            Self::Chinese => "Chinese",
+        }
+    }
+
+    /// Name of the language written in `display`, e.g. "Allemand" for German
+    /// in French. Display languages without translations get the English
+    /// name.
+    pub fn localized_name(&self, display: Language) -> &'static str {
+        LANGUAGE_NAMES
+            .iter()
+            .find(|(language, _)| *language == display)
+            .map_or_else(|| self.name(), |(_, names)| names[*self as usize])
+    }
+
+    /// Look up a language by ISO 639-1 code (case-insensitive).
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|l| l.code().eq_ignore_ascii_case(code))
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Error returned when a string cannot be parsed into a `Language`.
+#[derive(Debug, Clone)]
+pub struct LanguageParseError(pub String);
+
+impl std::fmt::Display for LanguageParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown language: '{}'", self.0)
+    }
+}
+
+impl std::error::Error for LanguageParseError {}
+
+impl std::str::FromStr for Language {
+    type Err = LanguageParseError;
+
+    /// Parse a language from its ISO 639-1 code or English name
+    /// (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_code(s)
+            .or_else(|| {
+                Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|l| l.name().eq_ignore_ascii_case(s))
+            })
+            .ok_or_else(|| LanguageParseError(s.to_string()))
+    }
+}
+
+/// Language names written in each translated display language, in variant
+/// order.
+const LANGUAGE_NAMES: &[(Language, [&str; 11])] = &[
+    (
+        Language::French,
+        [
+            "Japonais",
+            "Anglais",
+            "Français",
+            "Allemand",
+            "Italien",
+            "Espagnol",
+            "Néerlandais",
+            "Portugais",
+            "Russe",
+            "Coréen",
+            "Chinois",
+        ],
+    ),
+    (
+        Language::German,
+        [
+            "Japanisch",
+            "Englisch",
+            "Französisch",
+            "Deutsch",
+            "Italienisch",
+            "Spanisch",
+            "Niederländisch",
+            "Portugiesisch",
+            "Russisch",
+            "Koreanisch",
+            "Chinesisch",
+        ],
+    ),
+    (
+        Language::Spanish,
+        [
+            "Japonés",
+            "Inglés",
+            "Francés",
+            "Alemán",
+            "Italiano",
+            "Español",
+            "Neerlandés",
+            "Portugués",
+            "Ruso",
+            "Coreano",
+            "Chino",
+        ],
+    ),
+    (
+        Language::Japanese,
+        [
+            "日本語",
+            "英語",
+            "フランス語",
+            "ドイツ語",
+            "イタリア語",
+            "スペイン語",
+            "オランダ語",
+            "ポルトガル語",
+            "ロシア語",
+            "韓国語",
+            "中国語",
+        ],
+    ),
+];
+
+#[cfg(test)]
+#[path = "tests/language_tests.rs"]
+mod tests;
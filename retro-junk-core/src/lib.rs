@@ -3,19 +3,41 @@ use std::io::{Read, Seek};
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
 
+pub mod block_image;
+pub mod build_date;
 pub mod checksum;
+pub mod custom_platform;
 pub mod disc;
+pub mod dump_check;
 pub mod error;
+pub mod hash;
+pub mod language;
+pub mod limits;
+pub mod named_enum;
 pub mod platform;
 pub mod progress;
 pub mod region;
+pub mod serial;
+pub mod sniff;
+pub mod stream;
+#[cfg(any(test, feature = "testkit"))]
+pub mod synthetic;
 pub mod util;
 
+pub use block_image::{BlockImage, BlockSource};
+pub use build_date::BuildDate;
 pub use checksum::{ChecksumAlgorithm, ExpectedChecksum};
-pub use error::AnalysisError;
-pub use platform::{Platform, PlatformParseError};
+pub use error::{AnalysisError, AnalysisErrorKind};
+pub use hash::{HashState, StreamHasher, hash_reader};
+pub use language::{Language, LanguageParseError};
+pub use limits::{LimitExceeded, LimitedReader, ParseLimits};
+pub use platform::{Platform, PlatformFamily, PlatformParseError};
 pub use progress::AnalysisProgress;
 pub use region::Region;
+pub use sniff::{DEFAULT_SNIFF_LEN, SniffReader};
+pub use stream::StreamReader;
+#[cfg(any(test, feature = "testkit"))]
+pub use synthetic::SyntheticRom;
 
 // Re-export hash types used across crate boundaries
 // (FileHashes is used in trait methods, HashAlgorithms is a parameter type)
@@ -33,6 +55,15 @@ pub struct AnalysisOptions {
     /// Path to the file being analyzed. Used by disc-based analyzers
     /// (e.g., CUE sheets) to resolve relative file references.
     pub file_path: Option<PathBuf>,
+
+    /// Attach the raw header bytes to the result (see [`RawHeader`]).
+    /// Intended for debugging misdetections.
+    pub include_raw_header: bool,
+
+    /// Bounds on allocations, seeks and table entries. Use
+    /// [`ParseLimits::hardened`] for untrusted files, and wrap the reader
+    /// in a [`LimitedReader`] to enforce the seek bound.
+    pub limits: ParseLimits,
 }
 
 impl AnalysisOptions {
@@ -49,6 +80,55 @@ impl AnalysisOptions {
         self.file_path = Some(path.into());
         self
     }
+
+    pub fn include_raw_header(mut self, include: bool) -> Self {
+        self.include_raw_header = include;
+        self
+    }
+
+    pub fn limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
+
+/// Raw header bytes captured during analysis, for bug reports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawHeader {
+    /// File offset the bytes were read from.
+    pub offset: u64,
+    /// Lowercase hex encoding of the bytes.
+    pub hex: String,
+}
+
+impl RawHeader {
+    /// Upper bound on captured bytes, so disc headers can't bloat results.
+    pub const MAX_LEN: usize = 1024;
+
+    /// Read up to `len` bytes (capped at [`MAX_LEN`](Self::MAX_LEN)) from
+    /// `offset`. Fewer bytes are captured if the file ends first.
+    pub fn read(reader: &mut dyn ReadSeek, offset: u64, len: usize) -> Result<Self, AnalysisError> {
+        reader.seek(std::io::SeekFrom::Start(offset))?;
+        let mut buf = Vec::with_capacity(len.min(Self::MAX_LEN));
+        reader
+            .take(len.min(Self::MAX_LEN) as u64)
+            .read_to_end(&mut buf)?;
+        Ok(Self::from_bytes(offset, &buf))
+    }
+
+    pub fn from_bytes(offset: u64, bytes: &[u8]) -> Self {
+        let bytes = &bytes[..bytes.len().min(Self::MAX_LEN)];
+        Self {
+            offset,
+            hex: hash::hex(bytes),
+        }
+    }
+
+    /// Decode the captured bytes. Empty if `hex` has been edited into
+    /// something that isn't hex.
+    pub fn bytes(&self) -> Vec<u8> {
+        hash::unhex(&self.hex).unwrap_or_default()
+    }
 }
 
 /// Information extracted from analyzing a ROM or disc image.
@@ -60,6 +140,11 @@ pub struct RomIdentification {
     /// Internal name stored in the ROM header
     pub internal_name: Option<String>,
 
+    /// Localized titles, for formats that store one per language
+    /// (e.g., DS banners).
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub titles: std::collections::HashMap<Language, String>,
+
     /// Region(s) the ROM is intended for
     pub regions: Vec<Region>,
 
@@ -82,8 +167,18 @@ pub struct RomIdentification {
     /// Maker/publisher code
     pub maker_code: Option<String>,
 
+    /// Build or mastering date from the header, if the format records one.
+    /// Usually close to the release date.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_date: Option<BuildDate>,
+
     /// Additional platform-specific metadata
     pub extra: std::collections::HashMap<String, String>,
+
+    /// Raw header bytes, when requested via
+    /// [`AnalysisOptions::include_raw_header`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_header: Option<RawHeader>,
 }
 
 impl RomIdentification {
@@ -110,6 +205,30 @@ impl RomIdentification {
         self.platform = Some(platform);
         self
     }
+
+    /// The header title to show for `language`: that language's title, else
+    /// the English one, else the internal name.
+    pub fn title_for(&self, language: Option<Language>) -> Option<&str> {
+        language
+            .and_then(|l| self.titles.get(&l))
+            .or_else(|| self.titles.get(&Language::English))
+            .map(String::as_str)
+            .or(self.internal_name.as_deref())
+    }
+
+    /// Capture `len` header bytes at `offset` if the options ask for them.
+    pub fn attach_raw_header(
+        &mut self,
+        reader: &mut dyn ReadSeek,
+        offset: u64,
+        len: usize,
+        options: &AnalysisOptions,
+    ) -> Result<(), AnalysisError> {
+        if options.include_raw_header {
+            self.raw_header = Some(RawHeader::read(reader, offset, len)?);
+        }
+        Ok(())
+    }
 }
 
 /// The source database for DAT files.
@@ -153,13 +272,16 @@ pub struct FileHashes {
     pub crc32: String,
     pub sha1: Option<String>,
     pub md5: Option<String>,
+    /// Only computed for [`HashAlgorithms::AllWithSha256`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
     /// Size of the data that was hashed (after header stripping or container extraction)
     pub data_size: u64,
 }
 
 /// Which hash algorithms to compute.
 ///
-/// CRC32 is always included. Higher modes add SHA1 and MD5.
+/// CRC32 is always included. Higher modes add SHA1, MD5 and SHA-256.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HashAlgorithms {
     /// CRC32 only (fast DAT matching).
@@ -168,6 +290,8 @@ pub enum HashAlgorithms {
     Crc32Sha1,
     /// CRC32 + SHA1 + MD5 (ScreenScraper API needs all three).
     All,
+    /// CRC32 + SHA1 + MD5 + SHA-256 (`.smdb` listings).
+    AllWithSha256,
 }
 
 impl HashAlgorithms {
@@ -175,10 +299,13 @@ impl HashAlgorithms {
         true
     }
     pub fn sha1(&self) -> bool {
-        matches!(self, Self::Crc32Sha1 | Self::All)
+        matches!(self, Self::Crc32Sha1 | Self::All | Self::AllWithSha256)
     }
     pub fn md5(&self) -> bool {
-        matches!(self, Self::All)
+        matches!(self, Self::All | Self::AllWithSha256)
+    }
+    pub fn sha256(&self) -> bool {
+        matches!(self, Self::AllWithSha256)
     }
 }
 
@@ -265,6 +392,61 @@ pub trait RomAnalyzer: Send + Sync {
     /// full analysis. Useful for auto-detection of ROM type.
     fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool;
 
+    /// How many bytes from the start of a file [`sniff`](Self::sniff) needs.
+    fn sniff_len(&self) -> usize {
+        DEFAULT_SNIFF_LEN
+    }
+
+    /// Check whether a file looks like this format from its first bytes.
+    ///
+    /// `head` holds up to [`sniff_len`](Self::sniff_len) bytes from the start
+    /// of a `file_size`-byte file. Used for auto-detection where reading the
+    /// whole file, or seeking around it, is slow (network shares, archives).
+    /// The default runs [`can_handle`](Self::can_handle) on the prefix, so
+    /// checks that need data past it report `false`.
+    fn sniff(&self, head: &[u8], file_size: u64) -> bool {
+        sniff::can_handle_prefix(self, head, file_size)
+    }
+
+    /// Whether `dir` is an extracted game folder, such as a JB-format PS3
+    /// game, that [`analyze_dir`](Self::analyze_dir) reads.
+    fn is_game_dir(&self, _dir: &Path) -> bool {
+        false
+    }
+
+    /// Analyze an extracted game folder from the files inside it.
+    fn analyze_dir(
+        &self,
+        _dir: &Path,
+        _options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        Err(AnalysisError::unsupported(format!(
+            "{} games are not analyzed from folders",
+            self.platform_name()
+        )))
+    }
+
+    /// Extensions of music rips of this platform's games (NSF, SPC, VGM).
+    ///
+    /// Kept apart from [`file_extensions`](Self::file_extensions) so ROM
+    /// pipelines (rename, verify, scrape) never pick rips up. They're read
+    /// by [`analyze_music`](Self::analyze_music) instead.
+    fn music_extensions(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Identify a music rip of this platform's games from its tags.
+    fn analyze_music(
+        &self,
+        _reader: &mut dyn ReadSeek,
+        _options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        Err(AnalysisError::unsupported(format!(
+            "{} music rips are not recognized",
+            self.platform_name()
+        )))
+    }
+
     /// Check if this analyzer matches a folder name (case-insensitive).
     fn matches_folder(&self, folder_name: &str) -> bool {
         folder_name.parse::<Platform>().ok() == Some(self.platform())
@@ -304,6 +486,22 @@ pub trait RomAnalyzer: Send + Sync {
         !self.dat_names().is_empty()
     }
 
+    /// Whether the DAT lists this platform's discs as raw 2352-byte CD
+    /// tracks (Redump's CD systems). An OS block device only reads the
+    /// 2048-byte user data of each sector, which can't reproduce them.
+    fn dat_lists_raw_cd_tracks(&self) -> bool {
+        false
+    }
+
+    /// Whether the DAT lists the disc in `reader` as raw CD tracks. The
+    /// reader holds 2048-byte user-data sectors, as an ISO or a drive's
+    /// block device does. Platforms that shipped on both CD and DVD override
+    /// this to tell the two apart; the default is
+    /// [`dat_lists_raw_cd_tracks`](Self::dat_lists_raw_cd_tracks).
+    fn disc_is_raw_cd(&self, _reader: &mut dyn ReadSeek) -> Result<bool, AnalysisError> {
+        Ok(self.dat_lists_raw_cd_tracks())
+    }
+
     /// Returns the number of header bytes to skip before hashing for DAT matching.
     ///
     /// Override this for platforms with format headers (e.g., 16-byte iNES header,
@@ -334,6 +532,50 @@ pub trait RomAnalyzer: Send + Sync {
         Ok(None)
     }
 
+    /// The hashes of the original data this file was converted from, when
+    /// the file records them (e.g. the NKit header of a GameCube WBFS).
+    ///
+    /// Checked before [`dat_mismatch_reason`](Self::dat_mismatch_reason),
+    /// so a processed image whose own hashes can't match may still be
+    /// identified. Returns `None` (the default) when nothing is recorded.
+    fn recorded_dat_hashes(
+        &self,
+        _reader: &mut dyn ReadSeek,
+        _file_path: Option<&Path>,
+    ) -> Option<FileHashes> {
+        None
+    }
+
+    /// Why this file can't match its DAT whatever its hashes, judged from
+    /// its structure alone (e.g. a disc image with its junk data scrubbed).
+    ///
+    /// Checked before hashing, so callers can report the reason instead of
+    /// spending minutes hashing a large image that can't match. Returns
+    /// `None` (the default) when the file may match.
+    fn dat_mismatch_reason(
+        &self,
+        _reader: &mut dyn ReadSeek,
+        _file_path: Option<&Path>,
+    ) -> Option<String> {
+        None
+    }
+
+    /// Compute the hash of every track of a multi-track disc image, in
+    /// track order.
+    ///
+    /// Redump lists each track of such a disc as a separate ROM of one
+    /// game, so matching all of them identifies the disc more reliably
+    /// than the hash of its boot track alone. Returns an empty list for
+    /// files that aren't split into tracks (the default).
+    fn compute_track_hashes(
+        &self,
+        _reader: &mut dyn ReadSeek,
+        _algorithms: HashAlgorithms,
+        _file_path: Option<&Path>,
+    ) -> Result<Vec<FileHashes>, AnalysisError> {
+        Ok(Vec::new())
+    }
+
     /// Returns a closure that normalizes each chunk of ROM data before hashing.
     ///
     /// Override this for platforms with byte-order variants (e.g., N64 ROMs exist
@@ -406,4 +648,32 @@ pub trait RomAnalyzer: Send + Sync {
     fn extract_scraper_serial(&self, serial: &str) -> Option<String> {
         self.extract_dat_game_code(serial)
     }
+
+    // -- Serial number support --
+
+    /// Regular expression every canonical serial of this platform matches,
+    /// e.g. `^[A-Z]{4}-\d{5}$` for PlayStation's `SLUS-00594`.
+    ///
+    /// Returns `None` (the default) when serials aren't checked.
+    fn serial_pattern(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// The canonical form of a serial for this platform.
+    ///
+    /// Catalog lookups, DAT matching and ScreenScraper lookups all start
+    /// from this form, so a serial read from a DAT, typed by hand or
+    /// reported by another tool compares equal to the one analysis reports.
+    /// The default trims and uppercases it.
+    fn normalize_serial(&self, serial: &str) -> String {
+        serial::normalize_basic(serial)
+    }
+
+    /// Whether `serial`, once normalized, matches
+    /// [`serial_pattern`](Self::serial_pattern). Always true for platforms
+    /// without a pattern.
+    fn is_valid_serial(&self, serial: &str) -> bool {
+        self.serial_pattern()
+            .is_none_or(|pattern| serial::matches_pattern(pattern, &self.normalize_serial(serial)))
+    }
 }
@@ -0,0 +1,187 @@
+//! Resource bounds for parsing untrusted files.
+//!
+//! Headers and directory records carry sizes and counts that analyzers
+//! act on: a corrupt or hostile file can claim a 4 GB system file or a
+//! million-sector root directory. [`ParseLimits`] caps what a single
+//! analysis may allocate, how many entries it may walk, and (through
+//! [`LimitedReader`]) how often it may seek, so such a file fails with
+//! [`AnalysisError::LimitExceeded`] instead of exhausting memory or time.
+//!
+//! The default limits only stop clearly absurd values. Use
+//! [`ParseLimits::hardened`] for files from untrusted sources.
+
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom};
+
+use thiserror::Error;
+
+use crate::AnalysisError;
+
+/// Which bound a file ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Limit {
+    /// Bytes buffered in memory at once.
+    Allocation,
+    /// Seeks on the analyzed reader.
+    Seeks,
+    /// Records, tracks or sectors walked in one table.
+    Entries,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Allocation => "allocation",
+            Self::Seeks => "seek",
+            Self::Entries => "entry",
+        })
+    }
+}
+
+/// A file needed more than its [`ParseLimits`] allow.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("{what} exceeds the {limit} limit: {requested} > {max}")]
+pub struct LimitExceeded {
+    pub limit: Limit,
+    /// What was being read, e.g. "SYSTEM.CNF" or "CUE sheet".
+    pub what: &'static str,
+    pub requested: u64,
+    pub max: u64,
+}
+
+/// Upper bounds on the work a single analysis may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Largest buffer an analyzer may allocate, in bytes.
+    pub max_alloc: u64,
+    /// Most seeks an analyzer may make on a [`LimitedReader`].
+    pub max_seeks: u64,
+    /// Most entries an analyzer may walk in one table.
+    pub max_entries: u64,
+}
+
+impl Default for ParseLimits {
+    /// Generous enough for any real dump, including disc images analyzed
+    /// in full.
+    fn default() -> Self {
+        Self {
+            max_alloc: 1024 * 1024 * 1024,
+            max_seeks: u64::MAX,
+            max_entries: 1 << 20,
+        }
+    }
+}
+
+impl ParseLimits {
+    /// Tight limits for files from untrusted sources (uploads, URLs,
+    /// stdin). Header analysis of real dumps stays well inside them; full
+    /// checksum passes over cartridges larger than 64 MB do not.
+    pub fn hardened() -> Self {
+        Self {
+            max_alloc: 64 * 1024 * 1024,
+            max_seeks: 10_000,
+            max_entries: 10_000,
+        }
+    }
+
+    /// Check that a `len`-byte buffer for `what` may be allocated, returning
+    /// the length as a `usize`.
+    pub fn check_alloc(&self, len: u64, what: &'static str) -> Result<usize, AnalysisError> {
+        if len > self.max_alloc {
+            return Err(exceeded(Limit::Allocation, what, len, self.max_alloc).into());
+        }
+        usize::try_from(len)
+            .map_err(|_| exceeded(Limit::Allocation, what, len, usize::MAX as u64).into())
+    }
+
+    /// Check that `count` entries of `what` may be walked.
+    pub fn check_entries(&self, count: u64, what: &'static str) -> Result<(), AnalysisError> {
+        if count > self.max_entries {
+            return Err(exceeded(Limit::Entries, what, count, self.max_entries).into());
+        }
+        Ok(())
+    }
+
+    /// Read the rest of `reader` as text (a CUE or GDI sheet), failing
+    /// rather than buffering more than [`max_alloc`](Self::max_alloc) bytes.
+    pub fn read_text(
+        &self,
+        reader: &mut dyn Read,
+        what: &'static str,
+    ) -> Result<String, AnalysisError> {
+        let mut text = String::new();
+        let read = reader
+            .take(self.max_alloc.saturating_add(1))
+            .read_to_string(&mut text)?;
+        if read as u64 > self.max_alloc {
+            return Err(exceeded(Limit::Allocation, what, read as u64, self.max_alloc).into());
+        }
+        Ok(text)
+    }
+}
+
+fn exceeded(limit: Limit, what: &'static str, requested: u64, max: u64) -> LimitExceeded {
+    LimitExceeded {
+        limit,
+        what,
+        requested,
+        max,
+    }
+}
+
+/// A reader that fails once it has been seeked more than
+/// [`ParseLimits::max_seeks`] times.
+///
+/// Analyzers seek before nearly every header read, so a file that makes
+/// one loop over bogus offsets shows up here even where the loop itself
+/// has no count to check. The error surfaces through [`io::Error`] and
+/// converts back to [`AnalysisError::LimitExceeded`].
+pub struct LimitedReader<R> {
+    inner: R,
+    max_seeks: u64,
+    seeks: u64,
+}
+
+impl<R: Read + Seek> LimitedReader<R> {
+    pub fn new(inner: R, limits: &ParseLimits) -> Self {
+        Self {
+            inner,
+            max_seeks: limits.max_seeks,
+            seeks: 0,
+        }
+    }
+
+    /// Seeks made so far.
+    pub fn seeks(&self) -> u64 {
+        self.seeks
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for LimitedReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.seeks += 1;
+        if self.seeks > self.max_seeks {
+            return Err(io::Error::other(exceeded(
+                Limit::Seeks,
+                "reader",
+                self.seeks,
+                self.max_seeks,
+            )));
+        }
+        self.inner.seek(pos)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/limits_tests.rs"]
+mod tests;
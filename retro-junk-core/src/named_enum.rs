@@ -0,0 +1,81 @@
+//! Enums of options chosen by name on the command line or in config files.
+//!
+//! [`named_enum!`](crate::named_enum) declares the enum along with its
+//! `as_str`, `Display` and `FromStr`, so the names are listed once.
+
+/// Declare an enum whose variants are chosen by name.
+///
+/// Write `pub enum Name("what it is") { ... }` with each variant as
+/// `Variant = "name" | "alias" | ...`; attributes and doc comments pass
+/// through. The enum gets `ALL`, `as_str` (the first name), `Display` and a
+/// case-insensitive `FromStr` whose error lists the first names, e.g.
+/// "unknown symlink policy 'x' (expected follow or skip)".
+#[macro_export]
+macro_rules! named_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident($what:literal) {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $canonical:literal $(| $alias:literal)*
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )+
+        }
+
+        impl $name {
+            /// Every variant, in declaration order.
+            pub const ALL: &'static [Self] = &[$(Self::$variant),+];
+
+            /// Name used on the command line.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $canonical,)+
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.to_lowercase().as_str() {
+                    $($canonical $(| $alias)* => Ok(Self::$variant),)+
+                    _ => Err(format!(
+                        "unknown {} '{}' (expected {})",
+                        $what,
+                        s,
+                        $crate::named_enum::or_list(&[$($canonical),+])
+                    )),
+                }
+            }
+        }
+    };
+}
+
+/// `a`, `a or b`, or `a, b, or c`.
+#[doc(hidden)]
+pub fn or_list(names: &[&str]) -> String {
+    match names {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [a, b] => format!("{} or {}", a, b),
+        [rest @ .., last] => format!("{}, or {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/named_enum_tests.rs"]
+mod tests;
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::custom_platform;
+
 /// Platform/console identifiers for all supported systems.
 ///
 /// This enum centralizes console identity — short names, display names,
@@ -41,6 +43,22 @@ pub enum Platform {
     // Microsoft
     Xbox,
     Xbox360,
+
+    // Atari
+    Atari2600,
+    Atari5200,
+    Atari7800,
+    Lynx,
+    Jaguar,
+
+    // NEC
+    PcEngine,
+    SuperGrafx,
+    PcEngineCd,
+
+    /// A platform defined in user configuration; indexes the
+    /// [`custom_platform`](crate::custom_platform) registry.
+    Custom(u16),
 }
 
 /// All platform variants in registration order.
@@ -70,6 +88,14 @@ const ALL_PLATFORMS: &[Platform] = &[
     Platform::Vita,
     Platform::Xbox,
     Platform::Xbox360,
+    Platform::Atari2600,
+    Platform::Atari5200,
+    Platform::Atari7800,
+    Platform::Lynx,
+    Platform::Jaguar,
+    Platform::PcEngine,
+    Platform::SuperGrafx,
+    Platform::PcEngineCd,
 ];
 
 impl Platform {
@@ -101,6 +127,15 @@ impl Platform {
             Self::Vita => "vita",
             Self::Xbox => "xbox",
             Self::Xbox360 => "xbox360",
+            Self::Atari2600 => "atari2600",
+            Self::Atari5200 => "atari5200",
+            Self::Atari7800 => "atari7800",
+            Self::Lynx => "lynx",
+            Self::Jaguar => "jaguar",
+            Self::PcEngine => "pcengine",
+            Self::SuperGrafx => "supergrafx",
+            Self::PcEngineCd => "pcenginecd",
+            Self::Custom(i) => custom_platform::get(*i).map_or("custom", |p| p.short_name),
         }
     }
 
@@ -132,6 +167,17 @@ impl Platform {
             Self::Vita => "Sony PlayStation Vita",
             Self::Xbox => "Microsoft Xbox",
             Self::Xbox360 => "Microsoft Xbox 360",
+            Self::Atari2600 => "Atari 2600",
+            Self::Atari5200 => "Atari 5200",
+            Self::Atari7800 => "Atari 7800",
+            Self::Lynx => "Atari Lynx",
+            Self::Jaguar => "Atari Jaguar",
+            Self::PcEngine => "NEC PC Engine / TurboGrafx-16",
+            Self::SuperGrafx => "NEC SuperGrafx",
+            Self::PcEngineCd => "NEC PC Engine CD / TurboGrafx-CD",
+            Self::Custom(i) => {
+                custom_platform::get(*i).map_or("Custom Platform", |p| p.display_name)
+            }
         }
     }
 
@@ -161,6 +207,14 @@ impl Platform {
             Self::Ps1 | Self::Ps2 | Self::Ps3 | Self::Psp | Self::Vita => "Sony",
 
             Self::Xbox | Self::Xbox360 => "Microsoft",
+
+            Self::Atari2600 | Self::Atari5200 | Self::Atari7800 | Self::Lynx | Self::Jaguar => {
+                "Atari"
+            }
+
+            Self::PcEngine | Self::SuperGrafx | Self::PcEngineCd => "NEC",
+
+            Self::Custom(i) => custom_platform::get(*i).map_or("Unknown", |p| p.manufacturer),
         }
     }
 
@@ -202,13 +256,177 @@ impl Platform {
             Self::Vita => &["vita", "psvita", "ps vita", "playstation vita"],
             Self::Xbox => &["xbox", "xbox1", "ogxbox"],
             Self::Xbox360 => &["xbox360", "xbox 360", "x360"],
+            Self::Atari2600 => &[
+                "atari2600",
+                "atari 2600",
+                "2600",
+                "a2600",
+                "vcs",
+                "atari vcs",
+            ],
+            Self::Atari5200 => &["atari5200", "atari 5200", "5200", "a5200"],
+            Self::Atari7800 => &["atari7800", "atari 7800", "7800", "a7800"],
+            Self::Lynx => &["lynx", "atarilynx", "atari lynx"],
+            Self::Jaguar => &["jaguar", "atarijaguar", "atari jaguar", "jag"],
+            Self::PcEngine => &[
+                "pcengine",
+                "pc engine",
+                "pce",
+                "tg16",
+                "turbografx16",
+                "turbografx-16",
+                "turbografx",
+            ],
+            Self::SuperGrafx => &["supergrafx", "sgx", "pcengine supergrafx"],
+            Self::PcEngineCd => &[
+                "pcenginecd",
+                "pc engine cd",
+                "pcecd",
+                "tgcd",
+                "tg-cd",
+                "turbografxcd",
+                "turbografx-cd",
+            ],
+            Self::Custom(i) => custom_platform::get(*i).map_or(&[], |p| p.aliases),
         }
     }
 
-    /// All 25 platform variants.
+    /// All 33 built-in platform variants. Custom platforms are listed by
+    /// [`custom_platform::all`].
     pub fn all() -> &'static [Platform] {
         ALL_PLATFORMS
     }
+
+    /// Whether this is a user-defined platform.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom(_))
+    }
+
+    /// The family this platform belongs to, if any.
+    pub fn family(&self) -> Option<PlatformFamily> {
+        PlatformFamily::all()
+            .iter()
+            .copied()
+            .find(|f| f.members().contains(self))
+    }
+}
+
+/// Groups of related platforms that collections often keep in one folder,
+/// such as a single Game Boy folder holding GB, GBC, and GBA ROMs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlatformFamily {
+    /// Game Boy, Game Boy Color, Game Boy Advance
+    GameBoy,
+    /// Genesis / Mega Drive with the 32X and Sega CD add-ons
+    MegaDrive,
+    /// SG-1000, Master System, Game Gear
+    SegaEightBit,
+    /// DS and 3DS
+    NintendoDs,
+    /// GameCube and Wii
+    GameCubeWii,
+    /// Xbox and Xbox 360
+    Xbox,
+    /// PC Engine / TurboGrafx-16 and SuperGrafx
+    PcEngine,
+}
+
+const ALL_FAMILIES: &[PlatformFamily] = &[
+    PlatformFamily::GameBoy,
+    PlatformFamily::MegaDrive,
+    PlatformFamily::SegaEightBit,
+    PlatformFamily::NintendoDs,
+    PlatformFamily::GameCubeWii,
+    PlatformFamily::Xbox,
+    PlatformFamily::PcEngine,
+];
+
+impl PlatformFamily {
+    /// Platforms in this family.
+    pub fn members(&self) -> &'static [Platform] {
+        match self {
+            Self::GameBoy => &[Platform::GameBoy, Platform::Gba],
+            Self::MegaDrive => &[Platform::Genesis, Platform::Sega32x, Platform::SegaCd],
+            Self::SegaEightBit => &[Platform::Sg1000, Platform::MasterSystem, Platform::GameGear],
+            Self::NintendoDs => &[Platform::Ds, Platform::N3ds],
+            Self::GameCubeWii => &[Platform::GameCube, Platform::Wii],
+            Self::Xbox => &[Platform::Xbox, Platform::Xbox360],
+            Self::PcEngine => &[Platform::PcEngine, Platform::SuperGrafx],
+        }
+    }
+
+    /// Canonical short name, usable as a folder name.
+    pub fn short_name(&self) -> &'static str {
+        self.aliases()[0]
+    }
+
+    /// Display name for the family.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::GameBoy => "Game Boy family",
+            Self::MegaDrive => "Mega Drive / Genesis family",
+            Self::SegaEightBit => "Sega 8-bit family",
+            Self::NintendoDs => "Nintendo DS family",
+            Self::GameCubeWii => "GameCube / Wii",
+            Self::Xbox => "Xbox family",
+            Self::PcEngine => "PC Engine family",
+        }
+    }
+
+    /// Accepted folder names for a combined folder (case-insensitive).
+    ///
+    /// These never overlap a single platform's aliases.
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Self::GameBoy => &[
+                "gb-family",
+                "gameboy family",
+                "game boy family",
+                "gb+gbc+gba",
+            ],
+            Self::MegaDrive => &[
+                "md-family",
+                "mega drive family",
+                "megadrive family",
+                "genesis family",
+            ],
+            Self::SegaEightBit => &["sega8bit", "sega 8-bit", "sega-8bit"],
+            Self::NintendoDs => &["ds-family", "ds family", "nds+3ds"],
+            Self::GameCubeWii => &["gc-wii", "gamecube-wii", "gamecube wii", "gc+wii"],
+            Self::Xbox => &["xbox-family", "xbox family"],
+            Self::PcEngine => &[
+                "pce-family",
+                "pc engine family",
+                "pcengine family",
+                "pce+sgx",
+            ],
+        }
+    }
+
+    /// All platform families.
+    pub fn all() -> &'static [PlatformFamily] {
+        ALL_FAMILIES
+    }
+}
+
+impl std::fmt::Display for PlatformFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+impl std::str::FromStr for PlatformFamily {
+    type Err = PlatformParseError;
+
+    /// Parse a family from any of its aliases (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase();
+        ALL_FAMILIES
+            .iter()
+            .copied()
+            .find(|f| f.aliases().contains(&lower.as_str()))
+            .ok_or_else(|| PlatformParseError(s.to_string()))
+    }
 }
 
 impl std::fmt::Display for Platform {
@@ -247,7 +465,7 @@ impl std::str::FromStr for Platform {
                 }
             }
         }
-        Err(PlatformParseError(s.to_string()))
+        custom_platform::find(&lower).ok_or_else(|| PlatformParseError(s.to_string()))
     }
 }
 
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::Language;
+
 /// Geographic regions for ROM releases.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Region {
@@ -71,6 +73,15 @@ impl Region {
         }
     }
 
+    /// Name of this region written in `display`, e.g. "Japon" in French.
+    /// Display languages without translations get [`Region::name`].
+    pub fn localized_name(&self, display: Language) -> &'static str {
+        REGION_NAMES
+            .iter()
+            .find(|(language, _)| *language == display)
+            .map_or_else(|| self.name(), |(_, names)| names[*self as usize])
+    }
+
     /// Attempt to parse a region from a code character (common in serial numbers).
     pub fn from_code_char(c: char) -> Option<Self> {
         match c.to_ascii_uppercase() {
@@ -91,3 +102,72 @@ impl std::fmt::Display for Region {
         write!(f, "{}", self.name())
     }
 }
+
+/// Region names written in each translated display language, in variant
+/// order.
+const REGION_NAMES: &[(Language, [&str; 10])] = &[
+    (
+        Language::French,
+        [
+            "Japon",
+            "États-Unis",
+            "Europe",
+            "Australie",
+            "Corée",
+            "Chine",
+            "Taïwan",
+            "Brésil",
+            "Monde",
+            "Inconnue",
+        ],
+    ),
+    (
+        Language::German,
+        [
+            "Japan",
+            "USA",
+            "Europa",
+            "Australien",
+            "Korea",
+            "China",
+            "Taiwan",
+            "Brasilien",
+            "Welt",
+            "Unbekannt",
+        ],
+    ),
+    (
+        Language::Spanish,
+        [
+            "Japón",
+            "EE. UU.",
+            "Europa",
+            "Australia",
+            "Corea",
+            "China",
+            "Taiwán",
+            "Brasil",
+            "Mundo",
+            "Desconocida",
+        ],
+    ),
+    (
+        Language::Japanese,
+        [
+            "日本",
+            "北米",
+            "ヨーロッパ",
+            "オーストラリア",
+            "韓国",
+            "中国",
+            "台湾",
+            "ブラジル",
+            "ワールド",
+            "不明",
+        ],
+    ),
+];
+
+#[cfg(test)]
+#[path = "tests/region_tests.rs"]
+mod tests;
@@ -0,0 +1,47 @@
+//! Serial number normalization and validation helpers.
+//!
+//! Each analyzer defines the canonical form of its platform's serials through
+//! [`RomAnalyzer::normalize_serial`](crate::RomAnalyzer::normalize_serial)
+//! and [`RomAnalyzer::serial_pattern`](crate::RomAnalyzer::serial_pattern).
+//! These helpers cover the steps most platforms share.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use regex::Regex;
+
+/// Compiled serial patterns, by pattern. Invalid patterns are kept as `None`.
+static PATTERNS: LazyLock<Mutex<HashMap<&'static str, Option<Regex>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Trim and uppercase a serial: the default canonical form.
+pub fn normalize_basic(serial: &str) -> String {
+    serial.trim().to_uppercase()
+}
+
+/// Trim and uppercase a serial, joining its parts with single hyphens.
+///
+/// Runs of spaces, underscores and hyphens become one hyphen, so
+/// `"ntr_admE"` and `"NTR - ADME"` both read `"NTR-ADME"`.
+pub fn normalize_hyphenated(serial: &str) -> String {
+    normalize_basic(serial)
+        .split([' ', '_', '-'])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Whether `serial` matches the regular expression `pattern`. Patterns are
+/// compiled once; one that doesn't compile matches nothing.
+pub fn matches_pattern(pattern: &'static str, serial: &str) -> bool {
+    let mut patterns = PATTERNS.lock().unwrap_or_else(|e| e.into_inner());
+    patterns
+        .entry(pattern)
+        .or_insert_with(|| Regex::new(pattern).ok())
+        .as_ref()
+        .is_some_and(|re| re.is_match(serial))
+}
+
+#[cfg(test)]
+#[path = "tests/serial_tests.rs"]
+mod tests;
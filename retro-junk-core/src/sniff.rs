@@ -0,0 +1,87 @@
+//! Format detection from the start of a file.
+//!
+//! Auto-detection tries every analyzer against a file. On network shares and
+//! inside archives each seek is expensive (or means decompressing from the
+//! start again), so detection reads one prefix up front and lets every
+//! analyzer look at that instead of the file. [`SniffReader`] presents the
+//! prefix as a reader of the full file size, so existing `can_handle` checks
+//! work unchanged as long as they stay inside the prefix.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::util::seek_target;
+use crate::{ReadSeek, RomAnalyzer};
+
+/// Bytes read for sniffing when an analyzer doesn't ask for more. Covers
+/// cartridge headers and the first disc sectors.
+pub const DEFAULT_SNIFF_LEN: usize = 64 * 1024;
+
+/// A reader over the first bytes of a file that reports the full file size.
+///
+/// Seeking anywhere within `file_size` succeeds; reads past the prefix hit
+/// end-of-file, so a check that needs data from further in simply fails.
+#[derive(Debug, Clone)]
+pub struct SniffReader<'a> {
+    head: &'a [u8],
+    file_size: u64,
+    pos: u64,
+}
+
+impl<'a> SniffReader<'a> {
+    pub fn new(head: &'a [u8], file_size: u64) -> Self {
+        Self {
+            head,
+            file_size: file_size.max(head.len() as u64),
+            pos: 0,
+        }
+    }
+}
+
+impl Read for SniffReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(available) = usize::try_from(self.pos)
+            .ok()
+            .and_then(|pos| self.head.get(pos..))
+        else {
+            return Ok(0);
+        };
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for SniffReader<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.pos = seek_target(pos, self.pos, self.file_size)?;
+        Ok(self.pos)
+    }
+}
+
+/// Read up to `len` bytes from the start of `reader` and return them with the
+/// total file size. The reader is left at the start.
+pub fn read_prefix(reader: &mut dyn ReadSeek, len: usize) -> io::Result<(Vec<u8>, u64)> {
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+    let mut head = Vec::with_capacity(len.min(file_size as usize));
+    (&mut *reader).take(len as u64).read_to_end(&mut head)?;
+    reader.seek(SeekFrom::Start(0))?;
+    Ok((head, file_size))
+}
+
+/// Run an analyzer's `can_handle` against a prefix.
+///
+/// This is what [`RomAnalyzer::sniff`] does by default; analyzers that
+/// override `sniff` can call it for the cases they don't special-case.
+pub fn can_handle_prefix<A: RomAnalyzer + ?Sized>(
+    analyzer: &A,
+    head: &[u8],
+    file_size: u64,
+) -> bool {
+    analyzer.can_handle(&mut SniffReader::new(head, file_size))
+}
+
+#[cfg(test)]
+#[path = "tests/sniff_tests.rs"]
+mod tests;
@@ -0,0 +1,193 @@
+//! Seekable view of a forward-only stream.
+//!
+//! Analyzers take a `Read + Seek`, but stdin, pipes and HTTP bodies can only
+//! be read front to back. [`StreamReader`] pulls the stream in fixed-size
+//! chunks and keeps them, so an analyzer can seek back to a header it has
+//! already seen. Chunks count against a retention budget whether they were
+//! read or only skipped over, and the least recently used ones are dropped
+//! once it's spent, so piping a large disc image doesn't hold the whole image
+//! in memory.
+//!
+//! The first chunk, where headers live, and the last one pulled, which is the
+//! one being read or a trailer after a seek from the end, are always kept.
+//! Seeking from the end has to consume the rest of the stream to learn its
+//! size.
+
+use std::collections::BTreeMap;
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::util::seek_target;
+
+/// Granularity of buffering.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Data kept by default before chunks start being dropped.
+pub const DEFAULT_RETAIN_LIMIT: usize = 64 * 1024 * 1024;
+
+/// A `Read + Seek` adapter over a forward-only reader.
+///
+/// Reading data that was dropped fails with [`io::ErrorKind::Unsupported`];
+/// everything else behaves like a file.
+pub struct StreamReader<R> {
+    inner: R,
+    chunks: BTreeMap<u64, Chunk>,
+    /// Unpinned chunk indices by the tick they were last used at, oldest
+    /// first.
+    lru: BTreeMap<u64, u64>,
+    clock: u64,
+    /// Index of the most recently pulled chunk.
+    tail: Option<u64>,
+    /// Bytes pulled from `inner` so far.
+    consumed: u64,
+    /// Total length, once the stream has hit end-of-file.
+    len: Option<u64>,
+    pos: u64,
+    retained: usize,
+    retain_limit: usize,
+}
+
+struct Chunk {
+    data: Vec<u8>,
+    /// Tick of the last use; 0 while the chunk is pinned.
+    used: u64,
+}
+
+impl<R: Read> StreamReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_retain_limit(inner, DEFAULT_RETAIN_LIMIT)
+    }
+
+    /// Keep about `retain_limit` bytes of the stream, dropping the least
+    /// recently used chunks beyond that. The first chunk and the last one
+    /// pulled are kept even when they don't fit.
+    pub fn with_retain_limit(inner: R, retain_limit: usize) -> Self {
+        Self {
+            inner,
+            chunks: BTreeMap::new(),
+            lru: BTreeMap::new(),
+            clock: 0,
+            tail: None,
+            consumed: 0,
+            len: None,
+            pos: 0,
+            retained: 0,
+            retain_limit,
+        }
+    }
+
+    /// Total length of the stream, if it has been read to the end.
+    pub fn total_len(&self) -> Option<u64> {
+        self.len
+    }
+
+    /// Bytes currently held in memory.
+    pub fn buffered(&self) -> usize {
+        self.retained
+    }
+
+    /// Pull chunks until `index` has been read (or the stream ends).
+    fn fill_through(&mut self, index: Option<u64>) -> io::Result<()> {
+        while self.len.is_none() && index.is_none_or(|i| self.consumed / CHUNK_SIZE as u64 <= i) {
+            let chunk_index = self.consumed / CHUNK_SIZE as u64;
+            let data = self.pull_chunk()?;
+            if data.is_empty() {
+                break;
+            }
+            self.retained += data.len();
+            self.chunks.insert(chunk_index, Chunk { data, used: 0 });
+            if let Some(previous) = self.tail.replace(chunk_index) {
+                self.touch(previous);
+            }
+            self.evict();
+        }
+        Ok(())
+    }
+
+    /// Read one full chunk, or a short one at end-of-file.
+    fn pull_chunk(&mut self) -> io::Result<Vec<u8>> {
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+        (&mut self.inner)
+            .take(CHUNK_SIZE as u64)
+            .read_to_end(&mut chunk)?;
+        self.consumed += chunk.len() as u64;
+        if chunk.len() < CHUNK_SIZE {
+            self.len = Some(self.consumed);
+        }
+        Ok(chunk)
+    }
+
+    fn is_pinned(&self, index: u64) -> bool {
+        index == 0 || self.tail == Some(index)
+    }
+
+    /// Mark a buffered chunk as just used.
+    fn touch(&mut self, index: u64) {
+        if self.is_pinned(index) {
+            return;
+        }
+        let Some(chunk) = self.chunks.get_mut(&index) else {
+            return;
+        };
+        self.lru.remove(&chunk.used);
+        self.clock += 1;
+        chunk.used = self.clock;
+        self.lru.insert(self.clock, index);
+    }
+
+    /// Drop least recently used chunks until the budget is met or only
+    /// pinned chunks are left.
+    fn evict(&mut self) {
+        while self.retained > self.retain_limit {
+            let Some((_, index)) = self.lru.pop_first() else {
+                break;
+            };
+            if let Some(chunk) = self.chunks.remove(&index) {
+                self.retained -= chunk.data.len();
+            }
+        }
+    }
+}
+
+impl<R: Read> Read for StreamReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() || self.len.is_some_and(|len| self.pos >= len) {
+            return Ok(0);
+        }
+        let index = self.pos / CHUNK_SIZE as u64;
+        let offset = (self.pos % CHUNK_SIZE as u64) as usize;
+        self.fill_through(Some(index))?;
+        self.touch(index);
+
+        let Some(chunk) = self.chunks.get(&index).map(|c| c.data.as_slice()) else {
+            if self.len.is_some_and(|len| self.pos >= len) {
+                return Ok(0);
+            }
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "data at offset {} is no longer buffered from the stream",
+                    self.pos
+                ),
+            ));
+        };
+        let available = chunk.get(offset..).unwrap_or_default();
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Read> Seek for StreamReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        if let SeekFrom::End(_) = pos {
+            self.fill_through(None)?;
+        }
+        self.pos = seek_target(pos, self.pos, self.len.unwrap_or(self.consumed))?;
+        Ok(self.pos)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/stream_tests.rs"]
+mod tests;
@@ -0,0 +1,50 @@
+//! Synthetic ROM images for tests.
+//!
+//! Each platform crate has a `synthetic` module that builds the smallest
+//! image its analyzer accepts: a valid header, correct checksums, and
+//! padding where the format needs it. Nothing in them comes from a real
+//! game, so they can be committed and shipped freely.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::Platform;
+
+/// A generated ROM image, plus any files it refers to (CUE/GDI tracks).
+#[derive(Debug, Clone)]
+pub struct SyntheticRom {
+    pub platform: Platform,
+    /// Name of the file to analyze.
+    pub file_name: String,
+    pub data: Vec<u8>,
+    /// Files next to the main one, by name.
+    pub companions: Vec<(String, Vec<u8>)>,
+}
+
+impl SyntheticRom {
+    pub fn new(platform: Platform, file_name: impl Into<String>, data: Vec<u8>) -> Self {
+        Self {
+            platform,
+            file_name: file_name.into(),
+            data,
+            companions: Vec::new(),
+        }
+    }
+
+    /// Add a file the main one refers to.
+    pub fn with_companion(mut self, name: impl Into<String>, data: Vec<u8>) -> Self {
+        self.companions.push((name.into(), data));
+        self
+    }
+
+    /// Write the image and its companions into `dir`, returning the path of
+    /// the main file.
+    pub fn write_to(&self, dir: &Path) -> io::Result<PathBuf> {
+        for (name, data) in &self.companions {
+            std::fs::write(dir.join(name), data)?;
+        }
+        let path = dir.join(&self.file_name);
+        std::fs::write(&path, &self.data)?;
+        Ok(path)
+    }
+}
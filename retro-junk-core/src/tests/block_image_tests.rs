@@ -0,0 +1,89 @@
+use super::*;
+
+/// Blocks of `block_size` bytes, each filled with its index.
+struct Counting {
+    block_size: usize,
+    len: u64,
+    decoded: usize,
+}
+
+impl BlockSource for Counting {
+    fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    fn image_len(&self) -> u64 {
+        self.len
+    }
+
+    fn read_block(&mut self, index: u64, buf: &mut Vec<u8>) -> io::Result<()> {
+        self.decoded += 1;
+        let start = index * self.block_size as u64;
+        let size = (self.len - start).min(self.block_size as u64) as usize;
+        buf.clear();
+        buf.resize(size, index as u8);
+        Ok(())
+    }
+}
+
+fn image(len: u64) -> BlockImage<Counting> {
+    BlockImage::new(Counting {
+        block_size: 4,
+        len,
+        decoded: 0,
+    })
+}
+
+#[test]
+fn reads_across_blocks() {
+    let mut img = image(10);
+    let mut data = Vec::new();
+    img.read_to_end(&mut data).unwrap();
+    assert_eq!(data, [0, 0, 0, 0, 1, 1, 1, 1, 2, 2]);
+}
+
+#[test]
+fn seeks_and_reuses_the_cached_block() {
+    let mut img = image(10);
+    img.seek(SeekFrom::Start(5)).unwrap();
+    let mut buf = [0u8; 2];
+    img.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, [1, 1]);
+    img.seek(SeekFrom::Current(-2)).unwrap();
+    img.read_exact(&mut buf).unwrap();
+    assert_eq!(img.source().decoded, 1);
+
+    assert_eq!(img.seek(SeekFrom::End(-1)).unwrap(), 9);
+    img.read_exact(&mut buf[..1]).unwrap();
+    assert_eq!(buf[0], 2);
+    assert_eq!(img.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn rejects_negative_seeks() {
+    let mut img = image(10);
+    assert!(img.seek(SeekFrom::End(-11)).is_err());
+}
+
+#[test]
+fn with_cache_keeps_the_most_recently_used_blocks() {
+    let mut img = BlockImage::with_cache(
+        Counting {
+            block_size: 4,
+            len: 12,
+            decoded: 0,
+        },
+        2,
+    );
+    let mut byte = [0u8; 1];
+    for offset in [0, 4, 0, 8, 0] {
+        img.seek(SeekFrom::Start(offset)).unwrap();
+        img.read_exact(&mut byte).unwrap();
+        assert_eq!(byte[0] as u64, offset / 4);
+    }
+    // Block 1 was evicted for block 2; block 0 stayed in use throughout
+    assert_eq!(img.source().decoded, 3);
+    img.seek(SeekFrom::Start(4)).unwrap();
+    img.read_exact(&mut byte).unwrap();
+    assert_eq!(img.source().decoded, 4);
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+#[test]
+fn parses_separated_and_packed_dates() {
+    let expected = BuildDate::new(1999, Some(9), Some(9));
+    assert_eq!(BuildDate::parse("1999/09/09"), expected);
+    assert_eq!(BuildDate::parse("1999-09-09"), expected);
+    assert_eq!(BuildDate::parse("19990909"), expected);
+    assert_eq!(BuildDate::parse("  19990909\0\0"), expected);
+    assert_eq!(
+        BuildDate::parse("1991.04"),
+        BuildDate::new(1991, Some(4), None)
+    );
+    assert_eq!(BuildDate::parse("1991"), BuildDate::new(1991, None, None));
+}
+
+#[test]
+fn rejects_garbage() {
+    assert!(BuildDate::parse("").is_none());
+    assert!(BuildDate::parse("ABCDEFGH").is_none());
+    assert!(BuildDate::parse("19991301").is_none());
+    assert!(BuildDate::parse("1999/02/32").is_none());
+    assert!(BuildDate::parse("1850").is_none());
+    assert!(BuildDate::new(2000, None, Some(5)).is_none());
+}
+
+#[test]
+fn display_matches_precision() {
+    assert_eq!(
+        BuildDate::new(1994, None, None).unwrap().to_string(),
+        "1994"
+    );
+    assert_eq!(
+        BuildDate::new(1994, Some(11), None).unwrap().to_string(),
+        "1994-11"
+    );
+    assert_eq!(
+        BuildDate::new(1994, Some(11), Some(22))
+            .unwrap()
+            .to_string(),
+        "1994-11-22"
+    );
+}
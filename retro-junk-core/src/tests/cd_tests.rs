@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn test_parse_chd_track() {
+    let track = parse_chd_track(
+        "TRACK:2 TYPE:AUDIO SUBTYPE:NONE FRAMES:9000 PREGAP:150 PGTYPE:VAUDIO PGSUB:RW POSTGAP:0\0",
+    )
+    .unwrap();
+    assert_eq!(track.number, 2);
+    assert_eq!(track.kind, "AUDIO");
+    assert_eq!(track.frames, 9000);
+    assert_eq!(track.stored_pregap, 150);
+    assert!(!track.is_data());
+
+    // A pregap that isn't stored in the image doesn't shift the first sector
+    let track =
+        parse_chd_track("TRACK:1 TYPE:MODE1_RAW SUBTYPE:NONE FRAMES:1350 PREGAP:150 PGTYPE:MODE1")
+            .unwrap();
+    assert_eq!(track.stored_pregap, 0);
+    assert_eq!(track.sector_size(), 2352);
+    assert_eq!(track.data_offset(), 16);
+    assert!(track.is_data());
+
+    assert!(parse_chd_track("TRACK:1 TYPE:MODE1").is_none());
+}
+
+#[test]
+fn test_layout_chd_tracks_pads_to_four_frames() {
+    let tracks = layout_chd_tracks(vec![
+        parse_chd_track("TRACK:2 TYPE:AUDIO FRAMES:100").unwrap(),
+        parse_chd_track("TRACK:1 TYPE:MODE1_RAW FRAMES:1001").unwrap(),
+        parse_chd_track("TRACK:3 TYPE:AUDIO FRAMES:8").unwrap(),
+    ]);
+    let offsets: Vec<(u32, u64)> = tracks.iter().map(|t| (t.number, t.chd_offset)).collect();
+    assert_eq!(offsets, vec![(1, 0), (2, 1004), (3, 1104)]);
+}
+
+#[test]
+fn test_parse_chd_track_without_pregap_type() {
+    let track =
+        parse_chd_track("TRACK:1 TYPE:MODE2_RAW SUBTYPE:NONE FRAMES:229020 PREFRAMES:150").unwrap();
+    assert_eq!(track.number, 1);
+    assert_eq!(track.kind, "MODE2_RAW");
+    assert_eq!(track.frames, 229020);
+    assert_eq!(track.stored_pregap, 0);
+    assert_eq!(track.data_offset(), 24);
+}
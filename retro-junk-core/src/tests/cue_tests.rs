@@ -0,0 +1,90 @@
+use super::*;
+
+#[test]
+fn test_parse_single_track() {
+    let sheet = parse("FILE \"game.bin\" BINARY\n  TRACK 01 MODE2/2352\n    INDEX 01 00:00:00\n");
+    assert_eq!(sheet.files.len(), 1);
+    assert_eq!(sheet.files[0].filename, "game.bin");
+    assert_eq!(sheet.files[0].file_type, "BINARY");
+    assert_eq!(sheet.files[0].tracks.len(), 1);
+    assert_eq!(sheet.files[0].tracks[0].number, 1);
+    assert_eq!(sheet.files[0].tracks[0].mode, "MODE2/2352");
+}
+
+#[test]
+fn test_parse_multi_track() {
+    let sheet = parse(
+        r#"FILE "game.bin" BINARY
+  TRACK 01 MODE2/2352
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 00 45:00:00
+    INDEX 01 45:02:00
+  TRACK 03 AUDIO
+    INDEX 00 50:30:00
+    INDEX 01 50:32:00
+"#,
+    );
+    assert_eq!(sheet.files.len(), 1);
+    let tracks: Vec<&CueTrack> = sheet.tracks().collect();
+    assert_eq!(tracks.len(), 3);
+    assert!(tracks[0].is_data());
+    assert!(!tracks[1].is_data());
+    assert_eq!(tracks[1].start, (45 * 60 + 2) * 75);
+    assert_eq!(tracks[2].number, 3);
+}
+
+#[test]
+fn test_parse_multiple_files() {
+    let sheet = parse(
+        "REM test\nFILE \"Game (Track 1).bin\" BINARY\n  TRACK 01 MODE2/2352\n\
+         file track2.bin BINARY\n  TRACK 02 AUDIO\n    INDEX 00 00:00:00\n    INDEX 01 00:02:00\n",
+    );
+    assert_eq!(
+        sheet.filenames().collect::<Vec<_>>(),
+        vec!["Game (Track 1).bin", "track2.bin"]
+    );
+    assert_eq!(sheet.files[1].tracks[0].start, 150);
+}
+
+#[test]
+fn test_parse_skips_tracks_without_file() {
+    assert!(parse("TRACK 01 AUDIO\n").files.is_empty());
+}
+
+#[test]
+fn test_parse_file_directive() {
+    assert_eq!(
+        parse_file_directive("  FILE \"a b.bin\" BINARY"),
+        Some(("a b.bin".into(), "BINARY".into()))
+    );
+    assert_eq!(
+        parse_file_directive("File\ttrack.wav\tWAVE"),
+        None,
+        "the keyword must be followed by a space"
+    );
+    assert_eq!(
+        parse_file_directive("file track.bin\tBINARY"),
+        Some(("track.bin".into(), "BINARY".into()))
+    );
+    assert_eq!(parse_file_directive("FILE \"unterminated.bin BINARY"), None);
+    assert_eq!(parse_file_directive("TRACK 01 AUDIO"), None);
+}
+
+#[test]
+fn test_parse_msf() {
+    assert_eq!(parse_msf("00:02:00"), Some(150));
+    assert_eq!(parse_msf("01:00:05"), Some(60 * 75 + 5));
+    assert_eq!(parse_msf("00:02"), None);
+}
+
+#[test]
+fn test_looks_like_sheet() {
+    assert!(looks_like_sheet(
+        b"FILE \"game.bin\" BINARY\r\n  TRACK 01 MODE1/2352\r\n"
+    ));
+    assert!(looks_like_sheet(b"file game.bin binary\n track 01 audio\n"));
+    assert!(!looks_like_sheet(b"FILE \"game.bin\" BINARY\n"));
+    assert!(!looks_like_sheet(b"FILE x\0TRACK 01\n"));
+    assert!(!looks_like_sheet(b""));
+}
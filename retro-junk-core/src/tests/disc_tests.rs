@@ -193,3 +193,87 @@ fn derive_base_game_name_divergent_last_group() {
         "Game (USA)"
     );
 }
+
+// -- multi-part (non-disc) tests --
+
+const FLOPPY: &[PartKind] = &[PartKind::Disk, PartKind::Side];
+
+#[test]
+fn extract_part_floppy_tags() {
+    let tag = extract_part("Lemmings (1991)(Psygnosis)(Disk 2 of 2)", FLOPPY).unwrap();
+    assert_eq!(
+        (tag.kind, tag.number, tag.total),
+        (PartKind::Disk, 2, Some(2))
+    );
+
+    let tag = extract_part("Zelda no Densetsu (Japan) (Disk 1 Side B)", FLOPPY).unwrap();
+    assert_eq!(tag.sort_key(), (1, 2));
+    assert_eq!(tag.label(), "Disk 1 Side B");
+
+    let tag = extract_part("Metroid (Japan) (Side A)", FLOPPY).unwrap();
+    assert_eq!((tag.kind, tag.number), (PartKind::Side, 1));
+    assert_eq!(tag.label(), "Side A");
+}
+
+#[test]
+fn extract_part_respects_kinds() {
+    assert!(extract_part("Final Fantasy VII (Disc 1) (USA)", FLOPPY).is_none());
+    assert!(extract_part("Lemmings (Disk 1)", &[PartKind::Disc]).is_none());
+    assert!(extract_part("Game (USA) (Disk Version)", FLOPPY).is_none());
+}
+
+#[test]
+fn strip_part_tag_without_leading_space() {
+    assert_eq!(
+        strip_part_tag("Lemmings (1991)(Psygnosis)(Disk 1 of 2)", FLOPPY),
+        "Lemmings (1991)(Psygnosis)"
+    );
+    assert_eq!(
+        strip_part_tag("Zelda no Densetsu (Japan) (Disk 1 Side A)", FLOPPY),
+        "Zelda no Densetsu (Japan)"
+    );
+}
+
+#[test]
+fn detect_part_groups_orders_sides() {
+    let entries = vec![
+        (0, "Zelda no Densetsu (Japan) (Disk 1 Side B)"),
+        (1, "Zelda no Densetsu (Japan) (Disk 1 Side A)"),
+        (2, "Metroid (Japan)"),
+    ];
+    let groups = detect_part_groups(&entries, FLOPPY);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].base_name, "Zelda no Densetsu (Japan)");
+    assert_eq!(groups[0].member_indices, vec![1, 0]);
+}
+
+#[test]
+fn detect_part_groups_keeps_release_variants_apart() {
+    let entries = vec![
+        (0, "Monkey Island (1990)(Lucasfilm)(Disk 1 of 4)"),
+        (1, "Monkey Island (1990)(Lucasfilm)(Disk 2 of 4)"),
+        (2, "Monkey Island (1990)(Lucasfilm)(Disk 1 of 8)"),
+        (3, "Monkey Island (1990)(Lucasfilm)(Disk 2 of 8)"),
+    ];
+    let groups = detect_part_groups(&entries, FLOPPY);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].member_indices, vec![0, 1]);
+    assert_eq!(groups[1].member_indices, vec![2, 3]);
+}
+
+#[test]
+fn derive_base_name_for_disks() {
+    assert_eq!(
+        derive_base_name(
+            &["Pirates! (USA) (Disk 1)", "Pirates! (USA) (Disk 2)"],
+            PartKind::ALL
+        ),
+        "Pirates! (USA)"
+    );
+}
+
+#[test]
+fn part_kinds_per_platform() {
+    assert_eq!(part_kinds(Platform::Ps1), &[PartKind::Disc]);
+    assert!(part_kinds(Platform::Nes).contains(&PartKind::Side));
+}
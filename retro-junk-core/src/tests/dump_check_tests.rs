@@ -0,0 +1,85 @@
+use std::io::Cursor;
+
+use super::*;
+
+/// Non-repeating data that never looks like fill.
+fn rom_data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 + i / 251) as u8).collect()
+}
+
+fn check(data: Vec<u8>, expected: Option<u64>, sector: Option<u64>) -> Vec<DumpWarning> {
+    check_dump(&mut Cursor::new(data), expected, sector).unwrap()
+}
+
+#[test]
+fn clean_rom_has_no_warnings() {
+    let data = rom_data(256 * 1024);
+    assert!(check(data, Some(256 * 1024), None).is_empty());
+}
+
+#[test]
+fn blank_file_is_reported_alone() {
+    let warnings = check(vec![0xFF; 128 * 1024], Some(64 * 1024), None);
+    assert_eq!(warnings, vec![DumpWarning::BlankFile { fill: 0xFF }]);
+}
+
+#[test]
+fn blank_padding_past_expected_size() {
+    let mut data = rom_data(64 * 1024);
+    data.extend(vec![0x00; 64 * 1024]);
+    let warnings = check(data, Some(64 * 1024), None);
+    assert_eq!(
+        warnings,
+        vec![DumpWarning::BlankPadding {
+            offset: 64 * 1024,
+            len: 64 * 1024,
+            fill: 0x00,
+        }]
+    );
+}
+
+#[test]
+fn real_data_past_expected_size_is_not_padding() {
+    let data = rom_data(128 * 1024);
+    assert!(check(data, Some(64 * 1024), None).is_empty());
+}
+
+#[test]
+fn repeated_blocks_are_reported() {
+    let block = rom_data(BLOCK_SIZE);
+    let mut data = rom_data(16 * 1024);
+    for _ in 0..MIN_REPEATED_BLOCKS {
+        data.extend_from_slice(&block);
+    }
+    let warnings = check(data, None, None);
+    assert_eq!(
+        warnings,
+        vec![DumpWarning::RepeatedBlocks {
+            offset: 16 * 1024,
+            count: MIN_REPEATED_BLOCKS,
+        }]
+    );
+}
+
+#[test]
+fn truncated_disc_sector() {
+    let data = rom_data(2352 * 10 + 100);
+    let warnings = check(data, None, Some(2352));
+    assert_eq!(
+        warnings,
+        vec![DumpWarning::TruncatedSector {
+            sector_size: 2352,
+            remainder: 100,
+        }]
+    );
+}
+
+#[test]
+fn sector_size_by_extension() {
+    assert_eq!(disc_sector_size(Path::new("game.ISO")), Some(2048));
+    assert_eq!(
+        disc_sector_size(Path::new("game (Track 1).bin")),
+        Some(2352)
+    );
+    assert_eq!(disc_sector_size(Path::new("game.chd")), None);
+}
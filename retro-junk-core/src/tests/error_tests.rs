@@ -0,0 +1,50 @@
+use super::*;
+
+#[test]
+fn io_errors_convert_without_offset() {
+    let err: AnalysisError = std::io::Error::other("disk gone").into();
+    assert_eq!(err.code(), "io");
+    assert_eq!(err.kind(), AnalysisErrorKind::Io);
+    assert_eq!(err.to_string(), "I/O error: disk gone");
+}
+
+#[test]
+fn io_at_reports_offset() {
+    let err = AnalysisError::io_at(std::io::Error::other("bad sector"), 0x8000);
+    assert_eq!(err.to_string(), "I/O error at 0x8000: bad sector");
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn invalid_magic_shows_expected_and_found() {
+    let err = AnalysisError::invalid_magic(0x100, b"NCSD", &[0, 1, 2, 3]);
+    assert_eq!(
+        err.to_string(),
+        "Invalid magic at 0x100: expected 4E 43 53 44, found 00 01 02 03"
+    );
+    assert_eq!(err.code(), "invalid_magic");
+    assert_eq!(err.kind(), AnalysisErrorKind::NotThisFormat);
+}
+
+#[test]
+fn kinds_separate_wrong_format_from_damage_and_gaps() {
+    assert_eq!(
+        AnalysisError::too_small(16, 4).kind(),
+        AnalysisErrorKind::NotThisFormat
+    );
+    assert_eq!(
+        AnalysisError::truncated_header(0x200, 0x80).kind(),
+        AnalysisErrorKind::Corrupt
+    );
+    assert_eq!(
+        AnalysisError::corrupted_header("bad").kind(),
+        AnalysisErrorKind::Corrupt
+    );
+    let unsupported = AnalysisError::unsupported("CD-i mode");
+    assert_eq!(unsupported.kind(), AnalysisErrorKind::Unsupported);
+    assert_eq!(unsupported.code(), "unsupported_sub_format");
+    assert_eq!(
+        AnalysisErrorKind::NotThisFormat.to_string(),
+        "not_this_format"
+    );
+}
@@ -0,0 +1,141 @@
+use std::io::Cursor;
+
+use super::*;
+
+#[test]
+fn test_hash_reader_known_digests() {
+    let hashes = hash_reader(&mut Cursor::new(b"abc"), HashAlgorithms::All).unwrap();
+    assert_eq!(hashes.crc32, "352441c2");
+    assert_eq!(
+        hashes.sha1.as_deref(),
+        Some("a9993e364706816aba3e25717850c26c9cd0d89d")
+    );
+    assert_eq!(
+        hashes.md5.as_deref(),
+        Some("900150983cd24fb0d6963f7d28e17f72")
+    );
+    assert_eq!(hashes.data_size, 3);
+}
+
+#[test]
+fn test_hash_reader_skips_unrequested_digests() {
+    let hashes = hash_reader(&mut Cursor::new(b"abc"), HashAlgorithms::Crc32).unwrap();
+    assert!(hashes.sha1.is_none());
+    assert!(hashes.md5.is_none());
+    assert!(hashes.sha256.is_none());
+}
+
+#[test]
+fn test_hash_reader_sha256() {
+    let hashes = hash_reader(&mut Cursor::new(b"abc"), HashAlgorithms::AllWithSha256).unwrap();
+    assert_eq!(
+        hashes.sha256.as_deref(),
+        Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+    );
+    let threaded = {
+        let mut hasher = StreamHasher::threaded(HashAlgorithms::AllWithSha256);
+        hasher.update(b"abc");
+        hasher.finish()
+    };
+    assert_eq!(threaded.sha256, hashes.sha256);
+    assert!(
+        StreamHasher::new(HashAlgorithms::AllWithSha256)
+            .state()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_hash_reader_take_hashes_leading_bytes() {
+    let mut reader = Cursor::new(b"abcdef".to_vec());
+    let hashes = hash_reader(&mut (&mut reader).take(3), HashAlgorithms::Crc32).unwrap();
+    assert_eq!(hashes.crc32, "352441c2");
+    assert_eq!(hashes.data_size, 3);
+}
+
+#[test]
+fn test_threaded_matches_inline() {
+    let data: Vec<u8> = (0..3_000_000u32).map(|i| (i % 251) as u8).collect();
+    let mut inline = StreamHasher::new(HashAlgorithms::All);
+    let mut threaded = StreamHasher::threaded(HashAlgorithms::All);
+    for chunk in data.chunks(1 << 20) {
+        inline.update(chunk);
+        threaded.update(chunk);
+    }
+    let (inline, threaded) = (inline.finish(), threaded.finish());
+    assert_eq!(inline.crc32, threaded.crc32);
+    assert_eq!(inline.sha1, threaded.sha1);
+    assert_eq!(inline.md5, threaded.md5);
+    assert_eq!(threaded.data_size, data.len() as u64);
+}
+
+/// Hash `data` in two halves, saving after the first and resuming the save
+/// in a fresh hasher built by `make`.
+fn hash_resumed(data: &[u8], make: fn(HashAlgorithms) -> StreamHasher) -> FileHashes {
+    let (head, tail) = data.split_at(data.len() / 2 + 7);
+    let mut first = make(HashAlgorithms::Crc32Sha1);
+    for chunk in head.chunks(1 << 20) {
+        first.update(chunk);
+    }
+    let state = first.state().unwrap();
+    assert_eq!(state.len, head.len() as u64);
+
+    let mut second = make(HashAlgorithms::Crc32Sha1);
+    assert!(second.resume(&state));
+    for chunk in tail.chunks(1 << 20) {
+        second.update(chunk);
+    }
+    second.finish()
+}
+
+#[test]
+fn test_resume_from_saved_state() {
+    let data: Vec<u8> = (0..3_000_000u32).map(|i| (i % 253) as u8).collect();
+    let expected = hash_reader(&mut &data[..], HashAlgorithms::Crc32Sha1).unwrap();
+    for make in [StreamHasher::new, StreamHasher::threaded] {
+        let resumed = hash_resumed(&data, make);
+        assert_eq!(resumed.crc32, expected.crc32);
+        assert_eq!(resumed.sha1, expected.sha1);
+        assert_eq!(resumed.data_size, expected.data_size);
+    }
+}
+
+#[test]
+fn test_state_unavailable_with_md5() {
+    let mut hasher = StreamHasher::threaded(HashAlgorithms::All);
+    hasher.update(b"abc");
+    assert!(hasher.state().is_none());
+}
+
+#[test]
+fn test_resume_rejects_mismatched_state() {
+    let mut hasher = StreamHasher::new(HashAlgorithms::Crc32Sha1);
+    hasher.update(b"abc");
+    let state = hasher.state().unwrap();
+
+    // Already fed data
+    assert!(!hasher.resume(&state));
+    // State without the SHA1 this hasher computes
+    let crc_only = HashState {
+        sha1: None,
+        ..state.clone()
+    };
+    assert!(!StreamHasher::new(HashAlgorithms::Crc32Sha1).resume(&crc_only));
+    // Corrupt SHA1 state
+    let corrupt = HashState {
+        sha1: Some(vec![0; 3]),
+        ..state
+    };
+    assert!(!StreamHasher::new(HashAlgorithms::Crc32Sha1).resume(&corrupt));
+}
+
+#[test]
+fn test_unhex_round_trips_hex() {
+    assert_eq!(
+        unhex(&hex(&[0x00, 0xAB, 0xff])),
+        Some(vec![0x00, 0xAB, 0xFF])
+    );
+    assert_eq!(unhex("00ABff"), Some(vec![0x00, 0xAB, 0xFF]));
+    assert_eq!(unhex("abc"), None);
+    assert_eq!(unhex("zz"), None);
+}
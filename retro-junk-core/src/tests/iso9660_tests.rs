@@ -0,0 +1,270 @@
+use super::*;
+
+const ROOT_LBA: u32 = 20;
+const DATA_LBA: u32 = 21;
+const SYSTEM_CNF: &[u8] = b"BOOT2 = cdrom0:\\SLUS_123.45;1\r\n";
+
+/// A directory record for `id`, padded to an even length.
+fn dir_record(id: &[u8], lba: u32, len: u32, flags: u8) -> Vec<u8> {
+    let mut rec = vec![0u8; DIR_RECORD_HEADER];
+    rec[2..6].copy_from_slice(&lba.to_le_bytes());
+    rec[10..14].copy_from_slice(&len.to_le_bytes());
+    rec[25] = flags;
+    rec[32] = id.len() as u8;
+    rec.extend_from_slice(id);
+    if rec.len() % 2 == 1 {
+        rec.push(0);
+    }
+    rec[0] = rec.len() as u8;
+    rec
+}
+
+fn put_dir(image: &mut [u8], lba: u32, records: &[Vec<u8>]) {
+    let mut pos = lba as usize * SECTOR_SIZE;
+    for rec in records {
+        image[pos..pos + rec.len()].copy_from_slice(rec);
+        pos += rec.len();
+    }
+}
+
+fn ucs2(name: &str) -> Vec<u8> {
+    name.encode_utf16().flat_map(u16::to_be_bytes).collect()
+}
+
+/// An image with SYSTEM.CNF and DATA/0.BIN, and optionally a Joliet tree
+/// naming the same files.
+fn make_image(joliet: bool) -> Vec<u8> {
+    let mut image = vec![0u8; 32 * SECTOR_SIZE];
+    let sector = |n: usize| n * SECTOR_SIZE;
+
+    let pvd = sector(16);
+    image[pvd] = TYPE_PRIMARY;
+    image[pvd + 1..pvd + 6].copy_from_slice(b"CD001");
+    image[pvd + 8..pvd + 40].copy_from_slice(&[b' '; 32]);
+    image[pvd + 8..pvd + 19].copy_from_slice(b"PLAYSTATION");
+    image[pvd + 40..pvd + 72].copy_from_slice(&[b' '; 32]);
+    image[pvd + 40..pvd + 44].copy_from_slice(b"GAME");
+    image[pvd + 80..pvd + 84].copy_from_slice(&32u32.to_le_bytes());
+    image[pvd + 132..pvd + 136].copy_from_slice(&22u32.to_le_bytes());
+    image[pvd + 140..pvd + 144].copy_from_slice(&19u32.to_le_bytes());
+    image[pvd + 156 + 2..pvd + 156 + 6].copy_from_slice(&ROOT_LBA.to_le_bytes());
+    image[pvd + 156 + 10..pvd + 156 + 14].copy_from_slice(&2048u32.to_le_bytes());
+    image[pvd + 813..pvd + 821].copy_from_slice(b"20001231");
+
+    let mut next = 17;
+    if joliet {
+        let svd = sector(next);
+        image[svd] = TYPE_SUPPLEMENTARY;
+        image[svd + 1..svd + 6].copy_from_slice(b"CD001");
+        image[svd + 88..svd + 91].copy_from_slice(b"%/E");
+        image[svd + 156 + 2..svd + 156 + 6].copy_from_slice(&26u32.to_le_bytes());
+        image[svd + 156 + 10..svd + 156 + 14].copy_from_slice(&2048u32.to_le_bytes());
+        next += 1;
+    }
+    let term = sector(next);
+    image[term] = TYPE_TERMINATOR;
+    image[term + 1..term + 6].copy_from_slice(b"CD001");
+
+    // Path table: root, then DATA under it
+    let pt = sector(19);
+    image[pt] = 1;
+    image[pt + 2..pt + 6].copy_from_slice(&ROOT_LBA.to_le_bytes());
+    image[pt + 6] = 1;
+    image[pt + 10] = 4;
+    image[pt + 12..pt + 16].copy_from_slice(&22u32.to_le_bytes());
+    image[pt + 16] = 1;
+    image[pt + 18..pt + 22].copy_from_slice(b"DATA");
+
+    put_dir(
+        &mut image,
+        ROOT_LBA,
+        &[
+            dir_record(&[0], ROOT_LBA, 2048, FLAG_DIRECTORY),
+            dir_record(&[1], ROOT_LBA, 2048, FLAG_DIRECTORY),
+            dir_record(b"DATA", 22, 2048, FLAG_DIRECTORY),
+            dir_record(b"SYSTEM.CNF;1", DATA_LBA, SYSTEM_CNF.len() as u32, 0),
+        ],
+    );
+    put_dir(
+        &mut image,
+        22,
+        &[
+            dir_record(&[0], 22, 2048, FLAG_DIRECTORY),
+            dir_record(&[1], ROOT_LBA, 2048, FLAG_DIRECTORY),
+            dir_record(b"0.BIN;1", 23, 3000, 0),
+        ],
+    );
+    if joliet {
+        put_dir(
+            &mut image,
+            26,
+            &[
+                dir_record(&[0], 26, 2048, FLAG_DIRECTORY),
+                dir_record(&[1], 26, 2048, FLAG_DIRECTORY),
+                dir_record(&ucs2("Data Files"), 27, 2048, FLAG_DIRECTORY),
+                dir_record(&ucs2("system.cnf;1"), DATA_LBA, SYSTEM_CNF.len() as u32, 0),
+            ],
+        );
+        put_dir(
+            &mut image,
+            27,
+            &[
+                dir_record(&[0], 27, 2048, FLAG_DIRECTORY),
+                dir_record(&[1], 26, 2048, FLAG_DIRECTORY),
+                dir_record(&ucs2("0.bin;1"), 23, 3000, 0),
+            ],
+        );
+    }
+
+    let data = sector(DATA_LBA as usize);
+    image[data..data + SYSTEM_CNF.len()].copy_from_slice(SYSTEM_CNF);
+    // 0.BIN spans two sectors
+    image[sector(23)..sector(23) + 3000].fill(0xAB);
+    image
+}
+
+fn source(image: &[u8]) -> impl FnMut(u64) -> Result<[u8; SECTOR_SIZE], AnalysisError> + '_ {
+    |lba| {
+        let start = lba as usize * SECTOR_SIZE;
+        image
+            .get(start..start + SECTOR_SIZE)
+            .map(|s| s.try_into().unwrap())
+            .ok_or_else(|| AnalysisError::corrupted_header("sector beyond end of image"))
+    }
+}
+
+#[test]
+fn open_parses_pvd() {
+    let image = make_image(false);
+    let fs = Iso9660::open(source(&image), &ParseLimits::default()).unwrap();
+    let pvd = fs.pvd();
+    assert_eq!(pvd.system_identifier, "PLAYSTATION");
+    assert_eq!(pvd.volume_identifier, "GAME");
+    assert_eq!(pvd.volume_space_size, 32);
+    assert_eq!(pvd.image_size(2048), 32 * 2048);
+    assert_eq!(pvd.image_size(2352), 32 * 2352);
+    assert_eq!(pvd.root_dir_extent_lba, ROOT_LBA);
+    assert_eq!(pvd.creation_date.unwrap().to_string(), "2000-12-31");
+    assert!(!fs.is_joliet());
+}
+
+#[test]
+fn has_udf_finds_nsr_descriptor() {
+    assert!(!has_udf(&mut source(&make_image(false))));
+
+    // Volume recognition sequence after the ISO descriptors
+    let mut image = vec![0u8; 24 * SECTOR_SIZE];
+    for (lba, id) in [
+        (16, b"CD001"),
+        (17, b"BEA01"),
+        (18, b"NSR02"),
+        (19, b"TEA01"),
+    ] {
+        let sector = &mut image[lba * SECTOR_SIZE..(lba + 1) * SECTOR_SIZE];
+        sector[1..6].copy_from_slice(id);
+        sector[6] = 1;
+    }
+    assert!(has_udf(&mut source(&image)));
+}
+
+#[test]
+fn open_rejects_missing_pvd() {
+    let image = vec![0u8; 32 * SECTOR_SIZE];
+    assert!(Iso9660::open(source(&image), &ParseLimits::default()).is_err());
+}
+
+#[test]
+fn read_dir_skips_dot_entries_and_versions() {
+    let image = make_image(false);
+    let mut fs = Iso9660::open(source(&image), &ParseLimits::default()).unwrap();
+    let root = fs.root();
+    let names: Vec<String> = fs
+        .read_dir(&root)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    assert_eq!(names, ["DATA", "SYSTEM.CNF"]);
+}
+
+#[test]
+fn lookup_and_read_nested_file() {
+    let image = make_image(false);
+    let mut fs = Iso9660::open(source(&image), &ParseLimits::default()).unwrap();
+
+    assert_eq!(fs.read_path("system.cnf;1").unwrap(), SYSTEM_CNF);
+    let bin = fs.lookup("cdrom0:\\DATA\\0.BIN;1").unwrap().unwrap();
+    assert!(!bin.is_dir());
+    let data = fs.read_file(&bin).unwrap();
+    assert_eq!(data.len(), 3000);
+    assert!(data.iter().all(|&b| b == 0xAB));
+
+    assert!(fs.lookup("/DATA").unwrap().unwrap().is_dir());
+    assert!(fs.lookup("MISSING.BIN").unwrap().is_none());
+    assert!(fs.lookup("SYSTEM.CNF/0.BIN").unwrap().is_none());
+    assert!(fs.read_path("DATA").is_err());
+}
+
+#[test]
+fn walk_lists_every_entry() {
+    let image = make_image(false);
+    let mut fs = Iso9660::open(source(&image), &ParseLimits::default()).unwrap();
+    let paths: Vec<String> = fs.walk().unwrap().into_iter().map(|(p, _)| p).collect();
+    assert_eq!(paths, ["/DATA", "/DATA/0.BIN", "/SYSTEM.CNF"]);
+}
+
+#[test]
+fn path_table_lists_directories() {
+    let image = make_image(false);
+    let mut fs = Iso9660::open(source(&image), &ParseLimits::default()).unwrap();
+    let table = fs.path_table().unwrap();
+    assert_eq!(table.len(), 2);
+    assert_eq!(table[0].name, "");
+    assert_eq!(table[0].extent_lba, ROOT_LBA);
+    assert_eq!(table[1].name, "DATA");
+    assert_eq!(table[1].parent, 1);
+}
+
+#[test]
+fn joliet_names_are_used_when_present() {
+    let image = make_image(true);
+    let mut fs = Iso9660::open(source(&image), &ParseLimits::default()).unwrap();
+    assert!(fs.is_joliet());
+    assert_eq!(fs.read_path("SYSTEM.CNF").unwrap(), SYSTEM_CNF);
+    let bin = fs.lookup("Data Files/0.BIN").unwrap().unwrap();
+    assert_eq!(bin.name, "0.bin");
+}
+
+#[test]
+fn limits_are_enforced() {
+    let image = make_image(false);
+    let tiny_alloc = ParseLimits {
+        max_alloc: 8,
+        ..ParseLimits::default()
+    };
+    let mut fs = Iso9660::open(source(&image), &tiny_alloc).unwrap();
+    let err = fs.read_path("SYSTEM.CNF").unwrap_err();
+    assert_eq!(err.code(), "limit_exceeded");
+
+    let few_entries = ParseLimits {
+        max_entries: 1,
+        ..ParseLimits::default()
+    };
+    let mut fs = Iso9660::open(source(&image), &few_entries).unwrap();
+    let root = fs.root();
+    assert_eq!(fs.read_dir(&root).unwrap_err().code(), "limit_exceeded");
+}
+
+#[test]
+fn self_referencing_directories_terminate() {
+    let mut image = make_image(false);
+    // Point DATA back at the root
+    put_dir(
+        &mut image,
+        22,
+        &[dir_record(b"LOOP", 22, 2048, FLAG_DIRECTORY)],
+    );
+    let mut fs = Iso9660::open(source(&image), &ParseLimits::default()).unwrap();
+    let entries = fs.walk().unwrap();
+    assert!(entries.len() < 10);
+}
@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn localized_names_follow_display_language() {
+    assert_eq!(Language::German.localized_name(Language::English), "German");
+    assert_eq!(
+        Language::German.localized_name(Language::French),
+        "Allemand"
+    );
+    assert_eq!(Language::English.localized_name(Language::Japanese), "英語");
+    assert_eq!(Language::Chinese.localized_name(Language::Spanish), "Chino");
+    assert_eq!(
+        Language::Korean.localized_name(Language::German),
+        "Koreanisch"
+    );
+}
+
+#[test]
+fn untranslated_display_language_falls_back_to_english() {
+    assert_eq!(Language::French.localized_name(Language::Korean), "French");
+}
+
+#[test]
+fn every_display_language_names_every_language() {
+    for &display in Language::DISPLAY {
+        for &language in Language::ALL {
+            assert!(!language.localized_name(display).is_empty());
+        }
+    }
+}
+
+#[test]
+fn parse_from_code_or_name() {
+    assert_eq!("ja".parse::<Language>().unwrap(), Language::Japanese);
+    assert_eq!("FR".parse::<Language>().unwrap(), Language::French);
+    assert_eq!("spanish".parse::<Language>().unwrap(), Language::Spanish);
+    assert!("klingon".parse::<Language>().is_err());
+}
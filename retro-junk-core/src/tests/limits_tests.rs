@@ -0,0 +1,87 @@
+use super::*;
+use crate::AnalysisErrorKind;
+use std::io::Cursor;
+
+#[test]
+fn check_alloc_passes_within_limit() {
+    let limits = ParseLimits::hardened();
+    assert_eq!(limits.check_alloc(2048, "sector").unwrap(), 2048);
+}
+
+#[test]
+fn check_alloc_rejects_oversized_buffers() {
+    let err = ParseLimits::hardened()
+        .check_alloc(u32::MAX as u64, "SYSTEM.CNF")
+        .unwrap_err();
+    assert_eq!(err.code(), "limit_exceeded");
+    assert_eq!(err.kind(), AnalysisErrorKind::Limit);
+    assert_eq!(
+        err.to_string(),
+        "SYSTEM.CNF exceeds the allocation limit: 4294967295 > 67108864"
+    );
+}
+
+#[test]
+fn check_entries_rejects_long_tables() {
+    let limits = ParseLimits {
+        max_entries: 4,
+        ..ParseLimits::default()
+    };
+    assert!(limits.check_entries(4, "tracks").is_ok());
+    let err = limits.check_entries(5, "tracks").unwrap_err();
+    assert!(matches!(
+        err,
+        AnalysisError::LimitExceeded(LimitExceeded {
+            limit: Limit::Entries,
+            requested: 5,
+            max: 4,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn read_text_stops_at_max_alloc() {
+    let limits = ParseLimits {
+        max_alloc: 4,
+        ..ParseLimits::default()
+    };
+    assert_eq!(
+        limits
+            .read_text(&mut Cursor::new("FILE"), "CUE sheet")
+            .unwrap(),
+        "FILE"
+    );
+    let err = limits
+        .read_text(&mut Cursor::new("FILE \"a.bin\""), "CUE sheet")
+        .unwrap_err();
+    assert_eq!(err.code(), "limit_exceeded");
+}
+
+#[test]
+fn limited_reader_counts_seeks() {
+    let limits = ParseLimits {
+        max_seeks: 2,
+        ..ParseLimits::default()
+    };
+    let mut reader = LimitedReader::new(Cursor::new(vec![0u8; 16]), &limits);
+    reader.seek(SeekFrom::Start(4)).unwrap();
+    reader.seek(SeekFrom::Start(8)).unwrap();
+    assert_eq!(reader.seeks(), 2);
+
+    let io_err = reader.seek(SeekFrom::Start(0)).unwrap_err();
+    let err = AnalysisError::from(io_err);
+    assert_eq!(err.code(), "limit_exceeded");
+    assert_eq!(err.kind(), AnalysisErrorKind::Limit);
+}
+
+#[test]
+fn io_at_keeps_limit_errors() {
+    let limits = ParseLimits {
+        max_seeks: 0,
+        ..ParseLimits::default()
+    };
+    let mut reader = LimitedReader::new(Cursor::new(vec![0u8; 16]), &limits);
+    let io_err = reader.seek(SeekFrom::Start(0)).unwrap_err();
+    assert_eq!(AnalysisError::io_at(io_err, 0).code(), "limit_exceeded");
+}
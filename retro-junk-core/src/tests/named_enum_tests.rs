@@ -0,0 +1,43 @@
+use super::*;
+
+crate::named_enum! {
+    /// How loudly to complain.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum Volume("volume") {
+        #[default]
+        Quiet = "quiet",
+        Loud = "loud" | "noisy",
+        VeryLoud = "very-loud",
+    }
+}
+
+#[test]
+fn parses_names_and_aliases_in_any_case() {
+    assert_eq!("quiet".parse(), Ok(Volume::Quiet));
+    assert_eq!("NOISY".parse(), Ok(Volume::Loud));
+    assert_eq!("Very-Loud".parse(), Ok(Volume::VeryLoud));
+}
+
+#[test]
+fn displays_the_canonical_name() {
+    for volume in Volume::ALL {
+        assert_eq!(volume.to_string(), volume.as_str());
+        assert_eq!(volume.as_str().parse(), Ok(*volume));
+    }
+    assert_eq!(Volume::default(), Volume::Quiet);
+}
+
+#[test]
+fn unknown_names_list_the_choices() {
+    assert_eq!(
+        "meh".parse::<Volume>().unwrap_err(),
+        "unknown volume 'meh' (expected quiet, loud, or very-loud)"
+    );
+}
+
+#[test]
+fn test_or_list() {
+    assert_eq!(or_list(&["a"]), "a");
+    assert_eq!(or_list(&["a", "b"]), "a or b");
+    assert_eq!(or_list(&["a", "b", "c"]), "a, b, or c");
+}
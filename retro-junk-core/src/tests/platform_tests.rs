@@ -65,3 +65,43 @@ fn short_name_is_first_alias() {
         );
     }
 }
+
+#[test]
+fn families_group_related_platforms() {
+    assert_eq!(Platform::Gba.family(), Some(PlatformFamily::GameBoy));
+    assert_eq!(Platform::Sega32x.family(), Some(PlatformFamily::MegaDrive));
+    assert_eq!(
+        Platform::GameGear.family(),
+        Some(PlatformFamily::SegaEightBit)
+    );
+    assert_eq!(Platform::Nes.family(), None);
+
+    for &family in PlatformFamily::all() {
+        for member in family.members() {
+            assert_eq!(member.family(), Some(family));
+        }
+    }
+}
+
+#[test]
+fn family_aliases_parse_and_never_shadow_platforms() {
+    assert_eq!(
+        "GB-Family".parse::<PlatformFamily>().unwrap(),
+        PlatformFamily::GameBoy
+    );
+    assert!("snes".parse::<PlatformFamily>().is_err());
+
+    for &family in PlatformFamily::all() {
+        assert_eq!(
+            family.short_name().parse::<PlatformFamily>().unwrap(),
+            family
+        );
+        for alias in family.aliases() {
+            assert!(
+                alias.parse::<Platform>().is_err(),
+                "family alias '{}' is also a platform alias",
+                alias
+            );
+        }
+    }
+}
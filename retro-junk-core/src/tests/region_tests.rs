@@ -0,0 +1,16 @@
+use super::*;
+
+#[test]
+fn localized_names_follow_display_language() {
+    assert_eq!(Region::Europe.localized_name(Language::English), "Europe");
+    assert_eq!(Region::Japan.localized_name(Language::French), "Japon");
+    assert_eq!(Region::World.localized_name(Language::German), "Welt");
+    assert_eq!(Region::Usa.localized_name(Language::Spanish), "EE. UU.");
+    assert_eq!(Region::Korea.localized_name(Language::Japanese), "韓国");
+    assert_eq!(Region::Unknown.localized_name(Language::French), "Inconnue");
+}
+
+#[test]
+fn untranslated_display_language_falls_back_to_english() {
+    assert_eq!(Region::Brazil.localized_name(Language::Russian), "Brazil");
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn basic_trims_and_uppercases() {
+    assert_eq!(normalize_basic("  slus-00594 "), "SLUS-00594");
+    assert_eq!(normalize_basic("GM 00001009-00"), "GM 00001009-00");
+}
+
+#[test]
+fn hyphenated_folds_separators() {
+    assert_eq!(normalize_hyphenated("ntr_admE"), "NTR-ADME");
+    assert_eq!(normalize_hyphenated("NTR - ADME"), "NTR-ADME");
+    assert_eq!(normalize_hyphenated("-NUS--NSME-USA-"), "NUS-NSME-USA");
+}
+
+#[test]
+fn patterns_match_whole_serials_when_anchored() {
+    assert!(matches_pattern(r"^[A-Z]{4}-\d{5}$", "SLUS-00594"));
+    assert!(!matches_pattern(r"^[A-Z]{4}-\d{5}$", "SLUS-00594X"));
+    assert!(!matches_pattern(r"^[A-Z]{4}-\d{5}$", "SLUS-594"));
+}
+
+#[test]
+fn invalid_pattern_matches_nothing() {
+    assert!(!matches_pattern(r"^[A-Z", "A"));
+}
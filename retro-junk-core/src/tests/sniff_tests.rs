@@ -0,0 +1,31 @@
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+use super::*;
+
+#[test]
+fn sniff_reader_reports_full_size_but_serves_only_prefix() {
+    let head = [1u8, 2, 3, 4];
+    let mut reader = SniffReader::new(&head, 1000);
+    assert_eq!(reader.seek(SeekFrom::End(0)).unwrap(), 1000);
+
+    reader.seek(SeekFrom::Start(2)).unwrap();
+    let mut buf = [0u8; 4];
+    assert_eq!(reader.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], &[3, 4]);
+
+    reader.seek(SeekFrom::Start(500)).unwrap();
+    assert!(reader.read_exact(&mut buf).is_err());
+    assert!(reader.seek(SeekFrom::Current(-1000)).is_err());
+}
+
+#[test]
+fn read_prefix_caps_length_and_rewinds() {
+    let data: Vec<u8> = (0..100u8).collect();
+    let mut cursor = Cursor::new(data);
+    cursor.seek(SeekFrom::Start(50)).unwrap();
+
+    let (head, size) = read_prefix(&mut cursor, 10).unwrap();
+    assert_eq!(head, (0..10u8).collect::<Vec<_>>());
+    assert_eq!(size, 100);
+    assert_eq!(cursor.position(), 0);
+}
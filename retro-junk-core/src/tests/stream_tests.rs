@@ -0,0 +1,151 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use super::*;
+
+/// A reader that refuses to be anything but read front to back.
+struct Pipe(std::io::Cursor<Vec<u8>>);
+
+impl Read for Pipe {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+fn pipe(len: usize) -> Pipe {
+    Pipe(std::io::Cursor::new(
+        (0..len).map(|i| (i % 251) as u8).collect(),
+    ))
+}
+
+fn byte_at(offset: usize) -> u8 {
+    (offset % 251) as u8
+}
+
+#[test]
+fn seeks_back_to_data_already_read() {
+    let mut reader = StreamReader::new(pipe(1000));
+    let mut buf = [0u8; 16];
+    reader.seek(SeekFrom::Start(100)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf[0], byte_at(100));
+
+    reader.seek(SeekFrom::Start(0)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf[0], byte_at(0));
+    assert_eq!(reader.total_len(), Some(1000));
+}
+
+#[test]
+fn seek_from_end_learns_the_size() {
+    let mut reader = StreamReader::new(pipe(3 * CHUNK_SIZE + 10));
+    assert_eq!(reader.total_len(), None);
+    assert_eq!(
+        reader.seek(SeekFrom::End(0)).unwrap(),
+        (3 * CHUNK_SIZE + 10) as u64
+    );
+
+    let mut buf = [0u8; 4];
+    reader.seek(SeekFrom::End(-4)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf[3], byte_at(3 * CHUNK_SIZE + 9));
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn reads_span_chunk_boundaries() {
+    let mut reader = StreamReader::new(pipe(2 * CHUNK_SIZE));
+    reader.seek(SeekFrom::Start(CHUNK_SIZE as u64 - 2)).unwrap();
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf[2], byte_at(CHUNK_SIZE));
+
+    let mut rest = Vec::new();
+    reader.seek(SeekFrom::Start(0)).unwrap();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest.len(), 2 * CHUNK_SIZE);
+}
+
+#[test]
+fn drops_skipped_data_past_the_budget() {
+    let total = 8 * CHUNK_SIZE + 5;
+    let mut reader = StreamReader::with_retain_limit(pipe(total), CHUNK_SIZE);
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf).unwrap();
+
+    // Draining to the end keeps only the pinned header and last chunk
+    reader.seek(SeekFrom::End(0)).unwrap();
+    assert!(reader.buffered() < 3 * CHUNK_SIZE);
+
+    reader.seek(SeekFrom::Start(0)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf[0], byte_at(0));
+
+    reader.seek(SeekFrom::Start(total as u64 - 2)).unwrap();
+    reader.read_exact(&mut buf[..2]).unwrap();
+    assert_eq!(buf[1], byte_at(total - 1));
+
+    reader.seek(SeekFrom::Start(4 * CHUNK_SIZE as u64)).unwrap();
+    let err = reader.read_exact(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+}
+
+#[test]
+fn chunk_being_read_is_kept_beyond_the_budget() {
+    let mut reader = StreamReader::with_retain_limit(pipe(6 * CHUNK_SIZE), 0);
+    let mut buf = [0u8; 4];
+    let offset = 4 * CHUNK_SIZE as u64 + 7;
+    reader.seek(SeekFrom::Start(offset)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf[0], byte_at(offset as usize));
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf[0], byte_at(offset as usize + 4));
+
+    // Once another chunk is pulled, only the head stays pinned
+    reader.seek(SeekFrom::End(0)).unwrap();
+    reader.seek(SeekFrom::Start(offset)).unwrap();
+    let err = reader.read_exact(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    reader.seek(SeekFrom::Start(0)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf[0], byte_at(0));
+}
+
+#[test]
+fn reading_more_than_the_budget_evicts_least_recently_used() {
+    let total = 20 * CHUNK_SIZE;
+    let mut reader = StreamReader::with_retain_limit(pipe(total), 5 * CHUNK_SIZE);
+    let mut buf = vec![0u8; CHUNK_SIZE / 4];
+    let mut read = 0;
+    while read < 4 * CHUNK_SIZE {
+        reader.read_exact(&mut buf).unwrap();
+        read += buf.len();
+    }
+
+    // Chunk 1 is used again, so chunk 2 is now the oldest
+    reader.seek(SeekFrom::Start(CHUNK_SIZE as u64)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    reader.seek(SeekFrom::Start(5 * CHUNK_SIZE as u64)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.buffered(), 5 * CHUNK_SIZE);
+
+    reader.seek(SeekFrom::Start(CHUNK_SIZE as u64 + 3)).unwrap();
+    reader.read_exact(&mut buf[..1]).unwrap();
+    assert_eq!(buf[0], byte_at(CHUNK_SIZE + 3));
+    reader.seek(SeekFrom::Start(2 * CHUNK_SIZE as u64)).unwrap();
+    let err = reader.read_exact(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+
+    // Streaming the rest never holds more than the budget
+    reader.seek(SeekFrom::Start(6 * CHUNK_SIZE as u64)).unwrap();
+    let mut streamed = 6 * CHUNK_SIZE;
+    loop {
+        let n = reader.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        assert_eq!(buf[0], byte_at(streamed));
+        streamed += n;
+        assert!(reader.buffered() <= 5 * CHUNK_SIZE);
+    }
+    assert_eq!(streamed, total);
+}
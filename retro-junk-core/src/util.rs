@@ -36,6 +36,47 @@ pub fn file_size(reader: &mut dyn crate::ReadSeek) -> Result<u64, crate::Analysi
     Ok(size)
 }
 
+/// Fill `buf` with the header at `offset`.
+///
+/// A file that ends partway through is a
+/// [`TruncatedHeader`](crate::AnalysisError::TruncatedHeader) reporting how
+/// many bytes were there; other read failures keep the offset.
+pub fn read_header(
+    reader: &mut dyn crate::ReadSeek,
+    offset: u64,
+    buf: &mut [u8],
+) -> Result<(), crate::AnalysisError> {
+    reader.seek(std::io::SeekFrom::Start(offset))?;
+    let read =
+        crate::hash::read_full(reader, buf).map_err(|e| crate::AnalysisError::io_at(e, offset))?;
+    if read < buf.len() {
+        return Err(crate::AnalysisError::truncated_header(
+            buf.len() as u64,
+            read as u64,
+        ));
+    }
+    Ok(())
+}
+
+/// Resolve a [`SeekFrom`](std::io::SeekFrom) against the current position
+/// `cur` and total length `len`, for readers that implement `Seek` by hand.
+///
+/// Seeking past the end is allowed, as with files; seeking before the start
+/// or beyond `u64::MAX` is an `InvalidInput` error.
+pub fn seek_target(pos: std::io::SeekFrom, cur: u64, len: u64) -> std::io::Result<u64> {
+    let target = match pos {
+        std::io::SeekFrom::Start(n) => Some(n),
+        std::io::SeekFrom::Current(n) => cur.checked_add_signed(n),
+        std::io::SeekFrom::End(n) => len.checked_add_signed(n),
+    };
+    target.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+    })
+}
+
 /// Read a null-terminated ASCII string from a byte slice.
 ///
 /// Stops at the first null byte, filters out non-printable characters,
@@ -72,6 +113,37 @@ pub fn read_ascii_fixed(buf: &[u8]) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_seek_target() {
+        use std::io::SeekFrom;
+        assert_eq!(seek_target(SeekFrom::Start(7), 3, 10).unwrap(), 7);
+        assert_eq!(seek_target(SeekFrom::Current(-2), 3, 10).unwrap(), 1);
+        assert_eq!(seek_target(SeekFrom::End(5), 3, 10).unwrap(), 15);
+        assert!(seek_target(SeekFrom::Current(-4), 3, 10).is_err());
+        assert!(seek_target(SeekFrom::End(-11), 3, 10).is_err());
+        assert!(seek_target(SeekFrom::Current(1), u64::MAX, 10).is_err());
+    }
+
+    #[test]
+    fn test_read_header() {
+        use crate::AnalysisError;
+        use std::io::Cursor;
+
+        let mut buf = [0u8; 4];
+        read_header(&mut Cursor::new(b"..HEAD..".to_vec()), 2, &mut buf).unwrap();
+        assert_eq!(&buf, b"HEAD");
+
+        let mut buf = [0u8; 8];
+        let err = read_header(&mut Cursor::new(b"..HEA".to_vec()), 2, &mut buf).unwrap_err();
+        assert!(matches!(
+            err,
+            AnalysisError::TruncatedHeader {
+                expected: 8,
+                actual: 3
+            }
+        ));
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(0), "0 bytes");
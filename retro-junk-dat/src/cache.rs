@@ -225,37 +225,48 @@ pub fn load_dats(
         return Ok(dats);
     }
 
+    if let Some(dats) = load_cached_dats(short_name, dat_names.len())? {
+        return Ok(dats);
+    }
+
+    // Download and cache
+    let paths = fetch(short_name, dat_names, download_ids, dat_source)?;
+    let mut dats = Vec::new();
+    for path in &paths {
+        dats.push(dat::parse_dat_file(path)?);
+    }
+    Ok(dats)
+}
+
+/// Load a system's DAT files from the cache without downloading anything.
+///
+/// Returns `None` unless all `dat_count` DATs are cached under the current
+/// cache version.
+pub fn load_cached_dats(
+    short_name: &str,
+    dat_count: usize,
+) -> Result<Option<Vec<DatFile>>, DatError> {
     // Check cache version before trusting cached files — a version mismatch
     // means the DAT source or format changed and we need to re-download.
     let meta = load_meta()?;
-    let cache_valid = meta.version == CACHE_VERSION;
-
-    // Try cache first — check if all indexed DATs exist and cache version is current
-    if cache_valid {
-        let mut cached_paths = Vec::new();
-        for i in 0..dat_names.len() {
-            let dat_path = dat_file_path(short_name, i)?;
-            if dat_path.exists() {
-                cached_paths.push(dat_path);
-            }
-        }
+    if meta.version != CACHE_VERSION {
+        return Ok(None);
+    }
 
-        if cached_paths.len() == dat_names.len() {
-            let mut dats = Vec::new();
-            for path in &cached_paths {
-                dats.push(dat::parse_dat_file(path)?);
-            }
-            return Ok(dats);
+    let mut cached_paths = Vec::new();
+    for i in 0..dat_count {
+        let dat_path = dat_file_path(short_name, i)?;
+        if !dat_path.exists() {
+            return Ok(None);
         }
+        cached_paths.push(dat_path);
     }
 
-    // Download and cache
-    let paths = fetch(short_name, dat_names, download_ids, dat_source)?;
     let mut dats = Vec::new();
-    for path in &paths {
+    for path in &cached_paths {
         dats.push(dat::parse_dat_file(path)?);
     }
-    Ok(dats)
+    Ok(Some(dats))
 }
 
 /// Find a DAT file in a user-provided directory.
@@ -20,10 +20,15 @@ pub struct DatGame {
     pub name: String,
     /// Region string (e.g., "USA", "Japan"), if present (LibRetro enhanced DATs).
     pub region: Option<String>,
+    /// Parent set name for clones (`cloneof`), as found in parent/clone DATs.
+    pub clone_of: Option<String>,
     pub roms: Vec<DatRom>,
 }
 
 /// A single ROM entry within a game.
+///
+/// MAME-style `disk` entries (CHD images) are stored as ROMs too: they carry
+/// only a name and the CHD's SHA1, so `size` is 0 and `crc` is empty.
 #[derive(Debug, Clone)]
 pub struct DatRom {
     pub name: String,
@@ -38,6 +43,13 @@ pub struct DatRom {
     pub serial: Option<String>,
 }
 
+impl DatRom {
+    /// Whether this entry is a `disk` (CHD) entry, identified by SHA1 alone.
+    pub fn is_disk(&self) -> bool {
+        self.crc.is_empty() && self.size == 0 && self.sha1.is_some()
+    }
+}
+
 /// Parse a DAT file, auto-detecting format (XML or ClrMamePro).
 pub fn parse_dat<R: BufRead>(mut reader: R) -> Result<DatFile, DatError> {
     // Peek at the first non-whitespace content to detect format
@@ -101,15 +113,22 @@ fn parse_xml<R: BufRead>(reader: R) -> Result<DatFile, DatError> {
                     "header" => in_header = true,
                     "game" => {
                         let mut name = String::new();
+                        let mut clone_of = None;
                         for attr in e.attributes() {
                             let attr = attr?;
-                            if attr.key.as_ref() == b"name" {
-                                name = String::from_utf8_lossy(&attr.value).to_string();
+                            match attr.key.as_ref() {
+                                b"name" => name = String::from_utf8_lossy(&attr.value).to_string(),
+                                b"cloneof" => {
+                                    clone_of =
+                                        Some(String::from_utf8_lossy(&attr.value).to_string())
+                                }
+                                _ => {}
                             }
                         }
                         current_game = Some(DatGame {
                             name,
                             region: None,
+                            clone_of,
                             roms: Vec::new(),
                         });
                         game_serial = None;
@@ -119,7 +138,7 @@ fn parse_xml<R: BufRead>(reader: R) -> Result<DatFile, DatError> {
             }
             Event::Empty(ref e) => {
                 let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
-                if tag_name == "rom"
+                if (tag_name == "rom" || tag_name == "disk")
                     && let Some(ref mut game) = current_game
                 {
                     let rom = parse_xml_rom_attributes(e)?;
@@ -251,6 +270,7 @@ fn parse_clrmamepro<R: BufRead>(reader: R) -> Result<DatFile, DatError> {
                     current_game = Some(DatGame {
                         name: String::new(),
                         region: None,
+                        clone_of: None,
                         roms: Vec::new(),
                     });
                 }
@@ -295,11 +315,12 @@ fn parse_clrmamepro<R: BufRead>(reader: R) -> Result<DatFile, DatError> {
                         match key.as_str() {
                             "name" => game.name = value,
                             "region" => game.region = Some(value),
+                            "cloneof" => game.clone_of = Some(value),
                             "serial" => {
                                 // Store game-level serial to propagate to ROMs later
                                 game_serial = Some(value);
                             }
-                            "rom" => {
+                            "rom" | "disk" => {
                                 if let Some(rom) = parse_clr_rom_inline(&value) {
                                     game.roms.push(rom);
                                 }
@@ -335,16 +356,19 @@ fn detect_block_start(line: &str) -> Option<String> {
 }
 
 /// Parse a key-value line like `name "Some Value"` or `version 20240101`.
-/// For `rom ( ... )` lines, the value is the content inside outer parens.
+/// For `rom ( ... )` and `disk ( ... )` lines, the value is the content inside
+/// outer parens.
 fn parse_kv(line: &str) -> Option<(String, String)> {
     let trimmed = line.trim();
 
     // Handle "rom ( ... )" specially — the key is "rom" and value is the inner content
-    if let Some(after_rom) = trimmed.strip_prefix("rom") {
-        let rest = after_rom.trim();
-        if rest.starts_with('(') && rest.ends_with(')') {
-            let inner = rest[1..rest.len() - 1].trim();
-            return Some(("rom".to_string(), inner.to_string()));
+    for block in ["rom", "disk"] {
+        if let Some(after_rom) = trimmed.strip_prefix(block) {
+            let rest = after_rom.trim();
+            if rest.starts_with('(') && rest.ends_with(')') {
+                let inner = rest[1..rest.len() - 1].trim();
+                return Some((block.to_string(), inner.to_string()));
+            }
         }
     }
 
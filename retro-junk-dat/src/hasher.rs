@@ -0,0 +1,59 @@
+//! Hashing of multi-track disc images for Redump matching.
+//!
+//! Redump lists a BIN/CUE dump as one game with a ROM per track file (plus
+//! the CUE sheet itself). Hashing only the file being renamed can't match
+//! such a game, so a CUE sheet is followed to its track files and the whole
+//! set is matched with [`DatIndex::match_tracks`].
+
+use std::fs::File;
+use std::path::Path;
+
+use retro_junk_core::disc::cue;
+use retro_junk_core::{HashAlgorithms, hash_reader};
+
+use crate::error::DatError;
+use crate::matcher::{DatIndex, FileHashes, MatchResult};
+
+/// Hash every track file of the CUE sheet at `cue_path`, in sheet order.
+///
+/// Fails if the sheet lists no files or any of them can't be read.
+pub fn hash_cue_tracks(
+    cue_path: &Path,
+    algorithms: HashAlgorithms,
+) -> Result<Vec<FileHashes>, DatError> {
+    let tracks = cue::file_paths(cue_path)?;
+    if tracks.is_empty() {
+        return Err(DatError::invalid_dat(format!(
+            "{} lists no track files",
+            cue_path.display()
+        )));
+    }
+    tracks
+        .iter()
+        .map(|track| Ok(hash_reader(&mut File::open(track)?, algorithms)?))
+        .collect()
+}
+
+/// Match the disc described by the CUE sheet at `cue_path` by the hashes of
+/// all its track files.
+///
+/// The result points at the game's CUE sheet ROM when the DAT lists one, so
+/// the sheet is named after it rather than after a track.
+pub fn match_cue(index: &DatIndex, cue_path: &Path) -> Result<Option<MatchResult>, DatError> {
+    let tracks = hash_cue_tracks(cue_path, HashAlgorithms::Crc32)?;
+    Ok(index.match_tracks(&tracks).map(|mut result| {
+        let roms = &index.games[result.game_index].roms;
+        if let Some(ri) = roms.iter().position(|r| {
+            Path::new(&r.name)
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("cue"))
+        }) {
+            result.rom_index = ri;
+        }
+        result
+    }))
+}
+
+#[cfg(test)]
+#[path = "tests/hasher_tests.rs"]
+mod tests;
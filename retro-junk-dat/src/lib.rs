@@ -4,7 +4,9 @@ pub mod error;
 pub mod gdb;
 pub mod gdb_cache;
 pub mod gdb_index;
+pub mod hasher;
 pub mod matcher;
+pub mod one_g1r;
 pub(crate) mod util;
 
 pub use cache::{CacheEntry, CachedDat};
@@ -13,4 +15,6 @@ pub use error::DatError;
 pub use gdb::{GdbFile, GdbGame, GdbTags};
 pub use gdb_cache::GdbCacheEntry;
 pub use gdb_index::GdbIndex;
-pub use matcher::{DatIndex, FileHashes, MatchMethod, MatchResult, SerialLookupResult};
+pub use matcher::{
+    CanonicalSerial, DatIndex, FileHashes, MatchMethod, MatchResult, SerialLookupResult,
+};
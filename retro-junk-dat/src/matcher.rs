@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use crate::dat::{DatFile, DatGame};
 
 pub use retro_junk_core::FileHashes;
+use retro_junk_core::RomAnalyzer;
 
 /// How a match was determined.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -14,6 +15,8 @@ pub enum MatchMethod {
     Crc32,
     /// Matched by SHA1 hash (definitive)
     Sha1,
+    /// Pinned to a DAT entry by a user override
+    Override,
 }
 
 /// Result of matching a file against the DAT index.
@@ -39,6 +42,17 @@ pub enum SerialLookupResult {
     },
     /// No match found at all
     NotFound,
+    /// The serial doesn't fit its platform's serial format, so it wasn't
+    /// looked up
+    Malformed,
+}
+
+/// A serial in its platform's canonical form, with the DAT game code taken
+/// from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalSerial {
+    pub serial: String,
+    pub game_code: Option<String>,
 }
 
 /// An indexed view of a DAT file for fast lookups.
@@ -49,17 +63,24 @@ pub struct DatIndex {
     by_crc32: HashMap<String, (usize, usize)>,
     /// SHA1 (lowercase hex) → (game_index, rom_index)
     by_sha1: HashMap<String, (usize, usize)>,
-    /// Serial (uppercase, stripped of spaces/hyphens) → list of (game_index, rom_index)
+    /// Serial key (see [`serial_key`]) → list of (game_index, rom_index)
     by_serial: HashMap<String, Vec<(usize, usize)>>,
+    /// Game name → game_index (for parent/clone resolution)
+    by_name: HashMap<String, usize>,
     /// Backing store of games
     pub games: Vec<DatGame>,
 }
 
-/// Normalize a serial number for matching.
-/// Uppercases, strips spaces and hyphens. Redump DATs inconsistently use
-/// spaces (e.g., "SLPS 00700") vs dashes (e.g., "SLPS-00700"), so we
-/// strip both for reliable matching.
-fn normalize_serial(serial: &str) -> String {
+/// The key a serial is indexed and looked up under.
+///
+/// Uppercases and strips spaces and hyphens. This is deliberately looser
+/// than [`RomAnalyzer::normalize_serial`]: an index is built from DATs
+/// without knowing which analyzer will query it, and Redump DATs mix
+/// spaces (e.g., "SLPS 00700") and dashes (e.g., "SLPS-00700") that some
+/// canonical forms keep. Callers put serials in canonical form first (see
+/// [`DatIndex::match_analyzer_serial`]); this only makes both sides of the
+/// lookup agree on separators.
+fn serial_key(serial: &str) -> String {
     serial.to_uppercase().replace([' ', '-'], "")
 }
 
@@ -86,10 +107,16 @@ impl DatIndex {
         let mut by_sha1 = HashMap::new();
         let mut by_serial: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
 
+        let mut by_name = HashMap::new();
+
         for (gi, game) in dat.games.iter().enumerate() {
+            by_name.entry(game.name.clone()).or_insert(gi);
             for (ri, rom) in game.roms.iter().enumerate() {
-                by_size.entry(rom.size).or_default().push((gi, ri));
-                by_crc32.insert(rom.crc.clone(), (gi, ri));
+                // Disk entries have no size or CRC to index by
+                if !rom.is_disk() {
+                    by_size.entry(rom.size).or_default().push((gi, ri));
+                    by_crc32.insert(rom.crc.clone(), (gi, ri));
+                }
 
                 if let Some(ref sha1) = rom.sha1 {
                     by_sha1.insert(sha1.clone(), (gi, ri));
@@ -103,7 +130,7 @@ impl DatIndex {
                         let trimmed = part.trim();
                         if !trimmed.is_empty() {
                             by_serial
-                                .entry(normalize_serial(trimmed))
+                                .entry(serial_key(trimmed))
                                 .or_default()
                                 .push((gi, ri));
 
@@ -135,6 +162,7 @@ impl DatIndex {
             by_crc32,
             by_sha1,
             by_serial,
+            by_name,
             games: dat.games,
         }
     }
@@ -167,6 +195,53 @@ impl DatIndex {
         None
     }
 
+    /// Match a multi-track disc by the hashes of all its tracks.
+    ///
+    /// Every track has to match a ROM of the same game by size and CRC32.
+    /// Tracks shared by many discs (such as the audio warning track of a
+    /// GD-ROM) can't tell games apart on their own, so candidates are
+    /// narrowed track by track. The result points at the first track's ROM.
+    pub fn match_tracks(&self, tracks: &[FileHashes]) -> Option<MatchResult> {
+        let matches = |hashes: &FileHashes| -> Vec<(usize, usize)> {
+            self.by_size
+                .get(&hashes.data_size)
+                .into_iter()
+                .flatten()
+                .filter(|&&(gi, ri)| self.games[gi].roms[ri].crc == hashes.crc32)
+                .copied()
+                .collect()
+        };
+        let (first, rest) = tracks.split_first()?;
+        let mut candidates = matches(first);
+        for hashes in rest {
+            let games: std::collections::HashSet<usize> =
+                matches(hashes).into_iter().map(|(gi, _)| gi).collect();
+            candidates.retain(|(gi, _)| games.contains(gi));
+        }
+        let &(game_index, rom_index) = candidates.first()?;
+        Some(MatchResult {
+            game_index,
+            rom_index,
+            method: MatchMethod::Crc32,
+        })
+    }
+
+    /// Match a CHD against `disk` entries by the SHA1 stored in its header.
+    ///
+    /// MAME-style DATs identify CHDs by this SHA1 alone, so a match here
+    /// doesn't require hashing the image.
+    pub fn match_disk_sha1(&self, sha1: &str) -> Option<MatchResult> {
+        let &(gi, ri) = self.by_sha1.get(&sha1.to_lowercase())?;
+        if !self.games[gi].roms[ri].is_disk() {
+            return None;
+        }
+        Some(MatchResult {
+            game_index: gi,
+            rom_index: ri,
+            method: MatchMethod::Sha1,
+        })
+    }
+
     /// Match by serial number extracted from the ROM header.
     ///
     /// Handles the format gap between analyzers and DATs:
@@ -183,24 +258,76 @@ impl DatIndex {
     /// (e.g., `NSME` from `NUS-NSME-USA`), provided by the analyzer's
     /// `extract_dat_game_code()` method.
     pub fn match_by_serial(&self, serial: &str, game_code: Option<&str>) -> SerialLookupResult {
-        let norm = normalize_serial(serial);
+        self.match_by_serial_where(serial, game_code, |_| true)
+    }
+
+    /// Match a serial from `analyzer`'s platform, after putting it in the
+    /// analyzer's canonical form. The analyzer's DAT game code for it is
+    /// tried too. Serials that fail the analyzer's format check are
+    /// [`Malformed`](SerialLookupResult::Malformed).
+    pub fn match_analyzer_serial(
+        &self,
+        analyzer: &dyn RomAnalyzer,
+        serial: &str,
+    ) -> SerialLookupResult {
+        self.match_analyzer_serial_where(analyzer, serial, |_| true)
+            .1
+    }
+
+    /// Like [`match_analyzer_serial`](Self::match_analyzer_serial), but only
+    /// considers the games `keep` accepts. Also returns the canonical serial
+    /// and game code that were looked up.
+    pub fn match_analyzer_serial_where(
+        &self,
+        analyzer: &dyn RomAnalyzer,
+        serial: &str,
+        keep: impl Fn(&DatGame) -> bool,
+    ) -> (CanonicalSerial, SerialLookupResult) {
+        let serial = analyzer.normalize_serial(serial);
+        if !analyzer.is_valid_serial(&serial) {
+            let canonical = CanonicalSerial {
+                serial,
+                game_code: None,
+            };
+            return (canonical, SerialLookupResult::Malformed);
+        }
+        let game_code = analyzer.extract_dat_game_code(&serial);
+        let result = self.match_by_serial_where(&serial, game_code.as_deref(), keep);
+        (CanonicalSerial { serial, game_code }, result)
+    }
+
+    /// Like [`match_by_serial`](Self::match_by_serial), but only considers
+    /// the games `keep` accepts, such as the updates in a DAT that lists
+    /// them alongside their base games under one serial.
+    pub fn match_by_serial_where(
+        &self,
+        serial: &str,
+        game_code: Option<&str>,
+        keep: impl Fn(&DatGame) -> bool,
+    ) -> SerialLookupResult {
+        let lookup = |key: &str| -> Vec<(usize, usize)> {
+            self.by_serial
+                .get(key)
+                .into_iter()
+                .flatten()
+                .filter(|&&(gi, _)| keep(&self.games[gi]))
+                .copied()
+                .collect()
+        };
+        let norm = serial_key(serial);
 
         // Try exact match first
-        if let Some(entries) = self.by_serial.get(&norm) {
-            let result = self.resolve_serial_entries(entries, &norm);
-            if !matches!(result, SerialLookupResult::NotFound) {
-                return result;
-            }
+        let result = self.resolve_serial_entries(&lookup(&norm), &norm, &lookup);
+        if !matches!(result, SerialLookupResult::NotFound) {
+            return result;
         }
 
         // Try with the pre-extracted game code
         if let Some(code) = game_code {
-            let norm_code = normalize_serial(code);
-            if let Some(entries) = self.by_serial.get(&norm_code) {
-                let result = self.resolve_serial_entries(entries, &norm_code);
-                if !matches!(result, SerialLookupResult::NotFound) {
-                    return result;
-                }
+            let norm_code = serial_key(code);
+            let result = self.resolve_serial_entries(&lookup(&norm_code), &norm_code, &lookup);
+            if !matches!(result, SerialLookupResult::NotFound) {
+                return result;
             }
         }
 
@@ -209,11 +336,9 @@ impl DatIndex {
         // in the DAT but does appear with a suffix.
         for suffix in b'0'..=b'9' {
             let suffixed = format!("{norm}{}", suffix as char);
-            if let Some(entries) = self.by_serial.get(&suffixed) {
-                let result = self.resolve_serial_entries(entries, &suffixed);
-                if !matches!(result, SerialLookupResult::NotFound) {
-                    return result;
-                }
+            let result = self.resolve_serial_entries(&lookup(&suffixed), &suffixed, &lookup);
+            if !matches!(result, SerialLookupResult::NotFound) {
+                return result;
             }
         }
 
@@ -226,15 +351,24 @@ impl DatIndex {
     /// - Multiple entries but a `-0` suffix resolves uniquely → use that
     ///   (preserves multi-disc behavior where bare serial is shared)
     /// - Multiple entries with no suffix resolution → Ambiguous
-    fn resolve_serial_entries(&self, entries: &[(usize, usize)], norm: &str) -> SerialLookupResult {
+    ///
+    /// `lookup` gives the entries indexed under a serial.
+    fn resolve_serial_entries(
+        &self,
+        entries: &[(usize, usize)],
+        norm: &str,
+        lookup: &dyn Fn(&str) -> Vec<(usize, usize)>,
+    ) -> SerialLookupResult {
+        if entries.is_empty() {
+            return SerialLookupResult::NotFound;
+        }
         if entries.len() == 1 {
             let (gi, ri) = entries[0];
             // Check if a "-0" suffixed entry exists — if so, the bare serial
             // is from a multi-disc set and we should use the specific entry.
             let suffixed = format!("{norm}0");
-            if let Some(suffixed_entries) = self.by_serial.get(&suffixed)
-                && suffixed_entries.len() == 1
-            {
+            let suffixed_entries = lookup(&suffixed);
+            if suffixed_entries.len() == 1 {
                 let (sgi, sri) = suffixed_entries[0];
                 return SerialLookupResult::Match(MatchResult {
                     game_index: sgi,
@@ -251,9 +385,8 @@ impl DatIndex {
 
         // Multiple entries — try "-0" suffix to disambiguate multi-disc sets
         let suffixed = format!("{norm}0");
-        if let Some(suffixed_entries) = self.by_serial.get(&suffixed)
-            && suffixed_entries.len() == 1
-        {
+        let suffixed_entries = lookup(&suffixed);
+        if suffixed_entries.len() == 1 {
             let (sgi, sri) = suffixed_entries[0];
             return SerialLookupResult::Match(MatchResult {
                 game_index: sgi,
@@ -286,11 +419,39 @@ impl DatIndex {
         }
     }
 
+    /// Look up a game by its exact DAT name.
+    pub fn game_by_name(&self, name: &str) -> Option<usize> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Look up a ROM by CRC32 alone, without checking the file size.
+    pub fn rom_by_crc32(&self, crc32: &str) -> Option<(usize, usize)> {
+        self.by_crc32.get(&crc32.to_lowercase()).copied()
+    }
+
     /// Number of games in the index.
     pub fn game_count(&self) -> usize {
         self.games.len()
     }
 
+    /// Parent set of a clone game, if the DAT carries parent/clone data.
+    ///
+    /// Returns `None` for parents, for games without a `cloneof` entry, and
+    /// when the named parent isn't in the index.
+    pub fn parent_of(&self, game_index: usize) -> Option<&DatGame> {
+        let parent_name = self.games.get(game_index)?.clone_of.as_deref()?;
+        self.by_name
+            .get(parent_name)
+            .and_then(|&gi| self.games.get(gi))
+    }
+
+    /// File name of the parent set's first ROM for the clone named
+    /// `game_name`, for lookups that fall back to the parent's entry.
+    pub fn parent_rom_name(&self, game_name: &str) -> Option<&str> {
+        let parent = self.parent_of(self.game_by_name(game_name)?)?;
+        parent.roms.first().map(|rom| rom.name.as_str())
+    }
+
     /// Get entries matching a given file size (for pre-filtering before hashing).
     pub fn candidates_by_size(&self, size: u64) -> Option<&[(usize, usize)]> {
         self.by_size.get(&size).map(|v| v.as_slice())
@@ -0,0 +1,237 @@
+//! One game, one ROM (1G1R) selection.
+//!
+//! A DAT lists every release of a game: each region, revision, demo and
+//! prototype. 1G1R keeps one of them. Names are grouped by game, using the
+//! DAT's parent/clone data when it has it (so `Rockman (Japan)` joins
+//! `Mega Man (USA)`) and the title otherwise, and each group is ordered by
+//! [`Preferences`]:
+//!
+//! 1. Retail releases before prototypes, demos, hacks and bad dumps
+//! 2. Preferred regions, in order
+//! 3. Preferred languages, in order
+//! 4. The latest revision (or the original, if configured)
+//! 5. The DAT's parent before its clones
+//!
+//! Discs of a multi-disc game form separate groups, so every disc is kept.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use retro_junk_catalog::name_parser::{
+    DEFAULT_REGION_PRIORITY, DumpStatus, ParsedDatName, parse_dat_name, region_slug_to_display,
+};
+use retro_junk_catalog::title_key;
+
+use crate::matcher::DatIndex;
+
+/// Flags that mark a release as something other than the retail game.
+const NON_RETAIL_FLAGS: &[&str] = &[
+    "proto",
+    "prototype",
+    "beta",
+    "demo",
+    "sample",
+    "kiosk",
+    "debug",
+    "promo",
+    "pirate",
+];
+
+/// Guard against `cloneof` cycles in malformed DATs.
+const MAX_PARENT_DEPTH: usize = 16;
+
+/// How to rank the releases of a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preferences {
+    /// No-Intro region names, most preferred first (e.g. "USA", "Europe").
+    pub regions: Vec<String>,
+    /// Language codes, most preferred first (e.g. "En", "Ja").
+    pub languages: Vec<String>,
+    /// Prefer the highest revision; otherwise prefer the original release.
+    pub latest_revision: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            regions: DEFAULT_REGION_PRIORITY
+                .iter()
+                .map(|slug| region_slug_to_display(slug))
+                .collect(),
+            languages: ["En", "Ja"].map(String::from).to_vec(),
+            latest_revision: true,
+        }
+    }
+}
+
+/// The releases of one game, best first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    /// Normalized title the group was formed on.
+    pub key: String,
+    /// Indices into the names passed to [`select`], preferred first.
+    pub members: Vec<usize>,
+}
+
+impl Group {
+    /// Index of the release to keep.
+    pub fn preferred(&self) -> usize {
+        self.members[0]
+    }
+
+    /// Indices of the releases 1G1R drops.
+    pub fn duplicates(&self) -> &[usize] {
+        &self.members[1..]
+    }
+}
+
+/// Group `names` by game and order each group by `prefs`.
+///
+/// Names are DAT game names (or file names following the No-Intro
+/// convention). With an `index`, names found in it are grouped under their
+/// top-level parent. Groups come back sorted by key.
+pub fn select(names: &[&str], index: Option<&DatIndex>, prefs: &Preferences) -> Vec<Group> {
+    let mut groups: BTreeMap<String, Vec<(usize, Rank)>> = BTreeMap::new();
+    for (i, name) in names.iter().enumerate() {
+        let parsed = parse_dat_name(name);
+        let (root, is_parent) = match index {
+            Some(index) => resolve_parent(index, name),
+            None => (None, false),
+        };
+        let root_parsed = root.as_deref().map(parse_dat_name);
+        let key = group_key(root_parsed.as_ref().unwrap_or(&parsed));
+        groups
+            .entry(key)
+            .or_default()
+            .push((i, Rank::new(&parsed, is_parent, prefs)));
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, mut members)| {
+            members.sort_by(|(a_index, a), (b_index, b)| {
+                a.cmp(b).then_with(|| names[*a_index].cmp(names[*b_index]))
+            });
+            Group {
+                key,
+                members: members.into_iter().map(|(i, _)| i).collect(),
+            }
+        })
+        .collect()
+}
+
+/// The top-level parent of `name` in `index` (if it's a clone), and whether
+/// `name` is itself a parent.
+fn resolve_parent(index: &DatIndex, name: &str) -> (Option<String>, bool) {
+    let Some(mut game_index) = index.game_by_name(name) else {
+        return (None, false);
+    };
+    let is_parent = index.games[game_index].clone_of.is_none();
+    let mut root = None;
+    for _ in 0..MAX_PARENT_DEPTH {
+        let Some(parent) = index.parent_of(game_index) else {
+            break;
+        };
+        root = Some(parent.name.clone());
+        match index.game_by_name(&parent.name) {
+            Some(next) => game_index = next,
+            None => break,
+        }
+    }
+    (root, is_parent)
+}
+
+fn group_key(parsed: &ParsedDatName) -> String {
+    let key = title_key(&parsed.title, parsed.regions.first().map(String::as_str));
+    match parsed.disc_number {
+        Some(n) => format!("{key} (disc {n})"),
+        None => key,
+    }
+}
+
+/// Sort key for one release; smaller is better.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Rank {
+    non_retail: usize,
+    region: usize,
+    language: usize,
+    revision: RevisionOrder,
+    clone: bool,
+}
+
+impl Rank {
+    fn new(parsed: &ParsedDatName, is_parent: bool, prefs: &Preferences) -> Self {
+        let non_retail = parsed
+            .flags
+            .iter()
+            .filter(|f| NON_RETAIL_FLAGS.contains(&f.to_lowercase().as_str()))
+            .count()
+            + parsed.modifications.len()
+            + usize::from(parsed.translation.is_some())
+            + usize::from(parsed.status != DumpStatus::Verified);
+        let revision = (
+            revision_key(
+                parsed
+                    .revision
+                    .as_deref()
+                    .map(|r| r.trim_start_matches("Rev ")),
+            ),
+            revision_key(
+                parsed
+                    .version
+                    .as_deref()
+                    .map(|v| v.trim_start_matches(['v', 'V'])),
+            ),
+        );
+        Self {
+            non_retail,
+            region: best_position(&parsed.regions, &prefs.regions),
+            language: best_position(&parsed.languages, &prefs.languages),
+            revision: if prefs.latest_revision {
+                RevisionOrder::Latest(Reverse(revision))
+            } else {
+                RevisionOrder::Original(revision)
+            },
+            clone: !is_parent,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum RevisionOrder {
+    Latest(Reverse<(Vec<u32>, Vec<u32>)>),
+    Original((Vec<u32>, Vec<u32>)),
+}
+
+/// Position of the first preferred value among `values`, or past the end
+/// when none is preferred.
+fn best_position(values: &[String], preferred: &[String]) -> usize {
+    values
+        .iter()
+        .filter_map(|v| preferred.iter().position(|p| p.eq_ignore_ascii_case(v)))
+        .min()
+        .unwrap_or(preferred.len())
+}
+
+/// Comparable form of a revision like "1", "1.1" or "A": dot-separated
+/// numbers, with letters counted from 1. No revision sorts first.
+fn revision_key(revision: Option<&str>) -> Vec<u32> {
+    let Some(revision) = revision else {
+        return Vec::new();
+    };
+    revision
+        .split('.')
+        .map(|part| match part.trim().parse::<u32>() {
+            Ok(n) => n,
+            Err(_) => part
+                .chars()
+                .next()
+                .filter(char::is_ascii_alphabetic)
+                .map_or(0, |c| c.to_ascii_uppercase() as u32 - 'A' as u32 + 1),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "tests/one_g1r_tests.rs"]
+mod tests;
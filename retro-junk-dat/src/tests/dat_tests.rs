@@ -56,6 +56,23 @@ fn test_parse_xml_with_serial() {
     assert_eq!(dat.games[0].roms[0].serial.as_deref(), Some("SLUS-00001"));
 }
 
+#[test]
+fn test_parse_xml_cloneof() {
+    let xml = r#"<?xml version="1.0"?>
+<datafile>
+    <header><name>Test</name><version>1</version></header>
+    <game name="sf2">
+        <rom name="sf2.zip" size="1024" crc="deadbeef"/>
+    </game>
+    <game name="sf2ua" cloneof="sf2">
+        <rom name="sf2ua.zip" size="1024" crc="cafebabe"/>
+    </game>
+</datafile>"#;
+    let dat = parse_dat(xml.as_bytes()).unwrap();
+    assert_eq!(dat.games[0].clone_of, None);
+    assert_eq!(dat.games[1].clone_of.as_deref(), Some("sf2"));
+}
+
 // -- ClrMamePro tests --
 
 const SAMPLE_CLR_DAT: &str = r#"clrmamepro (
@@ -232,3 +249,35 @@ fn test_xml_game_level_serial_propagation() {
     assert_eq!(ff7.roms[0].serial.as_deref(), Some("SCUS-94163"));
     assert_eq!(ff7.roms[1].serial.as_deref(), Some("SCUS-94163"));
 }
+
+#[test]
+fn test_parse_disk_entries() {
+    let xml = r#"<?xml version="1.0"?>
+<datafile>
+    <header><name>Sony - PlayStation (CHD)</name></header>
+    <game name="Ridge Racer (USA)">
+        <disk name="Ridge Racer (USA)" sha1="0123456789ABCDEF0123456789ABCDEF01234567"/>
+    </game>
+</datafile>"#;
+    let dat = parse_dat(xml.as_bytes()).unwrap();
+    let disk = &dat.games[0].roms[0];
+    assert_eq!(disk.name, "Ridge Racer (USA)");
+    assert_eq!(
+        disk.sha1.as_deref(),
+        Some("0123456789abcdef0123456789abcdef01234567")
+    );
+    assert!(disk.is_disk());
+
+    let clr = r#"clrmamepro (
+	name "Sony - PlayStation (CHD)"
+)
+
+game (
+	name "Ridge Racer (USA)"
+	disk ( name "Ridge Racer (USA)" sha1 0123456789abcdef0123456789abcdef01234567 )
+)
+"#;
+    let dat = parse_dat(clr.as_bytes()).unwrap();
+    assert!(dat.games[0].roms[0].is_disk());
+    assert!(!parse_dat(SAMPLE_XML_DAT.as_bytes()).unwrap().games[0].roms[0].is_disk());
+}
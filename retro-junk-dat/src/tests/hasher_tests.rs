@@ -0,0 +1,60 @@
+use super::*;
+use crate::dat::{DatFile, DatGame, DatRom};
+
+fn rom(name: &str, data: &[u8]) -> DatRom {
+    DatRom {
+        name: name.into(),
+        size: data.len() as u64,
+        crc: hash_reader(&mut &data[..], HashAlgorithms::Crc32)
+            .unwrap()
+            .crc32,
+        sha1: None,
+        md5: None,
+        serial: None,
+    }
+}
+
+#[test]
+fn test_match_cue_matches_every_track() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.bin"), b"data track").unwrap();
+    std::fs::write(dir.path().join("b.bin"), b"audio track").unwrap();
+    let cue_path = dir.path().join("game.cue");
+    std::fs::write(
+        &cue_path,
+        "FILE \"a.bin\" BINARY\n  TRACK 01 MODE2/2352\nFILE \"b.bin\" BINARY\n  TRACK 02 AUDIO\n",
+    )
+    .unwrap();
+
+    let index = DatIndex::from_dat(DatFile {
+        name: "Test".into(),
+        description: "Test".into(),
+        version: "1".into(),
+        games: vec![
+            DatGame {
+                name: "Other (USA)".into(),
+                region: None,
+                clone_of: None,
+                roms: vec![rom("Other (USA).bin", b"data track")],
+            },
+            DatGame {
+                name: "Game (USA)".into(),
+                region: None,
+                clone_of: None,
+                roms: vec![
+                    rom("Game (USA).cue", b"sheet"),
+                    rom("Game (USA) (Track 1).bin", b"data track"),
+                    rom("Game (USA) (Track 2).bin", b"audio track"),
+                ],
+            },
+        ],
+    });
+
+    let result = match_cue(&index, &cue_path).unwrap().unwrap();
+    assert_eq!(index.games[result.game_index].name, "Game (USA)");
+    assert_eq!(result.rom_index, 0);
+
+    // A missing track can't be hashed
+    std::fs::remove_file(dir.path().join("b.bin")).unwrap();
+    assert!(match_cue(&index, &cue_path).is_err());
+}
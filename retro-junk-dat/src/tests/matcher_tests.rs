@@ -9,6 +9,7 @@ fn expect_match(result: SerialLookupResult) -> MatchResult {
             panic!("Expected Match, got Ambiguous with: {:?}", candidates)
         }
         SerialLookupResult::NotFound => panic!("Expected Match, got NotFound"),
+        SerialLookupResult::Malformed => panic!("Expected Match, got Malformed"),
     }
 }
 
@@ -21,6 +22,7 @@ fn make_test_dat() -> DatFile {
             DatGame {
                 name: "Super Mario World (USA)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Super Mario World (USA).sfc".into(),
                     size: 524288,
@@ -33,6 +35,7 @@ fn make_test_dat() -> DatFile {
             DatGame {
                 name: "Super Mario 64 (USA)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Super Mario 64 (USA).z64".into(),
                     size: 8388608,
@@ -46,6 +49,7 @@ fn make_test_dat() -> DatFile {
             DatGame {
                 name: "Super Mario 64 (Japan)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Super Mario 64 (Japan).z64".into(),
                     size: 8388608,
@@ -58,6 +62,7 @@ fn make_test_dat() -> DatFile {
             DatGame {
                 name: "The Legend of Zelda - A Link to the Past (USA)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "The Legend of Zelda - A Link to the Past (USA).sfc".into(),
                     size: 1048576,
@@ -78,6 +83,7 @@ fn test_match_by_crc32() {
         crc32: "b19ed489".into(),
         sha1: None,
         md5: None,
+        sha256: None,
         data_size: 524288,
     };
     let result = index.match_by_hash(524288, &hashes).unwrap();
@@ -136,6 +142,7 @@ fn test_hash_distinguishes_regions() {
         crc32: "635a2bff".into(),
         sha1: None,
         md5: None,
+        sha256: None,
         data_size: 8388608,
     };
     let usa = index.match_by_hash(8388608, &usa_hashes).unwrap();
@@ -145,6 +152,7 @@ fn test_hash_distinguishes_regions() {
         crc32: "4eab3152".into(),
         sha1: None,
         md5: None,
+        sha256: None,
         data_size: 8388608,
     };
     let jpn = index.match_by_hash(8388608, &jpn_hashes).unwrap();
@@ -158,6 +166,7 @@ fn test_no_match() {
         crc32: "00000000".into(),
         sha1: None,
         md5: None,
+        sha256: None,
         data_size: 999,
     };
     assert!(index.match_by_hash(999, &hashes).is_none());
@@ -176,6 +185,7 @@ fn test_from_dats_merge() {
         games: vec![DatGame {
             name: "Game A (USA)".into(),
             region: None,
+            clone_of: None,
             roms: vec![DatRom {
                 name: "Game A (USA).bin".into(),
                 size: 1024,
@@ -193,6 +203,7 @@ fn test_from_dats_merge() {
         games: vec![DatGame {
             name: "Game B (USA)".into(),
             region: None,
+            clone_of: None,
             roms: vec![DatRom {
                 name: "Game B (USA).bin".into(),
                 size: 2048,
@@ -220,6 +231,7 @@ fn test_from_dats_merge() {
         crc32: "bbbb0002".into(),
         sha1: None,
         md5: None,
+        sha256: None,
         data_size: 2048,
     };
     let hash_result = index.match_by_hash(2048, &hashes).unwrap();
@@ -234,6 +246,7 @@ fn test_crc32_requires_matching_size() {
         crc32: "b19ed489".into(),
         sha1: None,
         md5: None,
+        sha256: None,
         data_size: 524288,
     };
     assert!(index.match_by_hash(999, &hashes).is_none());
@@ -249,6 +262,7 @@ fn test_comma_separated_serials() {
         games: vec![DatGame {
             name: "Chrono Cross (USA) (Disc 1)".into(),
             region: None,
+            clone_of: None,
             roms: vec![DatRom {
                 name: "Chrono Cross (USA) (Disc 1).bin".into(),
                 size: 736651104,
@@ -287,6 +301,7 @@ fn test_serial_space_dash_normalization() {
         games: vec![DatGame {
             name: "Some Game (Japan)".into(),
             region: None,
+            clone_of: None,
             roms: vec![DatRom {
                 name: "Some Game (Japan).bin".into(),
                 size: 1024,
@@ -318,6 +333,7 @@ fn test_multi_disc_suffix_prefers_suffixed_over_bare() {
             DatGame {
                 name: "FF7 (USA) (Disc 1)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "FF7 (USA) (Disc 1).bin".into(),
                     size: 747435024,
@@ -330,6 +346,7 @@ fn test_multi_disc_suffix_prefers_suffixed_over_bare() {
             DatGame {
                 name: "FF7 (USA) (Disc 1) [suffixed]".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "FF7 (USA) (Disc 1).bin".into(),
                     size: 747435024,
@@ -342,6 +359,7 @@ fn test_multi_disc_suffix_prefers_suffixed_over_bare() {
             DatGame {
                 name: "FF7 (USA) (Disc 2)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "FF7 (USA) (Disc 2).bin".into(),
                     size: 732657408,
@@ -354,6 +372,7 @@ fn test_multi_disc_suffix_prefers_suffixed_over_bare() {
             DatGame {
                 name: "FF7 (USA) (Disc 2) [suffixed]".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "FF7 (USA) (Disc 2).bin".into(),
                     size: 732657408,
@@ -366,6 +385,7 @@ fn test_multi_disc_suffix_prefers_suffixed_over_bare() {
             DatGame {
                 name: "FF7 (USA) (Disc 3)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "FF7 (USA) (Disc 3).bin".into(),
                     size: 659561952,
@@ -378,6 +398,7 @@ fn test_multi_disc_suffix_prefers_suffixed_over_bare() {
             DatGame {
                 name: "FF7 (USA) (Disc 3) [suffixed]".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "FF7 (USA) (Disc 3).bin".into(),
                     size: 659561952,
@@ -416,6 +437,7 @@ fn test_suffix_fallback_when_no_exact_match() {
         games: vec![DatGame {
             name: "Some Game (USA) (Disc 1)".into(),
             region: None,
+            clone_of: None,
             roms: vec![DatRom {
                 name: "Some Game (USA) (Disc 1).bin".into(),
                 size: 700000000,
@@ -447,6 +469,7 @@ fn test_normal_game_unaffected_by_suffix_logic() {
         games: vec![DatGame {
             name: "Crash Bandicoot (USA)".into(),
             region: None,
+            clone_of: None,
             roms: vec![DatRom {
                 name: "Crash Bandicoot (USA).bin".into(),
                 size: 500000000,
@@ -476,6 +499,7 @@ fn test_ambiguous_serial_returns_ambiguous() {
             DatGame {
                 name: "Pokemon FireRed (USA)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Pokemon FireRed (USA).gba".into(),
                     size: 16777216,
@@ -488,6 +512,7 @@ fn test_ambiguous_serial_returns_ambiguous() {
             DatGame {
                 name: "Pokemon FireRed (USA) (Rev 1)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Pokemon FireRed (USA) (Rev 1).gba".into(),
                     size: 16777216,
@@ -522,6 +547,7 @@ fn test_ambiguous_via_game_code() {
             DatGame {
                 name: "Game Original (USA)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Game Original (USA).z64".into(),
                     size: 8388608,
@@ -534,6 +560,7 @@ fn test_ambiguous_via_game_code() {
             DatGame {
                 name: "Game Original (USA) (Rev 1)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Game Original (USA) (Rev 1).z64".into(),
                     size: 8388608,
@@ -570,6 +597,7 @@ fn test_multi_disc_shared_bare_serial_resolves_via_suffix() {
             DatGame {
                 name: "Multi Disc Game (USA) (Disc 1)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Multi Disc Game (USA) (Disc 1).bin".into(),
                     size: 700000000,
@@ -582,6 +610,7 @@ fn test_multi_disc_shared_bare_serial_resolves_via_suffix() {
             DatGame {
                 name: "Multi Disc Game (USA) (Disc 2)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Multi Disc Game (USA) (Disc 2).bin".into(),
                     size: 700000000,
@@ -594,6 +623,7 @@ fn test_multi_disc_shared_bare_serial_resolves_via_suffix() {
             DatGame {
                 name: "Multi Disc Game (USA) (Disc 1) [suffixed]".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Multi Disc Game (USA) (Disc 1).bin".into(),
                     size: 700000000,
@@ -630,6 +660,7 @@ fn test_same_name_entries_resolve_as_match() {
             DatGame {
                 name: "Metroid Fusion (USA)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Metroid Fusion (USA).gba".into(),
                     size: 8388608,
@@ -642,6 +673,7 @@ fn test_same_name_entries_resolve_as_match() {
             DatGame {
                 name: "Metroid Fusion (USA)".into(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Metroid Fusion (USA).gba".into(),
                     size: 8388608,
@@ -671,6 +703,7 @@ fn test_match_short_game_code_to_long_dat_serial() {
         games: vec![DatGame {
             name: "The Legend of Zelda - The Wind Waker (USA)".into(),
             region: None,
+            clone_of: None,
             roms: vec![DatRom {
                 name: "The Legend of Zelda - The Wind Waker (USA).iso".into(),
                 size: 1459978240,
@@ -690,3 +723,270 @@ fn test_match_short_game_code_to_long_dat_serial() {
         "The Legend of Zelda - The Wind Waker (USA)"
     );
 }
+
+#[test]
+fn test_parent_of_resolves_clone() {
+    let rom = |name: &str, crc: &str| DatRom {
+        name: name.into(),
+        size: 1024,
+        crc: crc.into(),
+        sha1: None,
+        md5: None,
+        serial: None,
+    };
+    let index = DatIndex::from_dat(DatFile {
+        name: "Test".into(),
+        description: "Test".into(),
+        version: "1".into(),
+        games: vec![
+            DatGame {
+                name: "sf2".into(),
+                region: None,
+                clone_of: None,
+                roms: vec![rom("sf2.zip", "00000001")],
+            },
+            DatGame {
+                name: "sf2ua".into(),
+                region: None,
+                clone_of: Some("sf2".into()),
+                roms: vec![rom("sf2ua.zip", "00000002")],
+            },
+            DatGame {
+                name: "orphan".into(),
+                region: None,
+                clone_of: Some("missing".into()),
+                roms: vec![rom("orphan.zip", "00000003")],
+            },
+        ],
+    });
+
+    assert_eq!(index.parent_of(1).map(|g| g.name.as_str()), Some("sf2"));
+    assert!(index.parent_of(0).is_none());
+    assert!(index.parent_of(2).is_none());
+    assert!(index.parent_of(99).is_none());
+
+    assert_eq!(index.parent_rom_name("sf2ua"), Some("sf2.zip"));
+    assert_eq!(index.parent_rom_name("sf2"), None);
+    assert_eq!(index.parent_rom_name("orphan"), None);
+}
+
+#[test]
+fn test_direct_lookups_for_overrides() {
+    let index = DatIndex::from_dat(make_test_dat());
+    assert_eq!(index.game_by_name("Super Mario 64 (Japan)"), Some(2));
+    assert_eq!(index.game_by_name("Super Mario 64"), None);
+    // CRC lookup ignores size and accepts uppercase input
+    assert_eq!(index.rom_by_crc32("B19ED489"), Some((0, 0)));
+    assert_eq!(index.rom_by_crc32("00000000"), None);
+}
+
+#[test]
+fn test_disk_entries_match_by_sha1_only() {
+    let mut dat = make_test_dat();
+    dat.games.push(DatGame {
+        name: "Ridge Racer (USA)".into(),
+        region: None,
+        clone_of: None,
+        roms: vec![DatRom {
+            name: "Ridge Racer (USA)".into(),
+            size: 0,
+            crc: String::new(),
+            sha1: Some("0123456789abcdef0123456789abcdef01234567".into()),
+            md5: None,
+            serial: None,
+        }],
+    });
+    let index = DatIndex::from_dat(dat);
+
+    let m = index
+        .match_disk_sha1("0123456789ABCDEF0123456789ABCDEF01234567")
+        .unwrap();
+    assert_eq!(index.games[m.game_index].name, "Ridge Racer (USA)");
+    assert_eq!(m.method, MatchMethod::Sha1);
+
+    // Regular ROM SHA1s aren't disk matches, and disks aren't indexed by size
+    assert!(
+        index
+            .match_disk_sha1("6b47bb75d16514b6a476aa0c73a683a2a4c18765")
+            .is_none()
+    );
+    assert!(index.candidates_by_size(0).is_none());
+    assert!(index.rom_by_crc32("").is_none());
+}
+
+#[test]
+fn test_match_tracks_requires_one_game() {
+    let track = |name: &str, size: u64, crc: &str| DatRom {
+        name: name.into(),
+        size,
+        crc: crc.into(),
+        sha1: None,
+        md5: None,
+        serial: None,
+    };
+    let game = |name: &str, roms: Vec<DatRom>| DatGame {
+        name: name.into(),
+        region: None,
+        clone_of: None,
+        roms,
+    };
+    // Both discs share the same audio warning track
+    let index = DatIndex::from_dat(DatFile {
+        name: "Test".into(),
+        description: "Test".into(),
+        version: "1".into(),
+        games: vec![
+            game(
+                "Crazy Taxi (USA)",
+                vec![
+                    track("Crazy Taxi (USA) (Track 1).bin", 1000, "11111111"),
+                    track("Crazy Taxi (USA) (Track 2).raw", 2000, "22222222"),
+                    track("Crazy Taxi (USA) (Track 3).bin", 3000, "33333333"),
+                ],
+            ),
+            game(
+                "Shenmue (USA)",
+                vec![
+                    track("Shenmue (USA) (Track 1).bin", 1000, "44444444"),
+                    track("Shenmue (USA) (Track 2).raw", 2000, "22222222"),
+                    track("Shenmue (USA) (Track 3).bin", 3000, "55555555"),
+                ],
+            ),
+        ],
+    });
+    let hashes = |size: u64, crc: &str| FileHashes {
+        crc32: crc.into(),
+        sha1: None,
+        md5: None,
+        sha256: None,
+        data_size: size,
+    };
+
+    let m = index
+        .match_tracks(&[
+            hashes(1000, "44444444"),
+            hashes(2000, "22222222"),
+            hashes(3000, "55555555"),
+        ])
+        .unwrap();
+    assert_eq!(index.games[m.game_index].name, "Shenmue (USA)");
+    assert_eq!(m.rom_index, 0);
+
+    // Tracks from two different discs don't make a match
+    assert!(
+        index
+            .match_tracks(&[hashes(1000, "11111111"), hashes(3000, "55555555")])
+            .is_none()
+    );
+    assert!(index.match_tracks(&[]).is_none());
+}
+
+#[test]
+fn test_match_by_serial_where_filters_games() {
+    let rom = |name: &str| DatRom {
+        name: format!("{name}.cia"),
+        size: 1024,
+        crc: format!("{:08x}", name.len()),
+        sha1: None,
+        md5: None,
+        serial: Some("CTR-P-AREE".into()),
+    };
+    let game = |name: &str| DatGame {
+        name: name.into(),
+        region: None,
+        clone_of: None,
+        roms: vec![rom(name)],
+    };
+    let index = DatIndex::from_dat(DatFile {
+        name: "Test".into(),
+        description: "Test".into(),
+        version: "1".into(),
+        games: vec![
+            game("Pilotwings Resort (USA)"),
+            game("Pilotwings Resort (USA) (Update)"),
+        ],
+    });
+
+    assert!(matches!(
+        index.match_by_serial("CTR-P-AREE", None),
+        SerialLookupResult::Ambiguous { .. }
+    ));
+    let update = expect_match(
+        index.match_by_serial_where("CTR-P-AREE", None, |g| g.name.ends_with("(Update)")),
+    );
+    assert_eq!(update.game_index, 1);
+    assert!(matches!(
+        index.match_by_serial_where("CTR-P-AREE", None, |_| false),
+        SerialLookupResult::NotFound
+    ));
+}
+
+/// An N64-like analyzer: serials hyphenated, game code the second part.
+struct HyphenatedSerials;
+
+impl retro_junk_core::RomAnalyzer for HyphenatedSerials {
+    fn analyze(
+        &self,
+        _reader: &mut dyn retro_junk_core::ReadSeek,
+        _options: &retro_junk_core::AnalysisOptions,
+    ) -> Result<retro_junk_core::RomIdentification, retro_junk_core::AnalysisError> {
+        Err(retro_junk_core::AnalysisError::invalid_format(
+            "test analyzer",
+        ))
+    }
+
+    fn platform(&self) -> retro_junk_core::Platform {
+        retro_junk_core::Platform::N64
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn can_handle(&self, _reader: &mut dyn retro_junk_core::ReadSeek) -> bool {
+        false
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        retro_junk_core::serial::normalize_hyphenated(serial)
+    }
+
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(r"^NUS-[A-Z0-9]{4}-[A-Z]{3}$")
+    }
+
+    fn extract_dat_game_code(&self, serial: &str) -> Option<String> {
+        serial.split('-').nth(1).map(str::to_string)
+    }
+}
+
+#[test]
+fn test_match_analyzer_serial_normalizes_first() {
+    let index = DatIndex::from_dat(make_test_dat());
+    // "nus_nsme_usa" only matches once normalized to NUS-NSME-USA → NSME
+    assert!(matches!(
+        index.match_by_serial("nus_nsme_usa", None),
+        SerialLookupResult::NotFound
+    ));
+    let result = expect_match(index.match_analyzer_serial(&HyphenatedSerials, "nus_nsme_usa"));
+    assert_eq!(index.games[result.game_index].name, "Super Mario 64 (USA)");
+}
+
+#[test]
+fn test_match_analyzer_serial_rejects_malformed() {
+    let index = DatIndex::from_dat(make_test_dat());
+    assert!(matches!(
+        index.match_analyzer_serial(&HyphenatedSerials, "NSME"),
+        SerialLookupResult::Malformed
+    ));
+    let (canonical, result) =
+        index.match_analyzer_serial_where(&HyphenatedSerials, "nus nsme usa", |_| true);
+    assert!(matches!(result, SerialLookupResult::Match(_)));
+    assert_eq!(
+        canonical,
+        CanonicalSerial {
+            serial: "NUS-NSME-USA".into(),
+            game_code: Some("NSME".into()),
+        }
+    );
+}
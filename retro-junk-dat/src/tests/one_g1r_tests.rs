@@ -0,0 +1,149 @@
+use super::*;
+use crate::dat::{DatFile, DatGame};
+
+fn preferred<'a>(names: &[&'a str], prefs: &Preferences) -> Vec<&'a str> {
+    select(names, None, prefs)
+        .iter()
+        .map(|g| names[g.preferred()])
+        .collect()
+}
+
+fn game(name: &str, clone_of: Option<&str>) -> DatGame {
+    DatGame {
+        name: name.into(),
+        region: None,
+        clone_of: clone_of.map(Into::into),
+        roms: Vec::new(),
+    }
+}
+
+#[test]
+fn test_prefers_regions_in_order() {
+    let names = [
+        "Tetris (Japan)",
+        "Tetris (Europe)",
+        "Tetris (USA)",
+        "Dr. Mario (Japan, USA)",
+    ];
+    let groups = select(&names, None, &Preferences::default());
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].key, "dr mario");
+    assert_eq!(groups[1].key, "tetris");
+    assert_eq!(names[groups[1].preferred()], "Tetris (USA)");
+    assert_eq!(groups[1].duplicates(), &[1, 0]);
+    assert!(groups[0].duplicates().is_empty());
+
+    let prefs = Preferences {
+        regions: vec!["Europe".into()],
+        ..Preferences::default()
+    };
+    assert_eq!(preferred(&names[..3], &prefs), ["Tetris (Europe)"]);
+}
+
+#[test]
+fn test_retail_beats_prototypes_and_hacks() {
+    let names = [
+        "Zelda (USA) (Proto)",
+        "Zelda (USA) [b]",
+        "Zelda (Japan)",
+        "Zelda (USA) [h1]",
+    ];
+    assert_eq!(
+        preferred(&names, &Preferences::default()),
+        ["Zelda (Japan)"]
+    );
+}
+
+#[test]
+fn test_languages_break_region_ties() {
+    let names = ["Game (Europe) (Fr,De)", "Game (Europe) (En,Fr,De)"];
+    assert_eq!(
+        preferred(&names, &Preferences::default()),
+        ["Game (Europe) (En,Fr,De)"]
+    );
+}
+
+#[test]
+fn test_revision_preference() {
+    let names = [
+        "Game (USA)",
+        "Game (USA) (Rev 2)",
+        "Game (USA) (Rev 1)",
+        "Game (USA) (Rev A)",
+    ];
+    assert_eq!(
+        preferred(&names, &Preferences::default()),
+        ["Game (USA) (Rev 2)"]
+    );
+    let original = Preferences {
+        latest_revision: false,
+        ..Preferences::default()
+    };
+    assert_eq!(preferred(&names, &original), ["Game (USA)"]);
+}
+
+#[test]
+fn test_discs_are_grouped_separately() {
+    let names = [
+        "Saga (USA) (Disc 1)",
+        "Saga (USA) (Disc 2)",
+        "Saga (Japan) (Disc 1)",
+    ];
+    let groups = select(&names, None, &Preferences::default());
+    assert_eq!(groups.len(), 2);
+    assert_eq!(names[groups[0].preferred()], "Saga (USA) (Disc 1)");
+    assert_eq!(names[groups[1].preferred()], "Saga (USA) (Disc 2)");
+}
+
+#[test]
+fn test_clones_join_their_parent_group() {
+    let index = DatIndex::from_dat(DatFile {
+        name: "Test".into(),
+        description: "Test".into(),
+        version: "1".into(),
+        games: vec![
+            game("Mega Man (USA)", None),
+            game("Rockman (Japan)", Some("Mega Man (USA)")),
+            game("Mega Man (Europe)", Some("Mega Man (USA)")),
+        ],
+    });
+    let names = ["Rockman (Japan)", "Mega Man (Europe)", "Mega Man (USA)"];
+    let groups = select(&names, Some(&index), &Preferences::default());
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].members, [2, 1, 0]);
+
+    // Without the DAT, the Japanese title stands on its own
+    assert_eq!(select(&names, None, &Preferences::default()).len(), 2);
+}
+
+#[test]
+fn test_parent_wins_an_otherwise_even_tie() {
+    let index = DatIndex::from_dat(DatFile {
+        name: "Test".into(),
+        description: "Test".into(),
+        version: "1".into(),
+        games: vec![
+            game("Game (USA) (Alt 1)", Some("Game (USA)")),
+            game("Game (USA)", None),
+        ],
+    });
+    let names = ["Game (USA) (Alt 1)", "Game (USA)"];
+    let groups = select(&names, Some(&index), &Preferences::default());
+    assert_eq!(groups[0].members, [1, 0]);
+}
+
+#[test]
+fn test_cloneof_cycles_terminate() {
+    let index = DatIndex::from_dat(DatFile {
+        name: "Test".into(),
+        description: "Test".into(),
+        version: "1".into(),
+        games: vec![
+            game("A (USA)", Some("B (USA)")),
+            game("B (USA)", Some("A (USA)")),
+        ],
+    });
+    let names = ["A (USA)", "B (USA)"];
+    let groups = select(&names, Some(&index), &Preferences::default());
+    assert_eq!(groups.iter().map(|g| g.members.len()).sum::<usize>(), 2);
+}
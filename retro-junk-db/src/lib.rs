@@ -8,28 +8,37 @@ pub mod queries;
 pub mod schema;
 
 pub use operations::{
-    OperationError, SeedStats, apply_disagreement_resolution, clear_not_found_flags,
-    delete_orphan_works, delete_release, find_company_by_alias, find_media_by_dat_name,
-    find_release, find_work_by_name, insert_asset, insert_disagreement, insert_import_log,
-    insert_work, mark_release_not_found, move_assets_to_release, move_disagreements_for_release,
-    move_media_to_release, resolve_disagreement, seed_from_catalog, unenrich_releases,
+    OperationError, SeedStats, apply_disagreement_resolution, approve_pending_company_alias,
+    clear_not_found_flags, delete_asset, delete_orphan_works, delete_release,
+    find_company_by_alias, find_media_by_dat_name, find_release, find_work_by_name, insert_asset,
+    insert_disagreement, insert_import_log, insert_work, insert_work_relationship,
+    mark_release_not_found, move_assets_to_release, move_disagreements_for_release,
+    move_media_to_release, move_work_relationships, record_collection_snapshot,
+    record_pending_company_alias_use, reject_pending_company_alias, resolve_disagreement,
+    seed_from_catalog, stage_company_alias, unenrich_releases, update_asset_hash,
     update_release_enrichment, update_releases_work_id, update_work_name, upsert_collection_entry,
     upsert_company, upsert_media, upsert_override, upsert_platform, upsert_release,
 };
 pub use queries::{
-    CatalogStats, CollectionRow, CompanyRow, DisagreementFilter, PlatformRow, ReconcileGroup,
-    ReleaseCollision, WorkRow, WorkWithCount, asset_counts_by_type, asset_coverage_summary,
-    assets_for_release, catalog_stats, check_release_collision, collection_counts_by_platform,
-    count_collection, count_companies_search, count_enriched_releases, count_media_search,
-    count_releases_for_work, count_releases_search, count_works_search, find_collection_entry,
-    find_media_by_crc32, find_media_by_md5, find_media_by_serial, find_media_by_sha1,
-    find_reconcilable_works, find_release_by_serial, get_company_name, get_disagreement,
-    get_media_by_id, get_platform_by_id, get_platform_display_name, get_release_by_id,
-    get_work_by_id, list_collection, list_collection_paged, list_import_logs, list_platforms,
-    list_unresolved_disagreements, media_for_release, platform_media_counts,
-    platform_release_counts, releases_for_platform, releases_for_work, releases_missing_asset_type,
-    releases_to_enrich, releases_with_no_assets, search_companies, search_media, search_releases,
-    search_releases_filtered, search_releases_paged, search_works, works_for_platform,
+    CatalogStats, CollectionRow, CollectionSnapshot, CompanyRow, DisagreementFilter,
+    PendingCompanyAlias, PlatformRow, PlatformWorkCounts, ReconcileGroup, ReleaseCollision,
+    WorkOwnership, WorkRelationshipRow, WorkRow, WorkWithCount, asset_counts_by_type,
+    asset_coverage_summary, assets_for_release, catalog_stats, check_release_collision,
+    collection_counts_by_platform, collection_history, company_names, count_collection,
+    count_companies_search, count_enriched_releases, count_media_search,
+    count_pending_company_aliases, count_releases_for_work, count_releases_search,
+    count_works_search, find_collection_entry, find_media_by_crc32, find_media_by_md5,
+    find_media_by_serial, find_media_by_sha1, find_pending_company_alias, find_reconcilable_works,
+    find_release_by_serial, get_company_name, get_disagreement, get_media_by_id,
+    get_pending_company_alias, get_platform_by_id, get_platform_display_name, get_release_by_id,
+    get_work_by_id, is_work_owned, list_assets, list_collection, list_collection_paged,
+    list_import_logs, list_pending_company_aliases, list_platforms, list_unresolved_disagreements,
+    list_work_relationships, media_for_platform, media_for_release, platform_media_counts,
+    platform_release_counts, relationships_for_work, releases_for_platform, releases_for_work,
+    releases_missing_asset_type, releases_to_enrich, releases_to_enrich_paged,
+    releases_with_no_assets, search_companies, search_media, search_releases,
+    search_releases_filtered, search_releases_paged, search_works, work_counts_by_platform,
+    work_ownership_for_platform, works_for_platform,
 };
 pub use rusqlite::Connection;
 pub use schema::{open_database, open_memory};
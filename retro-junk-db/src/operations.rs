@@ -4,6 +4,8 @@ use retro_junk_catalog::types::*;
 use rusqlite::{Connection, params};
 use thiserror::Error;
 
+use crate::queries::CollectionSnapshot;
+
 #[derive(Debug, Error)]
 pub enum OperationError {
     #[error("SQLite error: {0}")]
@@ -107,6 +109,120 @@ pub fn find_company_by_alias(
     }
 }
 
+/// Stage a company name for review. Returns the new pending alias ID.
+pub fn stage_company_alias(
+    conn: &Connection,
+    alias: &str,
+    source: &str,
+    suggested_company_id: Option<&str>,
+) -> Result<i64, OperationError> {
+    conn.execute(
+        "INSERT INTO pending_company_aliases (alias, source, suggested_company_id)
+         VALUES (?1, ?2, ?3)",
+        params![alias, source, suggested_company_id],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Record that enrichment saw a staged company name as a release's
+/// `role` ("developer" or "publisher").
+pub fn record_pending_company_alias_use(
+    conn: &Connection,
+    pending_id: i64,
+    release_id: &str,
+    role: &str,
+) -> Result<(), OperationError> {
+    conn.execute(
+        "UPDATE pending_company_aliases SET occurrences = occurrences + 1 WHERE id = ?1",
+        params![pending_id],
+    )?;
+    conn.execute(
+        "INSERT OR IGNORE INTO pending_company_alias_refs (pending_id, release_id, role)
+         VALUES (?1, ?2, ?3)",
+        params![pending_id, release_id, role],
+    )?;
+    Ok(())
+}
+
+/// Approve a staged company name as an alias of `company_id`.
+///
+/// Adds the alias, links every release that referenced the name and has no
+/// company in that role yet, and marks the name approved. Returns the number
+/// of releases linked.
+pub fn approve_pending_company_alias(
+    conn: &Connection,
+    pending_id: i64,
+    company_id: &str,
+) -> Result<u64, OperationError> {
+    let alias: String = conn
+        .query_row(
+            "SELECT alias FROM pending_company_aliases WHERE id = ?1",
+            params![pending_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => OperationError::NotFound {
+                entity_type: "pending company alias".to_string(),
+                id: pending_id.to_string(),
+            },
+            e => e.into(),
+        })?;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO company_aliases (company_id, alias) VALUES (?1, ?2)",
+        params![company_id, alias],
+    )?;
+
+    let mut linked = 0u64;
+    for (role, column) in [("developer", "developer_id"), ("publisher", "publisher_id")] {
+        linked += conn.execute(
+            &format!(
+                "UPDATE releases SET {column} = ?1, updated_at = datetime('now')
+                 WHERE {column} IS NULL AND id IN (
+                     SELECT release_id FROM pending_company_alias_refs
+                     WHERE pending_id = ?2 AND role = ?3)"
+            ),
+            params![company_id, pending_id, role],
+        )? as u64;
+    }
+
+    conn.execute(
+        "DELETE FROM pending_company_alias_refs WHERE pending_id = ?1",
+        params![pending_id],
+    )?;
+    conn.execute(
+        "UPDATE pending_company_aliases
+         SET status = 'approved', resolved_company_id = ?2, resolved_at = datetime('now')
+         WHERE id = ?1",
+        params![pending_id, company_id],
+    )?;
+
+    Ok(linked)
+}
+
+/// Reject a staged company name. Enrichment will keep ignoring it.
+pub fn reject_pending_company_alias(
+    conn: &Connection,
+    pending_id: i64,
+) -> Result<(), OperationError> {
+    conn.execute(
+        "DELETE FROM pending_company_alias_refs WHERE pending_id = ?1",
+        params![pending_id],
+    )?;
+    let changed = conn.execute(
+        "UPDATE pending_company_aliases SET status = 'rejected', resolved_at = datetime('now')
+         WHERE id = ?1",
+        params![pending_id],
+    )?;
+    if changed == 0 {
+        return Err(OperationError::NotFound {
+            entity_type: "pending company alias".to_string(),
+            id: pending_id.to_string(),
+        });
+    }
+    Ok(())
+}
+
 // ── Work Operations ─────────────────────────────────────────────────────────
 
 /// Insert a new work. Returns the generated ID.
@@ -133,6 +249,52 @@ pub fn find_work_by_name(conn: &Connection, name: &str) -> Result<Option<String>
     }
 }
 
+/// Record a relationship between two works. Symmetric relationships
+/// (siblings) are stored with the lower ID first so each pair is kept once.
+pub fn insert_work_relationship(
+    conn: &Connection,
+    work_a: &str,
+    work_b: &str,
+    relationship: WorkRelationship,
+) -> Result<(), OperationError> {
+    let (a, b) = match relationship {
+        WorkRelationship::Sibling | WorkRelationship::NotSibling if work_b < work_a => {
+            (work_b, work_a)
+        }
+        _ => (work_a, work_b),
+    };
+    conn.execute(
+        "INSERT OR IGNORE INTO work_relationships (work_a, work_b, relationship)
+         VALUES (?1, ?2, ?3)",
+        params![a, b, work_relationship_str(&relationship)],
+    )?;
+    Ok(())
+}
+
+/// Point an absorbed work's relationships at the surviving work, dropping
+/// any that would become duplicates or relate the survivor to itself.
+pub fn move_work_relationships(
+    conn: &Connection,
+    absorbed_work_id: &str,
+    surviving_work_id: &str,
+) -> Result<u64, OperationError> {
+    let mut moved = conn.execute(
+        "UPDATE OR IGNORE work_relationships SET work_a = ?2
+         WHERE work_a = ?1 AND work_b != ?2",
+        params![absorbed_work_id, surviving_work_id],
+    )?;
+    moved += conn.execute(
+        "UPDATE OR IGNORE work_relationships SET work_b = ?2
+         WHERE work_b = ?1 AND work_a != ?2",
+        params![absorbed_work_id, surviving_work_id],
+    )?;
+    conn.execute(
+        "DELETE FROM work_relationships WHERE work_a = ?1 OR work_b = ?1",
+        params![absorbed_work_id],
+    )?;
+    Ok(moved as u64)
+}
+
 /// Update a work's canonical name.
 pub fn update_work_name(
     conn: &Connection,
@@ -478,6 +640,25 @@ pub fn insert_asset(conn: &Connection, asset: &Asset) -> Result<i64, OperationEr
     Ok(conn.last_insert_rowid())
 }
 
+/// Record the content hash of an asset file.
+pub fn update_asset_hash(
+    conn: &Connection,
+    id: i64,
+    file_hash: &str,
+) -> Result<(), OperationError> {
+    conn.execute(
+        "UPDATE media_assets SET file_hash = ?1 WHERE id = ?2",
+        params![file_hash, id],
+    )?;
+    Ok(())
+}
+
+/// Delete a media asset row. The file on disk is left alone.
+pub fn delete_asset(conn: &Connection, id: i64) -> Result<(), OperationError> {
+    conn.execute("DELETE FROM media_assets WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
 // ── Collection Operations ───────────────────────────────────────────────────
 
 /// Insert or update a collection entry.
@@ -509,6 +690,65 @@ pub fn upsert_collection_entry(
     Ok(())
 }
 
+/// Record a snapshot of a platform's collection counts in `collection_history`.
+///
+/// Counts are taken from the current collection, so call this after a scan or
+/// verify has finished writing.
+pub fn record_collection_snapshot(
+    conn: &Connection,
+    platform_id: &str,
+    user_id: &str,
+    source: &str,
+    recorded_at: &str,
+) -> Result<CollectionSnapshot, OperationError> {
+    let (owned, verified, owned_works): (i64, i64, i64) = conn.query_row(
+        "SELECT COUNT(*), COUNT(c.verified_at), COUNT(DISTINCT r.work_id)
+         FROM collection c
+         JOIN media m ON c.media_id = m.id
+         JOIN releases r ON m.release_id = r.id
+         WHERE c.owned = 1 AND c.user_id = ?1 AND r.platform_id = ?2",
+        params![user_id, platform_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+    let assets: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM media_assets a
+         JOIN releases r ON a.release_id = r.id
+         WHERE r.platform_id = ?2
+           AND EXISTS (SELECT 1 FROM media m
+                       JOIN collection c ON c.media_id = m.id
+                       WHERE m.release_id = r.id AND c.owned = 1 AND c.user_id = ?1)",
+        params![user_id, platform_id],
+        |row| row.get(0),
+    )?;
+
+    conn.execute(
+        "INSERT INTO collection_history (recorded_at, source, platform_id, user_id,
+             owned, verified, assets, owned_works)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            recorded_at,
+            source,
+            platform_id,
+            user_id,
+            owned,
+            verified,
+            assets,
+            owned_works
+        ],
+    )?;
+
+    Ok(CollectionSnapshot {
+        recorded_at: recorded_at.to_string(),
+        source: source.to_string(),
+        platform_id: platform_id.to_string(),
+        user_id: user_id.to_string(),
+        owned,
+        verified,
+        assets,
+        owned_works,
+    })
+}
+
 // ── Import Log Operations ───────────────────────────────────────────────────
 
 /// Insert an import log entry. Returns the generated ID.
@@ -775,6 +1015,12 @@ pub fn delete_release(conn: &Connection, id: &str) -> Result<(), OperationError>
 
 /// Delete works that have no remaining releases.
 pub fn delete_orphan_works(conn: &Connection) -> Result<u64, OperationError> {
+    conn.execute(
+        "DELETE FROM work_relationships
+         WHERE work_a NOT IN (SELECT DISTINCT work_id FROM releases)
+            OR work_b NOT IN (SELECT DISTINCT work_id FROM releases)",
+        [],
+    )?;
     let changed = conn.execute(
         "DELETE FROM works WHERE id NOT IN (SELECT DISTINCT work_id FROM releases)",
         [],
@@ -793,6 +1039,19 @@ fn media_type_str(mt: &MediaType) -> &'static str {
     }
 }
 
+pub(crate) fn work_relationship_str(r: &WorkRelationship) -> &'static str {
+    match r {
+        WorkRelationship::Sequel => "sequel",
+        WorkRelationship::Prequel => "prequel",
+        WorkRelationship::Remake => "remake",
+        WorkRelationship::Port => "port",
+        WorkRelationship::Remaster => "remaster",
+        WorkRelationship::Dlc => "dlc",
+        WorkRelationship::Sibling => "sibling",
+        WorkRelationship::NotSibling => "not_sibling",
+    }
+}
+
 fn relationship_str(r: &PlatformRelationship) -> &'static str {
     match r {
         PlatformRelationship::RegionalVariant => "regional_variant",
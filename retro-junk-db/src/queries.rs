@@ -67,6 +67,25 @@ pub fn media_for_release(
     rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
 }
 
+/// List a platform's media, ordered by DAT name. With `owned_by`, only the
+/// media that user owns.
+pub fn media_for_platform(
+    conn: &Connection,
+    platform_id: &str,
+    owned_by: Option<&str>,
+) -> Result<Vec<Media>, OperationError> {
+    let sql = format!(
+        "SELECT {MEDIA_COLUMNS} FROM media \
+         WHERE release_id IN (SELECT id FROM releases WHERE platform_id = ?1) \
+         AND (?2 IS NULL OR id IN \
+             (SELECT media_id FROM collection WHERE user_id = ?2 AND owned = 1)) \
+         ORDER BY dat_name, id"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params![platform_id, owned_by], row_to_media)?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+}
+
 // ── Release Lookups ─────────────────────────────────────────────────────────
 
 /// Query releases with a single-param WHERE clause.
@@ -89,10 +108,13 @@ pub fn releases_for_platform(
     query_releases(conn, "platform_id = ?1 ORDER BY title", platform_id)
 }
 
-/// Search releases by title (case-insensitive LIKE).
+/// Search releases by title, ignoring case, articles, diacritics, and punctuation.
 pub fn search_releases(conn: &Connection, query: &str) -> Result<Vec<Release>, OperationError> {
-    let pattern = format!("%{}%", query);
-    query_releases(conn, "title LIKE ?1 ORDER BY title LIMIT 100", &pattern)
+    query_releases(
+        conn,
+        "title_matches(title, ?1) ORDER BY title LIMIT 100",
+        query,
+    )
 }
 
 /// Search releases by title with optional platform filter and configurable limit.
@@ -102,22 +124,21 @@ pub fn search_releases_filtered(
     platform_id: Option<&str>,
     limit: u32,
 ) -> Result<Vec<Release>, OperationError> {
-    let pattern = format!("%{}%", query);
     let (sql, param_values): (String, Vec<Box<dyn rusqlite::types::ToSql>>) = match platform_id {
         Some(pid) => (
             format!(
                 "SELECT {RELEASE_COLUMNS} FROM releases \
-                 WHERE title LIKE ?1 AND platform_id = ?2 \
+                 WHERE title_matches(title, ?1) AND platform_id = ?2 \
                  ORDER BY title LIMIT {limit}"
             ),
-            vec![Box::new(pattern), Box::new(pid.to_string())],
+            vec![Box::new(query.to_string()), Box::new(pid.to_string())],
         ),
         None => (
             format!(
                 "SELECT {RELEASE_COLUMNS} FROM releases \
-                 WHERE title LIKE ?1 ORDER BY title LIMIT {limit}"
+                 WHERE title_matches(title, ?1) ORDER BY title LIMIT {limit}"
             ),
-            vec![Box::new(pattern)],
+            vec![Box::new(query.to_string())],
         ),
     };
     let mut stmt = conn.prepare(&sql)?;
@@ -145,6 +166,20 @@ pub fn releases_to_enrich(
     platform_id: &str,
     skip_existing: bool,
     limit: Option<u32>,
+) -> Result<Vec<Release>, OperationError> {
+    releases_to_enrich_paged(conn, platform_id, skip_existing, limit, 0)
+}
+
+/// Like `releases_to_enrich`, skipping the first `offset` releases.
+///
+/// Needed when processing a batch doesn't take its releases out of the
+/// result set (re-enriching everything, or a dry run).
+pub fn releases_to_enrich_paged(
+    conn: &Connection,
+    platform_id: &str,
+    skip_existing: bool,
+    limit: Option<u32>,
+    offset: u32,
 ) -> Result<Vec<Release>, OperationError> {
     let limit = limit.unwrap_or(u32::MAX);
     let extra_filter = if skip_existing {
@@ -161,8 +196,8 @@ pub fn releases_to_enrich(
          FROM releases r \
          JOIN media m ON m.release_id = r.id \
          WHERE r.platform_id = ?1{extra_filter} \
-         ORDER BY r.title \
-         LIMIT {limit}"
+         ORDER BY r.title, r.id \
+         LIMIT {limit} OFFSET {offset}"
     );
     let mut stmt = conn.prepare(&sql)?;
     let rows = stmt.query_map(params![platform_id], row_to_release)?;
@@ -314,6 +349,178 @@ pub fn works_for_platform(
     rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
 }
 
+/// A recorded relationship between two works.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkRelationshipRow {
+    pub work_a: String,
+    pub work_b: String,
+    pub relationship: String,
+}
+
+/// List work relationships, optionally only those of one kind.
+pub fn list_work_relationships(
+    conn: &Connection,
+    relationship: Option<WorkRelationship>,
+) -> Result<Vec<WorkRelationshipRow>, OperationError> {
+    let mut stmt = conn.prepare(
+        "SELECT work_a, work_b, relationship FROM work_relationships
+         WHERE ?1 IS NULL OR relationship = ?1
+         ORDER BY work_a, work_b",
+    )?;
+    let kind = relationship.map(|r| crate::operations::work_relationship_str(&r));
+    let rows = stmt.query_map(params![kind], |row| {
+        Ok(WorkRelationshipRow {
+            work_a: row.get(0)?,
+            work_b: row.get(1)?,
+            relationship: row.get(2)?,
+        })
+    })?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+}
+
+/// Relationships involving a work, from either side.
+pub fn relationships_for_work(
+    conn: &Connection,
+    work_id: &str,
+) -> Result<Vec<WorkRelationshipRow>, OperationError> {
+    let mut stmt = conn.prepare(
+        "SELECT work_a, work_b, relationship FROM work_relationships
+         WHERE work_a = ?1 OR work_b = ?1
+         ORDER BY relationship, work_a, work_b",
+    )?;
+    let rows = stmt.query_map(params![work_id], |row| {
+        Ok(WorkRelationshipRow {
+            work_a: row.get(0)?,
+            work_b: row.get(1)?,
+            relationship: row.get(2)?,
+        })
+    })?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+}
+
+// ── Work Aggregation ────────────────────────────────────────────────────────
+
+/// Collection ownership of one work on a platform, rolled up from its releases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkOwnership {
+    pub work_id: String,
+    pub canonical_name: String,
+    pub release_count: i64,
+    /// Releases with at least one owned media entry.
+    pub owned_release_count: i64,
+    /// Regions of the owned releases, sorted and deduplicated.
+    pub owned_regions: Vec<String>,
+}
+
+impl WorkOwnership {
+    /// Whether any release of the work is owned.
+    pub fn is_owned(&self) -> bool {
+        self.owned_release_count > 0
+    }
+
+    /// The owned region that ranks highest in `priority`, or the first owned
+    /// region if none of them are listed.
+    pub fn best_owned_region(&self, priority: &[&str]) -> Option<&str> {
+        self.owned_regions
+            .iter()
+            .min_by_key(|r| {
+                priority
+                    .iter()
+                    .position(|p| p == r)
+                    .unwrap_or(priority.len())
+            })
+            .map(String::as_str)
+    }
+}
+
+/// Work totals for one platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlatformWorkCounts {
+    pub platform_id: String,
+    pub works: i64,
+    /// Works with at least one owned release.
+    pub owned_works: i64,
+}
+
+/// Roll a platform's releases up to works, with the user's ownership of each.
+pub fn work_ownership_for_platform(
+    conn: &Connection,
+    platform_id: &str,
+    user_id: &str,
+) -> Result<Vec<WorkOwnership>, OperationError> {
+    let mut stmt = conn.prepare(
+        "SELECT w.id, w.canonical_name, COUNT(r.id),
+                COUNT(o.release_id),
+                GROUP_CONCAT(DISTINCT CASE WHEN o.release_id IS NOT NULL THEN r.region END)
+         FROM works w
+         JOIN releases r ON r.work_id = w.id
+         LEFT JOIN (SELECT DISTINCT m.release_id FROM media m
+                    JOIN collection c ON c.media_id = m.id
+                    WHERE c.owned = 1 AND c.user_id = ?2) o ON o.release_id = r.id
+         WHERE r.platform_id = ?1
+         GROUP BY w.id
+         ORDER BY w.canonical_name",
+    )?;
+    let rows = stmt.query_map(params![platform_id, user_id], |row| {
+        let regions: Option<String> = row.get(4)?;
+        let mut owned_regions: Vec<String> = regions
+            .map(|s| s.split(',').map(str::to_string).collect())
+            .unwrap_or_default();
+        owned_regions.sort();
+        Ok(WorkOwnership {
+            work_id: row.get(0)?,
+            canonical_name: row.get(1)?,
+            release_count: row.get(2)?,
+            owned_release_count: row.get(3)?,
+            owned_regions,
+        })
+    })?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+}
+
+/// Whether the user owns any release of a work, on any platform.
+pub fn is_work_owned(
+    conn: &Connection,
+    work_id: &str,
+    user_id: &str,
+) -> Result<bool, OperationError> {
+    conn.query_row(
+        "SELECT EXISTS(
+             SELECT 1 FROM collection c
+             JOIN media m ON c.media_id = m.id
+             JOIN releases r ON m.release_id = r.id
+             WHERE c.owned = 1 AND c.user_id = ?2 AND r.work_id = ?1)",
+        params![work_id, user_id],
+        |row| row.get(0),
+    )
+    .map_err(Into::into)
+}
+
+/// Total and owned work counts for every platform with releases.
+pub fn work_counts_by_platform(
+    conn: &Connection,
+    user_id: &str,
+) -> Result<Vec<PlatformWorkCounts>, OperationError> {
+    let mut stmt = conn.prepare(
+        "SELECT r.platform_id, COUNT(DISTINCT r.work_id),
+                COUNT(DISTINCT CASE WHEN o.release_id IS NOT NULL THEN r.work_id END)
+         FROM releases r
+         LEFT JOIN (SELECT DISTINCT m.release_id FROM media m
+                    JOIN collection c ON c.media_id = m.id
+                    WHERE c.owned = 1 AND c.user_id = ?1) o ON o.release_id = r.id
+         GROUP BY r.platform_id
+         ORDER BY r.platform_id",
+    )?;
+    let rows = stmt.query_map(params![user_id], |row| {
+        Ok(PlatformWorkCounts {
+            platform_id: row.get(0)?,
+            works: row.get(1)?,
+            owned_works: row.get(2)?,
+        })
+    })?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+}
+
 // ── Statistics ──────────────────────────────────────────────────────────────
 
 /// Get overall catalog statistics.
@@ -328,6 +535,15 @@ pub fn catalog_stats(conn: &Connection) -> Result<CatalogStats, OperationError>
         conn.query_row("SELECT COUNT(*) FROM collection WHERE owned = 1", [], |r| {
             r.get(0)
         })?;
+    let works_owned: i64 = conn.query_row(
+        "SELECT COUNT(DISTINCT r.work_id)
+         FROM collection c
+         JOIN media m ON c.media_id = m.id
+         JOIN releases r ON m.release_id = r.id
+         WHERE c.owned = 1",
+        [],
+        |r| r.get(0),
+    )?;
     let unresolved: i64 = conn.query_row(
         "SELECT COUNT(*) FROM disagreements WHERE resolved = 0",
         [],
@@ -342,6 +558,7 @@ pub fn catalog_stats(conn: &Connection) -> Result<CatalogStats, OperationError>
         media,
         assets,
         collection_owned: collection,
+        works_owned,
         unresolved_disagreements: unresolved,
     })
 }
@@ -356,6 +573,8 @@ pub struct CatalogStats {
     pub media: i64,
     pub assets: i64,
     pub collection_owned: i64,
+    /// Works with at least one owned release.
+    pub works_owned: i64,
     pub unresolved_disagreements: i64,
 }
 
@@ -594,6 +813,53 @@ pub fn collection_counts_by_platform(
     rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
 }
 
+// ── Collection History Queries ──────────────────────────────────────────────
+
+/// Per-platform collection counts recorded after a scan or verify.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionSnapshot {
+    pub recorded_at: String,
+    /// What recorded the snapshot ("scan" or "verify").
+    pub source: String,
+    pub platform_id: String,
+    pub user_id: String,
+    pub owned: i64,
+    /// Owned entries whose files have been matched or re-verified.
+    pub verified: i64,
+    /// Media assets attached to owned releases.
+    pub assets: i64,
+    /// Works with at least one owned release.
+    pub owned_works: i64,
+}
+
+/// List recorded collection snapshots, oldest first.
+pub fn collection_history(
+    conn: &Connection,
+    platform_id: Option<&str>,
+    user_id: &str,
+) -> Result<Vec<CollectionSnapshot>, OperationError> {
+    let mut stmt = conn.prepare(
+        "SELECT recorded_at, source, platform_id, user_id, owned, verified, assets,
+                owned_works
+         FROM collection_history
+         WHERE user_id = ?1 AND (?2 IS NULL OR platform_id = ?2)
+         ORDER BY recorded_at, id",
+    )?;
+    let rows = stmt.query_map(params![user_id, platform_id], |row| {
+        Ok(CollectionSnapshot {
+            recorded_at: row.get(0)?,
+            source: row.get(1)?,
+            platform_id: row.get(2)?,
+            user_id: row.get(3)?,
+            owned: row.get(4)?,
+            verified: row.get(5)?,
+            assets: row.get(6)?,
+            owned_works: row.get(7)?,
+        })
+    })?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+}
+
 // ── Import Log Queries ──────────────────────────────────────────────────────
 
 /// List recent import logs.
@@ -640,6 +906,23 @@ pub fn assets_for_release(
     rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
 }
 
+/// List all assets with a file on disk, optionally filtered by platform.
+pub fn list_assets(
+    conn: &Connection,
+    platform_id: Option<&str>,
+) -> Result<Vec<Asset>, OperationError> {
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.release_id, a.media_id, a.asset_type, a.region, a.source,
+                a.file_path, a.source_url, a.scraped, a.file_hash, a.width, a.height, a.created_at
+         FROM media_assets a
+         LEFT JOIN releases r ON a.release_id = r.id
+         WHERE a.file_path IS NOT NULL AND (?1 IS NULL OR r.platform_id = ?1)
+         ORDER BY a.release_id, a.asset_type",
+    )?;
+    let rows = stmt.query_map(params![platform_id], row_to_asset)?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+}
+
 /// Count assets per type for a platform, optionally restricted to collection.
 ///
 /// Returns rows of (asset_type, count).
@@ -821,21 +1104,20 @@ pub fn asset_coverage_summary(
 
 // ── Catalog List Queries ────────────────────────────────────────────────────
 
-/// Search works by canonical name (case-insensitive LIKE).
+/// Search works by canonical name, ignoring case, articles, diacritics, and punctuation.
 pub fn search_works(
     conn: &Connection,
     query: &str,
     limit: u32,
     offset: u32,
 ) -> Result<Vec<WorkRow>, OperationError> {
-    let pattern = format!("%{}%", query);
     let sql = format!(
         "SELECT id, canonical_name FROM works \
-         WHERE canonical_name LIKE ?1 \
+         WHERE title_matches(canonical_name, ?1) \
          ORDER BY canonical_name LIMIT {limit} OFFSET {offset}"
     );
     let mut stmt = conn.prepare(&sql)?;
-    let rows = stmt.query_map(params![pattern], |row| {
+    let rows = stmt.query_map(params![query], |row| {
         Ok(WorkRow {
             id: row.get(0)?,
             canonical_name: row.get(1)?,
@@ -887,22 +1169,21 @@ pub fn search_releases_paged(
     limit: u32,
     offset: u32,
 ) -> Result<Vec<Release>, OperationError> {
-    let pattern = format!("%{}%", query);
     let (sql, param_values): (String, Vec<Box<dyn rusqlite::types::ToSql>>) = match platform_id {
         Some(pid) => (
             format!(
                 "SELECT {RELEASE_COLUMNS} FROM releases \
-                 WHERE title LIKE ?1 AND platform_id = ?2 \
+                 WHERE title_matches(title, ?1) AND platform_id = ?2 \
                  ORDER BY title LIMIT {limit} OFFSET {offset}"
             ),
-            vec![Box::new(pattern), Box::new(pid.to_string())],
+            vec![Box::new(query.to_string()), Box::new(pid.to_string())],
         ),
         None => (
             format!(
                 "SELECT {RELEASE_COLUMNS} FROM releases \
-                 WHERE title LIKE ?1 ORDER BY title LIMIT {limit} OFFSET {offset}"
+                 WHERE title_matches(title, ?1) ORDER BY title LIMIT {limit} OFFSET {offset}"
             ),
-            vec![Box::new(pattern)],
+            vec![Box::new(query.to_string())],
         ),
     };
     let mut stmt = conn.prepare(&sql)?;
@@ -1123,16 +1404,15 @@ pub fn count_releases_search(
     query: &str,
     platform_id: Option<&str>,
 ) -> Result<i64, OperationError> {
-    let pattern = format!("%{}%", query);
     let count: i64 = match platform_id {
         Some(pid) => conn.query_row(
-            "SELECT COUNT(*) FROM releases WHERE title LIKE ?1 AND platform_id = ?2",
-            params![pattern, pid],
+            "SELECT COUNT(*) FROM releases WHERE title_matches(title, ?1) AND platform_id = ?2",
+            params![query, pid],
             |r| r.get(0),
         )?,
         None => conn.query_row(
-            "SELECT COUNT(*) FROM releases WHERE title LIKE ?1",
-            params![pattern],
+            "SELECT COUNT(*) FROM releases WHERE title_matches(title, ?1)",
+            params![query],
             |r| r.get(0),
         )?,
     };
@@ -1165,10 +1445,9 @@ pub fn count_media_search(
 
 /// Count works matching a canonical_name search.
 pub fn count_works_search(conn: &Connection, query: &str) -> Result<i64, OperationError> {
-    let pattern = format!("%{}%", query);
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM works WHERE canonical_name LIKE ?1",
-        params![pattern],
+        "SELECT COUNT(*) FROM works WHERE title_matches(canonical_name, ?1)",
+        params![query],
         |r| r.get(0),
     )?;
     Ok(count)
@@ -1231,6 +1510,117 @@ pub fn search_companies(
     rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
 }
 
+/// A company name from an enrichment source that matched no known company.
+#[derive(Debug, Clone)]
+pub struct PendingCompanyAlias {
+    pub id: i64,
+    pub alias: String,
+    /// Source that first reported the name (e.g. "screenscraper", "gdb").
+    pub source: String,
+    /// Known company the name looks like, if any.
+    pub suggested_company_id: Option<String>,
+    /// How many times enrichment has seen the name.
+    pub occurrences: i64,
+    /// Releases waiting to be linked once the name is approved.
+    pub release_count: i64,
+    /// "pending", "approved", or "rejected".
+    pub status: String,
+    /// Company the name was approved as.
+    pub resolved_company_id: Option<String>,
+    pub first_seen: String,
+}
+
+const PENDING_ALIAS_COLUMNS: &str =
+    "p.id, p.alias, p.source, p.suggested_company_id, p.occurrences,
+     (SELECT COUNT(*) FROM pending_company_alias_refs r WHERE r.pending_id = p.id),
+     p.status, p.resolved_company_id, p.first_seen";
+
+fn pending_alias_from_row(row: &rusqlite::Row) -> rusqlite::Result<PendingCompanyAlias> {
+    Ok(PendingCompanyAlias {
+        id: row.get(0)?,
+        alias: row.get(1)?,
+        source: row.get(2)?,
+        suggested_company_id: row.get(3)?,
+        occurrences: row.get(4)?,
+        release_count: row.get(5)?,
+        status: row.get(6)?,
+        resolved_company_id: row.get(7)?,
+        first_seen: row.get(8)?,
+    })
+}
+
+/// List company names awaiting review, most frequently seen first.
+pub fn list_pending_company_aliases(
+    conn: &Connection,
+    limit: u32,
+) -> Result<Vec<PendingCompanyAlias>, OperationError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {PENDING_ALIAS_COLUMNS} FROM pending_company_aliases p
+         WHERE p.status = 'pending'
+         ORDER BY p.occurrences DESC, p.alias LIMIT {limit}"
+    ))?;
+    let rows = stmt.query_map([], pending_alias_from_row)?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+}
+
+/// Count company names awaiting review.
+pub fn count_pending_company_aliases(conn: &Connection) -> Result<i64, OperationError> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM pending_company_aliases WHERE status = 'pending'",
+        [],
+        |r| r.get(0),
+    )?;
+    Ok(count)
+}
+
+/// Get a staged company name by ID, whatever its status.
+pub fn get_pending_company_alias(
+    conn: &Connection,
+    id: i64,
+) -> Result<Option<PendingCompanyAlias>, OperationError> {
+    let result = conn.query_row(
+        &format!("SELECT {PENDING_ALIAS_COLUMNS} FROM pending_company_aliases p WHERE p.id = ?1"),
+        params![id],
+        pending_alias_from_row,
+    );
+    match result {
+        Ok(alias) => Ok(Some(alias)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Find a staged company name (case-insensitive), whatever its status.
+pub fn find_pending_company_alias(
+    conn: &Connection,
+    alias: &str,
+) -> Result<Option<PendingCompanyAlias>, OperationError> {
+    let result = conn.query_row(
+        &format!(
+            "SELECT {PENDING_ALIAS_COLUMNS} FROM pending_company_aliases p WHERE p.alias = ?1"
+        ),
+        params![alias],
+        pending_alias_from_row,
+    );
+    match result {
+        Ok(alias) => Ok(Some(alias)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// All names a company is known by: its display name and every alias,
+/// as `(company_id, name)` pairs.
+pub fn company_names(conn: &Connection) -> Result<Vec<(String, String)>, OperationError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name FROM companies
+         UNION
+         SELECT company_id, alias FROM company_aliases",
+    )?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+}
+
 /// Count companies matching a name search.
 pub fn count_companies_search(conn: &Connection, query: &str) -> Result<i64, OperationError> {
     let pattern = format!("%{}%", query);
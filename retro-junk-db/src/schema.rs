@@ -1,6 +1,8 @@
 //! SQLite schema creation and migration.
 
+use retro_junk_catalog::title_key;
 use rusqlite::Connection;
+use rusqlite::functions::FunctionFlags;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -12,7 +14,7 @@ pub enum SchemaError {
 }
 
 /// Current schema version. Increment when adding migrations.
-pub const CURRENT_VERSION: i32 = 4;
+pub const CURRENT_VERSION: i32 = 7;
 
 /// Create all tables and indexes if they don't exist.
 ///
@@ -27,6 +29,7 @@ pub fn create_schema(conn: &Connection) -> Result<(), SchemaError> {
 pub fn open_database(path: &std::path::Path) -> Result<Connection, SchemaError> {
     let conn = Connection::open(path)?;
     conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")?;
+    register_functions(&conn)?;
 
     let version = get_schema_version(&conn)?;
     if version == 0 {
@@ -42,10 +45,37 @@ pub fn open_database(path: &std::path::Path) -> Result<Connection, SchemaError>
 pub fn open_memory() -> Result<Connection, SchemaError> {
     let conn = Connection::open_in_memory()?;
     conn.execute_batch("PRAGMA foreign_keys=ON;")?;
+    register_functions(&conn)?;
     create_schema(&conn)?;
     Ok(conn)
 }
 
+/// Register the SQL functions that queries rely on.
+///
+/// `title_matches(title, query)` is true when `query` appears in `title`,
+/// comparing title keys so articles, diacritics, and punctuation don't
+/// matter (see [`retro_junk_catalog::title_key`]).
+fn register_functions(conn: &Connection) -> Result<(), SchemaError> {
+    conn.create_scalar_function(
+        "title_matches",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| {
+            let title: Option<String> = ctx.get(0)?;
+            let query: Option<String> = ctx.get(1)?;
+            let (Some(title), Some(query)) = (title, query) else {
+                return Ok(false);
+            };
+            let query_key = title_key(&query, None);
+            if query_key.is_empty() {
+                return Ok(title.to_lowercase().contains(&query.to_lowercase()));
+            }
+            Ok(title_key(&title, None).contains(&query_key))
+        },
+    )?;
+    Ok(())
+}
+
 /// Get the current schema version, or 0 if no schema exists.
 fn get_schema_version(conn: &Connection) -> Result<i32, SchemaError> {
     // Check if schema_version table exists
@@ -108,6 +138,49 @@ fn migrate(conn: &Connection, from_version: i32) -> Result<(), SchemaError> {
                      ALTER TABLE releases ADD COLUMN cover_title TEXT;",
                 )?;
             }
+            4 => {
+                conn.execute_batch(
+                    "CREATE TABLE IF NOT EXISTS collection_history (
+                         id INTEGER PRIMARY KEY AUTOINCREMENT,
+                         recorded_at TEXT NOT NULL,
+                         source TEXT NOT NULL,
+                         platform_id TEXT NOT NULL,
+                         user_id TEXT NOT NULL DEFAULT 'default',
+                         owned INTEGER NOT NULL DEFAULT 0,
+                         verified INTEGER NOT NULL DEFAULT 0,
+                         assets INTEGER NOT NULL DEFAULT 0
+                     );
+                     CREATE INDEX IF NOT EXISTS idx_collection_history_platform
+                         ON collection_history(platform_id, recorded_at);",
+                )?;
+            }
+            5 => {
+                conn.execute_batch(
+                    "ALTER TABLE collection_history ADD COLUMN owned_works INTEGER NOT NULL DEFAULT 0;",
+                )?;
+            }
+            6 => {
+                conn.execute_batch(
+                    "CREATE TABLE IF NOT EXISTS pending_company_aliases (
+                         id INTEGER PRIMARY KEY AUTOINCREMENT,
+                         alias TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                         source TEXT NOT NULL,
+                         suggested_company_id TEXT REFERENCES companies(id),
+                         occurrences INTEGER NOT NULL DEFAULT 0,
+                         status TEXT NOT NULL DEFAULT 'pending',
+                         resolved_company_id TEXT REFERENCES companies(id),
+                         first_seen TEXT NOT NULL DEFAULT (datetime('now')),
+                         resolved_at TEXT
+                     );
+
+                     CREATE TABLE IF NOT EXISTS pending_company_alias_refs (
+                         pending_id INTEGER NOT NULL REFERENCES pending_company_aliases(id),
+                         release_id TEXT NOT NULL REFERENCES releases(id) ON DELETE CASCADE,
+                         role TEXT NOT NULL,
+                         PRIMARY KEY (pending_id, release_id, role)
+                     );",
+                )?;
+            }
             _ => {}
         }
         version += 1;
@@ -164,6 +237,27 @@ CREATE TABLE IF NOT EXISTS company_aliases (
     PRIMARY KEY (company_id, alias)
 );
 
+-- Company names seen during enrichment that matched no known company,
+-- held for review instead of becoming new companies
+CREATE TABLE IF NOT EXISTS pending_company_aliases (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    alias TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    source TEXT NOT NULL,
+    suggested_company_id TEXT REFERENCES companies(id),
+    occurrences INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'pending',
+    resolved_company_id TEXT REFERENCES companies(id),
+    first_seen TEXT NOT NULL DEFAULT (datetime('now')),
+    resolved_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS pending_company_alias_refs (
+    pending_id INTEGER NOT NULL REFERENCES pending_company_aliases(id),
+    release_id TEXT NOT NULL REFERENCES releases(id) ON DELETE CASCADE,
+    role TEXT NOT NULL,
+    PRIMARY KEY (pending_id, release_id, role)
+);
+
 -- Abstract game concept
 CREATE TABLE IF NOT EXISTS works (
     id TEXT PRIMARY KEY,
@@ -264,6 +358,21 @@ CREATE TABLE IF NOT EXISTS collection (
     UNIQUE(media_id, user_id)
 );
 
+-- Collection snapshots recorded after each scan/verify
+CREATE TABLE IF NOT EXISTS collection_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    recorded_at TEXT NOT NULL,
+    source TEXT NOT NULL,
+    platform_id TEXT NOT NULL,
+    user_id TEXT NOT NULL DEFAULT 'default',
+    owned INTEGER NOT NULL DEFAULT 0,
+    verified INTEGER NOT NULL DEFAULT 0,
+    assets INTEGER NOT NULL DEFAULT 0,
+    owned_works INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_collection_history_platform
+    ON collection_history(platform_id, recorded_at);
+
 -- Import tracking
 CREATE TABLE IF NOT EXISTS import_log (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
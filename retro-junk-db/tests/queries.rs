@@ -1,3 +1,4 @@
+use retro_junk_catalog::DEFAULT_REGION_PRIORITY;
 use retro_junk_catalog::types::*;
 use retro_junk_db::*;
 
@@ -138,6 +139,20 @@ fn search_releases_by_title() {
     assert_eq!(results.len(), 1);
 }
 
+#[test]
+fn search_ignores_articles_and_punctuation() {
+    let conn = setup_db();
+    // Stored as "The Legend of Zelda"
+    let results = search_releases(&conn, "Legend of Zelda, The").unwrap();
+    assert_eq!(results.len(), 1);
+    let results = search_releases(&conn, "super mario bros").unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+        count_releases_search(&conn, "Legend-of-Zelda", None).unwrap(),
+        1
+    );
+}
+
 #[test]
 fn catalog_stats_counts() {
     let conn = setup_db();
@@ -483,6 +498,159 @@ fn asset_queries_with_collection_filter() {
     assert_eq!(missing[0].1, "Super Mario Bros.");
 }
 
+// ── Collection History Tests ──────────────────────────────────────────────
+
+#[test]
+fn collection_snapshots_record_counts() {
+    let conn = setup_db_with_assets();
+
+    let empty = record_collection_snapshot(&conn, "nes", "default", "scan", "2024-01-01").unwrap();
+    assert_eq!((empty.owned, empty.verified, empty.assets), (0, 0, 0));
+
+    upsert_collection_entry(
+        &conn,
+        &CollectionEntry {
+            id: 0,
+            media_id: "smb1-nes-usa-v1".to_string(),
+            user_id: "default".to_string(),
+            owned: true,
+            condition: None,
+            notes: None,
+            date_acquired: None,
+            rom_path: None,
+            verified_at: Some("2024-02-01T00:00:00Z".to_string()),
+        },
+    )
+    .unwrap();
+    record_collection_snapshot(&conn, "nes", "default", "verify", "2024-02-01").unwrap();
+
+    let history = collection_history(&conn, Some("nes"), "default").unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].source, "scan");
+    assert_eq!(history[1].recorded_at, "2024-02-01");
+    assert_eq!(
+        (history[1].owned, history[1].verified, history[1].assets),
+        (1, 1, 2)
+    );
+    assert_eq!(history[1].owned_works, 1);
+
+    assert_eq!(collection_history(&conn, None, "default").unwrap().len(), 2);
+    assert!(
+        collection_history(&conn, Some("snes"), "default")
+            .unwrap()
+            .is_empty()
+    );
+    assert!(collection_history(&conn, None, "other").unwrap().is_empty());
+}
+
+// ── Work Aggregation Tests ────────────────────────────────────────────────
+
+fn own(conn: &rusqlite::Connection, media_id: &str) {
+    upsert_collection_entry(
+        conn,
+        &CollectionEntry {
+            id: 0,
+            media_id: media_id.to_string(),
+            user_id: "default".to_string(),
+            owned: true,
+            condition: None,
+            notes: None,
+            date_acquired: None,
+            rom_path: None,
+            verified_at: None,
+        },
+    )
+    .unwrap();
+}
+
+/// Add a Japanese release of SMB with its own media.
+fn add_smb_japan(conn: &rusqlite::Connection) {
+    let mut release = get_release_by_id(conn, "smb1-nes-usa").unwrap().unwrap();
+    release.id = "smb1-nes-japan".to_string();
+    release.region = "japan".to_string();
+    upsert_release(conn, &release).unwrap();
+
+    let mut media = get_media_by_id(conn, "smb1-nes-usa-v1").unwrap().unwrap();
+    media.id = "smb1-nes-japan-v1".to_string();
+    media.release_id = "smb1-nes-japan".to_string();
+    media.dat_name = Some("Super Mario Bros. (Japan).nes".to_string());
+    media.crc32 = Some("3337ec46".to_string());
+    media.sha1 = None;
+    upsert_media(conn, &media).unwrap();
+}
+
+#[test]
+fn media_for_platform_filters_by_owner() {
+    let conn = setup_db();
+    add_smb_japan(&conn);
+    own(&conn, "smb1-nes-japan-v1");
+
+    let all = media_for_platform(&conn, "nes", None).unwrap();
+    let ids: Vec<_> = all.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(ids, ["smb1-nes-japan-v1", "smb1-nes-usa-v1"]);
+
+    let owned = media_for_platform(&conn, "nes", Some("default")).unwrap();
+    assert_eq!(owned.len(), 1);
+    assert_eq!(owned[0].id, "smb1-nes-japan-v1");
+    assert!(
+        media_for_platform(&conn, "nes", Some("other"))
+            .unwrap()
+            .is_empty()
+    );
+    assert!(media_for_platform(&conn, "snes", None).unwrap().is_empty());
+}
+
+#[test]
+fn work_ownership_rolls_up_releases() {
+    let conn = setup_db();
+    add_smb_japan(&conn);
+    own(&conn, "smb1-nes-japan-v1");
+
+    let works = work_ownership_for_platform(&conn, "nes", "default").unwrap();
+    assert_eq!(works.len(), 2);
+    let smb = works.iter().find(|w| w.work_id == "smb1").unwrap();
+    assert_eq!(smb.release_count, 2);
+    assert_eq!(smb.owned_release_count, 1);
+    assert!(smb.is_owned());
+    assert_eq!(
+        smb.best_owned_region(DEFAULT_REGION_PRIORITY),
+        Some("japan")
+    );
+    let zelda = works.iter().find(|w| w.work_id == "zelda1").unwrap();
+    assert!(!zelda.is_owned());
+    assert_eq!(zelda.best_owned_region(DEFAULT_REGION_PRIORITY), None);
+
+    own(&conn, "smb1-nes-usa-v1");
+    let works = work_ownership_for_platform(&conn, "nes", "default").unwrap();
+    let smb = works.iter().find(|w| w.work_id == "smb1").unwrap();
+    assert_eq!(smb.owned_regions, vec!["japan", "usa"]);
+    assert_eq!(smb.best_owned_region(DEFAULT_REGION_PRIORITY), Some("usa"));
+    assert_eq!(smb.best_owned_region(&["japan"]), Some("japan"));
+
+    assert!(is_work_owned(&conn, "smb1", "default").unwrap());
+    assert!(!is_work_owned(&conn, "zelda1", "default").unwrap());
+    assert!(!is_work_owned(&conn, "smb1", "other").unwrap());
+}
+
+#[test]
+fn work_counts_by_platform_counts_owned_works_once() {
+    let conn = setup_db();
+    add_smb_japan(&conn);
+    own(&conn, "smb1-nes-usa-v1");
+    own(&conn, "smb1-nes-japan-v1");
+
+    let counts = work_counts_by_platform(&conn, "default").unwrap();
+    assert_eq!(
+        counts,
+        vec![PlatformWorkCounts {
+            platform_id: "nes".to_string(),
+            works: 2,
+            owned_works: 1,
+        }]
+    );
+    assert_eq!(catalog_stats(&conn).unwrap().works_owned, 1);
+}
+
 // ── Works For Platform Tests ──────────────────────────────────────────────
 
 #[test]
@@ -40,12 +40,15 @@ fn all_tables_exist() {
         "platform_relationships",
         "companies",
         "company_aliases",
+        "pending_company_aliases",
+        "pending_company_alias_refs",
         "works",
         "work_relationships",
         "releases",
         "media",
         "media_assets",
         "collection",
+        "collection_history",
         "import_log",
         "disagreements",
         "overrides",
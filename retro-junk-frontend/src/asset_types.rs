@@ -10,16 +10,22 @@ pub enum AssetType {
     TitleScreen,
     /// Front box art (2D)
     Cover,
+    /// Back box art (2D)
+    BackCover,
     /// 3D rendered box art
     Cover3D,
-    /// Logo / marquee / wheel image
+    /// Clear logo (wheel), used as the marquee by most themes
     Marquee,
     /// Gameplay or promotional video
     Video,
     /// Fan-created artwork
     Fanart,
-    /// Physical media image (cartridge/disc)
+    /// Physical media scan (cartridge or disc)
     PhysicalMedia,
+    /// Cartridge or disc label artwork, flat rather than photographed
+    Label,
+    /// Circuit board photo
+    Pcb,
     /// Composite miximage (screenshot + box + marquee + physical media)
     Miximage,
 }
@@ -30,22 +36,86 @@ impl fmt::Display for AssetType {
             AssetType::Screenshot => write!(f, "screenshot"),
             AssetType::TitleScreen => write!(f, "title screen"),
             AssetType::Cover => write!(f, "cover"),
+            AssetType::BackCover => write!(f, "back cover"),
             AssetType::Cover3D => write!(f, "3D box"),
             AssetType::Marquee => write!(f, "marquee"),
             AssetType::Video => write!(f, "video"),
             AssetType::Fanart => write!(f, "fanart"),
             AssetType::PhysicalMedia => write!(f, "physical media"),
+            AssetType::Label => write!(f, "label"),
+            AssetType::Pcb => write!(f, "PCB"),
             AssetType::Miximage => write!(f, "miximage"),
         }
     }
 }
 
 impl AssetType {
+    /// Every asset type, in display order.
+    pub const ALL: &'static [AssetType] = &[
+        AssetType::Cover,
+        AssetType::BackCover,
+        AssetType::Cover3D,
+        AssetType::Screenshot,
+        AssetType::TitleScreen,
+        AssetType::Marquee,
+        AssetType::PhysicalMedia,
+        AssetType::Label,
+        AssetType::Pcb,
+        AssetType::Fanart,
+        AssetType::Video,
+        AssetType::Miximage,
+    ];
+
     /// File extension for this asset type.
     pub fn default_extension(&self) -> &'static str {
         match self {
             AssetType::Video => "mp4",
+            AssetType::Pcb => "jpg",
             _ => "png",
         }
     }
+
+    /// Media subdirectory for this asset type.
+    ///
+    /// Types ES-DE knows use its folder names; the rest get folders of
+    /// their own that ES-DE ignores.
+    pub fn media_subdir(&self) -> &'static str {
+        match self {
+            AssetType::Cover => "covers",
+            AssetType::BackCover => "backcovers",
+            AssetType::Cover3D => "3dboxes",
+            AssetType::Screenshot => "screenshots",
+            AssetType::TitleScreen => "titlescreens",
+            AssetType::Marquee => "marquees",
+            AssetType::Video => "videos",
+            AssetType::Fanart => "fanart",
+            AssetType::PhysicalMedia => "physicalmedia",
+            AssetType::Label => "labels",
+            AssetType::Pcb => "pcbs",
+            AssetType::Miximage => "miximages",
+        }
+    }
+
+    /// The `asset_type` name used for this type in the catalog database.
+    pub fn catalog_name(&self) -> &'static str {
+        match self {
+            AssetType::Cover => "box-front",
+            AssetType::BackCover => "box-back",
+            AssetType::Cover3D => "box-3d",
+            AssetType::Screenshot => "screenshot",
+            AssetType::TitleScreen => "title-screen",
+            AssetType::Marquee => "wheel",
+            AssetType::Video => "video",
+            AssetType::Fanart => "fanart",
+            AssetType::PhysicalMedia => "cart-front",
+            AssetType::Label => "label",
+            AssetType::Pcb => "pcb",
+            AssetType::Miximage => "miximage",
+        }
+    }
+
+    /// Look up a type by its catalog database name.
+    pub fn from_catalog_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|t| t.catalog_name() == name)
+    }
 }
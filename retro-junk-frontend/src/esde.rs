@@ -145,6 +145,7 @@ impl Frontend for EsDeFrontend {
     fn asset_subdirs(&self) -> &[(&str, AssetType)] {
         &[
             ("covers", AssetType::Cover),
+            ("backcovers", AssetType::BackCover),
             ("screenshots", AssetType::Screenshot),
             ("titlescreens", AssetType::TitleScreen),
             ("marquees", AssetType::Marquee),
@@ -200,11 +201,12 @@ fn escape_xml(s: &str) -> String {
 fn format_esde_date(date: &str) -> String {
     // Handle YYYY-MM-DD
     let cleaned = date.replace('-', "");
-    // Ensure we have at least 8 digits, pad with zeros
-    if cleaned.len() >= 8 {
-        format!("{}T000000", &cleaned[..8])
-    } else {
-        format!("{}T000000", cleaned)
+    // Partial dates ("1994", "1994-11") default to the first month/day
+    match cleaned.len() {
+        n if n >= 8 => format!("{}T000000", &cleaned[..8]),
+        6 => format!("{}01T000000", cleaned),
+        4 => format!("{}0101T000000", cleaned),
+        _ => format!("{}T000000", cleaned),
     }
 }
 
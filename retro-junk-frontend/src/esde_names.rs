@@ -0,0 +1,94 @@
+//! ES-DE system names for retro-junk platforms.
+//!
+//! ES-DE keys gamelists and media folders by system name, and its bundled
+//! systems use that name as the ROM folder too. Several names differ from
+//! retro-junk's short names (`psx` vs `ps1`, `n3ds` vs `3ds`), and some
+//! platforms have regional variants ES-DE treats as separate systems
+//! (`genesis` and `megadrive`).
+
+use retro_junk_core::{Platform, PlatformFamily};
+
+/// ES-DE system names for a platform, preferred name first.
+///
+/// Empty for custom platforms, which ES-DE has no fixed name for.
+pub fn system_names(platform: Platform) -> &'static [&'static str] {
+    match platform {
+        // Nintendo
+        Platform::Nes => &["nes", "famicom"],
+        Platform::Snes => &["snes", "sfc", "snesna"],
+        Platform::N64 => &["n64"],
+        Platform::GameCube => &["gc"],
+        Platform::Wii => &["wii"],
+        Platform::WiiU => &["wiiu"],
+        Platform::GameBoy => &["gb", "gbc"],
+        Platform::Gba => &["gba"],
+        Platform::Ds => &["nds"],
+        Platform::N3ds => &["n3ds"],
+
+        // Sony
+        Platform::Ps1 => &["psx"],
+        Platform::Ps2 => &["ps2"],
+        Platform::Ps3 => &["ps3"],
+        Platform::Psp => &["psp"],
+        Platform::Vita => &["psvita"],
+
+        // Sega
+        Platform::Sg1000 => &["sg-1000"],
+        Platform::MasterSystem => &["mastersystem", "mark3"],
+        Platform::Genesis => &["genesis", "megadrive", "megadrivejp"],
+        Platform::SegaCd => &["segacd", "megacd", "megacdjp"],
+        Platform::Sega32x => &["sega32x", "sega32xjp", "sega32xna"],
+        Platform::Saturn => &["saturn", "saturnjp"],
+        Platform::Dreamcast => &["dreamcast"],
+        Platform::GameGear => &["gamegear"],
+
+        // Microsoft
+        Platform::Xbox => &["xbox"],
+        Platform::Xbox360 => &["xbox360"],
+
+        // Atari
+        Platform::Atari2600 => &["atari2600"],
+        Platform::Atari5200 => &["atari5200"],
+        Platform::Atari7800 => &["atari7800"],
+        Platform::Lynx => &["atarilynx"],
+        Platform::Jaguar => &["atarijaguar"],
+
+        // NEC
+        Platform::PcEngine => &["pcengine", "tg16"],
+        Platform::SuperGrafx => &["supergrafx"],
+        Platform::PcEngineCd => &["pcenginecd", "tg-cd"],
+
+        Platform::Custom(_) => &[],
+    }
+}
+
+/// The preferred ES-DE system name for a platform.
+pub fn system_name(platform: Platform) -> Option<&'static str> {
+    system_names(platform).first().copied()
+}
+
+/// Whether `name` is one of ES-DE's system names for `platform`
+/// (case-insensitive).
+pub fn is_system_name(platform: Platform, name: &str) -> bool {
+    system_names(platform)
+        .iter()
+        .any(|n| n.eq_ignore_ascii_case(name))
+}
+
+/// The system name to write gamelists and media under for a ROM folder.
+///
+/// A folder already named after one of the platform's ES-DE systems keeps
+/// its name, so regional variants stay separate. Family folders also keep
+/// theirs, since all their consoles share one gamelist. Anything else maps
+/// to the platform's preferred ES-DE name, falling back to the folder name
+/// for custom platforms.
+pub fn output_system_name(platform: Platform, folder_name: &str) -> &str {
+    if is_system_name(platform, folder_name) || folder_name.parse::<PlatformFamily>().is_ok() {
+        return folder_name;
+    }
+    system_name(platform).unwrap_or(folder_name)
+}
+
+#[cfg(test)]
+#[path = "tests/esde_names_tests.rs"]
+mod tests;
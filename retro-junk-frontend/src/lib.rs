@@ -1,8 +1,10 @@
 pub mod asset_types;
 pub mod error;
 pub mod esde;
+pub mod esde_names;
 pub mod miximage;
 pub mod miximage_layout;
+pub mod sync;
 
 pub use asset_types::AssetType;
 pub use error::FrontendError;
@@ -0,0 +1,238 @@
+//! Keep ES-DE metadata in step with the ROMs actually on disk.
+//!
+//! Scraping only ever adds to a system's media folder, and ES-DE edits its
+//! own gamelists, so entries and images for deleted ROMs pile up. A sync
+//! plan lists the `gamelist.xml` entries whose `<path>` no longer exists
+//! and the media files whose name matches no ROM; applying it removes them.
+//! Kept gamelist entries are written back byte-for-byte, so ES-DE's own
+//! fields (favorites, play counts) survive.
+
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use quick_xml::Reader;
+use quick_xml::events::Event;
+
+use crate::esde::EsDeFrontend;
+use crate::{Frontend, FrontendError};
+
+/// How deep to look for ROMs below the system folder (letter folders and
+/// `.m3u` directories).
+const MAX_ROM_DEPTH: usize = 3;
+
+/// What a sync would remove for one system.
+#[derive(Debug, Default)]
+pub struct SyncPlan {
+    /// The gamelist to rewrite, if it has stale entries.
+    pub gamelist: Option<PathBuf>,
+    /// `<path>` values of gamelist entries whose ROM is gone.
+    pub stale_entries: Vec<String>,
+    /// Media files that don't belong to any ROM.
+    pub orphaned_media: Vec<PathBuf>,
+    /// Gamelist contents with the stale entries cut out.
+    pruned_gamelist: Option<String>,
+}
+
+impl SyncPlan {
+    /// Whether there's nothing to prune.
+    pub fn is_empty(&self) -> bool {
+        self.stale_entries.is_empty() && self.orphaned_media.is_empty()
+    }
+}
+
+/// One top-level `<game>` or `<folder>` entry in a gamelist.
+struct GamelistEntry {
+    path: Option<String>,
+    /// Byte range of the element in the file.
+    span: Range<usize>,
+}
+
+/// Plan a sync of one system's gamelist and media against `rom_dir`.
+///
+/// `metadata_dir` and `media_dir` are the system's own folders (holding
+/// `gamelist.xml` and the media subfolders). A missing or empty ROM folder
+/// yields an empty plan, so an unmounted drive never wipes a library.
+pub fn plan_sync(
+    rom_dir: &Path,
+    metadata_dir: &Path,
+    media_dir: &Path,
+) -> Result<SyncPlan, FrontendError> {
+    let mut plan = SyncPlan::default();
+    let mut rom_stems = HashSet::new();
+    collect_stems(rom_dir, MAX_ROM_DEPTH, &mut rom_stems)?;
+    if rom_stems.is_empty() {
+        return Ok(plan);
+    }
+
+    let gamelist_path = metadata_dir.join("gamelist.xml");
+    if gamelist_path.is_file() {
+        let xml = fs::read_to_string(&gamelist_path)?;
+        let stale: Vec<GamelistEntry> = parse_entries(&xml)?
+            .into_iter()
+            .filter(|e| {
+                e.path
+                    .as_deref()
+                    .and_then(|p| resolve_rom_path(rom_dir, p))
+                    .is_some_and(|p| !p.exists())
+            })
+            .collect();
+        if !stale.is_empty() {
+            let spans: Vec<Range<usize>> = stale.iter().map(|e| e.span.clone()).collect();
+            plan.pruned_gamelist = Some(remove_spans(&xml, &spans));
+            plan.stale_entries = stale.into_iter().filter_map(|e| e.path).collect();
+            plan.gamelist = Some(gamelist_path);
+        }
+    }
+
+    for (subdir, _) in EsDeFrontend.asset_subdirs() {
+        let dir = media_dir.join(subdir);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name();
+            if name.to_string_lossy().starts_with('.') || !entry.file_type()?.is_file() {
+                continue;
+            }
+            let path = entry.path();
+            let stem = path.file_stem().unwrap_or_default().to_owned();
+            if !rom_stems.contains(&stem) {
+                plan.orphaned_media.push(path);
+            }
+        }
+    }
+    plan.orphaned_media.sort();
+
+    Ok(plan)
+}
+
+/// Rewrite the gamelist and delete orphaned media as planned.
+pub fn apply_sync(plan: &SyncPlan) -> Result<(), FrontendError> {
+    if let (Some(path), Some(xml)) = (&plan.gamelist, &plan.pruned_gamelist) {
+        fs::write(path, xml)?;
+    }
+    for path in &plan.orphaned_media {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Stems of every file and folder below `dir`, down to `depth` levels.
+fn collect_stems(
+    dir: &Path,
+    depth: usize,
+    stems: &mut HashSet<OsString>,
+) -> Result<(), FrontendError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if let Some(stem) = path.file_stem() {
+            stems.insert(stem.to_owned());
+        }
+        if depth > 1 && path.is_dir() {
+            collect_stems(&path, depth - 1, stems)?;
+        }
+    }
+    Ok(())
+}
+
+/// Resolve a gamelist `<path>` against the ROM folder. Paths using ES-DE
+/// placeholders such as `%ROMPATH%` can't be checked and are left alone.
+fn resolve_rom_path(rom_dir: &Path, path: &str) -> Option<PathBuf> {
+    if path.is_empty() || path.starts_with('%') {
+        return None;
+    }
+    Some(rom_dir.join(path))
+}
+
+fn parse_entries(xml: &str) -> Result<Vec<GamelistEntry>, FrontendError> {
+    let mut reader = Reader::from_str(xml);
+    let mut entries = Vec::new();
+    let mut depth = 0usize;
+    let mut current: Option<GamelistEntry> = None;
+    let mut in_path = false;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                depth += 1;
+                let name = e.name();
+                if depth == 2 && matches!(name.as_ref(), b"game" | b"folder") {
+                    current = Some(GamelistEntry {
+                        path: None,
+                        span: start..start,
+                    });
+                } else if depth == 3 && current.is_some() && name.as_ref() == b"path" {
+                    in_path = true;
+                }
+            }
+            Ok(Event::Text(t)) if in_path => {
+                let text = t
+                    .unescape()
+                    .map_err(|e| FrontendError::InvalidMetadata(format!("gamelist.xml: {}", e)))?;
+                if let Some(entry) = current.as_mut() {
+                    entry.path = Some(text.trim().to_string());
+                }
+            }
+            Ok(Event::End(_)) => {
+                if depth == 3 {
+                    in_path = false;
+                } else if depth == 2
+                    && let Some(mut entry) = current.take()
+                {
+                    entry.span.end = reader.buffer_position() as usize;
+                    entries.push(entry);
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(FrontendError::InvalidMetadata(format!(
+                    "gamelist.xml at position {}: {}",
+                    reader.error_position(),
+                    e
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Cut `spans` (sorted, non-overlapping) out of `xml`, along with the
+/// indentation before each and the line break after it.
+fn remove_spans(xml: &str, spans: &[Range<usize>]) -> String {
+    let bytes = xml.as_bytes();
+    let mut out = String::with_capacity(xml.len());
+    let mut last = 0;
+    for span in spans {
+        let mut start = span.start;
+        while start > last && matches!(bytes[start - 1], b' ' | b'\t') {
+            start -= 1;
+        }
+        let mut end = span.end;
+        if xml[end..].starts_with("\r\n") {
+            end += 2;
+        } else if xml[end..].starts_with('\n') {
+            end += 1;
+        }
+        out.push_str(&xml[last..start]);
+        last = end;
+    }
+    out.push_str(&xml[last..]);
+    out
+}
+
+#[cfg(test)]
+#[path = "tests/sync_tests.rs"]
+mod tests;
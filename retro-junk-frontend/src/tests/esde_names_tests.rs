@@ -0,0 +1,43 @@
+use super::*;
+
+#[test]
+fn maps_short_names_to_esde_systems() {
+    assert_eq!(system_name(Platform::Ps1), Some("psx"));
+    assert_eq!(system_name(Platform::N3ds), Some("n3ds"));
+    assert_eq!(system_name(Platform::GameCube), Some("gc"));
+    assert_eq!(system_name(Platform::Sg1000), Some("sg-1000"));
+    assert_eq!(system_name(Platform::Custom(0)), None);
+}
+
+#[test]
+fn every_builtin_platform_has_a_name() {
+    for platform in Platform::all() {
+        assert!(system_name(*platform).is_some(), "{:?}", platform);
+    }
+}
+
+#[test]
+fn output_name_keeps_valid_folders() {
+    // Regional variants are separate ES-DE systems
+    assert_eq!(
+        output_system_name(Platform::Genesis, "megadrive"),
+        "megadrive"
+    );
+    assert_eq!(output_system_name(Platform::Genesis, "Genesis"), "Genesis");
+    // Family folders share one gamelist
+    assert_eq!(output_system_name(Platform::Gba, "gb-family"), "gb-family");
+}
+
+#[test]
+fn output_name_maps_other_aliases() {
+    assert_eq!(output_system_name(Platform::Ps1, "ps1"), "psx");
+    assert_eq!(output_system_name(Platform::N3ds, "3ds"), "n3ds");
+    assert_eq!(
+        output_system_name(Platform::MasterSystem, "sms"),
+        "mastersystem"
+    );
+    assert_eq!(
+        output_system_name(Platform::Custom(0), "vectrex"),
+        "vectrex"
+    );
+}
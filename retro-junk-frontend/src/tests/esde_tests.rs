@@ -6,6 +6,8 @@ use std::collections::HashMap;
 fn test_format_esde_date() {
     assert_eq!(format_esde_date("1996-06-23"), "19960623T000000");
     assert_eq!(format_esde_date("19960623"), "19960623T000000");
+    assert_eq!(format_esde_date("1996-06"), "19960601T000000");
+    assert_eq!(format_esde_date("1996"), "19960101T000000");
 }
 
 #[test]
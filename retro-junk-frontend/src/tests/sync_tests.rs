@@ -0,0 +1,108 @@
+use super::*;
+
+const GAMELIST: &str = "<?xml version=\"1.0\"?>
+<gameList>
+  <game>
+    <path>./Kept (USA).sfc</path>
+    <name>Kept</name>
+    <favorite>true</favorite>
+  </game>
+  <game>
+    <path>./Gone (USA).sfc</path>
+    <name>Gone &amp; Forgotten</name>
+  </game>
+  <folder>
+    <path>./A</path>
+    <name>A</name>
+  </folder>
+  <game>
+    <path>%ROMPATH%/snes/Elsewhere.sfc</path>
+  </game>
+</gameList>
+";
+
+struct Library {
+    _dir: tempfile::TempDir,
+    roms: PathBuf,
+    metadata: PathBuf,
+    media: PathBuf,
+}
+
+fn library() -> Library {
+    let dir = tempfile::tempdir().unwrap();
+    let roms = dir.path().join("roms/snes");
+    let metadata = dir.path().join("roms-metadata/snes");
+    let media = dir.path().join("roms-media/snes");
+    fs::create_dir_all(roms.join("A")).unwrap();
+    fs::create_dir_all(&metadata).unwrap();
+    fs::create_dir_all(media.join("covers")).unwrap();
+    fs::create_dir_all(media.join("miximages")).unwrap();
+
+    fs::write(roms.join("Kept (USA).sfc"), b"rom").unwrap();
+    fs::write(roms.join("A").join("Nested (Japan).sfc"), b"rom").unwrap();
+    fs::write(metadata.join("gamelist.xml"), GAMELIST).unwrap();
+    for name in ["Kept (USA).png", "Nested (Japan).png", "Gone (USA).png"] {
+        fs::write(media.join("covers").join(name), b"png").unwrap();
+    }
+    fs::write(media.join("miximages").join("Gone (USA).png"), b"png").unwrap();
+    fs::write(media.join("covers").join(".DS_Store"), b"").unwrap();
+
+    Library {
+        _dir: dir,
+        roms,
+        metadata,
+        media,
+    }
+}
+
+#[test]
+fn plans_stale_entries_and_orphaned_media() {
+    let lib = library();
+    let plan = plan_sync(&lib.roms, &lib.metadata, &lib.media).unwrap();
+
+    assert_eq!(plan.stale_entries, vec!["./Gone (USA).sfc"]);
+    assert_eq!(plan.gamelist, Some(lib.metadata.join("gamelist.xml")));
+    assert_eq!(
+        plan.orphaned_media,
+        vec![
+            lib.media.join("covers/Gone (USA).png"),
+            lib.media.join("miximages/Gone (USA).png"),
+        ]
+    );
+
+    // Planning doesn't touch anything
+    assert!(lib.media.join("covers/Gone (USA).png").exists());
+    assert_eq!(
+        fs::read_to_string(lib.metadata.join("gamelist.xml")).unwrap(),
+        GAMELIST
+    );
+}
+
+#[test]
+fn apply_keeps_other_entries_verbatim() {
+    let lib = library();
+    let plan = plan_sync(&lib.roms, &lib.metadata, &lib.media).unwrap();
+    apply_sync(&plan).unwrap();
+
+    let xml = fs::read_to_string(lib.metadata.join("gamelist.xml")).unwrap();
+    assert!(!xml.contains("Gone"));
+    assert!(xml.contains("<favorite>true</favorite>"));
+    assert!(xml.contains("%ROMPATH%"));
+    assert!(xml.contains("  </game>\n  <folder>"));
+    assert!(!lib.media.join("covers/Gone (USA).png").exists());
+    assert!(lib.media.join("covers/Kept (USA).png").exists());
+    assert!(lib.media.join("covers/Nested (Japan).png").exists());
+
+    // A second pass finds nothing
+    let again = plan_sync(&lib.roms, &lib.metadata, &lib.media).unwrap();
+    assert!(again.is_empty());
+}
+
+#[test]
+fn missing_rom_folder_prunes_nothing() {
+    let lib = library();
+    fs::remove_dir_all(&lib.roms).unwrap();
+    let plan = plan_sync(&lib.roms, &lib.metadata, &lib.media).unwrap();
+    assert!(plan.is_empty());
+    assert!(plan.gamelist.is_none());
+}
@@ -80,7 +80,15 @@ impl RetroJunkApp {
         egui_extras::install_image_loaders(&cc.egui_ctx);
         crate::fonts::configure_cjk_fonts(&cc.egui_ctx);
         let (tx, rx) = mpsc::channel();
-        let context = Arc::new(retro_junk_lib::create_default_context());
+        let mut context = retro_junk_lib::create_default_context();
+        let platforms_path = retro_junk_lib::custom_platforms::default_config_path();
+        if let Err(e) =
+            retro_junk_lib::custom_platforms::register_from_file(&mut context, &platforms_path)
+        {
+            log::warn!("{}", e);
+        }
+        retro_junk_lib::esde_systems::EsSystems::discover().apply_to(&mut context);
+        let context = Arc::new(context);
         let settings = crate::settings::load_settings();
 
         // Try to open the catalog DB for title enrichment
@@ -122,7 +122,7 @@ fn scrape_media_for_selection(
         None => return,
     };
 
-    // Borrow the analyzer for extract_scraper_serial (UI thread only)
+    // Borrow the analyzer for serial normalization (UI thread only)
     let analyzer = app.context.get_by_platform(platform);
 
     // Collect work items from selected entries
@@ -145,7 +145,11 @@ fn scrape_media_for_selection(
             let serial = entry
                 .identification
                 .as_ref()
-                .and_then(|id| id.serial_number.clone());
+                .and_then(|id| id.serial_number.as_deref())
+                .map(|s| match analyzer {
+                    Some(a) => a.analyzer.normalize_serial(s),
+                    None => s.to_string(),
+                });
             let scraper_serial = serial
                 .as_deref()
                 .and_then(|s| analyzer.and_then(|a| a.analyzer.extract_scraper_serial(s)));
@@ -293,6 +297,7 @@ fn scrape_media_for_selection(
                     sha1: item.sha1.clone(),
                     platform: item.platform,
                     expects_serial: retro_junk_scraper::expects_serial(item.platform),
+                    parent_filename: None,
                 };
 
                 // Look up the game on ScreenScraper
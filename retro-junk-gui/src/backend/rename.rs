@@ -298,8 +298,13 @@ pub fn rename_selected_entries(app: &mut RetroJunkApp, console_idx: usize, ctx:
                 source_folder: source_folder.clone(),
                 discs: all_discs.clone(),
                 game_name_override: m3u_job.game_name_override.clone(),
+                create_folder: false,
             };
-            let m3u_result = retro_junk_lib::rename::execute_m3u_rename(&lib_job);
+            let m3u_result = retro_junk_lib::rename::execute_m3u_rename(
+                &lib_job,
+                retro_junk_lib::rename::LinkRename::default(),
+                None,
+            );
 
             let any_work = m3u_result.discs_renamed > 0
                 || m3u_result.playlist_written
@@ -433,9 +438,8 @@ fn get_target_rom_name(
                 .iter()
                 .find(|c| c.folder_name == folder_name)?;
             let registered = app.context.get_by_platform(console.platform)?;
-            let game_code = registered.analyzer.extract_dat_game_code(serial);
             if let retro_junk_dat::SerialLookupResult::Match(m) =
-                dat_index.match_by_serial(serial, game_code.as_deref())
+                dat_index.match_analyzer_serial(registered.analyzer.as_ref(), serial)
             {
                 return Some(dat_index.games[m.game_index].roms[m.rom_index].name.clone());
             }
@@ -533,11 +537,12 @@ fn sniff_detected_extension(
     context: &AnalysisContext,
     platform: retro_junk_lib::Platform,
 ) -> Option<String> {
-    let registered = context.get_by_platform(platform)?;
     let mut file = std::fs::File::open(file_path).ok()?;
     let opts = retro_junk_lib::AnalysisOptions::new()
         .quick(true)
         .file_path(file_path);
-    let info = registered.analyzer.analyze(&mut file, &opts).ok()?;
+    let (_, info) = context
+        .analyze_reader(&mut file, file_path, Some(&[platform]), &opts)
+        .ok()?;
     info.extra.get("detected_extension").cloned()
 }
@@ -79,7 +79,11 @@ pub fn quick_scan_console(app: &mut RetroJunkApp, console_idx: usize, ctx: &egui
         };
 
         let extensions = scanner::extension_set(registered.analyzer.file_extensions());
-        let entries = match scanner::scan_game_entries(&folder_path, &extensions) {
+        let entries = match scanner::scan_game_entries_with(
+            &folder_path,
+            &extensions,
+            &context.scan_options(),
+        ) {
             Ok(e) => e,
             Err(e) => {
                 log::warn!("Failed to scan {}: {}", folder_path.display(), e);
@@ -222,7 +226,7 @@ fn analyze_entries(
                         };
                         analyzer.analyze(&mut file, &file_options)
                     }
-                    Err(e) => Err(retro_junk_lib::AnalysisError::Io(e)),
+                    Err(e) => Err(retro_junk_lib::AnalysisError::from(e)),
                 };
 
                 let _ = tx.send(AppMessage::EntryAnalyzed {
@@ -246,7 +250,7 @@ fn analyze_entries(
                                 };
                                 analyzer.analyze(&mut file, &file_options)
                             }
-                            Err(e) => Err(retro_junk_lib::AnalysisError::Io(e)),
+                            Err(e) => Err(retro_junk_lib::AnalysisError::from(e)),
                         };
                         (path.clone(), result)
                     })
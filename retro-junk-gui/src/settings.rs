@@ -1,3 +1,4 @@
+use retro_junk_lib::Language;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -37,12 +38,19 @@ pub struct GeneralSettings {
     /// Relative paths resolve from the ROM root.
     #[serde(default, alias = "media_dir")]
     pub assets_dir: String,
+    /// Language region and language names are shown in.
+    #[serde(default = "default_display_language")]
+    pub display_language: Language,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_display_language() -> Language {
+    Language::English
+}
+
 fn default_metadata_dir() -> String {
     ".".to_string()
 }
@@ -54,6 +62,7 @@ impl Default for GeneralSettings {
             warn_on_region_override: true,
             metadata_dir: default_metadata_dir(),
             assets_dir: String::new(),
+            display_language: default_display_language(),
         }
     }
 }
@@ -142,29 +142,17 @@ fn resolve_dir(root_path: &Path, setting: &str) -> PathBuf {
     }
 }
 
-/// Subdirectory name for a media type (matches ES-DE layout).
-fn asset_subdir(mt: AssetType) -> &'static str {
-    match mt {
-        AssetType::Cover => "covers",
-        AssetType::Cover3D => "3dboxes",
-        AssetType::Screenshot => "screenshots",
-        AssetType::TitleScreen => "titlescreens",
-        AssetType::Marquee => "marquees",
-        AssetType::Video => "videos",
-        AssetType::Fanart => "fanart",
-        AssetType::PhysicalMedia => "physicalmedia",
-        AssetType::Miximage => "miximages",
-    }
-}
-
 /// All displayable media types in preferred display order.
 pub const DISPLAY_ASSET_TYPES: &[AssetType] = &[
     AssetType::Cover,
+    AssetType::BackCover,
     AssetType::Cover3D,
     AssetType::Screenshot,
     AssetType::TitleScreen,
     AssetType::Marquee,
     AssetType::PhysicalMedia,
+    AssetType::Label,
+    AssetType::Pcb,
     AssetType::Fanart,
     AssetType::Miximage,
 ];
@@ -178,7 +166,7 @@ pub fn collect_existing_assets(media_dir: &Path, rom_stem: &str) -> HashMap<Asse
         if mt == AssetType::Video {
             continue;
         }
-        let subdir = media_dir.join(asset_subdir(mt));
+        let subdir = media_dir.join(mt.media_subdir());
         let ext = mt.default_extension();
         let path = subdir.join(format!("{}.{}", rom_stem, ext));
         if path.exists() {
@@ -811,8 +799,7 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
                         && let Some(p) = platform
                         && let Some(ref registered) = context.get_by_platform(p)
                     {
-                        let game_code = registered.analyzer.extract_dat_game_code(serial);
-                        match dat.match_by_serial(serial, game_code.as_deref()) {
+                        match dat.match_analyzer_serial(registered.analyzer.as_ref(), serial) {
                             SerialLookupResult::Match(m) => {
                                 let game_name = dat.games[m.game_index].name.clone();
                                 let rom_name =
@@ -829,7 +816,7 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
                                 entry.status = EntryStatus::Ambiguous;
                                 entry.ambiguous_candidates = candidates;
                             }
-                            SerialLookupResult::NotFound => {}
+                            SerialLookupResult::NotFound | SerialLookupResult::Malformed => {}
                         }
                     }
 
@@ -968,8 +955,9 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
                     if let Some(ref mut discs) = entry.disc_identifications {
                         for disc in discs.iter_mut() {
                             if let Some(ref serial) = disc.identification.serial_number {
-                                let game_code = registered.analyzer.extract_dat_game_code(serial);
-                                match dat.match_by_serial(serial, game_code.as_deref()) {
+                                match dat
+                                    .match_analyzer_serial(registered.analyzer.as_ref(), serial)
+                                {
                                     SerialLookupResult::Match(m) => {
                                         let name = dat.games[m.game_index].name.clone();
                                         let rom_name =
@@ -992,7 +980,8 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
                                             }
                                         }
                                     }
-                                    SerialLookupResult::NotFound => {}
+                                    SerialLookupResult::NotFound
+                                    | SerialLookupResult::Malformed => {}
                                 }
                             }
                         }
@@ -1011,7 +1000,10 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
                             .unwrap_or_else(|| {
                                 let name_refs: Vec<&str> =
                                     matched_names.iter().map(|s| s.as_str()).collect();
-                                retro_junk_core::disc::derive_base_game_name(&name_refs)
+                                retro_junk_core::disc::derive_base_name(
+                                    &name_refs,
+                                    retro_junk_core::disc::PartKind::ALL,
+                                )
                             });
                         entry.dat_match = Some(DatMatchInfo {
                             game_name: combined,
@@ -1120,8 +1112,8 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
                         if let Some(ref id) = entry.identification
                             && let Some(ref serial) = id.serial_number
                         {
-                            let game_code = registered.analyzer.extract_dat_game_code(serial);
-                            match index.match_by_serial(serial, game_code.as_deref()) {
+                            match index.match_analyzer_serial(registered.analyzer.as_ref(), serial)
+                            {
                                 SerialLookupResult::Match(m) => {
                                     let game_name = index.games[m.game_index].name.clone();
                                     let rom_name =
@@ -1138,7 +1130,7 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
                                     entry.status = EntryStatus::Ambiguous;
                                     entry.ambiguous_candidates = candidates;
                                 }
-                                SerialLookupResult::NotFound => {
+                                SerialLookupResult::NotFound | SerialLookupResult::Malformed => {
                                     // Keep current status
                                 }
                             }
@@ -1162,8 +1154,9 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
 
                         for disc in discs.iter_mut() {
                             if let Some(ref serial) = disc.identification.serial_number {
-                                let game_code = registered.analyzer.extract_dat_game_code(serial);
-                                match index.match_by_serial(serial, game_code.as_deref()) {
+                                match index
+                                    .match_analyzer_serial(registered.analyzer.as_ref(), serial)
+                                {
                                     SerialLookupResult::Match(m) => {
                                         let name = index.games[m.game_index].name.clone();
                                         let rom_name = index.games[m.game_index].roms[m.rom_index]
@@ -1189,7 +1182,8 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
                                             }
                                         }
                                     }
-                                    SerialLookupResult::NotFound => {}
+                                    SerialLookupResult::NotFound
+                                    | SerialLookupResult::Malformed => {}
                                 }
                             }
 
@@ -1227,7 +1221,10 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
                                 .unwrap_or_else(|| {
                                     let name_refs: Vec<&str> =
                                         matched_names.iter().map(|s| s.as_str()).collect();
-                                    retro_junk_core::disc::derive_base_game_name(&name_refs)
+                                    retro_junk_core::disc::derive_base_name(
+                                        &name_refs,
+                                        retro_junk_core::disc::PartKind::ALL,
+                                    )
                                 });
                             entry.dat_match = Some(DatMatchInfo {
                                 game_name: combined,
@@ -1389,7 +1386,10 @@ pub fn handle_message(app: &mut RetroJunkApp, msg: AppMessage, ctx: &egui::Conte
                                     d.dat_match.as_ref().map(|dm| dm.game_name.as_str())
                                 })
                                 .collect();
-                            retro_junk_core::disc::derive_base_game_name(&names)
+                            retro_junk_core::disc::derive_base_name(
+                                &names,
+                                retro_junk_core::disc::PartKind::ALL,
+                            )
                         });
                     let first_match = discs.iter().find_map(|d| d.dat_match.as_ref());
                     let first_rom_name = first_match
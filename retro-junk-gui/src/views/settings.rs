@@ -1,3 +1,5 @@
+use retro_junk_lib::Language;
+
 use crate::app::RetroJunkApp;
 
 /// Render the Settings view.
@@ -95,6 +97,28 @@ fn show_library_section(ui: &mut egui::Ui, app: &mut RetroJunkApp) {
         &mut app.settings.general.warn_on_region_override,
         "Warn when overriding a specific detected region",
     );
+
+    // Language for region and language names
+    ui.horizontal(|ui| {
+        ui.label("Display language:");
+        let current = app.settings.general.display_language;
+        egui::ComboBox::from_id_salt("display_language_combo")
+            .selected_text(current.localized_name(current))
+            .show_ui(ui, |ui| {
+                for &language in Language::DISPLAY {
+                    if ui
+                        .selectable_label(current == language, language.localized_name(language))
+                        .clicked()
+                    {
+                        app.settings.general.display_language = language;
+                        let _ = crate::settings::save_settings(&app.settings);
+                    }
+                }
+            });
+    });
+    ui.indent("display_language_hint", |ui| {
+        ui.weak("Used for region and language names.");
+    });
 }
 
 fn show_output_directories_section(ui: &mut egui::Ui, app: &mut RetroJunkApp) {
@@ -107,6 +107,7 @@ fn show_stats_section(ui: &mut egui::Ui, state: &ToolsState) {
             stat_row(ui, "Media entries", stats.media);
             stat_row(ui, "Assets", stats.assets);
             stat_row(ui, "Collection (owned)", stats.collection_owned);
+            stat_row(ui, "Works owned", stats.works_owned);
             stat_row(
                 ui,
                 "Unresolved disagreements",
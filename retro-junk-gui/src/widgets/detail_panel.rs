@@ -7,6 +7,7 @@ use crate::state::{DISPLAY_ASSET_TYPES, EntryStatus};
 pub fn show(ui: &mut egui::Ui, app: &mut RetroJunkApp) {
     ui.heading("Details");
     ui.separator();
+    let display_language = app.settings.general.display_language;
 
     let (console_idx, entry_idx) = match (app.selected_console, app.focused_entry) {
         (Some(ci), Some(ei)) => (ci, ei),
@@ -140,13 +141,16 @@ pub fn show(ui: &mut egui::Ui, app: &mut RetroJunkApp) {
             if detected_regions.is_empty() {
                 "Unknown".to_string()
             } else {
-                let names: Vec<&str> = detected_regions.iter().map(|r| r.name()).collect();
+                let names: Vec<&str> = detected_regions
+                    .iter()
+                    .map(|r| r.localized_name(display_language))
+                    .collect();
                 format!("Auto-detect ({})", names.join(", "))
             }
         } else {
             effective
                 .first()
-                .map(|r| r.name().to_string())
+                .map(|r| r.localized_name(display_language).to_string())
                 .unwrap_or_else(|| "Unknown".to_string())
         };
 
@@ -164,7 +168,10 @@ pub fn show(ui: &mut egui::Ui, app: &mut RetroJunkApp) {
                     let auto_label = if detected_regions.is_empty() {
                         "Auto-detect".to_string()
                     } else {
-                        let names: Vec<&str> = detected_regions.iter().map(|r| r.name()).collect();
+                        let names: Vec<&str> = detected_regions
+                            .iter()
+                            .map(|r| r.localized_name(display_language))
+                            .collect();
                         format!("Auto-detect ({})", names.join(", "))
                     };
                     if ui
@@ -181,7 +188,10 @@ pub fn show(ui: &mut egui::Ui, app: &mut RetroJunkApp) {
                         ui.label(egui::RichText::new("Detected:").weak().small());
                         for &r in &detected_regions {
                             if ui
-                                .selectable_label(current_override == Some(r), r.name())
+                                .selectable_label(
+                                    current_override == Some(r),
+                                    r.localized_name(display_language),
+                                )
                                 .clicked()
                             {
                                 new_override = Some(r);
@@ -192,7 +202,10 @@ pub fn show(ui: &mut egui::Ui, app: &mut RetroJunkApp) {
                         for &r in Region::ALL {
                             if !detected_regions.contains(&r)
                                 && ui
-                                    .selectable_label(current_override == Some(r), r.name())
+                                    .selectable_label(
+                                        current_override == Some(r),
+                                        r.localized_name(display_language),
+                                    )
                                     .clicked()
                             {
                                 new_override = Some(r);
@@ -202,7 +215,10 @@ pub fn show(ui: &mut egui::Ui, app: &mut RetroJunkApp) {
                         // Specific (1) or none: show all regions flat
                         for &r in Region::ALL {
                             if ui
-                                .selectable_label(current_override == Some(r), r.name())
+                                .selectable_label(
+                                    current_override == Some(r),
+                                    r.localized_name(display_language),
+                                )
                                 .clicked()
                             {
                                 new_override = Some(r);
@@ -240,7 +256,7 @@ pub fn show(ui: &mut egui::Ui, app: &mut RetroJunkApp) {
                             "\u{26a0} Overriding detected region ({})",
                             detected_regions
                                 .iter()
-                                .map(|r| r.name())
+                                .map(|r| r.localized_name(display_language))
                                 .collect::<Vec<_>>()
                                 .join(", ")
                         ))
@@ -299,14 +315,30 @@ pub fn show(ui: &mut egui::Ui, app: &mut RetroJunkApp) {
             if let Some(ref name) = id.internal_name {
                 detail_row(ui, "Internal Name", name);
             }
+            let mut titles: Vec<_> = id.titles.iter().collect();
+            titles.sort();
+            for (language, title) in titles {
+                detail_row(
+                    ui,
+                    &format!("Title ({})", language.localized_name(display_language)),
+                    title,
+                );
+            }
             if let Some(ref maker) = id.maker_code {
                 detail_row(ui, "Maker", maker);
             }
             if let Some(ref version) = id.version {
                 detail_row(ui, "Version", version);
             }
+            if let Some(date) = id.build_date {
+                detail_row(ui, "Build Date", &date.to_string());
+            }
             if !id.regions.is_empty() {
-                let regions: Vec<&str> = id.regions.iter().map(|r| r.name()).collect();
+                let regions: Vec<&str> = id
+                    .regions
+                    .iter()
+                    .map(|r| r.localized_name(display_language))
+                    .collect();
                 detail_row(ui, "Region", &regions.join(", "));
             }
 
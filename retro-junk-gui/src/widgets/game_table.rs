@@ -466,6 +466,7 @@ fn show_set_region_submenu(ui: &mut egui::Ui, app: &mut RetroJunkApp, console_id
         }
     }
     let recommended = recommended.unwrap_or_default();
+    let display_language = app.settings.general.display_language;
 
     ui.menu_button("Set Region", |ui| {
         if ui.button("Auto-detect").clicked() {
@@ -482,7 +483,9 @@ fn show_set_region_submenu(ui: &mut egui::Ui, app: &mut RetroJunkApp, console_id
             ui.separator();
             ui.label("Recommended");
             for &region in Region::ALL {
-                if recommended.contains(&region) && ui.button(region.name()).clicked() {
+                if recommended.contains(&region)
+                    && ui.button(region.localized_name(display_language)).clicked()
+                {
                     for &i in &app.selected_entries.clone() {
                         if let Some(entry) = app.library.consoles[console_idx].entries.get_mut(i) {
                             entry.region_override = Some(region);
@@ -497,7 +500,9 @@ fn show_set_region_submenu(ui: &mut egui::Ui, app: &mut RetroJunkApp, console_id
         ui.separator();
         ui.label("Other Regions");
         for &region in Region::ALL {
-            if !recommended.contains(&region) && ui.button(region.name()).clicked() {
+            if !recommended.contains(&region)
+                && ui.button(region.localized_name(display_language)).clicked()
+            {
                 for &i in &app.selected_entries.clone() {
                     if let Some(entry) = app.library.consoles[console_idx].entries.get_mut(i) {
                         entry.region_override = Some(region);
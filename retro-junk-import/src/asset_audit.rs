@@ -0,0 +1,281 @@
+//! Integrity audits for media asset files referenced by the catalog.
+//!
+//! Every downloaded or imported asset records a SHA1 of its file in
+//! `media_assets.file_hash`. Auditing re-hashes the files on disk and reports
+//! any that have gone missing or changed since they were recorded.
+//!
+//! Pruning compares the asset directory with the catalog the other way
+//! round: files nobody references, and rows whose files are gone.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use retro_junk_catalog::types::Asset;
+use retro_junk_db::{operations, queries};
+use retro_junk_scraper::ScreenScraperClient;
+use retro_junk_scraper::assets::{asset_file_hash, download_verified_media};
+use retro_junk_scraper::types::Media;
+use rusqlite::Connection;
+
+/// Statistics from an asset verification run.
+#[derive(Debug, Default)]
+pub struct AssetVerifyStats {
+    pub checked: u64,
+    pub verified: u64,
+    pub missing: u64,
+    pub mismatched: u64,
+    /// Assets without a recorded hash that were hashed and updated.
+    pub hashed: u64,
+    pub errors: u64,
+}
+
+/// An asset whose file no longer matches the catalog.
+#[derive(Debug)]
+pub struct AssetProblem {
+    pub asset_id: i64,
+    pub release_id: Option<String>,
+    pub asset_type: String,
+    pub path: PathBuf,
+    pub missing: bool,
+}
+
+/// Result of verifying catalog assets.
+pub struct AssetVerifyResult {
+    pub stats: AssetVerifyStats,
+    pub problems: Vec<AssetProblem>,
+}
+
+/// Verify asset files against their recorded hashes.
+///
+/// Assets recorded before hashes were stored get their hash backfilled
+/// instead of being reported.
+pub fn verify_assets(
+    conn: &Connection,
+    platform_id: Option<&str>,
+) -> Result<AssetVerifyResult, operations::OperationError> {
+    let mut stats = AssetVerifyStats::default();
+    let mut problems = Vec::new();
+
+    for asset in queries::list_assets(conn, platform_id)? {
+        let Some(path) = asset.file_path.as_deref().map(PathBuf::from) else {
+            continue;
+        };
+        stats.checked += 1;
+
+        let problem = |missing: bool, path: PathBuf| AssetProblem {
+            asset_id: asset.id,
+            release_id: asset.release_id.clone(),
+            asset_type: asset.asset_type.clone(),
+            path,
+            missing,
+        };
+
+        if !path.exists() {
+            stats.missing += 1;
+            problems.push(problem(true, path));
+            continue;
+        }
+
+        let actual = match hash_asset_file(&path) {
+            Ok(h) => h,
+            Err(e) => {
+                log::warn!("Error reading asset {}: {}", path.display(), e);
+                stats.errors += 1;
+                continue;
+            }
+        };
+
+        match asset.file_hash.as_deref() {
+            Some(expected) if expected.eq_ignore_ascii_case(&actual) => stats.verified += 1,
+            Some(_) => {
+                stats.mismatched += 1;
+                problems.push(problem(false, path));
+            }
+            None => {
+                operations::update_asset_hash(conn, asset.id, &actual)?;
+                stats.hashed += 1;
+            }
+        }
+    }
+
+    Ok(AssetVerifyResult { stats, problems })
+}
+
+/// Where the asset directory and the catalog disagree.
+#[derive(Debug, Default)]
+pub struct AssetPrunePlan {
+    /// Files under the asset directory that no asset row points at.
+    pub orphaned_files: Vec<PathBuf>,
+    /// Asset rows whose file no longer exists.
+    pub missing: Vec<Asset>,
+}
+
+impl AssetPrunePlan {
+    pub fn is_empty(&self) -> bool {
+        self.orphaned_files.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Statistics from applying an [`AssetPrunePlan`].
+#[derive(Debug, Default)]
+pub struct AssetPruneStats {
+    pub files_deleted: u64,
+    pub rows_deleted: u64,
+    pub errors: u64,
+}
+
+/// Find orphaned files under `asset_dir` and asset rows with missing files.
+///
+/// Hidden files are ignored. Rows are checked wherever their files live, not
+/// only under `asset_dir`.
+pub fn plan_asset_prune(
+    conn: &Connection,
+    asset_dir: &Path,
+) -> Result<AssetPrunePlan, operations::OperationError> {
+    let mut plan = AssetPrunePlan::default();
+    let mut referenced = HashSet::new();
+
+    for asset in queries::list_assets(conn, None)? {
+        let Some(path) = asset.file_path.as_deref().map(PathBuf::from) else {
+            continue;
+        };
+        if path.exists() {
+            referenced.insert(normalize(&path));
+        } else {
+            plan.missing.push(asset);
+        }
+    }
+
+    let mut files = Vec::new();
+    collect_files(asset_dir, &mut files);
+    files.sort();
+    plan.orphaned_files = files
+        .into_iter()
+        .filter(|f| !referenced.contains(&normalize(f)))
+        .collect();
+
+    Ok(plan)
+}
+
+/// Delete the plan's orphaned files and the rows of its missing assets.
+///
+/// Directories left empty by the deletions are removed as well.
+pub fn apply_asset_prune(
+    conn: &Connection,
+    plan: &AssetPrunePlan,
+) -> Result<AssetPruneStats, operations::OperationError> {
+    let mut stats = AssetPruneStats::default();
+
+    for path in &plan.orphaned_files {
+        match std::fs::remove_file(path) {
+            Ok(()) => {
+                stats.files_deleted += 1;
+                if let Some(parent) = path.parent() {
+                    // Only succeeds if nothing else is left in it
+                    let _ = std::fs::remove_dir(parent);
+                }
+            }
+            Err(e) => {
+                log::warn!("Failed to delete {}: {}", path.display(), e);
+                stats.errors += 1;
+            }
+        }
+    }
+
+    for asset in &plan.missing {
+        operations::delete_asset(conn, asset.id)?;
+        stats.rows_deleted += 1;
+    }
+
+    Ok(stats)
+}
+
+/// Re-download missing assets from their recorded source URLs.
+///
+/// Downloads are checked against the asset's recorded hash and retried on a
+/// mismatch, as scraping does. Restored files get their hash updated. Returns
+/// the assets that couldn't be restored (no source URL, or the download
+/// failed or never matched).
+pub async fn redownload_assets(
+    client: &ScreenScraperClient,
+    conn: &Connection,
+    missing: &[Asset],
+) -> Result<Vec<Asset>, operations::OperationError> {
+    let mut failed = Vec::new();
+
+    for asset in missing {
+        let (Some(url), Some(path)) = (asset.source_url.as_deref(), asset.file_path.as_deref())
+        else {
+            failed.push(asset.clone());
+            continue;
+        };
+        let path = Path::new(path);
+
+        // The recorded hash is what the file should come back as; without one
+        // the download is taken as-is
+        let media = Media {
+            media_type: asset.asset_type.clone(),
+            url: url.to_string(),
+            region: asset.region.clone().unwrap_or_default(),
+            format: String::new(),
+            crc: None,
+            md5: None,
+            sha1: asset.file_hash.clone(),
+            size: None,
+        };
+        let data = match download_verified_media(client, &media).await {
+            Ok(data) => data,
+            Err(e) => {
+                log::warn!("Failed to re-download {}: {}", path.display(), e);
+                failed.push(asset.clone());
+                continue;
+            }
+        };
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(path, &data));
+        if let Err(e) = written {
+            log::warn!("Failed to write {}: {}", path.display(), e);
+            failed.push(asset.clone());
+            continue;
+        }
+        operations::update_asset_hash(conn, asset.id, &asset_file_hash(&data))?;
+    }
+
+    Ok(failed)
+}
+
+/// Recursively list non-hidden files under `dir`.
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("Failed to read {}: {}", dir.display(), e);
+            }
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, out);
+        } else {
+            out.push(path);
+        }
+    }
+}
+
+/// Resolve a path for comparison, falling back to the path as given.
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn hash_asset_file(path: &Path) -> std::io::Result<String> {
+    let bytes = std::fs::read(path)?;
+    Ok(asset_file_hash(&bytes))
+}
@@ -0,0 +1,158 @@
+//! Rule-based resolution of disagreements between data sources.
+//!
+//! Most disagreements follow a pattern a curator would resolve the same way
+//! every time (e.g. Redump dates are authoritative, the longer synopsis is
+//! the better one). Rules loaded from `catalog/resolutions/` decide those
+//! automatically after an import or enrichment. Each decision writes the
+//! chosen value and marks the disagreement resolved with a resolution string
+//! naming the rule, so automatic resolutions stay auditable and can be told
+//! apart from manual ones.
+
+use std::collections::BTreeMap;
+
+use retro_junk_catalog::types::{Disagreement, ResolutionRule, ResolutionStrategy};
+use retro_junk_db::{DisagreementFilter, OperationError, operations, queries};
+use rusqlite::Connection;
+
+use crate::dat_import::ImportError;
+
+/// Which side of a disagreement a rule picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionSide {
+    SourceA,
+    SourceB,
+}
+
+impl ResolutionSide {
+    /// The resolution string used for manual resolutions of the same side.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SourceA => "source_a",
+            Self::SourceB => "source_b",
+        }
+    }
+}
+
+/// Statistics from an auto-resolution pass.
+#[derive(Debug, Default)]
+pub struct AutoResolveStats {
+    /// Disagreements resolved by a rule.
+    pub resolved: u64,
+    /// Disagreements a rule decided but whose field can't be written back.
+    pub skipped: u64,
+    /// Resolved count per field.
+    pub by_field: BTreeMap<String, u64>,
+}
+
+/// Decide a disagreement with a single rule.
+///
+/// Returns `None` when the rule doesn't apply (different field or entity
+/// type) or can't pick a side (e.g. the preferred source isn't involved, or
+/// both values are the same length).
+pub fn decide_disagreement(rule: &ResolutionRule, d: &Disagreement) -> Option<ResolutionSide> {
+    if rule.field != d.field {
+        return None;
+    }
+    if rule
+        .entity_type
+        .as_ref()
+        .is_some_and(|et| *et != d.entity_type)
+    {
+        return None;
+    }
+    let a = d.value_a.as_deref()?;
+    let b = d.value_b.as_deref()?;
+
+    match rule.strategy {
+        ResolutionStrategy::PreferSource => {
+            let source = rule.source.as_deref()?;
+            if d.source_a == source {
+                Some(ResolutionSide::SourceA)
+            } else if d.source_b == source {
+                Some(ResolutionSide::SourceB)
+            } else {
+                None
+            }
+        }
+        ResolutionStrategy::Longest | ResolutionStrategy::Shortest => {
+            let (len_a, len_b) = (a.chars().count(), b.chars().count());
+            if len_a == len_b {
+                return None;
+            }
+            let a_longer = len_a > len_b;
+            let want_longer = rule.strategy == ResolutionStrategy::Longest;
+            if a_longer == want_longer {
+                Some(ResolutionSide::SourceA)
+            } else {
+                Some(ResolutionSide::SourceB)
+            }
+        }
+        ResolutionStrategy::PreferExisting => Some(ResolutionSide::SourceA),
+        ResolutionStrategy::PreferIncoming => Some(ResolutionSide::SourceB),
+    }
+}
+
+/// Resolve unresolved disagreements using `rules`, in order.
+///
+/// Each rule only sees disagreements that earlier rules left unresolved. The
+/// chosen value is written to the entity and the disagreement is marked
+/// resolved as e.g. `source_b (auto: prefer gdb)`. Disagreements on fields
+/// that can't be written back are left for manual review. With `dry_run`,
+/// the pass runs in a transaction that is rolled back, so the stats report
+/// what would be resolved without changing anything.
+pub fn auto_resolve_disagreements(
+    conn: &Connection,
+    rules: &[ResolutionRule],
+    dry_run: bool,
+) -> Result<AutoResolveStats, ImportError> {
+    let mut stats = AutoResolveStats::default();
+    if rules.is_empty() {
+        return Ok(stats);
+    }
+
+    let tx = conn.unchecked_transaction()?;
+
+    for rule in rules {
+        let filter = DisagreementFilter {
+            entity_type: rule.entity_type.as_deref(),
+            field: Some(&rule.field),
+            platform_id: rule.platform_id.as_deref(),
+            limit: Some(u32::MAX),
+        };
+        for d in queries::list_unresolved_disagreements(&tx, &filter)? {
+            let Some(side) = decide_disagreement(rule, &d) else {
+                continue;
+            };
+            let value = match side {
+                ResolutionSide::SourceA => d.value_a.as_deref(),
+                ResolutionSide::SourceB => d.value_b.as_deref(),
+            };
+            let Some(value) = value else { continue };
+
+            match operations::apply_disagreement_resolution(
+                &tx,
+                &d.entity_type,
+                &d.entity_id,
+                &d.field,
+                value,
+            ) {
+                Ok(()) => {}
+                Err(OperationError::InvalidField(_)) => {
+                    stats.skipped += 1;
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            }
+            let resolution = format!("{} (auto: {})", side.as_str(), rule.describe());
+            operations::resolve_disagreement(&tx, d.id, &resolution)?;
+
+            stats.resolved += 1;
+            *stats.by_field.entry(d.field.clone()).or_default() += 1;
+        }
+    }
+
+    if !dry_run {
+        tx.commit()?;
+    }
+    Ok(stats)
+}
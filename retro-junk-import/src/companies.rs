@@ -0,0 +1,221 @@
+//! Matching company names from enrichment sources against known companies.
+//!
+//! ScreenScraper and GameDataBase spell publishers and developers many ways
+//! ("Nintendo", "Nintendo Co., Ltd.", "NINTENDO"). Names that match a company
+//! or one of its aliases are linked directly. Anything else is staged as a
+//! pending alias instead of becoming a new company, with a suggested company
+//! when the name only differs by punctuation or a legal suffix. Releases that
+//! used a staged name are linked once it's approved.
+
+use retro_junk_catalog::types::Company;
+use retro_junk_catalog::{fold_diacritics, slugify};
+use retro_junk_db::{operations, queries};
+use rusqlite::Connection;
+
+use crate::ImportError;
+
+/// Trailing words that don't distinguish one company from another.
+const LEGAL_SUFFIXES: &[&str] = &[
+    "ag",
+    "co",
+    "company",
+    "corp",
+    "corporation",
+    "gmbh",
+    "inc",
+    "incorporated",
+    "kk",
+    "limited",
+    "llc",
+    "ltd",
+    "plc",
+    "sa",
+    "srl",
+];
+
+/// Outcome of looking up a company name from an enrichment source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompanyMatch {
+    /// The name belongs to a known company.
+    Known(String),
+    /// The name is waiting for review. `new` is set when this lookup staged it.
+    Staged { new: bool },
+    /// The name was reviewed and rejected.
+    Rejected,
+}
+
+impl CompanyMatch {
+    /// The matched company, if the name is known.
+    pub fn company_id(&self) -> Option<&str> {
+        match self {
+            Self::Known(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Whether this lookup added a new name to the review queue.
+    pub fn newly_staged(&self) -> bool {
+        matches!(self, Self::Staged { new: true })
+    }
+}
+
+/// Result of approving a staged company name.
+#[derive(Debug, Clone)]
+pub struct ApprovedAlias {
+    pub company_id: String,
+    /// Whether a new company was created for the name.
+    pub company_created: bool,
+    /// Releases linked to the company.
+    pub releases_linked: u64,
+}
+
+/// Reduce a company name to a comparison key: lowercase alphanumerics with
+/// diacritics folded and legal suffixes ("Co., Ltd.", "Inc.") dropped.
+pub fn normalize_company_name(name: &str) -> String {
+    let lower = fold_diacritics(name).to_lowercase();
+    let mut words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    while words.len() > 1 && words.last().is_some_and(|w| LEGAL_SUFFIXES.contains(w)) {
+        words.pop();
+    }
+    words.concat()
+}
+
+/// Find a known company whose name or alias normalizes to the same key.
+///
+/// Returns `None` when nothing matches or the match is ambiguous.
+pub fn suggest_company(conn: &Connection, name: &str) -> Result<Option<String>, ImportError> {
+    let key = normalize_company_name(name);
+    if key.is_empty() {
+        return Ok(None);
+    }
+    let mut found: Option<String> = None;
+    for (company_id, known) in queries::company_names(conn)? {
+        if normalize_company_name(&known) != key {
+            continue;
+        }
+        match &found {
+            Some(id) if *id != company_id => return Ok(None),
+            _ => found = Some(company_id),
+        }
+    }
+    Ok(found)
+}
+
+/// Look up a company name reported by `source` as a release's `role`
+/// ("developer" or "publisher").
+///
+/// Unknown names are staged for review (or their use recorded, if already
+/// staged) so the release can be linked when the name is approved.
+pub fn resolve_company(
+    conn: &Connection,
+    name: &str,
+    source: &str,
+    release_id: &str,
+    role: &str,
+) -> Result<CompanyMatch, ImportError> {
+    if let Some(company_id) = operations::find_company_by_alias(conn, name)? {
+        return Ok(CompanyMatch::Known(company_id));
+    }
+
+    let slug = slugify(name);
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM companies WHERE id = ?1)",
+        [&slug],
+        |row| row.get(0),
+    )?;
+    if exists {
+        return Ok(CompanyMatch::Known(slug));
+    }
+
+    if let Some(pending) = queries::find_pending_company_alias(conn, name)? {
+        return match pending.status.as_str() {
+            // Re-seeding companies from YAML replaces their aliases, so an
+            // approved name may no longer be in company_aliases.
+            "approved" => match pending.resolved_company_id {
+                Some(company_id) => Ok(CompanyMatch::Known(company_id)),
+                None => Ok(CompanyMatch::Rejected),
+            },
+            "rejected" => Ok(CompanyMatch::Rejected),
+            _ => {
+                operations::record_pending_company_alias_use(conn, pending.id, release_id, role)?;
+                Ok(CompanyMatch::Staged { new: false })
+            }
+        };
+    }
+
+    let suggested = suggest_company(conn, name)?;
+    let pending_id = operations::stage_company_alias(conn, name, source, suggested.as_deref())?;
+    operations::record_pending_company_alias_use(conn, pending_id, release_id, role)?;
+    log::debug!(
+        "Staged company name for review: {} (suggested: {})",
+        name,
+        suggested.as_deref().unwrap_or("none"),
+    );
+    Ok(CompanyMatch::Staged { new: true })
+}
+
+/// Approve a staged company name.
+///
+/// The name becomes an alias of `company_id`, or of the suggested company
+/// when none is given. With neither, a new company is created from the name.
+pub fn approve_company_alias(
+    conn: &Connection,
+    pending_id: i64,
+    company_id: Option<&str>,
+) -> Result<ApprovedAlias, ImportError> {
+    let pending = queries::get_pending_company_alias(conn, pending_id)?.ok_or_else(|| {
+        retro_junk_db::OperationError::NotFound {
+            entity_type: "pending company alias".to_string(),
+            id: pending_id.to_string(),
+        }
+    })?;
+
+    let tx = conn.unchecked_transaction()?;
+    let (company_id, company_created) = match company_id.or(pending.suggested_company_id.as_deref())
+    {
+        Some(id) => {
+            if queries::get_company_name(&tx, id)?.is_none() {
+                return Err(retro_junk_db::OperationError::NotFound {
+                    entity_type: "company".to_string(),
+                    id: id.to_string(),
+                }
+                .into());
+            }
+            (id.to_string(), false)
+        }
+        None => {
+            let slug = slugify(&pending.alias);
+            if slug.is_empty() {
+                return Err(retro_junk_db::OperationError::InvalidField(format!(
+                    "Can't derive a company ID from '{}'; merge it into an existing company",
+                    pending.alias
+                ))
+                .into());
+            }
+            if queries::get_company_name(&tx, &slug)?.is_some() {
+                (slug, false)
+            } else {
+                let company = Company {
+                    id: slug,
+                    name: pending.alias.clone(),
+                    country: None,
+                    aliases: vec![pending.alias.clone()],
+                };
+                operations::upsert_company(&tx, &company)?;
+                (company.id, true)
+            }
+        }
+    };
+
+    let releases_linked = operations::approve_pending_company_alias(&tx, pending_id, &company_id)?;
+    tx.commit()?;
+
+    Ok(ApprovedAlias {
+        company_id,
+        company_created,
+        releases_linked,
+    })
+}
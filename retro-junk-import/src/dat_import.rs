@@ -4,15 +4,15 @@
 //! and status. These are mapped to Work → Release → Media entities in the database.
 
 use retro_junk_catalog::name_parser::{self, DumpStatus};
+use retro_junk_catalog::slugify;
 use retro_junk_catalog::types::*;
-use retro_junk_core::Platform;
+use retro_junk_core::RomAnalyzer;
 use retro_junk_dat::DatFile;
 use retro_junk_db::operations::{self, OperationError};
 use rusqlite::Connection;
 use thiserror::Error;
 
 use crate::progress::ImportProgress;
-use crate::slugify;
 
 #[derive(Debug, Error)]
 pub enum ImportError {
@@ -43,14 +43,15 @@ pub struct ImportStats {
 
 /// Import a parsed DAT file into the catalog database.
 ///
-/// `platform` identifies the target platform (converted to string at the DB boundary).
+/// `analyzer` is the target platform's analyzer; it supplies the platform
+/// (converted to string at the DB boundary) and the canonical serial form.
 /// `dat_source` is "no-intro" or "redump".
 ///
 /// The optional `progress` callback is invoked after each game is processed.
 pub fn import_dat(
     conn: &Connection,
     dat: &DatFile,
-    platform: Platform,
+    analyzer: &dyn RomAnalyzer,
     dat_source: &str,
     progress: Option<&dyn ImportProgress>,
 ) -> Result<ImportStats, ImportError> {
@@ -62,7 +63,7 @@ pub fn import_dat(
     let tx = conn.unchecked_transaction()?;
 
     for (i, game) in dat.games.iter().enumerate() {
-        import_game(&tx, game, platform, dat_source, &mut stats)?;
+        import_game(&tx, game, analyzer, dat_source, &mut stats)?;
 
         if let Some(p) = progress {
             p.on_game(i + 1, dat.games.len(), &game.name);
@@ -78,11 +79,11 @@ pub fn import_dat(
 fn import_game(
     conn: &Connection,
     game: &retro_junk_dat::DatGame,
-    platform: Platform,
+    analyzer: &dyn RomAnalyzer,
     dat_source: &str,
     stats: &mut ImportStats,
 ) -> Result<(), ImportError> {
-    let platform_id = platform.short_name();
+    let platform_id = analyzer.platform().short_name();
     let parsed = name_parser::parse_dat_name(&game.name);
 
     // Skip bad dumps by default
@@ -121,15 +122,20 @@ fn import_game(
     }
 
     // Generate work ID from title + platform
-    let work_id = make_work_id(&canonical_title, platform_id);
+    let mut work_id = make_work_id(&canonical_title, platform_id);
 
     // Find or create Work (check by generated ID, not by name, to avoid
     // false positives from cross-platform titles like "Tetris")
-    let work_exists: bool = conn.query_row(
+    let mut work_exists: bool = conn.query_row(
         "SELECT EXISTS(SELECT 1 FROM works WHERE id = ?1)",
         [&work_id],
         |row| row.get(0),
     )?;
+    if !work_exists && let Some(legacy_id) = find_legacy_work(conn, &canonical_title, platform_id)?
+    {
+        work_id = legacy_id;
+        work_exists = true;
+    }
     if work_exists {
         stats.works_existing += 1;
     } else {
@@ -201,7 +207,13 @@ fn import_game(
 
     // Create Media entries — one per ROM in the DatGame
     for rom in &game.roms {
-        let media_id = make_media_id(&effective_release_id, &rom.name);
+        let mut media_id = make_media_id(&effective_release_id, &rom.name);
+        if !rom.name.is_ascii() {
+            let legacy_id = format!("{}:{}", effective_release_id, legacy_slug(&rom.name));
+            if retro_junk_db::queries::get_media_by_id(conn, &legacy_id)?.is_some() {
+                media_id = legacy_id;
+            }
+        }
 
         // Check if this media already exists
         let existing = operations::find_media_by_dat_name(conn, &game.name)?;
@@ -222,7 +234,10 @@ fn import_game(
         let media = Media {
             id: media_id,
             release_id: effective_release_id.clone(),
-            media_serial: rom.serial.clone(),
+            media_serial: rom
+                .serial
+                .as_deref()
+                .map(|s| canonical_dat_serial(s, analyzer)),
             disc_number: parsed.disc_number.map(|n| n as i32),
             disc_label: parsed.disc_label.clone(),
             revision: parsed.revision.clone(),
@@ -293,6 +308,16 @@ fn compute_release_variant(parsed: &name_parser::ParsedDatName) -> String {
 
 // ── ID Generation ───────────────────────────────────────────────────────────
 
+/// A DAT serial in the analyzer's canonical form. DATs list the serials of
+/// a game with several as "SLUS-00594, SLUS-00595"; each is normalized.
+fn canonical_dat_serial(serial: &str, analyzer: &dyn RomAnalyzer) -> String {
+    serial
+        .split(',')
+        .map(|s| analyzer.normalize_serial(s))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Generate a stable work ID from title and platform.
 ///
 /// Uses a simple slug: lowercase, alphanumeric + hyphens.
@@ -301,6 +326,38 @@ fn make_work_id(title: &str, platform_id: &str) -> String {
     format!("{platform_id}:{slug}")
 }
 
+/// The slug older imports generated: every non-ASCII character was treated
+/// as a separator, so `Pokémon` became `pok-mon`.
+fn legacy_slug(s: &str) -> String {
+    s.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_ascii_lowercase()
+}
+
+/// Find a work whose ID was generated with [`legacy_slug`], when it differs
+/// from the current one. The work is only reused if its stored name is the
+/// same title.
+fn find_legacy_work(
+    conn: &Connection,
+    title: &str,
+    platform_id: &str,
+) -> Result<Option<String>, ImportError> {
+    if title.is_ascii() {
+        return Ok(None);
+    }
+    let legacy_id = format!("{platform_id}:{}", legacy_slug(title));
+    let name: Option<String> = conn
+        .query_row(
+            "SELECT canonical_name FROM works WHERE id = ?1",
+            [&legacy_id],
+            |row| row.get(0),
+        )
+        .ok();
+    Ok(name.filter(|n| n == title).map(|_| legacy_id))
+}
+
 /// Generate a stable release ID from work + platform + region + revision + variant.
 fn make_release_id(
     work_id: &str,
@@ -312,11 +369,11 @@ fn make_release_id(
     let mut id = format!("{work_id}:{platform_id}:{region}");
     if !revision.is_empty() {
         id.push(':');
-        id.push_str(&crate::slugify(revision));
+        id.push_str(&slugify(revision));
     }
     if !variant.is_empty() {
         id.push(':');
-        id.push_str(&crate::slugify(variant));
+        id.push_str(&slugify(variant));
     }
     id
 }
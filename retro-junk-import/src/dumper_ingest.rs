@@ -0,0 +1,320 @@
+//! Ingest fresh dumps from a cartridge dumper into the collection.
+//!
+//! Cartridge dumpers like the Retrode or GB Operator expose the inserted
+//! cartridge as a file on a mounted drive or write dumps into a folder. This
+//! module watches such a folder, and for each new dump: analyzes the header,
+//! verifies it against the catalog's No-Intro media, copies it into the
+//! library under its canonical name, and records it in the collection. A
+//! different file already at the canonical name is left alone and reported
+//! as a conflict.
+//!
+//! Watching is done by polling: dumpers write files in place, so a file is
+//! only considered ready once its size and modification time are unchanged
+//! across two polls.
+//!
+//! A long-running watcher keeps a [`DumperState`] file with its counters and
+//! the dumps it has handled, so after a restart it doesn't ingest the same
+//! files again.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use retro_junk_catalog::types::CollectionEntry;
+use retro_junk_core::{AnalysisOptions, Platform, RomAnalyzer};
+use retro_junk_db::{operations, queries};
+use retro_junk_lib::rename::target_filename_for_rename;
+use rusqlite::Connection;
+
+use crate::scan_import::{ScanError, find_matching_media, hash_file};
+
+/// Options for dump ingestion.
+pub struct DumperOptions {
+    /// Console folder in the library where verified dumps are copied.
+    pub library_dir: PathBuf,
+    /// User ID for collection entries (default: "default").
+    pub user_id: String,
+}
+
+/// Result of ingesting one dump.
+#[derive(Debug)]
+pub enum DumpOutcome {
+    /// Verified against the catalog, copied into the library and recorded.
+    Added {
+        title: String,
+        target: PathBuf,
+        /// The dump was already in the collection; the entry was refreshed.
+        already_owned: bool,
+    },
+    /// Not found in the catalog. Usually a dirty contact or unlisted cart.
+    Unverified {
+        crc32: String,
+        sha1: Option<String>,
+        /// Title from the ROM header, if the analyzer could read one.
+        header_title: Option<String>,
+    },
+    /// Verified, but the library already holds a different file under the
+    /// canonical name. Nothing was copied or recorded.
+    Conflict { title: String, target: PathBuf },
+}
+
+/// Verify a dump against the catalog and add it to the library.
+///
+/// The dump is copied rather than moved: dumper drives are often read-only,
+/// and the original is the user's only copy until the library write succeeds.
+pub fn ingest_dump(
+    conn: &Connection,
+    dump: &Path,
+    analyzer: &dyn RomAnalyzer,
+    platform: Platform,
+    options: &DumperOptions,
+) -> Result<DumpOutcome, ScanError> {
+    let hashes = hash_file(dump, analyzer)?;
+
+    let Some((media, title)) = find_matching_media(conn, &hashes, platform)? else {
+        return Ok(DumpOutcome::Unverified {
+            crc32: hashes.crc32,
+            sha1: hashes.sha1,
+            header_title: header_title(dump, analyzer),
+        });
+    };
+
+    let ext = dump.extension().map(|e| e.to_string_lossy().to_lowercase());
+    // The catalog holds game names, which have no extension of their own
+    let dat_name = media.dat_name.as_deref().unwrap_or(&title);
+    let filename = match ext.as_deref() {
+        Some(ext) => target_filename_for_rename(&format!("{}.{}", dat_name, ext), dump, Some(ext)),
+        None => dat_name.to_string(),
+    };
+    let target = options.library_dir.join(&filename);
+
+    if target.exists() {
+        let existing = hash_file(&target, analyzer)?;
+        if existing.crc32 != hashes.crc32 || existing.sha1 != hashes.sha1 {
+            return Ok(DumpOutcome::Conflict { title, target });
+        }
+    } else {
+        std::fs::create_dir_all(&options.library_dir)?;
+        let tmp = options.library_dir.join(format!("{}.part", filename));
+        std::fs::copy(dump, &tmp)?;
+        std::fs::rename(&tmp, &target)?;
+    }
+
+    let existing = queries::find_collection_entry(conn, &media.id, &options.user_id)?;
+    let already_owned = existing.is_some();
+    let entry = CollectionEntry {
+        id: 0,
+        media_id: media.id.clone(),
+        user_id: options.user_id.clone(),
+        owned: true,
+        condition: existing.as_ref().and_then(|e| e.condition.clone()),
+        notes: existing.as_ref().and_then(|e| e.notes.clone()),
+        date_acquired: existing
+            .and_then(|e| e.date_acquired)
+            .or_else(|| Some(chrono::Utc::now().format("%Y-%m-%d").to_string())),
+        rom_path: Some(target.to_string_lossy().to_string()),
+        verified_at: Some(chrono::Utc::now().to_rfc3339()),
+    };
+    operations::upsert_collection_entry(conn, &entry)?;
+
+    Ok(DumpOutcome::Added {
+        title,
+        target,
+        already_owned,
+    })
+}
+
+/// A dump the watcher has already handed out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandledDump {
+    pub path: PathBuf,
+    pub size: u64,
+    /// RFC 3339 modification time, with nanoseconds.
+    pub modified: Option<String>,
+}
+
+/// What a long-running watcher has done, saved between polls.
+///
+/// ```toml
+/// pid = 4242
+/// started_at = "2026-10-16T12:00:00+00:00"
+/// last_poll = "2026-10-16T12:30:00+00:00"
+/// added = 3
+/// unverified = 1
+/// conflicts = 0
+/// failed = 0
+///
+/// [[handled]]
+/// path = "/mnt/retrode/cart.sfc"
+/// size = 1048576
+/// modified = "2026-10-16T12:10:00.123456789Z"
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DumperState {
+    /// Process ID of the watcher that last wrote the file.
+    pub pid: Option<u32>,
+    pub started_at: Option<String>,
+    pub last_poll: Option<String>,
+    /// Set when the watcher shut down cleanly; cleared while it runs.
+    pub stopped_at: Option<String>,
+    #[serde(default)]
+    pub added: u64,
+    #[serde(default)]
+    pub unverified: u64,
+    #[serde(default)]
+    pub conflicts: u64,
+    #[serde(default)]
+    pub failed: u64,
+    #[serde(default)]
+    pub handled: Vec<HandledDump>,
+}
+
+impl DumperState {
+    /// Read a state file. A missing file is a fresh state.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents).map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e),
+                )
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write the state file. It is replaced in one step, so a watcher
+    /// stopped mid-write leaves the previous state behind.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string(self).map_err(std::io::Error::other)?;
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".part");
+        std::fs::write(&tmp, contents)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Count the outcome of one dump.
+    pub fn record(&mut self, outcome: &Result<DumpOutcome, ScanError>) {
+        match outcome {
+            Ok(DumpOutcome::Added { .. }) => self.added += 1,
+            Ok(DumpOutcome::Unverified { .. }) => self.unverified += 1,
+            Ok(DumpOutcome::Conflict { .. }) => self.conflicts += 1,
+            Err(_) => self.failed += 1,
+        }
+    }
+}
+
+/// Polls a folder for dumps that have finished writing.
+pub struct DumperWatcher {
+    folder: PathBuf,
+    extensions: Vec<String>,
+    /// Last observed (size, mtime) per file, and whether it was handed out.
+    seen: HashMap<PathBuf, (u64, Option<SystemTime>, bool)>,
+}
+
+impl DumperWatcher {
+    pub fn new(folder: &Path, analyzer: &dyn RomAnalyzer) -> Self {
+        Self {
+            folder: folder.to_path_buf(),
+            extensions: analyzer
+                .file_extensions()
+                .iter()
+                .map(|e| e.to_lowercase())
+                .collect(),
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Return files that are unchanged since the previous poll and haven't
+    /// been returned before. A file that changes is returned again once it
+    /// settles (e.g., a new cartridge dumped to the same filename).
+    pub fn poll(&mut self) -> std::io::Result<Vec<PathBuf>> {
+        let mut ready = Vec::new();
+        let mut present = Vec::new();
+
+        for entry in std::fs::read_dir(&self.folder)? {
+            let path = entry?.path();
+            let matches_ext = path
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .is_some_and(|e| self.extensions.contains(&e));
+            if !path.is_file() || !matches_ext {
+                continue;
+            }
+            let meta = std::fs::metadata(&path)?;
+            let state = (meta.len(), meta.modified().ok());
+            present.push(path.clone());
+
+            match self.seen.get_mut(&path) {
+                Some((size, mtime, handled)) if (*size, *mtime) == state => {
+                    if !*handled && state.0 > 0 {
+                        *handled = true;
+                        ready.push(path);
+                    }
+                }
+                _ => {
+                    self.seen.insert(path, (state.0, state.1, false));
+                }
+            }
+        }
+
+        self.seen.retain(|p, _| present.contains(p));
+        ready.sort();
+        Ok(ready)
+    }
+
+    /// The dumps handed out so far that are still in the folder.
+    pub fn handled(&self) -> Vec<HandledDump> {
+        let mut handled: Vec<HandledDump> = self
+            .seen
+            .iter()
+            .filter(|(_, (_, _, handled))| *handled)
+            .map(|(path, (size, mtime, _))| HandledDump {
+                path: path.clone(),
+                size: *size,
+                modified: mtime.map(format_mtime),
+            })
+            .collect();
+        handled.sort_by(|a, b| a.path.cmp(&b.path));
+        handled
+    }
+
+    /// Hand `path` out again once it's seen unchanged, for a dump that was
+    /// returned by [`poll`](Self::poll) but not processed.
+    pub fn release(&mut self, path: &Path) {
+        if let Some((_, _, handled)) = self.seen.get_mut(path) {
+            *handled = false;
+        }
+    }
+
+    /// Treat `dumps` as already handed out, as long as they haven't changed
+    /// since (a restarted watcher skips what it processed before).
+    pub fn mark_handled(&mut self, dumps: &[HandledDump]) {
+        for dump in dumps {
+            let mtime = dump.modified.as_deref().and_then(|m| {
+                DateTime::parse_from_rfc3339(m)
+                    .ok()
+                    .map(|t| SystemTime::from(t.with_timezone(&Utc)))
+            });
+            self.seen
+                .insert(dump.path.clone(), (dump.size, mtime, true));
+        }
+    }
+}
+
+fn format_mtime(mtime: SystemTime) -> String {
+    DateTime::<Utc>::from(mtime).to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+fn header_title(path: &Path, analyzer: &dyn RomAnalyzer) -> Option<String> {
+    let mut file = std::io::BufReader::new(std::fs::File::open(path).ok()?);
+    let options = AnalysisOptions::new().quick(true).file_path(path);
+    analyzer.analyze(&mut file, &options).ok()?.internal_name
+}
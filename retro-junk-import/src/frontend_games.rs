@@ -0,0 +1,132 @@
+//! Build frontend games from the catalog.
+//!
+//! Scraping a ROM folder builds a [`ScrapedGame`] from ScreenScraper's answer
+//! for each file. This builds the same values from what the catalog already
+//! holds: a release's enriched metadata and its downloaded assets. Any
+//! [`Frontend`](retro_junk_frontend::Frontend) can then write its metadata
+//! without going back to ScreenScraper.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use retro_junk_catalog::types::{Asset, Release};
+use retro_junk_db::{OperationError, queries};
+use retro_junk_frontend::{AssetType, ScrapedGame};
+use rusqlite::Connection;
+
+use crate::scraper_import::catalog_region_to_ss;
+
+/// Which media to build games for, and how their ROMs are named.
+#[derive(Debug, Clone, Default)]
+pub struct CatalogGameOptions {
+    /// ROM file extension, without the dot.
+    pub extension: String,
+    /// Only build games for media this user owns.
+    pub owned_by: Option<String>,
+}
+
+/// A release's assets by type, for [`ScrapedGame::assets`].
+///
+/// Assets without a file on disk, and of types frontends don't know, are
+/// left out. When a type has several, the one for the release's region wins.
+pub fn release_asset_paths(release: &Release, assets: &[Asset]) -> HashMap<AssetType, PathBuf> {
+    let region = catalog_region_to_ss(&release.region);
+    let mut paths: HashMap<AssetType, (PathBuf, bool)> = HashMap::new();
+    for asset in assets {
+        let Some(at) = AssetType::from_catalog_name(&asset.asset_type) else {
+            continue;
+        };
+        let Some(path) = asset.file_path.as_deref().map(PathBuf::from) else {
+            continue;
+        };
+        if !path.is_file() {
+            continue;
+        }
+        let regional = asset.region.as_deref() == Some(region);
+        let replace = match paths.get(&at) {
+            Some((_, had_regional)) => regional && !had_regional,
+            None => true,
+        };
+        if replace {
+            paths.insert(at, (path, regional));
+        }
+    }
+    paths
+        .into_iter()
+        .map(|(at, (path, _))| (at, path))
+        .collect()
+}
+
+/// Build the game for one ROM of a release from its catalog metadata.
+pub fn scraped_game_from_release(
+    conn: &Connection,
+    release: &Release,
+    rom_stem: &str,
+    rom_filename: &str,
+) -> Result<ScrapedGame, OperationError> {
+    let assets = queries::assets_for_release(conn, &release.id)?;
+    let company = |id: &Option<String>| match id {
+        Some(id) => queries::get_company_name(conn, id),
+        None => Ok(None),
+    };
+
+    Ok(ScrapedGame {
+        rom_stem: rom_stem.to_string(),
+        rom_filename: rom_filename.to_string(),
+        name: release.title.clone(),
+        description: release.description.clone(),
+        developer: company(&release.developer_id)?,
+        publisher: company(&release.publisher_id)?,
+        genre: release.genre.clone(),
+        players: release.players.clone(),
+        rating: release.rating.map(|r| r as f32),
+        release_date: release.release_date.clone(),
+        assets: release_asset_paths(release, &assets),
+        cover_title: release.cover_title.clone(),
+    })
+}
+
+/// Build a game for each of a platform's media, named by its DAT name.
+///
+/// Media without a DAT name are skipped, as are media whose ROM filename an
+/// earlier one already used.
+pub fn scraped_games_for_platform(
+    conn: &Connection,
+    platform_id: &str,
+    options: &CatalogGameOptions,
+) -> Result<Vec<ScrapedGame>, OperationError> {
+    let mut releases: HashMap<String, Option<Release>> = HashMap::new();
+    let mut filenames = HashSet::new();
+    let mut games = Vec::new();
+
+    for media in queries::media_for_platform(conn, platform_id, options.owned_by.as_deref())? {
+        let Some(dat_name) = media.dat_name.as_deref() else {
+            continue;
+        };
+        let rom_filename = if options.extension.is_empty() {
+            dat_name.to_string()
+        } else {
+            format!("{dat_name}.{}", options.extension)
+        };
+        if !filenames.insert(rom_filename.clone()) {
+            continue;
+        }
+        let release = match releases.entry(media.release_id.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                entry.insert(queries::get_release_by_id(conn, &media.release_id)?)
+            }
+        };
+        if let Some(release) = release {
+            games.push(scraped_game_from_release(
+                conn,
+                release,
+                dat_name,
+                &rom_filename,
+            )?);
+        }
+    }
+
+    Ok(games)
+}
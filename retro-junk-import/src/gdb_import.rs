@@ -7,15 +7,14 @@
 //! Data source: <https://github.com/PigSaint/GameDataBase>
 //! License: CC BY 4.0 — Attribution to PigSaint required.
 
-use retro_junk_catalog::types::Company;
 use retro_junk_dat::gdb::{self, GdbGame};
 use retro_junk_dat::gdb_cache;
-use retro_junk_db::{Connection, operations, queries};
+use retro_junk_db::{Connection, queries};
 use rusqlite::params;
 
 use crate::ImportError;
+use crate::companies;
 use crate::merge;
-use crate::slugify;
 
 /// Statistics from a GDB enrichment run.
 #[derive(Debug, Default)]
@@ -30,8 +29,8 @@ pub struct GdbEnrichStats {
     pub disagreements: u32,
     /// Media entries with no SHA1 hash (skipped)
     pub skipped_no_hash: u32,
-    /// Companies created during enrichment
-    pub companies_created: u32,
+    /// Unknown company names staged for review
+    pub companies_staged: u32,
 }
 
 /// Options for GDB enrichment.
@@ -203,13 +202,19 @@ fn enrich_release(
 
     // -- developer --
     if !gdb_game.developer.is_empty() {
-        let dev_id = find_or_create_company(conn, &gdb_game.developer, stats)?;
+        let found =
+            companies::resolve_company(conn, &gdb_game.developer, source, release_id, "developer")?;
+        if found.newly_staged() {
+            stats.companies_staged += 1;
+        }
         if release.developer_id.is_none() {
-            conn.execute(
-                "UPDATE releases SET developer_id = ?2, updated_at = datetime('now') WHERE id = ?1 AND developer_id IS NULL",
-                params![release_id, dev_id],
-            )?;
-            updated = true;
+            if let Some(dev_id) = found.company_id() {
+                conn.execute(
+                    "UPDATE releases SET developer_id = ?2, updated_at = datetime('now') WHERE id = ?1 AND developer_id IS NULL",
+                    params![release_id, dev_id],
+                )?;
+                updated = true;
+            }
         } else {
             let existing_name = release
                 .developer_id
@@ -233,13 +238,19 @@ fn enrich_release(
 
     // -- publisher --
     if !gdb_game.publisher.is_empty() {
-        let pub_id = find_or_create_company(conn, &gdb_game.publisher, stats)?;
+        let found =
+            companies::resolve_company(conn, &gdb_game.publisher, source, release_id, "publisher")?;
+        if found.newly_staged() {
+            stats.companies_staged += 1;
+        }
         if release.publisher_id.is_none() {
-            conn.execute(
-                "UPDATE releases SET publisher_id = ?2, updated_at = datetime('now') WHERE id = ?1 AND publisher_id IS NULL",
-                params![release_id, pub_id],
-            )?;
-            updated = true;
+            if let Some(pub_id) = found.company_id() {
+                conn.execute(
+                    "UPDATE releases SET publisher_id = ?2, updated_at = datetime('now') WHERE id = ?1 AND publisher_id IS NULL",
+                    params![release_id, pub_id],
+                )?;
+                updated = true;
+            }
         } else {
             let existing_name = release
                 .publisher_id
@@ -342,39 +353,3 @@ fn enrich_release(
 
     Ok(updated)
 }
-
-/// Find or create a company by name, returning its ID.
-fn find_or_create_company(
-    conn: &Connection,
-    name: &str,
-    stats: &mut GdbEnrichStats,
-) -> Result<String, ImportError> {
-    // Check by alias first
-    if let Some(company_id) = operations::find_company_by_alias(conn, name)? {
-        return Ok(company_id);
-    }
-
-    // Check by slug
-    let slug = slugify(name);
-    let exists: bool = conn.query_row(
-        "SELECT EXISTS(SELECT 1 FROM companies WHERE id = ?1)",
-        [&slug],
-        |row| row.get(0),
-    )?;
-    if exists {
-        return Ok(slug);
-    }
-
-    // Create new company
-    let company = Company {
-        id: slug.clone(),
-        name: name.to_string(),
-        country: None,
-        aliases: vec![name.to_string()],
-    };
-    operations::upsert_company(conn, &company)?;
-    stats.companies_created += 1;
-    log::debug!("Created new company: {} ({})", name, slug);
-
-    Ok(slug)
-}
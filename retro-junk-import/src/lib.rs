@@ -4,49 +4,58 @@
 //! merging data from multiple sources, detecting disagreements, and applying
 //! overrides.
 
+pub mod asset_audit;
+pub mod auto_resolve;
+pub mod companies;
 pub mod dat_import;
+pub mod dumper_ingest;
+pub mod frontend_games;
 pub mod gdb_import;
+pub mod listing_export;
 pub mod merge;
 pub mod progress;
 pub mod reconcile;
+pub mod refresh;
 pub mod scan_import;
 pub mod scraper_import;
+pub mod screenshot_import;
 
+pub use asset_audit::{
+    AssetProblem, AssetPrunePlan, AssetPruneStats, AssetVerifyResult, AssetVerifyStats,
+    apply_asset_prune, plan_asset_prune, redownload_assets, verify_assets,
+};
+pub use auto_resolve::{
+    AutoResolveStats, ResolutionSide, auto_resolve_disagreements, decide_disagreement,
+};
+pub use companies::{
+    ApprovedAlias, CompanyMatch, approve_company_alias, normalize_company_name, resolve_company,
+    suggest_company,
+};
 pub use dat_import::{ImportError, ImportStats, dat_source_str, import_dat, log_import};
+pub use dumper_ingest::{DumpOutcome, DumperOptions, DumperWatcher, ingest_dump};
+pub use frontend_games::{
+    CatalogGameOptions, release_asset_paths, scraped_game_from_release, scraped_games_for_platform,
+};
 pub use gdb_import::{GdbEnrichOptions, GdbEnrichStats, enrich_gdb};
+pub use listing_export::{
+    EmuMoviesNaming, EverDriveNaming, Listing, ListingEntry, ListingOptions, NAMING_CONVENTIONS,
+    NamingConvention, NoIntroNaming, build_listing, find_convention, write_smdb,
+};
 pub use merge::{apply_overrides, check_field, merge_release_fields};
 pub use progress::{ImportProgress, LogProgress, SilentProgress};
 pub use reconcile::{
     ReconcileError, ReconcileOptions, ReconcileResult, ReconcileStats, reconcile_works,
 };
+pub use refresh::{RefreshStats, refresh_collection};
 pub use scan_import::{
     ScanError, ScanOptions, ScanProgress, ScanResult, ScanStats, SilentScanProgress, VerifyStats,
     scan_folder, verify_collection,
 };
 pub use scraper_import::{
-    EnrichError, EnrichEvent, EnrichOptions, EnrichStats, catalog_region_to_ss, enrich_releases,
-    map_game_info, ss_media_type_to_asset_type, ss_region_to_catalog,
+    EnrichError, EnrichEvent, EnrichOptions, EnrichStats, FieldChange, catalog_region_to_ss,
+    enrich_releases, known_catalog_region, map_game_info, planned_enrichment_changes,
+    ss_media_type_to_asset_type, ss_region_to_catalog,
+};
+pub use screenshot_import::{
+    ScreenshotError, ScreenshotOptions, ScreenshotResult, ScreenshotStats, ingest_screenshots,
 };
-
-/// Convert a string to a URL-friendly slug (lowercase, hyphens, no trailing hyphen).
-pub(crate) fn slugify(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    let mut last_was_separator = false;
-
-    for c in s.chars() {
-        if c.is_ascii_alphanumeric() {
-            result.push(c.to_ascii_lowercase());
-            last_was_separator = false;
-        } else if !last_was_separator && !result.is_empty() {
-            result.push('-');
-            last_was_separator = true;
-        }
-    }
-
-    // Trim trailing separator
-    if result.ends_with('-') {
-        result.pop();
-    }
-
-    result
-}
@@ -0,0 +1,187 @@
+//! Export renamed catalog listings for ROM pack tools.
+//!
+//! A listing gives each of a platform's media the path it should have in a
+//! pack, named by a [`NamingConvention`], along with its hashes. Listings are
+//! written in the `.smdb` format EverDrive pack maintainers use: one
+//! tab-separated line per file with its SHA-256, path, SHA-1, MD5 and CRC32.
+//!
+//! The catalog doesn't record SHA-256, so that column is left empty; tools
+//! that match on it need the file's other hashes instead.
+
+use std::collections::HashSet;
+use std::io::Write;
+
+use retro_junk_catalog::name_parser::parse_dat_name;
+use retro_junk_db::{OperationError, queries};
+use rusqlite::Connection;
+
+/// A way of naming exported files.
+pub trait NamingConvention: Sync {
+    /// Name used to pick the convention (e.g. "no-intro").
+    fn id(&self) -> &'static str;
+
+    /// One-line description for help output.
+    fn description(&self) -> &'static str;
+
+    /// Path of a file, without its extension, from its DAT name.
+    fn file_stem(&self, dat_name: &str) -> String;
+}
+
+/// DAT names as they are: the No-Intro and Redump convention.
+pub struct NoIntroNaming;
+
+impl NamingConvention for NoIntroNaming {
+    fn id(&self) -> &'static str {
+        "no-intro"
+    }
+
+    fn description(&self) -> &'static str {
+        "DAT names unchanged"
+    }
+
+    fn file_stem(&self, dat_name: &str) -> String {
+        dat_name.to_string()
+    }
+}
+
+/// Title, regions and disc only, as EmuMovies media packs name files.
+/// Revisions and versions share one name.
+pub struct EmuMoviesNaming;
+
+impl NamingConvention for EmuMoviesNaming {
+    fn id(&self) -> &'static str {
+        "emumovies"
+    }
+
+    fn description(&self) -> &'static str {
+        "Title and regions, e.g. \"Super Mario Bros. (USA)\""
+    }
+
+    fn file_stem(&self, dat_name: &str) -> String {
+        let parsed = parse_dat_name(dat_name);
+        let mut name = parsed.title;
+        if !parsed.regions.is_empty() {
+            name.push_str(&format!(" ({})", parsed.regions.join(", ")));
+        }
+        if let Some(n) = parsed.disc_number {
+            name.push_str(&format!(" (Disc {n})"));
+        }
+        name
+    }
+}
+
+/// DAT names in one folder per initial letter, as EverDrive packs are laid
+/// out. Names not starting with a letter go in `#`.
+pub struct EverDriveNaming;
+
+impl NamingConvention for EverDriveNaming {
+    fn id(&self) -> &'static str {
+        "everdrive"
+    }
+
+    fn description(&self) -> &'static str {
+        "DAT names in A-Z folders, e.g. \"S/Super Mario Bros. (USA)\""
+    }
+
+    fn file_stem(&self, dat_name: &str) -> String {
+        let folder = match dat_name.chars().next() {
+            Some(c) if c.is_ascii_alphabetic() => c.to_ascii_uppercase().to_string(),
+            _ => "#".to_string(),
+        };
+        format!("{folder}/{dat_name}")
+    }
+}
+
+/// All naming conventions, the default first.
+pub static NAMING_CONVENTIONS: &[&dyn NamingConvention] =
+    &[&NoIntroNaming, &EmuMoviesNaming, &EverDriveNaming];
+
+/// Look up a naming convention by its ID.
+pub fn find_convention(id: &str) -> Option<&'static dyn NamingConvention> {
+    NAMING_CONVENTIONS
+        .iter()
+        .copied()
+        .find(|c| c.id().eq_ignore_ascii_case(id))
+}
+
+/// What to export, and how to name it.
+pub struct ListingOptions<'a> {
+    pub convention: &'a dyn NamingConvention,
+    /// File extension, without the dot.
+    pub extension: String,
+    /// Folder every path is placed under, if any.
+    pub folder: Option<String>,
+    /// Only export media this user owns.
+    pub owned_by: Option<String>,
+}
+
+/// One file of a listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListingEntry {
+    /// Path within the pack, `/`-separated.
+    pub path: String,
+    pub sha1: Option<String>,
+    pub md5: Option<String>,
+    pub crc32: Option<String>,
+}
+
+/// A platform's listing.
+#[derive(Debug, Default)]
+pub struct Listing {
+    pub entries: Vec<ListingEntry>,
+    /// Media left out because the convention gave them a path already used.
+    pub duplicates: u64,
+    /// Media left out because they have no DAT name to rename.
+    pub unnamed: u64,
+}
+
+/// Build the listing of a platform's media.
+pub fn build_listing(
+    conn: &Connection,
+    platform_id: &str,
+    options: &ListingOptions,
+) -> Result<Listing, OperationError> {
+    let mut listing = Listing::default();
+    let mut paths = HashSet::new();
+
+    for media in queries::media_for_platform(conn, platform_id, options.owned_by.as_deref())? {
+        let Some(dat_name) = media.dat_name.as_deref() else {
+            listing.unnamed += 1;
+            continue;
+        };
+        let mut path = options.convention.file_stem(dat_name);
+        if !options.extension.is_empty() {
+            path = format!("{path}.{}", options.extension);
+        }
+        if let Some(folder) = &options.folder {
+            path = format!("{}/{path}", folder.trim_end_matches('/'));
+        }
+        if !paths.insert(path.clone()) {
+            listing.duplicates += 1;
+            continue;
+        }
+        listing.entries.push(ListingEntry {
+            path,
+            sha1: media.sha1,
+            md5: media.md5,
+            crc32: media.crc32,
+        });
+    }
+
+    Ok(listing)
+}
+
+/// Write entries as `.smdb` lines. The SHA-256 column is left empty.
+pub fn write_smdb(out: &mut impl Write, entries: &[ListingEntry]) -> std::io::Result<()> {
+    for entry in entries {
+        writeln!(
+            out,
+            "\t{}\t{}\t{}\t{}",
+            entry.path,
+            entry.sha1.as_deref().unwrap_or_default(),
+            entry.md5.as_deref().unwrap_or_default(),
+            entry.crc32.as_deref().unwrap_or_default(),
+        )?;
+    }
+    Ok(())
+}
@@ -5,7 +5,22 @@
 //! After ScreenScraper enrichment, both releases receive the same `screenscraper_id`,
 //! proving they're the same game. This module detects shared IDs and merges the
 //! duplicate works into one canonical work.
+//!
+//! Works on the same platform whose titles only differ by articles,
+//! diacritics, or punctuation (`Legend of Zelda, The` and `The Legend of
+//! Zelda`) are merged too, using [`retro_junk_catalog::title_key`] with each
+//! release's region.
+//!
+//! The same title on different platforms (Aladdin on SNES and Genesis) is
+//! never merged, since each platform's version is its own work. Instead
+//! [`find_cross_platform_siblings`] reports such works for review, and
+//! [`link_siblings`] or [`reject_siblings`] records the decision as a work
+//! relationship so the pair isn't reported again.
 
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use retro_junk_catalog::title_key;
+use retro_junk_catalog::types::WorkRelationship;
 use retro_junk_db::{operations, queries};
 use rusqlite::Connection;
 use thiserror::Error;
@@ -52,6 +67,12 @@ pub struct ReconcileResult {
     pub details: Vec<MergeDetail>,
 }
 
+/// Works on one platform that should become a single work.
+struct MergeGroup {
+    platform_id: String,
+    work_ids: Vec<String>,
+}
+
 /// A work candidate with metadata for tie-breaking.
 struct WorkCandidate {
     id: String,
@@ -60,7 +81,8 @@ struct WorkCandidate {
     created_at: String,
 }
 
-/// Run work reconciliation, merging duplicate works that share a ScreenScraper ID.
+/// Run work reconciliation, merging duplicate works that share a ScreenScraper ID
+/// or a title key.
 ///
 /// Returns statistics and per-group details for CLI display.
 pub fn reconcile_works(
@@ -70,7 +92,15 @@ pub fn reconcile_works(
     let mut stats = ReconcileStats::default();
     let mut details = Vec::new();
 
-    let groups = queries::find_reconcilable_works(conn)?;
+    let mut groups: Vec<MergeGroup> = queries::find_reconcilable_works(conn)?
+        .into_iter()
+        .map(|g| MergeGroup {
+            platform_id: g.platform_id,
+            work_ids: g.work_ids,
+        })
+        .collect();
+    groups.extend(find_title_groups(conn)?);
+    let groups = combine_groups(groups);
 
     // Filter by platform if requested
     let groups: Vec<_> = if options.platform_ids.is_empty() {
@@ -117,10 +147,220 @@ pub fn reconcile_works(
     Ok(ReconcileResult { stats, details })
 }
 
+/// A work reported as a possible cross-platform sibling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiblingWork {
+    pub work_id: String,
+    pub platform_id: String,
+    pub canonical_name: String,
+}
+
+/// Works on different platforms whose titles share a title key.
+#[derive(Debug, Clone)]
+pub struct SiblingGroup {
+    pub title_key: String,
+    /// Ordered by platform, then work ID.
+    pub works: Vec<SiblingWork>,
+}
+
+/// Find works with the same title on different platforms that haven't been
+/// reviewed yet.
+///
+/// A group is reported while any pair of its works on different platforms
+/// has neither a sibling nor a not-sibling relationship. With
+/// `platform_ids`, only groups including one of those platforms are kept.
+pub fn find_cross_platform_siblings(
+    conn: &Connection,
+    platform_ids: &[String],
+) -> Result<Vec<SiblingGroup>, ReconcileError> {
+    let reviewed: BTreeSet<(String, String)> = queries::list_work_relationships(conn, None)?
+        .into_iter()
+        .filter(|r| r.relationship == "sibling" || r.relationship == "not_sibling")
+        .flat_map(|r| [(r.work_a.clone(), r.work_b.clone()), (r.work_b, r.work_a)])
+        .collect();
+
+    let mut names: HashMap<String, String> = HashMap::new();
+    let mut by_key: BTreeMap<String, BTreeSet<(String, String)>> = BTreeMap::new();
+    for ((platform_id, key), works) in title_key_index(conn, &mut names)? {
+        let entry = by_key.entry(key).or_default();
+        for work_id in works {
+            entry.insert((platform_id.clone(), work_id));
+        }
+    }
+
+    let mut groups = Vec::new();
+    for (key, works) in by_key {
+        let platforms: BTreeSet<&str> = works.iter().map(|(p, _)| p.as_str()).collect();
+        if platforms.len() < 2 {
+            continue;
+        }
+        if !platform_ids.is_empty() && !platform_ids.iter().any(|p| platforms.contains(p.as_str()))
+        {
+            continue;
+        }
+        let works: Vec<_> = works.into_iter().collect();
+        let unreviewed = works.iter().enumerate().any(|(i, (pa, a))| {
+            works[i + 1..]
+                .iter()
+                .any(|(pb, b)| pa != pb && !reviewed.contains(&(a.clone(), b.clone())))
+        });
+        if !unreviewed {
+            continue;
+        }
+        groups.push(SiblingGroup {
+            title_key: key,
+            works: works
+                .into_iter()
+                .map(|(platform_id, work_id)| SiblingWork {
+                    canonical_name: names.get(&work_id).cloned().unwrap_or_default(),
+                    work_id,
+                    platform_id,
+                })
+                .collect(),
+        });
+    }
+    Ok(groups)
+}
+
+/// Record every pair of `work_ids` as siblings. Returns the number of pairs.
+pub fn link_siblings(conn: &Connection, work_ids: &[String]) -> Result<usize, ReconcileError> {
+    record_pairs(conn, work_ids, WorkRelationship::Sibling)
+}
+
+/// Record every pair of `work_ids` as not siblings, so they are no longer
+/// reported. Returns the number of pairs.
+pub fn reject_siblings(conn: &Connection, work_ids: &[String]) -> Result<usize, ReconcileError> {
+    record_pairs(conn, work_ids, WorkRelationship::NotSibling)
+}
+
+fn record_pairs(
+    conn: &Connection,
+    work_ids: &[String],
+    relationship: WorkRelationship,
+) -> Result<usize, ReconcileError> {
+    for work_id in work_ids {
+        if queries::get_work_by_id(conn, work_id)?.is_none() {
+            return Err(operations::OperationError::NotFound {
+                entity_type: "work".to_string(),
+                id: work_id.clone(),
+            }
+            .into());
+        }
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let mut pairs = 0;
+    for (i, a) in work_ids.iter().enumerate() {
+        for b in &work_ids[i + 1..] {
+            if a != b {
+                operations::insert_work_relationship(&tx, a, b, relationship)?;
+                pairs += 1;
+            }
+        }
+    }
+    tx.commit()?;
+    Ok(pairs)
+}
+
+/// Map each (platform, title key) to the works that have it, for any of
+/// their releases' regions. Work names are collected into `names`.
+fn title_key_index(
+    conn: &Connection,
+    names: &mut HashMap<String, String>,
+) -> Result<BTreeMap<(String, String), BTreeSet<String>>, ReconcileError> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT w.id, r.platform_id, w.canonical_name, r.region
+         FROM works w JOIN releases r ON r.work_id = w.id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+        ))
+    })?;
+
+    let mut by_key: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+    for row in rows {
+        let (work_id, platform_id, name, region) = row?;
+        let key = title_key(&name, Some(&region));
+        if key.is_empty() {
+            continue;
+        }
+        names.entry(work_id.clone()).or_insert(name);
+        by_key
+            .entry((platform_id, key))
+            .or_default()
+            .insert(work_id);
+    }
+    Ok(by_key)
+}
+
+/// Group works on the same platform whose titles share a title key for any
+/// of their releases' regions.
+fn find_title_groups(conn: &Connection) -> Result<Vec<MergeGroup>, ReconcileError> {
+    let by_key = title_key_index(conn, &mut HashMap::new())?;
+    Ok(by_key
+        .into_iter()
+        .filter(|(_, works)| works.len() > 1)
+        .map(|((platform_id, _), works)| MergeGroup {
+            platform_id,
+            work_ids: works.into_iter().collect(),
+        })
+        .collect())
+}
+
+/// Merge groups on the same platform that share a work, so each work is
+/// reconciled once.
+fn combine_groups(groups: Vec<MergeGroup>) -> Vec<MergeGroup> {
+    fn find(
+        parent: &mut HashMap<(String, String), (String, String)>,
+        node: &(String, String),
+    ) -> (String, String) {
+        let next = parent.get(node).cloned().unwrap_or_else(|| node.clone());
+        if next == *node {
+            return next;
+        }
+        let root = find(parent, &next);
+        parent.insert(node.clone(), root.clone());
+        root
+    }
+
+    let mut parent: HashMap<(String, String), (String, String)> = HashMap::new();
+    for group in &groups {
+        let first = (group.platform_id.clone(), group.work_ids[0].clone());
+        for work_id in &group.work_ids[1..] {
+            let a = find(&mut parent, &first);
+            let b = find(&mut parent, &(group.platform_id.clone(), work_id.clone()));
+            if a != b {
+                parent.insert(b, a);
+            }
+        }
+    }
+
+    let mut combined: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+    for group in &groups {
+        for work_id in &group.work_ids {
+            let node = (group.platform_id.clone(), work_id.clone());
+            let root = find(&mut parent, &node);
+            combined.entry(root).or_default().insert(work_id.clone());
+        }
+    }
+
+    combined
+        .into_iter()
+        .map(|((platform_id, _), works)| MergeGroup {
+            platform_id,
+            work_ids: works.into_iter().collect(),
+        })
+        .collect()
+}
+
 /// Process all reconcile groups.
 fn reconcile_groups(
     conn: &Connection,
-    groups: &[queries::ReconcileGroup],
+    groups: &[MergeGroup],
     options: &ReconcileOptions,
     stats: &mut ReconcileStats,
     details: &mut Vec<MergeDetail>,
@@ -227,6 +467,9 @@ fn merge_work_into(
     let moved = operations::update_releases_work_id(conn, absorbed_work_id, surviving_work_id)?;
     stats.releases_reassigned += moved as usize;
 
+    // Keep sibling links and other relationships with the surviving work
+    operations::move_work_relationships(conn, absorbed_work_id, surviving_work_id)?;
+
     Ok(())
 }
 
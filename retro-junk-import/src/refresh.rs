@@ -0,0 +1,154 @@
+//! Re-analyze collection files to fill catalog metadata.
+//!
+//! Analyzers learn to read new header fields over time. Refreshing re-runs
+//! quick analysis (no hashing) on each collection file and fills catalog
+//! fields that are still empty: the release's serial and date, and the
+//! media's serial. Values already in the catalog are never overwritten.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::Path;
+
+use retro_junk_core::{AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+use retro_junk_db::queries;
+use rusqlite::{Connection, params};
+
+use crate::scan_import::{ScanError, set_missing_release_date};
+
+/// Collection entries read per query.
+const PAGE_SIZE: u32 = 500;
+
+/// Statistics from a refresh run.
+#[derive(Debug, Default)]
+pub struct RefreshStats {
+    pub checked: u64,
+    /// Files whose analysis filled at least one field.
+    pub updated: u64,
+    pub unchanged: u64,
+    pub missing: u64,
+    pub no_path: u64,
+    pub errors: u64,
+    /// Release and media serials filled.
+    pub serials_filled: u64,
+    pub release_dates_filled: u64,
+    /// One line per missing or unreadable file.
+    pub problems: Vec<String>,
+}
+
+/// Re-analyze each collection file of a platform and record the metadata it
+/// yields that the catalog lacks.
+///
+/// Files are analyzed in quick mode and never re-hashed, so the media they
+/// were matched to is trusted. Media owned by several users is analyzed once.
+pub fn refresh_collection(
+    conn: &Connection,
+    analyzer: &dyn RomAnalyzer,
+    platform: Platform,
+) -> Result<RefreshStats, ScanError> {
+    let mut stats = RefreshStats::default();
+    let mut seen = HashSet::new();
+    let mut offset = 0;
+
+    loop {
+        let entries =
+            queries::list_collection_paged(conn, Some(platform.short_name()), PAGE_SIZE, offset)?;
+        if entries.is_empty() {
+            break;
+        }
+        offset += PAGE_SIZE;
+
+        for entry in &entries {
+            if !seen.insert(entry.media_id.clone()) {
+                continue;
+            }
+            let Some(rom_path) = entry.rom_path.as_deref().map(Path::new) else {
+                stats.no_path += 1;
+                continue;
+            };
+
+            stats.checked += 1;
+
+            if !rom_path.exists() {
+                stats.missing += 1;
+                stats
+                    .problems
+                    .push(format!("missing: {} ({})", entry.title, rom_path.display()));
+                continue;
+            }
+
+            let id = match analyze_quick(rom_path, analyzer) {
+                Ok(id) => id,
+                Err(e) => {
+                    stats.errors += 1;
+                    log::warn!(
+                        "Error analyzing '{}' at {}: {}",
+                        entry.title,
+                        rom_path.display(),
+                        e
+                    );
+                    stats.problems.push(format!(
+                        "analysis error: {} ({}): {}",
+                        entry.title,
+                        rom_path.display(),
+                        e
+                    ));
+                    continue;
+                }
+            };
+
+            let mut filled = false;
+            if let Some(serial) = id.serial_number.as_deref() {
+                let serial = analyzer.normalize_serial(serial);
+                for (table, column, key) in [
+                    ("releases", "game_serial", &entry.release_id),
+                    ("media", "media_serial", &entry.media_id),
+                ] {
+                    if fill_missing(conn, table, column, key, &serial)? {
+                        stats.serials_filled += 1;
+                        filled = true;
+                    }
+                }
+            }
+            if let Some(date) = &id.build_date
+                && set_missing_release_date(conn, &entry.release_id, date)?
+            {
+                stats.release_dates_filled += 1;
+                filled = true;
+            }
+
+            if filled {
+                stats.updated += 1;
+            } else {
+                stats.unchanged += 1;
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Analyze a file without hashing or checksum work.
+fn analyze_quick(path: &Path, analyzer: &dyn RomAnalyzer) -> Result<RomIdentification, ScanError> {
+    let mut file = File::open(path)?;
+    let options = AnalysisOptions::new().quick(true).file_path(path);
+    Ok(analyzer.analyze(&mut file, &options)?)
+}
+
+/// Set `table.column` on the row with `id` if it is empty. Returns whether
+/// the row was updated.
+fn fill_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    id: &str,
+    value: &str,
+) -> Result<bool, ScanError> {
+    let updated = conn.execute(
+        &format!(
+            "UPDATE {table} SET {column} = ?2, updated_at = datetime('now')
+             WHERE id = ?1 AND ({column} IS NULL OR {column} = '')"
+        ),
+        params![id, value],
+    )?;
+    Ok(updated > 0)
+}
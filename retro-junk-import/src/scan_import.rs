@@ -3,7 +3,10 @@
 //! This module scans ROM directories, hashes each file using the appropriate
 //! analyzer (for header stripping and byte-order normalization), and matches
 //! against the catalog's media table by CRC32/SHA1. Matched files are recorded
-//! as owned in the collection table.
+//! as owned in the collection table, and a matched release with no release
+//! date takes the build date from the ROM header, if it has one. Each scan
+//! and verify records a snapshot of the platform's counts in
+//! `collection_history`.
 
 use std::collections::HashSet;
 use std::fs::File;
@@ -11,9 +14,11 @@ use std::io::BufReader;
 use std::path::{Path, PathBuf};
 
 use retro_junk_catalog::types::*;
-use retro_junk_core::{Platform, RomAnalyzer};
+use retro_junk_core::{AnalysisOptions, BuildDate, Platform, RomAnalyzer};
+use retro_junk_dat::matcher::FileHashes;
 use retro_junk_db::{operations, queries};
-use rusqlite::Connection;
+use retro_junk_lib::remote::{RemoteError, Source};
+use rusqlite::{Connection, params};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -26,6 +31,10 @@ pub enum ScanError {
     Io(#[from] std::io::Error),
     #[error("Hashing error: {0}")]
     Hash(#[from] retro_junk_dat::DatError),
+    #[error("Analysis error: {0}")]
+    Analysis(#[from] retro_junk_core::AnalysisError),
+    #[error(transparent)]
+    Remote(#[from] RemoteError),
 }
 
 /// Options for a collection scan.
@@ -50,6 +59,8 @@ pub struct ScanStats {
     pub unmatched: u64,
     pub already_owned: u64,
     pub errors: u64,
+    /// Releases whose missing release date was filled from a header build date.
+    pub release_dates_filled: u64,
 }
 
 /// An unmatched ROM file — not found in the catalog.
@@ -147,6 +158,10 @@ pub fn scan_folder(
 
         match matched_media {
             Some((media, title)) => {
+                if fill_release_date(conn, &media.release_id, file_path, analyzer)? {
+                    stats.release_dates_filled += 1;
+                }
+
                 // Check if already in collection
                 let existing = queries::find_collection_entry(conn, &media.id, &options.user_id)?;
                 if existing.is_some() {
@@ -189,6 +204,8 @@ pub fn scan_folder(
         }
     }
 
+    record_snapshot(conn, platform, &options.user_id, "scan")?;
+
     if let Some(p) = progress {
         p.on_complete(&stats);
     }
@@ -196,10 +213,11 @@ pub fn scan_folder(
     Ok(ScanResult { stats, unmatched })
 }
 
-/// Re-verify existing collection entries against files on disk.
+/// Re-verify existing collection entries against their files.
 ///
 /// For each collection entry with a rom_path, re-hash the file and check
-/// that it still matches the catalog. Returns the number of entries verified
+/// that it still matches the catalog. A rom_path can also be a URL or an SMB
+/// share, which is read through [`Source::open`]. Returns the number of entries verified
 /// and the number that no longer match or are missing.
 pub fn verify_collection(
     conn: &Connection,
@@ -213,7 +231,7 @@ pub fn verify_collection(
 
     for entry in &entries {
         let rom_path = match &entry.rom_path {
-            Some(p) => PathBuf::from(p),
+            Some(p) => Source::parse(p),
             None => {
                 stats.no_path += 1;
                 continue;
@@ -222,18 +240,19 @@ pub fn verify_collection(
 
         stats.checked += 1;
 
-        if !rom_path.exists() {
+        if let Source::Local(path) = &rom_path
+            && !path.exists()
+        {
             stats.missing += 1;
-            log::warn!(
-                "ROM file missing for '{}': {}",
-                entry.title,
-                rom_path.display()
-            );
+            log::warn!("ROM file missing for '{}': {}", entry.title, rom_path);
+            stats
+                .problems
+                .push(format!("missing: {} ({})", entry.title, rom_path));
             continue;
         }
 
         // Re-hash and compare
-        match hash_file(&rom_path, analyzer) {
+        match hash_source(&rom_path, analyzer) {
             Ok(hashes) => {
                 let crc_match = entry.crc32.as_deref() == Some(&hashes.crc32);
                 let sha1_match = match (&entry.sha1, &hashes.sha1) {
@@ -251,25 +270,24 @@ pub fn verify_collection(
                     stats.verified += 1;
                 } else {
                     stats.hash_mismatch += 1;
-                    log::warn!(
-                        "Hash mismatch for '{}' at {}",
-                        entry.title,
-                        rom_path.display()
-                    );
+                    log::warn!("Hash mismatch for '{}' at {}", entry.title, rom_path);
+                    stats
+                        .problems
+                        .push(format!("hash mismatch: {} ({})", entry.title, rom_path));
                 }
             }
             Err(e) => {
                 stats.errors += 1;
-                log::warn!(
-                    "Error hashing '{}' at {}: {}",
-                    entry.title,
-                    rom_path.display(),
-                    e
-                );
+                log::warn!("Error hashing '{}' at {}: {}", entry.title, rom_path, e);
+                stats
+                    .problems
+                    .push(format!("hash error: {} ({}): {}", entry.title, rom_path, e));
             }
         }
     }
 
+    record_snapshot(conn, platform, user_id, "verify")?;
+
     Ok(stats)
 }
 
@@ -282,12 +300,40 @@ pub struct VerifyStats {
     pub hash_mismatch: u64,
     pub no_path: u64,
     pub errors: u64,
+    /// One line per missing, changed, or unreadable file.
+    pub problems: Vec<String>,
 }
 
 // ── Internal Helpers ────────────────────────────────────────────────────────
 
+/// Append the platform's current collection counts to the history table.
+fn record_snapshot(
+    conn: &Connection,
+    platform: Platform,
+    user_id: &str,
+    source: &str,
+) -> Result<(), ScanError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    operations::record_collection_snapshot(conn, platform.short_name(), user_id, source, &now)?;
+    Ok(())
+}
+
+/// [`hash_file`] for a ROM that may also be on a URL or an SMB share.
+fn hash_source(source: &Source, analyzer: &dyn RomAnalyzer) -> Result<FileHashes, ScanError> {
+    if let Source::Local(path) = source {
+        return hash_file(path, analyzer);
+    }
+    let mut reader = source.open()?;
+    let local = source.local_path();
+    Ok(retro_junk_lib::hasher::compute_crc32_sha1(
+        reader.as_mut(),
+        analyzer,
+        local.as_deref(),
+    )?)
+}
+
 /// Hash a ROM file using the analyzer's header stripping and normalization.
-fn hash_file(
+pub(crate) fn hash_file(
     path: &Path,
     analyzer: &dyn RomAnalyzer,
 ) -> Result<retro_junk_dat::matcher::FileHashes, ScanError> {
@@ -297,10 +343,50 @@ fn hash_file(
     Ok(hashes)
 }
 
+/// Read the build date from a ROM header (quick analysis, no hashing).
+fn header_build_date(path: &Path, analyzer: &dyn RomAnalyzer) -> Option<BuildDate> {
+    let mut file = File::open(path).ok()?;
+    let options = AnalysisOptions::new().quick(true).file_path(path);
+    analyzer.analyze(&mut file, &options).ok()?.build_date
+}
+
+/// Set a release's date from the ROM header's build date if the catalog has
+/// none. Returns whether the release was updated.
+fn fill_release_date(
+    conn: &Connection,
+    release_id: &str,
+    path: &Path,
+    analyzer: &dyn RomAnalyzer,
+) -> Result<bool, ScanError> {
+    let Some(release) = queries::get_release_by_id(conn, release_id)? else {
+        return Ok(false);
+    };
+    if release.release_date.is_some() {
+        return Ok(false);
+    }
+    let Some(date) = header_build_date(path, analyzer) else {
+        return Ok(false);
+    };
+    set_missing_release_date(conn, release_id, &date)
+}
+
+/// Set a release's date if it has none. Returns whether it was updated.
+pub(crate) fn set_missing_release_date(
+    conn: &Connection,
+    release_id: &str,
+    date: &BuildDate,
+) -> Result<bool, ScanError> {
+    let updated = conn.execute(
+        "UPDATE releases SET release_date = ?2, updated_at = datetime('now') WHERE id = ?1 AND release_date IS NULL",
+        params![release_id, date.to_string()],
+    )?;
+    Ok(updated > 0)
+}
+
 /// Find a media entry matching the given hashes, scoped to a platform.
 ///
 /// Tries CRC32 first (fast index lookup), then validates with SHA1 if available.
-fn find_matching_media(
+pub(crate) fn find_matching_media(
     conn: &Connection,
     hashes: &retro_junk_dat::matcher::FileHashes,
     platform: Platform,
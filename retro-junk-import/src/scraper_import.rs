@@ -5,6 +5,7 @@
 //! metadata (title, dates, genre, description, publisher, developer, rating)
 //! and optionally downloads media assets.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -13,7 +14,12 @@ use std::time::SystemTime;
 use futures::stream::{self, StreamExt};
 use retro_junk_catalog::types::*;
 use retro_junk_core::Platform;
+use retro_junk_dat::DatIndex;
 use retro_junk_db::{operations, queries};
+use retro_junk_frontend::AssetType;
+use retro_junk_scraper::assets::{
+    AssetNames, AssetNaming, asset_subdir, asset_type_for_ss_media, ss_media_types,
+};
 use retro_junk_scraper::client::ScreenScraperClient;
 use retro_junk_scraper::error::ScrapeError;
 use retro_junk_scraper::lookup::{self, LookupMethod, LookupResult, RomInfo};
@@ -31,17 +37,29 @@ const ITEM_TIMEOUT: Duration = Duration::from_secs(90);
 /// should not exceed this.
 const ASSET_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
 
+/// Asset types downloaded into the catalog during enrichment.
+const CATALOG_ASSET_TYPES: &[AssetType] = &[
+    AssetType::Cover,
+    AssetType::BackCover,
+    AssetType::Screenshot,
+    AssetType::TitleScreen,
+    AssetType::Marquee,
+    AssetType::Fanart,
+    AssetType::PhysicalMedia,
+    AssetType::Label,
+];
+
 /// Watchdog timeout for the Phase 3 event loop.
 /// If no worker result arrives within this duration, all workers are likely stuck
 /// (e.g., after a laptop sleep killed connections). The loop breaks and returns
 /// partial progress so the user can re-run.
 const WATCHDOG_TIMEOUT: Duration = Duration::from_secs(180);
 
-use crate::slugify;
 use rusqlite::Connection;
 use thiserror::Error;
 use tokio::sync::mpsc::Sender;
 
+use crate::companies;
 use crate::merge;
 
 #[derive(Debug, Error)]
@@ -74,10 +92,23 @@ pub struct EnrichOptions {
     pub download_assets: bool,
     /// Directory for downloaded assets.
     pub asset_dir: Option<PathBuf>,
+    /// How downloaded assets are named. `None` keeps one folder per release
+    /// (`<release>/<type>.png`); a naming files them per type instead
+    /// (`<type>/<name>.png`), as frontends and theme packs lay them out.
+    pub asset_naming: Option<AssetNaming>,
     /// Preferred region for name/media selection (e.g., "us", "eu", "jp").
     pub preferred_region: String,
+    /// Per-asset-type region overrides keyed by catalog asset type
+    /// (e.g., "box-front" → "jp"). Falls back to `preferred_region`.
+    pub asset_regions: HashMap<String, String>,
     /// Preferred language for descriptions (e.g., "en", "ja").
     pub preferred_language: String,
+    /// Look releases up but write nothing and download nothing. Planned
+    /// changes are reported through `EnrichEvent::ReleaseWouldChange`.
+    pub dry_run: bool,
+    /// DAT indexes by platform, whose parent/clone data lets a clone
+    /// without its own ScreenScraper entry fall back to its parent's.
+    pub dat_indexes: HashMap<Platform, DatIndex>,
 }
 
 impl Default for EnrichOptions {
@@ -88,8 +119,12 @@ impl Default for EnrichOptions {
             skip_existing: true,
             download_assets: false,
             asset_dir: None,
+            asset_naming: None,
             preferred_region: "us".to_string(),
+            asset_regions: HashMap::new(),
             preferred_language: "en".to_string(),
+            dry_run: false,
+            dat_indexes: HashMap::new(),
         }
     }
 }
@@ -103,7 +138,8 @@ pub struct EnrichStats {
     pub releases_skipped: u64,
     pub assets_downloaded: u64,
     pub disagreements_found: u64,
-    pub companies_created: u64,
+    /// Unknown company names staged for review
+    pub companies_staged: u64,
     pub errors: u64,
 }
 
@@ -121,6 +157,14 @@ pub enum EnrichEvent {
         ss_name: String,
         method: LookupMethod,
     },
+    /// Dry run only: the release was found and these fields would change.
+    ReleaseWouldChange {
+        index: usize,
+        title: String,
+        ss_name: String,
+        method: LookupMethod,
+        changes: Vec<FieldChange>,
+    },
     ReleaseNotFound {
         index: usize,
         title: String,
@@ -144,6 +188,14 @@ pub enum EnrichEvent {
     },
 }
 
+/// A release field that enrichment would change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: Option<String>,
+    pub new: String,
+}
+
 // ── Three-Phase Enrichment Types ─────────────────────────────────────────
 
 /// Pre-fetched work item for parallel API lookups.
@@ -153,6 +205,8 @@ struct EnrichWorkItem {
     media_entries: Vec<Media>,
     core_platform: Platform,
     system_id: u32,
+    /// ROM file name of the DAT parent set, when the release is a clone.
+    parent_filename: Option<String>,
 }
 
 /// Outcome of a single API lookup (Phase 2 result).
@@ -161,6 +215,8 @@ enum LookupOutcome {
     Found {
         index: usize,
         release: Box<Release>,
+        /// DAT name of the media looked up, for ROM-named assets.
+        rom_stem: Option<String>,
         result: Box<LookupResult>,
         mapped: Box<MappedGameInfo>,
     },
@@ -251,7 +307,7 @@ pub async fn enrich_releases(
         };
 
         // If --force, clear not-found flags so they get re-queried
-        if !options.skip_existing {
+        if !options.skip_existing && !options.dry_run {
             let cleared = operations::clear_not_found_flags(conn, platform_id)?;
             if cleared > 0 {
                 log::info!("Cleared {} not-found flags for {}", cleared, platform_id);
@@ -298,8 +354,20 @@ pub async fn enrich_releases(
             };
 
             // ── Phase 1: DB Read — pre-fetch releases + media ──────────────
-            let releases =
-                queries::releases_to_enrich(conn, platform_id, options.skip_existing, batch_limit)?;
+            // Enriched releases drop out of the skip_existing query, so the
+            // next batch starts from the top unless nothing gets written.
+            let offset = if options.skip_existing && !options.dry_run {
+                0
+            } else {
+                platform_processed
+            };
+            let releases = queries::releases_to_enrich_paged(
+                conn,
+                platform_id,
+                options.skip_existing,
+                batch_limit,
+                offset,
+            )?;
 
             if releases.is_empty() {
                 break;
@@ -322,12 +390,23 @@ pub async fn enrich_releases(
 
             for (i, release) in releases.into_iter().enumerate() {
                 let media_entries = queries::media_for_release(conn, &release.id)?;
+                let parent_filename = match options.dat_indexes.get(&core_platform) {
+                    Some(index) if !media_entries.is_empty() => {
+                        pick_best_media_for_lookup(&media_entries)
+                            .dat_name
+                            .as_deref()
+                            .and_then(|name| index.parent_rom_name(name))
+                            .map(str::to_string)
+                    }
+                    _ => None,
+                };
                 work_items.push(EnrichWorkItem {
                     index: i,
                     release,
                     media_entries,
                     core_platform,
                     system_id,
+                    parent_filename,
                 });
             }
 
@@ -423,7 +502,11 @@ pub async fn enrich_releases(
                         }
 
                         let best_media = pick_best_media_for_lookup(&item.media_entries);
-                        let rom_info = build_rom_info(best_media, item.core_platform);
+                        let rom_info = build_rom_info(
+                            best_media,
+                            item.core_platform,
+                            item.parent_filename.clone(),
+                        );
 
                         match tokio::time::timeout(
                             ITEM_TIMEOUT,
@@ -445,9 +528,11 @@ pub async fn enrich_releases(
                                     result.game.id,
                                     worker_start.elapsed().as_millis(),
                                 );
+                                let rom_stem = best_media.dat_name.clone();
                                 LookupOutcome::Found {
                                     index: item.index,
                                     release: Box::new(item.release),
+                                    rom_stem,
                                     result: Box::new(result),
                                     mapped: Box::new(mapped),
                                 }
@@ -616,12 +701,28 @@ pub async fn enrich_releases(
                     LookupOutcome::Found {
                         index,
                         release,
+                        rom_stem,
                         result,
                         mapped,
                     } => {
                         consecutive_errors = 0;
                         let game = &result.game;
 
+                        if options.dry_run {
+                            stats.releases_enriched += 1;
+                            let _ = events.try_send(EnrichEvent::ReleaseWouldChange {
+                                index,
+                                title: release.title.clone(),
+                                ss_name: game
+                                    .name_for_region("us")
+                                    .unwrap_or(&release.title)
+                                    .to_string(),
+                                method: result.method,
+                                changes: planned_enrichment_changes(&release, &game.id, &mapped),
+                            });
+                            continue;
+                        }
+
                         // 1. Download assets (async, no DB) — do this before
                         //    the transaction so network I/O doesn't hold a lock.
                         let downloaded_assets = if options.download_assets {
@@ -636,9 +737,10 @@ pub async fn enrich_releases(
                                     download_assets_only(
                                         &client,
                                         game,
-                                        &release.id,
+                                        &release,
+                                        rom_stem.as_deref(),
                                         asset_dir,
-                                        &options.preferred_region,
+                                        options,
                                     ),
                                 )
                                 .await
@@ -672,16 +774,24 @@ pub async fn enrich_releases(
                         let tx_result: Result<u32, EnrichError> = (|| {
                             conn.execute_batch("BEGIN")?;
 
-                            let publisher_id = if let Some(ref pub_name) = mapped.publisher {
-                                Some(find_or_create_company(conn, pub_name, &mut stats)?)
-                            } else {
-                                None
-                            };
-                            let developer_id = if let Some(ref dev_name) = mapped.developer {
-                                Some(find_or_create_company(conn, dev_name, &mut stats)?)
-                            } else {
-                                None
+                            let mut company_id = |name: &Option<String>, role: &str| {
+                                let Some(name) = name else {
+                                    return Ok::<_, EnrichError>(None);
+                                };
+                                let found = companies::resolve_company(
+                                    conn,
+                                    name,
+                                    "screenscraper",
+                                    &release.id,
+                                    role,
+                                )?;
+                                if found.newly_staged() {
+                                    stats.companies_staged += 1;
+                                }
+                                Ok(found.company_id().map(str::to_string))
                             };
+                            let publisher_id = company_id(&mapped.publisher, "publisher")?;
+                            let developer_id = company_id(&mapped.developer, "developer")?;
 
                             let disagreement_count = merge::merge_release_fields(
                                 conn,
@@ -720,7 +830,7 @@ pub async fn enrich_releases(
                                     file_path: Some(asset.file_path.to_string_lossy().to_string()),
                                     source_url: Some(asset.source_url.clone()),
                                     scraped: true,
-                                    file_hash: None,
+                                    file_hash: Some(asset.file_hash.clone()),
                                     width: None,
                                     height: None,
                                     created_at: String::new(),
@@ -772,7 +882,9 @@ pub async fn enrich_releases(
                             index,
                             title: release.title.clone(),
                         });
-                        operations::mark_release_not_found(conn, &release.id)?;
+                        if !options.dry_run {
+                            operations::mark_release_not_found(conn, &release.id)?;
+                        }
                         stats.releases_not_found += 1;
                     }
                     LookupOutcome::Skipped { index } => {
@@ -898,6 +1010,64 @@ pub struct MappedGameInfo {
     pub developer: Option<String>,
 }
 
+/// The changes `update_release_enrichment` would make to `release`.
+///
+/// Mirrors its rules: the ScreenScraper ID is always set, other fields are
+/// only filled when empty, and a differing title becomes the alt title.
+pub fn planned_enrichment_changes(
+    release: &Release,
+    screenscraper_id: &str,
+    mapped: &MappedGameInfo,
+) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    if release.screenscraper_id.as_deref() != Some(screenscraper_id) {
+        changes.push(FieldChange {
+            field: "screenscraper_id",
+            old: release.screenscraper_id.clone(),
+            new: screenscraper_id.to_string(),
+        });
+    }
+
+    let fills = [
+        ("release_date", &release.release_date, &mapped.release_date),
+        ("genre", &release.genre, &mapped.genre),
+        ("players", &release.players, &mapped.players),
+        ("description", &release.description, &mapped.description),
+        ("publisher", &release.publisher_id, &mapped.publisher),
+        ("developer", &release.developer_id, &mapped.developer),
+    ];
+    for (field, current, new) in fills {
+        if let (None, Some(new)) = (current, new) {
+            changes.push(FieldChange {
+                field,
+                old: None,
+                new: new.clone(),
+            });
+        }
+    }
+    if let (None, Some(rating)) = (release.rating, mapped.rating) {
+        changes.push(FieldChange {
+            field: "rating",
+            old: None,
+            new: format!("{:.2}", rating),
+        });
+    }
+
+    if let Some(title) = &mapped.title
+        && release.alt_title.is_none()
+        && *title != release.title
+    {
+        changes.push(FieldChange {
+            field: "alt_title",
+            old: None,
+            new: title.clone(),
+        });
+    }
+
+    changes
+}
+
 /// Extract release-relevant fields from a ScreenScraper GameInfo response.
 pub fn map_game_info(game: &GameInfo, region: &str, language: &str) -> MappedGameInfo {
     let ss_region = catalog_region_to_ss(region);
@@ -938,7 +1108,7 @@ fn pick_best_media_for_lookup(media: &[Media]) -> &Media {
 }
 
 /// Build a RomInfo struct from catalog Media data.
-fn build_rom_info(media: &Media, platform: Platform) -> RomInfo {
+fn build_rom_info(media: &Media, platform: Platform, parent_filename: Option<String>) -> RomInfo {
     let filename = media.dat_name.as_deref().unwrap_or("").to_string();
 
     RomInfo {
@@ -951,53 +1121,17 @@ fn build_rom_info(media: &Media, platform: Platform) -> RomInfo {
         sha1: media.sha1.clone(),
         platform,
         expects_serial: systems::expects_serial(platform),
+        parent_filename,
     }
 }
 
-/// Find or create a company in the database by name.
-///
-/// First checks aliases, then creates a new company if not found.
-fn find_or_create_company(
-    conn: &Connection,
-    name: &str,
-    stats: &mut EnrichStats,
-) -> Result<String, EnrichError> {
-    // Check if a company with this alias already exists
-    if let Some(company_id) = operations::find_company_by_alias(conn, name)? {
-        return Ok(company_id);
-    }
-
-    // Also check by exact company name
-    let slug = slugify(name);
-    let exists: bool = conn.query_row(
-        "SELECT EXISTS(SELECT 1 FROM companies WHERE id = ?1)",
-        [&slug],
-        |row| row.get(0),
-    )?;
-    if exists {
-        return Ok(slug);
-    }
-
-    // Create new company
-    let company = Company {
-        id: slug.clone(),
-        name: name.to_string(),
-        country: None,
-        aliases: vec![name.to_string()],
-    };
-    operations::upsert_company(conn, &company)?;
-    stats.companies_created += 1;
-    log::debug!("Created new company: {} ({})", name, slug);
-
-    Ok(slug)
-}
-
 /// A successfully downloaded asset ready for DB insertion.
 struct DownloadedAsset {
     asset_type: String,
     file_path: PathBuf,
     region: String,
     source_url: String,
+    file_hash: String,
 }
 
 /// Download media assets without touching the database.
@@ -1007,38 +1141,26 @@ struct DownloadedAsset {
 async fn download_assets_only(
     client: &ScreenScraperClient,
     game: &GameInfo,
-    release_id: &str,
+    release: &Release,
+    rom_stem: Option<&str>,
     asset_dir: &Path,
-    preferred_region: &str,
+    options: &EnrichOptions,
 ) -> Result<Vec<DownloadedAsset>, EnrichError> {
-    let ss_region = catalog_region_to_ss(preferred_region);
     let mut downloaded = Vec::new();
 
-    // Asset types to download and their ScreenScraper media type names
-    let asset_mappings: &[(&str, &str)] = &[
-        ("box-front", "box-2D"),
-        ("box-back", "box-2D-back"),
-        ("screenshot", "ss"),
-        ("title-screen", "sstitle"),
-        ("wheel", "wheel-hd"),
-        ("fanart", "fanart"),
-        ("cart-front", "support-2D"),
-    ];
-
-    for &(asset_type, ss_type) in asset_mappings {
-        let media = match game.media_for_region(ss_type, ss_region) {
-            Some(m) => m,
-            None => {
-                // Try wheel as fallback for wheel-hd
-                if ss_type == "wheel-hd" {
-                    match game.media_for_region("wheel", ss_region) {
-                        Some(m) => m,
-                        None => continue,
-                    }
-                } else {
-                    continue;
-                }
-            }
+    for &at in CATALOG_ASSET_TYPES {
+        let asset_type = at.catalog_name();
+        let region = options
+            .asset_regions
+            .get(asset_type)
+            .map(String::as_str)
+            .unwrap_or(&options.preferred_region);
+        let ss_region = catalog_region_to_ss(region);
+        let Some(media) = ss_media_types(at)
+            .iter()
+            .find_map(|ss_type| game.media_for_region(ss_type, ss_region))
+        else {
+            continue;
         };
 
         let url = &media.url;
@@ -1048,12 +1170,17 @@ async fn download_assets_only(
             &media.format
         };
 
-        // Create directory structure: asset_dir/release_id/
-        let release_dir = asset_dir.join(release_id);
-        std::fs::create_dir_all(&release_dir)?;
-
-        let file_name = format!("{}.{}", asset_type, extension);
-        let file_path = release_dir.join(&file_name);
+        let file_path = catalog_asset_path(
+            asset_dir,
+            release,
+            rom_stem,
+            options.asset_naming,
+            at,
+            extension,
+        );
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
 
         // Skip if already downloaded
         if file_path.exists() {
@@ -1061,7 +1188,7 @@ async fn download_assets_only(
         }
 
         // Download
-        match client.download_media(url).await {
+        match retro_junk_scraper::assets::download_verified_media(client, media).await {
             Ok(data) => {
                 std::fs::write(&file_path, &data)?;
                 downloaded.push(DownloadedAsset {
@@ -1069,13 +1196,14 @@ async fn download_assets_only(
                     file_path,
                     region: media.region.clone(),
                     source_url: url.clone(),
+                    file_hash: retro_junk_scraper::assets::asset_file_hash(&data),
                 });
             }
             Err(e) => {
                 log::debug!(
                     "Failed to download {} for {}: {}",
                     asset_type,
-                    release_id,
+                    release.id,
                     e
                 );
             }
@@ -1085,6 +1213,33 @@ async fn download_assets_only(
     Ok(downloaded)
 }
 
+/// Where a release's asset is stored under `asset_dir`: in the release's
+/// folder by asset type without a naming, or in the asset type's folder by
+/// the name the naming picks. Releases without a DAT name use their slug as
+/// the ROM stem.
+pub fn catalog_asset_path(
+    asset_dir: &Path,
+    release: &Release,
+    rom_stem: Option<&str>,
+    naming: Option<AssetNaming>,
+    at: AssetType,
+    extension: &str,
+) -> PathBuf {
+    let Some(naming) = naming else {
+        return asset_dir
+            .join(&release.id)
+            .join(format!("{}.{}", at.catalog_name(), extension));
+    };
+    let stem = naming.file_stem(&AssetNames {
+        rom_stem: rom_stem.unwrap_or(&release.id),
+        slug: &release.id,
+        serial: release.game_serial.as_deref(),
+    });
+    asset_dir
+        .join(asset_subdir(at))
+        .join(format!("{}.{}", stem, extension))
+}
+
 // ── Helpers ─────────────────────────────────────────────────────────────────
 
 /// Current wall-clock time as seconds since the Unix epoch.
@@ -1099,19 +1254,26 @@ fn epoch_secs() -> u64 {
         .as_secs()
 }
 
-/// Map catalog region slug to ScreenScraper region code.
+/// Map catalog region slug to ScreenScraper region code, falling back to
+/// `us` for regions it doesn't know.
 pub fn catalog_region_to_ss(region: &str) -> &str {
+    known_catalog_region(region).unwrap_or("us")
+}
+
+/// The ScreenScraper region code for a catalog region slug or short code
+/// (`japan` or `jp`), or `None` if it isn't one.
+pub fn known_catalog_region(region: &str) -> Option<&'static str> {
     match region {
-        "usa" | "us" => "us",
-        "europe" | "eu" => "eu",
-        "japan" | "jp" => "jp",
-        "australia" | "au" => "au",
-        "korea" | "kr" => "kr",
-        "china" | "cn" => "cn",
-        "taiwan" | "tw" => "tw",
-        "brazil" | "br" => "br",
-        "world" | "wor" => "wor",
-        _ => "us",
+        "usa" | "us" => Some("us"),
+        "europe" | "eu" => Some("eu"),
+        "japan" | "jp" => Some("jp"),
+        "australia" | "au" => Some("au"),
+        "korea" | "kr" => Some("kr"),
+        "china" | "cn" => Some("cn"),
+        "taiwan" | "tw" => Some("tw"),
+        "brazil" | "br" => Some("br"),
+        "world" | "wor" => Some("wor"),
+        _ => None,
     }
 }
 
@@ -1133,17 +1295,5 @@ pub fn ss_region_to_catalog(ss_region: &str) -> &str {
 
 /// Map ScreenScraper media type to our asset_type string.
 pub fn ss_media_type_to_asset_type(ss_type: &str) -> Option<&'static str> {
-    match ss_type {
-        "box-2D" => Some("box-front"),
-        "box-2D-back" => Some("box-back"),
-        "box-3D" => Some("box-3d"),
-        "ss" => Some("screenshot"),
-        "sstitle" => Some("title-screen"),
-        "wheel-hd" | "wheel" => Some("wheel"),
-        "fanart" => Some("fanart"),
-        "support-2D" => Some("cart-front"),
-        "video-normalized" | "video" => Some("video"),
-        "miximage" => Some("miximage"),
-        _ => None,
-    }
+    asset_type_for_ss_media(ss_type).map(|at| at.catalog_name())
 }
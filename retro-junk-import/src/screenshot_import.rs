@@ -0,0 +1,259 @@
+//! Ingest user-supplied screenshots into the catalog as media assets.
+//!
+//! Emulators write capture images named after the loaded content (RetroArch
+//! uses `<rom stem>-YYMMDD-HHMMSS.png`). This module walks a capture folder,
+//! matches each image to a release by ROM filename or an explicit mapping,
+//! copies it into the asset directory, and records it in `media_assets` so
+//! frontend generators can pick it up alongside scraped media.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use retro_junk_catalog::types::Asset;
+use retro_junk_core::Platform;
+use retro_junk_db::{operations, queries};
+use retro_junk_scraper::assets::asset_file_hash;
+use rusqlite::Connection;
+use thiserror::Error;
+
+/// Image extensions accepted as capture files.
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "webp"];
+
+#[derive(Debug, Error)]
+pub enum ScreenshotError {
+    #[error("Database error: {0}")]
+    Db(#[from] operations::OperationError),
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Options for screenshot ingestion.
+pub struct ScreenshotOptions {
+    /// Directory where asset files are stored (`asset_dir/release_id/`).
+    pub asset_dir: PathBuf,
+    /// Asset type to register ("screenshot" or "title-screen").
+    pub asset_type: String,
+    /// Explicit image stem → release ID mappings, checked before filename matching.
+    pub mappings: HashMap<String, String>,
+    /// Replace an existing asset file of the same type for a release.
+    pub overwrite: bool,
+}
+
+impl Default for ScreenshotOptions {
+    fn default() -> Self {
+        Self {
+            asset_dir: PathBuf::from("assets"),
+            asset_type: "screenshot".to_string(),
+            mappings: HashMap::new(),
+            overwrite: false,
+        }
+    }
+}
+
+/// Statistics from a screenshot ingestion run.
+#[derive(Debug, Default)]
+pub struct ScreenshotStats {
+    pub images_found: u64,
+    pub imported: u64,
+    pub unmatched: u64,
+    pub skipped_existing: u64,
+    pub errors: u64,
+}
+
+/// Result of ingesting a capture folder.
+pub struct ScreenshotResult {
+    pub stats: ScreenshotStats,
+    pub unmatched: Vec<PathBuf>,
+}
+
+/// Ingest screenshots from `folder` for the given platform.
+///
+/// Each image is matched to a release by, in order:
+/// 1. an entry in `options.mappings` keyed by the image stem,
+/// 2. the stem of a ROM path in the user's collection,
+/// 3. the stem of a media entry's DAT name.
+///
+/// Emulator capture timestamps (`-YYMMDD-HHMMSS`) are stripped before
+/// filename matching. Matched images are copied to
+/// `asset_dir/release_id/{asset_type}.{ext}` and registered with source "user".
+pub fn ingest_screenshots(
+    conn: &Connection,
+    folder: &Path,
+    platform: Platform,
+    options: &ScreenshotOptions,
+) -> Result<ScreenshotResult, ScreenshotError> {
+    let index = build_stem_index(conn, platform)?;
+    let mut stats = ScreenshotStats::default();
+    let mut unmatched = Vec::new();
+
+    let mut images: Vec<PathBuf> = std::fs::read_dir(folder)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && is_image(p))
+        .collect();
+    images.sort();
+
+    for image in &images {
+        stats.images_found += 1;
+
+        let stem = match image.file_stem() {
+            Some(s) => s.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        let release_id = options
+            .mappings
+            .get(&stem)
+            .cloned()
+            .or_else(|| index.get(&normalize_stem(&stem)).cloned());
+
+        let release_id = match release_id {
+            Some(id) => id,
+            None => {
+                stats.unmatched += 1;
+                unmatched.push(image.clone());
+                continue;
+            }
+        };
+
+        if queries::get_release_by_id(conn, &release_id)?.is_none() {
+            log::warn!(
+                "Mapping for '{}' points to unknown release '{}'",
+                stem,
+                release_id
+            );
+            stats.errors += 1;
+            continue;
+        }
+
+        let ext = image
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_else(|| "png".to_string());
+        let release_dir = options.asset_dir.join(&release_id);
+        let dest = release_dir.join(format!("{}.{}", options.asset_type, ext));
+
+        if dest.exists() && !options.overwrite {
+            stats.skipped_existing += 1;
+            continue;
+        }
+
+        std::fs::create_dir_all(&release_dir)?;
+        let copied = std::fs::read(image).and_then(|bytes| {
+            std::fs::write(&dest, &bytes)?;
+            Ok(bytes)
+        });
+        let bytes = match copied {
+            Ok(b) => b,
+            Err(e) => {
+                log::warn!("Failed to copy '{}': {}", image.display(), e);
+                stats.errors += 1;
+                continue;
+            }
+        };
+
+        let dest_str = dest.to_string_lossy().to_string();
+        conn.execute(
+            "DELETE FROM media_assets WHERE release_id = ?1 AND asset_type = ?2 AND file_path = ?3",
+            rusqlite::params![release_id, options.asset_type, dest_str],
+        )?;
+
+        let asset = Asset {
+            id: 0,
+            release_id: Some(release_id),
+            media_id: None,
+            asset_type: options.asset_type.clone(),
+            region: None,
+            source: "user".to_string(),
+            file_path: Some(dest_str),
+            source_url: None,
+            scraped: false,
+            file_hash: Some(asset_file_hash(&bytes)),
+            width: None,
+            height: None,
+            created_at: String::new(),
+        };
+        operations::insert_asset(conn, &asset)?;
+        stats.imported += 1;
+    }
+
+    Ok(ScreenshotResult { stats, unmatched })
+}
+
+/// Normalize an image or ROM stem for matching.
+///
+/// Lowercases and strips a trailing RetroArch-style capture timestamp
+/// (`-YYMMDD-HHMMSS`).
+pub fn normalize_stem(stem: &str) -> String {
+    let trimmed = strip_capture_timestamp(stem);
+    trimmed.trim().to_lowercase()
+}
+
+// ── Internal Helpers ────────────────────────────────────────────────────────
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.as_str()))
+}
+
+/// Strip a `-YYMMDD-HHMMSS` suffix if present.
+fn strip_capture_timestamp(stem: &str) -> &str {
+    let bytes = stem.as_bytes();
+    // "-YYMMDD-HHMMSS" is 14 bytes
+    if bytes.len() > 14 {
+        let tail = &bytes[bytes.len() - 14..];
+        let is_stamp = tail[0] == b'-'
+            && tail[7] == b'-'
+            && tail[1..7].iter().all(u8::is_ascii_digit)
+            && tail[8..].iter().all(u8::is_ascii_digit);
+        if is_stamp {
+            return &stem[..stem.len() - 14];
+        }
+    }
+    stem
+}
+
+/// Build a normalized stem → release ID index for a platform.
+///
+/// Collection ROM paths take priority over DAT names, since they reflect
+/// what the emulator actually loaded.
+fn build_stem_index(
+    conn: &Connection,
+    platform: Platform,
+) -> Result<HashMap<String, String>, ScreenshotError> {
+    let mut index = HashMap::new();
+
+    let mut stmt = conn.prepare(
+        "SELECT m.dat_name, m.release_id
+         FROM media m
+         JOIN releases r ON m.release_id = r.id
+         WHERE r.platform_id = ?1 AND m.dat_name IS NOT NULL",
+    )?;
+    let rows = stmt.query_map(rusqlite::params![platform.short_name()], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for row in rows {
+        let (dat_name, release_id) = row?;
+        let stem = Path::new(&dat_name)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or(dat_name);
+        index.insert(normalize_stem(&stem), release_id);
+    }
+
+    let collection = queries::list_collection(conn, Some(platform.short_name()), Some(u32::MAX))?;
+    for entry in collection {
+        if let Some(stem) = entry
+            .rom_path
+            .as_deref()
+            .and_then(|p| Path::new(p).file_stem())
+        {
+            index.insert(normalize_stem(&stem.to_string_lossy()), entry.release_id);
+        }
+    }
+
+    Ok(index)
+}
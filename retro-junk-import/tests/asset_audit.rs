@@ -0,0 +1,93 @@
+use retro_junk_catalog::types::*;
+use retro_junk_db::*;
+use retro_junk_import::{apply_asset_prune, plan_asset_prune, verify_assets};
+
+fn asset(path: &std::path::Path, file_hash: Option<&str>) -> Asset {
+    Asset {
+        id: 0,
+        release_id: None,
+        media_id: None,
+        asset_type: "screenshot".to_string(),
+        region: None,
+        source: "screenscraper".to_string(),
+        file_path: Some(path.to_string_lossy().to_string()),
+        source_url: None,
+        scraped: true,
+        file_hash: file_hash.map(String::from),
+        width: None,
+        height: None,
+        created_at: String::new(),
+    }
+}
+
+#[test]
+fn verify_assets_reports_missing_and_changed() {
+    let conn = open_memory().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+
+    let good = dir.path().join("good.png");
+    let changed = dir.path().join("changed.png");
+    let unhashed = dir.path().join("unhashed.png");
+    std::fs::write(&good, b"abc").unwrap();
+    std::fs::write(&changed, b"abd").unwrap();
+    std::fs::write(&unhashed, b"abc").unwrap();
+
+    let sha1_abc = "a9993e364706816aba3e25717850c26c9cd0d89d";
+    insert_asset(&conn, &asset(&good, Some(sha1_abc))).unwrap();
+    insert_asset(&conn, &asset(&changed, Some(sha1_abc))).unwrap();
+    let unhashed_id = insert_asset(&conn, &asset(&unhashed, None)).unwrap();
+    insert_asset(&conn, &asset(&dir.path().join("gone.png"), Some(sha1_abc))).unwrap();
+
+    let result = verify_assets(&conn, None).unwrap();
+    assert_eq!(result.stats.checked, 4);
+    assert_eq!(result.stats.verified, 1);
+    assert_eq!(result.stats.mismatched, 1);
+    assert_eq!(result.stats.missing, 1);
+    assert_eq!(result.stats.hashed, 1);
+    assert_eq!(result.problems.len(), 2);
+
+    // The backfilled hash is used on the next run
+    let assets = list_assets(&conn, None).unwrap();
+    let backfilled = assets.iter().find(|a| a.id == unhashed_id).unwrap();
+    assert_eq!(backfilled.file_hash.as_deref(), Some(sha1_abc));
+
+    let again = verify_assets(&conn, None).unwrap();
+    assert_eq!(again.stats.verified, 2);
+    assert_eq!(again.stats.hashed, 0);
+}
+
+#[test]
+fn prune_finds_orphans_and_missing_rows() {
+    let conn = open_memory().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let release_dir = dir.path().join("smb1-nes-usa");
+    std::fs::create_dir(&release_dir).unwrap();
+
+    let kept = release_dir.join("box-front.png");
+    let orphan = release_dir.join("screenshot.png");
+    let stray_dir = dir.path().join("gone-release");
+    std::fs::create_dir(&stray_dir).unwrap();
+    let stray = stray_dir.join("wheel.png");
+    for f in [&kept, &orphan, &stray] {
+        std::fs::write(f, b"abc").unwrap();
+    }
+    std::fs::write(dir.path().join(".DS_Store"), b"").unwrap();
+
+    insert_asset(&conn, &asset(&kept, None)).unwrap();
+    let missing_id = insert_asset(&conn, &asset(&release_dir.join("fanart.jpg"), None)).unwrap();
+
+    let plan = plan_asset_prune(&conn, dir.path()).unwrap();
+    assert_eq!(plan.orphaned_files, vec![stray.clone(), orphan.clone()]);
+    assert_eq!(plan.missing.len(), 1);
+    assert_eq!(plan.missing[0].id, missing_id);
+
+    let stats = apply_asset_prune(&conn, &plan).unwrap();
+    assert_eq!(stats.files_deleted, 2);
+    assert_eq!(stats.rows_deleted, 1);
+    assert!(kept.exists());
+    assert!(!orphan.exists());
+    assert!(!stray_dir.exists());
+    assert_eq!(list_assets(&conn, None).unwrap().len(), 1);
+
+    assert!(plan_asset_prune(&conn, dir.path()).unwrap().is_empty());
+}
@@ -0,0 +1,171 @@
+use retro_junk_catalog::types::*;
+use retro_junk_db::*;
+use retro_junk_import::*;
+
+mod common;
+
+fn setup_db_with_release() -> (rusqlite::Connection, String) {
+    let conn = common::nes_catalog();
+    let release = Release {
+        release_date: Some("1985-10-18".to_string()),
+        genre: Some("Platform".to_string()),
+        ..common::release("smb1", "nes", "Super Mario Bros.")
+    };
+    common::add_release(&conn, &release);
+    (conn, release.id)
+}
+
+fn rule(field: &str, strategy: ResolutionStrategy, source: Option<&str>) -> ResolutionRule {
+    ResolutionRule {
+        field: field.to_string(),
+        entity_type: None,
+        platform_id: None,
+        strategy,
+        source: source.map(|s| s.to_string()),
+        reason: None,
+    }
+}
+
+fn unresolved(conn: &rusqlite::Connection) -> Vec<Disagreement> {
+    list_unresolved_disagreements(
+        conn,
+        &DisagreementFilter {
+            entity_type: None,
+            field: None,
+            platform_id: None,
+            limit: None,
+        },
+    )
+    .unwrap()
+}
+
+#[test]
+fn decide_picks_sides_by_strategy() {
+    let d = Disagreement {
+        id: 1,
+        entity_type: "release".to_string(),
+        entity_id: "smb1-nes-usa".to_string(),
+        field: "description".to_string(),
+        source_a: "screenscraper".to_string(),
+        value_a: Some("Short.".to_string()),
+        source_b: "gdb".to_string(),
+        value_b: Some("A much longer synopsis.".to_string()),
+        resolved: false,
+        resolution: None,
+        resolved_at: None,
+        created_at: String::new(),
+    };
+
+    let longest = rule("description", ResolutionStrategy::Longest, None);
+    assert_eq!(
+        decide_disagreement(&longest, &d),
+        Some(ResolutionSide::SourceB)
+    );
+    let shortest = rule("description", ResolutionStrategy::Shortest, None);
+    assert_eq!(
+        decide_disagreement(&shortest, &d),
+        Some(ResolutionSide::SourceA)
+    );
+    let prefer = rule(
+        "description",
+        ResolutionStrategy::PreferSource,
+        Some("screenscraper"),
+    );
+    assert_eq!(
+        decide_disagreement(&prefer, &d),
+        Some(ResolutionSide::SourceA)
+    );
+
+    // Source not involved, or a different field: no decision
+    let other_source = rule(
+        "description",
+        ResolutionStrategy::PreferSource,
+        Some("dat-import"),
+    );
+    assert_eq!(decide_disagreement(&other_source, &d), None);
+    let other_field = rule("genre", ResolutionStrategy::PreferIncoming, None);
+    assert_eq!(decide_disagreement(&other_field, &d), None);
+}
+
+#[test]
+fn auto_resolve_applies_value_and_records_rule() {
+    let (conn, release_id) = setup_db_with_release();
+    check_field(
+        &conn,
+        "release",
+        &release_id,
+        "release_date",
+        "dat-import",
+        Some("1985-10-18"),
+        "screenscraper",
+        Some("1985-09-13"),
+    )
+    .unwrap();
+    check_field(
+        &conn,
+        "release",
+        &release_id,
+        "genre",
+        "dat-import",
+        Some("Platform"),
+        "screenscraper",
+        Some("Platform / Run and jump"),
+    )
+    .unwrap();
+    check_field(
+        &conn,
+        "release",
+        &release_id,
+        "developer",
+        "screenscraper",
+        Some("Nintendo EAD"),
+        "gdb",
+        Some("Nintendo"),
+    )
+    .unwrap();
+
+    let mut date_rule = rule(
+        "release_date",
+        ResolutionStrategy::PreferSource,
+        Some("screenscraper"),
+    );
+    date_rule.reason = Some("ScreenScraper has first-release dates".to_string());
+    let rules = vec![
+        date_rule,
+        rule("genre", ResolutionStrategy::Longest, None),
+        rule("developer", ResolutionStrategy::PreferExisting, None),
+    ];
+
+    // Dry run reports without touching anything
+    let preview = auto_resolve_disagreements(&conn, &rules, true).unwrap();
+    assert_eq!(preview.resolved, 2);
+    assert_eq!(unresolved(&conn).len(), 3);
+
+    let stats = auto_resolve_disagreements(&conn, &rules, false).unwrap();
+    assert_eq!(stats.resolved, 2);
+    assert_eq!(stats.skipped, 1);
+    assert_eq!(stats.by_field.get("genre"), Some(&1));
+
+    let release = get_release_by_id(&conn, &release_id).unwrap().unwrap();
+    assert_eq!(release.release_date.as_deref(), Some("1985-09-13"));
+    assert_eq!(release.genre.as_deref(), Some("Platform / Run and jump"));
+
+    // Developer isn't a writable field, so it stays for manual review
+    let remaining = unresolved(&conn);
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].field, "developer");
+
+    let all: Vec<Disagreement> = (1..=3)
+        .filter_map(|id| get_disagreement(&conn, id).unwrap())
+        .collect();
+    let date = all.iter().find(|d| d.field == "release_date").unwrap();
+    assert_eq!(
+        date.resolution.as_deref(),
+        Some("source_b (auto: ScreenScraper has first-release dates)")
+    );
+    let genre = all.iter().find(|d| d.field == "genre").unwrap();
+    assert_eq!(
+        genre.resolution.as_deref(),
+        Some("source_b (auto: longest value)")
+    );
+}
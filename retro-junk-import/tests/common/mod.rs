@@ -0,0 +1,96 @@
+//! Catalog fixtures shared by the import tests.
+//!
+//! Tests build on these with struct update syntax, e.g.
+//! `Release { genre: Some(..), ..common::release(..) }`. Each test binary
+//! uses only some of them.
+
+#![allow(dead_code)]
+
+use retro_junk_catalog::types::*;
+use retro_junk_db::*;
+use rusqlite::Connection;
+
+/// The NES as the catalog lists it.
+pub fn nes_platform() -> CatalogPlatform {
+    CatalogPlatform {
+        id: "nes".to_string(),
+        display_name: "Nintendo Entertainment System".to_string(),
+        short_name: "NES".to_string(),
+        manufacturer: "Nintendo".to_string(),
+        generation: Some(3),
+        media_type: MediaType::Cartridge,
+        release_year: Some(1985),
+        description: None,
+        core_platform: Some("Nes".to_string()),
+        regions: vec![],
+        relationships: vec![],
+    }
+}
+
+/// An in-memory catalog holding `platform`.
+pub fn catalog_with(platform: &CatalogPlatform) -> Connection {
+    let conn = open_memory().unwrap();
+    upsert_platform(&conn, platform).unwrap();
+    conn
+}
+
+/// An in-memory catalog holding the NES.
+pub fn nes_catalog() -> Connection {
+    catalog_with(&nes_platform())
+}
+
+/// A USA release of `work_id` on `platform_id`, with the ID a DAT import
+/// gives it and no metadata beyond its title.
+pub fn release(work_id: &str, platform_id: &str, title: &str) -> Release {
+    Release {
+        id: format!("{work_id}:{platform_id}:usa"),
+        work_id: work_id.to_string(),
+        platform_id: platform_id.to_string(),
+        region: "usa".to_string(),
+        revision: String::new(),
+        variant: String::new(),
+        title: title.to_string(),
+        alt_title: None,
+        publisher_id: None,
+        developer_id: None,
+        release_date: None,
+        game_serial: None,
+        genre: None,
+        players: None,
+        rating: None,
+        description: None,
+        screen_title: None,
+        cover_title: None,
+        screenscraper_id: None,
+        scraper_not_found: false,
+        created_at: String::new(),
+        updated_at: String::new(),
+    }
+}
+
+/// Add `release` and a work of the same title for it to belong to.
+pub fn add_release(conn: &Connection, release: &Release) {
+    insert_work(conn, &release.work_id, &release.title).unwrap();
+    upsert_release(conn, release).unwrap();
+}
+
+/// A verified No-Intro media entry of `release_id`, without hashes.
+pub fn media(id: &str, release_id: &str, dat_name: Option<&str>) -> Media {
+    Media {
+        id: id.to_string(),
+        release_id: release_id.to_string(),
+        media_serial: None,
+        disc_number: None,
+        disc_label: None,
+        revision: None,
+        status: MediaStatus::Verified,
+        dat_name: dat_name.map(str::to_string),
+        dat_source: Some("no-intro".to_string()),
+        file_size: None,
+        crc32: None,
+        sha1: None,
+        md5: None,
+        created_at: String::new(),
+        updated_at: String::new(),
+    }
+}
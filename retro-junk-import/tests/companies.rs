@@ -0,0 +1,137 @@
+use retro_junk_catalog::types::*;
+use retro_junk_db::*;
+use retro_junk_import::*;
+
+mod common;
+
+fn setup_db_with_release() -> (rusqlite::Connection, String) {
+    let conn = common::nes_catalog();
+    let release = Release {
+        release_date: Some("1985-10-18".to_string()),
+        genre: Some("Platform".to_string()),
+        ..common::release("smb1", "nes", "Super Mario Bros.")
+    };
+    common::add_release(&conn, &release);
+    (conn, release.id)
+}
+
+fn add_nintendo(conn: &rusqlite::Connection) {
+    let company = Company {
+        id: "nintendo".to_string(),
+        name: "Nintendo".to_string(),
+        country: Some("Japan".to_string()),
+        aliases: vec!["Nintendo".to_string(), "Nintendo EAD".to_string()],
+    };
+    upsert_company(conn, &company).unwrap();
+}
+
+#[test]
+fn normalize_drops_punctuation_and_legal_suffixes() {
+    assert_eq!(normalize_company_name("Nintendo Co., Ltd."), "nintendo");
+    assert_eq!(normalize_company_name("SEGA Corporation"), "sega");
+    assert_eq!(normalize_company_name("Hudson Soft"), "hudsonsoft");
+    // A lone suffix-like word is kept
+    assert_eq!(normalize_company_name("Co."), "co");
+}
+
+#[test]
+fn known_names_match_and_unknown_names_are_staged() {
+    let (conn, release_id) = setup_db_with_release();
+    add_nintendo(&conn);
+
+    let known = resolve_company(&conn, "nintendo ead", "gdb", &release_id, "developer").unwrap();
+    assert_eq!(known, CompanyMatch::Known("nintendo".to_string()));
+
+    let staged = resolve_company(
+        &conn,
+        "Nintendo Co., Ltd.",
+        "screenscraper",
+        &release_id,
+        "publisher",
+    )
+    .unwrap();
+    assert!(staged.newly_staged());
+    assert_eq!(staged.company_id(), None);
+
+    // Seen again: counted, not staged twice, and no company was created
+    let again =
+        resolve_company(&conn, "NINTENDO CO., LTD.", "gdb", &release_id, "publisher").unwrap();
+    assert_eq!(again, CompanyMatch::Staged { new: false });
+    assert!(
+        get_company_name(&conn, "nintendo-co-ltd")
+            .unwrap()
+            .is_none()
+    );
+
+    let pending = list_pending_company_aliases(&conn, 10).unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].occurrences, 2);
+    assert_eq!(pending[0].release_count, 1);
+    assert_eq!(pending[0].suggested_company_id.as_deref(), Some("nintendo"));
+}
+
+#[test]
+fn approving_links_waiting_releases() {
+    let (conn, release_id) = setup_db_with_release();
+    add_nintendo(&conn);
+
+    resolve_company(
+        &conn,
+        "Nintendo Co., Ltd.",
+        "screenscraper",
+        &release_id,
+        "publisher",
+    )
+    .unwrap();
+    resolve_company(
+        &conn,
+        "Intelligent Systems",
+        "gdb",
+        &release_id,
+        "developer",
+    )
+    .unwrap();
+    let pending = list_pending_company_aliases(&conn, 10).unwrap();
+    let alias_id = pending
+        .iter()
+        .find(|p| p.alias.starts_with("Nintendo"))
+        .unwrap()
+        .id;
+    let new_id = pending
+        .iter()
+        .find(|p| p.alias == "Intelligent Systems")
+        .unwrap()
+        .id;
+
+    let approved = approve_company_alias(&conn, alias_id, None).unwrap();
+    assert_eq!(approved.company_id, "nintendo");
+    assert!(!approved.company_created);
+    assert_eq!(approved.releases_linked, 1);
+
+    let created = approve_company_alias(&conn, new_id, None).unwrap();
+    assert_eq!(created.company_id, "intelligent-systems");
+    assert!(created.company_created);
+
+    let release = get_release_by_id(&conn, &release_id).unwrap().unwrap();
+    assert_eq!(release.publisher_id.as_deref(), Some("nintendo"));
+    assert_eq!(release.developer_id.as_deref(), Some("intelligent-systems"));
+    assert_eq!(count_pending_company_aliases(&conn).unwrap(), 0);
+
+    // The approved spelling now matches directly
+    let known =
+        resolve_company(&conn, "Nintendo Co., Ltd.", "gdb", &release_id, "publisher").unwrap();
+    assert_eq!(known, CompanyMatch::Known("nintendo".to_string()));
+}
+
+#[test]
+fn rejected_names_stay_unlinked() {
+    let (conn, release_id) = setup_db_with_release();
+
+    resolve_company(&conn, "Unknown", "screenscraper", &release_id, "developer").unwrap();
+    let id = list_pending_company_aliases(&conn, 10).unwrap()[0].id;
+    reject_pending_company_alias(&conn, id).unwrap();
+
+    let again = resolve_company(&conn, "Unknown", "gdb", &release_id, "developer").unwrap();
+    assert_eq!(again, CompanyMatch::Rejected);
+    assert_eq!(count_pending_company_aliases(&conn).unwrap(), 0);
+}
@@ -23,6 +23,18 @@ fn setup_db() -> rusqlite::Connection {
     conn
 }
 
+/// Import `dat` with the registered analyzer for `platform`.
+fn import(
+    conn: &rusqlite::Connection,
+    dat: &DatFile,
+    platform: Platform,
+    dat_source: &str,
+) -> ImportStats {
+    let ctx = retro_junk_lib::create_default_context();
+    let console = ctx.get_by_platform(platform).unwrap();
+    import_dat(conn, dat, console.analyzer.as_ref(), dat_source, None).unwrap()
+}
+
 fn sample_dat() -> DatFile {
     DatFile {
         name: "Nintendo - Nintendo Entertainment System".to_string(),
@@ -32,6 +44,7 @@ fn sample_dat() -> DatFile {
             DatGame {
                 name: "Super Mario Bros. (USA)".to_string(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Super Mario Bros. (USA).nes".to_string(),
                     size: 40976,
@@ -44,6 +57,7 @@ fn sample_dat() -> DatFile {
             DatGame {
                 name: "The Legend of Zelda (USA)".to_string(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Legend of Zelda, The (USA).nes".to_string(),
                     size: 131088,
@@ -56,6 +70,7 @@ fn sample_dat() -> DatFile {
             DatGame {
                 name: "The Legend of Zelda (USA) (Rev A)".to_string(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Legend of Zelda, The (USA) (Rev A).nes".to_string(),
                     size: 131088,
@@ -68,6 +83,7 @@ fn sample_dat() -> DatFile {
             DatGame {
                 name: "Bad Game (USA) [b]".to_string(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Bad Game (USA) [b].nes".to_string(),
                     size: 16384,
@@ -86,7 +102,7 @@ fn import_creates_works_releases_media() {
     let conn = setup_db();
     let dat = sample_dat();
 
-    let stats = import_dat(&conn, &dat, Platform::Nes, "no-intro", None).unwrap();
+    let stats = import(&conn, &dat, Platform::Nes, "no-intro");
 
     // 3 unique works (SMB, Zelda, Bad Game skipped)
     assert_eq!(stats.works_created, 2);
@@ -98,7 +114,7 @@ fn import_creates_works_releases_media() {
 fn import_creates_correct_releases() {
     let conn = setup_db();
     let dat = sample_dat();
-    import_dat(&conn, &dat, Platform::Nes, "no-intro", None).unwrap();
+    import(&conn, &dat, Platform::Nes, "no-intro");
 
     let releases = releases_for_platform(&conn, "nes").unwrap();
     assert_eq!(releases.len(), 3); // SMB + Zelda + Zelda Rev A (revisions are separate releases)
@@ -119,7 +135,7 @@ fn import_creates_correct_releases() {
 fn import_media_has_correct_hashes() {
     let conn = setup_db();
     let dat = sample_dat();
-    import_dat(&conn, &dat, Platform::Nes, "no-intro", None).unwrap();
+    import(&conn, &dat, Platform::Nes, "no-intro");
 
     let media = find_media_by_crc32(&conn, "d445f698").unwrap();
     assert_eq!(media.len(), 1);
@@ -135,7 +151,7 @@ fn import_media_has_correct_hashes() {
 fn import_revision_creates_separate_media() {
     let conn = setup_db();
     let dat = sample_dat();
-    import_dat(&conn, &dat, Platform::Nes, "no-intro", None).unwrap();
+    import(&conn, &dat, Platform::Nes, "no-intro");
 
     // Both Zelda entries should exist as media
     let zelda_orig = find_media_by_crc32(&conn, "a12d74c1").unwrap();
@@ -156,10 +172,10 @@ fn reimport_is_idempotent() {
     let conn = setup_db();
     let dat = sample_dat();
 
-    let stats1 = import_dat(&conn, &dat, Platform::Nes, "no-intro", None).unwrap();
+    let stats1 = import(&conn, &dat, Platform::Nes, "no-intro");
     assert_eq!(stats1.media_created, 3);
 
-    let stats2 = import_dat(&conn, &dat, Platform::Nes, "no-intro", None).unwrap();
+    let stats2 = import(&conn, &dat, Platform::Nes, "no-intro");
     assert_eq!(stats2.media_created, 0);
     assert_eq!(stats2.media_unchanged, 3);
     // 3 games processed (bad dump skipped), each finds existing work
@@ -167,11 +183,121 @@ fn reimport_is_idempotent() {
     assert_eq!(stats2.works_existing, 3);
 }
 
+#[test]
+fn non_latin_titles_get_distinct_works() {
+    let conn = setup_db();
+    let game = |name: &str, crc: &str| DatGame {
+        name: name.to_string(),
+        region: None,
+        clone_of: None,
+        roms: vec![DatRom {
+            name: format!("{}.nes", name),
+            size: 1024,
+            crc: crc.to_string(),
+            sha1: None,
+            md5: None,
+            serial: None,
+        }],
+    };
+    let dat = DatFile {
+        name: "Nintendo - Family Computer".to_string(),
+        description: String::new(),
+        version: "1".to_string(),
+        games: vec![
+            game("ドラゴンクエスト (Japan)", "00000001"),
+            game("ゼルダの伝説 (Japan)", "00000002"),
+            game("Pokémon Puzzle (Japan)", "00000003"),
+        ],
+    };
+
+    let stats = import(&conn, &dat, Platform::Nes, "no-intro");
+    assert_eq!(stats.works_created, 3);
+    assert!(
+        get_work_by_id(&conn, "nes:pokemon-puzzle")
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[test]
+fn reimport_reuses_legacy_work_ids() {
+    let conn = setup_db();
+    // A work created by an older import that dropped non-ASCII characters
+    insert_work(&conn, "nes:pok-mon-puzzle", "Pokémon Puzzle").unwrap();
+
+    let dat = DatFile {
+        name: "Nintendo - Nintendo Entertainment System".to_string(),
+        description: String::new(),
+        version: "1".to_string(),
+        games: vec![DatGame {
+            name: "Pokémon Puzzle (USA)".to_string(),
+            region: None,
+            clone_of: None,
+            roms: vec![DatRom {
+                name: "Pokémon Puzzle (USA).nes".to_string(),
+                size: 1024,
+                crc: "00000003".to_string(),
+                sha1: None,
+                md5: None,
+                serial: None,
+            }],
+        }],
+    };
+
+    let stats = import(&conn, &dat, Platform::Nes, "no-intro");
+    assert_eq!(stats.works_existing, 1);
+    assert!(
+        get_work_by_id(&conn, "nes:pokemon-puzzle")
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn reconcile_merges_works_with_matching_title_keys() {
+    let conn = setup_db();
+    let game = |name: &str, crc: &str| DatGame {
+        name: name.to_string(),
+        region: None,
+        clone_of: None,
+        roms: vec![DatRom {
+            name: format!("{}.nes", name),
+            size: 1024,
+            crc: crc.to_string(),
+            sha1: None,
+            md5: None,
+            serial: None,
+        }],
+    };
+    let dat = DatFile {
+        name: "Nintendo - Nintendo Entertainment System".to_string(),
+        description: String::new(),
+        version: "1".to_string(),
+        games: vec![
+            game("Legend of Zelda, The (USA)", "00000001"),
+            game("The Legend of Zelda (Europe)", "00000002"),
+            game("Die Hard (USA)", "00000003"),
+            game("Hard (Germany)", "00000004"),
+        ],
+    };
+    import(&conn, &dat, Platform::Nes, "no-intro");
+
+    let options = reconcile::ReconcileOptions {
+        platform_ids: vec![],
+        dry_run: false,
+    };
+    let result = reconcile::reconcile_works(&conn, &options).unwrap();
+    // The Zeldas merge; "Die Hard" (USA) keeps its "Die"
+    assert_eq!(result.stats.groups_found, 1);
+    assert_eq!(result.stats.works_merged, 1);
+    assert_eq!(count_works_search(&conn, "zelda").unwrap(), 1);
+}
+
 #[test]
 fn bad_dumps_skipped() {
     let conn = setup_db();
     let dat = sample_dat();
-    let stats = import_dat(&conn, &dat, Platform::Nes, "no-intro", None).unwrap();
+    let stats = import(&conn, &dat, Platform::Nes, "no-intro");
 
     assert_eq!(stats.skipped_bad, 1);
     assert_eq!(stats.total_games, 4);
@@ -181,7 +307,7 @@ fn bad_dumps_skipped() {
 fn log_import_records_stats() {
     let conn = setup_db();
     let dat = sample_dat();
-    let stats = import_dat(&conn, &dat, Platform::Nes, "no-intro", None).unwrap();
+    let stats = import(&conn, &dat, Platform::Nes, "no-intro");
 
     let log_id = log_import(
         &conn,
@@ -209,6 +335,7 @@ fn multi_region_game() {
         games: vec![DatGame {
             name: "Tetris (USA, Europe)".to_string(),
             region: None,
+            clone_of: None,
             roms: vec![DatRom {
                 name: "Tetris (USA, Europe).nes".to_string(),
                 size: 32768,
@@ -220,7 +347,7 @@ fn multi_region_game() {
         }],
     };
 
-    let stats = import_dat(&conn, &dat, Platform::Nes, "no-intro", None).unwrap();
+    let stats = import(&conn, &dat, Platform::Nes, "no-intro");
     assert_eq!(stats.works_created, 1);
     assert_eq!(stats.releases_created, 1);
 
@@ -239,6 +366,7 @@ fn prototype_flag_sets_media_status() {
         games: vec![DatGame {
             name: "Unreleased Game (USA) (Proto)".to_string(),
             region: None,
+            clone_of: None,
             roms: vec![DatRom {
                 name: "Unreleased Game (USA) (Proto).nes".to_string(),
                 size: 16384,
@@ -250,12 +378,58 @@ fn prototype_flag_sets_media_status() {
         }],
     };
 
-    import_dat(&conn, &dat, Platform::Nes, "no-intro", None).unwrap();
+    import(&conn, &dat, Platform::Nes, "no-intro");
     let media = find_media_by_crc32(&conn, "11223344").unwrap();
     assert_eq!(media.len(), 1);
     assert_eq!(media[0].status, MediaStatus::Prototype);
 }
 
+#[test]
+fn dat_serials_stored_in_canonical_form() {
+    let conn = setup_db();
+    let ps1 = CatalogPlatform {
+        id: "ps1".to_string(),
+        display_name: "Sony PlayStation".to_string(),
+        short_name: "PS1".to_string(),
+        manufacturer: "Sony".to_string(),
+        generation: Some(5),
+        media_type: MediaType::Disc,
+        release_year: Some(1994),
+        description: None,
+        core_platform: Some("Ps1".to_string()),
+        regions: vec![],
+        relationships: vec![],
+    };
+    upsert_platform(&conn, &ps1).unwrap();
+
+    let dat = DatFile {
+        name: "Test".to_string(),
+        description: "Test".to_string(),
+        version: "1".to_string(),
+        games: vec![DatGame {
+            name: "Parasite Eve (Japan)".to_string(),
+            region: None,
+            clone_of: None,
+            roms: vec![DatRom {
+                name: "Parasite Eve (Japan).bin".to_string(),
+                size: 700000000,
+                crc: "aabb0003".to_string(),
+                sha1: None,
+                md5: None,
+                serial: Some("SLPS 01230, slps-01231".to_string()),
+            }],
+        }],
+    };
+
+    import(&conn, &dat, Platform::Ps1, "redump");
+
+    let media = find_media_by_crc32(&conn, "aabb0003").unwrap();
+    assert_eq!(
+        media[0].media_serial.as_deref(),
+        Some("SLPS-01230, SLPS-01231")
+    );
+}
+
 #[test]
 fn disc_number_extracted() {
     let conn = setup_db();
@@ -282,6 +456,7 @@ fn disc_number_extracted() {
             DatGame {
                 name: "Final Fantasy VII (USA) (Disc 1)".to_string(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Final Fantasy VII (USA) (Disc 1).bin".to_string(),
                     size: 700000000,
@@ -294,6 +469,7 @@ fn disc_number_extracted() {
             DatGame {
                 name: "Final Fantasy VII (USA) (Disc 2)".to_string(),
                 region: None,
+                clone_of: None,
                 roms: vec![DatRom {
                     name: "Final Fantasy VII (USA) (Disc 2).bin".to_string(),
                     size: 700000000,
@@ -306,7 +482,7 @@ fn disc_number_extracted() {
         ],
     };
 
-    import_dat(&conn, &dat, Platform::Ps1, "redump", None).unwrap();
+    import(&conn, &dat, Platform::Ps1, "redump");
 
     let disc1 = find_media_by_crc32(&conn, "aabb0001").unwrap();
     let disc2 = find_media_by_crc32(&conn, "aabb0002").unwrap();
@@ -0,0 +1,206 @@
+use retro_junk_catalog::types::*;
+use retro_junk_core::Platform;
+use retro_junk_db::*;
+use retro_junk_import::dumper_ingest::{
+    DumpOutcome, DumperOptions, DumperState, DumperWatcher, ingest_dump,
+};
+
+mod common;
+
+/// Fake cartridge contents: headerless, so the NES analyzer hashes it as-is.
+const CART: &[u8] = &[0x4c; 8192];
+
+fn setup_db(crc32: &str, sha1: Option<String>, dat_name: &str) -> rusqlite::Connection {
+    let conn = common::nes_catalog();
+    let release = common::release("nes:dr-mario", "nes", "Dr. Mario");
+    common::add_release(&conn, &release);
+    let media = Media {
+        file_size: Some(CART.len() as i64),
+        crc32: Some(crc32.to_string()),
+        sha1,
+        ..common::media("m1", &release.id, Some(dat_name))
+    };
+    upsert_media(&conn, &media).unwrap();
+    conn
+}
+
+fn nes_context() -> retro_junk_lib::AnalysisContext {
+    retro_junk_lib::create_default_context()
+}
+
+#[test]
+fn verified_dump_is_copied_and_recorded() {
+    let ctx = nes_context();
+    let analyzer = ctx
+        .get_by_platform(Platform::Nes)
+        .unwrap()
+        .analyzer
+        .as_ref();
+    let hashes =
+        retro_junk_lib::hasher::compute_crc32_sha1(&mut std::io::Cursor::new(CART), analyzer, None)
+            .unwrap();
+    let conn = setup_db(&hashes.crc32, hashes.sha1.clone(), "Dr. Mario (Japan, USA)");
+
+    let dir = tempfile::tempdir().unwrap();
+    let dump = dir.path().join("DR MARIO.nes");
+    std::fs::write(&dump, CART).unwrap();
+    let library = dir.path().join("library").join("nes");
+    let options = DumperOptions {
+        library_dir: library.clone(),
+        user_id: "default".to_string(),
+    };
+
+    let outcome = ingest_dump(&conn, &dump, analyzer, Platform::Nes, &options).unwrap();
+    let DumpOutcome::Added {
+        title,
+        target,
+        already_owned,
+    } = outcome
+    else {
+        panic!("expected dump to verify");
+    };
+    assert_eq!(title, "Dr. Mario");
+    assert_eq!(target, library.join("Dr. Mario (Japan, USA).nes"));
+    assert!(!already_owned);
+    assert_eq!(std::fs::read(&target).unwrap(), CART);
+    // Original is left in place on the dumper
+    assert!(dump.exists());
+
+    let entry = find_collection_entry(&conn, "m1", "default")
+        .unwrap()
+        .unwrap();
+    assert_eq!(entry.rom_path.as_deref(), Some(target.to_str().unwrap()));
+
+    let again = ingest_dump(&conn, &dump, analyzer, Platform::Nes, &options).unwrap();
+    assert!(matches!(
+        again,
+        DumpOutcome::Added {
+            already_owned: true,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn different_file_at_target_is_reported_as_conflict() {
+    let ctx = nes_context();
+    let analyzer = ctx
+        .get_by_platform(Platform::Nes)
+        .unwrap()
+        .analyzer
+        .as_ref();
+    let hashes =
+        retro_junk_lib::hasher::compute_crc32_sha1(&mut std::io::Cursor::new(CART), analyzer, None)
+            .unwrap();
+    let conn = setup_db(&hashes.crc32, hashes.sha1.clone(), "Dr. Mario (Japan, USA)");
+
+    let dir = tempfile::tempdir().unwrap();
+    let dump = dir.path().join("DR MARIO.nes");
+    std::fs::write(&dump, CART).unwrap();
+    let library = dir.path().join("library");
+    std::fs::create_dir_all(&library).unwrap();
+    let existing = library.join("Dr. Mario (Japan, USA).nes");
+    std::fs::write(&existing, b"some other file").unwrap();
+    let options = DumperOptions {
+        library_dir: library.clone(),
+        user_id: "default".to_string(),
+    };
+
+    let outcome = ingest_dump(&conn, &dump, analyzer, Platform::Nes, &options).unwrap();
+    let DumpOutcome::Conflict { target, .. } = outcome else {
+        panic!("expected a conflict, got {:?}", outcome);
+    };
+    assert_eq!(target, existing);
+    assert_eq!(std::fs::read(&existing).unwrap(), b"some other file");
+    assert!(
+        find_collection_entry(&conn, "m1", "default")
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn unknown_dump_is_reported_unverified() {
+    let ctx = nes_context();
+    let analyzer = ctx
+        .get_by_platform(Platform::Nes)
+        .unwrap()
+        .analyzer
+        .as_ref();
+    let conn = setup_db("00000000", None, "Dr. Mario (Japan, USA)");
+
+    let dir = tempfile::tempdir().unwrap();
+    let dump = dir.path().join("dirty.nes");
+    std::fs::write(&dump, CART).unwrap();
+    let options = DumperOptions {
+        library_dir: dir.path().join("library"),
+        user_id: "default".to_string(),
+    };
+
+    let outcome = ingest_dump(&conn, &dump, analyzer, Platform::Nes, &options).unwrap();
+    assert!(matches!(outcome, DumpOutcome::Unverified { .. }));
+    assert!(!dir.path().join("library").exists());
+}
+
+#[test]
+fn watcher_waits_for_files_to_settle() {
+    let ctx = nes_context();
+    let analyzer = ctx
+        .get_by_platform(Platform::Nes)
+        .unwrap()
+        .analyzer
+        .as_ref();
+    let dir = tempfile::tempdir().unwrap();
+    let mut watcher = DumperWatcher::new(dir.path(), analyzer);
+
+    std::fs::write(dir.path().join("cart.nes"), CART).unwrap();
+    std::fs::write(dir.path().join("cart.srm"), [0u8; 16]).unwrap();
+
+    // First sighting only records the file
+    assert!(watcher.poll().unwrap().is_empty());
+    // Unchanged on the next poll: ready, and only ROM extensions count
+    assert_eq!(watcher.poll().unwrap(), vec![dir.path().join("cart.nes")]);
+    // Not handed out twice
+    assert!(watcher.poll().unwrap().is_empty());
+}
+
+#[test]
+fn restarted_watcher_skips_handled_dumps() {
+    let ctx = nes_context();
+    let analyzer = ctx
+        .get_by_platform(Platform::Nes)
+        .unwrap()
+        .analyzer
+        .as_ref();
+    let dir = tempfile::tempdir().unwrap();
+    let dumps = dir.path().join("dumps");
+    std::fs::create_dir(&dumps).unwrap();
+    std::fs::write(dumps.join("a.nes"), CART).unwrap();
+    std::fs::write(dumps.join("b.nes"), CART).unwrap();
+
+    let mut watcher = DumperWatcher::new(&dumps, analyzer);
+    watcher.poll().unwrap();
+    let ready = watcher.poll().unwrap();
+    assert_eq!(ready.len(), 2);
+    // Stopped before processing the second dump
+    watcher.release(&ready[1]);
+
+    let state_path = dir.path().join("state").join("dumper.toml");
+    let mut state = DumperState::load(&state_path).unwrap();
+    state.record(&Ok(DumpOutcome::Unverified {
+        crc32: "00000000".into(),
+        sha1: None,
+        header_title: None,
+    }));
+    state.handled = watcher.handled();
+    state.save(&state_path).unwrap();
+
+    let state = DumperState::load(&state_path).unwrap();
+    assert_eq!(state.unverified, 1);
+    assert_eq!(state.handled.len(), 1);
+
+    let mut restarted = DumperWatcher::new(&dumps, analyzer);
+    restarted.mark_handled(&state.handled);
+    assert!(restarted.poll().unwrap().is_empty());
+    assert_eq!(restarted.poll().unwrap(), vec![dumps.join("b.nes")]);
+}
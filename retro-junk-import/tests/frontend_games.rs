@@ -0,0 +1,137 @@
+use retro_junk_catalog::types::*;
+use retro_junk_db::*;
+use retro_junk_frontend::AssetType;
+use retro_junk_import::{
+    CatalogGameOptions, release_asset_paths, scraped_game_from_release, scraped_games_for_platform,
+};
+
+mod common;
+
+fn make_release() -> Release {
+    Release {
+        publisher_id: Some("nintendo".to_string()),
+        release_date: Some("1985-10-18".to_string()),
+        game_serial: Some("NES-SM-USA".to_string()),
+        genre: Some("Platform".to_string()),
+        players: Some("1-2".to_string()),
+        rating: Some(0.9),
+        description: Some("Save the princess.".to_string()),
+        cover_title: Some("Super Mario Bros.".to_string()),
+        ..common::release("nes:smb", "nes", "Super Mario Bros.")
+    }
+}
+
+fn make_asset(asset_type: &str, region: &str, file_path: &std::path::Path) -> Asset {
+    Asset {
+        id: 0,
+        release_id: Some("nes:smb:nes:usa".to_string()),
+        media_id: None,
+        asset_type: asset_type.to_string(),
+        region: Some(region.to_string()),
+        source: "screenscraper".to_string(),
+        file_path: Some(file_path.to_string_lossy().to_string()),
+        source_url: None,
+        scraped: true,
+        file_hash: None,
+        width: None,
+        height: None,
+        created_at: String::new(),
+    }
+}
+
+fn setup_db() -> rusqlite::Connection {
+    let conn = common::nes_catalog();
+    upsert_company(
+        &conn,
+        &Company {
+            id: "nintendo".to_string(),
+            name: "Nintendo".to_string(),
+            country: None,
+            aliases: vec![],
+        },
+    )
+    .unwrap();
+    common::add_release(&conn, &make_release());
+
+    for (id, dat_name) in [
+        ("m1", Some("Super Mario Bros. (World)")),
+        ("m2", Some("Super Mario Bros. (World)")),
+        ("m3", None),
+    ] {
+        upsert_media(&conn, &common::media(id, "nes:smb:nes:usa", dat_name)).unwrap();
+    }
+    conn
+}
+
+#[test]
+fn asset_paths_prefer_release_region_and_existing_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let world = dir.path().join("box-world.png");
+    let usa = dir.path().join("box-usa.png");
+    let wheel = dir.path().join("wheel.png");
+    for path in [&world, &usa, &wheel] {
+        std::fs::write(path, b"png").unwrap();
+    }
+
+    let assets = vec![
+        make_asset("box-front", "wor", &world),
+        make_asset("box-front", "us", &usa),
+        make_asset("wheel", "wor", &wheel),
+        make_asset("screenshot", "us", &dir.path().join("missing.png")),
+        make_asset("manual", "us", &wheel),
+    ];
+    let paths = release_asset_paths(&make_release(), &assets);
+
+    assert_eq!(paths.len(), 2);
+    assert_eq!(paths[&AssetType::Cover], usa);
+    assert_eq!(paths[&AssetType::Marquee], wheel);
+}
+
+#[test]
+fn game_from_release_uses_catalog_metadata() {
+    let conn = setup_db();
+    let dir = tempfile::tempdir().unwrap();
+    let cover = dir.path().join("cover.png");
+    std::fs::write(&cover, b"png").unwrap();
+    insert_asset(&conn, &make_asset("box-front", "us", &cover)).unwrap();
+
+    let game = scraped_game_from_release(
+        &conn,
+        &make_release(),
+        "Super Mario Bros. (World)",
+        "Super Mario Bros. (World).nes",
+    )
+    .unwrap();
+
+    assert_eq!(game.rom_stem, "Super Mario Bros. (World)");
+    assert_eq!(game.name, "Super Mario Bros.");
+    assert_eq!(game.publisher.as_deref(), Some("Nintendo"));
+    assert_eq!(game.developer, None);
+    assert_eq!(game.release_date.as_deref(), Some("1985-10-18"));
+    assert_eq!(game.rating, Some(0.9));
+    assert_eq!(game.assets[&AssetType::Cover], cover);
+}
+
+#[test]
+fn platform_games_one_per_named_rom() {
+    let conn = setup_db();
+    let options = CatalogGameOptions {
+        extension: "nes".to_string(),
+        owned_by: None,
+    };
+    let games = scraped_games_for_platform(&conn, "nes", &options).unwrap();
+
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].rom_filename, "Super Mario Bros. (World).nes");
+    assert_eq!(games[0].genre.as_deref(), Some("Platform"));
+
+    let owned = CatalogGameOptions {
+        owned_by: Some("nobody".to_string()),
+        ..options
+    };
+    assert!(
+        scraped_games_for_platform(&conn, "nes", &owned)
+            .unwrap()
+            .is_empty()
+    );
+}
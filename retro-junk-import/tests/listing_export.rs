@@ -0,0 +1,131 @@
+use retro_junk_catalog::types::*;
+use retro_junk_db::*;
+use retro_junk_import::{
+    EmuMoviesNaming, EverDriveNaming, ListingOptions, NamingConvention, NoIntroNaming,
+    build_listing, find_convention, write_smdb,
+};
+
+mod common;
+
+fn setup_db() -> rusqlite::Connection {
+    let conn = common::nes_catalog();
+    let release = common::release("nes:smb", "nes", "Super Mario Bros.");
+    common::add_release(&conn, &release);
+
+    for (id, dat_name, crc32) in [
+        ("m1", Some("Super Mario Bros. (World)"), "3337ec46"),
+        ("m2", Some("Super Mario Bros. (World) (Rev 1)"), "d445f698"),
+        ("m3", None, "00000000"),
+    ] {
+        let media = Media {
+            file_size: Some(40976),
+            crc32: Some(crc32.to_string()),
+            sha1: Some(format!("sha1-{id}")),
+            ..common::media(id, &release.id, dat_name)
+        };
+        upsert_media(&conn, &media).unwrap();
+    }
+    conn
+}
+
+fn options(convention: &dyn NamingConvention) -> ListingOptions<'_> {
+    ListingOptions {
+        convention,
+        extension: "nes".to_string(),
+        folder: None,
+        owned_by: None,
+    }
+}
+
+#[test]
+fn conventions_name_files() {
+    let name = "Final Fantasy VII (USA) (Disc 2) (Rev 1)";
+    assert_eq!(NoIntroNaming.file_stem(name), name);
+    assert_eq!(
+        EmuMoviesNaming.file_stem(name),
+        "Final Fantasy VII (USA) (Disc 2)"
+    );
+    assert_eq!(
+        EverDriveNaming.file_stem(name),
+        "F/Final Fantasy VII (USA) (Disc 2) (Rev 1)"
+    );
+    assert_eq!(
+        EverDriveNaming.file_stem("1942 (Japan, USA)"),
+        "#/1942 (Japan, USA)"
+    );
+}
+
+#[test]
+fn find_convention_by_id() {
+    assert_eq!(find_convention("EmuMovies").unwrap().id(), "emumovies");
+    assert!(find_convention("goodtools").is_none());
+}
+
+#[test]
+fn listing_uses_dat_names() {
+    let conn = setup_db();
+    let mut opts = options(&NoIntroNaming);
+    opts.folder = Some("NES/".to_string());
+    let listing = build_listing(&conn, "nes", &opts).unwrap();
+
+    let paths: Vec<_> = listing.entries.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            "NES/Super Mario Bros. (World).nes",
+            "NES/Super Mario Bros. (World) (Rev 1).nes",
+        ]
+    );
+    assert_eq!(listing.duplicates, 0);
+    assert_eq!(listing.unnamed, 1);
+}
+
+#[test]
+fn listing_skips_duplicate_names() {
+    let conn = setup_db();
+    let listing = build_listing(&conn, "nes", &options(&EmuMoviesNaming)).unwrap();
+
+    assert_eq!(listing.entries.len(), 1);
+    assert_eq!(listing.entries[0].path, "Super Mario Bros. (World).nes");
+    assert_eq!(listing.duplicates, 1);
+}
+
+#[test]
+fn listing_only_owned_media() {
+    let conn = setup_db();
+    upsert_collection_entry(
+        &conn,
+        &CollectionEntry {
+            id: 0,
+            media_id: "m1".to_string(),
+            user_id: "default".to_string(),
+            owned: true,
+            condition: None,
+            notes: None,
+            date_acquired: None,
+            rom_path: None,
+            verified_at: None,
+        },
+    )
+    .unwrap();
+    let mut opts = options(&NoIntroNaming);
+    opts.owned_by = Some("default".to_string());
+    let listing = build_listing(&conn, "nes", &opts).unwrap();
+
+    assert_eq!(listing.entries.len(), 1);
+    assert_eq!(listing.entries[0].crc32.as_deref(), Some("3337ec46"));
+}
+
+#[test]
+fn smdb_lines_leave_sha256_empty() {
+    let conn = setup_db();
+    let listing = build_listing(&conn, "nes", &options(&NoIntroNaming)).unwrap();
+    let mut out = Vec::new();
+    write_smdb(&mut out, &listing.entries).unwrap();
+
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\tSuper Mario Bros. (World).nes\tsha1-m1\t\t3337ec46\n\
+         \tSuper Mario Bros. (World) (Rev 1).nes\tsha1-m2\t\td445f698\n"
+    );
+}
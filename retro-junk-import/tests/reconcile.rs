@@ -0,0 +1,155 @@
+use retro_junk_catalog::types::*;
+use retro_junk_db::*;
+use retro_junk_import::reconcile::*;
+
+fn add_platform(conn: &rusqlite::Connection, id: &str, name: &str) {
+    let platform = CatalogPlatform {
+        id: id.to_string(),
+        display_name: name.to_string(),
+        short_name: name.to_string(),
+        manufacturer: String::new(),
+        generation: Some(4),
+        media_type: MediaType::Cartridge,
+        release_year: None,
+        description: None,
+        core_platform: None,
+        regions: vec![],
+        relationships: vec![],
+    };
+    upsert_platform(conn, &platform).unwrap();
+}
+
+fn add_release(conn: &rusqlite::Connection, work_id: &str, platform_id: &str, region: &str) {
+    let title = get_work_by_id(conn, work_id)
+        .unwrap()
+        .unwrap()
+        .canonical_name;
+    let release = Release {
+        id: format!("{}-{}", work_id, region),
+        work_id: work_id.to_string(),
+        platform_id: platform_id.to_string(),
+        region: region.to_string(),
+        revision: String::new(),
+        variant: String::new(),
+        title,
+        alt_title: None,
+        publisher_id: None,
+        developer_id: None,
+        release_date: None,
+        game_serial: None,
+        genre: None,
+        players: None,
+        rating: None,
+        description: None,
+        screen_title: None,
+        cover_title: None,
+        screenscraper_id: None,
+        scraper_not_found: false,
+        created_at: String::new(),
+        updated_at: String::new(),
+    };
+    upsert_release(conn, &release).unwrap();
+}
+
+fn add_work(conn: &rusqlite::Connection, id: &str, name: &str, platform_id: &str, region: &str) {
+    insert_work(conn, id, name).unwrap();
+    add_release(conn, id, platform_id, region);
+}
+
+fn setup_db() -> rusqlite::Connection {
+    let conn = open_memory().unwrap();
+    add_platform(&conn, "snes", "SNES");
+    add_platform(&conn, "genesis", "Genesis");
+    add_work(&conn, "snes:aladdin", "Aladdin", "snes", "usa");
+    add_work(&conn, "genesis:aladdin", "Aladdin", "genesis", "usa");
+    add_work(&conn, "snes:f-zero", "F-Zero", "snes", "usa");
+    conn
+}
+
+fn ids(works: &[&str]) -> Vec<String> {
+    works.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn siblings_reported_across_platforms() {
+    let conn = setup_db();
+    let groups = find_cross_platform_siblings(&conn, &[]).unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].title_key, "aladdin");
+    let works: Vec<_> = groups[0].works.iter().map(|w| w.work_id.as_str()).collect();
+    assert_eq!(works, ["genesis:aladdin", "snes:aladdin"]);
+
+    // Siblings are reported, never merged
+    let options = ReconcileOptions {
+        platform_ids: vec![],
+        dry_run: false,
+    };
+    let result = reconcile_works(&conn, &options).unwrap();
+    assert_eq!(result.stats.groups_found, 0);
+    assert!(get_work_by_id(&conn, "genesis:aladdin").unwrap().is_some());
+}
+
+#[test]
+fn platform_filter_limits_groups() {
+    let conn = setup_db();
+    add_platform(&conn, "nes", "NES");
+    add_work(&conn, "nes:f-zero", "F-Zero", "nes", "usa");
+
+    let groups = find_cross_platform_siblings(&conn, &ids(&["genesis"])).unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].title_key, "aladdin");
+    assert_eq!(find_cross_platform_siblings(&conn, &[]).unwrap().len(), 2);
+}
+
+#[test]
+fn linked_and_rejected_siblings_are_not_reported_again() {
+    let conn = setup_db();
+    add_platform(&conn, "nes", "NES");
+    add_work(&conn, "nes:f-zero", "F-Zero", "nes", "usa");
+
+    let pairs = link_siblings(&conn, &ids(&["snes:aladdin", "genesis:aladdin"])).unwrap();
+    assert_eq!(pairs, 1);
+    reject_siblings(&conn, &ids(&["nes:f-zero", "snes:f-zero"])).unwrap();
+    assert!(find_cross_platform_siblings(&conn, &[]).unwrap().is_empty());
+
+    let rels = relationships_for_work(&conn, "snes:aladdin").unwrap();
+    assert_eq!(rels.len(), 1);
+    assert_eq!(rels[0].relationship, "sibling");
+    assert_eq!(
+        list_work_relationships(&conn, Some(WorkRelationship::NotSibling))
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn linking_unknown_work_fails() {
+    let conn = setup_db();
+    let err = link_siblings(&conn, &ids(&["snes:aladdin", "nes:aladdin"]));
+    assert!(err.is_err());
+    assert!(list_work_relationships(&conn, None).unwrap().is_empty());
+}
+
+#[test]
+fn merge_keeps_sibling_links() {
+    let conn = setup_db();
+    add_release(&conn, "snes:aladdin", "snes", "europe");
+    add_work(&conn, "snes:aladdin-jp", "Aladdin", "snes", "japan");
+    link_siblings(&conn, &ids(&["snes:aladdin-jp", "genesis:aladdin"])).unwrap();
+
+    // snes:aladdin hasn't been reviewed against genesis:aladdin yet
+    assert_eq!(find_cross_platform_siblings(&conn, &[]).unwrap().len(), 1);
+
+    let options = ReconcileOptions {
+        platform_ids: vec![],
+        dry_run: false,
+    };
+    let result = reconcile_works(&conn, &options).unwrap();
+    assert_eq!(result.stats.works_merged, 1);
+    assert!(get_work_by_id(&conn, "snes:aladdin-jp").unwrap().is_none());
+
+    let rels = relationships_for_work(&conn, "snes:aladdin").unwrap();
+    assert_eq!(rels.len(), 1);
+    assert!(find_cross_platform_siblings(&conn, &[]).unwrap().is_empty());
+}
@@ -0,0 +1,130 @@
+use retro_junk_catalog::types::*;
+use retro_junk_core::Platform;
+use retro_junk_db::*;
+use retro_junk_import::refresh_collection;
+
+mod common;
+
+/// A Genesis ROM whose header carries a serial and a copyright date.
+fn genesis_rom() -> Vec<u8> {
+    let mut rom = vec![0u8; 0x400];
+    rom[0x100..0x110].copy_from_slice(b"SEGA GENESIS    ");
+    rom[0x110..0x120].copy_from_slice(b"(C)SEGA 1991.APR");
+    rom[0x180..0x18E].copy_from_slice(b"GM 00001009-00");
+    rom
+}
+
+fn setup_db(game_serial: Option<&str>) -> rusqlite::Connection {
+    let conn = common::catalog_with(&CatalogPlatform {
+        id: "genesis".to_string(),
+        display_name: "Sega Genesis".to_string(),
+        short_name: "Genesis".to_string(),
+        manufacturer: "Sega".to_string(),
+        generation: Some(4),
+        release_year: Some(1989),
+        core_platform: Some("Genesis".to_string()),
+        ..common::nes_platform()
+    });
+    let release = Release {
+        game_serial: game_serial.map(str::to_string),
+        ..common::release("genesis:sonic", "genesis", "Sonic the Hedgehog")
+    };
+    common::add_release(&conn, &release);
+    let media = Media {
+        file_size: Some(0x400),
+        crc32: Some("00000000".to_string()),
+        ..common::media("m1", &release.id, Some("Sonic the Hedgehog (USA, Europe)"))
+    };
+    upsert_media(&conn, &media).unwrap();
+    conn
+}
+
+fn own(conn: &rusqlite::Connection, user_id: &str, rom_path: Option<&std::path::Path>) {
+    upsert_collection_entry(
+        conn,
+        &CollectionEntry {
+            id: 0,
+            media_id: "m1".to_string(),
+            user_id: user_id.to_string(),
+            owned: true,
+            condition: None,
+            notes: None,
+            date_acquired: None,
+            rom_path: rom_path.map(|p| p.to_string_lossy().to_string()),
+            verified_at: None,
+        },
+    )
+    .unwrap();
+}
+
+fn refresh(conn: &rusqlite::Connection) -> retro_junk_import::RefreshStats {
+    let ctx = retro_junk_lib::create_default_context();
+    let analyzer = ctx
+        .get_by_platform(Platform::Genesis)
+        .unwrap()
+        .analyzer
+        .as_ref();
+    refresh_collection(conn, analyzer, Platform::Genesis).unwrap()
+}
+
+#[test]
+fn refresh_fills_missing_metadata_once() {
+    let conn = setup_db(None);
+    let dir = tempfile::tempdir().unwrap();
+    let rom = dir.path().join("sonic.md");
+    std::fs::write(&rom, genesis_rom()).unwrap();
+    own(&conn, "default", Some(&rom));
+    own(&conn, "friend", Some(&rom));
+
+    let stats = refresh(&conn);
+    assert_eq!(stats.checked, 1, "media owned twice is analyzed once");
+    assert_eq!(stats.updated, 1);
+    assert_eq!(stats.serials_filled, 2);
+    assert_eq!(stats.release_dates_filled, 1);
+
+    let release = get_release_by_id(&conn, "genesis:sonic:genesis:usa")
+        .unwrap()
+        .unwrap();
+    assert_eq!(release.game_serial.as_deref(), Some("GM 00001009-00"));
+    assert_eq!(release.release_date.as_deref(), Some("1991-04"));
+    let media = get_media_by_id(&conn, "m1").unwrap().unwrap();
+    assert_eq!(media.media_serial.as_deref(), Some("GM 00001009-00"));
+    assert_eq!(media.crc32.as_deref(), Some("00000000"), "hashes untouched");
+
+    let again = refresh(&conn);
+    assert_eq!(again.updated, 0);
+    assert_eq!(again.unchanged, 1);
+}
+
+#[test]
+fn refresh_keeps_existing_values() {
+    let conn = setup_db(Some("MK-1009"));
+    let dir = tempfile::tempdir().unwrap();
+    let rom = dir.path().join("sonic.md");
+    std::fs::write(&rom, genesis_rom()).unwrap();
+    own(&conn, "default", Some(&rom));
+
+    let stats = refresh(&conn);
+    assert_eq!(stats.serials_filled, 1);
+    let release = get_release_by_id(&conn, "genesis:sonic:genesis:usa")
+        .unwrap()
+        .unwrap();
+    assert_eq!(release.game_serial.as_deref(), Some("MK-1009"));
+}
+
+#[test]
+fn refresh_reports_missing_and_pathless_entries() {
+    let conn = setup_db(None);
+    let dir = tempfile::tempdir().unwrap();
+    own(&conn, "default", Some(&dir.path().join("gone.md")));
+
+    let stats = refresh(&conn);
+    assert_eq!(stats.missing, 1);
+    assert_eq!(stats.problems.len(), 1);
+
+    let conn = setup_db(None);
+    own(&conn, "default", None);
+    let stats = refresh(&conn);
+    assert_eq!(stats.no_path, 1);
+    assert_eq!(stats.checked, 0);
+}
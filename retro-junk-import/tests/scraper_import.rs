@@ -201,6 +201,14 @@ fn catalog_region_to_ss_mappings() {
     assert_eq!(catalog_region_to_ss("unknown"), "us");
 }
 
+#[test]
+fn known_catalog_region_rejects_unknown_regions() {
+    assert_eq!(known_catalog_region("jp"), Some("jp"));
+    assert_eq!(known_catalog_region("brazil"), Some("br"));
+    assert_eq!(known_catalog_region("japn"), None);
+    assert_eq!(known_catalog_region(""), None);
+}
+
 #[test]
 fn ss_region_to_catalog_mappings() {
     assert_eq!(ss_region_to_catalog("us"), "usa");
@@ -227,6 +235,11 @@ fn ss_media_type_mappings() {
         ss_media_type_to_asset_type("video-normalized"),
         Some("video")
     );
+    assert_eq!(
+        ss_media_type_to_asset_type("support-texture"),
+        Some("label")
+    );
+    assert_eq!(ss_media_type_to_asset_type("box-3D"), Some("box-3d"));
     assert_eq!(ss_media_type_to_asset_type("unknown-type"), None);
 }
 
@@ -487,6 +500,11 @@ fn releases_to_enrich_query() {
     // With limit
     let limited = releases_to_enrich(&conn, "nes", false, Some(1)).unwrap();
     assert_eq!(limited.len(), 1);
+
+    // Paging picks up where the previous batch left off
+    let next = releases_to_enrich_paged(&conn, "nes", false, Some(1), 1).unwrap();
+    assert_eq!(next.len(), 1);
+    assert_eq!(next[0].title, "The Legend of Zelda");
 }
 
 #[test]
@@ -543,3 +561,112 @@ fn media_for_release_query() {
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].crc32.as_deref(), Some("d445f698"));
 }
+
+#[test]
+fn planned_changes_mirror_enrichment_rules() {
+    let release = Release {
+        id: "nes:zelda:nes:usa".to_string(),
+        work_id: "nes:zelda".to_string(),
+        platform_id: "nes".to_string(),
+        region: "usa".to_string(),
+        revision: String::new(),
+        variant: String::new(),
+        title: "Legend of Zelda, The".to_string(),
+        alt_title: None,
+        publisher_id: None,
+        developer_id: None,
+        release_date: Some("1987-08-22".to_string()),
+        game_serial: None,
+        genre: None,
+        players: None,
+        rating: None,
+        description: None,
+        screen_title: None,
+        cover_title: None,
+        screenscraper_id: None,
+        scraper_not_found: false,
+        created_at: String::new(),
+        updated_at: String::new(),
+    };
+    let mapped = map_game_info(&sample_game_info(), "us", "en");
+
+    let changes = planned_enrichment_changes(&release, "12345", &mapped);
+    let fields: Vec<&str> = changes.iter().map(|c| c.field).collect();
+    assert_eq!(fields[0], "screenscraper_id");
+    // Already set by the DAT, so left alone
+    assert!(!fields.contains(&"release_date"));
+    assert!(fields.contains(&"genre"));
+    assert!(fields.contains(&"alt_title"));
+
+    let mut enriched = release.clone();
+    enriched.screenscraper_id = Some("12345".to_string());
+    enriched.genre = mapped.genre.clone();
+    enriched.players = mapped.players.clone();
+    enriched.rating = mapped.rating;
+    enriched.description = mapped.description.clone();
+    enriched.publisher_id = Some("nintendo".to_string());
+    enriched.developer_id = Some("nintendo".to_string());
+    enriched.alt_title = mapped.title.clone();
+    assert!(planned_enrichment_changes(&enriched, "12345", &mapped).is_empty());
+}
+
+#[test]
+fn catalog_asset_paths_follow_naming() {
+    use retro_junk_frontend::AssetType;
+    use retro_junk_scraper::assets::AssetNaming;
+    use std::path::Path;
+
+    let mut release = Release {
+        id: "nes:super-mario-bros:nes:usa".to_string(),
+        work_id: "nes:super-mario-bros".to_string(),
+        platform_id: "nes".to_string(),
+        region: "usa".to_string(),
+        revision: String::new(),
+        variant: String::new(),
+        title: "Super Mario Bros.".to_string(),
+        alt_title: None,
+        publisher_id: None,
+        developer_id: None,
+        release_date: None,
+        game_serial: Some("NES-SM-USA".to_string()),
+        genre: None,
+        players: None,
+        rating: None,
+        description: None,
+        screen_title: None,
+        cover_title: None,
+        screenscraper_id: None,
+        scraper_not_found: false,
+        created_at: String::new(),
+        updated_at: String::new(),
+    };
+    let dir = Path::new("assets");
+    let rom = Some("Super Mario Bros. (World)");
+    let path = |release: &Release, rom, naming| {
+        catalog_asset_path(dir, release, rom, naming, AssetType::Cover, "png")
+    };
+
+    assert_eq!(
+        path(&release, rom, None),
+        dir.join("nes:super-mario-bros:nes:usa/box-front.png")
+    );
+    assert_eq!(
+        path(&release, rom, Some(AssetNaming::RomStem)),
+        dir.join("covers/Super Mario Bros. (World).png")
+    );
+    assert_eq!(
+        path(&release, rom, Some(AssetNaming::Slug)),
+        dir.join("covers/nes:super-mario-bros:nes:usa.png")
+    );
+    assert_eq!(
+        path(&release, rom, Some(AssetNaming::Serial)),
+        dir.join("covers/NES-SM-USA.png")
+    );
+
+    // Without a serial or DAT name, names fall back to the release
+    release.game_serial = None;
+    assert_eq!(
+        path(&release, None, Some(AssetNaming::Serial)),
+        dir.join("covers/nes:super-mario-bros:nes:usa.png")
+    );
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use retro_junk_core::Platform;
+use retro_junk_db::*;
+use retro_junk_import::screenshot_import::{ScreenshotOptions, ingest_screenshots, normalize_stem};
+
+mod common;
+
+fn setup_db() -> rusqlite::Connection {
+    let conn = common::nes_catalog();
+    for (slug, title, dat_name) in [
+        (
+            "super-mario-bros",
+            "Super Mario Bros.",
+            "Super Mario Bros. (World).nes",
+        ),
+        (
+            "zelda",
+            "The Legend of Zelda",
+            "Legend of Zelda, The (USA).nes",
+        ),
+    ] {
+        let release = common::release(&format!("nes:{slug}"), "nes", title);
+        common::add_release(&conn, &release);
+        let media = common::media(&format!("m-{slug}"), &release.id, Some(dat_name));
+        upsert_media(&conn, &media).unwrap();
+    }
+
+    conn
+}
+
+#[test]
+fn normalize_stem_strips_capture_timestamp() {
+    assert_eq!(
+        normalize_stem("Super Mario Bros. (World)-240131-201502"),
+        "super mario bros. (world)"
+    );
+    assert_eq!(normalize_stem("Tetris (World)"), "tetris (world)");
+    assert_eq!(normalize_stem("Game-12-34"), "game-12-34");
+}
+
+#[test]
+fn ingests_by_dat_name_and_mapping() {
+    let conn = setup_db();
+    let captures = tempfile::tempdir().unwrap();
+    let assets = tempfile::tempdir().unwrap();
+
+    std::fs::write(
+        captures
+            .path()
+            .join("Super Mario Bros. (World)-240131-201502.png"),
+        b"png",
+    )
+    .unwrap();
+    std::fs::write(captures.path().join("zelda-shot.jpg"), b"jpg").unwrap();
+    std::fs::write(captures.path().join("Unknown Game.png"), b"png").unwrap();
+    std::fs::write(captures.path().join("notes.txt"), b"text").unwrap();
+
+    let mut mappings = HashMap::new();
+    mappings.insert("zelda-shot".to_string(), "nes:zelda:nes:usa".to_string());
+
+    let options = ScreenshotOptions {
+        asset_dir: assets.path().to_path_buf(),
+        mappings,
+        ..Default::default()
+    };
+    let result = ingest_screenshots(&conn, captures.path(), Platform::Nes, &options).unwrap();
+
+    assert_eq!(result.stats.images_found, 3);
+    assert_eq!(result.stats.imported, 2);
+    assert_eq!(result.stats.unmatched, 1);
+    assert!(
+        assets
+            .path()
+            .join("nes:super-mario-bros:nes:usa/screenshot.png")
+            .exists()
+    );
+
+    let smb = assets_for_release(&conn, "nes:super-mario-bros:nes:usa").unwrap();
+    assert_eq!(smb.len(), 1);
+    assert_eq!(smb[0].asset_type, "screenshot");
+    assert_eq!(smb[0].source, "user");
+    assert!(!smb[0].scraped);
+    assert!(smb[0].file_hash.is_some());
+
+    let zelda = assets_for_release(&conn, "nes:zelda:nes:usa").unwrap();
+    assert_eq!(zelda.len(), 1);
+    assert!(
+        zelda[0]
+            .file_path
+            .as_deref()
+            .unwrap()
+            .ends_with("screenshot.jpg")
+    );
+
+    // A second run leaves existing assets alone
+    let again = ingest_screenshots(&conn, captures.path(), Platform::Nes, &options).unwrap();
+    assert_eq!(again.stats.imported, 0);
+    assert_eq!(again.stats.skipped_existing, 2);
+}
@@ -0,0 +1,107 @@
+//! Structured output for `analyze --format json|ndjson`.
+//!
+//! Each analyzed file becomes one [`AnalysisRecord`]: the path, the
+//! platform, and the [`RomIdentification`] (or why analysis failed), so
+//! results can be piped into scripts instead of parsed from colored text.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use retro_junk_core::{Platform, RomIdentification};
+
+retro_junk_core::named_enum! {
+    /// How `analyze` prints its results.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum OutputFormat("output format") {
+        /// Colored, human-readable lines.
+        #[default]
+        Text = "text",
+        /// One JSON array, printed when the run finishes.
+        Json = "json",
+        /// One JSON object per line, printed as each file is analyzed.
+        Ndjson = "ndjson" | "jsonl",
+    }
+}
+
+impl OutputFormat {
+    /// Whether this format prints records instead of log lines.
+    pub fn is_structured(&self) -> bool {
+        !matches!(self, Self::Text)
+    }
+}
+
+/// One analyzed file.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisRecord {
+    /// The file on disk (the archive, for a ROM inside one).
+    pub path: PathBuf,
+    /// The ROM's name inside `path`, when `path` is an archive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+    pub platform: Platform,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identification: Option<RomIdentification>,
+    /// Why analysis failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Bad-dump warnings.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl AnalysisRecord {
+    /// A successfully analyzed file.
+    pub fn identified(path: PathBuf, platform: Platform, info: RomIdentification) -> Self {
+        Self {
+            path,
+            entry: None,
+            platform,
+            identification: Some(info),
+            error: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// A file that couldn't be opened or analyzed.
+    pub fn failed(path: PathBuf, platform: Platform, error: impl Into<String>) -> Self {
+        Self {
+            path,
+            entry: None,
+            platform,
+            identification: None,
+            error: Some(error.into()),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Name the ROM inside an archive.
+    pub fn with_entry(mut self, entry: impl Into<String>) -> Self {
+        self.entry = Some(entry.into());
+        self
+    }
+
+    /// The record as a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Render finished records in `format`: a pretty-printed array for
+/// [`OutputFormat::Json`], one line per record for
+/// [`OutputFormat::Ndjson`], and nothing for [`OutputFormat::Text`].
+pub fn render_records(format: OutputFormat, records: &[AnalysisRecord]) -> String {
+    match format {
+        OutputFormat::Text => String::new(),
+        OutputFormat::Json => serde_json::to_string_pretty(records).unwrap_or_default(),
+        OutputFormat::Ndjson => records
+            .iter()
+            .map(AnalysisRecord::to_json_line)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/analysis_output_tests.rs"]
+mod tests;
@@ -0,0 +1,470 @@
+//! ROMs stored in `.zip` and `.7z` archives.
+//!
+//! An archive holding a single ROM stands in for that ROM: [`open_rom`]
+//! reads the decompressed entry, so it is analyzed and hashed like a loose
+//! file, and rename gives the archive the DAT name. An archive holding
+//! several ROMs (a compilation) is treated as a set of files instead: each
+//! ROM entry is listed, analyzed, and hashed on its own, and rename can
+//! split the archive into individual canonically named files.
+//! Entries are decompressed into memory, which is fine for cartridge-sized
+//! ROMs; anything over [`MAX_ENTRY_SIZE`] is refused.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, LimitedReader, ReadSeek, RomAnalyzer, RomIdentification,
+};
+use retro_junk_dat::DatIndex;
+
+use crate::rename::target_filename_for_rename;
+use crate::trash::{Trash, write_new};
+use crate::util::long_path;
+
+/// File extensions recognized as archives.
+pub const ARCHIVE_EXTENSIONS: &[&str] = &["zip", "7z"];
+
+/// Largest entry that will be extracted into memory (1 GiB).
+pub const MAX_ENTRY_SIZE: u64 = 1 << 30;
+
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid archive {path}: {message}")]
+    Invalid { path: PathBuf, message: String },
+    #[error("Entry {name} is too large to extract ({size} bytes)")]
+    TooLarge { name: String, size: u64 },
+}
+
+impl ArchiveError {
+    fn invalid(path: &Path, message: impl ToString) -> Self {
+        Self::Invalid {
+            path: path.to_path_buf(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Supported archive formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    SevenZip,
+}
+
+impl ArchiveKind {
+    /// Detect the format from a file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "zip" => Some(Self::Zip),
+            "7z" => Some(Self::SevenZip),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `path` has an archive extension.
+pub fn is_archive(path: &Path) -> bool {
+    ArchiveKind::from_path(path).is_some()
+}
+
+/// A file stored inside an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Path inside the archive, with `/` separators.
+    pub name: String,
+    /// Uncompressed size in bytes.
+    pub size: u64,
+    /// CRC32 recorded by the archive, if any.
+    pub crc32: Option<u32>,
+}
+
+impl ArchiveEntry {
+    /// The entry's file name without any directory part.
+    pub fn file_name(&self) -> &str {
+        self.name.rsplit(['/', '\\']).next().unwrap_or(&self.name)
+    }
+
+    /// Lowercase extension of the entry, if any.
+    pub fn extension(&self) -> Option<String> {
+        Path::new(self.file_name())
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase)
+    }
+}
+
+/// Archive files directly inside `folder`, sorted by name. Hidden files are
+/// skipped.
+pub fn find_archives(folder: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut archives: Vec<PathBuf> = fs::read_dir(folder)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && is_archive(p))
+        .filter(|p| {
+            !p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.'))
+        })
+        .collect();
+    archives.sort();
+    Ok(archives)
+}
+
+/// List the files in an archive (directories are omitted).
+pub fn list_entries(path: &Path) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+    match ArchiveKind::from_path(path) {
+        Some(ArchiveKind::Zip) => {
+            let mut zip = open_zip(path)?;
+            let mut entries = Vec::new();
+            for i in 0..zip.len() {
+                let file = zip
+                    .by_index(i)
+                    .map_err(|e| ArchiveError::invalid(path, e))?;
+                if file.is_dir() {
+                    continue;
+                }
+                entries.push(ArchiveEntry {
+                    name: file.name().to_string(),
+                    size: file.size(),
+                    crc32: Some(file.crc32()),
+                });
+            }
+            Ok(entries)
+        }
+        Some(ArchiveKind::SevenZip) => {
+            let reader = open_7z(path)?;
+            Ok(reader
+                .archive()
+                .files
+                .iter()
+                .filter(|f| !f.is_directory)
+                .map(|f| ArchiveEntry {
+                    name: f.name.clone(),
+                    size: f.size,
+                    crc32: f.has_crc.then_some(f.crc as u32),
+                })
+                .collect())
+        }
+        None => Err(ArchiveError::invalid(path, "not a .zip or .7z file")),
+    }
+}
+
+/// Entries whose extension is in `extensions` (lowercase, without dots).
+pub fn rom_entries(
+    path: &Path,
+    extensions: &HashSet<String>,
+) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+    Ok(list_entries(path)?
+        .into_iter()
+        .filter(|e| e.extension().is_some_and(|ext| extensions.contains(&ext)))
+        .collect())
+}
+
+/// Whether the archive holds more than one ROM with the given extensions.
+pub fn is_multi_game(path: &Path, extensions: &HashSet<String>) -> bool {
+    rom_entries(path, extensions).is_ok_and(|e| e.len() > 1)
+}
+
+/// The ROM entry of an archive holding exactly one ROM with the given
+/// extensions. Unreadable archives have none.
+pub fn single_rom_entry(path: &Path, extensions: &HashSet<String>) -> Option<ArchiveEntry> {
+    let mut entries = rom_entries(path, extensions).ok()?;
+    if entries.len() == 1 {
+        entries.pop()
+    } else {
+        None
+    }
+}
+
+/// A ROM opened for reading, either a plain file or the decompressed entry
+/// of a single-ROM archive.
+pub struct OpenedRom {
+    pub reader: Box<dyn ReadSeek>,
+    /// Path to analyze the ROM as: the file itself, or `archive/entry` for
+    /// an archived ROM.
+    pub analysis_path: PathBuf,
+    /// The entry read, when the ROM came from an archive.
+    pub entry: Option<ArchiveEntry>,
+}
+
+impl OpenedRom {
+    /// Size of the ROM data (the decompressed size for an archived ROM).
+    pub fn size(&self, path: &Path) -> u64 {
+        match &self.entry {
+            Some(entry) => entry.size,
+            None => fs::metadata(long_path(path)).map_or(0, |m| m.len()),
+        }
+    }
+}
+
+/// Open `path` for analysis. An archive is read through to its only ROM
+/// (an entry with one of `extensions`); other files are opened as they are.
+pub fn open_rom(path: &Path, extensions: &HashSet<String>) -> Result<OpenedRom, ArchiveError> {
+    if !is_archive(path) {
+        return Ok(OpenedRom {
+            reader: Box::new(fs::File::open(long_path(path))?),
+            analysis_path: path.to_path_buf(),
+            entry: None,
+        });
+    }
+
+    let entries = rom_entries(path, extensions)?;
+    let [entry] = entries.as_slice() else {
+        return Err(ArchiveError::invalid(
+            path,
+            format!("expected one ROM, found {}", entries.len()),
+        ));
+    };
+    let mut contents = Vec::new();
+    for_each_entry(path, std::slice::from_ref(entry), |_, data| contents = data)?;
+    Ok(OpenedRom {
+        reader: Box::new(Cursor::new(contents)),
+        analysis_path: path.join(&entry.name),
+        entry: Some(entry.clone()),
+    })
+}
+
+/// Decompress each ROM entry in archive order and pass its contents to `f`.
+pub fn for_each_rom_entry(
+    path: &Path,
+    extensions: &HashSet<String>,
+    f: impl FnMut(&ArchiveEntry, Vec<u8>),
+) -> Result<(), ArchiveError> {
+    let wanted = rom_entries(path, extensions)?;
+    for_each_entry(path, &wanted, f)
+}
+
+/// Decompress the `wanted` entries and pass each one's contents to `f`.
+fn for_each_entry(
+    path: &Path,
+    wanted: &[ArchiveEntry],
+    mut f: impl FnMut(&ArchiveEntry, Vec<u8>),
+) -> Result<(), ArchiveError> {
+    if let Some(big) = wanted.iter().find(|e| e.size > MAX_ENTRY_SIZE) {
+        return Err(ArchiveError::TooLarge {
+            name: big.name.clone(),
+            size: big.size,
+        });
+    }
+
+    match ArchiveKind::from_path(path) {
+        Some(ArchiveKind::Zip) => {
+            let mut zip = open_zip(path)?;
+            for entry in wanted {
+                let mut file = zip
+                    .by_name(&entry.name)
+                    .map_err(|e| ArchiveError::invalid(path, e))?;
+                let mut data = Vec::with_capacity(entry.size as usize);
+                file.read_to_end(&mut data)?;
+                f(entry, data);
+            }
+            Ok(())
+        }
+        Some(ArchiveKind::SevenZip) => {
+            let mut reader = open_7z(path)?;
+            let mut failure: Option<ArchiveError> = None;
+            reader
+                .for_each_entries(|file, stream| {
+                    match wanted.iter().find(|e| e.name == file.name) {
+                        Some(entry) => {
+                            let mut data = Vec::with_capacity(entry.size as usize);
+                            if let Err(e) = stream.read_to_end(&mut data) {
+                                failure = Some(e.into());
+                                return Ok(false);
+                            }
+                            f(entry, data);
+                        }
+                        // Solid blocks must be consumed in order
+                        None => {
+                            io::copy(stream, &mut io::sink())?;
+                        }
+                    }
+                    Ok(true)
+                })
+                .map_err(|e| ArchiveError::invalid(path, e))?;
+            failure.map_or(Ok(()), Err)
+        }
+        None => Err(ArchiveError::invalid(path, "not a .zip or .7z file")),
+    }
+}
+
+/// Identification of one archive entry.
+#[derive(Debug)]
+pub struct EntryAnalysis {
+    pub entry: ArchiveEntry,
+    pub result: Result<RomIdentification, AnalysisError>,
+}
+
+/// Analyze every ROM entry in an archive separately.
+pub fn analyze_entries(
+    path: &Path,
+    analyzer: &dyn RomAnalyzer,
+    options: &AnalysisOptions,
+) -> Result<Vec<EntryAnalysis>, ArchiveError> {
+    let extensions = crate::scanner::extension_set(analyzer.file_extensions());
+    let mut results = Vec::new();
+    for_each_rom_entry(path, &extensions, |entry, data| {
+        let entry_options = AnalysisOptions {
+            file_path: Some(path.join(&entry.name)),
+            ..options.clone()
+        };
+        let mut reader = LimitedReader::new(Cursor::new(data), &options.limits);
+        let result = analyzer.analyze(&mut reader, &entry_options);
+        results.push(EntryAnalysis {
+            entry: entry.clone(),
+            result,
+        });
+    })?;
+    Ok(results)
+}
+
+/// Where one archive entry will be extracted.
+#[derive(Debug, Clone)]
+pub struct SplitEntry {
+    pub entry: ArchiveEntry,
+    /// Output path next to the archive.
+    pub target: PathBuf,
+    /// DAT game name, when the entry's hash matched.
+    pub game_name: Option<String>,
+}
+
+/// A planned split of a multi-game archive into individual files.
+#[derive(Debug, Clone)]
+pub struct ArchiveSplit {
+    pub archive: PathBuf,
+    pub entries: Vec<SplitEntry>,
+}
+
+impl ArchiveSplit {
+    /// Entries that matched a DAT game.
+    pub fn matched(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.game_name.is_some())
+            .count()
+    }
+}
+
+/// Plan splitting an archive: hash each ROM entry and name its output file
+/// after the matching DAT entry, or keep the entry's own name if unmatched.
+pub fn plan_split(
+    path: &Path,
+    analyzer: &dyn RomAnalyzer,
+    index: &DatIndex,
+) -> Result<ArchiveSplit, ArchiveError> {
+    let extensions = crate::scanner::extension_set(analyzer.file_extensions());
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut entries = Vec::new();
+    let mut failure: Option<ArchiveError> = None;
+
+    for_each_rom_entry(path, &extensions, |entry, data| {
+        let hashes = match crate::hasher::compute_crc32_sha1(&mut Cursor::new(data), analyzer, None)
+        {
+            Ok(h) => h,
+            Err(e) => {
+                failure.get_or_insert(ArchiveError::invalid(path, e));
+                return;
+            }
+        };
+        let entry_name = Path::new(entry.file_name());
+        let (file_name, game_name) = match index.match_by_hash(hashes.data_size, &hashes) {
+            Some(m) => {
+                let game = &index.games[m.game_index];
+                let rom_name = &game.roms[m.rom_index].name;
+                (
+                    target_filename_for_rename(rom_name, entry_name, None),
+                    Some(game.name.clone()),
+                )
+            }
+            None => (entry.file_name().to_string(), None),
+        };
+        entries.push(SplitEntry {
+            entry: entry.clone(),
+            target: dir.join(file_name),
+            game_name,
+        });
+    })?;
+
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(ArchiveSplit {
+            archive: path.to_path_buf(),
+            entries,
+        }),
+    }
+}
+
+/// Extract every planned entry and remove the archive, or move it to
+/// `trash` when given.
+///
+/// Nothing is written if any target already exists (or two entries share a
+/// target), and the archive is only removed once every entry was written.
+/// Returns the number of files written.
+pub fn execute_split(split: &ArchiveSplit, trash: Option<&Trash>) -> Result<usize, ArchiveError> {
+    let mut targets = HashSet::new();
+    for entry in &split.entries {
+        if entry.target.exists() || !targets.insert(&entry.target) {
+            return Err(ArchiveError::Io(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("target already exists: {}", entry.target.display()),
+            )));
+        }
+    }
+
+    let wanted: Vec<ArchiveEntry> = split.entries.iter().map(|s| s.entry.clone()).collect();
+    let mut written = 0;
+    let mut failure: Option<io::Error> = None;
+    for_each_entry(&split.archive, &wanted, |entry, data| {
+        if failure.is_some() {
+            return;
+        }
+        let Some(planned) = split.entries.iter().find(|s| s.entry.name == entry.name) else {
+            return;
+        };
+        match write_new(&planned.target, data) {
+            Ok(()) => written += 1,
+            Err(e) => failure = Some(e),
+        }
+    })?;
+
+    if let Some(e) = failure {
+        return Err(e.into());
+    }
+    if written != split.entries.len() {
+        return Err(ArchiveError::invalid(
+            &split.archive,
+            format!(
+                "only {} of {} entries were extracted; archive kept",
+                written,
+                split.entries.len()
+            ),
+        ));
+    }
+    match trash {
+        Some(trash) => {
+            trash.stash(&split.archive, "split into individual ROMs")?;
+        }
+        None => fs::remove_file(&split.archive)?,
+    }
+    Ok(written)
+}
+
+fn open_zip(path: &Path) -> Result<zip::ZipArchive<fs::File>, ArchiveError> {
+    zip::ZipArchive::new(fs::File::open(path)?).map_err(|e| ArchiveError::invalid(path, e))
+}
+
+fn open_7z(path: &Path) -> Result<sevenz_rust::SevenZReader<fs::File>, ArchiveError> {
+    sevenz_rust::SevenZReader::open(path, sevenz_rust::Password::empty())
+        .map_err(|e| ArchiveError::invalid(path, e))
+}
+
+#[cfg(test)]
+#[path = "tests/archive_tests.rs"]
+mod tests;
@@ -0,0 +1,232 @@
+//! Verify CHD images using the SHA1s stored in their headers.
+//!
+//! A CHD header records two SHA1s: the raw SHA1 of the uncompressed hunk
+//! data, and (from v4 on) a combined SHA1 covering the raw data plus the
+//! checksummed metadata entries. MAME-style DATs list CHDs as `disk`
+//! entries keyed by the combined SHA1, so an image can be identified from
+//! its header without decompressing anything.
+//!
+//! The header only says what the data *should* hash to. A deep check
+//! decompresses every hunk and re-derives the raw SHA1, which catches
+//! images whose data was damaged after they were created.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use retro_junk_core::hash::{hex, unhex};
+use retro_junk_core::{HashAlgorithms, StreamHasher};
+use retro_junk_dat::matcher::{DatIndex, MatchResult};
+use thiserror::Error;
+
+/// Metadata flag marking entries that are covered by the combined SHA1.
+const METADATA_CHECKSUM_FLAG: u8 = 0x01;
+
+#[derive(Debug, Error)]
+pub enum ChdVerifyError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid CHD: {0}")]
+    Chd(String),
+    #[error("CHD v{0} headers don't record a SHA1")]
+    NoSha1(u32),
+}
+
+retro_junk_core::named_enum! {
+    /// How thoroughly to verify a CHD.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum ChdVerifyMode("CHD verify mode") {
+        /// Compare the header SHA1 against the DAT and check it is consistent
+        /// with the metadata. Reads only the header and metadata.
+        #[default]
+        Fast = "fast",
+        /// Also decompress the whole image and re-derive the raw SHA1.
+        Deep = "deep",
+    }
+}
+
+/// The SHA1s recorded in a CHD header, as lowercase hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChdHeaderHashes {
+    pub version: u32,
+    /// Combined SHA1 (raw data plus metadata on v4+, raw data only on v3).
+    /// This is what DAT `disk` entries list.
+    pub sha1: String,
+    /// SHA1 of the uncompressed hunk data alone (v4+).
+    pub raw_sha1: Option<String>,
+    /// Whether the image is a delta against a parent CHD.
+    pub has_parent: bool,
+}
+
+impl ChdHeaderHashes {
+    /// The SHA1 that the decompressed data should hash to.
+    pub fn expected_raw_sha1(&self) -> &str {
+        self.raw_sha1.as_deref().unwrap_or(&self.sha1)
+    }
+}
+
+/// Outcome of verifying one CHD.
+#[derive(Debug, Clone)]
+pub struct ChdVerification {
+    pub mode: ChdVerifyMode,
+    pub header: ChdHeaderHashes,
+    /// DAT `disk` entry whose SHA1 matches the header, if an index was given.
+    pub dat_match: Option<MatchResult>,
+    /// Whether the header SHA1 agrees with the raw SHA1 and metadata.
+    /// `None` on v3 images, which have no separate raw SHA1.
+    pub metadata_consistent: Option<bool>,
+    /// Raw SHA1 re-derived from the decompressed data (deep mode only).
+    pub derived_raw_sha1: Option<String>,
+    /// First hunk that failed to decompress (deep mode only).
+    pub bad_hunk: Option<u32>,
+}
+
+impl ChdVerification {
+    /// Whether the image is known to be damaged.
+    ///
+    /// Fast mode can only catch a header that disagrees with its metadata;
+    /// damaged hunk data is only detected in deep mode.
+    pub fn is_corrupt(&self) -> bool {
+        self.metadata_consistent == Some(false)
+            || self.bad_hunk.is_some()
+            || self
+                .derived_raw_sha1
+                .as_deref()
+                .is_some_and(|sha1| sha1 != self.header.expected_raw_sha1())
+    }
+}
+
+/// Read the SHA1s from a CHD header without touching the hunk data.
+pub fn read_header_hashes<R: Read + Seek + ?Sized>(
+    reader: &mut R,
+) -> Result<ChdHeaderHashes, ChdVerifyError> {
+    reader.seek(SeekFrom::Start(0))?;
+    let chd = open(reader)?;
+    header_hashes(&chd)
+}
+
+/// Verify a CHD image, optionally identifying it against `index`.
+///
+/// `progress` receives `(bytes_done, bytes_total)` while a deep check
+/// decompresses the image.
+pub fn verify_chd<R: Read + Seek>(
+    reader: &mut R,
+    mode: ChdVerifyMode,
+    index: Option<&DatIndex>,
+    progress: &dyn Fn(u64, u64),
+) -> Result<ChdVerification, ChdVerifyError> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut chd = open(reader)?;
+    let header = header_hashes(&chd)?;
+
+    let metadata_consistent = match &header.raw_sha1 {
+        Some(raw) => Some(combined_sha1(&mut chd, raw)? == header.sha1),
+        None => None,
+    };
+
+    let (derived_raw_sha1, bad_hunk) = match mode {
+        ChdVerifyMode::Fast => (None, None),
+        ChdVerifyMode::Deep => match derive_raw_sha1(&mut chd, progress) {
+            Ok(sha1) => (Some(sha1), None),
+            Err(hunk) => (None, Some(hunk)),
+        },
+    };
+
+    Ok(ChdVerification {
+        mode,
+        dat_match: index.and_then(|idx| idx.match_disk_sha1(&header.sha1)),
+        header,
+        metadata_consistent,
+        derived_raw_sha1,
+        bad_hunk,
+    })
+}
+
+fn open<R: Read + Seek>(reader: R) -> Result<chd::Chd<R>, ChdVerifyError> {
+    chd::Chd::open(reader, None).map_err(|e| ChdVerifyError::Chd(e.to_string()))
+}
+
+fn header_hashes<R: Read + Seek>(chd: &chd::Chd<R>) -> Result<ChdHeaderHashes, ChdVerifyError> {
+    let header = chd.header();
+    let version = header.version() as u32;
+    let sha1 = header.sha1().ok_or(ChdVerifyError::NoSha1(version))?;
+    Ok(ChdHeaderHashes {
+        version,
+        sha1: hex(&sha1),
+        raw_sha1: header.raw_sha1().map(|s| hex(&s)),
+        has_parent: header.has_parent(),
+    })
+}
+
+/// Recompute the combined SHA1 the way chdman does: the raw SHA1 followed
+/// by the tag and SHA1 of each checksummed metadata entry, sorted.
+fn combined_sha1<R: Read + Seek>(
+    chd: &mut chd::Chd<R>,
+    raw_sha1: &str,
+) -> Result<String, ChdVerifyError> {
+    let refs: Vec<_> = chd.metadata_refs().collect();
+    let mut entries: Vec<[u8; 24]> = Vec::new();
+    for meta_ref in &refs {
+        let meta = meta_ref
+            .read(chd.inner())
+            .map_err(|e| ChdVerifyError::Chd(format!("failed to read metadata: {}", e)))?;
+        if meta.flags & METADATA_CHECKSUM_FLAG == 0 {
+            continue;
+        }
+        let mut entry = [0u8; 24];
+        entry[..4].copy_from_slice(&meta.metatag.to_be_bytes());
+        entry[4..].copy_from_slice(&sha1_bytes(&meta.value));
+        entries.push(entry);
+    }
+    entries.sort();
+
+    let mut hasher = sha1_hasher();
+    hasher.update(&unhex(raw_sha1).unwrap_or_default());
+    for entry in &entries {
+        hasher.update(entry);
+    }
+    Ok(finish_sha1(hasher))
+}
+
+/// Decompress every hunk and hash the logical data. On failure, returns
+/// the number of the hunk that couldn't be read.
+fn derive_raw_sha1<R: Read + Seek>(
+    chd: &mut chd::Chd<R>,
+    progress: &dyn Fn(u64, u64),
+) -> Result<String, u32> {
+    let total = chd.header().logical_bytes();
+    let hunk_count = chd.header().hunk_count();
+    let mut hunk_buf = chd.get_hunksized_buffer();
+    let mut cmp_buf = Vec::new();
+    let mut hasher = sha1_hasher();
+    let mut done = 0u64;
+
+    for hunk_num in 0..hunk_count {
+        chd.hunk(hunk_num)
+            .and_then(|mut hunk| hunk.read_hunk_in(&mut cmp_buf, &mut hunk_buf))
+            .map_err(|_| hunk_num)?;
+        // The last hunk is padded past the logical size
+        let len = (total - done).min(hunk_buf.len() as u64) as usize;
+        hasher.update(&hunk_buf[..len]);
+        done += len as u64;
+        progress(done, total);
+    }
+
+    Ok(finish_sha1(hasher))
+}
+
+fn sha1_hasher() -> StreamHasher {
+    StreamHasher::new(HashAlgorithms::Crc32Sha1)
+}
+
+fn finish_sha1(hasher: StreamHasher) -> String {
+    hasher.finish().sha1.expect("SHA1 was requested")
+}
+
+fn sha1_bytes(data: &[u8]) -> Vec<u8> {
+    let mut hasher = sha1_hasher();
+    hasher.update(data);
+    unhex(&finish_sha1(hasher)).expect("digests are hex")
+}
+
+#[cfg(test)]
+#[path = "tests/chd_verify_tests.rs"]
+mod tests;
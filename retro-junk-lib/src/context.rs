@@ -1,8 +1,37 @@
 //! Analysis context for ROM analysis.
 
+use std::collections::{HashMap, HashSet};
+use std::io;
 use std::path::{Path, PathBuf};
 
-use retro_junk_core::{Platform, RomAnalyzer};
+use thiserror::Error;
+
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, LimitedReader, Platform, PlatformFamily, ReadSeek, RomAnalyzer,
+    RomIdentification,
+};
+
+use crate::archive::{self, ArchiveError};
+use crate::scanner::{self, ScanOptions, SymlinkPolicy};
+use crate::util::long_path;
+
+/// Default number of directory levels searched for console folders: the root's
+/// children plus one grouping level (e.g., `roms/nintendo/snes/`).
+pub const DEFAULT_SCAN_DEPTH: usize = 2;
+
+/// Errors from [`AnalysisContext::analyze_path`].
+#[derive(Debug, Error)]
+pub enum AnalyzePathError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Archive(#[from] ArchiveError),
+    #[error("No registered console recognizes {0}")]
+    NotRecognized(PathBuf),
+    /// Every candidate analyzer failed; this is the last one's error.
+    #[error(transparent)]
+    Analysis(#[from] AnalysisError),
+}
 
 /// Metadata about a registered console.
 #[derive(Debug, Clone)]
@@ -57,6 +86,19 @@ impl RegisteredConsole {
 /// register consoles, then use it to analyze ROMs.
 pub struct AnalysisContext {
     consoles: Vec<RegisteredConsole>,
+    /// Lowercase folder name → frontend-defined alias (e.g., from ES-DE).
+    folder_aliases: HashMap<String, FolderAlias>,
+    /// How many directory levels below the root to look for console folders.
+    scan_depth: usize,
+    /// How folder scans treat symlinks and junctions.
+    symlinks: SymlinkPolicy,
+}
+
+/// A folder name defined by a frontend's system configuration.
+#[derive(Debug, Clone)]
+struct FolderAlias {
+    system_name: String,
+    platforms: Vec<Platform>,
 }
 
 impl Default for AnalysisContext {
@@ -70,6 +112,9 @@ impl AnalysisContext {
     pub fn new() -> Self {
         Self {
             consoles: Vec::new(),
+            folder_aliases: HashMap::new(),
+            scan_depth: DEFAULT_SCAN_DEPTH,
+            symlinks: SymlinkPolicy::default(),
         }
     }
 
@@ -100,14 +145,330 @@ impl AnalysisContext {
         }
     }
 
+    /// Map a folder name to specific platforms, overriding the built-in
+    /// alias lists. `system_name` is the frontend's name for the system,
+    /// used for metadata and media output folders.
+    pub fn add_folder_alias(
+        &mut self,
+        folder_name: &str,
+        system_name: &str,
+        platforms: &[Platform],
+    ) {
+        self.folder_aliases.insert(
+            folder_name.to_lowercase(),
+            FolderAlias {
+                system_name: system_name.to_string(),
+                platforms: platforms.to_vec(),
+            },
+        );
+    }
+
+    /// The frontend system name registered for a folder, if any.
+    pub fn frontend_system_name(&self, folder_name: &str) -> Option<&str> {
+        self.folder_aliases
+            .get(&folder_name.to_lowercase())
+            .map(|a| a.system_name.as_str())
+    }
+
+    /// Set how many directory levels below the root
+    /// [`scan_console_folders`](Self::scan_console_folders) searches. A depth
+    /// of 1 only considers the root's direct children.
+    pub fn set_scan_depth(&mut self, depth: usize) -> &mut Self {
+        self.scan_depth = depth.max(1);
+        self
+    }
+
+    /// The current console folder search depth.
+    pub fn scan_depth(&self) -> usize {
+        self.scan_depth
+    }
+
+    /// Set how folder scans treat symlinks and junctions.
+    pub fn set_symlink_policy(&mut self, policy: SymlinkPolicy) -> &mut Self {
+        self.symlinks = policy;
+        self
+    }
+
+    /// The current symlink policy.
+    pub fn symlink_policy(&self) -> SymlinkPolicy {
+        self.symlinks
+    }
+
+    /// Options for scanning a console folder's games with this context's
+    /// symlink policy.
+    pub fn scan_options(&self) -> ScanOptions {
+        ScanOptions::new().symlinks(self.symlinks)
+    }
+
     /// Find consoles that match a folder name.
+    ///
+    /// A family folder (e.g., `gb-family`) matches every registered member of
+    /// that [`PlatformFamily`], so each analyzer picks its own files from it.
     pub fn find_by_folder(&self, folder_name: &str) -> Vec<&RegisteredConsole> {
+        if let Some(alias) = self.folder_aliases.get(&folder_name.to_lowercase()) {
+            return self
+                .consoles
+                .iter()
+                .filter(|c| alias.platforms.contains(&c.metadata.platform))
+                .collect();
+        }
+        if let Ok(family) = folder_name.parse::<PlatformFamily>() {
+            return self
+                .consoles
+                .iter()
+                .filter(|c| c.metadata.platform.family() == Some(family))
+                .collect();
+        }
         self.consoles
             .iter()
             .filter(|c| c.analyzer.matches_folder(folder_name))
             .collect()
     }
 
+    /// Platforms whose analyzers claim a file from its first bytes.
+    ///
+    /// `head` is the start of a `file_size`-byte file; each analyzer sees as
+    /// much of it as its [`sniff_len`](RomAnalyzer::sniff_len) asks for.
+    pub fn sniff_platforms(&self, head: &[u8], file_size: u64) -> Vec<Platform> {
+        self.consoles
+            .iter()
+            .filter(|c| {
+                let len = c.analyzer.sniff_len().min(head.len());
+                c.analyzer.sniff(&head[..len], file_size)
+            })
+            .map(|c| c.metadata.platform)
+            .collect()
+    }
+
+    /// Detect candidate platforms for a file, reading one prefix large
+    /// enough for every registered analyzer instead of letting each one seek
+    /// around the file. The reader is left at the start.
+    pub fn detect_platforms(&self, reader: &mut dyn ReadSeek) -> std::io::Result<Vec<Platform>> {
+        let len = self
+            .consoles
+            .iter()
+            .map(|c| c.analyzer.sniff_len())
+            .max()
+            .unwrap_or(0);
+        let (head, file_size) = retro_junk_core::sniff::read_prefix(reader, len)?;
+        Ok(self.sniff_platforms(&head, file_size))
+    }
+
+    /// Analyze a file, choosing the analyzer automatically.
+    ///
+    /// See [`analyze_path_with`](Self::analyze_path_with).
+    pub fn analyze_path(
+        &self,
+        path: &Path,
+    ) -> Result<(Platform, RomIdentification), AnalyzePathError> {
+        self.analyze_path_with(path, &AnalysisOptions::new())
+    }
+
+    /// Analyze a file with `options`, choosing the analyzer automatically.
+    ///
+    /// Analyzers that recognize the file's first bytes and claim its
+    /// extension are tried first, then the rest that recognize its bytes,
+    /// then those that only claim the extension (for formats without a
+    /// signature). An extension only one analyzer claims (`.sgx`, `.gdi`)
+    /// outranks other analyzers recognizing the bytes. The first successful
+    /// analysis wins. `options.file_path`
+    /// is set to `path`, and a single-ROM `.zip` or `.7z` is analyzed
+    /// through to the ROM inside. Each analyzer gets its own
+    /// `options.limits.max_seeks` budget. A directory is analyzed as an
+    /// extracted game by the analyzers that recognize it
+    /// ([`RomAnalyzer::is_game_dir`]), and a music rip by the analyzers
+    /// claiming its extension (see [`analyze_music`](Self::analyze_music)).
+    pub fn analyze_path_with(
+        &self,
+        path: &Path,
+        options: &AnalysisOptions,
+    ) -> Result<(Platform, RomIdentification), AnalyzePathError> {
+        if path.is_dir() {
+            return self.analyze_game_dir(path, options);
+        }
+        let extensions: HashSet<String> = self
+            .consoles
+            .iter()
+            .flat_map(|c| c.analyzer.file_extensions())
+            .map(|e| e.to_lowercase())
+            .collect();
+        let opened = archive::open_rom(path, &extensions)?;
+        let mut reader = opened.reader;
+        let file_options = AnalysisOptions {
+            file_path: Some(opened.analysis_path.clone()),
+            ..options.clone()
+        };
+        self.analyze_reader(reader.as_mut(), &opened.analysis_path, None, &file_options)
+    }
+
+    /// Analyze an open file, choosing the analyzer automatically.
+    ///
+    /// `name` is the file's path or name, whose extension ranks the
+    /// analyzers as described on [`analyze_path_with`](Self::analyze_path_with)
+    /// and picks out music rips. With `platforms`, only those platforms'
+    /// analyzers are tried, each of them even if it neither recognizes the
+    /// bytes nor claims the extension. `options` is passed to the analyzers
+    /// as is, so set its `file_path` for analyzers that open companion files.
+    pub fn analyze_reader(
+        &self,
+        reader: &mut dyn ReadSeek,
+        name: &Path,
+        platforms: Option<&[Platform]>,
+        options: &AnalysisOptions,
+    ) -> Result<(Platform, RomIdentification), AnalyzePathError> {
+        let allowed =
+            |c: &&RegisteredConsole| platforms.is_none_or(|p| p.contains(&c.metadata.platform));
+        let extension = name
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        if let Some(result) = self.try_music(
+            reader,
+            self.music_consoles(extension).filter(allowed),
+            options,
+        ) {
+            return Ok(result?);
+        }
+
+        let sniffed = self.detect_platforms(reader)?;
+        let mut candidates = self.candidate_platforms(name, &sniffed);
+        if platforms.is_some() {
+            candidates.retain(allowed);
+            let rest: Vec<&RegisteredConsole> = self
+                .consoles
+                .iter()
+                .filter(allowed)
+                .filter(|c| {
+                    !candidates
+                        .iter()
+                        .any(|k| k.metadata.platform == c.metadata.platform)
+                })
+                .collect();
+            candidates.extend(rest);
+        }
+
+        let mut last_error = None;
+        for console in candidates {
+            reader.seek(io::SeekFrom::Start(0))?;
+            let mut limited = LimitedReader::new(&mut *reader, &options.limits);
+            match console.analyzer.analyze(&mut limited, options) {
+                Ok(info) => return Ok((console.metadata.platform, info)),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(match last_error {
+            Some(e) => AnalyzePathError::Analysis(e),
+            None => AnalyzePathError::NotRecognized(name.to_path_buf()),
+        })
+    }
+
+    /// Identify a music rip with the analyzers whose
+    /// [`music_extensions`](RomAnalyzer::music_extensions) include
+    /// `extension`, in registration order. Returns `None` if no console
+    /// claims the extension, and otherwise the first identification or the
+    /// last analyzer's error.
+    pub fn analyze_music(
+        &self,
+        reader: &mut dyn ReadSeek,
+        extension: &str,
+        options: &AnalysisOptions,
+    ) -> Option<Result<(Platform, RomIdentification), AnalysisError>> {
+        self.try_music(reader, self.music_consoles(extension), options)
+    }
+
+    /// Run `consoles`' music analyzers in order; see
+    /// [`analyze_music`](Self::analyze_music).
+    fn try_music<'a>(
+        &self,
+        reader: &mut dyn ReadSeek,
+        consoles: impl Iterator<Item = &'a RegisteredConsole>,
+        options: &AnalysisOptions,
+    ) -> Option<Result<(Platform, RomIdentification), AnalysisError>> {
+        let mut last_error = None;
+        for console in consoles {
+            if let Err(e) = reader.seek(io::SeekFrom::Start(0)) {
+                return Some(Err(e.into()));
+            }
+            let mut limited = LimitedReader::new(&mut *reader, &options.limits);
+            match console.analyzer.analyze_music(&mut limited, options) {
+                Ok(info) => return Some(Ok((console.metadata.platform, info))),
+                Err(e) => last_error = Some(Err(e)),
+            }
+        }
+        last_error
+    }
+
+    /// Consoles whose music rips use `extension`.
+    fn music_consoles(&self, extension: &str) -> impl Iterator<Item = &RegisteredConsole> {
+        self.consoles.iter().filter(move |c| {
+            c.analyzer
+                .music_extensions()
+                .iter()
+                .any(|e| e.eq_ignore_ascii_case(extension))
+        })
+    }
+
+    /// Analyze an extracted game folder with the first analyzer that reads it.
+    fn analyze_game_dir(
+        &self,
+        dir: &Path,
+        options: &AnalysisOptions,
+    ) -> Result<(Platform, RomIdentification), AnalyzePathError> {
+        let console = self
+            .consoles
+            .iter()
+            .find(|c| c.analyzer.is_game_dir(dir))
+            .ok_or_else(|| AnalyzePathError::NotRecognized(dir.to_path_buf()))?;
+        let dir_options = AnalysisOptions {
+            file_path: Some(dir.to_path_buf()),
+            ..options.clone()
+        };
+        let info = console.analyzer.analyze_dir(dir, &dir_options)?;
+        Ok((console.metadata.platform, info))
+    }
+
+    /// Consoles to try for a file, in the order described on
+    /// [`analyze_path_with`](Self::analyze_path_with).
+    fn candidate_platforms(&self, path: &Path, sniffed: &[Platform]) -> Vec<&RegisteredConsole> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+        let claims_extension = |c: &RegisteredConsole| {
+            extension.as_deref().is_some_and(|ext| {
+                c.analyzer
+                    .file_extensions()
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(ext))
+            })
+        };
+        let is_sniffed = |c: &RegisteredConsole| sniffed.contains(&c.metadata.platform);
+
+        let unique_extension = self.consoles.iter().filter(|c| claims_extension(c)).count() == 1;
+
+        let mut candidates: Vec<&RegisteredConsole> = self
+            .consoles
+            .iter()
+            .filter(|c| is_sniffed(c) && claims_extension(c))
+            .collect();
+        let sniffed_only = self
+            .consoles
+            .iter()
+            .filter(|c| is_sniffed(c) && !claims_extension(c));
+        let extension_only = self
+            .consoles
+            .iter()
+            .filter(|c| !is_sniffed(c) && claims_extension(c));
+        if unique_extension {
+            candidates.extend(extension_only);
+            candidates.extend(sniffed_only);
+        } else {
+            candidates.extend(sniffed_only);
+            candidates.extend(extension_only);
+        }
+        candidates
+    }
+
     /// List all short names.
     pub fn short_names(&self) -> Vec<&'static str> {
         self.consoles
@@ -118,41 +479,81 @@ impl AnalysisContext {
 
     /// Check if a folder name matches any registered console.
     pub fn matches_any_console(&self, folder_name: &str) -> bool {
-        self.consoles
-            .iter()
-            .any(|c| c.analyzer.matches_folder(folder_name))
+        !self.find_by_folder(folder_name).is_empty()
     }
 
     /// Scan a root directory and match subfolders to registered consoles.
     ///
+    /// Folders that don't match a console are searched for console folders
+    /// of their own, down to the [scan depth](Self::set_scan_depth), so
+    /// grouped layouts like `roms/nintendo/snes/` are found. A matched
+    /// console folder is never searched further.
+    ///
     /// Returns a `FolderScanResult` containing matched console folders and
-    /// the names of any non-hidden folders that didn't match a console.
+    /// the names of any non-hidden folders that didn't match a console
+    /// (nested names are relative to the root, e.g., `nintendo/misc`).
     pub fn scan_console_folders(
         &self,
         root: &Path,
         filter: Option<&[Platform]>,
     ) -> std::io::Result<FolderScanResult> {
-        let mut matches = Vec::new();
-        let mut unrecognized = Vec::new();
+        let mut result = FolderScanResult {
+            matches: Vec::new(),
+            unrecognized: Vec::new(),
+        };
+        let mut visited = HashSet::new();
+        scanner::mark_visited(root, &mut visited);
+        self.scan_level(root, "", filter, self.scan_depth, &mut visited, &mut result)?;
+        Ok(result)
+    }
 
-        let mut dir_entries: Vec<std::fs::DirEntry> = std::fs::read_dir(root)?.flatten().collect();
-        dir_entries.sort_by_key(|e| e.path());
+    /// Match the subfolders of `dir` against consoles, recursing into
+    /// unmatched ones while `depth` allows. `prefix` is `dir` relative to
+    /// the scan root, used to label unrecognized folders.
+    fn scan_level(
+        &self,
+        dir: &Path,
+        prefix: &str,
+        filter: Option<&[Platform]>,
+        depth: usize,
+        visited: &mut HashSet<PathBuf>,
+        result: &mut FolderScanResult,
+    ) -> std::io::Result<()> {
+        let mut dir_entries: Vec<std::fs::DirEntry> =
+            std::fs::read_dir(long_path(dir))?.flatten().collect();
+        dir_entries.sort_by_key(|e| e.file_name());
 
         for entry in dir_entries {
-            let path = entry.path();
-            if !path.is_dir() {
+            if self.symlinks == SymlinkPolicy::Skip && scanner::is_link(&entry) {
+                continue;
+            }
+            let path = dir.join(entry.file_name());
+            if !long_path(&path).is_dir() || !scanner::mark_visited(&path, visited) {
                 continue;
             }
 
-            let folder_name = match path.file_name().and_then(|n| n.to_str()) {
-                Some(name) => name.to_string(),
-                None => continue,
-            };
+            // Console names are ASCII, so a lossy name only affects the
+            // label of a non-UTF8 grouping folder, not matching
+            let folder_name = entry.file_name().to_string_lossy().into_owned();
 
             let matching_consoles = self.find_by_folder(&folder_name);
             if matching_consoles.is_empty() {
-                if !folder_name.starts_with('.') {
-                    unrecognized.push(folder_name);
+                if folder_name.starts_with('.') || folder_name.ends_with(".m3u") {
+                    continue;
+                }
+                let matched_before = result.matches.len();
+                let unrecognized_before = result.unrecognized.len();
+                let label = format!("{}{}", prefix, folder_name);
+                if depth > 1 {
+                    let nested_prefix = format!("{}/", label);
+                    // An unreadable grouping folder is reported as unrecognized
+                    let _ =
+                        self.scan_level(&path, &nested_prefix, filter, depth - 1, visited, result);
+                }
+                // Nothing found inside: report just this folder, not its children
+                if result.matches.len() == matched_before {
+                    result.unrecognized.truncate(unrecognized_before);
+                    result.unrecognized.push(label);
                 }
                 continue;
             }
@@ -167,7 +568,7 @@ impl AnalysisContext {
             };
 
             for console in consoles_to_use {
-                matches.push(ConsoleFolder {
+                result.matches.push(ConsoleFolder {
                     path: path.clone(),
                     folder_name: folder_name.clone(),
                     platform: console.metadata.platform,
@@ -175,10 +576,7 @@ impl AnalysisContext {
             }
         }
 
-        Ok(FolderScanResult {
-            matches,
-            unrecognized,
-        })
+        Ok(())
     }
 }
 
@@ -201,3 +599,7 @@ pub struct FolderScanResult {
     /// Non-hidden folder names that didn't match any console.
     pub unrecognized: Vec<String>,
 }
+
+#[cfg(test)]
+#[path = "tests/context_tests.rs"]
+mod tests;
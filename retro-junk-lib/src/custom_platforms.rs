@@ -0,0 +1,333 @@
+//! User-defined platforms from `~/.config/retro-junk/platforms.toml`.
+//!
+//! Obscure systems without a dedicated analyzer can be declared in config:
+//!
+//! ```toml
+//! [[platform]]
+//! short_name = "vectrex"
+//! name = "GCE Vectrex"
+//! manufacturer = "GCE"
+//! aliases = ["gce vectrex"]
+//! extensions = ["vec", "bin"]
+//! dat_names = ["GCE - Vectrex"]
+//! screenscraper_id = 102
+//! ```
+//!
+//! Computer platforms whose games span several floppies or tapes can say
+//! which name tags mark the parts of one game, for `.m3u` grouping:
+//!
+//! ```toml
+//! [[platform]]
+//! short_name = "amiga"
+//! name = "Commodore Amiga"
+//! extensions = ["adf", "ipf"]
+//! multi_part = ["disk"]
+//! ```
+//!
+//! Each entry is backed by a [`GenericAnalyzer`], which recognizes files by
+//! extension only and supports hashing, so the platform can be renamed
+//! against its DAT and scraped by hash.
+//!
+//! The same file can correct the ScreenScraper system ID of any platform,
+//! built-in or custom, keyed by platform name:
+//!
+//! ```toml
+//! [screenscraper_ids]
+//! sg1000 = 109
+//! vectrex = 102
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::{self, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use retro_junk_core::custom_platform::{self, CustomPlatform};
+use retro_junk_core::disc::PartKind;
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, DatSource, Platform, ReadSeek, RomAnalyzer, RomIdentification,
+};
+
+use crate::context::AnalysisContext;
+
+/// File name of the custom platform config, next to `settings.toml`.
+pub const CONFIG_FILE_NAME: &str = "platforms.toml";
+
+#[derive(Debug, Error)]
+pub enum CustomPlatformError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid platforms file {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+    #[error("Invalid platform '{name}': {message}")]
+    Invalid { name: String, message: String },
+}
+
+/// One `[[platform]]` entry.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomPlatformConfig {
+    pub short_name: String,
+    pub name: String,
+    #[serde(default)]
+    pub manufacturer: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub extensions: Vec<String>,
+    #[serde(default)]
+    pub dat_names: Vec<String>,
+    /// "no-intro" (default) or "redump".
+    #[serde(default)]
+    pub dat_source: Option<String>,
+    /// Download identifiers when they differ from `dat_names` (Redump).
+    #[serde(default)]
+    pub dat_download_ids: Vec<String>,
+    #[serde(default)]
+    pub screenscraper_id: Option<u32>,
+    /// Optional header size in bytes, skipped before DAT hashing when the
+    /// file is that many bytes over a multiple of 1 KiB.
+    #[serde(default)]
+    pub header_size: u64,
+    /// Tags that mark parts of one game: "disc", "disk", "side", "tape".
+    /// Defaults to "disc".
+    #[serde(default)]
+    pub multi_part: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    platform: Vec<CustomPlatformConfig>,
+    /// Platform name -> ScreenScraper system ID.
+    #[serde(default)]
+    screenscraper_ids: BTreeMap<String, u32>,
+}
+
+/// Default location of the custom platform config.
+pub fn default_config_path() -> PathBuf {
+    crate::settings::settings_path().with_file_name(CONFIG_FILE_NAME)
+}
+
+/// Parse the contents of a platforms file.
+pub fn parse_config(
+    contents: &str,
+    path: &Path,
+) -> Result<Vec<CustomPlatformConfig>, CustomPlatformError> {
+    Ok(parse_file(contents, path)?.platform)
+}
+
+fn parse_file(contents: &str, path: &Path) -> Result<ConfigFile, CustomPlatformError> {
+    toml::from_str(contents).map_err(|e| CustomPlatformError::Parse {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })
+}
+
+/// Load custom platforms from `path` and register them in `ctx`, then
+/// apply any `[screenscraper_ids]` overrides.
+///
+/// A missing file is not an error. Returns the registered platforms.
+pub fn register_from_file(
+    ctx: &mut AnalysisContext,
+    path: &Path,
+) -> Result<Vec<Platform>, CustomPlatformError> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let file = parse_file(&contents, path)?;
+    let platforms = file
+        .platform
+        .into_iter()
+        .map(|config| {
+            let analyzer = GenericAnalyzer::from_config(config)?;
+            let platform = analyzer.platform();
+            if ctx.get_by_platform(platform).is_none() {
+                ctx.register(analyzer);
+            }
+            Ok(platform)
+        })
+        .collect::<Result<Vec<_>, CustomPlatformError>>()?;
+
+    // Resolved after registration so custom platforms can be named too
+    for (name, id) in file.screenscraper_ids {
+        let platform: Platform = name.parse().map_err(|_| CustomPlatformError::Invalid {
+            name: name.clone(),
+            message: "unknown platform in [screenscraper_ids]".to_string(),
+        })?;
+        custom_platform::set_screenscraper_id(platform, id);
+    }
+
+    Ok(platforms)
+}
+
+/// Analyzer for custom platforms: extension matching and hashing only.
+#[derive(Debug, Clone)]
+pub struct GenericAnalyzer {
+    platform: Platform,
+    extensions: &'static [&'static str],
+    dat_source: DatSource,
+    dat_names: &'static [&'static str],
+    dat_download_ids: &'static [&'static str],
+    header_size: u64,
+}
+
+impl GenericAnalyzer {
+    /// Validate a config entry and register its platform.
+    pub fn from_config(config: CustomPlatformConfig) -> Result<Self, CustomPlatformError> {
+        let invalid = |message: &str| CustomPlatformError::Invalid {
+            name: config.short_name.clone(),
+            message: message.to_string(),
+        };
+
+        let short_name = config.short_name.trim().to_lowercase();
+        if short_name.is_empty() {
+            return Err(invalid("short_name must not be empty"));
+        }
+        if config.extensions.is_empty() {
+            return Err(invalid("at least one extension is required"));
+        }
+        let mut aliases = vec![short_name.clone()];
+        for alias in &config.aliases {
+            let alias = alias.trim().to_lowercase();
+            if !alias.is_empty() && !aliases.contains(&alias) {
+                aliases.push(alias);
+            }
+        }
+        if let Some(taken) = aliases.iter().find(|a| {
+            Platform::all()
+                .iter()
+                .any(|p| p.aliases().contains(&a.as_str()))
+        }) {
+            return Err(invalid(&format!(
+                "'{}' is already the name of a built-in platform",
+                taken
+            )));
+        }
+        let dat_source = match config.dat_source.as_deref() {
+            None | Some("no-intro") | Some("nointro") => DatSource::NoIntro,
+            Some("redump") => DatSource::Redump,
+            Some(other) => {
+                return Err(invalid(&format!(
+                    "unknown dat_source '{}' (expected no-intro or redump)",
+                    other
+                )));
+            }
+        };
+
+        let part_kinds = config
+            .multi_part
+            .iter()
+            .map(|name| {
+                PartKind::from_name(name.trim()).ok_or_else(|| {
+                    invalid(&format!(
+                        "unknown multi_part '{}' (expected disc, disk, side, or tape)",
+                        name
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let dat_names = leak_strs(config.dat_names);
+        let dat_download_ids = if config.dat_download_ids.is_empty() {
+            dat_names
+        } else {
+            leak_strs(config.dat_download_ids)
+        };
+        let extensions = leak_strs(
+            config
+                .extensions
+                .iter()
+                .map(|e| e.trim_start_matches('.').to_lowercase())
+                .collect(),
+        );
+
+        let platform = custom_platform::register(CustomPlatform {
+            short_name: leak(short_name),
+            display_name: leak(config.name),
+            manufacturer: leak(config.manufacturer.unwrap_or_else(|| "Unknown".to_string())),
+            aliases: leak_strs(aliases),
+            screenscraper_id: config.screenscraper_id,
+            part_kinds: Box::leak(part_kinds.into_boxed_slice()),
+        });
+
+        Ok(Self {
+            platform,
+            extensions,
+            dat_source,
+            dat_names,
+            dat_download_ids,
+            header_size: config.header_size,
+        })
+    }
+}
+
+impl RomAnalyzer for GenericAnalyzer {
+    fn analyze(
+        &self,
+        reader: &mut dyn ReadSeek,
+        _options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let mut id = RomIdentification::new().with_platform(self.platform);
+        id.file_size = Some(reader.seek(SeekFrom::End(0))?);
+        Ok(id)
+    }
+
+    fn platform(&self) -> Platform {
+        self.platform
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        self.extensions
+    }
+
+    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
+        // No format knowledge; files are recognized by extension only.
+        false
+    }
+
+    fn dat_source(&self) -> DatSource {
+        self.dat_source
+    }
+
+    fn dat_names(&self) -> &'static [&'static str] {
+        self.dat_names
+    }
+
+    fn dat_download_ids(&self) -> &'static [&'static str] {
+        self.dat_download_ids
+    }
+
+    fn dat_header_size(
+        &self,
+        _reader: &mut dyn ReadSeek,
+        file_size: u64,
+    ) -> Result<u64, AnalysisError> {
+        let headered = self.header_size > 0
+            && file_size > self.header_size
+            && file_size % 1024 == self.header_size % 1024;
+        Ok(if headered { self.header_size } else { 0 })
+    }
+}
+
+/// Custom platform metadata lives for the whole process (see
+/// [`retro_junk_core::custom_platform`]).
+fn leak(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+fn leak_strs(v: Vec<String>) -> &'static [&'static str] {
+    Box::leak(
+        v.into_iter()
+            .map(leak)
+            .collect::<Vec<_>>()
+            .into_boxed_slice(),
+    )
+}
+
+#[cfg(test)]
+#[path = "tests/custom_platforms_tests.rs"]
+mod tests;
@@ -0,0 +1,459 @@
+//! Library-wide DAT verification.
+//!
+//! Hashes every ROM in a console folder and checks it against the folder's
+//! DATs, sorting each file into the states a 1G1R audit cares about:
+//! verified, bad dump, overdump, or unknown. Nothing is renamed or modified,
+//! so it can run on a read-only share between `analyze` and `rename`.
+//! [`verify_source`] checks a single file, which can also be a URL or an
+//! SMB share (see [`crate::remote`]).
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use retro_junk_core::{AnalysisOptions, ReadSeek, RomAnalyzer};
+use retro_junk_dat::cache;
+use retro_junk_dat::error::DatError;
+use retro_junk_dat::matcher::DatIndex;
+
+use crate::archive;
+use crate::display::{SizeVerdict, compute_size_verdict};
+use crate::remote::Source;
+use crate::rename::{self, HashMatchOutcome, RenameProgress};
+use crate::rename_export::{ExportFormat, write_json};
+use crate::scanner::{ScanOptions, SymlinkPolicy};
+
+/// How a file compares to the DAT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyStatus {
+    /// The hashes match a DAT entry.
+    Verified,
+    /// The file has the size of a DAT entry, or of its header, but the
+    /// hashes don't match: corrupt, trimmed, or truncated.
+    BadDump,
+    /// The file is larger than its header declares (extra data or a copier
+    /// header), so it can't match the DAT as-is.
+    Overdump,
+    /// Nothing in the DAT resembles the file.
+    Unknown,
+}
+
+impl VerifyStatus {
+    /// Short label for console output.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Verified => "VERIFIED",
+            Self::BadDump => "BAD DUMP",
+            Self::Overdump => "OVERDUMP",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// Name used in report files.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Verified => "verified",
+            Self::BadDump => "bad-dump",
+            Self::Overdump => "overdump",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// One checked file.
+#[derive(Debug, Clone)]
+pub struct VerifiedFile {
+    pub path: PathBuf,
+    pub status: VerifyStatus,
+    /// DAT game name, for verified files.
+    pub game_name: Option<String>,
+    /// CRC32 of the hashed data (empty for CHDs and CUE sheets matched by
+    /// other hashes).
+    pub crc32: String,
+    /// Why a file isn't verified (e.g. the size verdict).
+    pub detail: Option<String>,
+}
+
+/// Results for one console folder.
+#[derive(Debug, Clone, Default)]
+pub struct FolderVerification {
+    /// DAT names and versions the files were checked against.
+    pub dat_versions: Vec<String>,
+    pub files: Vec<VerifiedFile>,
+    /// Files that couldn't be read or hashed.
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+impl FolderVerification {
+    /// Number of files with the given status.
+    pub fn count(&self, status: &VerifyStatus) -> usize {
+        self.files.iter().filter(|f| &f.status == status).count()
+    }
+}
+
+/// Options controlling verification.
+#[derive(Debug, Clone, Default)]
+pub struct VerifyOptions {
+    /// Custom DAT directory (instead of cache).
+    pub dat_dir: Option<PathBuf>,
+    /// Maximum number of ROMs to check.
+    pub limit: Option<usize>,
+    pub symlinks: SymlinkPolicy,
+}
+
+/// Progress information for callbacks.
+#[derive(Debug, Clone)]
+pub enum VerifyProgress {
+    /// Scanning the folder for ROM files.
+    Scanning { file_count: usize },
+    /// Hashing a file.
+    Hashing {
+        file_name: String,
+        file_index: usize,
+        total: usize,
+        bytes_done: u64,
+        bytes_total: u64,
+    },
+    /// Done checking.
+    Done,
+}
+
+/// Check every ROM in `folder` against the platform's DATs.
+pub fn verify_folder(
+    folder: &Path,
+    analyzer: &dyn RomAnalyzer,
+    options: &VerifyOptions,
+    progress: &dyn Fn(VerifyProgress),
+) -> Result<FolderVerification, DatError> {
+    let (index, dat_versions) = load_index(analyzer, options)?;
+
+    let extensions = crate::scanner::extension_set(analyzer.file_extensions());
+    let scan_options = ScanOptions::new().symlinks(options.symlinks).archives(true);
+    let game_entries =
+        crate::scanner::scan_game_entries_with(folder, &extensions, &scan_options)
+            .map_err(|e| DatError::cache(format!("Error scanning {}: {}", folder.display(), e)))?;
+
+    let mut paths: Vec<PathBuf> = game_entries
+        .iter()
+        .flat_map(|entry| entry.all_files())
+        .cloned()
+        .collect();
+    if let Some(max) = options.limit {
+        paths.truncate(max);
+    }
+
+    progress(VerifyProgress::Scanning {
+        file_count: paths.len(),
+    });
+
+    let mut verification = FolderVerification {
+        dat_versions,
+        ..Default::default()
+    };
+    for (i, path) in paths.iter().enumerate() {
+        let on_progress = forward_hashing(progress, i, paths.len());
+        let outcome = match rename::match_by_hash(path, &index, analyzer, &on_progress) {
+            Ok(o) => o,
+            Err(e) => {
+                verification.errors.push((path.clone(), e.to_string()));
+                continue;
+            }
+        };
+        let file = classify(path.clone(), outcome, &index, || {
+            size_verdict(path, analyzer, &extensions)
+        });
+        verification.files.push(file);
+    }
+
+    progress(VerifyProgress::Done);
+    Ok(verification)
+}
+
+/// Check one file against the platform's DATs. Local files are checked
+/// like those in [`verify_folder`]; URLs and SMB shares are read on demand
+/// through [`Source::open`], and a source that can't be opened is reported
+/// in `errors`.
+pub fn verify_source(
+    source: &Source,
+    analyzer: &dyn RomAnalyzer,
+    options: &VerifyOptions,
+    progress: &dyn Fn(VerifyProgress),
+) -> Result<FolderVerification, DatError> {
+    let (index, dat_versions) = load_index(analyzer, options)?;
+    let mut verification = FolderVerification {
+        dat_versions,
+        ..Default::default()
+    };
+    progress(VerifyProgress::Scanning { file_count: 1 });
+    let on_progress = forward_hashing(progress, 0, 1);
+
+    let path = PathBuf::from(source.to_string());
+    let checked = match source {
+        Source::Local(local) => {
+            let extensions = crate::scanner::extension_set(analyzer.file_extensions());
+            rename::match_by_hash(local, &index, analyzer, &on_progress).map(|outcome| {
+                classify(path.clone(), outcome, &index, || {
+                    size_verdict(local, analyzer, &extensions)
+                })
+            })
+        }
+        _ => verify_remote(source, &index, analyzer, &on_progress).map(|(outcome, mut reader)| {
+            let local = source.local_path();
+            classify(path.clone(), outcome, &index, || {
+                reader_size_verdict(reader.as_mut(), local.as_deref(), analyzer)
+            })
+        }),
+    };
+    match checked {
+        Ok(file) => verification.files.push(file),
+        Err(e) => verification.errors.push((path, e.to_string())),
+    }
+
+    progress(VerifyProgress::Done);
+    Ok(verification)
+}
+
+/// Open and hash a remote source, returning the reader for a second look.
+fn verify_remote(
+    source: &Source,
+    index: &DatIndex,
+    analyzer: &dyn RomAnalyzer,
+    progress: &dyn Fn(RenameProgress),
+) -> Result<(HashMatchOutcome, Box<dyn ReadSeek>), DatError> {
+    let mut reader = source
+        .open()
+        .map_err(|e| DatError::Io(std::io::Error::other(e)))?;
+    let local = source.local_path();
+    let outcome = rename::match_reader_by_hash(
+        reader.as_mut(),
+        &source.file_name(),
+        local.as_deref(),
+        None,
+        index,
+        analyzer,
+        progress,
+    )?;
+    Ok((outcome, reader))
+}
+
+/// Report hashing progress of file `file_index` out of `total`.
+fn forward_hashing(
+    progress: &dyn Fn(VerifyProgress),
+    file_index: usize,
+    total: usize,
+) -> impl Fn(RenameProgress) + '_ {
+    move |p| {
+        if let RenameProgress::Hashing {
+            file_name,
+            bytes_done,
+            bytes_total,
+        } = p
+        {
+            progress(VerifyProgress::Hashing {
+                file_name,
+                file_index,
+                total,
+                bytes_done,
+                bytes_total,
+            });
+        }
+    }
+}
+
+/// Load the platform's DATs, with their names and versions for display.
+fn load_index(
+    analyzer: &dyn RomAnalyzer,
+    options: &VerifyOptions,
+) -> Result<(DatIndex, Vec<String>), DatError> {
+    let dat_names = analyzer.dat_names();
+    if dat_names.is_empty() {
+        return Err(DatError::cache(format!(
+            "No DAT support for platform '{}'",
+            analyzer.platform_name()
+        )));
+    }
+
+    let dats = cache::load_dats(
+        analyzer.short_name(),
+        dat_names,
+        analyzer.dat_download_ids(),
+        options.dat_dir.as_deref(),
+        analyzer.dat_source(),
+    )?;
+    let dat_versions = dats
+        .iter()
+        .map(|d| {
+            if d.version.is_empty() {
+                d.name.clone()
+            } else {
+                format!("{} ({})", d.name, d.version)
+            }
+        })
+        .collect();
+    Ok((DatIndex::from_dats(dats), dat_versions))
+}
+
+/// Sort a file into a [`VerifyStatus`] from its hash match, looking at its
+/// header size (`verdict`) only when nothing matched.
+fn classify(
+    path: PathBuf,
+    outcome: HashMatchOutcome,
+    index: &DatIndex,
+    verdict: impl FnOnce() -> Option<SizeVerdict>,
+) -> VerifiedFile {
+    match outcome.result {
+        None if outcome.mismatch_reason.is_some() => VerifiedFile {
+            path,
+            status: VerifyStatus::BadDump,
+            game_name: None,
+            crc32: String::new(),
+            detail: outcome.mismatch_reason,
+        },
+        Some(result) => VerifiedFile {
+            path,
+            status: VerifyStatus::Verified,
+            game_name: Some(index.games[result.game_index].name.clone()),
+            crc32: outcome.crc32,
+            detail: None,
+        },
+        None => {
+            let (status, detail) = classify_unmatched(
+                verdict().as_ref(),
+                index.candidates_by_size(outcome.data_size).is_some(),
+            );
+            VerifiedFile {
+                path,
+                status,
+                game_name: None,
+                crc32: outcome.crc32,
+                detail,
+            }
+        }
+    }
+}
+
+/// Classify a file whose hashes matched nothing, from its header size
+/// verdict and whether any DAT entry has its (header-stripped) size.
+pub fn classify_unmatched(
+    verdict: Option<&SizeVerdict>,
+    dat_size_exists: bool,
+) -> (VerifyStatus, Option<String>) {
+    match verdict {
+        Some(v @ (SizeVerdict::Oversized { .. } | SizeVerdict::CopierHeader)) => {
+            (VerifyStatus::Overdump, Some(v.description()))
+        }
+        Some(v @ (SizeVerdict::Trimmed { .. } | SizeVerdict::Truncated { .. })) => {
+            (VerifyStatus::BadDump, Some(v.description()))
+        }
+        _ if dat_size_exists => (
+            VerifyStatus::BadDump,
+            Some("size matches a DAT entry but the hashes don't".to_string()),
+        ),
+        _ => (VerifyStatus::Unknown, None),
+    }
+}
+
+/// The file's size compared to its header, if the header declares one.
+fn size_verdict(
+    path: &Path,
+    analyzer: &dyn RomAnalyzer,
+    extensions: &std::collections::HashSet<String>,
+) -> Option<SizeVerdict> {
+    let mut opened = archive::open_rom(path, extensions).ok()?;
+    reader_size_verdict(
+        opened.reader.as_mut(),
+        Some(&opened.analysis_path),
+        analyzer,
+    )
+}
+
+/// [`size_verdict`] of an open file.
+fn reader_size_verdict(
+    reader: &mut dyn ReadSeek,
+    analysis_path: Option<&Path>,
+    analyzer: &dyn RomAnalyzer,
+) -> Option<SizeVerdict> {
+    reader.rewind().ok()?;
+    let options = AnalysisOptions {
+        file_path: analysis_path.map(Path::to_path_buf),
+        ..AnalysisOptions::new().quick(true)
+    };
+    let info = analyzer.analyze(reader, &options).ok()?;
+    Some(compute_size_verdict(info.file_size?, info.expected_size?))
+}
+
+/// One row of a verification report.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReportRow {
+    /// Console short name (e.g., "snes").
+    pub console: String,
+    pub file: String,
+    /// "verified", "bad-dump", "overdump", "unknown", or "error".
+    pub status: &'static str,
+    pub game_name: Option<String>,
+    pub crc32: Option<String>,
+    pub detail: Option<String>,
+}
+
+/// Flatten a folder's results into report rows, errors last.
+pub fn verify_report_rows(
+    console: &str,
+    verification: &FolderVerification,
+) -> Vec<VerifyReportRow> {
+    let mut rows: Vec<VerifyReportRow> = verification
+        .files
+        .iter()
+        .map(|f| VerifyReportRow {
+            console: console.to_string(),
+            file: f.path.display().to_string(),
+            status: f.status.as_str(),
+            game_name: f.game_name.clone(),
+            crc32: (!f.crc32.is_empty()).then(|| f.crc32.clone()),
+            detail: f.detail.clone(),
+        })
+        .collect();
+    rows.extend(
+        verification
+            .errors
+            .iter()
+            .map(|(path, msg)| VerifyReportRow {
+                console: console.to_string(),
+                file: path.display().to_string(),
+                status: "error",
+                game_name: None,
+                crc32: None,
+                detail: Some(msg.clone()),
+            }),
+    );
+    rows
+}
+
+/// Write report rows to `path` in the given format.
+pub fn write_verify_report(
+    path: &Path,
+    format: ExportFormat,
+    rows: &[VerifyReportRow],
+) -> std::io::Result<()> {
+    match format {
+        ExportFormat::Json => write_json(path, rows),
+        ExportFormat::Csv => {
+            let mut out = csv::Writer::from_path(path)?;
+            out.write_record(["console", "file", "status", "game_name", "crc32", "detail"])?;
+            for r in rows {
+                out.write_record([
+                    r.console.as_str(),
+                    r.file.as_str(),
+                    r.status,
+                    r.game_name.as_deref().unwrap_or(""),
+                    r.crc32.as_deref().unwrap_or(""),
+                    r.detail.as_deref().unwrap_or(""),
+                ])?;
+            }
+            out.flush()
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/dat_verify_tests.rs"]
+mod tests;
@@ -0,0 +1,482 @@
+//! Device profiles and device-ready exports.
+//!
+//! Flash carts, FPGA consoles and handhelds each expect their own SD card
+//! layout: where each system's games go, which formats the firmware loads,
+//! and what the card's filesystem allows in a file name. A
+//! [`DeviceProfile`] records that, and an export copies games from the
+//! library into that layout, so the card can be filled straight from the
+//! collection without hand-sorting.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use retro_junk_core::Platform;
+use retro_junk_core::disc::cue;
+
+use crate::sanitize::sanitize_file_name;
+use crate::scanner::{self, GameEntry, ScanOptions};
+use crate::split::{self, SplitFormat};
+
+/// Filesystem a device's card is formatted with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetFilesystem {
+    Fat32,
+    ExFat,
+}
+
+impl TargetFilesystem {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Fat32 => "FAT32",
+            Self::ExFat => "exFAT",
+        }
+    }
+
+    /// Largest file the filesystem can hold, if limited.
+    pub fn max_file_size(&self) -> Option<u64> {
+        match self {
+            Self::Fat32 => Some(u32::MAX as u64),
+            Self::ExFat => None,
+        }
+    }
+}
+
+/// Where one system's games go on a device, and in which formats.
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceSystem {
+    pub platform: Platform,
+    /// Folder below the profile's root.
+    pub folder: &'static str,
+    /// Extensions the device loads, most preferred first. When a game is
+    /// in the library in several formats, only the first match is copied.
+    pub formats: &'static [&'static str],
+    /// How files too large for the card's filesystem are split.
+    pub split: SplitFormat,
+}
+
+/// A device's card layout.
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceProfile {
+    /// Name used on the command line.
+    pub id: &'static str,
+    pub name: &'static str,
+    pub filesystem: TargetFilesystem,
+    /// Folder holding the system folders (empty for the card root).
+    pub root: &'static str,
+    /// Whether multi-disc games keep their `.m3u` folder. Devices that
+    /// don't read `.m3u` folders get the discs directly in the system folder.
+    pub m3u_folders: bool,
+    pub systems: &'static [DeviceSystem],
+}
+
+const fn system(
+    platform: Platform,
+    folder: &'static str,
+    formats: &'static [&'static str],
+) -> DeviceSystem {
+    DeviceSystem {
+        platform,
+        folder,
+        formats,
+        split: SplitFormat::Parts,
+    }
+}
+
+/// Built-in profiles.
+pub const PROFILES: &[DeviceProfile] = &[
+    DeviceProfile {
+        id: "mister",
+        name: "MiSTer FPGA",
+        filesystem: TargetFilesystem::ExFat,
+        root: "games",
+        m3u_folders: false,
+        systems: &[
+            system(Platform::Nes, "NES", &["nes"]),
+            system(Platform::Snes, "SNES", &["sfc", "smc"]),
+            system(Platform::N64, "N64", &["z64", "n64", "v64"]),
+            system(Platform::GameBoy, "GAMEBOY", &["gb", "gbc"]),
+            system(Platform::Gba, "GBA", &["gba"]),
+            system(Platform::MasterSystem, "SMS", &["sms"]),
+            system(Platform::GameGear, "SMS", &["gg"]),
+            system(Platform::Genesis, "Genesis", &["md", "gen", "bin"]),
+            system(Platform::Sega32x, "S32X", &["32x"]),
+            system(Platform::SegaCd, "MegaCD", &["chd", "cue"]),
+            system(Platform::Saturn, "Saturn", &["chd", "cue"]),
+            system(Platform::Ps1, "PSX", &["chd", "cue"]),
+        ],
+    },
+    DeviceProfile {
+        id: "everdrive-n8",
+        name: "EverDrive N8",
+        filesystem: TargetFilesystem::Fat32,
+        root: "",
+        m3u_folders: false,
+        systems: &[system(Platform::Nes, "NES", &["nes"])],
+    },
+    DeviceProfile {
+        id: "everdrive-gba",
+        name: "EverDrive GBA",
+        filesystem: TargetFilesystem::Fat32,
+        root: "",
+        m3u_folders: false,
+        systems: &[system(Platform::Gba, "GBA", &["gba"])],
+    },
+    DeviceProfile {
+        id: "megasd",
+        name: "Terraonion MegaSD",
+        filesystem: TargetFilesystem::Fat32,
+        root: "",
+        m3u_folders: false,
+        systems: &[
+            system(Platform::Genesis, "Genesis", &["md", "gen", "bin"]),
+            system(Platform::Sega32x, "32X", &["32x"]),
+            system(Platform::SegaCd, "SegaCD", &["cue", "iso"]),
+            system(Platform::MasterSystem, "SMS", &["sms"]),
+        ],
+    },
+    DeviceProfile {
+        id: "usb-loader-gx",
+        name: "USB Loader GX (Wii)",
+        filesystem: TargetFilesystem::Fat32,
+        root: "",
+        m3u_folders: false,
+        systems: &[DeviceSystem {
+            platform: Platform::Wii,
+            folder: "wbfs",
+            formats: &["wbfs", "iso"],
+            split: SplitFormat::Wbfs,
+        }],
+    },
+    DeviceProfile {
+        id: "anbernic",
+        name: "Anbernic handheld (stock OS)",
+        filesystem: TargetFilesystem::Fat32,
+        root: "Roms",
+        m3u_folders: true,
+        systems: &[
+            system(Platform::Nes, "FC", &["nes"]),
+            system(Platform::Snes, "SFC", &["sfc", "smc"]),
+            system(Platform::N64, "N64", &["z64", "n64", "v64"]),
+            system(Platform::GameBoy, "GB", &["gb", "gbc"]),
+            system(Platform::Gba, "GBA", &["gba"]),
+            system(Platform::Ds, "NDS", &["nds"]),
+            system(Platform::MasterSystem, "SMS", &["sms"]),
+            system(Platform::GameGear, "GG", &["gg"]),
+            system(Platform::Genesis, "MD", &["md", "gen", "bin"]),
+            system(Platform::Sega32x, "32X", &["32x"]),
+            system(Platform::SegaCd, "SEGACD", &["chd", "cue"]),
+            system(Platform::Ps1, "PS", &["chd", "pbp", "cue"]),
+            system(Platform::Psp, "PSP", &["iso", "cso"]),
+        ],
+    },
+];
+
+/// Look up a built-in profile by id or display name (case-insensitive).
+pub fn find_profile(name: &str) -> Option<&'static DeviceProfile> {
+    PROFILES
+        .iter()
+        .find(|p| p.id.eq_ignore_ascii_case(name) || p.name.eq_ignore_ascii_case(name))
+}
+
+impl DeviceProfile {
+    /// Where `platform`'s games go, if the device runs them.
+    pub fn system(&self, platform: Platform) -> Option<&DeviceSystem> {
+        self.systems.iter().find(|s| s.platform == platform)
+    }
+
+    /// The folder for `system` below the card root.
+    pub fn system_dir(&self, target: &Path, system: &DeviceSystem) -> PathBuf {
+        target.join(self.root).join(system.folder)
+    }
+
+    /// Adjust a file name to what the device's filesystem accepts.
+    pub fn file_name(&self, name: &str) -> String {
+        sanitize_file_name(name)
+    }
+}
+
+/// One file to copy onto the device.
+#[derive(Debug, Clone)]
+pub struct ExportFile {
+    pub source: PathBuf,
+    pub dest: PathBuf,
+    pub size: u64,
+    /// The destination already holds a file of the same size.
+    pub up_to_date: bool,
+    /// CUE `FILE` references to rewrite because their targets were renamed.
+    pub rewrites: Vec<(String, String)>,
+    /// Set when the file is too large for the card and is copied in pieces.
+    pub split: Option<SplitFormat>,
+}
+
+/// What an export would copy for one system.
+#[derive(Debug, Default)]
+pub struct ExportPlan {
+    pub files: Vec<ExportFile>,
+    /// Library files in a format the device doesn't load.
+    pub unsupported: Vec<PathBuf>,
+    /// Files dropped because the same game exists in a preferred format.
+    pub superseded: Vec<PathBuf>,
+}
+
+impl ExportPlan {
+    /// Bytes that still need copying.
+    pub fn pending_bytes(&self) -> u64 {
+        self.files
+            .iter()
+            .filter(|f| !f.up_to_date)
+            .map(|f| f.size)
+            .sum()
+    }
+}
+
+/// Plan copying a system's games from `rom_dir` into the device layout.
+///
+/// `library_extensions` are the extensions the library holds for the
+/// system (the analyzer's), used to report games the device can't load.
+/// `filter` keeps only games whose name contains it (case-insensitive).
+pub fn plan_export(
+    profile: &DeviceProfile,
+    system: &DeviceSystem,
+    rom_dir: &Path,
+    target: &Path,
+    library_extensions: &HashSet<String>,
+    filter: Option<&str>,
+    limit: Option<usize>,
+) -> io::Result<ExportPlan> {
+    let formats = scanner::extension_set(system.formats);
+    let mut extensions = library_extensions.clone();
+    extensions.extend(formats.iter().cloned());
+
+    let filter = filter.map(str::to_lowercase);
+    let mut entries: Vec<GameEntry> =
+        scanner::scan_game_entries_with(rom_dir, &extensions, &ScanOptions::default())?
+            .into_iter()
+            .filter(|e| {
+                filter
+                    .as_ref()
+                    .is_none_or(|f| e.sort_key().to_string_lossy().to_lowercase().contains(f))
+            })
+            .collect();
+
+    let mut plan = ExportPlan::default();
+    let rank = |path: &Path| {
+        let ext = extension(path);
+        system.formats.iter().position(|f| *f == ext)
+    };
+
+    // Keep the most preferred format of each game
+    let mut best: HashMap<String, usize> = HashMap::new();
+    for entry in &entries {
+        if let GameEntry::SingleFile(path) = entry
+            && let Some(r) = rank(path)
+        {
+            let stem = stem_key(path);
+            let slot = best.entry(stem).or_insert(r);
+            *slot = (*slot).min(r);
+        }
+    }
+    entries.retain(|entry| match entry {
+        GameEntry::SingleFile(path) => match rank(path) {
+            None => {
+                plan.unsupported.push(path.clone());
+                false
+            }
+            Some(r) if best.get(&stem_key(path)).is_some_and(|b| *b < r) => {
+                plan.superseded.push(path.clone());
+                false
+            }
+            Some(_) => true,
+        },
+        GameEntry::MultiDisc { .. } => true,
+    });
+    if let Some(max) = limit {
+        entries.truncate(max);
+    }
+
+    let system_dir = profile.system_dir(target, system);
+    for entry in &entries {
+        match entry {
+            GameEntry::SingleFile(path) => {
+                add_game_file(&mut plan, profile, system, path, &system_dir)?
+            }
+            GameEntry::MultiDisc { name, files } => {
+                let dir = if profile.m3u_folders {
+                    system_dir.join(profile.file_name(name))
+                } else {
+                    system_dir.clone()
+                };
+                for path in files {
+                    if rank(path).is_some() {
+                        add_game_file(&mut plan, profile, system, path, &dir)?;
+                    } else {
+                        plan.unsupported.push(path.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    // Tracks pulled in by a CUE sheet aren't games of their own
+    let copied: HashSet<&PathBuf> = plan.files.iter().map(|f| &f.source).collect();
+    let unsupported = std::mem::take(&mut plan.unsupported);
+    plan.unsupported = unsupported
+        .into_iter()
+        .filter(|p| !copied.contains(p))
+        .collect();
+    Ok(plan)
+}
+
+/// Add a game file, plus the tracks a CUE sheet refers to.
+fn add_game_file(
+    plan: &mut ExportPlan,
+    profile: &DeviceProfile,
+    system: &DeviceSystem,
+    path: &Path,
+    dest_dir: &Path,
+) -> io::Result<()> {
+    let mut rewrites = Vec::new();
+    let mut sheet = String::new();
+    if extension(path) == "cue" {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        sheet = fs::read_to_string(path)?;
+        for (track, _) in sheet
+            .lines()
+            .filter_map(|l| cue::parse_file_directive(l.trim()))
+        {
+            let track_name = profile.file_name(&track);
+            if track_name != track {
+                rewrites.push((track.clone(), track_name.clone()));
+            }
+            plan.files.push(export_file(
+                profile,
+                system,
+                &dir.join(&track),
+                dest_dir.join(track_name),
+            )?);
+        }
+    }
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut file = export_file(
+        profile,
+        system,
+        path,
+        dest_dir.join(profile.file_name(&name)),
+    )?;
+    if !rewrites.is_empty() {
+        file.size = rewrite_sheet(&sheet, &rewrites).len() as u64;
+        file.up_to_date = fs::metadata(&file.dest).is_ok_and(|m| m.len() == file.size);
+        file.rewrites = rewrites;
+    }
+    plan.files.push(file);
+    Ok(())
+}
+
+fn export_file(
+    profile: &DeviceProfile,
+    system: &DeviceSystem,
+    source: &Path,
+    dest: PathBuf,
+) -> io::Result<ExportFile> {
+    let size = fs::metadata(source)?.len();
+    let split = profile
+        .filesystem
+        .max_file_size()
+        .is_some_and(|max| size > max)
+        .then_some(system.split);
+    let up_to_date = match split {
+        // Every piece is there at its expected size
+        Some(format) => {
+            let parts = format.part_paths(&dest, size);
+            let last = parts.len() as u64 - 1;
+            parts.iter().enumerate().all(|(i, part)| {
+                let expected = if i as u64 == last {
+                    size - last * split::SPLIT_CHUNK_SIZE
+                } else {
+                    split::SPLIT_CHUNK_SIZE
+                };
+                fs::metadata(part).is_ok_and(|m| m.len() == expected)
+            })
+        }
+        None => fs::metadata(&dest).is_ok_and(|m| m.len() == size),
+    };
+    Ok(ExportFile {
+        source: source.to_path_buf(),
+        dest,
+        size,
+        up_to_date,
+        rewrites: Vec::new(),
+        split,
+    })
+}
+
+/// Copy the files in `plan` that aren't already on the device.
+///
+/// `progress` is called before each copy with the file and the number of
+/// files handled so far. Returns the number of files copied.
+pub fn apply_export(plan: &ExportPlan, progress: &dyn Fn(&ExportFile, usize)) -> io::Result<usize> {
+    let mut copied = 0;
+    for (i, file) in plan.files.iter().enumerate() {
+        if file.up_to_date {
+            continue;
+        }
+        progress(file, i);
+        if let Some(parent) = file.dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if let Some(format) = file.split {
+            split::split_file(&file.source, &file.dest, format)?;
+        } else if file.rewrites.is_empty() {
+            fs::copy(&file.source, &file.dest)?;
+        } else {
+            let sheet = fs::read_to_string(&file.source)?;
+            fs::write(&file.dest, rewrite_sheet(&sheet, &file.rewrites))?;
+        }
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// Point a CUE sheet's `FILE` lines at renamed tracks. Other lines, such
+/// as a `TITLE` that happens to match a track name, are left alone.
+fn rewrite_sheet(sheet: &str, rewrites: &[(String, String)]) -> String {
+    let mut out = String::with_capacity(sheet.len());
+    for line in sheet.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\r', '\n']);
+        let renamed = cue::parse_file_directive(body).and_then(|(track, file_type)| {
+            let (_, to) = rewrites.iter().find(|(from, _)| *from == track)?;
+            Some((to, file_type))
+        });
+        match renamed {
+            Some((to, file_type)) => {
+                let indent = &body[..body.len() - body.trim_start().len()];
+                out.push_str(&format!("{indent}FILE \"{to}\" {file_type}"));
+                out.push_str(&line[body.len()..]);
+            }
+            None => out.push_str(line),
+        }
+    }
+    out
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn stem_key(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[path = "tests/device_tests.rs"]
+mod tests;
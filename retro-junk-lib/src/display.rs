@@ -156,6 +156,7 @@ pub const HARDWARE_KEYS: &[&str] = &[
     "prg_rom_size",
     "chr_rom_size",
     "sram_size",
+    "save_type",
     "ram_size",
     "prg_ram_size",
     "prg_nvram_size",
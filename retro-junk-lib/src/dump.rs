@@ -0,0 +1,228 @@
+//! Dump DVDs from an optical drive to an ISO image.
+//!
+//! The drive is read through the OS block device (`/dev/sr0`, `/dev/disk2`,
+//! `\\.\D:`), which exposes the 2048-byte user data of each sector. That is
+//! exactly what DVD-based Redump entries (PS2 DVD, Xbox) describe, so those
+//! dumps verify directly. Only ISO output is written: raw 2352-byte CD reads
+//! need drive-specific passthrough commands and are not supported, and
+//! neither is CUE/BIN output. CDs, whose Redump entries are raw tracks, are
+//! refused before the copy starts: CD-based systems outright, and the CD
+//! discs of systems that also shipped on DVD (PS2) once their volume
+//! descriptors show the disc is a CD. Cartridge systems are refused too.
+//!
+//! Unreadable sectors are retried, then zero-filled and reported so the
+//! user can clean the disc and try again.
+
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use retro_junk_core::{AnalysisError, DatSource, RomAnalyzer};
+use retro_junk_dat::cache;
+use retro_junk_dat::error::DatError;
+use retro_junk_dat::matcher::{DatIndex, FileHashes};
+use thiserror::Error;
+
+use crate::hasher;
+
+/// User-data bytes per sector exposed by the block device.
+pub const SECTOR_SIZE: u64 = 2048;
+
+/// Sectors read per request when the disc is reading cleanly.
+const SECTORS_PER_READ: u64 = 32;
+
+#[derive(Debug, Error)]
+pub enum DumpError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Verification failed: {0}")]
+    Dat(#[from] DatError),
+    #[error("Can't identify the disc: {0}")]
+    Analysis(#[from] AnalysisError),
+    #[error("Device {0} reports no readable data (is a disc inserted?)")]
+    EmptyDevice(PathBuf),
+    #[error(
+        "{0} CDs are verified as raw 2352-byte tracks, which a drive's \
+         2048-byte data sectors can't reproduce"
+    )]
+    RawCdRequired(&'static str),
+    #[error("{0} is not a disc-based system")]
+    NotADisc(&'static str),
+}
+
+/// Options for a disc dump.
+#[derive(Debug, Clone)]
+pub struct DumpOptions {
+    /// Read attempts per sector before giving up on it.
+    pub retries: u32,
+    /// Custom DAT directory (instead of cache) for verification.
+    pub dat_dir: Option<PathBuf>,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            dat_dir: None,
+        }
+    }
+}
+
+/// Progress information for callbacks.
+#[derive(Debug, Clone)]
+pub enum DumpProgress {
+    /// Copying sectors from the drive.
+    Reading { bytes_done: u64, bytes_total: u64 },
+    /// A sector could not be read and was zero-filled.
+    BadSector { sector: u64 },
+    /// Hashing the finished image.
+    Verifying { bytes_done: u64, bytes_total: u64 },
+}
+
+/// Outcome of a dump.
+#[derive(Debug, Clone)]
+pub struct DumpSummary {
+    pub output: PathBuf,
+    pub bytes_written: u64,
+    /// Sector numbers that could not be read and were zero-filled.
+    pub bad_sectors: Vec<u64>,
+    pub hashes: FileHashes,
+    /// DAT game name the image matched, if any.
+    pub matched_game: Option<String>,
+}
+
+/// Check that the disc in `device` can be dumped to an image the
+/// analyzer's DATs can verify. Cartridge systems and systems that only
+/// shipped on CD are refused without touching the drive; otherwise only the
+/// disc's volume descriptors are read.
+pub fn check_dumpable(analyzer: &dyn RomAnalyzer, device: &Path) -> Result<(), DumpError> {
+    if analyzer.dat_source() != DatSource::Redump {
+        return Err(DumpError::NotADisc(analyzer.platform_name()));
+    }
+    if analyzer.dat_lists_raw_cd_tracks() {
+        return Err(DumpError::RawCdRequired(analyzer.platform_name()));
+    }
+    let mut disc = fs::File::open(device)?;
+    if analyzer.disc_is_raw_cd(&mut disc)? {
+        return Err(DumpError::RawCdRequired(analyzer.platform_name()));
+    }
+    Ok(())
+}
+
+/// Copy a DVD from `device` to `output` and verify it against the
+/// analyzer's DATs. Fails before copying anything if [`check_dumpable`]
+/// does.
+pub fn dump_dvd(
+    device: &Path,
+    output: &Path,
+    analyzer: &dyn RomAnalyzer,
+    options: &DumpOptions,
+    progress: &dyn Fn(DumpProgress),
+) -> Result<DumpSummary, DumpError> {
+    check_dumpable(analyzer, device)?;
+    let bad_sectors = copy_sectors(device, output, options.retries, progress)?;
+    let bytes_written = fs::metadata(output)?.len();
+
+    let mut file = fs::File::open(output)?;
+    let hashes = hasher::compute_crc32_sha1_with_progress(
+        &mut file,
+        analyzer,
+        &|done, total| {
+            progress(DumpProgress::Verifying {
+                bytes_done: done,
+                bytes_total: total,
+            })
+        },
+        Some(output),
+    )?;
+
+    let matched_game = if analyzer.has_dat_support() {
+        let dats = cache::load_dats(
+            analyzer.short_name(),
+            analyzer.dat_names(),
+            analyzer.dat_download_ids(),
+            options.dat_dir.as_deref(),
+            analyzer.dat_source(),
+        )?;
+        let index = DatIndex::from_dats(dats);
+        index
+            .match_by_hash(hashes.data_size, &hashes)
+            .map(|m| index.games[m.game_index].name.clone())
+    } else {
+        None
+    };
+
+    Ok(DumpSummary {
+        output: output.to_path_buf(),
+        bytes_written,
+        bad_sectors,
+        hashes,
+        matched_game,
+    })
+}
+
+/// Copy every sector of `device` into `output`, returning unreadable sectors.
+///
+/// Reads in multi-sector blocks and drops to single sectors (with retries)
+/// when a block fails, so one scratch only costs the sectors it covers.
+pub fn copy_sectors(
+    device: &Path,
+    output: &Path,
+    retries: u32,
+    progress: &dyn Fn(DumpProgress),
+) -> Result<Vec<u64>, DumpError> {
+    let mut input = fs::File::open(device)?;
+    let total = input.seek(SeekFrom::End(0))?;
+    if total == 0 {
+        return Err(DumpError::EmptyDevice(device.to_path_buf()));
+    }
+    input.seek(SeekFrom::Start(0))?;
+
+    let mut out = io::BufWriter::new(fs::File::create(output)?);
+    let mut bad_sectors = Vec::new();
+    let block_size = SECTOR_SIZE * SECTORS_PER_READ;
+    let mut buf = vec![0u8; block_size as usize];
+    let mut offset = 0u64;
+
+    while offset < total {
+        let len = block_size.min(total - offset) as usize;
+        match read_at(&mut input, offset, &mut buf[..len]) {
+            Ok(()) => out.write_all(&buf[..len])?,
+            Err(_) => {
+                // Fall back to sector-by-sector reads for this block
+                let mut sector_offset = offset;
+                while sector_offset < offset + len as u64 {
+                    let sector_len = SECTOR_SIZE.min(total - sector_offset) as usize;
+                    let sector_buf = &mut buf[..sector_len];
+                    let readable = (0..retries.max(1))
+                        .any(|_| read_at(&mut input, sector_offset, sector_buf).is_ok());
+                    if !readable {
+                        let sector = sector_offset / SECTOR_SIZE;
+                        sector_buf.fill(0);
+                        bad_sectors.push(sector);
+                        progress(DumpProgress::BadSector { sector });
+                    }
+                    out.write_all(sector_buf)?;
+                    sector_offset += sector_len as u64;
+                }
+            }
+        }
+        offset += len as u64;
+        progress(DumpProgress::Reading {
+            bytes_done: offset,
+            bytes_total: total,
+        });
+    }
+
+    out.flush()?;
+    Ok(bad_sectors)
+}
+
+fn read_at(input: &mut fs::File, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+    input.seek(SeekFrom::Start(offset))?;
+    input.read_exact(buf)
+}
+
+#[cfg(test)]
+#[path = "tests/dump_tests.rs"]
+mod tests;
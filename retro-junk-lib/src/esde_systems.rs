@@ -0,0 +1,268 @@
+//! ES-DE `es_systems.xml` awareness.
+//!
+//! ES-DE defines each system's ROM folder, extensions, and scraper platform
+//! in `es_systems.xml`: a bundled copy shipped with the application and an
+//! optional `custom_systems/es_systems.xml` whose entries replace bundled
+//! ones with the same name. Reading these lets folder matching follow the
+//! user's actual layout (e.g., a `Super Nintendo` folder for the `snes`
+//! system) and lets frontend output use ES-DE's system names.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use quick_xml::Reader;
+use quick_xml::events::Event;
+
+use retro_junk_core::Platform;
+
+use crate::context::AnalysisContext;
+
+/// One `<system>` entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EsSystem {
+    /// Short name (`<name>`), also used for gamelist and media folders.
+    pub name: String,
+    /// Display name (`<fullname>`).
+    pub fullname: String,
+    /// ROM folder name, taken from the last component of `<path>`.
+    pub folder: Option<String>,
+    /// Extensions from `<extension>`, lowercase without the leading dot.
+    pub extensions: Vec<String>,
+    /// Scraper platform IDs from `<platform>`.
+    pub platforms: Vec<String>,
+}
+
+/// A merged set of ES-DE system definitions.
+#[derive(Debug, Clone, Default)]
+pub struct EsSystems {
+    systems: Vec<EsSystem>,
+}
+
+impl EsSystems {
+    /// Parse the contents of an `es_systems.xml` file.
+    pub fn parse(xml: &str) -> Result<Self, String> {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut systems = Vec::new();
+        let mut current: Option<EsSystem> = None;
+        let mut tag = String::new();
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => {
+                    tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    if tag == "system" {
+                        current = Some(EsSystem::default());
+                    }
+                }
+                Ok(Event::Text(t)) => {
+                    let Some(system) = current.as_mut() else {
+                        continue;
+                    };
+                    let text = t.unescape().map_err(|e| e.to_string())?.trim().to_string();
+                    match tag.as_str() {
+                        "name" => system.name = text,
+                        "fullname" => system.fullname = text,
+                        "path" => system.folder = folder_from_path(&text),
+                        "extension" => system.extensions = parse_extensions(&text),
+                        "platform" => {
+                            system.platforms = text
+                                .split(',')
+                                .map(|p| p.trim().to_lowercase())
+                                .filter(|p| !p.is_empty())
+                                .collect()
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::End(e)) => {
+                    if e.name().as_ref() == b"system"
+                        && let Some(system) = current.take()
+                        && !system.name.is_empty()
+                    {
+                        systems.push(system);
+                    }
+                    tag.clear();
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => {
+                    return Err(format!(
+                        "XML error at position {}: {}",
+                        reader.error_position(),
+                        e
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self { systems })
+    }
+
+    /// Load and parse an `es_systems.xml` file.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let xml = std::fs::read_to_string(path)?;
+        Self::parse(&xml).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })
+    }
+
+    /// Load the bundled and custom system files from ES-DE's standard
+    /// locations. Missing or unreadable files are skipped.
+    pub fn discover() -> Self {
+        let mut merged = Self::default();
+        for path in bundled_paths().into_iter().chain(custom_path()) {
+            if !path.is_file() {
+                continue;
+            }
+            match Self::load(&path) {
+                Ok(systems) => {
+                    log::debug!("Loaded ES-DE systems from {}", path.display());
+                    merged.merge(systems);
+                }
+                Err(e) => log::warn!("Ignoring ES-DE systems file: {}", e),
+            }
+        }
+        merged
+    }
+
+    /// Merge `other` into this set; entries replace existing ones by name,
+    /// as ES-DE does for custom systems.
+    pub fn merge(&mut self, other: Self) {
+        for system in other.systems {
+            match self.systems.iter_mut().find(|s| s.name == system.name) {
+                Some(existing) => *existing = system,
+                None => self.systems.push(system),
+            }
+        }
+    }
+
+    pub fn systems(&self) -> &[EsSystem] {
+        &self.systems
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    /// The system whose ROM folder is `folder_name` (case-insensitive).
+    pub fn by_folder(&self, folder_name: &str) -> Option<&EsSystem> {
+        self.systems.iter().find(|s| {
+            s.folder
+                .as_deref()
+                .is_some_and(|f| f.eq_ignore_ascii_case(folder_name))
+        })
+    }
+
+    /// Register every system's ROM folder as a folder alias in `ctx`.
+    ///
+    /// Systems are resolved to platforms through their name and `<platform>`
+    /// IDs; when that yields several consoles, those sharing an extension
+    /// with the ES-DE entry win. Returns the number of aliases added.
+    pub fn apply_to(&self, ctx: &mut AnalysisContext) -> usize {
+        let mut added = 0;
+        for system in &self.systems {
+            let Some(ref folder) = system.folder else {
+                continue;
+            };
+            let platforms = resolve_platforms(ctx, system);
+            if platforms.is_empty() {
+                continue;
+            }
+            ctx.add_folder_alias(folder, &system.name, &platforms);
+            added += 1;
+        }
+        added
+    }
+}
+
+/// Platforms an ES-DE system corresponds to.
+fn resolve_platforms(ctx: &AnalysisContext, system: &EsSystem) -> Vec<Platform> {
+    let mut candidates: Vec<Platform> = Vec::new();
+    for name in std::iter::once(&system.name).chain(&system.platforms) {
+        for console in ctx.consoles() {
+            if console.analyzer.matches_folder(name)
+                && !candidates.contains(&console.metadata.platform)
+            {
+                candidates.push(console.metadata.platform);
+            }
+        }
+    }
+
+    if candidates.len() > 1 && !system.extensions.is_empty() {
+        let overlapping: Vec<Platform> = candidates
+            .iter()
+            .copied()
+            .filter(|p| {
+                ctx.get_by_platform(*p).is_some_and(|c| {
+                    c.metadata
+                        .extensions
+                        .iter()
+                        .any(|e| system.extensions.contains(&e.to_lowercase()))
+                })
+            })
+            .collect();
+        if !overlapping.is_empty() {
+            return overlapping;
+        }
+    }
+    candidates
+}
+
+/// `%ROMPATH%/snes` or `%ROMPATH%\snes` → `snes`.
+fn folder_from_path(path: &str) -> Option<String> {
+    path.trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+        .filter(|f| !f.is_empty() && !f.starts_with('%'))
+        .map(str::to_string)
+}
+
+/// `.sfc .SFC .smc` → `["sfc", "smc"]`.
+fn parse_extensions(text: &str) -> Vec<String> {
+    let mut exts: Vec<String> = Vec::new();
+    for ext in text.split_whitespace() {
+        let ext = ext.trim_start_matches('.').to_lowercase();
+        if !ext.is_empty() && !exts.contains(&ext) {
+            exts.push(ext);
+        }
+    }
+    exts
+}
+
+/// ES-DE's application data directory (`~/ES-DE`).
+fn appdata_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("ESDE_APPDATA_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    dirs::home_dir().map(|h| h.join("ES-DE"))
+}
+
+fn custom_path() -> Option<PathBuf> {
+    appdata_dir().map(|d| d.join("custom_systems").join("es_systems.xml"))
+}
+
+fn bundled_paths() -> Vec<PathBuf> {
+    if cfg!(target_os = "macos") {
+        vec![PathBuf::from(
+            "/Applications/ES-DE.app/Contents/Resources/resources/systems/macos/es_systems.xml",
+        )]
+    } else if cfg!(target_os = "windows") {
+        // Portable installs keep resources next to the executable; there's
+        // no fixed location to probe.
+        Vec::new()
+    } else {
+        ["/usr/share", "/usr/local/share"]
+            .iter()
+            .map(|p| Path::new(p).join("es-de/resources/systems/linux/es_systems.xml"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/esde_systems_tests.rs"]
+mod tests;
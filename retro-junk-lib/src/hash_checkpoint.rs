@@ -0,0 +1,137 @@
+//! Persisted hash state for resuming interrupted hashes of huge images.
+//!
+//! Hashing a 25 GB Wii U or PS3 image takes long enough that a cancelled
+//! rename shouldn't throw the work away. While streaming a large file the
+//! hasher periodically saves its CRC32 and SHA1 state to a checkpoint in the
+//! cache directory; the next run for the same unchanged file seeks to the
+//! saved offset and continues from there.
+//!
+//! The saved state is a [`HashState`] from the core hasher, which takes
+//! SHA1 state from the `sha1` crate's own serialization.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use retro_junk_core::hash::{hex, unhex};
+use retro_junk_core::{HashAlgorithms, HashState, StreamHasher};
+use serde::{Deserialize, Serialize};
+
+/// Files smaller than this are hashed without checkpoints.
+pub const DEFAULT_MIN_SIZE: u64 = 1024 * 1024 * 1024; // 1 GiB
+
+/// Bytes hashed between checkpoint writes.
+pub const DEFAULT_INTERVAL: u64 = 256 * 1024 * 1024; // 256 MiB
+
+/// Saved progress for one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashCheckpoint {
+    pub path: PathBuf,
+    pub file_size: u64,
+    /// File modification time (nanoseconds since the Unix epoch).
+    pub modified: u128,
+    /// Header bytes skipped before hashing.
+    pub skip: u64,
+    /// Data bytes hashed so far (after `skip`).
+    pub processed: u64,
+    pub crc32: u32,
+    /// Serialized SHA1 hasher state (hex).
+    pub sha1_state: Option<String>,
+}
+
+impl HashCheckpoint {
+    /// The hasher state saved in this checkpoint.
+    pub fn state(&self) -> Option<HashState> {
+        let sha1 = match &self.sha1_state {
+            Some(state) => Some(unhex(state)?),
+            None => None,
+        };
+        Some(HashState {
+            len: self.processed,
+            crc32: self.crc32,
+            sha1,
+        })
+    }
+}
+
+/// Where checkpoints live and when they are written.
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    pub dir: PathBuf,
+    /// Only files at least this large are checkpointed.
+    pub min_size: u64,
+    /// Bytes hashed between checkpoint writes.
+    pub interval: u64,
+}
+
+impl CheckpointStore {
+    /// The default store in `~/.cache/retro-junk/hash-checkpoints`.
+    pub fn default_store() -> Option<Self> {
+        Some(Self {
+            dir: dirs::cache_dir()?
+                .join("retro-junk")
+                .join("hash-checkpoints"),
+            min_size: DEFAULT_MIN_SIZE,
+            interval: DEFAULT_INTERVAL,
+        })
+    }
+
+    /// Load the saved hash state for `path` if it still describes the file
+    /// on disk.
+    pub fn load(&self, path: &Path, skip: u64) -> Option<HashState> {
+        let (file_size, modified) = file_identity(path).ok()?;
+        let contents = std::fs::read_to_string(self.checkpoint_path(path)).ok()?;
+        let checkpoint: HashCheckpoint = serde_json::from_str(&contents).ok()?;
+        let valid = checkpoint.path == path
+            && checkpoint.file_size == file_size
+            && checkpoint.modified == modified
+            && checkpoint.skip == skip
+            && checkpoint.processed <= file_size.saturating_sub(skip);
+        if !valid {
+            return None;
+        }
+        checkpoint.state()
+    }
+
+    /// Save a checkpoint for `path`, `skip` header bytes in.
+    pub fn save(&self, path: &Path, skip: u64, state: &HashState) -> io::Result<()> {
+        let (file_size, modified) = file_identity(path)?;
+        let checkpoint = HashCheckpoint {
+            path: path.to_path_buf(),
+            file_size,
+            modified,
+            skip,
+            processed: state.len,
+            crc32: state.crc32,
+            sha1_state: state.sha1.as_deref().map(hex),
+        };
+        std::fs::create_dir_all(&self.dir)?;
+        let dest = self.checkpoint_path(path);
+        let tmp = dest.with_extension("json.tmp");
+        let json = serde_json::to_string(&checkpoint).map_err(io::Error::other)?;
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &dest)
+    }
+
+    /// Remove the checkpoint for `path`, if any.
+    pub fn clear(&self, path: &Path) {
+        let _ = std::fs::remove_file(self.checkpoint_path(path));
+    }
+
+    fn checkpoint_path(&self, path: &Path) -> PathBuf {
+        let mut key = StreamHasher::new(HashAlgorithms::Crc32Sha1);
+        key.update(path.to_string_lossy().as_bytes());
+        let key = key.finish().sha1.expect("SHA1 was requested");
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+fn file_identity(path: &Path) -> io::Result<(u64, u128)> {
+    let meta = std::fs::metadata(path)?;
+    let modified = meta
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    Ok((meta.len(), modified))
+}
@@ -1,14 +1,26 @@
 use std::io::SeekFrom;
 use std::path::Path;
 
-use sha1::Digest;
-
-use retro_junk_core::{HashAlgorithms, ReadSeek, RomAnalyzer};
+use retro_junk_core::hash::read_full;
+use retro_junk_core::{HashAlgorithms, ReadSeek, RomAnalyzer, StreamHasher};
 use retro_junk_dat::error::DatError;
+
+use crate::hash_checkpoint::CheckpointStore;
 pub use retro_junk_dat::matcher::FileHashes;
 
 const CHUNK_SIZE: usize = 64 * 1024; // 64 KB
 
+/// Streams at least this large hash SHA1 and MD5 on worker threads. Below
+/// it, spawning threads costs more than it saves.
+const PARALLEL_MIN_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Chunk size when feeding worker threads, large enough that channel
+/// overhead is negligible next to the hashing itself.
+const PARALLEL_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Per-chunk transform applied before hashing (e.g. N64 byte-order swaps).
+type Normalizer = Option<Box<dyn FnMut(&mut [u8])>>;
+
 /// Try container hashes first; if the analyzer handles the format internally,
 /// return the precomputed hashes. Otherwise return None and caller proceeds
 /// with streaming.
@@ -24,11 +36,11 @@ fn try_container_hashes(
 }
 
 /// Set up the reader for streaming: determine skip bytes, create normalizer,
-/// seek past header. Returns (data_size, normalizer).
+/// seek past header. Returns (skip, data_size, normalizer).
 fn setup_stream(
     reader: &mut dyn ReadSeek,
     analyzer: &dyn RomAnalyzer,
-) -> Result<(u64, Option<Box<dyn FnMut(&mut [u8])>>), DatError> {
+) -> Result<(u64, u64, Normalizer), DatError> {
     let file_size = reader.seek(SeekFrom::End(0))?;
     let skip = analyzer
         .dat_header_size(reader, file_size)
@@ -37,18 +49,23 @@ fn setup_stream(
         .dat_chunk_normalizer(reader, skip)
         .map_err(|e| DatError::cache(e.to_string()))?;
     reader.seek(SeekFrom::Start(skip))?;
-    Ok((file_size - skip, normalizer))
+    Ok((skip, file_size - skip, normalizer))
 }
 
-/// Read chunks from the reader, normalizing each, and pass to the callback.
+/// Read `chunk_size` chunks from the reader, normalizing each, and pass
+/// them to the callback.
+///
+/// Chunks are filled completely (except the last), so normalizers that work
+/// on fixed-size blocks always see whole blocks.
 fn stream_chunks(
     reader: &mut dyn ReadSeek,
-    normalizer: &mut Option<Box<dyn FnMut(&mut [u8])>>,
+    normalizer: &mut Normalizer,
+    chunk_size: usize,
     mut on_chunk: impl FnMut(&[u8]),
 ) -> Result<(), DatError> {
-    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut buf = vec![0u8; chunk_size];
     loop {
-        let n = reader.read(&mut buf)?;
+        let n = read_full(reader, &mut buf)?;
         if n == 0 {
             break;
         }
@@ -62,50 +79,78 @@ fn stream_chunks(
 
 /// Unified internal hash engine. Computes whichever combination of CRC32/SHA1/MD5
 /// is requested by `algorithms`, optionally reporting progress via `on_progress`.
+///
+/// The file is read once. On large files SHA1 and MD5 each run on their own
+/// thread, so `All` takes about as long as SHA1 alone rather than the sum of
+/// every digest.
+///
+/// With `checkpoints`, a large file's hash state is saved as it streams and
+/// an earlier save for the same unchanged file is resumed. MD5 state can't
+/// be saved and normalizers can't be resumed mid-stream, so those hashes
+/// always start from the beginning.
 fn compute_hashes_internal(
     reader: &mut dyn ReadSeek,
     analyzer: &dyn RomAnalyzer,
     algorithms: HashAlgorithms,
     on_progress: Option<&dyn Fn(u64, u64)>,
     file_path: Option<&Path>,
+    checkpoints: Option<&CheckpointStore>,
 ) -> Result<FileHashes, DatError> {
     if let Some(hashes) = try_container_hashes(reader, analyzer, algorithms, file_path)? {
         return Ok(hashes);
     }
 
-    let (data_size, mut normalizer) = setup_stream(reader, analyzer)?;
-    let mut crc = crc32fast::Hasher::new();
-    let mut sha: Option<sha1::Sha1> = if algorithms.sha1() {
-        Some(sha1::Sha1::new())
-    } else {
-        None
-    };
-    let mut md5_ctx: Option<md5::Context> = if algorithms.md5() {
-        Some(md5::Context::new())
+    let (skip, data_size, mut normalizer) = setup_stream(reader, analyzer)?;
+    let (mut hasher, chunk_size) = if data_size >= PARALLEL_MIN_SIZE && algorithms.sha1() {
+        (StreamHasher::threaded(algorithms), PARALLEL_CHUNK_SIZE)
     } else {
-        None
+        (StreamHasher::new(algorithms), CHUNK_SIZE)
     };
 
-    let mut processed: u64 = 0;
-    stream_chunks(reader, &mut normalizer, |chunk| {
-        crc.update(chunk);
-        if let Some(ref mut s) = sha {
-            s.update(chunk);
+    let checkpoint = match (checkpoints, file_path) {
+        (Some(store), Some(path))
+            if normalizer.is_none() && !algorithms.md5() && data_size >= store.min_size =>
+        {
+            let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            Some((store, path))
         }
-        if let Some(ref mut m) = md5_ctx {
-            m.consume(chunk);
-        }
-        processed += chunk.len() as u64;
+        _ => None,
+    };
+    if let Some((store, path)) = &checkpoint
+        && let Some(state) = store.load(path, skip)
+        && hasher.resume(&state)
+    {
+        log::debug!("Resuming hash of {} at byte {}", path.display(), state.len);
+        reader.seek(SeekFrom::Start(skip + state.len))?;
+    }
+
+    let mut next_checkpoint = checkpoint
+        .as_ref()
+        .map(|(store, _)| hasher.len() + store.interval);
+    stream_chunks(reader, &mut normalizer, chunk_size, |chunk| {
+        hasher.update(chunk);
         if let Some(cb) = on_progress {
-            cb(processed, data_size);
+            cb(hasher.len(), data_size);
+        }
+        if let (Some((store, path)), Some(next)) = (&checkpoint, &mut next_checkpoint)
+            && hasher.len() >= *next
+            && hasher.len() < data_size
+        {
+            if let Some(state) = hasher.state()
+                && let Err(e) = store.save(path, skip, &state)
+            {
+                log::warn!("Failed to save hash checkpoint: {}", e);
+            }
+            *next = hasher.len() + store.interval;
         }
     })?;
+    if let Some((store, path)) = &checkpoint {
+        store.clear(path);
+    }
 
     Ok(FileHashes {
-        crc32: format!("{:08x}", crc.finalize()),
-        sha1: sha.map(|s| format!("{:x}", s.finalize())),
-        md5: md5_ctx.map(|m| format!("{:x}", m.compute())),
         data_size,
+        ..hasher.finish()
     })
 }
 
@@ -115,7 +160,14 @@ pub fn compute_crc32_sha1(
     analyzer: &dyn RomAnalyzer,
     file_path: Option<&Path>,
 ) -> Result<FileHashes, DatError> {
-    compute_hashes_internal(reader, analyzer, HashAlgorithms::Crc32Sha1, None, file_path)
+    compute_hashes_internal(
+        reader,
+        analyzer,
+        HashAlgorithms::Crc32Sha1,
+        None,
+        file_path,
+        None,
+    )
 }
 
 /// Compute CRC32 and SHA1 with a progress callback.
@@ -132,6 +184,45 @@ pub fn compute_crc32_sha1_with_progress(
         HashAlgorithms::Crc32Sha1,
         Some(progress),
         file_path,
+        None,
+    )
+}
+
+/// Compute CRC32 and SHA1 with progress, resuming from a saved checkpoint.
+///
+/// Large files (see [`crate::hash_checkpoint::DEFAULT_MIN_SIZE`]) periodically save
+/// their hash state, so an interrupted hash of the same unchanged file picks
+/// up where it stopped. Container formats and analyzers with a chunk
+/// normalizer are hashed normally.
+pub fn compute_crc32_sha1_resumable(
+    reader: &mut dyn ReadSeek,
+    analyzer: &dyn RomAnalyzer,
+    progress: &dyn Fn(u64, u64),
+    file_path: &Path,
+) -> Result<FileHashes, DatError> {
+    match CheckpointStore::default_store() {
+        Some(store) => {
+            compute_crc32_sha1_checkpointed(reader, analyzer, progress, file_path, &store)
+        }
+        None => compute_crc32_sha1_with_progress(reader, analyzer, progress, Some(file_path)),
+    }
+}
+
+/// [`compute_crc32_sha1_resumable`] against an explicit checkpoint store.
+pub fn compute_crc32_sha1_checkpointed(
+    reader: &mut dyn ReadSeek,
+    analyzer: &dyn RomAnalyzer,
+    progress: &dyn Fn(u64, u64),
+    file_path: &Path,
+    store: &CheckpointStore,
+) -> Result<FileHashes, DatError> {
+    compute_hashes_internal(
+        reader,
+        analyzer,
+        HashAlgorithms::Crc32Sha1,
+        Some(progress),
+        Some(file_path),
+        Some(store),
     )
 }
 
@@ -142,7 +233,36 @@ pub fn compute_all_hashes(
     analyzer: &dyn RomAnalyzer,
     file_path: Option<&Path>,
 ) -> Result<FileHashes, DatError> {
-    compute_hashes_internal(reader, analyzer, HashAlgorithms::All, None, file_path)
+    compute_hashes_internal(reader, analyzer, HashAlgorithms::All, None, file_path, None)
+}
+
+/// Hashes of a whole file as stored, including SHA-256.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawFileHashes {
+    pub crc32: String,
+    pub sha1: String,
+    pub md5: String,
+    pub sha256: String,
+    pub size: u64,
+}
+
+/// Compute CRC32, SHA1, MD5 and SHA-256 of a whole file in a single pass,
+/// with no header skipping or normalization. Used to check files against
+/// `.smdb` listings, which hash files exactly as they are stored.
+pub fn compute_raw_hashes(reader: &mut dyn ReadSeek) -> Result<RawFileHashes, DatError> {
+    reader.seek(SeekFrom::Start(0))?;
+
+    let mut hasher = StreamHasher::new(HashAlgorithms::AllWithSha256);
+    stream_chunks(reader, &mut None, CHUNK_SIZE, |chunk| hasher.update(chunk))?;
+    let hashes = hasher.finish();
+
+    Ok(RawFileHashes {
+        crc32: hashes.crc32,
+        sha1: hashes.sha1.unwrap_or_default(),
+        md5: hashes.md5.unwrap_or_default(),
+        sha256: hashes.sha256.unwrap_or_default(),
+        size: hashes.data_size,
+    })
 }
 
 /// Specification for padding bytes to prepend/append when computing hashes.
@@ -168,35 +288,29 @@ pub fn compute_crc32_sha1_with_padding(
     analyzer: &dyn RomAnalyzer,
     padding: &PaddingSpec,
 ) -> Result<FileHashes, DatError> {
-    let (file_data_size, mut normalizer) = setup_stream(reader, analyzer)?;
+    let (_, file_data_size, mut normalizer) = setup_stream(reader, analyzer)?;
     let total_data_size = padding.prepend_size + file_data_size + padding.append_size;
 
-    let mut crc = crc32fast::Hasher::new();
-    let mut sha = sha1::Sha1::new();
+    let mut hasher = StreamHasher::new(HashAlgorithms::Crc32Sha1);
 
     // Phase 1: prepend padding (not normalized)
     stream_padding(padding.prepend_size, padding.fill_byte, |chunk| {
-        crc.update(chunk);
-        sha.update(chunk);
+        hasher.update(chunk)
     });
 
     // Phase 2: file data (normalized if applicable)
-    stream_chunks(reader, &mut normalizer, |chunk| {
-        crc.update(chunk);
-        sha.update(chunk);
+    stream_chunks(reader, &mut normalizer, CHUNK_SIZE, |chunk| {
+        hasher.update(chunk)
     })?;
 
     // Phase 3: append padding (not normalized)
     stream_padding(padding.append_size, padding.fill_byte, |chunk| {
-        crc.update(chunk);
-        sha.update(chunk);
+        hasher.update(chunk)
     });
 
     Ok(FileHashes {
-        crc32: format!("{:08x}", crc.finalize()),
-        sha1: Some(format!("{:x}", sha.finalize())),
-        md5: None,
         data_size: total_data_size,
+        ..hasher.finish()
     })
 }
 
@@ -3,24 +3,49 @@
 pub use retro_junk_core::*;
 
 // Modules that still live in retro-junk-lib:
+pub mod analysis_output;
+pub mod archive;
 pub mod async_util;
+pub mod chd_verify;
 pub mod context;
+pub mod custom_platforms;
+pub mod dat_verify;
+pub mod device;
 pub mod display;
+pub mod dump;
+pub mod esde_systems;
+pub mod hash_checkpoint;
 pub mod hasher;
+pub mod metrics;
+pub mod notify;
+pub mod one_g1r;
+pub mod remote;
 pub mod rename;
+pub mod rename_export;
+pub mod rename_overrides;
 pub mod repair;
+pub mod report;
+pub mod sanitize;
 pub mod scanner;
 pub mod settings;
+pub mod sidecar;
+pub mod size_audit;
+pub mod smdb;
+pub mod split;
+pub mod trash;
 pub mod util;
 
 // Re-export context items at crate root for backwards compatibility.
-pub use context::{AnalysisContext, Console, ConsoleFolder, FolderScanResult, RegisteredConsole};
+pub use context::{
+    AnalysisContext, AnalyzePathError, Console, ConsoleFolder, FolderScanResult, RegisteredConsole,
+};
 
 /// Create an `AnalysisContext` with all built-in console analyzers registered.
 ///
-/// Registers all 25 analyzers: NES, SNES, N64, GameCube, Wii, Wii U, GB, GBA,
+/// Registers all 33 analyzers: NES, SNES, N64, GameCube, Wii, Wii U, GB, GBA,
 /// DS, 3DS, PS1, PS2, PS3, PSP, Vita, SG-1000, Master System, Genesis, Sega CD,
-/// 32X, Saturn, Dreamcast, Game Gear, Xbox, Xbox 360.
+/// 32X, Saturn, Dreamcast, Game Gear, Xbox, Xbox 360, Atari 2600, 5200, 7800,
+/// Lynx, Jaguar, PC Engine, SuperGrafx, PC Engine CD.
 pub fn create_default_context() -> AnalysisContext {
     let mut ctx = AnalysisContext::new();
 
@@ -57,5 +82,17 @@ pub fn create_default_context() -> AnalysisContext {
     ctx.register(retro_junk_microsoft::XboxAnalyzer);
     ctx.register(retro_junk_microsoft::Xbox360Analyzer);
 
+    // Atari
+    ctx.register(retro_junk_atari::Atari2600Analyzer);
+    ctx.register(retro_junk_atari::Atari5200Analyzer);
+    ctx.register(retro_junk_atari::Atari7800Analyzer);
+    ctx.register(retro_junk_atari::LynxAnalyzer);
+    ctx.register(retro_junk_atari::JaguarAnalyzer);
+
+    // NEC
+    ctx.register(retro_junk_nec::PcEngineAnalyzer);
+    ctx.register(retro_junk_nec::SuperGrafxAnalyzer);
+    ctx.register(retro_junk_nec::PcEngineCdAnalyzer);
+
     ctx
 }
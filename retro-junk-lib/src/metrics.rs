@@ -0,0 +1,189 @@
+//! Prometheus-style metrics for long-running modes.
+//!
+//! [`Metrics`] holds counters the watcher bumps as it works, and the
+//! ScreenScraper client's view of the daily API quota; [`serve`] answers
+//! `GET /metrics` with them in the Prometheus text format, so an always-on
+//! setup can be graphed and alerted on without reading logs.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Most bytes read from one request, request line and headers together.
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+/// Most header lines read from one request.
+const MAX_HEADERS: usize = 64;
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Counters exposed on the metrics endpoint.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    pub files_analyzed: AtomicU64,
+    pub hash_bytes: AtomicU64,
+    pub files_added: AtomicU64,
+    /// Files that matched nothing in the DAT.
+    pub unmatched_files: AtomicU64,
+    /// Files that couldn't be read or imported.
+    pub failed_files: AtomicU64,
+    /// Unix time of the last completed poll (0 before the first).
+    pub last_poll: AtomicU64,
+    /// ScreenScraper API requests used today, as the server last reported.
+    pub api_requests: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Count a file that was analyzed and hashed.
+    pub fn record_analyzed(&self, bytes: u64) {
+        self.files_analyzed.fetch_add(1, Ordering::Relaxed);
+        self.hash_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn record_added(&self) {
+        self.files_added.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_unmatched(&self) {
+        self.unmatched_files.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_failed(&self) {
+        self.failed_files.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record that a poll finished now.
+    pub fn mark_poll(&self) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.last_poll.store(now, Ordering::Relaxed);
+    }
+
+    /// Record the API requests used today, from the scraper's quota tracking.
+    pub fn set_api_requests(&self, used: u64) {
+        self.api_requests.store(used, Ordering::Relaxed);
+    }
+
+    /// The counters in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let metrics: [(&str, &str, &str, &AtomicU64); 7] = [
+            (
+                "retro_junk_files_analyzed_total",
+                "counter",
+                "Files analyzed and hashed.",
+                &self.files_analyzed,
+            ),
+            (
+                "retro_junk_hash_bytes_total",
+                "counter",
+                "Bytes read while hashing files.",
+                &self.hash_bytes,
+            ),
+            (
+                "retro_junk_files_added_total",
+                "counter",
+                "Files verified and added to the collection.",
+                &self.files_added,
+            ),
+            (
+                "retro_junk_unmatched_files_total",
+                "counter",
+                "Files that matched nothing in the DAT.",
+                &self.unmatched_files,
+            ),
+            (
+                "retro_junk_failed_files_total",
+                "counter",
+                "Files that could not be read or imported.",
+                &self.failed_files,
+            ),
+            (
+                "retro_junk_last_poll_timestamp_seconds",
+                "gauge",
+                "Unix time of the last completed poll.",
+                &self.last_poll,
+            ),
+            (
+                "retro_junk_api_requests_used",
+                "gauge",
+                "ScreenScraper API requests used today.",
+                &self.api_requests,
+            ),
+        ];
+        let mut out = String::new();
+        for (name, kind, help, value) in metrics {
+            out.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {}\n",
+                value.load(Ordering::Relaxed)
+            ));
+        }
+        out
+    }
+}
+
+/// Serve `metrics` at `http://<addr>/metrics` on a background thread.
+/// Each connection is answered on its own thread, so a slow client doesn't
+/// hold up the scraper. Returns the bound address (useful with port 0).
+pub fn serve(addr: impl ToSocketAddrs, metrics: Arc<Metrics>) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local = listener.local_addr()?;
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let metrics = Arc::clone(&metrics);
+            std::thread::spawn(move || {
+                if let Err(e) = respond(stream, &metrics) {
+                    log::debug!("Metrics request failed: {}", e);
+                }
+            });
+        }
+    });
+    Ok(local)
+}
+
+/// Answer one HTTP request: the metrics for `GET /metrics`, 404 otherwise.
+fn respond(stream: TcpStream, metrics: &Metrics) -> io::Result<()> {
+    // A client that never finishes its request, or sends an endless one,
+    // mustn't keep its thread alive
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers so the client sees a clean close
+    let mut line = String::new();
+    for _ in 0..MAX_HEADERS {
+        line.clear();
+        if reader.read_line(&mut line)? <= 2 {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            metrics.render(),
+        ),
+        _ => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+#[path = "tests/metrics_tests.rs"]
+mod tests;
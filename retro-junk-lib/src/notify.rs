@@ -0,0 +1,201 @@
+//! Completion notifications for unattended runs.
+//!
+//! After a scheduled or watching run, a [`RunSummary`] (counts plus the
+//! files that need attention) is POSTed to a URL, so problems show up on a
+//! phone instead of in a log nobody reads. Three payloads are supported:
+//!
+//! - `webhook`: the summary as JSON, for home automation or custom scripts
+//! - `ntfy`: plain text with `Title` and `Tags` headers, for an ntfy topic
+//! - `discord`: `{"content": "..."}`, for Discord (and compatible) webhooks
+
+use serde::Serialize;
+use thiserror::Error;
+
+/// Problems listed by name in text notifications; the rest are counted.
+pub const MAX_LISTED_PROBLEMS: usize = 20;
+
+/// Discord rejects messages longer than this.
+const DISCORD_MAX_CHARS: usize = 2000;
+
+#[derive(Debug, Error)]
+pub enum NotifyError {
+    #[error("Notification failed: {0}")]
+    Http(String),
+}
+
+retro_junk_core::named_enum! {
+    /// Payload shape for a notification URL.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum NotifyFormat("notification format") {
+        /// The [`RunSummary`] as JSON.
+        #[default]
+        Webhook = "webhook" | "json",
+        /// Plain text for an ntfy topic URL.
+        Ntfy = "ntfy",
+        /// A Discord webhook message.
+        Discord = "discord",
+    }
+}
+
+/// Where notifications go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotifyTarget {
+    pub url: String,
+    pub format: NotifyFormat,
+}
+
+/// One named count in a [`RunSummary`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SummaryCount {
+    pub name: String,
+    pub value: u64,
+}
+
+/// What a run did, as sent in a notification.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunSummary {
+    /// The command that ran (e.g. "analyze", "scrape").
+    pub command: String,
+    /// RFC 3339 start and end times.
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub counts: Vec<SummaryCount>,
+    /// Files that need attention: unmatched, corrupt, failed to verify.
+    pub problems: Vec<String>,
+}
+
+impl RunSummary {
+    /// A summary of `command`, started now.
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+            counts: Vec::new(),
+            problems: Vec::new(),
+        }
+    }
+
+    /// Add `value` to the count called `name`, creating it if needed.
+    pub fn count(&mut self, name: &str, value: u64) {
+        match self.counts.iter_mut().find(|c| c.name == name) {
+            Some(count) => count.value += value,
+            None => self.counts.push(SummaryCount {
+                name: name.to_string(),
+                value,
+            }),
+        }
+    }
+
+    pub fn problem(&mut self, problem: impl Into<String>) {
+        self.problems.push(problem.into());
+    }
+
+    /// Mark the run as finished now.
+    pub fn finish(&mut self) {
+        self.finished_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+
+    pub fn title(&self) -> String {
+        match self.problems.len() {
+            0 => format!("retro-junk {}: done", self.command),
+            1 => format!("retro-junk {}: 1 problem", self.command),
+            n => format!("retro-junk {}: {} problems", self.command, n),
+        }
+    }
+
+    /// The counts, then up to [`MAX_LISTED_PROBLEMS`] problems, one per line.
+    pub fn text(&self) -> String {
+        let mut lines: Vec<String> = self
+            .counts
+            .iter()
+            .map(|c| format!("{}: {}", c.name, c.value))
+            .collect();
+        if !self.problems.is_empty() {
+            lines.push(String::new());
+            lines.extend(
+                self.problems
+                    .iter()
+                    .take(MAX_LISTED_PROBLEMS)
+                    .map(|p| format!("- {}", p)),
+            );
+            if self.problems.len() > MAX_LISTED_PROBLEMS {
+                lines.push(format!(
+                    "...and {} more",
+                    self.problems.len() - MAX_LISTED_PROBLEMS
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// An HTTP POST ready to send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotifyRequest {
+    pub content_type: &'static str,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+/// Build the POST for `summary` in the target's format.
+pub fn build_request(format: NotifyFormat, summary: &RunSummary) -> NotifyRequest {
+    match format {
+        NotifyFormat::Webhook => NotifyRequest {
+            content_type: "application/json",
+            headers: Vec::new(),
+            body: serde_json::to_string(summary).unwrap_or_default(),
+        },
+        NotifyFormat::Ntfy => {
+            let tags = if summary.problems.is_empty() {
+                "white_check_mark"
+            } else {
+                "warning"
+            };
+            NotifyRequest {
+                content_type: "text/plain; charset=utf-8",
+                headers: vec![("Title", summary.title()), ("Tags", tags.to_string())],
+                body: summary.text(),
+            }
+        }
+        NotifyFormat::Discord => {
+            let mut content = format!("**{}**\n{}", summary.title(), summary.text());
+            if content.chars().count() > DISCORD_MAX_CHARS {
+                content = content.chars().take(DISCORD_MAX_CHARS - 1).collect();
+                content.push('\u{2026}');
+            }
+            NotifyRequest {
+                content_type: "application/json",
+                headers: Vec::new(),
+                body: serde_json::json!({ "content": content }).to_string(),
+            }
+        }
+    }
+}
+
+/// Send `summary` to `target`.
+pub fn send(target: &NotifyTarget, summary: &RunSummary) -> Result<(), NotifyError> {
+    let request = build_request(target.format, summary);
+    let mut builder = reqwest::blocking::Client::new()
+        .post(&target.url)
+        .header(reqwest::header::CONTENT_TYPE, request.content_type)
+        .body(request.body);
+    for (name, value) in request.headers {
+        builder = builder.header(name, value);
+    }
+    let response = builder
+        .send()
+        .map_err(|e| NotifyError::Http(e.to_string()))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(NotifyError::Http(format!(
+            "HTTP {} from {}",
+            status, target.url
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/notify_tests.rs"]
+mod tests;
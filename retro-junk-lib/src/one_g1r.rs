@@ -0,0 +1,193 @@
+//! One game, one ROM pruning of a console folder.
+//!
+//! Groups a folder's games by their (DAT) names with
+//! [`retro_junk_dat::one_g1r`] and lists, for each game with several
+//! releases, the one to keep and the ones to drop. Dropped entries can be
+//! moved into a [`DUPLICATES_DIR`] subfolder, keeping their relative paths,
+//! so nothing is deleted and the scanner no longer sees them.
+//!
+//! Games are grouped by file name, so run `rename` first: files that don't
+//! follow the No-Intro naming convention are grouped by whatever title
+//! their names parse to.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use retro_junk_core::RomAnalyzer;
+use retro_junk_dat::cache;
+use retro_junk_dat::error::DatError;
+use retro_junk_dat::matcher::DatIndex;
+use retro_junk_dat::one_g1r::{self, Preferences};
+
+use crate::scanner::{GameEntry, GroupedEntry, ScanOptions, SymlinkPolicy};
+use crate::trash::rename_no_clobber;
+use crate::util::long_path;
+
+/// Subfolder of a console folder that dropped releases are moved into.
+pub const DUPLICATES_DIR: &str = "_duplicates";
+
+/// Options controlling 1G1R selection.
+#[derive(Debug, Clone, Default)]
+pub struct OneG1rOptions {
+    pub preferences: Preferences,
+    pub symlinks: SymlinkPolicy,
+}
+
+/// The releases of one game found in a folder.
+#[derive(Debug, Clone)]
+pub struct DuplicateSet {
+    pub keep: GroupedEntry,
+    /// Other releases, best first.
+    pub drop: Vec<GroupedEntry>,
+}
+
+/// 1G1R selection for one console folder.
+#[derive(Debug, Clone, Default)]
+pub struct FolderPlan {
+    /// Whether DAT parent/clone data was used for grouping.
+    pub used_dat: bool,
+    /// Games with more than one release, by group key.
+    pub sets: Vec<DuplicateSet>,
+}
+
+impl FolderPlan {
+    /// Number of entries that would be dropped.
+    pub fn duplicate_count(&self) -> usize {
+        self.sets.iter().map(|s| s.drop.len()).sum()
+    }
+}
+
+/// Load the platform's DATs for parent/clone data, or `None` for
+/// platforms without DAT support.
+pub fn load_index(
+    analyzer: &dyn RomAnalyzer,
+    dat_dir: Option<&Path>,
+) -> Result<Option<DatIndex>, DatError> {
+    if !analyzer.has_dat_support() {
+        return Ok(None);
+    }
+    let dats = cache::load_dats(
+        analyzer.short_name(),
+        analyzer.dat_names(),
+        analyzer.dat_download_ids(),
+        dat_dir,
+        analyzer.dat_source(),
+    )?;
+    Ok(Some(DatIndex::from_dats(dats)))
+}
+
+/// Like [`load_index`], but only from the DAT cache: never downloads, so
+/// callers in the middle of other network work don't block on a fetch.
+/// `None` when the platform has no DAT support or its DATs aren't cached.
+pub fn load_cached_index(analyzer: &dyn RomAnalyzer) -> Option<DatIndex> {
+    if !analyzer.has_dat_support() {
+        return None;
+    }
+    match cache::load_cached_dats(analyzer.short_name(), analyzer.dat_names().len()) {
+        Ok(dats) => dats.map(DatIndex::from_dats),
+        Err(e) => {
+            log::debug!(
+                "Couldn't load cached DATs for {}: {}",
+                analyzer.short_name(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Choose one release per game among the entries in `folder`.
+pub fn plan_folder(
+    folder: &Path,
+    analyzer: &dyn RomAnalyzer,
+    index: Option<&DatIndex>,
+    options: &OneG1rOptions,
+) -> std::io::Result<FolderPlan> {
+    let extensions = crate::scanner::extension_set(analyzer.file_extensions());
+    let scan_options = ScanOptions::new().symlinks(options.symlinks).archives(true);
+    let entries = crate::scanner::scan_grouped_entries(folder, &extensions, &scan_options)?;
+
+    let names: Vec<&str> = entries.iter().map(entry_name).collect();
+    let sets = one_g1r::select(&names, index, &options.preferences)
+        .into_iter()
+        .filter(|group| !group.duplicates().is_empty())
+        .map(|group| DuplicateSet {
+            keep: entries[group.preferred()].clone(),
+            drop: group
+                .duplicates()
+                .iter()
+                .map(|&i| entries[i].clone())
+                .collect(),
+        })
+        .collect();
+    Ok(FolderPlan {
+        used_dat: index.is_some(),
+        sets,
+    })
+}
+
+/// The name an entry is grouped by: its file stem, or its `.m3u` folder
+/// name without the extension.
+fn entry_name(entry: &GroupedEntry) -> &str {
+    let stem = entry.entry.rom_stem();
+    match entry.entry {
+        GameEntry::MultiDisc { .. } => stem.strip_suffix(".m3u").unwrap_or(stem),
+        GameEntry::SingleFile(_) => stem,
+    }
+}
+
+/// Result of moving a plan's dropped entries.
+#[derive(Debug, Default)]
+pub struct MoveSummary {
+    /// Files and `.m3u` folders moved.
+    pub moved: usize,
+    /// Paths that couldn't be moved, with the reason.
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+/// Move every dropped entry of `plan` (with its companions) into
+/// `folder`/[`DUPLICATES_DIR`]. Existing files there are never overwritten.
+pub fn move_duplicates(folder: &Path, plan: &FolderPlan) -> MoveSummary {
+    let target_root = folder.join(DUPLICATES_DIR);
+    let mut summary = MoveSummary::default();
+    for entry in plan.sets.iter().flat_map(|s| &s.drop) {
+        let paths: Vec<PathBuf> = match &entry.entry {
+            GameEntry::SingleFile(_) => entry.files_with_companions().cloned().collect(),
+            GameEntry::MultiDisc { files, .. } => files
+                .first()
+                .and_then(|f| f.parent())
+                .map(Path::to_path_buf)
+                .into_iter()
+                .chain(entry.companions.iter().cloned())
+                .collect(),
+        };
+        for path in paths {
+            match move_into(folder, &path, &target_root) {
+                Ok(()) => summary.moved += 1,
+                Err(e) => summary.errors.push((path, e.to_string())),
+            }
+        }
+    }
+    summary
+}
+
+/// Move `path` to the same relative location under `target_root`, failing
+/// with `AlreadyExists` if something is already there.
+fn move_into(folder: &Path, path: &Path, target_root: &Path) -> std::io::Result<()> {
+    let relative = path.strip_prefix(folder).map_err(|_| {
+        std::io::Error::other(format!(
+            "{} is outside {}",
+            path.display(),
+            folder.display()
+        ))
+    })?;
+    let target = target_root.join(relative);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(long_path(parent))?;
+    }
+    rename_no_clobber(&long_path(path), &long_path(&target))
+}
+
+#[cfg(test)]
+#[path = "tests/one_g1r_tests.rs"]
+mod tests;
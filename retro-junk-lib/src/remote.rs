@@ -0,0 +1,284 @@
+//! Reading ROMs from places other than the local filesystem.
+//!
+//! A [`Source`] is parsed from what the user typed: a local path, `-` for
+//! stdin, an `http(s)://` URL, or an SMB share (`smb://host/share/path` or a
+//! UNC path). [`Source::open`] returns a `Read + Seek` handle, so analyzers
+//! don't know where the bytes come from.
+//!
+//! HTTP servers that honour `Range` requests are read block by block with
+//! [`HttpRangeReader`]; only the blocks an analyzer touches are fetched.
+//! Servers that don't fall back to streaming the body through a
+//! [`StreamReader`]. SMB shares are opened through the operating system,
+//! which means UNC paths on Windows; elsewhere the share has to be mounted.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use retro_junk_core::{BlockImage, BlockSource, ReadSeek, StreamReader};
+use thiserror::Error;
+
+/// Bytes fetched per range request.
+const BLOCK_SIZE: u64 = 256 * 1024;
+
+/// Blocks kept in memory by an [`HttpRangeReader`].
+const MAX_CACHED_BLOCKS: usize = 32;
+
+/// Longest wait for a connection, or for any one read once connected.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+const USER_AGENT: &str = concat!("retro-junk/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug, Error)]
+pub enum RemoteError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("HTTP error: {0}")]
+    Http(String),
+    #[error("{0}")]
+    Unsupported(String),
+}
+
+/// Where a ROM is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Local(PathBuf),
+    Stdin,
+    Http(String),
+    /// A file on an SMB share.
+    Smb {
+        host: String,
+        share: String,
+        path: String,
+    },
+}
+
+impl Source {
+    /// Interpret a command-line argument.
+    pub fn parse(input: &str) -> Self {
+        if input == "-" {
+            return Self::Stdin;
+        }
+        let lower = input.to_ascii_lowercase();
+        if lower.starts_with("http://") || lower.starts_with("https://") {
+            return Self::Http(input.to_string());
+        }
+        let unc = if lower.starts_with("smb://") {
+            Some(&input[6..])
+        } else {
+            input.strip_prefix(r"\\")
+        };
+        if let Some(rest) = unc {
+            let mut parts = rest.splitn(3, ['/', '\\']);
+            if let (Some(host), Some(share)) = (parts.next(), parts.next())
+                && !host.is_empty()
+                && !share.is_empty()
+            {
+                return Self::Smb {
+                    host: host.to_string(),
+                    share: share.to_string(),
+                    path: parts.next().unwrap_or_default().replace('\\', "/"),
+                };
+            }
+        }
+        Self::Local(PathBuf::from(input))
+    }
+
+    /// Whether the bytes come from somewhere other than a local file.
+    pub fn is_remote(&self) -> bool {
+        !matches!(self, Self::Local(_))
+    }
+
+    /// Local filesystem path, for sources that have one.
+    pub fn local_path(&self) -> Option<PathBuf> {
+        match self {
+            Self::Local(path) => Some(path.clone()),
+            #[cfg(windows)]
+            Self::Smb { .. } => Some(PathBuf::from(self.to_string())),
+            _ => None,
+        }
+    }
+
+    /// The file name, for display.
+    pub fn file_name(&self) -> String {
+        let full = match self {
+            Self::Local(path) => {
+                return path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string());
+            }
+            Self::Stdin => return "<stdin>".to_string(),
+            Self::Http(url) => url.split(['?', '#']).next().unwrap_or(url),
+            Self::Smb { path, .. } => path.as_str(),
+        };
+        full.trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|n| !n.is_empty())
+            .unwrap_or(full)
+            .to_string()
+    }
+
+    /// Open the source for reading.
+    pub fn open(&self) -> Result<Box<dyn ReadSeek>, RemoteError> {
+        match self {
+            Self::Local(path) => Ok(Box::new(fs::File::open(path)?)),
+            Self::Stdin => Ok(Box::new(StreamReader::new(io::stdin().lock()))),
+            Self::Http(url) => open_http(url),
+            Self::Smb { .. } => match self.local_path() {
+                Some(path) => Ok(Box::new(fs::File::open(path)?)),
+                None => Err(RemoteError::Unsupported(format!(
+                    "SMB shares are only read directly on Windows; mount {} and pass its local path",
+                    self
+                ))),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local(path) => write!(f, "{}", path.display()),
+            Self::Stdin => f.write_str("<stdin>"),
+            Self::Http(url) => f.write_str(url),
+            Self::Smb { host, share, path } => {
+                write!(f, r"\\{}\{}", host, share)?;
+                if !path.is_empty() {
+                    write!(f, r"\{}", path.replace('/', r"\"))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Open a URL with range requests if the server supports them, or as a
+/// stream otherwise.
+fn open_http(url: &str) -> Result<Box<dyn ReadSeek>, RemoteError> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|e| RemoteError::Http(e.to_string()))?;
+    // A one-byte range probe reveals both range support and the total size
+    let response = client
+        .get(url)
+        .header(reqwest::header::RANGE, "bytes=0-0")
+        .send()
+        .map_err(|e| RemoteError::Http(e.to_string()))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(RemoteError::Http(format!("HTTP {} for {}", status, url)));
+    }
+
+    let total = response
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(content_range_total);
+    if status != reqwest::StatusCode::PARTIAL_CONTENT {
+        // The server ignored the range and is sending the whole body
+        return Ok(Box::new(StreamReader::new(response)));
+    }
+    match total {
+        Some(len) => Ok(Box::new(HttpRangeSource::reader(
+            client,
+            url.to_string(),
+            len,
+        ))),
+        // Ranges work but the size is unknown, and the probe body is only
+        // the first byte, so ask for the whole file instead
+        None => {
+            let response = client
+                .get(url)
+                .send()
+                .map_err(|e| RemoteError::Http(e.to_string()))?;
+            if !response.status().is_success() {
+                return Err(RemoteError::Http(format!(
+                    "HTTP {} for {}",
+                    response.status(),
+                    url
+                )));
+            }
+            Ok(Box::new(StreamReader::new(response)))
+        }
+    }
+}
+
+/// Total size from a `Content-Range: bytes 0-0/12345` header.
+fn content_range_total(value: &str) -> Option<u64> {
+    value.rsplit('/').next()?.trim().parse().ok()
+}
+
+/// A `Read + Seek` view of a URL served with HTTP range requests.
+///
+/// Data is fetched in fixed-size blocks on demand and a small number of
+/// recent blocks are cached, so header reads cost one request and a full
+/// hash streams the file without holding it all in memory.
+pub type HttpRangeReader = BlockImage<HttpRangeSource>;
+
+/// The blocks of a URL, each fetched with one range request.
+pub struct HttpRangeSource {
+    client: reqwest::blocking::Client,
+    url: String,
+    len: u64,
+}
+
+impl HttpRangeSource {
+    fn reader(client: reqwest::blocking::Client, url: String, len: u64) -> HttpRangeReader {
+        BlockImage::with_cache(Self { client, url, len }, MAX_CACHED_BLOCKS)
+    }
+}
+
+impl BlockSource for HttpRangeSource {
+    fn block_size(&self) -> usize {
+        BLOCK_SIZE as usize
+    }
+
+    fn image_len(&self) -> u64 {
+        self.len
+    }
+
+    fn read_block(&mut self, index: u64, buf: &mut Vec<u8>) -> io::Result<()> {
+        let start = index * BLOCK_SIZE;
+        let end = (start + BLOCK_SIZE).min(self.len) - 1;
+        let response = self
+            .client
+            .get(&self.url)
+            .header(reqwest::header::RANGE, format!("bytes={}-{}", start, end))
+            .send()
+            .map_err(io::Error::other)?;
+        if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return Err(io::Error::other(format!(
+                "HTTP {} for range {}-{} of {}",
+                response.status(),
+                start,
+                end,
+                self.url
+            )));
+        }
+        let data = response.bytes().map_err(io::Error::other)?;
+        if data.len() as u64 != end - start + 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "expected {} bytes at offset {}, got {}",
+                    end - start + 1,
+                    start,
+                    data.len()
+                ),
+            ));
+        }
+        buf.clear();
+        buf.extend_from_slice(&data);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/remote_tests.rs"]
+mod tests;
@@ -1,14 +1,25 @@
 use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use retro_junk_core::{AnalysisOptions, RomAnalyzer};
+use retro_junk_catalog::name_parser::{ParsedDatName, parse_dat_name};
+use retro_junk_core::{AnalysisOptions, HashAlgorithms, ReadSeek, RomAnalyzer};
 use retro_junk_dat::cache;
 use retro_junk_dat::error::DatError;
+use retro_junk_dat::hasher as dat_hasher;
 use retro_junk_dat::matcher::{DatIndex, MatchMethod, MatchResult, SerialLookupResult};
 
+use crate::archive::{self, ArchiveSplit};
+use crate::chd_verify;
 use crate::hasher;
-use crate::scanner::GameEntry;
+use crate::rename_overrides::{RenameOverride, RenameOverrides};
+use crate::sanitize::{CanonicalNames, sanitize_file_name};
+use crate::scanner::{
+    DEFAULT_COMPANION_EXTENSIONS, GameEntry, GroupingRule, SameStemRule, ScanOptions, SymlinkPolicy,
+};
+use crate::trash::{Trash, rename_no_clobber, write_new};
+use crate::util::long_path;
 
 /// A broken file reference found in a CUE or M3U file.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -97,6 +108,13 @@ pub fn check_broken_references(entry: &GameEntry) -> Vec<BrokenReference> {
 /// File extensions that represent disc-image entry points for M3U playlists.
 const M3U_ENTRY_POINT_EXTENSIONS: &[&str] = &["cue", "chd", "iso", "gdi", "cso", "pbp"];
 
+/// Content type analyzers report for a base game.
+const BASE_CONTENT_TYPE: &str = "Base";
+
+/// Content types analyzers report for add-ons, and the DAT name flag their
+/// entries carry.
+const CONTENT_TYPE_FLAGS: &[(&str, &str)] = &[("Update", "Update"), ("DLC", "DLC")];
+
 /// A planned rename action.
 #[derive(Debug, Clone)]
 pub struct RenameAction {
@@ -110,6 +128,17 @@ pub struct RenameAction {
     pub matched_by: MatchMethod,
 }
 
+/// A file next to a ROM sharing its name (a save, patch, cheat file, or
+/// box art) that is renamed along with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompanionRename {
+    /// The ROM the file belongs to, before renaming. The file is only
+    /// renamed if the ROM is.
+    pub rom: PathBuf,
+    pub source: PathBuf,
+    pub target: PathBuf,
+}
+
 /// Progress information for callbacks.
 #[derive(Debug, Clone)]
 pub enum RenameProgress {
@@ -135,7 +164,7 @@ pub enum RenameProgress {
 }
 
 /// Options controlling rename behavior.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct RenameOptions {
     /// Force CRC32-based matching instead of serial/name
     pub hash_mode: bool,
@@ -143,6 +172,81 @@ pub struct RenameOptions {
     pub dat_dir: Option<PathBuf>,
     /// Maximum number of ROMs to process
     pub limit: Option<usize>,
+    /// How to handle multiple files that map to the same target name
+    pub conflict_strategy: ConflictStrategy,
+    /// Split multi-game .zip/.7z archives into individual canonical files
+    pub split_archives: bool,
+    /// Match .zip/.7z files holding a single ROM by that ROM, and give the
+    /// archive the DAT name
+    pub archives: bool,
+    /// Whether to scan symlinked ROMs and folders at all
+    pub symlinks: SymlinkPolicy,
+    /// Whether renaming a symlinked ROM renames the link or its target
+    pub link_rename: LinkRename,
+    /// Adjust target names for FAT32/exFAT cards, recording the canonical
+    /// names in `canonical-names.toml`
+    pub sanitize_names: bool,
+    /// Move loose discs of one game into a new `.m3u` folder with a playlist
+    pub create_m3u: bool,
+    /// Extensions of files sharing a ROM's name (saves, patches, box art)
+    /// that are renamed along with it. Empty to leave them alone.
+    pub companion_extensions: Vec<String>,
+    /// Keep files that are removed or rewritten in the console folder's
+    /// `.retro-junk-trash/` so they can be restored
+    pub use_trash: bool,
+}
+
+impl Default for RenameOptions {
+    fn default() -> Self {
+        Self {
+            hash_mode: false,
+            dat_dir: None,
+            limit: None,
+            conflict_strategy: ConflictStrategy::default(),
+            split_archives: false,
+            archives: false,
+            symlinks: SymlinkPolicy::default(),
+            link_rename: LinkRename::default(),
+            sanitize_names: false,
+            create_m3u: false,
+            companion_extensions: DEFAULT_COMPANION_EXTENSIONS
+                .iter()
+                .map(|e| e.to_string())
+                .collect(),
+            use_trash: false,
+        }
+    }
+}
+
+retro_junk_core::named_enum! {
+    /// What gets renamed when a ROM is a symlink.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum LinkRename("link rename mode") {
+        /// Rename the link itself; the file it points to keeps its name.
+        #[default]
+        Link = "link",
+        /// Rename the file the link points to, then recreate the link under the
+        /// new name pointing at it.
+        Target = "target",
+    }
+}
+
+retro_junk_core::named_enum! {
+    /// How to resolve multiple files mapping to the same DAT target.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum ConflictStrategy("conflict strategy") {
+        /// Leave every conflicting file untouched and report the conflict.
+        #[default]
+        Skip = "skip",
+        /// Rename the first file (in scan order) and leave the rest.
+        KeepFirst = "keep-first" | "first",
+        /// Rename the file with the strongest match
+        /// (override > SHA1 > CRC32 > serial),
+        /// falling back to scan order on ties.
+        KeepBest = "keep-best" | "best",
+        /// Rename every file, appending " (1)", " (2)", ... to later duplicates.
+        DedupSuffix = "dedup-suffix" | "suffix",
+    }
 }
 
 /// Summary of a rename operation.
@@ -154,10 +258,17 @@ pub struct RenameSummary {
     pub errors: Vec<String>,
     pub conflicts: Vec<String>,
     pub m3u_folders_renamed: usize,
+    /// `.m3u` folders created for loose discs (with `create_m3u`).
+    pub m3u_folders_created: usize,
+    /// Saves, patches, and other companion files renamed with their ROMs.
+    pub companions_renamed: usize,
     pub m3u_playlists_written: usize,
     pub cue_files_updated: usize,
     pub m3u_references_updated: usize,
     pub m3u_playlists_renamed: usize,
+    pub archives_split: usize,
+    /// Sanitized names recorded in `canonical-names.toml` files.
+    pub canonical_names_recorded: usize,
 }
 
 /// A file that couldn't be matched by serial or hash.
@@ -168,6 +279,9 @@ pub struct UnmatchedFile {
     pub crc32: Option<String>,
     /// Data size that was hashed (after header stripping)
     pub data_size: Option<u64>,
+    /// Why the file can't match the DAT, when that was clear without
+    /// hashing it (e.g. a scrubbed disc image).
+    pub mismatch_reason: Option<String>,
 }
 
 /// A discrepancy between serial-based and hash-based matching (reported in --hash mode).
@@ -205,6 +319,9 @@ pub enum SerialWarningKind {
         game_code: Option<String>,
         candidates: Vec<String>,
     },
+    /// Serial found in ROM header but not in the platform's serial format,
+    /// so it wasn't looked up
+    Malformed { full_serial: String },
     /// Platform expects serial but none was found in ROM
     Missing,
 }
@@ -233,6 +350,47 @@ pub fn target_filename_for_rename(
     }
 }
 
+/// Resolve a user override to a DAT entry.
+///
+/// For a game name, picks the ROM whose extension matches the file (falling
+/// back to the game's first ROM). Logs and returns `None` when the override
+/// doesn't exist in the DAT, so the file goes through normal matching.
+fn match_override(
+    entry: &RenameOverride,
+    file_path: &Path,
+    index: &DatIndex,
+) -> Option<MatchResult> {
+    let found = match entry {
+        RenameOverride::Game(name) => index.game_by_name(name).and_then(|gi| {
+            let roms = &index.games[gi].roms;
+            let ext = file_path.extension().and_then(|e| e.to_str());
+            let ri = roms
+                .iter()
+                .position(|r| {
+                    let rom_ext = Path::new(&r.name).extension().and_then(|e| e.to_str());
+                    matches!((rom_ext, ext), (Some(a), Some(b)) if a.eq_ignore_ascii_case(b))
+                })
+                .or((!roms.is_empty()).then_some(0))?;
+            Some((gi, ri))
+        }),
+        RenameOverride::Crc32(crc) => index.rom_by_crc32(crc),
+    };
+
+    if found.is_none() {
+        log::warn!(
+            "Override for {} does not match any DAT entry: {:?}",
+            file_path.display(),
+            entry
+        );
+    }
+
+    found.map(|(game_index, rom_index)| MatchResult {
+        game_index,
+        rom_index,
+        method: MatchMethod::Override,
+    })
+}
+
 /// Internal result from serial matching, carrying diagnostic info.
 struct SerialMatchOutcome {
     result: Option<MatchResult>,
@@ -242,6 +400,8 @@ struct SerialMatchOutcome {
     game_code: Option<String>,
     /// When serial matched multiple games, the candidate names
     ambiguous_candidates: Option<Vec<String>>,
+    /// Whether the serial failed the analyzer's format check
+    malformed: bool,
     /// Detected file format extension from analyzer (e.g., "iso", "chd", "rvz")
     detected_extension: Option<String>,
 }
@@ -291,7 +451,7 @@ pub fn plan_m3u_action(
         Some(name) if !name.is_empty() => name.to_string(),
         _ => {
             let game_names: Vec<&str> = discs.iter().map(|d| d.game_name.as_str()).collect();
-            derive_base_game_name(&game_names)
+            derive_base_name(&game_names, PartKind::ALL)
         }
     };
     if base_game_name.is_empty() {
@@ -304,15 +464,19 @@ pub fn plan_m3u_action(
         None => return None,
     };
 
-    // Build playlist entries: target filenames for entry-point files, sorted by disc number
-    // or alphabetically if no disc numbers are present.
-    let mut playlist_entries: Vec<(Option<u32>, String)> = discs
+    // Build playlist entries: target filenames for entry-point files, sorted by part
+    // (disc, disk, side, tape) or alphabetically if no part numbers are present.
+    // DAT names tag parts canonically, so every part kind is recognized here.
+    let mut playlist_entries: Vec<(Option<(u32, u32)>, String)> = discs
         .iter()
         .filter(|d| is_m3u_entry_point(&d.target_filename))
-        .map(|d| (extract_disc_number(&d.game_name), d.target_filename.clone()))
+        .map(|d| {
+            let part = extract_part(&d.game_name, PartKind::ALL).map(|tag| tag.sort_key());
+            (part, d.target_filename.clone())
+        })
         .collect();
     if playlist_entries.iter().any(|(d, _)| d.is_some()) {
-        playlist_entries.sort_by_key(|(disc, _)| disc.unwrap_or(u32::MAX));
+        playlist_entries.sort_by_key(|(part, _)| part.unwrap_or((u32::MAX, 0)));
     } else {
         playlist_entries.sort_by(|(_, a), (_, b)| a.cmp(b));
     }
@@ -372,7 +536,11 @@ struct M3uExecutionResult {
 ///
 /// This does NOT rename individual disc files — the caller is responsible for that.
 /// Execution order: write playlist first (using source_folder path), then rename folder.
-fn execute_m3u_action(action: &M3uAction, errors: &mut Vec<String>) -> M3uExecutionResult {
+fn execute_m3u_action(
+    action: &M3uAction,
+    trash: Option<&Trash>,
+    errors: &mut Vec<String>,
+) -> M3uExecutionResult {
     let mut result = M3uExecutionResult::default();
 
     // Write .m3u playlist file (using source folder path, before folder rename)
@@ -385,7 +553,20 @@ fn execute_m3u_action(action: &M3uAction, errors: &mut Vec<String>) -> M3uExecut
                     && let Some(ext) = path.extension().and_then(|e| e.to_str())
                     && ext.eq_ignore_ascii_case("m3u")
                 {
-                    let _ = fs::remove_file(&path);
+                    match trash {
+                        Some(trash) => {
+                            if let Err(e) = trash.stash(&path, "replaced by a new playlist") {
+                                errors.push(format!(
+                                    "Failed to move {} to the trash: {}",
+                                    path.display(),
+                                    e
+                                ));
+                            }
+                        }
+                        None => {
+                            let _ = fs::remove_file(&path);
+                        }
+                    }
                 }
             }
         }
@@ -393,7 +574,7 @@ fn execute_m3u_action(action: &M3uAction, errors: &mut Vec<String>) -> M3uExecut
         let playlist_name = format!("{}.m3u", action.game_name);
         let playlist_path = action.source_folder.join(&playlist_name);
         let contents = action.playlist_entries.join("\n") + "\n";
-        match fs::write(&playlist_path, contents) {
+        match write_new(&playlist_path, contents) {
             Ok(()) => result.playlist_written = true,
             Err(e) => {
                 errors.push(format!(
@@ -407,13 +588,16 @@ fn execute_m3u_action(action: &M3uAction, errors: &mut Vec<String>) -> M3uExecut
 
     // Rename .m3u folder (last, so playlist write used valid path)
     if action.source_folder != action.target_folder {
-        if action.target_folder.exists() {
+        if long_path(&action.target_folder).exists() {
             errors.push(format!(
                 "Target folder already exists: {}",
                 action.target_folder.display()
             ));
         } else {
-            match fs::rename(&action.source_folder, &action.target_folder) {
+            match rename_no_clobber(
+                &long_path(&action.source_folder),
+                &long_path(&action.target_folder),
+            ) {
                 Ok(()) => result.folder_renamed = true,
                 Err(e) => {
                     errors.push(format!(
@@ -442,6 +626,9 @@ pub struct M3uRenameJob {
     pub discs: Vec<DiscMatchData>,
     /// Pre-resolved game name (from catalog DB); skips derive_base_game_name
     pub game_name_override: Option<String>,
+    /// The folder doesn't exist yet: the discs are loose files next to it and
+    /// are moved in (with their CUE tracks) when the job runs
+    pub create_folder: bool,
 }
 
 /// Result of executing a single M3U folder rename via `execute_m3u_rename()`.
@@ -453,6 +640,7 @@ pub struct M3uRenameResult {
     pub playlist_written: bool,
     pub playlist_renamed: bool,
     pub folder_renamed: bool,
+    pub folder_created: bool,
     pub final_folder: PathBuf,
     pub errors: Vec<String>,
 }
@@ -460,18 +648,57 @@ pub struct M3uRenameResult {
 /// Execute the full rename flow for a single M3U folder.
 ///
 /// Steps:
+/// 0. Create the folder and move CUE tracks into it (`create_folder` only)
 /// 1. Rename disc files
 /// 2. Fix CUE FILE references broken by the renames
 /// 3. Fix M3U playlist entries broken by the renames
 /// 4. Plan M3U action (folder rename + playlist write)
 /// 5. Rename misnamed inner `.m3u` file (if playlist won't be rewritten)
 /// 6. Execute M3U action (write playlist, rename folder)
-pub fn execute_m3u_rename(job: &M3uRenameJob) -> M3uRenameResult {
+pub fn execute_m3u_rename(
+    job: &M3uRenameJob,
+    link_rename: LinkRename,
+    trash: Option<&Trash>,
+) -> M3uRenameResult {
     let mut result = M3uRenameResult {
         final_folder: job.source_folder.clone(),
         ..Default::default()
     };
 
+    // Step 0: Create the folder for loose discs, bringing along the tracks
+    // their CUE sheets reference (the discs themselves move in step 1)
+    if job.create_folder {
+        if long_path(&job.source_folder).exists() {
+            result.errors.push(format!(
+                "Target folder already exists: {}",
+                job.source_folder.display()
+            ));
+            return result;
+        }
+        if let Err(e) = fs::create_dir(long_path(&job.source_folder)) {
+            result.errors.push(format!(
+                "Failed to create folder {}: {}",
+                job.source_folder.display(),
+                e,
+            ));
+            return result;
+        }
+        result.folder_created = true;
+        for track in job.discs.iter().flat_map(|d| cue_tracks(&d.file_path)) {
+            let Some(name) = track.file_name() else {
+                continue;
+            };
+            if let Err(e) = rename_no_clobber(
+                &long_path(&track),
+                &long_path(&job.source_folder.join(name)),
+            ) {
+                result
+                    .errors
+                    .push(format!("Failed to move '{}': {}", track.display(), e,));
+            }
+        }
+    }
+
     // Step 1: Rename disc files
     let mut rename_map: HashMap<String, String> = HashMap::new();
     for disc in &job.discs {
@@ -485,10 +712,17 @@ pub fn execute_m3u_rename(job: &M3uRenameJob) -> M3uRenameResult {
             .unwrap_or_default()
             .to_string_lossy()
             .to_string();
-        match fs::rename(&disc.file_path, &target) {
+        match rename_rom_file(&disc.file_path, &target, link_rename) {
             Ok(()) => {
                 result.discs_renamed += 1;
                 rename_map.insert(old_name, disc.target_filename.clone());
+                if let Err(e) = crate::sidecar::rename_sidecars(&disc.file_path, &target) {
+                    result.errors.push(format!(
+                        "Failed to move sidecar for '{}': {}",
+                        disc.file_path.display(),
+                        e,
+                    ));
+                }
             }
             Err(e) => {
                 result.errors.push(format!(
@@ -502,11 +736,11 @@ pub fn execute_m3u_rename(job: &M3uRenameJob) -> M3uRenameResult {
 
     // Step 2: Fix CUE FILE references
     result.cue_files_updated =
-        fix_cue_references_in_dir(&job.source_folder, &rename_map, &mut result.errors);
+        fix_cue_references_in_dir(&job.source_folder, &rename_map, trash, &mut result.errors);
 
     // Step 3: Fix M3U playlist entries
     result.m3u_references_updated =
-        fix_m3u_references_in_dir(&job.source_folder, &rename_map, &mut result.errors);
+        fix_m3u_references_in_dir(&job.source_folder, &rename_map, trash, &mut result.errors);
 
     // Step 4: Plan M3U action
     if let Some(action) = plan_m3u_action(
@@ -519,7 +753,7 @@ pub fn execute_m3u_rename(job: &M3uRenameJob) -> M3uRenameResult {
         if action.playlist_entries.is_empty() {
             let expected = format!("{}.m3u", action.game_name);
             if let Some((src, dst)) = detect_misnamed_m3u(&job.source_folder, &expected) {
-                match fs::rename(&src, &dst) {
+                match rename_no_clobber(&long_path(&src), &long_path(&dst)) {
                     Ok(()) => result.playlist_renamed = true,
                     Err(e) => {
                         result
@@ -531,7 +765,7 @@ pub fn execute_m3u_rename(job: &M3uRenameJob) -> M3uRenameResult {
         }
 
         // Step 6: Execute M3U action (write playlist, rename folder)
-        let m3u_exec = execute_m3u_action(&action, &mut result.errors);
+        let m3u_exec = execute_m3u_action(&action, trash, &mut result.errors);
         result.playlist_written = m3u_exec.playlist_written;
         result.folder_renamed = m3u_exec.folder_renamed;
         if m3u_exec.folder_renamed {
@@ -542,11 +776,29 @@ pub fn execute_m3u_rename(job: &M3uRenameJob) -> M3uRenameResult {
     result
 }
 
+/// Existing files referenced by a CUE sheet, or nothing for other files.
+fn cue_tracks(path: &Path) -> Vec<PathBuf> {
+    let is_cue = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("cue"));
+    if !is_cue {
+        return Vec::new();
+    }
+    cue::file_paths(path)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|track| track.is_file())
+        .collect()
+}
+
 /// Result of planning renames for a single console folder.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct RenamePlan {
     /// Single-file renames (non-M3U). Disc renames live inside `m3u_jobs`.
     pub renames: Vec<RenameAction>,
+    /// Companion files renamed along with the ROMs in `renames`.
+    pub companion_renames: Vec<CompanionRename>,
     pub already_correct: Vec<PathBuf>,
     pub unmatched: Vec<UnmatchedFile>,
     pub conflicts: Vec<(PathBuf, String)>,
@@ -556,10 +808,27 @@ pub struct RenamePlan {
     pub serial_warnings: Vec<SerialWarning>,
     /// M3U folder jobs: disc renames + CUE/M3U fix + playlist + folder rename
     pub m3u_jobs: Vec<M3uRenameJob>,
+    /// Multi-disc sets found as loose files, which `create_m3u` would move
+    /// into a new `.m3u` folder. Only filled when that option is off.
+    pub loose_disc_sets: Vec<M3uRenameJob>,
     /// CUE files with broken FILE references in non-M3U dirs (pre-existing)
     pub broken_cue_files: Vec<PathBuf>,
     /// M3U playlist files with broken entries in non-M3U dirs (pre-existing)
     pub broken_m3u_files: Vec<PathBuf>,
+    /// Every file that matched a DAT entry, including already-correct files
+    /// and M3U discs. `target` is where normal matching would put the file.
+    pub matched: Vec<RenameAction>,
+    /// Name and version of each DAT the plan was matched against.
+    pub dat_versions: Vec<String>,
+    /// Multi-game archives to split (with `split_archives`).
+    pub archive_splits: Vec<ArchiveSplit>,
+    /// How symlinked ROMs are renamed when the plan is executed.
+    pub link_rename: LinkRename,
+    /// Files whose target name differs from the DAT name because it was
+    /// sanitized, with the canonical name to record for them.
+    pub canonical_names: Vec<(PathBuf, String)>,
+    /// Where removed and rewritten files are kept (with `use_trash`).
+    pub trash: Option<Trash>,
 }
 
 impl RenamePlan {
@@ -570,7 +839,7 @@ impl RenamePlan {
 
     /// Whether this plan has any work to do.
     pub fn has_actions(&self) -> bool {
-        !self.renames.is_empty() || !self.m3u_jobs.is_empty()
+        !self.renames.is_empty() || !self.m3u_jobs.is_empty() || !self.archive_splits.is_empty()
     }
 
     /// Whether this plan has any problems (conflicts, unmatched, broken refs).
@@ -582,7 +851,7 @@ impl RenamePlan {
     }
 }
 
-use retro_junk_core::disc::{derive_base_game_name, extract_disc_number};
+use retro_junk_core::disc::{PartKind, cue, derive_base_name, extract_disc_number, extract_part};
 
 /// Returns true for file extensions that are M3U entry points (playable disc images).
 /// Returns false for companion data files (.bin, .img) that shouldn't appear in playlists.
@@ -600,6 +869,8 @@ pub fn is_m3u_entry_point(filename: &str) -> bool {
 /// Uses the analyzer to extract serial/name from each file, then matches
 /// against the DAT index. Falls back to hashing when serial/name matching
 /// fails (unless `hash_mode` is set, in which case all files are hashed).
+/// Files listed in the folder's `rename-overrides.toml` skip matching and
+/// use the pinned DAT entry instead.
 pub fn plan_renames(
     folder: &Path,
     analyzer: &dyn RomAnalyzer,
@@ -624,12 +895,27 @@ pub fn plan_renames(
         options.dat_dir.as_deref(),
         dat_source,
     )?;
+    let dat_versions = dats
+        .iter()
+        .map(|d| {
+            if d.version.is_empty() {
+                d.name.clone()
+            } else {
+                format!("{} ({})", d.name, d.version)
+            }
+        })
+        .collect();
     let index = DatIndex::from_dats(dats);
+    let overrides = RenameOverrides::load(folder)?;
 
     // Collect ROM files (including inside .m3u subdirectories)
     let extensions = crate::scanner::extension_set(analyzer.file_extensions());
-    let game_entries = crate::scanner::scan_game_entries(folder, &extensions)
-        .map_err(|e| DatError::cache(format!("Error scanning {}: {}", folder.display(), e)))?;
+    let scan_options = ScanOptions::new()
+        .symlinks(options.symlinks)
+        .archives(options.archives);
+    let game_entries =
+        crate::scanner::scan_game_entries_with(folder, &extensions, &scan_options)
+            .map_err(|e| DatError::cache(format!("Error scanning {}: {}", folder.display(), e)))?;
 
     let mut files: Vec<PathBuf> = game_entries
         .iter()
@@ -650,6 +936,9 @@ pub fn plan_renames(
     let mut unmatched = Vec::new();
     let mut discrepancies = Vec::new();
     let mut serial_warnings = Vec::new();
+    let mut matched = Vec::new();
+    let mut canonical_names = Vec::new();
+    let mut recorded_names: HashMap<PathBuf, CanonicalNames> = HashMap::new();
     // Track file → (game_name, target_filename) for M3U post-processing
     let mut file_game_names: HashMap<PathBuf, (String, String)> = HashMap::new();
     for (i, file_path) in files.iter().enumerate() {
@@ -667,11 +956,21 @@ pub fn plan_renames(
 
         // Track hash info for diagnostics if the file ends up unmatched
         let mut last_hash: Option<(String, u64)> = None;
+        let mut mismatch_reason = None;
+
+        let forced = overrides
+            .get(folder, file_path)
+            .and_then(|o| match_override(o, file_path, &index));
 
-        let (match_result, detected_ext) = if options.hash_mode {
+        let (match_result, detected_ext) = if let Some(result) = forced {
+            (Some(result), None)
+        } else if options.hash_mode {
             // Hash mode: hash is authoritative, but also check serial for discrepancies
             let hash_outcome = match_by_hash(file_path, &index, analyzer, progress)?;
-            last_hash = Some((hash_outcome.crc32, hash_outcome.data_size));
+            if hash_outcome.mismatch_reason.is_none() {
+                last_hash = Some((hash_outcome.crc32, hash_outcome.data_size));
+            }
+            mismatch_reason = hash_outcome.mismatch_reason;
             let serial_outcome = match_by_serial(file_path, analyzer, &index);
 
             // Report discrepancy if both matched but to different games
@@ -696,9 +995,22 @@ pub fn plan_renames(
             } else {
                 // Serial failed — try hash, then create serial warning with hash info
                 let hash_outcome = match_by_hash(file_path, &index, analyzer, progress)?;
-                last_hash = Some((hash_outcome.crc32.clone(), hash_outcome.data_size));
+                if hash_outcome.mismatch_reason.is_none() {
+                    last_hash = Some((hash_outcome.crc32.clone(), hash_outcome.data_size));
+                }
+                mismatch_reason = hash_outcome.mismatch_reason.clone();
 
-                if let Some(ref candidates) = serial_outcome.ambiguous_candidates {
+                if serial_outcome.malformed {
+                    serial_warnings.push(SerialWarning {
+                        file: file_path.clone(),
+                        kind: SerialWarningKind::Malformed {
+                            full_serial: serial_outcome.full_serial.clone().unwrap_or_default(),
+                        },
+                        crc32: last_hash.as_ref().map(|(crc, _)| crc.clone()),
+                        data_size: last_hash.as_ref().map(|&(_, size)| size),
+                        matched_by_hash: hash_outcome.result.is_some(),
+                    });
+                } else if let Some(ref candidates) = serial_outcome.ambiguous_candidates {
                     // Serial matched multiple games — report ambiguity
                     serial_warnings.push(SerialWarning {
                         file: file_path.clone(),
@@ -707,8 +1019,8 @@ pub fn plan_renames(
                             game_code: serial_outcome.game_code.clone(),
                             candidates: candidates.clone(),
                         },
-                        crc32: Some(hash_outcome.crc32.clone()),
-                        data_size: Some(hash_outcome.data_size),
+                        crc32: last_hash.as_ref().map(|(crc, _)| crc.clone()),
+                        data_size: last_hash.as_ref().map(|&(_, size)| size),
                         matched_by_hash: hash_outcome.result.is_some(),
                     });
                 } else if let Some(ref full_serial) = serial_outcome.full_serial {
@@ -718,16 +1030,16 @@ pub fn plan_renames(
                             full_serial: full_serial.clone(),
                             game_code: serial_outcome.game_code.clone(),
                         },
-                        crc32: Some(hash_outcome.crc32.clone()),
-                        data_size: Some(hash_outcome.data_size),
+                        crc32: last_hash.as_ref().map(|(crc, _)| crc.clone()),
+                        data_size: last_hash.as_ref().map(|&(_, size)| size),
                         matched_by_hash: hash_outcome.result.is_some(),
                     });
                 } else if analyzer.expects_serial() {
                     serial_warnings.push(SerialWarning {
                         file: file_path.clone(),
                         kind: SerialWarningKind::Missing,
-                        crc32: Some(hash_outcome.crc32.clone()),
-                        data_size: Some(hash_outcome.data_size),
+                        crc32: last_hash.as_ref().map(|(crc, _)| crc.clone()),
+                        data_size: last_hash.as_ref().map(|&(_, size)| size),
                         matched_by_hash: hash_outcome.result.is_some(),
                     });
                 }
@@ -741,9 +1053,47 @@ pub fn plan_renames(
             let rom = &game.roms[result.rom_index];
 
             let parent = file_path.parent().unwrap_or(folder);
-            let target_name =
-                target_filename_for_rename(&rom.name, file_path, detected_ext.as_deref());
-            let target = parent.join(&target_name);
+            // Disk entries are named without an extension, so keep the whole
+            // name rather than letting a "." in the title look like one
+            let target_name = if archive::is_archive(file_path) {
+                // An archive keeps its own extension around the DAT name
+                target_filename_for_rename(&rom.name, file_path, None)
+            } else if rom.is_disk() {
+                target_filename_for_rename(&format!("{}.chd", rom.name), file_path, None)
+            } else if game.roms.len() > 1
+                && let Some(ext @ ("chd" | "gdi")) = detected_ext.as_deref()
+            {
+                // A CHD holds every track and a GDI sheet lists them, so
+                // either takes the game's name rather than that of the
+                // track it was matched by
+                target_filename_for_rename(&format!("{}.{}", game.name, ext), file_path, None)
+            } else {
+                target_filename_for_rename(&rom.name, file_path, detected_ext.as_deref())
+            };
+            let target = if options.sanitize_names {
+                let sanitized = sanitize_file_name(&target_name);
+                if sanitized != target_name {
+                    canonical_names.push((parent.join(&sanitized), target_name.clone()));
+                }
+                parent.join(sanitized)
+            } else {
+                // A file stored under a recorded sanitized name is correct
+                let recorded = match recorded_names.entry(parent.to_path_buf()) {
+                    std::collections::hash_map::Entry::Occupied(e) => e.into_mut(),
+                    std::collections::hash_map::Entry::Vacant(e) => {
+                        e.insert(CanonicalNames::load(parent)?)
+                    }
+                };
+                let is_recorded = file_path
+                    .file_name()
+                    .and_then(|n| recorded.get(&n.to_string_lossy()))
+                    .is_some_and(|canonical| canonical == target_name);
+                if is_recorded {
+                    file_path.clone()
+                } else {
+                    parent.join(&target_name)
+                }
+            };
 
             let target_filename = target
                 .file_name()
@@ -752,16 +1102,18 @@ pub fn plan_renames(
                 .to_string();
             file_game_names.insert(file_path.clone(), (game.name.clone(), target_filename));
 
-            if *file_path == target {
+            let action = RenameAction {
+                source: file_path.clone(),
+                target,
+                game_name: game.name.clone(),
+                matched_by: result.method,
+            };
+            if action.source == action.target {
                 already_correct.push(file_path.clone());
             } else {
-                renames.push(RenameAction {
-                    source: file_path.clone(),
-                    target,
-                    game_name: game.name.clone(),
-                    matched_by: result.method,
-                });
+                renames.push(action.clone());
             }
+            matched.push(action);
         } else {
             let (crc32, data_size) = match last_hash {
                 Some((c, s)) => (Some(c), Some(s)),
@@ -771,49 +1123,13 @@ pub fn plan_renames(
                 file: file_path.clone(),
                 crc32,
                 data_size,
+                mismatch_reason,
             });
         }
     }
 
-    // Detect conflicts: multiple files mapping to the same target
-    let mut target_map: HashMap<PathBuf, Vec<usize>> = HashMap::new();
-    for (i, rename) in renames.iter().enumerate() {
-        target_map.entry(rename.target.clone()).or_default().push(i);
-    }
-
-    let mut conflicts = Vec::new();
-    let conflict_indices: std::collections::HashSet<usize> = target_map
-        .iter()
-        .filter(|(_, indices)| indices.len() > 1)
-        .flat_map(|(target, indices)| {
-            conflicts.push((
-                target.clone(),
-                format!(
-                    "Multiple files map to {:?}: {}",
-                    target.file_name().unwrap_or_default(),
-                    indices
-                        .iter()
-                        .map(|&i| renames[i]
-                            .source
-                            .file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy()
-                            .to_string())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                ),
-            ));
-            indices.clone()
-        })
-        .collect();
-
-    // Remove conflicting renames
-    let clean_renames: Vec<RenameAction> = renames
-        .into_iter()
-        .enumerate()
-        .filter(|(i, _)| !conflict_indices.contains(i))
-        .map(|(_, r)| r)
-        .collect();
+    // Resolve conflicts: multiple files mapping to the same target
+    let (clean_renames, conflicts) = resolve_conflicts(renames, options.conflict_strategy);
 
     // M3U post-processing: build M3uRenameJobs for multi-disc sets.
     // Each job owns its disc renames, CUE/M3U fixing, playlist, and folder rename.
@@ -842,22 +1158,93 @@ pub fn plan_renames(
                 None => continue,
             };
 
+            let game_name_override =
+                m3u_folder_name_override(&source_folder, &discs, options, &mut canonical_names)?;
+
             // Only create a job if there's actual work: disc renames or M3U action needed
             let any_disc_rename = discs
                 .iter()
                 .any(|d| d.file_path != source_folder.join(&d.target_filename));
-            let needs_m3u_action = plan_m3u_action(&source_folder, &discs, None, None).is_some();
+            let needs_m3u_action =
+                plan_m3u_action(&source_folder, &discs, None, game_name_override.as_deref())
+                    .is_some();
 
             if any_disc_rename || needs_m3u_action {
                 m3u_jobs.push(M3uRenameJob {
                     source_folder,
                     discs,
-                    game_name_override: None,
+                    game_name_override,
+                    create_folder: false,
                 });
             }
         }
     }
 
+    // Loose discs of one game side by side get a new .m3u folder, or are
+    // reported so the user can ask for one
+    let conflicted: std::collections::HashSet<&PathBuf> =
+        conflicts.iter().map(|(path, _)| path).collect();
+    let loose: Vec<DiscMatchData> = game_entries
+        .iter()
+        .filter_map(|entry| match entry {
+            GameEntry::SingleFile(path) if !conflicted.contains(path) => {
+                let (game_name, target_filename) = file_game_names.get(path)?;
+                Some(DiscMatchData {
+                    file_path: path.clone(),
+                    game_name: game_name.clone(),
+                    target_filename: target_filename.clone(),
+                })
+            }
+            _ => None,
+        })
+        .collect();
+    let mut loose_disc_sets = Vec::new();
+    for discs in group_loose_discs(loose) {
+        let Some(parent) = discs[0].file_path.parent() else {
+            continue;
+        };
+        let game_names: Vec<&str> = discs.iter().map(|d| d.game_name.as_str()).collect();
+        let folder_name = format!("{}.m3u", derive_base_name(&game_names, PartKind::ALL));
+        let folder_name = if options.sanitize_names {
+            sanitize_file_name(&folder_name)
+        } else {
+            folder_name
+        };
+        let source_folder = parent.join(folder_name);
+        if long_path(&source_folder).exists() {
+            continue;
+        }
+        if !options.create_m3u {
+            loose_disc_sets.push(M3uRenameJob {
+                source_folder,
+                discs,
+                game_name_override: None,
+                create_folder: true,
+            });
+            continue;
+        }
+        let game_name_override =
+            m3u_folder_name_override(&source_folder, &discs, options, &mut canonical_names)?;
+        // Sanitized disc names are recorded in the new folder
+        let targets: Vec<PathBuf> = discs
+            .iter()
+            .map(|d| parent.join(&d.target_filename))
+            .collect();
+        canonical_names.iter_mut().for_each(|(path, _)| {
+            if let Some(name) = path.file_name()
+                && targets.contains(path)
+            {
+                *path = source_folder.join(name);
+            }
+        });
+        m3u_jobs.push(M3uRenameJob {
+            source_folder,
+            discs,
+            game_name_override,
+            create_folder: true,
+        });
+    }
+
     // Separate disc renames from single-file renames: disc files in M3U jobs
     // are handled by execute_m3u_rename(), not by the top-level rename step.
     let m3u_job_files: std::collections::HashSet<PathBuf> = m3u_jobs
@@ -889,19 +1276,171 @@ pub fn plan_renames(
     let broken_cue_files = detect_broken_cue_files(&non_m3u_files);
     let broken_m3u_files = detect_broken_m3u_playlists(&non_m3u_files);
 
+    let archive_splits = if options.split_archives {
+        plan_archive_splits(folder, analyzer, &extensions, &index)
+    } else {
+        Vec::new()
+    };
+
+    let companion_renames = plan_companion_renames(&single_renames, &options.companion_extensions);
+
     Ok(RenamePlan {
         renames: single_renames,
+        companion_renames,
         already_correct: single_already_correct,
         unmatched,
         conflicts,
         discrepancies,
         serial_warnings,
         m3u_jobs,
+        loose_disc_sets,
         broken_cue_files,
         broken_m3u_files,
+        matched,
+        dat_versions,
+        archive_splits,
+        link_rename: options.link_rename,
+        canonical_names,
+        trash: options.use_trash.then(|| Trash::new(folder)),
     })
 }
 
+/// Find the companion files of each rename: files in the ROM's folder that
+/// share its name and have one of `extensions`. A file claimed by several
+/// ROMs (`Game.sav` next to `Game.cue` and `Game.bin`) goes with the first.
+fn plan_companion_renames(renames: &[RenameAction], extensions: &[String]) -> Vec<CompanionRename> {
+    if extensions.is_empty() {
+        return Vec::new();
+    }
+    let extensions: Vec<&str> = extensions.iter().map(String::as_str).collect();
+    let rule = SameStemRule::new(&extensions);
+    let sources: std::collections::HashSet<&Path> =
+        renames.iter().map(|r| r.source.as_path()).collect();
+    let mut listings: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    let mut claimed: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+    let mut companions = Vec::new();
+
+    for rename in renames {
+        let (Some(dir), Some(stem)) = (rename.source.parent(), rename.target.file_stem()) else {
+            continue;
+        };
+        if rename.source.file_stem() == Some(stem) {
+            continue;
+        }
+        let siblings = listings
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| list_files(dir));
+        for source in rule.companions(&rename.source, siblings) {
+            if sources.contains(source.as_path()) || !claimed.insert(source.clone()) {
+                continue;
+            }
+            let mut name = stem.to_os_string();
+            if let Some(ext) = source.extension() {
+                name.push(".");
+                name.push(ext);
+            }
+            companions.push(CompanionRename {
+                rom: rename.source.clone(),
+                target: rename.target.with_file_name(name),
+                source,
+            });
+        }
+    }
+    companions
+}
+
+/// The files directly in `dir`, or none if it can't be read.
+fn list_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(long_path(dir)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| !t.is_dir()))
+        .map(|e| dir.join(e.file_name()))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Name to give a multi-disc folder instead of the DAT-derived one.
+///
+/// When sanitizing, a folder name the card can't hold is replaced and its
+/// canonical name recorded. Otherwise, a folder already stored under a
+/// recorded sanitized name keeps it.
+fn m3u_folder_name_override(
+    source_folder: &Path,
+    discs: &[DiscMatchData],
+    options: &RenameOptions,
+    canonical_names: &mut Vec<(PathBuf, String)>,
+) -> Result<Option<String>, DatError> {
+    let Some(parent) = source_folder.parent() else {
+        return Ok(None);
+    };
+    let game_names: Vec<&str> = discs.iter().map(|d| d.game_name.as_str()).collect();
+    let canonical = format!("{}.m3u", derive_base_name(&game_names, PartKind::ALL));
+
+    if options.sanitize_names {
+        let sanitized = sanitize_file_name(&canonical);
+        if sanitized == canonical {
+            return Ok(None);
+        }
+        canonical_names.push((parent.join(&sanitized), canonical));
+        return Ok(Some(
+            sanitized
+                .strip_suffix(".m3u")
+                .unwrap_or(&sanitized)
+                .to_string(),
+        ));
+    }
+
+    let current = source_folder
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let recorded = CanonicalNames::load(parent)?;
+    Ok((recorded.get(&current) == Some(canonical.as_str()))
+        .then(|| current.strip_suffix(".m3u").unwrap_or(&current).to_string()))
+}
+
+/// Group loose discs into multi-disc sets.
+///
+/// Discs belong together when they sit in the same directory and their DAT
+/// names differ only in the disc tag. A set needs at least two different
+/// disc numbers and one file a playlist can point at, so a lone disc or a
+/// folder of bare `.bin` tracks is left alone.
+fn group_loose_discs(discs: Vec<DiscMatchData>) -> Vec<Vec<DiscMatchData>> {
+    let mut sets: std::collections::BTreeMap<(PathBuf, String), Vec<(u32, DiscMatchData)>> =
+        std::collections::BTreeMap::new();
+    for disc in discs {
+        let parsed = parse_dat_name(&disc.game_name);
+        let (Some(number), Some(dir)) = (parsed.disc_number, disc.file_path.parent()) else {
+            continue;
+        };
+        let base = ParsedDatName {
+            disc_number: None,
+            disc_label: None,
+            ..parsed
+        }
+        .to_no_intro_name();
+        sets.entry((dir.to_path_buf(), base))
+            .or_default()
+            .push((number, disc));
+    }
+
+    sets.into_values()
+        .filter_map(|mut members| {
+            members.sort_by(|(a, x), (b, y)| a.cmp(b).then_with(|| x.file_path.cmp(&y.file_path)));
+            let numbers: std::collections::BTreeSet<u32> =
+                members.iter().map(|(n, _)| *n).collect();
+            let playable = members
+                .iter()
+                .any(|(_, d)| is_m3u_entry_point(&d.target_filename));
+            (numbers.len() >= 2 && playable).then(|| members.into_iter().map(|(_, d)| d).collect())
+        })
+        .collect()
+}
+
 /// Try to match a file by serial number only (no hashing).
 ///
 /// Returns a `SerialMatchOutcome` with diagnostic info regardless of success,
@@ -911,99 +1450,220 @@ fn match_by_serial(
     analyzer: &dyn RomAnalyzer,
     index: &DatIndex,
 ) -> SerialMatchOutcome {
-    let analysis_options = AnalysisOptions::new().quick(true).file_path(file_path);
     let no_match = SerialMatchOutcome {
         result: None,
         full_serial: None,
         game_code: None,
         ambiguous_candidates: None,
+        malformed: false,
         detected_extension: None,
     };
 
-    let mut file = match fs::File::open(file_path) {
-        Ok(f) => f,
-        Err(_) => return no_match,
+    let extensions = crate::scanner::extension_set(analyzer.file_extensions());
+    let Ok(mut opened) = archive::open_rom(file_path, &extensions) else {
+        return no_match;
     };
-    let info = match analyzer.analyze(&mut file, &analysis_options) {
+    let analysis_options = AnalysisOptions::new()
+        .quick(true)
+        .file_path(&opened.analysis_path);
+    let info = match analyzer.analyze(opened.reader.as_mut(), &analysis_options) {
         Ok(i) => i,
         Err(_) => return no_match,
     };
 
     let detected_extension = info.extra.get("detected_extension").cloned();
 
-    let serial = match info.serial_number {
-        Some(s) => s,
-        None => {
-            return SerialMatchOutcome {
-                detected_extension,
-                ..no_match
-            };
-        }
+    let Some(serial) = info.serial_number else {
+        return SerialMatchOutcome {
+            detected_extension,
+            ..no_match
+        };
     };
 
-    let game_code = analyzer.extract_dat_game_code(&serial);
-    let lookup = index.match_by_serial(&serial, game_code.as_deref());
+    let content_type = info.extra.get("content_type").map(String::as_str);
+    let (canonical, lookup) = index.match_analyzer_serial_where(analyzer, &serial, |game| {
+        content_type_matches(content_type, &game.name)
+    });
+    let outcome = SerialMatchOutcome {
+        full_serial: Some(canonical.serial),
+        game_code: canonical.game_code,
+        detected_extension,
+        ..no_match
+    };
 
     match lookup {
         SerialLookupResult::Match(result) => SerialMatchOutcome {
             result: Some(result),
-            full_serial: Some(serial),
-            game_code,
-            ambiguous_candidates: None,
-            detected_extension,
+            ..outcome
         },
         SerialLookupResult::Ambiguous { candidates } => SerialMatchOutcome {
-            result: None,
-            full_serial: Some(serial),
-            game_code,
             ambiguous_candidates: Some(candidates),
-            detected_extension,
+            ..outcome
         },
-        SerialLookupResult::NotFound => SerialMatchOutcome {
-            result: None,
-            full_serial: Some(serial),
-            game_code,
-            ambiguous_candidates: None,
-            detected_extension,
+        SerialLookupResult::Malformed => SerialMatchOutcome {
+            malformed: true,
+            ..outcome
         },
+        SerialLookupResult::NotFound => outcome,
+    }
+}
+
+/// Whether a DAT entry is the kind of content an analyzer reported in
+/// `extra["content_type"]`. Updates and DLC share their base game's serial,
+/// so they only match entries carrying their DAT flag, and a `Base` game
+/// only matches entries carrying neither. Other content types match anything.
+fn content_type_matches(content_type: Option<&str>, game_name: &str) -> bool {
+    let has_flag = |flag: &str| {
+        parse_dat_name(game_name)
+            .flags
+            .iter()
+            .any(|f| f.eq_ignore_ascii_case(flag))
+    };
+    match content_type {
+        Some(BASE_CONTENT_TYPE) => !CONTENT_TYPE_FLAGS.iter().any(|(_, flag)| has_flag(flag)),
+        Some(content_type) => CONTENT_TYPE_FLAGS
+            .iter()
+            .find(|(kind, _)| *kind == content_type)
+            .is_none_or(|(_, flag)| has_flag(flag)),
+        None => true,
     }
 }
 
 /// Result of a hash matching attempt, carrying hash info regardless of match success.
-struct HashMatchOutcome {
-    result: Option<MatchResult>,
+pub(crate) struct HashMatchOutcome {
+    pub(crate) result: Option<MatchResult>,
     /// CRC32 of the hashed data
-    crc32: String,
+    pub(crate) crc32: String,
     /// Size of data that was hashed (after header stripping)
-    data_size: u64,
+    pub(crate) data_size: u64,
+    /// Why the file can't match, when the analyzer could tell without
+    /// hashing it (nothing was hashed then).
+    pub(crate) mismatch_reason: Option<String>,
 }
 
 /// Match a file by computing its CRC32 hash (with SHA1 fallback).
-fn match_by_hash(
+///
+/// CHDs listed as `disk` entries are matched by their header SHA1 first,
+/// which avoids decompressing the whole image. Multi-track discs and CUE
+/// sheets are then matched by all their tracks, before falling back to the
+/// file's own hash. A single-ROM archive is hashed by its decompressed ROM.
+/// Hashes the file records for its original data
+/// ([`RomAnalyzer::recorded_dat_hashes`]) are tried before anything is
+/// hashed, and files whose analyzer reports a
+/// [`RomAnalyzer::dat_mismatch_reason`] aren't hashed at all.
+pub(crate) fn match_by_hash(
     file_path: &Path,
     index: &DatIndex,
     analyzer: &dyn RomAnalyzer,
     progress: &dyn Fn(RenameProgress),
 ) -> Result<HashMatchOutcome, DatError> {
-    let mut file = fs::File::open(file_path)?;
+    let extensions = crate::scanner::extension_set(analyzer.file_extensions());
+    let mut opened = archive::open_rom(file_path, &extensions).map_err(|e| match e {
+        archive::ArchiveError::Io(e) => DatError::from(e),
+        e => DatError::cache(e.to_string()),
+    })?;
     let file_name = file_path
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("?")
         .to_string();
-
-    let hashes = hasher::compute_crc32_sha1_with_progress(
-        &mut file,
+    // An archived ROM is already in memory, so there's nothing to resume
+    let resume_path = opened.entry.is_none().then_some(file_path);
+    match_reader_by_hash(
+        opened.reader.as_mut(),
+        &file_name,
+        Some(&opened.analysis_path),
+        resume_path,
+        index,
         analyzer,
-        &|done, total| {
-            progress(RenameProgress::Hashing {
-                file_name: file_name.clone(),
-                bytes_done: done,
-                bytes_total: total,
-            });
-        },
-        Some(file_path),
-    )?;
+        progress,
+    )
+}
+
+/// [`match_by_hash`] for a file that's already open, such as a remote
+/// source. `analysis_path` is its local path, if it has one, and
+/// `resume_path` the file whose hashing can be checkpointed.
+pub(crate) fn match_reader_by_hash(
+    file: &mut dyn ReadSeek,
+    file_name: &str,
+    analysis_path: Option<&Path>,
+    resume_path: Option<&Path>,
+    index: &DatIndex,
+    analyzer: &dyn RomAnalyzer,
+    progress: &dyn Fn(RenameProgress),
+) -> Result<HashMatchOutcome, DatError> {
+    if file_name.to_ascii_lowercase().ends_with(".chd")
+        && let Ok(header) = chd_verify::read_header_hashes(file)
+        && let Some(result) = index.match_disk_sha1(&header.sha1)
+    {
+        return Ok(HashMatchOutcome {
+            result: Some(result),
+            crc32: String::new(),
+            data_size: 0,
+            mismatch_reason: None,
+        });
+    }
+    file.rewind()?;
+
+    if let Some(recorded) = analyzer.recorded_dat_hashes(file, analysis_path)
+        && let Some(result) = index.match_by_hash(recorded.data_size, &recorded)
+    {
+        return Ok(HashMatchOutcome {
+            result: Some(result),
+            crc32: recorded.crc32,
+            data_size: recorded.data_size,
+            mismatch_reason: None,
+        });
+    }
+    file.rewind()?;
+
+    if let Some(reason) = analyzer.dat_mismatch_reason(file, analysis_path) {
+        return Ok(HashMatchOutcome {
+            result: None,
+            crc32: String::new(),
+            data_size: 0,
+            mismatch_reason: Some(reason),
+        });
+    }
+    file.rewind()?;
+
+    let tracks = analyzer
+        .compute_track_hashes(file, HashAlgorithms::Crc32, analysis_path)
+        .unwrap_or_default();
+    if let Some(result) = index.match_tracks(&tracks) {
+        return Ok(HashMatchOutcome {
+            result: Some(result),
+            crc32: tracks[0].crc32.clone(),
+            data_size: tracks[0].data_size,
+            mismatch_reason: None,
+        });
+    }
+    if file_name.to_ascii_lowercase().ends_with(".cue")
+        && let Some(cue_path) = analysis_path
+        && let Ok(Some(result)) = dat_hasher::match_cue(index, &long_path(cue_path))
+    {
+        return Ok(HashMatchOutcome {
+            result: Some(result),
+            crc32: String::new(),
+            data_size: 0,
+            mismatch_reason: None,
+        });
+    }
+    file.rewind()?;
+
+    let on_progress = |done, total| {
+        progress(RenameProgress::Hashing {
+            file_name: file_name.to_string(),
+            bytes_done: done,
+            bytes_total: total,
+        });
+    };
+    let hashes = match resume_path {
+        Some(path) => hasher::compute_crc32_sha1_resumable(file, analyzer, &on_progress, path)?,
+        None => {
+            hasher::compute_crc32_sha1_with_progress(file, analyzer, &on_progress, analysis_path)?
+        }
+    };
 
     let crc32 = hashes.crc32.clone();
     let data_size = hashes.data_size;
@@ -1013,15 +1673,209 @@ fn match_by_hash(
         result,
         crc32,
         data_size,
+        mismatch_reason: None,
     })
 }
 
+/// Resolve renames that share a target path according to `strategy`.
+///
+/// Returns the renames to perform and a `(target, message)` entry for each
+/// conflicting target where at least one file was left untouched.
+pub fn resolve_conflicts(
+    renames: Vec<RenameAction>,
+    strategy: ConflictStrategy,
+) -> (Vec<RenameAction>, Vec<(PathBuf, String)>) {
+    let mut target_map: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+    for (i, rename) in renames.iter().enumerate() {
+        target_map.entry(rename.target.clone()).or_default().push(i);
+    }
+
+    let file_name = |r: &RenameAction| {
+        r.source
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    };
+
+    let mut groups: Vec<Vec<usize>> = target_map
+        .into_values()
+        .filter(|indices| indices.len() > 1)
+        .collect();
+    groups.sort_by_key(|indices| indices[0]);
+
+    let mut conflicts = Vec::new();
+    let mut dropped: std::collections::HashSet<usize> = std::collections::HashSet::new();
+    let mut retargeted: HashMap<usize, PathBuf> = HashMap::new();
+    let taken: std::collections::HashSet<PathBuf> =
+        renames.iter().map(|r| r.target.clone()).collect();
+
+    for indices in groups {
+        let target = renames[indices[0]].target.clone();
+        let names = indices
+            .iter()
+            .map(|&i| file_name(&renames[i]))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let keep = match strategy {
+            ConflictStrategy::Skip => None,
+            ConflictStrategy::KeepFirst => Some(indices[0]),
+            ConflictStrategy::KeepBest => indices
+                .iter()
+                .copied()
+                .min_by_key(|&i| match_strength(&renames[i].matched_by)),
+            ConflictStrategy::DedupSuffix => {
+                let mut n = 1;
+                for &i in &indices[1..] {
+                    let candidate = loop {
+                        let candidate = dedup_target(&target, n);
+                        n += 1;
+                        if !taken.contains(&candidate) && !long_path(&candidate).exists() {
+                            break candidate;
+                        }
+                    };
+                    retargeted.insert(i, candidate);
+                }
+                continue;
+            }
+        };
+
+        let message = match keep {
+            Some(k) => format!(
+                "Multiple files map to {:?}: {} (kept {})",
+                target.file_name().unwrap_or_default(),
+                names,
+                file_name(&renames[k])
+            ),
+            None => format!(
+                "Multiple files map to {:?}: {}",
+                target.file_name().unwrap_or_default(),
+                names
+            ),
+        };
+        conflicts.push((target, message));
+        dropped.extend(indices.into_iter().filter(|&i| Some(i) != keep));
+    }
+
+    let resolved = renames
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| !dropped.contains(i))
+        .map(|(i, mut r)| {
+            if let Some(target) = retargeted.remove(&i) {
+                r.target = target;
+            }
+            r
+        })
+        .collect();
+
+    (resolved, conflicts)
+}
+
+/// Rank a match method for conflict resolution (lower is stronger).
+fn match_strength(method: &MatchMethod) -> u8 {
+    match method {
+        MatchMethod::Override => 0,
+        MatchMethod::Sha1 => 1,
+        MatchMethod::Crc32 => 2,
+        MatchMethod::Serial => 3,
+    }
+}
+
+/// Build `"<stem> (<n>).<ext>"` alongside `target`.
+fn dedup_target(target: &Path, n: usize) -> PathBuf {
+    let stem = target
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = match target.extension() {
+        Some(ext) => format!("{} ({}).{}", stem, n, ext.to_string_lossy()),
+        None => format!("{} ({})", stem, n),
+    };
+    target.with_file_name(name)
+}
+
+/// Plan splits for every multi-game archive directly inside `folder`.
+/// Archives that can't be read are logged and skipped.
+fn plan_archive_splits(
+    folder: &Path,
+    analyzer: &dyn RomAnalyzer,
+    extensions: &std::collections::HashSet<String>,
+    index: &DatIndex,
+) -> Vec<ArchiveSplit> {
+    let archives = match archive::find_archives(folder) {
+        Ok(a) => a,
+        Err(e) => {
+            log::warn!("Error listing archives in {}: {}", folder.display(), e);
+            return Vec::new();
+        }
+    };
+    archives
+        .iter()
+        .filter(|path| archive::is_multi_game(path, extensions))
+        .filter_map(|path| match archive::plan_split(path, analyzer, index) {
+            Ok(split) => Some(split),
+            Err(e) => {
+                log::warn!("Skipping archive {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Rename a ROM file. A symlinked ROM is handled per `link_rename`: either
+/// the link is renamed, or its target is renamed and the link recreated
+/// under the new name, keeping a relative link relative.
+fn rename_rom_file(source: &Path, target: &Path, link_rename: LinkRename) -> std::io::Result<()> {
+    let is_link = fs::symlink_metadata(long_path(source))
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false);
+    if !is_link || link_rename == LinkRename::Link || source == target {
+        return rename_no_clobber(&long_path(source), &long_path(target));
+    }
+
+    let link_dest = fs::read_link(long_path(source))?;
+    let resolved = match source.parent() {
+        Some(dir) if link_dest.is_relative() => dir.join(&link_dest),
+        _ => link_dest.clone(),
+    };
+    let new_name = target.file_name().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "target has no file name")
+    })?;
+    let new_resolved = resolved.with_file_name(new_name);
+    if new_resolved != resolved && long_path(&new_resolved).exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("link target already exists: {}", new_resolved.display()),
+        ));
+    }
+
+    rename_no_clobber(&long_path(&resolved), &long_path(&new_resolved))?;
+    if let Err(e) = create_file_symlink(&link_dest.with_file_name(new_name), &long_path(target)) {
+        // Put the target back so the original link still resolves
+        let _ = fs::rename(long_path(&new_resolved), long_path(&resolved));
+        return Err(e);
+    }
+    fs::remove_file(long_path(source))
+}
+
+#[cfg(unix)]
+fn create_file_symlink(dest: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(dest, link)
+}
+
+#[cfg(windows)]
+fn create_file_symlink(dest: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(dest, link)
+}
+
 /// Execute a rename plan, performing the actual file renames and M3U operations.
 ///
 /// Execution order:
-/// 1. Rename single files (non-M3U)
+/// 1. Rename single files (non-M3U), then their companion files
 /// 2. Fix CUE/M3U references in non-M3U directories
 /// 3. Execute each M3U job (disc renames + CUE/M3U fix + playlist + folder rename)
+/// 4. Split multi-game archives
 pub fn execute_renames(plan: &RenamePlan) -> RenameSummary {
     let mut summary = RenameSummary {
         already_correct: plan.already_correct.len(),
@@ -1033,8 +1887,9 @@ pub fn execute_renames(plan: &RenamePlan) -> RenameSummary {
     }
 
     // Step 1: Rename single files (disc renames are handled by M3U jobs)
+    let mut renamed_sources: std::collections::HashSet<&Path> = std::collections::HashSet::new();
     for rename in &plan.renames {
-        if rename.target.exists() && rename.source != rename.target {
+        if long_path(&rename.target).exists() && rename.source != rename.target {
             summary.errors.push(format!(
                 "Target already exists: {}",
                 rename.target.display()
@@ -1042,8 +1897,18 @@ pub fn execute_renames(plan: &RenamePlan) -> RenameSummary {
             continue;
         }
 
-        match fs::rename(&rename.source, &rename.target) {
-            Ok(()) => summary.renamed += 1,
+        match rename_rom_file(&rename.source, &rename.target, plan.link_rename) {
+            Ok(()) => {
+                summary.renamed += 1;
+                renamed_sources.insert(&rename.source);
+                if let Err(e) = crate::sidecar::rename_sidecars(&rename.source, &rename.target) {
+                    summary.errors.push(format!(
+                        "Failed to move sidecar for {:?}: {}",
+                        rename.source.file_name().unwrap_or_default(),
+                        e,
+                    ));
+                }
+            }
             Err(e) => {
                 summary.errors.push(format!(
                     "Failed to rename {:?} -> {:?}: {}",
@@ -1055,6 +1920,29 @@ pub fn execute_renames(plan: &RenamePlan) -> RenameSummary {
         }
     }
 
+    // Step 1b: Move companion files of the renamed ROMs
+    for companion in &plan.companion_renames {
+        if !renamed_sources.contains(companion.rom.as_path()) {
+            continue;
+        }
+        if long_path(&companion.target).exists() {
+            summary.errors.push(format!(
+                "Companion target already exists: {}",
+                companion.target.display()
+            ));
+            continue;
+        }
+        match rename_no_clobber(&long_path(&companion.source), &long_path(&companion.target)) {
+            Ok(()) => summary.companions_renamed += 1,
+            Err(e) => summary.errors.push(format!(
+                "Failed to rename companion {:?} -> {:?}: {}",
+                companion.source.file_name().unwrap_or_default(),
+                companion.target.file_name().unwrap_or_default(),
+                e,
+            )),
+        }
+    }
+
     // Step 2: Fix CUE/M3U references in non-M3U directories
     let mut dir_rename_maps: HashMap<PathBuf, HashMap<String, String>> = HashMap::new();
     let mut fix_dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
@@ -1102,14 +1990,24 @@ pub fn execute_renames(plan: &RenamePlan) -> RenameSummary {
         let empty_map = HashMap::new();
         let rename_map = dir_rename_maps.get(dir).unwrap_or(&empty_map);
         summary.cue_files_updated +=
-            fix_cue_references_in_dir(dir, rename_map, &mut summary.errors);
+            fix_cue_references_in_dir(dir, rename_map, plan.trash.as_ref(), &mut summary.errors);
         summary.m3u_references_updated +=
-            fix_m3u_references_in_dir(dir, rename_map, &mut summary.errors);
+            fix_m3u_references_in_dir(dir, rename_map, plan.trash.as_ref(), &mut summary.errors);
+    }
+
+    // Step 2b: Record canonical names of sanitized files. Disc entries are
+    // written before the M3U folder renames below, which carry them along.
+    summary.canonical_names_recorded = plan.canonical_names.len();
+    if let Err(e) = record_canonical_names(plan) {
+        summary
+            .errors
+            .push(format!("Failed to record canonical names: {}", e));
+        summary.canonical_names_recorded = 0;
     }
 
     // Step 3: Execute M3U jobs (each handles disc renames + CUE/M3U fix + playlist + folder)
     for job in &plan.m3u_jobs {
-        let result = execute_m3u_rename(job);
+        let result = execute_m3u_rename(job, plan.link_rename, plan.trash.as_ref());
         summary.renamed += result.discs_renamed;
         summary.cue_files_updated += result.cue_files_updated;
         summary.m3u_references_updated += result.m3u_references_updated;
@@ -1122,12 +2020,68 @@ pub fn execute_renames(plan: &RenamePlan) -> RenameSummary {
         if result.folder_renamed {
             summary.m3u_folders_renamed += 1;
         }
+        if result.folder_created {
+            summary.m3u_folders_created += 1;
+        }
         summary.errors.extend(result.errors);
     }
 
+    // Step 4: Split multi-game archives into individual files
+    for split in &plan.archive_splits {
+        match archive::execute_split(split, plan.trash.as_ref()) {
+            Ok(_) => summary.archives_split += 1,
+            Err(e) => summary.errors.push(format!(
+                "Failed to split {:?}: {}",
+                split.archive.file_name().unwrap_or_default(),
+                e,
+            )),
+        }
+    }
+
     summary
 }
 
+/// Update the `canonical-names.toml` maps touched by a plan: entries for
+/// renamed files are dropped and sanitized targets are recorded.
+fn record_canonical_names(plan: &RenamePlan) -> std::io::Result<()> {
+    let mut maps: HashMap<PathBuf, CanonicalNames> = HashMap::new();
+    let file_name = |p: &Path| {
+        p.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+
+    for rename in &plan.renames {
+        let dir = rename.source.parent().unwrap_or(Path::new("."));
+        if !dir
+            .join(crate::sanitize::CANONICAL_NAMES_FILE_NAME)
+            .exists()
+        {
+            continue;
+        }
+        if !maps.contains_key(dir) {
+            maps.insert(dir.to_path_buf(), CanonicalNames::load(dir)?);
+        }
+        if let Some(map) = maps.get_mut(dir) {
+            map.remove(&file_name(&rename.source));
+        }
+    }
+    for (path, canonical) in &plan.canonical_names {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        if !maps.contains_key(dir) {
+            maps.insert(dir.to_path_buf(), CanonicalNames::load(dir)?);
+        }
+        if let Some(map) = maps.get_mut(dir) {
+            map.insert(&file_name(path), canonical);
+        }
+    }
+
+    for (dir, map) in &maps {
+        map.save(dir)?;
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Media file renaming (screenshots, covers, videos, etc.)
 // ---------------------------------------------------------------------------
@@ -1174,22 +2128,13 @@ pub fn plan_media_renames(
     media_dir: &Path,
     console_folder: &str,
 ) -> MediaRenamePlan {
-    // Build stem rename map: old file_stem → new file_stem
-    let mut stem_map: HashMap<String, String> = HashMap::new();
+    // Build stem rename map: old file_stem → new file_stem. Stems stay as
+    // OsString so media for non-UTF8 ROM names is matched exactly.
+    let mut stem_map: HashMap<OsString, OsString> = HashMap::new();
 
     for rename in &plan.renames {
-        let old_stem = rename
-            .source
-            .file_stem()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_string();
-        let new_stem = rename
-            .target
-            .file_stem()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_string();
+        let old_stem = rename.source.file_stem().unwrap_or_default().to_owned();
+        let new_stem = rename.target.file_stem().unwrap_or_default().to_owned();
         if old_stem != new_stem {
             stem_map.insert(old_stem, new_stem);
         }
@@ -1199,22 +2144,26 @@ pub fn plan_media_renames(
     for job in &plan.m3u_jobs {
         // Disc-level renames
         for disc in &job.discs {
-            let old_stem = disc
-                .file_path
-                .file_stem()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string();
+            let old_stem = disc.file_path.file_stem().unwrap_or_default().to_owned();
             let new_stem = Path::new(&disc.target_filename)
                 .file_stem()
                 .unwrap_or_default()
-                .to_string_lossy()
-                .to_string();
+                .to_owned();
             if old_stem != new_stem {
                 stem_map.insert(old_stem, new_stem);
             }
         }
 
+        // A new folder takes over the media of its first disc
+        if job.create_folder
+            && let Some(first) = job.discs.first()
+        {
+            stem_map.insert(
+                first.file_path.file_stem().unwrap_or_default().to_owned(),
+                job.source_folder.file_stem().unwrap_or_default().to_owned(),
+            );
+        }
+
         // Folder-level rename (old .m3u folder stem → new .m3u folder stem)
         if let Some(action) = plan_m3u_action(
             &job.source_folder,
@@ -1227,14 +2176,12 @@ pub fn plan_media_renames(
                     .source_folder
                     .file_stem()
                     .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string();
+                    .to_owned();
                 let new_folder_stem = action
                     .target_folder
                     .file_stem()
                     .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string();
+                    .to_owned();
                 if old_folder_stem != new_folder_stem {
                     stem_map.insert(old_folder_stem, new_folder_stem);
                 }
@@ -1242,7 +2189,7 @@ pub fn plan_media_renames(
         }
     }
 
-    plan_media_renames_from_stems(&stem_map, media_dir, console_folder)
+    plan_media_renames_from_os_stems(&stem_map, media_dir, console_folder)
 }
 
 /// Plan media file renames from an explicit stem rename map.
@@ -1257,6 +2204,20 @@ pub fn plan_media_renames_from_stems(
     stem_map: &HashMap<String, String>,
     media_dir: &Path,
     console_folder: &str,
+) -> MediaRenamePlan {
+    let stem_map: HashMap<OsString, OsString> = stem_map
+        .iter()
+        .map(|(old, new)| (old.into(), new.into()))
+        .collect();
+    plan_media_renames_from_os_stems(&stem_map, media_dir, console_folder)
+}
+
+/// [`plan_media_renames_from_stems`] with lossless stems: names are compared
+/// byte-for-byte, so non-UTF8 media names neither fail to match nor collide.
+fn plan_media_renames_from_os_stems(
+    stem_map: &HashMap<OsString, OsString>,
+    media_dir: &Path,
+    console_folder: &str,
 ) -> MediaRenamePlan {
     let console_media_dir = media_dir.join(console_folder);
     let mut result = MediaRenamePlan::default();
@@ -1297,11 +2258,14 @@ pub fn plan_media_renames_from_stems(
 
             // Check if this file starts with any old stem followed by "."
             for (old_stem, new_stem) in stem_map {
-                if file_name_str.starts_with(old_stem)
-                    && file_name_str.as_bytes().get(old_stem.len()) == Some(&b'.')
-                {
-                    let suffix = &file_name_str[old_stem.len()..];
-                    let new_name = format!("{new_stem}{suffix}");
+                let suffix = file_name
+                    .as_encoded_bytes()
+                    .strip_prefix(old_stem.as_encoded_bytes())
+                    .filter(|rest| rest.first() == Some(&b'.'))
+                    .and_then(|rest| std::str::from_utf8(rest).ok());
+                if let Some(suffix) = suffix {
+                    let mut new_name = new_stem.clone();
+                    new_name.push(suffix);
                     let target = subdir_path.join(&new_name);
 
                     if target == file_path {
@@ -1315,7 +2279,7 @@ pub fn plan_media_renames_from_stems(
                             file_path.clone(),
                             format!(
                                 "Multiple media files map to \"{}\": \"{}\" and \"{}\"",
-                                new_name,
+                                new_name.to_string_lossy(),
                                 prev_source
                                     .file_name()
                                     .unwrap_or_default()
@@ -1347,7 +2311,7 @@ pub fn execute_media_renames(plan: &MediaRenamePlan) -> MediaRenameSummary {
     let mut summary = MediaRenameSummary::default();
 
     for action in &plan.renames {
-        if action.target.exists() && action.source != action.target {
+        if long_path(&action.target).exists() && action.source != action.target {
             summary.errors.push(format!(
                 "Media target already exists: {}",
                 action.target.display()
@@ -1355,7 +2319,7 @@ pub fn execute_media_renames(plan: &MediaRenamePlan) -> MediaRenameSummary {
             continue;
         }
 
-        match fs::rename(&action.source, &action.target) {
+        match rename_no_clobber(&long_path(&action.source), &long_path(&action.target)) {
             Ok(()) => summary.renamed += 1,
             Err(e) => {
                 summary.errors.push(format!(
@@ -1377,6 +2341,7 @@ pub fn format_match_method(method: &MatchMethod) -> &'static str {
         MatchMethod::Serial => "serial",
         MatchMethod::Crc32 => "CRC32",
         MatchMethod::Sha1 => "SHA1",
+        MatchMethod::Override => "override",
     }
 }
 
@@ -1450,7 +2415,7 @@ impl RefFileFormat for CueFormat {
         // original line for `extra`, we store nothing there and reconstruct.
         // Actually, we need the file_type portion. We'll use a small trick:
         // store the trimmed suffix offset so rebuild_line can grab it.
-        let (filename, _file_type) = parse_cue_file_directive(trimmed)?;
+        let (filename, _file_type) = cue::parse_file_directive(trimmed)?;
         Some(RefLine {
             filename,
             extra: trimmed, // pass trimmed line so rebuild_line can re-parse
@@ -1459,7 +2424,7 @@ impl RefFileFormat for CueFormat {
 
     fn rebuild_line(&self, original_line: &str, new_filename: &str, ref_line: &RefLine) -> String {
         // Re-parse the file type from the stored trimmed line
-        let file_type = parse_cue_file_directive(ref_line.extra)
+        let file_type = cue::parse_file_directive(ref_line.extra)
             .map(|(_, ft)| ft)
             .unwrap_or_default();
         let trimmed = original_line.trim();
@@ -1601,6 +2566,7 @@ fn fix_references_in_dir(
     fmt: &dyn RefFileFormat,
     dir: &Path,
     rename_map: &HashMap<String, String>,
+    trash: Option<&Trash>,
     errors: &mut Vec<String>,
 ) -> usize {
     let entries = match fs::read_dir(dir) {
@@ -1620,7 +2586,7 @@ fn fix_references_in_dir(
             continue;
         }
 
-        match fix_single_ref_file(fmt, &path, dir, rename_map) {
+        match fix_single_ref_file(fmt, &path, dir, rename_map, trash) {
             Ok(true) => updated += 1,
             Ok(false) => {}
             Err(e) => errors.push(format!(
@@ -1638,11 +2604,13 @@ fn fix_references_in_dir(
 /// Fix broken references in a single reference file.
 ///
 /// Returns Ok(true) if the file was modified, Ok(false) if no changes were needed.
+/// With a trash, the original is copied there before it's rewritten.
 fn fix_single_ref_file(
     fmt: &dyn RefFileFormat,
     ref_path: &Path,
     dir: &Path,
     rename_map: &HashMap<String, String>,
+    trash: Option<&Trash>,
 ) -> Result<bool, String> {
     let content = fs::read_to_string(ref_path).map_err(|e| format!("read error: {}", e))?;
 
@@ -1688,6 +2656,11 @@ fn fix_single_ref_file(
 
     if changed {
         let new_content = fmt.serialize(&content, &output_lines);
+        if let Some(trash) = trash {
+            trash
+                .stash_copy(ref_path, &format!("{} references updated", fmt.label()))
+                .map_err(|e| format!("trash error: {}", e))?;
+        }
         fs::write(ref_path, &new_content).map_err(|e| format!("write error: {}", e))?;
     }
 
@@ -1710,47 +2683,24 @@ fn detect_broken_m3u_playlists(files: &[PathBuf]) -> Vec<PathBuf> {
 fn fix_cue_references_in_dir(
     dir: &Path,
     rename_map: &HashMap<String, String>,
+    trash: Option<&Trash>,
     errors: &mut Vec<String>,
 ) -> usize {
-    fix_references_in_dir(&CueFormat, dir, rename_map, errors)
+    fix_references_in_dir(&CueFormat, dir, rename_map, trash, errors)
 }
 
 /// Fix M3U playlist entries in a directory. Returns the number of .m3u files updated.
 fn fix_m3u_references_in_dir(
     dir: &Path,
     rename_map: &HashMap<String, String>,
+    trash: Option<&Trash>,
     errors: &mut Vec<String>,
 ) -> usize {
-    fix_references_in_dir(&M3uFormat, dir, rename_map, errors)
+    fix_references_in_dir(&M3uFormat, dir, rename_map, trash, errors)
 }
 
 // --- CUE-specific helpers (not duplicated, used only by CueFormat) ---
 
-/// Parse a CUE FILE directive, returning (filename, file_type).
-///
-/// Handles both quoted and unquoted filenames, case-insensitive keyword:
-///   FILE "filename.bin" BINARY
-///   File filename.bin BINARY
-fn parse_cue_file_directive(line: &str) -> Option<(String, String)> {
-    let upper = line.to_uppercase();
-    if !upper.starts_with("FILE ") {
-        return None;
-    }
-    let rest = &line[5..]; // skip "FILE "
-
-    if let Some(after_quote) = rest.strip_prefix('"') {
-        let end_quote = after_quote.find('"')?;
-        let filename = after_quote[..end_quote].to_string();
-        let remainder = after_quote[end_quote + 1..].trim().to_string();
-        Some((filename, remainder))
-    } else {
-        let mut parts = rest.splitn(2, ' ');
-        let filename = parts.next()?.to_string();
-        let remainder = parts.next().unwrap_or("").trim().to_string();
-        Some((filename, remainder))
-    }
-}
-
 /// Try to find the correct filename for a broken CUE FILE reference.
 ///
 /// Strategies (in order):
@@ -2042,3 +2992,7 @@ fn detect_misnamed_m3u(dir: &Path, expected_name: &str) -> Option<(PathBuf, Path
         None
     }
 }
+
+#[cfg(test)]
+#[path = "tests/rename_tests.rs"]
+mod tests;
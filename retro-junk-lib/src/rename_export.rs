@@ -0,0 +1,211 @@
+//! Export rename plans to CSV or JSON for offline review.
+//!
+//! A dry run on a large library prints thousands of lines; exporting the
+//! plan lets users sort and filter it in a spreadsheet before committing
+//! to the real rename.
+
+use std::io::Write;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::rename::{RenamePlan, SerialWarningKind, format_match_method};
+
+/// Output format for an exported plan, chosen from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Pick the format from a path's extension (`.csv` or `.json`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// One row of an exported rename plan.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanExportRow {
+    /// Console short name (e.g., "snes").
+    pub console: String,
+    /// "rename", "already-correct", "unmatched", "conflict", "broken-cue",
+    /// "broken-m3u", or "warning" (a diagnostic for a file with no other row).
+    pub action: &'static str,
+    pub source: String,
+    pub target: Option<String>,
+    pub game_name: Option<String>,
+    pub match_method: Option<&'static str>,
+    /// Diagnostics attached to this file, joined with "; ".
+    pub warnings: Option<String>,
+}
+
+/// Flatten a rename plan into export rows.
+///
+/// Discrepancies and serial warnings are attached to the row of the file
+/// they concern rather than emitted as separate rows.
+pub fn plan_export_rows(console: &str, plan: &RenamePlan) -> Vec<PlanExportRow> {
+    let mut rows = Vec::new();
+    let row = |action: &'static str, source: &Path| PlanExportRow {
+        console: console.to_string(),
+        action,
+        source: source.display().to_string(),
+        target: None,
+        game_name: None,
+        match_method: None,
+        warnings: None,
+    };
+
+    for r in &plan.renames {
+        rows.push(PlanExportRow {
+            target: Some(r.target.display().to_string()),
+            game_name: Some(r.game_name.clone()),
+            match_method: Some(format_match_method(&r.matched_by)),
+            ..row("rename", &r.source)
+        });
+    }
+    for job in &plan.m3u_jobs {
+        for disc in &job.discs {
+            rows.push(PlanExportRow {
+                target: Some(
+                    job.source_folder
+                        .join(&disc.target_filename)
+                        .display()
+                        .to_string(),
+                ),
+                game_name: Some(disc.game_name.clone()),
+                ..row("rename", &disc.file_path)
+            });
+        }
+    }
+    for path in &plan.already_correct {
+        rows.push(row("already-correct", path));
+    }
+    for u in &plan.unmatched {
+        rows.push(PlanExportRow {
+            warnings: u
+                .mismatch_reason
+                .clone()
+                .or_else(|| u.crc32.as_ref().map(|crc| format!("CRC32 {crc}"))),
+            ..row("unmatched", &u.file)
+        });
+    }
+    for (path, msg) in &plan.conflicts {
+        rows.push(PlanExportRow {
+            warnings: Some(msg.clone()),
+            ..row("conflict", path)
+        });
+    }
+    for path in &plan.broken_cue_files {
+        rows.push(row("broken-cue", path));
+    }
+    for path in &plan.broken_m3u_files {
+        rows.push(row("broken-m3u", path));
+    }
+
+    let mut attach = |file: &Path, warning: String| {
+        let file = file.display().to_string();
+        if let Some(r) = rows.iter_mut().find(|r| r.source == file) {
+            r.warnings = Some(match r.warnings.take() {
+                Some(existing) => format!("{existing}; {warning}"),
+                None => warning,
+            });
+        } else {
+            let mut r = row("warning", Path::new(&file));
+            r.warnings = Some(warning);
+            rows.push(r);
+        }
+    };
+    for d in &plan.discrepancies {
+        attach(
+            &d.file,
+            format!(
+                "serial matched \"{}\" but hash matched \"{}\"",
+                d.serial_game, d.hash_game
+            ),
+        );
+    }
+    for w in &plan.serial_warnings {
+        attach(&w.file, describe_serial_warning(&w.kind));
+    }
+
+    rows
+}
+
+/// Write export rows to `path` in the given format.
+pub fn write_plan_export(
+    path: &Path,
+    format: ExportFormat,
+    rows: &[PlanExportRow],
+) -> std::io::Result<()> {
+    match format {
+        ExportFormat::Json => write_json(path, rows),
+        ExportFormat::Csv => {
+            let mut out = csv::Writer::from_path(path)?;
+            out.write_record([
+                "console",
+                "action",
+                "source",
+                "target",
+                "game_name",
+                "match_method",
+                "warnings",
+            ])?;
+            for r in rows {
+                out.write_record([
+                    r.console.as_str(),
+                    r.action,
+                    r.source.as_str(),
+                    r.target.as_deref().unwrap_or(""),
+                    r.game_name.as_deref().unwrap_or(""),
+                    r.match_method.unwrap_or(""),
+                    r.warnings.as_deref().unwrap_or(""),
+                ])?;
+            }
+            out.flush()
+        }
+    }
+}
+
+/// Write `rows` to `path` as a pretty-printed JSON array.
+pub(crate) fn write_json<T: Serialize>(path: &Path, rows: &[T]) -> std::io::Result<()> {
+    let mut out = std::io::BufWriter::new(std::fs::File::create(path)?);
+    serde_json::to_writer_pretty(&mut out, rows).map_err(std::io::Error::other)?;
+    writeln!(out)?;
+    out.flush()
+}
+
+fn describe_serial_warning(kind: &SerialWarningKind) -> String {
+    match kind {
+        SerialWarningKind::NoMatch {
+            full_serial,
+            game_code: Some(code),
+        } => format!("serial \"{full_serial}\" (looked up as \"{code}\") not found in DAT"),
+        SerialWarningKind::NoMatch { full_serial, .. } => {
+            format!("serial \"{full_serial}\" not found in DAT")
+        }
+        SerialWarningKind::Ambiguous {
+            full_serial,
+            candidates,
+            ..
+        } => format!(
+            "serial \"{}\" matches {} DAT entries",
+            full_serial,
+            candidates.len()
+        ),
+        SerialWarningKind::Malformed { full_serial } => {
+            format!("serial \"{full_serial}\" is not in this platform's serial format")
+        }
+        SerialWarningKind::Missing => "no serial found (expected for this platform)".to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/rename_export_tests.rs"]
+mod tests;
@@ -0,0 +1,123 @@
+//! Per-file rename overrides loaded from `rename-overrides.toml`.
+//!
+//! Some files never match a DAT on their own: bad dumps, prototypes with
+//! unlisted hashes, or hacks the user wants named after the original. An
+//! overrides file in the console folder pins those files to a DAT entry:
+//!
+//! ```toml
+//! # Pin by DAT game name
+//! "smw beta.sfc" = "Super Mario World (USA)"
+//! # Or by the CRC32 of a DAT ROM entry
+//! "proto.sfc" = { crc32 = "b19ed489" }
+//! # Paths inside subfolders are relative to the console folder
+//! "FF7/disc1.cue" = { game = "Final Fantasy VII (USA) (Disc 1)" }
+//! ```
+//!
+//! `plan_renames` consults overrides before any serial or hash matching.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// File name of the overrides file inside a console folder.
+pub const OVERRIDES_FILE_NAME: &str = "rename-overrides.toml";
+
+/// The DAT entry a file is pinned to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameOverride {
+    /// Exact DAT game name.
+    Game(String),
+    /// CRC32 (lowercase hex) of a ROM entry in the DAT.
+    Crc32(String),
+}
+
+/// Overrides for one console folder, keyed by path relative to that folder.
+#[derive(Debug, Clone, Default)]
+pub struct RenameOverrides {
+    entries: HashMap<String, RenameOverride>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawOverride {
+    Game(String),
+    Table {
+        game: Option<String>,
+        crc32: Option<String>,
+    },
+}
+
+impl RenameOverrides {
+    /// Load `rename-overrides.toml` from a console folder.
+    ///
+    /// Returns empty overrides when the file does not exist.
+    pub fn load(folder: &Path) -> io::Result<Self> {
+        let path = folder.join(OVERRIDES_FILE_NAME);
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Parse overrides from TOML text.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let raw: HashMap<String, RawOverride> =
+            toml::from_str(contents).map_err(|e| e.to_string())?;
+
+        let mut entries = HashMap::new();
+        for (file, value) in raw {
+            let entry = match value {
+                RawOverride::Game(name)
+                | RawOverride::Table {
+                    game: Some(name),
+                    crc32: None,
+                } => RenameOverride::Game(name),
+                RawOverride::Table {
+                    game: None,
+                    crc32: Some(crc),
+                } => RenameOverride::Crc32(crc.trim().to_lowercase()),
+                RawOverride::Table { .. } => {
+                    return Err(format!(
+                        "override for '{}' must set exactly one of 'game' or 'crc32'",
+                        file
+                    ));
+                }
+            };
+            entries.insert(normalize_key(&file), entry);
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Override for `file`, looked up by its path relative to `folder`.
+    pub fn get(&self, folder: &Path, file: &Path) -> Option<&RenameOverride> {
+        let relative = file.strip_prefix(folder).unwrap_or(file);
+        self.entries
+            .get(&normalize_key(&relative.to_string_lossy()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Use forward slashes so keys written on one OS match paths on another.
+fn normalize_key(key: &str) -> String {
+    key.trim().replace('\\', "/")
+}
+
+#[cfg(test)]
+#[path = "tests/rename_overrides_tests.rs"]
+mod tests;
@@ -9,6 +9,7 @@ use retro_junk_dat::error::DatError;
 use retro_junk_dat::matcher::DatIndex;
 
 use crate::hasher::{self, PaddingSpec};
+use crate::trash::{self, Trash};
 
 /// CD pregap size: 2 seconds × 75 sectors/sec × 2352 bytes/sector = 352,800 bytes.
 const CD_PREGAP_SIZE: u64 = 352_800;
@@ -20,6 +21,9 @@ pub enum RepairMethod {
     AppendPadding { fill_byte: u8, bytes_added: u64 },
     /// Prepend fill bytes to the beginning of the file.
     PrependPadding { fill_byte: u8, bytes_added: u64 },
+    /// Remove a copier header (e.g. the 512-byte SMC header) that the DAT's
+    /// dumps don't have.
+    StripHeader { bytes_removed: u64 },
 }
 
 impl RepairMethod {
@@ -46,6 +50,9 @@ impl RepairMethod {
                     fill_byte
                 )
             }
+            RepairMethod::StripHeader { bytes_removed } => {
+                format!("remove copier header ({})", format_bytes(*bytes_removed))
+            }
         }
     }
 }
@@ -74,6 +81,8 @@ pub struct RepairPlan {
     pub no_match: Vec<PathBuf>,
     /// Files that encountered errors during planning.
     pub errors: Vec<(PathBuf, String)>,
+    /// Where originals are copied before they're repaired (with `use_trash`).
+    pub trash: Option<Trash>,
 }
 
 impl RepairPlan {
@@ -97,6 +106,11 @@ pub struct RepairOptions {
     pub limit: Option<usize>,
     /// Whether to create .bak backup files before modifying.
     pub create_backup: bool,
+    /// Copy each file into the console folder's `.retro-junk-trash/`
+    /// before modifying it, so it can be restored.
+    pub use_trash: bool,
+    /// Remove copier headers from files that match the DAT without them.
+    pub strip_headers: bool,
 }
 
 impl Default for RepairOptions {
@@ -105,6 +119,8 @@ impl Default for RepairOptions {
             dat_dir: None,
             limit: None,
             create_backup: true,
+            use_trash: false,
+            strip_headers: false,
         }
     }
 }
@@ -326,7 +342,7 @@ pub fn plan_repairs(
         let expected_data_size = get_expected_data_size(file_path, analyzer, &analysis_options);
 
         // Step 3: Compute current data_size (file_size - header)
-        let data_size = match get_data_size(file_path, analyzer) {
+        let (data_size, header_size) = match get_data_size(file_path, analyzer) {
             Ok(s) => s,
             Err(e) => {
                 errors.push((file_path.clone(), e.to_string()));
@@ -337,9 +353,30 @@ pub fn plan_repairs(
         // Check if file is trimmed (smaller than header-declared size)
         let is_trimmed = matches!(expected_data_size, Some(expected) if expected > data_size);
 
-        // If the as-is hash matches and the file is NOT trimmed, it's correct
-        if as_is_result.is_some() && !is_trimmed {
-            already_correct.push(file_path.clone());
+        // If the as-is hash matches and the file is NOT trimmed, it's correct,
+        // apart from a copier header the DAT's dumps don't have
+        if let Some(game_name) = &as_is_result
+            && !is_trimmed
+        {
+            if options.strip_headers
+                && header_size > 0
+                && header_is_strippable(file_path, analyzer, header_size)
+            {
+                repairable.push(RepairAction {
+                    file_path: file_path.clone(),
+                    game_name: game_name.clone(),
+                    method: RepairMethod::StripHeader {
+                        bytes_removed: header_size,
+                    },
+                    padding: PaddingSpec {
+                        prepend_size: 0,
+                        append_size: 0,
+                        fill_byte: 0x00,
+                    },
+                });
+            } else {
+                already_correct.push(file_path.clone());
+            }
             continue;
         }
 
@@ -420,6 +457,7 @@ pub fn plan_repairs(
         repairable,
         no_match,
         errors,
+        trash: options.use_trash.then(|| Trash::new(folder)),
     })
 }
 
@@ -439,14 +477,27 @@ fn hash_and_match(
     Ok(None)
 }
 
-/// Get the data size of a file (file_size - header_size).
-fn get_data_size(file_path: &Path, analyzer: &dyn RomAnalyzer) -> Result<u64, DatError> {
+/// Get the data size of a file (file_size - header_size) and the size of
+/// the header the analyzer skips for DAT matching.
+fn get_data_size(file_path: &Path, analyzer: &dyn RomAnalyzer) -> Result<(u64, u64), DatError> {
     let mut file = fs::File::open(file_path)?;
     let file_size = file.seek(io::SeekFrom::End(0))?;
     let skip = analyzer
         .dat_header_size(&mut file, file_size)
         .map_err(|e| DatError::cache(e.to_string()))?;
-    Ok(file_size - skip)
+    Ok((file_size.saturating_sub(skip), skip))
+}
+
+/// Whether removing the header alone leaves the bytes that were hashed, i.e.
+/// the data isn't also normalized (de-interleaved, byte-swapped) for hashing.
+fn header_is_strippable(file_path: &Path, analyzer: &dyn RomAnalyzer, header_size: u64) -> bool {
+    let Ok(mut file) = fs::File::open(file_path) else {
+        return false;
+    };
+    matches!(
+        analyzer.dat_chunk_normalizer(&mut file, header_size),
+        Ok(None)
+    )
 }
 
 /// Try to get expected data size from the analyzer (expected_size - header_size).
@@ -501,6 +552,17 @@ pub fn execute_repairs(plan: &RepairPlan, create_backup: bool) -> RepairSummary
             }
         }
 
+        if let Some(trash) = &plan.trash
+            && let Err(e) = trash.stash_copy(&action.file_path, "repaired")
+        {
+            summary.errors.push(format!(
+                "Failed to copy {} to the trash: {}",
+                action.file_path.display(),
+                e,
+            ));
+            continue;
+        }
+
         match &action.method {
             RepairMethod::AppendPadding {
                 fill_byte,
@@ -528,6 +590,18 @@ pub fn execute_repairs(plan: &RepairPlan, create_backup: bool) -> RepairSummary
                     ));
                 }
             },
+            RepairMethod::StripHeader { bytes_removed } => {
+                match strip_file_header(&action.file_path, *bytes_removed) {
+                    Ok(()) => summary.repaired += 1,
+                    Err(e) => {
+                        summary.errors.push(format!(
+                            "Failed to repair {}: {}",
+                            action.file_path.display(),
+                            e,
+                        ));
+                    }
+                }
+            }
         }
     }
 
@@ -573,6 +647,23 @@ fn prepend_to_file(path: &Path, fill_byte: u8, count: u64) -> io::Result<()> {
     Ok(())
 }
 
+/// Remove the first `count` bytes of a file using a temp file. Fails
+/// rather than overwrite a file already at the temp path.
+fn strip_file_header(path: &Path, count: u64) -> io::Result<()> {
+    let tmp_path = path.with_extension("repair_tmp");
+
+    let mut original = fs::File::open(path)?;
+    original.seek(io::SeekFrom::Start(count))?;
+    let mut tmp = trash::create_new(&tmp_path)?;
+    io::copy(&mut original, &mut tmp)?;
+    tmp.flush()?;
+    drop(tmp);
+    drop(original);
+
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
 fn is_power_of_two(n: u64) -> bool {
     n > 0 && (n & (n - 1)) == 0
 }
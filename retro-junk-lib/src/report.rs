@@ -0,0 +1,281 @@
+//! Plain-text per-folder reports for archival record-keeping.
+//!
+//! After a rename or verify, a `_retro-junk-report.txt` can be written into
+//! the console folder listing every file's status, when the report was made, and which
+//! DAT versions it was checked against — the same role NFO files play for
+//! DAT-verified sets.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::dat_verify::{FolderVerification, VerifyStatus};
+use crate::rename::{RenamePlan, SerialWarningKind};
+
+/// File name of the report written into each console folder.
+pub const REPORT_FILE_NAME: &str = "_retro-junk-report.txt";
+
+/// Status of one file in a report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileStatus {
+    /// Already had its canonical name.
+    Ok,
+    /// Renamed from the given file name.
+    Renamed { from: String },
+    /// Matched, but the rename wasn't carried out.
+    NotRenamed { to: String },
+    /// Another file claimed the same DAT entry.
+    Conflict,
+    /// No DAT entry matched.
+    Bad { crc32: Option<String> },
+    /// Resembles a DAT entry but doesn't match it, or couldn't be read.
+    Damaged { reason: String },
+}
+
+impl FileStatus {
+    /// Short label for the status column.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Renamed { .. } => "RENAMED",
+            Self::NotRenamed { .. } => "PENDING",
+            Self::Conflict => "CONFLICT",
+            Self::Bad { .. } | Self::Damaged { .. } => "BAD",
+        }
+    }
+}
+
+/// One file in a report.
+#[derive(Debug, Clone)]
+pub struct ReportEntry {
+    /// Path relative to the console folder, after any rename.
+    pub file: String,
+    pub status: FileStatus,
+    /// DAT game name, for matched files.
+    pub game_name: Option<String>,
+    /// The platform expects a serial but the header had none.
+    pub missing_serial: bool,
+}
+
+/// Report for a single console folder.
+#[derive(Debug, Clone)]
+pub struct FolderReport {
+    pub platform_name: String,
+    pub folder: PathBuf,
+    pub created: DateTime<Utc>,
+    pub dat_versions: Vec<String>,
+    pub entries: Vec<ReportEntry>,
+}
+
+impl FolderReport {
+    /// Build a report from a rename plan.
+    ///
+    /// Call after executing the plan: a planned rename counts as done when
+    /// its source file is gone.
+    pub fn from_rename_plan(
+        platform_name: &str,
+        folder: &Path,
+        plan: &RenamePlan,
+        created: DateTime<Utc>,
+    ) -> Self {
+        let planned: HashSet<&Path> = plan
+            .renames
+            .iter()
+            .map(|r| r.source.as_path())
+            .chain(
+                plan.m3u_jobs
+                    .iter()
+                    .flat_map(|j| j.discs.iter().map(|d| d.file_path.as_path())),
+            )
+            .collect();
+        let missing_serial: HashSet<&Path> = plan
+            .serial_warnings
+            .iter()
+            .filter(|w| matches!(w.kind, SerialWarningKind::Missing))
+            .map(|w| w.file.as_path())
+            .collect();
+
+        let mut entries = Vec::new();
+        for m in &plan.matched {
+            let status = if m.source == m.target {
+                FileStatus::Ok
+            } else if !planned.contains(m.source.as_path()) {
+                FileStatus::Conflict
+            } else if !m.source.exists() {
+                FileStatus::Renamed {
+                    from: file_name(&m.source),
+                }
+            } else {
+                FileStatus::NotRenamed {
+                    to: file_name(&m.target),
+                }
+            };
+            let path = match status {
+                FileStatus::Renamed { .. } => &m.target,
+                _ => &m.source,
+            };
+            entries.push(ReportEntry {
+                file: relative(folder, path),
+                status,
+                game_name: Some(m.game_name.clone()),
+                missing_serial: missing_serial.contains(m.source.as_path()),
+            });
+        }
+        for u in &plan.unmatched {
+            entries.push(ReportEntry {
+                file: relative(folder, &u.file),
+                status: FileStatus::Bad {
+                    crc32: u.crc32.clone(),
+                },
+                game_name: None,
+                missing_serial: missing_serial.contains(u.file.as_path()),
+            });
+        }
+        entries.sort_by_key(|e| e.file.to_lowercase());
+
+        Self {
+            platform_name: platform_name.to_string(),
+            folder: folder.to_path_buf(),
+            created,
+            dat_versions: plan.dat_versions.clone(),
+            entries,
+        }
+    }
+
+    /// Build a report from a verify run.
+    pub fn from_verification(
+        platform_name: &str,
+        folder: &Path,
+        verification: &FolderVerification,
+        created: DateTime<Utc>,
+    ) -> Self {
+        let mut entries: Vec<ReportEntry> = verification
+            .files
+            .iter()
+            .map(|f| {
+                let status = match f.status {
+                    VerifyStatus::Verified => FileStatus::Ok,
+                    VerifyStatus::Unknown => FileStatus::Bad {
+                        crc32: Some(f.crc32.clone()).filter(|c| !c.is_empty()),
+                    },
+                    VerifyStatus::BadDump | VerifyStatus::Overdump => FileStatus::Damaged {
+                        reason: f
+                            .detail
+                            .clone()
+                            .unwrap_or_else(|| f.status.label().to_lowercase()),
+                    },
+                };
+                ReportEntry {
+                    file: relative(folder, &f.path),
+                    status,
+                    game_name: f.game_name.clone(),
+                    missing_serial: false,
+                }
+            })
+            .chain(verification.errors.iter().map(|(path, msg)| ReportEntry {
+                file: relative(folder, path),
+                status: FileStatus::Damaged {
+                    reason: format!("unreadable: {}", msg),
+                },
+                game_name: None,
+                missing_serial: false,
+            }))
+            .collect();
+        entries.sort_by_key(|e| e.file.to_lowercase());
+
+        Self {
+            platform_name: platform_name.to_string(),
+            folder: folder.to_path_buf(),
+            created,
+            dat_versions: verification.dat_versions.clone(),
+            entries,
+        }
+    }
+
+    /// Number of entries with the given status label.
+    pub fn count(&self, label: &str) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.status.label() == label)
+            .count()
+    }
+
+    /// Render the report as text.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("retro-junk report: {}\n", self.platform_name));
+        out.push_str(&format!(
+            "Created:   {}\n",
+            self.created.format("%Y-%m-%d %H:%M:%S UTC")
+        ));
+        out.push_str(&format!("Folder:    {}\n", self.folder.display()));
+        if self.dat_versions.is_empty() {
+            out.push_str("DAT:       (none)\n");
+        }
+        for dat in &self.dat_versions {
+            out.push_str(&format!("DAT:       {}\n", dat));
+        }
+        out.push_str(&format!(
+            "Generator: retro-junk {}\n\n",
+            env!("CARGO_PKG_VERSION")
+        ));
+
+        for entry in &self.entries {
+            let mut line = format!("{:<9} {}", entry.status.label(), entry.file);
+            match &entry.status {
+                FileStatus::Renamed { from } => line.push_str(&format!("  (was {})", from)),
+                FileStatus::NotRenamed { to } => line.push_str(&format!("  (-> {})", to)),
+                FileStatus::Bad { crc32: Some(crc) } => {
+                    line.push_str(&format!("  (CRC32 {}, no DAT match)", crc))
+                }
+                FileStatus::Bad { crc32: None } => line.push_str("  (no DAT match)"),
+                FileStatus::Damaged { reason } => line.push_str(&format!("  ({})", reason)),
+                FileStatus::Ok | FileStatus::Conflict => {}
+            }
+            if entry.missing_serial {
+                line.push_str("  [missing serial]");
+            }
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+
+        let missing_serial = self.entries.iter().filter(|e| e.missing_serial).count();
+        out.push_str(&format!(
+            "\n{} files: {} OK, {} renamed, {} pending, {} conflict, {} bad, {} missing serial\n",
+            self.entries.len(),
+            self.count("OK"),
+            self.count("RENAMED"),
+            self.count("PENDING"),
+            self.count("CONFLICT"),
+            self.count("BAD"),
+            missing_serial,
+        ));
+        out
+    }
+
+    /// Write the report into its folder, replacing any previous report.
+    pub fn write(&self) -> io::Result<PathBuf> {
+        let path = self.folder.join(REPORT_FILE_NAME);
+        std::fs::write(&path, self.render())?;
+        Ok(path)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn relative(folder: &Path, path: &Path) -> String {
+    path.strip_prefix(folder)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+#[path = "tests/report_tests.rs"]
+mod tests;
@@ -0,0 +1,152 @@
+//! File names that FAT32 and exFAT cards accept.
+//!
+//! DAT names can hold characters these filesystems reject (`:`, `?`, `"`)
+//! or end in a dot, and SD cards for flash carts and handhelds are almost
+//! always FAT32 or exFAT. [`sanitize_file_name`] adjusts a name so it can be
+//! written there. When `rename` stores a sanitized name, it records the
+//! canonical one in a `canonical-names.toml` next to the file, so a later
+//! run still knows the file is correctly named:
+//!
+//! ```toml
+//! "Zelda - Link's Awakening (USA).gb" = "Zelda: Link's Awakening (USA).gb"
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+/// File name of the canonical-name map inside a ROM directory.
+pub const CANONICAL_NAMES_FILE_NAME: &str = "canonical-names.toml";
+
+/// Longest name, in bytes, that's safe on both filesystems.
+pub const MAX_NAME_BYTES: usize = 255;
+
+/// Names Windows (and so FAT tooling) reserves for devices.
+const RESERVED_STEMS: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Adjust `name` to what FAT32 and exFAT accept.
+///
+/// `Title: Subtitle` becomes `Title - Subtitle`; other rejected characters
+/// become `_`. Trailing dots and spaces are dropped, reserved device names
+/// get a `_` suffix, and names over 255 bytes are shortened before the
+/// extension. Names that are already valid come back unchanged.
+pub fn sanitize_file_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ':' if chars.peek() == Some(&' ') => {
+                // Avoid a double space when the colon follows a space
+                if !out.ends_with(' ') {
+                    out.push(' ');
+                }
+                out.push('-');
+            }
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => out.push('_'),
+            c if c.is_control() => out.push('_'),
+            c => out.push(c),
+        }
+    }
+    let trimmed = out.trim_end_matches(['.', ' ']).len();
+    out.truncate(trimmed);
+
+    let (stem, ext) = split_extension(&out);
+    if RESERVED_STEMS.iter().any(|r| stem.eq_ignore_ascii_case(r)) {
+        out = format!("{}_{}", stem, ext);
+    }
+
+    if out.len() > MAX_NAME_BYTES {
+        let (stem, ext) = split_extension(&out);
+        let mut end = MAX_NAME_BYTES.saturating_sub(ext.len()).min(stem.len());
+        while !stem.is_char_boundary(end) {
+            end -= 1;
+        }
+        out = format!("{}{}", stem[..end].trim_end_matches(['.', ' ']), ext);
+    }
+    out
+}
+
+/// Split off the extension, including its dot.
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(dot) if dot > 0 && name.len() - dot <= 8 => (&name[..dot], &name[dot..]),
+        _ => (name, ""),
+    }
+}
+
+/// Canonical names of sanitized files in one directory, keyed by the
+/// name on disk.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CanonicalNames {
+    entries: BTreeMap<String, String>,
+}
+
+impl CanonicalNames {
+    /// Load `canonical-names.toml` from `dir`.
+    ///
+    /// Returns an empty map when the file does not exist.
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let path = dir.join(CANONICAL_NAMES_FILE_NAME);
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map(|entries| Self { entries })
+                .map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}: {}", path.display(), e),
+                    )
+                }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write the map to `dir`, or remove the file when the map is empty.
+    pub fn save(&self, dir: &Path) -> io::Result<()> {
+        let path = dir.join(CANONICAL_NAMES_FILE_NAME);
+        if self.entries.is_empty() {
+            return match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        let contents = toml::to_string(&self.entries).map_err(io::Error::other)?;
+        std::fs::write(path, contents)
+    }
+
+    /// Canonical name recorded for the file named `on_disk`.
+    pub fn get(&self, on_disk: &str) -> Option<&str> {
+        self.entries.get(on_disk).map(String::as_str)
+    }
+
+    /// Record that `on_disk` stands for `canonical`. Recording a name that
+    /// equals its canonical form removes any entry for it.
+    pub fn insert(&mut self, on_disk: &str, canonical: &str) {
+        if on_disk == canonical {
+            self.entries.remove(on_disk);
+        } else {
+            self.entries
+                .insert(on_disk.to_string(), canonical.to_string());
+        }
+    }
+
+    /// Forget the entry for `on_disk`.
+    pub fn remove(&mut self, on_disk: &str) {
+        self.entries.remove(on_disk);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/sanitize_tests.rs"]
+mod tests;
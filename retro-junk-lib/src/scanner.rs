@@ -1,12 +1,216 @@
 //! Directory scanner for ROM collections.
 //!
-//! Handles both flat file layouts and ES-DE `.m3u` multi-disc directories.
+//! Handles flat file layouts, ES-DE `.m3u` multi-disc directories, and
+//! nested subfolders (e.g., per-letter `snes/A/`, `snes/B/`).
+//! Symlinks (including Windows junctions) are followed by default, with
+//! cycle detection; see [`SymlinkPolicy`].
 //! Used by both the CLI analyze and scraper commands.
+//!
+//! [`scan_game_entries`], [`scan_grouped_entries`], and [`GameEntry`] are
+//! meant to be used by the GUI and other tools too. Files that belong with
+//! a game file (a CUE's tracks, a GDI's tracks, patches and saves sharing
+//! its name) are found by [`GroupingRule`]s, which can be replaced or
+//! extended through [`ScanOptions::grouping`].
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use retro_junk_core::RomAnalyzer;
+use retro_junk_core::disc::cue;
+
+use crate::util::long_path;
+
+/// How many levels of plain subfolders [`scan_game_entries`] descends into,
+/// enough for per-letter layouts like `snes/A/`.
+pub const DEFAULT_SUBFOLDER_DEPTH: usize = 1;
+
+retro_junk_core::named_enum! {
+    /// What the scanners do with symlinks and junctions.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum SymlinkPolicy("symlink policy") {
+        /// Treat links like the files and folders they point to. Folders already
+        /// visited through another path are skipped, so link cycles terminate.
+        #[default]
+        Follow = "follow",
+        /// Ignore links entirely.
+        Skip = "skip",
+    }
+}
+
+/// Extensions of the patches, saves, cheats, and box art
+/// [`default_grouping_rules`] keeps with the game file sharing their name.
+pub const DEFAULT_COMPANION_EXTENSIONS: &[&str] =
+    &["ips", "bps", "ups", "sav", "srm", "cht", "png"];
+
+/// Decides which files in a folder belong with a game file.
+///
+/// Implement this to teach the scanner about other layouts; see
+/// [`ScanOptions::grouping`].
+pub trait GroupingRule: std::fmt::Debug + Send + Sync {
+    /// The files among `siblings` (every file in `primary`'s folder) that
+    /// belong with `primary`. `primary` itself may be among the siblings and
+    /// is ignored if returned.
+    fn companions(&self, primary: &Path, siblings: &[PathBuf]) -> Vec<PathBuf>;
+}
+
+/// The track files named by a CUE sheet's `FILE` lines, plus a `.bin`,
+/// `.img`, or `.iso` sharing the sheet's name.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CueSheetRule;
+
+impl GroupingRule for CueSheetRule {
+    fn companions(&self, primary: &Path, siblings: &[PathBuf]) -> Vec<PathBuf> {
+        if !has_extension(primary, "cue") {
+            return Vec::new();
+        }
+        let text = std::fs::read(long_path(primary))
+            .map(|b| String::from_utf8_lossy(&b).into_owned())
+            .unwrap_or_default();
+        let referenced: Vec<String> = text
+            .lines()
+            .filter_map(|l| cue::parse_file_directive(l.trim()))
+            .map(|(name, _)| name)
+            .collect();
+        let cue_stems = collect_cue_stems(std::slice::from_ref(&primary.to_path_buf()));
+        siblings
+            .iter()
+            .filter(|p| is_data_file_covered_by_cue(p, &cue_stems) || names_any(p, &referenced))
+            .cloned()
+            .collect()
+    }
+}
+
+/// The track files listed in a Dreamcast `.gdi`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GdiRule;
+
+impl GroupingRule for GdiRule {
+    fn companions(&self, primary: &Path, siblings: &[PathBuf]) -> Vec<PathBuf> {
+        if !has_extension(primary, "gdi") {
+            return Vec::new();
+        }
+        let text = std::fs::read(long_path(primary))
+            .map(|b| String::from_utf8_lossy(&b).into_owned())
+            .unwrap_or_default();
+        let referenced: Vec<String> = text.lines().skip(1).filter_map(gdi_track_file).collect();
+        siblings
+            .iter()
+            .filter(|p| names_any(p, &referenced))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Files sharing the game file's name with one of the given extensions,
+/// such as `Game.ips` and `Game.sav` next to `Game.sfc`.
+#[derive(Debug, Clone, Default)]
+pub struct SameStemRule {
+    /// Lowercase extensions, without the dot.
+    pub extensions: HashSet<String>,
+}
+
+impl SameStemRule {
+    pub fn new(extensions: &[&str]) -> Self {
+        Self {
+            extensions: extension_set(extensions),
+        }
+    }
+}
+
+impl GroupingRule for SameStemRule {
+    fn companions(&self, primary: &Path, siblings: &[PathBuf]) -> Vec<PathBuf> {
+        let Some(stem) = primary.file_stem() else {
+            return Vec::new();
+        };
+        siblings
+            .iter()
+            .filter(|p| p.file_stem() == Some(stem) && has_matching_extension(p, &self.extensions))
+            .cloned()
+            .collect()
+    }
+}
+
+/// CUE and GDI tracks, and patches and saves with the
+/// [`DEFAULT_COMPANION_EXTENSIONS`].
+pub fn default_grouping_rules() -> Vec<Arc<dyn GroupingRule>> {
+    vec![
+        Arc::new(CueSheetRule),
+        Arc::new(GdiRule),
+        Arc::new(SameStemRule::new(DEFAULT_COMPANION_EXTENSIONS)),
+    ]
+}
+
+/// Options for [`scan_game_entries_with`] and [`scan_grouped_entries`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ScanOptions {
+    /// Levels of plain (non-`.m3u`, non-hidden) subfolders to descend into.
+    /// 0 only looks at the console folder itself.
+    pub depth: usize,
+    /// How to treat symlinks and junctions.
+    pub symlinks: SymlinkPolicy,
+    /// Rules that find each game file's companions.
+    pub grouping: Vec<Arc<dyn GroupingRule>>,
+    /// Drop game files claimed as another file's companion (e.g. the
+    /// `(Track 1).bin` of a CUE sheet) instead of listing them as entries too.
+    pub fold_companions: bool,
+    /// Also list `.zip` and `.7z` files holding exactly one ROM, which are
+    /// read with [`crate::archive::open_rom`].
+    pub archives: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            depth: DEFAULT_SUBFOLDER_DEPTH,
+            symlinks: SymlinkPolicy::default(),
+            grouping: default_grouping_rules(),
+            fold_companions: false,
+            archives: false,
+        }
+    }
+}
+
+impl ScanOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn symlinks(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlinks = policy;
+        self
+    }
+
+    /// Replace the grouping rules.
+    pub fn grouping(mut self, rules: Vec<Arc<dyn GroupingRule>>) -> Self {
+        self.grouping = rules;
+        self
+    }
+
+    /// Add a grouping rule to the current ones.
+    pub fn rule(mut self, rule: impl GroupingRule + 'static) -> Self {
+        self.grouping.push(Arc::new(rule));
+        self
+    }
+
+    pub fn fold_companions(mut self, fold: bool) -> Self {
+        self.fold_companions = fold;
+        self
+    }
+
+    pub fn archives(mut self, archives: bool) -> Self {
+        self.archives = archives;
+        self
+    }
+}
 
 /// A logical game entry — either a single file or a multi-disc set from an .m3u folder.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +275,27 @@ impl GameEntry {
         }
     }
 
+    /// The entry's subfolder within `folder` as a `/`-terminated prefix
+    /// (e.g., `"A/"`), or an empty string for top-level entries.
+    ///
+    /// Frontends address nested entries by their relative path, so this is
+    /// prepended to filenames and media stems.
+    pub fn subfolder_prefix(&self, folder: &Path) -> String {
+        let dir = match self {
+            GameEntry::SingleFile(p) => p.parent(),
+            GameEntry::MultiDisc { files, .. } => files
+                .first()
+                .and_then(|f| f.parent())
+                .and_then(|d| d.parent()),
+        };
+        let Some(rel) = dir.and_then(|d| d.strip_prefix(folder).ok()) else {
+            return String::new();
+        };
+        rel.components()
+            .map(|c| format!("{}/", c.as_os_str().to_string_lossy()))
+            .collect()
+    }
+
     /// All ROM file paths in this entry (1 for single, N for multi-disc).
     pub fn all_files(&self) -> &[PathBuf] {
         match self {
@@ -80,36 +305,141 @@ impl GameEntry {
     }
 }
 
+/// A game entry with the companion files its [`GroupingRule`]s found.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupedEntry {
+    pub entry: GameEntry,
+    /// Companion files of the entry's files, sorted, without duplicates.
+    pub companions: Vec<PathBuf>,
+}
+
+impl GroupedEntry {
+    /// The entry's files followed by its companions.
+    pub fn files_with_companions(&self) -> impl Iterator<Item = &PathBuf> {
+        self.entry.all_files().iter().chain(&self.companions)
+    }
+}
+
 /// Scan a console folder and return logical game entries.
 ///
 /// Handles:
 /// - Top-level ROM files matching the given extensions
 /// - `.m3u` subdirectories containing disc images (ES-DE convention)
+/// - Plain subfolders up to [`DEFAULT_SUBFOLDER_DEPTH`] levels deep
 /// - CUE/BIN deduplication (`.bin`/`.img`/`.iso` files paired with a `.cue` are filtered)
 pub fn scan_game_entries(
     folder: &Path,
     extensions: &HashSet<String>,
 ) -> std::io::Result<Vec<GameEntry>> {
+    scan_game_entries_with(folder, extensions, &ScanOptions::default())
+}
+
+/// Like [`scan_game_entries`], with explicit subfolder depth and symlink
+/// handling.
+pub fn scan_game_entries_with(
+    folder: &Path,
+    extensions: &HashSet<String>,
+    options: &ScanOptions,
+) -> std::io::Result<Vec<GameEntry>> {
+    Ok(scan_grouped_entries(folder, extensions, options)?
+        .into_iter()
+        .map(|g| g.entry)
+        .collect())
+}
+
+/// Like [`scan_game_entries_with`], keeping the companion files found by
+/// `options.grouping` with each entry.
+pub fn scan_grouped_entries(
+    folder: &Path,
+    extensions: &HashSet<String>,
+    options: &ScanOptions,
+) -> std::io::Result<Vec<GroupedEntry>> {
+    let mut visited = HashSet::new();
+    let mut game_entries =
+        scan_directory(folder, extensions, options, options.depth, &mut visited)?;
+    game_entries.sort_by(|a, b| a.entry.sort_key().cmp(b.entry.sort_key()));
+    Ok(game_entries)
+}
+
+/// Returns true the first time a directory is seen. Directories are keyed by
+/// their canonical path, so a folder reached again through a link is
+/// reported as already visited.
+pub(crate) fn mark_visited(dir: &Path, visited: &mut HashSet<PathBuf>) -> bool {
+    let key = std::fs::canonicalize(long_path(dir)).unwrap_or_else(|_| dir.to_path_buf());
+    visited.insert(key)
+}
+
+/// Whether a directory entry is a symlink or junction (not following it).
+pub(crate) fn is_link(entry: &std::fs::DirEntry) -> bool {
+    entry.file_type().map(|t| t.is_symlink()).unwrap_or(false)
+}
+
+/// Collect the entries of one directory, recursing into subfolders while
+/// `depth` allows.
+fn scan_directory(
+    folder: &Path,
+    extensions: &HashSet<String>,
+    options: &ScanOptions,
+    depth: usize,
+    visited: &mut HashSet<PathBuf>,
+) -> std::io::Result<Vec<GroupedEntry>> {
     let mut game_entries: Vec<GameEntry> = Vec::new();
-    let mut dir_entries: Vec<std::fs::DirEntry> = std::fs::read_dir(folder)?.flatten().collect();
-    dir_entries.sort_by_key(|e| e.path());
+    let mut nested: Vec<GroupedEntry> = Vec::new();
+    let mut dir_files: Vec<PathBuf> = Vec::new();
+    let mut dir_entries: Vec<std::fs::DirEntry> =
+        std::fs::read_dir(long_path(folder))?.flatten().collect();
+    dir_entries.sort_by_key(|e| e.file_name());
+    mark_visited(folder, visited);
 
     for entry in &dir_entries {
-        let path = entry.path();
-        if path.is_file() {
-            if has_matching_extension(&path, extensions) {
-                game_entries.push(GameEntry::SingleFile(path));
+        if options.symlinks == SymlinkPolicy::Skip && is_link(entry) {
+            continue;
+        }
+        // Built from the unprefixed folder so paths stay displayable; names
+        // are kept as OsString so non-UTF8 files survive to the rename step
+        let name = entry.file_name();
+        let path = folder.join(&name);
+        let fs_path = long_path(&path);
+        if fs_path.is_file() {
+            if has_matching_extension(&path, extensions)
+                || (options.archives
+                    && crate::archive::is_archive(&path)
+                    && crate::archive::single_rom_entry(&path, extensions).is_some())
+            {
+                game_entries.push(GameEntry::SingleFile(path.clone()));
             }
-        } else if path.is_dir()
-            && let Some(name) = path.file_name().and_then(|n| n.to_str())
-            && name.ends_with(".m3u")
-        {
-            let disc_files = collect_matching_files(&path, extensions);
-            if !disc_files.is_empty() {
-                game_entries.push(GameEntry::MultiDisc {
-                    name: name.to_string(),
-                    files: disc_files,
-                });
+            dir_files.push(path);
+        } else if fs_path.is_dir() {
+            if !mark_visited(&path, visited) {
+                // Already scanned through another path (or a link cycle)
+                continue;
+            }
+            if has_extension(&path, "m3u") {
+                let all_files = collect_files(&path, options.symlinks);
+                let disc_files: Vec<PathBuf> = all_files
+                    .iter()
+                    .filter(|p| has_matching_extension(p, extensions))
+                    .cloned()
+                    .collect();
+                if !disc_files.is_empty() {
+                    let (files, companions) = group_files(disc_files, &all_files, options);
+                    nested.push(GroupedEntry {
+                        entry: GameEntry::MultiDisc {
+                            name: name.to_string_lossy().into_owned(),
+                            files,
+                        },
+                        companions,
+                    });
+                }
+            } else if depth > 0
+                && !name.as_encoded_bytes().starts_with(b".")
+                && name != crate::one_g1r::DUPLICATES_DIR
+            {
+                // Unreadable subfolders are skipped rather than failing the scan
+                if let Ok(entries) = scan_directory(&path, extensions, options, depth - 1, visited)
+                {
+                    nested.extend(entries);
+                }
             }
         }
     }
@@ -130,9 +460,134 @@ pub fn scan_game_entries(
         });
     }
 
-    game_entries.sort_by(|a, b| a.sort_key().cmp(b.sort_key()));
+    let singles: Vec<PathBuf> = game_entries
+        .into_iter()
+        .filter_map(|e| match e {
+            GameEntry::SingleFile(p) => Some(p),
+            GameEntry::MultiDisc { .. } => None,
+        })
+        .collect();
+    let mut grouped: Vec<GroupedEntry> = Vec::new();
+    let mut claimed: HashSet<PathBuf> = HashSet::new();
+    let with_companions: Vec<(PathBuf, Vec<PathBuf>)> = singles
+        .into_iter()
+        .map(|p| {
+            let companions = companions_of(&p, &dir_files, options);
+            claimed.extend(companions.iter().cloned());
+            (p, companions)
+        })
+        .collect();
+    for (path, companions) in with_companions {
+        if options.fold_companions && claimed.contains(&path) {
+            continue;
+        }
+        grouped.push(GroupedEntry {
+            entry: GameEntry::SingleFile(path),
+            companions,
+        });
+    }
+
+    grouped.extend(nested);
+    Ok(grouped)
+}
 
-    Ok(game_entries)
+/// Companions of one file according to every rule, sorted and deduplicated.
+fn companions_of(primary: &Path, siblings: &[PathBuf], options: &ScanOptions) -> Vec<PathBuf> {
+    let mut companions: Vec<PathBuf> = options
+        .grouping
+        .iter()
+        .flat_map(|rule| rule.companions(primary, siblings))
+        .filter(|p| p != primary)
+        .collect();
+    companions.sort();
+    companions.dedup();
+    companions
+}
+
+/// Split a `.m3u` folder's disc files into discs and the companions of all
+/// of them, dropping claimed discs when `options.fold_companions` is set.
+fn group_files(
+    discs: Vec<PathBuf>,
+    all_files: &[PathBuf],
+    options: &ScanOptions,
+) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut companions: Vec<PathBuf> = discs
+        .iter()
+        .flat_map(|d| companions_of(d, all_files, options))
+        .collect();
+    companions.sort();
+    companions.dedup();
+    let discs = if options.fold_companions {
+        let kept: Vec<PathBuf> = discs
+            .iter()
+            .filter(|d| !companions.contains(d))
+            .cloned()
+            .collect();
+        // A folder made only of claimed files keeps them all
+        if kept.is_empty() { discs } else { kept }
+    } else {
+        discs
+    };
+    companions.retain(|c| !discs.contains(c));
+    (discs, companions)
+}
+
+/// Find the extracted game folders `analyzer` reads with
+/// [`RomAnalyzer::analyze_dir`], searching plain subfolders of `folder` as
+/// deep as `options.depth` allows. A game folder's own subfolders aren't
+/// searched.
+pub fn find_game_dirs(
+    folder: &Path,
+    analyzer: &dyn RomAnalyzer,
+    options: &ScanOptions,
+) -> Vec<PathBuf> {
+    let mut visited = HashSet::new();
+    let mut found = Vec::new();
+    collect_game_dirs(
+        folder,
+        analyzer,
+        options,
+        options.depth,
+        &mut visited,
+        &mut found,
+    );
+    found
+}
+
+fn collect_game_dirs(
+    folder: &Path,
+    analyzer: &dyn RomAnalyzer,
+    options: &ScanOptions,
+    depth: usize,
+    visited: &mut HashSet<PathBuf>,
+    found: &mut Vec<PathBuf>,
+) {
+    let Ok(entries) = std::fs::read_dir(long_path(folder)) else {
+        return;
+    };
+    let mut entries: Vec<std::fs::DirEntry> = entries.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+    mark_visited(folder, visited);
+
+    for entry in &entries {
+        if options.symlinks == SymlinkPolicy::Skip && is_link(entry) {
+            continue;
+        }
+        let name = entry.file_name();
+        let path = folder.join(&name);
+        if name.as_encoded_bytes().starts_with(b".")
+            || has_extension(&path, "m3u")
+            || !long_path(&path).is_dir()
+            || !mark_visited(&path, visited)
+        {
+            continue;
+        }
+        if analyzer.is_game_dir(&path) {
+            found.push(path);
+        } else if depth > 0 {
+            collect_game_dirs(&path, analyzer, options, depth - 1, visited, found);
+        }
+    }
 }
 
 /// Build the extension set from an analyzer's file_extensions().
@@ -140,6 +595,12 @@ pub fn extension_set(extensions: &[&str]) -> HashSet<String> {
     extensions.iter().map(|e| e.to_lowercase()).collect()
 }
 
+/// Check a path's extension (case-insensitive) without requiring a UTF-8 name.
+fn has_extension(path: &Path, ext: &str) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case(ext))
+}
+
 /// Check if a path has an extension in the allowed set.
 fn has_matching_extension(path: &Path, extensions: &HashSet<String>) -> bool {
     path.extension()
@@ -148,18 +609,19 @@ fn has_matching_extension(path: &Path, extensions: &HashSet<String>) -> bool {
         .unwrap_or(false)
 }
 
-/// Collect all files with matching extensions from a directory (sorted).
-fn collect_matching_files(dir: &Path, extensions: &HashSet<String>) -> Vec<PathBuf> {
-    let entries = match std::fs::read_dir(dir) {
+/// Collect all files in a directory (sorted).
+fn collect_files(dir: &Path, symlinks: SymlinkPolicy) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(long_path(dir)) {
         Ok(e) => e,
         Err(_) => return Vec::new(),
     };
 
     let mut files: Vec<PathBuf> = entries
         .flatten()
+        .filter(|e| symlinks == SymlinkPolicy::Follow || !is_link(e))
         .filter_map(|e| {
-            let path = e.path();
-            if path.is_file() && has_matching_extension(&path, extensions) {
+            let path = dir.join(e.file_name());
+            if long_path(&path).is_file() {
                 Some(path)
             } else {
                 None
@@ -171,21 +633,38 @@ fn collect_matching_files(dir: &Path, extensions: &HashSet<String>) -> Vec<PathB
     files
 }
 
+/// Whether the file name of `path` is one of `names` (case-insensitive).
+/// Names may carry a relative directory, which is ignored.
+fn names_any(path: &Path, names: &[String]) -> bool {
+    let Some(file_name) = path.file_name().map(|n| n.to_string_lossy()) else {
+        return false;
+    };
+    names.iter().any(|name| {
+        let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+        name.eq_ignore_ascii_case(&file_name)
+    })
+}
+
+/// The file named by a GDI track line: `number lba type sector_size file offset`,
+/// where the file name may be quoted.
+fn gdi_track_file(line: &str) -> Option<String> {
+    let mut rest = line.trim();
+    for _ in 0..4 {
+        let (_, tail) = rest.split_once(char::is_whitespace)?;
+        rest = tail.trim_start();
+    }
+    match rest.strip_prefix('"') {
+        Some(quoted) => quoted.split_once('"').map(|(name, _)| name.to_string()),
+        None => rest.split_whitespace().next().map(str::to_string),
+    }
+}
+
 /// Collect the lowercase stems of all .cue files in a list of paths.
 fn collect_cue_stems(files: &[PathBuf]) -> HashSet<String> {
     files
         .iter()
-        .filter(|p| {
-            p.extension()
-                .and_then(|e| e.to_str())
-                .map(|e| e.eq_ignore_ascii_case("cue"))
-                .unwrap_or(false)
-        })
-        .filter_map(|p| {
-            p.file_stem()
-                .and_then(|s| s.to_str())
-                .map(|s| s.to_lowercase())
-        })
+        .filter(|p| has_extension(p, "cue"))
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_lowercase()))
         .collect()
 }
 
@@ -201,8 +680,11 @@ fn is_data_file_covered_by_cue(path: &Path, cue_stems: &HashSet<String>) -> bool
     }
     let stem = path
         .file_stem()
-        .and_then(|s| s.to_str())
-        .map(|s| s.to_lowercase())
+        .map(|s| s.to_string_lossy().to_lowercase())
         .unwrap_or_default();
     cue_stems.contains(&stem)
 }
+
+#[cfg(test)]
+#[path = "tests/scanner_tests.rs"]
+mod tests;
@@ -0,0 +1,185 @@
+//! Per-ROM sidecar metadata files.
+//!
+//! A sidecar sits next to a ROM (`Game (USA).sfc.retro-junk.toml`) and
+//! records what retro-junk learned about it: header identification, hashes,
+//! the DAT entry it matched, and scraped metadata. Other tools can read it
+//! without knowing ROM formats, and each command fills in its own section
+//! while preserving the others.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use retro_junk_core::RomIdentification;
+use retro_junk_dat::matcher::FileHashes;
+
+use crate::trash::rename_no_clobber;
+use crate::util::long_path;
+
+retro_junk_core::named_enum! {
+    /// Sidecar file format.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum SidecarFormat("sidecar format") {
+        #[default]
+        Toml = "toml",
+        Json = "json",
+    }
+}
+
+impl SidecarFormat {
+    /// Suffix appended to the ROM filename.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Self::Toml => ".retro-junk.toml",
+            Self::Json => ".retro-junk.json",
+        }
+    }
+}
+
+/// Contents of a sidecar file. Every section is optional.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RomSidecar {
+    /// retro-junk version that last wrote the file.
+    #[serde(default)]
+    pub generator: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identification: Option<RomIdentification>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hashes: Option<SidecarHashes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dat_match: Option<SidecarDatMatch>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scrape: Option<SidecarScrape>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SidecarHashes {
+    pub crc32: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub md5: Option<String>,
+    /// Size of the hashed data (after header stripping).
+    pub data_size: u64,
+}
+
+impl From<&FileHashes> for SidecarHashes {
+    fn from(h: &FileHashes) -> Self {
+        Self {
+            crc32: h.crc32.clone(),
+            sha1: h.sha1.clone(),
+            md5: h.md5.clone(),
+            data_size: h.data_size,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SidecarDatMatch {
+    pub game_name: String,
+    /// How the match was made ("serial", "CRC32", "SHA1", "override").
+    pub method: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SidecarScrape {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub developer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genre: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub players: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_date: Option<String>,
+    /// Media type → downloaded file.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub media: BTreeMap<String, PathBuf>,
+}
+
+/// Path of the sidecar for `rom` in the given format.
+pub fn sidecar_path(rom: &Path, format: SidecarFormat) -> PathBuf {
+    let mut name = rom.file_name().unwrap_or_default().to_os_string();
+    name.push(format.suffix());
+    rom.with_file_name(name)
+}
+
+/// Read the sidecar for `rom`, trying TOML then JSON.
+///
+/// Returns `Ok(None)` when no sidecar exists.
+pub fn read_sidecar(rom: &Path) -> io::Result<Option<RomSidecar>> {
+    for format in [SidecarFormat::Toml, SidecarFormat::Json] {
+        let path = sidecar_path(rom, format);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        let sidecar = match format {
+            SidecarFormat::Toml => toml::from_str(&contents).map_err(invalid_data(&path))?,
+            SidecarFormat::Json => serde_json::from_str(&contents).map_err(invalid_data(&path))?,
+        };
+        return Ok(Some(sidecar));
+    }
+    Ok(None)
+}
+
+/// Write `sidecar` next to `rom`.
+pub fn write_sidecar(rom: &Path, sidecar: &RomSidecar, format: SidecarFormat) -> io::Result<()> {
+    let path = sidecar_path(rom, format);
+    let contents = match format {
+        SidecarFormat::Toml => toml::to_string_pretty(sidecar).map_err(io::Error::other)?,
+        SidecarFormat::Json => {
+            serde_json::to_string_pretty(sidecar).map_err(io::Error::other)? + "\n"
+        }
+    };
+    std::fs::write(path, contents)
+}
+
+/// Load the existing sidecar (if any), apply `update`, and write it back.
+///
+/// Sections the update doesn't touch are preserved, so analyze, rename, and
+/// scrape can each contribute their part.
+pub fn update_sidecar(
+    rom: &Path,
+    format: SidecarFormat,
+    update: impl FnOnce(&mut RomSidecar),
+) -> io::Result<()> {
+    let mut sidecar = read_sidecar(rom)?.unwrap_or_default();
+    update(&mut sidecar);
+    sidecar.generator = format!("retro-junk {}", env!("CARGO_PKG_VERSION"));
+    write_sidecar(rom, &sidecar, format)
+}
+
+/// Move a ROM's sidecars along with a rename. Fails without touching an
+/// existing sidecar at the new name.
+pub fn rename_sidecars(from: &Path, to: &Path) -> io::Result<()> {
+    for format in [SidecarFormat::Toml, SidecarFormat::Json] {
+        let src = sidecar_path(from, format);
+        if long_path(&src).exists() {
+            rename_no_clobber(&long_path(&src), &long_path(&sidecar_path(to, format)))?;
+        }
+    }
+    Ok(())
+}
+
+fn invalid_data<E: std::fmt::Display>(path: &Path) -> impl FnOnce(E) -> io::Error + '_ {
+    move |e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), e),
+        )
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/sidecar_tests.rs"]
+mod tests;
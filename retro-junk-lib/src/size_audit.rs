@@ -0,0 +1,131 @@
+//! Library-wide size audit.
+//!
+//! Runs only the cheap header analysis (quick mode, no hashing) and compares
+//! each file's size to the size its header declares, tallying the
+//! [`SizeVerdict`]s so over- and underdumps can be found without a DAT.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use retro_junk_core::{AnalysisOptions, RomAnalyzer};
+
+use crate::display::{SizeVerdict, compute_size_verdict};
+use crate::scanner::{self, GameEntry};
+
+/// A file whose size doesn't match its header.
+#[derive(Debug, Clone)]
+pub struct SizeProblem {
+    pub path: PathBuf,
+    pub verdict: SizeVerdict,
+}
+
+/// Verdict counts for a folder (or a whole library, via [`merge`](Self::merge)).
+#[derive(Debug, Clone, Default)]
+pub struct SizeAudit {
+    pub ok: usize,
+    pub trimmed: usize,
+    pub truncated: usize,
+    pub copier_header: usize,
+    pub oversized: usize,
+    /// The header doesn't declare a size.
+    pub unknown: usize,
+    /// The file couldn't be opened or analyzed.
+    pub failed: usize,
+    /// Every non-OK verdict, in scan order.
+    pub problems: Vec<SizeProblem>,
+}
+
+impl SizeAudit {
+    /// Record one file's verdict (`None` when the size is unknown).
+    pub fn record(&mut self, path: &Path, verdict: Option<SizeVerdict>) {
+        let Some(verdict) = verdict else {
+            self.unknown += 1;
+            return;
+        };
+        match verdict {
+            SizeVerdict::Ok => {
+                self.ok += 1;
+                return;
+            }
+            SizeVerdict::Trimmed { .. } => self.trimmed += 1,
+            SizeVerdict::Truncated { .. } => self.truncated += 1,
+            SizeVerdict::CopierHeader => self.copier_header += 1,
+            SizeVerdict::Oversized { .. } => self.oversized += 1,
+        }
+        self.problems.push(SizeProblem {
+            path: path.to_path_buf(),
+            verdict,
+        });
+    }
+
+    pub fn merge(&mut self, other: SizeAudit) {
+        self.ok += other.ok;
+        self.trimmed += other.trimmed;
+        self.truncated += other.truncated;
+        self.copier_header += other.copier_header;
+        self.oversized += other.oversized;
+        self.unknown += other.unknown;
+        self.failed += other.failed;
+        self.problems.extend(other.problems);
+    }
+
+    /// Number of files audited.
+    pub fn total(&self) -> usize {
+        self.ok
+            + self.trimmed
+            + self.truncated
+            + self.copier_header
+            + self.oversized
+            + self.unknown
+            + self.failed
+    }
+}
+
+/// Audit every ROM in `folder`, up to `limit` game entries.
+pub fn audit_folder(
+    folder: &Path,
+    analyzer: &dyn RomAnalyzer,
+    limit: Option<usize>,
+) -> std::io::Result<SizeAudit> {
+    let extensions = scanner::extension_set(analyzer.file_extensions());
+    let mut entries = scanner::scan_game_entries(folder, &extensions)?;
+    if let Some(max) = limit {
+        entries.truncate(max);
+    }
+
+    let mut audit = SizeAudit::default();
+    for entry in &entries {
+        let files = match entry {
+            GameEntry::SingleFile(path) => std::slice::from_ref(path),
+            GameEntry::MultiDisc { files, .. } => files.as_slice(),
+        };
+        for path in files {
+            match file_verdict(path, analyzer) {
+                Some(verdict) => audit.record(path, verdict),
+                None => audit.failed += 1,
+            }
+        }
+    }
+    Ok(audit)
+}
+
+/// `None` if the file can't be analyzed; `Some(None)` if its size is unknown.
+fn file_verdict(path: &Path, analyzer: &dyn RomAnalyzer) -> Option<Option<SizeVerdict>> {
+    let mut file = fs::File::open(path).ok()?;
+    let options = AnalysisOptions::new().quick(true).file_path(path);
+    let info = match analyzer.analyze(&mut file, &options) {
+        Ok(info) => info,
+        Err(e) => {
+            log::debug!("Size audit: {} failed analysis: {}", path.display(), e);
+            return None;
+        }
+    };
+    Some(match (info.file_size, info.expected_size) {
+        (Some(actual), Some(expected)) => Some(compute_size_verdict(actual, expected)),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/size_audit_tests.rs"]
+mod tests;
@@ -0,0 +1,333 @@
+//! Check and rename a folder tree against an `.smdb` listing.
+//!
+//! EverDrive and other handheld ROM packs are organized by SMDB files rather
+//! than raw DATs: one tab-separated line per file giving its SHA-256, its
+//! path within the pack, and optionally its SHA-1, MD5, CRC32 and size.
+//!
+//! Every file under the folder is hashed whole, exactly as stored, and
+//! matched by the strongest hash its line lists. A matched file is either
+//! at its listed path or misplaced, in which case [`apply_moves`] moves it
+//! there. Listed paths with no matching file are reported as missing.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, Path};
+
+use thiserror::Error;
+
+use retro_junk_dat::error::DatError;
+
+use crate::hasher::{RawFileHashes, compute_raw_hashes};
+use crate::trash::rename_no_clobber;
+
+/// Errors reading an SMDB file.
+#[derive(Debug, Error)]
+pub enum SmdbError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid SMDB line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+/// One file listed in an SMDB. Hashes are lowercase hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmdbEntry {
+    /// Path within the pack, `/`-separated.
+    pub path: String,
+    pub sha256: Option<String>,
+    pub sha1: Option<String>,
+    pub md5: Option<String>,
+    pub crc32: Option<String>,
+    pub size: Option<u64>,
+}
+
+impl SmdbEntry {
+    /// Whether a file with these hashes is this entry. The strongest hash
+    /// the entry lists decides; a listed size must also agree.
+    pub fn matches(&self, hashes: &RawFileHashes) -> bool {
+        if self.size.is_some_and(|size| size != hashes.size) {
+            return false;
+        }
+        [
+            (&self.sha256, &hashes.sha256),
+            (&self.sha1, &hashes.sha1),
+            (&self.md5, &hashes.md5),
+            (&self.crc32, &hashes.crc32),
+        ]
+        .into_iter()
+        .find_map(|(listed, actual)| listed.as_ref().map(|listed| listed == actual))
+        .unwrap_or(false)
+    }
+}
+
+/// A parsed SMDB file.
+#[derive(Debug, Default)]
+pub struct Smdb {
+    pub entries: Vec<SmdbEntry>,
+}
+
+impl Smdb {
+    /// Read and parse an SMDB file.
+    pub fn load(path: &Path) -> Result<Self, SmdbError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parse SMDB text. Blank lines are skipped; paths may use `\` or `/`
+    /// but must stay inside the pack.
+    pub fn parse(text: &str) -> Result<Self, SmdbError> {
+        let mut entries = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(parse_line(line).map_err(|message| SmdbError::Parse {
+                line: index + 1,
+                message,
+            })?);
+        }
+        Ok(Self { entries })
+    }
+}
+
+fn parse_line(line: &str) -> Result<SmdbEntry, String> {
+    let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+    if columns.len() < 2 {
+        return Err("expected a SHA-256 and a path separated by a tab".into());
+    }
+
+    let path = columns[1].replace('\\', "/");
+    if path.is_empty() {
+        return Err("empty path".into());
+    }
+    let relative = Path::new(&path);
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(format!("path leaves the pack: {path}"));
+    }
+
+    let column = |i: usize| columns.get(i).copied().unwrap_or_default();
+    let sha256 = hex_column(column(0), "SHA-256", 64)?;
+    let sha1 = hex_column(column(2), "SHA-1", 40)?;
+    let md5 = hex_column(column(3), "MD5", 32)?;
+    let crc32 = hex_column(column(4), "CRC32", 8)?;
+    let size = match column(5) {
+        "" => None,
+        size => Some(size.parse().map_err(|_| format!("invalid size: {size}"))?),
+    };
+
+    if sha256.is_none() && sha1.is_none() && md5.is_none() && crc32.is_none() {
+        return Err(format!("no hashes for {path}"));
+    }
+
+    Ok(SmdbEntry {
+        path,
+        sha256,
+        sha1,
+        md5,
+        crc32,
+        size,
+    })
+}
+
+/// A hex hash column: empty is `None`, anything else must be `len` hex digits.
+fn hex_column(value: &str, name: &str, len: usize) -> Result<Option<String>, String> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    if value.len() != len || !value.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(format!("invalid {name}: {value}"));
+    }
+    Ok(Some(value.to_ascii_lowercase()))
+}
+
+/// Where a file stands against the SMDB.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SmdbStatus {
+    /// At the path the SMDB lists for it.
+    InPlace,
+    /// Listed, but somewhere else; `target` is its listed path.
+    Misplaced { target: String },
+    /// Another copy of a file already at (or headed to) every path listed
+    /// for it.
+    Duplicate { of: String },
+    /// Not in the SMDB.
+    Unknown,
+}
+
+/// A file found under the checked folder.
+#[derive(Debug, Clone)]
+pub struct SmdbFile {
+    /// Path relative to the folder, `/`-separated.
+    pub path: String,
+    pub status: SmdbStatus,
+}
+
+/// The result of checking a folder against an SMDB.
+#[derive(Debug, Default)]
+pub struct SmdbVerification {
+    pub files: Vec<SmdbFile>,
+    /// Listed paths no file was found for.
+    pub missing: Vec<String>,
+    /// Files that couldn't be read, with the error.
+    pub errors: Vec<(String, String)>,
+}
+
+impl SmdbVerification {
+    /// Files that [`apply_moves`] would move.
+    pub fn misplaced(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.files.iter().filter_map(|f| match &f.status {
+            SmdbStatus::Misplaced { target } => Some((f.path.as_str(), target.as_str())),
+            _ => None,
+        })
+    }
+
+    /// Number of files with a status of the same kind as `status`.
+    pub fn count(&self, status: &SmdbStatus) -> usize {
+        let kind = std::mem::discriminant(status);
+        self.files
+            .iter()
+            .filter(|f| std::mem::discriminant(&f.status) == kind)
+            .count()
+    }
+}
+
+/// Hash every file under `root` and check it against the SMDB.
+///
+/// `on_progress` is called before each file is hashed, with its index, the
+/// file count and its relative path. Hidden folders (including the trash)
+/// are skipped.
+pub fn verify_against_smdb(
+    root: &Path,
+    smdb: &Smdb,
+    on_progress: &dyn Fn(usize, usize, &str),
+) -> Result<SmdbVerification, SmdbError> {
+    let mut paths = Vec::new();
+    collect_files(root, root, &mut paths)?;
+
+    let mut verification = SmdbVerification::default();
+    let mut matched: Vec<(String, Vec<usize>)> = Vec::new();
+    for (i, path) in paths.iter().enumerate() {
+        on_progress(i, paths.len(), path);
+        let hashes = match File::open(root.join(path))
+            .map_err(DatError::from)
+            .and_then(|mut file| compute_raw_hashes(&mut file))
+        {
+            Ok(hashes) => hashes,
+            Err(e) => {
+                verification.errors.push((path.clone(), e.to_string()));
+                continue;
+            }
+        };
+        let candidates: Vec<usize> = smdb
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.matches(&hashes))
+            .map(|(index, _)| index)
+            .collect();
+        matched.push((path.clone(), candidates));
+    }
+
+    // Files already in place claim their entries first, so a misplaced copy
+    // is never sent to a path that is already correct.
+    let mut claimed = HashSet::new();
+    let mut statuses: HashMap<String, SmdbStatus> = HashMap::new();
+    for (path, candidates) in &matched {
+        if let Some(&index) = candidates.iter().find(|&&i| smdb.entries[i].path == *path) {
+            claimed.insert(index);
+            statuses.insert(path.clone(), SmdbStatus::InPlace);
+        }
+    }
+    for (path, candidates) in &matched {
+        if statuses.contains_key(path) {
+            continue;
+        }
+        let status = match candidates.iter().find(|i| !claimed.contains(*i)) {
+            Some(&index) => {
+                claimed.insert(index);
+                SmdbStatus::Misplaced {
+                    target: smdb.entries[index].path.clone(),
+                }
+            }
+            None => match candidates.first() {
+                Some(&index) => SmdbStatus::Duplicate {
+                    of: smdb.entries[index].path.clone(),
+                },
+                None => SmdbStatus::Unknown,
+            },
+        };
+        statuses.insert(path.clone(), status);
+    }
+
+    for (path, _) in matched {
+        let status = statuses.remove(&path).unwrap_or(SmdbStatus::Unknown);
+        verification.files.push(SmdbFile { path, status });
+    }
+    verification.missing = smdb
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !claimed.contains(i))
+        .map(|(_, entry)| entry.path.clone())
+        .collect();
+    Ok(verification)
+}
+
+/// Every file under `dir`, relative to `root` and sorted.
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> io::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        if path.is_dir() {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            collect_files(root, &path, files)?;
+        } else if path.is_file()
+            && let Ok(relative) = path.strip_prefix(root)
+        {
+            let parts: Vec<_> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect();
+            files.push(parts.join("/"));
+        }
+    }
+    Ok(())
+}
+
+/// The outcome of [`apply_moves`].
+#[derive(Debug, Default)]
+pub struct SmdbMoveSummary {
+    pub moved: usize,
+    /// Files that couldn't be moved, with the error.
+    pub failed: Vec<(String, String)>,
+}
+
+/// Move each misplaced file to its listed path under `root`, creating
+/// folders as needed. A file already at the target is never replaced; those
+/// moves fail and are reported.
+pub fn apply_moves(root: &Path, verification: &SmdbVerification) -> SmdbMoveSummary {
+    let mut summary = SmdbMoveSummary::default();
+    for (source, target) in verification.misplaced() {
+        let target_path = root.join(target);
+        let result = target_path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| rename_no_clobber(&root.join(source), &target_path));
+        match result {
+            Ok(()) => summary.moved += 1,
+            Err(e) => summary.failed.push((source.to_string(), e.to_string())),
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+#[path = "tests/smdb_tests.rs"]
+mod tests;
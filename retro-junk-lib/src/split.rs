@@ -0,0 +1,204 @@
+//! Splitting files too large for FAT32, and joining them back.
+//!
+//! FAT32 can't hold a file of 4 GiB or more, so disc images for cards
+//! formatted that way are stored in pieces, named the way the loader on
+//! the device expects:
+//!
+//! - [`SplitFormat::Parts`]: `Game.iso.part0`, `Game.iso.part1`, ...
+//! - [`SplitFormat::Wbfs`]: `Game.wbfs`, `Game.wbf1`, `Game.wbf2`, ... as
+//!   read by Wii USB loaders.
+//!
+//! Every piece but the last is exactly [`SPLIT_CHUNK_SIZE`] bytes. Splitting
+//! re-reads the written pieces and compares their CRC32 with the source,
+//! and joining checks the piece sizes, so a truncated copy is caught.
+
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use retro_junk_core::hash::read_full;
+use retro_junk_core::{HashAlgorithms, StreamHasher};
+
+use crate::trash;
+
+/// Size of each piece: 4 GiB less 32 KiB, the size Wii loaders use, which
+/// also keeps every piece under the FAT32 limit.
+pub const SPLIT_CHUNK_SIZE: u64 = 0xFFFF_8000;
+
+const BUF_SIZE: usize = 1024 * 1024;
+
+/// How a split file's pieces are named.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitFormat {
+    /// `name.part0`, `name.part1`, ...
+    #[default]
+    Parts,
+    /// `name.wbfs`, `name.wbf1`, `name.wbf2`, ...
+    Wbfs,
+}
+
+impl SplitFormat {
+    /// Name of piece `index` of a file named `name`.
+    pub fn part_name(&self, name: &str, index: usize) -> String {
+        match self {
+            Self::Parts => format!("{}.part{}", name, index),
+            Self::Wbfs if index == 0 => name.to_string(),
+            Self::Wbfs => {
+                let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+                format!("{}.wbf{}", stem, index)
+            }
+        }
+    }
+
+    /// Number of pieces a file of `size` bytes is split into.
+    pub fn part_count(size: u64) -> usize {
+        size.div_ceil(SPLIT_CHUNK_SIZE).max(1) as usize
+    }
+
+    /// Paths of the pieces `dest` would be split into for a `size`-byte file.
+    pub fn part_paths(&self, dest: &Path, size: u64) -> Vec<PathBuf> {
+        let name = dest
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        (0..Self::part_count(size))
+            .map(|i| dest.with_file_name(self.part_name(&name, i)))
+            .collect()
+    }
+}
+
+/// Outcome of joining a split file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinResult {
+    pub size: u64,
+    /// CRC32 of the joined file, as lowercase hex.
+    pub crc32: String,
+}
+
+/// Split `source` into pieces named after `dest`. Returns the pieces.
+///
+/// Fails with [`io::ErrorKind::InvalidData`] if the pieces read back
+/// differently from the source.
+pub fn split_file(source: &Path, dest: &Path, format: SplitFormat) -> io::Result<Vec<PathBuf>> {
+    split_with_chunk(source, dest, format, SPLIT_CHUNK_SIZE)
+}
+
+fn split_with_chunk(
+    source: &Path,
+    dest: &Path,
+    format: SplitFormat,
+    chunk: u64,
+) -> io::Result<Vec<PathBuf>> {
+    let size = fs::metadata(source)?.len();
+    let count = size.div_ceil(chunk).max(1) as usize;
+    let name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let parts: Vec<PathBuf> = (0..count)
+        .map(|i| dest.with_file_name(format.part_name(&name, i)))
+        .collect();
+
+    let mut input = fs::File::open(source)?;
+    let mut written = StreamHasher::new(HashAlgorithms::Crc32);
+    let mut buf = vec![0u8; BUF_SIZE];
+    for part in &parts {
+        let mut out = BufWriter::new(fs::File::create(part)?);
+        let mut piece = (&mut input).take(chunk);
+        loop {
+            let n = read_full(&mut piece, &mut buf)?;
+            if n == 0 {
+                break;
+            }
+            written.update(&buf[..n]);
+            out.write_all(&buf[..n])?;
+        }
+        out.flush()?;
+    }
+
+    let mut read_back = StreamHasher::new(HashAlgorithms::Crc32);
+    for part in &parts {
+        read_back.update_reader(&mut fs::File::open(part)?)?;
+    }
+    if read_back.finish().crc32 != written.finish().crc32 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("pieces of {} don't match the source", dest.display()),
+        ));
+    }
+    Ok(parts)
+}
+
+/// Find the pieces of a split file from its first piece (`Game.iso.part0`
+/// or `Game.wbfs`). Returns `None` if `first` isn't the start of a split.
+pub fn find_parts(first: &Path) -> Option<(SplitFormat, Vec<PathBuf>)> {
+    let name = first.file_name()?.to_string_lossy().into_owned();
+    let (format, base) = if let Some(base) = name.strip_suffix(".part0") {
+        (SplitFormat::Parts, base.to_string())
+    } else if name.to_lowercase().ends_with(".wbfs") {
+        (SplitFormat::Wbfs, name.clone())
+    } else {
+        return None;
+    };
+
+    let mut parts = vec![first.to_path_buf()];
+    loop {
+        let next = first.with_file_name(format.part_name(&base, parts.len()));
+        if !next.is_file() {
+            break;
+        }
+        parts.push(next);
+    }
+    // A lone .wbfs file is just a WBFS image
+    (format == SplitFormat::Parts || parts.len() > 1).then_some((format, parts))
+}
+
+/// Join `parts` into `dest`, checking that no piece was truncated. Fails
+/// with [`io::ErrorKind::AlreadyExists`] rather than overwrite `dest`.
+pub fn join_parts(parts: &[PathBuf], dest: &Path) -> io::Result<JoinResult> {
+    join_with_chunk(parts, dest, SPLIT_CHUNK_SIZE)
+}
+
+fn join_with_chunk(parts: &[PathBuf], dest: &Path, chunk: u64) -> io::Result<JoinResult> {
+    for (i, part) in parts.iter().enumerate() {
+        let len = fs::metadata(part)?.len();
+        let last = i + 1 == parts.len();
+        if (!last && len != chunk) || (last && len > chunk) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{} is {} bytes, expected {}{} (incomplete copy?)",
+                    part.display(),
+                    len,
+                    if last { "at most " } else { "" },
+                    chunk
+                ),
+            ));
+        }
+    }
+
+    let mut out = BufWriter::new(trash::create_new(dest)?);
+    let mut hasher = StreamHasher::new(HashAlgorithms::Crc32);
+    let mut buf = vec![0u8; BUF_SIZE];
+    for part in parts {
+        let mut input = fs::File::open(part)?;
+        loop {
+            let n = read_full(&mut input, &mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            out.write_all(&buf[..n])?;
+        }
+    }
+    out.flush()?;
+    let hashes = hasher.finish();
+    Ok(JoinResult {
+        size: hashes.data_size,
+        crc32: hashes.crc32,
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/split_tests.rs"]
+mod tests;
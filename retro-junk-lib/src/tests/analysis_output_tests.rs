@@ -0,0 +1,75 @@
+use super::*;
+
+fn sample_info() -> RomIdentification {
+    RomIdentification {
+        serial_number: Some("NUS-NSME-USA".to_string()),
+        internal_name: Some("SUPER MARIO 64".to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_output_format_from_str() {
+    assert_eq!("text".parse::<OutputFormat>(), Ok(OutputFormat::Text));
+    assert_eq!("JSON".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+    assert_eq!("ndjson".parse::<OutputFormat>(), Ok(OutputFormat::Ndjson));
+    assert_eq!("jsonl".parse::<OutputFormat>(), Ok(OutputFormat::Ndjson));
+    assert!("xml".parse::<OutputFormat>().is_err());
+    assert!(!OutputFormat::Text.is_structured());
+    assert!(OutputFormat::Ndjson.is_structured());
+}
+
+#[test]
+fn test_record_serializes_path_platform_and_identification() {
+    let record = AnalysisRecord::identified(
+        PathBuf::from("/roms/n64/Super Mario 64 (USA).z64"),
+        Platform::N64,
+        sample_info(),
+    );
+    let value: serde_json::Value = serde_json::from_str(&record.to_json_line()).unwrap();
+    assert_eq!(value["path"], "/roms/n64/Super Mario 64 (USA).z64");
+    assert_eq!(value["platform"], "n64");
+    assert_eq!(value["identification"]["serial_number"], "NUS-NSME-USA");
+    // Empty fields are left out
+    assert!(value.get("error").is_none());
+    assert!(value.get("entry").is_none());
+    assert!(value.get("warnings").is_none());
+}
+
+#[test]
+fn test_failed_record_in_archive() {
+    let record = AnalysisRecord::failed(
+        PathBuf::from("/roms/nes/Pack.zip"),
+        Platform::Nes,
+        "Not recognized",
+    )
+    .with_entry("broken.nes");
+    let value: serde_json::Value = serde_json::from_str(&record.to_json_line()).unwrap();
+    assert_eq!(value["entry"], "broken.nes");
+    assert_eq!(value["error"], "Not recognized");
+    assert!(value.get("identification").is_none());
+}
+
+#[test]
+fn test_render_records() {
+    let records = vec![
+        AnalysisRecord::identified(PathBuf::from("a.z64"), Platform::N64, sample_info()),
+        AnalysisRecord::failed(PathBuf::from("b.z64"), Platform::N64, "Damaged file"),
+    ];
+
+    let ndjson = render_records(OutputFormat::Ndjson, &records);
+    let lines: Vec<&str> = ndjson.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(
+        lines
+            .iter()
+            .all(|l| serde_json::from_str::<serde_json::Value>(l).is_ok())
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&render_records(OutputFormat::Json, &records)).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 2);
+    assert_eq!(json[1]["error"], "Damaged file");
+
+    assert!(render_records(OutputFormat::Text, &records).is_empty());
+}
@@ -0,0 +1,201 @@
+use std::io::Write;
+
+use retro_junk_dat::{DatFile, DatGame, DatRom};
+
+use super::*;
+
+/// An iNES ROM with 1 x 16 KB PRG filled with `fill`.
+fn nes_rom(fill: u8) -> Vec<u8> {
+    let mut data = vec![fill; 16 + 16 * 1024];
+    data[0..16].fill(0);
+    data[0..4].copy_from_slice(b"NES\x1A");
+    data[4] = 1;
+    data
+}
+
+fn write_zip(path: &Path, files: &[(&str, &[u8])]) {
+    let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+    for (name, data) in files {
+        zip.start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(data).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+fn nes_analyzer() -> Box<dyn RomAnalyzer> {
+    Box::new(retro_junk_nintendo::NesAnalyzer)
+}
+
+#[test]
+fn lists_and_analyzes_each_rom_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Compilation.zip");
+    write_zip(
+        &path,
+        &[
+            ("roms/Game A.nes", &nes_rom(0x11)),
+            ("readme.txt", b"hello"),
+            ("roms/Game B.nes", &nes_rom(0x22)),
+        ],
+    );
+
+    let entries = list_entries(&path).unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].file_name(), "Game A.nes");
+    assert_eq!(entries[0].extension().as_deref(), Some("nes"));
+
+    let analyzer = nes_analyzer();
+    let extensions = crate::scanner::extension_set(analyzer.file_extensions());
+    assert!(is_multi_game(&path, &extensions));
+
+    let results = analyze_entries(&path, analyzer.as_ref(), &AnalysisOptions::new()).unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].entry.name, "roms/Game B.nes");
+    let id = results[0].result.as_ref().unwrap();
+    assert_eq!(id.file_size, Some(16 + 16 * 1024));
+}
+
+#[test]
+fn split_names_matched_entries_and_removes_archive() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("2 in 1.zip");
+    let game_a = nes_rom(0x11);
+    write_zip(&path, &[("a.nes", &game_a), ("b.nes", &nes_rom(0x22))]);
+
+    let crc = format!("{:08x}", crc32fast::hash(&game_a[16..]));
+    let index = DatIndex::from_dat(DatFile {
+        name: "Test".into(),
+        description: "".into(),
+        version: "1".into(),
+        games: vec![DatGame {
+            name: "Game A (USA)".into(),
+            region: None,
+            clone_of: None,
+            roms: vec![DatRom {
+                name: "Game A (USA).nes".into(),
+                size: 16 * 1024,
+                crc,
+                sha1: None,
+                md5: None,
+                serial: None,
+            }],
+        }],
+    });
+
+    let analyzer = nes_analyzer();
+    let split = plan_split(&path, analyzer.as_ref(), &index).unwrap();
+    assert_eq!(split.matched(), 1);
+    assert_eq!(split.entries[0].target, dir.path().join("Game A (USA).nes"));
+    assert_eq!(split.entries[1].target, dir.path().join("b.nes"));
+
+    assert_eq!(execute_split(&split, None).unwrap(), 2);
+    assert!(!path.exists());
+    assert_eq!(
+        fs::read(dir.path().join("Game A (USA).nes")).unwrap(),
+        game_a
+    );
+}
+
+#[test]
+fn split_refuses_existing_targets() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pack.zip");
+    write_zip(&path, &[("a.nes", &nes_rom(1)), ("b.nes", &nes_rom(2))]);
+    fs::write(dir.path().join("b.nes"), b"existing").unwrap();
+
+    let analyzer = nes_analyzer();
+    let index = DatIndex::from_dats(Vec::new());
+    let split = plan_split(&path, analyzer.as_ref(), &index).unwrap();
+    assert!(execute_split(&split, None).is_err());
+    assert!(path.exists());
+    assert!(!dir.path().join("a.nes").exists());
+}
+
+#[test]
+fn split_moves_archive_to_trash() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pack.zip");
+    write_zip(&path, &[("a.nes", &nes_rom(1)), ("b.nes", &nes_rom(2))]);
+
+    let analyzer = nes_analyzer();
+    let index = DatIndex::from_dats(Vec::new());
+    let split = plan_split(&path, analyzer.as_ref(), &index).unwrap();
+    let trash = Trash::new(dir.path());
+    assert_eq!(execute_split(&split, Some(&trash)).unwrap(), 2);
+    assert!(!path.exists());
+    assert_eq!(trash.entries().unwrap()[0].original, path);
+}
+
+#[test]
+fn opens_the_rom_inside_a_single_rom_archive() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Game.zip");
+    let rom = nes_rom(0x33);
+    write_zip(&path, &[("readme.txt", b"hello"), ("Game.nes", &rom)]);
+
+    let analyzer = nes_analyzer();
+    let extensions = crate::scanner::extension_set(analyzer.file_extensions());
+    assert_eq!(
+        single_rom_entry(&path, &extensions).map(|e| e.name),
+        Some("Game.nes".to_string())
+    );
+
+    let mut opened = open_rom(&path, &extensions).unwrap();
+    assert_eq!(opened.analysis_path, path.join("Game.nes"));
+    assert_eq!(opened.size(&path), rom.len() as u64);
+    let id = analyzer
+        .analyze(opened.reader.as_mut(), &AnalysisOptions::new())
+        .unwrap();
+    assert_eq!(id.file_size, Some(rom.len() as u64));
+
+    let loose = dir.path().join("Loose.nes");
+    fs::write(&loose, &rom).unwrap();
+    let opened = open_rom(&loose, &extensions).unwrap();
+    assert!(opened.entry.is_none());
+    assert_eq!(opened.analysis_path, loose);
+}
+
+#[test]
+fn open_rom_refuses_compilations() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("2 in 1.zip");
+    write_zip(&path, &[("a.nes", &nes_rom(1)), ("b.nes", &nes_rom(2))]);
+
+    let extensions = crate::scanner::extension_set(nes_analyzer().file_extensions());
+    assert!(single_rom_entry(&path, &extensions).is_none());
+    assert!(matches!(
+        open_rom(&path, &extensions),
+        Err(ArchiveError::Invalid { .. })
+    ));
+}
+
+#[test]
+fn scanner_lists_single_rom_archives_when_asked() {
+    let dir = tempfile::tempdir().unwrap();
+    write_zip(
+        &dir.path().join("Zipped.zip"),
+        &[("Zipped.nes", &nes_rom(1))],
+    );
+    write_zip(
+        &dir.path().join("2 in 1.zip"),
+        &[("a.nes", &nes_rom(1)), ("b.nes", &nes_rom(2))],
+    );
+    write_zip(&dir.path().join("Notes.zip"), &[("notes.txt", b"hi")]);
+    fs::write(dir.path().join("Loose.nes"), nes_rom(3)).unwrap();
+
+    let extensions = crate::scanner::extension_set(nes_analyzer().file_extensions());
+    let names = |options: &crate::scanner::ScanOptions| -> Vec<String> {
+        crate::scanner::scan_game_entries_with(dir.path(), &extensions, options)
+            .unwrap()
+            .iter()
+            .map(|e| e.display_name().to_string())
+            .collect()
+    };
+    let options = crate::scanner::ScanOptions::new();
+    assert_eq!(names(&options), vec!["Loose.nes"]);
+    assert_eq!(
+        names(&options.archives(true)),
+        vec!["Loose.nes", "Zipped.zip"]
+    );
+}
@@ -0,0 +1,172 @@
+use std::io::Cursor;
+
+use retro_junk_dat::dat::{DatFile, DatGame, DatRom};
+use sha1::{Digest, Sha1};
+
+use super::*;
+
+const HUNK_BYTES: usize = 16;
+const MAP_OFFSET: usize = 128;
+const META_OFFSET: usize = 160;
+const DATA_OFFSET: usize = 256;
+
+const TRACK_META: &[u8] = b"TRACK:1 TYPE:MODE1 SUBTYPE:NONE FRAMES:1\0";
+
+/// Build an uncompressed v5 CHD holding `data`, with one checksummed
+/// metadata entry and correct header SHA1s.
+fn make_chd(data: &[u8]) -> Vec<u8> {
+    let hunks = data.len().div_ceil(HUNK_BYTES);
+    let mut out = vec![0u8; DATA_OFFSET + hunks * HUNK_BYTES];
+
+    out[..8].copy_from_slice(b"MComprHD");
+    out[8..12].copy_from_slice(&124u32.to_be_bytes());
+    out[12..16].copy_from_slice(&5u32.to_be_bytes());
+    // compressors [16..32] stay zero (uncompressed)
+    out[32..40].copy_from_slice(&(data.len() as u64).to_be_bytes());
+    out[40..48].copy_from_slice(&(MAP_OFFSET as u64).to_be_bytes());
+    out[48..56].copy_from_slice(&(META_OFFSET as u64).to_be_bytes());
+    out[56..60].copy_from_slice(&(HUNK_BYTES as u32).to_be_bytes());
+    out[60..64].copy_from_slice(&(HUNK_BYTES as u32).to_be_bytes());
+
+    for i in 0..hunks {
+        let entry = (DATA_OFFSET / HUNK_BYTES + i) as u32;
+        out[MAP_OFFSET + i * 4..MAP_OFFSET + i * 4 + 4].copy_from_slice(&entry.to_be_bytes());
+    }
+
+    let tag = u32::from_be_bytes(*b"CHT2");
+    out[META_OFFSET..META_OFFSET + 4].copy_from_slice(&tag.to_be_bytes());
+    let flags_len = (u32::from(METADATA_CHECKSUM_FLAG) << 24) | TRACK_META.len() as u32;
+    out[META_OFFSET + 4..META_OFFSET + 8].copy_from_slice(&flags_len.to_be_bytes());
+    // next-entry offset stays zero
+    out[META_OFFSET + 16..META_OFFSET + 16 + TRACK_META.len()].copy_from_slice(TRACK_META);
+
+    out[DATA_OFFSET..DATA_OFFSET + data.len()].copy_from_slice(data);
+
+    let raw: [u8; 20] = Sha1::digest(data).into();
+    let mut combined = Sha1::new();
+    combined.update(raw);
+    combined.update(tag.to_be_bytes());
+    combined.update(Sha1::digest(TRACK_META));
+    out[64..84].copy_from_slice(&raw);
+    out[84..104].copy_from_slice(&combined.finalize());
+    out
+}
+
+fn sample_data() -> Vec<u8> {
+    (0..40u8).collect()
+}
+
+fn disk_index(sha1: &str) -> DatIndex {
+    DatIndex::from_dat(DatFile {
+        name: "Test".into(),
+        description: "Test".into(),
+        version: "1".into(),
+        games: vec![DatGame {
+            name: "Test Disc (USA)".into(),
+            region: None,
+            clone_of: None,
+            roms: vec![DatRom {
+                name: "Test Disc (USA)".into(),
+                size: 0,
+                crc: String::new(),
+                sha1: Some(sha1.into()),
+                md5: None,
+                serial: None,
+            }],
+        }],
+    })
+}
+
+#[test]
+fn reads_header_hashes() {
+    let data = sample_data();
+    let header = read_header_hashes(&mut Cursor::new(make_chd(&data))).unwrap();
+    assert_eq!(header.version, 5);
+    assert_eq!(
+        header.raw_sha1.as_deref(),
+        Some(hex(&Sha1::digest(&data)).as_str())
+    );
+    assert_ne!(header.sha1, header.expected_raw_sha1());
+    assert!(!header.has_parent);
+}
+
+#[test]
+fn fast_mode_matches_dat_disk_entry() {
+    let chd = make_chd(&sample_data());
+    let header = read_header_hashes(&mut Cursor::new(&chd)).unwrap();
+    let index = disk_index(&header.sha1);
+
+    let result = verify_chd(
+        &mut Cursor::new(&chd),
+        ChdVerifyMode::Fast,
+        Some(&index),
+        &|_, _| {},
+    )
+    .unwrap();
+    let m = result.dat_match.clone().expect("disk entry should match");
+    assert_eq!(index.games[m.game_index].name, "Test Disc (USA)");
+    assert_eq!(result.metadata_consistent, Some(true));
+    assert!(result.derived_raw_sha1.is_none());
+    assert!(!result.is_corrupt());
+}
+
+#[test]
+fn deep_mode_detects_damaged_data() {
+    let data = sample_data();
+    let mut chd = make_chd(&data);
+
+    let result = verify_chd(
+        &mut Cursor::new(&chd),
+        ChdVerifyMode::Deep,
+        None,
+        &|_, _| {},
+    )
+    .unwrap();
+    assert_eq!(
+        result.derived_raw_sha1.as_deref(),
+        Some(result.header.expected_raw_sha1())
+    );
+    assert!(!result.is_corrupt());
+
+    // Flip a byte in the last (partial) hunk; the header still looks fine
+    chd[DATA_OFFSET + 35] ^= 0xff;
+    let fast = verify_chd(
+        &mut Cursor::new(&chd),
+        ChdVerifyMode::Fast,
+        None,
+        &|_, _| {},
+    )
+    .unwrap();
+    assert!(!fast.is_corrupt());
+    let deep = verify_chd(
+        &mut Cursor::new(&chd),
+        ChdVerifyMode::Deep,
+        None,
+        &|_, _| {},
+    )
+    .unwrap();
+    assert!(deep.is_corrupt());
+}
+
+#[test]
+fn fast_mode_detects_header_metadata_mismatch() {
+    let mut chd = make_chd(&sample_data());
+    chd[84] ^= 0xff;
+    let result = verify_chd(
+        &mut Cursor::new(&chd),
+        ChdVerifyMode::Fast,
+        None,
+        &|_, _| {},
+    )
+    .unwrap();
+    assert_eq!(result.metadata_consistent, Some(false));
+    assert!(result.is_corrupt());
+}
+
+#[test]
+fn verify_mode_round_trips() {
+    for mode in [ChdVerifyMode::Fast, ChdVerifyMode::Deep] {
+        assert_eq!(mode.to_string().parse::<ChdVerifyMode>(), Ok(mode));
+    }
+    assert!("thorough".parse::<ChdVerifyMode>().is_err());
+}
@@ -0,0 +1,264 @@
+use super::*;
+use std::fs;
+
+fn folder_names(result: &FolderScanResult) -> Vec<&str> {
+    result
+        .matches
+        .iter()
+        .map(|m| m.folder_name.as_str())
+        .collect()
+}
+
+#[test]
+fn finds_grouped_console_folders() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("nes")).unwrap();
+    fs::create_dir_all(root.join("nintendo/snes")).unwrap();
+    fs::create_dir_all(root.join("nintendo/misc")).unwrap();
+    fs::create_dir_all(root.join("stuff/more")).unwrap();
+
+    let ctx = crate::create_default_context();
+    let result = ctx.scan_console_folders(root, None).unwrap();
+
+    assert_eq!(folder_names(&result), ["nes", "snes"]);
+    assert_eq!(result.matches[1].path, root.join("nintendo/snes"));
+    assert_eq!(result.unrecognized, ["nintendo/misc", "stuff"]);
+}
+
+#[test]
+fn scan_depth_one_is_flat() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("nes")).unwrap();
+    fs::create_dir_all(root.join("nintendo/snes")).unwrap();
+
+    let mut ctx = crate::create_default_context();
+    ctx.set_scan_depth(1);
+    let result = ctx.scan_console_folders(root, None).unwrap();
+
+    assert_eq!(folder_names(&result), ["nes"]);
+    assert_eq!(result.unrecognized, ["nintendo"]);
+}
+
+#[test]
+fn console_folders_are_not_searched() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("snes/nes")).unwrap();
+
+    let ctx = crate::create_default_context();
+    let result = ctx.scan_console_folders(root, None).unwrap();
+
+    assert_eq!(folder_names(&result), ["snes"]);
+    assert!(result.unrecognized.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn linked_console_folders() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(store.path().join("snes")).unwrap();
+    std::os::unix::fs::symlink(store.path().join("snes"), root.join("snes")).unwrap();
+    fs::create_dir_all(root.join("group")).unwrap();
+    std::os::unix::fs::symlink(root, root.join("group/loop")).unwrap();
+
+    let mut ctx = crate::create_default_context();
+    ctx.set_scan_depth(6);
+    let result = ctx.scan_console_folders(root, None).unwrap();
+    assert_eq!(folder_names(&result), ["snes"]);
+    assert_eq!(result.unrecognized, ["group"]);
+
+    ctx.set_symlink_policy(SymlinkPolicy::Skip);
+    let result = ctx.scan_console_folders(root, None).unwrap();
+    assert!(result.matches.is_empty());
+}
+
+/// Wraps a reader and records the furthest byte read.
+struct FurthestRead<R> {
+    inner: R,
+    furthest: u64,
+}
+
+impl<R: std::io::Read + std::io::Seek> std::io::Read for FurthestRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        let pos = self.inner.stream_position()?;
+        self.furthest = self.furthest.max(pos);
+        Ok(n)
+    }
+}
+
+impl<R: std::io::Seek> std::io::Seek for FurthestRead<R> {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn detect_platforms_reads_only_a_prefix() {
+    // iNES header (2 x 16 KB PRG, 1 x 8 KB CHR) at the start of a 4 MB file
+    let mut rom = vec![0u8; 4 * 1024 * 1024];
+    rom[..4].copy_from_slice(b"NES\x1a");
+    rom[4] = 2;
+    rom[5] = 1;
+
+    let ctx = crate::create_default_context();
+    let mut reader = FurthestRead {
+        inner: std::io::Cursor::new(&rom),
+        furthest: 0,
+    };
+    let platforms = ctx.detect_platforms(&mut reader).unwrap();
+
+    assert!(platforms.contains(&Platform::Nes), "{:?}", platforms);
+    let limit = ctx
+        .consoles()
+        .map(|c| c.analyzer.sniff_len())
+        .max()
+        .unwrap();
+    assert!(reader.furthest <= limit as u64);
+    assert_eq!(reader.inner.position(), 0);
+}
+
+#[test]
+fn sniff_platforms_rejects_unknown_data() {
+    let ctx = crate::create_default_context();
+    assert!(ctx.sniff_platforms(&[0u8; 64], 64).is_empty());
+}
+
+/// An iNES file with one 16 KB PRG bank and one 8 KB CHR bank.
+fn nes_rom() -> Vec<u8> {
+    let mut rom = vec![0u8; 16 + 16 * 1024 + 8 * 1024];
+    rom[..4].copy_from_slice(b"NES\x1a");
+    rom[4] = 1;
+    rom[5] = 1;
+    rom
+}
+
+#[test]
+fn analyze_path_detects_the_platform() {
+    let dir = tempfile::tempdir().unwrap();
+    // The extension is wrong, but the header is unmistakable
+    let path = dir.path().join("game.bin");
+    fs::write(&path, nes_rom()).unwrap();
+
+    let ctx = crate::create_default_context();
+    let (platform, info) = ctx.analyze_path(&path).unwrap();
+    assert_eq!(platform, Platform::Nes);
+    assert_eq!(info.expected_size, Some(nes_rom().len() as u64));
+}
+
+#[test]
+fn analyze_path_reads_single_rom_archives() {
+    use std::io::Write;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("game.zip");
+    let mut zip = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+    zip.start_file("Game (USA).nes", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(&nes_rom()).unwrap();
+    zip.finish().unwrap();
+
+    let ctx = crate::create_default_context();
+    let (platform, _) = ctx.analyze_path(&path).unwrap();
+    assert_eq!(platform, Platform::Nes);
+}
+
+#[test]
+fn analyze_path_reads_game_folders() {
+    let dir = tempfile::tempdir().unwrap();
+    let game = dir.path().join("Resistance");
+    fs::create_dir_all(game.join("PS3_GAME")).unwrap();
+    fs::write(
+        game.join("PS3_GAME").join("PARAM.SFO"),
+        retro_junk_sony::synthetic::make_ps3_game_sfo("BLUS30001", "RESISTANCE"),
+    )
+    .unwrap();
+
+    let ctx = crate::create_default_context();
+    let (platform, info) = ctx.analyze_path(&game).unwrap();
+    assert_eq!(platform, Platform::Ps3);
+    assert_eq!(info.serial_number.as_deref(), Some("BLUS-30001"));
+    assert!(matches!(
+        ctx.analyze_path(dir.path()),
+        Err(AnalyzePathError::NotRecognized(_))
+    ));
+}
+
+#[test]
+fn analyze_path_reads_music_rips() {
+    use retro_junk_music::vgm::{Gd3Tag, VgmChip};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Pocket Test.vgm");
+    let tag = Gd3Tag {
+        game: "Pocket Test".into(),
+        ..Gd3Tag::default()
+    };
+    fs::write(
+        &path,
+        retro_junk_music::synthetic::vgm(&[VgmChip::GbDmg], Some(&tag)),
+    )
+    .unwrap();
+
+    let ctx = crate::create_default_context();
+    let (platform, info) = ctx.analyze_path(&path).unwrap();
+    assert_eq!(platform, Platform::GameBoy);
+    assert_eq!(info.internal_name.as_deref(), Some("Pocket Test"));
+    // Rips never reach the ROM pipelines
+    assert!(
+        ctx.consoles()
+            .all(|c| !c.analyzer.file_extensions().contains(&"vgm"))
+    );
+}
+
+#[test]
+fn analyze_path_reports_unrecognized_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let unknown = dir.path().join("notes.txt");
+    fs::write(&unknown, [0u8; 64]).unwrap();
+    let bad_nes = dir.path().join("broken.nes");
+    fs::write(&bad_nes, [0u8; 64]).unwrap();
+
+    let ctx = crate::create_default_context();
+    assert!(matches!(
+        ctx.analyze_path(&unknown),
+        Err(AnalyzePathError::NotRecognized(_))
+    ));
+    // Claimed by extension, but the NES analyzer rejects it
+    assert!(matches!(
+        ctx.analyze_path(&bad_nes),
+        Err(AnalyzePathError::Analysis(_))
+    ));
+}
+
+#[test]
+fn analyze_reader_tries_only_the_given_platforms() {
+    use std::io::Cursor;
+
+    let ctx = crate::create_default_context();
+    let name = Path::new("game.bin");
+    let (platform, _) = ctx
+        .analyze_reader(
+            &mut Cursor::new(nes_rom()),
+            name,
+            None,
+            &AnalysisOptions::new(),
+        )
+        .unwrap();
+    assert_eq!(platform, Platform::Nes);
+
+    // A platform that neither sniffs nor claims the file is still tried
+    assert!(matches!(
+        ctx.analyze_reader(
+            &mut Cursor::new(nes_rom()),
+            name,
+            Some(&[Platform::Snes]),
+            &AnalysisOptions::new(),
+        ),
+        Err(AnalyzePathError::Analysis(_))
+    ));
+}
@@ -0,0 +1,145 @@
+use std::io::Cursor;
+
+use super::*;
+
+const CONFIG: &str = r#"
+[[platform]]
+short_name = "Vectrex"
+name = "GCE Vectrex"
+manufacturer = "GCE"
+aliases = ["GCE Vectrex"]
+extensions = [".vec", "bin"]
+dat_names = ["GCE - Vectrex"]
+screenscraper_id = 102
+
+[[platform]]
+short_name = "lynx-test"
+name = "Atari Lynx (test)"
+extensions = ["lnx"]
+header_size = 64
+"#;
+
+#[test]
+fn registers_platforms_from_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(CONFIG_FILE_NAME);
+    std::fs::write(&path, CONFIG).unwrap();
+
+    let mut ctx = crate::create_default_context();
+    let platforms = register_from_file(&mut ctx, &path).unwrap();
+    assert_eq!(platforms.len(), 2);
+
+    let vectrex = platforms[0];
+    assert!(vectrex.is_custom());
+    assert_eq!(vectrex.short_name(), "vectrex");
+    assert_eq!(vectrex.display_name(), "GCE Vectrex");
+    assert_eq!("gce vectrex".parse::<Platform>().unwrap(), vectrex);
+
+    let console = ctx.get_by_short_name("vectrex").unwrap();
+    assert_eq!(console.metadata.extensions, &["vec", "bin"]);
+    assert_eq!(console.metadata.manufacturer, "GCE");
+    assert!(console.analyzer.has_dat_support());
+    assert_eq!(ctx.find_by_folder("Vectrex").len(), 1);
+
+    // Registering again reuses the existing platform
+    let again = register_from_file(&mut ctx, &path).unwrap();
+    assert_eq!(again, platforms);
+    assert_eq!(
+        ctx.consoles()
+            .filter(|c| c.metadata.platform == vectrex)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn missing_file_registers_nothing() {
+    let mut ctx = AnalysisContext::new();
+    let platforms = register_from_file(&mut ctx, Path::new("/nonexistent/platforms.toml")).unwrap();
+    assert!(platforms.is_empty());
+}
+
+#[test]
+fn rejects_built_in_names() {
+    let config = parse_config(
+        "[[platform]]\nshort_name = \"mygb\"\nname = \"x\"\naliases = [\"gbc\"]\nextensions = [\"gb\"]\n",
+        Path::new("platforms.toml"),
+    )
+    .unwrap();
+    let err = GenericAnalyzer::from_config(config.into_iter().next().unwrap()).unwrap_err();
+    assert!(err.to_string().contains("built-in"), "{err}");
+}
+
+#[test]
+fn header_is_skipped_only_when_present() {
+    let config = parse_config(CONFIG, Path::new("platforms.toml")).unwrap();
+    let analyzer = GenericAnalyzer::from_config(config[1].clone()).unwrap();
+    let mut reader = Cursor::new(Vec::new());
+
+    assert_eq!(
+        analyzer.dat_header_size(&mut reader, 65536 + 64).unwrap(),
+        64
+    );
+    assert_eq!(analyzer.dat_header_size(&mut reader, 65536).unwrap(), 0);
+
+    let mut rom = Cursor::new(vec![0u8; 2048]);
+    let id = analyzer.analyze(&mut rom, &AnalysisOptions::new()).unwrap();
+    assert_eq!(id.file_size, Some(2048));
+    assert_eq!(id.platform, Some(analyzer.platform()));
+}
+
+#[test]
+fn applies_screenscraper_id_overrides() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(CONFIG_FILE_NAME);
+    std::fs::write(
+        &path,
+        "[[platform]]\nshort_name = \"ss-override-test\"\nname = \"x\"\nextensions = [\"sso\"]\n\n\
+         [screenscraper_ids]\nss-override-test = 250\n",
+    )
+    .unwrap();
+
+    let mut ctx = AnalysisContext::new();
+    let platforms = register_from_file(&mut ctx, &path).unwrap();
+    assert_eq!(
+        custom_platform::screenscraper_id_override(platforms[0]),
+        Some(250)
+    );
+    assert_eq!(
+        custom_platform::screenscraper_id_override(Platform::Nes),
+        None
+    );
+}
+
+#[test]
+fn rejects_unknown_platform_in_overrides() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(CONFIG_FILE_NAME);
+    std::fs::write(&path, "[screenscraper_ids]\nnot-a-console = 1\n").unwrap();
+
+    let mut ctx = AnalysisContext::new();
+    let err = register_from_file(&mut ctx, &path).unwrap_err();
+    assert!(err.to_string().contains("not-a-console"), "{err}");
+}
+
+#[test]
+fn reads_multi_part_kinds() {
+    let config = parse_config(
+        "[[platform]]\nshort_name = \"amiga-parts-test\"\nname = \"x\"\nextensions = [\"adf\"]\nmulti_part = [\"disk\", \"Side\"]\n",
+        Path::new("platforms.toml"),
+    )
+    .unwrap();
+    let analyzer = GenericAnalyzer::from_config(config.into_iter().next().unwrap()).unwrap();
+    assert_eq!(
+        retro_junk_core::disc::part_kinds(analyzer.platform()),
+        &[PartKind::Disk, PartKind::Side]
+    );
+
+    let config = parse_config(
+        "[[platform]]\nshort_name = \"bad-parts-test\"\nname = \"x\"\nextensions = [\"adf\"]\nmulti_part = [\"reel\"]\n",
+        Path::new("platforms.toml"),
+    )
+    .unwrap();
+    let err = GenericAnalyzer::from_config(config.into_iter().next().unwrap()).unwrap_err();
+    assert!(err.to_string().contains("multi_part"), "{err}");
+}
@@ -0,0 +1,142 @@
+use super::*;
+
+fn sample_verification() -> FolderVerification {
+    FolderVerification {
+        dat_versions: vec!["Nintendo - Nintendo Entertainment System (20240101)".to_string()],
+        files: vec![
+            VerifiedFile {
+                path: PathBuf::from("/roms/nes/Tetris (USA).nes"),
+                status: VerifyStatus::Verified,
+                game_name: Some("Tetris (USA)".to_string()),
+                crc32: "6d72c53a".to_string(),
+                detail: None,
+            },
+            VerifiedFile {
+                path: PathBuf::from("/roms/nes/hack, v2.nes"),
+                status: VerifyStatus::Unknown,
+                game_name: None,
+                crc32: "deadbeef".to_string(),
+                detail: None,
+            },
+        ],
+        errors: vec![(
+            PathBuf::from("/roms/nes/locked.nes"),
+            "permission denied".to_string(),
+        )],
+    }
+}
+
+#[test]
+fn test_classify_unmatched_by_size_verdict() {
+    let (status, detail) = classify_unmatched(Some(&SizeVerdict::Oversized { excess: 16 }), true);
+    assert_eq!(status, VerifyStatus::Overdump);
+    assert!(detail.unwrap().starts_with("OVERSIZED"));
+
+    let (status, _) = classify_unmatched(Some(&SizeVerdict::CopierHeader), false);
+    assert_eq!(status, VerifyStatus::Overdump);
+
+    let (status, detail) =
+        classify_unmatched(Some(&SizeVerdict::Truncated { missing: 1024 }), false);
+    assert_eq!(status, VerifyStatus::BadDump);
+    assert!(detail.unwrap().starts_with("TRUNCATED"));
+}
+
+#[test]
+fn test_classify_unmatched_by_dat_size() {
+    // Right size, wrong hashes: corrupt
+    let (status, detail) = classify_unmatched(Some(&SizeVerdict::Ok), true);
+    assert_eq!(status, VerifyStatus::BadDump);
+    assert!(detail.is_some());
+
+    let (status, _) = classify_unmatched(None, true);
+    assert_eq!(status, VerifyStatus::BadDump);
+
+    let (status, detail) = classify_unmatched(None, false);
+    assert_eq!(status, VerifyStatus::Unknown);
+    assert!(detail.is_none());
+}
+
+#[test]
+fn test_folder_verification_count() {
+    let v = sample_verification();
+    assert_eq!(v.count(&VerifyStatus::Verified), 1);
+    assert_eq!(v.count(&VerifyStatus::Unknown), 1);
+    assert_eq!(v.count(&VerifyStatus::BadDump), 0);
+}
+
+#[test]
+fn test_write_verify_report() {
+    let dir = std::env::temp_dir().join(format!("rj-verify-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let rows = verify_report_rows("nes", &sample_verification());
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[2].status, "error");
+
+    let path = dir.join("verify.csv");
+    write_verify_report(&path, ExportFormat::Csv, &rows).unwrap();
+    let csv = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "console,file,status,game_name,crc32,detail");
+    assert_eq!(lines.len(), 4);
+    assert!(lines[2].starts_with("nes,\"/roms/nes/hack, v2.nes\",unknown"));
+
+    let json_path = dir.join("verify.json");
+    write_verify_report(&json_path, ExportFormat::Json, &rows).unwrap();
+    let parsed: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    assert_eq!(parsed[0]["status"], "verified");
+    assert_eq!(parsed[0]["game_name"], "Tetris (USA)");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_verify_source_checks_local_and_remote_files() {
+    use retro_junk_nintendo::NesAnalyzer;
+    use std::fs;
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut rom = vec![0u8; 16 + 16 * 1024 + 8 * 1024];
+    rom[..4].copy_from_slice(b"NES\x1a");
+    rom[4] = 1;
+    rom[5] = 1;
+    let path = dir.path().join("Game.nes");
+    fs::write(&path, &rom).unwrap();
+    let hashes =
+        crate::hasher::compute_crc32_sha1(&mut fs::File::open(&path).unwrap(), &NesAnalyzer, None)
+            .unwrap();
+
+    let dat_dir = dir.path().join("dats");
+    fs::create_dir(&dat_dir).unwrap();
+    fs::write(
+        dat_dir.join(format!("{}.dat", NesAnalyzer.short_name())),
+        format!(
+            "<?xml version=\"1.0\"?>\n<datafile>\n\t<header>\n\t\t<name>Test</name>\n\
+             \t\t<description>Test</description>\n\t\t<version>1</version>\n\t</header>\n\
+             \t<game name=\"Game (USA)\">\n\t\t<description>Game (USA)</description>\n\
+             \t\t<rom name=\"Game (USA).nes\" size=\"{}\" crc=\"{}\" sha1=\"{}\"/>\n\
+             \t</game>\n</datafile>\n",
+            hashes.data_size,
+            hashes.crc32,
+            hashes.sha1.unwrap(),
+        ),
+    )
+    .unwrap();
+    let options = VerifyOptions {
+        dat_dir: Some(dat_dir),
+        ..VerifyOptions::default()
+    };
+
+    let local = verify_source(&Source::Local(path), &NesAnalyzer, &options, &|_| {}).unwrap();
+    assert_eq!(local.count(&VerifyStatus::Verified), 1);
+    assert_eq!(local.files[0].game_name.as_deref(), Some("Game (USA)"));
+
+    // An unreachable share is reported, not fatal
+    let share = Source::parse("smb://nas.invalid/roms/Game.nes");
+    let remote = verify_source(&share, &NesAnalyzer, &options, &|_| {}).unwrap();
+    assert!(remote.files.is_empty());
+    assert_eq!(remote.errors.len(), 1);
+    #[cfg(not(windows))]
+    assert!(remote.errors[0].1.contains("only read directly on Windows"));
+}
@@ -0,0 +1,136 @@
+use std::fs;
+
+use super::*;
+
+fn write(path: &Path, contents: &[u8]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+fn extensions(exts: &[&str]) -> HashSet<String> {
+    scanner::extension_set(exts)
+}
+
+#[test]
+fn finds_profiles_by_id_or_name() {
+    assert_eq!(find_profile("MiSTer").unwrap().id, "mister");
+    assert!(find_profile("EverDrive N8").is_some());
+    assert!(find_profile("walkman").is_none());
+    let ids: HashSet<_> = PROFILES.iter().map(|p| p.id).collect();
+    assert_eq!(ids.len(), PROFILES.len());
+}
+
+#[test]
+fn file_names_fit_fat32() {
+    let profile = find_profile("anbernic").unwrap();
+    assert_eq!(
+        profile.file_name("Zelda: Link's Awakening? (USA).gb"),
+        "Zelda - Link's Awakening_ (USA).gb"
+    );
+    assert_eq!(profile.file_name("Game... "), "Game");
+
+    let long = format!("{}.sfc", "A".repeat(300));
+    let short = profile.file_name(&long);
+    assert_eq!(short.len(), 255);
+    assert!(short.ends_with("A.sfc"));
+}
+
+// Library names with ':' can't be created on Windows
+#[cfg(unix)]
+#[test]
+fn plans_preferred_formats_into_the_device_layout() {
+    let lib = tempfile::tempdir().unwrap();
+    let card = tempfile::tempdir().unwrap();
+    let rom_dir = lib.path().join("ps1");
+    write(&rom_dir.join("Game A (USA).chd"), b"chd");
+    write(
+        &rom_dir.join("Game A (USA).cue"),
+        b"FILE \"Game A (USA).bin\" BINARY\n",
+    );
+    write(&rom_dir.join("Game A (USA).bin"), b"bin");
+    write(&rom_dir.join("Game B: Redux (USA).cue"), b"FILE \"Game B: Redux (USA) (Track 1).bin\" BINARY\nFILE \"Game B: Redux (USA) (Track 2).bin\" BINARY\n");
+    write(&rom_dir.join("Game B: Redux (USA) (Track 1).bin"), b"t1");
+    write(&rom_dir.join("Game B: Redux (USA) (Track 2).bin"), b"t2");
+    write(&rom_dir.join("Game C (USA).iso"), b"iso");
+
+    let profile = find_profile("mister").unwrap();
+    let system = profile.system(Platform::Ps1).unwrap();
+    let plan = plan_export(
+        profile,
+        system,
+        &rom_dir,
+        card.path(),
+        &extensions(&["iso", "bin", "chd", "cue"]),
+        None,
+        None,
+    )
+    .unwrap();
+
+    let dests: Vec<_> = plan
+        .files
+        .iter()
+        .map(|f| f.dest.strip_prefix(card.path()).unwrap().to_path_buf())
+        .collect();
+    let psx = Path::new("games").join("PSX");
+    assert!(dests.contains(&psx.join("Game A (USA).chd")));
+    assert!(!dests.contains(&psx.join("Game A (USA).cue")));
+    assert!(dests.contains(&psx.join("Game B - Redux (USA) (Track 2).bin")));
+    assert!(dests.contains(&psx.join("Game B - Redux (USA).cue")));
+    assert_eq!(plan.superseded, vec![rom_dir.join("Game A (USA).cue")]);
+    assert_eq!(plan.unsupported, vec![rom_dir.join("Game C (USA).iso")]);
+
+    assert_eq!(apply_export(&plan, &|_, _| {}).unwrap(), plan.files.len());
+    let sheet =
+        fs::read_to_string(card.path().join(&psx).join("Game B - Redux (USA).cue")).unwrap();
+    assert!(sheet.contains("\"Game B - Redux (USA) (Track 1).bin\""));
+
+    // A second export finds everything already copied
+    let again = plan_export(
+        profile,
+        system,
+        &rom_dir,
+        card.path(),
+        &extensions(&["iso", "bin", "chd", "cue"]),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(again.pending_bytes(), 0);
+}
+
+#[test]
+fn rewrite_sheet_only_touches_file_lines() {
+    let sheet = "REM COMMENT \"Game: A.bin\"\r\nTITLE \"Game: A.bin\"\r\n  file \"Game: A.bin\" BINARY\r\n  TRACK 01 MODE2/2352\r\nFILE \"Other.bin\" BINARY";
+    let rewrites = vec![("Game: A.bin".to_string(), "Game - A.bin".to_string())];
+    assert_eq!(
+        rewrite_sheet(sheet, &rewrites),
+        "REM COMMENT \"Game: A.bin\"\r\nTITLE \"Game: A.bin\"\r\n  FILE \"Game - A.bin\" BINARY\r\n  TRACK 01 MODE2/2352\r\nFILE \"Other.bin\" BINARY"
+    );
+}
+
+#[test]
+fn filter_selects_games_by_name() {
+    let lib = tempfile::tempdir().unwrap();
+    let card = tempfile::tempdir().unwrap();
+    let rom_dir = lib.path().join("nes");
+    write(&rom_dir.join("Mega Man 2 (USA).nes"), b"mm2");
+    write(&rom_dir.join("Metroid (USA).nes"), b"metroid");
+
+    let profile = find_profile("everdrive-n8").unwrap();
+    let system = profile.system(Platform::Nes).unwrap();
+    let plan = plan_export(
+        profile,
+        system,
+        &rom_dir,
+        card.path(),
+        &extensions(&["nes"]),
+        Some("mega man"),
+        None,
+    )
+    .unwrap();
+    assert_eq!(plan.files.len(), 1);
+    assert_eq!(
+        plan.files[0].dest,
+        card.path().join("NES/Mega Man 2 (USA).nes")
+    );
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+#[test]
+fn copy_sectors_reproduces_device_contents() {
+    let dir = tempfile::tempdir().unwrap();
+    let device = dir.path().join("device.img");
+    let output = dir.path().join("dump.iso");
+    // Not a whole number of read blocks, and a partial final sector
+    let data: Vec<u8> = (0..(SECTOR_SIZE * 70 + 100))
+        .map(|i| (i % 253) as u8)
+        .collect();
+    fs::write(&device, &data).unwrap();
+
+    let last = std::cell::Cell::new(0u64);
+    let bad = copy_sectors(&device, &output, 1, &|p| {
+        if let DumpProgress::Reading { bytes_done, .. } = p {
+            last.set(bytes_done);
+        }
+    })
+    .unwrap();
+
+    assert!(bad.is_empty());
+    assert_eq!(fs::read(&output).unwrap(), data);
+    assert_eq!(last.get(), data.len() as u64);
+}
+
+#[test]
+fn copy_sectors_rejects_empty_device() {
+    let dir = tempfile::tempdir().unwrap();
+    let device = dir.path().join("empty");
+    fs::write(&device, b"").unwrap();
+
+    let err = copy_sectors(&device, &dir.path().join("out.iso"), 1, &|_| {}).unwrap_err();
+    assert!(matches!(err, DumpError::EmptyDevice(_)));
+}
+
+#[test]
+fn dump_dvd_refuses_raw_cd_systems_before_reading() {
+    let dir = tempfile::tempdir().unwrap();
+    let device = dir.path().join("device.img");
+    let output = dir.path().join("dump.iso");
+    fs::write(&device, vec![0u8; SECTOR_SIZE as usize * 4]).unwrap();
+
+    let ps1 = retro_junk_sony::Ps1Analyzer;
+    let err = dump_dvd(&device, &output, &ps1, &DumpOptions::default(), &|_| {}).unwrap_err();
+    assert!(matches!(err, DumpError::RawCdRequired(_)));
+    assert!(!output.exists());
+}
+
+#[test]
+fn check_dumpable_tells_ps2_cds_from_dvds() {
+    use retro_junk_sony::synthetic::{make_iso_with_system_cnf, mark_udf_bridge};
+
+    let dir = tempfile::tempdir().unwrap();
+    let ps2 = retro_junk_sony::Ps2Analyzer;
+
+    let cd = dir.path().join("cd.iso");
+    let mut iso = make_iso_with_system_cnf("SLUS_200.01", "BOOT2");
+    fs::write(&cd, &iso).unwrap();
+    let err = check_dumpable(&ps2, &cd).unwrap_err();
+    assert!(matches!(err, DumpError::RawCdRequired(_)));
+
+    let dvd = dir.path().join("dvd.iso");
+    mark_udf_bridge(&mut iso);
+    fs::write(&dvd, &iso).unwrap();
+    assert!(check_dumpable(&ps2, &dvd).is_ok());
+}
+
+#[test]
+fn check_dumpable_refuses_cartridge_systems() {
+    let dir = tempfile::tempdir().unwrap();
+    let device = dir.path().join("device.img");
+    fs::write(&device, vec![0u8; SECTOR_SIZE as usize * 4]).unwrap();
+
+    let err = check_dumpable(&retro_junk_nintendo::GbaAnalyzer, &device).unwrap_err();
+    assert!(matches!(err, DumpError::NotADisc(_)));
+}
@@ -0,0 +1,105 @@
+use super::*;
+
+const BUNDLED: &str = r#"<?xml version="1.0"?>
+<!-- Bundled systems -->
+<systemList>
+    <system>
+        <name>snes</name>
+        <fullname>Nintendo SNES (Super Nintendo)</fullname>
+        <path>%ROMPATH%/snes</path>
+        <extension>.bs .BS .sfc .SFC .smc .SMC .7z .7Z .zip .ZIP</extension>
+        <command label="Snes9x - Current">%EMULATOR_RETROARCH% %CORE_RETROARCH%/snes9x_libretro.so %ROM%</command>
+        <platform>snes</platform>
+        <theme>snes</theme>
+    </system>
+    <system>
+        <name>genesis</name>
+        <fullname>Sega Genesis</fullname>
+        <path>%ROMPATH%/genesis</path>
+        <extension>.bin .BIN .gen .GEN .md .MD</extension>
+        <platform>genesis</platform>
+        <theme>genesis</theme>
+    </system>
+</systemList>
+"#;
+
+const CUSTOM: &str = r#"<?xml version="1.0"?>
+<systemList>
+    <system>
+        <name>snes</name>
+        <fullname>Super Nintendo</fullname>
+        <path>%ROMPATH%\SFC Collection\</path>
+        <extension>.sfc .smc</extension>
+        <platform>snes</platform>
+    </system>
+    <system>
+        <name>mycarts</name>
+        <fullname>My Carts</fullname>
+        <path>%ROMPATH%/Carts</path>
+        <extension>.sfc .gb</extension>
+        <platform>snes, gb</platform>
+    </system>
+</systemList>
+"#;
+
+#[test]
+fn parses_system_entries() {
+    let systems = EsSystems::parse(BUNDLED).unwrap();
+    assert_eq!(systems.systems().len(), 2);
+
+    let snes = &systems.systems()[0];
+    assert_eq!(snes.name, "snes");
+    assert_eq!(snes.fullname, "Nintendo SNES (Super Nintendo)");
+    assert_eq!(snes.folder.as_deref(), Some("snes"));
+    assert_eq!(snes.extensions, vec!["bs", "sfc", "smc", "7z", "zip"]);
+    assert_eq!(snes.platforms, vec!["snes"]);
+}
+
+#[test]
+fn custom_entries_replace_bundled_by_name() {
+    let mut systems = EsSystems::parse(BUNDLED).unwrap();
+    systems.merge(EsSystems::parse(CUSTOM).unwrap());
+
+    assert_eq!(systems.systems().len(), 3);
+    let snes = systems.by_folder("sfc collection").unwrap();
+    assert_eq!(snes.name, "snes");
+    assert!(systems.by_folder("snes").is_none());
+    assert_eq!(
+        systems.by_folder("Carts").unwrap().platforms,
+        vec!["snes", "gb"]
+    );
+}
+
+#[test]
+fn apply_maps_custom_folders_to_platforms() {
+    let mut ctx = crate::create_default_context();
+    assert!(ctx.find_by_folder("SFC Collection").is_empty());
+
+    let systems = EsSystems::parse(CUSTOM).unwrap();
+    assert_eq!(systems.apply_to(&mut ctx), 2);
+
+    let found: Vec<Platform> = ctx
+        .find_by_folder("SFC Collection")
+        .iter()
+        .map(|c| c.metadata.platform)
+        .collect();
+    assert_eq!(found, vec![Platform::Snes]);
+    assert_eq!(ctx.frontend_system_name("sfc collection"), Some("snes"));
+
+    let carts: Vec<Platform> = ctx
+        .find_by_folder("Carts")
+        .iter()
+        .map(|c| c.metadata.platform)
+        .collect();
+    assert_eq!(carts, vec![Platform::Snes, Platform::GameBoy]);
+}
+
+#[test]
+fn folder_from_path_handles_separators() {
+    assert_eq!(folder_from_path("%ROMPATH%/snes").as_deref(), Some("snes"));
+    assert_eq!(
+        folder_from_path("%ROMPATH%\\Super Nintendo\\").as_deref(),
+        Some("Super Nintendo")
+    );
+    assert_eq!(folder_from_path("%ROMPATH%"), None);
+}
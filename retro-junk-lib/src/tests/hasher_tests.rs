@@ -186,3 +186,233 @@ fn test_progress_callback_reports_total_bytes() {
     assert_eq!(hashes.crc32, expected.crc32);
     assert_eq!(hashes.sha1, expected.sha1);
 }
+
+// -- Resumable hashing --
+
+use crate::hash_checkpoint::CheckpointStore;
+
+/// Reader that fails after a fixed number of bytes, simulating an interrupt.
+struct InterruptingReader {
+    inner: std::fs::File,
+    remaining: usize,
+}
+
+impl std::io::Read for InterruptingReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.remaining == 0 {
+            return Err(std::io::Error::other("interrupted"));
+        }
+        let len = buf.len().min(self.remaining);
+        let n = self.inner.read(&mut buf[..len])?;
+        self.remaining -= n;
+        Ok(n)
+    }
+}
+
+impl std::io::Seek for InterruptingReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+fn test_data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+fn sha1_hex(data: &[u8]) -> String {
+    use sha1::Digest as _;
+    retro_junk_core::hash::hex(&sha1::Sha1::digest(data))
+}
+
+#[test]
+fn test_checkpointed_hash_resumes_after_interrupt() {
+    let dir = tempfile::tempdir().unwrap();
+    let rom = dir.path().join("big.bin");
+    let data = test_data(CHUNK_SIZE * 5 + 123);
+    std::fs::write(&rom, &data).unwrap();
+    let store = CheckpointStore {
+        dir: dir.path().join("checkpoints"),
+        min_size: 1,
+        interval: CHUNK_SIZE as u64,
+    };
+
+    let mut reader = InterruptingReader {
+        inner: std::fs::File::open(&rom).unwrap(),
+        remaining: CHUNK_SIZE * 3 + 10,
+    };
+    let interrupted =
+        compute_crc32_sha1_checkpointed(&mut reader, &NullAnalyzer, &|_, _| {}, &rom, &store);
+    assert!(interrupted.is_err());
+
+    let first_progress = std::cell::Cell::new(None);
+    let mut file = std::fs::File::open(&rom).unwrap();
+    let hashes = compute_crc32_sha1_checkpointed(
+        &mut file,
+        &NullAnalyzer,
+        &|done, _| {
+            if first_progress.get().is_none() {
+                first_progress.set(Some(done));
+            }
+        },
+        &rom,
+        &store,
+    )
+    .unwrap();
+
+    let expected = compute_crc32_sha1(&mut Cursor::new(data), &NullAnalyzer, None).unwrap();
+    assert_eq!(hashes.crc32, expected.crc32);
+    assert_eq!(hashes.sha1, expected.sha1);
+    assert_eq!(hashes.data_size, expected.data_size);
+    // Resumed past the checkpointed chunks instead of starting from zero
+    assert!(first_progress.get().unwrap() > CHUNK_SIZE as u64 * 3);
+    // Checkpoint is removed once hashing completes
+    assert_eq!(std::fs::read_dir(&store.dir).unwrap().count(), 0);
+}
+
+#[test]
+fn test_checkpointed_threaded_hash_resumes_after_interrupt() {
+    let dir = tempfile::tempdir().unwrap();
+    let rom = dir.path().join("big.bin");
+    // Large enough for the threaded path
+    let data = test_data(PARALLEL_MIN_SIZE as usize + 4321);
+    std::fs::write(&rom, &data).unwrap();
+    let store = CheckpointStore {
+        dir: dir.path().join("checkpoints"),
+        min_size: 1,
+        interval: PARALLEL_CHUNK_SIZE as u64,
+    };
+
+    let mut reader = InterruptingReader {
+        inner: std::fs::File::open(&rom).unwrap(),
+        remaining: PARALLEL_CHUNK_SIZE * 2 + 10,
+    };
+    let interrupted =
+        compute_crc32_sha1_checkpointed(&mut reader, &NullAnalyzer, &|_, _| {}, &rom, &store);
+    assert!(interrupted.is_err());
+
+    let first_progress = std::cell::Cell::new(None);
+    let hashes = compute_crc32_sha1_checkpointed(
+        &mut std::fs::File::open(&rom).unwrap(),
+        &NullAnalyzer,
+        &|done, _| {
+            if first_progress.get().is_none() {
+                first_progress.set(Some(done));
+            }
+        },
+        &rom,
+        &store,
+    )
+    .unwrap();
+
+    assert_eq!(hashes.crc32, format!("{:08x}", crc32fast::hash(&data)));
+    assert_eq!(hashes.sha1, Some(sha1_hex(&data)));
+    assert!(first_progress.get().unwrap() > PARALLEL_CHUNK_SIZE as u64 * 2);
+}
+
+#[test]
+fn test_checkpoint_ignored_when_file_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let rom = dir.path().join("big.bin");
+    std::fs::write(&rom, test_data(CHUNK_SIZE * 4)).unwrap();
+    let store = CheckpointStore {
+        dir: dir.path().join("checkpoints"),
+        min_size: 1,
+        interval: CHUNK_SIZE as u64,
+    };
+
+    let mut reader = InterruptingReader {
+        inner: std::fs::File::open(&rom).unwrap(),
+        remaining: CHUNK_SIZE * 2 + 10,
+    };
+    let _ = compute_crc32_sha1_checkpointed(&mut reader, &NullAnalyzer, &|_, _| {}, &rom, &store);
+
+    let changed = test_data(CHUNK_SIZE * 4 + 1);
+    std::fs::write(&rom, &changed).unwrap();
+    let mut file = std::fs::File::open(&rom).unwrap();
+    let hashes =
+        compute_crc32_sha1_checkpointed(&mut file, &NullAnalyzer, &|_, _| {}, &rom, &store)
+            .unwrap();
+    let expected = compute_crc32_sha1(&mut Cursor::new(changed), &NullAnalyzer, None).unwrap();
+    assert_eq!(hashes.sha1, expected.sha1);
+}
+
+// -- Parallel hashing --
+
+/// Byte-swaps each 16-bit word, like the N64 normalizer.
+struct SwappingAnalyzer;
+
+impl RomAnalyzer for SwappingAnalyzer {
+    fn analyze(
+        &self,
+        _reader: &mut dyn ReadSeek,
+        _options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        Ok(RomIdentification::new())
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::N64
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["v64"]
+    }
+
+    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
+        true
+    }
+
+    fn dat_chunk_normalizer(
+        &self,
+        _reader: &mut dyn ReadSeek,
+        _header_offset: u64,
+    ) -> retro_junk_core::ChunkNormalizerResult {
+        Ok(Some(Box::new(|chunk: &mut [u8]| {
+            for pair in chunk.chunks_exact_mut(2) {
+                pair.swap(0, 1);
+            }
+        })))
+    }
+}
+
+#[test]
+fn test_parallel_all_hashes_match_single_digests() {
+    // Large enough to take the threaded path, with a partial final chunk
+    let data = test_data(PARALLEL_MIN_SIZE as usize + 12_346);
+    let hashes = compute_all_hashes(&mut Cursor::new(data.clone()), &NullAnalyzer, None).unwrap();
+
+    assert_eq!(hashes.crc32, format!("{:08x}", crc32fast::hash(&data)));
+    assert_eq!(hashes.sha1, Some(sha1_hex(&data)));
+    assert_eq!(hashes.md5, Some(format!("{:x}", md5::compute(&data))));
+    assert_eq!(hashes.data_size, data.len() as u64);
+}
+
+#[test]
+fn test_parallel_hashes_apply_normalizer() {
+    let data = test_data(PARALLEL_MIN_SIZE as usize + 2);
+    let mut swapped = data.clone();
+    for pair in swapped.chunks_exact_mut(2) {
+        pair.swap(0, 1);
+    }
+
+    let hashes = compute_all_hashes(&mut Cursor::new(data), &SwappingAnalyzer, None).unwrap();
+    let expected = compute_all_hashes(&mut Cursor::new(swapped), &NullAnalyzer, None).unwrap();
+    assert_eq!(hashes.crc32, expected.crc32);
+    assert_eq!(hashes.sha1, expected.sha1);
+    assert_eq!(hashes.md5, expected.md5);
+}
+
+#[test]
+fn test_raw_hashes_cover_whole_file() {
+    let mut reader = Cursor::new(b"abc".to_vec());
+    std::io::Seek::seek(&mut reader, SeekFrom::End(0)).unwrap();
+    let hashes = compute_raw_hashes(&mut reader).unwrap();
+    assert_eq!(hashes.crc32, "352441c2");
+    assert_eq!(hashes.sha1, "a9993e364706816aba3e25717850c26c9cd0d89d");
+    assert_eq!(hashes.md5, "900150983cd24fb0d6963f7d28e17f72");
+    assert_eq!(
+        hashes.sha256,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(hashes.size, 3);
+}
@@ -0,0 +1,117 @@
+use std::io::Read;
+
+use super::*;
+
+fn get(addr: SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+/// Send `request` and keep the connection open, returning whatever the
+/// server answers before it closes.
+fn send_unfinished(addr: SocketAddr, request: &[u8]) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(request).unwrap();
+    let mut response = Vec::new();
+    let mut buf = [0u8; 1024];
+    // The server may reset the connection after answering, as it leaves
+    // the rest of the request unread
+    while let Ok(n) = stream.read(&mut buf) {
+        if n == 0 {
+            break;
+        }
+        response.extend_from_slice(&buf[..n]);
+    }
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+#[test]
+fn test_render_counters() {
+    let metrics = Metrics::default();
+    metrics.record_analyzed(1000);
+    metrics.record_analyzed(24);
+    metrics.record_analyzed(0);
+    metrics.record_unmatched();
+
+    let text = metrics.render();
+    assert!(text.contains("# TYPE retro_junk_files_analyzed_total counter\n"));
+    assert!(text.contains("\nretro_junk_files_analyzed_total 3\n"));
+    assert!(text.contains("\nretro_junk_hash_bytes_total 1024\n"));
+    assert!(text.contains("\nretro_junk_unmatched_files_total 1\n"));
+    assert!(text.contains("# TYPE retro_junk_last_poll_timestamp_seconds gauge\n"));
+
+    metrics.set_api_requests(120);
+    metrics.set_api_requests(125);
+    assert!(
+        metrics
+            .render()
+            .contains("\nretro_junk_api_requests_used 125\n")
+    );
+}
+
+#[test]
+fn test_mark_poll() {
+    let metrics = Metrics::default();
+    metrics.mark_poll();
+    assert!(metrics.last_poll.load(Ordering::Relaxed) > 0);
+}
+
+#[test]
+fn test_serve_metrics() {
+    let metrics = Metrics::new();
+    metrics.record_added();
+    metrics.record_added();
+    let addr = serve("127.0.0.1:0", metrics.clone()).unwrap();
+
+    let response = get(addr, "/metrics");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("text/plain; version=0.0.4"));
+    assert!(response.contains("\nretro_junk_files_added_total 2\n"));
+
+    // Counters are read live
+    metrics.record_added();
+    assert!(get(addr, "/metrics").contains("\nretro_junk_files_added_total 3\n"));
+
+    assert!(get(addr, "/").starts_with("HTTP/1.1 404 Not Found\r\n"));
+}
+
+#[test]
+fn test_serve_caps_request_size() {
+    let addr = serve("127.0.0.1:0", Metrics::new()).unwrap();
+    let mut request = b"GET /metrics HTTP/1.1\r\nX-Padding: ".to_vec();
+    request.resize(2 * MAX_REQUEST_BYTES as usize, b'a');
+
+    let started = std::time::Instant::now();
+    let response = send_unfinished(addr, &request);
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(started.elapsed() < READ_TIMEOUT);
+}
+
+#[test]
+fn test_serve_caps_header_count() {
+    let addr = serve("127.0.0.1:0", Metrics::new()).unwrap();
+    let mut request = b"GET /metrics HTTP/1.1\r\n".to_vec();
+    for _ in 0..MAX_HEADERS * 2 {
+        request.extend_from_slice(b"X: y\r\n");
+    }
+
+    let started = std::time::Instant::now();
+    let response = send_unfinished(addr, &request);
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(started.elapsed() < READ_TIMEOUT);
+}
+
+#[test]
+fn test_serve_answers_while_a_client_stalls() {
+    let addr = serve("127.0.0.1:0", Metrics::new()).unwrap();
+    // Connected, but the request never finishes
+    let mut stalled = TcpStream::connect(addr).unwrap();
+    stalled.write_all(b"GET /metrics HTTP/1.1\r\n").unwrap();
+
+    let started = std::time::Instant::now();
+    assert!(get(addr, "/metrics").starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(started.elapsed() < READ_TIMEOUT);
+}
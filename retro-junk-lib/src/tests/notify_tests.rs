@@ -0,0 +1,63 @@
+use super::*;
+
+fn summary() -> RunSummary {
+    let mut summary = RunSummary::new("analyze");
+    summary.count("files", 3);
+    summary.count("files", 2);
+    summary.count("problems", 1);
+    summary.problem("snes/Bad.sfc: Damaged file");
+    summary.finish();
+    summary
+}
+
+#[test]
+fn counts_accumulate_and_text_lists_problems() {
+    let summary = summary();
+    assert_eq!(summary.counts[0].value, 5);
+    assert_eq!(summary.title(), "retro-junk analyze: 1 problem");
+    assert_eq!(
+        summary.text(),
+        "files: 5\nproblems: 1\n\n- snes/Bad.sfc: Damaged file"
+    );
+}
+
+#[test]
+fn long_problem_lists_are_cut_short() {
+    let mut summary = RunSummary::new("scrape");
+    for i in 0..MAX_LISTED_PROBLEMS + 3 {
+        summary.problem(format!("game {}", i));
+    }
+    assert!(summary.text().ends_with("...and 3 more"));
+}
+
+#[test]
+fn builds_each_payload() {
+    let summary = summary();
+
+    let webhook = build_request(NotifyFormat::Webhook, &summary);
+    let json: serde_json::Value = serde_json::from_str(&webhook.body).unwrap();
+    assert_eq!(json["command"], "analyze");
+    assert_eq!(json["counts"][0]["name"], "files");
+    assert_eq!(json["problems"][0], "snes/Bad.sfc: Damaged file");
+
+    let ntfy = build_request(NotifyFormat::Ntfy, &summary);
+    assert_eq!(ntfy.body, summary.text());
+    assert!(ntfy.headers.contains(&("Tags", "warning".to_string())));
+
+    let discord = build_request(NotifyFormat::Discord, &summary);
+    let json: serde_json::Value = serde_json::from_str(&discord.body).unwrap();
+    assert!(
+        json["content"]
+            .as_str()
+            .unwrap()
+            .starts_with("**retro-junk analyze: 1 problem**\nfiles: 5")
+    );
+}
+
+#[test]
+fn format_parses_cli_names() {
+    assert_eq!("ntfy".parse::<NotifyFormat>(), Ok(NotifyFormat::Ntfy));
+    assert_eq!("Discord".parse::<NotifyFormat>(), Ok(NotifyFormat::Discord));
+    assert_eq!("json".parse::<NotifyFormat>(), Ok(NotifyFormat::Webhook));
+    assert!("email".parse::<NotifyFormat>().is_err());
+}
@@ -0,0 +1,69 @@
+use super::*;
+use retro_junk_nintendo::NesAnalyzer;
+
+fn touch(dir: &Path, name: &str) {
+    let path = dir.join(name);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, b"rom").unwrap();
+}
+
+fn file_name(entry: &GroupedEntry) -> &str {
+    entry.entry.display_name()
+}
+
+#[test]
+fn test_plan_keeps_one_release_per_game() {
+    let dir = tempfile::tempdir().unwrap();
+    touch(dir.path(), "Tetris (Japan).nes");
+    touch(dir.path(), "Tetris (USA).nes");
+    touch(dir.path(), "Tetris (Europe) (Beta).nes");
+    touch(dir.path(), "Zelda (USA).nes");
+
+    let plan = plan_folder(dir.path(), &NesAnalyzer, None, &OneG1rOptions::default()).unwrap();
+    assert!(!plan.used_dat);
+    assert_eq!(plan.sets.len(), 1);
+    assert_eq!(file_name(&plan.sets[0].keep), "Tetris (USA).nes");
+    let dropped: Vec<&str> = plan.sets[0].drop.iter().map(file_name).collect();
+    assert_eq!(
+        dropped,
+        ["Tetris (Japan).nes", "Tetris (Europe) (Beta).nes"]
+    );
+    assert_eq!(plan.duplicate_count(), 2);
+}
+
+#[test]
+fn test_move_duplicates_keeps_relative_paths_and_hides_them() {
+    let dir = tempfile::tempdir().unwrap();
+    touch(dir.path(), "T/Tetris (Japan).nes");
+    touch(dir.path(), "T/Tetris (USA).nes");
+    touch(dir.path(), "T/Tetris (Japan).srm");
+
+    let plan = plan_folder(dir.path(), &NesAnalyzer, None, &OneG1rOptions::default()).unwrap();
+    let summary = move_duplicates(dir.path(), &plan);
+    assert!(summary.errors.is_empty(), "{:?}", summary.errors);
+    assert_eq!(summary.moved, 2);
+
+    let moved = dir.path().join(DUPLICATES_DIR).join("T");
+    assert!(moved.join("Tetris (Japan).nes").exists());
+    assert!(moved.join("Tetris (Japan).srm").exists());
+    assert!(dir.path().join("T/Tetris (USA).nes").exists());
+
+    // The duplicates folder is not scanned again
+    let plan = plan_folder(dir.path(), &NesAnalyzer, None, &OneG1rOptions::default()).unwrap();
+    assert!(plan.sets.is_empty());
+}
+
+#[test]
+fn test_move_duplicates_never_overwrites() {
+    let dir = tempfile::tempdir().unwrap();
+    touch(dir.path(), "Tetris (Japan).nes");
+    touch(dir.path(), "Tetris (USA).nes");
+    touch(&dir.path().join(DUPLICATES_DIR), "Tetris (Japan).nes");
+
+    let plan = plan_folder(dir.path(), &NesAnalyzer, None, &OneG1rOptions::default()).unwrap();
+    let summary = move_duplicates(dir.path(), &plan);
+    assert_eq!(summary.moved, 0);
+    assert_eq!(summary.errors.len(), 1);
+    assert!(summary.errors[0].1.contains("already exists"));
+    assert!(dir.path().join("Tetris (Japan).nes").exists());
+}
@@ -0,0 +1,168 @@
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::*;
+
+/// How the test server answers `Range` requests.
+#[derive(Clone, Copy, PartialEq)]
+enum Ranges {
+    Ignored,
+    Honoured,
+    /// Honoured, with `*` for the total in `Content-Range`.
+    WithoutTotal,
+}
+
+/// Serve `data` over HTTP on a local port, answering `Range` headers as
+/// `ranges` says. Returns the URL and a count of requests served.
+fn serve(data: Vec<u8>, ranges: Ranges) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}/roms/Test%20Game.nes",
+        listener.local_addr().unwrap()
+    );
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            counter.fetch_add(1, Ordering::SeqCst);
+            let mut range = None;
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                    break;
+                }
+                let lower = line.to_ascii_lowercase();
+                if let Some(value) = lower.strip_prefix("range: bytes=") {
+                    let (start, end) = value.trim().split_once('-').unwrap();
+                    range = Some((
+                        start.parse::<usize>().unwrap(),
+                        end.parse::<usize>().unwrap(),
+                    ));
+                }
+            }
+            let response = match range.filter(|_| ranges != Ranges::Ignored) {
+                Some((start, end)) => {
+                    let end = end.min(data.len() - 1);
+                    let total = match ranges {
+                        Ranges::WithoutTotal => "*".to_string(),
+                        _ => data.len().to_string(),
+                    };
+                    let mut head = format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        start,
+                        end,
+                        total,
+                        end - start + 1
+                    )
+                    .into_bytes();
+                    head.extend_from_slice(&data[start..=end]);
+                    head
+                }
+                None => {
+                    let mut head = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        data.len()
+                    )
+                    .into_bytes();
+                    head.extend_from_slice(&data);
+                    head
+                }
+            };
+            let _ = stream.write_all(&response);
+        }
+    });
+    (url, requests)
+}
+
+fn sample(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 253) as u8).collect()
+}
+
+#[test]
+fn parses_sources() {
+    assert_eq!(Source::parse("-"), Source::Stdin);
+    assert_eq!(
+        Source::parse("https://example.com/a.nes"),
+        Source::Http("https://example.com/a.nes".into())
+    );
+    let smb = Source::Smb {
+        host: "nas".into(),
+        share: "roms".into(),
+        path: "nes/Game.nes".into(),
+    };
+    assert_eq!(Source::parse("smb://nas/roms/nes/Game.nes"), smb);
+    assert_eq!(Source::parse(r"\\nas\roms\nes\Game.nes"), smb);
+    assert_eq!(smb.to_string(), r"\\nas\roms\nes\Game.nes");
+    assert_eq!(
+        Source::parse("roms/Game.nes"),
+        Source::Local(PathBuf::from("roms/Game.nes"))
+    );
+}
+
+#[test]
+fn file_names_ignore_queries_and_folders() {
+    assert_eq!(
+        Source::parse("http://host/dir/Game.nes?dl=1").file_name(),
+        "Game.nes"
+    );
+    assert_eq!(
+        Source::parse("smb://nas/roms/Game.nes").file_name(),
+        "Game.nes"
+    );
+    assert_eq!(Source::parse("-").file_name(), "<stdin>");
+}
+
+#[test]
+fn content_range_gives_total_size() {
+    assert_eq!(content_range_total("bytes 0-0/12345"), Some(12345));
+    assert_eq!(content_range_total("bytes 0-0/*"), None);
+}
+
+#[test]
+fn range_reader_fetches_only_requested_blocks() {
+    let data = sample(4 * BLOCK_SIZE as usize + 100);
+    let (url, requests) = serve(data.clone(), Ranges::Honoured);
+    let mut reader = Source::Http(url).open().unwrap();
+
+    assert_eq!(reader.seek(SeekFrom::End(0)).unwrap(), data.len() as u64);
+    let mut buf = [0u8; 8];
+    reader.seek(SeekFrom::Start(3 * BLOCK_SIZE - 4)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..], &data[3 * BLOCK_SIZE as usize - 4..][..8]);
+    // Probe plus the two blocks the read straddled
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    reader.seek(SeekFrom::Start(3 * BLOCK_SIZE)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn servers_without_ranges_are_streamed() {
+    let data = sample(1000);
+    let (url, _) = serve(data.clone(), Ranges::Ignored);
+    let mut reader = Source::Http(url).open().unwrap();
+
+    assert_eq!(reader.seek(SeekFrom::End(0)).unwrap(), 1000);
+    reader.seek(SeekFrom::Start(0)).unwrap();
+    let mut all = Vec::new();
+    reader.read_to_end(&mut all).unwrap();
+    assert_eq!(all, data);
+}
+
+#[test]
+fn ranges_without_a_total_size_are_streamed_whole() {
+    let data = sample(1000);
+    let (url, requests) = serve(data.clone(), Ranges::WithoutTotal);
+    let mut reader = Source::Http(url).open().unwrap();
+
+    let mut all = Vec::new();
+    reader.read_to_end(&mut all).unwrap();
+    assert_eq!(all, data);
+    // The probe, then one plain request for the whole body
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use retro_junk_dat::matcher::MatchMethod;
+
+use super::*;
+use crate::rename::{MatchDiscrepancy, RenameAction, UnmatchedFile};
+
+fn sample_plan() -> RenamePlan {
+    RenamePlan {
+        renames: vec![RenameAction {
+            source: PathBuf::from("/roms/snes/smw.sfc"),
+            target: PathBuf::from("/roms/snes/Super Mario World (USA).sfc"),
+            game_name: "Super Mario World (USA)".to_string(),
+            matched_by: MatchMethod::Crc32,
+        }],
+        already_correct: vec![PathBuf::from("/roms/snes/F-Zero (USA).sfc")],
+        unmatched: vec![UnmatchedFile {
+            file: PathBuf::from("/roms/snes/hack, v2.sfc"),
+            crc32: Some("deadbeef".to_string()),
+            data_size: Some(1024),
+            mismatch_reason: None,
+        }],
+        discrepancies: vec![MatchDiscrepancy {
+            file: PathBuf::from("/roms/snes/smw.sfc"),
+            serial_game: "Super Mario World (Japan)".to_string(),
+            hash_game: "Super Mario World (USA)".to_string(),
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_export_format_from_path() {
+    assert_eq!(
+        ExportFormat::from_path(Path::new("plan.CSV")),
+        Some(ExportFormat::Csv)
+    );
+    assert_eq!(
+        ExportFormat::from_path(Path::new("plan.json")),
+        Some(ExportFormat::Json)
+    );
+    assert_eq!(ExportFormat::from_path(Path::new("plan.txt")), None);
+    assert_eq!(ExportFormat::from_path(Path::new("plan")), None);
+}
+
+#[test]
+fn test_plan_export_rows() {
+    let rows = plan_export_rows("snes", &sample_plan());
+    assert_eq!(rows.len(), 3);
+
+    assert_eq!(rows[0].action, "rename");
+    assert_eq!(rows[0].match_method, Some("CRC32"));
+    assert!(
+        rows[0]
+            .warnings
+            .as_deref()
+            .unwrap()
+            .contains("hash matched")
+    );
+
+    assert_eq!(rows[1].action, "already-correct");
+    assert_eq!(rows[2].action, "unmatched");
+    assert_eq!(rows[2].warnings.as_deref(), Some("CRC32 deadbeef"));
+}
+
+#[test]
+fn test_write_plan_export_csv_quotes_fields() {
+    let dir = std::env::temp_dir().join(format!("rj-export-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("plan.csv");
+
+    let rows = plan_export_rows("snes", &sample_plan());
+    write_plan_export(&path, ExportFormat::Csv, &rows).unwrap();
+    let csv = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "console,action,source,target,game_name,match_method,warnings"
+    );
+    assert_eq!(lines.len(), 4);
+    assert!(lines[3].contains("\"/roms/snes/hack, v2.sfc\""));
+
+    let json_path = dir.join("plan.json");
+    write_plan_export(&json_path, ExportFormat::Json, &rows).unwrap();
+    let parsed: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    assert_eq!(parsed.as_array().unwrap().len(), 3);
+    assert_eq!(parsed[0]["game_name"], "Super Mario World (USA)");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_csv_export_quotes_fields() {
+    let dir = std::env::temp_dir().join(format!("rj_export_quotes_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("plan.csv");
+    let row = PlanExportRow {
+        console: "snes".into(),
+        action: "unmatched",
+        source: "/roms/say \"hi\"\nagain.sfc".into(),
+        target: None,
+        game_name: None,
+        match_method: None,
+        warnings: None,
+    };
+    write_plan_export(&path, ExportFormat::Csv, &[row]).unwrap();
+    let csv = std::fs::read_to_string(&path).unwrap();
+    assert!(csv.contains("snes,unmatched,\"/roms/say \"\"hi\"\"\nagain.sfc\",,,,\n"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
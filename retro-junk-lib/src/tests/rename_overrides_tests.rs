@@ -0,0 +1,48 @@
+use std::path::Path;
+
+use super::*;
+
+#[test]
+fn parses_game_and_crc_forms() {
+    let overrides = RenameOverrides::parse(
+        r#"
+"smw beta.sfc" = "Super Mario World (USA)"
+"proto.sfc" = { crc32 = "B19ED489" }
+"FF7\\disc1.cue" = { game = "Final Fantasy VII (USA) (Disc 1)" }
+"#,
+    )
+    .unwrap();
+    let folder = Path::new("/roms/snes");
+
+    assert_eq!(overrides.len(), 3);
+    assert_eq!(
+        overrides.get(folder, &folder.join("smw beta.sfc")),
+        Some(&RenameOverride::Game("Super Mario World (USA)".into()))
+    );
+    assert_eq!(
+        overrides.get(folder, &folder.join("proto.sfc")),
+        Some(&RenameOverride::Crc32("b19ed489".into()))
+    );
+    assert_eq!(
+        overrides.get(folder, &folder.join("FF7").join("disc1.cue")),
+        Some(&RenameOverride::Game(
+            "Final Fantasy VII (USA) (Disc 1)".into()
+        ))
+    );
+    assert!(overrides.get(folder, &folder.join("other.sfc")).is_none());
+}
+
+#[test]
+fn rejects_ambiguous_table() {
+    let err =
+        RenameOverrides::parse(r#""a.sfc" = { game = "A", crc32 = "00000000" }"#).unwrap_err();
+    assert!(err.contains("a.sfc"));
+    assert!(RenameOverrides::parse(r#""a.sfc" = {}"#).is_err());
+}
+
+#[test]
+fn missing_file_loads_empty() {
+    let dir = std::env::temp_dir().join("retro-junk-no-overrides-here");
+    let overrides = RenameOverrides::load(&dir).unwrap();
+    assert!(overrides.is_empty());
+}
@@ -0,0 +1,455 @@
+use super::*;
+
+fn action(source: &str, target: &str, method: MatchMethod) -> RenameAction {
+    RenameAction {
+        source: PathBuf::from(source),
+        target: PathBuf::from(target),
+        game_name: "Game".to_string(),
+        matched_by: method,
+    }
+}
+
+fn duplicates() -> Vec<RenameAction> {
+    vec![
+        action("/roms/a.sfc", "/roms/Game (USA).sfc", MatchMethod::Serial),
+        action("/roms/b.sfc", "/roms/Game (USA).sfc", MatchMethod::Sha1),
+        action("/roms/c.sfc", "/roms/Other (USA).sfc", MatchMethod::Crc32),
+    ]
+}
+
+fn sources(renames: &[RenameAction]) -> Vec<&str> {
+    renames.iter().map(|r| r.source.to_str().unwrap()).collect()
+}
+
+#[test]
+fn skip_drops_all_conflicting_renames() {
+    let (renames, conflicts) = resolve_conflicts(duplicates(), ConflictStrategy::Skip);
+    assert_eq!(sources(&renames), vec!["/roms/c.sfc"]);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].0, PathBuf::from("/roms/Game (USA).sfc"));
+}
+
+#[test]
+fn keep_first_keeps_scan_order_winner() {
+    let (renames, conflicts) = resolve_conflicts(duplicates(), ConflictStrategy::KeepFirst);
+    assert_eq!(sources(&renames), vec!["/roms/a.sfc", "/roms/c.sfc"]);
+    assert!(conflicts[0].1.contains("kept a.sfc"));
+}
+
+#[test]
+fn keep_best_prefers_hash_match() {
+    let (renames, conflicts) = resolve_conflicts(duplicates(), ConflictStrategy::KeepBest);
+    assert_eq!(sources(&renames), vec!["/roms/b.sfc", "/roms/c.sfc"]);
+    assert!(conflicts[0].1.contains("kept b.sfc"));
+}
+
+#[test]
+fn dedup_suffix_renames_every_file() {
+    let (renames, conflicts) = resolve_conflicts(duplicates(), ConflictStrategy::DedupSuffix);
+    assert!(conflicts.is_empty());
+    assert_eq!(renames.len(), 3);
+    assert_eq!(renames[0].target, PathBuf::from("/roms/Game (USA).sfc"));
+    assert_eq!(renames[1].target, PathBuf::from("/roms/Game (USA) (1).sfc"));
+}
+
+#[test]
+fn dedup_suffix_avoids_other_targets() {
+    let mut input = duplicates();
+    input.push(action(
+        "/roms/d.sfc",
+        "/roms/Game (USA) (1).sfc",
+        MatchMethod::Crc32,
+    ));
+    let (renames, _) = resolve_conflicts(input, ConflictStrategy::DedupSuffix);
+    assert_eq!(renames[1].target, PathBuf::from("/roms/Game (USA) (2).sfc"));
+}
+
+#[test]
+fn conflict_strategy_parses_cli_names() {
+    for strategy in [
+        ConflictStrategy::Skip,
+        ConflictStrategy::KeepFirst,
+        ConflictStrategy::KeepBest,
+        ConflictStrategy::DedupSuffix,
+    ] {
+        assert_eq!(strategy.as_str().parse::<ConflictStrategy>(), Ok(strategy));
+    }
+    assert!("newest".parse::<ConflictStrategy>().is_err());
+}
+
+#[test]
+fn execute_renames_moves_sidecars() {
+    use crate::sidecar::{SidecarFormat, read_sidecar, sidecar_path, update_sidecar};
+
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("smw.sfc");
+    let target = dir.path().join("Super Mario World (USA).sfc");
+    std::fs::write(&source, b"rom").unwrap();
+    update_sidecar(&source, SidecarFormat::Toml, |_| {}).unwrap();
+
+    let plan = RenamePlan {
+        renames: vec![RenameAction {
+            source: source.clone(),
+            target: target.clone(),
+            game_name: "Super Mario World (USA)".to_string(),
+            matched_by: MatchMethod::Crc32,
+        }],
+        ..Default::default()
+    };
+    let summary = execute_renames(&plan);
+
+    assert_eq!(summary.renamed, 1);
+    assert!(summary.errors.is_empty());
+    assert!(!sidecar_path(&source, SidecarFormat::Toml).exists());
+    assert!(read_sidecar(&target).unwrap().is_some());
+}
+
+#[cfg(unix)]
+#[test]
+fn renames_symlink_target_when_asked() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = dir.path().join("store");
+    let roms = dir.path().join("snes");
+    std::fs::create_dir_all(&store).unwrap();
+    std::fs::create_dir_all(&roms).unwrap();
+    std::fs::write(store.join("smw.sfc"), b"rom").unwrap();
+    std::os::unix::fs::symlink("../store/smw.sfc", roms.join("smw.sfc")).unwrap();
+
+    let plan = |link_rename| RenamePlan {
+        renames: vec![RenameAction {
+            source: roms.join("smw.sfc"),
+            target: roms.join("Super Mario World (USA).sfc"),
+            game_name: "Super Mario World (USA)".to_string(),
+            matched_by: MatchMethod::Crc32,
+        }],
+        link_rename,
+        ..Default::default()
+    };
+    let summary = execute_renames(&plan(LinkRename::Target));
+
+    assert_eq!(summary.renamed, 1, "{:?}", summary.errors);
+    let link = roms.join("Super Mario World (USA).sfc");
+    assert!(!roms.join("smw.sfc").exists());
+    assert!(store.join("Super Mario World (USA).sfc").exists());
+    assert_eq!(
+        std::fs::read_link(&link).unwrap(),
+        Path::new("../store/Super Mario World (USA).sfc")
+    );
+    assert_eq!(std::fs::read(&link).unwrap(), b"rom");
+}
+
+#[cfg(unix)]
+#[test]
+fn renames_symlink_itself_by_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("store.sfc");
+    std::fs::write(&target, b"rom").unwrap();
+    let link = dir.path().join("smw.sfc");
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+
+    let plan = RenamePlan {
+        renames: vec![RenameAction {
+            source: link.clone(),
+            target: dir.path().join("Super Mario World (USA).sfc"),
+            game_name: "Super Mario World (USA)".to_string(),
+            matched_by: MatchMethod::Crc32,
+        }],
+        ..Default::default()
+    };
+    let summary = execute_renames(&plan);
+
+    assert_eq!(summary.renamed, 1);
+    assert!(target.exists());
+    let renamed = dir.path().join("Super Mario World (USA).sfc");
+    assert!(renamed.symlink_metadata().unwrap().file_type().is_symlink());
+}
+
+#[cfg(unix)]
+#[test]
+fn renames_non_utf8_rom_and_media() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let roms = dir.path().join("gb");
+    let media = dir.path().join("media");
+    let covers = media.join("gb/covers");
+    std::fs::create_dir_all(&roms).unwrap();
+    std::fs::create_dir_all(&covers).unwrap();
+
+    let source = roms.join(OsStr::from_bytes(b"Pok\xe9mon.gb"));
+    std::fs::write(&source, b"rom").unwrap();
+    std::fs::write(covers.join(OsStr::from_bytes(b"Pok\xe9mon.png")), b"png").unwrap();
+    // Same lossy spelling, different bytes: must not be picked up
+    std::fs::write(covers.join(OsStr::from_bytes(b"Pok\xe8mon.png")), b"png").unwrap();
+
+    let target = roms.join("Pokemon Red (USA).gb");
+    let plan = RenamePlan {
+        renames: vec![RenameAction {
+            source: source.clone(),
+            target: target.clone(),
+            game_name: "Pokemon Red (USA)".to_string(),
+            matched_by: MatchMethod::Crc32,
+        }],
+        ..Default::default()
+    };
+
+    let media_plan = plan_media_renames(&plan, &media, "gb");
+    assert_eq!(media_plan.renames.len(), 1);
+    assert_eq!(
+        media_plan.renames[0].source,
+        covers.join(OsStr::from_bytes(b"Pok\xe9mon.png"))
+    );
+    assert_eq!(
+        media_plan.renames[0].target,
+        covers.join("Pokemon Red (USA).png")
+    );
+
+    let summary = execute_renames(&plan);
+    assert_eq!(summary.renamed, 1, "{:?}", summary.errors);
+    assert!(target.exists());
+    assert!(!source.exists());
+}
+
+#[test]
+fn execute_renames_records_sanitized_names() {
+    use crate::sanitize::CanonicalNames;
+
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("zelda.gb");
+    let target = dir.path().join("Zelda - Link's Awakening (USA).gb");
+    std::fs::write(&source, b"rom").unwrap();
+    let mut stale = CanonicalNames::default();
+    stale.insert("zelda.gb", "Something Else.gb");
+    stale.save(dir.path()).unwrap();
+
+    let plan = RenamePlan {
+        renames: vec![RenameAction {
+            source,
+            target: target.clone(),
+            game_name: "Zelda: Link's Awakening (USA)".to_string(),
+            matched_by: MatchMethod::Crc32,
+        }],
+        canonical_names: vec![(target, "Zelda: Link's Awakening (USA).gb".to_string())],
+        ..Default::default()
+    };
+    let summary = execute_renames(&plan);
+
+    assert!(summary.errors.is_empty(), "{:?}", summary.errors);
+    assert_eq!(summary.canonical_names_recorded, 1);
+    let names = CanonicalNames::load(dir.path()).unwrap();
+    assert_eq!(names.len(), 1);
+    assert_eq!(
+        names.get("Zelda - Link's Awakening (USA).gb"),
+        Some("Zelda: Link's Awakening (USA).gb")
+    );
+}
+
+fn disc(path: &str, game_name: &str, ext: &str) -> DiscMatchData {
+    DiscMatchData {
+        file_path: PathBuf::from(path),
+        game_name: game_name.to_string(),
+        target_filename: format!("{}.{}", game_name, ext),
+    }
+}
+
+#[test]
+fn groups_loose_discs_by_dat_name() {
+    let sets = group_loose_discs(vec![
+        disc("/psx/ff7-2.chd", "Final Fantasy VII (USA) (Disc 2)", "chd"),
+        disc("/psx/ff7-1.chd", "Final Fantasy VII (USA) (Disc 1)", "chd"),
+        disc(
+            "/psx/ff7-eu.chd",
+            "Final Fantasy VII (Europe) (Disc 1)",
+            "chd",
+        ),
+        disc(
+            "/psx/other/ff7-3.chd",
+            "Final Fantasy VII (USA) (Disc 3)",
+            "chd",
+        ),
+        disc("/psx/crash.chd", "Crash Bandicoot (USA)", "chd"),
+    ]);
+
+    assert_eq!(sets.len(), 1);
+    let files: Vec<_> = sets[0].iter().map(|d| d.file_path.clone()).collect();
+    assert_eq!(
+        files,
+        vec![
+            PathBuf::from("/psx/ff7-1.chd"),
+            PathBuf::from("/psx/ff7-2.chd")
+        ]
+    );
+}
+
+#[test]
+fn loose_discs_need_a_playlist_entry() {
+    let sets = group_loose_discs(vec![
+        disc("/psx/a.bin", "Game (USA) (Disc 1)", "bin"),
+        disc("/psx/b.bin", "Game (USA) (Disc 2)", "bin"),
+    ]);
+    assert!(sets.is_empty());
+}
+
+#[test]
+fn create_folder_moves_loose_discs_and_tracks() {
+    let dir = tempfile::tempdir().unwrap();
+    for n in 1..=2 {
+        std::fs::write(dir.path().join(format!("game{n}.bin")), b"data").unwrap();
+        std::fs::write(
+            dir.path().join(format!("game{n}.cue")),
+            format!("FILE \"game{n}.bin\" BINARY\n  TRACK 01 MODE2/2352\n"),
+        )
+        .unwrap();
+    }
+    let folder = dir.path().join("Game (USA).m3u");
+    let job = M3uRenameJob {
+        source_folder: folder.clone(),
+        discs: (1..=2)
+            .map(|n| {
+                disc(
+                    dir.path().join(format!("game{n}.cue")).to_str().unwrap(),
+                    &format!("Game (USA) (Disc {n})"),
+                    "cue",
+                )
+            })
+            .collect(),
+        game_name_override: None,
+        create_folder: true,
+    };
+
+    let result = execute_m3u_rename(&job, LinkRename::default(), None);
+
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert!(result.folder_created);
+    assert_eq!(result.discs_renamed, 2);
+    assert!(folder.join("game1.bin").is_file());
+    assert!(folder.join("Game (USA) (Disc 2).cue").is_file());
+    assert!(!dir.path().join("game1.cue").exists());
+    assert_eq!(
+        std::fs::read_to_string(folder.join("Game (USA).m3u")).unwrap(),
+        "Game (USA) (Disc 1).cue\nGame (USA) (Disc 2).cue\n"
+    );
+}
+
+#[test]
+fn companion_files_follow_their_rom() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("smw.sfc");
+    let target = dir.path().join("Super Mario World (USA).sfc");
+    for name in ["smw.sfc", "smw.srm", "smw.IPS", "smw.txt", "zelda.srm"] {
+        std::fs::write(dir.path().join(name), name).unwrap();
+    }
+    let renames = vec![RenameAction {
+        source: source.clone(),
+        target: target.clone(),
+        game_name: "Super Mario World (USA)".to_string(),
+        matched_by: MatchMethod::Crc32,
+    }];
+
+    let companion_renames =
+        plan_companion_renames(&renames, &RenameOptions::default().companion_extensions);
+    let mut targets: Vec<_> = companion_renames
+        .iter()
+        .map(|c| c.target.file_name().unwrap().to_str().unwrap())
+        .collect();
+    targets.sort();
+    assert_eq!(
+        targets,
+        vec!["Super Mario World (USA).IPS", "Super Mario World (USA).srm"]
+    );
+    assert!(plan_companion_renames(&renames, &[]).is_empty());
+
+    let plan = RenamePlan {
+        renames,
+        companion_renames,
+        ..Default::default()
+    };
+    let summary = execute_renames(&plan);
+
+    assert_eq!(summary.renamed, 1);
+    assert_eq!(summary.companions_renamed, 2);
+    assert!(summary.errors.is_empty());
+    assert!(dir.path().join("Super Mario World (USA).srm").exists());
+    assert!(!dir.path().join("smw.srm").exists());
+    assert!(dir.path().join("smw.txt").exists());
+    assert!(dir.path().join("zelda.srm").exists());
+}
+
+#[test]
+fn companion_files_stay_when_rom_rename_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("smw.sfc");
+    let target = dir.path().join("Super Mario World (USA).sfc");
+    std::fs::write(&source, b"rom").unwrap();
+    std::fs::write(&target, b"other").unwrap();
+    std::fs::write(dir.path().join("smw.sav"), b"save").unwrap();
+
+    let plan = RenamePlan {
+        companion_renames: vec![CompanionRename {
+            rom: source.clone(),
+            source: dir.path().join("smw.sav"),
+            target: dir.path().join("Super Mario World (USA).sav"),
+        }],
+        renames: vec![RenameAction {
+            source,
+            target,
+            game_name: "Super Mario World (USA)".to_string(),
+            matched_by: MatchMethod::Crc32,
+        }],
+        ..Default::default()
+    };
+    let summary = execute_renames(&plan);
+
+    assert_eq!(summary.companions_renamed, 0);
+    assert!(dir.path().join("smw.sav").exists());
+}
+
+#[test]
+fn match_by_hash_skips_files_that_cannot_match() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut disc = retro_junk_nintendo::synthetic::gamecube().data;
+    disc[0x200..0x204].copy_from_slice(b"NKIT");
+    let path = dir.path().join("Game.iso");
+    fs::write(&path, disc).unwrap();
+
+    let index = DatIndex::from_dats(Vec::new());
+    let outcome = match_by_hash(
+        &path,
+        &index,
+        &retro_junk_nintendo::GameCubeAnalyzer,
+        &|p| panic!("hashed anyway: {p:?}"),
+    )
+    .unwrap();
+    assert!(outcome.result.is_none());
+    assert!(outcome.crc32.is_empty());
+    assert!(outcome.mismatch_reason.unwrap().contains("NKit"));
+}
+
+#[test]
+fn content_type_picks_matching_dat_entries() {
+    let base = "Pilotwings Resort (USA)";
+    let update = "Pilotwings Resort (USA) (Update)";
+    let dlc = "Pilotwings Resort (USA) (DLC)";
+
+    assert!(content_type_matches(Some("Base"), base));
+    assert!(!content_type_matches(Some("Base"), update));
+    assert!(!content_type_matches(Some("Base"), dlc));
+    assert!(content_type_matches(Some("Update"), update));
+    assert!(!content_type_matches(Some("Update"), base));
+    assert!(content_type_matches(Some("DLC"), dlc));
+    assert!(!content_type_matches(Some("DLC"), update));
+    assert!(content_type_matches(None, update));
+}
+
+#[test]
+fn other_content_types_match_anything() {
+    assert!(content_type_matches(
+        Some("Title Update"),
+        "Halo 3 (USA) (Update)"
+    ));
+    assert!(content_type_matches(
+        Some("Games on Demand"),
+        "Halo 3 (USA)"
+    ));
+}
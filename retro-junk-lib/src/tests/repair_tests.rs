@@ -67,6 +67,36 @@ fn test_repair_method_description() {
         bytes_added: 352800,
     };
     assert_eq!(m.description(), "prepend 352800 bytes of 0x00");
+
+    let m = RepairMethod::StripHeader { bytes_removed: 512 };
+    assert_eq!(m.description(), "remove copier header (512 bytes)");
+}
+
+#[test]
+fn test_strip_file_header() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("game.sfc");
+    let mut data = vec![0xAAu8; 512];
+    data.extend((0..1024u32).map(|i| i as u8));
+    fs::write(&path, &data).unwrap();
+
+    strip_file_header(&path, 512).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), &data[512..]);
+    assert!(!path.with_extension("repair_tmp").exists());
+}
+
+#[test]
+fn test_strip_file_header_keeps_existing_temp_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("game.sfc");
+    fs::write(&path, vec![0xAAu8; 1024]).unwrap();
+    let tmp_path = path.with_extension("repair_tmp");
+    fs::write(&tmp_path, b"someone else's").unwrap();
+
+    let err = strip_file_header(&path, 512).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    assert_eq!(fs::read(&tmp_path).unwrap(), b"someone else's");
+    assert_eq!(fs::read(&path).unwrap().len(), 1024);
 }
 
 #[test]
@@ -79,3 +109,107 @@ fn test_backup_extension() {
     ));
     assert_eq!(bak_path, PathBuf::from("/roms/snes/game.sfc.bak"));
 }
+
+#[test]
+fn test_strip_headers_plans_and_repairs_headered_genesis_rom() {
+    use retro_junk_sega::GenesisAnalyzer;
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut rom = vec![0u8; 0x400];
+    rom[0x100..0x110].copy_from_slice(b"SEGA GENESIS    ");
+    let plain_path = dir.path().join("plain.md");
+    fs::write(&plain_path, &rom).unwrap();
+    let hashes = hasher::compute_crc32_sha1(
+        &mut fs::File::open(&plain_path).unwrap(),
+        &GenesisAnalyzer,
+        None,
+    )
+    .unwrap();
+    fs::remove_file(&plain_path).unwrap();
+
+    let dat_dir = dir.path().join("dats");
+    fs::create_dir(&dat_dir).unwrap();
+    fs::write(
+        dat_dir.join(format!("{}.dat", GenesisAnalyzer.short_name())),
+        format!(
+            "<?xml version=\"1.0\"?>\n<datafile>\n\t<header>\n\t\t<name>Test</name>\n\
+             \t\t<description>Test</description>\n\t\t<version>1</version>\n\t</header>\n\
+             \t<game name=\"Game (USA)\">\n\t\t<description>Game (USA)</description>\n\
+             \t\t<rom name=\"Game (USA).md\" size=\"{}\" crc=\"{}\" sha1=\"{}\"/>\n\
+             \t</game>\n</datafile>\n",
+            rom.len(),
+            hashes.crc32,
+            hashes.sha1.unwrap(),
+        ),
+    )
+    .unwrap();
+
+    let roms = dir.path().join("genesis");
+    fs::create_dir(&roms).unwrap();
+    let path = roms.join("Game.md");
+    let mut headered = vec![0u8; 512];
+    headered.extend_from_slice(&rom);
+    fs::write(&path, &headered).unwrap();
+
+    let options = RepairOptions {
+        dat_dir: Some(dat_dir),
+        ..RepairOptions::default()
+    };
+    let plan = plan_repairs(&roms, &GenesisAnalyzer, &options, &|_| {}).unwrap();
+    assert_eq!(plan.already_correct, std::slice::from_ref(&path));
+    assert!(plan.repairable.is_empty());
+
+    let options = RepairOptions {
+        strip_headers: true,
+        ..options
+    };
+    let plan = plan_repairs(&roms, &GenesisAnalyzer, &options, &|_| {}).unwrap();
+    assert_eq!(plan.repairable.len(), 1);
+    assert_eq!(plan.repairable[0].game_name, "Game (USA)");
+    assert!(matches!(
+        plan.repairable[0].method,
+        RepairMethod::StripHeader { bytes_removed: 512 }
+    ));
+
+    let summary = execute_repairs(&plan, true);
+    assert_eq!(summary.repaired, 1);
+    assert_eq!(fs::read(&path).unwrap(), rom);
+    assert_eq!(fs::read(roms.join("Game.md.bak")).unwrap(), headered);
+}
+
+#[test]
+fn test_strip_headers_leaves_interleaved_genesis_rom() {
+    use retro_junk_sega::GenesisAnalyzer;
+
+    // An SMD dump: the 512-byte header is skipped and the rest de-interleaved
+    // for hashing, so stripping the header alone wouldn't give the DAT's bytes
+    let dir = tempfile::tempdir().unwrap();
+    let mut plain = vec![0u8; 0x4000];
+    plain[0x100..0x110].copy_from_slice(b"SEGA GENESIS    ");
+    let mut smd = vec![0u8; 512];
+    for i in 0..0x2000 {
+        smd.push(plain[i * 2 + 1]);
+    }
+    for i in 0..0x2000 {
+        smd.push(plain[i * 2]);
+    }
+    let plain_path = dir.path().join("plain.md");
+    fs::write(&plain_path, &plain).unwrap();
+    let plain_hashes = hasher::compute_crc32_sha1(
+        &mut fs::File::open(&plain_path).unwrap(),
+        &GenesisAnalyzer,
+        None,
+    )
+    .unwrap();
+    let smd_path = dir.path().join("game.smd");
+    fs::write(&smd_path, &smd).unwrap();
+    let smd_hashes = hasher::compute_crc32_sha1(
+        &mut fs::File::open(&smd_path).unwrap(),
+        &GenesisAnalyzer,
+        None,
+    )
+    .unwrap();
+    assert_eq!(smd_hashes.crc32, plain_hashes.crc32);
+    assert!(!header_is_strippable(&smd_path, &GenesisAnalyzer, 512));
+    assert!(header_is_strippable(&plain_path, &GenesisAnalyzer, 0));
+}
@@ -0,0 +1,152 @@
+use chrono::TimeZone;
+use retro_junk_dat::matcher::MatchMethod;
+
+use super::*;
+use crate::rename::{RenameAction, SerialWarning, UnmatchedFile};
+
+fn action(folder: &Path, source: &str, target: &str) -> RenameAction {
+    RenameAction {
+        source: folder.join(source),
+        target: folder.join(target),
+        game_name: Path::new(target)
+            .file_stem()
+            .unwrap()
+            .to_string_lossy()
+            .to_string(),
+        matched_by: MatchMethod::Crc32,
+    }
+}
+
+fn created() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap()
+}
+
+#[test]
+fn statuses_reflect_what_happened_on_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    let folder = dir.path();
+    std::fs::write(folder.join("F-Zero (USA).sfc"), b"ok").unwrap();
+    std::fs::write(folder.join("Super Mario World (USA).sfc"), b"renamed").unwrap();
+    std::fs::write(folder.join("zelda.sfc"), b"declined").unwrap();
+    std::fs::write(folder.join("copy.sfc"), b"conflict").unwrap();
+
+    let renamed = action(folder, "smw.sfc", "Super Mario World (USA).sfc");
+    let declined = action(folder, "zelda.sfc", "Zelda (USA).sfc");
+    let plan = RenamePlan {
+        renames: vec![renamed.clone(), declined.clone()],
+        already_correct: vec![folder.join("F-Zero (USA).sfc")],
+        unmatched: vec![UnmatchedFile {
+            file: folder.join("hack.sfc"),
+            crc32: Some("deadbeef".to_string()),
+            data_size: Some(4),
+            mismatch_reason: None,
+        }],
+        serial_warnings: vec![SerialWarning {
+            file: folder.join("hack.sfc"),
+            kind: SerialWarningKind::Missing,
+            crc32: None,
+            data_size: None,
+            matched_by_hash: false,
+        }],
+        matched: vec![
+            action(folder, "F-Zero (USA).sfc", "F-Zero (USA).sfc"),
+            renamed,
+            declined,
+            action(folder, "copy.sfc", "Super Mario World (USA).sfc"),
+        ],
+        dat_versions: vec!["Nintendo - SNES (20260101-000000)".to_string()],
+        ..Default::default()
+    };
+
+    let report = FolderReport::from_rename_plan("Super Nintendo", folder, &plan, created());
+    let statuses: Vec<(&str, &FileStatus)> = report
+        .entries
+        .iter()
+        .map(|e| (e.file.as_str(), &e.status))
+        .collect();
+
+    assert_eq!(
+        statuses,
+        vec![
+            ("copy.sfc", &FileStatus::Conflict),
+            ("F-Zero (USA).sfc", &FileStatus::Ok),
+            (
+                "hack.sfc",
+                &FileStatus::Bad {
+                    crc32: Some("deadbeef".to_string())
+                }
+            ),
+            (
+                "Super Mario World (USA).sfc",
+                &FileStatus::Renamed {
+                    from: "smw.sfc".to_string()
+                }
+            ),
+            (
+                "zelda.sfc",
+                &FileStatus::NotRenamed {
+                    to: "Zelda (USA).sfc".to_string()
+                }
+            ),
+        ]
+    );
+    assert!(report.entries[2].missing_serial);
+}
+
+#[test]
+fn render_includes_header_and_summary() {
+    let folder = Path::new("/roms/snes");
+    let plan = RenamePlan {
+        matched: vec![action(folder, "F-Zero (USA).sfc", "F-Zero (USA).sfc")],
+        dat_versions: vec!["Nintendo - SNES (20260101-000000)".to_string()],
+        ..Default::default()
+    };
+    let text = FolderReport::from_rename_plan("Super Nintendo", folder, &plan, created()).render();
+
+    assert!(text.starts_with("retro-junk report: Super Nintendo\n"));
+    assert!(text.contains("Created:   2026-01-02 03:04:05 UTC\n"));
+    assert!(text.contains("DAT:       Nintendo - SNES (20260101-000000)\n"));
+    assert!(text.contains("\nOK        F-Zero (USA).sfc\n"));
+    assert!(
+        text.ends_with(
+            "1 files: 1 OK, 0 renamed, 0 pending, 0 conflict, 0 bad, 0 missing serial\n"
+        )
+    );
+}
+
+#[test]
+fn verification_statuses_map_to_report_statuses() {
+    use crate::dat_verify::VerifiedFile;
+
+    let folder = Path::new("/roms/snes");
+    let file = |name: &str, status, crc32: &str, detail: Option<&str>| VerifiedFile {
+        path: folder.join(name),
+        status,
+        game_name: None,
+        crc32: crc32.to_string(),
+        detail: detail.map(str::to_string),
+    };
+    let verification = FolderVerification {
+        dat_versions: vec!["Nintendo - SNES (20260101-000000)".to_string()],
+        files: vec![
+            file("F-Zero (USA).sfc", VerifyStatus::Verified, "aa", None),
+            file("hack.sfc", VerifyStatus::Unknown, "deadbeef", None),
+            file("trimmed.sfc", VerifyStatus::BadDump, "bb", Some("trimmed")),
+        ],
+        errors: vec![(folder.join("locked.sfc"), "permission denied".to_string())],
+    };
+    let report =
+        FolderReport::from_verification("Super Nintendo", folder, &verification, created());
+    let text = report.render();
+
+    assert!(text.contains("DAT:       Nintendo - SNES (20260101-000000)\n"));
+    assert!(text.contains("\nOK        F-Zero (USA).sfc\n"));
+    assert!(text.contains("\nBAD       hack.sfc  (CRC32 deadbeef, no DAT match)\n"));
+    assert!(text.contains("\nBAD       locked.sfc  (unreadable: permission denied)\n"));
+    assert!(text.contains("\nBAD       trimmed.sfc  (trimmed)\n"));
+    assert!(
+        text.ends_with(
+            "4 files: 1 OK, 0 renamed, 0 pending, 0 conflict, 3 bad, 0 missing serial\n"
+        )
+    );
+}
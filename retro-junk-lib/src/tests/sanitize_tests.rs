@@ -0,0 +1,62 @@
+use super::*;
+
+#[test]
+fn valid_names_are_unchanged() {
+    let name = "Super Mario World (USA).sfc";
+    assert_eq!(sanitize_file_name(name), name);
+}
+
+#[test]
+fn replaces_rejected_characters() {
+    assert_eq!(
+        sanitize_file_name("Zelda: Link's Awakening (USA).gb"),
+        "Zelda - Link's Awakening (USA).gb"
+    );
+    assert_eq!(
+        sanitize_file_name("What? \"Game\" <1|2>*.nes"),
+        "What_ _Game_ _1_2__.nes"
+    );
+    assert_eq!(sanitize_file_name("A/B\\C:D.nes"), "A_B_C_D.nes");
+}
+
+#[test]
+fn drops_trailing_dots_and_spaces() {
+    assert_eq!(sanitize_file_name("Game Vol. 1..."), "Game Vol. 1");
+    assert_eq!(sanitize_file_name("Folder. "), "Folder");
+}
+
+#[test]
+fn avoids_reserved_device_names() {
+    assert_eq!(sanitize_file_name("CON.nes"), "CON_.nes");
+    assert_eq!(sanitize_file_name("aux"), "aux_");
+    assert_eq!(sanitize_file_name("Console.nes"), "Console.nes");
+}
+
+#[test]
+fn shortens_long_names_before_the_extension() {
+    let long = format!("{}.sfc", "\u{00e9}".repeat(200));
+    let short = sanitize_file_name(&long);
+    assert!(short.len() <= MAX_NAME_BYTES);
+    assert!(short.ends_with("\u{00e9}.sfc"));
+}
+
+#[test]
+fn canonical_names_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut names = CanonicalNames::default();
+    names.insert("A - B.nes", "A: B.nes");
+    names.insert("Same.nes", "Same.nes");
+    assert_eq!(names.len(), 1);
+    names.save(dir.path()).unwrap();
+
+    let loaded = CanonicalNames::load(dir.path()).unwrap();
+    assert_eq!(loaded, names);
+    assert_eq!(loaded.get("A - B.nes"), Some("A: B.nes"));
+
+    // An emptied map removes the file
+    let mut emptied = loaded;
+    emptied.remove("A - B.nes");
+    emptied.save(dir.path()).unwrap();
+    assert!(!dir.path().join(CANONICAL_NAMES_FILE_NAME).exists());
+    assert!(CanonicalNames::load(dir.path()).unwrap().is_empty());
+}
@@ -0,0 +1,307 @@
+use super::*;
+use std::fs;
+
+fn touch(path: &Path) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, b"rom").unwrap();
+}
+
+fn names(entries: &[GameEntry]) -> Vec<&str> {
+    entries.iter().map(|e| e.display_name()).collect()
+}
+
+#[test]
+fn scans_per_letter_subfolders() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    touch(&root.join("Top.sfc"));
+    touch(&root.join("A/Alpha.sfc"));
+    touch(&root.join("B/Beta.sfc"));
+    touch(&root.join("B/notes.txt"));
+    touch(&root.join(".hidden/Ghost.sfc"));
+
+    let exts = extension_set(&["sfc"]);
+    let entries = scan_game_entries(root, &exts).unwrap();
+
+    assert_eq!(names(&entries), ["Alpha.sfc", "Beta.sfc", "Top.sfc"]);
+    assert_eq!(entries[0].subfolder_prefix(root), "A/");
+    assert_eq!(entries[2].subfolder_prefix(root), "");
+}
+
+#[test]
+fn depth_limits_recursion() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    touch(&root.join("Top.sfc"));
+    touch(&root.join("A/Alpha.sfc"));
+    touch(&root.join("A/Deep/Deeper.sfc"));
+
+    let exts = extension_set(&["sfc"]);
+    let flat = scan_game_entries_with(root, &exts, &ScanOptions::new().depth(0)).unwrap();
+    assert_eq!(names(&flat), ["Top.sfc"]);
+
+    let deep = scan_game_entries_with(root, &exts, &ScanOptions::new().depth(2)).unwrap();
+    assert_eq!(names(&deep), ["Alpha.sfc", "Deeper.sfc", "Top.sfc"]);
+    assert_eq!(deep[1].subfolder_prefix(root), "A/Deep/");
+}
+
+#[test]
+fn nested_m3u_and_cue_dedup() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    touch(&root.join("F/Final Fantasy VII.m3u/Disc 1.cue"));
+    touch(&root.join("F/Final Fantasy VII.m3u/Disc 1.bin"));
+    touch(&root.join("C/Crash.cue"));
+    touch(&root.join("C/Crash.bin"));
+
+    let exts = extension_set(&["cue", "bin"]);
+    let entries = scan_game_entries(root, &exts).unwrap();
+
+    assert_eq!(names(&entries), ["Crash.cue", "Final Fantasy VII.m3u"]);
+    assert_eq!(entries[1].subfolder_prefix(root), "F/");
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_cycles_terminate() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    touch(&root.join("A/Alpha.sfc"));
+    std::os::unix::fs::symlink("..", root.join("A/loop")).unwrap();
+    std::os::unix::fs::symlink("A", root.join("B")).unwrap();
+
+    let exts = extension_set(&["sfc"]);
+    let options = ScanOptions::new().depth(8);
+    let entries = scan_game_entries_with(root, &exts, &options).unwrap();
+
+    // Reachable as A/, B/, and A/loop/A/..., but scanned once
+    assert_eq!(names(&entries), ["Alpha.sfc"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn skip_policy_ignores_links() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    touch(&root.join("Real.sfc"));
+    touch(&store.path().join("Linked.sfc"));
+    touch(&store.path().join("Z/Zelda.sfc"));
+    std::os::unix::fs::symlink(store.path().join("Linked.sfc"), root.join("Linked.sfc")).unwrap();
+    std::os::unix::fs::symlink(store.path().join("Z"), root.join("Z")).unwrap();
+
+    let exts = extension_set(&["sfc"]);
+    let followed = scan_game_entries(root, &exts).unwrap();
+    assert_eq!(names(&followed), ["Linked.sfc", "Real.sfc", "Zelda.sfc"]);
+
+    let options = ScanOptions::new().symlinks(SymlinkPolicy::Skip);
+    let skipped = scan_game_entries_with(root, &exts, &options).unwrap();
+    assert_eq!(names(&skipped), ["Real.sfc"]);
+}
+
+#[test]
+fn finds_extracted_game_folders() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    touch(&root.join("Resistance/PS3_GAME/PARAM.SFO"));
+    touch(&root.join("R/Ratchet/PS3_GAME/PARAM.SFO"));
+    touch(&root.join("Deep/Er/Game/PS3_GAME/PARAM.SFO"));
+    touch(&root.join("Loose/readme.txt"));
+
+    let ctx = crate::create_default_context();
+    let ps3 = ctx
+        .get_by_platform(retro_junk_core::Platform::Ps3)
+        .unwrap()
+        .analyzer
+        .as_ref();
+    let found = find_game_dirs(root, ps3, &ScanOptions::default());
+    assert_eq!(
+        found,
+        [root.join("R").join("Ratchet"), root.join("Resistance")]
+    );
+
+    // Analyzers without a folder format find nothing
+    let snes = ctx
+        .get_by_platform(retro_junk_core::Platform::Snes)
+        .unwrap()
+        .analyzer
+        .as_ref();
+    assert!(find_game_dirs(root, snes, &ScanOptions::default()).is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn keeps_non_utf8_names() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    // Latin-1 "Pokémon" and "É", as written by some older NAS setups
+    let rom = root.join(OsStr::from_bytes(b"Pok\xe9mon.gb"));
+    touch(&rom);
+    touch(&root.join(OsStr::from_bytes(b"\xc9")).join("Excite.gb"));
+    touch(
+        &root
+            .join(OsStr::from_bytes(b"F\xe9e.m3u"))
+            .join("Disc 1.gb"),
+    );
+
+    let exts = extension_set(&["gb"]);
+    let entries = scan_game_entries(root, &exts).unwrap();
+
+    assert_eq!(entries.len(), 3);
+    assert!(entries.iter().any(|e| e.all_files() == [rom.clone()]));
+    assert!(entries.iter().any(|e| e.display_name() == "F\u{FFFD}e.m3u"));
+    let nested = entries
+        .iter()
+        .find(|e| e.display_name() == "Excite.gb")
+        .unwrap();
+    assert_eq!(nested.subfolder_prefix(root), "\u{FFFD}/");
+}
+
+fn write(path: &Path, text: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, text).unwrap();
+}
+
+fn file_names(paths: &[PathBuf]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+        .collect()
+}
+
+#[test]
+fn groups_cue_tracks_patches_and_saves() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(
+        &root.join("Game.cue"),
+        "FILE \"Game (Track 1).bin\" BINARY\n  TRACK 01 MODE1/2352\nFILE \"Game (Track 2).bin\" BINARY\n  TRACK 02 AUDIO\n",
+    );
+    touch(&root.join("Game (Track 1).bin"));
+    touch(&root.join("Game (Track 2).bin"));
+    touch(&root.join("Game.sav"));
+    touch(&root.join("Other.sfc"));
+    touch(&root.join("Other.ips"));
+    touch(&root.join("Other (Beta).ips"));
+
+    let exts = extension_set(&["cue", "bin", "sfc"]);
+    let grouped = scan_grouped_entries(root, &exts, &ScanOptions::new()).unwrap();
+    let cue = grouped
+        .iter()
+        .find(|g| g.entry.display_name() == "Game.cue")
+        .unwrap();
+    assert_eq!(
+        file_names(&cue.companions),
+        ["Game (Track 1).bin", "Game (Track 2).bin", "Game.sav"]
+    );
+    let sfc = grouped
+        .iter()
+        .find(|g| g.entry.display_name() == "Other.sfc")
+        .unwrap();
+    assert_eq!(file_names(&sfc.companions), ["Other.ips"]);
+    // Track files are still entries of their own unless folded
+    assert_eq!(grouped.len(), 4);
+
+    let folded =
+        scan_grouped_entries(root, &exts, &ScanOptions::new().fold_companions(true)).unwrap();
+    let names: Vec<&str> = folded.iter().map(|g| g.entry.display_name()).collect();
+    assert_eq!(names, ["Game.cue", "Other.sfc"]);
+}
+
+#[test]
+fn groups_gdi_tracks() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(
+        &root.join("Game.gdi"),
+        "3\n1 0 4 2352 track01.bin 0\n2 756 0 2352 track02.raw 0\n3 45000 4 2352 \"Game (Track 3).bin\" 0\n",
+    );
+    touch(&root.join("track01.bin"));
+    touch(&root.join("track02.raw"));
+    touch(&root.join("Game (Track 3).bin"));
+    touch(&root.join("unrelated.bin"));
+
+    let grouped =
+        scan_grouped_entries(root, &extension_set(&["gdi"]), &ScanOptions::new()).unwrap();
+    assert_eq!(grouped.len(), 1);
+    assert_eq!(
+        file_names(&grouped[0].companions),
+        ["Game (Track 3).bin", "track01.bin", "track02.raw"]
+    );
+    assert_eq!(grouped[0].files_with_companions().count(), 4);
+}
+
+#[derive(Debug)]
+struct CheatRule;
+
+impl GroupingRule for CheatRule {
+    fn companions(&self, primary: &Path, siblings: &[PathBuf]) -> Vec<PathBuf> {
+        let cheats = primary.with_extension("cht");
+        siblings.iter().filter(|p| **p == cheats).cloned().collect()
+    }
+}
+
+#[test]
+fn custom_rules_replace_or_extend_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    touch(&root.join("Game.nes"));
+    touch(&root.join("Game.cht"));
+    touch(&root.join("Game.sav"));
+    let exts = extension_set(&["nes"]);
+
+    let extended = scan_grouped_entries(root, &exts, &ScanOptions::new().rule(CheatRule)).unwrap();
+    assert_eq!(
+        file_names(&extended[0].companions),
+        ["Game.cht", "Game.sav"]
+    );
+
+    let only_cheats = ScanOptions::new().grouping(vec![Arc::new(CheatRule)]);
+    let replaced = scan_grouped_entries(root, &exts, &only_cheats).unwrap();
+    assert_eq!(file_names(&replaced[0].companions), ["Game.cht"]);
+}
+
+#[test]
+fn m3u_folders_keep_discs_and_collect_companions() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let set = root.join("Game.m3u");
+    write(
+        &set.join("Game (Disc 1).cue"),
+        "FILE \"Game (Disc 1).bin\" BINARY\n",
+    );
+    touch(&set.join("Game (Disc 1).bin"));
+    touch(&set.join("Game (Disc 1).sav"));
+
+    let exts = extension_set(&["cue", "bin"]);
+    let grouped = scan_grouped_entries(root, &exts, &ScanOptions::new()).unwrap();
+    assert_eq!(grouped[0].entry.all_files().len(), 2);
+    assert_eq!(file_names(&grouped[0].companions), ["Game (Disc 1).sav"]);
+
+    let folded =
+        scan_grouped_entries(root, &exts, &ScanOptions::new().fold_companions(true)).unwrap();
+    assert_eq!(
+        file_names(folded[0].entry.all_files()),
+        ["Game (Disc 1).cue"]
+    );
+    assert_eq!(
+        file_names(&folded[0].companions),
+        ["Game (Disc 1).bin", "Game (Disc 1).sav"]
+    );
+}
+
+#[test]
+fn parses_gdi_track_lines() {
+    assert_eq!(
+        gdi_track_file("3 45000 4 2352 \"My Game (Track 3).bin\" 0").as_deref(),
+        Some("My Game (Track 3).bin")
+    );
+    assert_eq!(
+        gdi_track_file("1 0 4 2352 track01.bin 0").as_deref(),
+        Some("track01.bin")
+    );
+    assert_eq!(gdi_track_file("3"), None);
+}
@@ -0,0 +1,119 @@
+use super::*;
+
+use retro_junk_core::{ChecksumAlgorithm, ExpectedChecksum, Platform, Region};
+
+fn sample() -> RomSidecar {
+    let mut identification = RomIdentification::new()
+        .with_serial("SNS-MW-USA")
+        .with_internal_name("SUPER MARIOWORLD")
+        .with_region(Region::Usa)
+        .with_platform(Platform::Snes);
+    identification
+        .expected_checksums
+        .push(ExpectedChecksum::new(
+            ChecksumAlgorithm::PlatformSpecific("SNES".into()),
+            vec![0xa0, 0xda],
+        ));
+    identification.extra.insert("mapper".into(), "LoROM".into());
+
+    RomSidecar {
+        generator: String::new(),
+        identification: Some(identification),
+        hashes: Some(SidecarHashes {
+            crc32: "b19ed489".into(),
+            sha1: Some("6b47bb75d16514b6a476aa0c73a683a2a4c18765".into()),
+            md5: None,
+            data_size: 524288,
+        }),
+        dat_match: Some(SidecarDatMatch {
+            game_name: "Super Mario World (USA)".into(),
+            method: "serial".into(),
+        }),
+        scrape: None,
+    }
+}
+
+fn as_json(sidecar: Option<RomSidecar>) -> serde_json::Value {
+    serde_json::to_value(sidecar).unwrap()
+}
+
+#[test]
+fn round_trips_in_both_formats() {
+    for format in [SidecarFormat::Toml, SidecarFormat::Json] {
+        let dir = tempfile::tempdir().unwrap();
+        let rom = dir.path().join("smw.sfc");
+        write_sidecar(&rom, &sample(), format).unwrap();
+
+        assert!(sidecar_path(&rom, format).exists());
+        assert_eq!(
+            as_json(read_sidecar(&rom).unwrap()),
+            as_json(Some(sample())),
+            "{format:?}"
+        );
+    }
+}
+
+#[test]
+fn sidecar_path_appends_suffix() {
+    assert_eq!(
+        sidecar_path(Path::new("/roms/snes/Game (USA).sfc"), SidecarFormat::Toml),
+        PathBuf::from("/roms/snes/Game (USA).sfc.retro-junk.toml")
+    );
+}
+
+#[test]
+fn update_preserves_other_sections() {
+    let dir = tempfile::tempdir().unwrap();
+    let rom = dir.path().join("smw.sfc");
+    write_sidecar(&rom, &sample(), SidecarFormat::Toml).unwrap();
+
+    update_sidecar(&rom, SidecarFormat::Toml, |s| {
+        s.scrape = Some(SidecarScrape {
+            name: "Super Mario World".into(),
+            ..Default::default()
+        });
+    })
+    .unwrap();
+
+    let updated = read_sidecar(&rom).unwrap().unwrap();
+    assert_eq!(updated.dat_match, sample().dat_match);
+    assert_eq!(updated.scrape.unwrap().name, "Super Mario World");
+    assert!(updated.generator.starts_with("retro-junk "));
+}
+
+#[test]
+fn missing_sidecar_reads_as_none() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(
+        read_sidecar(&dir.path().join("none.sfc"))
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn rename_moves_sidecar() {
+    let dir = tempfile::tempdir().unwrap();
+    let from = dir.path().join("smw.sfc");
+    let to = dir.path().join("Super Mario World (USA).sfc");
+    write_sidecar(&from, &sample(), SidecarFormat::Json).unwrap();
+
+    rename_sidecars(&from, &to).unwrap();
+    assert!(!sidecar_path(&from, SidecarFormat::Json).exists());
+    assert_eq!(as_json(read_sidecar(&to).unwrap()), as_json(Some(sample())));
+}
+
+#[test]
+fn rename_keeps_existing_sidecar_at_target() {
+    let dir = tempfile::tempdir().unwrap();
+    let from = dir.path().join("smw.sfc");
+    let to = dir.path().join("Super Mario World (USA).sfc");
+    write_sidecar(&from, &sample(), SidecarFormat::Json).unwrap();
+    let existing = sidecar_path(&to, SidecarFormat::Json);
+    std::fs::write(&existing, "{}").unwrap();
+
+    let err = rename_sidecars(&from, &to).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+    assert_eq!(std::fs::read_to_string(&existing).unwrap(), "{}");
+    assert!(sidecar_path(&from, SidecarFormat::Json).exists());
+}
@@ -0,0 +1,47 @@
+use super::*;
+
+/// An iNES file declaring 2 x 16 KB PRG and 1 x 8 KB CHR, with `body` bytes
+/// after the header.
+fn write_nes(dir: &Path, name: &str, body: usize) {
+    let mut data = vec![0u8; 16 + body];
+    data[0..4].copy_from_slice(b"NES\x1A");
+    data[4] = 2;
+    data[5] = 1;
+    fs::write(dir.join(name), data).unwrap();
+}
+
+#[test]
+fn tallies_verdicts_for_a_folder() {
+    let dir = tempfile::tempdir().unwrap();
+    write_nes(dir.path(), "good.nes", 40 * 1024);
+    write_nes(dir.path(), "short.nes", 10 * 1024);
+    write_nes(dir.path(), "long.nes", 40 * 1024 + 100);
+    fs::write(dir.path().join("junk.nes"), b"not a rom").unwrap();
+
+    let ctx = crate::create_default_context();
+    let nes = ctx.get_by_short_name("nes").unwrap();
+    let audit = audit_folder(dir.path(), nes.analyzer.as_ref(), None).unwrap();
+
+    assert_eq!(audit.ok, 1);
+    assert_eq!(audit.truncated, 1);
+    assert_eq!(audit.oversized, 1);
+    assert_eq!(audit.failed, 1);
+    assert_eq!(audit.total(), 4);
+    assert_eq!(audit.problems.len(), 2);
+}
+
+#[test]
+fn merge_adds_counts() {
+    let mut a = SizeAudit::default();
+    a.record(Path::new("a"), Some(SizeVerdict::Ok));
+    a.record(Path::new("b"), None);
+    let mut b = SizeAudit::default();
+    b.record(Path::new("c"), Some(SizeVerdict::CopierHeader));
+    b.failed = 2;
+
+    a.merge(b);
+    assert_eq!(a.total(), 5);
+    assert_eq!(a.copier_header, 1);
+    assert_eq!(a.unknown, 1);
+    assert_eq!(a.problems[0].path, PathBuf::from("c"));
+}
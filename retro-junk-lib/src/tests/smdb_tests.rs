@@ -0,0 +1,125 @@
+use super::*;
+
+const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+const ABC_SHA1: &str = "a9993e364706816aba3e25717850c26c9cd0d89d";
+const XYZ_CRC32: &str = "eb8eba67";
+
+fn hashes_of(data: &[u8]) -> RawFileHashes {
+    compute_raw_hashes(&mut std::io::Cursor::new(data.to_vec())).unwrap()
+}
+
+#[test]
+fn parses_full_and_short_lines() {
+    let text = format!(
+        "{ABC_SHA256}\tGB/A/Abc (USA).gb\t{ABC_SHA1}\t\t\t3\r\n\n\tGB\\X\\Xyz.gb\t\t\t{XYZ_CRC32}\n"
+    );
+    let smdb = Smdb::parse(&text).unwrap();
+    assert_eq!(smdb.entries.len(), 2);
+    assert_eq!(smdb.entries[0].path, "GB/A/Abc (USA).gb");
+    assert_eq!(smdb.entries[0].sha256.as_deref(), Some(ABC_SHA256));
+    assert_eq!(smdb.entries[0].sha1.as_deref(), Some(ABC_SHA1));
+    assert_eq!(smdb.entries[0].size, Some(3));
+    assert_eq!(smdb.entries[1].path, "GB/X/Xyz.gb");
+    assert_eq!(smdb.entries[1].sha256, None);
+    assert_eq!(smdb.entries[1].crc32.as_deref(), Some(XYZ_CRC32));
+}
+
+#[test]
+fn rejects_bad_lines() {
+    for line in [
+        "just a path",
+        "\tno/hashes.gb",
+        "abc\tshort/sha256.gb",
+        &format!("{ABC_SHA256}\t../outside.gb"),
+        &format!("{ABC_SHA256}\t/absolute.gb"),
+    ] {
+        let text = format!("{ABC_SHA256}\tok.gb\n{line}\n");
+        match Smdb::parse(&text) {
+            Err(SmdbError::Parse { line, .. }) => assert_eq!(line, 2),
+            other => panic!("{line:?} parsed as {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn strongest_listed_hash_decides() {
+    let hashes = hashes_of(b"abc");
+    let mut entry = Smdb::parse(&format!("{ABC_SHA256}\ta.gb\t{}", "0".repeat(40)))
+        .unwrap()
+        .entries
+        .remove(0);
+    assert!(entry.matches(&hashes));
+
+    entry.sha256 = None;
+    assert!(!entry.matches(&hashes));
+
+    entry.sha1 = Some(ABC_SHA1.into());
+    entry.size = Some(4);
+    assert!(!entry.matches(&hashes));
+}
+
+#[test]
+fn verify_and_move_files_into_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("GB/A")).unwrap();
+    fs::write(root.join("GB/A/Abc (USA).gb"), b"abc").unwrap();
+    fs::write(root.join("loose xyz.gb"), b"xyz").unwrap();
+    fs::write(root.join("copy of abc.gb"), b"abc").unwrap();
+    fs::write(root.join("notes.txt"), b"hello").unwrap();
+    fs::create_dir_all(root.join(".hidden")).unwrap();
+    fs::write(root.join(".hidden/skipped.gb"), b"abc").unwrap();
+
+    let smdb = Smdb::parse(&format!(
+        "{ABC_SHA256}\tGB/A/Abc (USA).gb\n\tGB/X/Xyz.gb\t\t\t{XYZ_CRC32}\n\tGB/M/Missing.gb\t{}\n",
+        "1".repeat(40)
+    ))
+    .unwrap();
+
+    let verification = verify_against_smdb(root, &smdb, &|_, _, _| {}).unwrap();
+    let status = |path: &str| {
+        verification
+            .files
+            .iter()
+            .find(|f| f.path == path)
+            .map(|f| f.status.clone())
+    };
+    assert_eq!(verification.files.len(), 4);
+    assert_eq!(status("GB/A/Abc (USA).gb"), Some(SmdbStatus::InPlace));
+    assert_eq!(
+        status("loose xyz.gb"),
+        Some(SmdbStatus::Misplaced {
+            target: "GB/X/Xyz.gb".into()
+        })
+    );
+    assert_eq!(
+        status("copy of abc.gb"),
+        Some(SmdbStatus::Duplicate {
+            of: "GB/A/Abc (USA).gb".into()
+        })
+    );
+    assert_eq!(status("notes.txt"), Some(SmdbStatus::Unknown));
+    assert_eq!(verification.missing, vec!["GB/M/Missing.gb".to_string()]);
+    assert_eq!(verification.count(&SmdbStatus::Unknown), 1);
+
+    let summary = apply_moves(root, &verification);
+    assert_eq!(summary.moved, 1);
+    assert!(summary.failed.is_empty());
+    assert_eq!(fs::read(root.join("GB/X/Xyz.gb")).unwrap(), b"xyz");
+    assert!(!root.join("loose xyz.gb").exists());
+}
+
+#[test]
+fn moves_never_replace_existing_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::write(root.join("abc.gb"), b"abc").unwrap();
+    fs::write(root.join("Abc (USA).gb"), b"not abc").unwrap();
+
+    let smdb = Smdb::parse(&format!("{ABC_SHA256}\tAbc (USA).gb\n")).unwrap();
+    let verification = verify_against_smdb(root, &smdb, &|_, _, _| {}).unwrap();
+    let summary = apply_moves(root, &verification);
+    assert_eq!(summary.moved, 0);
+    assert_eq!(summary.failed.len(), 1);
+    assert_eq!(fs::read(root.join("Abc (USA).gb")).unwrap(), b"not abc");
+}
@@ -0,0 +1,105 @@
+use super::*;
+
+fn data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 % 251) as u8).collect()
+}
+
+#[test]
+fn names_parts() {
+    assert_eq!(
+        SplitFormat::Parts.part_name("Game (USA).iso", 0),
+        "Game (USA).iso.part0"
+    );
+    assert_eq!(
+        SplitFormat::Parts.part_name("Game (USA).iso", 2),
+        "Game (USA).iso.part2"
+    );
+    assert_eq!(SplitFormat::Wbfs.part_name("Game.wbfs", 0), "Game.wbfs");
+    assert_eq!(SplitFormat::Wbfs.part_name("Game.wbfs", 3), "Game.wbf3");
+}
+
+#[test]
+fn counts_parts() {
+    assert_eq!(SplitFormat::part_count(0), 1);
+    assert_eq!(SplitFormat::part_count(SPLIT_CHUNK_SIZE), 1);
+    assert_eq!(SplitFormat::part_count(SPLIT_CHUNK_SIZE + 1), 2);
+    assert_eq!(SplitFormat::part_count(5 * 1024 * 1024 * 1024), 2);
+}
+
+#[test]
+fn split_and_join_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source.iso");
+    let contents = data(2500);
+    fs::write(&source, &contents).unwrap();
+
+    let dest = dir.path().join("card").join("Game.iso");
+    fs::create_dir_all(dest.parent().unwrap()).unwrap();
+    let parts = split_with_chunk(&source, &dest, SplitFormat::Parts, 1000).unwrap();
+    assert_eq!(parts.len(), 3);
+    assert_eq!(fs::metadata(&parts[2]).unwrap().len(), 500);
+
+    let (format, found) = find_parts(&parts[0]).unwrap();
+    assert_eq!(format, SplitFormat::Parts);
+    assert_eq!(found, parts);
+
+    let joined = dir.path().join("joined.iso");
+    let result = join_with_chunk(&found, &joined, 1000).unwrap();
+    assert_eq!(result.size, 2500);
+    assert_eq!(result.crc32, format!("{:08x}", crc32fast::hash(&contents)));
+    assert_eq!(fs::read(&joined).unwrap(), contents);
+}
+
+#[test]
+fn finds_wbfs_pieces() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source.wbfs");
+    fs::write(&source, data(1500)).unwrap();
+
+    let dest = dir.path().join("Game.wbfs");
+    let parts = split_with_chunk(&source, &dest, SplitFormat::Wbfs, 1000).unwrap();
+    assert_eq!(parts, vec![dest.clone(), dir.path().join("Game.wbf1")]);
+    assert_eq!(find_parts(&dest).unwrap().0, SplitFormat::Wbfs);
+
+    // A single .wbfs is a whole image, not a split
+    fs::remove_file(dir.path().join("Game.wbf1")).unwrap();
+    assert!(find_parts(&dest).is_none());
+}
+
+#[test]
+fn join_rejects_truncated_piece() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source.iso");
+    fs::write(&source, data(2500)).unwrap();
+    let parts = split_with_chunk(
+        &source,
+        &dir.path().join("Game.iso"),
+        SplitFormat::Parts,
+        1000,
+    )
+    .unwrap();
+
+    fs::write(&parts[1], data(900)).unwrap();
+    let err = join_with_chunk(&parts, &dir.path().join("joined.iso"), 1000).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn join_refuses_to_overwrite() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source.iso");
+    fs::write(&source, data(1500)).unwrap();
+    let parts = split_with_chunk(
+        &source,
+        &dir.path().join("Game.iso"),
+        SplitFormat::Parts,
+        1000,
+    )
+    .unwrap();
+
+    let joined = dir.path().join("joined.iso");
+    fs::write(&joined, b"keep me").unwrap();
+    let err = join_with_chunk(&parts, &joined, 1000).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+    assert_eq!(fs::read(&joined).unwrap(), b"keep me");
+}
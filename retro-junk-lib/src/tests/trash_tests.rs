@@ -0,0 +1,101 @@
+use super::*;
+
+#[test]
+fn stash_and_restore_moved_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("Games.zip");
+    fs::write(&archive, b"zip").unwrap();
+
+    let trash = Trash::new(dir.path());
+    let stored = trash.stash(&archive, "split into individual ROMs").unwrap();
+    assert!(!archive.exists());
+    assert!(stored.starts_with(dir.path().join(TRASH_DIR_NAME)));
+
+    let entries = trash.entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].reason, "split into individual ROMs");
+    assert!(!entries[0].copied);
+
+    let summary = trash.restore().unwrap();
+    assert_eq!(summary.restored, 1);
+    assert!(summary.errors.is_empty());
+    assert_eq!(fs::read(&archive).unwrap(), b"zip");
+    assert!(!trash.dir().exists());
+}
+
+#[test]
+fn restore_brings_back_the_oldest_copy() {
+    let dir = tempfile::tempdir().unwrap();
+    let cue = dir.path().join("Game.cue");
+    let trash = Trash::new(dir.path());
+
+    fs::write(&cue, "first").unwrap();
+    trash.stash_copy(&cue, "CUE references updated").unwrap();
+    fs::write(&cue, "second").unwrap();
+    trash.stash_copy(&cue, "CUE references updated").unwrap();
+    fs::write(&cue, "third").unwrap();
+
+    assert_eq!(trash.restore().unwrap().restored, 2);
+    assert_eq!(fs::read_to_string(&cue).unwrap(), "first");
+}
+
+#[test]
+fn restore_keeps_files_whose_place_is_taken() {
+    let dir = tempfile::tempdir().unwrap();
+    let playlist = dir.path().join("Game.m3u");
+    fs::write(&playlist, "old").unwrap();
+
+    let trash = Trash::new(dir.path());
+    trash
+        .stash(&playlist, "replaced by a new playlist")
+        .unwrap();
+    fs::write(&playlist, "new").unwrap();
+
+    let summary = trash.restore().unwrap();
+    assert_eq!(summary.restored, 0);
+    assert_eq!(summary.errors.len(), 1);
+    assert_eq!(fs::read_to_string(&playlist).unwrap(), "new");
+    assert_eq!(trash.entries().unwrap().len(), 1);
+}
+
+#[test]
+fn find_all_skips_folders_without_a_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let snes = dir.path().join("snes");
+    fs::create_dir_all(snes.join(TRASH_DIR_NAME)).unwrap();
+    fs::create_dir_all(dir.path().join("nes").join(TRASH_DIR_NAME)).unwrap();
+    fs::write(snes.join("a.sfc"), b"rom").unwrap();
+    Trash::new(&snes)
+        .stash(&snes.join("a.sfc"), "test")
+        .unwrap();
+
+    let found = Trash::find_all(dir.path(), 3);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].dir(), snes.join(TRASH_DIR_NAME));
+}
+
+#[test]
+fn rename_no_clobber_refuses_existing_target() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("a.sfc");
+    let target = dir.path().join("b.sfc");
+    fs::write(&source, b"a").unwrap();
+    fs::write(&target, b"b").unwrap();
+
+    let err = rename_no_clobber(&source, &target).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+    assert_eq!(fs::read(&target).unwrap(), b"b");
+
+    fs::remove_file(&target).unwrap();
+    rename_no_clobber(&source, &target).unwrap();
+    assert_eq!(fs::read(&target).unwrap(), b"a");
+}
+
+#[test]
+fn write_new_refuses_existing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Game.m3u");
+    write_new(&path, "one").unwrap();
+    assert!(write_new(&path, "two").is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "one");
+}
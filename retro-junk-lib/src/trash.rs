@@ -0,0 +1,279 @@
+//! Safety net for files that `rename` and `repair` remove or rewrite.
+//!
+//! Moves and writes go through [`rename_no_clobber`] and [`write_new`],
+//! which refuse to replace a file that is already there. With the trash
+//! enabled, a file that would be deleted (an archive after splitting, an old
+//! playlist) is moved into a `.retro-junk-trash/` folder inside the console
+//! folder instead, and a file about to be changed in place (a CUE sheet
+//! whose references are fixed, a ROM being repaired) is copied there first.
+//! Every stashed file is listed in the folder's `manifest.toml`, so
+//! `retro-junk trash restore` can put it back:
+//!
+//! ```toml
+//! [[entries]]
+//! original = "/roms/snes/Games.zip"
+//! trashed = "0-Games.zip"
+//! reason = "split into individual ROMs"
+//! trashed_at = "2026-10-16T12:00:00+00:00"
+//! copied = false
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the staging folder inside a console folder.
+pub const TRASH_DIR_NAME: &str = ".retro-junk-trash";
+
+/// File name of the manifest inside the staging folder.
+pub const MANIFEST_FILE_NAME: &str = "manifest.toml";
+
+/// A file kept in the trash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    /// Where the file was.
+    pub original: PathBuf,
+    /// Its name inside the trash folder.
+    pub trashed: String,
+    /// What `retro-junk` was doing when it stashed the file.
+    pub reason: String,
+    /// RFC 3339 time the file was stashed.
+    pub trashed_at: String,
+    /// The original stayed in place and was changed afterwards, so
+    /// restoring it replaces the changed file.
+    #[serde(default)]
+    pub copied: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    entries: Vec<TrashEntry>,
+}
+
+/// Result of restoring a trash folder.
+#[derive(Debug, Default)]
+pub struct RestoreSummary {
+    pub restored: usize,
+    pub errors: Vec<String>,
+}
+
+/// The trash folder of one console folder.
+#[derive(Debug, Clone)]
+pub struct Trash {
+    dir: PathBuf,
+}
+
+impl Trash {
+    /// The trash of `folder`. Nothing is created until a file is stashed.
+    pub fn new(folder: &Path) -> Self {
+        Self {
+            dir: folder.join(TRASH_DIR_NAME),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Move `path` into the trash.
+    pub fn stash(&self, path: &Path, reason: &str) -> io::Result<PathBuf> {
+        self.store(path, reason, false)
+    }
+
+    /// Copy `path` into the trash, leaving it in place to be changed.
+    pub fn stash_copy(&self, path: &Path, reason: &str) -> io::Result<PathBuf> {
+        self.store(path, reason, true)
+    }
+
+    fn store(&self, path: &Path, reason: &str, copy: bool) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let mut manifest = self.load()?;
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| io::Error::other(format!("{} has no file name", path.display())))?
+            .to_string_lossy();
+
+        let mut n = manifest.entries.len();
+        let (trashed, stored) = loop {
+            let trashed = format!("{}-{}", n, file_name);
+            let stored = self.dir.join(&trashed);
+            if fs::symlink_metadata(&stored).is_err() {
+                break (trashed, stored);
+            }
+            n += 1;
+        };
+
+        if copy {
+            fs::copy(path, &stored)?;
+        } else if fs::rename(path, &stored).is_err() {
+            // Across filesystems, fall back to copying
+            fs::copy(path, &stored)?;
+            fs::remove_file(path)?;
+        }
+
+        manifest.entries.push(TrashEntry {
+            original: std::path::absolute(path)?,
+            trashed,
+            reason: reason.to_string(),
+            trashed_at: chrono::Utc::now().to_rfc3339(),
+            copied: copy,
+        });
+        self.save(&manifest)?;
+        Ok(stored)
+    }
+
+    /// The files in the trash, oldest first.
+    pub fn entries(&self) -> io::Result<Vec<TrashEntry>> {
+        Ok(self.load()?.entries)
+    }
+
+    /// Put every file back where it came from, newest first, so a file
+    /// stashed twice ends up as it was before the first change.
+    ///
+    /// A moved file isn't restored over something that has since taken its
+    /// place; those entries stay in the trash. The trash folder is removed
+    /// once it's empty.
+    pub fn restore(&self) -> io::Result<RestoreSummary> {
+        let mut manifest = self.load()?;
+        let mut summary = RestoreSummary::default();
+        let mut kept = Vec::new();
+
+        while let Some(entry) = manifest.entries.pop() {
+            let stored = self.dir.join(&entry.trashed);
+            let occupied = fs::symlink_metadata(&entry.original).is_ok();
+            let result = if occupied && !entry.copied {
+                Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    "a file has taken its place",
+                ))
+            } else {
+                entry
+                    .original
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|()| fs::rename(&stored, &entry.original))
+            };
+            match result {
+                Ok(()) => summary.restored += 1,
+                Err(e) => {
+                    summary.errors.push(format!(
+                        "Failed to restore {}: {}",
+                        entry.original.display(),
+                        e
+                    ));
+                    kept.push(entry);
+                }
+            }
+        }
+
+        kept.reverse();
+        manifest.entries = kept;
+        if manifest.entries.is_empty() {
+            fs::remove_file(self.dir.join(MANIFEST_FILE_NAME))?;
+            let _ = fs::remove_dir(&self.dir);
+        } else {
+            self.save(&manifest)?;
+        }
+        Ok(summary)
+    }
+
+    /// Find the trash folders under `root`, looking at most `max_depth`
+    /// folders deep.
+    pub fn find_all(root: &Path, max_depth: usize) -> Vec<Trash> {
+        let mut found = Vec::new();
+        let mut pending = vec![(root.to_path_buf(), 0)];
+        while let Some((dir, depth)) = pending.pop() {
+            let trash = Trash::new(&dir);
+            if trash.dir.join(MANIFEST_FILE_NAME).is_file() {
+                found.push(trash);
+            }
+            if depth >= max_depth {
+                continue;
+            }
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                if entry.file_name() != TRASH_DIR_NAME
+                    && entry.file_type().is_ok_and(|t| t.is_dir())
+                {
+                    pending.push((entry.path(), depth + 1));
+                }
+            }
+        }
+        found.sort_by(|a, b| a.dir.cmp(&b.dir));
+        found
+    }
+
+    fn load(&self) -> io::Result<Manifest> {
+        let path = self.dir.join(MANIFEST_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save(&self, manifest: &Manifest) -> io::Result<()> {
+        let contents = toml::to_string(manifest).map_err(io::Error::other)?;
+        fs::write(self.dir.join(MANIFEST_FILE_NAME), contents)
+    }
+}
+
+/// Rename `source` to `target`, failing if something else is already at
+/// `target`. Renaming a file to a name differing only in case is allowed on
+/// case-insensitive filesystems, where both names point at the same file.
+pub fn rename_no_clobber(source: &Path, target: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(target).is_ok() && !is_same_file(source, target) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("target already exists: {}", target.display()),
+        ));
+    }
+    fs::rename(source, target)
+}
+
+/// Write `contents` to a new file, failing if `path` already exists.
+pub fn write_new(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    use std::io::Write;
+
+    create_new(path)?.write_all(contents.as_ref())
+}
+
+/// Create a new file for streaming writes, failing if `path` already exists.
+pub fn create_new(path: &Path) -> io::Result<fs::File> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (fs::symlink_metadata(a), fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    matches!(
+        (fs::canonicalize(a), fs::canonicalize(b)),
+        (Ok(a), Ok(b)) if a == b
+    )
+}
+
+#[cfg(test)]
+#[path = "tests/trash_tests.rs"]
+mod tests;
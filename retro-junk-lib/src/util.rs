@@ -13,3 +13,72 @@ pub fn default_media_dir(root: &Path) -> PathBuf {
         root.file_name().unwrap_or_default().to_string_lossy()
     ))
 }
+
+/// Compute the default metadata directory for a given ROM root path.
+///
+/// Convention: `{parent}/{folder_name}-metadata`.
+pub fn default_metadata_dir(root: &Path) -> PathBuf {
+    root.parent().unwrap_or(root).join(format!(
+        "{}-metadata",
+        root.file_name().unwrap_or_default().to_string_lossy()
+    ))
+}
+
+/// Make a path usable by file APIs regardless of length.
+///
+/// Windows rejects paths of `MAX_PATH` (260) characters or more unless they
+/// carry the `\\?\` prefix (`\\?\UNC\` for network shares). Long absolute
+/// paths are rewritten into that form, with `.` and `..` resolved since
+/// prefixed paths are taken literally. Everything else, and every path on
+/// other platforms, is returned as-is. Use the result only for the file
+/// operation; keep the original for display.
+#[cfg(windows)]
+pub fn long_path(path: &Path) -> std::borrow::Cow<'_, Path> {
+    use std::borrow::Cow;
+    use std::ffi::OsString;
+    use std::path::{Component, Prefix};
+
+    const MAX_PATH: usize = 260;
+    if path.as_os_str().len() < MAX_PATH {
+        return Cow::Borrowed(path);
+    }
+
+    let mut components = path.components();
+    let mut out = match components.next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::Disk(_) => {
+                let mut s = OsString::from(r"\\?\");
+                s.push(prefix.as_os_str());
+                PathBuf::from(s)
+            }
+            Prefix::UNC(server, share) => {
+                let mut s = OsString::from(r"\\?\UNC\");
+                s.push(server);
+                s.push(r"\");
+                s.push(share);
+                PathBuf::from(s)
+            }
+            // Verbatim and device paths already bypass the limit
+            _ => return Cow::Borrowed(path),
+        },
+        // Relative paths can't be prefixed
+        _ => return Cow::Borrowed(path),
+    };
+    for component in components {
+        match component {
+            Component::RootDir => out.push(r"\"),
+            Component::Normal(name) => out.push(name),
+            Component::CurDir | Component::Prefix(_) => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Make a path usable by file APIs regardless of length (no-op off Windows).
+#[cfg(not(windows))]
+pub fn long_path(path: &Path) -> std::borrow::Cow<'_, Path> {
+    std::borrow::Cow::Borrowed(path)
+}
@@ -5,8 +5,26 @@
 //! - Xbox (Original)
 //! - Xbox 360
 
+#[cfg(any(test, feature = "testkit"))]
+pub mod synthetic;
+mod title_id;
+pub mod xbe;
 pub mod xbox;
 pub mod xbox360;
+pub mod xdvdfs;
+
+use std::io::SeekFrom;
+
+use retro_junk_core::{AnalysisError, ReadSeek};
 
 pub use xbox::XboxAnalyzer;
 pub use xbox360::Xbox360Analyzer;
+
+/// The first four bytes, or `None` for shorter files.
+pub(crate) fn read_magic(reader: &mut dyn ReadSeek) -> Result<Option<[u8; 4]>, AnalysisError> {
+    let mut magic = [0u8; 4];
+    reader.seek(SeekFrom::Start(0))?;
+    let read = reader.read_exact(&mut magic).ok().map(|_| magic);
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(read)
+}
@@ -0,0 +1,109 @@
+//! Synthetic images for Microsoft platforms.
+//!
+//! Each builder produces the smallest image its analyzer fully accepts.
+
+use retro_junk_core::{Platform, SyntheticRom};
+
+use crate::xbe::XBE_MAGIC;
+use crate::xbox360::{STFS_MAGICS, XEX2_MAGIC};
+use crate::xdvdfs::{MEDIA_MAGIC, SECTOR_SIZE};
+
+/// Title ID of the synthetic Xbox 360 images: publisher "MS", number 2022.
+pub const TITLE_ID: u32 = 0x4D53_07E6;
+
+/// Title ID of the synthetic Xbox images: publisher "MS", number 4.
+pub const XBOX_TITLE_ID: u32 = 0x4D53_0004;
+
+/// The synthetic image for `platform`, if this crate can build one.
+pub fn synthesize(platform: Platform) -> Option<SyntheticRom> {
+    match platform {
+        Platform::Xbox => Some(xbox()),
+        Platform::Xbox360 => Some(xbox360()),
+        _ => None,
+    }
+}
+
+/// A `default.xbe` whose certificate names "Synthetic Xbox", version 2,
+/// for North America.
+pub fn xbox_xbe() -> Vec<u8> {
+    let base: u32 = 0x1_0000;
+    let cert = 0x180;
+    let mut xbe = vec![0u8; 0x400];
+    xbe[0..4].copy_from_slice(XBE_MAGIC);
+    xbe[0x104..0x108].copy_from_slice(&base.to_le_bytes());
+    xbe[0x108..0x10C].copy_from_slice(&0x400u32.to_le_bytes());
+    xbe[0x118..0x11C].copy_from_slice(&(base + cert as u32).to_le_bytes());
+    xbe[cert + 0x08..cert + 0x0C].copy_from_slice(&XBOX_TITLE_ID.to_le_bytes());
+    for (i, unit) in "Synthetic Xbox".encode_utf16().enumerate() {
+        let at = cert + 0x0C + i * 2;
+        xbe[at..at + 2].copy_from_slice(&unit.to_le_bytes());
+    }
+    xbe[cert + 0xA0..cert + 0xA4].copy_from_slice(&1u32.to_le_bytes());
+    xbe[cert + 0xAC..cert + 0xB0].copy_from_slice(&2u32.to_le_bytes());
+    xbe
+}
+
+/// XISO: the volume descriptor in sector 32, a root directory in sector 33
+/// holding only `default.xbe`, and the XBE in sector 34.
+pub fn xbox() -> SyntheticRom {
+    let sector = SECTOR_SIZE as usize;
+    let mut iso = vec![0u8; 35 * sector];
+    let descriptor = 32 * sector;
+    iso[descriptor..descriptor + 20].copy_from_slice(MEDIA_MAGIC);
+    iso[descriptor + 0x14..descriptor + 0x18].copy_from_slice(&33u32.to_le_bytes());
+    iso[descriptor + 0x18..descriptor + 0x1C].copy_from_slice(&(sector as u32).to_le_bytes());
+    iso[descriptor + 0x7EC..descriptor + 0x800].copy_from_slice(MEDIA_MAGIC);
+
+    let root = &mut iso[33 * sector..34 * sector];
+    root.fill(0xFF);
+    root[0..4].fill(0);
+    root[4..8].copy_from_slice(&34u32.to_le_bytes());
+    root[8..12].copy_from_slice(&0x400u32.to_le_bytes());
+    root[12] = 0x20; // archive
+    root[13] = 11;
+    root[14..25].copy_from_slice(b"default.xbe");
+
+    iso[34 * sector..34 * sector + 0x400].copy_from_slice(&xbox_xbe());
+    SyntheticRom::new(Platform::Xbox, "synthetic.iso", iso)
+}
+
+/// Execution info record: media ID, version 1.0.5.2, title ID, disc 1 of 1.
+fn execution_info() -> [u8; 0x18] {
+    let mut record = [0u8; 0x18];
+    record[0x00..0x04].copy_from_slice(&0x1234_5678u32.to_be_bytes());
+    record[0x04..0x08].copy_from_slice(&0x1000_0502u32.to_be_bytes());
+    record[0x0C..0x10].copy_from_slice(&TITLE_ID.to_be_bytes());
+    record[0x12] = 1;
+    record[0x13] = 1;
+    record
+}
+
+/// `default.xex` with one optional header, the execution info, and
+/// region flags for North America and Europe.
+pub fn xbox360() -> SyntheticRom {
+    let mut data = vec![0u8; 0x300];
+    data[0..4].copy_from_slice(XEX2_MAGIC);
+    data[0x10..0x14].copy_from_slice(&0x100u32.to_be_bytes());
+    data[0x14..0x18].copy_from_slice(&1u32.to_be_bytes());
+    data[0x18..0x1C].copy_from_slice(&0x0004_0006u32.to_be_bytes());
+    data[0x1C..0x20].copy_from_slice(&0x40u32.to_be_bytes());
+    data[0x40..0x58].copy_from_slice(&execution_info());
+    data[0x278..0x27C].copy_from_slice(&0x00FE_00FFu32.to_be_bytes());
+    SyntheticRom::new(Platform::Xbox360, "default.xex", data)
+}
+
+/// Games on Demand header file: a `LIVE` STFS container of content type
+/// 0x7000 with English display and title names.
+pub fn xbox360_god() -> Vec<u8> {
+    let mut data = vec![0u8; 0xB000];
+    data[0..4].copy_from_slice(STFS_MAGICS[1]);
+    data[0x344..0x348].copy_from_slice(&0x7000u32.to_be_bytes());
+    data[0x354..0x36C].copy_from_slice(&execution_info());
+    data[0x39D..0x3A1].copy_from_slice(&3u32.to_be_bytes());
+    for (at, name) in [(0x411, "Synthetic Display"), (0x1691, "Synthetic Title")] {
+        for (i, unit) in name.encode_utf16().enumerate() {
+            data[at + i * 2..at + i * 2 + 2].copy_from_slice(&unit.to_be_bytes());
+        }
+    }
+    data
+}
@@ -0,0 +1,9 @@
+use super::*;
+
+#[test]
+fn test_title_serial() {
+    assert_eq!(title_serial(0x4D530004), "MS-004");
+    assert_eq!(title_serial(0x4D5307E6), "MS-2022");
+    assert_eq!(title_serial(0x584111F7), "XA-4599");
+    assert_eq!(title_serial(0xFFFE07D1), "FFFE07D1");
+}
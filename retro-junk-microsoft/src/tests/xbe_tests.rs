@@ -0,0 +1,37 @@
+use super::*;
+use crate::synthetic::{XBOX_TITLE_ID, xbox_xbe};
+
+#[test]
+fn test_parse_certificate() {
+    let cert = parse_certificate(&xbox_xbe()).unwrap();
+    assert_eq!(cert.title_id, XBOX_TITLE_ID);
+    assert_eq!(cert.title_name, "Synthetic Xbox");
+    assert_eq!(cert.version, 2);
+    assert_eq!(cert.regions(), vec![Region::Usa]);
+}
+
+#[test]
+fn test_region_flags() {
+    let mut cert = parse_certificate(&xbox_xbe()).unwrap();
+    cert.region_flags = 0x0000_0006;
+    assert_eq!(cert.regions(), vec![Region::Japan, Region::Europe]);
+    cert.region_flags = 0x8000_0007;
+    assert_eq!(cert.regions(), vec![Region::World]);
+}
+
+#[test]
+fn test_certificate_outside_headers() {
+    let mut xbe = xbox_xbe();
+    xbe[0x118..0x11C].copy_from_slice(&0x2_0000u32.to_le_bytes());
+    assert!(parse_certificate(&xbe).is_err());
+    // Below the base address
+    xbe[0x118..0x11C].copy_from_slice(&0x100u32.to_le_bytes());
+    assert!(parse_certificate(&xbe).is_err());
+}
+
+#[test]
+fn test_not_an_xbe() {
+    assert!(parse_certificate(&[0u8; 0x400]).is_err());
+    assert_eq!(headers_size(&xbox_xbe()), Some(0x400));
+    assert_eq!(headers_size(b"XBEH"), None);
+}
@@ -0,0 +1,103 @@
+use super::*;
+use crate::synthetic::{xbox360, xbox360_god};
+use std::io::Cursor;
+
+fn analyze(data: Vec<u8>) -> Result<RomIdentification, AnalysisError> {
+    Xbox360Analyzer.analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+}
+
+// -- XEX --
+
+#[test]
+fn test_xex_execution_info() {
+    let data = xbox360().data;
+    assert!(Xbox360Analyzer.can_handle(&mut Cursor::new(data.clone())));
+    let id = analyze(data).unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("MS-2022"));
+    assert_eq!(id.version.as_deref(), Some("1.0.5.2"));
+    assert_eq!(
+        id.extra.get("title_id").map(String::as_str),
+        Some("4D5307E6")
+    );
+    assert_eq!(
+        id.extra.get("media_id").map(String::as_str),
+        Some("12345678")
+    );
+    assert_eq!(id.extra.get("format").map(String::as_str), Some("XEX"));
+    assert!(!id.extra.contains_key("disc_count"));
+}
+
+#[test]
+fn test_xex_region_flags() {
+    let id = analyze(xbox360().data).unwrap();
+    assert_eq!(id.regions, vec![Region::Usa, Region::Europe]);
+    assert_eq!(
+        id.extra.get("region_flags").map(String::as_str),
+        Some("00FE00FF")
+    );
+
+    let mut data = xbox360().data;
+    data[0x278..0x27C].copy_from_slice(&XEX_REGION_ALL.to_be_bytes());
+    assert_eq!(analyze(data).unwrap().regions, vec![Region::World]);
+}
+
+#[test]
+fn test_xex_multi_disc() {
+    let mut data = xbox360().data;
+    data[0x40 + 0x12] = 2;
+    data[0x40 + 0x13] = 2;
+    let id = analyze(data).unwrap();
+    assert_eq!(id.extra.get("disc_number").map(String::as_str), Some("2"));
+    assert_eq!(id.extra.get("disc_count").map(String::as_str), Some("2"));
+}
+
+#[test]
+fn test_xex_without_execution_info() {
+    let mut data = xbox360().data;
+    data[0x18..0x1C].copy_from_slice(&0x0001_0100u32.to_be_bytes());
+    assert!(analyze(data).is_err());
+}
+
+// -- STFS / Games on Demand --
+
+#[test]
+fn test_god_header() {
+    let data = xbox360_god();
+    assert!(Xbox360Analyzer.can_handle(&mut Cursor::new(data.clone())));
+    let id = analyze(data).unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("MS-2022"));
+    assert_eq!(
+        id.extra.get("content_type").map(String::as_str),
+        Some("Games on Demand")
+    );
+    assert_eq!(id.extra.get("data_files").map(String::as_str), Some("3"));
+    assert_eq!(id.internal_name.as_deref(), Some("Synthetic Title"));
+    assert_eq!(
+        id.titles.get(&Language::English).map(String::as_str),
+        Some("Synthetic Display")
+    );
+}
+
+#[test]
+fn test_stfs_non_game_rejected() {
+    // Profile
+    let mut data = xbox360_god();
+    data[0x344..0x348].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+    assert!(!Xbox360Analyzer.can_handle(&mut Cursor::new(data.clone())));
+    assert!(analyze(data).is_err());
+}
+
+#[test]
+fn test_truncated_stfs_rejected() {
+    let data = xbox360_god()[..0x400].to_vec();
+    assert!(!Xbox360Analyzer.can_handle(&mut Cursor::new(data)));
+}
+
+// -- Disc images --
+
+#[test]
+fn test_iso_not_handled() {
+    let data = vec![0u8; 0x1000];
+    assert!(!Xbox360Analyzer.can_handle(&mut Cursor::new(data.clone())));
+    assert!(analyze(data).is_err());
+}
@@ -0,0 +1,74 @@
+use super::*;
+use crate::synthetic::{xbox, xbox_xbe};
+use crate::xdvdfs::SECTOR_SIZE;
+use retro_junk_core::Region;
+use std::io::{Cursor, Seek, Write};
+
+fn analyze(reader: &mut dyn ReadSeek) -> Result<RomIdentification, AnalysisError> {
+    XboxAnalyzer.analyze(reader, &AnalysisOptions::default())
+}
+
+#[test]
+fn test_xiso_default_xbe() {
+    let data = xbox().data;
+    assert!(XboxAnalyzer.can_handle(&mut Cursor::new(data.clone())));
+    let id = analyze(&mut Cursor::new(data)).unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("MS-004"));
+    assert_eq!(id.internal_name.as_deref(), Some("Synthetic Xbox"));
+    assert_eq!(id.version.as_deref(), Some("v2"));
+    assert_eq!(id.regions, vec![Region::Usa]);
+    assert_eq!(id.extra.get("format").map(String::as_str), Some("XISO"));
+    assert_eq!(
+        id.extra.get("title_id").map(String::as_str),
+        Some("4D530004")
+    );
+}
+
+#[test]
+fn test_redump_image_at_xgd1_offset() {
+    // Sparse file: the game partition starts at the XGD1 offset
+    let xiso = xbox().data;
+    let mut file = tempfile::tempfile().unwrap();
+    file.set_len(0x1830_0000 + xiso.len() as u64).unwrap();
+    file.seek(SeekFrom::Start(0x1830_0000)).unwrap();
+    file.write_all(&xiso).unwrap();
+
+    assert!(XboxAnalyzer.can_handle(&mut file));
+    let id = analyze(&mut file).unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("MS-004"));
+    assert_eq!(id.extra.get("format").map(String::as_str), Some("XGD1"));
+}
+
+#[test]
+fn test_standalone_xbe() {
+    let data = xbox_xbe();
+    assert!(XboxAnalyzer.can_handle(&mut Cursor::new(data.clone())));
+    let id = analyze(&mut Cursor::new(data)).unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("MS-004"));
+    assert_eq!(id.extra.get("format").map(String::as_str), Some("XBE"));
+}
+
+#[test]
+fn test_disc_without_default_xbe_rejected() {
+    // An Xbox 360 game partition launches default.xex instead
+    let mut data = xbox().data;
+    let name = 33 * SECTOR_SIZE as usize + 14;
+    data[name..name + 11].copy_from_slice(b"default.xex");
+    assert!(!XboxAnalyzer.can_handle(&mut Cursor::new(data.clone())));
+    assert!(analyze(&mut Cursor::new(data)).is_err());
+}
+
+#[test]
+fn test_sniff() {
+    let xiso = xbox().data;
+    assert!(XboxAnalyzer.sniff(&xiso[..XboxAnalyzer.sniff_len()], xiso.len() as u64));
+    assert!(XboxAnalyzer.sniff(&xbox_xbe(), 0x400));
+    assert!(!XboxAnalyzer.sniff(&[0u8; 0x10000], 0x10000));
+}
+
+#[test]
+fn test_non_xbox_rejected() {
+    let data = vec![0u8; 0x20000];
+    assert!(!XboxAnalyzer.can_handle(&mut Cursor::new(data.clone())));
+    assert!(analyze(&mut Cursor::new(data)).is_err());
+}
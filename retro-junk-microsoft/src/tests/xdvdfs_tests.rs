@@ -0,0 +1,67 @@
+use super::*;
+
+/// Append an entry at `at` (a multiple of 4) in `dir`.
+fn put_entry(dir: &mut [u8], at: usize, left: u16, right: u16, sector: u32, name: &str) {
+    dir[at..at + 2].copy_from_slice(&left.to_le_bytes());
+    dir[at + 2..at + 4].copy_from_slice(&right.to_le_bytes());
+    dir[at + 4..at + 8].copy_from_slice(&sector.to_le_bytes());
+    dir[at + 8..at + 12].copy_from_slice(&0x100u32.to_le_bytes());
+    dir[at + 12] = if name.contains('.') { 0x20 } else { 0x10 };
+    dir[at + 13] = name.len() as u8;
+    dir[at + 14..at + 14 + name.len()].copy_from_slice(name.as_bytes());
+}
+
+/// "media" at the root of the tree, "$SystemUpdate" to its left and
+/// "default.xbe" to its right.
+fn make_dir() -> Vec<u8> {
+    let mut dir = vec![0xFFu8; 0x800];
+    put_entry(&mut dir, 0x00, 0x20 / 4, 0x40 / 4, 40, "media");
+    put_entry(&mut dir, 0x20, 0, 0, 41, "$SystemUpdate");
+    put_entry(&mut dir, 0x40, 0, 0, 42, "default.xbe");
+    dir
+}
+
+#[test]
+fn test_find_entry_walks_tree() {
+    let dir = make_dir();
+    let limits = ParseLimits::default();
+    let xbe = find_entry(&dir, "default.xbe", &limits).unwrap().unwrap();
+    assert_eq!(xbe.sector, 42);
+    assert!(!xbe.is_dir);
+    let update = find_entry(&dir, "$SYSTEMUPDATE", &limits).unwrap().unwrap();
+    assert_eq!(update.sector, 41);
+    assert!(update.is_dir);
+    assert!(find_entry(&dir, "default.xex", &limits).unwrap().is_none());
+}
+
+#[test]
+fn test_find_entry_empty_directory() {
+    let dir = vec![0xFFu8; 0x800];
+    assert!(
+        find_entry(&dir, "default.xbe", &ParseLimits::default())
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_find_entry_survives_cycles() {
+    let mut dir = make_dir();
+    // "$SystemUpdate" and "default.xbe" point at each other
+    dir[0x22..0x24].copy_from_slice(&(0x40u16 / 4).to_le_bytes());
+    dir[0x40..0x42].copy_from_slice(&(0x20u16 / 4).to_le_bytes());
+    assert!(
+        find_entry(&dir, "missing", &ParseLimits::default())
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_find_entry_respects_entry_limit() {
+    let limits = ParseLimits {
+        max_entries: 1,
+        ..ParseLimits::default()
+    };
+    assert!(find_entry(&make_dir(), "default.xbe", &limits).is_err());
+}
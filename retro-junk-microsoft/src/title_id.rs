@@ -0,0 +1,19 @@
+//! Title IDs shared by Xbox and Xbox 360 titles.
+//!
+//! A title ID is 32 bits: two ASCII publisher letters, then a 16-bit number.
+//! 0x4D530004 is "MS" and 4, written "MS-004"; 0x4D5307E6 is "MS-2022".
+
+/// The publisher code and number a title ID encodes, or the hex ID when
+/// the publisher bytes aren't letters or digits.
+pub(crate) fn title_serial(title_id: u32) -> String {
+    let [a, b, ..] = title_id.to_be_bytes();
+    if a.is_ascii_alphanumeric() && b.is_ascii_alphanumeric() {
+        format!("{}{}-{:03}", a as char, b as char, title_id & 0xFFFF)
+    } else {
+        format!("{:08X}", title_id)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/title_id_tests.rs"]
+mod tests;
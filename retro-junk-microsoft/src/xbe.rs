@@ -0,0 +1,117 @@
+//! XBE executables and their certificates.
+//!
+//! An XBE image header gives the base address the image loads at and the
+//! address of its certificate, which names the title and holds its title
+//! ID, allowed regions and version. The certificate lies within the
+//! headers, at its address minus the base address. All integers are
+//! little-endian.
+//!
+//! Sources:
+//! - Xbox Dev Wiki, "Xbe" page
+
+use retro_junk_core::{AnalysisError, Region};
+
+/// XBE magic.
+pub const XBE_MAGIC: &[u8; 4] = b"XBEH";
+
+/// Image header offsets.
+const BASE_ADDRESS: usize = 0x104;
+const HEADERS_SIZE: usize = 0x108;
+const CERTIFICATE_ADDRESS: usize = 0x118;
+
+/// Bytes of image header needed to locate the certificate.
+pub(crate) const IMAGE_HEADER_LEN: usize = CERTIFICATE_ADDRESS + 4;
+
+/// Certificate offsets.
+const CERT_TITLE_ID: usize = 0x08;
+const CERT_TITLE_NAME: usize = 0x0C;
+const CERT_REGION: usize = 0xA0;
+const CERT_VERSION: usize = 0xAC;
+
+/// Length of the certificate fields read, through the version.
+const CERT_SIZE: usize = CERT_VERSION + 4;
+
+/// Title name: 40 UTF-16LE characters.
+const TITLE_NAME_LEN: usize = 0x50;
+
+/// Region flags, by region. 0x80000000 marks debug/manufacturing builds.
+const REGIONS: &[(u32, Region)] = &[
+    (0x0000_0001, Region::Usa),
+    (0x0000_0002, Region::Japan),
+    (0x0000_0004, Region::Europe),
+];
+
+/// Flags of a title allowed in every retail region.
+const ALL_REGIONS: u32 = 0x0000_0007;
+
+/// Fields of an XBE certificate.
+#[derive(Debug, Clone)]
+pub struct Certificate {
+    pub title_id: u32,
+    pub title_name: String,
+    pub region_flags: u32,
+    pub version: u32,
+}
+
+impl Certificate {
+    /// Regions the region flags allow.
+    pub fn regions(&self) -> Vec<Region> {
+        if self.region_flags & ALL_REGIONS == ALL_REGIONS {
+            return vec![Region::World];
+        }
+        REGIONS
+            .iter()
+            .filter(|&&(flag, _)| self.region_flags & flag != 0)
+            .map(|&(_, region)| region)
+            .collect()
+    }
+}
+
+/// Parse the certificate from the start of an XBE, headers included.
+pub fn parse_certificate(xbe: &[u8]) -> Result<Certificate, AnalysisError> {
+    if xbe.len() < IMAGE_HEADER_LEN {
+        return Err(AnalysisError::too_small(
+            IMAGE_HEADER_LEN as u64,
+            xbe.len() as u64,
+        ));
+    }
+    if &xbe[..4] != XBE_MAGIC {
+        return Err(AnalysisError::invalid_magic(0, XBE_MAGIC, &xbe[..4]));
+    }
+    let base = le_u32(xbe, BASE_ADDRESS);
+    let at = le_u32(xbe, CERTIFICATE_ADDRESS)
+        .checked_sub(base)
+        .map(|offset| offset as usize)
+        .filter(|&offset| {
+            offset
+                .checked_add(CERT_SIZE)
+                .is_some_and(|end| end <= xbe.len())
+        })
+        .ok_or_else(|| AnalysisError::corrupted_header("XBE certificate outside its headers"))?;
+    let cert = &xbe[at..at + CERT_SIZE];
+
+    let units: Vec<u16> = cert[CERT_TITLE_NAME..CERT_TITLE_NAME + TITLE_NAME_LEN]
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&u| u != 0)
+        .collect();
+    Ok(Certificate {
+        title_id: le_u32(cert, CERT_TITLE_ID),
+        title_name: String::from_utf16_lossy(&units).trim().to_string(),
+        region_flags: le_u32(cert, CERT_REGION),
+        version: le_u32(cert, CERT_VERSION),
+    })
+}
+
+/// Size of the headers an XBE declares.
+pub(crate) fn headers_size(xbe: &[u8]) -> Option<u32> {
+    (xbe.len() >= IMAGE_HEADER_LEN && &xbe[..4] == XBE_MAGIC).then(|| le_u32(xbe, HEADERS_SIZE))
+}
+
+fn le_u32(buf: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(buf[at..at + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+#[path = "tests/xbe_tests.rs"]
+mod tests;
@@ -1,13 +1,32 @@
 //! Original Xbox disc image analyzer.
 //!
 //! Supports:
-//! - ISO images
-//! - XISO format
+//! - Redump ISO images of the whole disc, with the game partition at the
+//!   XGD1 offset
+//! - XISO images (the game partition alone)
+//! - XBE executables (`default.xbe`)
+//!
+//! The game is identified from the certificate of the `default.xbe` in the
+//! root of the XDVDFS filesystem (see [`crate::xdvdfs`] and [`crate::xbe`]).
+
+use std::io::SeekFrom;
 
 use retro_junk_core::ReadSeek;
 
+use retro_junk_core::util::read_header;
 use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
 
+use crate::read_magic;
+use crate::title_id::title_serial;
+use crate::xbe::{self, Certificate, XBE_MAGIC};
+use crate::xdvdfs::{self, Volume};
+
+/// Executable the dashboard launches from a game disc.
+const DEFAULT_XBE: &str = "default.xbe";
+
+/// Largest XBE header read; real ones are a few KB.
+const MAX_XBE_HEADERS: u64 = 0x10_0000;
+
 /// Analyzer for original Xbox disc images.
 #[derive(Debug, Default)]
 pub struct XboxAnalyzer;
@@ -15,12 +34,37 @@ pub struct XboxAnalyzer;
 impl RomAnalyzer for XboxAnalyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "Xbox disc analysis not yet implemented",
-        ))
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let (cert, format) = if read_magic(reader)? == Some(*XBE_MAGIC) {
+            let headers = read_xbe_headers(reader, file_size, options)?;
+            (xbe::parse_certificate(&headers)?, "XBE")
+        } else {
+            let volume = Volume::open(reader).ok_or_else(|| {
+                AnalysisError::invalid_format("No XDVDFS filesystem found in Xbox image")
+            })?;
+            (
+                read_disc_certificate(reader, &volume, options)?,
+                volume.layout,
+            )
+        };
+
+        let mut id = RomIdentification::new().with_platform(Platform::Xbox);
+        id.file_size = Some(file_size);
+        id.serial_number = Some(title_serial(cert.title_id));
+        id.version = Some(format!("v{}", cert.version));
+        id.regions = cert.regions();
+        if !cert.title_name.is_empty() {
+            id.internal_name = Some(cert.title_name.clone());
+        }
+        id.extra.insert("format".into(), format.into());
+        id.extra
+            .insert("title_id".into(), format!("{:08X}", cert.title_id));
+        id.extra
+            .insert("region_flags".into(), format!("{:08X}", cert.region_flags));
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -28,11 +72,30 @@ impl RomAnalyzer for XboxAnalyzer {
     }
 
     fn file_extensions(&self) -> &'static [&'static str] {
-        &["iso", "xiso"]
+        &["iso", "xiso", "xbe"]
+    }
+
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        if matches!(read_magic(reader), Ok(Some(magic)) if &magic == XBE_MAGIC) {
+            return true;
+        }
+        // Xbox 360 discs share the filesystem but launch default.xex
+        Volume::open(reader).is_some_and(|volume| {
+            volume
+                .find_in_root(reader, DEFAULT_XBE, &Default::default())
+                .is_ok_and(|entry| entry.is_some_and(|e| !e.is_dir))
+        })
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn sniff_len(&self) -> usize {
+        xdvdfs::XISO_SNIFF_LEN
+    }
+
+    /// XBEs and XISOs, whose volume descriptor is at the end of the prefix.
+    /// The root directory may be anywhere, so `default.xbe` isn't looked
+    /// for, and Redump images start with their video partition.
+    fn sniff(&self, head: &[u8], _file_size: u64) -> bool {
+        head.starts_with(XBE_MAGIC) || xdvdfs::is_xiso_head(head)
     }
 
     fn dat_source(&self) -> retro_junk_core::DatSource {
@@ -43,3 +106,53 @@ impl RomAnalyzer for XboxAnalyzer {
         &["Microsoft - Xbox"]
     }
 }
+
+/// The certificate of the disc's `default.xbe`.
+fn read_disc_certificate(
+    reader: &mut dyn ReadSeek,
+    volume: &Volume,
+    options: &AnalysisOptions,
+) -> Result<Certificate, AnalysisError> {
+    let entry = volume
+        .find_in_root(reader, DEFAULT_XBE, &options.limits)?
+        .filter(|e| !e.is_dir)
+        .ok_or_else(|| AnalysisError::invalid_format("No default.xbe in the disc's root"))?;
+    let start = volume.read_file(
+        reader,
+        &entry,
+        xbe::IMAGE_HEADER_LEN as u64,
+        &options.limits,
+    )?;
+    let headers_size = xbe::headers_size(&start)
+        .ok_or_else(|| AnalysisError::corrupted_header("default.xbe is not an XBE"))?;
+    let headers = volume.read_file(
+        reader,
+        &entry,
+        u64::from(headers_size).min(MAX_XBE_HEADERS),
+        &options.limits,
+    )?;
+    xbe::parse_certificate(&headers)
+}
+
+/// The headers of a standalone XBE.
+fn read_xbe_headers(
+    reader: &mut dyn ReadSeek,
+    file_size: u64,
+    options: &AnalysisOptions,
+) -> Result<Vec<u8>, AnalysisError> {
+    let mut start = [0u8; xbe::IMAGE_HEADER_LEN];
+    read_header(reader, 0, &mut start)?;
+    let headers_size = xbe::headers_size(&start).unwrap_or_default();
+    let len = options.limits.check_alloc(
+        u64::from(headers_size).min(MAX_XBE_HEADERS).min(file_size),
+        "XBE headers",
+    )?;
+    let mut headers = vec![0u8; len];
+    reader.seek(SeekFrom::Start(0))?;
+    reader.read_exact(&mut headers)?;
+    Ok(headers)
+}
+
+#[cfg(test)]
+#[path = "tests/xbox_tests.rs"]
+mod tests;
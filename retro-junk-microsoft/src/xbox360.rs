@@ -1,13 +1,128 @@
 //! Xbox 360 disc image analyzer.
 //!
 //! Supports:
-//! - ISO images
-//! - GOD (Games on Demand) format
-//! - XEX executables
+//! - XEX executables (`default.xex`): title ID, media ID, version and the
+//!   region flags from the XEX2 header
+//! - STFS containers (`CON `, `LIVE`, `PIRS`), including the header file of a
+//!   Games on Demand folder (`<title ID>/00007000/<name>`, with its data in
+//!   `<name>.data/`) and Xbox Live Arcade titles
+//! - ISO images are recognized by extension only
+//!
+//! Title IDs are written as their publisher code and number (see
+//! [`crate::title_id`]).
+//!
+//! Sources:
+//! - Free60 wiki, "XEX" and "STFS" pages
+//! - Xenia, `xex2_info.h` (XEX2 header, execution info and region flags)
+
+use std::io::SeekFrom;
 
 use retro_junk_core::ReadSeek;
+use retro_junk_core::util::read_header;
+
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, Language, Platform, Region, RomAnalyzer, RomIdentification,
+};
+
+use crate::read_magic;
+use crate::title_id::title_serial;
+
+/// XEX2 executable magic.
+pub const XEX2_MAGIC: &[u8; 4] = b"XEX2";
+
+/// STFS container magics: console-signed, Xbox Live and Microsoft-signed.
+pub const STFS_MAGICS: [&[u8; 4]; 3] = [b"CON ", b"LIVE", b"PIRS"];
+
+/// XEX2 header: magic, module flags, PE offset, reserved, security info
+/// offset and optional header count.
+const XEX_HEADER_SIZE: usize = 0x18;
+
+/// Optional header key of the execution info.
+const XEX_EXECUTION_INFO: u32 = 0x0004_0006;
+
+/// Size of the execution info record.
+const XEX_EXECUTION_INFO_SIZE: usize = 0x18;
+
+/// Offset of the region flags in the security info.
+const XEX_REGION_OFFSET: u64 = 0x178;
+
+/// Region flags of a region-free title.
+const XEX_REGION_ALL: u32 = 0xFFFF_FFFF;
+
+/// XEX region flag masks, by region. Flags outside these (the rest of
+/// Asia, and "other") are kept only in `extra["region_flags"]`.
+const XEX_REGIONS: &[(u32, Region)] = &[
+    (0x0000_00FF, Region::Usa),
+    (0x0000_0100, Region::Japan),
+    (0x0000_0200, Region::China),
+    (0x0001_0000, Region::Australia),
+    (0x00FE_0000, Region::Europe),
+];
+
+/// STFS metadata offsets.
+const STFS_CONTENT_TYPE: usize = 0x344;
+const STFS_EXECUTION_INFO: usize = 0x354;
+const STFS_DATA_FILE_COUNT: usize = 0x39D;
+const STFS_DISPLAY_NAME: usize = 0x411;
+const STFS_TITLE_NAME: usize = 0x1691;
 
-use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+/// Length of a UTF-16 name field in the STFS metadata.
+const STFS_NAME_LEN: usize = 0x80;
+
+/// Bytes of STFS header read, through the title name.
+const STFS_HEADER_SIZE: usize = STFS_TITLE_NAME + STFS_NAME_LEN;
+
+/// STFS content types holding games, by code.
+const GAME_CONTENT_TYPES: &[(u32, &str)] = &[
+    (0x0000_0002, "Marketplace Content"),
+    (0x0000_4000, "Installed Game"),
+    (0x0000_5000, "Xbox Original Game"),
+    (0x0000_7000, "Games on Demand"),
+    (0x0008_0000, "Game Demo"),
+    (0x000B_0000, "Title Update"),
+    (0x000D_0000, "Xbox Live Arcade"),
+    (0x0200_0000, "Community Game"),
+];
+
+/// The execution info shared by XEX headers and STFS metadata.
+#[derive(Debug, Clone, Copy)]
+struct ExecutionInfo {
+    media_id: u32,
+    version: u32,
+    title_id: u32,
+    disc_number: u8,
+    disc_count: u8,
+}
+
+impl ExecutionInfo {
+    /// Media ID, version, base version, title ID, platform, executable
+    /// type, disc number and disc count.
+    fn parse(buf: &[u8]) -> Self {
+        Self {
+            media_id: be_u32(buf, 0x00),
+            version: be_u32(buf, 0x04),
+            title_id: be_u32(buf, 0x0C),
+            disc_number: buf[0x12],
+            disc_count: buf[0x13],
+        }
+    }
+
+    /// Fill in the fields shared by every format.
+    fn apply(&self, id: &mut RomIdentification) {
+        id.serial_number = Some(title_serial(self.title_id));
+        id.version = Some(format_version(self.version));
+        id.extra
+            .insert("title_id".into(), format!("{:08X}", self.title_id));
+        id.extra
+            .insert("media_id".into(), format!("{:08X}", self.media_id));
+        if self.disc_count > 1 {
+            id.extra
+                .insert("disc_number".into(), self.disc_number.to_string());
+            id.extra
+                .insert("disc_count".into(), self.disc_count.to_string());
+        }
+    }
+}
 
 /// Analyzer for Xbox 360 disc images.
 #[derive(Debug, Default)]
@@ -16,12 +131,22 @@ pub struct Xbox360Analyzer;
 impl RomAnalyzer for Xbox360Analyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
+        reader: &mut dyn ReadSeek,
         _options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "Xbox 360 disc analysis not yet implemented",
-        ))
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let mut id = match read_magic(reader)? {
+            Some(magic) if &magic == XEX2_MAGIC => analyze_xex(reader)?,
+            Some(magic) if STFS_MAGICS.contains(&&magic) => analyze_stfs(reader)?,
+            _ => {
+                return Err(AnalysisError::other(
+                    "Xbox 360 disc analysis not yet implemented",
+                ));
+            }
+        };
+        id.platform = Some(Platform::Xbox360);
+        id.file_size = Some(file_size);
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -32,8 +157,14 @@ impl RomAnalyzer for Xbox360Analyzer {
         &["iso", "xex"]
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        match read_magic(reader) {
+            Ok(Some(magic)) if &magic == XEX2_MAGIC => true,
+            Ok(Some(magic)) if STFS_MAGICS.contains(&&magic) => {
+                read_stfs_header(reader).is_ok_and(|h| content_type_name(&h).is_some())
+            }
+            _ => false,
+        }
     }
 
     fn dat_source(&self) -> retro_junk_core::DatSource {
@@ -44,3 +175,134 @@ impl RomAnalyzer for Xbox360Analyzer {
         &["Microsoft - Xbox 360"]
     }
 }
+
+/// Identify an XEX from its execution info and security info.
+fn analyze_xex(reader: &mut dyn ReadSeek) -> Result<RomIdentification, AnalysisError> {
+    let mut header = [0u8; XEX_HEADER_SIZE];
+    read_header(reader, 0, &mut header)?;
+    let security_offset = be_u32(&header, 0x10);
+    let header_count = be_u32(&header, 0x14);
+
+    // Optional headers are (key, value) pairs; the execution info's value
+    // is the offset of its record.
+    let mut execution_offset = None;
+    for i in 0..header_count.min(0x100) {
+        let mut entry = [0u8; 8];
+        read_header(
+            reader,
+            (XEX_HEADER_SIZE + i as usize * 8) as u64,
+            &mut entry,
+        )?;
+        if be_u32(&entry, 0) == XEX_EXECUTION_INFO {
+            execution_offset = Some(be_u32(&entry, 4));
+            break;
+        }
+    }
+    let execution_offset = execution_offset
+        .ok_or_else(|| AnalysisError::corrupted_header("XEX has no execution info"))?;
+
+    let mut record = [0u8; XEX_EXECUTION_INFO_SIZE];
+    read_header(reader, execution_offset.into(), &mut record)?;
+
+    let mut id = RomIdentification::new();
+    ExecutionInfo::parse(&record).apply(&mut id);
+    id.extra.insert("format".into(), "XEX".into());
+
+    let mut flags = [0u8; 4];
+    reader.seek(SeekFrom::Start(
+        u64::from(security_offset) + XEX_REGION_OFFSET,
+    ))?;
+    if reader.read_exact(&mut flags).is_ok() {
+        let flags = u32::from_be_bytes(flags);
+        id.regions = regions_from_flags(flags);
+        id.extra
+            .insert("region_flags".into(), format!("{:08X}", flags));
+    }
+    Ok(id)
+}
+
+/// Identify an STFS container from its metadata.
+fn analyze_stfs(reader: &mut dyn ReadSeek) -> Result<RomIdentification, AnalysisError> {
+    let header = read_stfs_header(reader)?;
+    let content_type = content_type_name(&header).ok_or_else(|| {
+        AnalysisError::invalid_format(format!(
+            "STFS content type 0x{:08X} is not a game",
+            be_u32(&header, STFS_CONTENT_TYPE)
+        ))
+    })?;
+
+    let mut id = RomIdentification::new();
+    ExecutionInfo::parse(&header[STFS_EXECUTION_INFO..]).apply(&mut id);
+    id.extra.insert("format".into(), "STFS".into());
+    id.extra.insert("content_type".into(), content_type.into());
+
+    let data_files = be_u32(&header, STFS_DATA_FILE_COUNT);
+    if data_files > 0 {
+        id.extra.insert("data_files".into(), data_files.to_string());
+    }
+    if let Some(name) = read_utf16_be(&header[STFS_TITLE_NAME..][..STFS_NAME_LEN]) {
+        id.internal_name = Some(name);
+    }
+    // The first display name is the English one
+    if let Some(name) = read_utf16_be(&header[STFS_DISPLAY_NAME..][..STFS_NAME_LEN]) {
+        id.titles.insert(Language::English, name);
+    }
+    Ok(id)
+}
+
+fn read_stfs_header(reader: &mut dyn ReadSeek) -> Result<Vec<u8>, AnalysisError> {
+    let mut header = vec![0u8; STFS_HEADER_SIZE];
+    read_header(reader, 0, &mut header)?;
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(header)
+}
+
+fn content_type_name(header: &[u8]) -> Option<&'static str> {
+    let code = be_u32(header, STFS_CONTENT_TYPE);
+    GAME_CONTENT_TYPES
+        .iter()
+        .find(|&&(c, _)| c == code)
+        .map(|&(_, name)| name)
+}
+
+/// The regions an XEX's region flags allow.
+fn regions_from_flags(flags: u32) -> Vec<Region> {
+    if flags == XEX_REGION_ALL {
+        return vec![Region::World];
+    }
+    XEX_REGIONS
+        .iter()
+        .filter(|&&(mask, _)| flags & mask != 0)
+        .map(|&(_, region)| region)
+        .collect()
+}
+
+/// Major (4 bits), minor (4), build (16) and QFE (8): "1.0.1234.5".
+fn format_version(version: u32) -> String {
+    format!(
+        "{}.{}.{}.{}",
+        version >> 28,
+        (version >> 24) & 0xF,
+        (version >> 8) & 0xFFFF,
+        version & 0xFF
+    )
+}
+
+/// A NUL-padded UTF-16BE string, if not blank.
+fn read_utf16_be(buf: &[u8]) -> Option<String> {
+    let units: Vec<u16> = buf
+        .chunks_exact(2)
+        .map(|c| u16::from_be_bytes([c[0], c[1]]))
+        .take_while(|&u| u != 0)
+        .collect();
+    let text = String::from_utf16_lossy(&units).trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+fn be_u32(buf: &[u8], at: usize) -> u32 {
+    u32::from_be_bytes(buf[at..at + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+#[path = "tests/xbox360_tests.rs"]
+mod tests;
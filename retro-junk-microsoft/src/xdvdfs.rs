@@ -0,0 +1,187 @@
+//! XDVDFS, the filesystem of Xbox and Xbox 360 game discs.
+//!
+//! The volume descriptor sits in sector 32 of the game partition. An
+//! extracted XISO starts with the partition; a Redump image keeps the whole
+//! disc, with the game partition at an offset fixed by the disc type
+//! (XGD1 for Xbox, XGD2 and XGD3 for Xbox 360).
+//!
+//! A directory is a binary tree of entries, each holding the offsets of its
+//! left and right subtrees in 4-byte units (0 for none), the file's first
+//! sector and size, its attributes and its name. All integers are
+//! little-endian.
+//!
+//! Sources:
+//! - Xbox Dev Wiki, "XDVDFS" and "Xbox Game Disc" pages
+
+use std::collections::HashSet;
+use std::io::SeekFrom;
+
+use retro_junk_core::{AnalysisError, ParseLimits, ReadSeek};
+
+/// Volume descriptor magic, at its start and end.
+pub const MEDIA_MAGIC: &[u8; 20] = b"MICROSOFT*XBOX*MEDIA";
+
+/// XDVDFS sector size.
+pub const SECTOR_SIZE: u64 = 2048;
+
+/// Sector of the volume descriptor within the game partition.
+const VOLUME_DESCRIPTOR_SECTOR: u64 = 32;
+
+/// Offset of an XISO's volume descriptor.
+const XISO_DESCRIPTOR_OFFSET: usize = (VOLUME_DESCRIPTOR_SECTOR * SECTOR_SIZE) as usize;
+
+/// Bytes from the start of an image [`is_xiso_head`] needs.
+pub const XISO_SNIFF_LEN: usize = XISO_DESCRIPTOR_OFFSET + MEDIA_MAGIC.len();
+
+/// Game partition offsets, by image layout.
+pub const PARTITION_OFFSETS: [(u64, &str); 4] = [
+    (0, "XISO"),
+    (0x1830_0000, "XGD1"),
+    (0x0FD9_0000, "XGD2"),
+    (0x0208_0000, "XGD3"),
+];
+
+/// Directory entry attribute of a subdirectory.
+const ATTR_DIRECTORY: u8 = 0x10;
+
+/// Fixed part of a directory entry, before the name.
+const ENTRY_HEADER_SIZE: usize = 14;
+
+/// Subtree offset marking unused directory space.
+const NO_ENTRY: u16 = 0xFFFF;
+
+/// A game partition found in an image.
+#[derive(Debug, Clone, Copy)]
+pub struct Volume {
+    /// Offset of the partition in the image.
+    pub offset: u64,
+    /// Image layout: "XISO", or the disc type of a full image.
+    pub layout: &'static str,
+    root_sector: u32,
+    root_size: u32,
+}
+
+/// A file or directory in an XDVDFS directory.
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    pub sector: u32,
+    pub size: u32,
+    pub is_dir: bool,
+}
+
+impl Volume {
+    /// Find the game partition, trying each known layout.
+    pub fn open(reader: &mut dyn ReadSeek) -> Option<Self> {
+        PARTITION_OFFSETS.iter().find_map(|&(offset, layout)| {
+            let mut descriptor = [0u8; 0x1C];
+            reader
+                .seek(SeekFrom::Start(
+                    offset + VOLUME_DESCRIPTOR_SECTOR * SECTOR_SIZE,
+                ))
+                .ok()?;
+            reader.read_exact(&mut descriptor).ok()?;
+            (&descriptor[..20] == MEDIA_MAGIC).then(|| Self {
+                offset,
+                layout,
+                root_sector: le_u32(&descriptor, 0x14),
+                root_size: le_u32(&descriptor, 0x18),
+            })
+        })
+    }
+
+    /// Look up `name` (case-insensitively) in the root directory.
+    pub fn find_in_root(
+        &self,
+        reader: &mut dyn ReadSeek,
+        name: &str,
+        limits: &ParseLimits,
+    ) -> Result<Option<Entry>, AnalysisError> {
+        let len = limits.check_alloc(self.root_size.into(), "XDVDFS root directory")?;
+        let mut dir = vec![0u8; len];
+        reader.seek(SeekFrom::Start(self.sector_offset(self.root_sector)))?;
+        reader
+            .read_exact(&mut dir)
+            .map_err(|_| AnalysisError::corrupted_header("XDVDFS root directory truncated"))?;
+        find_entry(&dir, name, limits)
+    }
+
+    /// Read the first `len` bytes of a file, or all of it if shorter.
+    pub fn read_file(
+        &self,
+        reader: &mut dyn ReadSeek,
+        entry: &Entry,
+        len: u64,
+        limits: &ParseLimits,
+    ) -> Result<Vec<u8>, AnalysisError> {
+        let len = limits.check_alloc(len.min(entry.size.into()), "XDVDFS file")?;
+        let mut data = vec![0u8; len];
+        reader.seek(SeekFrom::Start(self.sector_offset(entry.sector)))?;
+        reader
+            .read_exact(&mut data)
+            .map_err(|_| AnalysisError::corrupted_header("XDVDFS file truncated"))?;
+        Ok(data)
+    }
+
+    fn sector_offset(&self, sector: u32) -> u64 {
+        self.offset + u64::from(sector) * SECTOR_SIZE
+    }
+}
+
+/// Whether `head`, the start of an image, holds an XISO volume descriptor.
+pub fn is_xiso_head(head: &[u8]) -> bool {
+    head.get(XISO_DESCRIPTOR_OFFSET..XISO_SNIFF_LEN) == Some(MEDIA_MAGIC)
+}
+
+/// Walk a directory's entry tree for `name`.
+fn find_entry(
+    dir: &[u8],
+    name: &str,
+    limits: &ParseLimits,
+) -> Result<Option<Entry>, AnalysisError> {
+    let mut pending = vec![0usize];
+    let mut visited = HashSet::new();
+    while let Some(at) = pending.pop() {
+        if !visited.insert(at) {
+            continue;
+        }
+        limits.check_entries(visited.len() as u64, "XDVDFS directory entries")?;
+        let Some(header) = dir.get(at..at + ENTRY_HEADER_SIZE) else {
+            continue;
+        };
+        let left = le_u16(header, 0);
+        let right = le_u16(header, 2);
+        if left == NO_ENTRY && right == NO_ENTRY {
+            continue;
+        }
+        let name_len = header[13] as usize;
+        let Some(entry_name) = dir.get(at + ENTRY_HEADER_SIZE..at + ENTRY_HEADER_SIZE + name_len)
+        else {
+            continue;
+        };
+        if entry_name.eq_ignore_ascii_case(name.as_bytes()) {
+            return Ok(Some(Entry {
+                sector: le_u32(header, 4),
+                size: le_u32(header, 8),
+                is_dir: header[12] & ATTR_DIRECTORY != 0,
+            }));
+        }
+        for child in [left, right] {
+            if child != 0 && child != NO_ENTRY {
+                pending.push(usize::from(child) * 4);
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn le_u16(buf: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([buf[at], buf[at + 1]])
+}
+
+fn le_u32(buf: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(buf[at..at + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+#[path = "tests/xdvdfs_tests.rs"]
+mod tests;
@@ -0,0 +1,97 @@
+//! Console music rips.
+//!
+//! Sound files ripped from games (NSF, SPC, GBS, VGM) hold a game's sound
+//! driver or a log of its sound chip writes, plus tags naming the game and
+//! composer. Platform crates parse their own formats into a [`MusicRip`],
+//! which turns into a [`RomIdentification`] the same way for all of them.
+//! VGM logs cover many consoles, so [`vgm`] reads them here and each
+//! platform decides which logs are its own from the chips they use.
+//!
+//! Rips are identified through
+//! [`RomAnalyzer::analyze_music`](retro_junk_core::RomAnalyzer::analyze_music),
+//! never as ROMs.
+
+#[cfg(any(test, feature = "testkit"))]
+pub mod synthetic;
+pub mod vgm;
+
+use retro_junk_core::util::read_ascii;
+use retro_junk_core::{Platform, RomIdentification};
+
+/// Tags and playback facts common to music rip formats.
+#[derive(Debug, Clone, Default)]
+pub struct MusicRip {
+    /// Short format name ("NSF", "SPC", "VGM").
+    pub format: &'static str,
+    /// Game the music comes from.
+    pub game: Option<String>,
+    /// Song title, for single-track formats.
+    pub title: Option<String>,
+    /// Composer or artist.
+    pub artist: Option<String>,
+    pub copyright: Option<String>,
+    /// Who ripped or logged the music.
+    pub ripper: Option<String>,
+    /// Number of tracks, for multi-track formats.
+    pub tracks: Option<u32>,
+    /// Playing time in seconds, when the file records it.
+    pub length_secs: Option<u32>,
+    /// Sound chips the music drives, where the format lists them.
+    pub chips: Vec<&'static str>,
+}
+
+impl MusicRip {
+    pub fn new(format: &'static str) -> Self {
+        Self {
+            format,
+            ..Self::default()
+        }
+    }
+
+    /// Identification for a rip of `platform` music. The game name stands
+    /// in for the internal name, falling back to the song title.
+    pub fn to_identification(&self, platform: Platform, file_size: u64) -> RomIdentification {
+        let mut id = RomIdentification::new().with_platform(platform);
+        if let Some(name) = self.game.as_ref().or(self.title.as_ref()) {
+            id = id.with_internal_name(name);
+        }
+        id.file_size = Some(file_size);
+
+        id.extra
+            .insert("format".into(), format!("{} music rip", self.format));
+        let tags = [
+            ("title", &self.title),
+            ("artist", &self.artist),
+            ("copyright", &self.copyright),
+            ("ripper", &self.ripper),
+        ];
+        for (key, value) in tags {
+            if let Some(value) = value {
+                id.extra.insert(key.into(), value.clone());
+            }
+        }
+        if let Some(tracks) = self.tracks {
+            id.extra.insert("tracks".into(), tracks.to_string());
+        }
+        if let Some(secs) = self.length_secs {
+            id.extra
+                .insert("length".into(), format!("{}:{:02}", secs / 60, secs % 60));
+        }
+        if !self.chips.is_empty() {
+            id.extra.insert("sound_chips".into(), self.chips.join(", "));
+        }
+        id
+    }
+}
+
+/// Read a null-padded tag field, treating blanks and the "<?>" placeholder
+/// NSF and GBS rippers use for unknown fields as missing.
+pub fn tag_text(buf: &[u8]) -> Option<String> {
+    let text = read_ascii(buf);
+    let text = text.trim();
+    (!text.is_empty() && text != "<?>").then(|| text.to_string())
+}
+
+#[cfg(test)]
+#[path = "tests/music_tests.rs"]
+mod tests;
@@ -0,0 +1,51 @@
+//! Synthetic music rips for tests.
+
+use crate::vgm::{Gd3Tag, VGM_MAGIC, VgmChip};
+
+/// A version 1.71 VGM log of `chips` playing for 90 seconds, with a GD3
+/// tag when `tag` is given. Music rips are claimed by the consoles whose
+/// chips they use, so every platform crate's tests share this builder.
+pub fn vgm(chips: &[VgmChip], tag: Option<&Gd3Tag>) -> Vec<u8> {
+    const HEADER_LEN: usize = 0x100;
+    let mut data = vec![0u8; HEADER_LEN];
+    data[..4].copy_from_slice(VGM_MAGIC);
+    data[0x08..0x0C].copy_from_slice(&0x171u32.to_le_bytes());
+    data[0x18..0x1C].copy_from_slice(&(90 * 44_100u32).to_le_bytes());
+    data[0x34..0x38].copy_from_slice(&((HEADER_LEN - 0x34) as u32).to_le_bytes());
+    for chip in chips {
+        let offset = chip.clock_offset();
+        data[offset..offset + 4].copy_from_slice(&3_579_545u32.to_le_bytes());
+    }
+    // End of sound data
+    data.push(0x66);
+
+    if let Some(tag) = tag {
+        let strings = [
+            &tag.track,
+            &tag.track_jp,
+            &tag.game,
+            &tag.game_jp,
+            &tag.system,
+            &tag.system_jp,
+            &tag.author,
+            &tag.author_jp,
+            &tag.release_date,
+            &tag.ripper,
+            &tag.notes,
+        ];
+        let body: Vec<u8> = strings
+            .iter()
+            .flat_map(|s| s.encode_utf16().chain([0]))
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let gd3_offset = data.len() as u32 - 0x14;
+        data[0x14..0x18].copy_from_slice(&gd3_offset.to_le_bytes());
+        data.extend_from_slice(b"Gd3 ");
+        data.extend_from_slice(&0x100u32.to_le_bytes());
+        data.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        data.extend_from_slice(&body);
+    }
+    let eof_offset = data.len() as u32 - 0x04;
+    data[0x04..0x08].copy_from_slice(&eof_offset.to_le_bytes());
+    data
+}
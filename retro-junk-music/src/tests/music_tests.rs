@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn tag_text_drops_blanks_and_placeholders() {
+    assert_eq!(tag_text(b"Mega Man 2\0\0\0").as_deref(), Some("Mega Man 2"));
+    assert_eq!(tag_text(b"<?>\0\0"), None);
+    assert_eq!(tag_text(b"   \0"), None);
+}
+
+#[test]
+fn identification_prefers_game_over_title() {
+    let mut rip = MusicRip::new("SPC");
+    rip.title = Some("Main Theme".into());
+    rip.artist = Some("Koji Kondo".into());
+    rip.length_secs = Some(125);
+    let id = rip.to_identification(Platform::Snes, 66_048);
+    assert_eq!(id.internal_name.as_deref(), Some("Main Theme"));
+    assert_eq!(id.extra["format"], "SPC music rip");
+    assert_eq!(id.extra["length"], "2:05");
+    assert!(!id.extra.contains_key("tracks"));
+
+    rip.game = Some("Super Mario World".into());
+    rip.chips = vec!["Konami VRC6", "Namco 163"];
+    let id = rip.to_identification(Platform::Snes, 66_048);
+    assert_eq!(id.internal_name.as_deref(), Some("Super Mario World"));
+    assert_eq!(id.extra["title"], "Main Theme");
+    assert_eq!(id.extra["artist"], "Koji Kondo");
+    assert_eq!(id.extra["sound_chips"], "Konami VRC6, Namco 163");
+    assert_eq!(id.file_size, Some(66_048));
+}
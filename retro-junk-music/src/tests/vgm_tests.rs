@@ -0,0 +1,122 @@
+use std::io::{Cursor, Write};
+
+use super::*;
+use crate::synthetic;
+
+fn tag() -> Gd3Tag {
+    Gd3Tag {
+        track: "Green Hill Zone".into(),
+        game: "Sonic the Hedgehog".into(),
+        system: "Sega Mega Drive / Genesis".into(),
+        author: "Masato Nakamura".into(),
+        release_date: "1991".into(),
+        ripper: "someone".into(),
+        ..Gd3Tag::default()
+    }
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn parse_reads_chips_and_gd3() {
+    let data = synthetic::vgm(&[VgmChip::Sn76489, VgmChip::Ym2612], Some(&tag()));
+    let vgm = Vgm::parse(&data).unwrap();
+    assert_eq!(vgm.version_string(), "1.71");
+    assert_eq!(vgm.chips, [VgmChip::Sn76489, VgmChip::Ym2612]);
+    assert_eq!(vgm.total_samples, 90 * 44_100);
+    assert_eq!(vgm.gd3.as_ref().unwrap().game, "Sonic the Hedgehog");
+    assert_eq!(vgm.system(), Some("Sega Mega Drive / Genesis"));
+}
+
+#[test]
+fn parse_rejects_other_files_by_magic() {
+    let mut data = synthetic::vgm(&[VgmChip::Sn76489], None);
+    data[..4].copy_from_slice(b"RIFF");
+    assert_eq!(Vgm::parse(&data).unwrap_err().code(), "invalid_magic");
+}
+
+#[test]
+fn clocks_past_the_header_are_ignored() {
+    let mut data = synthetic::vgm(&[VgmChip::NesApu], None);
+    // Version 1.01: a 64-byte header without the later chips
+    data[0x08..0x0C].copy_from_slice(&0x101u32.to_le_bytes());
+    data[0x2C..0x30].copy_from_slice(&7_670_453u32.to_le_bytes());
+    let vgm = Vgm::parse(&data).unwrap();
+    assert!(vgm.chips.is_empty());
+}
+
+#[test]
+fn dual_chip_flag_is_not_a_clock() {
+    let mut data = synthetic::vgm(&[], None);
+    data[0x0C..0x10].copy_from_slice(&0x4000_0000u32.to_le_bytes());
+    assert!(Vgm::parse(&data).unwrap().chips.is_empty());
+}
+
+#[test]
+fn read_vgm_decompresses_vgz() {
+    let data = synthetic::vgm(&[VgmChip::GbDmg], Some(&tag()));
+    let vgm = read_vgm(&mut Cursor::new(gzip(&data)), &ParseLimits::default()).unwrap();
+    assert!(vgm.compressed);
+    assert_eq!(vgm.chips, [VgmChip::GbDmg]);
+    assert_eq!(vgm.to_music_rip().format, "VGZ");
+}
+
+#[test]
+fn read_vgm_respects_alloc_limit() {
+    let data = synthetic::vgm(&[VgmChip::GbDmg], None);
+    let limits = ParseLimits {
+        max_alloc: 64,
+        ..ParseLimits::default()
+    };
+    let err = read_vgm(&mut Cursor::new(gzip(&data)), &limits).unwrap_err();
+    assert_eq!(err.code(), "limit_exceeded");
+}
+
+#[test]
+fn chips_in_prefix_handles_both_encodings() {
+    let data = synthetic::vgm(&[VgmChip::HuC6280], Some(&tag()));
+    assert_eq!(chips_in_prefix(&data), Some(vec![VgmChip::HuC6280]));
+    assert_eq!(chips_in_prefix(&gzip(&data)), Some(vec![VgmChip::HuC6280]));
+    assert_eq!(chips_in_prefix(b"NESM\x1a"), None);
+    assert_eq!(chips_in_prefix(&gzip(b"not a vgm")), None);
+}
+
+#[test]
+fn analyze_builds_identification() {
+    let data = synthetic::vgm(&[VgmChip::Ym2612], Some(&tag()));
+    let id = analyze(
+        &mut Cursor::new(data),
+        &AnalysisOptions::default(),
+        Platform::Genesis,
+        |v| v.has_chip(VgmChip::Ym2612),
+    )
+    .unwrap();
+    assert_eq!(id.platform, Some(Platform::Genesis));
+    assert_eq!(id.internal_name.as_deref(), Some("Sonic the Hedgehog"));
+    assert_eq!(id.extra["format"], "VGM music rip");
+    assert_eq!(id.extra["title"], "Green Hill Zone");
+    assert_eq!(id.extra["artist"], "Masato Nakamura");
+    assert_eq!(id.extra["length"], "1:30");
+    assert_eq!(id.extra["sound_chips"], "YM2612");
+    assert_eq!(id.extra["tagged_system"], "Sega Mega Drive / Genesis");
+    assert_eq!(id.extra["release_date"], "1991");
+    assert_eq!(id.extra["loops"], "No");
+}
+
+#[test]
+fn analyze_rejects_logs_belonging_elsewhere() {
+    let data = synthetic::vgm(&[VgmChip::NesApu], None);
+    let mut reader = Cursor::new(data);
+    let err = analyze(
+        &mut reader,
+        &AnalysisOptions::default(),
+        Platform::GameBoy,
+        |v| v.has_chip(VgmChip::GbDmg),
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), "invalid_format");
+}
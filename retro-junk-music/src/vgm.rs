@@ -0,0 +1,421 @@
+//! VGM sound chip logs (.vgm, and gzip-compressed .vgz).
+//!
+//! A VGM file records every write a game made to its sound chips. The
+//! header gives a clock for each chip used (zero for the rest), the play
+//! length in 44.1 kHz samples, and the offset of a GD3 tag: eleven UTF-16
+//! strings naming the track, game, system and composer in English and
+//! Japanese. Header fields added in later versions are only meaningful
+//! when the header reaches them; version 1.50 added the data offset that
+//! says how long it is.
+
+use std::io::{Read, SeekFrom};
+
+use flate2::read::GzDecoder;
+
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, ParseLimits, Platform, ReadSeek, RomIdentification,
+};
+
+use crate::MusicRip;
+
+/// VGM magic.
+pub const VGM_MAGIC: &[u8; 4] = b"Vgm ";
+
+/// GD3 tag magic.
+const GD3_MAGIC: &[u8; 4] = b"Gd3 ";
+
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// Header length before version 1.50, and the most any version uses.
+const LEGACY_HEADER_LEN: usize = 0x40;
+const MAX_HEADER_LEN: usize = 0x100;
+
+/// VGM sample rate, fixed regardless of the chips' own rates.
+const SAMPLE_RATE: u32 = 44_100;
+
+/// A sound chip a VGM log can drive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VgmChip {
+    Sn76489,
+    Ym2413,
+    Ym2612,
+    Ym2151,
+    SegaPcm,
+    Rf5c68,
+    Ym2203,
+    Ym2608,
+    Ym2610,
+    Ym3812,
+    Ym3526,
+    Y8950,
+    Ymf262,
+    Ymf278b,
+    Ymf271,
+    Ymz280b,
+    Rf5c164,
+    Pwm,
+    Ay8910,
+    GbDmg,
+    NesApu,
+    MultiPcm,
+    Upd7759,
+    Okim6258,
+    Okim6295,
+    K051649,
+    K054539,
+    HuC6280,
+    C140,
+    K053260,
+    Pokey,
+    QSound,
+    Scsp,
+    WonderSwan,
+    Vsu,
+    Saa1099,
+    Es5503,
+    Es5506,
+    X1010,
+    C352,
+    Ga20,
+}
+
+/// Header offset of each chip's clock. Version 1.10 moved the YM2612 and
+/// YM2151 off the YM2413's field; older logs can't tell the three apart.
+const CHIP_CLOCKS: &[(usize, VgmChip)] = &[
+    (0x0C, VgmChip::Sn76489),
+    (0x10, VgmChip::Ym2413),
+    (0x2C, VgmChip::Ym2612),
+    (0x30, VgmChip::Ym2151),
+    (0x38, VgmChip::SegaPcm),
+    (0x40, VgmChip::Rf5c68),
+    (0x44, VgmChip::Ym2203),
+    (0x48, VgmChip::Ym2608),
+    (0x4C, VgmChip::Ym2610),
+    (0x50, VgmChip::Ym3812),
+    (0x54, VgmChip::Ym3526),
+    (0x58, VgmChip::Y8950),
+    (0x5C, VgmChip::Ymf262),
+    (0x60, VgmChip::Ymf278b),
+    (0x64, VgmChip::Ymf271),
+    (0x68, VgmChip::Ymz280b),
+    (0x6C, VgmChip::Rf5c164),
+    (0x70, VgmChip::Pwm),
+    (0x74, VgmChip::Ay8910),
+    (0x80, VgmChip::GbDmg),
+    (0x84, VgmChip::NesApu),
+    (0x88, VgmChip::MultiPcm),
+    (0x8C, VgmChip::Upd7759),
+    (0x90, VgmChip::Okim6258),
+    (0x98, VgmChip::Okim6295),
+    (0x9C, VgmChip::K051649),
+    (0xA0, VgmChip::K054539),
+    (0xA4, VgmChip::HuC6280),
+    (0xA8, VgmChip::C140),
+    (0xAC, VgmChip::K053260),
+    (0xB0, VgmChip::Pokey),
+    (0xB4, VgmChip::QSound),
+    (0xB8, VgmChip::Scsp),
+    (0xC0, VgmChip::WonderSwan),
+    (0xC4, VgmChip::Vsu),
+    (0xC8, VgmChip::Saa1099),
+    (0xCC, VgmChip::Es5503),
+    (0xD0, VgmChip::Es5506),
+    (0xD8, VgmChip::X1010),
+    (0xDC, VgmChip::C352),
+    (0xE0, VgmChip::Ga20),
+];
+
+impl VgmChip {
+    /// Header offset of the chip's clock.
+    pub fn clock_offset(&self) -> usize {
+        CHIP_CLOCKS
+            .iter()
+            .find(|(_, chip)| chip == self)
+            .map(|&(offset, _)| offset)
+            .expect("every chip has a clock field")
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sn76489 => "SN76489",
+            Self::Ym2413 => "YM2413",
+            Self::Ym2612 => "YM2612",
+            Self::Ym2151 => "YM2151",
+            Self::SegaPcm => "Sega PCM",
+            Self::Rf5c68 => "RF5C68",
+            Self::Ym2203 => "YM2203",
+            Self::Ym2608 => "YM2608",
+            Self::Ym2610 => "YM2610",
+            Self::Ym3812 => "YM3812",
+            Self::Ym3526 => "YM3526",
+            Self::Y8950 => "Y8950",
+            Self::Ymf262 => "YMF262",
+            Self::Ymf278b => "YMF278B",
+            Self::Ymf271 => "YMF271",
+            Self::Ymz280b => "YMZ280B",
+            Self::Rf5c164 => "RF5C164",
+            Self::Pwm => "PWM",
+            Self::Ay8910 => "AY-3-8910",
+            Self::GbDmg => "Game Boy DMG",
+            Self::NesApu => "NES APU",
+            Self::MultiPcm => "MultiPCM",
+            Self::Upd7759 => "uPD7759",
+            Self::Okim6258 => "OKIM6258",
+            Self::Okim6295 => "OKIM6295",
+            Self::K051649 => "K051649",
+            Self::K054539 => "K054539",
+            Self::HuC6280 => "HuC6280",
+            Self::C140 => "C140",
+            Self::K053260 => "K053260",
+            Self::Pokey => "POKEY",
+            Self::QSound => "QSound",
+            Self::Scsp => "SCSP",
+            Self::WonderSwan => "WonderSwan",
+            Self::Vsu => "VSU",
+            Self::Saa1099 => "SAA1099",
+            Self::Es5503 => "ES5503",
+            Self::Es5506 => "ES5506",
+            Self::X1010 => "X1-010",
+            Self::C352 => "C352",
+            Self::Ga20 => "GA20",
+        }
+    }
+}
+
+/// The GD3 tag's strings. Each has an English and a Japanese version
+/// except the date, ripper and notes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Gd3Tag {
+    pub track: String,
+    pub track_jp: String,
+    pub game: String,
+    pub game_jp: String,
+    pub system: String,
+    pub system_jp: String,
+    pub author: String,
+    pub author_jp: String,
+    pub release_date: String,
+    pub ripper: String,
+    pub notes: String,
+}
+
+/// A parsed VGM log.
+#[derive(Debug, Clone)]
+pub struct Vgm {
+    /// BCD version, e.g. `0x171` for 1.71.
+    pub version: u32,
+    /// Whether the file was gzip-compressed (.vgz).
+    pub compressed: bool,
+    /// Chips with a nonzero clock, in header order.
+    pub chips: Vec<VgmChip>,
+    /// Play length in samples at 44.1 kHz, excluding loops.
+    pub total_samples: u32,
+    /// Samples in the looped part, 0 if the log doesn't loop.
+    pub loop_samples: u32,
+    pub gd3: Option<Gd3Tag>,
+}
+
+impl Vgm {
+    /// Parse a whole, uncompressed VGM file.
+    pub fn parse(data: &[u8]) -> Result<Self, AnalysisError> {
+        let mut vgm = parse_header(data)?;
+        let gd3_offset = read_u32(data, 0x14);
+        if gd3_offset != 0 {
+            vgm.gd3 = Some(parse_gd3(data, 0x14 + gd3_offset as usize)?);
+        }
+        Ok(vgm)
+    }
+
+    /// Version as it's written, e.g. "1.71".
+    pub fn version_string(&self) -> String {
+        format!("{:x}.{:02x}", self.version >> 8, self.version & 0xFF)
+    }
+
+    pub fn has_chip(&self, chip: VgmChip) -> bool {
+        self.chips.contains(&chip)
+    }
+
+    /// The GD3 system name, if tagged.
+    pub fn system(&self) -> Option<&str> {
+        self.gd3
+            .as_ref()
+            .map(|t| t.system.as_str())
+            .filter(|s| !s.is_empty())
+    }
+
+    /// Tags and length as a [`MusicRip`].
+    pub fn to_music_rip(&self) -> MusicRip {
+        let mut rip = MusicRip::new(if self.compressed { "VGZ" } else { "VGM" });
+        let either = |en: &str, jp: &str| {
+            let s = if en.is_empty() { jp } else { en };
+            (!s.is_empty()).then(|| s.to_string())
+        };
+        if let Some(tag) = &self.gd3 {
+            rip.game = either(&tag.game, &tag.game_jp);
+            rip.title = either(&tag.track, &tag.track_jp);
+            rip.artist = either(&tag.author, &tag.author_jp);
+            rip.ripper = either(&tag.ripper, "");
+        }
+        rip.length_secs = Some(self.total_samples / SAMPLE_RATE);
+        rip.chips = self.chips.iter().map(VgmChip::name).collect();
+        rip
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    data.get(offset..offset + 4)
+        .map_or(0, |b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Parse the header, leaving the GD3 tag out.
+fn parse_header(data: &[u8]) -> Result<Vgm, AnalysisError> {
+    if data.len() < LEGACY_HEADER_LEN {
+        return Err(AnalysisError::TooSmall {
+            expected: LEGACY_HEADER_LEN as u64,
+            actual: data.len() as u64,
+        });
+    }
+    if &data[..4] != VGM_MAGIC {
+        return Err(AnalysisError::invalid_magic(0, VGM_MAGIC, &data[..4]));
+    }
+    let version = read_u32(data, 0x08);
+    let data_offset = read_u32(data, 0x34) as usize;
+    let header_len = if version >= 0x150 && data_offset != 0 {
+        (0x34 + data_offset).min(MAX_HEADER_LEN)
+    } else {
+        LEGACY_HEADER_LEN
+    };
+
+    let chips = CHIP_CLOCKS
+        .iter()
+        .filter(|&&(offset, chip)| {
+            let moved = matches!(chip, VgmChip::Ym2612 | VgmChip::Ym2151);
+            offset + 4 <= header_len && !(moved && version < 0x110)
+        })
+        // The top two bits flag dual chips and variants, not the clock
+        .filter(|&&(offset, _)| read_u32(data, offset) & 0x3FFF_FFFF != 0)
+        .map(|&(_, chip)| chip)
+        .collect();
+
+    Ok(Vgm {
+        version,
+        compressed: false,
+        chips,
+        total_samples: read_u32(data, 0x18),
+        loop_samples: read_u32(data, 0x20),
+        gd3: None,
+    })
+}
+
+fn parse_gd3(data: &[u8], offset: usize) -> Result<Gd3Tag, AnalysisError> {
+    let header = data
+        .get(offset..offset + 12)
+        .ok_or_else(|| AnalysisError::corrupted_header("GD3 tag is past the end of the file"))?;
+    if &header[..4] != GD3_MAGIC {
+        return Err(AnalysisError::corrupted_header("GD3 offset has no GD3 tag"));
+    }
+    let len = read_u32(header, 8) as usize;
+    let body = data
+        .get(offset + 12..)
+        .map(|b| &b[..len.min(b.len())])
+        .unwrap_or_default();
+    let units: Vec<u16> = body
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    let mut strings = units
+        .split(|&u| u == 0)
+        .map(|s| String::from_utf16_lossy(s).trim().to_string());
+    let mut next = || strings.next().unwrap_or_default();
+    Ok(Gd3Tag {
+        track: next(),
+        track_jp: next(),
+        game: next(),
+        game_jp: next(),
+        system: next(),
+        system_jp: next(),
+        author: next(),
+        author_jp: next(),
+        release_date: next(),
+        ripper: next(),
+        notes: next(),
+    })
+}
+
+/// Read a whole VGM or VGZ file, decompressing it if needed.
+pub fn read_vgm(reader: &mut dyn ReadSeek, limits: &ParseLimits) -> Result<Vgm, AnalysisError> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut magic = [0u8; 2];
+    reader.read_exact(&mut magic)?;
+    reader.seek(SeekFrom::Start(0))?;
+    let compressed = magic == GZIP_MAGIC;
+
+    let mut data = Vec::new();
+    let read = if compressed {
+        GzDecoder::new(&mut *reader)
+            .take(limits.max_alloc.saturating_add(1))
+            .read_to_end(&mut data)?
+    } else {
+        reader
+            .take(limits.max_alloc.saturating_add(1))
+            .read_to_end(&mut data)?
+    };
+    limits.check_alloc(read as u64, "VGM data")?;
+
+    let mut vgm = Vgm::parse(&data)?;
+    vgm.compressed = compressed;
+    Ok(vgm)
+}
+
+/// Chips a VGM or VGZ file uses, from the start of the file. `None` if it
+/// isn't one.
+pub fn chips_in_prefix(head: &[u8]) -> Option<Vec<VgmChip>> {
+    let header = if head.starts_with(&GZIP_MAGIC) {
+        let mut header = Vec::with_capacity(MAX_HEADER_LEN);
+        // A prefix cut mid-stream still yields what it has decoded
+        let _ = GzDecoder::new(head)
+            .take(MAX_HEADER_LEN as u64)
+            .read_to_end(&mut header);
+        header
+    } else {
+        head[..head.len().min(MAX_HEADER_LEN)].to_vec()
+    };
+    parse_header(&header).ok().map(|vgm| vgm.chips)
+}
+
+/// Analyze a VGM or VGZ file as `platform` music, if `belongs` accepts it.
+pub fn analyze(
+    reader: &mut dyn ReadSeek,
+    options: &AnalysisOptions,
+    platform: Platform,
+    belongs: impl FnOnce(&Vgm) -> bool,
+) -> Result<RomIdentification, AnalysisError> {
+    let file_size = retro_junk_core::util::file_size(reader)?;
+    let vgm = read_vgm(reader, &options.limits)?;
+    if !belongs(&vgm) {
+        return Err(AnalysisError::invalid_format(format!(
+            "VGM log is not {} music",
+            platform.display_name()
+        )));
+    }
+
+    let mut id = vgm.to_music_rip().to_identification(platform, file_size);
+    id.extra.insert("vgm_version".into(), vgm.version_string());
+    if let Some(system) = vgm.system() {
+        id.extra.insert("tagged_system".into(), system.into());
+    }
+    if let Some(date) = vgm.gd3.as_ref().map(|t| &t.release_date)
+        && !date.is_empty()
+    {
+        id.extra.insert("release_date".into(), date.clone());
+    }
+    id.extra.insert(
+        "loops".into(),
+        if vgm.loop_samples != 0 { "Yes" } else { "No" }.into(),
+    );
+    Ok(id)
+}
+
+#[cfg(test)]
+#[path = "tests/vgm_tests.rs"]
+mod tests;
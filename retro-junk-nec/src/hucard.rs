@@ -0,0 +1,143 @@
+//! HuCard layout shared by the PC Engine and SuperGrafx analyzers.
+//!
+//! HuCards have no header. Bank 0 (the first 8 KB) is mapped at `$E000` on
+//! power-up, so the 6280's reset vector sits at offset `0x1FFE` and points
+//! into `$E000–$FFFF`.
+//!
+//! TurboGrafx-16 cards wire the data bus in reverse bit order, which kept
+//! Japanese cards from running on US consoles. A raw dump of a US card
+//! therefore has every byte bit-reversed, and its reset vector only makes
+//! sense once the bits are flipped back. A US card dumped through a PC
+//! Engine adapter reads in normal order and looks Japanese; the DAT name
+//! settles the region for those.
+//!
+//! Dumps from old copiers carry a 512-byte header, recognizable because the
+//! file is 512 bytes past a multiple of 8 KB. No-Intro hashes ROMs without it.
+
+use retro_junk_core::ReadSeek;
+use std::io::SeekFrom;
+
+use retro_junk_core::{AnalysisError, Platform, Region, RomIdentification};
+
+/// Size of one HuCard bank.
+pub const BANK_SIZE: u64 = 8 * 1024;
+
+/// Size of a copier header.
+pub const COPIER_HEADER_SIZE: u64 = 512;
+
+/// Offset of the reset vector within bank 0.
+const RESET_VECTOR_OFFSET: u64 = 0x1FFE;
+
+/// Street Fighter II' uses a mapper for its 2.5 MB ROM; nothing else exceeds 1 MB.
+const MAX_ROM_SIZE: u64 = 2560 * 1024;
+
+/// Byte order of a HuCard dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Readable as-is: a PC Engine card, or a US card read through an adapter.
+    Normal,
+    /// Every byte bit-reversed: a raw TurboGrafx-16 card.
+    Reversed,
+}
+
+/// What could be read from a HuCard dump.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HuCardInfo {
+    /// Copier header size (0 or 512).
+    pub header_size: u64,
+    /// ROM size without the copier header.
+    pub rom_size: u64,
+    /// Reset vector, after undoing any bit reversal.
+    pub reset_vector: u16,
+    pub bit_order: BitOrder,
+}
+
+impl HuCardInfo {
+    /// Region implied by the bit order.
+    pub fn region(&self) -> Region {
+        match self.bit_order {
+            BitOrder::Normal => Region::Japan,
+            BitOrder::Reversed => Region::Usa,
+        }
+    }
+}
+
+/// Size of the copier header on a file of `file_size` bytes.
+pub fn copier_header_size(file_size: u64) -> u64 {
+    if file_size % BANK_SIZE == COPIER_HEADER_SIZE {
+        COPIER_HEADER_SIZE
+    } else {
+        0
+    }
+}
+
+/// Read the HuCard layout, or `None` if the reset vector doesn't point
+/// into bank 0 in either bit order.
+pub fn read_hucard(reader: &mut dyn ReadSeek) -> Result<Option<HuCardInfo>, AnalysisError> {
+    let file_size = retro_junk_core::util::file_size(reader)?;
+    let header_size = copier_header_size(file_size);
+    let rom_size = file_size - header_size;
+    if rom_size < BANK_SIZE {
+        return Err(AnalysisError::TooSmall {
+            expected: BANK_SIZE,
+            actual: rom_size,
+        });
+    }
+    if !rom_size.is_multiple_of(BANK_SIZE) || rom_size > MAX_ROM_SIZE {
+        return Ok(None);
+    }
+
+    reader.seek(SeekFrom::Start(header_size + RESET_VECTOR_OFFSET))?;
+    let mut vector = [0u8; 2];
+    reader.read_exact(&mut vector)?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    let normal = u16::from_le_bytes(vector);
+    let reversed = u16::from_le_bytes([vector[0].reverse_bits(), vector[1].reverse_bits()]);
+    let (reset_vector, bit_order) = if normal >= 0xE000 {
+        (normal, BitOrder::Normal)
+    } else if reversed >= 0xE000 {
+        (reversed, BitOrder::Reversed)
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(HuCardInfo {
+        header_size,
+        rom_size,
+        reset_vector,
+        bit_order,
+    }))
+}
+
+/// Build the identification shared by HuCard platforms.
+pub fn to_identification(
+    info: &HuCardInfo,
+    platform: Platform,
+    file_size: u64,
+) -> RomIdentification {
+    let mut id = RomIdentification::new()
+        .with_platform(platform)
+        .with_region(info.region());
+    id.file_size = Some(file_size);
+    id.extra
+        .insert("reset_vector".into(), format!("${:04X}", info.reset_vector));
+    id.extra.insert(
+        "bit_order".into(),
+        match info.bit_order {
+            BitOrder::Normal => "Normal".into(),
+            BitOrder::Reversed => "Reversed (TurboGrafx-16)".into(),
+        },
+    );
+    if info.header_size > 0 {
+        id.extra.insert(
+            "copier_header".into(),
+            format!("{} bytes", info.header_size),
+        );
+    }
+    id
+}
+
+#[cfg(test)]
+#[path = "tests/hucard_tests.rs"]
+mod tests;
@@ -0,0 +1,18 @@
+//! NEC console ROM analyzers.
+//!
+//! This crate provides ROM analysis implementations for NEC consoles:
+//!
+//! - PC Engine / TurboGrafx-16 (HuCard)
+//! - SuperGrafx (HuCard)
+//! - PC Engine CD / TurboGrafx-CD
+
+pub mod hucard;
+pub mod pc_engine;
+pub mod pc_engine_cd;
+pub mod supergrafx;
+#[cfg(any(test, feature = "testkit"))]
+pub mod synthetic;
+
+pub use pc_engine::PcEngineAnalyzer;
+pub use pc_engine_cd::PcEngineCdAnalyzer;
+pub use supergrafx::SuperGrafxAnalyzer;
@@ -0,0 +1,61 @@
+//! PC Engine / TurboGrafx-16 HuCard analyzer.
+//!
+//! Supports:
+//! - Headerless HuCard dumps (.pce)
+//! - Dumps with a 512-byte copier header
+//!
+//! See [`crate::hucard`] for the layout and how the region is read from
+//! the bit order.
+
+use retro_junk_core::ReadSeek;
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+
+use crate::hucard;
+
+/// Analyzer for PC Engine / TurboGrafx-16 HuCards.
+#[derive(Debug, Default)]
+pub struct PcEngineAnalyzer;
+
+impl RomAnalyzer for PcEngineAnalyzer {
+    fn analyze(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let info = hucard::read_hucard(reader)?
+            .ok_or_else(|| AnalysisError::invalid_format("No HuCard reset vector in bank 0"))?;
+        let mut id = hucard::to_identification(&info, Platform::PcEngine, file_size);
+        id.attach_raw_header(reader, info.header_size + 0x1FF0, 16, options)?;
+        Ok(id)
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::PcEngine
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["pce"]
+    }
+
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        hucard::read_hucard(reader).is_ok_and(|info| info.is_some())
+    }
+
+    fn dat_names(&self) -> &'static [&'static str] {
+        &["NEC - PC Engine - TurboGrafx-16"]
+    }
+
+    fn dat_header_size(
+        &self,
+        _reader: &mut dyn ReadSeek,
+        file_size: u64,
+    ) -> Result<u64, AnalysisError> {
+        Ok(hucard::copier_header_size(file_size))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/pc_engine_tests.rs"]
+mod tests;
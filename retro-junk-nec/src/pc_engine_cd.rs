@@ -0,0 +1,234 @@
+//! PC Engine CD / TurboGrafx-CD disc image analyzer.
+//!
+//! Supports:
+//! - BIN/CUE images (raw 2352 bytes/sector), one file per track or a
+//!   single file for the whole disc
+//! - ISO images of the data track (2048 bytes/sector)
+//! - CHD compressed images
+//!
+//! The first track is usually audio (a warning not to play the disc in a
+//! CD player), so the CUE sheet is read to find the first data track. The
+//! System Card boots a disc when the second sector of that track holds
+//! `PC Engine CD-ROM SYSTEM` at offset 0x20.
+
+use retro_junk_core::ReadSeek;
+use retro_junk_core::disc::cd::{CHD_MAGIC, RAW_SECTOR_SIZE, SYNC_PATTERN};
+use retro_junk_core::disc::cue::{self, CueTrack};
+use std::io::SeekFrom;
+use std::path::Path;
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+
+/// Boot signature in the second sector of the data track.
+pub(crate) const BOOT_SIGNATURE: &[u8] = b"PC Engine CD-ROM SYSTEM";
+
+/// Offset of [`BOOT_SIGNATURE`] within the sector's user data.
+pub(crate) const BOOT_SIGNATURE_OFFSET: u64 = 0x20;
+
+/// PC-FX discs carry the same boot sector with this marker in front.
+const PCFX_SIGNATURE: &[u8] = b"PC-FX:Hu_CD-ROM";
+
+/// Sector layout of a disc image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SectorFormat {
+    /// 2352-byte sectors with sync and header; user data at offset 16.
+    Raw,
+    /// 2048 bytes of user data per sector.
+    Cooked,
+}
+
+impl SectorFormat {
+    pub(crate) fn sector_size(&self) -> u64 {
+        match self {
+            Self::Raw => RAW_SECTOR_SIZE,
+            Self::Cooked => 2048,
+        }
+    }
+
+    pub(crate) fn data_offset(&self) -> u64 {
+        match self {
+            Self::Raw => 16,
+            Self::Cooked => 0,
+        }
+    }
+}
+
+/// Read sector `index` (counted from `base`) and return its user data.
+fn read_user_data(
+    reader: &mut dyn ReadSeek,
+    format: SectorFormat,
+    base: u64,
+    index: u64,
+) -> Result<[u8; 2048], AnalysisError> {
+    let mut buf = [0u8; 2048];
+    reader.seek(SeekFrom::Start(
+        base + index * format.sector_size() + format.data_offset(),
+    ))?;
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Whether the data track starting at byte `base` is a PC Engine CD.
+fn has_boot_sector(
+    reader: &mut dyn ReadSeek,
+    format: SectorFormat,
+    base: u64,
+) -> Result<bool, AnalysisError> {
+    let first = read_user_data(reader, format, base, 0)?;
+    if first.starts_with(PCFX_SIGNATURE) {
+        return Ok(false);
+    }
+    let second = read_user_data(reader, format, base, 1)?;
+    let at = BOOT_SIGNATURE_OFFSET as usize;
+    Ok(&second[at..at + BOOT_SIGNATURE.len()] == BOOT_SIGNATURE)
+}
+
+/// Sector format of a BIN or ISO, from the sync pattern.
+fn sector_format(reader: &mut dyn ReadSeek) -> Result<SectorFormat, AnalysisError> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut sync = [0u8; 12];
+    reader.read_exact(&mut sync)?;
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(if sync == SYNC_PATTERN {
+        SectorFormat::Raw
+    } else {
+        SectorFormat::Cooked
+    })
+}
+
+fn is_chd(reader: &mut dyn ReadSeek) -> Result<bool, AnalysisError> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut magic = [0u8; 8];
+    let found = reader.read_exact(&mut magic).is_ok() && &magic == CHD_MAGIC;
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(found)
+}
+
+/// Analyzer for PC Engine CD / TurboGrafx-CD disc images.
+#[derive(Debug, Default)]
+pub struct PcEngineCdAnalyzer;
+
+impl PcEngineCdAnalyzer {
+    /// Analyze a BIN or ISO that starts with the data track.
+    fn analyze_image(&self, reader: &mut dyn ReadSeek) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let format = sector_format(reader)?;
+        if !has_boot_sector(reader, format, 0)? {
+            return Err(AnalysisError::invalid_format(
+                "No PC Engine CD boot sector in the first track",
+            ));
+        }
+
+        let mut id = RomIdentification::new().with_platform(Platform::PcEngineCd);
+        id.file_size = Some(file_size);
+        let (name, ext) = match format {
+            SectorFormat::Raw => ("BIN (2352)", "bin"),
+            SectorFormat::Cooked => ("ISO (2048)", "iso"),
+        };
+        id.extra.insert("format".into(), name.into());
+        id.extra.insert("detected_extension".into(), ext.into());
+        Ok(id)
+    }
+
+    /// Analyze a CUE sheet, checking the boot sector of its first data
+    /// track when the referenced file can be opened.
+    fn analyze_cue(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let text = options.limits.read_text(reader, "CUE sheet")?;
+        let sheet = cue::parse(&text);
+        let tracks: Vec<&CueTrack> = sheet.tracks().collect();
+        if tracks.is_empty() {
+            return Err(AnalysisError::invalid_format("CUE sheet lists no tracks"));
+        }
+
+        let mut id = RomIdentification::new().with_platform(Platform::PcEngineCd);
+        id.file_size = Some(file_size);
+        id.extra.insert("format".into(), "CUE Sheet".into());
+        id.extra.insert("detected_extension".into(), "cue".into());
+        let data_tracks = tracks.iter().filter(|t| t.is_data()).count();
+        id.extra.insert("tracks".into(), tracks.len().to_string());
+        id.extra
+            .insert("data_tracks".into(), data_tracks.to_string());
+        id.extra.insert(
+            "audio_tracks".into(),
+            (tracks.len() - data_tracks).to_string(),
+        );
+
+        let data_track = sheet
+            .files
+            .iter()
+            .find_map(|f| f.tracks.iter().find(|t| t.is_data()).map(|t| (f, t)));
+        if let (Some((file, track)), Some(cue_path)) = (data_track, &options.file_path) {
+            id.extra
+                .insert("boot_track".into(), track.number.to_string());
+            let dir = cue_path.parent().unwrap_or(Path::new(""));
+            if let Ok(mut bin) = std::fs::File::open(dir.join(&file.filename)) {
+                let format = sector_format(&mut bin)?;
+                let base = track.start * format.sector_size();
+                if !has_boot_sector(&mut bin, format, base).unwrap_or(false) {
+                    return Err(AnalysisError::invalid_format(format!(
+                        "No PC Engine CD boot sector in track {}",
+                        track.number
+                    )));
+                }
+            }
+        }
+        Ok(id)
+    }
+}
+
+impl RomAnalyzer for PcEngineCdAnalyzer {
+    fn analyze(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        if is_chd(reader)? {
+            let mut id = RomIdentification::new().with_platform(Platform::PcEngineCd);
+            id.file_size = Some(retro_junk_core::util::file_size(reader)?);
+            id.extra.insert("format".into(), "CHD".into());
+            id.extra.insert("detected_extension".into(), "chd".into());
+            return Ok(id);
+        }
+        if cue::sniff(reader)? {
+            return self.analyze_cue(reader, options);
+        }
+        self.analyze_image(reader)
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::PcEngineCd
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["cue", "bin", "iso", "chd"]
+    }
+
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        // CUE and CHD can't be told apart from other discs without the
+        // track data, so only images with a boot sector are claimed
+        sector_format(reader)
+            .and_then(|format| has_boot_sector(reader, format, 0))
+            .unwrap_or(false)
+    }
+
+    fn dat_source(&self) -> retro_junk_core::DatSource {
+        retro_junk_core::DatSource::Redump
+    }
+
+    fn dat_lists_raw_cd_tracks(&self) -> bool {
+        true
+    }
+
+    fn dat_names(&self) -> &'static [&'static str] {
+        &["NEC - PC Engine CD & TurboGrafx CD"]
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/pc_engine_cd_tests.rs"]
+mod tests;
@@ -0,0 +1,63 @@
+//! SuperGrafx HuCard analyzer.
+//!
+//! Supports:
+//! - Headerless HuCard dumps (.sgx)
+//! - Dumps with a 512-byte copier header
+//!
+//! SuperGrafx cards use the PC Engine layout (see [`crate::hucard`]) and
+//! were only sold in Japan. Nothing in the ROM marks a SuperGrafx game, so
+//! the `.sgx` extension or a SuperGrafx folder tells them apart.
+
+use retro_junk_core::ReadSeek;
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+
+use crate::hucard;
+
+/// Analyzer for SuperGrafx HuCards.
+#[derive(Debug, Default)]
+pub struct SuperGrafxAnalyzer;
+
+impl RomAnalyzer for SuperGrafxAnalyzer {
+    fn analyze(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let info = hucard::read_hucard(reader)?
+            .ok_or_else(|| AnalysisError::invalid_format("No HuCard reset vector in bank 0"))?;
+        let mut id = hucard::to_identification(&info, Platform::SuperGrafx, file_size);
+        id.attach_raw_header(reader, info.header_size + 0x1FF0, 16, options)?;
+        Ok(id)
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::SuperGrafx
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["sgx"]
+    }
+
+    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
+        // Indistinguishable from a PC Engine card by content
+        false
+    }
+
+    fn dat_names(&self) -> &'static [&'static str] {
+        &["NEC - PC Engine SuperGrafx"]
+    }
+
+    fn dat_header_size(
+        &self,
+        _reader: &mut dyn ReadSeek,
+        file_size: u64,
+    ) -> Result<u64, AnalysisError> {
+        Ok(hucard::copier_header_size(file_size))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/supergrafx_tests.rs"]
+mod tests;
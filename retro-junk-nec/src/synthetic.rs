@@ -0,0 +1,55 @@
+//! Synthetic ROM images for NEC platforms.
+//!
+//! Each builder produces the smallest image its analyzer fully accepts.
+
+use retro_junk_core::disc::cd::SYNC_PATTERN;
+use retro_junk_core::{Platform, SyntheticRom};
+
+use crate::pc_engine_cd::{BOOT_SIGNATURE, BOOT_SIGNATURE_OFFSET, SectorFormat};
+
+/// The synthetic image for `platform`, if this crate can build one.
+pub fn synthesize(platform: Platform) -> Option<SyntheticRom> {
+    match platform {
+        Platform::PcEngine => Some(pc_engine()),
+        Platform::SuperGrafx => Some(supergrafx()),
+        Platform::PcEngineCd => Some(pc_engine_cd()),
+        _ => None,
+    }
+}
+
+/// HuCard of `size` bytes whose reset vector points at `$E000`.
+fn hucard(size: usize) -> Vec<u8> {
+    let mut data = vec![0u8; size];
+    data[0x1FFE..0x2000].copy_from_slice(&0xE000u16.to_le_bytes());
+    data
+}
+
+/// 128 KB Japanese HuCard.
+pub fn pc_engine() -> SyntheticRom {
+    SyntheticRom::new(Platform::PcEngine, "synthetic.pce", hucard(128 * 1024))
+}
+
+/// 1 MB HuCard. SuperGrafx cards are only told apart by extension.
+pub fn supergrafx() -> SyntheticRom {
+    SyntheticRom::new(Platform::SuperGrafx, "synthetic.sgx", hucard(1024 * 1024))
+}
+
+/// Cooked data track of four sectors carrying the boot signature.
+pub fn pc_engine_cd() -> SyntheticRom {
+    let track = make_track(SectorFormat::Cooked, 4);
+    SyntheticRom::new(Platform::PcEngineCd, "synthetic.iso", track)
+}
+
+/// Build a data track of `sectors` sectors with the boot signature.
+pub(crate) fn make_track(format: SectorFormat, sectors: u64) -> Vec<u8> {
+    let size = format.sector_size();
+    let mut data = vec![0u8; (size * sectors) as usize];
+    if format == SectorFormat::Raw {
+        for sector in 0..sectors as usize {
+            data[sector * size as usize..][..12].copy_from_slice(&SYNC_PATTERN);
+        }
+    }
+    let at = (size + format.data_offset() + BOOT_SIGNATURE_OFFSET) as usize;
+    data[at..at + BOOT_SIGNATURE.len()].copy_from_slice(BOOT_SIGNATURE);
+    data
+}
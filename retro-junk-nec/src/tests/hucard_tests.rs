@@ -0,0 +1,58 @@
+use super::*;
+use std::io::Cursor;
+
+const ROM_SIZE: usize = 256 * 1024;
+
+/// Build a HuCard ROM whose reset vector points at `$E010`.
+fn make_rom() -> Vec<u8> {
+    let mut data = vec![0xFFu8; ROM_SIZE];
+    data[0x1FFE] = 0x10;
+    data[0x1FFF] = 0xE0;
+    data
+}
+
+fn reverse(data: &mut [u8]) {
+    data.iter_mut().for_each(|b| *b = b.reverse_bits());
+}
+
+#[test]
+fn test_normal_order_is_japanese() {
+    let info = read_hucard(&mut Cursor::new(make_rom())).unwrap().unwrap();
+    assert_eq!(info.bit_order, BitOrder::Normal);
+    assert_eq!(info.reset_vector, 0xE010);
+    assert_eq!(info.region(), Region::Japan);
+    assert_eq!(info.header_size, 0);
+}
+
+#[test]
+fn test_reversed_order_is_turbografx() {
+    let mut data = make_rom();
+    reverse(&mut data);
+    let info = read_hucard(&mut Cursor::new(data)).unwrap().unwrap();
+    assert_eq!(info.bit_order, BitOrder::Reversed);
+    assert_eq!(info.reset_vector, 0xE010);
+    assert_eq!(info.region(), Region::Usa);
+}
+
+#[test]
+fn test_copier_header_is_skipped() {
+    let mut data = vec![0u8; COPIER_HEADER_SIZE as usize];
+    data.extend(make_rom());
+    let info = read_hucard(&mut Cursor::new(data)).unwrap().unwrap();
+    assert_eq!(info.header_size, COPIER_HEADER_SIZE);
+    assert_eq!(info.rom_size, ROM_SIZE as u64);
+}
+
+#[test]
+fn test_rejects_vector_outside_bank_zero() {
+    let mut data = make_rom();
+    data[0x1FFF] = 0x40;
+    assert!(read_hucard(&mut Cursor::new(data)).unwrap().is_none());
+}
+
+#[test]
+fn test_rejects_odd_size() {
+    let mut data = make_rom();
+    data.push(0);
+    assert!(read_hucard(&mut Cursor::new(data)).unwrap().is_none());
+}
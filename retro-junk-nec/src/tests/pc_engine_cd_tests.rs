@@ -0,0 +1,60 @@
+use super::*;
+use std::io::{Cursor, Write};
+
+use crate::synthetic::make_track;
+
+#[test]
+fn test_can_handle_raw_and_cooked_tracks() {
+    for format in [SectorFormat::Raw, SectorFormat::Cooked] {
+        let data = make_track(format, 4);
+        assert!(PcEngineCdAnalyzer.can_handle(&mut Cursor::new(data)));
+    }
+    assert!(!PcEngineCdAnalyzer.can_handle(&mut Cursor::new(vec![0u8; 2352 * 4])));
+}
+
+#[test]
+fn test_analyze_raw_bin() {
+    let id = PcEngineCdAnalyzer
+        .analyze(
+            &mut Cursor::new(make_track(SectorFormat::Raw, 4)),
+            &AnalysisOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::PcEngineCd));
+    assert_eq!(id.extra.get("detected_extension").unwrap(), "bin");
+}
+
+#[test]
+fn test_rejects_pcfx_disc() {
+    let mut data = make_track(SectorFormat::Cooked, 4);
+    data[..PCFX_SIGNATURE.len()].copy_from_slice(PCFX_SIGNATURE);
+    assert!(!PcEngineCdAnalyzer.can_handle(&mut Cursor::new(data)));
+}
+
+#[test]
+fn test_analyze_cue_checks_data_track() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Track 1.bin"), vec![0u8; 2352 * 2]).unwrap();
+    std::fs::write(
+        dir.path().join("Track 2.bin"),
+        make_track(SectorFormat::Raw, 4),
+    )
+    .unwrap();
+    let cue = dir.path().join("Game.cue");
+    let mut f = std::fs::File::create(&cue).unwrap();
+    write!(
+        f,
+        "FILE \"Track 1.bin\" BINARY\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\n\
+         FILE \"Track 2.bin\" BINARY\n  TRACK 02 MODE1/2352\n    INDEX 01 00:00:00\n"
+    )
+    .unwrap();
+    drop(f);
+
+    let options = AnalysisOptions::new().file_path(&cue);
+    let id = PcEngineCdAnalyzer
+        .analyze(&mut std::fs::File::open(&cue).unwrap(), &options)
+        .unwrap();
+    assert_eq!(id.extra.get("tracks").unwrap(), "2");
+    assert_eq!(id.extra.get("audio_tracks").unwrap(), "1");
+    assert_eq!(id.extra.get("boot_track").unwrap(), "2");
+}
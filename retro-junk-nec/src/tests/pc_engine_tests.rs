@@ -0,0 +1,40 @@
+use super::*;
+use std::io::Cursor;
+
+fn make_rom() -> Vec<u8> {
+    crate::synthetic::pc_engine().data
+}
+
+#[test]
+fn test_can_handle_hucard() {
+    assert!(PcEngineAnalyzer.can_handle(&mut Cursor::new(make_rom())));
+    assert!(!PcEngineAnalyzer.can_handle(&mut Cursor::new(vec![0u8; 128 * 1024])));
+}
+
+#[test]
+fn test_analyze_reports_region_and_vector() {
+    let id = PcEngineAnalyzer
+        .analyze(&mut Cursor::new(make_rom()), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::PcEngine));
+    assert_eq!(id.regions, vec![retro_junk_core::Region::Japan]);
+    assert_eq!(id.extra.get("reset_vector").unwrap(), "$E000");
+    assert_eq!(id.extra.get("bit_order").unwrap(), "Normal");
+}
+
+#[test]
+fn test_dat_header_size() {
+    let size = 128 * 1024 + 512;
+    assert_eq!(
+        PcEngineAnalyzer
+            .dat_header_size(&mut Cursor::new(vec![0u8; size]), size as u64)
+            .unwrap(),
+        512
+    );
+    assert_eq!(
+        PcEngineAnalyzer
+            .dat_header_size(&mut Cursor::new(make_rom()), 128 * 1024)
+            .unwrap(),
+        0
+    );
+}
@@ -0,0 +1,21 @@
+use super::*;
+use std::io::Cursor;
+
+#[test]
+fn test_analyze_supergrafx() {
+    let mut data = vec![0u8; 1024 * 1024];
+    data[0x1FFE] = 0x00;
+    data[0x1FFF] = 0xE0;
+    let id = SuperGrafxAnalyzer
+        .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::SuperGrafx));
+    assert_eq!(id.extra.get("reset_vector").unwrap(), "$E000");
+}
+
+#[test]
+fn test_not_claimed_by_content() {
+    let mut data = vec![0u8; 1024 * 1024];
+    data[0x1FFF] = 0xE0;
+    assert!(!SuperGrafxAnalyzer.can_handle(&mut Cursor::new(data)));
+}
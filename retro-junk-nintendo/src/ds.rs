@@ -12,9 +12,11 @@
 use retro_junk_core::ReadSeek;
 use std::io::SeekFrom;
 
+use std::collections::HashMap;
+
 use retro_junk_core::{
-    AnalysisError, AnalysisOptions, ChecksumAlgorithm, ExpectedChecksum, Platform, RomAnalyzer,
-    RomIdentification,
+    AnalysisError, AnalysisOptions, ChecksumAlgorithm, ExpectedChecksum, Language, Platform,
+    RomAnalyzer, RomIdentification,
 };
 
 // ---------------------------------------------------------------------------
@@ -39,7 +41,7 @@ use crate::constants::{NINTENDO_LOGO_156 as NINTENDO_LOGO, region_from_game_code
 // ---------------------------------------------------------------------------
 
 /// Compute CRC-16 used by the NDS header (polynomial 0x8005, reflected, init 0xFFFF).
-fn crc16(data: &[u8]) -> u16 {
+pub(crate) fn crc16(data: &[u8]) -> u16 {
     let mut crc: u16 = 0xFFFF;
     for &byte in data {
         crc ^= byte as u16;
@@ -109,7 +111,7 @@ fn parse_header(reader: &mut dyn ReadSeek) -> Result<NdsHeader, AnalysisError> {
                 actual: 0,
             }
         } else {
-            AnalysisError::Io(e)
+            AnalysisError::io_at(e, 0)
         }
     })?;
 
@@ -155,8 +157,81 @@ fn parse_header(reader: &mut dyn ReadSeek) -> Result<NdsHeader, AnalysisError> {
     })
 }
 
+// ---------------------------------------------------------------------------
+// Icon/title banner
+// ---------------------------------------------------------------------------
+
+/// Offset of the first title within the banner.
+const BANNER_TITLES_OFFSET: usize = 0x240;
+
+/// Size of each UTF-16LE title slot (128 characters).
+const BANNER_TITLE_SIZE: usize = 0x100;
+
+/// Title slot order. Version 1 banners have the first six; version 2 adds
+/// Chinese and version 3 adds Korean.
+const BANNER_LANGUAGES: [Language; 8] = [
+    Language::Japanese,
+    Language::English,
+    Language::French,
+    Language::German,
+    Language::Italian,
+    Language::Spanish,
+    Language::Chinese,
+    Language::Korean,
+];
+
+/// Read the localized titles from the icon/title banner.
+///
+/// Each title is up to three lines: name, optional subtitle, and publisher.
+/// The publisher line is dropped and the rest joined with a space. Errors
+/// (no banner, truncated file) yield an empty map.
+fn read_banner_titles(reader: &mut dyn ReadSeek, banner_offset: u32) -> HashMap<Language, String> {
+    let mut titles = HashMap::new();
+    if banner_offset == 0 {
+        return titles;
+    }
+    let mut version = [0u8; 2];
+    if reader.seek(SeekFrom::Start(banner_offset as u64)).is_err()
+        || reader.read_exact(&mut version).is_err()
+    {
+        return titles;
+    }
+    let count = match u16::from_le_bytes(version) & 0xFF {
+        1 => 6,
+        2 => 7,
+        3 => 8,
+        _ => return titles,
+    };
+
+    let mut buf = vec![0u8; BANNER_TITLE_SIZE * count];
+    let start = banner_offset as u64 + BANNER_TITLES_OFFSET as u64;
+    if reader.seek(SeekFrom::Start(start)).is_err() || reader.read_exact(&mut buf).is_err() {
+        return titles;
+    }
+    for (slot, language) in buf.chunks(BANNER_TITLE_SIZE).zip(BANNER_LANGUAGES) {
+        let units: Vec<u16> = slot
+            .chunks(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .take_while(|&u| u != 0)
+            .collect();
+        let text = String::from_utf16_lossy(&units);
+        let mut lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect();
+        if lines.len() > 1 {
+            lines.pop();
+        }
+        if !lines.is_empty() {
+            titles.insert(language, lines.join(" "));
+        }
+    }
+    titles
+}
+
 /// Compute the header CRC-16 over bytes 0x000–0x15D.
-fn compute_header_checksum(reader: &mut dyn ReadSeek) -> Result<u16, AnalysisError> {
+pub(crate) fn compute_header_checksum(reader: &mut dyn ReadSeek) -> Result<u16, AnalysisError> {
     reader.seek(SeekFrom::Start(0))?;
     let mut buf = [0u8; 0x15E]; // 0x000..=0x15D = 350 bytes
     reader.read_exact(&mut buf)?;
@@ -179,7 +254,8 @@ enum SecureAreaState {
 /// Magic bytes at 0x4000 that indicate a decrypted secure area dump.
 /// The BIOS overwrites the "encryObj" ID with 0xE7FFDEFF (an undefined ARM
 /// instruction) repeated twice. Stored little-endian in the file.
-const DECRYPTED_SECURE_AREA_MAGIC: [u8; 8] = [0xFF, 0xDE, 0xFF, 0xE7, 0xFF, 0xDE, 0xFF, 0xE7];
+pub(crate) const DECRYPTED_SECURE_AREA_MAGIC: [u8; 8] =
+    [0xFF, 0xDE, 0xFF, 0xE7, 0xFF, 0xDE, 0xFF, 0xE7];
 
 /// Detect the secure area state and optionally compute its CRC-16.
 /// The secure area is the 16 KB block at 0x4000–0x7FFF. The stored CRC at
@@ -517,12 +593,10 @@ impl RomAnalyzer for DsAnalyzer {
             SecureAreaState::Skipped
         };
 
-        Ok(to_identification(
-            &header,
-            file_size,
-            computed_header_checksum,
-            secure_area,
-        ))
+        let mut id = to_identification(&header, file_size, computed_header_checksum, secure_area);
+        id.titles = read_banner_titles(reader, header.icon_title_offset);
+        id.attach_raw_header(reader, 0, MIN_FILE_SIZE as usize, options)?;
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -576,6 +650,14 @@ impl RomAnalyzer for DsAnalyzer {
         ]
     }
 
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(r"^(NTR|TWL)-[A-Z0-9]{4}$")
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        retro_junk_core::serial::normalize_hyphenated(serial)
+    }
+
     fn expects_serial(&self) -> bool {
         true
     }
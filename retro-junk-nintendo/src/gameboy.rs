@@ -4,6 +4,7 @@
 //! - Game Boy ROMs (.gb)
 //! - Game Boy Color ROMs (.gbc)
 //! - Dual-mode ROMs (GB/GBC compatible)
+//! - GBS and Game Boy VGM music rips (.gbs, .vgm, .vgz)
 //!
 //! GB and GBC share the same header format at 0x0100-0x014F, differing only
 //! in the CGB flag byte at 0x0143. Detection uses the 48-byte Nintendo logo
@@ -30,7 +31,7 @@ const HEADER_START: u64 = 0x0100;
 
 /// Nintendo logo (48 bytes at 0x0104). Used for format detection.
 /// The boot ROM compares this against its internal copy.
-const NINTENDO_LOGO: [u8; 48] = [
+pub(crate) const NINTENDO_LOGO: [u8; 48] = [
     0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
     0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
     0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
@@ -73,7 +74,7 @@ fn parse_header(reader: &mut dyn ReadSeek) -> Result<GbHeader, AnalysisError> {
                 actual: 0,
             }
         } else {
-            AnalysisError::Io(e)
+            AnalysisError::io_at(e, HEADER_START)
         }
     })?;
 
@@ -205,7 +206,7 @@ fn ram_size(code: u8) -> Option<u64> {
 
 /// Compute the header checksum (verified by boot ROM).
 /// Sum bytes 0x0134 through 0x014C using: x = x - byte - 1 (wrapping).
-fn compute_header_checksum(reader: &mut dyn ReadSeek) -> Result<u8, AnalysisError> {
+pub(crate) fn compute_header_checksum(reader: &mut dyn ReadSeek) -> Result<u8, AnalysisError> {
     reader.seek(SeekFrom::Start(0x0134))?;
     let mut buf = [0u8; 25]; // 0x0134..=0x014C = 25 bytes
     reader.read_exact(&mut buf)?;
@@ -218,7 +219,7 @@ fn compute_header_checksum(reader: &mut dyn ReadSeek) -> Result<u8, AnalysisErro
 }
 
 /// Compute the global checksum (sum of all bytes in file except 0x014E-0x014F).
-fn compute_global_checksum(reader: &mut dyn ReadSeek) -> Result<u16, AnalysisError> {
+pub(crate) fn compute_global_checksum(reader: &mut dyn ReadSeek) -> Result<u16, AnalysisError> {
     retro_junk_core::util::file_size(reader)?; // seek to end and back to start
 
     let mut sum: u16 = 0;
@@ -387,7 +388,7 @@ impl RomAnalyzer for GameBoyAnalyzer {
     fn analyze(
         &self,
         reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
         let file_size = retro_junk_core::util::file_size(reader)?;
 
@@ -402,12 +403,14 @@ impl RomAnalyzer for GameBoyAnalyzer {
         let computed_header = compute_header_checksum(reader)?;
         let computed_global = compute_global_checksum(reader)?;
 
-        Ok(to_identification(
-            &header,
-            file_size,
-            computed_header,
-            computed_global,
-        ))
+        let mut id = to_identification(&header, file_size, computed_header, computed_global);
+        id.attach_raw_header(
+            reader,
+            HEADER_START,
+            (MIN_FILE_SIZE - HEADER_START) as usize,
+            options,
+        )?;
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -440,6 +443,18 @@ impl RomAnalyzer for GameBoyAnalyzer {
         logo == NINTENDO_LOGO
     }
 
+    fn music_extensions(&self) -> &'static [&'static str] {
+        crate::music::GAME_BOY_EXTENSIONS
+    }
+
+    fn analyze_music(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        crate::music::analyze(reader, options, Platform::GameBoy)
+    }
+
     fn dat_names(&self) -> &'static [&'static str] {
         &["Nintendo - Game Boy", "Nintendo - Game Boy Color"]
     }
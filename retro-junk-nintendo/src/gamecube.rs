@@ -24,9 +24,6 @@ use retro_junk_core::{
 
 use crate::nintendo_disc;
 
-/// Standard GameCube disc size: 1,459,978,240 bytes (1.4 GB mini-DVD).
-const GCM_DISC_SIZE: u64 = 1_459_978_240;
-
 /// Analyzer for Nintendo GameCube disc images.
 #[derive(Debug, Default)]
 pub struct GameCubeAnalyzer;
@@ -40,14 +37,11 @@ impl RomAnalyzer for GameCubeAnalyzer {
         let file_size = retro_junk_core::util::file_size(reader)?;
 
         // Detect compressed container (RVZ, WIA, WBFS, CISO, GCZ) or raw ISO
-        let (header, format_name) = if nintendo_disc::is_compressed_disc(reader) {
-            let path = options.file_path.as_ref().ok_or_else(|| {
-                AnalysisError::invalid_format(
-                    "Compressed disc format detected but no file path provided",
-                )
-            })?;
-            let (header, format_name, _disc_size) = nintendo_disc::open_compressed_disc(path)?;
-            (header, format_name)
+        let compressed = nintendo_disc::is_compressed_disc(reader);
+        let (header, format_name) = if compressed {
+            let container =
+                nintendo_disc::read_compressed_disc_header(reader, options.file_path.as_deref())?;
+            (container.header, container.format)
         } else {
             (nintendo_disc::parse_disc_header(reader)?, "ISO")
         };
@@ -60,12 +54,23 @@ impl RomAnalyzer for GameCubeAnalyzer {
 
         let mut id = nintendo_disc::build_identification(&header, Platform::GameCube);
         id.file_size = Some(file_size);
-        id.expected_size = Some(GCM_DISC_SIZE);
+        id.expected_size = Some(nintendo_disc::GC_DISC_SIZE);
         id.extra.insert("format".into(), format_name.into());
         id.extra.insert(
             "detected_extension".into(),
             format_name.to_ascii_lowercase(),
         );
+        nintendo_disc::insert_nkit_info(&mut id, reader, compressed, options.file_path.as_deref());
+        if !options.quick {
+            let scrubbed = match &options.file_path {
+                Some(path) if compressed => nintendo_disc::lossy_container_reason(path),
+                _ if compressed => None,
+                _ => nintendo_disc::scrub_reason(reader, &header),
+            };
+            if let Some(reason) = scrubbed {
+                id.extra.insert("scrubbed".into(), reason);
+            }
+        }
 
         Ok(id)
     }
@@ -75,7 +80,7 @@ impl RomAnalyzer for GameCubeAnalyzer {
     }
 
     fn file_extensions(&self) -> &'static [&'static str] {
-        &["iso", "gcm", "rvz", "ciso", "gcz"]
+        &["iso", "gcm", "rvz", "wia", "ciso", "gcz"]
     }
 
     fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
@@ -103,6 +108,25 @@ impl RomAnalyzer for GameCubeAnalyzer {
         Ok(Some(hashes))
     }
 
+    fn recorded_dat_hashes(
+        &self,
+        reader: &mut dyn ReadSeek,
+        file_path: Option<&Path>,
+    ) -> Option<FileHashes> {
+        if !nintendo_disc::is_compressed_disc(reader) {
+            return None;
+        }
+        nintendo_disc::recorded_disc_hashes(file_path?)
+    }
+
+    fn dat_mismatch_reason(
+        &self,
+        reader: &mut dyn ReadSeek,
+        file_path: Option<&Path>,
+    ) -> Option<String> {
+        nintendo_disc::redump_mismatch_reason(reader, file_path)
+    }
+
     fn dat_source(&self) -> retro_junk_core::DatSource {
         retro_junk_core::DatSource::Redump
     }
@@ -111,6 +135,10 @@ impl RomAnalyzer for GameCubeAnalyzer {
         &["Nintendo - GameCube"]
     }
 
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(nintendo_disc::GAME_CODE_PATTERN)
+    }
+
     fn expects_serial(&self) -> bool {
         true
     }
@@ -27,7 +27,7 @@ const MIN_FILE_SIZE: u64 = 0xC0;
 const MAX_ROM_SIZE: u64 = 32 * 1024 * 1024;
 
 /// Fixed value that must appear at offset 0xB2.
-const FIXED_VALUE: u8 = 0x96;
+pub(crate) const FIXED_VALUE: u8 = 0x96;
 
 /// Nintendo compressed logo bitmap (156 bytes at offset 0x04).
 use crate::constants::{NINTENDO_LOGO_156 as NINTENDO_LOGO, region_from_game_code};
@@ -65,7 +65,7 @@ fn parse_header(reader: &mut dyn ReadSeek) -> Result<GbaHeader, AnalysisError> {
                 actual: 0,
             }
         } else {
-            AnalysisError::Io(e)
+            AnalysisError::io_at(e, 0)
         }
     })?;
 
@@ -105,7 +105,7 @@ fn parse_header(reader: &mut dyn ReadSeek) -> Result<GbaHeader, AnalysisError> {
 
 /// Compute the GBA header complement checksum.
 /// Sum bytes 0xA0–0xBC, then negate and subtract 0x19.
-fn compute_header_checksum(reader: &mut dyn ReadSeek) -> Result<u8, AnalysisError> {
+pub(crate) fn compute_header_checksum(reader: &mut dyn ReadSeek) -> Result<u8, AnalysisError> {
     reader.seek(SeekFrom::Start(0xA0))?;
     let mut buf = [0u8; 29]; // 0xA0..=0xBC = 29 bytes
     reader.read_exact(&mut buf)?;
@@ -294,12 +294,9 @@ impl RomAnalyzer for GbaAnalyzer {
             detect_save_type(reader)?
         };
 
-        Ok(to_identification(
-            &header,
-            file_size,
-            computed_checksum,
-            save_type,
-        ))
+        let mut id = to_identification(&header, file_size, computed_checksum, save_type);
+        id.attach_raw_header(reader, 0, MIN_FILE_SIZE as usize, options)?;
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -348,6 +345,14 @@ impl RomAnalyzer for GbaAnalyzer {
         &["Nintendo - Game Boy Advance"]
     }
 
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(r"^AGB-[A-Z0-9]{4}$")
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        retro_junk_core::serial::normalize_hyphenated(serial)
+    }
+
     fn expects_serial(&self) -> bool {
         true
     }
@@ -12,6 +12,10 @@
 //! - Game Boy Advance
 //! - Nintendo DS
 //! - Nintendo 3DS
+//!
+//! Music rips of these consoles' games (NSF, SPC, GBS, and VGM logs of
+//! their sound chips) are identified by the matching analyzer's
+//! `analyze_music`, apart from its ROMs.
 
 pub(crate) mod constants;
 pub mod ds;
@@ -19,14 +23,19 @@ pub mod gameboy;
 pub mod gamecube;
 pub mod gba;
 pub(crate) mod licensee;
+pub(crate) mod music;
 pub mod n3ds;
 pub mod n64;
 pub(crate) mod n64_byteorder;
 pub mod nes;
 pub(crate) mod nintendo_disc;
 pub mod snes;
+#[cfg(any(test, feature = "testkit"))]
+pub mod synthetic;
+pub(crate) mod tmd;
 pub mod wii;
 pub mod wiiu;
+pub(crate) mod wux;
 
 pub use ds::DsAnalyzer;
 pub use gameboy::GameBoyAnalyzer;
@@ -0,0 +1,235 @@
+//! Nintendo console music rips.
+//!
+//! Supports:
+//! - NSF: NES/Famicom sound driver and music data (.nsf)
+//! - GBS: Game Boy sound driver and music data (.gbs)
+//! - SPC: SNES audio RAM snapshot with an ID666 tag (.spc)
+//!
+//! NSF and GBS files hold the game's own sound code, with a header giving
+//! the track count and the game, artist and copyright tags. An SPC file is
+//! the SPC700's 64 KB RAM and DSP registers captured while one song plays;
+//! its ID666 tag comes in a text and a binary layout that differ only from
+//! the length field on, and nothing in the file says which one is used.
+//!
+//! VGM logs of the NES APU or the Game Boy's sound hardware are claimed by
+//! those consoles too. Rips are read through each console's
+//! [`RomAnalyzer::analyze_music`](retro_junk_core::RomAnalyzer::analyze_music).
+
+use std::io::SeekFrom;
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, ReadSeek, RomIdentification};
+use retro_junk_music::vgm::{self, VgmChip};
+use retro_junk_music::{MusicRip, tag_text};
+
+/// Music rip extensions of each console.
+pub(crate) const NES_EXTENSIONS: &[&str] = &["nsf", "vgm", "vgz"];
+pub(crate) const GAME_BOY_EXTENSIONS: &[&str] = &["gbs", "vgm", "vgz"];
+pub(crate) const SNES_EXTENSIONS: &[&str] = &["spc"];
+
+/// NSF magic: "NESM" and an end-of-file marker.
+const NSF_MAGIC: &[u8; 5] = b"NESM\x1A";
+
+/// GBS magic.
+const GBS_MAGIC: &[u8; 3] = b"GBS";
+
+/// SPC magic, followed by a version such as "v0.30".
+const SPC_MAGIC: &[u8; 27] = b"SNES-SPC700 Sound File Data";
+
+const NSF_HEADER_LEN: usize = 0x80;
+const GBS_HEADER_LEN: usize = 0x70;
+/// Header and ID666 tag, up to the start of the SPC700 RAM.
+const SPC_HEADER_LEN: usize = 0x100;
+
+/// SPC byte 0x23 when an ID666 tag is present.
+const SPC_HAS_TAG: u8 = 0x1A;
+
+/// NSF expansion sound chips, by bit of header byte 0x7B.
+const NSF_EXPANSION_CHIPS: &[(u8, &str)] = &[
+    (0x01, "Konami VRC6"),
+    (0x02, "Konami VRC7"),
+    (0x04, "Famicom Disk System"),
+    (0x08, "Nintendo MMC5"),
+    (0x10, "Namco 163"),
+    (0x20, "Sunsoft 5B"),
+    (0x40, "VT02+"),
+];
+
+/// A console's native rip format.
+struct RipFormat {
+    magic: &'static [u8],
+    header_len: usize,
+    identify: fn(&mut dyn ReadSeek, u64) -> Result<RomIdentification, AnalysisError>,
+}
+
+fn native_format(platform: Platform) -> Option<RipFormat> {
+    let (magic, header_len, identify): (&'static [u8], _, _) = match platform {
+        Platform::Nes => (NSF_MAGIC, NSF_HEADER_LEN, identify_nsf as _),
+        Platform::GameBoy => (GBS_MAGIC, GBS_HEADER_LEN, identify_gbs as _),
+        Platform::Snes => (SPC_MAGIC, SPC_HEADER_LEN, identify_spc as _),
+        _ => return None,
+    };
+    Some(RipFormat {
+        magic,
+        header_len,
+        identify,
+    })
+}
+
+/// The sound chip that makes a VGM log `platform` music.
+fn vgm_chip(platform: Platform) -> Option<VgmChip> {
+    match platform {
+        Platform::Nes => Some(VgmChip::NesApu),
+        Platform::GameBoy => Some(VgmChip::GbDmg),
+        _ => None,
+    }
+}
+
+/// Analyze the file as a `platform` music rip.
+pub(crate) fn analyze(
+    reader: &mut dyn ReadSeek,
+    options: &AnalysisOptions,
+    platform: Platform,
+) -> Result<RomIdentification, AnalysisError> {
+    if let Some(format) = native_format(platform)
+        && has_magic(reader, format.magic)
+    {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let mut id = (format.identify)(reader, file_size)?;
+        id.attach_raw_header(reader, 0, format.header_len, options)?;
+        return Ok(id);
+    }
+    match vgm_chip(platform) {
+        Some(chip) => vgm::analyze(reader, options, platform, |v| v.has_chip(chip)),
+        None => Err(AnalysisError::invalid_format(format!(
+            "Not a {} music rip",
+            platform.display_name()
+        ))),
+    }
+}
+
+/// Whether the file starts with `magic`. The reader is left at the start.
+fn has_magic(reader: &mut dyn ReadSeek, magic: &[u8]) -> bool {
+    let mut buf = vec![0u8; magic.len()];
+    let found = reader.seek(SeekFrom::Start(0)).is_ok()
+        && reader.read_exact(&mut buf).is_ok()
+        && buf == magic;
+    let _ = reader.seek(SeekFrom::Start(0));
+    found
+}
+
+fn read_header<const N: usize>(
+    reader: &mut dyn ReadSeek,
+    magic: &[u8],
+    format: &str,
+) -> Result<[u8; N], AnalysisError> {
+    let mut buf = [0u8; N];
+    reader.seek(SeekFrom::Start(0))?;
+    reader.read_exact(&mut buf).map_err(|_| {
+        AnalysisError::invalid_format(format!("{format} file is shorter than its header"))
+    })?;
+    if &buf[..magic.len()] != magic {
+        return Err(AnalysisError::invalid_format(format!(
+            "Missing {format} magic"
+        )));
+    }
+    Ok(buf)
+}
+
+/// Video standards an NSF plays at, from header byte 0x7A.
+fn nsf_video(flags: u8) -> &'static str {
+    match flags & 0x03 {
+        0x00 => "NTSC",
+        0x01 => "PAL",
+        _ => "NTSC/PAL",
+    }
+}
+
+/// Identify an NSF file from its header.
+fn identify_nsf(
+    reader: &mut dyn ReadSeek,
+    file_size: u64,
+) -> Result<RomIdentification, AnalysisError> {
+    let buf: [u8; NSF_HEADER_LEN] = read_header(reader, NSF_MAGIC, "NSF")?;
+    let mut rip = MusicRip::new("NSF");
+    rip.tracks = Some(u32::from(buf[0x06]));
+    rip.game = tag_text(&buf[0x0E..0x2E]);
+    rip.artist = tag_text(&buf[0x2E..0x4E]);
+    rip.copyright = tag_text(&buf[0x4E..0x6E]);
+    rip.chips = NSF_EXPANSION_CHIPS
+        .iter()
+        .filter(|&&(bit, _)| buf[0x7B] & bit != 0)
+        .map(|&(_, name)| name)
+        .collect();
+
+    let mut id = rip.to_identification(Platform::Nes, file_size);
+    id.extra.insert("nsf_version".into(), buf[0x05].to_string());
+    id.extra.insert("first_track".into(), buf[0x07].to_string());
+    id.extra.insert("video".into(), nsf_video(buf[0x7A]).into());
+    Ok(id)
+}
+
+/// Identify a GBS file from its header.
+fn identify_gbs(
+    reader: &mut dyn ReadSeek,
+    file_size: u64,
+) -> Result<RomIdentification, AnalysisError> {
+    let buf: [u8; GBS_HEADER_LEN] = read_header(reader, GBS_MAGIC, "GBS")?;
+    if buf[0x03] != 1 {
+        return Err(AnalysisError::unsupported(format!(
+            "GBS version {}",
+            buf[0x03]
+        )));
+    }
+    let mut rip = MusicRip::new("GBS");
+    rip.tracks = Some(u32::from(buf[0x04]));
+    rip.game = tag_text(&buf[0x10..0x30]);
+    rip.artist = tag_text(&buf[0x30..0x50]);
+    rip.copyright = tag_text(&buf[0x50..0x70]);
+
+    let mut id = rip.to_identification(Platform::GameBoy, file_size);
+    id.extra.insert("first_track".into(), buf[0x05].to_string());
+    Ok(id)
+}
+
+/// Whether an ID666 tag uses the text layout: the dump date, play length
+/// and fade length are then all digits, slashes or padding.
+fn id666_is_text(buf: &[u8]) -> bool {
+    buf[0x9E..0xB1]
+        .iter()
+        .all(|&b| b.is_ascii_digit() || b == b'/' || b == 0)
+        && buf[0xA9..0xAC].iter().any(|&b| b != 0)
+}
+
+/// Identify an SPC file from its ID666 tag.
+fn identify_spc(
+    reader: &mut dyn ReadSeek,
+    file_size: u64,
+) -> Result<RomIdentification, AnalysisError> {
+    let buf: [u8; SPC_HEADER_LEN] = read_header(reader, SPC_MAGIC, "SPC")?;
+    let mut rip = MusicRip::new("SPC");
+    if buf[0x23] != SPC_HAS_TAG {
+        return Ok(rip.to_identification(Platform::Snes, file_size));
+    }
+
+    rip.title = tag_text(&buf[0x2E..0x4E]);
+    rip.game = tag_text(&buf[0x4E..0x6E]);
+    rip.ripper = tag_text(&buf[0x6E..0x7E]);
+    let length_secs = if id666_is_text(&buf) {
+        rip.artist = tag_text(&buf[0xB1..0xD1]);
+        tag_text(&buf[0xA9..0xAC]).and_then(|s| s.parse().ok())
+    } else {
+        rip.artist = tag_text(&buf[0xB0..0xD0]);
+        Some(u32::from_le_bytes([buf[0xA9], buf[0xAA], buf[0xAB], 0]))
+    };
+    rip.length_secs = length_secs.filter(|&secs| secs != 0);
+
+    let mut id = rip.to_identification(Platform::Snes, file_size);
+    if let Some(comment) = tag_text(&buf[0x7E..0x9E]) {
+        id.extra.insert("comment".into(), comment);
+    }
+    Ok(id)
+}
+
+#[cfg(test)]
+#[path = "tests/music_tests.rs"]
+mod tests;
@@ -9,8 +9,9 @@ use retro_junk_core::{
 };
 
 use super::common::*;
-use super::ncch::parse_ncch_header;
+use super::ncch::{apply_title_details, parse_ncch_header};
 use super::{CIA_HEADER_SIZE, MEDIA_UNIT};
+use crate::tmd::{parse_tmd, signature_block_size};
 
 // ---------------------------------------------------------------------------
 // CIA header
@@ -50,75 +51,20 @@ pub(crate) fn parse_cia_header(reader: &mut dyn ReadSeek) -> Result<CiaHeader, A
 }
 
 // ---------------------------------------------------------------------------
-// TMD parsing
+// Ticket parsing
 // ---------------------------------------------------------------------------
 
-/// Information extracted from the CIA TMD.
-pub(crate) struct CiaTmdInfo {
-    pub(crate) title_id: u64,
-    pub(crate) title_version: u16,
-    pub(crate) content_count: u16,
-}
-
-/// Determine the size of a TMD/Ticket signature block based on signature type.
-fn signature_block_size(sig_type: u32) -> Option<usize> {
-    match sig_type {
-        0x00010003 => Some(4 + 0x200 + 0x3C), // RSA-4096: type(4) + sig(512) + pad(60)
-        0x00010004 => Some(4 + 0x100 + 0x3C), // RSA-2048: type(4) + sig(256) + pad(60)
-        0x00010005 => Some(4 + 0x3C + 0x40),  // ECDSA: type(4) + sig(60) + pad(64)
-        _ => None,
-    }
-}
-
-/// Parse title information from the CIA's TMD section.
-pub(crate) fn parse_cia_tmd(
-    reader: &mut dyn ReadSeek,
-    tmd_offset: u64,
-    tmd_size: u32,
-) -> Result<CiaTmdInfo, AnalysisError> {
-    if tmd_size < 8 {
-        return Err(AnalysisError::corrupted_header("TMD too small"));
-    }
-
-    // Read signature type to determine header offset
-    reader.seek(SeekFrom::Start(tmd_offset))?;
-    let mut sig_type_buf = [0u8; 4];
-    reader.read_exact(&mut sig_type_buf)?;
-    let sig_type = read_u32_be(&sig_type_buf, 0);
-
-    let sig_block_size = signature_block_size(sig_type).ok_or_else(|| {
-        AnalysisError::invalid_format(format!("Unknown TMD signature type: 0x{:08X}", sig_type))
-    })?;
-
-    // TMD header starts after signature block
-    let tmd_header_offset = tmd_offset + sig_block_size as u64;
-    reader.seek(SeekFrom::Start(tmd_header_offset))?;
-
-    let mut tmd_buf = [0u8; 0xC4];
-    reader
-        .read_exact(&mut tmd_buf)
-        .map_err(|_| AnalysisError::corrupted_header("TMD header truncated"))?;
-
-    let title_id = read_u64_be(&tmd_buf, 0x4C);
-    let title_version = read_u16_be(&tmd_buf, 0x9C);
-    let content_count = read_u16_be(&tmd_buf, 0x9E);
-
-    Ok(CiaTmdInfo {
-        title_id,
-        title_version,
-        content_count,
-    })
+/// Title ID and title version from a CIA's Ticket.
+struct TicketInfo {
+    title_id: u64,
+    title_version: u16,
 }
 
-// ---------------------------------------------------------------------------
-// Ticket parsing
-// ---------------------------------------------------------------------------
-
-/// Parse title ID from the CIA's Ticket section.
-fn parse_cia_ticket_title_id(
+/// Parse the title ID and version from the CIA's Ticket section.
+fn parse_cia_ticket(
     reader: &mut dyn ReadSeek,
     ticket_offset: u64,
-) -> Result<u64, AnalysisError> {
+) -> Result<TicketInfo, AnalysisError> {
     reader.seek(SeekFrom::Start(ticket_offset))?;
     let mut sig_type_buf = [0u8; 4];
     reader.read_exact(&mut sig_type_buf)?;
@@ -129,10 +75,14 @@ fn parse_cia_ticket_title_id(
     })?;
 
     let ticket_data_offset = ticket_offset + sig_block_size as u64;
+    // Title ID at 0x9C, ticket title version at 0xA6
     reader.seek(SeekFrom::Start(ticket_data_offset + 0x9C))?;
-    let mut tid_buf = [0u8; 8];
-    reader.read_exact(&mut tid_buf)?;
-    Ok(read_u64_be(&tid_buf, 0))
+    let mut buf = [0u8; 0x0C];
+    reader.read_exact(&mut buf)?;
+    Ok(TicketInfo {
+        title_id: read_u64_be(&buf, 0),
+        title_version: read_u16_be(&buf, 0x0A),
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -199,7 +149,7 @@ pub(crate) fn analyze_cia(
 
     // Parse TMD for title info
     let tmd_offset = cia_tmd_offset(&cia);
-    let tmd_info = parse_cia_tmd(reader, tmd_offset, cia.tmd_size)?;
+    let tmd_info = parse_tmd(reader, tmd_offset, cia.tmd_size as u64)?;
 
     // Title ID
     if tmd_info.title_id != 0 {
@@ -209,14 +159,14 @@ pub(crate) fn analyze_cia(
             "title_type".into(),
             title_type_from_id(tmd_info.title_id).into(),
         );
+        if let Some(content_type) = content_type_from_id(tmd_info.title_id) {
+            id.extra.insert("content_type".into(), content_type.into());
+        }
     }
 
     // Title version
     if tmd_info.title_version > 0 {
-        let major = tmd_info.title_version >> 10;
-        let minor = (tmd_info.title_version >> 4) & 0x3F;
-        let micro = tmd_info.title_version & 0xF;
-        id.version = Some(format!("v{}.{}.{}", major, minor, micro));
+        id.version = Some(format_title_version(tmd_info.title_version));
         id.extra.insert(
             "title_version_raw".into(),
             format!("{}", tmd_info.title_version),
@@ -231,14 +181,19 @@ pub(crate) fn analyze_cia(
         format!("{}", tmd_info.content_count),
     );
 
-    // Parse ticket for title ID (cross-reference)
+    // Cross-reference the ticket's title ID and version with the TMD's
     let ticket_offset = cia_ticket_offset(&cia);
-    if let Ok(ticket_tid) = parse_cia_ticket_title_id(reader, ticket_offset)
-        && ticket_tid != tmd_info.title_id
-        && ticket_tid != 0
-    {
-        id.extra
-            .insert("ticket_title_id".into(), format_title_id(ticket_tid));
+    if let Ok(ticket) = parse_cia_ticket(reader, ticket_offset) {
+        if ticket.title_id != tmd_info.title_id && ticket.title_id != 0 {
+            id.extra
+                .insert("ticket_title_id".into(), format_title_id(ticket.title_id));
+        }
+        if ticket.title_version != tmd_info.title_version {
+            id.extra.insert(
+                "ticket_title_version".into(),
+                format_title_version(ticket.title_version),
+            );
+        }
     }
 
     // Try to parse NCCH from content section
@@ -272,7 +227,7 @@ pub(crate) fn analyze_cia(
 
         // Content type
         id.extra.insert(
-            "content_type".into(),
+            "ncch_content_type".into(),
             content_type_description(ncch.content_type_flags).into(),
         );
 
@@ -317,6 +272,9 @@ pub(crate) fn analyze_cia(
             );
         }
 
+        // New 3DS exclusivity, region lockout and localized titles
+        apply_title_details(reader, content_offset, &ncch, &mut id)?;
+
         // SHA-256 verification for unencrypted content (not quick mode)
         if !options.quick && ncch.no_crypto && ncch.exheader_size > 0 {
             let exheader_offset = content_offset + 0x200;
@@ -164,6 +164,14 @@ pub(crate) fn format_title_id(tid: u64) -> String {
     format!("{:08X}{:08X}", high, low)
 }
 
+/// Format a title version as `vMAJOR.MINOR.MICRO` (6, 6 and 4 bits).
+pub(crate) fn format_title_version(version: u16) -> String {
+    let major = version >> 10;
+    let minor = (version >> 4) & 0x3F;
+    let micro = version & 0xF;
+    format!("v{}.{}.{}", major, minor, micro)
+}
+
 /// Extract the title type from the high 32 bits of a title ID.
 pub(crate) fn title_type_from_id(tid: u64) -> &'static str {
     let high = (tid >> 32) as u32;
@@ -180,6 +188,18 @@ pub(crate) fn title_type_from_id(tid: u64) -> &'static str {
     }
 }
 
+/// Classify a title as a base game, update or DLC from the high 32 bits of
+/// its title ID, for `extra["content_type"]`. Other title types (system
+/// titles, demos) aren't classified.
+pub(crate) fn content_type_from_id(tid: u64) -> Option<&'static str> {
+    match (tid >> 32) as u32 {
+        0x00040000 => Some("Base"),
+        0x0004000E => Some("Update"),
+        0x0004008C => Some("DLC"),
+        _ => None,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Origin detection
 // ---------------------------------------------------------------------------
@@ -294,7 +314,7 @@ pub(crate) fn verify_sha256(
             if e.kind() == std::io::ErrorKind::UnexpectedEof {
                 AnalysisError::corrupted_header("Data truncated during hash verification")
             } else {
-                AnalysisError::Io(e)
+                AnalysisError::io_at(e, offset + (size - remaining))
             }
         })?;
         hasher.update(&buf[..to_read]);
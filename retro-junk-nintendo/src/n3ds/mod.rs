@@ -10,17 +10,27 @@
 //! image originated from a physical game card or was converted from a CIA.
 //!
 //! SHA-256 hashes in the NCCH header can be verified when content is unencrypted
-//! (NoCrypto flag set).
+//! (NoCrypto flag set). Such NCCHs also give up their ExHeader's New 3DS
+//! enhancements and the SMDH's region lockout and localized titles.
+//!
+//! DATs list untrimmed card images, so trimmed CCIs are hashed with their
+//! padding restored. A CCI rebuilt from a CIA can't match any card dump and
+//! is reported as such instead of being hashed.
 
 mod cia;
 mod common;
 mod ncch;
 pub(crate) mod ncsd;
+mod smdh;
 
 use retro_junk_core::ReadSeek;
 use std::io::SeekFrom;
 
-use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, FileHashes, HashAlgorithms, Platform, RomAnalyzer,
+    RomIdentification,
+};
+use std::path::Path;
 
 use common::{read_u16_le, read_u32_le, read_u64_le};
 
@@ -29,13 +39,13 @@ use common::{read_u16_le, read_u32_le, read_u64_le};
 // ---------------------------------------------------------------------------
 
 /// 1 media unit = 0x200 bytes (512 bytes).
-const MEDIA_UNIT: u64 = 0x200;
+pub(crate) const MEDIA_UNIT: u64 = 0x200;
 
 /// NCSD magic at offset 0x100: "NCSD".
-const NCSD_MAGIC: [u8; 4] = [0x4E, 0x43, 0x53, 0x44];
+pub(crate) const NCSD_MAGIC: [u8; 4] = [0x4E, 0x43, 0x53, 0x44];
 
 /// NCCH magic at offset 0x100 within a partition: "NCCH".
-const NCCH_MAGIC: [u8; 4] = [0x4E, 0x43, 0x43, 0x48];
+pub(crate) const NCCH_MAGIC: [u8; 4] = [0x4E, 0x43, 0x43, 0x48];
 
 /// Typical CIA header size field value.
 const CIA_HEADER_SIZE: u32 = 0x2020;
@@ -156,6 +166,37 @@ impl RomAnalyzer for N3dsAnalyzer {
         ]
     }
 
+    fn compute_container_hashes(
+        &self,
+        reader: &mut dyn ReadSeek,
+        algorithms: HashAlgorithms,
+        _file_path: Option<&Path>,
+    ) -> Result<Option<FileHashes>, AnalysisError> {
+        if detect_format(reader)? != Some(N3dsFormat::Cci) {
+            return Ok(None);
+        }
+        ncsd::hash_untrimmed(reader, algorithms)
+    }
+
+    fn dat_mismatch_reason(
+        &self,
+        reader: &mut dyn ReadSeek,
+        _file_path: Option<&Path>,
+    ) -> Option<String> {
+        match detect_format(reader) {
+            Ok(Some(N3dsFormat::Cci)) => ncsd::dat_mismatch_reason(reader),
+            _ => None,
+        }
+    }
+
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(r"^(CTR|KTR)-[A-Z]-[A-Z0-9]{4}$")
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        retro_junk_core::serial::normalize_hyphenated(serial)
+    }
+
     fn expects_serial(&self) -> bool {
         true
     }
@@ -1,10 +1,12 @@
-//! NCCH partition header parsing for Nintendo 3DS.
+//! NCCH partition header and ExHeader parsing for Nintendo 3DS.
 
-use retro_junk_core::{AnalysisError, ReadSeek};
+use retro_junk_core::util::read_header;
+use retro_junk_core::{AnalysisError, ReadSeek, RomIdentification};
 use std::io::SeekFrom;
 
 use super::NCCH_MAGIC;
 use super::common::{read_ascii, read_u32_le, read_u64_le};
+use super::smdh::read_smdh;
 
 // ---------------------------------------------------------------------------
 // NCCH header
@@ -49,21 +51,15 @@ pub(crate) fn parse_ncch_header(
     reader: &mut dyn ReadSeek,
     offset: u64,
 ) -> Result<NcchHeader, AnalysisError> {
-    reader.seek(SeekFrom::Start(offset))?;
     let mut buf = [0u8; 0x200];
-    reader.read_exact(&mut buf).map_err(|e| {
-        if e.kind() == std::io::ErrorKind::UnexpectedEof {
-            AnalysisError::corrupted_header("NCCH header truncated")
-        } else {
-            AnalysisError::Io(e)
-        }
-    })?;
+    read_header(reader, offset, &mut buf)?;
 
     if buf[0x100..0x104] != NCCH_MAGIC {
-        return Err(AnalysisError::invalid_format(format!(
-            "Missing NCCH magic at 0x{:X}",
-            offset + 0x100
-        )));
+        return Err(AnalysisError::invalid_magic(
+            offset + 0x100,
+            &NCCH_MAGIC,
+            &buf[0x100..0x104],
+        ));
     }
 
     let content_size_mu = read_u32_le(&buf, 0x104);
@@ -129,3 +125,127 @@ pub(crate) fn parse_ncch_header(
         romfs_superblock_hash,
     })
 }
+
+// ---------------------------------------------------------------------------
+// ExHeader ARM11 local capabilities
+// ---------------------------------------------------------------------------
+
+/// Offset of the ARM11 local capability flags within the ExHeader
+/// (flag1 at 0x20C, flag2 at 0x20D).
+const EXHEADER_ARM11_FLAGS_OFFSET: u64 = 0x20C;
+
+/// New 3DS hardware an ExHeader asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct New3dsCaps {
+    /// flag1 bit 0: enable the L2 cache.
+    pub(crate) l2_cache: bool,
+    /// flag1 bit 1: run the CPU at 804 MHz.
+    pub(crate) cpu_804mhz: bool,
+    /// flag2 bits 0-3: New 3DS system mode (memory layout).
+    pub(crate) system_mode: u8,
+}
+
+impl New3dsCaps {
+    /// The enhancements asked for, e.g. "L2 cache, 804 MHz CPU"; empty when
+    /// the title runs as on an Old 3DS.
+    pub(crate) fn description(&self) -> String {
+        let mut parts = Vec::new();
+        if self.l2_cache {
+            parts.push("L2 cache".to_string());
+        }
+        if self.cpu_804mhz {
+            parts.push("804 MHz CPU".to_string());
+        }
+        if let Some(mode) = new3ds_system_mode_name(self.system_mode) {
+            parts.push(format!("{mode} memory mode"));
+        }
+        parts.join(", ")
+    }
+}
+
+/// New 3DS system mode names; 0 keeps the Old 3DS layout.
+fn new3ds_system_mode_name(mode: u8) -> Option<&'static str> {
+    match mode {
+        1 => Some("124 MB"),
+        2 => Some("178 MB"),
+        3 => Some("124 MB (dev)"),
+        _ => None,
+    }
+}
+
+/// Read the New 3DS capabilities from the ExHeader of the NCCH at
+/// `ncch_offset`. Returns `Ok(None)` for encrypted NCCHs and NCCHs without
+/// an ExHeader.
+pub(crate) fn read_new3ds_caps(
+    reader: &mut dyn ReadSeek,
+    ncch_offset: u64,
+    ncch: &NcchHeader,
+) -> Result<Option<New3dsCaps>, AnalysisError> {
+    if !ncch.no_crypto || (ncch.exheader_size as u64) < EXHEADER_ARM11_FLAGS_OFFSET + 2 {
+        return Ok(None);
+    }
+    reader.seek(SeekFrom::Start(
+        ncch_offset + 0x200 + EXHEADER_ARM11_FLAGS_OFFSET,
+    ))?;
+    let mut flags = [0u8; 2];
+    if reader.read_exact(&mut flags).is_err() {
+        return Ok(None);
+    }
+    Ok(Some(New3dsCaps {
+        l2_cache: flags[0] & 0x01 != 0,
+        cpu_804mhz: flags[0] & 0x02 != 0,
+        system_mode: flags[1] & 0x0F,
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// Title details
+// ---------------------------------------------------------------------------
+
+/// Report New 3DS exclusivity, and for unencrypted NCCHs the New 3DS
+/// enhancements, region lockout and localized titles.
+///
+/// Exclusivity comes from the header's content platform, so it is known
+/// even for encrypted NCCHs; everything else needs the ExHeader or ExeFS.
+pub(crate) fn apply_title_details(
+    reader: &mut dyn ReadSeek,
+    ncch_offset: u64,
+    ncch: &NcchHeader,
+    id: &mut RomIdentification,
+) -> Result<(), AnalysisError> {
+    if ncch.content_platform > 0 {
+        let exclusive = ncch.content_platform == 2;
+        id.extra.insert(
+            "new_3ds_exclusive".into(),
+            if exclusive { "Yes" } else { "No" }.into(),
+        );
+    }
+
+    if let Some(caps) = read_new3ds_caps(reader, ncch_offset, ncch)? {
+        let description = caps.description();
+        if !description.is_empty() {
+            id.extra.insert("new_3ds_enhanced".into(), description);
+        }
+    }
+
+    if let Some(smdh) = read_smdh(reader, ncch_offset, ncch)? {
+        id.extra
+            .insert("region_lock".into(), smdh.region_lock_description());
+        for title in &smdh.titles {
+            if !title.short_title.is_empty() {
+                id.extra.insert(
+                    format!("short_title:{}", title.language),
+                    title.short_title.clone(),
+                );
+            }
+            if !title.long_title.is_empty() {
+                id.extra.insert(
+                    format!("long_title:{}", title.language),
+                    title.long_title.clone(),
+                );
+            }
+        }
+        id.titles = smdh.titles_by_language();
+    }
+    Ok(())
+}
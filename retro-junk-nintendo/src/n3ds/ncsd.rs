@@ -1,15 +1,15 @@
 //! NCSD (CCI) header parsing and analysis for Nintendo 3DS game card dumps.
 
 use retro_junk_core::ReadSeek;
-use std::io::SeekFrom;
+use std::io::{Read, SeekFrom};
 
 use retro_junk_core::{
-    AnalysisError, AnalysisOptions, ChecksumAlgorithm, ExpectedChecksum, Platform,
-    RomIdentification,
+    AnalysisError, AnalysisOptions, ChecksumAlgorithm, ExpectedChecksum, FileHashes,
+    HashAlgorithms, Platform, RomIdentification,
 };
 
 use super::common::*;
-use super::ncch::parse_ncch_header;
+use super::ncch::{apply_title_details, parse_ncch_header};
 use super::{CARD_SEED_SIZE, MEDIA_UNIT, MIN_CCI_SIZE, NCSD_MAGIC};
 
 // ---------------------------------------------------------------------------
@@ -54,13 +54,17 @@ pub(crate) fn parse_ncsd_header(reader: &mut dyn ReadSeek) -> Result<NcsdHeader,
                 actual: 0,
             }
         } else {
-            AnalysisError::Io(e)
+            AnalysisError::io_at(e, 0)
         }
     })?;
 
     // Verify magic
     if buf[0x100..0x104] != NCSD_MAGIC {
-        return Err(AnalysisError::invalid_format("Missing NCSD magic at 0x100"));
+        return Err(AnalysisError::invalid_magic(
+            0x100,
+            &NCSD_MAGIC,
+            &buf[0x100..0x104],
+        ));
     }
 
     let signature_is_zero = is_all_zeros(&buf[0x000..0x100]);
@@ -115,6 +119,46 @@ pub(crate) fn parse_ncsd_header(reader: &mut dyn ReadSeek) -> Result<NcsdHeader,
     })
 }
 
+// ---------------------------------------------------------------------------
+// DAT matching
+// ---------------------------------------------------------------------------
+
+/// Byte that fills a game card past its content.
+const CARD_PADDING: u8 = 0xFF;
+
+/// Hash a trimmed CCI as the full card image it was trimmed from.
+///
+/// Trimming only drops the padding after `filled_size`, so restoring it up
+/// to the card's image size gives back the untrimmed dump DATs list. Returns
+/// `None` for untrimmed images, and for files shorter than their content.
+pub(crate) fn hash_untrimmed(
+    reader: &mut dyn ReadSeek,
+    algorithms: HashAlgorithms,
+) -> Result<Option<FileHashes>, AnalysisError> {
+    let ncsd = parse_ncsd_header(reader)?;
+    let file_size = retro_junk_core::util::file_size(reader)?;
+    let image_size = ncsd.image_size_mu as u64 * MEDIA_UNIT;
+    if ncsd.filled_size == 0 || file_size < ncsd.filled_size || file_size >= image_size {
+        return Ok(None);
+    }
+    reader.seek(SeekFrom::Start(0))?;
+    let padding = std::io::repeat(CARD_PADDING).take(image_size - file_size);
+    let mut image = (&mut *reader).take(file_size).chain(padding);
+    Ok(Some(retro_junk_core::hash_reader(&mut image, algorithms)?))
+}
+
+/// Why a CCI can't match a game card DAT entry: one rebuilt from a CIA has
+/// a generated NCSD header and no card seed, so it differs from every dump
+/// of the card even when its NCCH partitions are the same.
+pub(crate) fn dat_mismatch_reason(reader: &mut dyn ReadSeek) -> Option<String> {
+    let ncsd = parse_ncsd_header(reader).ok()?;
+    (detect_cci_origin(&ncsd) == CciOrigin::Digital).then(|| {
+        "converted from a CIA: its NCSD header and card info were generated, \
+         so it can't match a game card dump; match the original CIA instead"
+            .to_string()
+    })
+}
+
 // ---------------------------------------------------------------------------
 // CCI analysis
 // ---------------------------------------------------------------------------
@@ -165,6 +209,9 @@ pub(crate) fn analyze_cci(
             "title_type".into(),
             title_type_from_id(ncch.program_id).into(),
         );
+        if let Some(content_type) = content_type_from_id(ncch.program_id) {
+            id.extra.insert("content_type".into(), content_type.into());
+        }
     }
 
     // Regions from product code
@@ -173,10 +220,7 @@ pub(crate) fn analyze_cci(
 
     // Title version from card info header
     if ncsd.title_version > 0 {
-        let major = ncsd.title_version >> 10;
-        let minor = (ncsd.title_version >> 4) & 0x3F;
-        let micro = ncsd.title_version & 0xF;
-        id.version = Some(format!("v{}.{}.{}", major, minor, micro));
+        id.version = Some(format_title_version(ncsd.title_version));
         id.extra.insert(
             "title_version_raw".into(),
             format!("{}", ncsd.title_version),
@@ -235,6 +279,9 @@ pub(crate) fn analyze_cci(
         );
     }
 
+    // New 3DS exclusivity, region lockout and localized titles
+    apply_title_details(reader, partition0_offset, &ncch, &mut id)?;
+
     // Card type
     match ncsd.media_type {
         1 => {
@@ -296,7 +343,7 @@ pub(crate) fn analyze_cci(
         format!("{} KB", ncch.content_size_mu as u64 * MEDIA_UNIT / 1024),
     );
     id.extra.insert(
-        "content_type".into(),
+        "ncch_content_type".into(),
         content_type_description(ncch.content_type_flags).into(),
     );
 
@@ -0,0 +1,210 @@
+//! ExeFS and SMDH icon parsing for unencrypted NCCHs.
+//!
+//! The ExeFS `icon` file holds the title's SMDH: short and long titles and
+//! the publisher in sixteen language slots, and the region lockout mask the
+//! HOME Menu checks before launching it. Both are encrypted in retail card
+//! dumps, so they're only read when the NCCH has the NoCrypto flag.
+
+use std::collections::HashMap;
+use std::io::SeekFrom;
+
+use retro_junk_core::{AnalysisError, Language, ReadSeek};
+
+use super::MEDIA_UNIT;
+use super::common::read_u32_le;
+use super::ncch::NcchHeader;
+
+/// ExeFS header size: 10 file entries, reserved space and 10 file hashes.
+const EXEFS_HEADER_SIZE: usize = 0x200;
+
+/// Number of file entries in the ExeFS header.
+const EXEFS_FILE_COUNT: usize = 10;
+
+/// SMDH magic at offset 0.
+const SMDH_MAGIC: &[u8; 4] = b"SMDH";
+
+/// Size of an SMDH file, icons included.
+const SMDH_SIZE: u32 = 0x36C0;
+
+/// Bytes read from the SMDH: everything before the icons.
+const SMDH_INFO_SIZE: usize = 0x2040;
+
+/// Offset of the sixteen title slots.
+const SMDH_TITLES_OFFSET: usize = 0x08;
+
+/// Size of one title slot: short title, long title, publisher.
+const SMDH_TITLE_SIZE: usize = 0x200;
+
+/// Offset of the region lockout mask.
+const SMDH_REGION_LOCKOUT_OFFSET: usize = 0x2018;
+
+/// Lockout mask value allowing every region.
+const REGION_FREE: u32 = 0x7FFF_FFFF;
+
+/// Region lockout bits, in mask order.
+const LOCKOUT_REGIONS: &[&str] = &[
+    "Japan",
+    "North America",
+    "Europe",
+    "Australia",
+    "China",
+    "Korea",
+    "Taiwan",
+];
+
+/// Title slots, in SMDH order. Slots 12-15 are unused.
+const SMDH_LANGUAGES: &[(&str, Language)] = &[
+    ("Japanese", Language::Japanese),
+    ("English", Language::English),
+    ("French", Language::French),
+    ("German", Language::German),
+    ("Italian", Language::Italian),
+    ("Spanish", Language::Spanish),
+    ("Simplified Chinese", Language::Chinese),
+    ("Korean", Language::Korean),
+    ("Dutch", Language::Dutch),
+    ("Portuguese", Language::Portuguese),
+    ("Russian", Language::Russian),
+    ("Traditional Chinese", Language::Chinese),
+];
+
+/// One language's titles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SmdhTitle {
+    /// Language name, e.g. "Simplified Chinese".
+    pub(crate) language: &'static str,
+    pub(crate) short_title: String,
+    pub(crate) long_title: String,
+}
+
+/// Parsed SMDH fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Smdh {
+    /// Non-empty title slots, in SMDH order.
+    pub(crate) titles: Vec<SmdhTitle>,
+    /// Region lockout mask.
+    pub(crate) region_lockout: u32,
+}
+
+impl Smdh {
+    /// Short titles by language, for [`retro_junk_core::RomIdentification::titles`].
+    /// Simplified Chinese is preferred over Traditional.
+    pub(crate) fn titles_by_language(&self) -> HashMap<Language, String> {
+        let mut titles = HashMap::new();
+        for title in &self.titles {
+            if let Some((_, language)) = SMDH_LANGUAGES.iter().find(|(n, _)| *n == title.language) {
+                titles
+                    .entry(*language)
+                    .or_insert_with(|| title.short_title.clone());
+            }
+        }
+        titles
+    }
+
+    /// The regions the title may be launched in, or "Region free".
+    pub(crate) fn region_lock_description(&self) -> String {
+        let all = (1u32 << LOCKOUT_REGIONS.len()) - 1;
+        if self.region_lockout == REGION_FREE || self.region_lockout & all == all {
+            return "Region free".into();
+        }
+        let allowed: Vec<&str> = LOCKOUT_REGIONS
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| self.region_lockout & (1 << bit) != 0)
+            .map(|(_, name)| *name)
+            .collect();
+        if allowed.is_empty() {
+            "None".into()
+        } else {
+            allowed.join(", ")
+        }
+    }
+}
+
+/// Parse an SMDH, icons optional. Returns `None` without the magic or when
+/// too short to hold the region lockout.
+pub(crate) fn parse_smdh(buf: &[u8]) -> Option<Smdh> {
+    if buf.len() < SMDH_INFO_SIZE || &buf[0..4] != SMDH_MAGIC {
+        return None;
+    }
+    let titles = SMDH_LANGUAGES
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (language, _))| {
+            let slot = &buf[SMDH_TITLES_OFFSET + i * SMDH_TITLE_SIZE..][..SMDH_TITLE_SIZE];
+            let short_title = read_utf16(&slot[..0x80]);
+            let long_title = read_utf16(&slot[0x80..0x180]);
+            (!short_title.is_empty() || !long_title.is_empty()).then_some(SmdhTitle {
+                language,
+                short_title,
+                long_title,
+            })
+        })
+        .collect();
+    Some(Smdh {
+        titles,
+        region_lockout: read_u32_le(buf, SMDH_REGION_LOCKOUT_OFFSET),
+    })
+}
+
+/// A NUL-terminated UTF-16LE string, with line breaks folded to spaces.
+fn read_utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&u| u != 0)
+        .collect();
+    String::from_utf16_lossy(&units)
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Read the SMDH from the ExeFS `icon` file of the NCCH at `ncch_offset`.
+///
+/// Returns `Ok(None)` for encrypted NCCHs, NCCHs without an ExeFS or icon,
+/// and icons that aren't a valid SMDH.
+pub(crate) fn read_smdh(
+    reader: &mut dyn ReadSeek,
+    ncch_offset: u64,
+    ncch: &NcchHeader,
+) -> Result<Option<Smdh>, AnalysisError> {
+    if !ncch.no_crypto || ncch.exefs_size_mu == 0 {
+        return Ok(None);
+    }
+    let exefs_offset = ncch_offset + ncch.exefs_offset_mu as u64 * MEDIA_UNIT;
+    let exefs_size = ncch.exefs_size_mu as u64 * MEDIA_UNIT;
+
+    reader.seek(SeekFrom::Start(exefs_offset))?;
+    let mut header = [0u8; EXEFS_HEADER_SIZE];
+    if reader.read_exact(&mut header).is_err() {
+        return Ok(None);
+    }
+    let icon = header
+        .chunks(16)
+        .take(EXEFS_FILE_COUNT)
+        .find(|entry| entry[..8] == *b"icon\0\0\0\0")
+        .map(|entry| (read_u32_le(entry, 8), read_u32_le(entry, 12)));
+    let Some((offset, size)) = icon else {
+        return Ok(None);
+    };
+    let end = EXEFS_HEADER_SIZE as u64 + offset as u64 + SMDH_INFO_SIZE as u64;
+    if size < SMDH_INFO_SIZE as u32 || size > SMDH_SIZE || end > exefs_size {
+        return Ok(None);
+    }
+
+    reader.seek(SeekFrom::Start(
+        exefs_offset + EXEFS_HEADER_SIZE as u64 + offset as u64,
+    ))?;
+    let mut buf = vec![0u8; SMDH_INFO_SIZE];
+    if reader.read_exact(&mut buf).is_err() {
+        return Ok(None);
+    }
+    Ok(parse_smdh(&buf))
+}
+
+#[cfg(test)]
+#[path = "tests/smdh_tests.rs"]
+pub(super) mod tests;
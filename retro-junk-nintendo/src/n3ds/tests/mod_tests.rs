@@ -170,3 +170,16 @@ fn test_full_cia_via_analyzer() {
     assert_eq!(result.serial_number.as_deref(), Some("CTR-N-ABCJ"));
     assert_eq!(result.regions, vec![Region::Japan]);
 }
+
+#[test]
+fn test_cia_hashed_as_is() {
+    let analyzer = N3dsAnalyzer;
+    let mut cia = Cursor::new(make_cia_minimal());
+    assert!(
+        analyzer
+            .compute_container_hashes(&mut cia, HashAlgorithms::Crc32, None)
+            .unwrap()
+            .is_none()
+    );
+    assert!(analyzer.dat_mismatch_reason(&mut cia, None).is_none());
+}
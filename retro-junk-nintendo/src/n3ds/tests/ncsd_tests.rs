@@ -1,128 +1,11 @@
 use super::*;
-use sha2::{Digest, Sha256};
 use std::io::Cursor;
 
-use super::super::{NCCH_MAGIC, NCSD_MAGIC};
-
 /// Build a minimal synthetic CCI (NCSD + NCCH partition 0).
 /// The NCCH at partition 0 is marked NoCrypto with a valid ExHeader region
 /// whose SHA-256 hash can be verified.
 fn make_cci() -> Vec<u8> {
-    let partition0_offset: u64 = 0x4000;
-    let ncch_content_size_mu: u32 = 0x100; // 128 KB of NCCH content
-    let total_size = partition0_offset + ncch_content_size_mu as u64 * MEDIA_UNIT;
-    let mut rom = vec![0u8; total_size as usize];
-
-    // -- NCSD header --
-
-    // RSA signature: non-zero (simulating authentic card)
-    rom[0x00] = 0xAB;
-    rom[0x01] = 0xCD;
-
-    // Magic "NCSD" at 0x100
-    rom[0x100..0x104].copy_from_slice(&NCSD_MAGIC);
-
-    // Image size in media units
-    let image_size_mu = (total_size / MEDIA_UNIT) as u32;
-    rom[0x104..0x108].copy_from_slice(&image_size_mu.to_le_bytes());
-
-    // Media ID
-    rom[0x108..0x110].copy_from_slice(&0x0004000000ABCDEF_u64.to_le_bytes());
-
-    // Partition 0: offset=0x20 MU (0x4000 bytes), size=0x100 MU
-    let p0_offset_mu = (partition0_offset / MEDIA_UNIT) as u32;
-    rom[0x120..0x124].copy_from_slice(&p0_offset_mu.to_le_bytes());
-    rom[0x124..0x128].copy_from_slice(&ncch_content_size_mu.to_le_bytes());
-
-    // Partition flags: media_platform=1 (CTR), media_type=1 (Card1)
-    rom[0x188 + 4] = 1; // platform = CTR
-    rom[0x188 + 5] = 1; // media type = Card1
-
-    // Card info: writable address = 0xFFFFFFFF (Card1)
-    rom[0x200..0x204].copy_from_slice(&0xFFFFFFFF_u32.to_le_bytes());
-
-    // Filled size at 0x300: less than total (simulates content that doesn't
-    // fill the entire card image — typical for real game cards).
-    let filled = (partition0_offset + ncch_content_size_mu as u64 * MEDIA_UNIT / 2) as u32;
-    rom[0x300..0x304].copy_from_slice(&filled.to_le_bytes());
-
-    // Title version at 0x310
-    rom[0x310..0x312].copy_from_slice(&0x0000u16.to_le_bytes());
-
-    // Card seed at 0x1000: non-zero (authentic card)
-    rom[0x1000] = 0x42;
-    rom[0x1001] = 0x37;
-
-    // -- NCCH header at partition0_offset --
-    let p0 = partition0_offset as usize;
-
-    // Magic "NCCH" at partition + 0x100
-    rom[p0 + 0x100..p0 + 0x104].copy_from_slice(&NCCH_MAGIC);
-
-    // Content size
-    rom[p0 + 0x104..p0 + 0x108].copy_from_slice(&ncch_content_size_mu.to_le_bytes());
-
-    // Partition ID
-    rom[p0 + 0x108..p0 + 0x110].copy_from_slice(&0x0004000000ABCDEF_u64.to_le_bytes());
-
-    // Maker code "31" (Nintendo)
-    rom[p0 + 0x110..p0 + 0x112].copy_from_slice(b"31");
-
-    // NCCH version
-    rom[p0 + 0x112..p0 + 0x114].copy_from_slice(&2u16.to_le_bytes());
-
-    // Program ID
-    rom[p0 + 0x118..p0 + 0x120].copy_from_slice(&0x0004000000ABCDEF_u64.to_le_bytes());
-
-    // Product code: "CTR-P-ABCE" (USA)
-    let product = b"CTR-P-ABCE\0\0\0\0\0\0";
-    rom[p0 + 0x150..p0 + 0x160].copy_from_slice(product);
-
-    // ExHeader size = 0x400
-    rom[p0 + 0x180..p0 + 0x184].copy_from_slice(&0x400u32.to_le_bytes());
-
-    // Flags: NoCrypto (flags[7] bit 2), platform=CTR (flags[4]=1), form=executable (flags[5]=3)
-    rom[p0 + 0x188 + 3] = 0x00; // crypto method
-    rom[p0 + 0x188 + 4] = 0x01; // platform CTR
-    rom[p0 + 0x188 + 5] = 0x03; // executable with RomFS
-    rom[p0 + 0x188 + 7] = 0x04; // NoCrypto
-
-    // ExeFS: offset in MU from NCCH start, after ExHeader (0x200 header + 0x800 exheader = 0xA00)
-    // ExeFS offset = 0xA00 / 0x200 = 5 MU
-    // Size = 0x2000 / 0x200 = 16 MU
-    rom[p0 + 0x1A0..p0 + 0x1A4].copy_from_slice(&5u32.to_le_bytes());
-    rom[p0 + 0x1A4..p0 + 0x1A8].copy_from_slice(&16u32.to_le_bytes());
-    rom[p0 + 0x1A8..p0 + 0x1AC].copy_from_slice(&1u32.to_le_bytes()); // hash region = 1 MU
-
-    // Write some ExHeader data at partition + 0x200
-    for i in 0..0x400 {
-        rom[p0 + 0x200 + i] = (i & 0xFF) as u8;
-    }
-
-    // Compute ExHeader SHA-256 hash and store at partition + 0x160
-    let exheader_hash = {
-        let mut hasher = Sha256::new();
-        hasher.update(&rom[p0 + 0x200..p0 + 0x200 + 0x400]);
-        hasher.finalize()
-    };
-    rom[p0 + 0x160..p0 + 0x180].copy_from_slice(&exheader_hash);
-
-    // Write some ExeFS data and compute superblock hash
-    let exefs_start = p0 + 5 * MEDIA_UNIT as usize;
-    for i in 0..MEDIA_UNIT as usize {
-        if exefs_start + i < rom.len() {
-            rom[exefs_start + i] = ((i * 3) & 0xFF) as u8;
-        }
-    }
-    let exefs_hash = {
-        let mut hasher = Sha256::new();
-        let end = (exefs_start + MEDIA_UNIT as usize).min(rom.len());
-        hasher.update(&rom[exefs_start..end]);
-        hasher.finalize()
-    };
-    rom[p0 + 0x1C0..p0 + 0x1E0].copy_from_slice(&exefs_hash);
-
-    rom
+    crate::synthetic::make_cci_with(0x0004000000ABCDEF, 0)
 }
 
 /// Modify a CCI to look like it was converted from a CIA.
@@ -459,3 +342,138 @@ fn test_cci_genuinely_truncated() {
     assert_eq!(result.expected_size, Some(filled as u64)); // genuinely truncated
     assert!(result.extra.get("dump_status").is_none()); // no status for truncated
 }
+
+// -----------------------------------------------------------------------
+// DAT matching tests
+// -----------------------------------------------------------------------
+
+/// An untrimmed CCI whose content ends at 0x6000, padded with 0xFF.
+fn make_cci_padded() -> Vec<u8> {
+    let mut rom = make_cci();
+    rom[0x300..0x304].copy_from_slice(&0x6000u32.to_le_bytes());
+    rom[0x6000..].fill(0xFF);
+    rom
+}
+
+#[test]
+fn test_trimmed_cci_hashes_as_untrimmed() {
+    let full = make_cci_padded();
+    let expected =
+        retro_junk_core::hash_reader(&mut Cursor::new(full.clone()), HashAlgorithms::All).unwrap();
+
+    for trimmed_size in [0x6000, 0x8000] {
+        let mut rom = full.clone();
+        rom.truncate(trimmed_size);
+        let hashes = hash_untrimmed(&mut Cursor::new(rom), HashAlgorithms::All)
+            .unwrap()
+            .unwrap();
+        assert_eq!(hashes.crc32, expected.crc32);
+        assert_eq!(hashes.sha1, expected.sha1);
+        assert_eq!(hashes.md5, expected.md5);
+        assert_eq!(hashes.data_size, full.len() as u64);
+    }
+}
+
+#[test]
+fn test_untrimmed_cci_hashed_as_is() {
+    let rom = make_cci_padded();
+    assert!(
+        hash_untrimmed(&mut Cursor::new(rom), HashAlgorithms::Crc32)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_truncated_cci_not_padded() {
+    let mut rom = make_cci_padded();
+    rom.truncate(0x5000);
+    assert!(
+        hash_untrimmed(&mut Cursor::new(rom), HashAlgorithms::Crc32)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_cia_converted_cci_cannot_match() {
+    let reason = dat_mismatch_reason(&mut Cursor::new(make_cci_digital_origin())).unwrap();
+    assert!(reason.contains("CIA"));
+    assert!(dat_mismatch_reason(&mut Cursor::new(make_cci())).is_none());
+}
+
+/// A CCI whose ExeFS holds an SMDH icon, asking for New 3DS hardware.
+fn make_cci_with_icon(content_platform: u8) -> Vec<u8> {
+    let mut rom = make_cci();
+    let p0 = 0x4000usize;
+    rom[p0 + 0x188 + 4] = content_platform;
+
+    // ExHeader ARM11 flags: L2 cache + 804 MHz, 124 MB system mode
+    rom[p0 + 0x200 + 0x20C] = 0x03;
+    rom[p0 + 0x200 + 0x20D] = 0x01;
+
+    // ExeFS: 0x20 MU, holding "icon" at file offset 0
+    rom[p0 + 0x1A4..p0 + 0x1A8].copy_from_slice(&0x20u32.to_le_bytes());
+    let exefs = p0 + 5 * MEDIA_UNIT as usize;
+    rom[exefs..exefs + 0x200].fill(0);
+    rom[exefs..exefs + 8].copy_from_slice(b"icon\0\0\0\0");
+    rom[exefs + 12..exefs + 16].copy_from_slice(&0x36C0u32.to_le_bytes());
+    let smdh = crate::n3ds::smdh::tests::make_smdh(
+        &[
+            (0, "テスト", "テストゲーム"),
+            (1, "Test Game", "Test Game Deluxe"),
+        ],
+        0x01,
+    );
+    rom[exefs + 0x200..exefs + 0x200 + smdh.len()].copy_from_slice(&smdh);
+    rom
+}
+
+#[test]
+fn test_cci_smdh_titles_and_region_lock() {
+    let rom = make_cci_with_icon(2);
+    let file_size = rom.len() as u64;
+    let result = analyze_cci(
+        &mut Cursor::new(rom),
+        file_size,
+        &AnalysisOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(result.extra.get("region_lock").unwrap(), "Japan");
+    assert_eq!(
+        result.extra.get("short_title:English").unwrap(),
+        "Test Game"
+    );
+    assert_eq!(
+        result.extra.get("long_title:Japanese").unwrap(),
+        "テストゲーム"
+    );
+    assert_eq!(
+        result.title_for(Some(retro_junk_core::Language::Japanese)),
+        Some("テスト")
+    );
+    assert_eq!(result.extra.get("new_3ds_exclusive").unwrap(), "Yes");
+    assert_eq!(
+        result.extra.get("new_3ds_enhanced").unwrap(),
+        "L2 cache, 804 MHz CPU, 124 MB memory mode"
+    );
+}
+
+#[test]
+fn test_cci_encrypted_skips_smdh() {
+    let mut rom = make_cci_with_icon(1);
+    rom[0x4000 + 0x188 + 7] = 0; // clear NoCrypto
+    let file_size = rom.len() as u64;
+    let result = analyze_cci(
+        &mut Cursor::new(rom),
+        file_size,
+        &AnalysisOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(result.extra.get("new_3ds_exclusive").unwrap(), "No");
+    assert!(!result.extra.contains_key("region_lock"));
+    assert!(!result.extra.contains_key("new_3ds_enhanced"));
+    assert!(result.titles.is_empty());
+}
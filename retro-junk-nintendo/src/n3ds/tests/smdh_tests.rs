@@ -0,0 +1,65 @@
+use super::*;
+
+/// Build an SMDH (without icons) with the given titles and lockout mask.
+pub(crate) fn make_smdh(titles: &[(usize, &str, &str)], region_lockout: u32) -> Vec<u8> {
+    let mut buf = vec![0u8; SMDH_INFO_SIZE];
+    buf[0..4].copy_from_slice(SMDH_MAGIC);
+    for &(slot, short, long) in titles {
+        let base = SMDH_TITLES_OFFSET + slot * SMDH_TITLE_SIZE;
+        for (i, unit) in short.encode_utf16().enumerate() {
+            buf[base + i * 2..base + i * 2 + 2].copy_from_slice(&unit.to_le_bytes());
+        }
+        for (i, unit) in long.encode_utf16().enumerate() {
+            let at = base + 0x80 + i * 2;
+            buf[at..at + 2].copy_from_slice(&unit.to_le_bytes());
+        }
+    }
+    buf[SMDH_REGION_LOCKOUT_OFFSET..SMDH_REGION_LOCKOUT_OFFSET + 4]
+        .copy_from_slice(&region_lockout.to_le_bytes());
+    buf
+}
+
+#[test]
+fn test_smdh_titles() {
+    let smdh = parse_smdh(&make_smdh(
+        &[
+            (1, "Test Game", "Test Game\nDeluxe Edition"),
+            (6, "测试", "测试游戏"),
+            (11, "測試", "測試遊戲"),
+        ],
+        0x02,
+    ))
+    .unwrap();
+
+    assert_eq!(smdh.titles.len(), 3);
+    assert_eq!(smdh.titles[0].language, "English");
+    assert_eq!(smdh.titles[0].long_title, "Test Game Deluxe Edition");
+    assert_eq!(smdh.titles[2].language, "Traditional Chinese");
+
+    let titles = smdh.titles_by_language();
+    assert_eq!(titles.get(&Language::English).unwrap(), "Test Game");
+    assert_eq!(titles.get(&Language::Chinese).unwrap(), "测试");
+    assert!(!titles.contains_key(&Language::Japanese));
+}
+
+#[test]
+fn test_smdh_region_lock() {
+    let lock = |mask| {
+        parse_smdh(&make_smdh(&[], mask))
+            .unwrap()
+            .region_lock_description()
+    };
+    assert_eq!(lock(0x01), "Japan");
+    assert_eq!(lock(0x0C), "Europe, Australia");
+    assert_eq!(lock(0x7FFF_FFFF), "Region free");
+    assert_eq!(lock(0x7F), "Region free");
+    assert_eq!(lock(0), "None");
+}
+
+#[test]
+fn test_smdh_rejects_bad_magic_and_short_data() {
+    let mut buf = make_smdh(&[], 1);
+    assert!(parse_smdh(&buf[..0x100]).is_none());
+    buf[0] = b'X';
+    assert!(parse_smdh(&buf).is_none());
+}
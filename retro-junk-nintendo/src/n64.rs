@@ -37,7 +37,7 @@ const CRC_END: u64 = 0x101000;
 /// CIC lockout chip variants. Each has a different seed and potentially
 /// different checksum algorithm behavior.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum CicVariant {
+pub(crate) enum CicVariant {
     Cic6101,
     Cic6102,
     Cic6103,
@@ -133,7 +133,7 @@ fn parse_header(reader: &mut dyn ReadSeek) -> Result<N64Header, AnalysisError> {
                 actual: 0,
             }
         } else {
-            AnalysisError::Io(e)
+            AnalysisError::io_at(e, 0)
         }
     })?;
 
@@ -197,7 +197,7 @@ fn parse_header(reader: &mut dyn ReadSeek) -> Result<N64Header, AnalysisError> {
 /// Compute the N64 CRC checksum pair using the correct algorithm for the
 /// detected CIC variant. The boot_code parameter is needed for CIC-6105
 /// which reads from it during computation.
-fn compute_n64_crc(
+pub(crate) fn compute_n64_crc(
     reader: &mut dyn ReadSeek,
     format: RomFormat,
     cic: CicVariant,
@@ -428,7 +428,7 @@ impl RomAnalyzer for N64Analyzer {
     fn analyze(
         &self,
         reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
         let file_size = retro_junk_core::util::file_size(reader)?;
 
@@ -447,7 +447,10 @@ impl RomAnalyzer for N64Analyzer {
             None
         };
 
-        Ok(to_identification(&header, file_size, crc_result))
+        // Captured in file byte order, so byte-swapped dumps show as such
+        let mut id = to_identification(&header, file_size, crc_result);
+        id.attach_raw_header(reader, 0, HEADER_SIZE as usize, options)?;
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -502,6 +505,15 @@ impl RomAnalyzer for N64Analyzer {
         }
     }
 
+    fn serial_pattern(&self) -> Option<&'static str> {
+        // The game ID bytes may be any printable ASCII, spaces included
+        Some(r"^NUS-[ -~]{4}-[A-Z]{3}$")
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        retro_junk_core::serial::normalize_basic(serial)
+    }
+
     fn expects_serial(&self) -> bool {
         true
     }
@@ -5,17 +5,18 @@
 //! - NES 2.0 format
 //! - UNIF format (.unf) - detection only
 //! - FDS format (.fds) - basic header parsing
+//! - NSF and NES APU VGM music rips (.nsf, .vgm, .vgz)
 
 use retro_junk_core::ReadSeek;
 use std::io::SeekFrom;
 
 use retro_junk_core::util::format_bytes;
 use retro_junk_core::{
-    AnalysisError, AnalysisOptions, Platform, Region, RomAnalyzer, RomIdentification,
+    AnalysisError, AnalysisOptions, BuildDate, Platform, Region, RomAnalyzer, RomIdentification,
 };
 
 /// The 4-byte magic at the start of every iNES / NES 2.0 file.
-const INES_MAGIC: [u8; 4] = [0x4E, 0x45, 0x53, 0x1A]; // "NES\x1A"
+pub(crate) const INES_MAGIC: [u8; 4] = [0x4E, 0x45, 0x53, 0x1A]; // "NES\x1A"
 
 /// The 4-byte magic at the start of every UNIF file.
 const UNIF_MAGIC: [u8; 4] = [0x55, 0x4E, 0x49, 0x46]; // "UNIF"
@@ -427,6 +428,24 @@ fn format_bcd_date(year: u8, month: u8, day: u8) -> String {
     format!("19{:02x}-{:02x}-{:02x}", year, month, day)
 }
 
+/// Convert an FDS manufacturing date to a calendar date. Disks mostly use
+/// Japanese era years (Showa 61 = 1986, Heisei 1 = 1989), though some
+/// record Western two-digit years.
+fn fds_build_date(year: u8, month: u8, day: u8) -> Option<BuildDate> {
+    let bcd = |b: u8| -> Option<u8> {
+        let (hi, lo) = (b >> 4, b & 0x0F);
+        (hi <= 9 && lo <= 9).then_some(hi * 10 + lo)
+    };
+    let yy = bcd(year)? as u16;
+    let year = match yy {
+        58..=64 => 1925 + yy, // Showa
+        80..=99 => 1900 + yy,
+        1..=20 => 1988 + yy, // Heisei
+        _ => return None,
+    };
+    BuildDate::new(year, Some(bcd(month)?), Some(bcd(day)?))
+}
+
 /// Look up a human-readable name for common NES mapper numbers.
 fn mapper_name(mapper: u16) -> Option<&'static str> {
     match mapper {
@@ -573,12 +592,12 @@ fn analyze_ines(reader: &mut dyn ReadSeek) -> Result<NesRomInfo, AnalysisError>
                 actual: 0,
             }
         } else {
-            AnalysisError::Io(e)
+            AnalysisError::io_at(e, 0)
         }
     })?;
 
     if header[0..4] != INES_MAGIC {
-        return Err(AnalysisError::invalid_format("Not an iNES file"));
+        return Err(AnalysisError::invalid_magic(0, &INES_MAGIC, &header[0..4]));
     }
 
     let parsed = parse_ines_header(&header)?;
@@ -669,7 +688,7 @@ fn analyze_unif(reader: &mut dyn ReadSeek) -> Result<NesRomInfo, AnalysisError>
         })?;
 
     if header[0..4] != UNIF_MAGIC {
-        return Err(AnalysisError::invalid_format("Not a UNIF file"));
+        return Err(AnalysisError::invalid_magic(0, &UNIF_MAGIC, &header[0..4]));
     }
 
     // Bytes 4-7: revision number (little-endian u32)
@@ -805,6 +824,7 @@ fn to_identification(info: &NesRomInfo, file_size: u64) -> RomIdentification {
                 if let Some((y, m, d)) = first.manufacturing_date {
                     id.extra
                         .insert("manufacturing_date".into(), format_bcd_date(y, m, d));
+                    id.build_date = fds_build_date(y, m, d);
                 }
                 if let Some((y, m, d)) = first.rewrite_date {
                     id.extra
@@ -887,7 +907,7 @@ impl RomAnalyzer for NesAnalyzer {
     fn analyze(
         &self,
         reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
         let file_size = retro_junk_core::util::file_size(reader)?;
 
@@ -899,7 +919,16 @@ impl RomAnalyzer for NesAnalyzer {
             NesFormat::Unif => analyze_unif(reader)?,
         };
 
-        Ok(to_identification(&info, file_size))
+        let mut id = to_identification(&info, file_size);
+        // iNES/fwNES headers are 16 bytes, UNIF's is 32, and a raw FDS image
+        // starts with the 56-byte disk info block.
+        let header_len = match format {
+            NesFormat::INes | NesFormat::Nes2 | NesFormat::FdsHeadered => 16,
+            NesFormat::Unif => 32,
+            NesFormat::FdsRaw => 0x38,
+        };
+        id.attach_raw_header(reader, 0, header_len, options)?;
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -914,6 +943,18 @@ impl RomAnalyzer for NesAnalyzer {
         detect_format(reader).is_ok()
     }
 
+    fn music_extensions(&self) -> &'static [&'static str] {
+        crate::music::NES_EXTENSIONS
+    }
+
+    fn analyze_music(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        crate::music::analyze(reader, options, Platform::Nes)
+    }
+
     fn dat_names(&self) -> &'static [&'static str] {
         &["Nintendo - Nintendo Entertainment System"]
     }
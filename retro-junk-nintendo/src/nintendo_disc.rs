@@ -12,11 +12,11 @@
 //! - Yet Another GameCube Documentation (YAGCD): https://www.gc-forever.com/yagcd/chap13.html
 //! - Wiibrew disc format: https://wiibrew.org/wiki/Wii_disc
 
-use std::io::SeekFrom;
+use std::io::{Read, SeekFrom};
 use std::path::Path;
 
 use retro_junk_core::{
-    AnalysisError, FileHashes, HashAlgorithms, Platform, ReadSeek, RomIdentification,
+    AnalysisError, BuildDate, FileHashes, HashAlgorithms, Platform, ReadSeek, RomIdentification,
 };
 
 use crate::constants::region_from_game_code;
@@ -35,9 +35,16 @@ pub(crate) const WII_MAGIC: u32 = 0x5D1C9EA3;
 /// Size of the disc header ("boot.bin"): 0x440 bytes.
 pub(crate) const HEADER_SIZE: usize = 0x440;
 
+/// A disc's game code: system, game and region characters ("GALE").
+pub(crate) const GAME_CODE_PATTERN: &str = "^[A-Z0-9]{4}$";
+
 /// Minimum bytes needed to check both magic words (through offset 0x001F).
 pub(crate) const MAGIC_CHECK_SIZE: usize = 0x20;
 
+/// Offset of the apploader build date ("YYYY/MM/DD"). GameCube only; on Wii
+/// discs the apploader is inside the encrypted game partition.
+pub(crate) const APPLOADER_DATE_OFFSET: u64 = 0x2440;
+
 // ---------------------------------------------------------------------------
 // Header struct
 // ---------------------------------------------------------------------------
@@ -71,6 +78,8 @@ pub(crate) struct NintendoDiscHeader {
     pub fst_offset: u32,
     /// File System Table size
     pub fst_size: u32,
+    /// Apploader build date, if readable
+    pub apploader_date: Option<BuildDate>,
 }
 
 // ---------------------------------------------------------------------------
@@ -94,6 +103,14 @@ pub(crate) fn parse_disc_header(
         }
     })?;
 
+    Ok(header_from_bytes(&buf, read_apploader_date(reader)))
+}
+
+/// Parse the fields of a disc header already in memory.
+fn header_from_bytes(
+    buf: &[u8; HEADER_SIZE],
+    apploader_date: Option<BuildDate>,
+) -> NintendoDiscHeader {
     let game_code: [u8; 4] = buf[0x0000..0x0004].try_into().unwrap();
     let maker_code: [u8; 2] = buf[0x0004..0x0006].try_into().unwrap();
     let disc_id = buf[0x0006];
@@ -118,7 +135,7 @@ pub(crate) fn parse_disc_header(
     let fst_offset = u32::from_be_bytes(buf[0x0424..0x0428].try_into().unwrap());
     let fst_size = u32::from_be_bytes(buf[0x0428..0x042C].try_into().unwrap());
 
-    Ok(NintendoDiscHeader {
+    NintendoDiscHeader {
         game_code,
         maker_code,
         disc_id,
@@ -131,7 +148,16 @@ pub(crate) fn parse_disc_header(
         dol_offset,
         fst_offset,
         fst_size,
-    })
+        apploader_date,
+    }
+}
+
+/// Read the apploader date. Missing or unparseable dates yield `None`.
+fn read_apploader_date(reader: &mut dyn ReadSeek) -> Option<BuildDate> {
+    let mut buf = [0u8; 10];
+    reader.seek(SeekFrom::Start(APPLOADER_DATE_OFFSET)).ok()?;
+    reader.read_exact(&mut buf).ok()?;
+    BuildDate::parse(std::str::from_utf8(&buf).ok()?)
 }
 
 // ---------------------------------------------------------------------------
@@ -187,6 +213,7 @@ pub(crate) fn build_identification(
 
     // Maker code
     id.maker_code = Some(maker.clone());
+    id.build_date = header.apploader_date;
 
     // Composite product code for display (e.g., "DOL-GALE-0" for GC, "RVL-RSBE-0" for Wii)
     let platform_prefix = match platform {
@@ -226,9 +253,13 @@ pub(crate) fn build_identification(
 }
 
 /// Read the first 0x20 bytes and check magic words without full header parsing.
+/// Compressed containers are judged by the disc header they keep uncompressed.
 ///
 /// Returns `(gc_magic_matches, wii_magic_matches)`. Seeks back to start.
 pub(crate) fn check_magic(reader: &mut dyn ReadSeek) -> Result<(bool, bool), AnalysisError> {
+    if let Some(container) = read_container_header(reader) {
+        return Ok((is_gamecube(&container.header), is_wii(&container.header)));
+    }
     reader.seek(SeekFrom::Start(0))?;
     let mut buf = [0u8; MAGIC_CHECK_SIZE];
     if reader.read(&mut buf)? < MAGIC_CHECK_SIZE {
@@ -255,20 +286,26 @@ pub(crate) fn check_magic(reader: &mut dyn ReadSeek) -> Result<(bool, bool), Ana
 /// Detects RVZ, WIA, WBFS, CISO, and GCZ by magic bytes using `nod::Disc::detect()`.
 /// Returns `false` for raw ISO/GCM or unrecognized formats. Always seeks back to start.
 pub(crate) fn is_compressed_disc(reader: &mut dyn ReadSeek) -> bool {
+    compressed_format(reader).is_some()
+}
+
+/// The container format the reader begins with, if it's one of the
+/// compressed ones. Always seeks back to start.
+fn compressed_format(reader: &mut dyn ReadSeek) -> Option<nod::Format> {
     reader.seek(SeekFrom::Start(0)).ok();
     let result = nod::Disc::detect(reader);
     reader.seek(SeekFrom::Start(0)).ok();
 
-    matches!(
-        result,
-        Ok(Some(
+    result.ok().flatten().filter(|format| {
+        matches!(
+            format,
             nod::Format::Rvz
                 | nod::Format::Wia
                 | nod::Format::Wbfs
                 | nod::Format::Ciso
                 | nod::Format::Gcz
-        ))
-    )
+        )
+    })
 }
 
 /// Returns a display name for a `nod::Format` variant.
@@ -284,11 +321,91 @@ fn nod_format_name(format: nod::Format) -> &'static str {
     }
 }
 
+/// The disc header of a compressed container, with the container's name
+/// and the size of the disc it holds when known.
+pub(crate) struct ContainerHeader {
+    pub header: NintendoDiscHeader,
+    pub format: &'static str,
+    pub disc_size: Option<u64>,
+}
+
+/// Read the disc header of a compressed container.
+///
+/// With the file path, `nod` opens the container and reads the whole header
+/// from the decompressed disc. Without it, the header comes from the copy
+/// the container keeps uncompressed (see [`read_container_header`]).
+pub(crate) fn read_compressed_disc_header(
+    reader: &mut dyn ReadSeek,
+    file_path: Option<&Path>,
+) -> Result<ContainerHeader, AnalysisError> {
+    if let Some(path) = file_path {
+        let (header, format, disc_size) = open_compressed_disc(path)?;
+        return Ok(ContainerHeader {
+            header,
+            format,
+            disc_size: Some(disc_size),
+        });
+    }
+    read_container_header(reader).ok_or_else(|| {
+        AnalysisError::invalid_format("Compressed disc format detected but no file path provided")
+    })
+}
+
+/// WIA and RVZ: offset of the ISO size in the file header.
+const WIA_ISO_SIZE_OFFSET: u64 = 0x24;
+
+/// WIA and RVZ: offset of the disc's first 0x80 bytes, in the disc struct
+/// that follows the 0x48-byte file header.
+const WIA_DISC_HEAD_OFFSET: u64 = 0x58;
+const WIA_DISC_HEAD_SIZE: usize = 0x80;
+
+/// WBFS: the first 0x100 bytes of the disc open the disc info, which starts
+/// one (1 << header byte 8) sized sector in.
+const WBFS_DISC_HEAD_SIZE: usize = 0x100;
+
+/// CISO: the first present block follows the 0x8000-byte header and map.
+const CISO_DATA_OFFSET: u64 = 0x8000;
+
+/// Read the disc header a container keeps uncompressed, without `nod`.
+///
+/// WIA and RVZ store the first 0x80 bytes of the disc (through the start of
+/// the game name), WBFS the first 0x100, and CISO stores block 0 as is. GCZ
+/// compresses every block, so it yields `None`. Fields past the stored
+/// bytes read as zero.
+pub(crate) fn read_container_header(reader: &mut dyn ReadSeek) -> Option<ContainerHeader> {
+    let format = compressed_format(reader)?;
+    let (offset, len, disc_size) = match format {
+        nod::Format::Wia | nod::Format::Rvz => {
+            let size = read_at(reader, WIA_ISO_SIZE_OFFSET, 8)?;
+            let size = u64::from_be_bytes(size.try_into().ok()?);
+            (WIA_DISC_HEAD_OFFSET, WIA_DISC_HEAD_SIZE, Some(size))
+        }
+        nod::Format::Wbfs => {
+            let shift = *read_at(reader, 8, 1)?.first()?;
+            let sector = 1u64.checked_shl(u32::from(shift))?;
+            (sector, WBFS_DISC_HEAD_SIZE, None)
+        }
+        // Block 0 must be present for the header to be there
+        nod::Format::Ciso if read_at(reader, 8, 1)? == [1] => (CISO_DATA_OFFSET, HEADER_SIZE, None),
+        _ => return None,
+    };
+    let head = read_at(reader, offset, len);
+    reader.seek(SeekFrom::Start(0)).ok();
+
+    let mut buf = [0u8; HEADER_SIZE];
+    buf[..len].copy_from_slice(&head?);
+    Some(ContainerHeader {
+        header: header_from_bytes(&buf, None),
+        format: nod_format_name(format),
+        disc_size,
+    })
+}
+
 /// Open a compressed disc image via `nod` and parse the Nintendo disc header.
 ///
 /// Returns the parsed header, format name string, and uncompressed disc size.
 /// The file path is required because `nod::Disc::new()` opens the file directly.
-pub(crate) fn open_compressed_disc(
+fn open_compressed_disc(
     path: &Path,
 ) -> Result<(NintendoDiscHeader, &'static str, u64), AnalysisError> {
     let mut disc = nod::Disc::new(path).map_err(|e| {
@@ -305,10 +422,69 @@ pub(crate) fn open_compressed_disc(
 }
 
 // ---------------------------------------------------------------------------
-// Compressed disc hashing
+// NKit
 // ---------------------------------------------------------------------------
 
-const HASH_CHUNK_SIZE: usize = 64 * 1024; // 64 KB
+/// Whether a raw image has been through NKit, which strips the junk data
+/// and padding Redump's hashes cover. NKit writes "NKIT" into the unused
+/// part of the disc header.
+pub(crate) fn is_nkit_image(reader: &mut dyn ReadSeek) -> bool {
+    read_at(reader, NKIT_MAGIC_OFFSET, 4).as_deref() == Some(b"NKIT")
+}
+
+/// The original disc's hashes, as recorded by the NKit header a WBFS or
+/// CISO container can carry. Lets such an image be matched against Redump
+/// without decompressing it.
+pub(crate) fn recorded_disc_hashes(path: &Path) -> Option<FileHashes> {
+    meta_hashes(&nod::Disc::new(path).ok()?.meta())
+}
+
+/// The hashes a container's metadata records, when it has both a CRC32
+/// and the original disc size to match it by.
+fn meta_hashes(meta: &nod::DiscMeta) -> Option<FileHashes> {
+    let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    Some(FileHashes {
+        crc32: format!("{:08x}", meta.crc32?),
+        sha1: meta.sha1.as_ref().map(|h| hex(h)),
+        md5: meta.md5.as_ref().map(|h| hex(h)),
+        sha256: None,
+        data_size: meta.disc_size?,
+    })
+}
+
+/// Report what NKit did to an image in `id.extra`: `nkit` describes it, and
+/// a container's recorded hashes of the original disc are listed as
+/// `original_crc32` and `original_sha1`.
+pub(crate) fn insert_nkit_info(
+    id: &mut RomIdentification,
+    reader: &mut dyn ReadSeek,
+    compressed: bool,
+    file_path: Option<&Path>,
+) {
+    if !compressed {
+        if is_nkit_image(reader) {
+            id.extra.insert(
+                "nkit".into(),
+                "NKit image: junk data removed, hashes won't match Redump".into(),
+            );
+        }
+        return;
+    }
+    if let Some(hashes) = file_path.and_then(recorded_disc_hashes) {
+        id.extra.insert(
+            "nkit".into(),
+            "NKit header: original disc hashes recorded".into(),
+        );
+        id.extra.insert("original_crc32".into(), hashes.crc32);
+        if let Some(sha1) = hashes.sha1 {
+            id.extra.insert("original_sha1".into(), sha1);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Hashing
+// ---------------------------------------------------------------------------
 
 /// Decompress a compressed Nintendo disc image and hash the raw data.
 ///
@@ -322,9 +498,6 @@ pub(crate) fn hash_compressed_disc(
     path: &Path,
     algorithms: HashAlgorithms,
 ) -> Result<FileHashes, AnalysisError> {
-    use sha1::Digest;
-    use std::io::Read;
-
     let mut disc = nod::Disc::new(path).map_err(|e| {
         AnalysisError::invalid_format(&format!("Failed to open compressed disc for hashing: {e}"))
     })?;
@@ -336,44 +509,241 @@ pub(crate) fn hash_compressed_disc(
         path.display(),
         data_size
     );
+    Ok(retro_junk_core::hash_reader(
+        &mut (&mut disc).take(data_size),
+        algorithms,
+    )?)
+}
+
+// ---------------------------------------------------------------------------
+// Scrub detection
+// ---------------------------------------------------------------------------
 
-    let mut crc = crc32fast::Hasher::new();
-    let mut sha: Option<sha1::Sha1> = if algorithms.sha1() {
-        Some(sha1::Sha1::new())
+/// Size of a GameCube disc image as Redump lists it.
+pub(crate) const GC_DISC_SIZE: u64 = 1_459_978_240;
+
+/// Sizes of single- and dual-layer Wii disc images as Redump lists them.
+pub(crate) const WII_DISC_SIZES: [u64; 2] = [4_699_979_776, 8_511_160_320];
+
+/// Offset of the "NKIT" magic that NKit writes into the unused part of the
+/// disc header.
+const NKIT_MAGIC_OFFSET: u64 = 0x200;
+
+/// Discs store files and junk in 32 KiB blocks (Wii "clusters").
+const DISC_BLOCK_SIZE: u64 = 0x8000;
+
+/// Bytes read at each sampled offset.
+const JUNK_SAMPLE_SIZE: usize = 0x800;
+
+/// Most offsets sampled per image.
+const MAX_JUNK_SAMPLES: usize = 16;
+
+/// Largest FST read when looking for the gaps between files.
+const MAX_FST_SIZE: u32 = 16 << 20;
+
+/// Wii partition info: four (count, table offset >> 2) pairs.
+const WII_PARTITION_INFO_OFFSET: u64 = 0x40000;
+
+/// Offset of (data offset >> 2, data size >> 2) in a Wii partition header.
+const WII_PARTITION_DATA_FIELDS: u64 = 0x2B8;
+
+/// Partitions read per table; retail discs have at most a handful.
+const MAX_WII_PARTITIONS: u32 = 16;
+
+/// Why a GameCube or Wii image can't match Redump, judged without hashing
+/// it: a lossy container, NKit conversion, zeroed junk, or a trimmed size.
+pub(crate) fn redump_mismatch_reason(
+    reader: &mut dyn ReadSeek,
+    file_path: Option<&Path>,
+) -> Option<String> {
+    if is_compressed_disc(reader) {
+        return lossy_container_reason(file_path?);
+    }
+    let header = parse_disc_header(reader).ok()?;
+    if let Some(reason) = scrub_reason(reader, &header) {
+        return Some(reason);
+    }
+    let size = retro_junk_core::util::file_size(reader).ok()?;
+    trim_reason(&header, size)
+}
+
+/// Why a compressed container can't be restored to the original disc:
+/// WBFS and CISO keep only the blocks in use unless NKit stored the junk.
+pub(crate) fn lossy_container_reason(path: &Path) -> Option<String> {
+    let meta = nod::Disc::new(path).ok()?.meta();
+    (!meta.lossless).then(|| {
+        format!(
+            "{} image without NKit junk data: the unused blocks it dropped can't be restored",
+            nod_format_name(meta.format)
+        )
+    })
+}
+
+/// Why a raw image has been processed so that it can't match Redump: NKit
+/// conversion, or junk padding zeroed ("scrubbed") to compress better.
+///
+/// Nintendo's mastering fills every unused block with pseudo-random junk
+/// (and Wii partitions are encrypted), so a 2 KiB run of one byte where
+/// junk belongs means a scrubber was there. On GameCube every sampled gap
+/// must be blank; a Wii image is scrubbed if any sample is.
+pub(crate) fn scrub_reason(
+    reader: &mut dyn ReadSeek,
+    header: &NintendoDiscHeader,
+) -> Option<String> {
+    if is_nkit_image(reader) {
+        return Some("NKit image: convert it back to ISO with NKit to verify it".into());
+    }
+
+    let wii = is_wii(header);
+    let offsets = if wii {
+        wii_junk_offsets(reader)
     } else {
-        None
+        gc_junk_offsets(reader, header)
     };
-    let mut md5_ctx: Option<md5::Context> = if algorithms.md5() {
-        Some(md5::Context::new())
+    let samples: Vec<bool> = offsets
+        .into_iter()
+        .filter_map(|offset| read_at(reader, offset, JUNK_SAMPLE_SIZE))
+        .map(|block| is_blank(&block))
+        .collect();
+
+    if wii && samples.iter().any(|&blank| blank) {
+        Some("scrubbed: unused partition data is zeroed".into())
+    } else if !wii && samples.len() >= 2 && samples.iter().all(|&blank| blank) {
+        Some("scrubbed: the junk padding between files is zeroed".into())
     } else {
         None
+    }
+}
+
+/// Why a raw image's size can't be a full disc (e.g. trimmed by wit).
+pub(crate) fn trim_reason(header: &NintendoDiscHeader, size: u64) -> Option<String> {
+    let full: &[u64] = if is_wii(header) {
+        &WII_DISC_SIZES
+    } else {
+        &[GC_DISC_SIZE]
     };
+    (!full.contains(&size)).then(|| {
+        format!(
+            "trimmed: {} isn't the size of a full disc",
+            retro_junk_core::util::format_bytes(size)
+        )
+    })
+}
 
-    let mut buf = vec![0u8; HASH_CHUNK_SIZE];
-    let mut remaining = data_size;
+/// Offsets inside the gaps between a GameCube disc's files, largest gaps
+/// first.
+fn gc_junk_offsets(reader: &mut dyn ReadSeek, header: &NintendoDiscHeader) -> Vec<u64> {
+    if header.fst_size < 12 || header.fst_size > MAX_FST_SIZE {
+        return Vec::new();
+    }
+    let Some(fst) = read_at(reader, header.fst_offset as u64, header.fst_size as usize) else {
+        return Vec::new();
+    };
+    let count = u32::from_be_bytes(fst[8..12].try_into().unwrap()) as usize;
+    let Some(entries) = fst.get(12..count.saturating_mul(12)) else {
+        return Vec::new();
+    };
 
-    while remaining > 0 {
-        let to_read = remaining.min(buf.len() as u64) as usize;
-        let n = disc.read(&mut buf[..to_read]).map_err(|e| {
-            AnalysisError::other(format!("Error reading decompressed disc data: {e}"))
-        })?;
-        if n == 0 {
-            break;
+    // Everything through the FST is system data, then one range per file
+    let mut used = vec![(0, header.fst_offset as u64 + header.fst_size as u64)];
+    for entry in entries.chunks_exact(12) {
+        if entry[0] == 0 {
+            let offset = u32::from_be_bytes(entry[4..8].try_into().unwrap()) as u64;
+            let len = u32::from_be_bytes(entry[8..12].try_into().unwrap()) as u64;
+            used.push((offset, offset + len));
+        }
+    }
+    used.sort_unstable();
+
+    let mut gaps = Vec::new();
+    let mut end = 0;
+    for (start, stop) in used {
+        if start > end {
+            gaps.push((end, start));
+        }
+        end = end.max(stop);
+    }
+    if end < GC_DISC_SIZE {
+        gaps.push((end, GC_DISC_SIZE));
+    }
+    gaps.sort_by_key(|&(start, stop)| std::cmp::Reverse(stop - start));
+    spread_samples(&gaps)
+}
+
+/// Offsets inside a Wii disc's encrypted partition data and the junk after
+/// the last partition.
+fn wii_junk_offsets(reader: &mut dyn ReadSeek) -> Vec<u64> {
+    let Some(info) = read_at(reader, WII_PARTITION_INFO_OFFSET, 32) else {
+        return Vec::new();
+    };
+    let mut regions = Vec::new();
+    for group in info.chunks_exact(8) {
+        let count = u32::from_be_bytes(group[0..4].try_into().unwrap()).min(MAX_WII_PARTITIONS);
+        let table = (u32::from_be_bytes(group[4..8].try_into().unwrap()) as u64) << 2;
+        if count == 0 {
+            continue;
+        }
+        let Some(entries) = read_at(reader, table, count as usize * 8) else {
+            continue;
+        };
+        for entry in entries.chunks_exact(8) {
+            let partition = (u32::from_be_bytes(entry[0..4].try_into().unwrap()) as u64) << 2;
+            let Some(fields) = read_at(reader, partition + WII_PARTITION_DATA_FIELDS, 8) else {
+                continue;
+            };
+            let data_offset = (u32::from_be_bytes(fields[0..4].try_into().unwrap()) as u64) << 2;
+            let data_size = (u32::from_be_bytes(fields[4..8].try_into().unwrap()) as u64) << 2;
+            let start = partition + data_offset;
+            regions.push((start, start + data_size));
+        }
+    }
+    if regions.is_empty() {
+        return Vec::new();
+    }
+    let end = regions.iter().map(|&(_, stop)| stop).max().unwrap_or(0);
+    if let Some(&disc_end) = WII_DISC_SIZES.iter().find(|&&size| size > end) {
+        regions.push((end, disc_end));
+    }
+    spread_samples(&regions)
+}
+
+/// Block-aligned offsets spread evenly across `regions`, at most
+/// [`MAX_JUNK_SAMPLES`] in all.
+fn spread_samples(regions: &[(u64, u64)]) -> Vec<u64> {
+    let per_region = (MAX_JUNK_SAMPLES / regions.len().max(1)).clamp(1, 4);
+    let mut offsets = Vec::new();
+    for &(start, stop) in regions {
+        let first = start.next_multiple_of(DISC_BLOCK_SIZE);
+        let blocks = stop.saturating_sub(first) / DISC_BLOCK_SIZE;
+        if blocks == 0 {
+            continue;
         }
-        crc.update(&buf[..n]);
-        if let Some(ref mut s) = sha {
-            s.update(&buf[..n]);
+        let n = (per_region as u64).min(blocks);
+        for k in 0..n {
+            offsets.push(first + blocks * k / n * DISC_BLOCK_SIZE);
         }
-        if let Some(ref mut m) = md5_ctx {
-            m.consume(&buf[..n]);
+        if offsets.len() >= MAX_JUNK_SAMPLES {
+            break;
         }
-        remaining -= n as u64;
     }
+    offsets.truncate(MAX_JUNK_SAMPLES);
+    offsets
+}
 
-    Ok(FileHashes {
-        crc32: format!("{:08x}", crc.finalize()),
-        sha1: sha.map(|s| format!("{:x}", s.finalize())),
-        md5: md5_ctx.map(|m| format!("{:x}", m.compute())),
-        data_size,
-    })
+/// Whether `block` is a single repeated 0x00 or 0xFF byte, as scrubbers
+/// leave it.
+fn is_blank(block: &[u8]) -> bool {
+    matches!(block.first(), Some(0x00 | 0xFF)) && block.iter().all(|&b| b == block[0])
 }
+
+/// Read `len` bytes at `offset`, or `None` if the image ends first.
+fn read_at(reader: &mut dyn ReadSeek, offset: u64, len: usize) -> Option<Vec<u8>> {
+    let mut buf = vec![0u8; len];
+    reader.seek(SeekFrom::Start(offset)).ok()?;
+    reader.read_exact(&mut buf).ok()?;
+    Some(buf)
+}
+
+#[cfg(test)]
+#[path = "tests/nintendo_disc_tests.rs"]
+mod tests;
@@ -4,17 +4,25 @@
 //! - Headered ROMs (.smc, .swc) with 512-byte copier header
 //! - Headerless ROMs (.sfc)
 //! - LoROM, HiROM, ExHiROM, SA-1, and S-DD1 mappings
+//! - BS-X Satellaview memory packs (.bs)
+//! - Sufami Turbo mini-carts (.st)
+//! - SPC music rips (.spc)
 //!
 //! SNES ROMs have no magic bytes. Detection uses a heuristic scoring system
 //! that evaluates candidate header locations and picks the best match.
+//!
+//! Satellaview memory packs and Sufami Turbo carts plug into adapters and
+//! have headers of their own, so they're recognized before the scoring
+//! runs: Sufami Turbo carts start with "BANDAI SFC-ADX", and Satellaview
+//! packs carry a map mode where cartridges keep their RAM size.
 
 use retro_junk_core::ReadSeek;
 use std::io::SeekFrom;
 
-use retro_junk_core::util::format_bytes;
+use retro_junk_core::util::{format_bytes, read_header};
 use retro_junk_core::{
-    AnalysisError, AnalysisOptions, ChecksumAlgorithm, ExpectedChecksum, Platform, Region,
-    RomAnalyzer, RomIdentification,
+    AnalysisError, AnalysisOptions, ChecksumAlgorithm, ExpectedChecksum, ParseLimits, Platform,
+    Region, RomAnalyzer, RomIdentification,
 };
 
 // ---------------------------------------------------------------------------
@@ -52,6 +60,22 @@ const OFF_EXT_EXPANSION_RAM: usize = 0x0D;
 const OFF_EXT_SPECIAL_VERSION: usize = 0x0E;
 const OFF_EXT_CARTRIDGE_SUBTYPE: usize = 0x0F;
 
+/// Magic at the start of every Sufami Turbo cart (the BIOS cart included).
+const SUFAMI_MAGIC: &[u8; 14] = b"BANDAI SFC-ADX";
+
+/// Size of the Sufami Turbo header at the start of the ROM.
+const SUFAMI_HEADER_SIZE: usize = 0x40;
+
+/// Satellaview header fields, relative to the same base as the cartridge
+/// header (the title sits at 0x10 in both).
+const OFF_BSX_MAKER_CODE: usize = 0x00; // 2 bytes ASCII
+const OFF_BSX_BLOCKS: usize = 0x20; // 4 bytes, one bit per 128 KB block
+const OFF_BSX_LIMITED_STARTS: usize = 0x24; // 2 bytes, little-endian
+const OFF_BSX_MONTH: usize = 0x26;
+const OFF_BSX_DAY: usize = 0x27;
+const OFF_BSX_MAP_MODE: usize = 0x28;
+const OFF_BSX_FIXED: usize = 0x2A; // always 0x33
+
 /// Minimum heuristic score to accept a header candidate. A single matching
 /// field (score 1) is not sufficient -- we require at least two independent
 /// indicators to avoid false positives on random data.
@@ -344,29 +368,10 @@ fn parse_header(
     has_copier: bool,
 ) -> Result<SnesHeader, AnalysisError> {
     let mut buf = [0u8; 0x30]; // 48 bytes: extended header (0x00-0x0F) + main header (0x10-0x2F)
-    reader.seek(SeekFrom::Start(offset))?;
-    reader.read_exact(&mut buf).map_err(|e| {
-        if e.kind() == std::io::ErrorKind::UnexpectedEof {
-            AnalysisError::corrupted_header("SNES header truncated")
-        } else {
-            AnalysisError::Io(e)
-        }
-    })?;
+    read_header(reader, offset, &mut buf)?;
 
     // Title: 21 bytes at offset 0x10, trim trailing spaces and nulls
-    let title_bytes = &buf[OFF_TITLE..OFF_TITLE + 21];
-    let title: String = title_bytes
-        .iter()
-        .map(|&b| {
-            if (0x20..=0x7E).contains(&b) {
-                b as char
-            } else {
-                ' '
-            }
-        })
-        .collect::<String>()
-        .trim()
-        .to_string();
+    let title = ascii_title(&buf[OFF_TITLE..OFF_TITLE + 21]);
 
     let map_mode = buf[OFF_MAP_MODE];
     let mapping = SnesMapping::from_byte(map_mode);
@@ -445,6 +450,311 @@ fn parse_header(
     })
 }
 
+/// Decode a header title, keeping printable ASCII. Other bytes (nulls,
+/// Shift-JIS) become spaces, and the result is trimmed.
+fn ascii_title(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&b| {
+            if (0x20..=0x7E).contains(&b) {
+                b as char
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+// ---------------------------------------------------------------------------
+// Sufami Turbo
+// ---------------------------------------------------------------------------
+
+/// Parsed Sufami Turbo header (the first 64 bytes of the ROM).
+#[derive(Debug, Clone)]
+pub struct SufamiHeader {
+    /// Title (up to 14 characters).
+    pub title: String,
+    /// 24-bit ID shared by a game or a series of linkable games (0 = none).
+    pub game_id: u32,
+    /// Position within the series.
+    pub series_index: u8,
+    /// CPU clock speed.
+    pub speed: SnesSpeed,
+    /// Feature byte (0x01 = SRAM or link support).
+    pub features: u8,
+    /// ROM size in bytes (128 KB units in the header).
+    pub rom_size: u64,
+    /// SRAM size in bytes (2 KB units in the header).
+    pub sram_size: u64,
+    /// Whether a 512-byte copier header was detected.
+    pub has_copier_header: bool,
+}
+
+/// Parse the Sufami Turbo header, or `None` without the magic.
+fn parse_sufami_header(
+    buf: &[u8; SUFAMI_HEADER_SIZE],
+    has_copier_header: bool,
+) -> Option<SufamiHeader> {
+    if &buf[..SUFAMI_MAGIC.len()] != SUFAMI_MAGIC {
+        return None;
+    }
+    Some(SufamiHeader {
+        title: ascii_title(&buf[0x10..0x1E]),
+        game_id: u32::from_be_bytes([0, buf[0x30], buf[0x31], buf[0x32]]),
+        series_index: buf[0x33],
+        speed: if buf[0x34] & 0x01 != 0 {
+            SnesSpeed::Fast
+        } else {
+            SnesSpeed::Slow
+        },
+        features: buf[0x35],
+        rom_size: u64::from(buf[0x36]) * 128 * 1024,
+        sram_size: u64::from(buf[0x37]) * 2 * 1024,
+        has_copier_header,
+    })
+}
+
+/// Read the Sufami Turbo header of a game cart, if this is one.
+///
+/// The BIOS cart starts with the same magic but is an ordinary LoROM
+/// cartridge, so a file that also has a valid cartridge header is left to
+/// the regular path.
+fn detect_sufami(reader: &mut dyn ReadSeek, file_size: u64) -> Option<SufamiHeader> {
+    let has_copier = detect_copier_header(file_size);
+    let copier_offset = if has_copier { COPIER_HEADER_SIZE } else { 0 };
+    let mut buf = [0u8; SUFAMI_HEADER_SIZE];
+    reader.seek(SeekFrom::Start(copier_offset)).ok()?;
+    reader.read_exact(&mut buf).ok()?;
+    let header = parse_sufami_header(&buf, has_copier)?;
+    if has_cartridge_header(reader, copier_offset + LOROM_HEADER_BASE) {
+        return None;
+    }
+    Some(header)
+}
+
+/// Whether a plausible cartridge header with a matching checksum
+/// complement sits at `offset`. Adapter formats defer to one, since their
+/// own checks are looser than a good checksum.
+fn has_cartridge_header(reader: &mut dyn ReadSeek, offset: u64) -> bool {
+    let mut buf = [0u8; 0x30];
+    if reader.seek(SeekFrom::Start(offset)).is_err() || reader.read_exact(&mut buf).is_err() {
+        return false;
+    }
+    let complement = u16::from_le_bytes([buf[OFF_COMPLEMENT], buf[OFF_COMPLEMENT + 1]]);
+    let checksum = u16::from_le_bytes([buf[OFF_CHECKSUM], buf[OFF_CHECKSUM + 1]]);
+    checksum.wrapping_add(complement) == 0xFFFF
+        && score_header_at(reader, offset) >= MIN_SCORE_THRESHOLD
+}
+
+fn sufami_identification(header: &SufamiHeader, file_size: u64) -> RomIdentification {
+    let mut id = RomIdentification::new().with_platform(Platform::Snes);
+    if !header.title.is_empty() {
+        id = id.with_internal_name(&header.title);
+    }
+    id.regions = vec![Region::Japan];
+    id.file_size = Some(file_size);
+    let copier = if header.has_copier_header {
+        COPIER_HEADER_SIZE
+    } else {
+        0
+    };
+    if header.rom_size > 0 && file_size - copier != header.rom_size {
+        id.expected_size = Some(header.rom_size + copier);
+    }
+
+    id.extra.insert("format".into(), "Sufami Turbo".into());
+    id.extra.insert("speed".into(), header.speed.name().into());
+    if header.game_id != 0 {
+        id.extra
+            .insert("sufami_game_id".into(), format!("{:06X}", header.game_id));
+        id.extra
+            .insert("series_index".into(), header.series_index.to_string());
+    }
+    if header.rom_size > 0 {
+        id.extra
+            .insert("rom_size".into(), format_bytes(header.rom_size));
+    }
+    if header.sram_size > 0 {
+        id.extra
+            .insert("sram_size".into(), format_bytes(header.sram_size));
+    }
+    if header.features & 0x01 != 0 {
+        id.extra.insert("linkable".into(), "Yes".into());
+    }
+    if header.has_copier_header {
+        id.extra.insert("copier_header".into(), "Yes".into());
+    }
+    id
+}
+
+// ---------------------------------------------------------------------------
+// BS-X Satellaview
+// ---------------------------------------------------------------------------
+
+/// Parsed header of a Satellaview memory pack (flash cart) dump.
+#[derive(Debug, Clone)]
+pub struct BsxHeader {
+    /// Title (up to 16 characters).
+    pub title: String,
+    /// 2-character maker code.
+    pub maker_code: Option<String>,
+    /// Memory mapping mode (LoROM or HiROM).
+    pub mapping: SnesMapping,
+    /// CPU clock speed.
+    pub speed: SnesSpeed,
+    /// Which 128 KB blocks of the pack the file occupies.
+    pub block_flags: u32,
+    /// Starts left for a limited-run broadcast, `None` when unlimited.
+    pub starts_remaining: Option<u32>,
+    /// Broadcast month (1-12), if set.
+    pub month: Option<u8>,
+    /// Broadcast day (1-31), if set.
+    pub day: Option<u8>,
+    /// Version number.
+    pub version: u8,
+    /// Stored checksum (little-endian u16).
+    pub checksum: u16,
+    /// Stored checksum complement (little-endian u16).
+    pub checksum_complement: u16,
+    /// Whether a 512-byte copier header was detected.
+    pub has_copier_header: bool,
+    /// Offset of the header base within the file.
+    pub header_offset: u64,
+}
+
+/// Parse a Satellaview header at a LoROM (`hirom == false`) or HiROM base,
+/// or `None` if the bytes there don't fit one.
+///
+/// The map mode byte sits where cartridges keep their RAM size code, so a
+/// BS-X map mode (0x20/0x21/0x30/0x31) matching the base is already
+/// implausible for a cartridge; the fixed 0x33 byte and a valid (or blank)
+/// broadcast date confirm it.
+fn parse_bsx_header(
+    buf: &[u8; 0x30],
+    hirom: bool,
+    has_copier_header: bool,
+    header_offset: u64,
+) -> Option<BsxHeader> {
+    let map_mode = buf[OFF_BSX_MAP_MODE];
+    if map_mode & 0xEE != 0x20 || (map_mode & 0x01 != 0) != hirom {
+        return None;
+    }
+    if buf[OFF_BSX_FIXED] != 0x33 {
+        return None;
+    }
+    let month = buf[OFF_BSX_MONTH];
+    let day = buf[OFF_BSX_DAY];
+    let month_ok = month == 0 || (month & 0x0F == 0 && (1..=12).contains(&(month >> 4)));
+    let day_ok = day == 0 || (day & 0x07 == 0 && (1..=31).contains(&(day >> 3)));
+    if !month_ok || !day_ok {
+        return None;
+    }
+
+    let maker = ascii_title(&buf[OFF_BSX_MAKER_CODE..OFF_BSX_MAKER_CODE + 2]);
+    let starts = u16::from_le_bytes([buf[OFF_BSX_LIMITED_STARTS], buf[OFF_BSX_LIMITED_STARTS + 1]]);
+    Some(BsxHeader {
+        title: ascii_title(&buf[OFF_TITLE..OFF_TITLE + 16]),
+        maker_code: if maker.is_empty() { None } else { Some(maker) },
+        mapping: if hirom {
+            SnesMapping::HiRom
+        } else {
+            SnesMapping::LoRom
+        },
+        speed: SnesSpeed::from_byte(map_mode),
+        block_flags: u32::from_le_bytes(
+            buf[OFF_BSX_BLOCKS..OFF_BSX_BLOCKS + 4].try_into().unwrap(),
+        ),
+        // Bit 15 clear means limited; each start clears one of bits 0-14
+        starts_remaining: (starts & 0x8000 == 0).then(|| (starts & 0x7FFF).count_ones()),
+        month: (month != 0).then_some(month >> 4),
+        day: (day != 0).then_some(day >> 3),
+        version: buf[OFF_VERSION],
+        checksum: u16::from_le_bytes([buf[OFF_CHECKSUM], buf[OFF_CHECKSUM + 1]]),
+        checksum_complement: u16::from_le_bytes([buf[OFF_COMPLEMENT], buf[OFF_COMPLEMENT + 1]]),
+        has_copier_header,
+        header_offset,
+    })
+}
+
+/// Find a Satellaview header at the LoROM or HiROM base, unless the other
+/// base holds a valid cartridge header.
+fn detect_bsx(reader: &mut dyn ReadSeek, file_size: u64) -> Option<BsxHeader> {
+    let has_copier = detect_copier_header(file_size);
+    let copier_offset = if has_copier { COPIER_HEADER_SIZE } else { 0 };
+    let header = [(LOROM_HEADER_BASE, false), (HIROM_HEADER_BASE, true)]
+        .into_iter()
+        .filter(|&(base, _)| copier_offset + base + 0x30 <= file_size)
+        .find_map(|(base, hirom)| {
+            let offset = copier_offset + base;
+            let mut buf = [0u8; 0x30];
+            reader.seek(SeekFrom::Start(offset)).ok()?;
+            reader.read_exact(&mut buf).ok()?;
+            parse_bsx_header(&buf, hirom, has_copier, offset)
+        })?;
+    let other = match header.mapping {
+        SnesMapping::HiRom => LOROM_HEADER_BASE,
+        _ => HIROM_HEADER_BASE,
+    };
+    if has_cartridge_header(reader, copier_offset + other) {
+        return None;
+    }
+    Some(header)
+}
+
+fn bsx_identification(header: &BsxHeader, file_size: u64) -> RomIdentification {
+    let mut id = RomIdentification::new().with_platform(Platform::Snes);
+    if !header.title.is_empty() {
+        id = id.with_internal_name(&header.title);
+    }
+    id.version = Some(format!("1.{}", header.version));
+    if let Some(ref maker) = header.maker_code {
+        id.maker_code = Some(match crate::licensee::maker_code_name(maker) {
+            Some(name) => format!("{} ({})", maker, name),
+            None => maker.clone(),
+        });
+    }
+    id.regions = vec![Region::Japan];
+    id.file_size = Some(file_size);
+
+    id.extra
+        .insert("format".into(), "BS-X Satellaview memory pack".into());
+    id.extra
+        .insert("mapping".into(), header.mapping.name().into());
+    id.extra.insert("speed".into(), header.speed.name().into());
+    if header.block_flags != 0 {
+        id.extra.insert(
+            "pack_blocks".into(),
+            format!(
+                "{} ({})",
+                header.block_flags.count_ones(),
+                format_bytes(u64::from(header.block_flags.count_ones()) * 128 * 1024)
+            ),
+        );
+    }
+    id.extra.insert(
+        "limited_starts".into(),
+        match header.starts_remaining {
+            Some(n) => format!("{} remaining", n),
+            None => "Unlimited".into(),
+        },
+    );
+    if let (Some(month), Some(day)) = (header.month, header.day) {
+        id.extra
+            .insert("broadcast_date".into(), format!("{:02}-{:02}", month, day));
+    }
+    if header.has_copier_header {
+        id.extra.insert("copier_header".into(), "Yes".into());
+    }
+    let complement_valid = header.checksum.wrapping_add(header.checksum_complement) == 0xFFFF;
+    id.extra.insert(
+        "checksum_complement_valid".into(),
+        if complement_valid { "Yes" } else { "No" }.into(),
+    );
+    id
+}
+
 // ---------------------------------------------------------------------------
 // Checksum computation
 // ---------------------------------------------------------------------------
@@ -454,9 +764,10 @@ fn parse_header(
 ///
 /// For non-power-of-2 ROM sizes, the remainder after the largest power-of-2
 /// block is mirrored (repeated) to fill the gap up to the next power of 2.
-fn compute_snes_checksum(
+pub(crate) fn compute_snes_checksum(
     reader: &mut dyn ReadSeek,
     has_copier: bool,
+    limits: &ParseLimits,
 ) -> Result<u16, AnalysisError> {
     let file_size = reader.seek(SeekFrom::End(0))?;
     let copier_offset = if has_copier { COPIER_HEADER_SIZE } else { 0 };
@@ -468,7 +779,7 @@ fn compute_snes_checksum(
 
     // Read entire ROM data (after copier header)
     reader.seek(SeekFrom::Start(copier_offset))?;
-    let mut rom_data = vec![0u8; rom_size as usize];
+    let mut rom_data = vec![0u8; limits.check_alloc(rom_size, "ROM")?];
     reader.read_exact(&mut rom_data)?;
 
     // Sum ALL bytes as-is (don't zero the checksum fields). The SNES checksum
@@ -793,17 +1104,36 @@ impl RomAnalyzer for SnesAnalyzer {
             });
         }
 
+        if let Some(header) = detect_sufami(reader, file_size) {
+            let mut id = sufami_identification(&header, file_size);
+            let copier = if header.has_copier_header {
+                COPIER_HEADER_SIZE
+            } else {
+                0
+            };
+            id.attach_raw_header(reader, copier, SUFAMI_HEADER_SIZE, options)?;
+            return Ok(id);
+        }
+        if let Some(header) = detect_bsx(reader, file_size) {
+            let mut id = bsx_identification(&header, file_size);
+            id.attach_raw_header(reader, header.header_offset, 0x50, options)?;
+            return Ok(id);
+        }
+
         let (header_offset, has_copier) = detect_mapping(reader, file_size)?;
         let header = parse_header(reader, header_offset, has_copier)?;
 
         // Compute checksum unless in quick mode
         let computed_checksum = if !options.quick {
-            compute_snes_checksum(reader, has_copier).ok()
+            compute_snes_checksum(reader, has_copier, &options.limits).ok()
         } else {
             None
         };
 
-        Ok(to_identification(&header, file_size, computed_checksum))
+        let mut id = to_identification(&header, file_size, computed_checksum);
+        // Expanded header plus the standard header (0x50 bytes from the base)
+        id.attach_raw_header(reader, header_offset, 0x50, options)?;
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -811,7 +1141,7 @@ impl RomAnalyzer for SnesAnalyzer {
     }
 
     fn file_extensions(&self) -> &'static [&'static str] {
-        &["sfc", "smc", "swc", "fig"]
+        &["sfc", "smc", "swc", "fig", "bs", "st"]
     }
 
     fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
@@ -822,11 +1152,36 @@ impl RomAnalyzer for SnesAnalyzer {
             return false;
         }
 
-        detect_mapping(reader, file_size).is_ok()
+        detect_sufami(reader, file_size).is_some()
+            || detect_bsx(reader, file_size).is_some()
+            || detect_mapping(reader, file_size).is_ok()
+    }
+
+    fn music_extensions(&self) -> &'static [&'static str] {
+        crate::music::SNES_EXTENSIONS
+    }
+
+    fn analyze_music(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        crate::music::analyze(reader, options, Platform::Snes)
+    }
+
+    /// Enough for the LoROM and HiROM headers behind a copier header.
+    /// ExHiROM headers sit past 4 MB, so those images are only recognized
+    /// when their HiROM-position header also scores.
+    fn sniff_len(&self) -> usize {
+        (COPIER_HEADER_SIZE + HIROM_HEADER_BASE + 0x30) as usize
     }
 
     fn dat_names(&self) -> &'static [&'static str] {
-        &["Nintendo - Super Nintendo Entertainment System"]
+        &[
+            "Nintendo - Super Nintendo Entertainment System",
+            "Nintendo - Satellaview",
+            "Nintendo - Sufami Turbo",
+        ]
     }
 
     fn gdb_csv_names(&self) -> &'static [&'static str] {
@@ -0,0 +1,356 @@
+//! Synthetic ROM images for Nintendo platforms.
+//!
+//! Each builder produces the smallest image its analyzer fully accepts,
+//! with checksums computed by the same code the analyzer verifies them with.
+
+use std::io::Cursor;
+
+use retro_junk_core::{ParseLimits, Platform, SyntheticRom};
+use sha2::{Digest, Sha256};
+
+use crate::constants::NINTENDO_LOGO_156;
+use crate::n3ds::{MEDIA_UNIT, NCCH_MAGIC, NCSD_MAGIC};
+use crate::n64::CicVariant;
+use crate::n64_byteorder::{MAGIC_Z64, N64Format};
+use crate::nintendo_disc::{GC_MAGIC, WII_MAGIC};
+use crate::wux::WUX_MAGIC;
+
+/// The synthetic image for `platform`, if this crate can build one.
+pub fn synthesize(platform: Platform) -> Option<SyntheticRom> {
+    match platform {
+        Platform::Nes => Some(nes()),
+        Platform::Snes => Some(snes()),
+        Platform::N64 => Some(n64()),
+        Platform::GameCube => Some(gamecube()),
+        Platform::Wii => Some(wii()),
+        Platform::GameBoy => Some(game_boy()),
+        Platform::Gba => Some(gba()),
+        Platform::Ds => Some(ds()),
+        Platform::N3ds => Some(n3ds_cci()),
+        Platform::WiiU => Some(wiiu()),
+        _ => None,
+    }
+}
+
+/// iNES ROM with 16 KB PRG and 8 KB CHR (mapper 0).
+pub fn nes() -> SyntheticRom {
+    let mut rom = vec![0u8; 16 + 16 * 1024 + 8 * 1024];
+    rom[0..4].copy_from_slice(&crate::nes::INES_MAGIC);
+    rom[4] = 1; // PRG banks
+    rom[5] = 1; // CHR banks
+    SyntheticRom::new(Platform::Nes, "synthetic.nes", rom)
+}
+
+/// 256 KB LoROM titled "TEST ROM".
+pub fn snes() -> SyntheticRom {
+    let mut rom = vec![0u8; 256 * 1024];
+    let header = 0x7FC0;
+    rom[header..header + 21].copy_from_slice(b"TEST ROM             ");
+    rom[header + 0x15] = 0x20; // map mode: LoROM, SlowROM
+    rom[header + 0x17] = 0x08; // ROM size: 1 << 8 KB
+    rom[header + 0x19] = 0x01; // country: USA
+    rom[header + 0x1A] = 0x01; // developer: Nintendo
+
+    // Checksum + complement always sum to 0xFFFF, so the total is the same
+    // whatever pair ends up written
+    rom[header + 0x1C..header + 0x1E].copy_from_slice(&0xFFFFu16.to_le_bytes());
+    let sum =
+        crate::snes::compute_snes_checksum(&mut Cursor::new(&rom), false, &ParseLimits::default())
+            .expect("in-memory ROM");
+    rom[header + 0x1C..header + 0x1E].copy_from_slice(&(!sum).to_le_bytes());
+    rom[header + 0x1E..header + 0x20].copy_from_slice(&sum.to_le_bytes());
+    SyntheticRom::new(Platform::Snes, "synthetic.sfc", rom)
+}
+
+/// Big-endian (z64) ROM just large enough to cover the CRC range.
+pub fn n64() -> SyntheticRom {
+    let mut rom = vec![0u8; 0x101000];
+    rom[0..4].copy_from_slice(&MAGIC_Z64);
+    rom[0x04..0x08].copy_from_slice(&0x0000000Fu32.to_be_bytes()); // clock rate
+    rom[0x08..0x0C].copy_from_slice(&0x80000400u32.to_be_bytes()); // boot address
+    rom[0x20..0x34].copy_from_slice(b"SYNTHETIC           ");
+    rom[0x3B] = b'N'; // category: Game Pak
+    rom[0x3C..0x3E].copy_from_slice(b"ZZ");
+    rom[0x3E] = b'E'; // destination: USA
+    for (i, b) in rom[0x1000..].iter_mut().enumerate() {
+        *b = (i * 7 + 3) as u8;
+    }
+
+    let (crc1, crc2) =
+        crate::n64::compute_n64_crc(&mut Cursor::new(&rom), N64Format::Z64, CicVariant::Unknown)
+            .expect("in-memory ROM");
+    rom[0x10..0x14].copy_from_slice(&crc1.to_be_bytes());
+    rom[0x14..0x18].copy_from_slice(&crc2.to_be_bytes());
+    SyntheticRom::new(Platform::N64, "synthetic.z64", rom)
+}
+
+/// Disc header shared by GameCube and Wii images.
+fn nintendo_disc(game_code: &[u8; 4], name: &str) -> Vec<u8> {
+    let mut disc = vec![0u8; 8 * 1024];
+    disc[0..4].copy_from_slice(game_code);
+    disc[4..6].copy_from_slice(b"01"); // maker: Nintendo
+    disc[0x20..0x20 + name.len()].copy_from_slice(name.as_bytes());
+    disc[0x420..0x424].copy_from_slice(&0x00040000u32.to_be_bytes()); // DOL offset
+    disc[0x424..0x428].copy_from_slice(&0x00080000u32.to_be_bytes()); // FST offset
+    disc[0x428..0x42C].copy_from_slice(&0x00001000u32.to_be_bytes()); // FST size
+    disc
+}
+
+/// 8 KB GameCube disc header.
+pub fn gamecube() -> SyntheticRom {
+    let mut disc = nintendo_disc(b"GZZE", "SYNTHETIC");
+    disc[0x1C..0x20].copy_from_slice(&GC_MAGIC.to_be_bytes());
+    SyntheticRom::new(Platform::GameCube, "synthetic.iso", disc)
+}
+
+/// 8 KB Wii disc header.
+pub fn wii() -> SyntheticRom {
+    let mut disc = nintendo_disc(b"RZZE", "SYNTHETIC");
+    disc[0x18..0x1C].copy_from_slice(&WII_MAGIC.to_be_bytes());
+    SyntheticRom::new(Platform::Wii, "synthetic.iso", disc)
+}
+
+/// 32 KB DMG ROM titled "TESTGAME", with both checksums set.
+pub fn game_boy() -> SyntheticRom {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x100..0x104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // NOP; JP $0150
+    rom[0x104..0x134].copy_from_slice(&crate::gameboy::NINTENDO_LOGO);
+    rom[0x134..0x13C].copy_from_slice(b"TESTGAME");
+    rom[0x14A] = 0x01; // destination: international
+    rom[0x14B] = 0x01; // licensee: Nintendo
+
+    rom[0x14D] =
+        crate::gameboy::compute_header_checksum(&mut Cursor::new(&rom)).expect("in-memory ROM");
+    let global =
+        crate::gameboy::compute_global_checksum(&mut Cursor::new(&rom)).expect("in-memory ROM");
+    rom[0x14E..0x150].copy_from_slice(&global.to_be_bytes());
+    SyntheticRom::new(Platform::GameBoy, "synthetic.gb", rom)
+}
+
+/// 256 KB ROM with game code ATEJ, titled "TESTGAME".
+pub fn gba() -> SyntheticRom {
+    let mut rom = vec![0u8; 256 * 1024];
+    rom[0x03] = 0xEA; // branch over the header
+    rom[0x04..0x04 + 156].copy_from_slice(&NINTENDO_LOGO_156);
+    rom[0xA0..0xA8].copy_from_slice(b"TESTGAME");
+    rom[0xAC..0xB0].copy_from_slice(b"ATEJ");
+    rom[0xB0..0xB2].copy_from_slice(b"01");
+    rom[0xB2] = crate::gba::FIXED_VALUE;
+
+    rom[0xBD] = crate::gba::compute_header_checksum(&mut Cursor::new(&rom)).expect("in-memory ROM");
+    SyntheticRom::new(Platform::Gba, "synthetic.gba", rom)
+}
+
+/// 64 KB ROM with game code ADME and a decrypted secure area.
+pub fn ds() -> SyntheticRom {
+    let size: usize = 0x10000;
+    let mut rom = vec![0u8; size];
+    rom[0x000..0x008].copy_from_slice(b"TESTGAME");
+    rom[0x00C..0x010].copy_from_slice(b"ADME");
+    rom[0x010..0x012].copy_from_slice(b"01");
+    rom[0x020..0x024].copy_from_slice(&0x4000u32.to_le_bytes()); // ARM9 offset
+    rom[0x02C..0x030].copy_from_slice(&0x1000u32.to_le_bytes()); // ARM9 size
+    rom[0x030..0x034].copy_from_slice(&0x8000u32.to_le_bytes()); // ARM7 offset
+    rom[0x03C..0x040].copy_from_slice(&0x800u32.to_le_bytes()); // ARM7 size
+    rom[0x080..0x084].copy_from_slice(&(size as u32).to_le_bytes()); // used ROM size
+    rom[0x084..0x088].copy_from_slice(&0x4000u32.to_le_bytes()); // header size
+    rom[0x0C0..0x15C].copy_from_slice(&NINTENDO_LOGO_156);
+    let logo_crc = crate::ds::crc16(&rom[0x0C0..0x15C]);
+    rom[0x15C..0x15E].copy_from_slice(&logo_crc.to_le_bytes());
+    rom[0x4000..0x4008].copy_from_slice(&crate::ds::DECRYPTED_SECURE_AREA_MAGIC);
+
+    let header_crc =
+        crate::ds::compute_header_checksum(&mut Cursor::new(&rom)).expect("in-memory ROM");
+    rom[0x15E..0x160].copy_from_slice(&header_crc.to_le_bytes());
+    SyntheticRom::new(Platform::Ds, "synthetic.nds", rom)
+}
+
+/// Title ID shared by the 3DS images.
+pub const N3DS_TITLE_ID: u64 = 0x0004000000ABCDEF;
+
+/// Game card image (CCI) with one NCCH partition, product code CTR-P-ABCE.
+pub fn n3ds_cci() -> SyntheticRom {
+    SyntheticRom::new(
+        Platform::N3ds,
+        "synthetic.3ds",
+        make_cci_with(N3DS_TITLE_ID, 0),
+    )
+}
+
+/// A card-dumped CCI of `title_id` at title `version`, with one NoCrypto
+/// NCCH partition whose ExHeader and ExeFS hashes verify. The used size
+/// covers half the partition, as on real cards.
+pub fn make_cci_with(title_id: u64, version: u16) -> Vec<u8> {
+    let partition_offset: usize = 0x4000;
+    let content_size_mu: u32 = 0x100;
+    let total_size = partition_offset + content_size_mu as usize * MEDIA_UNIT as usize;
+    let mut rom = vec![0u8; total_size];
+
+    rom[0x00] = 0xAB; // signature (not checked, but present on cards)
+    rom[0x01] = 0xCD;
+    rom[0x100..0x104].copy_from_slice(&NCSD_MAGIC);
+    rom[0x104..0x108].copy_from_slice(&((total_size as u64 / MEDIA_UNIT) as u32).to_le_bytes());
+    rom[0x108..0x110].copy_from_slice(&title_id.to_le_bytes());
+    rom[0x120..0x124]
+        .copy_from_slice(&((partition_offset as u64 / MEDIA_UNIT) as u32).to_le_bytes());
+    rom[0x124..0x128].copy_from_slice(&content_size_mu.to_le_bytes());
+    rom[0x188 + 4] = 1; // media platform: CTR
+    rom[0x188 + 5] = 1; // media type: card1
+    rom[0x200..0x204].copy_from_slice(&0xFFFFFFFFu32.to_le_bytes()); // writable address
+    let filled = partition_offset + content_size_mu as usize * MEDIA_UNIT as usize / 2;
+    rom[0x300..0x304].copy_from_slice(&(filled as u32).to_le_bytes());
+    rom[0x310..0x312].copy_from_slice(&version.to_le_bytes());
+    rom[0x1000] = 0x42; // card seed
+    rom[0x1001] = 0x37;
+
+    let ncch = &mut rom[partition_offset..];
+    ncch[0x100..0x104].copy_from_slice(&NCCH_MAGIC);
+    ncch[0x104..0x108].copy_from_slice(&content_size_mu.to_le_bytes());
+    ncch[0x108..0x110].copy_from_slice(&title_id.to_le_bytes()); // partition ID
+    ncch[0x110..0x112].copy_from_slice(b"31"); // maker
+    ncch[0x112..0x114].copy_from_slice(&2u16.to_le_bytes()); // NCCH version
+    ncch[0x118..0x120].copy_from_slice(&title_id.to_le_bytes()); // program ID
+    ncch[0x150..0x15A].copy_from_slice(b"CTR-P-ABCE");
+    ncch[0x180..0x184].copy_from_slice(&0x400u32.to_le_bytes()); // exheader size
+    ncch[0x188 + 4] = 0x01; // platform: CTR
+    ncch[0x188 + 5] = 0x03; // content type: executable
+    ncch[0x188 + 7] = 0x04; // flags: no crypto
+    // ExeFS right after the header and ExHeader: 16 units at unit 5, the
+    // first of them hashed
+    ncch[0x1A0..0x1A4].copy_from_slice(&5u32.to_le_bytes());
+    ncch[0x1A4..0x1A8].copy_from_slice(&16u32.to_le_bytes());
+    ncch[0x1A8..0x1AC].copy_from_slice(&1u32.to_le_bytes());
+
+    for (i, b) in ncch[0x200..0x600].iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    let exheader_hash = Sha256::digest(&ncch[0x200..0x600]);
+    ncch[0x160..0x180].copy_from_slice(&exheader_hash);
+
+    let exefs = 5 * MEDIA_UNIT as usize;
+    for (i, b) in ncch[exefs..exefs + MEDIA_UNIT as usize]
+        .iter_mut()
+        .enumerate()
+    {
+        *b = (i * 3) as u8;
+    }
+    let exefs_hash = Sha256::digest(&ncch[exefs..exefs + MEDIA_UNIT as usize]);
+    ncch[0x1C0..0x1E0].copy_from_slice(&exefs_hash);
+    rom
+}
+
+/// The plaintext start of a WUD for product code WUP-P-AZZE. The rest of a
+/// real disc is encrypted, so nothing past the code is read.
+pub fn wiiu() -> SyntheticRom {
+    let mut disc = vec![0u8; 0x8000];
+    disc[..10].copy_from_slice(b"WUP-P-AZZE");
+    SyntheticRom::new(Platform::WiiU, "synthetic.wud", disc)
+}
+
+/// Pack a WUD into a WUX image with `sector_size` sectors, storing each
+/// distinct sector once. A short last sector is zero-padded when stored.
+pub fn make_wux(wud: &[u8], sector_size: usize) -> Vec<u8> {
+    let mut stored: Vec<Vec<u8>> = Vec::new();
+    let mut index = Vec::new();
+    for sector in wud.chunks(sector_size) {
+        let mut sector = sector.to_vec();
+        sector.resize(sector_size, 0);
+        let i = match stored.iter().position(|s| *s == sector) {
+            Some(i) => i,
+            None => {
+                stored.push(sector);
+                stored.len() - 1
+            }
+        };
+        index.push(i as u32);
+    }
+
+    let mut wux = Vec::new();
+    wux.extend_from_slice(WUX_MAGIC);
+    wux.extend_from_slice(&(sector_size as u32).to_le_bytes());
+    wux.extend_from_slice(&[0; 4]);
+    wux.extend_from_slice(&(wud.len() as u64).to_le_bytes());
+    wux.extend_from_slice(&[0; 8]); // flags, padding
+    for i in index {
+        wux.extend_from_slice(&i.to_le_bytes());
+    }
+    wux.resize(wux.len().next_multiple_of(sector_size), 0);
+    for sector in stored {
+        wux.extend_from_slice(&sector);
+    }
+    wux
+}
+
+/// A Wii U title.tmd (RSA-2048 signed) for `title_id` at `version`, listing
+/// one content.
+pub fn wiiu_tmd(title_id: u64, version: u16) -> Vec<u8> {
+    let mut tmd = vec![0u8; 0x140 + 0xC4 + 0x30];
+    tmd[0x00..0x04].copy_from_slice(&0x00010004u32.to_be_bytes());
+    let header = 0x140;
+    tmd[header + 0x4C..header + 0x54].copy_from_slice(&title_id.to_be_bytes());
+    tmd[header + 0x9C..header + 0x9E].copy_from_slice(&version.to_be_bytes());
+    tmd[header + 0x9E..header + 0xA0].copy_from_slice(&1u16.to_be_bytes());
+    tmd
+}
+
+/// Installable archive (CIA) holding one NCCH, product code CTR-N-ABCJ.
+pub fn n3ds_cia() -> SyntheticRom {
+    SyntheticRom::new(
+        Platform::N3ds,
+        "synthetic.cia",
+        make_cia_with(N3DS_TITLE_ID, 0x0410),
+    )
+}
+
+/// A CIA of `title_id` at title version 1.1.0 (0x0410) holding one NCCH,
+/// whose ticket records `ticket_version`.
+pub fn make_cia_with(title_id: u64, ticket_version: u16) -> Vec<u8> {
+    let header_size: u32 = 0x2020;
+    let cert_chain_size: u32 = 0x0A00;
+    let ticket_size: u32 = 0x0350;
+    let tmd_size: u32 = 0x0208;
+    let ncch_size: u64 = 0x10000;
+    let align = |cia: &mut Vec<u8>| cia.resize(cia.len().next_multiple_of(64), 0);
+
+    let mut cia = vec![0u8; header_size as usize];
+    cia[0x00..0x04].copy_from_slice(&header_size.to_le_bytes());
+    cia[0x08..0x0C].copy_from_slice(&cert_chain_size.to_le_bytes());
+    cia[0x0C..0x10].copy_from_slice(&ticket_size.to_le_bytes());
+    cia[0x10..0x14].copy_from_slice(&tmd_size.to_le_bytes());
+    cia[0x18..0x20].copy_from_slice(&ncch_size.to_le_bytes());
+    cia[0x20] = 0x80; // content index: content 0 present
+    align(&mut cia);
+
+    cia.resize(cia.len() + cert_chain_size as usize, 0xCC);
+    align(&mut cia);
+
+    let mut ticket = vec![0u8; ticket_size as usize];
+    ticket[0x00..0x04].copy_from_slice(&0x00010004u32.to_be_bytes()); // RSA-2048 signature
+    ticket[0x1DC..0x1E4].copy_from_slice(&title_id.to_be_bytes());
+    ticket[0x1E6..0x1E8].copy_from_slice(&ticket_version.to_be_bytes());
+    cia.extend_from_slice(&ticket);
+    align(&mut cia);
+
+    let mut tmd = vec![0u8; tmd_size as usize];
+    tmd[0x00..0x04].copy_from_slice(&0x00010004u32.to_be_bytes());
+    let tmd_header = 0x140;
+    tmd[tmd_header + 0x4C..tmd_header + 0x54].copy_from_slice(&title_id.to_be_bytes());
+    tmd[tmd_header + 0x9C..tmd_header + 0x9E].copy_from_slice(&0x0410u16.to_be_bytes()); // version
+    tmd[tmd_header + 0x9E..tmd_header + 0xA0].copy_from_slice(&1u16.to_be_bytes()); // contents
+    cia.extend_from_slice(&tmd);
+    align(&mut cia);
+
+    let mut ncch = vec![0u8; ncch_size as usize];
+    ncch[0x100..0x104].copy_from_slice(&NCCH_MAGIC);
+    ncch[0x104..0x108].copy_from_slice(&((ncch_size / MEDIA_UNIT) as u32).to_le_bytes());
+    ncch[0x108..0x110].copy_from_slice(&title_id.to_le_bytes()); // partition ID
+    ncch[0x110..0x112].copy_from_slice(b"31");
+    ncch[0x118..0x120].copy_from_slice(&title_id.to_le_bytes()); // program ID
+    ncch[0x150..0x15A].copy_from_slice(b"CTR-N-ABCJ");
+    ncch[0x188 + 4] = 0x01;
+    ncch[0x188 + 5] = 0x03;
+    ncch[0x188 + 7] = 0x04;
+    cia.extend_from_slice(&ncch);
+    align(&mut cia);
+    cia
+}
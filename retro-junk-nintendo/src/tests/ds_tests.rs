@@ -572,3 +572,57 @@ fn test_extract_dat_game_code_unknown_prefix() {
     let analyzer = DsAnalyzer;
     assert_eq!(analyzer.extract_dat_game_code("XXX-ABCD"), None);
 }
+
+#[test]
+fn test_serial_normalized_and_validated() {
+    let analyzer = DsAnalyzer;
+    assert_eq!(analyzer.normalize_serial(" ntr_adme "), "NTR-ADME");
+    assert!(analyzer.is_valid_serial("NTR-ADME"));
+    assert!(analyzer.is_valid_serial("twl irbo"));
+    assert!(!analyzer.is_valid_serial("NTR-ADME-USA"));
+    assert!(!analyzer.is_valid_serial("AGB-ADME"));
+}
+
+/// Write a UTF-16LE string into a banner title slot.
+fn write_banner_title(rom: &mut [u8], banner: usize, slot: usize, text: &str) {
+    let start = banner + BANNER_TITLES_OFFSET + slot * BANNER_TITLE_SIZE;
+    for (i, unit) in text.encode_utf16().enumerate() {
+        rom[start + i * 2..start + i * 2 + 2].copy_from_slice(&unit.to_le_bytes());
+    }
+}
+
+#[test]
+fn test_banner_titles() {
+    let mut rom = make_nds_rom();
+    let banner = 0x9000;
+    rom[0x068..0x06C].copy_from_slice(&(banner as u32).to_le_bytes());
+    rom[banner..banner + 2].copy_from_slice(&1u16.to_le_bytes());
+    write_banner_title(&mut rom, banner, 0, "ぷよぷよ\nSEGA");
+    write_banner_title(&mut rom, banner, 1, "Puyo Pop\nFever Edition\nSEGA");
+    write_banner_title(&mut rom, banner, 2, "Puyo Pop");
+
+    let result = DsAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.titles.len(), 3);
+    assert_eq!(result.titles[&Language::Japanese], "ぷよぷよ");
+    assert_eq!(result.titles[&Language::English], "Puyo Pop Fever Edition");
+    // A single line has no publisher to drop
+    assert_eq!(result.titles[&Language::French], "Puyo Pop");
+    assert_eq!(
+        result.title_for(Some(Language::German)),
+        Some("Puyo Pop Fever Edition")
+    );
+}
+
+#[test]
+fn test_no_banner_no_titles() {
+    let result = DsAnalyzer
+        .analyze(
+            &mut Cursor::new(make_nds_rom()),
+            &AnalysisOptions::default(),
+        )
+        .unwrap();
+    assert!(result.titles.is_empty());
+    assert_eq!(result.title_for(Some(Language::English)), Some("TESTGAME"));
+}
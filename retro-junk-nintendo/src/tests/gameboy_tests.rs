@@ -329,3 +329,22 @@ fn recompute_checksums(rom: &mut Vec<u8>) {
     rom[0x014E] = (global >> 8) as u8;
     rom[0x014F] = (global & 0xFF) as u8;
 }
+
+#[test]
+fn test_raw_header_only_when_requested() {
+    let rom = make_gb_rom();
+    let analyzer = GameBoyAnalyzer;
+
+    let result = analyzer
+        .analyze(&mut Cursor::new(rom.clone()), &AnalysisOptions::default())
+        .unwrap();
+    assert!(result.raw_header.is_none());
+
+    let options = AnalysisOptions::new().include_raw_header(true);
+    let result = analyzer
+        .analyze(&mut Cursor::new(rom.clone()), &options)
+        .unwrap();
+    let raw = result.raw_header.unwrap();
+    assert_eq!(raw.offset, 0x100);
+    assert_eq!(raw.bytes(), &rom[0x100..0x150]);
+}
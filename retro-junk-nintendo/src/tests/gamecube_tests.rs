@@ -47,6 +47,84 @@ fn test_compressed_detection_resets_position() {
     assert_eq!(cursor.position(), 0);
 }
 
+/// Build the start of a WIA or RVZ file: the file header with the ISO size,
+/// then the disc struct holding the disc's first 0x80 bytes.
+fn make_wia_container(magic: &[u8; 4], disc: &[u8]) -> Vec<u8> {
+    let mut data = vec![0u8; 0x100];
+    data[0..4].copy_from_slice(magic);
+    data[0x24..0x2C].copy_from_slice(&(disc.len() as u64).to_be_bytes());
+    data[0x58..0xD8].copy_from_slice(&disc[..0x80]);
+    data
+}
+
+/// Build the start of a WBFS file with 512-byte HD sectors; the disc
+/// header copy opens the second sector.
+fn make_wbfs_container(disc: &[u8]) -> Vec<u8> {
+    let mut data = vec![0u8; 0x400];
+    data[0..4].copy_from_slice(b"WBFS");
+    data[8] = 9;
+    data[9] = 21;
+    data[0x200..0x300].copy_from_slice(&disc[..0x100]);
+    data
+}
+
+/// Build a CISO file storing the disc's first 2 MB block as is.
+fn make_ciso_container(disc: &[u8]) -> Vec<u8> {
+    let mut data = vec![0u8; 0x8000];
+    data[0..4].copy_from_slice(b"CISO");
+    data[4..8].copy_from_slice(&0x20_0000u32.to_le_bytes());
+    data[8] = 1;
+    data.extend_from_slice(disc);
+    data
+}
+
+#[test]
+fn test_container_headers_are_read_without_a_path() {
+    let disc = make_default_gc_disc();
+    let containers = [
+        (make_wia_container(b"RVZ\x01", &disc), "RVZ"),
+        (make_wia_container(b"WIA\x01", &disc), "WIA"),
+        (make_wbfs_container(&disc), "WBFS"),
+        (make_ciso_container(&disc), "CISO"),
+    ];
+    for (data, format) in containers {
+        assert!(GameCubeAnalyzer.can_handle(&mut Cursor::new(data.clone())));
+        let id = GameCubeAnalyzer
+            .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+            .unwrap();
+        assert_eq!(id.serial_number.as_deref(), Some("GALE"));
+        assert_eq!(id.internal_name.as_deref(), Some("THE LEGEND OF ZELDA"));
+        assert_eq!(id.extra.get("format").map(String::as_str), Some(format));
+    }
+}
+
+#[test]
+fn test_container_header_reports_wia_disc_size() {
+    let disc = make_default_gc_disc();
+    let container = nintendo_disc::read_container_header(&mut Cursor::new(make_wia_container(
+        b"RVZ\x01", &disc,
+    )))
+    .unwrap();
+    assert_eq!(container.disc_size, Some(disc.len() as u64));
+    assert_eq!(&container.header.game_code, b"GALE");
+}
+
+#[test]
+fn test_ciso_without_first_block_has_no_header() {
+    let mut data = make_ciso_container(&make_default_gc_disc());
+    data[8] = 0;
+    assert!(nintendo_disc::read_container_header(&mut Cursor::new(data.clone())).is_none());
+    assert!(!GameCubeAnalyzer.can_handle(&mut Cursor::new(data)));
+}
+
+#[test]
+fn test_wii_container_rejected() {
+    let mut disc = make_default_gc_disc();
+    disc[0x18..0x1C].copy_from_slice(&[0x5D, 0x1C, 0x9E, 0xA3]);
+    disc[0x1C..0x20].fill(0);
+    assert!(!GameCubeAnalyzer.can_handle(&mut Cursor::new(make_wbfs_container(&disc))));
+}
+
 /// Build a synthetic GameCube disc image with a valid header.
 ///
 /// The image is 8 KB (enough for header + some padding). The game code,
@@ -152,7 +230,7 @@ fn test_basic_analysis() {
         id.extra.get("product_code").map(|s| s.as_str()),
         Some("DOL-GALE-0")
     );
-    assert_eq!(id.expected_size, Some(GCM_DISC_SIZE));
+    assert_eq!(id.expected_size, Some(nintendo_disc::GC_DISC_SIZE));
 }
 
 #[test]
@@ -259,6 +337,15 @@ fn test_extract_dat_game_code_invalid() {
     assert_eq!(analyzer.extract_dat_game_code("GA"), None);
 }
 
+#[test]
+fn test_serial_validated_as_game_code() {
+    let analyzer = GameCubeAnalyzer;
+    assert_eq!(analyzer.normalize_serial(" gale "), "GALE");
+    assert!(analyzer.is_valid_serial("GALE"));
+    assert!(!analyzer.is_valid_serial("GALE01"));
+    assert!(!analyzer.is_valid_serial("DL-DOL-GALE-USA"));
+}
+
 #[test]
 fn test_expects_serial() {
     let analyzer = GameCubeAnalyzer;
@@ -300,3 +387,142 @@ fn test_container_hashes_returns_none_for_raw_iso() {
         "Raw ISO should return None (use standard hasher)"
     );
 }
+
+#[test]
+fn test_apploader_build_date() {
+    let mut disc = make_default_gc_disc();
+    disc.resize(0x3000, 0);
+    let offset = nintendo_disc::APPLOADER_DATE_OFFSET as usize;
+    disc[offset..offset + 10].copy_from_slice(b"2003/02/21");
+
+    let result = GameCubeAnalyzer
+        .analyze(&mut Cursor::new(disc), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(
+        result.build_date.map(|d| d.to_string()).as_deref(),
+        Some("2003-02-21")
+    );
+
+    // The default test disc is too short to hold an apploader
+    let result = GameCubeAnalyzer
+        .analyze(
+            &mut Cursor::new(make_default_gc_disc()),
+            &AnalysisOptions::default(),
+        )
+        .unwrap();
+    assert!(result.build_date.is_none());
+}
+
+// ---------------------------------------------------------------------------
+// Scrub detection tests
+// ---------------------------------------------------------------------------
+
+/// A 1 MiB disc with two files and junk everywhere else, like a retail
+/// disc (cut short).
+fn make_junk_filled_gc_disc() -> Vec<u8> {
+    let mut disc: Vec<u8> = (0..0x100000u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect();
+    disc[..0x440].copy_from_slice(&make_default_gc_disc()[..0x440]);
+    disc[0x200..0x204].fill(0);
+    disc[0x424..0x428].copy_from_slice(&0x10000u32.to_be_bytes());
+    disc[0x428..0x42C].copy_from_slice(&0x30u32.to_be_bytes());
+
+    // FST: root directory with two files
+    let fst = &mut disc[0x10000..0x10030];
+    fst.fill(0);
+    fst[0] = 1;
+    fst[8..12].copy_from_slice(&3u32.to_be_bytes());
+    for (entry, (offset, len)) in [(0x20000u32, 0x8000u32), (0x80000, 0x10000)]
+        .into_iter()
+        .enumerate()
+    {
+        let e = &mut fst[12 * (entry + 1)..12 * (entry + 2)];
+        e[4..8].copy_from_slice(&offset.to_be_bytes());
+        e[8..12].copy_from_slice(&len.to_be_bytes());
+    }
+    disc
+}
+
+fn gc_scrub_reason(disc: Vec<u8>) -> Option<String> {
+    let mut cursor = Cursor::new(disc);
+    let header = nintendo_disc::parse_disc_header(&mut cursor).unwrap();
+    nintendo_disc::scrub_reason(&mut cursor, &header)
+}
+
+#[test]
+fn test_junk_filled_disc_is_not_scrubbed() {
+    assert_eq!(gc_scrub_reason(make_junk_filled_gc_disc()), None);
+}
+
+#[test]
+fn test_zeroed_gaps_are_scrubbed() {
+    let mut disc = make_junk_filled_gc_disc();
+    disc[0x10030..0x20000].fill(0);
+    disc[0x28000..0x80000].fill(0);
+    disc[0x90000..].fill(0);
+    let reason = gc_scrub_reason(disc.clone()).unwrap();
+    assert!(reason.contains("junk padding"), "{reason}");
+
+    let id = GameCubeAnalyzer
+        .analyze(&mut Cursor::new(disc), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.extra.get("scrubbed"), Some(&reason));
+}
+
+#[test]
+fn test_one_blank_gap_is_not_enough() {
+    // Some mastering leaves a stretch blank; only all-blank gaps count
+    let mut disc = make_junk_filled_gc_disc();
+    disc[0x10030..0x20000].fill(0);
+    assert_eq!(gc_scrub_reason(disc), None);
+}
+
+#[test]
+fn test_nkit_image_is_reported() {
+    let mut disc = make_junk_filled_gc_disc();
+    disc[0x200..0x204].copy_from_slice(b"NKIT");
+    assert!(gc_scrub_reason(disc.clone()).unwrap().contains("NKit"));
+
+    // Reported even in quick mode, which skips the scrub check
+    let options = AnalysisOptions {
+        quick: true,
+        ..Default::default()
+    };
+    let id = GameCubeAnalyzer
+        .analyze(&mut Cursor::new(disc), &options)
+        .unwrap();
+    assert!(id.extra["nkit"].contains("won't match Redump"));
+    assert!(!id.extra.contains_key("scrubbed"));
+}
+
+#[test]
+fn test_plain_image_has_no_nkit_info() {
+    let id = GameCubeAnalyzer
+        .analyze(
+            &mut Cursor::new(make_junk_filled_gc_disc()),
+            &AnalysisOptions::default(),
+        )
+        .unwrap();
+    assert!(!id.extra.contains_key("nkit"));
+    assert!(
+        GameCubeAnalyzer
+            .recorded_dat_hashes(&mut Cursor::new(make_junk_filled_gc_disc()), None)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_dat_mismatch_reason_reports_trimmed_images() {
+    let reason = GameCubeAnalyzer
+        .dat_mismatch_reason(&mut Cursor::new(make_junk_filled_gc_disc()), None)
+        .unwrap();
+    assert!(reason.starts_with("trimmed"), "{reason}");
+
+    let header =
+        nintendo_disc::parse_disc_header(&mut Cursor::new(make_default_gc_disc())).unwrap();
+    assert_eq!(
+        nintendo_disc::trim_reason(&header, nintendo_disc::GC_DISC_SIZE),
+        None
+    );
+}
@@ -0,0 +1,182 @@
+use super::*;
+use crate::{GameBoyAnalyzer, NesAnalyzer, SnesAnalyzer};
+use retro_junk_core::RomAnalyzer;
+use retro_junk_music::vgm::Gd3Tag;
+use std::io::Cursor;
+
+fn put_str(buf: &mut [u8], offset: usize, s: &str) {
+    buf[offset..offset + s.len()].copy_from_slice(s.as_bytes());
+}
+
+/// A 3-track NSF using the VRC6 and Namco 163 expansion chips.
+fn make_nsf() -> Vec<u8> {
+    let mut data = vec![0u8; NSF_HEADER_LEN + 0x100];
+    data[..5].copy_from_slice(NSF_MAGIC);
+    data[0x05] = 1;
+    data[0x06] = 3;
+    data[0x07] = 1;
+    put_str(&mut data, 0x0E, "Test Quest");
+    put_str(&mut data, 0x2E, "<?>");
+    put_str(&mut data, 0x4E, "1988 Test Soft");
+    data[0x7A] = 0x02;
+    data[0x7B] = 0x11;
+    data
+}
+
+fn make_gbs() -> Vec<u8> {
+    let mut data = vec![0u8; GBS_HEADER_LEN + 0x100];
+    data[..3].copy_from_slice(GBS_MAGIC);
+    data[0x03] = 1;
+    data[0x04] = 12;
+    data[0x05] = 2;
+    put_str(&mut data, 0x10, "Pocket Test");
+    put_str(&mut data, 0x30, "J. Composer");
+    put_str(&mut data, 0x50, "1996 Test");
+    data
+}
+
+/// A full-size SPC with a text-format ID666 tag.
+fn make_spc() -> Vec<u8> {
+    let mut data = vec![0u8; 0x10200];
+    put_str(&mut data, 0, "SNES-SPC700 Sound File Data v0.30");
+    data[0x21] = 0x1A;
+    data[0x22] = 0x1A;
+    data[0x23] = SPC_HAS_TAG;
+    data[0x24] = 30;
+    put_str(&mut data, 0x2E, "Overworld");
+    put_str(&mut data, 0x4E, "Test Quest SNES");
+    put_str(&mut data, 0x6E, "Ripper");
+    put_str(&mut data, 0x7E, "Looped once");
+    put_str(&mut data, 0x9E, "06/01/2001");
+    put_str(&mut data, 0xA9, "150");
+    put_str(&mut data, 0xAC, "10000");
+    put_str(&mut data, 0xB1, "S. Composer");
+    data
+}
+
+#[test]
+fn test_nsf_identification() {
+    let result = NesAnalyzer
+        .analyze_music(&mut Cursor::new(make_nsf()), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.platform, Some(Platform::Nes));
+    assert_eq!(result.internal_name.as_deref(), Some("Test Quest"));
+    assert_eq!(result.extra["format"], "NSF music rip");
+    assert_eq!(result.extra["tracks"], "3");
+    assert_eq!(result.extra["copyright"], "1988 Test Soft");
+    assert_eq!(result.extra["sound_chips"], "Konami VRC6, Namco 163");
+    assert_eq!(result.extra["video"], "NTSC/PAL");
+    assert!(!result.extra.contains_key("artist"));
+}
+
+#[test]
+fn test_gbs_identification() {
+    let result = GameBoyAnalyzer
+        .analyze_music(&mut Cursor::new(make_gbs()), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.platform, Some(Platform::GameBoy));
+    assert_eq!(result.internal_name.as_deref(), Some("Pocket Test"));
+    assert_eq!(result.extra["tracks"], "12");
+    assert_eq!(result.extra["first_track"], "2");
+    assert_eq!(result.extra["artist"], "J. Composer");
+}
+
+#[test]
+fn test_gbs_rejects_unknown_version() {
+    let mut data = make_gbs();
+    data[0x03] = 2;
+    assert!(
+        GameBoyAnalyzer
+            .analyze_music(&mut Cursor::new(data), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_spc_text_tag() {
+    let result = SnesAnalyzer
+        .analyze_music(&mut Cursor::new(make_spc()), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.platform, Some(Platform::Snes));
+    assert_eq!(result.internal_name.as_deref(), Some("Test Quest SNES"));
+    assert_eq!(result.extra["title"], "Overworld");
+    assert_eq!(result.extra["artist"], "S. Composer");
+    assert_eq!(result.extra["ripper"], "Ripper");
+    assert_eq!(result.extra["comment"], "Looped once");
+    assert_eq!(result.extra["length"], "2:30");
+}
+
+#[test]
+fn test_spc_binary_tag() {
+    let mut data = make_spc();
+    data[0x9E..0xD1].fill(0);
+    // Binary date (day, month, year), 3-byte length, then the artist at 0xB0
+    data[0x9E..0xA2].copy_from_slice(&[1, 6, 0xD1, 0x07]);
+    data[0xA9] = 95;
+    put_str(&mut data, 0xB0, "B. Composer");
+    let result = SnesAnalyzer
+        .analyze_music(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.extra["artist"], "B. Composer");
+    assert_eq!(result.extra["length"], "1:35");
+}
+
+#[test]
+fn test_spc_without_tag() {
+    let mut data = make_spc();
+    data[0x23] = 0x1B;
+    let result = SnesAnalyzer
+        .analyze_music(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.extra["format"], "SPC music rip");
+    assert_eq!(result.internal_name, None);
+}
+
+#[test]
+fn test_vgm_claimed_by_its_console() {
+    let tag = Gd3Tag {
+        game: "Pocket Test".into(),
+        ..Gd3Tag::default()
+    };
+    let gb = retro_junk_music::synthetic::vgm(&[VgmChip::GbDmg], Some(&tag));
+    let result = GameBoyAnalyzer
+        .analyze_music(&mut Cursor::new(gb.clone()), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.internal_name.as_deref(), Some("Pocket Test"));
+    assert_eq!(result.extra["sound_chips"], "Game Boy DMG");
+
+    assert!(
+        NesAnalyzer
+            .analyze_music(&mut Cursor::new(gb), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_rips_are_not_roms() {
+    assert!(!NesAnalyzer.can_handle(&mut Cursor::new(make_nsf())));
+    assert!(!GameBoyAnalyzer.can_handle(&mut Cursor::new(make_gbs())));
+    assert!(
+        NesAnalyzer
+            .analyze(&mut Cursor::new(make_nsf()), &AnalysisOptions::default())
+            .is_err()
+    );
+    for analyzer in [
+        &NesAnalyzer as &dyn RomAnalyzer,
+        &GameBoyAnalyzer,
+        &SnesAnalyzer,
+    ] {
+        for ext in analyzer.music_extensions() {
+            assert!(!analyzer.file_extensions().contains(ext));
+        }
+    }
+}
+
+#[test]
+fn test_rip_of_another_console_rejected() {
+    assert!(
+        SnesAnalyzer
+            .analyze_music(&mut Cursor::new(make_nsf()), &AnalysisOptions::default())
+            .is_err()
+    );
+}
@@ -618,6 +618,19 @@ fn test_extract_scraper_serial_delegates_to_dat() {
     );
 }
 
+#[test]
+fn test_serial_keeps_embedded_space() {
+    let analyzer = N64Analyzer;
+    let serial = analyzer.normalize_serial(" nus-n me-usa ");
+    assert_eq!(serial, "NUS-N ME-USA");
+    assert!(analyzer.is_valid_serial(&serial));
+    assert_eq!(
+        analyzer.extract_dat_game_code(&serial).as_deref(),
+        Some("N ME")
+    );
+    assert!(!analyzer.is_valid_serial("NUS-NSME"));
+}
+
 // -- normalize_to_big_endian unit tests --
 
 #[test]
@@ -174,3 +174,24 @@ fn test_can_handle() {
     let garbage = vec![0xFFu8; 16];
     assert!(!analyzer.can_handle(&mut Cursor::new(garbage)));
 }
+
+#[test]
+fn test_fds_build_date_eras() {
+    // Showa 61 (1986), February 21
+    assert_eq!(
+        fds_build_date(0x61, 0x02, 0x21),
+        BuildDate::new(1986, Some(2), Some(21))
+    );
+    // Heisei 2 (1990)
+    assert_eq!(
+        fds_build_date(0x02, 0x12, 0x01),
+        BuildDate::new(1990, Some(12), Some(1))
+    );
+    // Western year
+    assert_eq!(
+        fds_build_date(0x87, 0x07, 0x15),
+        BuildDate::new(1987, Some(7), Some(15))
+    );
+    // Not BCD
+    assert_eq!(fds_build_date(0x6A, 0x01, 0x01), None);
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn test_meta_hashes_need_crc_and_size() {
+    let mut meta = nod::DiscMeta {
+        crc32: Some(0x1234_abcd),
+        ..Default::default()
+    };
+    assert!(meta_hashes(&meta).is_none());
+
+    meta.disc_size = Some(GC_DISC_SIZE);
+    let hashes = meta_hashes(&meta).unwrap();
+    assert_eq!(hashes.crc32, "1234abcd");
+    assert_eq!(hashes.data_size, GC_DISC_SIZE);
+    assert_eq!(hashes.sha1, None);
+}
+
+#[test]
+fn test_meta_hashes_format_digests_as_hex() {
+    let meta = nod::DiscMeta {
+        crc32: Some(1),
+        disc_size: Some(GC_DISC_SIZE),
+        sha1: Some([0xAB; 20]),
+        md5: Some([0x0F; 16]),
+        ..Default::default()
+    };
+    let hashes = meta_hashes(&meta).unwrap();
+    assert_eq!(hashes.crc32, "00000001");
+    assert_eq!(hashes.sha1.unwrap(), "ab".repeat(20));
+    assert_eq!(hashes.md5.unwrap(), "0f".repeat(16));
+}
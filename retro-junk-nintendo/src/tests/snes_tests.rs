@@ -472,3 +472,143 @@ fn test_copier_header_checksum_valid() {
         "OK"
     );
 }
+
+/// Build a 512 KB Sufami Turbo game cart.
+fn make_sufami_rom() -> Vec<u8> {
+    let mut rom = vec![0u8; 512 * 1024];
+    rom[..14].copy_from_slice(SUFAMI_MAGIC);
+    rom[0x10..0x1E].copy_from_slice(b"SD GUNDAM GN  ");
+    rom[0x30..0x33].copy_from_slice(&[0x00, 0x01, 0x02]);
+    rom[0x33] = 0x01; // second in its series
+    rom[0x34] = 0x01; // FastROM
+    rom[0x35] = 0x01; // SRAM
+    rom[0x36] = 0x04; // 4 x 128 KB
+    rom[0x37] = 0x04; // 4 x 2 KB
+    rom
+}
+
+/// Build a 1 MB LoROM BS-X memory pack with a valid checksum pair.
+fn make_bsx_rom() -> Vec<u8> {
+    let mut rom = vec![0u8; 1024 * 1024];
+    let base = LOROM_HEADER_BASE as usize;
+    rom[base..base + 2].copy_from_slice(b"01");
+    rom[base + OFF_TITLE..base + OFF_TITLE + 16].copy_from_slice(b"BS ZELDA        ");
+    rom[base + OFF_BSX_BLOCKS..base + OFF_BSX_BLOCKS + 4]
+        .copy_from_slice(&0x0000_00FFu32.to_le_bytes());
+    // Limited: bits 0-14 hold five remaining starts
+    rom[base + OFF_BSX_LIMITED_STARTS..base + OFF_BSX_LIMITED_STARTS + 2]
+        .copy_from_slice(&0x001Fu16.to_le_bytes());
+    rom[base + OFF_BSX_MONTH] = 8 << 4;
+    rom[base + OFF_BSX_DAY] = 6 << 3;
+    rom[base + OFF_BSX_MAP_MODE] = 0x20;
+    rom[base + 0x29] = 0x10;
+    rom[base + OFF_BSX_FIXED] = 0x33;
+    rom[base + OFF_VERSION] = 0x02;
+    rom[base + OFF_COMPLEMENT..base + OFF_COMPLEMENT + 2].copy_from_slice(&0xA55Au16.to_le_bytes());
+    rom[base + OFF_CHECKSUM..base + OFF_CHECKSUM + 2].copy_from_slice(&0x5AA5u16.to_le_bytes());
+    rom
+}
+
+#[test]
+fn test_sufami_turbo_analyze() {
+    let result = SnesAnalyzer
+        .analyze(
+            &mut Cursor::new(make_sufami_rom()),
+            &AnalysisOptions::default(),
+        )
+        .unwrap();
+
+    assert_eq!(result.platform, Some(Platform::Snes));
+    assert_eq!(result.internal_name.as_deref(), Some("SD GUNDAM GN"));
+    assert_eq!(result.regions, vec![Region::Japan]);
+    assert_eq!(result.expected_size, None);
+    assert_eq!(result.extra.get("format").unwrap(), "Sufami Turbo");
+    assert_eq!(result.extra.get("sufami_game_id").unwrap(), "000102");
+    assert_eq!(result.extra.get("series_index").unwrap(), "1");
+    assert_eq!(result.extra.get("speed").unwrap(), "FastROM (3.58 MHz)");
+    assert_eq!(result.extra.get("sram_size").unwrap(), "8 KB");
+    assert_eq!(result.extra.get("linkable").unwrap(), "Yes");
+}
+
+#[test]
+fn test_sufami_turbo_truncated_reports_expected_size() {
+    let mut rom = make_sufami_rom();
+    rom.truncate(256 * 1024);
+    let result = SnesAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.expected_size, Some(512 * 1024));
+}
+
+#[test]
+fn test_sufami_bios_stays_a_cartridge() {
+    // The BIOS cart has the magic and a real LoROM header
+    let mut rom = make_snes_rom();
+    rom[..14].copy_from_slice(SUFAMI_MAGIC);
+    recompute_snes_checksums(&mut rom, LOROM_HEADER_BASE as usize);
+    let result = SnesAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert!(result.extra.get("format").unwrap() != "Sufami Turbo");
+}
+
+#[test]
+fn test_bsx_memory_pack_analyze() {
+    let result = SnesAnalyzer
+        .analyze(
+            &mut Cursor::new(make_bsx_rom()),
+            &AnalysisOptions::default(),
+        )
+        .unwrap();
+
+    assert_eq!(result.platform, Some(Platform::Snes));
+    assert_eq!(result.internal_name.as_deref(), Some("BS ZELDA"));
+    assert_eq!(result.version.as_deref(), Some("1.2"));
+    assert_eq!(result.regions, vec![Region::Japan]);
+    assert_eq!(
+        result.extra.get("format").unwrap(),
+        "BS-X Satellaview memory pack"
+    );
+    assert_eq!(result.extra.get("mapping").unwrap(), "LoROM");
+    assert_eq!(result.extra.get("broadcast_date").unwrap(), "08-06");
+    assert_eq!(result.extra.get("limited_starts").unwrap(), "5 remaining");
+    assert_eq!(result.extra.get("pack_blocks").unwrap(), "8 (1 MB)");
+    assert_eq!(
+        result.extra.get("checksum_complement_valid").unwrap(),
+        "Yes"
+    );
+}
+
+#[test]
+fn test_bsx_memory_pack_with_copier_header() {
+    let rom = add_copier_header(&make_bsx_rom());
+    let result = SnesAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.extra.get("copier_header").unwrap(), "Yes");
+    assert_eq!(result.internal_name.as_deref(), Some("BS ZELDA"));
+}
+
+#[test]
+fn test_cartridges_are_not_adapter_formats() {
+    for rom in [make_snes_rom(), make_snes_hirom()] {
+        let mut cursor = Cursor::new(rom);
+        let size = cursor.get_ref().len() as u64;
+        assert!(detect_sufami(&mut cursor, size).is_none());
+        assert!(detect_bsx(&mut cursor, size).is_none());
+    }
+}
+
+#[test]
+fn test_can_handle_adapter_formats() {
+    assert!(SnesAnalyzer.can_handle(&mut Cursor::new(make_sufami_rom())));
+    assert!(SnesAnalyzer.can_handle(&mut Cursor::new(make_bsx_rom())));
+    assert!(SnesAnalyzer.file_extensions().contains(&"bs"));
+    assert!(SnesAnalyzer.file_extensions().contains(&"st"));
+    assert!(SnesAnalyzer.dat_names().contains(&"Nintendo - Satellaview"));
+    assert!(
+        SnesAnalyzer
+            .dat_names()
+            .contains(&"Nintendo - Sufami Turbo")
+    );
+}
@@ -24,6 +24,56 @@ fn test_compressed_detection_resets_position() {
     assert_eq!(cursor.position(), 0);
 }
 
+/// WBFS with 512-byte HD sectors: the disc header copy opens sector 1
+#[test]
+fn test_wbfs_header_read_without_a_path() {
+    let disc = make_default_wii_disc();
+    let mut data = vec![0u8; 0x400];
+    data[0..4].copy_from_slice(b"WBFS");
+    data[8] = 9;
+    data[9] = 21;
+    data[0x200..0x300].copy_from_slice(&disc[..0x100]);
+
+    assert!(WiiAnalyzer.can_handle(&mut Cursor::new(data.clone())));
+    let id = WiiAnalyzer
+        .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("RSBE"));
+    assert_eq!(id.internal_name.as_deref(), Some("Wii Sports"));
+    assert_eq!(id.extra.get("format").map(String::as_str), Some("WBFS"));
+    // WBFS doesn't record the disc size
+    assert!(!id.extra.contains_key("dvd_layer"));
+}
+
+/// RVZ keeps the disc's first 0x80 bytes and the ISO size in the clear
+#[test]
+fn test_rvz_header_read_without_a_path() {
+    let disc = make_default_wii_disc();
+    let mut data = vec![0u8; 0x100];
+    data[0..4].copy_from_slice(b"RVZ\x01");
+    data[0x24..0x2C].copy_from_slice(&DVD5_SIZE_THRESHOLD.to_be_bytes());
+    data[0x58..0xD8].copy_from_slice(&disc[..0x80]);
+
+    let id = WiiAnalyzer
+        .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("RSBE"));
+    assert_eq!(id.extra.get("format").map(String::as_str), Some("RVZ"));
+    assert_eq!(id.extra.get("dvd_layer").map(String::as_str), Some("DVD-5"));
+}
+
+/// GCZ compresses its first block too, so it needs the file path
+#[test]
+fn test_gcz_without_path_is_rejected() {
+    let mut data = vec![0u8; 0x100];
+    data[0..4].copy_from_slice(&0xB10B_C001u32.to_le_bytes());
+    assert!(
+        WiiAnalyzer
+            .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
 /// Build a synthetic Wii disc image with a valid header.
 ///
 /// The image is 8 KB (enough for header + some padding). The game code,
@@ -231,3 +281,56 @@ fn test_container_hashes_returns_none_for_raw_iso() {
         "Raw ISO should return None (use standard hasher)"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Scrub detection tests
+// ---------------------------------------------------------------------------
+
+/// A Wii disc with one partition whose data area (0x70000..0xB0000) is
+/// filled with noise, standing in for encrypted data.
+fn make_partitioned_wii_disc() -> Vec<u8> {
+    let mut disc: Vec<u8> = (0..0xC0000u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect();
+    disc[..0x440].copy_from_slice(&make_default_wii_disc()[..0x440]);
+    disc[0x200..0x204].fill(0);
+
+    let info = &mut disc[0x40000..0x40028];
+    info.fill(0);
+    info[0..4].copy_from_slice(&1u32.to_be_bytes());
+    info[4..8].copy_from_slice(&(0x40020u32 >> 2).to_be_bytes());
+    info[0x20..0x24].copy_from_slice(&(0x50000u32 >> 2).to_be_bytes());
+
+    disc[0x502B8..0x502BC].copy_from_slice(&(0x20000u32 >> 2).to_be_bytes());
+    disc[0x502BC..0x502C0].copy_from_slice(&(0x40000u32 >> 2).to_be_bytes());
+    disc
+}
+
+fn wii_scrub_reason(disc: Vec<u8>) -> Option<String> {
+    let mut cursor = Cursor::new(disc);
+    let header = nintendo_disc::parse_disc_header(&mut cursor).unwrap();
+    nintendo_disc::scrub_reason(&mut cursor, &header)
+}
+
+#[test]
+fn test_encrypted_partition_is_not_scrubbed() {
+    assert_eq!(wii_scrub_reason(make_partitioned_wii_disc()), None);
+}
+
+#[test]
+fn test_zeroed_partition_cluster_is_scrubbed() {
+    let mut disc = make_partitioned_wii_disc();
+    disc[0xA0000..0xB0000].fill(0);
+    let reason = wii_scrub_reason(disc).unwrap();
+    assert!(reason.contains("partition data"), "{reason}");
+}
+
+#[test]
+fn test_wii_trim_reason() {
+    let header =
+        nintendo_disc::parse_disc_header(&mut Cursor::new(make_default_wii_disc())).unwrap();
+    for size in nintendo_disc::WII_DISC_SIZES {
+        assert_eq!(nintendo_disc::trim_reason(&header, size), None);
+    }
+    assert!(nintendo_disc::trim_reason(&header, 0x8000).is_some());
+}
@@ -0,0 +1,223 @@
+use super::*;
+use crate::synthetic::{make_wux, wiiu, wiiu_tmd};
+use std::io::Cursor;
+
+const GAME_TITLE_ID: u64 = 0x0005000010101D00;
+
+// -- WUD --
+
+#[test]
+fn test_wud_product_code() {
+    let data = wiiu().data;
+    assert!(WiiUAnalyzer.can_handle(&mut Cursor::new(data.clone())));
+    let id = WiiUAnalyzer
+        .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("WUP-P-AZZE"));
+    assert_eq!(id.regions, vec![Region::Usa]);
+    assert_eq!(id.extra.get("game_code").map(String::as_str), Some("AZZE"));
+    assert_eq!(id.expected_size, Some(WUD_SIZE));
+}
+
+#[test]
+fn test_wud_region_from_game_code() {
+    let mut data = wiiu().data;
+    data[9] = b'P';
+    let id = WiiUAnalyzer
+        .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.regions, vec![Region::Europe]);
+}
+
+#[test]
+fn test_product_code_shape() {
+    assert!(is_product_code(b"WUP-P-ARPE"));
+    assert!(is_product_code(b"WUP-N-A2BJ"));
+    assert!(!is_product_code(b"WUP-P-arpe"));
+    assert!(!is_product_code(b"CTR-P-ARPE"));
+    assert!(!is_product_code(b"WUP-PARPE\0"));
+}
+
+#[test]
+fn test_encrypted_data_rejected() {
+    let data = vec![0xA5u8; 0x8000];
+    assert!(!WiiUAnalyzer.can_handle(&mut Cursor::new(data.clone())));
+    assert!(
+        WiiUAnalyzer
+            .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_product_code_serial_format() {
+    assert_eq!(WiiUAnalyzer.normalize_serial("wup p arpe"), "WUP-P-ARPE");
+    assert!(WiiUAnalyzer.is_valid_serial("WUP-N-A2BJ"));
+    assert!(!WiiUAnalyzer.is_valid_serial("WUP-ARPE"));
+}
+
+// -- WUX --
+
+#[test]
+fn test_wux_product_code() {
+    let wud = wiiu().data;
+    let wux = make_wux(&wud, 0x8000);
+    assert!(WiiUAnalyzer.can_handle(&mut Cursor::new(wux.clone())));
+    let id = WiiUAnalyzer
+        .analyze(&mut Cursor::new(wux.clone()), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("WUP-P-AZZE"));
+    assert_eq!(id.regions, vec![Region::Usa]);
+    assert_eq!(id.extra.get("format").map(String::as_str), Some("WUX"));
+    assert_eq!(
+        id.extra.get("uncompressed_size"),
+        Some(&wud.len().to_string())
+    );
+    assert_eq!(id.file_size, Some(wux.len() as u64));
+    assert_eq!(id.expected_size, None);
+}
+
+#[test]
+fn test_wux_hashes_decompressed_wud() {
+    let mut wud = wiiu().data;
+    wud.resize(0x30000, 0);
+    wud[0x28000..].fill(0x5A);
+    let wux = make_wux(&wud, 0x8000);
+    assert!(wux.len() < wud.len());
+
+    let algorithms = HashAlgorithms::All;
+    let hashes = WiiUAnalyzer
+        .compute_container_hashes(&mut Cursor::new(wux), algorithms, None)
+        .unwrap()
+        .unwrap();
+    let expected = retro_junk_core::hash_reader(&mut Cursor::new(&wud), algorithms).unwrap();
+    assert_eq!(hashes.crc32, expected.crc32);
+    assert_eq!(hashes.sha1, expected.sha1);
+    assert_eq!(hashes.data_size, wud.len() as u64);
+}
+
+#[test]
+fn test_wud_not_container_hashed() {
+    let hashes = WiiUAnalyzer
+        .compute_container_hashes(&mut Cursor::new(wiiu().data), HashAlgorithms::All, None)
+        .unwrap();
+    assert!(hashes.is_none());
+}
+
+#[test]
+fn test_wux_without_product_code_rejected() {
+    let wux = make_wux(&[0xA5u8; 0x8000], 0x8000);
+    assert!(
+        WiiUAnalyzer
+            .analyze(&mut Cursor::new(wux), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
+// -- title.tmd --
+
+#[test]
+fn test_tmd_title_id_and_version() {
+    let data = wiiu_tmd(GAME_TITLE_ID, 32);
+    assert!(WiiUAnalyzer.can_handle(&mut Cursor::new(data.clone())));
+    let id = WiiUAnalyzer
+        .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(
+        id.extra.get("title_id").map(String::as_str),
+        Some("0005000010101D00")
+    );
+    assert_eq!(id.extra.get("title_type").map(String::as_str), Some("Game"));
+    assert_eq!(id.version.as_deref(), Some("v32"));
+    assert_eq!(id.serial_number, None);
+}
+
+#[test]
+fn test_tmd_title_types() {
+    assert_eq!(title_type_from_id(0x0005000E10101D00), "Update");
+    assert_eq!(title_type_from_id(0x0005000C10101D00), "DLC");
+    assert_eq!(title_type_from_id(0x0005000210101D00), "Demo");
+}
+
+#[test]
+fn test_3ds_and_vwii_tmds_rejected() {
+    for title_id in [0x0004000000ABCDEF, 0x0007000012345678] {
+        let data = wiiu_tmd(title_id, 0);
+        assert!(!WiiUAnalyzer.can_handle(&mut Cursor::new(data)));
+    }
+}
+
+#[test]
+fn test_decrypted_folder_reads_meta_xml() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("code")).unwrap();
+    std::fs::create_dir_all(dir.path().join("meta")).unwrap();
+    let tmd_path = dir.path().join("code").join("title.tmd");
+    std::fs::write(&tmd_path, wiiu_tmd(GAME_TITLE_ID, 16)).unwrap();
+    std::fs::write(
+        dir.path().join("meta").join("meta.xml"),
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<menu type=\"complex\" access=\"777\">\n  \
+         <product_code type=\"string\" length=\"32\">WUP-P-AZZP</product_code>\n  \
+         <region type=\"hexBinary\" length=\"4\">00000006</region>\n  \
+         <longname_en type=\"string\" length=\"512\">Synthetic\nAdventure</longname_en>\n\
+         </menu>\n",
+    )
+    .unwrap();
+
+    let options = AnalysisOptions::new().file_path(&tmd_path);
+    let id = WiiUAnalyzer
+        .analyze(&mut std::fs::File::open(&tmd_path).unwrap(), &options)
+        .unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("WUP-P-AZZP"));
+    assert_eq!(id.internal_name.as_deref(), Some("Synthetic Adventure"));
+    assert_eq!(id.regions, vec![Region::Usa, Region::Europe]);
+    assert_eq!(id.version.as_deref(), Some("v16"));
+}
+
+// -- Extracted title folders --
+
+#[test]
+fn test_extracted_title_folder() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("code")).unwrap();
+    std::fs::create_dir_all(dir.path().join("meta")).unwrap();
+    std::fs::write(
+        dir.path().join("meta").join("meta.xml"),
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<menu type=\"complex\" access=\"777\">\n  \
+         <title_version type=\"unsignedInt\" length=\"4\">48</title_version>\n  \
+         <title_id type=\"hexBinary\" length=\"8\">0005000010101D00</title_id>\n  \
+         <product_code type=\"string\" length=\"32\">WUP-P-AZZJ</product_code>\n  \
+         <region type=\"hexBinary\" length=\"4\">00000001</region>\n  \
+         <longname_en type=\"string\" length=\"512\">Synthetic Adventure</longname_en>\n\
+         </menu>\n",
+    )
+    .unwrap();
+
+    assert!(WiiUAnalyzer.is_game_dir(dir.path()));
+    let id = WiiUAnalyzer
+        .analyze_dir(dir.path(), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(
+        id.extra.get("title_id").map(String::as_str),
+        Some("0005000010101D00")
+    );
+    assert_eq!(id.extra.get("title_type").map(String::as_str), Some("Game"));
+    assert_eq!(id.version.as_deref(), Some("v48"));
+    assert_eq!(id.serial_number.as_deref(), Some("WUP-P-AZZJ"));
+    assert_eq!(id.regions, vec![Region::Japan]);
+    assert_eq!(id.internal_name.as_deref(), Some("Synthetic Adventure"));
+}
+
+#[test]
+fn test_folder_without_meta_xml_not_a_game_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("code")).unwrap();
+    assert!(!WiiUAnalyzer.is_game_dir(dir.path()));
+}
+
+#[test]
+fn test_xml_tag_skips_longer_names() {
+    let xml = "<region_x>1</region_x><region type=\"hexBinary\">00000001</region>";
+    assert_eq!(xml_tag(xml, "region").as_deref(), Some("00000001"));
+    assert_eq!(xml_tag(xml, "missing"), None);
+}
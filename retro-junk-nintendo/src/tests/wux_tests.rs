@@ -0,0 +1,83 @@
+use super::*;
+use crate::synthetic::make_wux;
+use std::io::Cursor;
+
+fn decompress(image: Vec<u8>) -> Vec<u8> {
+    let mut wud = Vec::new();
+    open_image(Cursor::new(image), &ParseLimits::default())
+        .unwrap()
+        .read_to_end(&mut wud)
+        .unwrap();
+    wud
+}
+
+/// Four sectors of 0x100 bytes, the middle two identical.
+fn sample_wud() -> Vec<u8> {
+    let mut wud = vec![0u8; 0x400];
+    wud[..0x100].fill(0x11);
+    wud[0x300..].fill(0x33);
+    wud
+}
+
+#[test]
+fn test_round_trip() {
+    let wud = sample_wud();
+    assert_eq!(decompress(make_wux(&wud, 0x100)), wud);
+}
+
+#[test]
+fn test_repeated_sectors_stored_once() {
+    let wud = sample_wud();
+    let wux = make_wux(&wud, 0x100);
+    // Header and index share the first sector, then three distinct sectors
+    assert_eq!(wux.len(), 0x100 + 3 * 0x100);
+}
+
+#[test]
+fn test_partial_last_sector() {
+    let mut wud = sample_wud();
+    wud.truncate(0x380);
+    let mut image =
+        open_image(Cursor::new(make_wux(&wud, 0x100)), &ParseLimits::default()).unwrap();
+    assert_eq!(image.len(), 0x380);
+    let mut out = Vec::new();
+    image.read_to_end(&mut out).unwrap();
+    assert_eq!(out, wud);
+}
+
+#[test]
+fn test_seek_into_sector() {
+    let wud = sample_wud();
+    let mut image =
+        open_image(Cursor::new(make_wux(&wud, 0x100)), &ParseLimits::default()).unwrap();
+    image.seek(SeekFrom::Start(0x2FE)).unwrap();
+    let mut buf = [0u8; 4];
+    image.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, [0x00, 0x00, 0x33, 0x33]);
+}
+
+#[test]
+fn test_magic() {
+    let wux = make_wux(&sample_wud(), 0x100);
+    assert!(has_wux_magic(&mut Cursor::new(wux)));
+    assert!(!has_wux_magic(&mut Cursor::new(sample_wud())));
+}
+
+#[test]
+fn test_bad_sector_size_rejected() {
+    let mut wux = make_wux(&sample_wud(), 0x100);
+    wux[0x08..0x0C].copy_from_slice(&0x10u32.to_le_bytes());
+    assert!(matches!(
+        WuxSource::open(Cursor::new(wux), &ParseLimits::default()),
+        Err(AnalysisError::CorruptedHeader(_))
+    ));
+}
+
+#[test]
+fn test_truncated_index_rejected() {
+    let mut wux = make_wux(&sample_wud(), 0x100);
+    // Claim far more sectors than the file holds index entries for
+    wux[0x10..0x18].copy_from_slice(&0x10_0000u64.to_le_bytes());
+    wux.truncate(0x40);
+    assert!(WuxSource::open(Cursor::new(wux), &ParseLimits::default()).is_err());
+}
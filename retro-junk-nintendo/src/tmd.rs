@@ -0,0 +1,77 @@
+//! Title metadata (TMD), shared by 3DS CIAs and Wii U title folders.
+//!
+//! A TMD opens with a signature block whose size depends on its type, then
+//! a header giving the title ID, the title version and the content count,
+//! followed by one record per content. Both consoles use the same header
+//! layout, all big-endian.
+//!
+//! Sources:
+//! - 3dbrew Title metadata: https://www.3dbrew.org/wiki/Title_metadata
+//! - WiiUBrew Title metadata: https://wiiubrew.org/wiki/Title_metadata
+
+use retro_junk_core::ReadSeek;
+use std::io::SeekFrom;
+
+use retro_junk_core::AnalysisError;
+use retro_junk_core::util::read_header;
+
+/// Offsets of the title fields in the header after the signature block.
+const TITLE_ID_OFFSET: usize = 0x4C;
+const TITLE_VERSION_OFFSET: usize = 0x9C;
+const CONTENT_COUNT_OFFSET: usize = 0x9E;
+
+/// Size of the header after the signature block.
+const TMD_HEADER_SIZE: usize = 0xC4;
+
+/// Title information from a TMD header.
+pub(crate) struct TmdInfo {
+    pub(crate) title_id: u64,
+    pub(crate) title_version: u16,
+    pub(crate) content_count: u16,
+}
+
+/// Determine the size of a TMD/Ticket signature block based on signature type.
+pub(crate) fn signature_block_size(sig_type: u32) -> Option<usize> {
+    match sig_type {
+        0x00010003 => Some(4 + 0x200 + 0x3C), // RSA-4096: type(4) + sig(512) + pad(60)
+        0x00010004 => Some(4 + 0x100 + 0x3C), // RSA-2048: type(4) + sig(256) + pad(60)
+        0x00010005 => Some(4 + 0x3C + 0x40),  // ECDSA: type(4) + sig(60) + pad(64)
+        _ => None,
+    }
+}
+
+/// Parse the title information from a TMD at `tmd_offset`.
+pub(crate) fn parse_tmd(
+    reader: &mut dyn ReadSeek,
+    tmd_offset: u64,
+    tmd_size: u64,
+) -> Result<TmdInfo, AnalysisError> {
+    if tmd_size < 8 {
+        return Err(AnalysisError::corrupted_header("TMD too small"));
+    }
+
+    // Read signature type to determine header offset
+    reader.seek(SeekFrom::Start(tmd_offset))?;
+    let mut sig_type_buf = [0u8; 4];
+    reader.read_exact(&mut sig_type_buf)?;
+    let sig_type = u32::from_be_bytes(sig_type_buf);
+
+    let sig_block_size = signature_block_size(sig_type).ok_or_else(|| {
+        AnalysisError::invalid_format(format!("Unknown TMD signature type: 0x{:08X}", sig_type))
+    })?;
+
+    // TMD header starts after signature block
+    let mut buf = [0u8; TMD_HEADER_SIZE];
+    read_header(reader, tmd_offset + sig_block_size as u64, &mut buf)?;
+
+    let be_u16 = |at: usize| u16::from_be_bytes([buf[at], buf[at + 1]]);
+    Ok(TmdInfo {
+        title_id: u64::from_be_bytes(
+            buf[TITLE_ID_OFFSET..TITLE_ID_OFFSET + 8]
+                .try_into()
+                .unwrap(),
+        ),
+        title_version: be_u16(TITLE_VERSION_OFFSET),
+        content_count: be_u16(CONTENT_COUNT_OFFSET),
+    })
+}
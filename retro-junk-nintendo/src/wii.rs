@@ -41,16 +41,17 @@ impl RomAnalyzer for WiiAnalyzer {
 
         // Detect compressed container (RVZ, WIA, WBFS, CISO, GCZ) or raw ISO.
         // For compressed formats, use the uncompressed disc size for DVD layer detection.
-        let (header, format_name, layer_size) = if nintendo_disc::is_compressed_disc(reader) {
-            let path = options.file_path.as_ref().ok_or_else(|| {
-                AnalysisError::invalid_format(
-                    "Compressed disc format detected but no file path provided",
-                )
-            })?;
-            let (header, format_name, disc_size) = nintendo_disc::open_compressed_disc(path)?;
-            (header, format_name, disc_size)
+        let compressed = nintendo_disc::is_compressed_disc(reader);
+        let (header, format_name, layer_size) = if compressed {
+            let container =
+                nintendo_disc::read_compressed_disc_header(reader, options.file_path.as_deref())?;
+            (container.header, container.format, container.disc_size)
         } else {
-            (nintendo_disc::parse_disc_header(reader)?, "ISO", file_size)
+            (
+                nintendo_disc::parse_disc_header(reader)?,
+                "ISO",
+                Some(file_size),
+            )
         };
 
         if !nintendo_disc::is_wii(&header) {
@@ -66,14 +67,27 @@ impl RomAnalyzer for WiiAnalyzer {
             "detected_extension".into(),
             format_name.to_ascii_lowercase(),
         );
+        nintendo_disc::insert_nkit_info(&mut id, reader, compressed, options.file_path.as_deref());
+        if !options.quick {
+            let scrubbed = match &options.file_path {
+                Some(path) if compressed => nintendo_disc::lossy_container_reason(path),
+                _ if compressed => None,
+                _ => nintendo_disc::scrub_reason(reader, &header),
+            };
+            if let Some(reason) = scrubbed {
+                id.extra.insert("scrubbed".into(), reason);
+            }
+        }
 
-        // Detect DVD layer type from uncompressed disc size
-        let layer = if layer_size > DVD5_SIZE_THRESHOLD {
-            "DVD-9"
-        } else {
-            "DVD-5"
-        };
-        id.extra.insert("dvd_layer".into(), layer.into());
+        // Detect DVD layer type from uncompressed disc size, when known
+        if let Some(layer_size) = layer_size {
+            let layer = if layer_size > DVD5_SIZE_THRESHOLD {
+                "DVD-9"
+            } else {
+                "DVD-5"
+            };
+            id.extra.insert("dvd_layer".into(), layer.into());
+        }
 
         Ok(id)
     }
@@ -83,7 +97,7 @@ impl RomAnalyzer for WiiAnalyzer {
     }
 
     fn file_extensions(&self) -> &'static [&'static str] {
-        &["iso", "wbfs", "rvz", "ciso", "wia"]
+        &["iso", "wbfs", "rvz", "wia", "ciso", "gcz"]
     }
 
     fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
@@ -111,6 +125,25 @@ impl RomAnalyzer for WiiAnalyzer {
         Ok(Some(hashes))
     }
 
+    fn recorded_dat_hashes(
+        &self,
+        reader: &mut dyn ReadSeek,
+        file_path: Option<&Path>,
+    ) -> Option<FileHashes> {
+        if !nintendo_disc::is_compressed_disc(reader) {
+            return None;
+        }
+        nintendo_disc::recorded_disc_hashes(file_path?)
+    }
+
+    fn dat_mismatch_reason(
+        &self,
+        reader: &mut dyn ReadSeek,
+        file_path: Option<&Path>,
+    ) -> Option<String> {
+        nintendo_disc::redump_mismatch_reason(reader, file_path)
+    }
+
     fn dat_source(&self) -> retro_junk_core::DatSource {
         retro_junk_core::DatSource::Redump
     }
@@ -119,6 +152,10 @@ impl RomAnalyzer for WiiAnalyzer {
         &["Nintendo - Wii"]
     }
 
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(nintendo_disc::GAME_CODE_PATTERN)
+    }
+
     fn expects_serial(&self) -> bool {
         true
     }
@@ -1,25 +1,77 @@
-//! Nintendo Wii U disc image analyzer.
+//! Nintendo Wii U disc image and title folder analyzer.
 //!
 //! Supports:
-//! - WUD images (.wud)
-//! - WUX compressed images (.wux)
+//! - WUD images (.wud): raw dumps of the 25 GB disc. Only the first sectors
+//!   are plaintext, opening with the product code ("WUP-P-ARPE"); the
+//!   partitions holding the title ID are encrypted with the disc's own key,
+//!   which a WUD doesn't carry, so identification stops at the product code.
+//! - WUX images (.wux): WUDs with repeated sectors stored once. They're
+//!   identified like a WUD and hashed as the WUD they decompress to.
+//! - NUS/WUP title folders, through their `title.tmd`: the title ID, type
+//!   and version. Decrypted folders (`code/`, `content/`, `meta/`) also keep
+//!   `meta/meta.xml`, which adds the product code, name and regions.
+//! - Decrypted title folders themselves, read from `meta/meta.xml` alone:
+//!   title ID, version, product code, name and regions.
+//!
+//! Sources:
+//! - WiiUBrew Wii U Optical Disc: https://wiiubrew.org/wiki/Wii_U_Optical_Disc
+//! - WiiUBrew Title metadata: https://wiiubrew.org/wiki/Title_metadata
+//! - wudcompress, the tool that defines WUX: its source and readme
 
 use retro_junk_core::ReadSeek;
+use std::io::{Read, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, FileHashes, HashAlgorithms, ParseLimits, Platform, Region,
+    RomAnalyzer, RomIdentification,
+};
+
+use crate::constants::region_from_game_code;
+use crate::{tmd, wux};
+
+/// Size of a full WUD image (one 25 GB Blu-ray layer).
+pub const WUD_SIZE: u64 = 25_025_314_816;
 
-use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+/// Length of a product code ("WUP-P-ARPE").
+const PRODUCT_CODE_LEN: usize = 10;
 
-/// Analyzer for Nintendo Wii U disc images.
+/// Largest file treated as a TMD: the header plus a generous content list.
+const MAX_TMD_SIZE: u64 = 0x10000;
+
+/// Largest meta.xml read.
+const MAX_META_XML_SIZE: u64 = 0x100000;
+
+/// Analyzer for Nintendo Wii U disc images and title folders.
 #[derive(Debug, Default)]
 pub struct WiiUAnalyzer;
 
 impl RomAnalyzer for WiiUAnalyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "Wii U disc analysis not yet implemented",
+        let file_size = retro_junk_core::util::file_size(reader)?;
+
+        if wux::has_wux_magic(reader) {
+            return analyze_wux(reader, file_size, options);
+        }
+        if let Some(product_code) = read_wud_product_code(reader) {
+            let mut id = analyze_disc(&product_code);
+            id.file_size = Some(file_size);
+            id.expected_size = Some(WUD_SIZE);
+            id.extra.insert("format".into(), "WUD".into());
+            return Ok(id);
+        }
+        if file_size <= MAX_TMD_SIZE
+            && let Ok(info) = tmd::parse_tmd(reader, 0, file_size)
+            && is_wiiu_title(info.title_id)
+        {
+            return Ok(analyze_tmd(&info, file_size, options));
+        }
+        Err(AnalysisError::invalid_format(
+            "Not a Wii U disc image or title.tmd",
         ))
     }
 
@@ -28,14 +80,240 @@ impl RomAnalyzer for WiiUAnalyzer {
     }
 
     fn file_extensions(&self) -> &'static [&'static str] {
-        &["wud", "wux"]
+        &["wud", "wux", "tmd"]
+    }
+
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        if wux::has_wux_magic(reader) || read_wud_product_code(reader).is_some() {
+            return true;
+        }
+        retro_junk_core::util::file_size(reader).is_ok_and(|size| {
+            size <= MAX_TMD_SIZE
+                && tmd::parse_tmd(reader, 0, size).is_ok_and(|info| is_wiiu_title(info.title_id))
+        })
+    }
+
+    fn is_game_dir(&self, dir: &Path) -> bool {
+        dir.join("meta").join("meta.xml").is_file() && dir.join("code").is_dir()
+    }
+
+    fn analyze_dir(
+        &self,
+        dir: &Path,
+        _options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let meta = read_meta_xml(&dir.join("meta").join("meta.xml"))
+            .ok_or_else(|| AnalysisError::invalid_format("Unreadable meta/meta.xml"))?;
+
+        let mut id = RomIdentification::new().with_platform(Platform::WiiU);
+        id.extra.insert("format".into(), "Folder".into());
+        if let Some(title_id) =
+            xml_tag(&meta, "title_id").and_then(|t| u64::from_str_radix(&t, 16).ok())
+        {
+            id.extra
+                .insert("title_id".into(), format!("{title_id:016X}"));
+            id.extra
+                .insert("title_type".into(), title_type_from_id(title_id).into());
+        }
+        if let Some(version) = xml_tag(&meta, "title_version").and_then(|v| v.parse::<u32>().ok()) {
+            id.version = Some(format!("v{version}"));
+        }
+        apply_meta_xml(&meta, &mut id);
+        Ok(id)
+    }
+
+    fn compute_container_hashes(
+        &self,
+        reader: &mut dyn ReadSeek,
+        algorithms: HashAlgorithms,
+        _file_path: Option<&Path>,
+    ) -> Result<Option<FileHashes>, AnalysisError> {
+        if !wux::has_wux_magic(reader) {
+            return Ok(None);
+        }
+        let image = wux::open_image(reader, &ParseLimits::default())?;
+        let len = image.len();
+        log::info!("Hashing the {} byte WUD in a WUX image", len);
+        Ok(Some(retro_junk_core::hash_reader(
+            &mut image.take(len),
+            algorithms,
+        )?))
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(r"^WUP-[A-Z]-[A-Z0-9]{4}$")
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        retro_junk_core::serial::normalize_hyphenated(serial)
     }
 
     fn dat_names(&self) -> &'static [&'static str] {
         &["Nintendo - Wii U (Digital)"]
     }
 }
+
+/// Identify a disc from its plaintext product code.
+fn analyze_disc(product_code: &str) -> RomIdentification {
+    let game_code = &product_code[PRODUCT_CODE_LEN - 4..];
+    let mut id = RomIdentification::new()
+        .with_platform(Platform::WiiU)
+        .with_serial(product_code);
+    id.regions.extend(region_from_game_code(game_code));
+    id.extra.insert("product_code".into(), product_code.into());
+    id.extra.insert("game_code".into(), game_code.into());
+    id
+}
+
+/// Identify a WUX from the product code its first sector opens with.
+fn analyze_wux(
+    reader: &mut dyn ReadSeek,
+    file_size: u64,
+    options: &AnalysisOptions,
+) -> Result<RomIdentification, AnalysisError> {
+    let mut image = wux::open_image(reader, &options.limits)?;
+    let product_code = read_wud_product_code(&mut image)
+        .ok_or_else(|| AnalysisError::invalid_format("WUX disc has no Wii U product code"))?;
+
+    let mut id = analyze_disc(&product_code);
+    // The compressed file has no fixed size; the disc it holds does
+    id.file_size = Some(file_size);
+    id.extra
+        .insert("uncompressed_size".into(), image.len().to_string());
+    id.extra.insert("format".into(), "WUX".into());
+    Ok(id)
+}
+
+/// Identify a title folder from its TMD, and its meta.xml when the folder
+/// is decrypted.
+fn analyze_tmd(
+    info: &tmd::TmdInfo,
+    file_size: u64,
+    options: &AnalysisOptions,
+) -> RomIdentification {
+    let mut id = RomIdentification::new().with_platform(Platform::WiiU);
+    id.file_size = Some(file_size);
+    id.version = Some(format!("v{}", info.title_version));
+    id.extra.insert("format".into(), "Title folder".into());
+    id.extra
+        .insert("title_id".into(), format!("{:016X}", info.title_id));
+    id.extra.insert(
+        "title_type".into(),
+        title_type_from_id(info.title_id).into(),
+    );
+    id.extra
+        .insert("content_count".into(), info.content_count.to_string());
+
+    let meta = options
+        .file_path
+        .as_deref()
+        .and_then(find_meta_xml)
+        .and_then(|path| read_meta_xml(&path));
+    if let Some(meta) = meta {
+        apply_meta_xml(&meta, &mut id);
+    }
+    id
+}
+
+/// Set the product code, English name and regions from meta.xml.
+fn apply_meta_xml(meta: &str, id: &mut RomIdentification) {
+    if let Some(code) = xml_tag(meta, "product_code") {
+        id.serial_number = Some(code.clone());
+        id.extra.insert("product_code".into(), code);
+    }
+    if let Some(name) = xml_tag(meta, "longname_en") {
+        // Long names break lines with newlines
+        id.internal_name = Some(name.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+    if let Some(bits) = xml_tag(meta, "region").and_then(|r| u32::from_str_radix(&r, 16).ok()) {
+        id.regions = regions_from_bits(bits);
+    }
+}
+
+/// The product code a WUD opens with, if the reader holds one.
+fn read_wud_product_code(reader: &mut dyn ReadSeek) -> Option<String> {
+    let mut buf = [0u8; PRODUCT_CODE_LEN];
+    reader.seek(SeekFrom::Start(0)).ok()?;
+    reader.read_exact(&mut buf).ok()?;
+    reader.seek(SeekFrom::Start(0)).ok()?;
+    is_product_code(&buf).then(|| String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Whether `code` reads "WUP-", a category letter, '-' and a 4-character
+/// game code.
+fn is_product_code(code: &[u8]) -> bool {
+    code.len() == PRODUCT_CODE_LEN
+        && code.starts_with(b"WUP-")
+        && code[4].is_ascii_uppercase()
+        && code[5] == b'-'
+        && code[6..]
+            .iter()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// Wii U titles have IDs starting 0005; vWii titles (0007) are Wii software.
+fn is_wiiu_title(title_id: u64) -> bool {
+    (title_id >> 48) == 0x0005
+}
+
+/// Describe the title type from the high 32 bits of a title ID.
+fn title_type_from_id(title_id: u64) -> &'static str {
+    match (title_id >> 32) as u32 {
+        0x00050000 => "Game",
+        0x00050002 => "Demo",
+        0x0005000C => "DLC",
+        0x0005000E => "Update",
+        0x00050010 => "System Application",
+        _ => "Unknown",
+    }
+}
+
+/// Regions from meta.xml's region bit mask.
+fn regions_from_bits(bits: u32) -> Vec<Region> {
+    [
+        (0x01, Region::Japan),
+        (0x02, Region::Usa),
+        (0x04, Region::Europe),
+    ]
+    .into_iter()
+    .filter(|&(bit, _)| bits & bit != 0)
+    .map(|(_, region)| region)
+    .collect()
+}
+
+/// meta.xml of the folder a title.tmd belongs to: beside it in `meta/`, or
+/// one level up when the TMD sits in `code/`.
+fn find_meta_xml(tmd_path: &Path) -> Option<PathBuf> {
+    let dir = tmd_path.parent()?;
+    [Some(dir), dir.parent()]
+        .into_iter()
+        .flatten()
+        .map(|d| d.join("meta").join("meta.xml"))
+        .find(|p| p.is_file())
+}
+
+fn read_meta_xml(path: &Path) -> Option<String> {
+    let size = std::fs::metadata(path).ok()?.len();
+    (size <= MAX_META_XML_SIZE)
+        .then(|| std::fs::read(path).ok())
+        .flatten()
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// The trimmed text of the first `<tag ...>text</tag>` element, if not blank.
+fn xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{tag}");
+    let start = xml.match_indices(&open).find_map(|(at, _)| {
+        let rest = &xml[at + open.len()..];
+        // Skip longer tags sharing the prefix
+        rest.starts_with(['>', ' ']).then(|| at + open.len())
+    })?;
+    let body = &xml[start..];
+    let body = &body[body.find('>')? + 1..];
+    let text = body[..body.find(&format!("</{tag}>"))?].trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+#[path = "tests/wiiu_tests.rs"]
+mod tests;
@@ -0,0 +1,131 @@
+//! WUX compressed Wii U disc images.
+//!
+//! A WUX keeps each distinct sector of a WUD once. A table after the 32-byte
+//! header maps every sector of the disc to one of the stored sectors, so
+//! repeated sectors (mostly the zeroed space after the game data) take no
+//! room. The stored sectors themselves are not compressed.
+//!
+//! [`open_image`] wraps an image in a [`BlockImage`], which reads like the
+//! WUD it was made from.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use retro_junk_core::{AnalysisError, BlockImage, BlockSource, ParseLimits, ReadSeek};
+
+/// WUX magic: "WUX0" followed by 0x1099D02E (little-endian).
+pub const WUX_MAGIC: &[u8; 8] = b"WUX0\x2E\xD0\x99\x10";
+
+/// Size of the header before the sector index.
+const HEADER_SIZE: u64 = 0x20;
+
+/// Smallest sector size accepted.
+const MIN_SECTOR_SIZE: u32 = 0x100;
+
+/// Largest sector size accepted; real images use 32 KB.
+const MAX_SECTOR_SIZE: u32 = 1024 * 1024;
+
+/// A WUX image, reading one disc sector at a time.
+pub struct WuxSource<R> {
+    inner: R,
+    sector_size: u32,
+    total_bytes: u64,
+    /// Where the stored sectors start, aligned to the sector size.
+    data_offset: u64,
+    /// Stored sector index of each disc sector.
+    index: Vec<u32>,
+}
+
+impl<R: Read + Seek> WuxSource<R> {
+    /// Read the header and sector index.
+    pub fn open(mut inner: R, limits: &ParseLimits) -> Result<Self, AnalysisError> {
+        let file_size = inner.seek(SeekFrom::End(0))?;
+        if file_size < HEADER_SIZE {
+            return Err(AnalysisError::TooSmall {
+                expected: HEADER_SIZE,
+                actual: file_size,
+            });
+        }
+        inner.seek(SeekFrom::Start(0))?;
+        let mut header = [0u8; HEADER_SIZE as usize];
+        inner.read_exact(&mut header)?;
+        if &header[..8] != WUX_MAGIC {
+            return Err(AnalysisError::invalid_magic(0, WUX_MAGIC, &header[..8]));
+        }
+
+        let sector_size = u32::from_le_bytes(header[0x08..0x0C].try_into().unwrap());
+        let total_bytes = u64::from_le_bytes(header[0x10..0x18].try_into().unwrap());
+        if !(MIN_SECTOR_SIZE..=MAX_SECTOR_SIZE).contains(&sector_size) {
+            return Err(AnalysisError::corrupted_header(format!(
+                "WUX: implausible sector size {sector_size}"
+            )));
+        }
+
+        let sectors = total_bytes.div_ceil(u64::from(sector_size));
+        let index_len = limits.check_alloc(sectors * 4, "WUX sector index")?;
+        let mut raw = vec![0u8; index_len];
+        inner.seek(SeekFrom::Start(HEADER_SIZE))?;
+        inner
+            .read_exact(&mut raw)
+            .map_err(|_| AnalysisError::corrupted_header("WUX: sector index is truncated"))?;
+        let index = raw
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+
+        Ok(Self {
+            inner,
+            sector_size,
+            total_bytes,
+            data_offset: (HEADER_SIZE + sectors * 4).next_multiple_of(u64::from(sector_size)),
+            index,
+        })
+    }
+}
+
+impl<R: Read + Seek> BlockSource for WuxSource<R> {
+    fn block_size(&self) -> usize {
+        self.sector_size as usize
+    }
+
+    fn image_len(&self) -> u64 {
+        self.total_bytes
+    }
+
+    fn read_block(&mut self, index: u64, buf: &mut Vec<u8>) -> io::Result<()> {
+        let Some(&stored) = self.index.get(index as usize) else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("sector {index} is past the end of the disc"),
+            ));
+        };
+        let sector_size = u64::from(self.sector_size);
+        let out_len = (self.total_bytes - index * sector_size).min(sector_size) as usize;
+        buf.resize(out_len, 0);
+        self.inner.seek(SeekFrom::Start(
+            self.data_offset + u64::from(stored) * sector_size,
+        ))?;
+        self.inner.read_exact(buf)
+    }
+}
+
+/// Whether the reader opens with the WUX magic.
+pub fn has_wux_magic(reader: &mut dyn ReadSeek) -> bool {
+    let mut magic = [0u8; 8];
+    let found = reader.seek(SeekFrom::Start(0)).is_ok()
+        && reader.read_exact(&mut magic).is_ok()
+        && &magic == WUX_MAGIC;
+    let _ = reader.seek(SeekFrom::Start(0));
+    found
+}
+
+/// Open a WUX image as a readable WUD.
+pub fn open_image<R: Read + Seek>(
+    inner: R,
+    limits: &ParseLimits,
+) -> Result<BlockImage<WuxSource<R>>, AnalysisError> {
+    Ok(BlockImage::new(WuxSource::open(inner, limits)?))
+}
+
+#[cfg(test)]
+#[path = "tests/wux_tests.rs"]
+mod tests;
@@ -1,17 +1,22 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use retro_junk_core::{FileHashes, HashAlgorithms, StreamHasher};
 use retro_junk_frontend::AssetType;
 use tokio::sync::mpsc;
 
 use crate::client::ScreenScraperClient;
 use crate::error::ScrapeError;
 use crate::scrape::ScrapeEvent;
-use crate::types::GameInfo;
+use crate::types::{GameInfo, Media};
 
 /// Configuration for which asset types to download.
 #[derive(Debug, Clone)]
 pub struct AssetSelection {
     pub types: Vec<AssetType>,
+    /// Per-type region preferences (ScreenScraper region codes) that override
+    /// the session's preferred region, e.g. JP box art with US title screens.
+    pub region_overrides: HashMap<AssetType, String>,
 }
 
 impl Default for AssetSelection {
@@ -25,6 +30,7 @@ impl Default for AssetSelection {
                 AssetType::PhysicalMedia,
                 AssetType::Video,
             ],
+            region_overrides: HashMap::new(),
         }
     }
 }
@@ -34,6 +40,7 @@ impl AssetSelection {
         Self {
             types: vec![
                 AssetType::Cover,
+                AssetType::BackCover,
                 AssetType::Cover3D,
                 AssetType::Screenshot,
                 AssetType::TitleScreen,
@@ -41,67 +48,234 @@ impl AssetSelection {
                 AssetType::Video,
                 AssetType::Fanart,
                 AssetType::PhysicalMedia,
+                AssetType::Label,
             ],
+            region_overrides: HashMap::new(),
         }
     }
 
     /// Parse from a comma-separated list (e.g., "covers,screenshots,videos").
     pub fn from_names(names: &[String]) -> Self {
-        let types = names
-            .iter()
-            .filter_map(|n| match n.as_str() {
-                "covers" | "cover" => Some(AssetType::Cover),
-                "3dboxes" | "3dbox" | "cover3d" => Some(AssetType::Cover3D),
-                "screenshots" | "screenshot" => Some(AssetType::Screenshot),
-                "titlescreens" | "titlescreen" => Some(AssetType::TitleScreen),
-                "marquees" | "marquee" => Some(AssetType::Marquee),
-                "videos" | "video" => Some(AssetType::Video),
-                "fanart" => Some(AssetType::Fanart),
-                "physicalmedia" => Some(AssetType::PhysicalMedia),
-                _ => None,
-            })
-            .collect();
-        Self { types }
-    }
-}
-
-/// Map an AssetType to the ScreenScraper media type string.
-fn ss_asset_type(at: AssetType) -> &'static str {
-    match at {
-        AssetType::Screenshot => "ss",
-        AssetType::TitleScreen => "sstitle",
-        AssetType::Cover => "box-2D",
-        AssetType::Cover3D => "box-3D",
-        AssetType::Marquee => "wheel-hd",
-        AssetType::Video => "video-normalized",
-        AssetType::Fanart => "fanart",
-        AssetType::PhysicalMedia => "support-2D",
-        AssetType::Miximage => unreachable!("Miximage is generated, not downloaded"),
+        let types = names.iter().filter_map(|n| parse_asset_name(n)).collect();
+        Self {
+            types,
+            region_overrides: HashMap::new(),
+        }
+    }
+
+    /// Prefer `region` for one asset type, overriding the session region.
+    pub fn with_region(mut self, asset_type: AssetType, region: impl Into<String>) -> Self {
+        self.region_overrides.insert(asset_type, region.into());
+        self
+    }
+
+    /// Apply `type=region` pairs (e.g., "covers=jp", "titlescreens=us").
+    ///
+    /// `region_code` maps a region name to its ScreenScraper code, or `None`
+    /// for regions it doesn't know. Returns the entries with an unknown type
+    /// or region so the caller can report them.
+    pub fn apply_region_overrides(
+        &mut self,
+        pairs: &[String],
+        region_code: impl Fn(&str) -> Option<&'static str>,
+    ) -> Vec<String> {
+        let mut invalid = Vec::new();
+        for pair in pairs {
+            match pair.split_once('=').and_then(|(name, region)| {
+                Some((
+                    parse_asset_name(name.trim())?,
+                    region_code(&region.trim().to_lowercase())?,
+                ))
+            }) {
+                Some((at, region)) => {
+                    self.region_overrides.insert(at, region.to_string());
+                }
+                _ => invalid.push(pair.clone()),
+            }
+        }
+        invalid
+    }
+
+    /// The region to prefer for an asset type, falling back to `default`.
+    pub fn region_for<'a>(&'a self, asset_type: AssetType, default: &'a str) -> &'a str {
+        self.region_overrides
+            .get(&asset_type)
+            .map(String::as_str)
+            .unwrap_or(default)
     }
 }
 
-/// Fallback ScreenScraper media type if the primary isn't found.
-fn ss_asset_type_fallback(at: AssetType) -> Option<&'static str> {
-    match at {
-        AssetType::Marquee => Some("wheel"),
-        AssetType::Video => Some("video"),
+/// Parse a user-facing asset type name (e.g., "covers", "3dbox").
+fn parse_asset_name(name: &str) -> Option<AssetType> {
+    match name {
+        "covers" | "cover" => Some(AssetType::Cover),
+        "backcovers" | "backcover" => Some(AssetType::BackCover),
+        "3dboxes" | "3dbox" | "cover3d" => Some(AssetType::Cover3D),
+        "screenshots" | "screenshot" => Some(AssetType::Screenshot),
+        "titlescreens" | "titlescreen" => Some(AssetType::TitleScreen),
+        "marquees" | "marquee" => Some(AssetType::Marquee),
+        "videos" | "video" => Some(AssetType::Video),
+        "fanart" => Some(AssetType::Fanart),
+        "physicalmedia" => Some(AssetType::PhysicalMedia),
+        "labels" | "label" => Some(AssetType::Label),
+        "pcbs" | "pcb" => Some(AssetType::Pcb),
         _ => None,
     }
 }
 
+/// ScreenScraper media types for an asset type, most preferred first.
+///
+/// Empty for types ScreenScraper doesn't provide: miximages are generated
+/// locally and PCB photos only come from local files.
+pub fn ss_media_types(at: AssetType) -> &'static [&'static str] {
+    match at {
+        AssetType::Screenshot => &["ss"],
+        AssetType::TitleScreen => &["sstitle"],
+        AssetType::Cover => &["box-2D"],
+        AssetType::BackCover => &["box-2D-back"],
+        AssetType::Cover3D => &["box-3D"],
+        AssetType::Marquee => &["wheel-hd", "wheel"],
+        AssetType::Video => &["video-normalized", "video"],
+        AssetType::Fanart => &["fanart"],
+        AssetType::PhysicalMedia => &["support-2D"],
+        AssetType::Label => &["support-texture"],
+        AssetType::Pcb | AssetType::Miximage => &[],
+    }
+}
+
+/// The asset type a ScreenScraper media type downloads as, if any.
+pub fn asset_type_for_ss_media(ss_type: &str) -> Option<AssetType> {
+    AssetType::ALL
+        .iter()
+        .copied()
+        .find(|&at| ss_media_types(at).contains(&ss_type))
+}
+
+retro_junk_core::named_enum! {
+    /// How downloaded asset files are named, since frontends and theme packs
+    /// look images up by different names.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum AssetNaming("asset naming") {
+        /// After the ROM file, as ES-DE expects: `covers/Super Mario Bros. (USA).png`.
+        #[default]
+        RomStem = "rom" | "rom-stem",
+        /// After the release slug: `covers/super-mario-bros-usa.png`.
+        Slug = "slug",
+        /// After the game's serial: `covers/SLUS-00594.png`. Games without a
+        /// serial fall back to the ROM stem.
+        Serial = "serial",
+    }
+}
+
+impl AssetNaming {
+    /// File name of an asset, without its extension.
+    pub fn file_stem(&self, names: &AssetNames) -> String {
+        match (self, names.serial) {
+            (Self::RomStem, _) | (Self::Serial, None) => names.rom_stem.to_string(),
+            (Self::Slug, _) => names.slug.to_string(),
+            // Serials can hold characters that aren't safe in file names
+            (Self::Serial, Some(serial)) => serial
+                .chars()
+                .map(|c| match c {
+                    '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+                    c => c,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The names a game's assets can be filed under.
+#[derive(Debug, Clone, Copy)]
+pub struct AssetNames<'a> {
+    /// ROM file name without its extension (a catalog release's DAT name).
+    pub rom_stem: &'a str,
+    pub slug: &'a str,
+    pub serial: Option<&'a str>,
+}
+
 /// Subdirectory name for an asset type (matches ES-DE layout).
 pub fn asset_subdir(at: AssetType) -> &'static str {
-    match at {
-        AssetType::Cover => "covers",
-        AssetType::Cover3D => "3dboxes",
-        AssetType::Screenshot => "screenshots",
-        AssetType::TitleScreen => "titlescreens",
-        AssetType::Marquee => "marquees",
-        AssetType::Video => "videos",
-        AssetType::Fanart => "fanart",
-        AssetType::PhysicalMedia => "physicalmedia",
-        AssetType::Miximage => "miximages",
+    at.media_subdir()
+}
+
+/// Number of download attempts before giving up on media that fails its hash check.
+const MEDIA_DOWNLOAD_ATTEMPTS: usize = 2;
+
+/// Outcome of checking downloaded bytes against ScreenScraper's media hashes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaIntegrity {
+    /// At least one API-provided hash matched and none disagreed.
+    Verified,
+    /// The API provided no hashes to check against.
+    Unchecked,
+    /// An API-provided hash did not match the downloaded bytes.
+    Mismatch,
+}
+
+/// Check downloaded media bytes against the CRC32/MD5/SHA1 reported by the API.
+pub fn check_media_integrity(media: &Media, bytes: &[u8]) -> MediaIntegrity {
+    fn expected(h: &Option<String>) -> Option<String> {
+        h.as_deref()
+            .map(|v| v.trim().to_lowercase())
+            .filter(|v| !v.is_empty())
+    }
+
+    let hashes = file_hashes(bytes, HashAlgorithms::All);
+    let mut checked = false;
+    for (expected, actual) in [
+        (expected(&media.crc), Some(&hashes.crc32)),
+        (expected(&media.md5), hashes.md5.as_ref()),
+        (expected(&media.sha1), hashes.sha1.as_ref()),
+    ] {
+        if let Some(expected) = expected {
+            checked = true;
+            if actual != Some(&expected) {
+                return MediaIntegrity::Mismatch;
+            }
+        }
+    }
+
+    if checked {
+        MediaIntegrity::Verified
+    } else {
+        MediaIntegrity::Unchecked
+    }
+}
+
+/// Hash stored alongside downloaded assets for later integrity audits (SHA1 hex).
+pub fn asset_file_hash(bytes: &[u8]) -> String {
+    file_hashes(bytes, HashAlgorithms::Crc32Sha1)
+        .sha1
+        .unwrap_or_default()
+}
+
+fn file_hashes(bytes: &[u8], algorithms: HashAlgorithms) -> FileHashes {
+    let mut hasher = StreamHasher::new(algorithms);
+    hasher.update(bytes);
+    hasher.finish()
+}
+
+/// Download a media file, re-downloading when it fails its hash check.
+pub async fn download_verified_media(
+    client: &ScreenScraperClient,
+    media: &Media,
+) -> Result<Vec<u8>, ScrapeError> {
+    for attempt in 1..=MEDIA_DOWNLOAD_ATTEMPTS {
+        let bytes = client.download_media(&media.url).await?;
+        match check_media_integrity(media, &bytes) {
+            MediaIntegrity::Verified | MediaIntegrity::Unchecked => return Ok(bytes),
+            MediaIntegrity::Mismatch => {
+                log::debug!(
+                    "Hash mismatch for {} (attempt {}/{})",
+                    media.url,
+                    attempt,
+                    MEDIA_DOWNLOAD_ATTEMPTS
+                );
+            }
+        }
     }
+    Err(ScrapeError::HashMismatch(media.url.clone()))
 }
 
 /// Collect paths for asset files that already exist on disk for a given ROM.
@@ -151,17 +325,10 @@ pub async fn download_game_assets(
     let mut downloads = Vec::new();
 
     for &at in &selection.types {
-        // Miximage is generated locally, never downloaded
-        if at == AssetType::Miximage {
-            continue;
-        }
-        let ss_type = ss_asset_type(at);
-        let media = game
-            .media_for_region(ss_type, preferred_region)
-            .or_else(|| {
-                ss_asset_type_fallback(at)
-                    .and_then(|fb| game.media_for_region(fb, preferred_region))
-            });
+        let region = selection.region_for(at, preferred_region);
+        let media = ss_media_types(at)
+            .iter()
+            .find_map(|ss_type| game.media_for_region(ss_type, region));
 
         if let Some(media) = media {
             let ext = if media.format.is_empty() {
@@ -178,19 +345,21 @@ pub async fn download_game_assets(
                 continue;
             }
 
-            downloads.push((at, media.url.clone(), subdir, dest));
+            downloads.push((at, media.clone(), dest));
         }
     }
 
     // Build (AssetType, Future) pairs so we can emit events before each download
     let handles: Vec<_> = downloads
         .into_iter()
-        .map(|(at, url, subdir, dest)| {
-            let client_url = url.clone();
+        .map(|(at, media, dest)| {
             let client_ref = client;
             let fut = async move {
-                std::fs::create_dir_all(&subdir)?;
-                let bytes = client_ref.download_media(&client_url).await?;
+                // Nested ROM stems (e.g., "A/Game") put media in a subfolder too
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let bytes = download_verified_media(client_ref, &media).await?;
                 std::fs::write(&dest, &bytes)?;
                 Ok::<PathBuf, ScrapeError>(dest)
             };
@@ -218,3 +387,7 @@ pub async fn download_game_assets(
 
     Ok(results)
 }
+
+#[cfg(test)]
+#[path = "tests/assets_tests.rs"]
+mod tests;
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 use std::time::SystemTime;
 
+use retro_junk_lib::metrics::Metrics;
+
 use tokio::sync::Mutex;
 use tokio::time::Duration;
 
-use crate::credentials::Credentials;
+use crate::credentials::{Credentials, load_client_config};
 use crate::error::ScrapeError;
 use crate::types::{JeuInfosResponse, UserInfo, UserInfoResponse, UserQuota};
 
@@ -13,21 +16,77 @@ use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
 const BASE_URL: &str = "https://api.screenscraper.fr/api2";
 const MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(1200);
 
-/// Hard timeout for API requests (covers connect + headers + body read).
+/// Default hard timeout for API requests (covers connect + headers + body read).
 const API_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Default TCP connect timeout.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Timeout for acquiring internal mutex locks (should be near-instant).
 const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
 
-/// Maximum number of retries for transient server errors.
+/// Default maximum number of retries for transient server errors.
 const MAX_RETRIES: u32 = 3;
 
 /// Initial backoff duration before first retry (doubles each attempt).
 const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
 
-/// Hard timeout for media file downloads.
+/// Default hard timeout for media file downloads.
 const MEDIA_TIMEOUT: Duration = Duration::from_secs(120);
 
+/// Tunables for the HTTP client, separate from the credentials.
+///
+/// Loaded from the `[client]` section of the credentials file by
+/// [`ClientConfig::load`]. Applications embedding the scraper can build one
+/// directly to identify themselves with their own user agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    /// User-Agent header. `None` derives one from the soft name.
+    pub user_agent: Option<String>,
+    /// Hard timeout for each API request.
+    pub api_timeout: Duration,
+    /// TCP connect timeout.
+    pub connect_timeout: Duration,
+    /// Hard timeout for each media download.
+    pub media_timeout: Duration,
+    /// Retries after a transient server error.
+    pub max_retries: u32,
+    /// Largest media file to download, in bytes. `None` means no limit.
+    pub max_media_bytes: Option<u64>,
+    /// Base URL of the ScreenScraper API.
+    pub api_base_url: String,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: None,
+            api_timeout: API_TIMEOUT,
+            connect_timeout: CONNECT_TIMEOUT,
+            media_timeout: MEDIA_TIMEOUT,
+            max_retries: MAX_RETRIES,
+            max_media_bytes: None,
+            api_base_url: BASE_URL.to_string(),
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Load the client configuration from the config file, falling back to
+    /// defaults for anything not set.
+    pub fn load() -> Self {
+        load_client_config()
+    }
+
+    /// The User-Agent to send for the given soft name.
+    pub fn user_agent_for(&self, soft_name: &str) -> String {
+        match &self.user_agent {
+            Some(ua) => ua.clone(),
+            None => format!("{} (retro-junk/{})", soft_name, env!("CARGO_PKG_VERSION")),
+        }
+    }
+}
+
 /// HTTP client for the ScreenScraper API with rate limiting and quota tracking.
 ///
 /// Concurrency is controlled externally by the caller (e.g., worker pool count
@@ -36,7 +95,10 @@ const MEDIA_TIMEOUT: Duration = Duration::from_secs(120);
 pub struct ScreenScraperClient {
     http: reqwest::Client,
     creds: Credentials,
+    config: ClientConfig,
     quota: Mutex<Option<UserQuota>>,
+    /// Where quota updates are reported, if anywhere.
+    metrics: OnceLock<Arc<Metrics>>,
     /// Monotonic request counter for correlating log lines.
     request_counter: AtomicU64,
 }
@@ -44,12 +106,22 @@ pub struct ScreenScraperClient {
 impl ScreenScraperClient {
     /// Create a new client and validate credentials by calling ssuserInfos.php.
     ///
+    /// Client settings come from the config file (see [`ClientConfig::load`]).
     /// Returns the client and user info (which includes max_threads for the
     /// caller to configure its own concurrency control).
     pub async fn new(creds: Credentials) -> Result<(Self, UserInfo), ScrapeError> {
+        Self::with_config(creds, ClientConfig::load()).await
+    }
+
+    /// Create a new client with explicit client settings.
+    pub async fn with_config(
+        creds: Credentials,
+        config: ClientConfig,
+    ) -> Result<(Self, UserInfo), ScrapeError> {
         let http = reqwest::Client::builder()
-            .connect_timeout(Duration::from_secs(10))
-            .read_timeout(Duration::from_secs(30))
+            .user_agent(config.user_agent_for(&creds.soft_name))
+            .connect_timeout(config.connect_timeout)
+            .read_timeout(config.api_timeout)
             .pool_max_idle_per_host(10)
             .pool_idle_timeout(Duration::from_secs(90))
             .tcp_keepalive(Duration::from_secs(30))
@@ -59,7 +131,9 @@ impl ScreenScraperClient {
         let client = Self {
             http,
             creds,
+            config,
             quota: Mutex::new(None),
+            metrics: OnceLock::new(),
             request_counter: AtomicU64::new(0),
         };
 
@@ -74,7 +148,10 @@ impl ScreenScraperClient {
         params.insert("output", "json".to_string());
 
         let text = self
-            .rate_limited_get(&format!("{}/ssuserInfos.php", BASE_URL), &params)
+            .rate_limited_get(
+                &format!("{}/ssuserInfos.php", self.config.api_base_url),
+                &params,
+            )
             .await?;
 
         let status_err = check_auth_status_from_text(&text);
@@ -104,7 +181,10 @@ impl ScreenScraperClient {
         }
 
         let text = self
-            .rate_limited_get(&format!("{}/jeuInfos.php", BASE_URL), &all_params)
+            .rate_limited_get(
+                &format!("{}/jeuInfos.php", self.config.api_base_url),
+                &all_params,
+            )
             .await?;
 
         // Check for error patterns in the response text.
@@ -156,7 +236,12 @@ impl ScreenScraperClient {
         // Update quota tracking
         if let Some(ref user) = response.response.ssuser {
             match tokio::time::timeout(LOCK_TIMEOUT, self.quota.lock()).await {
-                Ok(mut guard) => *guard = Some(user.clone()),
+                Ok(mut guard) => {
+                    if let Some(metrics) = self.metrics.get() {
+                        metrics.set_api_requests(user.requests_today().into());
+                    }
+                    *guard = Some(user.clone());
+                }
                 Err(_) => log::debug!("Quota lock timed out during update"),
             }
         }
@@ -164,24 +249,45 @@ impl ScreenScraperClient {
         Ok(response)
     }
 
+    /// The client settings in use.
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
     /// Download a media file from a URL with a hard timeout.
     ///
     /// Media CDN downloads don't count against the API rate limit, so no
     /// rate limiting is applied here — but we still enforce a total timeout
-    /// to prevent hangs when ScreenScraper stalls mid-transfer.
+    /// to prevent hangs when ScreenScraper stalls mid-transfer. Files larger
+    /// than the configured maximum are abandoned as soon as that is known.
     pub async fn download_media(&self, url: &str) -> Result<Vec<u8>, ScrapeError> {
-        tokio::time::timeout(MEDIA_TIMEOUT, async {
-            let resp = self.http.get(url).send().await?;
-            Ok::<_, reqwest::Error>(resp.bytes().await?.to_vec())
+        let max = self.config.max_media_bytes;
+        let too_large = || ScrapeError::MediaTooLarge {
+            url: redact_credentials(url),
+            max: max.unwrap_or_default(),
+        };
+
+        tokio::time::timeout(self.config.media_timeout, async {
+            let mut resp = self.http.get(url).send().await?;
+            if exceeds_limit(resp.content_length().unwrap_or(0), max) {
+                return Err(too_large());
+            }
+            let mut bytes = Vec::new();
+            while let Some(chunk) = resp.chunk().await? {
+                bytes.extend_from_slice(&chunk);
+                if exceeds_limit(bytes.len() as u64, max) {
+                    return Err(too_large());
+                }
+            }
+            Ok(bytes)
         })
         .await
         .map_err(|_| {
             ScrapeError::Api(format!(
                 "Media download timed out after {}s",
-                MEDIA_TIMEOUT.as_secs()
+                self.config.media_timeout.as_secs()
             ))
         })?
-        .map_err(ScrapeError::from)
     }
 
     /// Get current quota info if available.
@@ -195,6 +301,16 @@ impl ScreenScraperClient {
         }
     }
 
+    /// Report the API requests used today to `metrics`, now and after every
+    /// lookup that returns an updated quota. Only the first call takes
+    /// effect.
+    pub async fn report_quota_to(&self, metrics: Arc<Metrics>) {
+        if let Some(quota) = self.current_quota().await {
+            metrics.set_api_requests(quota.requests_today().into());
+        }
+        let _ = self.metrics.set(metrics);
+    }
+
     /// Perform a rate-limited HTTP GET request with retries for transient errors.
     ///
     /// After each request, sleeps for `MIN_REQUEST_INTERVAL` to enforce
@@ -222,7 +338,10 @@ impl ScreenScraperClient {
 
         let request_start = tokio::time::Instant::now();
 
-        for attempt in 0..=MAX_RETRIES {
+        let max_retries = self.config.max_retries;
+        let api_timeout = self.config.api_timeout;
+
+        for attempt in 0..=max_retries {
             // Back off before retries (not before the first attempt)
             if attempt > 0 {
                 let backoff = INITIAL_BACKOFF * 2u32.pow(attempt - 1);
@@ -231,7 +350,7 @@ impl ScreenScraperClient {
                     req_id,
                     endpoint,
                     attempt + 1,
-                    MAX_RETRIES + 1,
+                    max_retries + 1,
                     backoff.as_secs(),
                 );
                 tokio::time::sleep(backoff).await;
@@ -240,7 +359,7 @@ impl ScreenScraperClient {
             let attempt_start = tokio::time::Instant::now();
             let wall_start = SystemTime::now();
 
-            let result = tokio::time::timeout(API_TIMEOUT, async {
+            let result = tokio::time::timeout(api_timeout, async {
                 let resp = self
                     .http
                     .get(url)
@@ -345,7 +464,7 @@ impl ScreenScraperClient {
                     consecutive_timeouts += 1;
                     let e = ScrapeError::Api(format!(
                         "API request timed out after {}s",
-                        API_TIMEOUT.as_secs()
+                        api_timeout.as_secs()
                     ));
                     log::warn!(
                         "[req:{}] {} TIMEOUT after {}ms ({} consecutive)",
@@ -416,6 +535,11 @@ fn looks_like_html_error(text: &str) -> bool {
     trimmed.starts_with("<!DOCTYPE") || trimmed.starts_with("<html") || trimmed.starts_with("<HTML")
 }
 
+/// Whether `len` bytes is over the optional media size limit.
+fn exceeds_limit(len: u64, max: Option<u64>) -> bool {
+    max.is_some_and(|max| len > max)
+}
+
 /// Check if a ScrapeError is retryable (transient server issue).
 fn is_retryable(e: &ScrapeError) -> bool {
     matches!(e, ScrapeError::ServerError { .. })
@@ -432,7 +556,16 @@ pub async fn create_client(
     let creds = Credentials::load()
         .map_err(|e| ScrapeError::Api(format!("Failed to load credentials: {e}")))?;
 
-    let (client, user_info) = ScreenScraperClient::new(creds).await?;
+    create_client_with(creds, ClientConfig::load(), threads).await
+}
+
+/// Like [`create_client`], with explicit credentials and client settings.
+pub async fn create_client_with(
+    creds: Credentials,
+    config: ClientConfig,
+    threads: Option<usize>,
+) -> Result<(std::sync::Arc<ScreenScraperClient>, usize), ScrapeError> {
+    let (client, user_info) = ScreenScraperClient::with_config(creds, config).await?;
 
     let ss_max = user_info.max_threads() as usize;
     let cpu_max = std::thread::available_parallelism()
@@ -495,14 +628,21 @@ fn redact_credentials(msg: &str) -> String {
     for param in &["devpassword", "sspassword", "devid", "ssid"] {
         // Match param=value where value ends at & or end of string/whitespace
         let prefix = format!("{}=", param);
-        while let Some(start) = result.find(&prefix) {
-            let value_start = start + prefix.len();
+        let mut search_from = 0;
+        while let Some(found) = result[search_from..].find(&prefix) {
+            let value_start = search_from + found + prefix.len();
             let value_end = result[value_start..]
                 .find(|c: char| c == '&' || c.is_whitespace() || c == '"' || c == '\'')
                 .map(|i| value_start + i)
                 .unwrap_or(result.len());
             result.replace_range(value_start..value_end, "[REDACTED]");
+            // Resume after the replacement so the same prefix isn't found again
+            search_from = value_start + "[REDACTED]".len();
         }
     }
     result
 }
+
+#[cfg(test)]
+#[path = "tests/client_tests.rs"]
+mod tests;
@@ -1,5 +1,7 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
+use crate::client::ClientConfig;
 use crate::error::ScrapeError;
 
 // XOR-obfuscated dev credentials embedded at compile time.
@@ -79,6 +81,43 @@ pub struct CredentialSources {
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 struct ConfigFile {
     screenscraper: Option<ScreenScraperConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client: Option<ClientConfigFile>,
+}
+
+/// `[client]` section: HTTP client tunables. Durations are in seconds.
+#[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
+struct ClientConfigFile {
+    user_agent: Option<String>,
+    api_timeout_secs: Option<u64>,
+    connect_timeout_secs: Option<u64>,
+    media_timeout_secs: Option<u64>,
+    max_retries: Option<u32>,
+    max_media_bytes: Option<u64>,
+}
+
+impl ClientConfigFile {
+    fn into_config(self) -> ClientConfig {
+        let defaults = ClientConfig::default();
+        ClientConfig {
+            user_agent: self.user_agent.or(defaults.user_agent),
+            api_timeout: self
+                .api_timeout_secs
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.api_timeout),
+            connect_timeout: self
+                .connect_timeout_secs
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.connect_timeout),
+            media_timeout: self
+                .media_timeout_secs
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.media_timeout),
+            max_retries: self.max_retries.unwrap_or(defaults.max_retries),
+            max_media_bytes: self.max_media_bytes.or(defaults.max_media_bytes),
+            api_base_url: defaults.api_base_url,
+        }
+    }
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
@@ -144,6 +183,13 @@ impl Credentials {
         })
     }
 
+    /// Identify requests with a different soft name (the application name
+    /// ScreenScraper attributes requests to).
+    pub fn with_soft_name(mut self, soft_name: impl Into<String>) -> Self {
+        self.soft_name = soft_name.into();
+        self
+    }
+
     /// Create credentials with explicit values (e.g., from CLI args).
     pub fn with_overrides(
         mut self,
@@ -193,7 +239,11 @@ pub fn save_to_file(creds: &Credentials) -> Result<PathBuf, ScrapeError> {
     let dev_pw_differs = embedded_pw.as_ref() != Some(&creds.dev_password);
     let save_dev = dev_id_differs || dev_pw_differs;
 
+    // Keep any client settings already in the file
+    let client = load_full_config().and_then(|c| c.client);
+
     let config = ConfigFile {
+        client,
         screenscraper: Some(ScreenScraperConfig {
             dev_id: if save_dev {
                 Some(creds.dev_id.clone())
@@ -287,9 +337,29 @@ pub fn credential_sources() -> CredentialSources {
     }
 }
 
+/// Load the `[client]` section of the config file, with defaults for
+/// anything not set.
+pub fn load_client_config() -> ClientConfig {
+    load_full_config()
+        .and_then(|c| c.client)
+        .unwrap_or_default()
+        .into_config()
+}
+
 fn load_config_file() -> Option<ScreenScraperConfig> {
+    load_full_config()?.screenscraper
+}
+
+fn load_full_config() -> Option<ConfigFile> {
     let path = config_path()?;
     let content = std::fs::read_to_string(&path).ok()?;
-    let config: ConfigFile = toml::from_str(&content).ok()?;
-    config.screenscraper
+    parse_config(&content)
+}
+
+fn parse_config(content: &str) -> Option<ConfigFile> {
+    toml::from_str(content).ok()
 }
+
+#[cfg(test)]
+#[path = "tests/credentials_tests.rs"]
+mod tests;
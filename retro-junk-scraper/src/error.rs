@@ -25,6 +25,12 @@ pub enum ScrapeError {
     #[error("API error: {0}")]
     Api(String),
 
+    #[error("Media file exceeds the {max}-byte size limit: {url}")]
+    MediaTooLarge { url: String, max: u64 },
+
+    #[error("Downloaded media failed hash verification: {0}")]
+    HashMismatch(String),
+
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
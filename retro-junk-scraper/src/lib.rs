@@ -4,15 +4,16 @@ pub mod credentials;
 pub mod error;
 pub mod log;
 pub mod lookup;
+pub mod media_pool;
 pub mod scrape;
 pub mod systems;
 pub mod types;
 
-pub use assets::{AssetSelection, asset_subdir};
-pub use client::{ScreenScraperClient, create_client};
+pub use assets::{AssetNames, AssetNaming, AssetSelection, asset_subdir};
+pub use client::{ClientConfig, ScreenScraperClient, create_client, create_client_with};
 pub use credentials::{
     CredentialSource, CredentialSources, Credentials, config_path, credential_sources,
-    has_embedded_dev_credentials, save_to_file,
+    has_embedded_dev_credentials, load_client_config, save_to_file,
 };
 pub use error::ScrapeError;
 pub use log::{LogEntry, ScrapeLog};
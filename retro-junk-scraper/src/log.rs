@@ -73,6 +73,7 @@ impl ScrapeLog {
                         LookupMethod::Serial => summary.by_serial += 1,
                         LookupMethod::Filename => summary.by_filename += 1,
                         LookupMethod::Hash => summary.by_hash += 1,
+                        LookupMethod::Parent => summary.by_parent += 1,
                     }
                 }
                 LogEntry::Partial { .. } => summary.total_partial += 1,
@@ -101,8 +102,12 @@ impl ScrapeLog {
         writeln!(file, "--- Summary ---")?;
         writeln!(
             file,
-            "Successful: {} (serial: {}, filename: {}, hash: {})",
-            summary.total_success, summary.by_serial, summary.by_filename, summary.by_hash
+            "Successful: {} (serial: {}, filename: {}, hash: {}, parent: {})",
+            summary.total_success,
+            summary.by_serial,
+            summary.by_filename,
+            summary.by_hash,
+            summary.by_parent
         )?;
         writeln!(file, "Grouped discs: {}", summary.total_grouped)?;
         writeln!(file, "Partial: {}", summary.total_partial)?;
@@ -209,4 +214,5 @@ pub struct LogSummary {
     pub by_serial: usize,
     pub by_filename: usize,
     pub by_hash: usize,
+    pub by_parent: usize,
 }
@@ -23,6 +23,8 @@ pub enum LookupMethod {
     Filename,
     /// Matched by hash (CRC32 + MD5 + SHA1)
     Hash,
+    /// Matched by the parent set's filename after the clone itself was not found
+    Parent,
 }
 
 impl std::fmt::Display for LookupMethod {
@@ -31,6 +33,7 @@ impl std::fmt::Display for LookupMethod {
             LookupMethod::Serial => write!(f, "serial"),
             LookupMethod::Filename => write!(f, "filename"),
             LookupMethod::Hash => write!(f, "hash"),
+            LookupMethod::Parent => write!(f, "parent"),
         }
     }
 }
@@ -64,6 +67,9 @@ pub struct RomInfo {
     pub platform: Platform,
     /// Whether this platform's analyzer expects ROMs to have serials
     pub expects_serial: bool,
+    /// Parent set filename for clones (from DAT parent/clone data), tried
+    /// when the clone itself isn't found
+    pub parent_filename: Option<String>,
 }
 
 /// Look up a game using the tiered strategy.
@@ -72,7 +78,9 @@ pub struct RomInfo {
 /// 2. Hash match — if CRC32 + MD5 + SHA1 are available (e.g., from DAT entries).
 ///    Skipped naturally when hashes are None (e.g., scrape path for serial
 ///    consoles that skip hashing for speed).
-/// 3. Filename match — last resort using NoIntro filename + system ID + file size
+/// 3. Filename match — using NoIntro filename + system ID + file size
+/// 4. Parent match — for clone sets, the parent's filename, so clones without
+///    their own entry inherit the parent's metadata
 ///
 /// Each tier validates that the returned game belongs to the expected platform.
 /// If a result comes back for the wrong platform (e.g., a serial collision),
@@ -192,6 +200,33 @@ pub async fn lookup_game(
             Err(e) => return Err(e),
         }
 
+        // Tier 4: Parent set — clones often have no entry of their own, so
+        // fall back to the parent's metadata.
+        if let Some(parent) = &rom_info.parent_filename {
+            log::debug!("Tier 4 (parent): trying '{}' for '{}'", parent, filename);
+            match try_filename_lookup(client, system_id, parent, rom_info.file_size).await {
+                Ok(game) => {
+                    if let Some(warning) = check_platform_mismatch(&game, system_id, rom_info.platform) {
+                        warnings.push(format!(
+                            "Parent '{}' matched wrong platform: {}",
+                            parent, warning,
+                        ));
+                    } else {
+                        warnings.push(format!("Using metadata from parent set '{}'", parent));
+                        return Ok(LookupResult {
+                            game,
+                            method: LookupMethod::Parent,
+                            warnings,
+                        });
+                    }
+                }
+                Err(ScrapeError::NotFound { .. }) => {
+                    warnings.push(format!("Parent '{}' not found in ScreenScraper", parent));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
         Err(ScrapeError::NotFound { warnings })
     })
     .await
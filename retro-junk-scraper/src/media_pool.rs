@@ -0,0 +1,105 @@
+//! Worker pool for image post-processing during a scrape.
+//!
+//! Miximage composition decodes, resizes, and re-encodes several images.
+//! Running it inline would hold an API worker (and its ScreenScraper thread
+//! slot) while the CPU does the work, so jobs go to a dedicated rayon pool
+//! and the API worker moves on to the next game. Results are collected once
+//! every lookup has finished.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use retro_junk_frontend::AssetType;
+use retro_junk_frontend::miximage_layout::MiximageLayout;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::error::ScrapeError;
+use crate::scrape::ScrapeEvent;
+
+/// Image workers shared by every game in a scrape.
+pub struct MediaPool {
+    pool: rayon::ThreadPool,
+    queued: AtomicUsize,
+    completed: Arc<AtomicUsize>,
+    events: mpsc::UnboundedSender<ScrapeEvent>,
+}
+
+/// A miximage being generated on the pool.
+pub(crate) struct PendingMiximage(oneshot::Receiver<Option<PathBuf>>);
+
+impl PendingMiximage {
+    /// Wait for the job. Returns the output path if a miximage was written.
+    pub(crate) async fn wait(self) -> Option<PathBuf> {
+        self.0.await.ok().flatten()
+    }
+}
+
+impl MediaPool {
+    /// Start a pool with `threads` workers (default: one per CPU).
+    pub fn new(
+        threads: Option<usize>,
+        events: mpsc::UnboundedSender<ScrapeEvent>,
+    ) -> Result<Self, ScrapeError> {
+        let mut builder =
+            rayon::ThreadPoolBuilder::new().thread_name(|i| format!("retro-junk-image-{}", i));
+        if let Some(n) = threads {
+            builder = builder.num_threads(n.max(1));
+        }
+        let pool = builder
+            .build()
+            .map_err(|e| ScrapeError::Config(format!("Failed to start image workers: {}", e)))?;
+        Ok(Self {
+            pool,
+            queued: AtomicUsize::new(0),
+            completed: Arc::new(AtomicUsize::new(0)),
+            events,
+        })
+    }
+
+    /// Queue a miximage composed from the assets in `media`.
+    pub(crate) fn generate_miximage(
+        &self,
+        index: usize,
+        file: &str,
+        media: HashMap<AssetType, PathBuf>,
+        output: PathBuf,
+        layout: MiximageLayout,
+    ) -> PendingMiximage {
+        let (tx, rx) = oneshot::channel();
+        let queued = self.queued.fetch_add(1, Ordering::Relaxed) + 1;
+        let _ = self.events.send(ScrapeEvent::ImageQueued {
+            index,
+            file: file.to_string(),
+            queued,
+        });
+
+        let completed = self.completed.clone();
+        let events = self.events.clone();
+        let file = file.to_string();
+        self.pool.spawn(move || {
+            let result =
+                match retro_junk_frontend::miximage::generate_miximage(&media, &output, &layout) {
+                    Ok(true) => Some(output),
+                    Ok(false) => None, // no screenshot, skip
+                    Err(e) => {
+                        log::debug!("Failed to generate miximage: {}", e);
+                        None
+                    }
+                };
+            let _ = events.send(ScrapeEvent::ImageFinished {
+                index,
+                file,
+                generated: result.is_some(),
+                completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+            });
+            let _ = tx.send(result);
+        });
+        PendingMiximage(rx)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/media_pool_tests.rs"]
+mod tests;
@@ -1,25 +1,29 @@
 use std::collections::HashMap;
+use std::io::Seek;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use futures::stream::{self, StreamExt};
 use retro_junk_core::disc;
-use retro_junk_core::{AnalysisOptions, Region, RomAnalyzer};
+use retro_junk_core::{AnalysisOptions, Language, Platform, RomAnalyzer};
+use retro_junk_dat::matcher::DatIndex;
 use retro_junk_frontend::ScrapedGame;
 use retro_junk_frontend::miximage_layout::MiximageLayout;
 use retro_junk_lib::scanner::{self, GameEntry};
+use retro_junk_lib::{AnalysisContext, archive};
 use tokio::sync::{Mutex, mpsc};
 use tokio::time::Duration;
 
 /// Timeout for acquiring internal mutex locks (should be near-instant).
 const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
 
-use crate::assets::{self, AssetSelection, asset_subdir};
+use crate::assets::{self, AssetNames, AssetNaming, AssetSelection, asset_subdir};
 use crate::client::ScreenScraperClient;
 use crate::error::ScrapeError;
 use crate::log::{LogEntry, ScrapeLog};
 use crate::lookup::{self, RomInfo};
+use crate::media_pool::{MediaPool, PendingMiximage};
 use crate::systems;
 
 /// Options for a scraping session.
@@ -35,6 +39,8 @@ pub struct ScrapeOptions {
     pub language_fallback: String,
     /// Which asset types to download
     pub asset_selection: AssetSelection,
+    /// How downloaded asset files are named
+    pub asset_naming: AssetNaming,
     /// Directory for metadata files (gamelist.xml etc.)
     pub metadata_dir: PathBuf,
     /// Directory for media files
@@ -55,15 +61,14 @@ pub struct ScrapeOptions {
     pub force_redownload: bool,
     /// Layout config for miximage generation (None when no_miximage is true)
     pub miximage_layout: Option<MiximageLayout>,
+    /// Image worker threads for miximage generation (default: one per CPU)
+    pub image_threads: Option<usize>,
 }
 
 impl ScrapeOptions {
     /// Create default options for a root path.
     pub fn new(root: PathBuf) -> Self {
-        let metadata_dir = root.parent().unwrap_or(&root).join(format!(
-            "{}-metadata",
-            root.file_name().unwrap_or_default().to_string_lossy()
-        ));
+        let metadata_dir = retro_junk_lib::util::default_metadata_dir(&root);
         let media_dir = retro_junk_lib::util::default_media_dir(&root);
 
         Self {
@@ -72,6 +77,7 @@ impl ScrapeOptions {
             language: "en".to_string(),
             language_fallback: "en".to_string(),
             asset_selection: AssetSelection::default(),
+            asset_naming: AssetNaming::default(),
             metadata_dir,
             media_dir,
             dry_run: false,
@@ -82,6 +88,7 @@ impl ScrapeOptions {
             no_miximage: false,
             force_redownload: false,
             miximage_layout: None,
+            image_threads: None,
         }
     }
 }
@@ -123,6 +130,20 @@ pub enum ScrapeEvent {
         file: String,
         reason: String,
     },
+    /// A miximage was queued on the image workers. `queued` counts the
+    /// jobs queued so far for this folder.
+    ImageQueued {
+        index: usize,
+        file: String,
+        queued: usize,
+    },
+    /// An image job finished. `completed` counts the jobs done so far.
+    ImageFinished {
+        index: usize,
+        file: String,
+        generated: bool,
+        completed: usize,
+    },
     /// A secondary disc was grouped with its primary.
     GameGrouped {
         index: usize,
@@ -149,10 +170,12 @@ enum GameResult {
         log_entry: LogEntry,
         /// If this game is a primary disc, the group index for cloning.
         primary_group: Option<usize>,
+        miximage: Option<PendingMiximage>,
     },
     Skipped {
         scraped: Option<ScrapedGame>,
         log_entry: Option<LogEntry>,
+        miximage: Option<PendingMiximage>,
     },
     Failed {
         log_entry: LogEntry,
@@ -163,51 +186,92 @@ enum GameResult {
     },
 }
 
-/// Try to generate a miximage into `media_map`, or register an existing one.
+/// Queue a miximage for `media_map` on the image workers, or register an
+/// existing one.
 ///
-/// When `force` is true, always regenerates even if the file exists.
-/// Inserts `AssetType::Miximage` into `media_map` on success.
-fn try_generate_miximage(
+/// When `force` is true, always regenerates even if the file exists. An
+/// existing miximage is inserted into `media_map` directly; a generated one
+/// is added when the returned job is collected.
+#[allow(clippy::too_many_arguments)]
+fn queue_miximage(
+    media_pool: &MediaPool,
     media_map: &mut HashMap<retro_junk_frontend::AssetType, PathBuf>,
     system_media_dir: &Path,
-    rom_stem: &str,
+    asset_stem: &str,
     layout: &MiximageLayout,
     force: bool,
-) {
-    let miximage_path = miximage_path(system_media_dir, rom_stem);
+    index: usize,
+    file: &str,
+) -> Option<PendingMiximage> {
+    let miximage_path = miximage_path(system_media_dir, asset_stem);
     if force || !miximage_path.exists() {
-        match retro_junk_frontend::miximage::generate_miximage(media_map, &miximage_path, layout) {
-            Ok(true) => {
-                media_map.insert(retro_junk_frontend::AssetType::Miximage, miximage_path);
-            }
-            Ok(false) => {} // no screenshot, skip
-            Err(e) => {
-                log::debug!("Failed to generate miximage: {}", e);
-            }
-        }
+        Some(media_pool.generate_miximage(
+            index,
+            file,
+            media_map.clone(),
+            miximage_path,
+            layout.clone(),
+        ))
     } else {
         media_map.insert(retro_junk_frontend::AssetType::Miximage, miximage_path);
+        None
     }
 }
 
-/// Miximage output path for a ROM.
-fn miximage_path(system_media_dir: &Path, rom_stem: &str) -> PathBuf {
+/// Miximage output path for a ROM's assets.
+fn miximage_path(system_media_dir: &Path, asset_stem: &str) -> PathBuf {
     system_media_dir
         .join("miximages")
-        .join(format!("{}.png", rom_stem))
+        .join(format!("{}.png", asset_stem))
+}
+
+/// Slug of a ROM stem, keeping any subfolder it sits in.
+fn rom_slug(rom_stem: &str) -> String {
+    match rom_stem.rsplit_once('/') {
+        Some((folder, name)) => format!("{}/{}", folder, retro_junk_catalog::slugify(name)),
+        None => retro_junk_catalog::slugify(rom_stem),
+    }
+}
+
+/// The serial from a quick analysis of a game, for serial-named assets.
+fn quick_serial(
+    entry: &GameEntry,
+    context: &AnalysisContext,
+    analyzer: &dyn RomAnalyzer,
+) -> Option<String> {
+    let extensions = scanner::extension_set(analyzer.file_extensions());
+    let mut opened = archive::open_rom(entry.analysis_path(), &extensions).ok()?;
+    let options = AnalysisOptions::new()
+        .quick(true)
+        .file_path(&opened.analysis_path);
+    let (_, info) = context
+        .analyze_reader(
+            opened.reader.as_mut(),
+            &opened.analysis_path,
+            Some(&[analyzer.platform()]),
+            &options,
+        )
+        .ok()?;
+    info.serial_number
 }
 
 /// Scrape all ROMs in a folder for a given console.
+#[allow(clippy::too_many_arguments)]
 pub async fn scrape_folder(
     client: &ScreenScraperClient,
     folder_path: &Path,
-    analyzer: &dyn RomAnalyzer,
+    context: &AnalysisContext,
+    platform: Platform,
     options: &ScrapeOptions,
     folder_name: &str,
     max_workers: usize,
     events: mpsc::UnboundedSender<ScrapeEvent>,
 ) -> Result<ScrapeResult, ScrapeError> {
-    let platform = analyzer.platform();
+    let analyzer = context
+        .get_by_platform(platform)
+        .ok_or_else(|| ScrapeError::Config(format!("No analyzer for platform {:?}", platform)))?
+        .analyzer
+        .as_ref();
     let short_name = platform.short_name();
     let system_id = systems::screenscraper_system_id(platform).ok_or_else(|| {
         ScrapeError::Config(format!("No ScreenScraper system ID for '{}'", short_name))
@@ -215,9 +279,11 @@ pub async fn scrape_folder(
 
     let extensions = scanner::extension_set(analyzer.file_extensions());
 
-    // Collect game entries: top-level ROM files and .m3u directories
+    // Collect game entries: top-level ROM files (zipped or not) and .m3u
+    // directories
     let _ = events.send(ScrapeEvent::Scanning);
-    let mut game_entries = scanner::scan_game_entries(folder_path, &extensions)
+    let scan_options = scanner::ScanOptions::new().archives(true);
+    let mut game_entries = scanner::scan_game_entries_with(folder_path, &extensions, &scan_options)
         .map_err(|e| ScrapeError::Config(format!("Error reading folder: {}", e)))?;
     if let Some(max) = options.limit {
         game_entries.truncate(max);
@@ -226,9 +292,15 @@ pub async fn scrape_folder(
     let total = game_entries.len();
     let _ = events.send(ScrapeEvent::ScanComplete { total });
 
+    let media_pool = MediaPool::new(options.image_threads, events.clone())?;
+
+    // Parent/clone data for falling back to a clone's parent set
+    let dat_index = retro_junk_lib::one_g1r::load_cached_index(analyzer);
+
     let system_media_dir = options.media_dir.join(folder_name);
 
-    // Detect disc groups among loose single-file entries
+    // Detect multi-part groups (discs, or disks and sides where the platform
+    // uses them) among loose single-file entries
     let disc_entries: Vec<(usize, &str)> = game_entries
         .iter()
         .enumerate()
@@ -237,7 +309,8 @@ pub async fn scrape_folder(
             _ => None,
         })
         .collect();
-    let disc_groups = disc::detect_disc_groups(&disc_entries);
+    let part_kinds = disc::part_kinds(platform);
+    let disc_groups = disc::detect_part_groups(&disc_entries, part_kinds);
 
     // Map from entry index → (group_index, is_primary)
     let mut disc_membership: HashMap<usize, (usize, bool)> = HashMap::new();
@@ -247,8 +320,10 @@ pub async fn scrape_folder(
         }
     }
 
-    // Classify entries into work items (primary + independent) and secondary items
-    let mut work_items: Vec<(usize, &GameEntry, String, Option<usize>)> = Vec::new();
+    // Classify entries into work items (primary + independent) and secondary items.
+    // Entries in subfolders keep their relative path in filenames and media
+    // stems, matching how ES-DE addresses them.
+    let mut work_items: Vec<(usize, &GameEntry, String, String, Option<usize>)> = Vec::new();
     let mut secondary_items: Vec<(usize, &GameEntry, usize)> = Vec::new();
 
     for (index, entry) in game_entries.iter().enumerate() {
@@ -259,13 +334,17 @@ pub async fn scrape_folder(
             }
             Some(&(group_idx, true)) => {
                 // Primary disc — use base name for media
-                let rom_stem = disc_groups[group_idx].base_name.clone();
-                work_items.push((index, entry, rom_stem, Some(group_idx)));
+                let prefix = entry.subfolder_prefix(folder_path);
+                let rom_stem = format!("{}{}", prefix, disc_groups[group_idx].base_name);
+                let filename = format!("{}{}", prefix, entry.display_name());
+                work_items.push((index, entry, rom_stem, filename, Some(group_idx)));
             }
             None => {
                 // Independent (non-disc) game
-                let rom_stem = entry.rom_stem().to_string();
-                work_items.push((index, entry, rom_stem, None));
+                let prefix = entry.subfolder_prefix(folder_path);
+                let rom_stem = format!("{}{}", prefix, entry.rom_stem());
+                let filename = format!("{}{}", prefix, entry.display_name());
+                work_items.push((index, entry, rom_stem, filename, None));
             }
         }
     }
@@ -277,21 +356,25 @@ pub async fn scrape_folder(
 
     // Process work items concurrently
     let results: Vec<GameResult> = stream::iter(work_items)
-        .map(|(index, entry, rom_stem, primary_group)| {
+        .map(|(index, entry, rom_stem, filename, primary_group)| {
             let events = events.clone();
             let cancel_flag = cancel_flag.clone();
             let primary_results = primary_results.clone();
             let system_media_dir = system_media_dir.clone();
+            let media_pool = &media_pool;
+            let dat_index = dat_index.as_ref();
             async move {
                 if cancel_flag.load(Ordering::Relaxed) {
                     return GameResult::Skipped {
                         scraped: None,
                         log_entry: None,
+                        miximage: None,
                     };
                 }
 
                 let result = process_single_game(
                     client,
+                    context,
                     analyzer,
                     options,
                     folder_name,
@@ -300,8 +383,11 @@ pub async fn scrape_folder(
                     index,
                     entry,
                     &rom_stem,
+                    &filename,
                     &events,
+                    media_pool,
                     primary_group,
+                    dat_index,
                 )
                 .await;
 
@@ -335,22 +421,41 @@ pub async fn scrape_folder(
         .collect()
         .await;
 
-    // Collect results
+    // Collect results, waiting for any image work still in flight
     let mut games = Vec::new();
     let mut log = ScrapeLog::new();
+    let mut primary_miximages: Vec<(usize, PathBuf)> = Vec::new();
 
     for result in results {
         match result {
             GameResult::Scraped {
-                scraped, log_entry, ..
+                mut scraped,
+                log_entry,
+                primary_group,
+                miximage,
             } => {
+                if let Some(path) = wait_for_miximage(miximage).await {
+                    if let Some(group_idx) = primary_group {
+                        primary_miximages.push((group_idx, path.clone()));
+                    }
+                    scraped
+                        .assets
+                        .insert(retro_junk_frontend::AssetType::Miximage, path);
+                }
                 games.push(scraped);
                 log.add(log_entry);
             }
             GameResult::Skipped {
-                scraped, log_entry, ..
+                scraped,
+                log_entry,
+                miximage,
             } => {
-                if let Some(s) = scraped {
+                let miximage = wait_for_miximage(miximage).await;
+                if let Some(mut s) = scraped {
+                    if let Some(path) = miximage {
+                        s.assets
+                            .insert(retro_junk_frontend::AssetType::Miximage, path);
+                    }
                     games.push(s);
                 }
                 if let Some(e) = log_entry {
@@ -367,19 +472,29 @@ pub async fn scrape_folder(
     }
 
     // Resolve secondary discs from primary results (no API calls needed)
-    let primary_map = tokio::time::timeout(LOCK_TIMEOUT, primary_results.lock())
+    let mut primary_map = tokio::time::timeout(LOCK_TIMEOUT, primary_results.lock())
         .await
         .map_err(|_| ScrapeError::Api("primary_results lock timed out".to_string()))?;
+    for (group_idx, path) in primary_miximages {
+        if let Some(primary) = primary_map.get_mut(&group_idx) {
+            primary
+                .assets
+                .insert(retro_junk_frontend::AssetType::Miximage, path);
+        }
+    }
     for (index, entry, group_idx) in &secondary_items {
-        let filename = entry.display_name().to_string();
+        let prefix = entry.subfolder_prefix(folder_path);
+        let filename = format!("{}{}", prefix, entry.display_name());
 
         if let Some(primary_scraped) = primary_map.get(group_idx) {
             let group = &disc_groups[*group_idx];
-            let disc_num = disc::extract_disc_number(&filename).unwrap_or(0);
+            let part = disc::extract_part(&filename, part_kinds)
+                .map(|tag| tag.label())
+                .unwrap_or_else(|| "Disc 0".to_string());
             let scraped = ScrapedGame {
                 rom_filename: filename.clone(),
-                rom_stem: group.base_name.clone(),
-                name: format!("{} (Disc {})", primary_scraped.name, disc_num),
+                rom_stem: format!("{}{}", prefix, group.base_name),
+                name: format!("{} ({})", primary_scraped.name, part),
                 ..primary_scraped.clone()
             };
 
@@ -411,10 +526,18 @@ pub async fn scrape_folder(
     Ok(ScrapeResult { games, log })
 }
 
+async fn wait_for_miximage(pending: Option<PendingMiximage>) -> Option<PathBuf> {
+    match pending {
+        Some(p) => p.wait().await,
+        None => None,
+    }
+}
+
 /// Process a single game entry: analyze, look up, download media.
 #[allow(clippy::too_many_arguments)]
 async fn process_single_game(
     client: &ScreenScraperClient,
+    context: &AnalysisContext,
     analyzer: &dyn RomAnalyzer,
     options: &ScrapeOptions,
     _folder_name: &str,
@@ -423,10 +546,13 @@ async fn process_single_game(
     index: usize,
     entry: &GameEntry,
     rom_stem: &str,
+    filename: &str,
     events: &mpsc::UnboundedSender<ScrapeEvent>,
+    media_pool: &MediaPool,
     primary_group: Option<usize>,
+    dat_index: Option<&DatIndex>,
 ) -> GameResult {
-    let filename = entry.display_name().to_string();
+    let filename = filename.to_string();
     let rom_path = entry.analysis_path();
 
     let _ = events.send(ScrapeEvent::GameStarted {
@@ -434,30 +560,54 @@ async fn process_single_game(
         file: filename.clone(),
     });
 
+    // Media is filed under the name the asset naming picks; serial naming
+    // needs a quick look at the header before existing media can be found
+    let naming_serial = match options.asset_naming {
+        AssetNaming::Serial => quick_serial(entry, context, analyzer),
+        _ => None,
+    };
+    let slug = rom_slug(rom_stem);
+    let asset_stem = options.asset_naming.file_stem(&AssetNames {
+        rom_stem,
+        slug: &slug,
+        serial: naming_serial.as_deref(),
+    });
+    let asset_stem = asset_stem.as_str();
+
     // Check if we can skip ScreenScraper entirely using existing media
     if !options.force_redownload {
         let existing =
-            assets::collect_existing_assets(&options.asset_selection, system_media_dir, rom_stem);
+            assets::collect_existing_assets(&options.asset_selection, system_media_dir, asset_stem);
 
         let has_screenshot = existing.contains_key(&retro_junk_frontend::AssetType::Screenshot);
-        let has_miximage = miximage_path(system_media_dir, rom_stem).exists();
+        let has_miximage = miximage_path(system_media_dir, asset_stem).exists();
         let needs_miximage = !options.no_miximage && !has_miximage;
 
         if has_screenshot && (!needs_miximage || options.miximage_layout.is_some()) {
             let mut media_map = existing;
+            let mut miximage = None;
 
             if needs_miximage {
                 let layout = options.miximage_layout.as_ref().unwrap();
-                try_generate_miximage(&mut media_map, system_media_dir, rom_stem, layout, false);
+                miximage = queue_miximage(
+                    media_pool,
+                    &mut media_map,
+                    system_media_dir,
+                    asset_stem,
+                    layout,
+                    false,
+                    index,
+                    &filename,
+                );
             } else if has_miximage {
                 media_map.insert(
                     retro_junk_frontend::AssetType::Miximage,
-                    miximage_path(system_media_dir, rom_stem),
+                    miximage_path(system_media_dir, asset_stem),
                 );
             }
 
             let reason = if needs_miximage {
-                "media exists, generated miximage"
+                "media exists, generating miximage"
             } else {
                 "media already exists"
             };
@@ -484,19 +634,18 @@ async fn process_single_game(
             return GameResult::Skipped {
                 scraped: Some(scraped),
                 log_entry: None,
+                miximage,
             };
         }
     }
 
     let platform = analyzer.platform();
 
-    // Analyze the ROM to extract serial and regions
-    let analysis_opts = AnalysisOptions::new().quick(true).file_path(rom_path);
-    let (serial, rom_regions): (Option<String>, Vec<Region>) = match std::fs::File::open(rom_path) {
-        Ok(mut f) => match analyzer.analyze(&mut f, &analysis_opts) {
-            Ok(info) => (info.serial_number, info.regions),
-            Err(_) => (None, Vec::new()),
-        },
+    // Analyze the ROM (the one inside a single-ROM archive) to extract
+    // serial and regions
+    let extensions = scanner::extension_set(analyzer.file_extensions());
+    let mut opened = match archive::open_rom(rom_path, &extensions) {
+        Ok(opened) => opened,
         Err(e) => {
             let message = format!("Failed to open file: {}", e);
             let _ = events.send(ScrapeEvent::GameFailed {
@@ -512,8 +661,26 @@ async fn process_single_game(
             };
         }
     };
-
-    let file_size = rom_path.metadata().map(|m| m.len()).unwrap_or(0);
+    let analysis_opts = AnalysisOptions::new()
+        .quick(true)
+        .file_path(&opened.analysis_path);
+    let info = context
+        .analyze_reader(
+            opened.reader.as_mut(),
+            &opened.analysis_path,
+            Some(&[platform]),
+            &analysis_opts,
+        )
+        .ok()
+        .map(|(_, info)| info);
+
+    let serial = info
+        .as_ref()
+        .and_then(|i| i.serial_number.as_deref())
+        .map(|s| analyzer.normalize_serial(s));
+    let rom_regions = info.as_ref().map(|i| i.regions.clone()).unwrap_or_default();
+    let build_date = info.as_ref().and_then(|i| i.build_date);
+    let file_size = opened.size(rom_path);
 
     // Compute effective region and language from ROM analysis
     let effective_region = rom_regions
@@ -532,17 +699,24 @@ async fn process_single_game(
 
     // Compute hashes if needed (for non-serial consoles or force_hash)
     let (crc32, md5, sha1) = if !systems::expects_serial(platform) || options.force_hash {
-        match std::fs::File::open(rom_path) {
-            Ok(mut f) => {
-                match retro_junk_lib::hasher::compute_all_hashes(&mut f, analyzer, Some(rom_path)) {
-                    Ok(hashes) => (Some(hashes.crc32), hashes.md5, hashes.sha1),
-                    Err(e) => {
-                        log::debug!("Failed to hash {}: {}", filename, e);
-                        (None, None, None)
-                    }
-                }
+        let hashed = opened
+            .reader
+            .rewind()
+            .map_err(|e| e.to_string())
+            .and_then(|()| {
+                retro_junk_lib::hasher::compute_all_hashes(
+                    opened.reader.as_mut(),
+                    analyzer,
+                    Some(&opened.analysis_path),
+                )
+                .map_err(|e| e.to_string())
+            });
+        match hashed {
+            Ok(hashes) => (Some(hashes.crc32), hashes.md5, hashes.sha1),
+            Err(e) => {
+                log::debug!("Failed to hash {}: {}", filename, e);
+                (None, None, None)
             }
-            Err(_) => (None, None, None),
         }
     } else {
         (None, None, None)
@@ -562,6 +736,9 @@ async fn process_single_game(
         sha1,
         platform,
         expects_serial: analyzer.expects_serial(),
+        parent_filename: dat_index
+            .and_then(|index| index.parent_rom_name(entry.rom_stem()))
+            .map(str::to_string),
     };
 
     if options.dry_run {
@@ -580,6 +757,7 @@ async fn process_single_game(
         return GameResult::Skipped {
             scraped: None,
             log_entry: None,
+            miximage: None,
         };
     }
 
@@ -591,9 +769,15 @@ async fn process_single_game(
 
     match lookup::lookup_game(client, system_id, &rom_info).await {
         Ok(result) => {
+            // Without a ScreenScraper name, fall back to the header title in
+            // the user's language
             let game_name = result
                 .game
                 .name_for_region(&effective_region)
+                .or_else(|| {
+                    info.as_ref()?
+                        .title_for(Language::from_code(&effective_language))
+                })
                 .unwrap_or("Unknown")
                 .to_string();
 
@@ -608,7 +792,7 @@ async fn process_single_game(
                 &result.game,
                 &options.asset_selection,
                 system_media_dir,
-                rom_stem,
+                asset_stem,
                 &effective_region,
                 options.force_redownload,
                 index,
@@ -618,16 +802,19 @@ async fn process_single_game(
             .await
             .unwrap_or_default();
 
-            // Generate miximage if enabled
-            if let Some(ref layout) = options.miximage_layout {
-                try_generate_miximage(
+            // Queue miximage generation if enabled; it finishes in the background
+            let miximage = options.miximage_layout.as_ref().and_then(|layout| {
+                queue_miximage(
+                    media_pool,
                     &mut media_map,
                     system_media_dir,
-                    rom_stem,
+                    asset_stem,
                     layout,
                     options.force_redownload,
-                );
-            }
+                    index,
+                    &filename,
+                )
+            });
 
             let media_names: Vec<String> = media_map
                 .keys()
@@ -676,10 +863,12 @@ async fn process_single_game(
                 genre,
                 players: result.game.joueurs.as_ref().map(|j| j.text.clone()),
                 rating: result.game.rating_normalized(),
+                // Fall back to the header's build date when ScreenScraper has none
                 release_date: result
                     .game
                     .date_for_region(&effective_region)
-                    .map(|d| d.to_string()),
+                    .map(|d| d.to_string())
+                    .or_else(|| build_date.map(|d| d.to_string())),
                 assets: media_map,
                 cover_title: None,
             };
@@ -694,6 +883,7 @@ async fn process_single_game(
                 scraped,
                 log_entry,
                 primary_group,
+                miximage,
             }
         }
         Err(ScrapeError::NotFound { warnings }) => {
@@ -3,8 +3,17 @@ use retro_junk_core::{Platform, Region};
 /// Map a `Platform` to its ScreenScraper system ID.
 ///
 /// System IDs are ScreenScraper-specific and live here rather than on
-/// the `RomAnalyzer` trait, as they're a third-party API detail.
+/// the `RomAnalyzer` trait, as they're a third-party API detail. Overrides
+/// from the `[screenscraper_ids]` table in `platforms.toml` take precedence.
 pub fn screenscraper_system_id(platform: Platform) -> Option<u32> {
+    if let Some(id) = retro_junk_core::custom_platform::screenscraper_id_override(platform) {
+        return Some(id);
+    }
+    builtin_system_id(platform)
+}
+
+/// The built-in ScreenScraper system ID for a platform.
+fn builtin_system_id(platform: Platform) -> Option<u32> {
     match platform {
         // Nintendo
         Platform::Nes => Some(3),
@@ -38,6 +47,22 @@ pub fn screenscraper_system_id(platform: Platform) -> Option<u32> {
         // Microsoft
         Platform::Xbox => Some(32),
         Platform::Xbox360 => Some(33),
+
+        // Atari
+        Platform::Atari2600 => Some(26),
+        Platform::Atari5200 => Some(40),
+        Platform::Atari7800 => Some(41),
+        Platform::Lynx => Some(28),
+        Platform::Jaguar => Some(27),
+
+        // NEC
+        Platform::PcEngine => Some(31),
+        Platform::SuperGrafx => Some(105),
+        Platform::PcEngineCd => Some(114),
+
+        Platform::Custom(i) => {
+            retro_junk_core::custom_platform::get(i).and_then(|p| p.screenscraper_id)
+        }
     }
 }
 
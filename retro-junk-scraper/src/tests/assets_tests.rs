@@ -0,0 +1,162 @@
+use super::*;
+
+#[test]
+fn test_region_for_defaults_to_session_region() {
+    let selection = AssetSelection::default();
+    assert_eq!(selection.region_for(AssetType::Cover, "us"), "us");
+}
+
+#[test]
+fn test_with_region_overrides_single_type() {
+    let selection = AssetSelection::default().with_region(AssetType::Cover, "jp");
+    assert_eq!(selection.region_for(AssetType::Cover, "us"), "jp");
+    assert_eq!(selection.region_for(AssetType::TitleScreen, "us"), "us");
+}
+
+#[test]
+fn test_apply_region_overrides_parses_pairs() {
+    let mut selection = AssetSelection::default();
+    let region_code = |region: &str| match region {
+        "jp" | "japan" => Some("jp"),
+        "us" => Some("us"),
+        _ => None,
+    };
+    let invalid = selection.apply_region_overrides(
+        &[
+            "covers=JP".to_string(),
+            "titlescreen=us".to_string(),
+            "bogus=eu".to_string(),
+            "screenshots".to_string(),
+            "3dbox=japn".to_string(),
+            "marquee=Japan".to_string(),
+        ],
+        region_code,
+    );
+    assert_eq!(invalid, vec!["bogus=eu", "screenshots", "3dbox=japn"]);
+    assert_eq!(selection.region_for(AssetType::Marquee, "eu"), "jp");
+    assert_eq!(selection.region_for(AssetType::Cover3D, "eu"), "eu");
+    assert_eq!(selection.region_for(AssetType::Cover, "eu"), "jp");
+    assert_eq!(selection.region_for(AssetType::TitleScreen, "eu"), "us");
+    assert_eq!(selection.region_for(AssetType::Screenshot, "eu"), "eu");
+}
+
+#[test]
+fn test_from_names_parses_new_types() {
+    let selection = AssetSelection::from_names(&[
+        "backcovers".to_string(),
+        "label".to_string(),
+        "pcbs".to_string(),
+        "bogus".to_string(),
+    ]);
+    assert_eq!(
+        selection.types,
+        vec![AssetType::BackCover, AssetType::Label, AssetType::Pcb]
+    );
+}
+
+#[test]
+fn test_ss_media_types_round_trip() {
+    for &at in AssetType::ALL {
+        for ss_type in ss_media_types(at) {
+            assert_eq!(asset_type_for_ss_media(ss_type), Some(at));
+        }
+        assert_eq!(AssetType::from_catalog_name(at.catalog_name()), Some(at));
+    }
+    assert_eq!(ss_media_types(AssetType::Marquee), &["wheel-hd", "wheel"]);
+    assert!(ss_media_types(AssetType::Pcb).is_empty());
+    assert!(ss_media_types(AssetType::Miximage).is_empty());
+    assert_eq!(asset_subdir(AssetType::BackCover), "backcovers");
+}
+
+fn media_with_hashes(crc: Option<&str>, md5: Option<&str>, sha1: Option<&str>) -> Media {
+    Media {
+        media_type: "ss".to_string(),
+        url: "https://example.invalid/ss.png".to_string(),
+        region: "us".to_string(),
+        format: "png".to_string(),
+        crc: crc.map(String::from),
+        md5: md5.map(String::from),
+        sha1: sha1.map(String::from),
+        size: None,
+    }
+}
+
+#[test]
+fn test_check_media_integrity_verified() {
+    // CRC32/MD5/SHA1 of "abc"
+    let media = media_with_hashes(
+        Some("352441C2"),
+        Some("900150983cd24fb0d6963f7d28e17f72"),
+        Some("a9993e364706816aba3e25717850c26c9cd0d89d"),
+    );
+    assert_eq!(
+        check_media_integrity(&media, b"abc"),
+        MediaIntegrity::Verified
+    );
+}
+
+#[test]
+fn test_check_media_integrity_mismatch() {
+    let media = media_with_hashes(Some("352441c2"), Some("deadbeef"), None);
+    assert_eq!(
+        check_media_integrity(&media, b"abc"),
+        MediaIntegrity::Mismatch
+    );
+    assert_eq!(
+        check_media_integrity(&media, b"abd"),
+        MediaIntegrity::Mismatch
+    );
+}
+
+#[test]
+fn test_check_media_integrity_unchecked() {
+    let media = media_with_hashes(None, Some(""), None);
+    assert_eq!(
+        check_media_integrity(&media, b"abc"),
+        MediaIntegrity::Unchecked
+    );
+}
+
+#[test]
+fn test_asset_file_hash_is_sha1() {
+    assert_eq!(
+        asset_file_hash(b"abc"),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+}
+
+#[test]
+fn test_asset_naming_picks_file_stem() {
+    let names = AssetNames {
+        rom_stem: "A/Super Mario Bros. (USA)",
+        slug: "A/super-mario-bros-usa",
+        serial: Some("NES-SM/USA"),
+    };
+    assert_eq!(
+        AssetNaming::RomStem.file_stem(&names),
+        "A/Super Mario Bros. (USA)"
+    );
+    assert_eq!(
+        AssetNaming::Slug.file_stem(&names),
+        "A/super-mario-bros-usa"
+    );
+    assert_eq!(AssetNaming::Serial.file_stem(&names), "NES-SM-USA");
+
+    let names = AssetNames {
+        serial: None,
+        ..names
+    };
+    assert_eq!(
+        AssetNaming::Serial.file_stem(&names),
+        "A/Super Mario Bros. (USA)"
+    );
+}
+
+#[test]
+fn test_asset_naming_parses_cli_names() {
+    for naming in [AssetNaming::RomStem, AssetNaming::Slug, AssetNaming::Serial] {
+        assert_eq!(naming.as_str().parse::<AssetNaming>(), Ok(naming));
+    }
+    assert_eq!("rom-stem".parse::<AssetNaming>(), Ok(AssetNaming::RomStem));
+    assert!("title".parse::<AssetNaming>().is_err());
+}
@@ -0,0 +1,29 @@
+use super::*;
+
+#[test]
+fn user_agent_defaults_to_soft_name() {
+    let config = ClientConfig::default();
+    let ua = config.user_agent_for("my-frontend");
+    assert!(ua.starts_with("my-frontend (retro-junk/"));
+
+    let custom = ClientConfig {
+        user_agent: Some("Custom/1.0".into()),
+        ..ClientConfig::default()
+    };
+    assert_eq!(custom.user_agent_for("my-frontend"), "Custom/1.0");
+}
+
+#[test]
+fn media_size_limit() {
+    assert!(!exceeds_limit(u64::MAX, None));
+    assert!(!exceeds_limit(100, Some(100)));
+    assert!(exceeds_limit(101, Some(100)));
+}
+
+#[test]
+fn redacts_credentials_in_urls() {
+    let msg = redact_credentials("https://x/media?devid=abc&devpassword=secret&ssid=me");
+    assert!(!msg.contains("secret"));
+    assert!(!msg.contains("abc"));
+    assert!(msg.contains("devpassword=[REDACTED]"));
+}
@@ -0,0 +1,54 @@
+use super::*;
+
+#[test]
+fn client_section_overrides_defaults() {
+    let config = parse_config(
+        r#"
+[screenscraper]
+soft_name = "my-frontend"
+
+[client]
+user_agent = "my-frontend/2.0"
+api_timeout_secs = 45
+max_retries = 1
+max_media_bytes = 1048576
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.screenscraper.unwrap().soft_name.as_deref(),
+        Some("my-frontend")
+    );
+
+    let client = config.client.unwrap().into_config();
+    assert_eq!(client.user_agent.as_deref(), Some("my-frontend/2.0"));
+    assert_eq!(client.api_timeout, Duration::from_secs(45));
+    assert_eq!(client.max_retries, 1);
+    assert_eq!(client.max_media_bytes, Some(1_048_576));
+    // Unset fields keep their defaults
+    assert_eq!(client.media_timeout, ClientConfig::default().media_timeout);
+    assert_eq!(
+        client.connect_timeout,
+        ClientConfig::default().connect_timeout
+    );
+}
+
+#[test]
+fn missing_client_section_uses_defaults() {
+    let config = parse_config("[screenscraper]\nuser_id = \"someone\"\n").unwrap();
+    assert!(config.client.is_none());
+    assert_eq!(
+        config.client.unwrap_or_default().into_config(),
+        ClientConfig::default()
+    );
+}
+
+#[test]
+fn client_section_is_not_written_when_absent() {
+    let config = ConfigFile {
+        screenscraper: None,
+        client: None,
+    };
+    let text = toml::to_string_pretty(&config).unwrap();
+    assert!(!text.contains("[client]"));
+}
@@ -31,3 +31,78 @@ fn test_serial_attempts_no_serial_at_all() {
     let attempts = serial_attempts(&serial, &scraper);
     assert!(attempts.is_empty());
 }
+
+/// Answer ScreenScraper API requests on a local port: user info always
+/// succeeds, and `jeuInfos.php` finds only the ROM named `known_rom`.
+/// Returns the API base URL.
+fn serve_api(known_rom: &'static str) -> String {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/api2", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            let _ = reader.read_line(&mut request_line);
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                    break;
+                }
+            }
+            let body = if request_line.contains("/ssuserInfos.php") {
+                r#"{"response":{"ssuser":{"id":"tester"}}}"#.to_string()
+            } else if request_line.contains(&format!("romnom={known_rom}&"))
+                || request_line.contains(&format!("romnom={known_rom} "))
+            {
+                r#"{"response":{"jeu":{"id":"42"}}}"#.to_string()
+            } else {
+                "Erreur : Rom/Iso/Dossier non trouvée !".to_string()
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+    });
+    base_url
+}
+
+#[tokio::test]
+async fn test_lookup_falls_back_to_parent() {
+    let config = crate::ClientConfig {
+        api_base_url: serve_api("Parent.md"),
+        max_retries: 0,
+        ..crate::ClientConfig::default()
+    };
+    let creds = crate::Credentials {
+        dev_id: "dev".into(),
+        dev_password: "pass".into(),
+        soft_name: "test".into(),
+        user_id: None,
+        user_password: None,
+    };
+    let (client, _) = ScreenScraperClient::with_config(creds, config)
+        .await
+        .unwrap();
+
+    let rom_info = RomInfo {
+        serial: None,
+        scraper_serial: None,
+        filename: "Clone.md".into(),
+        file_size: 1024,
+        crc32: None,
+        md5: None,
+        sha1: None,
+        platform: Platform::Genesis,
+        expects_serial: false,
+        parent_filename: Some("Parent.md".into()),
+    };
+    let result = lookup_game(&client, 1, &rom_info).await.unwrap();
+    assert_eq!(result.method, LookupMethod::Parent);
+    assert_eq!(result.game.id, "42");
+}
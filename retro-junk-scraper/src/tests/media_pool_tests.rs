@@ -0,0 +1,44 @@
+use super::*;
+
+#[tokio::test]
+async fn reports_progress_and_skips_games_without_screenshots() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let pool = MediaPool::new(Some(2), tx).unwrap();
+
+    let jobs: Vec<_> = (0..3)
+        .map(|i| {
+            pool.generate_miximage(
+                i,
+                &format!("game{}.sfc", i),
+                HashMap::new(),
+                // Never written: there is no screenshot to compose
+                PathBuf::from(format!("game{}.png", i)),
+                MiximageLayout::default(),
+            )
+        })
+        .collect();
+    for job in jobs {
+        assert_eq!(job.wait().await, None);
+    }
+    drop(pool);
+
+    let mut queued = Vec::new();
+    let mut completed = Vec::new();
+    while let Some(event) = rx.recv().await {
+        match event {
+            ScrapeEvent::ImageQueued { queued: n, .. } => queued.push(n),
+            ScrapeEvent::ImageFinished {
+                generated,
+                completed: n,
+                ..
+            } => {
+                assert!(!generated);
+                completed.push(n);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+    completed.sort();
+    assert_eq!(queued, vec![1, 2, 3]);
+    assert_eq!(completed, vec![1, 2, 3]);
+}
@@ -154,6 +154,10 @@ pub struct Media {
     #[serde(default)]
     pub crc: Option<String>,
     #[serde(default)]
+    pub md5: Option<String>,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
     pub size: Option<String>,
 }
 
@@ -1,13 +1,125 @@
 //! Sega Dreamcast disc image analyzer.
 //!
 //! Supports:
-//! - GDI images (.gdi)
-//! - CDI images (.cdi)
+//! - GDI images (.gdi), with the track files next to the sheet
+//! - CDI images (.cdi) (not analyzed yet)
 //! - CHD compressed images
+//!
+//! A GD-ROM has a single-density area (tracks 1 and 2) readable by CD
+//! drives, and a high-density area starting at track 3. The first sector
+//! of track 3 holds the IP.BIN header, which starts with `SEGA SEGAKATANA`
+//! and carries the product number, version, release date, area codes, and
+//! title. Redump lists every track of a disc, so besides the track 3 hash
+//! used for quick matching, each track can be hashed to confirm the match.
 
 use retro_junk_core::ReadSeek;
 
-use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+use retro_junk_core::util::read_ascii_fixed as read_ascii;
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, BuildDate, FileHashes, HashAlgorithms, ParseLimits, Platform,
+    Region, RomAnalyzer, RomIdentification,
+};
+
+use crate::sega_disc::{self, ChdDisc, DiscFormat, GdiDisc, USER_DATA_SIZE};
+
+/// Hardware identifier at the start of IP.BIN.
+const DISC_MAGIC: &[u8] = b"SEGA SEGAKATANA ";
+
+/// First track of the high-density area, which holds IP.BIN.
+const BOOT_TRACK: u32 = 3;
+
+/// Fields of the IP.BIN header.
+#[derive(Debug, Clone)]
+pub struct DreamcastHeader {
+    /// Maker ID (e.g. "SEGA ENTERPRISES").
+    pub maker_id: String,
+    /// Device information (e.g. "0000 GD-ROM1/1").
+    pub device_info: String,
+    /// Area symbols (e.g. "JUE").
+    pub area_codes: String,
+    /// Product number (e.g. "MK-51000").
+    pub product_number: String,
+    /// Version (e.g. "V1.000").
+    pub version: String,
+    /// Release date as `YYYYMMDD`.
+    pub release_date: String,
+    /// Game title.
+    pub title: String,
+}
+
+/// Parse IP.BIN from the first 256 bytes of the boot sector.
+pub(crate) fn parse_header(buf: &[u8]) -> DreamcastHeader {
+    DreamcastHeader {
+        maker_id: read_ascii(&buf[0x10..0x20]),
+        device_info: read_ascii(&buf[0x20..0x30]),
+        area_codes: read_ascii(&buf[0x30..0x38]),
+        product_number: read_ascii(&buf[0x40..0x4A]),
+        version: read_ascii(&buf[0x4A..0x50]),
+        release_date: read_ascii(&buf[0x50..0x60]),
+        title: read_ascii(&buf[0x80..0x100]),
+    }
+}
+
+/// Decode the area symbols, which are positional: `J`, `U`, then `E`.
+fn decode_areas(area_codes: &str) -> Vec<Region> {
+    let mut regions: Vec<Region> = area_codes
+        .chars()
+        .filter_map(|c| match c {
+            'J' => Some(Region::Japan),
+            'U' => Some(Region::Usa),
+            'E' => Some(Region::Europe),
+            _ => None,
+        })
+        .collect();
+    if regions.is_empty() {
+        regions.push(Region::Unknown);
+    }
+    regions
+}
+
+/// Disc number and count from device information such as "8B4F GD-ROM1/2".
+fn disc_of(device_info: &str) -> Option<(u32, u32)> {
+    let (_, disc) = device_info.split_once("GD-ROM")?;
+    let (number, total) = disc.split_once('/')?;
+    Some((number.trim().parse().ok()?, total.trim().parse().ok()?))
+}
+
+/// Build the identification from IP.BIN.
+fn identify(sector: &[u8; USER_DATA_SIZE]) -> Result<RomIdentification, AnalysisError> {
+    if !sector.starts_with(DISC_MAGIC) {
+        return Err(AnalysisError::invalid_magic(
+            0,
+            DISC_MAGIC,
+            &sector[..DISC_MAGIC.len()],
+        ));
+    }
+    let header = parse_header(sector);
+
+    let mut id = RomIdentification::new().with_platform(Platform::Dreamcast);
+    if !header.product_number.is_empty() {
+        id = id.with_serial(&header.product_number);
+    }
+    if !header.title.is_empty() {
+        id = id.with_internal_name(&header.title);
+    }
+    if !header.version.is_empty() {
+        id.version = Some(header.version.clone());
+    }
+    if !header.maker_id.is_empty() {
+        id.maker_code = Some(header.maker_id.clone());
+    }
+    id.regions = decode_areas(&header.area_codes);
+    id.build_date = BuildDate::parse(&header.release_date);
+    if !header.area_codes.is_empty() {
+        id.extra
+            .insert("area_codes".into(), header.area_codes.clone());
+    }
+    if let Some((number, total)) = disc_of(&header.device_info) {
+        id.extra
+            .insert("disc".into(), format!("{} of {}", number, total));
+    }
+    Ok(id)
+}
 
 /// Analyzer for Sega Dreamcast disc images.
 #[derive(Debug, Default)]
@@ -16,12 +128,33 @@ pub struct DreamcastAnalyzer;
 impl RomAnalyzer for DreamcastAnalyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "Dreamcast disc analysis not yet implemented",
-        ))
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let format = sega_disc::detect_format(reader)?;
+        let (sector, tracks) = match format {
+            DiscFormat::Chd => (ChdDisc::open(reader)?.read_sector(BOOT_TRACK, 0)?, None),
+            DiscFormat::Gdi => {
+                let gdi = GdiDisc::open(reader, options.file_path.as_deref(), &options.limits)?;
+                (gdi.read_sector(BOOT_TRACK, 0)?, Some(gdi.tracks().len()))
+            }
+            _ => {
+                return Err(AnalysisError::other(
+                    "Dreamcast CDI analysis not yet implemented",
+                ));
+            }
+        };
+
+        let mut id = identify(&sector)?;
+        id.file_size = Some(file_size);
+        id.extra.insert("format".into(), format.name().into());
+        id.extra
+            .insert("detected_extension".into(), format.extension().into());
+        if let Some(tracks) = tracks {
+            id.extra.insert("tracks".into(), tracks.to_string());
+        }
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -32,15 +165,65 @@ impl RomAnalyzer for DreamcastAnalyzer {
         &["gdi", "cdi", "chd"]
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        // GDI sheets need their path to be followed, so only CHDs are claimed
+        let result = sega_disc::detect_format(reader)
+            .and_then(|format| match format {
+                DiscFormat::Chd => ChdDisc::open(reader)?
+                    .read_sector(BOOT_TRACK, 0)
+                    .map(|sector| sector.starts_with(DISC_MAGIC)),
+                _ => Ok(false),
+            })
+            .unwrap_or(false);
+        let _ = reader.seek(std::io::SeekFrom::Start(0));
+        result
     }
 
     fn dat_source(&self) -> retro_junk_core::DatSource {
         retro_junk_core::DatSource::Redump
     }
 
+    fn dat_lists_raw_cd_tracks(&self) -> bool {
+        true
+    }
+
+    fn compute_container_hashes(
+        &self,
+        reader: &mut dyn ReadSeek,
+        algorithms: HashAlgorithms,
+        file_path: Option<&std::path::Path>,
+    ) -> Result<Option<FileHashes>, AnalysisError> {
+        match sega_disc::detect_format(reader)? {
+            DiscFormat::Chd => ChdDisc::open(reader)?
+                .hash_track(BOOT_TRACK, algorithms)
+                .map(Some),
+            DiscFormat::Gdi => GdiDisc::open(reader, file_path, &ParseLimits::default())?
+                .hash_track(BOOT_TRACK, algorithms)
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn compute_track_hashes(
+        &self,
+        reader: &mut dyn ReadSeek,
+        algorithms: HashAlgorithms,
+        file_path: Option<&std::path::Path>,
+    ) -> Result<Vec<FileHashes>, AnalysisError> {
+        match sega_disc::detect_format(reader)? {
+            DiscFormat::Chd => ChdDisc::open(reader)?.hash_tracks(algorithms),
+            DiscFormat::Gdi => {
+                GdiDisc::open(reader, file_path, &ParseLimits::default())?.hash_tracks(algorithms)
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
     fn dat_names(&self) -> &'static [&'static str] {
         &["Sega - Dreamcast"]
     }
 }
+
+#[cfg(test)]
+#[path = "tests/dreamcast_tests.rs"]
+mod tests;
@@ -1,12 +1,18 @@
 //! Sega Game Gear ROM analyzer.
 //!
 //! Supports:
-//! - Game Gear ROMs (.gg)
+//! - Game Gear ROMs (.gg), including games that run in Master System mode
+//! - VGM music rips (.vgm, .vgz)
 
 use retro_junk_core::ReadSeek;
 
 use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
 
+use crate::sms_header::{self, CartFamily};
+
+/// Smallest Game Gear cartridge.
+const MIN_ROM_SIZE: u64 = 32 * 1024;
+
 /// Analyzer for Sega Game Gear ROMs.
 #[derive(Debug, Default)]
 pub struct GameGearAnalyzer;
@@ -14,12 +20,32 @@ pub struct GameGearAnalyzer;
 impl RomAnalyzer for GameGearAnalyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "Game Gear ROM analysis not yet implemented",
-        ))
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        if file_size < MIN_ROM_SIZE {
+            return Err(AnalysisError::TooSmall {
+                expected: MIN_ROM_SIZE,
+                actual: file_size,
+            });
+        }
+        if sms_header::classify(reader)? == Some(CartFamily::Sg1000) {
+            return Err(AnalysisError::invalid_format(
+                "No TMR SEGA header and the code targets SG-1000 hardware",
+            ));
+        }
+
+        let mut id = RomIdentification::new().with_platform(Platform::GameGear);
+        id.file_size = Some(file_size);
+        if let Some(header) = sms_header::identify(reader, options, &mut id)?
+            && !header.is_game_gear()
+        {
+            // Master System region codes on a Game Gear cartridge mean the
+            // game runs in the console's Master System mode
+            id.extra.insert("mode".into(), "Master System".into());
+        }
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -30,8 +56,21 @@ impl RomAnalyzer for GameGearAnalyzer {
         &["gg"]
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        let result = sms_header::find_header(reader);
+        matches!(result, Ok(Some(header)) if header.is_game_gear())
+    }
+
+    fn music_extensions(&self) -> &'static [&'static str] {
+        crate::music::EXTENSIONS
+    }
+
+    fn analyze_music(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        crate::music::analyze(reader, options, Platform::GameGear)
     }
 
     fn dat_names(&self) -> &'static [&'static str] {
@@ -42,3 +81,7 @@ impl RomAnalyzer for GameGearAnalyzer {
         &["console_sega_gamegear"]
     }
 }
+
+#[cfg(test)]
+#[path = "tests/game_gear_tests.rs"]
+mod tests;
@@ -2,15 +2,18 @@
 //!
 //! Supports:
 //! - Genesis/Mega Drive ROMs (.md, .gen, .bin)
-//! - Interleaved ROMs (.smd)
+//! - Interleaved ROMs (.smd), de-interleaved before analysis and hashing
+//! - Byte-swapped ROMs and plain ROMs behind a 512-byte copier header
+//! - VGM music rips (.vgm, .vgz)
 
 use retro_junk_core::ReadSeek;
-use std::io::SeekFrom;
+use std::io::{Cursor, SeekFrom};
 
+use retro_junk_core::util::format_bytes;
 use retro_junk_core::util::read_ascii_fixed as read_ascii;
 use retro_junk_core::{
-    AnalysisError, AnalysisOptions, ChecksumAlgorithm, ExpectedChecksum, Platform, Region,
-    RomAnalyzer, RomIdentification,
+    AnalysisError, AnalysisOptions, BuildDate, ChecksumAlgorithm, ChunkNormalizerResult,
+    ExpectedChecksum, ParseLimits, Platform, RawHeader, Region, RomAnalyzer, RomIdentification,
 };
 
 /// Magic bytes at offset 0x0100 — the system type field always starts with "SEGA".
@@ -22,6 +25,118 @@ const HEADER_OFFSET: u64 = 0x100;
 /// Minimum file size to contain a full header (0x0200 bytes).
 const MIN_HEADER_SIZE: u64 = 0x200;
 
+/// Size of the header some copiers prepend to dumps.
+const COPIER_HEADER_SIZE: u64 = 0x200;
+
+/// Size of the blocks an SMD dump interleaves; each holds the odd bytes of
+/// 16 KiB of ROM followed by the even bytes.
+const SMD_BLOCK_SIZE: usize = 0x4000;
+
+/// How a Genesis dump's bytes are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenesisFormat {
+    /// Big-endian ROM as the console sees it (.md, .gen, .bin).
+    Plain,
+    /// Plain ROM behind a 512-byte copier header.
+    Headered,
+    /// 16-bit words stored little-endian.
+    ByteSwapped,
+    /// Super Magic Drive: a 512-byte header, then interleaved 16 KiB blocks.
+    Smd,
+}
+
+impl GenesisFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Plain => "Plain",
+            Self::Headered => "Plain (copier header)",
+            Self::ByteSwapped => "Byte-swapped",
+            Self::Smd => "SMD (interleaved)",
+        }
+    }
+
+    /// Bytes in front of the ROM data.
+    pub fn header_size(self) -> u64 {
+        match self {
+            Self::Plain | Self::ByteSwapped => 0,
+            Self::Headered | Self::Smd => COPIER_HEADER_SIZE,
+        }
+    }
+}
+
+/// Detect the layout of a Genesis dump from where its "SEGA" magic ends up,
+/// or `None` if it doesn't look like a Genesis ROM. Leaves the reader at the
+/// start.
+pub(crate) fn detect_format(
+    reader: &mut dyn ReadSeek,
+    file_size: u64,
+) -> std::io::Result<Option<GenesisFormat>> {
+    let plain = read_magic(reader, HEADER_OFFSET)?;
+    let mut format = if &plain == SEGA_MAGIC {
+        Some(GenesisFormat::Plain)
+    } else if plain == [b'E', b'S', b'A', b'G'] {
+        Some(GenesisFormat::ByteSwapped)
+    } else {
+        None
+    };
+
+    if format.is_none() && file_size % 1024 == COPIER_HEADER_SIZE {
+        if &read_magic(reader, COPIER_HEADER_SIZE + HEADER_OFFSET)? == SEGA_MAGIC {
+            format = Some(GenesisFormat::Headered);
+        } else if file_size >= COPIER_HEADER_SIZE + SMD_BLOCK_SIZE as u64 {
+            reader.seek(SeekFrom::Start(COPIER_HEADER_SIZE))?;
+            let mut block = vec![0u8; SMD_BLOCK_SIZE];
+            reader.read_exact(&mut block)?;
+            deinterleave_smd(&mut block);
+            if &block[HEADER_OFFSET as usize..HEADER_OFFSET as usize + 4] == SEGA_MAGIC {
+                format = Some(GenesisFormat::Smd);
+            }
+        }
+    }
+
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(format)
+}
+
+/// Convert ROM data in `format` (without its header) to plain big-endian,
+/// in place. Data must start on a block boundary; a trailing partial SMD
+/// block is left as is.
+pub(crate) fn normalize(format: GenesisFormat, data: &mut [u8]) {
+    match format {
+        GenesisFormat::Plain | GenesisFormat::Headered => {}
+        GenesisFormat::ByteSwapped => {
+            for pair in data.chunks_exact_mut(2) {
+                pair.swap(0, 1);
+            }
+        }
+        GenesisFormat::Smd => deinterleave_smd(data),
+    }
+}
+
+/// The four bytes at `offset`, or zeros past the end of the file.
+fn read_magic(reader: &mut dyn ReadSeek, offset: u64) -> std::io::Result<[u8; 4]> {
+    let mut magic = [0u8; 4];
+    reader.seek(SeekFrom::Start(offset))?;
+    match reader.read_exact(&mut magic) {
+        Ok(()) => Ok(magic),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok([0; 4]),
+        Err(e) => Err(e),
+    }
+}
+
+/// De-interleave every whole SMD block in `data`.
+fn deinterleave_smd(data: &mut [u8]) {
+    let half = SMD_BLOCK_SIZE / 2;
+    let mut block = [0u8; SMD_BLOCK_SIZE];
+    for chunk in data.chunks_exact_mut(SMD_BLOCK_SIZE) {
+        block.copy_from_slice(chunk);
+        for i in 0..half {
+            chunk[i * 2] = block[half + i];
+            chunk[i * 2 + 1] = block[i];
+        }
+    }
+}
+
 /// Parsed Genesis ROM header (0x0100–0x01FF).
 #[derive(Debug, Clone)]
 pub struct GenesisHeader {
@@ -47,15 +162,87 @@ pub struct GenesisHeader {
     pub ram_start: u32,
     /// RAM end address.
     pub ram_end: u32,
-    /// Extra memory / SRAM info field.
-    pub extra_memory: String,
-    /// Region support codes (e.g. "JUE").
+    /// Save memory declared in the extra memory field, if any.
+    pub extra_memory: Option<SaveMemory>,
+    /// Region support codes (e.g. "JUE", or a bitmask digit like "F").
     pub region_codes: String,
 }
 
+/// Save memory declared at 0x01B0 ("RA", type, 0x20, start, end).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveMemory {
+    pub kind: SaveMemoryKind,
+    /// Whether the memory keeps its contents without power (battery or EEPROM).
+    pub battery: bool,
+    /// First address of the memory.
+    pub start: u32,
+    /// Last address of the memory (inclusive).
+    pub end: u32,
+}
+
+/// How save memory is wired to the 68000 bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveMemoryKind {
+    /// RAM on both bytes of each word.
+    Word,
+    /// 8-bit RAM on even addresses.
+    EvenBytes,
+    /// 8-bit RAM on odd addresses.
+    OddBytes,
+    /// Serial EEPROM; the range is its I/O port, not its size.
+    Eeprom,
+}
+
+impl SaveMemory {
+    /// Parse the 12-byte extra memory field, or `None` when it declares nothing.
+    pub(crate) fn parse(field: &[u8]) -> Option<Self> {
+        if field.len() < 12 || &field[0..2] != b"RA" {
+            return None;
+        }
+        let flags = field[2];
+        let kind = match (flags >> 3) & 0b11 {
+            0b00 => SaveMemoryKind::Word,
+            0b01 => SaveMemoryKind::Eeprom,
+            0b10 => SaveMemoryKind::EvenBytes,
+            _ => SaveMemoryKind::OddBytes,
+        };
+        let start = u32::from_be_bytes([field[4], field[5], field[6], field[7]]);
+        let end = u32::from_be_bytes([field[8], field[9], field[10], field[11]]);
+        Some(Self {
+            kind,
+            battery: kind == SaveMemoryKind::Eeprom || flags & 0x40 != 0,
+            start,
+            end,
+        })
+    }
+
+    /// Usable bytes of RAM, or `None` for EEPROM and malformed ranges.
+    pub fn size(&self) -> Option<u64> {
+        let span = (self.end as u64).checked_sub(self.start as u64)?;
+        match self.kind {
+            SaveMemoryKind::Word => Some(span + 1),
+            SaveMemoryKind::EvenBytes | SaveMemoryKind::OddBytes => Some(span / 2 + 1),
+            SaveMemoryKind::Eeprom => None,
+        }
+    }
+
+    /// Human-readable type, e.g. "SRAM (odd bytes)".
+    pub fn description(&self) -> &'static str {
+        match (self.kind, self.battery) {
+            (SaveMemoryKind::Eeprom, _) => "EEPROM",
+            (SaveMemoryKind::Word, true) => "SRAM (16-bit)",
+            (SaveMemoryKind::EvenBytes, true) => "SRAM (even bytes)",
+            (SaveMemoryKind::OddBytes, true) => "SRAM (odd bytes)",
+            (SaveMemoryKind::Word, false) => "RAM (16-bit)",
+            (SaveMemoryKind::EvenBytes, false) => "RAM (even bytes)",
+            (SaveMemoryKind::OddBytes, false) => "RAM (odd bytes)",
+        }
+    }
+}
+
 /// Read a fixed-size ASCII string from a buffer slice, trimming trailing spaces and nulls.
 /// Parse the Genesis header from a 256-byte buffer (offsets 0x0100–0x01FF).
-fn parse_header(buf: &[u8; 256]) -> GenesisHeader {
+pub(crate) fn parse_header(buf: &[u8; 256]) -> GenesisHeader {
     let system_type = read_ascii(&buf[0x00..0x10]);
     let copyright = read_ascii(&buf[0x10..0x20]);
     let domestic_title = read_ascii(&buf[0x20..0x50]);
@@ -67,7 +254,7 @@ fn parse_header(buf: &[u8; 256]) -> GenesisHeader {
     let rom_end = u32::from_be_bytes([buf[0xA4], buf[0xA5], buf[0xA6], buf[0xA7]]);
     let ram_start = u32::from_be_bytes([buf[0xA8], buf[0xA9], buf[0xAA], buf[0xAB]]);
     let ram_end = u32::from_be_bytes([buf[0xAC], buf[0xAD], buf[0xAE], buf[0xAF]]);
-    let extra_memory = read_ascii(&buf[0xB0..0xBC]);
+    let extra_memory = SaveMemory::parse(&buf[0xB0..0xBC]);
     let region_codes = read_ascii(&buf[0xF0..0xF3]);
 
     GenesisHeader {
@@ -88,9 +275,31 @@ fn parse_header(buf: &[u8; 256]) -> GenesisHeader {
 }
 
 /// Decode region codes from the header's region field.
-fn decode_regions(region_codes: &str) -> Vec<Region> {
+///
+/// Early games list letters ("JUE"). Later games use a single hex digit
+/// bitmask: bit 0 Japan, bit 1 Asia (PAL), bit 2 Americas, bit 3 Europe.
+/// "A" and "E" are read as letters, which is how games almost always mean them.
+pub(crate) fn decode_regions(region_codes: &str) -> Vec<Region> {
     let mut regions = Vec::new();
-    for c in region_codes.chars() {
+    let code = region_codes.trim();
+    if let Some(mask) = new_style_region_mask(code) {
+        // Asia (PAL) maps to Japan, as the letter 'A' does
+        for (bit, region) in [
+            (0x1, Region::Japan),
+            (0x2, Region::Japan),
+            (0x4, Region::Usa),
+            (0x8, Region::Europe),
+        ] {
+            if mask & bit != 0 && !regions.contains(&region) {
+                regions.push(region);
+            }
+        }
+        if regions.is_empty() {
+            regions.push(Region::Unknown);
+        }
+        return regions;
+    }
+    for c in code.chars() {
         match c.to_ascii_uppercase() {
             'J' => regions.push(Region::Japan),
             'U' => regions.push(Region::Usa),
@@ -109,18 +318,86 @@ fn decode_regions(region_codes: &str) -> Vec<Region> {
     regions
 }
 
+/// The bitmask of a new-style region field (a single hex digit).
+fn new_style_region_mask(code: &str) -> Option<u8> {
+    let [c] = code.as_bytes() else {
+        return None;
+    };
+    if matches!(c.to_ascii_uppercase(), b'A' | b'E') {
+        return None;
+    }
+    (*c as char).to_digit(16).map(|d| d as u8)
+}
+
+/// Names of the peripherals listed in the I/O support field (0x0190).
+pub(crate) fn decode_io_support(device_support: &str) -> Vec<&'static str> {
+    let mut devices = Vec::new();
+    for c in device_support.chars() {
+        let name = match c.to_ascii_uppercase() {
+            'J' => "3-button controller",
+            '6' => "6-button controller",
+            '0' => "Master System controller",
+            'A' => "Analog joystick",
+            '4' => "Team Player",
+            'G' => "Light gun",
+            'L' => "Activator",
+            'M' => "Mouse",
+            'B' => "Trackball",
+            'T' => "Tablet",
+            'V' => "Paddle",
+            'K' => "Keyboard",
+            'R' => "RS-232",
+            'P' => "Printer",
+            'C' => "CD-ROM",
+            'F' => "Floppy drive",
+            'D' => "Download",
+            _ => continue,
+        };
+        if !devices.contains(&name) {
+            devices.push(name);
+        }
+    }
+    devices
+}
+
+/// Extract the year and month from a copyright line such as
+/// "(C)SEGA 1991.APR" or "(C)T-12 1993 JUL".
+pub(crate) fn parse_copyright_date(copyright: &str) -> Option<BuildDate> {
+    const MONTHS: [&str; 12] = [
+        "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+    ];
+    let bytes = copyright.as_bytes();
+    let start = (0..bytes.len().saturating_sub(3)).find(|&i| {
+        bytes[i..i + 4].iter().all(u8::is_ascii_digit)
+            && (bytes[i..].starts_with(b"19") || bytes[i..].starts_with(b"20"))
+    })?;
+    let year: u16 = copyright[start..start + 4].parse().ok()?;
+    let rest = copyright[start + 4..].trim_start_matches(['.', ' ', '/']);
+    let month = MONTHS
+        .iter()
+        .position(|m| rest.len() >= 3 && rest[..3].eq_ignore_ascii_case(m))
+        .map(|i| i as u8 + 1);
+    BuildDate::new(year, month, None)
+}
+
 /// Compute the additive checksum over ROM data from 0x0200 to `rom_end` (inclusive).
 /// Returns the lower 16 bits of the sum of all big-endian u16 words.
 ///
 /// The Genesis checksum only covers data up to the ROM end address declared in the
-/// header — any padding beyond that (common in dumped ROMs) is excluded.
-fn compute_checksum(reader: &mut dyn ReadSeek, rom_end: u32) -> Result<u16, AnalysisError> {
+/// header — any padding beyond that (common in dumped ROMs) is excluded. The
+/// range stops at the end of the file when the header declares more than it holds.
+pub(crate) fn compute_checksum(
+    reader: &mut dyn ReadSeek,
+    rom_end: u32,
+    limits: &ParseLimits,
+) -> Result<u16, AnalysisError> {
     let checksum_start = 0x200u64;
-    let checksum_end = rom_end as u64 + 1; // exclusive end
+    let file_size = retro_junk_core::util::file_size(reader)?;
+    let checksum_end = (rom_end as u64 + 1).min(file_size); // exclusive end
     if checksum_end <= checksum_start {
         return Ok(0);
     }
-    let len = (checksum_end - checksum_start) as usize;
+    let len = limits.check_alloc(checksum_end - checksum_start, "checksummed ROM")?;
 
     reader.seek(SeekFrom::Start(checksum_start))?;
     let mut buf = vec![0u8; len];
@@ -147,135 +424,200 @@ fn compute_checksum(reader: &mut dyn ReadSeek, rom_end: u32) -> Result<u16, Anal
 #[derive(Debug, Default)]
 pub struct GenesisAnalyzer;
 
-impl RomAnalyzer for GenesisAnalyzer {
-    fn analyze(
-        &self,
-        reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
-    ) -> Result<RomIdentification, AnalysisError> {
-        // Get file size
-        let file_size = retro_junk_core::util::file_size(reader)?;
-
-        if file_size < MIN_HEADER_SIZE {
-            return Err(AnalysisError::TooSmall {
+/// Analyze a plain (big-endian, headerless) ROM of `file_size` bytes.
+fn analyze_rom(
+    reader: &mut dyn ReadSeek,
+    options: &AnalysisOptions,
+    file_size: u64,
+) -> Result<RomIdentification, AnalysisError> {
+    // Read header
+    reader.seek(SeekFrom::Start(HEADER_OFFSET))?;
+    let mut header_buf = [0u8; 256];
+    reader.read_exact(&mut header_buf).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::UnexpectedEof {
+            AnalysisError::TooSmall {
                 expected: MIN_HEADER_SIZE,
                 actual: file_size,
-            });
-        }
-
-        // Read header
-        reader.seek(SeekFrom::Start(HEADER_OFFSET))?;
-        let mut header_buf = [0u8; 256];
-        reader.read_exact(&mut header_buf).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                AnalysisError::TooSmall {
-                    expected: MIN_HEADER_SIZE,
-                    actual: file_size,
-                }
-            } else {
-                AnalysisError::Io(e)
             }
-        })?;
-
-        // Verify magic
-        if &header_buf[0..4] != SEGA_MAGIC {
-            return Err(AnalysisError::invalid_format(
-                "Not a Sega Genesis ROM (missing SEGA magic at 0x0100)",
-            ));
+        } else {
+            AnalysisError::io_at(e, HEADER_OFFSET)
         }
+    })?;
+
+    // Verify magic
+    if &header_buf[0..4] != SEGA_MAGIC {
+        return Err(AnalysisError::invalid_magic(
+            HEADER_OFFSET,
+            SEGA_MAGIC,
+            &header_buf[0..4],
+        ));
+    }
 
-        let header = parse_header(&header_buf);
+    let header = parse_header(&header_buf);
 
-        // Build identification
-        let mut id = RomIdentification::new().with_platform(Platform::Genesis);
-        id.file_size = Some(file_size);
+    // Build identification
+    let mut id = RomIdentification::new().with_platform(Platform::Genesis);
+    id.file_size = Some(file_size);
 
-        if !header.serial_number.is_empty() {
-            id = id.with_serial(&header.serial_number);
-        }
-        if !header.domestic_title.is_empty() {
-            id = id.with_internal_name(&header.domestic_title);
-        }
-
-        // Regions
-        id.regions = decode_regions(&header.region_codes);
+    if !header.serial_number.is_empty() {
+        id = id.with_serial(&header.serial_number);
+    }
+    if !header.domestic_title.is_empty() {
+        id = id.with_internal_name(&header.domestic_title);
+    }
 
-        // Expected size from ROM end address (inclusive, so +1).
-        // Genesis dumps are commonly padded to the next power of 2, so a file
-        // larger than rom_end+1 is normal. We only flag truncated files.
-        let declared_size = if header.rom_end > 0 {
-            header.rom_end as u64 + 1
+    // Regions
+    id.regions = decode_regions(&header.region_codes);
+
+    // Expected size from ROM end address (inclusive, so +1).
+    // Genesis dumps are commonly padded to the next power of 2, so a file
+    // larger than rom_end+1 is normal. We only flag truncated files.
+    let declared_size = if header.rom_end > 0 {
+        header.rom_end as u64 + 1
+    } else {
+        0
+    };
+    if declared_size > 0 {
+        // Use the file size itself as expected when the file is at least as
+        // large as the declared ROM — this avoids false "oversized" reports
+        // from power-of-2 padding.  If the file is truncated, report the
+        // declared size so the mismatch is visible.
+        if file_size >= declared_size {
+            id.expected_size = Some(file_size);
         } else {
-            0
-        };
-        if declared_size > 0 {
-            // Use the file size itself as expected when the file is at least as
-            // large as the declared ROM — this avoids false "oversized" reports
-            // from power-of-2 padding.  If the file is truncated, report the
-            // declared size so the mismatch is visible.
-            if file_size >= declared_size {
-                id.expected_size = Some(file_size);
-            } else {
-                id.expected_size = Some(declared_size);
-            }
+            id.expected_size = Some(declared_size);
         }
+    }
 
-        // Store the header checksum as an expected checksum
-        id.expected_checksums.push(
-            ExpectedChecksum::new(
-                ChecksumAlgorithm::Additive,
-                header.checksum.to_be_bytes().to_vec(),
+    // Store the header checksum as an expected checksum
+    id.expected_checksums.push(
+        ExpectedChecksum::new(
+            ChecksumAlgorithm::Additive,
+            header.checksum.to_be_bytes().to_vec(),
+        )
+        .with_description("ROM checksum (0x0200 to ROM end)"),
+    );
+
+    // Verify checksum — only covers 0x0200..=rom_end per the Genesis spec.
+    // Some games declare a ROM end short of their data and sum the whole
+    // ROM instead, so fall back to the full file before reporting a mismatch.
+    let mut computed = compute_checksum(reader, header.rom_end, &options.limits)?;
+    if computed != header.checksum && declared_size < file_size {
+        let whole = compute_checksum(reader, u32::MAX, &options.limits)?;
+        if whole == header.checksum {
+            computed = whole;
+        }
+    }
+    let checksum_valid = computed == header.checksum;
+    id.extra.insert(
+        "checksum_status:rom".into(),
+        if checksum_valid {
+            "Valid".into()
+        } else {
+            format!(
+                "Invalid (expected 0x{:04X}, computed 0x{:04X})",
+                header.checksum, computed
             )
-            .with_description("ROM checksum (0x0200 to ROM end)"),
-        );
-
-        // Verify checksum — only covers 0x0200..=rom_end per the Genesis spec
-        let computed = compute_checksum(reader, header.rom_end)?;
-        let checksum_valid = computed == header.checksum;
-        id.extra.insert(
-            "checksum_status:rom".into(),
-            if checksum_valid {
-                "Valid".into()
-            } else {
-                format!(
-                    "Invalid (expected 0x{:04X}, computed 0x{:04X})",
-                    header.checksum, computed
-                )
-            },
-        );
+        },
+    );
 
-        // Extra fields
+    // Extra fields
+    id.extra
+        .insert("system_type".into(), header.system_type.clone());
+    if !header.copyright.is_empty() {
         id.extra
-            .insert("system_type".into(), header.system_type.clone());
-        if !header.copyright.is_empty() {
-            id.extra
-                .insert("copyright".into(), header.copyright.clone());
-        }
-        if !header.overseas_title.is_empty() {
-            id.extra
-                .insert("overseas_title".into(), header.overseas_title.clone());
-        }
-        if !header.device_support.is_empty() {
-            id.extra
-                .insert("device_support".into(), header.device_support.clone());
+            .insert("copyright".into(), header.copyright.clone());
+    }
+    id.build_date = parse_copyright_date(&header.copyright);
+    if !header.overseas_title.is_empty() {
+        id.extra
+            .insert("overseas_title".into(), header.overseas_title.clone());
+    }
+    if !header.device_support.is_empty() {
+        id.extra
+            .insert("device_support".into(), header.device_support.clone());
+        let devices = decode_io_support(&header.device_support);
+        if !devices.is_empty() {
+            id.extra.insert("io_support".into(), devices.join(", "));
         }
+    }
+    id.extra.insert(
+        "rom_address_range".into(),
+        format!("0x{:08X}-0x{:08X}", header.rom_start, header.rom_end),
+    );
+    id.extra.insert(
+        "ram_address_range".into(),
+        format!("0x{:08X}-0x{:08X}", header.ram_start, header.ram_end),
+    );
+    if !header.region_codes.is_empty() {
+        id.extra
+            .insert("region_codes".into(), header.region_codes.clone());
+    }
+    if let Some(save) = header.extra_memory {
+        id.extra
+            .insert("save_type".into(), save.description().into());
         id.extra.insert(
-            "rom_address_range".into(),
-            format!("0x{:08X}-0x{:08X}", header.rom_start, header.rom_end),
-        );
-        id.extra.insert(
-            "ram_address_range".into(),
-            format!("0x{:08X}-0x{:08X}", header.ram_start, header.ram_end),
+            "save_address_range".into(),
+            format!("0x{:08X}-0x{:08X}", save.start, save.end),
         );
-        if !header.region_codes.is_empty() {
-            id.extra
-                .insert("region_codes".into(), header.region_codes.clone());
+        if let Some(size) = save.size() {
+            id.extra.insert("sram_size".into(), format_bytes(size));
         }
-        if !header.extra_memory.is_empty() {
-            id.extra
-                .insert("extra_memory".into(), header.extra_memory.clone());
+        if save.battery {
+            id.extra.insert("battery".into(), "Yes".into());
+        }
+    }
+
+    id.raw_header = options
+        .include_raw_header
+        .then(|| RawHeader::from_bytes(HEADER_OFFSET, &header_buf));
+
+    Ok(id)
+}
+
+impl RomAnalyzer for GenesisAnalyzer {
+    fn analyze(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        // Get file size
+        let file_size = retro_junk_core::util::file_size(reader)?;
+
+        if file_size < MIN_HEADER_SIZE {
+            return Err(AnalysisError::TooSmall {
+                expected: MIN_HEADER_SIZE,
+                actual: file_size,
+            });
         }
 
+        let format = match detect_format(reader, file_size)? {
+            Some(GenesisFormat::Plain) | None => {
+                let mut id = analyze_rom(reader, options, file_size)?;
+                id.extra
+                    .insert("format".into(), GenesisFormat::Plain.name().into());
+                return Ok(id);
+            }
+            Some(format) => format,
+        };
+
+        // Other layouts are normalized in memory and analyzed as a plain ROM
+        let offset = format.header_size();
+        let len = options
+            .limits
+            .check_alloc(file_size - offset, "normalized ROM")?;
+        reader.seek(SeekFrom::Start(offset))?;
+        let mut data = vec![0u8; len];
+        reader.read_exact(&mut data)?;
+        normalize(format, &mut data);
+
+        let mut id = analyze_rom(&mut Cursor::new(data), options, file_size - offset)?;
+        id.file_size = Some(file_size);
+        id.expected_size = id.expected_size.map(|size| size + offset);
+        id.extra.insert("format".into(), format.name().into());
+        if offset > 0 {
+            id.extra.insert("copier_header".into(), "Yes".into());
+        }
         Ok(id)
     }
 
@@ -288,16 +630,47 @@ impl RomAnalyzer for GenesisAnalyzer {
     }
 
     fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
-        let result = (|| -> Result<bool, std::io::Error> {
-            reader.seek(SeekFrom::Start(HEADER_OFFSET))?;
-            let mut magic = [0u8; 4];
-            reader.read_exact(&mut magic)?;
-            reader.seek(SeekFrom::Start(0))?;
-            Ok(&magic == SEGA_MAGIC)
-        })();
+        let result = reader
+            .seek(SeekFrom::End(0))
+            .and_then(|size| detect_format(reader, size));
         // Always rewind on failure too
         let _ = reader.seek(SeekFrom::Start(0));
-        result.unwrap_or(false)
+        matches!(result, Ok(Some(_)))
+    }
+
+    fn music_extensions(&self) -> &'static [&'static str] {
+        crate::music::EXTENSIONS
+    }
+
+    fn analyze_music(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        crate::music::analyze(reader, options, Platform::Genesis)
+    }
+
+    fn dat_header_size(
+        &self,
+        reader: &mut dyn ReadSeek,
+        file_size: u64,
+    ) -> Result<u64, AnalysisError> {
+        let format = detect_format(reader, file_size)?;
+        Ok(format.map_or(0, GenesisFormat::header_size))
+    }
+
+    fn dat_chunk_normalizer(
+        &self,
+        reader: &mut dyn ReadSeek,
+        _header_offset: u64,
+    ) -> ChunkNormalizerResult {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        match detect_format(reader, file_size)? {
+            Some(format @ (GenesisFormat::ByteSwapped | GenesisFormat::Smd)) => {
+                Ok(Some(Box::new(move |buf: &mut [u8]| normalize(format, buf))))
+            }
+            _ => Ok(None),
+        }
     }
 
     fn dat_names(&self) -> &'static [&'static str] {
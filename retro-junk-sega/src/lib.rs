@@ -10,15 +10,23 @@
 //! - Saturn
 //! - Dreamcast
 //! - Game Gear
+//!
+//! VGM logs of the cartridge consoles' sound chips are identified by the
+//! matching analyzer's `analyze_music`, apart from its ROMs.
 
 pub mod dreamcast;
 pub mod game_gear;
 pub mod genesis;
 pub mod master_system;
+mod music;
 pub mod saturn;
 pub mod sega_32x;
 pub mod sega_cd;
+mod sega_disc;
 pub mod sg1000;
+mod sms_header;
+#[cfg(any(test, feature = "testkit"))]
+pub mod synthetic;
 
 pub use dreamcast::DreamcastAnalyzer;
 pub use game_gear::GameGearAnalyzer;
@@ -2,12 +2,18 @@
 //!
 //! Supports:
 //! - Master System ROMs (.sms)
-//! - Mark III ROMs
+//! - Mark III ROMs, including Japanese dumps without a "TMR SEGA" header
+//! - VGM music rips (.vgm, .vgz)
 
 use retro_junk_core::ReadSeek;
 
 use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
 
+use crate::sms_header::{self, CartFamily};
+
+/// Smallest Master System dump: an 8 KB Sega Card.
+const MIN_ROM_SIZE: u64 = 8 * 1024;
+
 /// Analyzer for Sega Master System ROMs.
 #[derive(Debug, Default)]
 pub struct MasterSystemAnalyzer;
@@ -15,12 +21,36 @@ pub struct MasterSystemAnalyzer;
 impl RomAnalyzer for MasterSystemAnalyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "Master System ROM analysis not yet implemented",
-        ))
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        if file_size < MIN_ROM_SIZE {
+            return Err(AnalysisError::TooSmall {
+                expected: MIN_ROM_SIZE,
+                actual: file_size,
+            });
+        }
+
+        match sms_header::classify(reader)? {
+            Some(CartFamily::Sg1000) => {
+                return Err(AnalysisError::invalid_format(
+                    "No TMR SEGA header and the code targets SG-1000 hardware",
+                ));
+            }
+            Some(CartFamily::GameGear) => {
+                return Err(AnalysisError::invalid_format(
+                    "TMR SEGA header has a Game Gear region code",
+                ));
+            }
+            Some(CartFamily::MasterSystem) | None => {}
+        }
+
+        let mut id = RomIdentification::new().with_platform(Platform::MasterSystem);
+        id.file_size = Some(file_size);
+
+        sms_header::identify(reader, options, &mut id)?;
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -31,8 +61,23 @@ impl RomAnalyzer for MasterSystemAnalyzer {
         &["sms"]
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        // Only the header is strong enough evidence for auto-detection;
+        // headerless dumps are recognized by extension or folder
+        let result = sms_header::find_header(reader);
+        matches!(result, Ok(Some(header)) if !header.is_game_gear())
+    }
+
+    fn music_extensions(&self) -> &'static [&'static str] {
+        crate::music::EXTENSIONS
+    }
+
+    fn analyze_music(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        crate::music::analyze(reader, options, Platform::MasterSystem)
     }
 
     fn dat_names(&self) -> &'static [&'static str] {
@@ -43,3 +88,7 @@ impl RomAnalyzer for MasterSystemAnalyzer {
         &["console_sega_markIII_mastersystem"]
     }
 }
+
+#[cfg(test)]
+#[path = "tests/master_system_tests.rs"]
+mod tests;
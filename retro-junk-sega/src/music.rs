@@ -0,0 +1,51 @@
+//! VGM music rips of Sega console games.
+//!
+//! The VGM format began as a log of Master System and Genesis sound, and
+//! most logs are still of these consoles. A YM2612 means Genesis music
+//! (Sega CD and 32X logs included); an SN76489 without one means the
+//! 8-bit consoles, which share the chip, so the GD3 tag's system name
+//! picks between them, defaulting to the Master System. Rips are read
+//! through each console's
+//! [`RomAnalyzer::analyze_music`](retro_junk_core::RomAnalyzer::analyze_music).
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, ReadSeek, RomIdentification};
+use retro_junk_music::vgm::{self, Vgm, VgmChip};
+
+/// Music rip extensions of the cartridge consoles.
+pub(crate) const EXTENSIONS: &[&str] = &["vgm", "vgz"];
+
+/// The console a VGM log's music comes from, if it's a Sega one.
+pub(crate) fn vgm_platform(vgm: &Vgm) -> Option<Platform> {
+    if vgm.has_chip(VgmChip::Ym2612) {
+        return Some(Platform::Genesis);
+    }
+    if !vgm.has_chip(VgmChip::Sn76489) {
+        return None;
+    }
+    let system = vgm.system().unwrap_or_default().to_lowercase();
+    Some(if system.contains("game gear") {
+        Platform::GameGear
+    } else if ["sg-1000", "sg1000", "sc-3000"]
+        .iter()
+        .any(|name| system.contains(name))
+    {
+        Platform::Sg1000
+    } else {
+        Platform::MasterSystem
+    })
+}
+
+/// Analyze the file as a `platform` VGM rip.
+pub(crate) fn analyze(
+    reader: &mut dyn ReadSeek,
+    options: &AnalysisOptions,
+    platform: Platform,
+) -> Result<RomIdentification, AnalysisError> {
+    vgm::analyze(reader, options, platform, |v| {
+        vgm_platform(v) == Some(platform)
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/music_tests.rs"]
+mod tests;
@@ -4,11 +4,126 @@
 //! - BIN/CUE images
 //! - ISO images
 //! - CHD compressed images
-//! - MDF/MDS images
+//! - MDF/MDS images (not analyzed yet)
+//!
+//! The first sector of the data track holds the system ID, which starts
+//! with `SEGA SEGASATURN` and carries the product number, release date,
+//! disc number, area codes, and title.
 
 use retro_junk_core::ReadSeek;
 
-use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+use retro_junk_core::util::read_ascii_fixed as read_ascii;
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, BuildDate, FileHashes, HashAlgorithms, Platform, Region,
+    RomAnalyzer, RomIdentification,
+};
+
+use crate::sega_disc::{self, ChdDisc, DiscFormat, USER_DATA_SIZE};
+
+/// Hardware identifier at the start of the boot sector.
+const DISC_MAGIC: &[u8] = b"SEGA SEGASATURN ";
+
+/// The boot track of a Saturn disc.
+const BOOT_TRACK: u32 = 1;
+
+/// Fields of the Saturn system ID.
+#[derive(Debug, Clone)]
+pub struct SaturnHeader {
+    /// Maker ID (e.g. "SEGA ENTERPRISES" or "SEGA TP T-7").
+    pub maker_id: String,
+    /// Product number (e.g. "MK-81005").
+    pub product_number: String,
+    /// Version (e.g. "V1.000").
+    pub version: String,
+    /// Release date as `YYYYMMDD`.
+    pub release_date: String,
+    /// Device information (e.g. "CD-1/2" for the first of two discs).
+    pub device_info: String,
+    /// Compatible area symbols (e.g. "JTUE").
+    pub area_codes: String,
+    /// Game title.
+    pub title: String,
+}
+
+/// Parse the system ID from the first 256 bytes of the boot sector.
+pub(crate) fn parse_header(buf: &[u8]) -> SaturnHeader {
+    SaturnHeader {
+        maker_id: read_ascii(&buf[0x10..0x20]),
+        product_number: read_ascii(&buf[0x20..0x2A]),
+        version: read_ascii(&buf[0x2A..0x30]),
+        release_date: read_ascii(&buf[0x30..0x38]),
+        device_info: read_ascii(&buf[0x38..0x40]),
+        area_codes: read_ascii(&buf[0x40..0x50]),
+        title: read_ascii(&buf[0x60..0xD0]),
+    }
+}
+
+/// Decode the Saturn area symbols.
+fn decode_areas(area_codes: &str) -> Vec<Region> {
+    let mut regions = Vec::new();
+    for c in area_codes.chars() {
+        let region = match c {
+            'J' => Region::Japan,
+            'T' => Region::Taiwan,
+            'U' => Region::Usa,
+            'B' => Region::Brazil,
+            'K' => Region::Korea,
+            'E' => Region::Europe,
+            // Asia PAL and Latin America have no matching region
+            _ => continue,
+        };
+        if !regions.contains(&region) {
+            regions.push(region);
+        }
+    }
+    if regions.is_empty() {
+        regions.push(Region::Unknown);
+    }
+    regions
+}
+
+/// Disc number and count from device information such as "CD-1/2".
+fn disc_of(device_info: &str) -> Option<(u32, u32)> {
+    let (number, total) = device_info.strip_prefix("CD-")?.split_once('/')?;
+    Some((number.trim().parse().ok()?, total.trim().parse().ok()?))
+}
+
+/// Build the identification from the boot sector.
+fn identify(sector: &[u8; USER_DATA_SIZE]) -> Result<RomIdentification, AnalysisError> {
+    if !sector.starts_with(DISC_MAGIC) {
+        return Err(AnalysisError::invalid_magic(
+            0,
+            DISC_MAGIC,
+            &sector[..DISC_MAGIC.len()],
+        ));
+    }
+    let header = parse_header(sector);
+
+    let mut id = RomIdentification::new().with_platform(Platform::Saturn);
+    if !header.product_number.is_empty() {
+        id = id.with_serial(&header.product_number);
+    }
+    if !header.title.is_empty() {
+        id = id.with_internal_name(&header.title);
+    }
+    if !header.version.is_empty() {
+        id.version = Some(header.version.clone());
+    }
+    if !header.maker_id.is_empty() {
+        id.maker_code = Some(header.maker_id.clone());
+    }
+    id.regions = decode_areas(&header.area_codes);
+    id.build_date = BuildDate::parse(&header.release_date);
+    if !header.area_codes.is_empty() {
+        id.extra
+            .insert("area_codes".into(), header.area_codes.clone());
+    }
+    if let Some((number, total)) = disc_of(&header.device_info) {
+        id.extra
+            .insert("disc".into(), format!("{} of {}", number, total));
+    }
+    Ok(id)
+}
 
 /// Analyzer for Sega Saturn disc images.
 #[derive(Debug, Default)]
@@ -17,12 +132,19 @@ pub struct SaturnAnalyzer;
 impl RomAnalyzer for SaturnAnalyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "Saturn disc analysis not yet implemented",
-        ))
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let format = sega_disc::detect_format(reader)?;
+        let sector = sega_disc::read_boot_sector(reader, format, BOOT_TRACK, options)?;
+
+        let mut id = identify(&sector)?;
+        id.file_size = Some(file_size);
+        id.extra.insert("format".into(), format.name().into());
+        id.extra
+            .insert("detected_extension".into(), format.extension().into());
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -33,14 +155,42 @@ impl RomAnalyzer for SaturnAnalyzer {
         &["bin", "cue", "iso", "chd", "mdf", "mds"]
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        // CUE sheets need their path to be followed, so only images are claimed
+        let options = AnalysisOptions::default();
+        let result = sega_disc::detect_format(reader)
+            .and_then(|format| match format {
+                DiscFormat::Cue => Ok(false),
+                _ => sega_disc::read_boot_sector(reader, format, BOOT_TRACK, &options)
+                    .map(|sector| sector.starts_with(DISC_MAGIC)),
+            })
+            .unwrap_or(false);
+        let _ = reader.seek(std::io::SeekFrom::Start(0));
+        result
     }
 
     fn dat_source(&self) -> retro_junk_core::DatSource {
         retro_junk_core::DatSource::Redump
     }
 
+    fn dat_lists_raw_cd_tracks(&self) -> bool {
+        true
+    }
+
+    fn compute_container_hashes(
+        &self,
+        reader: &mut dyn ReadSeek,
+        algorithms: HashAlgorithms,
+        _file_path: Option<&std::path::Path>,
+    ) -> Result<Option<FileHashes>, AnalysisError> {
+        if sega_disc::detect_format(reader)? != DiscFormat::Chd {
+            return Ok(None);
+        }
+        ChdDisc::open(reader)?
+            .hash_track(BOOT_TRACK, algorithms)
+            .map(Some)
+    }
+
     fn dat_names(&self) -> &'static [&'static str] {
         &["Sega - Saturn"]
     }
@@ -49,3 +199,7 @@ impl RomAnalyzer for SaturnAnalyzer {
         &["console_sega_saturn"]
     }
 }
+
+#[cfg(test)]
+#[path = "tests/saturn_tests.rs"]
+mod tests;
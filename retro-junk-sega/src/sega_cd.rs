@@ -4,10 +4,70 @@
 //! - BIN/CUE images
 //! - ISO images
 //! - CHD compressed images
+//!
+//! The first sector of the data track starts with `SEGADISCSYSTEM`, and
+//! holds a Genesis-style header at offset 0x100 with the title, serial,
+//! and region codes.
 
 use retro_junk_core::ReadSeek;
 
-use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, FileHashes, HashAlgorithms, Platform, RomAnalyzer,
+    RomIdentification,
+};
+
+use crate::genesis::{decode_regions, parse_copyright_date, parse_header};
+use crate::sega_disc::{self, ChdDisc, DiscFormat, USER_DATA_SIZE};
+
+/// Disc identifier at the start of the boot sector.
+const DISC_MAGIC: &[u8] = b"SEGADISCSYSTEM";
+
+/// Offset of the Genesis-style header within the boot sector.
+const HEADER_OFFSET: usize = 0x100;
+
+/// The boot track of a Sega CD.
+const BOOT_TRACK: u32 = 1;
+
+/// Build the identification from the boot sector.
+fn identify(sector: &[u8; USER_DATA_SIZE]) -> Result<RomIdentification, AnalysisError> {
+    if !sector.starts_with(DISC_MAGIC) {
+        return Err(AnalysisError::invalid_magic(
+            0,
+            DISC_MAGIC,
+            &sector[..DISC_MAGIC.len()],
+        ));
+    }
+    let header_buf: &[u8; 256] = sector[HEADER_OFFSET..HEADER_OFFSET + 256]
+        .try_into()
+        .expect("header slice is 256 bytes");
+    let header = parse_header(header_buf);
+
+    let mut id = RomIdentification::new().with_platform(Platform::SegaCd);
+    if !header.serial_number.is_empty() {
+        id = id.with_serial(&header.serial_number);
+    }
+    let title = if header.overseas_title.is_empty() {
+        &header.domestic_title
+    } else {
+        &header.overseas_title
+    };
+    if !title.is_empty() {
+        id = id.with_internal_name(title);
+    }
+    id.regions = decode_regions(&header.region_codes);
+    id.build_date = parse_copyright_date(&header.copyright);
+    id.extra
+        .insert("system_type".into(), header.system_type.clone());
+    if !header.copyright.is_empty() {
+        id.extra
+            .insert("copyright".into(), header.copyright.clone());
+    }
+    if !header.region_codes.is_empty() {
+        id.extra
+            .insert("region_codes".into(), header.region_codes.clone());
+    }
+    Ok(id)
+}
 
 /// Analyzer for Sega CD / Mega CD disc images.
 #[derive(Debug, Default)]
@@ -16,12 +76,19 @@ pub struct SegaCdAnalyzer;
 impl RomAnalyzer for SegaCdAnalyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "Sega CD disc analysis not yet implemented",
-        ))
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let format = sega_disc::detect_format(reader)?;
+        let sector = sega_disc::read_boot_sector(reader, format, BOOT_TRACK, options)?;
+
+        let mut id = identify(&sector)?;
+        id.file_size = Some(file_size);
+        id.extra.insert("format".into(), format.name().into());
+        id.extra
+            .insert("detected_extension".into(), format.extension().into());
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -32,14 +99,42 @@ impl RomAnalyzer for SegaCdAnalyzer {
         &["bin", "cue", "iso", "chd"]
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        // CUE sheets need their path to be followed, so only images are claimed
+        let options = AnalysisOptions::default();
+        let result = sega_disc::detect_format(reader)
+            .and_then(|format| match format {
+                DiscFormat::Cue => Ok(false),
+                _ => sega_disc::read_boot_sector(reader, format, BOOT_TRACK, &options)
+                    .map(|sector| sector.starts_with(DISC_MAGIC)),
+            })
+            .unwrap_or(false);
+        let _ = reader.seek(std::io::SeekFrom::Start(0));
+        result
     }
 
     fn dat_source(&self) -> retro_junk_core::DatSource {
         retro_junk_core::DatSource::Redump
     }
 
+    fn dat_lists_raw_cd_tracks(&self) -> bool {
+        true
+    }
+
+    fn compute_container_hashes(
+        &self,
+        reader: &mut dyn ReadSeek,
+        algorithms: HashAlgorithms,
+        _file_path: Option<&std::path::Path>,
+    ) -> Result<Option<FileHashes>, AnalysisError> {
+        if sega_disc::detect_format(reader)? != DiscFormat::Chd {
+            return Ok(None);
+        }
+        ChdDisc::open(reader)?
+            .hash_track(BOOT_TRACK, algorithms)
+            .map(Some)
+    }
+
     fn dat_names(&self) -> &'static [&'static str] {
         &["Sega - Mega-CD - Sega CD"]
     }
@@ -47,4 +142,23 @@ impl RomAnalyzer for SegaCdAnalyzer {
     fn gdb_csv_names(&self) -> &'static [&'static str] {
         &["console_sega_megacd_segacd"]
     }
+
+    fn extract_dat_game_code(&self, serial: &str) -> Option<String> {
+        // Header serials look like "GM T-93025 -00"; Redump lists "T-93025"
+        let code = serial.split_once(' ').map_or(serial, |(_, rest)| rest);
+        let code = match code.rsplit_once('-') {
+            Some((code, version))
+                if version.len() == 2 && version.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                code
+            }
+            _ => code,
+        };
+        let code = code.trim();
+        (!code.is_empty()).then(|| code.to_string())
+    }
 }
+
+#[cfg(test)]
+#[path = "tests/sega_cd_tests.rs"]
+mod tests;
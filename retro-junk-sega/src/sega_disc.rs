@@ -0,0 +1,457 @@
+//! Disc image reading shared by the Sega CD, Saturn, and Dreamcast analyzers.
+//!
+//! All three systems put their boot header in the first sectors of a data
+//! track, so analysis only needs to find that track and read its user data:
+//!
+//! - Raw BIN images (2352-byte sectors, starting with the CD sync pattern)
+//! - ISO images (2048-byte sectors)
+//! - CUE sheets, by opening the file of the first track
+//! - GDI sheets, by opening the file of the requested track
+//! - CHD v5 images, located through the `CHTR`/`CHT2`/`CHGD` track metadata
+//!
+//! Redump lists one hash per track, so CHDs are hashed one track at a time,
+//! using the bytes each track would have as a BIN file. GDI tracks are
+//! already separate files and are hashed as they are.
+
+use retro_junk_core::ReadSeek;
+use retro_junk_core::disc::cd::{
+    self, CHD_FRAME_SIZE, CHD_MAGIC, ChdTrack, RAW_SECTOR_SIZE, SYNC_PATTERN,
+};
+use retro_junk_core::disc::cue;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, FileHashes, HashAlgorithms, ParseLimits, StreamHasher,
+    hash_reader,
+};
+
+/// Bytes of user data in a sector.
+pub(crate) const USER_DATA_SIZE: usize = 2048;
+
+/// Container of a disc image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiscFormat {
+    /// A CUE sheet pointing at per-track files.
+    Cue,
+    /// A Dreamcast GDI sheet pointing at per-track files.
+    Gdi,
+    /// 2352-byte sectors with sync and header.
+    RawBin,
+    /// 2048 bytes of user data per sector.
+    Iso,
+    Chd,
+}
+
+impl DiscFormat {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Cue => "CUE Sheet",
+            Self::Gdi => "GDI",
+            Self::RawBin => "BIN (2352)",
+            Self::Iso => "ISO (2048)",
+            Self::Chd => "CHD",
+        }
+    }
+
+    pub(crate) fn extension(&self) -> &'static str {
+        match self {
+            Self::Cue => "cue",
+            Self::Gdi => "gdi",
+            Self::RawBin => "bin",
+            Self::Iso => "iso",
+            Self::Chd => "chd",
+        }
+    }
+}
+
+/// Tell the containers apart by their first bytes.
+pub(crate) fn detect_format(reader: &mut dyn ReadSeek) -> Result<DiscFormat, AnalysisError> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut head = [0u8; 256];
+    let n = reader.read(&mut head)?;
+    reader.seek(SeekFrom::Start(0))?;
+    let text = String::from_utf8_lossy(&head[..n]).to_uppercase();
+    Ok(if head.starts_with(CHD_MAGIC) {
+        DiscFormat::Chd
+    } else if n >= SYNC_PATTERN.len() && head.starts_with(&SYNC_PATTERN) {
+        DiscFormat::RawBin
+    } else if cue::looks_like_sheet(&head[..n]) {
+        DiscFormat::Cue
+    } else if looks_like_gdi(&text) {
+        DiscFormat::Gdi
+    } else {
+        DiscFormat::Iso
+    })
+}
+
+/// Read the first sector of a disc's boot track.
+///
+/// `track` picks the CHD or GDI track; BIN and ISO images are assumed to
+/// hold only the boot track, and a CUE sheet is followed to the file of its
+/// first track. Sheets are only followed when `options.file_path` says
+/// where to look.
+pub(crate) fn read_boot_sector(
+    reader: &mut dyn ReadSeek,
+    format: DiscFormat,
+    track: u32,
+    options: &AnalysisOptions,
+) -> Result<[u8; USER_DATA_SIZE], AnalysisError> {
+    match format {
+        DiscFormat::Chd => ChdDisc::open(reader)?.read_sector(track, 0),
+        DiscFormat::Gdi => GdiDisc::open(reader, options.file_path.as_deref(), &options.limits)?
+            .read_sector(track, 0),
+        DiscFormat::RawBin | DiscFormat::Iso => read_image_sector(reader, format, 0),
+        DiscFormat::Cue => {
+            let sheet = cue::parse(&options.limits.read_text(reader, "CUE sheet")?);
+            let file = sheet
+                .filenames()
+                .next()
+                .ok_or_else(|| AnalysisError::invalid_format("CUE sheet lists no files"))?;
+            let cue_path = options.file_path.as_deref().ok_or_else(|| {
+                AnalysisError::other("CUE sheet path is needed to find its track files")
+            })?;
+            let dir = cue_path.parent().unwrap_or(Path::new(""));
+            let mut bin = std::fs::File::open(dir.join(file))?;
+            match detect_format(&mut bin)? {
+                inner @ (DiscFormat::RawBin | DiscFormat::Iso) => {
+                    read_image_sector(&mut bin, inner, 0)
+                }
+                _ => Err(AnalysisError::invalid_format(format!(
+                    "{} is not a BIN or ISO track",
+                    file
+                ))),
+            }
+        }
+    }
+}
+
+/// Read the user data of sector `index` from a BIN or ISO image.
+pub(crate) fn read_image_sector(
+    reader: &mut dyn ReadSeek,
+    format: DiscFormat,
+    index: u64,
+) -> Result<[u8; USER_DATA_SIZE], AnalysisError> {
+    let offset = match format {
+        DiscFormat::RawBin => index * RAW_SECTOR_SIZE + 16,
+        DiscFormat::Iso => index * USER_DATA_SIZE as u64,
+        DiscFormat::Cue | DiscFormat::Gdi | DiscFormat::Chd => {
+            return Err(AnalysisError::other(format!(
+                "{} sectors can't be read directly",
+                format.name()
+            )));
+        }
+    };
+    let mut buf = [0u8; USER_DATA_SIZE];
+    reader.seek(SeekFrom::Start(offset))?;
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// An open CHD CD-ROM or GD-ROM image.
+pub(crate) struct ChdDisc<'a> {
+    chd: chd::Chd<&'a mut dyn ReadSeek>,
+    tracks: Vec<ChdTrack>,
+    hunk_buf: Vec<u8>,
+    cmp_buf: Vec<u8>,
+    loaded_hunk: Option<u32>,
+}
+
+impl<'a> ChdDisc<'a> {
+    pub fn open(reader: &'a mut dyn ReadSeek) -> Result<Self, AnalysisError> {
+        use chd::metadata::{KnownMetadata, MetadataTag};
+
+        reader.seek(SeekFrom::Start(0))?;
+        let mut chd = chd::Chd::open(reader, None)
+            .map_err(|e| AnalysisError::other(format!("Failed to open CHD: {}", e)))?;
+
+        let meta_refs: Vec<_> = chd.metadata_refs().collect();
+        let mut tracks = Vec::new();
+        for meta_ref in &meta_refs {
+            let tag = meta_ref.metatag();
+            if tag != KnownMetadata::CdRomTrack as u32
+                && tag != KnownMetadata::CdRomTrack2 as u32
+                && tag != KnownMetadata::GdRomTrack as u32
+            {
+                continue;
+            }
+            let meta = meta_ref
+                .read(chd.inner())
+                .map_err(|e| AnalysisError::other(format!("Failed to read CHD metadata: {}", e)))?;
+            if let Some(track) = cd::parse_chd_track(&String::from_utf8_lossy(&meta.value)) {
+                tracks.push(track);
+            }
+        }
+        if tracks.is_empty() {
+            return Err(AnalysisError::invalid_format(
+                "CHD has no CD-ROM track metadata",
+            ));
+        }
+
+        let hunk_buf = chd.get_hunksized_buffer();
+        Ok(Self {
+            chd,
+            tracks: cd::layout_chd_tracks(tracks),
+            hunk_buf,
+            cmp_buf: Vec::new(),
+            loaded_hunk: None,
+        })
+    }
+
+    pub fn track(&self, number: u32) -> Option<&ChdTrack> {
+        self.tracks.iter().find(|t| t.number == number)
+    }
+
+    /// Decompress the hunk holding `frame` and return the frame's bytes.
+    fn frame(&mut self, frame: u64) -> Result<&[u8], AnalysisError> {
+        let hunk_size = self.chd.header().hunk_size() as u64;
+        let byte_offset = frame * CHD_FRAME_SIZE;
+        let hunk_num = (byte_offset / hunk_size) as u32;
+        if self.loaded_hunk != Some(hunk_num) {
+            let mut hunk = self.chd.hunk(hunk_num).map_err(|e| {
+                AnalysisError::other(format!("Failed to get CHD hunk {}: {}", hunk_num, e))
+            })?;
+            hunk.read_hunk_in(&mut self.cmp_buf, &mut self.hunk_buf)
+                .map_err(|e| {
+                    AnalysisError::other(format!(
+                        "Failed to decompress CHD hunk {}: {}",
+                        hunk_num, e
+                    ))
+                })?;
+            self.loaded_hunk = Some(hunk_num);
+        }
+        let start = (byte_offset % hunk_size) as usize;
+        self.hunk_buf
+            .get(start..start + CHD_FRAME_SIZE as usize)
+            .ok_or_else(|| AnalysisError::corrupted_header("CHD frame extends beyond hunk"))
+    }
+
+    /// Read the user data of sector `index` of track `number`, counted from
+    /// the track's first sector after any stored pregap.
+    pub fn read_sector(
+        &mut self,
+        number: u32,
+        index: u64,
+    ) -> Result<[u8; USER_DATA_SIZE], AnalysisError> {
+        let track = self
+            .track(number)
+            .ok_or_else(|| AnalysisError::invalid_format(format!("CHD has no track {}", number)))?
+            .clone();
+        if !track.is_data() {
+            return Err(AnalysisError::invalid_format(format!(
+                "CHD track {} is an audio track",
+                number
+            )));
+        }
+        let frame = self.frame(track.chd_offset + track.stored_pregap + index)?;
+        let at = track.data_offset() as usize;
+        let mut buf = [0u8; USER_DATA_SIZE];
+        buf.copy_from_slice(&frame[at..at + USER_DATA_SIZE]);
+        Ok(buf)
+    }
+
+    /// Hash track `number` as Redump lists it: the bytes of its BIN file.
+    pub fn hash_track(
+        &mut self,
+        number: u32,
+        algorithms: HashAlgorithms,
+    ) -> Result<FileHashes, AnalysisError> {
+        let track = self
+            .track(number)
+            .ok_or_else(|| AnalysisError::invalid_format(format!("CHD has no track {}", number)))?
+            .clone();
+        let sector_size = track.sector_size() as usize;
+        log::info!(
+            "CHD hashing: track {} ({}), {} frames",
+            track.number,
+            track.kind,
+            track.frames
+        );
+
+        let mut hasher = StreamHasher::new(algorithms);
+        for frame in track.chd_offset..track.chd_offset + track.frames {
+            hasher.update(&self.frame(frame)?[..sector_size]);
+        }
+        Ok(hasher.finish())
+    }
+
+    /// Hash every track, in track order.
+    pub fn hash_tracks(
+        &mut self,
+        algorithms: HashAlgorithms,
+    ) -> Result<Vec<FileHashes>, AnalysisError> {
+        let numbers: Vec<u32> = self.tracks.iter().map(|t| t.number).collect();
+        numbers
+            .into_iter()
+            .map(|number| self.hash_track(number, algorithms))
+            .collect()
+    }
+}
+
+/// A track listed in a GDI sheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GdiTrack {
+    pub number: u32,
+    /// First sector of the track on the disc.
+    pub lba: u64,
+    /// Control field: 4 for data tracks, 0 for audio.
+    pub control: u32,
+    /// 2352 for raw sectors, 2048 for user data only.
+    pub sector_size: u64,
+    /// Track file, relative to the sheet.
+    pub file: String,
+}
+
+impl GdiTrack {
+    pub fn is_data(&self) -> bool {
+        self.control == 4
+    }
+}
+
+/// Parse a GDI sheet: a track count, then one line per track of
+/// `number lba control sector_size file offset`, where the file name may be
+/// quoted.
+pub(crate) fn parse_gdi(text: &str) -> Result<Vec<GdiTrack>, AnalysisError> {
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    let count: usize = lines.next().and_then(|l| l.parse().ok()).ok_or_else(|| {
+        AnalysisError::invalid_format("GDI sheet doesn't start with a track count")
+    })?;
+    let tracks = lines
+        .map(|line| {
+            parse_gdi_track(line).ok_or_else(|| {
+                AnalysisError::invalid_format(format!("Invalid GDI track line: {}", line))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if tracks.len() != count {
+        return Err(AnalysisError::invalid_format(format!(
+            "GDI sheet lists {} tracks but declares {}",
+            tracks.len(),
+            count
+        )));
+    }
+    Ok(tracks)
+}
+
+fn parse_gdi_track(line: &str) -> Option<GdiTrack> {
+    let mut fields = line.split_whitespace();
+    let number = fields.next()?.parse().ok()?;
+    let lba = fields.next()?.parse().ok()?;
+    let control = fields.next()?.parse().ok()?;
+    let sector_size = fields.next()?.parse().ok()?;
+    let rest = fields.collect::<Vec<_>>().join(" ");
+    let file = match rest.strip_prefix('"') {
+        Some(quoted) => quoted.split_once('"')?.0.to_string(),
+        None => rest.split_whitespace().next()?.to_string(),
+    };
+    Some(GdiTrack {
+        number,
+        lba,
+        control,
+        sector_size,
+        file,
+    })
+}
+
+/// A GDI sheet's first line is its track count, and track 1 comes next.
+fn looks_like_gdi(text: &str) -> bool {
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    lines.next().is_some_and(|l| l.parse::<u32>().is_ok())
+        && lines
+            .next()
+            .is_some_and(|l| l.split_whitespace().next() == Some("1"))
+}
+
+/// A GDI sheet and the folder its track files live in.
+pub(crate) struct GdiDisc {
+    dir: PathBuf,
+    tracks: Vec<GdiTrack>,
+}
+
+impl GdiDisc {
+    /// Read the sheet from `reader`. Track files are resolved relative to
+    /// `sheet_path`, without which they can't be found.
+    pub fn open(
+        reader: &mut dyn ReadSeek,
+        sheet_path: Option<&Path>,
+        limits: &ParseLimits,
+    ) -> Result<Self, AnalysisError> {
+        let sheet_path = sheet_path.ok_or_else(|| {
+            AnalysisError::other("GDI sheet path is needed to find its track files")
+        })?;
+        reader.seek(SeekFrom::Start(0))?;
+        let text = limits.read_text(reader, "GDI sheet")?;
+        Ok(Self {
+            dir: sheet_path.parent().unwrap_or(Path::new("")).to_path_buf(),
+            tracks: parse_gdi(&text)?,
+        })
+    }
+
+    pub fn tracks(&self) -> &[GdiTrack] {
+        &self.tracks
+    }
+
+    fn track(&self, number: u32) -> Result<&GdiTrack, AnalysisError> {
+        self.tracks
+            .iter()
+            .find(|t| t.number == number)
+            .ok_or_else(|| AnalysisError::invalid_format(format!("GDI has no track {}", number)))
+    }
+
+    fn open_track(&self, track: &GdiTrack) -> Result<std::fs::File, AnalysisError> {
+        std::fs::File::open(self.dir.join(&track.file)).map_err(|e| {
+            AnalysisError::other(format!("Failed to open GDI track {}: {}", track.file, e))
+        })
+    }
+
+    /// Read the user data of sector `index` of track `number`.
+    pub fn read_sector(
+        &self,
+        number: u32,
+        index: u64,
+    ) -> Result<[u8; USER_DATA_SIZE], AnalysisError> {
+        let track = self.track(number)?;
+        if !track.is_data() {
+            return Err(AnalysisError::invalid_format(format!(
+                "GDI track {} is an audio track",
+                number
+            )));
+        }
+        let format = match track.sector_size {
+            RAW_SECTOR_SIZE => DiscFormat::RawBin,
+            2048 => DiscFormat::Iso,
+            size => {
+                return Err(AnalysisError::invalid_format(format!(
+                    "GDI track {} has unsupported {}-byte sectors",
+                    number, size
+                )));
+            }
+        };
+        read_image_sector(&mut self.open_track(track)?, format, index)
+    }
+
+    /// Hash the file of track `number`.
+    pub fn hash_track(
+        &self,
+        number: u32,
+        algorithms: HashAlgorithms,
+    ) -> Result<FileHashes, AnalysisError> {
+        let mut file = self.open_track(self.track(number)?)?;
+        Ok(hash_reader(&mut file, algorithms)?)
+    }
+
+    /// Hash every track file, in sheet order.
+    pub fn hash_tracks(
+        &self,
+        algorithms: HashAlgorithms,
+    ) -> Result<Vec<FileHashes>, AnalysisError> {
+        self.tracks
+            .iter()
+            .map(|t| self.hash_track(t.number, algorithms))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/sega_disc_tests.rs"]
+mod tests;
@@ -3,11 +3,21 @@
 //! Supports:
 //! - SG-1000 ROMs (.sg)
 //! - SC-3000 software
+//! - VGM music rips (.vgm, .vgz)
+//!
+//! SG-1000 dumps have no header, so they're told apart from Master System
+//! dumps by their size and the hardware their code targets (see
+//! [`sms_header::classify`]).
 
 use retro_junk_core::ReadSeek;
 
 use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
 
+use crate::sms_header::{self, CartFamily};
+
+/// `DI`, the first instruction of practically every Z80 cartridge.
+const Z80_DI: u8 = 0xF3;
+
 /// Analyzer for Sega SG-1000 ROMs.
 #[derive(Debug, Default)]
 pub struct Sg1000Analyzer;
@@ -15,12 +25,37 @@ pub struct Sg1000Analyzer;
 impl RomAnalyzer for Sg1000Analyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
+        reader: &mut dyn ReadSeek,
         _options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "SG-1000 ROM analysis not yet implemented",
-        ))
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        if file_size == 0 {
+            return Err(AnalysisError::too_small(1, 0));
+        }
+
+        match sms_header::classify(reader)? {
+            Some(CartFamily::MasterSystem) => {
+                return Err(AnalysisError::invalid_format(
+                    "Looks like a Master System ROM (TMR SEGA header or Master System code)",
+                ));
+            }
+            Some(CartFamily::GameGear) => {
+                return Err(AnalysisError::invalid_format(
+                    "TMR SEGA header has a Game Gear region code",
+                ));
+            }
+            Some(CartFamily::Sg1000) | None => {}
+        }
+
+        let mut id = RomIdentification::new().with_platform(Platform::Sg1000);
+        id.file_size = Some(file_size);
+        if !sms_header::is_sg1000_size(file_size) {
+            id.extra.insert(
+                "size_profile".into(),
+                "Not an SG-1000 cartridge size".into(),
+            );
+        }
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -31,8 +66,27 @@ impl RomAnalyzer for Sg1000Analyzer {
         &["sg", "sc"]
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        let result = (|| -> std::io::Result<bool> {
+            let mut first = [0u8; 1];
+            reader.read_exact(&mut first)?;
+            Ok(first[0] == Z80_DI && sms_header::classify(reader)? == Some(CartFamily::Sg1000))
+        })();
+        // Always rewind on failure too
+        let _ = reader.seek(std::io::SeekFrom::Start(0));
+        result.unwrap_or(false)
+    }
+
+    fn music_extensions(&self) -> &'static [&'static str] {
+        crate::music::EXTENSIONS
+    }
+
+    fn analyze_music(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        crate::music::analyze(reader, options, Platform::Sg1000)
     }
 
     fn dat_names(&self) -> &'static [&'static str] {
@@ -43,3 +97,7 @@ impl RomAnalyzer for Sg1000Analyzer {
         &["console_sega_sg1000_sc3000_othellomultivision"]
     }
 }
+
+#[cfg(test)]
+#[path = "tests/sg1000_tests.rs"]
+mod tests;
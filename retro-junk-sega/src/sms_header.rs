@@ -0,0 +1,282 @@
+//! The "TMR SEGA" header shared by Master System and Game Gear cartridges,
+//! and the heuristics that tell them apart from SG-1000 dumps.
+//!
+//! The three systems run the same Z80 code on related hardware and their
+//! dumps have no magic at offset 0. Export Master System and all Game Gear
+//! BIOSes check for a 16-byte header ending the first 8, 16 or 32 KB;
+//! Japanese Master System games may omit it and SG-1000 games never have
+//! it. Without the header, the cartridge size and where the game puts its
+//! stack (1 KB of RAM on the SG-1000, 8 KB on the Master System) decide.
+
+use std::io::{Read, SeekFrom};
+
+use retro_junk_core::util::format_bytes;
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, ChecksumAlgorithm, ExpectedChecksum, ParseLimits, ReadSeek,
+    Region, RomIdentification,
+};
+
+/// Magic at the start of the header.
+pub(crate) const TMR_SEGA: &[u8; 8] = b"TMR SEGA";
+
+/// Where the header may sit, in the order the BIOS looks.
+pub(crate) const HEADER_OFFSETS: [u64; 3] = [0x7FF0, 0x3FF0, 0x1FF0];
+
+/// Size of the header.
+const HEADER_LEN: usize = 16;
+
+/// SG-1000 cartridges hold at most 48 KB, in 8 KB steps.
+const SG1000_MAX_SIZE: u64 = 48 * 1024;
+
+/// Bytes searched for the game's first `LD SP,nn`.
+const STARTUP_SCAN_LEN: usize = 0x100;
+
+/// Bytes searched for writes to the Master System mapper registers.
+const MAPPER_SCAN_LEN: usize = 0x8000;
+
+/// Parsed "TMR SEGA" header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TmrSegaHeader {
+    /// File offset of the header.
+    pub offset: u64,
+    /// Additive checksum (little-endian u16 at +0x0A).
+    pub checksum: u16,
+    /// Product code, decoded from BCD.
+    pub product_code: u32,
+    /// Version (low nibble of +0x0E).
+    pub version: u8,
+    /// Region code (high nibble of +0x0F).
+    pub region_code: u8,
+    /// ROM size code (low nibble of +0x0F).
+    pub rom_size_code: u8,
+}
+
+impl TmrSegaHeader {
+    /// Parse the 16 bytes at `offset`, or `None` without the magic.
+    pub(crate) fn parse(buf: &[u8; HEADER_LEN], offset: u64) -> Option<Self> {
+        if &buf[0..8] != TMR_SEGA {
+            return None;
+        }
+        let product_code = u32::from(buf[0x0E] >> 4) * 10000
+            + u32::from(bcd(buf[0x0D])) * 100
+            + u32::from(bcd(buf[0x0C]));
+        Some(Self {
+            offset,
+            checksum: u16::from_le_bytes([buf[0x0A], buf[0x0B]]),
+            product_code,
+            version: buf[0x0E] & 0x0F,
+            region_code: buf[0x0F] >> 4,
+            rom_size_code: buf[0x0F] & 0x0F,
+        })
+    }
+
+    /// Whether the region code is one of the Game Gear codes.
+    pub fn is_game_gear(&self) -> bool {
+        matches!(self.region_code, 5..=7)
+    }
+
+    /// Name of the region code, e.g. "SMS Export".
+    pub fn region_name(&self) -> &'static str {
+        match self.region_code {
+            3 => "SMS Japan",
+            4 => "SMS Export",
+            5 => "GG Japan",
+            6 => "GG Export",
+            7 => "GG International",
+            _ => "Unknown",
+        }
+    }
+
+    /// Regions the region code allows.
+    pub fn regions(&self) -> Vec<Region> {
+        match self.region_code {
+            3 | 5 => vec![Region::Japan],
+            4 | 6 => vec![Region::Usa, Region::Europe],
+            7 => vec![Region::World],
+            _ => vec![Region::Unknown],
+        }
+    }
+
+    /// Bytes covered by the checksum, from the ROM size code.
+    pub fn rom_size(&self) -> Option<u64> {
+        let kb = match self.rom_size_code {
+            0xA => 8,
+            0xB => 16,
+            0xC => 32,
+            0xD => 48,
+            0xE => 64,
+            0xF => 128,
+            0x0 => 256,
+            0x1 => 512,
+            0x2 => 1024,
+            _ => return None,
+        };
+        Some(kb * 1024)
+    }
+}
+
+fn bcd(b: u8) -> u8 {
+    (b >> 4) * 10 + (b & 0x0F)
+}
+
+/// Find the header at any of the [`HEADER_OFFSETS`].
+pub(crate) fn find_header(reader: &mut dyn ReadSeek) -> std::io::Result<Option<TmrSegaHeader>> {
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    let mut found = None;
+    for offset in HEADER_OFFSETS {
+        if offset + HEADER_LEN as u64 > file_size {
+            continue;
+        }
+        reader.seek(SeekFrom::Start(offset))?;
+        let mut buf = [0u8; HEADER_LEN];
+        reader.read_exact(&mut buf)?;
+        if let Some(header) = TmrSegaHeader::parse(&buf, offset) {
+            found = Some(header);
+            break;
+        }
+    }
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(found)
+}
+
+/// Compute the header checksum: the 16-bit sum of every byte up to the
+/// declared ROM size, skipping the header at 0x7FF0. Stops at the end of
+/// the file.
+pub(crate) fn compute_checksum(
+    reader: &mut dyn ReadSeek,
+    rom_size: u64,
+    limits: &ParseLimits,
+) -> Result<u16, AnalysisError> {
+    let file_size = retro_junk_core::util::file_size(reader)?;
+    let end = rom_size.min(file_size);
+    let len = limits.check_alloc(end, "checksummed ROM")?;
+    reader.seek(SeekFrom::Start(0))?;
+    let mut data = vec![0u8; len];
+    reader.read_exact(&mut data)?;
+
+    let first_bank_end = (rom_size.min(0x8000) - HEADER_LEN as u64).min(end) as usize;
+    let sum = data[..first_bank_end]
+        .iter()
+        .chain(data.get(0x8000..).unwrap_or_default())
+        .fold(0u16, |sum, &b| sum.wrapping_add(u16::from(b)));
+    Ok(sum)
+}
+
+/// Fill in `id` from the header, if the dump has one, and return it.
+///
+/// The declared ROM size is what the checksum covers, which some games set
+/// below their real size. A bigger power-of-two file is taken as the real
+/// size; any other mismatch is left for the size verdict to report.
+pub(crate) fn identify(
+    reader: &mut dyn ReadSeek,
+    options: &AnalysisOptions,
+    id: &mut RomIdentification,
+) -> Result<Option<TmrSegaHeader>, AnalysisError> {
+    let Some(header) = find_header(reader)? else {
+        id.extra.insert("header".into(), "None".into());
+        return Ok(None);
+    };
+
+    id.serial_number = Some(header.product_code.to_string());
+    id.version = Some(header.version.to_string());
+    id.regions = header.regions();
+    id.extra
+        .insert("header_offset".into(), format!("0x{:04X}", header.offset));
+    id.extra
+        .insert("region_code".into(), header.region_name().into());
+
+    if let Some(rom_size) = header.rom_size() {
+        id.extra.insert("rom_size".into(), format_bytes(rom_size));
+        if let Some(file_size) = id.file_size {
+            id.expected_size = Some(if file_size > rom_size && file_size.is_power_of_two() {
+                file_size
+            } else {
+                rom_size
+            });
+        }
+        id.expected_checksums.push(
+            ExpectedChecksum::new(
+                ChecksumAlgorithm::Additive,
+                header.checksum.to_le_bytes().to_vec(),
+            )
+            .with_description("ROM checksum (declared ROM size, excluding header)"),
+        );
+        let computed = compute_checksum(reader, rom_size, &options.limits)?;
+        id.extra.insert(
+            "checksum_status:rom".into(),
+            if computed == header.checksum {
+                "Valid".into()
+            } else {
+                format!(
+                    "Invalid (expected 0x{:04X}, computed 0x{:04X})",
+                    header.checksum, computed
+                )
+            },
+        );
+    }
+    Ok(Some(header))
+}
+
+/// Which of the three systems a cartridge dump belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CartFamily {
+    Sg1000,
+    MasterSystem,
+    GameGear,
+}
+
+/// Classify a dump, or `None` when nothing points either way.
+///
+/// The header decides when present. Without it, code that uses Master
+/// System RAM or mapper registers means a (Japanese) Master System game,
+/// and a stack in SG-1000 RAM on a cartridge of an SG-1000 size means an
+/// SG-1000 game.
+pub(crate) fn classify(reader: &mut dyn ReadSeek) -> std::io::Result<Option<CartFamily>> {
+    if let Some(header) = find_header(reader)? {
+        return Ok(Some(if header.is_game_gear() {
+            CartFamily::GameGear
+        } else {
+            CartFamily::MasterSystem
+        }));
+    }
+
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+    let mut head = Vec::with_capacity(MAPPER_SCAN_LEN);
+    (&mut *reader)
+        .take(MAPPER_SCAN_LEN as u64)
+        .read_to_end(&mut head)?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    if writes_mapper(&head) {
+        return Ok(Some(CartFamily::MasterSystem));
+    }
+    Ok(match initial_stack(&head) {
+        Some(sp) if sp > 0xC400 && sp <= 0xE000 => Some(CartFamily::MasterSystem),
+        Some(0xC000..=0xC400) if is_sg1000_size(file_size) => Some(CartFamily::Sg1000),
+        _ => None,
+    })
+}
+
+/// Whether `size` is one of the SG-1000 cartridge sizes.
+pub(crate) fn is_sg1000_size(size: u64) -> bool {
+    size > 0 && size <= SG1000_MAX_SIZE && size.is_multiple_of(8 * 1024)
+}
+
+/// The operand of the first `LD SP,nn` near the start of the code.
+fn initial_stack(code: &[u8]) -> Option<u16> {
+    let scan = &code[..code.len().min(STARTUP_SCAN_LEN)];
+    scan.windows(3)
+        .find(|w| w[0] == 0x31)
+        .map(|w| u16::from_le_bytes([w[1], w[2]]))
+}
+
+/// Whether the code stores to a mapper register (`LD (FFFC..FFFF),A`).
+fn writes_mapper(code: &[u8]) -> bool {
+    code.windows(3)
+        .any(|w| w[0] == 0x32 && w[2] == 0xFF && w[1] >= 0xFC)
+}
+
+#[cfg(test)]
+#[path = "tests/sms_header_tests.rs"]
+mod tests;
@@ -0,0 +1,162 @@
+//! Synthetic ROM images for Sega platforms.
+//!
+//! Each builder produces the smallest image its analyzer fully accepts,
+//! with checksums computed by the same code the analyzer verifies them with.
+
+use std::io::Cursor;
+
+use retro_junk_core::disc::cd::{RAW_SECTOR_SIZE, SYNC_PATTERN};
+use retro_junk_core::{ParseLimits, Platform, SyntheticRom};
+
+use crate::sega_disc::USER_DATA_SIZE;
+
+/// The synthetic image for `platform`, if this crate can build one.
+pub fn synthesize(platform: Platform) -> Option<SyntheticRom> {
+    match platform {
+        Platform::Sg1000 => Some(sg1000()),
+        Platform::MasterSystem => Some(master_system()),
+        Platform::GameGear => Some(game_gear()),
+        Platform::Genesis => Some(genesis()),
+        Platform::SegaCd => Some(sega_cd()),
+        Platform::Saturn => Some(saturn()),
+        Platform::Dreamcast => Some(dreamcast()),
+        _ => None,
+    }
+}
+
+/// Write `value` into `buf` at `offset`, padded with spaces to `len`.
+fn write_field(buf: &mut [u8], offset: usize, len: usize, value: &str) {
+    let field = &mut buf[offset..offset + len];
+    field.fill(b' ');
+    field[..value.len()].copy_from_slice(value.as_bytes());
+}
+
+/// Wrap user data in a raw Mode 1 sector.
+pub(crate) fn raw_sector(data: &[u8]) -> Vec<u8> {
+    let mut sector = vec![0u8; RAW_SECTOR_SIZE as usize];
+    sector[..12].copy_from_slice(&SYNC_PATTERN);
+    sector[15] = 1;
+    sector[16..16 + data.len()].copy_from_slice(data);
+    sector
+}
+
+/// 8 KB headerless cartridge that sets its stack in SG-1000 RAM.
+pub fn sg1000() -> SyntheticRom {
+    let mut rom = vec![0u8; 8 * 1024];
+    rom[..4].copy_from_slice(&[0xF3, 0x31, 0x00, 0xC4]); // DI; LD SP,$C400
+    SyntheticRom::new(Platform::Sg1000, "synthetic.sg", rom)
+}
+
+/// 32 KB cartridge with an export "TMR SEGA" header and a correct checksum.
+pub fn master_system() -> SyntheticRom {
+    // 7001, v0, SMS export, 32 KB
+    SyntheticRom::new(
+        Platform::MasterSystem,
+        "synthetic.sms",
+        tmr_sega_rom(&[0x01, 0x70, 0x00, 0x4C]),
+    )
+}
+
+/// 32 KB cartridge with an international Game Gear header.
+pub fn game_gear() -> SyntheticRom {
+    // 2301, v1, GG international, 32 KB
+    SyntheticRom::new(
+        Platform::GameGear,
+        "synthetic.gg",
+        tmr_sega_rom(&[0x01, 0x23, 0x01, 0x7C]),
+    )
+}
+
+/// 32 KB of Z80 code ending in a "TMR SEGA" header whose last four bytes
+/// are `code_version_region_size`, with a correct checksum.
+fn tmr_sega_rom(code_version_region_size: &[u8; 4]) -> Vec<u8> {
+    let mut rom = vec![0u8; 32 * 1024];
+    rom[..4].copy_from_slice(&[0xF3, 0x31, 0xF0, 0xDF]); // DI; LD SP,$DFF0
+    let header = &mut rom[0x7FF0..];
+    header[..8].copy_from_slice(crate::sms_header::TMR_SEGA);
+    header[0x0C..0x10].copy_from_slice(code_version_region_size);
+    let checksum = crate::sms_header::compute_checksum(
+        &mut Cursor::new(&rom),
+        32 * 1024,
+        &ParseLimits::default(),
+    )
+    .expect("in-memory ROM");
+    rom[0x7FFA..0x7FFC].copy_from_slice(&checksum.to_le_bytes());
+    rom
+}
+
+/// 1 KB cartridge with a full header and a correct checksum.
+pub fn genesis() -> SyntheticRom {
+    let mut rom = vec![0u8; 0x400];
+    rom[0x00..0x04].copy_from_slice(&0x00FF_FFFEu32.to_be_bytes()); // initial SP
+    rom[0x04..0x08].copy_from_slice(&0x0000_0200u32.to_be_bytes()); // initial PC
+    write_field(&mut rom, 0x100, 16, "SEGA MEGA DRIVE");
+    write_field(&mut rom, 0x110, 16, "(C)SEGA 1991.JAN");
+    write_field(&mut rom, 0x120, 48, "SYNTHETIC");
+    write_field(&mut rom, 0x150, 48, "SYNTHETIC");
+    write_field(&mut rom, 0x180, 14, "GM 00000000-00");
+    write_field(&mut rom, 0x190, 16, "J");
+    rom[0x1A4..0x1A8].copy_from_slice(&0x0000_03FFu32.to_be_bytes()); // ROM end
+    rom[0x1A8..0x1AC].copy_from_slice(&0x00FF_0000u32.to_be_bytes()); // RAM start
+    rom[0x1AC..0x1B0].copy_from_slice(&0x00FF_FFFFu32.to_be_bytes()); // RAM end
+    write_field(&mut rom, 0x1F0, 3, "JUE");
+    for (i, b) in rom[0x200..].iter_mut().enumerate() {
+        *b = i as u8;
+    }
+
+    let checksum =
+        crate::genesis::compute_checksum(&mut Cursor::new(&rom), 0x3FF, &ParseLimits::default())
+            .expect("in-memory ROM");
+    rom[0x18E..0x190].copy_from_slice(&checksum.to_be_bytes());
+    SyntheticRom::new(Platform::Genesis, "synthetic.md", rom)
+}
+
+/// Raw BIN of four sectors starting with a Sega CD boot sector.
+pub fn sega_cd() -> SyntheticRom {
+    let mut data = [0u8; USER_DATA_SIZE];
+    write_field(&mut data, 0x000, 16, "SEGADISCSYSTEM");
+    write_field(&mut data, 0x100, 16, "SEGA MEGA DRIVE");
+    write_field(&mut data, 0x110, 16, "(C)SEGA 1993.OCT");
+    write_field(&mut data, 0x120, 48, "SYNTHETIC");
+    write_field(&mut data, 0x150, 48, "SYNTHETIC");
+    write_field(&mut data, 0x180, 14, "GM 00000000-00");
+    write_field(&mut data, 0x1F0, 3, "JUE");
+    SyntheticRom::new(
+        Platform::SegaCd,
+        "synthetic.bin",
+        raw_sector(&data).repeat(4),
+    )
+}
+
+/// Cooked ISO of 16 sectors whose first sector is a Saturn system ID.
+pub fn saturn() -> SyntheticRom {
+    let mut iso = vec![0u8; USER_DATA_SIZE * 16];
+    write_field(&mut iso, 0x00, 16, "SEGA SEGASATURN ");
+    write_field(&mut iso, 0x10, 16, "SEGA ENTERPRISES");
+    write_field(&mut iso, 0x20, 10, "T-00000");
+    write_field(&mut iso, 0x2A, 6, "V1.000");
+    write_field(&mut iso, 0x30, 8, "19940101");
+    write_field(&mut iso, 0x38, 8, "CD-1/1");
+    write_field(&mut iso, 0x40, 16, "JTUE");
+    write_field(&mut iso, 0x60, 112, "SYNTHETIC");
+    SyntheticRom::new(Platform::Saturn, "synthetic.iso", iso)
+}
+
+/// GDI sheet with a single-sector audio track and an IP.BIN data track.
+pub fn dreamcast() -> SyntheticRom {
+    let mut ip_bin = [0u8; USER_DATA_SIZE];
+    write_field(&mut ip_bin, 0x00, 16, "SEGA SEGAKATANA ");
+    write_field(&mut ip_bin, 0x10, 16, "SEGA ENTERPRISES");
+    write_field(&mut ip_bin, 0x20, 16, "0000 GD-ROM1/1");
+    write_field(&mut ip_bin, 0x30, 8, "JUE");
+    write_field(&mut ip_bin, 0x40, 10, "T-00000");
+    write_field(&mut ip_bin, 0x4A, 6, "V1.000");
+    write_field(&mut ip_bin, 0x50, 16, "19990101");
+    write_field(&mut ip_bin, 0x60, 16, "1ST_READ.BIN");
+    write_field(&mut ip_bin, 0x80, 128, "SYNTHETIC");
+
+    let sheet = "2\n1 0 4 2352 track01.bin 0\n3 45000 4 2352 track03.bin 0\n";
+    SyntheticRom::new(Platform::Dreamcast, "synthetic.gdi", sheet.into())
+        .with_companion("track01.bin", vec![0u8; RAW_SECTOR_SIZE as usize])
+        .with_companion("track03.bin", raw_sector(&ip_bin))
+}
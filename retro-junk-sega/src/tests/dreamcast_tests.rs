@@ -0,0 +1,89 @@
+use super::*;
+use std::io::Cursor;
+
+/// Build a boot sector holding IP.BIN.
+fn make_ip_bin() -> [u8; USER_DATA_SIZE] {
+    let mut sector = [0u8; USER_DATA_SIZE];
+    let mut field = |offset: usize, len: usize, value: &str| {
+        sector[offset..offset + len].fill(b' ');
+        sector[offset..offset + value.len()].copy_from_slice(value.as_bytes());
+    };
+    field(0x00, 16, "SEGA SEGAKATANA ");
+    field(0x10, 16, "SEGA ENTERPRISES");
+    field(0x20, 16, "5E1D GD-ROM1/2");
+    field(0x30, 8, "JUE");
+    field(0x40, 10, "MK-51011");
+    field(0x4A, 6, "V1.002");
+    field(0x50, 16, "19991227");
+    field(0x60, 16, "1ST_READ.BIN");
+    field(0x80, 128, "SHENMUE");
+    sector
+}
+
+#[test]
+fn test_identify_ip_bin() {
+    let id = identify(&make_ip_bin()).unwrap();
+    assert_eq!(id.platform, Some(Platform::Dreamcast));
+    assert_eq!(id.serial_number.as_deref(), Some("MK-51011"));
+    assert_eq!(id.internal_name.as_deref(), Some("SHENMUE"));
+    assert_eq!(id.version.as_deref(), Some("V1.002"));
+    assert_eq!(id.regions, vec![Region::Japan, Region::Usa, Region::Europe]);
+    assert_eq!(id.build_date.unwrap().to_string(), "1999-12-27");
+    assert_eq!(id.extra.get("disc").map(String::as_str), Some("1 of 2"));
+}
+
+#[test]
+fn test_identify_rejects_other_discs() {
+    let mut sector = make_ip_bin();
+    sector[..16].copy_from_slice(b"SEGA SEGASATURN ");
+    assert!(identify(&sector).is_err());
+}
+
+#[test]
+fn test_non_chd_images_are_not_claimed() {
+    let mut data = make_ip_bin().to_vec();
+    data.resize(USER_DATA_SIZE * 4, 0);
+    assert!(!DreamcastAnalyzer.can_handle(&mut Cursor::new(data.clone())));
+    assert!(
+        DreamcastAnalyzer
+            .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_analyze_gdi() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut track03 = vec![0u8; 16];
+    track03[..12].copy_from_slice(&[
+        0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00,
+    ]);
+    track03.extend_from_slice(&make_ip_bin());
+    track03.resize(2352, 0);
+    std::fs::write(dir.path().join("track01.bin"), vec![0u8; 2352]).unwrap();
+    std::fs::write(dir.path().join("track03.bin"), &track03).unwrap();
+    let sheet_path = dir.path().join("Shenmue.gdi");
+    std::fs::write(
+        &sheet_path,
+        "2\n1 0 4 2352 track01.bin 0\n3 45000 4 2352 track03.bin 0\n",
+    )
+    .unwrap();
+
+    let options = AnalysisOptions::new().file_path(&sheet_path);
+    let mut reader = std::fs::File::open(&sheet_path).unwrap();
+    let id = DreamcastAnalyzer.analyze(&mut reader, &options).unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("MK-51011"));
+    assert_eq!(id.extra.get("format").map(String::as_str), Some("GDI"));
+    assert_eq!(id.extra.get("tracks").map(String::as_str), Some("2"));
+
+    let hashes = DreamcastAnalyzer
+        .compute_container_hashes(&mut reader, HashAlgorithms::Crc32, Some(&sheet_path))
+        .unwrap()
+        .unwrap();
+    assert_eq!(hashes.crc32, format!("{:08x}", crc32fast::hash(&track03)));
+    let tracks = DreamcastAnalyzer
+        .compute_track_hashes(&mut reader, HashAlgorithms::Crc32, Some(&sheet_path))
+        .unwrap();
+    assert_eq!(tracks.len(), 2);
+    assert_eq!(tracks[1].crc32, hashes.crc32);
+}
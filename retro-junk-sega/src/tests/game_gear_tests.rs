@@ -0,0 +1,90 @@
+use super::*;
+use std::io::Cursor;
+
+use retro_junk_core::Region;
+
+use crate::synthetic;
+
+fn analyze(rom: Vec<u8>) -> RomIdentification {
+    GameGearAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap()
+}
+
+/// Set the region/size byte and recompute the checksum.
+fn with_region_and_size(mut rom: Vec<u8>, region_and_size: u8) -> Vec<u8> {
+    rom[0x7FFF] = region_and_size;
+    let size = rom.len() as u64;
+    let checksum = sms_header::compute_checksum(
+        &mut Cursor::new(&rom),
+        size,
+        &retro_junk_core::ParseLimits::default(),
+    )
+    .unwrap();
+    rom[0x7FFA..0x7FFC].copy_from_slice(&checksum.to_le_bytes());
+    rom
+}
+
+#[test]
+fn test_analyze_international_rom() {
+    let rom = synthetic::game_gear().data;
+    assert!(GameGearAnalyzer.can_handle(&mut Cursor::new(rom.clone())));
+
+    let result = analyze(rom);
+    assert_eq!(result.serial_number.as_deref(), Some("2301"));
+    assert_eq!(result.version.as_deref(), Some("1"));
+    assert_eq!(result.regions, [Region::World]);
+    assert_eq!(result.extra["region_code"], "GG International");
+    assert_eq!(result.extra["rom_size"], "32 KB");
+    assert_eq!(result.expected_size, Some(32 * 1024));
+    assert_eq!(result.extra["checksum_status:rom"], "Valid");
+    assert!(!result.extra.contains_key("mode"));
+}
+
+#[test]
+fn test_region_codes() {
+    let rom = synthetic::game_gear().data;
+    let result = analyze(with_region_and_size(rom.clone(), 0x5C));
+    assert_eq!(result.extra["region_code"], "GG Japan");
+    assert_eq!(result.regions, [Region::Japan]);
+
+    let result = analyze(with_region_and_size(rom.clone(), 0x6C));
+    assert_eq!(result.extra["region_code"], "GG Export");
+    assert_eq!(result.regions, [Region::Usa, Region::Europe]);
+
+    // Master System codes on a .gg cartridge: Master System mode
+    let rom = with_region_and_size(rom, 0x4C);
+    assert!(!GameGearAnalyzer.can_handle(&mut Cursor::new(rom.clone())));
+    assert_eq!(analyze(rom).extra["mode"], "Master System");
+}
+
+#[test]
+fn test_declared_size_against_file_size() {
+    let rom = synthetic::game_gear().data;
+
+    // Declared 64 KB, file 32 KB: truncated
+    let result = analyze(with_region_and_size(rom.clone(), 0x7E));
+    assert_eq!(result.extra["rom_size"], "64 KB");
+    assert_eq!(result.expected_size, Some(64 * 1024));
+
+    // Declared 32 KB, file 64 KB: the checksum covers the first half only
+    let mut bigger = rom.clone();
+    bigger.resize(64 * 1024, 0xFF);
+    assert_eq!(analyze(bigger).expected_size, Some(64 * 1024));
+
+    // Declared 32 KB, file 40 KB: oversized
+    let mut odd = rom;
+    odd.resize(40 * 1024, 0xFF);
+    assert_eq!(analyze(odd).expected_size, Some(32 * 1024));
+}
+
+#[test]
+fn test_master_system_analyzer_rejects_game_gear_rom() {
+    let rom = synthetic::game_gear().data;
+    assert!(!crate::MasterSystemAnalyzer.can_handle(&mut Cursor::new(rom.clone())));
+    assert!(
+        crate::MasterSystemAnalyzer
+            .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+            .is_err()
+    );
+}
@@ -253,3 +253,272 @@ fn test_extract_dat_game_code_no_prefix() {
     // No type prefix — should return None
     assert_eq!(analyzer.extract_dat_game_code("MK-1058-00"), None);
 }
+
+#[test]
+fn test_build_date_from_copyright() {
+    let rom = make_genesis_rom("SEGA GENESIS", "GAME", "GAME", "GM 00001009-00", "U");
+    let result = GenesisAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.build_date, BuildDate::new(1991, Some(1), None));
+
+    assert_eq!(
+        parse_copyright_date("(C)T-12 1993 JUL"),
+        BuildDate::new(1993, Some(7), None)
+    );
+    assert_eq!(
+        parse_copyright_date("(C)SEGA1994"),
+        BuildDate::new(1994, None, None)
+    );
+    assert_eq!(parse_copyright_date("(C)SEGA"), None);
+}
+
+#[test]
+fn test_dat_header_size_detects_copier_header() {
+    let rom = make_genesis_rom("SEGA GENESIS", "GAME", "GAME", "GM 00001009-00", "U");
+    let size = rom.len() as u64;
+    assert_eq!(
+        GenesisAnalyzer
+            .dat_header_size(&mut Cursor::new(rom.clone()), size)
+            .unwrap(),
+        0
+    );
+
+    let mut headered = vec![0u8; 512];
+    headered.extend_from_slice(&rom);
+    let size = headered.len() as u64;
+    assert_eq!(
+        GenesisAnalyzer
+            .dat_header_size(&mut Cursor::new(headered), size)
+            .unwrap(),
+        512
+    );
+
+    // 512 extra bytes without the ROM header behind them aren't a copier header
+    let mut padded = rom;
+    padded.extend_from_slice(&[0u8; 512]);
+    let size = padded.len() as u64;
+    assert_eq!(
+        GenesisAnalyzer
+            .dat_header_size(&mut Cursor::new(padded), size)
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_region_decode_bitmask() {
+    assert_eq!(
+        decode_regions("F"),
+        [Region::Japan, Region::Usa, Region::Europe]
+    );
+    assert_eq!(decode_regions("4"), [Region::Usa]);
+    assert_eq!(decode_regions("8  "), [Region::Europe]);
+    assert_eq!(decode_regions("5"), [Region::Japan, Region::Usa]);
+    // Old-style letters that are also hex digits keep their letter meaning
+    assert_eq!(decode_regions("E"), [Region::Europe]);
+    assert_eq!(decode_regions("A"), [Region::Japan]);
+    assert_eq!(decode_regions("0"), [Region::Unknown]);
+}
+
+#[test]
+fn test_io_support_decode() {
+    assert_eq!(
+        decode_io_support("J6M"),
+        ["3-button controller", "6-button controller", "Mouse"]
+    );
+    assert!(decode_io_support("  ").is_empty());
+
+    let rom = make_genesis_rom("SEGA GENESIS", "TEST", "TEST", "GM 00000000-00", "U");
+    let result = GenesisAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.extra["io_support"], "3-button controller");
+}
+
+#[test]
+fn test_save_memory_sram() {
+    let mut rom = make_genesis_rom("SEGA GENESIS", "TEST", "TEST", "GM 00000000-00", "U");
+    rom[0x1B0..0x1B4].copy_from_slice(&[b'R', b'A', 0xF8, 0x20]);
+    rom[0x1B4..0x1B8].copy_from_slice(&0x0020_0001u32.to_be_bytes());
+    rom[0x1B8..0x1BC].copy_from_slice(&0x0020_3FFFu32.to_be_bytes());
+    let result = GenesisAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.extra["save_type"], "SRAM (odd bytes)");
+    assert_eq!(result.extra["sram_size"], "8 KB");
+    assert_eq!(result.extra["battery"], "Yes");
+    assert_eq!(result.extra["save_address_range"], "0x00200001-0x00203FFF");
+}
+
+#[test]
+fn test_save_memory_kinds() {
+    let field = |flags: u8, start: u32, end: u32| {
+        let mut f = vec![b'R', b'A', flags, 0x20];
+        f.extend_from_slice(&start.to_be_bytes());
+        f.extend_from_slice(&end.to_be_bytes());
+        SaveMemory::parse(&f).unwrap()
+    };
+    let eeprom = field(0xE8, 0x0020_0001, 0x0020_0001);
+    assert_eq!(eeprom.kind, SaveMemoryKind::Eeprom);
+    assert_eq!(eeprom.size(), None);
+    assert_eq!(eeprom.description(), "EEPROM");
+
+    let word = field(0xE0, 0x0020_0000, 0x0020_FFFF);
+    assert_eq!(word.kind, SaveMemoryKind::Word);
+    assert_eq!(word.size(), Some(0x10000));
+
+    let volatile = field(0xB0, 0x0020_0000, 0x0020_0FFF);
+    assert_eq!(volatile.kind, SaveMemoryKind::EvenBytes);
+    assert!(!volatile.battery);
+    assert_eq!(volatile.description(), "RAM (even bytes)");
+
+    assert_eq!(SaveMemory::parse(b"            "), None);
+}
+
+#[test]
+fn test_checksum_stops_at_end_of_truncated_file() {
+    let mut rom = make_genesis_rom("SEGA GENESIS", "TEST", "TEST", "GM 00000000-00", "U");
+    rom.truncate(0x300);
+    let result = GenesisAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert!(result.extra["checksum_status:rom"].starts_with("Invalid"));
+    assert_eq!(result.expected_size, Some(0x400));
+}
+
+#[test]
+fn test_checksum_over_whole_rom_when_end_is_short() {
+    let mut rom = make_genesis_rom("SEGA GENESIS", "TEST", "TEST", "GM 00000000-00", "U");
+    // Declare a ROM end short of the data the checksum covers
+    rom[0x1A4..0x1A8].copy_from_slice(&0x0000_02FFu32.to_be_bytes());
+    let result = GenesisAnalyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.extra["checksum_status:rom"], "Valid");
+}
+
+/// A 16 KiB plain ROM, as an SMD dump would hold it.
+fn make_block_sized_rom() -> Vec<u8> {
+    let mut rom = make_genesis_rom(
+        "SEGA GENESIS",
+        "SMD GAME",
+        "SMD GAME",
+        "GM 00000000-00",
+        "U",
+    );
+    rom.resize(SMD_BLOCK_SIZE, 0);
+    rom
+}
+
+fn to_smd(plain: &[u8]) -> Vec<u8> {
+    let mut smd = vec![0u8; 512];
+    smd[0] = (plain.len() / SMD_BLOCK_SIZE) as u8;
+    smd[8] = 0xAA;
+    smd[9] = 0xBB;
+    let half = SMD_BLOCK_SIZE / 2;
+    for chunk in plain.chunks(SMD_BLOCK_SIZE) {
+        let mut block = vec![0u8; SMD_BLOCK_SIZE];
+        for i in 0..half {
+            block[i] = chunk[i * 2 + 1];
+            block[half + i] = chunk[i * 2];
+        }
+        smd.extend_from_slice(&block);
+    }
+    smd
+}
+
+#[test]
+fn test_smd_is_deinterleaved_for_analysis() {
+    let plain = make_block_sized_rom();
+    let smd = to_smd(&plain);
+    assert!(GenesisAnalyzer.can_handle(&mut Cursor::new(smd.clone())));
+
+    let result = GenesisAnalyzer
+        .analyze(&mut Cursor::new(smd.clone()), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.extra["format"], "SMD (interleaved)");
+    assert_eq!(result.extra["copier_header"], "Yes");
+    assert_eq!(result.internal_name.as_deref(), Some("SMD GAME"));
+    assert_eq!(result.extra["checksum_status:rom"], "Valid");
+    assert_eq!(result.file_size, Some(smd.len() as u64));
+    assert_eq!(result.expected_size, Some(smd.len() as u64));
+}
+
+#[test]
+fn test_smd_normalizes_for_hashing() {
+    let plain = make_block_sized_rom();
+    let smd = to_smd(&plain);
+    let size = smd.len() as u64;
+    let mut reader = Cursor::new(smd.clone());
+    let skip = GenesisAnalyzer.dat_header_size(&mut reader, size).unwrap();
+    assert_eq!(skip, 512);
+
+    let mut normalizer = GenesisAnalyzer
+        .dat_chunk_normalizer(&mut reader, skip)
+        .unwrap()
+        .expect("SMD needs de-interleaving");
+    let mut data = smd[512..].to_vec();
+    normalizer(&mut data);
+    assert_eq!(data, plain);
+
+    assert!(
+        GenesisAnalyzer
+            .dat_chunk_normalizer(&mut Cursor::new(plain), 0)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_byte_swapped_rom() {
+    let plain = make_genesis_rom("SEGA GENESIS", "SWAPPED", "SWAPPED", "GM 00000000-00", "U");
+    let mut swapped = plain.clone();
+    for pair in swapped.chunks_exact_mut(2) {
+        pair.swap(0, 1);
+    }
+
+    let result = GenesisAnalyzer
+        .analyze(
+            &mut Cursor::new(swapped.clone()),
+            &AnalysisOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(result.extra["format"], "Byte-swapped");
+    assert_eq!(result.internal_name.as_deref(), Some("SWAPPED"));
+    assert_eq!(result.extra["checksum_status:rom"], "Valid");
+    assert!(!result.extra.contains_key("copier_header"));
+
+    let mut normalizer = GenesisAnalyzer
+        .dat_chunk_normalizer(&mut Cursor::new(swapped.clone()), 0)
+        .unwrap()
+        .unwrap();
+    normalizer(&mut swapped);
+    assert_eq!(swapped, plain);
+}
+
+#[test]
+fn test_headered_plain_rom() {
+    let plain = make_genesis_rom(
+        "SEGA GENESIS",
+        "HEADERED",
+        "HEADERED",
+        "GM 00000000-00",
+        "U",
+    );
+    let mut headered = vec![0u8; 512];
+    headered.extend_from_slice(&plain);
+
+    let result = GenesisAnalyzer
+        .analyze(&mut Cursor::new(headered), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.extra["format"], "Plain (copier header)");
+    assert_eq!(result.extra["copier_header"], "Yes");
+    assert_eq!(result.internal_name.as_deref(), Some("HEADERED"));
+    assert_eq!(result.expected_size, Some(plain.len() as u64 + 512));
+
+    let result = GenesisAnalyzer
+        .analyze(&mut Cursor::new(plain), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.extra["format"], "Plain");
+}
@@ -0,0 +1,66 @@
+use super::*;
+use crate::{GameGearAnalyzer, GenesisAnalyzer, MasterSystemAnalyzer, Sg1000Analyzer};
+use retro_junk_core::RomAnalyzer;
+use retro_junk_music::synthetic;
+use retro_junk_music::vgm::Gd3Tag;
+use std::io::Cursor;
+
+fn tagged(system: &str) -> Gd3Tag {
+    Gd3Tag {
+        game: "Test Game".into(),
+        system: system.into(),
+        ..Gd3Tag::default()
+    }
+}
+
+fn platform_of(chips: &[VgmChip], system: &str) -> Option<Platform> {
+    let data = synthetic::vgm(chips, Some(&tagged(system)));
+    vgm_platform(&Vgm::parse(&data).unwrap())
+}
+
+#[test]
+fn test_vgm_platform_from_chips_and_tag() {
+    let psg = &[VgmChip::Sn76489];
+    assert_eq!(
+        platform_of(&[VgmChip::Sn76489, VgmChip::Ym2612], "Sega Mega Drive"),
+        Some(Platform::Genesis)
+    );
+    assert_eq!(
+        platform_of(psg, "Sega Master System"),
+        Some(Platform::MasterSystem)
+    );
+    assert_eq!(platform_of(psg, ""), Some(Platform::MasterSystem));
+    assert_eq!(platform_of(psg, "Sega Game Gear"), Some(Platform::GameGear));
+    assert_eq!(platform_of(psg, "Sega SG-1000"), Some(Platform::Sg1000));
+    assert_eq!(platform_of(&[VgmChip::NesApu], "NES"), None);
+}
+
+#[test]
+fn test_vgm_analyzed_by_its_console_only() {
+    let data = synthetic::vgm(&[VgmChip::Sn76489], Some(&tagged("Sega Game Gear")));
+    let options = AnalysisOptions::default();
+
+    let id = GameGearAnalyzer
+        .analyze_music(&mut Cursor::new(data.clone()), &options)
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::GameGear));
+    assert_eq!(id.internal_name.as_deref(), Some("Test Game"));
+    assert_eq!(id.extra["format"], "VGM music rip");
+
+    assert!(
+        MasterSystemAnalyzer
+            .analyze_music(&mut Cursor::new(data.clone()), &options)
+            .is_err()
+    );
+    assert!(
+        Sg1000Analyzer
+            .analyze_music(&mut Cursor::new(data.clone()), &options)
+            .is_err()
+    );
+    assert!(
+        GenesisAnalyzer
+            .analyze_music(&mut Cursor::new(data.clone()), &options)
+            .is_err()
+    );
+    assert!(!GameGearAnalyzer.can_handle(&mut Cursor::new(data)));
+}
@@ -0,0 +1,76 @@
+use super::*;
+use std::io::Cursor;
+
+/// Write `value` into `buf` at `offset`, padded with spaces to `len`.
+fn write_field(buf: &mut [u8], offset: usize, len: usize, value: &str) {
+    let field = &mut buf[offset..offset + len];
+    field.fill(b' ');
+    field[..value.len()].copy_from_slice(value.as_bytes());
+}
+
+/// Build a cooked ISO whose first sector is a Saturn system ID.
+fn make_iso(product: &str, device: &str, areas: &str) -> Vec<u8> {
+    let mut iso = vec![0u8; 2048 * 16];
+    write_field(&mut iso, 0x00, 16, "SEGA SEGASATURN ");
+    write_field(&mut iso, 0x10, 16, "SEGA ENTERPRISES");
+    write_field(&mut iso, 0x20, 10, product);
+    write_field(&mut iso, 0x2A, 6, "V1.001");
+    write_field(&mut iso, 0x30, 8, "19941122");
+    write_field(&mut iso, 0x38, 8, device);
+    write_field(&mut iso, 0x40, 16, areas);
+    write_field(&mut iso, 0x60, 112, "VIRTUA FIGHTER");
+    iso
+}
+
+#[test]
+fn test_analyze_iso() {
+    let id = SaturnAnalyzer
+        .analyze(
+            &mut Cursor::new(make_iso("MK-81005", "CD-1/1", "JTUE")),
+            &AnalysisOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::Saturn));
+    assert_eq!(id.serial_number.as_deref(), Some("MK-81005"));
+    assert_eq!(id.internal_name.as_deref(), Some("VIRTUA FIGHTER"));
+    assert_eq!(id.version.as_deref(), Some("V1.001"));
+    assert_eq!(id.maker_code.as_deref(), Some("SEGA ENTERPRISES"));
+    assert_eq!(
+        id.regions,
+        vec![Region::Japan, Region::Taiwan, Region::Usa, Region::Europe]
+    );
+    assert_eq!(id.build_date.unwrap().to_string(), "1994-11-22");
+    assert_eq!(id.extra.get("disc").map(String::as_str), Some("1 of 1"));
+    assert_eq!(
+        id.extra.get("detected_extension").map(String::as_str),
+        Some("iso")
+    );
+}
+
+#[test]
+fn test_can_handle() {
+    assert!(SaturnAnalyzer.can_handle(&mut Cursor::new(make_iso("T-4507G", "CD-1/2", "J"))));
+    assert!(!SaturnAnalyzer.can_handle(&mut Cursor::new(vec![0u8; 2048 * 16])));
+}
+
+#[test]
+fn test_analyze_rejects_other_discs() {
+    let mut iso = make_iso("MK-81005", "CD-1/1", "U");
+    iso[..16].copy_from_slice(b"SEGADISCSYSTEM  ");
+    assert!(
+        SaturnAnalyzer
+            .analyze(&mut Cursor::new(iso), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_disc_of() {
+    assert_eq!(disc_of("CD-2/3"), Some((2, 3)));
+    assert_eq!(disc_of(""), None);
+}
+
+#[test]
+fn test_decode_areas_unknown() {
+    assert_eq!(decode_areas("AL"), vec![Region::Unknown]);
+}
@@ -0,0 +1,60 @@
+use super::*;
+use std::io::Cursor;
+
+/// Build a raw BIN whose first sector is a Sega CD boot sector.
+fn make_bin(serial: &str, regions: &str) -> Vec<u8> {
+    let mut data = [0u8; USER_DATA_SIZE];
+    data[..16].copy_from_slice(b"SEGADISCSYSTEM  ");
+    let mut field = |offset: usize, len: usize, value: &str| {
+        data[offset..offset + len].fill(b' ');
+        data[offset..offset + value.len()].copy_from_slice(value.as_bytes());
+    };
+    field(0x100, 16, "SEGA MEGA DRIVE");
+    field(0x110, 16, "(C)SEGA 1993.OCT");
+    field(0x120, 48, "SONIC THE HEDGEHOG CD");
+    field(0x150, 48, "SONIC CD");
+    field(0x180, 14, serial);
+    field(0x1F0, 3, regions);
+
+    let mut sector = vec![0u8; 2352];
+    sector[..12].copy_from_slice(&[
+        0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00,
+    ]);
+    sector[15] = 1;
+    sector[16..16 + USER_DATA_SIZE].copy_from_slice(&data);
+    sector.repeat(4)
+}
+
+#[test]
+fn test_analyze_raw_bin() {
+    let id = SegaCdAnalyzer
+        .analyze(
+            &mut Cursor::new(make_bin("GM MK-4407 -00", "U")),
+            &AnalysisOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::SegaCd));
+    assert_eq!(id.serial_number.as_deref(), Some("GM MK-4407 -00"));
+    assert_eq!(id.internal_name.as_deref(), Some("SONIC CD"));
+    assert_eq!(id.regions, vec![retro_junk_core::Region::Usa]);
+    assert_eq!(id.build_date.unwrap().to_string(), "1993-10");
+    assert_eq!(
+        id.extra.get("detected_extension").map(String::as_str),
+        Some("bin")
+    );
+}
+
+#[test]
+fn test_can_handle() {
+    assert!(SegaCdAnalyzer.can_handle(&mut Cursor::new(make_bin("GM T-93025-00", "JUE"))));
+    assert!(!SegaCdAnalyzer.can_handle(&mut Cursor::new(vec![0u8; 2352 * 4])));
+}
+
+#[test]
+fn test_extract_dat_game_code() {
+    let code = |s| SegaCdAnalyzer.extract_dat_game_code(s);
+    assert_eq!(code("GM MK-4407 -00").as_deref(), Some("MK-4407"));
+    assert_eq!(code("GM T-93025-00").as_deref(), Some("T-93025"));
+    assert_eq!(code("GM T-6201").as_deref(), Some("T-6201"));
+    assert_eq!(code("GM "), None);
+}
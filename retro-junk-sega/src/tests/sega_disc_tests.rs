@@ -0,0 +1,134 @@
+use super::*;
+use std::io::{Cursor, Write};
+
+use crate::synthetic::raw_sector;
+
+#[test]
+fn test_detect_format() {
+    let mut chd = b"MComprHD".to_vec();
+    chd.resize(64, 0);
+    assert_eq!(
+        detect_format(&mut Cursor::new(chd)).unwrap(),
+        DiscFormat::Chd
+    );
+    assert_eq!(
+        detect_format(&mut Cursor::new(raw_sector(b"SEGA"))).unwrap(),
+        DiscFormat::RawBin
+    );
+    let cue = b"FILE \"Game (Track 1).bin\" BINARY\n  TRACK 01 MODE1/2352\n".to_vec();
+    assert_eq!(
+        detect_format(&mut Cursor::new(cue)).unwrap(),
+        DiscFormat::Cue
+    );
+    let gdi = b"3\r\n1 0 4 2352 track01.bin 0\r\n".to_vec();
+    assert_eq!(
+        detect_format(&mut Cursor::new(gdi)).unwrap(),
+        DiscFormat::Gdi
+    );
+    assert_eq!(
+        detect_format(&mut Cursor::new(vec![0u8; 4096])).unwrap(),
+        DiscFormat::Iso
+    );
+}
+
+#[test]
+fn test_read_boot_sector_follows_cue() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut bin = raw_sector(b"SEGA SEGASATURN ");
+    bin.extend(raw_sector(b""));
+    std::fs::write(dir.path().join("Game (Track 1).bin"), &bin).unwrap();
+    let cue_path = dir.path().join("Game.cue");
+    let mut cue = std::fs::File::create(&cue_path).unwrap();
+    writeln!(
+        cue,
+        "FILE \"Game (Track 1).bin\" BINARY\n  TRACK 01 MODE1/2352"
+    )
+    .unwrap();
+
+    let options = AnalysisOptions::new().file_path(&cue_path);
+    let mut reader = std::fs::File::open(&cue_path).unwrap();
+    let sector = read_boot_sector(&mut reader, DiscFormat::Cue, 1, &options).unwrap();
+    assert!(sector.starts_with(b"SEGA SEGASATURN "));
+
+    // Without the CUE's path there's nowhere to look for the track
+    let mut reader = std::fs::File::open(&cue_path).unwrap();
+    assert!(
+        read_boot_sector(&mut reader, DiscFormat::Cue, 1, &AnalysisOptions::default()).is_err()
+    );
+}
+
+#[test]
+fn test_read_image_sector_raw_and_iso() {
+    let mut raw = raw_sector(b"first");
+    raw.extend(raw_sector(b"second"));
+    let sector = read_image_sector(&mut Cursor::new(raw), DiscFormat::RawBin, 1).unwrap();
+    assert!(sector.starts_with(b"second"));
+
+    let mut iso = vec![0u8; USER_DATA_SIZE * 2];
+    iso[USER_DATA_SIZE..USER_DATA_SIZE + 6].copy_from_slice(b"second");
+    let sector = read_image_sector(&mut Cursor::new(iso), DiscFormat::Iso, 1).unwrap();
+    assert!(sector.starts_with(b"second"));
+}
+
+#[test]
+fn test_open_rejects_invalid_chd() {
+    let mut data = b"MComprHD".to_vec();
+    data.resize(256, 0);
+    assert!(ChdDisc::open(&mut Cursor::new(data)).is_err());
+}
+
+#[test]
+fn test_parse_gdi() {
+    let tracks = parse_gdi(
+        "3\n1 0 4 2352 \"Game (Track 1).bin\" 0\n2 756 0 2352 track02.raw 0\n3 45000 4 2048 track03.iso 0\n",
+    )
+    .unwrap();
+    assert_eq!(tracks.len(), 3);
+    assert_eq!(tracks[0].file, "Game (Track 1).bin");
+    assert!(tracks[0].is_data());
+    assert_eq!(tracks[1].file, "track02.raw");
+    assert!(!tracks[1].is_data());
+    assert_eq!(tracks[2].lba, 45000);
+    assert_eq!(tracks[2].sector_size, 2048);
+
+    assert!(parse_gdi("2\n1 0 4 2352 track01.bin 0\n").is_err());
+    assert!(parse_gdi("1\n1 0 4 track01.bin 0\n").is_err());
+}
+
+#[test]
+fn test_gdi_disc_reads_and_hashes_tracks() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("track01.bin"), raw_sector(b"low density")).unwrap();
+    std::fs::write(dir.path().join("track02.raw"), vec![0u8; 2352]).unwrap();
+    std::fs::write(
+        dir.path().join("track03.bin"),
+        raw_sector(b"SEGA SEGAKATANA "),
+    )
+    .unwrap();
+    let sheet_path = dir.path().join("Game.gdi");
+    let sheet =
+        "3\n1 0 4 2352 track01.bin 0\n2 450 0 2352 track02.raw 0\n3 45000 4 2352 track03.bin 0\n";
+    std::fs::write(&sheet_path, sheet).unwrap();
+
+    let mut reader = Cursor::new(sheet.as_bytes().to_vec());
+    let gdi = GdiDisc::open(&mut reader, Some(&sheet_path), &ParseLimits::default()).unwrap();
+    assert!(
+        gdi.read_sector(3, 0)
+            .unwrap()
+            .starts_with(b"SEGA SEGAKATANA ")
+    );
+    assert!(gdi.read_sector(2, 0).is_err());
+    assert!(gdi.read_sector(4, 0).is_err());
+
+    let hashes = gdi.hash_tracks(HashAlgorithms::Crc32Sha1).unwrap();
+    assert_eq!(hashes.len(), 3);
+    assert_eq!(
+        hashes[1].crc32,
+        format!("{:08x}", crc32fast::hash(&[0u8; 2352]))
+    );
+    assert_eq!(hashes[1].data_size, 2352);
+    assert!(hashes[2].sha1.is_some());
+
+    // Without the sheet's path there's nowhere to look for the tracks
+    assert!(GdiDisc::open(&mut reader, None, &ParseLimits::default()).is_err());
+}
@@ -0,0 +1,53 @@
+use super::*;
+use std::io::Cursor;
+
+use crate::synthetic;
+
+#[test]
+fn test_detects_sg1000_rom() {
+    let rom = synthetic::sg1000().data;
+    assert!(Sg1000Analyzer.can_handle(&mut Cursor::new(rom.clone())));
+    let result = Sg1000Analyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.platform, Some(Platform::Sg1000));
+    assert_eq!(result.file_size, Some(8 * 1024));
+    assert!(!result.extra.contains_key("size_profile"));
+}
+
+#[test]
+fn test_rejects_master_system_rom() {
+    let rom = synthetic::master_system().data;
+    assert!(!Sg1000Analyzer.can_handle(&mut Cursor::new(rom.clone())));
+    assert!(
+        Sg1000Analyzer
+            .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_not_claimed_without_z80_startup() {
+    let mut rom = synthetic::sg1000().data;
+    rom[0] = 0x00;
+    assert!(!Sg1000Analyzer.can_handle(&mut Cursor::new(rom.clone())));
+    // Still analyzed when the folder or extension says SG-1000
+    assert!(
+        Sg1000Analyzer
+            .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_flags_oversized_dump() {
+    let mut rom = vec![0u8; 64 * 1024];
+    rom[0] = 0xF3;
+    let result = Sg1000Analyzer
+        .analyze(&mut Cursor::new(rom), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(
+        result.extra["size_profile"],
+        "Not an SG-1000 cartridge size"
+    );
+}
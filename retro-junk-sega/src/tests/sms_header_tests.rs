@@ -0,0 +1,103 @@
+use super::*;
+use std::io::Cursor;
+
+fn header_bytes(region_and_size: u8) -> [u8; HEADER_LEN] {
+    let mut buf = [0u8; HEADER_LEN];
+    buf[..8].copy_from_slice(TMR_SEGA);
+    buf[0x0A..0x0C].copy_from_slice(&0x1234u16.to_le_bytes());
+    buf[0x0C..0x10].copy_from_slice(&[0x27, 0x50, 0x12, region_and_size]);
+    buf
+}
+
+#[test]
+fn test_parse_header() {
+    let header = TmrSegaHeader::parse(&header_bytes(0x4F), 0x7FF0).unwrap();
+    assert_eq!(header.checksum, 0x1234);
+    assert_eq!(header.product_code, 15027);
+    assert_eq!(header.version, 2);
+    assert_eq!(header.region_name(), "SMS Export");
+    assert_eq!(header.regions(), [Region::Usa, Region::Europe]);
+    assert_eq!(header.rom_size(), Some(128 * 1024));
+    assert!(!header.is_game_gear());
+
+    let header = TmrSegaHeader::parse(&header_bytes(0x70), 0x7FF0).unwrap();
+    assert!(header.is_game_gear());
+    assert_eq!(header.rom_size(), Some(256 * 1024));
+
+    assert_eq!(TmrSegaHeader::parse(&[0u8; HEADER_LEN], 0), None);
+}
+
+#[test]
+fn test_find_header_at_each_offset() {
+    for offset in HEADER_OFFSETS {
+        let mut rom = vec![0u8; 0x8000];
+        rom[offset as usize..offset as usize + HEADER_LEN].copy_from_slice(&header_bytes(0x3C));
+        let header = find_header(&mut Cursor::new(rom)).unwrap().unwrap();
+        assert_eq!(header.offset, offset);
+    }
+    assert_eq!(
+        find_header(&mut Cursor::new(vec![0u8; 0x100])).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_checksum_skips_header_and_covers_upper_banks() {
+    let mut rom = vec![1u8; 0x10000];
+    rom[0x7FF0..0x8000].copy_from_slice(&header_bytes(0x4E));
+    let sum = compute_checksum(&mut Cursor::new(&rom), 0x10000, &ParseLimits::default()).unwrap();
+    assert_eq!(sum, (0x10000 - HEADER_LEN) as u16);
+
+    // A declared size past the end of the file stops at the end
+    let sum = compute_checksum(
+        &mut Cursor::new(&rom[..0x9000]),
+        0x10000,
+        &ParseLimits::default(),
+    )
+    .unwrap();
+    assert_eq!(sum, (0x9000 - HEADER_LEN) as u16);
+}
+
+#[test]
+fn test_classify() {
+    let with_code = |size: usize, code: &[u8]| {
+        let mut rom = vec![0u8; size];
+        rom[..code.len()].copy_from_slice(code);
+        classify(&mut Cursor::new(rom)).unwrap()
+    };
+
+    // Stack in SG-1000 RAM on an SG-1000-sized cartridge
+    assert_eq!(
+        with_code(0x4000, &[0xF3, 0x31, 0x00, 0xC4]),
+        Some(CartFamily::Sg1000)
+    );
+    // ... but not on a cartridge bigger than any SG-1000 one
+    assert_eq!(with_code(0x20000, &[0xF3, 0x31, 0x00, 0xC4]), None);
+    // Stack in Master System RAM
+    assert_eq!(
+        with_code(0x8000, &[0xF3, 0x31, 0xF0, 0xDF]),
+        Some(CartFamily::MasterSystem)
+    );
+    // Mapper writes
+    assert_eq!(
+        with_code(0x8000, &[0xF3, 0x3E, 0x00, 0x32, 0xFC, 0xFF]),
+        Some(CartFamily::MasterSystem)
+    );
+    assert_eq!(with_code(0x8000, &[0xF3]), None);
+
+    let mut gg = vec![0u8; 0x8000];
+    gg[0x7FF0..].copy_from_slice(&header_bytes(0x6C));
+    assert_eq!(
+        classify(&mut Cursor::new(gg)).unwrap(),
+        Some(CartFamily::GameGear)
+    );
+}
+
+#[test]
+fn test_sg1000_sizes() {
+    assert!(is_sg1000_size(8 * 1024));
+    assert!(is_sg1000_size(48 * 1024));
+    assert!(!is_sg1000_size(56 * 1024));
+    assert!(!is_sg1000_size(10 * 1024));
+    assert!(!is_sg1000_size(0));
+}
@@ -0,0 +1,276 @@
+//! CSO and ZSO compressed ISO images.
+//!
+//! Both formats split an ISO into fixed-size blocks (2 KB usually) and
+//! compress each one on its own: CSO with raw deflate, ZSO with LZ4. A table
+//! of block offsets follows the 24-byte header; the top bit of an entry marks
+//! a block stored uncompressed. CSO version 2 instead stores a block
+//! uncompressed when it doesn't shrink, and uses the top bit for LZ4.
+//!
+//! [`open_image`] wraps an image in a [`BlockImage`], which reads like the
+//! ISO it was made from.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use retro_junk_core::{AnalysisError, BlockImage, BlockSource, ParseLimits};
+
+/// CSO magic.
+pub const CSO_MAGIC: &[u8; 4] = b"CISO";
+
+/// ZSO magic.
+pub const ZSO_MAGIC: &[u8; 4] = b"ZISO";
+
+/// Size of the header before the block index.
+const HEADER_SIZE: u64 = 0x18;
+
+/// Index flag: block stored uncompressed (LZ4 in CSO v2).
+const INDEX_FLAG: u32 = 0x8000_0000;
+
+/// Largest block size accepted; real images use 2 KB to 16 KB.
+const MAX_BLOCK_SIZE: u32 = 1024 * 1024;
+
+/// Which of the two formats an image is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CisoKind {
+    Cso,
+    Zso,
+}
+
+impl CisoKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cso => "CSO",
+            Self::Zso => "ZSO",
+        }
+    }
+
+    /// The format with this magic, if any.
+    pub fn from_magic(magic: &[u8]) -> Option<Self> {
+        match magic.get(..4)? {
+            m if m == CSO_MAGIC => Some(Self::Cso),
+            m if m == ZSO_MAGIC => Some(Self::Zso),
+            _ => None,
+        }
+    }
+}
+
+/// How a block is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockEncoding {
+    Plain,
+    Deflate,
+    Lz4,
+}
+
+/// A CSO or ZSO image, decoding one block at a time.
+pub struct CisoSource<R> {
+    inner: R,
+    kind: CisoKind,
+    version: u8,
+    block_size: u32,
+    total_bytes: u64,
+    align: u8,
+    /// Raw index entries, one per block plus the end marker.
+    index: Vec<u32>,
+    compressed: Vec<u8>,
+}
+
+impl<R: Read + Seek> CisoSource<R> {
+    /// Read the header and block index.
+    pub fn open(mut inner: R, limits: &ParseLimits) -> Result<Self, AnalysisError> {
+        let file_size = inner.seek(SeekFrom::End(0))?;
+        if file_size < HEADER_SIZE {
+            return Err(AnalysisError::TooSmall {
+                expected: HEADER_SIZE,
+                actual: file_size,
+            });
+        }
+        inner.seek(SeekFrom::Start(0))?;
+        let mut header = [0u8; HEADER_SIZE as usize];
+        inner.read_exact(&mut header)?;
+        // Either CISO or ZISO is accepted; the error names CISO
+        let kind = CisoKind::from_magic(&header)
+            .ok_or_else(|| AnalysisError::invalid_magic(0, CSO_MAGIC, &header[..4]))?;
+
+        let total_bytes = u64::from_le_bytes(header[0x08..0x10].try_into().unwrap());
+        let block_size = u32::from_le_bytes(header[0x10..0x14].try_into().unwrap());
+        let version = header[0x14];
+        let align = header[0x15];
+        if block_size == 0 || block_size > MAX_BLOCK_SIZE || align > 31 {
+            return Err(AnalysisError::corrupted_header(format!(
+                "{}: implausible block size {} or alignment {}",
+                kind.name(),
+                block_size,
+                align
+            )));
+        }
+
+        let blocks = total_bytes.div_ceil(u64::from(block_size));
+        let index_len = limits.check_alloc((blocks + 1) * 4, "CSO block index")?;
+        let mut raw = vec![0u8; index_len];
+        inner.seek(SeekFrom::Start(HEADER_SIZE))?;
+        inner.read_exact(&mut raw).map_err(|_| {
+            AnalysisError::corrupted_header(format!("{}: block index is truncated", kind.name()))
+        })?;
+        let index = raw
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+
+        Ok(Self {
+            inner,
+            kind,
+            version,
+            block_size,
+            total_bytes,
+            align,
+            index,
+            compressed: Vec::new(),
+        })
+    }
+
+    fn encoding(&self, entry: u32, stored_len: u64) -> BlockEncoding {
+        let flagged = entry & INDEX_FLAG != 0;
+        match (self.kind, self.version) {
+            (CisoKind::Cso, 2) if stored_len >= u64::from(self.block_size) => BlockEncoding::Plain,
+            (CisoKind::Cso, 2) if flagged => BlockEncoding::Lz4,
+            (_, _) if flagged => BlockEncoding::Plain,
+            (CisoKind::Cso, _) => BlockEncoding::Deflate,
+            (CisoKind::Zso, _) => BlockEncoding::Lz4,
+        }
+    }
+}
+
+impl<R: Read + Seek> BlockSource for CisoSource<R> {
+    fn block_size(&self) -> usize {
+        self.block_size as usize
+    }
+
+    fn image_len(&self) -> u64 {
+        self.total_bytes
+    }
+
+    fn read_block(&mut self, index: u64, buf: &mut Vec<u8>) -> io::Result<()> {
+        let i = index as usize;
+        let (Some(&entry), Some(&next)) = (self.index.get(i), self.index.get(i + 1)) else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("block {index} is past the end of the image"),
+            ));
+        };
+        let start = u64::from(entry & !INDEX_FLAG) << self.align;
+        let end = u64::from(next & !INDEX_FLAG) << self.align;
+        let block_start = index * u64::from(self.block_size);
+        let out_len = (self.total_bytes - block_start).min(u64::from(self.block_size)) as usize;
+        let stored_len = end.checked_sub(start).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("block {index} has a negative length"),
+            )
+        })?;
+        // Alignment padding can make a stored block a little longer than
+        // the data, never more than twice the block size
+        if stored_len > 2 * u64::from(self.block_size) + (1 << self.align) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("block {index} is {stored_len} bytes stored"),
+            ));
+        }
+
+        self.compressed.resize(stored_len as usize, 0);
+        self.inner.seek(SeekFrom::Start(start))?;
+        self.inner.read_exact(&mut self.compressed)?;
+
+        buf.clear();
+        match self.encoding(entry, stored_len) {
+            BlockEncoding::Plain => {
+                buf.extend_from_slice(&self.compressed[..out_len.min(self.compressed.len())])
+            }
+            BlockEncoding::Deflate => {
+                flate2::read::DeflateDecoder::new(&self.compressed[..])
+                    .take(out_len as u64)
+                    .read_to_end(buf)?;
+            }
+            BlockEncoding::Lz4 => lz4_decompress_block(&self.compressed, buf, out_len)?,
+        }
+        if buf.len() != out_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "block {index} decoded to {} bytes, expected {out_len}",
+                    buf.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Open a CSO or ZSO image as a readable ISO.
+pub fn open_image<R: Read + Seek>(
+    inner: R,
+    limits: &ParseLimits,
+) -> Result<BlockImage<CisoSource<R>>, AnalysisError> {
+    Ok(BlockImage::new(CisoSource::open(inner, limits)?))
+}
+
+/// Decode one LZ4 block (no frame header) into `out`, stopping once it
+/// holds `max_len` bytes so alignment padding after the data is ignored.
+pub(crate) fn lz4_decompress_block(
+    src: &[u8],
+    out: &mut Vec<u8>,
+    max_len: usize,
+) -> io::Result<()> {
+    let corrupt = || io::Error::new(io::ErrorKind::InvalidData, "corrupt LZ4 block");
+    let mut pos = 0;
+    let read_length = |pos: &mut usize, mut len: usize| -> io::Result<usize> {
+        if len == 15 {
+            loop {
+                let b = *src.get(*pos).ok_or_else(corrupt)?;
+                *pos += 1;
+                len += b as usize;
+                if b != 255 {
+                    break;
+                }
+            }
+        }
+        Ok(len)
+    };
+
+    while pos < src.len() && out.len() < max_len {
+        let token = src[pos];
+        pos += 1;
+
+        let literals = read_length(&mut pos, (token >> 4) as usize)?;
+        let lit = src.get(pos..pos + literals).ok_or_else(corrupt)?;
+        out.extend_from_slice(lit);
+        pos += literals;
+        // The last sequence is literals only
+        if pos >= src.len() || out.len() >= max_len {
+            break;
+        }
+
+        let offset = src
+            .get(pos..pos + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+            .ok_or_else(corrupt)?;
+        pos += 2;
+        if offset == 0 || offset > out.len() {
+            return Err(corrupt());
+        }
+        let match_len = read_length(&mut pos, (token & 0x0F) as usize)? + 4;
+        if out.len() + match_len > max_len {
+            return Err(corrupt());
+        }
+        // Matches may overlap the bytes they produce
+        let from = out.len() - offset;
+        for i in 0..match_len {
+            out.push(out[from + i]);
+        }
+    }
+    out.truncate(max_len);
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/ciso_tests.rs"]
+mod tests;
@@ -0,0 +1,168 @@
+//! DAX compressed ISO images.
+//!
+//! DAX is the PSP homebrew scene's older answer to CSO: an ISO split into
+//! 8 KB frames, each compressed with zlib. The 32-byte header gives the ISO
+//! size and a count of "NC areas", runs of frames stored uncompressed. The
+//! header is followed by a table of frame offsets, a table of compressed
+//! frame lengths and, from version 1, the NC areas.
+//!
+//! [`open_image`] wraps an image in a [`BlockImage`], which reads like the
+//! ISO it was made from.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use retro_junk_core::{AnalysisError, BlockImage, BlockSource, ParseLimits};
+
+/// DAX magic.
+pub const DAX_MAGIC: &[u8; 4] = b"DAX\0";
+
+/// Size of the header before the frame tables.
+const HEADER_SIZE: u64 = 0x20;
+
+/// Uncompressed frame size.
+pub const FRAME_SIZE: u32 = 0x2000;
+
+/// Largest compressed frame the format allows.
+const MAX_COMPRESSED_FRAME: u16 = 0x2400;
+
+/// A DAX image, decoding one frame at a time.
+pub struct DaxSource<R> {
+    inner: R,
+    total_bytes: u64,
+    offsets: Vec<u32>,
+    lengths: Vec<u16>,
+    /// Uncompressed frame runs, as (first frame, frame count).
+    nc_areas: Vec<(u32, u32)>,
+    compressed: Vec<u8>,
+}
+
+impl<R: Read + Seek> DaxSource<R> {
+    /// Read the header and frame tables.
+    pub fn open(mut inner: R, limits: &ParseLimits) -> Result<Self, AnalysisError> {
+        let file_size = inner.seek(SeekFrom::End(0))?;
+        if file_size < HEADER_SIZE {
+            return Err(AnalysisError::TooSmall {
+                expected: HEADER_SIZE,
+                actual: file_size,
+            });
+        }
+        inner.seek(SeekFrom::Start(0))?;
+        let mut header = [0u8; HEADER_SIZE as usize];
+        inner.read_exact(&mut header)?;
+        if &header[..4] != DAX_MAGIC {
+            return Err(AnalysisError::invalid_magic(0, DAX_MAGIC, &header[..4]));
+        }
+        let word = |at: usize| u32::from_le_bytes(header[at..at + 4].try_into().unwrap());
+        let total_bytes = u64::from(word(0x04));
+        let version = word(0x08);
+        let nc_count = if version >= 1 { word(0x0C) } else { 0 };
+
+        let frames = total_bytes.div_ceil(u64::from(FRAME_SIZE));
+        let tables_len = limits.check_alloc(frames * 6, "DAX frame tables")?;
+        let nc_len = limits.check_alloc(u64::from(nc_count) * 8, "DAX NC areas")?;
+        let mut raw = vec![0u8; tables_len + nc_len];
+        inner
+            .read_exact(&mut raw)
+            .map_err(|_| AnalysisError::corrupted_header("DAX: frame tables are truncated"))?;
+
+        let (offsets, rest) = raw.split_at(frames as usize * 4);
+        let (lengths, nc) = rest.split_at(frames as usize * 2);
+        let offsets = offsets
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        let lengths = lengths
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        let nc_areas = nc
+            .chunks_exact(8)
+            .map(|c| {
+                (
+                    u32::from_le_bytes([c[0], c[1], c[2], c[3]]),
+                    u32::from_le_bytes([c[4], c[5], c[6], c[7]]),
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            inner,
+            total_bytes,
+            offsets,
+            lengths,
+            nc_areas,
+            compressed: Vec::new(),
+        })
+    }
+
+    /// Whether a frame is stored uncompressed.
+    fn is_stored(&self, frame: u64) -> bool {
+        self.nc_areas.iter().any(|&(first, count)| {
+            frame >= u64::from(first) && frame < u64::from(first) + u64::from(count)
+        })
+    }
+}
+
+impl<R: Read + Seek> BlockSource for DaxSource<R> {
+    fn block_size(&self) -> usize {
+        FRAME_SIZE as usize
+    }
+
+    fn image_len(&self) -> u64 {
+        self.total_bytes
+    }
+
+    fn read_block(&mut self, index: u64, buf: &mut Vec<u8>) -> io::Result<()> {
+        let i = index as usize;
+        let (Some(&offset), Some(&length)) = (self.offsets.get(i), self.lengths.get(i)) else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("frame {index} is past the end of the image"),
+            ));
+        };
+        let frame_start = index * u64::from(FRAME_SIZE);
+        let out_len = (self.total_bytes - frame_start).min(u64::from(FRAME_SIZE)) as usize;
+
+        buf.clear();
+        self.inner.seek(SeekFrom::Start(u64::from(offset)))?;
+        if self.is_stored(index) {
+            buf.resize(out_len, 0);
+            self.inner.read_exact(buf)?;
+            return Ok(());
+        }
+
+        if length > MAX_COMPRESSED_FRAME {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("frame {index} is {length} bytes compressed"),
+            ));
+        }
+        self.compressed.resize(usize::from(length), 0);
+        self.inner.read_exact(&mut self.compressed)?;
+        flate2::read::ZlibDecoder::new(&self.compressed[..])
+            .take(out_len as u64)
+            .read_to_end(buf)?;
+        if buf.len() != out_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "frame {index} decoded to {} bytes, expected {out_len}",
+                    buf.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Open a DAX image as a readable ISO.
+pub fn open_image<R: Read + Seek>(
+    inner: R,
+    limits: &ParseLimits,
+) -> Result<BlockImage<DaxSource<R>>, AnalysisError> {
+    Ok(BlockImage::new(DaxSource::open(inner, limits)?))
+}
+
+#[cfg(test)]
+#[path = "tests/dax_tests.rs"]
+mod tests;
@@ -8,17 +8,18 @@
 //! - PlayStation Portable (PSP)
 //! - PlayStation Vita
 
+pub mod ciso;
+pub mod dax;
 pub mod ps1;
 pub mod ps2;
 pub mod ps3;
 pub mod psp;
+pub mod sfo;
 pub(crate) mod sony_disc;
+#[cfg(any(test, feature = "testkit"))]
+pub mod synthetic;
 pub mod vita;
 
-#[cfg(test)]
-#[path = "tests/disc_test_helpers.rs"]
-pub(crate) mod disc_test_helpers;
-
 pub use ps1::Ps1Analyzer;
 pub use ps2::Ps2Analyzer;
 pub use ps3::Ps3Analyzer;
@@ -7,10 +7,9 @@
 //! - CHD compressed images
 
 use retro_junk_core::ReadSeek;
-use std::io::SeekFrom;
 
 use retro_junk_core::{
-    AnalysisError, AnalysisOptions, FileHashes, HashAlgorithms, Platform, RomAnalyzer,
+    AnalysisError, AnalysisOptions, FileHashes, HashAlgorithms, ParseLimits, Platform, RomAnalyzer,
     RomIdentification,
 };
 
@@ -40,7 +39,7 @@ impl Ps1Analyzer {
     fn analyze_disc_image(
         &self,
         reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        options: &AnalysisOptions,
         format: DiscFormat,
     ) -> Result<RomIdentification, AnalysisError> {
         let file_size = retro_junk_core::util::file_size(reader)?;
@@ -64,16 +63,22 @@ impl Ps1Analyzer {
         if !pvd.volume_identifier.is_empty() {
             id.internal_name = Some(pvd.volume_identifier.clone());
         }
+        id.build_date = pvd.creation_date;
 
-        // Calculate expected size from PVD
-        let sector_size = match format {
-            DiscFormat::RawSector2352 => 2352u64,
-            _ => 2048u64,
-        };
-        id.expected_size = Some(pvd.volume_space_size as u64 * sector_size);
+        id.expected_size = Some(pvd.image_size(format.sector_size()));
+
+        // A BIN on its own has no CUE to say which mode its data track uses
+        if format == DiscFormat::RawSector2352
+            && let Some(mode) = sony_disc::raw_sector_mode(reader, sony_disc::PVD_SECTOR)?
+        {
+            id.extra
+                .insert("sector_mode".into(), format!("Mode {}", mode));
+        }
 
         // Read SYSTEM.CNF for serial and region (fast: just 1-2 sector reads)
-        if let Ok(content) = sony_disc::find_file_in_root(reader, format, &pvd, "SYSTEM.CNF") {
+        if let Ok(content) =
+            sony_disc::find_file_in_root(reader, format, &pvd, "SYSTEM.CNF", &options.limits)
+        {
             let text = String::from_utf8_lossy(&content);
             if let Ok(ref cnf) = sony_disc::parse_system_cnf(&text) {
                 // Reject PS2 discs (BOOT2) — let the PS2 analyzer handle them
@@ -97,11 +102,7 @@ impl Ps1Analyzer {
     ) -> Result<RomIdentification, AnalysisError> {
         let file_size = retro_junk_core::util::file_size(reader)?;
 
-        // Read the CUE text
-        let mut cue_text = String::new();
-        reader.read_to_string(&mut cue_text)?;
-
-        let sheet = sony_disc::parse_cue(&cue_text)?;
+        let sheet = sony_disc::read_cue_sheet(reader, options)?;
 
         let mut id = RomIdentification::new().with_platform(Platform::Ps1);
         id.file_size = Some(file_size);
@@ -109,13 +110,8 @@ impl Ps1Analyzer {
         id.extra.insert("detected_extension".into(), "cue".into());
 
         // Count data and audio tracks
-        let total_tracks: usize = sheet.files.iter().map(|f| f.tracks.len()).sum();
-        let data_tracks: usize = sheet
-            .files
-            .iter()
-            .flat_map(|f| &f.tracks)
-            .filter(|t| t.mode.to_uppercase().contains("MODE"))
-            .count();
+        let total_tracks = sheet.tracks().count();
+        let data_tracks = sheet.tracks().filter(|t| t.is_data()).count();
         let audio_tracks = total_tracks - data_tracks;
 
         id.extra
@@ -126,7 +122,7 @@ impl Ps1Analyzer {
             .insert("audio_tracks".into(), audio_tracks.to_string());
 
         // Store referenced filenames
-        let filenames: Vec<&str> = sheet.files.iter().map(|f| f.filename.as_str()).collect();
+        let filenames: Vec<&str> = sheet.filenames().collect();
         if filenames.len() == 1 {
             id.extra.insert("bin_file".into(), filenames[0].to_string());
         } else {
@@ -139,11 +135,11 @@ impl Ps1Analyzer {
             && let Some(parent) = file_path.parent()
         {
             // Find the first file with a data track
-            if let Some(first_data_file) = sheet.files.iter().find(|f| {
-                f.tracks
-                    .iter()
-                    .any(|t| t.mode.to_uppercase().contains("MODE"))
-            }) {
+            if let Some(first_data_file) = sheet
+                .files
+                .iter()
+                .find(|f| f.tracks.iter().any(|t| t.is_data()))
+            {
                 let bin_path = parent.join(&first_data_file.filename);
                 if bin_path.exists()
                     && let Ok(mut bin_file) = std::fs::File::open(&bin_path)
@@ -160,11 +156,13 @@ impl Ps1Analyzer {
                             if !pvd.volume_identifier.is_empty() {
                                 id.internal_name = Some(pvd.volume_identifier.clone());
                             }
+                            id.build_date = pvd.creation_date;
                             if let Ok(content) = sony_disc::find_file_in_root(
                                 &mut bin_file,
                                 bin_format,
                                 &pvd,
                                 "SYSTEM.CNF",
+                                &options.limits,
                             ) {
                                 self.apply_system_cnf(&content, &mut id);
                             }
@@ -181,7 +179,7 @@ impl Ps1Analyzer {
     fn analyze_chd(
         &self,
         reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
         let file_size = retro_junk_core::util::file_size(reader)?;
 
@@ -201,7 +199,7 @@ impl Ps1Analyzer {
         );
 
         // Read SYSTEM.CNF from CHD (decompresses 1-2 hunks — fast enough)
-        match sony_disc::read_system_cnf_from_chd(reader) {
+        match sony_disc::read_system_cnf_from_chd(reader, &options.limits) {
             Ok(content) => {
                 self.apply_system_cnf(&content, &mut id);
             }
@@ -250,6 +248,9 @@ impl RomAnalyzer for Ps1Analyzer {
             }
             DiscFormat::Cue => self.analyze_cue(reader, options),
             DiscFormat::Chd => self.analyze_chd(reader, options),
+            DiscFormat::Cso | DiscFormat::Zso | DiscFormat::Dax => Err(AnalysisError::unsupported(
+                "CSO/ZSO/DAX images are not used for PlayStation discs",
+            )),
         }
     }
 
@@ -261,6 +262,10 @@ impl RomAnalyzer for Ps1Analyzer {
         &["iso", "bin", "chd"]
     }
 
+    fn sniff_len(&self) -> usize {
+        sony_disc::SNIFF_LEN
+    }
+
     fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
         let format = match sony_disc::detect_disc_format(reader) {
             Ok(f) => f,
@@ -278,9 +283,13 @@ impl RomAnalyzer for Ps1Analyzer {
                 // Differentiate PS1 from PS2 by checking SYSTEM.CNF boot key.
                 // PS1 uses BOOT, PS2 uses BOOT2. If we can read SYSTEM.CNF and
                 // it has BOOT2, this is a PS2 disc — reject it.
-                if let Ok(content) =
-                    sony_disc::find_file_in_root(reader, format, &pvd, "SYSTEM.CNF")
-                {
+                if let Ok(content) = sony_disc::find_file_in_root(
+                    reader,
+                    format,
+                    &pvd,
+                    "SYSTEM.CNF",
+                    &ParseLimits::hardened(),
+                ) {
                     let text = String::from_utf8_lossy(&content);
                     if let Ok(cnf) = sony_disc::parse_system_cnf(&text) {
                         return cnf.boot_key == sony_disc::BootKey::Boot;
@@ -292,6 +301,7 @@ impl RomAnalyzer for Ps1Analyzer {
             }
             // CUE and CHD: can't verify without reading disc data
             DiscFormat::Cue | DiscFormat::Chd => true,
+            DiscFormat::Cso | DiscFormat::Zso | DiscFormat::Dax => false,
         }
     }
 
@@ -299,6 +309,10 @@ impl RomAnalyzer for Ps1Analyzer {
         retro_junk_core::DatSource::Redump
     }
 
+    fn dat_lists_raw_cd_tracks(&self) -> bool {
+        true
+    }
+
     fn compute_container_hashes(
         &self,
         reader: &mut dyn ReadSeek,
@@ -326,7 +340,7 @@ impl RomAnalyzer for Ps1Analyzer {
                         "PS1 compute_container_hashes: raw BIN, hashing Track 1 ({} bytes)",
                         data_size
                     );
-                    let hashes = hash_raw_bin_track1(reader, algorithms, data_size)?;
+                    let hashes = sony_disc::hash_leading_bytes(reader, algorithms, data_size)?;
                     Ok(Some(hashes))
                 } else {
                     // Single-track BIN — let the standard hasher handle it
@@ -342,6 +356,14 @@ impl RomAnalyzer for Ps1Analyzer {
         &["Sony - PlayStation"]
     }
 
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(sony_disc::SERIAL_PATTERN)
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        sony_disc::normalize_serial(serial)
+    }
+
     fn expects_serial(&self) -> bool {
         true
     }
@@ -360,63 +382,6 @@ impl RomAnalyzer for Ps1Analyzer {
     }
 }
 
-/// Hash the first `data_size` bytes of a raw 2352-byte sector BIN file.
-pub(crate) fn hash_raw_bin_track1(
-    reader: &mut dyn ReadSeek,
-    algorithms: HashAlgorithms,
-    data_size: u64,
-) -> Result<FileHashes, AnalysisError> {
-    use sha1::Digest;
-
-    reader.seek(SeekFrom::Start(0))?;
-
-    let mut crc = if algorithms.crc32() {
-        Some(crc32fast::Hasher::new())
-    } else {
-        None
-    };
-    let mut sha = if algorithms.sha1() {
-        Some(sha1::Sha1::new())
-    } else {
-        None
-    };
-    let mut md5_ctx = if algorithms.md5() {
-        Some(md5::Context::new())
-    } else {
-        None
-    };
-
-    let mut buf = [0u8; 64 * 1024];
-    let mut remaining = data_size;
-
-    while remaining > 0 {
-        let to_read = remaining.min(buf.len() as u64) as usize;
-        let n = reader.read(&mut buf[..to_read])?;
-        if n == 0 {
-            break;
-        }
-        if let Some(ref mut h) = crc {
-            h.update(&buf[..n]);
-        }
-        if let Some(ref mut h) = sha {
-            h.update(&buf[..n]);
-        }
-        if let Some(ref mut h) = md5_ctx {
-            h.consume(&buf[..n]);
-        }
-        remaining -= n as u64;
-    }
-
-    Ok(FileHashes {
-        crc32: crc
-            .map(|h| format!("{:08x}", h.finalize()))
-            .unwrap_or_default(),
-        sha1: sha.map(|h| format!("{:x}", h.finalize())),
-        md5: md5_ctx.map(|h| format!("{:x}", h.compute())),
-        data_size,
-    })
-}
-
 #[cfg(test)]
 #[path = "tests/ps1_tests.rs"]
 mod tests;
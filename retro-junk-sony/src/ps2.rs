@@ -4,17 +4,26 @@
 //! - ISO images (2048 bytes/sector)
 //! - BIN images (raw 2352 bytes/sector)
 //! - CUE sheets (parses track layout, optionally opens referenced BIN)
-//! - CHD compressed images
+//! - CHD compressed images (CD and DVD)
+//! - CSO/ZSO/DAX compressed ISOs
 //!
 //! PS2 discs are nearly identical to PS1 from a filesystem perspective (ISO 9660
 //! with a SYSTEM.CNF boot descriptor). The key differentiator is `BOOT2` in
 //! SYSTEM.CNF (vs PS1's `BOOT`). All disc parsing is shared via `sony_disc`.
+//! Compressed images that hold a plain ISO (CSO, ZSO, DAX and DVD CHDs) are
+//! decompressed on the fly and analyzed and hashed as that ISO, which is
+//! what Redump lists.
+//!
+//! Early PS2 games shipped on CD-ROM (the blue-bottomed discs), later ones
+//! on DVD-ROM. CD dumps are raw Mode 2 BINs with a CUE, DVD dumps plain
+//! ISOs; the `media` extra says which one an image came from, and
+//! `dvd_layer` is only reported for DVDs.
 
 use retro_junk_core::ReadSeek;
-use std::io::{Seek, SeekFrom};
+use retro_junk_core::disc::iso9660;
 
 use retro_junk_core::{
-    AnalysisError, AnalysisOptions, FileHashes, HashAlgorithms, Platform, RomAnalyzer,
+    AnalysisError, AnalysisOptions, FileHashes, HashAlgorithms, ParseLimits, Platform, RomAnalyzer,
     RomIdentification,
 };
 
@@ -24,6 +33,26 @@ use crate::sony_disc::{self, BootKey, DiscFormat};
 /// Files larger than this are likely DVD-9 (dual layer).
 const DVD5_SIZE_THRESHOLD: u64 = 4_700_000_000;
 
+/// Most sectors a CD-ROM holds (90 minutes at 75 sectors a second). A
+/// larger ISO can only have come from a DVD.
+const CD_MAX_SECTORS: u32 = 405_000;
+
+/// The kind of disc a PS2 image was dumped from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Media {
+    Cd,
+    Dvd,
+}
+
+impl Media {
+    fn name(self) -> &'static str {
+        match self {
+            Media::Cd => "CD-ROM",
+            Media::Dvd => "DVD-ROM",
+        }
+    }
+}
+
 /// Multi-disc PS2 games where the per-disc boot serial (from SYSTEM.CNF)
 /// differs from the catalog serial used in the DAT.
 ///
@@ -39,7 +68,7 @@ impl Ps2Analyzer {
     fn analyze_disc_image(
         &self,
         reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        options: &AnalysisOptions,
         format: DiscFormat,
     ) -> Result<RomIdentification, AnalysisError> {
         let file_size = retro_junk_core::util::file_size(reader)?;
@@ -63,19 +92,17 @@ impl Ps2Analyzer {
         if !pvd.volume_identifier.is_empty() {
             id.internal_name = Some(pvd.volume_identifier.clone());
         }
+        id.build_date = pvd.creation_date;
 
-        // Calculate expected size from PVD
-        let sector_size = match format {
-            DiscFormat::RawSector2352 => 2352u64,
-            _ => 2048u64,
-        };
-        id.expected_size = Some(pvd.volume_space_size as u64 * sector_size);
+        id.expected_size = Some(pvd.image_size(format.sector_size()));
 
-        // Detect DVD layer type from file size
-        detect_dvd_layer(file_size, &mut id);
+        let media = detect_media(reader, format, &pvd);
+        set_media(media, file_size, &mut id);
 
         // Read SYSTEM.CNF for serial and region
-        if let Ok(content) = sony_disc::find_file_in_root(reader, format, &pvd, "SYSTEM.CNF") {
+        if let Ok(content) =
+            sony_disc::find_file_in_root(reader, format, &pvd, "SYSTEM.CNF", &options.limits)
+        {
             let text = String::from_utf8_lossy(&content);
             if let Ok(ref cnf) = sony_disc::parse_system_cnf(&text) {
                 // Reject PS1 discs (BOOT) — let the PS1 analyzer handle them
@@ -99,25 +126,18 @@ impl Ps2Analyzer {
     ) -> Result<RomIdentification, AnalysisError> {
         let file_size = retro_junk_core::util::file_size(reader)?;
 
-        // Read the CUE text
-        let mut cue_text = String::new();
-        reader.read_to_string(&mut cue_text)?;
-
-        let sheet = sony_disc::parse_cue(&cue_text)?;
+        let sheet = sony_disc::read_cue_sheet(reader, options)?;
 
         let mut id = RomIdentification::new().with_platform(Platform::Ps2);
         id.file_size = Some(file_size);
         id.extra.insert("format".into(), "CUE Sheet".into());
         id.extra.insert("detected_extension".into(), "cue".into());
+        // CUE sheets only describe CDs
+        set_media(Media::Cd, 0, &mut id);
 
         // Count data and audio tracks
-        let total_tracks: usize = sheet.files.iter().map(|f| f.tracks.len()).sum();
-        let data_tracks: usize = sheet
-            .files
-            .iter()
-            .flat_map(|f| &f.tracks)
-            .filter(|t| t.mode.to_uppercase().contains("MODE"))
-            .count();
+        let total_tracks = sheet.tracks().count();
+        let data_tracks = sheet.tracks().filter(|t| t.is_data()).count();
         let audio_tracks = total_tracks - data_tracks;
 
         id.extra
@@ -128,7 +148,7 @@ impl Ps2Analyzer {
             .insert("audio_tracks".into(), audio_tracks.to_string());
 
         // Store referenced filenames
-        let filenames: Vec<&str> = sheet.files.iter().map(|f| f.filename.as_str()).collect();
+        let filenames: Vec<&str> = sheet.filenames().collect();
         if filenames.len() == 1 {
             id.extra.insert("bin_file".into(), filenames[0].to_string());
         } else {
@@ -138,11 +158,10 @@ impl Ps2Analyzer {
         // Open the first data track BIN and extract serial/volume ID
         if let Some(ref file_path) = options.file_path
             && let Some(parent) = file_path.parent()
-            && let Some(first_data_file) = sheet.files.iter().find(|f| {
-                f.tracks
-                    .iter()
-                    .any(|t| t.mode.to_uppercase().contains("MODE"))
-            })
+            && let Some(first_data_file) = sheet
+                .files
+                .iter()
+                .find(|f| f.tracks.iter().any(|t| t.is_data()))
         {
             let bin_path = parent.join(&first_data_file.filename);
             if bin_path.exists()
@@ -154,21 +173,20 @@ impl Ps2Analyzer {
                     _ => DiscFormat::Iso2048,
                 };
 
-                // Detect DVD layer from the BIN file size
-                if let Ok(bin_size) = bin_file.seek(SeekFrom::End(0)) {
-                    detect_dvd_layer(bin_size, &mut id);
-                    bin_file.seek(SeekFrom::Start(0)).ok();
-                }
-
                 if let Ok(pvd) = sony_disc::read_pvd(&mut bin_file, bin_format)
                     && pvd.system_identifier.starts_with("PLAYSTATION")
                 {
                     if !pvd.volume_identifier.is_empty() {
                         id.internal_name = Some(pvd.volume_identifier.clone());
                     }
-                    if let Ok(content) =
-                        sony_disc::find_file_in_root(&mut bin_file, bin_format, &pvd, "SYSTEM.CNF")
-                    {
+                    id.build_date = pvd.creation_date;
+                    if let Ok(content) = sony_disc::find_file_in_root(
+                        &mut bin_file,
+                        bin_format,
+                        &pvd,
+                        "SYSTEM.CNF",
+                        &options.limits,
+                    ) {
                         let text = String::from_utf8_lossy(&content);
                         if let Ok(ref cnf) = sony_disc::parse_system_cnf(&text) {
                             apply_system_cnf(cnf, &mut id);
@@ -181,20 +199,50 @@ impl Ps2Analyzer {
         Ok(id)
     }
 
+    /// Analyze a CSO/ZSO/DAX image or a DVD CHD as the ISO it decompresses to.
+    fn analyze_compressed(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+        format: DiscFormat,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+
+        let mut image = sony_disc::open_compressed_iso(reader, format, &options.limits)?;
+        let mut id = self.analyze_disc_image(&mut image, options, DiscFormat::Iso2048)?;
+
+        // The PVD's size describes the ISO, not the compressed file
+        if let Some(iso_size) = id.file_size {
+            id.extra
+                .insert("uncompressed_size".into(), iso_size.to_string());
+        }
+        id.file_size = Some(file_size);
+        id.expected_size = None;
+        id.extra.insert("format".into(), format.name().into());
+        id.extra
+            .insert("detected_extension".into(), format.extension().into());
+        Ok(id)
+    }
+
     /// Analyze a CHD compressed disc image.
     fn analyze_chd(
         &self,
         reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
         let file_size = retro_junk_core::util::file_size(reader)?;
 
         let chd_info = sony_disc::read_chd_info(reader)?;
 
-        let mut id = RomIdentification::new().with_platform(Platform::Ps2);
+        // DVD images hold plain 2048-byte sectors
+        let mut id = if chd_info.is_dvd() {
+            let mut id = self.analyze_compressed(reader, options, DiscFormat::Chd)?;
+            set_media(Media::Dvd, chd_info.logical_size, &mut id);
+            id
+        } else {
+            self.analyze_chd_cd(reader, options, chd_info.logical_size)?
+        };
         id.file_size = Some(file_size);
-        id.extra.insert("format".into(), "CHD".into());
-        id.extra.insert("detected_extension".into(), "chd".into());
         id.extra
             .insert("chd_version".into(), format!("v{}", chd_info.version));
         id.extra
@@ -203,12 +251,24 @@ impl Ps2Analyzer {
             "chd_logical_size".into(),
             format!("{}", chd_info.logical_size),
         );
+        Ok(id)
+    }
+
+    /// Analyze a CD CHD image, reading SYSTEM.CNF from its raw sectors.
+    fn analyze_chd_cd(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+        logical_size: u64,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let mut id = RomIdentification::new().with_platform(Platform::Ps2);
+        id.extra.insert("format".into(), "CHD".into());
+        id.extra.insert("detected_extension".into(), "chd".into());
 
-        // Detect DVD layer from CHD logical size
-        detect_dvd_layer(chd_info.logical_size, &mut id);
+        set_media(Media::Cd, logical_size, &mut id);
 
         // Read SYSTEM.CNF from CHD
-        match sony_disc::read_system_cnf_from_chd(reader) {
+        match sony_disc::read_system_cnf_from_chd(reader, &options.limits) {
             Ok(content) => {
                 let text = String::from_utf8_lossy(&content);
                 if let Ok(ref cnf) = sony_disc::parse_system_cnf(&text) {
@@ -238,6 +298,9 @@ impl RomAnalyzer for Ps2Analyzer {
             }
             DiscFormat::Cue => self.analyze_cue(reader, options),
             DiscFormat::Chd => self.analyze_chd(reader, options),
+            DiscFormat::Cso | DiscFormat::Zso | DiscFormat::Dax => {
+                self.analyze_compressed(reader, options, format)
+            }
         }
     }
 
@@ -246,7 +309,11 @@ impl RomAnalyzer for Ps2Analyzer {
     }
 
     fn file_extensions(&self) -> &'static [&'static str] {
-        &["iso", "bin", "chd"]
+        &["iso", "bin", "chd", "cso", "zso"]
+    }
+
+    fn sniff_len(&self) -> usize {
+        sony_disc::SNIFF_LEN
     }
 
     fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
@@ -256,25 +323,11 @@ impl RomAnalyzer for Ps2Analyzer {
         };
 
         match format {
-            DiscFormat::Iso2048 | DiscFormat::RawSector2352 => {
-                // Verify PLAYSTATION system identifier in PVD
-                let pvd = match sony_disc::read_pvd(reader, format) {
-                    Ok(pvd) if pvd.system_identifier.starts_with("PLAYSTATION") => pvd,
-                    _ => return false,
-                };
-
-                // PS2 discs use BOOT2 in SYSTEM.CNF
-                if let Ok(content) =
-                    sony_disc::find_file_in_root(reader, format, &pvd, "SYSTEM.CNF")
-                {
-                    let text = String::from_utf8_lossy(&content);
-                    if let Ok(cnf) = sony_disc::parse_system_cnf(&text) {
-                        return cnf.boot_key == BootKey::Boot2;
-                    }
-                }
-
-                // No SYSTEM.CNF — not identifiable as PS2
-                false
+            DiscFormat::Iso2048 | DiscFormat::RawSector2352 => is_ps2_image(reader, format),
+            // PSP games share the formats; only a PS2 ISO inside will do
+            DiscFormat::Cso | DiscFormat::Zso | DiscFormat::Dax => {
+                sony_disc::open_compressed_iso(reader, format, &ParseLimits::hardened())
+                    .is_ok_and(|mut image| is_ps2_image(&mut image, DiscFormat::Iso2048))
             }
             // CUE and CHD: can't cheaply verify without reading disc data
             DiscFormat::Cue | DiscFormat::Chd => true,
@@ -294,6 +347,9 @@ impl RomAnalyzer for Ps2Analyzer {
         let format = sony_disc::detect_disc_format(reader)?;
 
         match format {
+            DiscFormat::Chd if sony_disc::read_chd_info(reader)?.is_dvd() => Ok(Some(
+                sony_disc::hash_compressed_iso(reader, format, algorithms)?,
+            )),
             DiscFormat::Chd => {
                 log::info!("PS2 compute_container_hashes: CHD detected");
                 let hashes = sony_disc::hash_chd_raw_sectors(reader, algorithms)?;
@@ -312,12 +368,15 @@ impl RomAnalyzer for Ps2Analyzer {
                         "PS2 compute_container_hashes: raw BIN, hashing Track 1 ({} bytes)",
                         data_size
                     );
-                    let hashes = crate::ps1::hash_raw_bin_track1(reader, algorithms, data_size)?;
+                    let hashes = sony_disc::hash_leading_bytes(reader, algorithms, data_size)?;
                     Ok(Some(hashes))
                 } else {
                     Ok(None)
                 }
             }
+            DiscFormat::Cso | DiscFormat::Zso | DiscFormat::Dax => Ok(Some(
+                sony_disc::hash_compressed_iso(reader, format, algorithms)?,
+            )),
             _ => Ok(None),
         }
     }
@@ -326,6 +385,19 @@ impl RomAnalyzer for Ps2Analyzer {
         &["Sony - PlayStation 2"]
     }
 
+    fn disc_is_raw_cd(&self, reader: &mut dyn ReadSeek) -> Result<bool, AnalysisError> {
+        let pvd = sony_disc::read_pvd(reader, DiscFormat::Iso2048)?;
+        Ok(detect_media(reader, DiscFormat::Iso2048, &pvd) == Media::Cd)
+    }
+
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(sony_disc::SERIAL_PATTERN)
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        sony_disc::normalize_serial(serial)
+    }
+
     fn expects_serial(&self) -> bool {
         true
     }
@@ -343,6 +415,29 @@ impl RomAnalyzer for Ps2Analyzer {
     }
 }
 
+/// Whether an ISO or raw BIN image is a PlayStation disc booting with
+/// `BOOT2`, i.e. a PS2 disc.
+fn is_ps2_image(reader: &mut dyn ReadSeek, format: DiscFormat) -> bool {
+    // Verify PLAYSTATION system identifier in PVD
+    let pvd = match sony_disc::read_pvd(reader, format) {
+        Ok(pvd) if pvd.system_identifier.starts_with("PLAYSTATION") => pvd,
+        _ => return false,
+    };
+
+    // PS2 discs use BOOT2 in SYSTEM.CNF
+    if let Ok(content) =
+        sony_disc::find_file_in_root(reader, format, &pvd, "SYSTEM.CNF", &ParseLimits::hardened())
+    {
+        let text = String::from_utf8_lossy(&content);
+        if let Ok(cnf) = sony_disc::parse_system_cnf(&text) {
+            return cnf.boot_key == BootKey::Boot2;
+        }
+    }
+
+    // No SYSTEM.CNF — not identifiable as PS2
+    false
+}
+
 /// Apply parsed SYSTEM.CNF data to the identification.
 fn apply_system_cnf(cnf: &sony_disc::SystemCnf, id: &mut RomIdentification) {
     id.extra.insert("boot_path".into(), cnf.boot_path.clone());
@@ -357,14 +452,40 @@ fn apply_system_cnf(cnf: &sony_disc::SystemCnf, id: &mut RomIdentification) {
     }
 }
 
-/// Detect DVD layer type from file/image size and record it in extras.
-fn detect_dvd_layer(size: u64, id: &mut RomIdentification) {
-    let layer = if size > DVD5_SIZE_THRESHOLD {
-        "DVD-9"
+/// Which kind of disc an ISO or raw BIN image was dumped from.
+///
+/// Raw 2352-byte sectors only exist on CDs. PS2 DVDs are UDF bridge discs,
+/// so an ISO with a UDF descriptor, or too big for a CD, is a DVD.
+fn detect_media(
+    reader: &mut dyn ReadSeek,
+    format: DiscFormat,
+    pvd: &sony_disc::PrimaryVolumeDescriptor,
+) -> Media {
+    if format == DiscFormat::RawSector2352 {
+        return Media::Cd;
+    }
+    let mut source = |lba| sony_disc::read_sector_data(reader, lba, format);
+    if pvd.volume_space_size > CD_MAX_SECTORS || iso9660::has_udf(&mut source) {
+        Media::Dvd
     } else {
-        "DVD-5"
-    };
-    id.extra.insert("dvd_layer".into(), layer.into());
+        Media::Cd
+    }
+}
+
+/// Record the media in extras, with the DVD layer type for DVDs (judged
+/// from the image size).
+fn set_media(media: Media, size: u64, id: &mut RomIdentification) {
+    id.extra.insert("media".into(), media.name().into());
+    if media == Media::Dvd {
+        let layer = if size > DVD5_SIZE_THRESHOLD {
+            "DVD-9"
+        } else {
+            "DVD-5"
+        };
+        id.extra.insert("dvd_layer".into(), layer.into());
+    } else {
+        id.extra.remove("dvd_layer");
+    }
 }
 
 #[cfg(test)]
@@ -1,14 +1,34 @@
 //! PlayStation 3 disc image analyzer.
 //!
 //! Supports:
-//! - ISO images
-//! - Folder/JB format
-//! - PKG files
+//! - Folder/JB format: an extracted disc (`PS3_GAME/PARAM.SFO`, see
+//!   [`crate::sfo`]), through [`RomAnalyzer::analyze_dir`]
+//! - ISO images and PKG files are recognized by extension only
+
+use std::path::Path;
 
 use retro_junk_core::ReadSeek;
 
 use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
 
+use crate::sfo::{self, ParamSfo};
+use crate::sony_disc;
+
+/// Folder of an extracted disc holding the game's files.
+const GAME_DIR: &str = "PS3_GAME";
+
+/// The game's PARAM.SFO, within [`GAME_DIR`].
+const PARAM_SFO: &str = "PARAM.SFO";
+
+/// PARAM.SFO categories, by code.
+const CATEGORIES: &[(&str, &str)] = &[
+    ("DG", "Disc game"),
+    ("HG", "HDD game"),
+    ("GD", "Game data"),
+    ("1P", "PSone Classic"),
+    ("2P", "PS2 Classic"),
+];
+
 /// Analyzer for PlayStation 3 disc images.
 #[derive(Debug, Default)]
 pub struct Ps3Analyzer;
@@ -36,11 +56,75 @@ impl RomAnalyzer for Ps3Analyzer {
         false // Not yet implemented
     }
 
+    fn is_game_dir(&self, dir: &Path) -> bool {
+        dir.join(GAME_DIR).join(PARAM_SFO).is_file()
+    }
+
+    fn analyze_dir(
+        &self,
+        dir: &Path,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let path = dir.join(GAME_DIR).join(PARAM_SFO);
+        sfo::check_size(std::fs::metadata(&path)?.len(), options)?;
+        let sfo = ParamSfo::parse(&std::fs::read(&path)?)?;
+
+        let mut id = RomIdentification::new().with_platform(Platform::Ps3);
+        id.extra.insert("format".into(), "Folder".into());
+        apply_sfo(&sfo, &mut id);
+        Ok(id)
+    }
+
     fn dat_source(&self) -> retro_junk_core::DatSource {
         retro_junk_core::DatSource::Redump
     }
 
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(sony_disc::SERIAL_PATTERN)
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        sony_disc::normalize_serial(serial)
+    }
+
     fn dat_names(&self) -> &'static [&'static str] {
         &["Sony - PlayStation 3"]
     }
 }
+
+/// Apply PARAM.SFO fields to the identification.
+fn apply_sfo(sfo: &ParamSfo, id: &mut RomIdentification) {
+    if let Some(title) = sfo.get_str("TITLE") {
+        id.internal_name = Some(title.to_string());
+    }
+    if let Some(code) = sfo.get_str("TITLE_ID") {
+        let serial = sfo::product_code_to_serial(code);
+        id.regions = sfo::product_code_region(&serial).into_iter().collect();
+        id.serial_number = Some(serial);
+    }
+    // The disc's version; APP_VER is raised by updates
+    if let Some(version) = sfo.get_str("VERSION") {
+        id.version = Some(version.to_string());
+    }
+    if let Some(version) = sfo.get_str("APP_VER") {
+        id.extra.insert("app_version".into(), version.to_string());
+    }
+    if let Some(code) = sfo.get_str("CATEGORY") {
+        let category = CATEGORIES
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map_or(code, |(_, name)| name);
+        id.extra.insert("category".into(), category.into());
+    }
+    if let Some(firmware) = sfo.get_str("PS3_SYSTEM_VER") {
+        id.extra
+            .insert("required_firmware".into(), firmware.to_string());
+    }
+    if let Some(level) = sfo.get_int("PARENTAL_LEVEL") {
+        id.extra.insert("parental_level".into(), level.to_string());
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/ps3_tests.rs"]
+mod tests;
@@ -1,28 +1,226 @@
 //! PlayStation Portable (PSP) disc/ROM analyzer.
 //!
 //! Supports:
-//! - ISO images
-//! - CSO compressed images
-//! - PBP (EBOOT.PBP format)
-//! - DAX compressed images
+//! - ISO images of UMD discs
+//! - CSO/ZSO and DAX compressed ISOs
+//! - EBOOT.PBP packages, including PSone Classics spanning several discs
+//!
+//! A UMD game names itself in `PSP_GAME/PARAM.SFO` (see [`crate::sfo`]): its
+//! `DISC_ID` is the product code Redump lists, "ULUS10041" for "ULUS-10041".
+//! `UMD_DATA.BIN` in the root starts with the same code and stands in when
+//! PARAM.SFO is missing. Compressed images are analyzed and hashed as the
+//! ISO they hold.
+//!
+//! An EBOOT.PBP starts with the offsets of its eight sections, the first
+//! being its PARAM.SFO and the last DATA.PSAR. A PSone Classic (category
+//! "ME") keeps its PS1 disc images in DATA.PSAR: one `PSISOIMG0000` image, or
+//! a `PSTITLEIMG000000` table of up to five, each naming its disc's serial.
+
+use std::io::SeekFrom;
 
 use retro_junk_core::ReadSeek;
+use retro_junk_core::disc::iso9660::Iso9660;
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, FileHashes, HashAlgorithms, ParseLimits, Platform, Region,
+    RomAnalyzer, RomIdentification,
+};
+
+use crate::sfo::{self, ParamSfo};
+use crate::sony_disc::{self, DiscFormat};
+
+/// EBOOT.PBP magic.
+pub const PBP_MAGIC: &[u8; 4] = b"\0PBP";
+
+/// EBOOT.PBP header: magic, version and eight section offsets.
+const PBP_HEADER_SIZE: usize = 0x28;
+
+/// PVD system identifier of a UMD.
+const PSP_SYSTEM_ID: &str = "PSP GAME";
+
+/// DATA.PSAR magic of a single-disc PSone Classic.
+const PSISO_MAGIC: &[u8; 12] = b"PSISOIMG0000";
+
+/// DATA.PSAR magic of a multi-disc PSone Classic.
+const PSTITLE_MAGIC: &[u8; 16] = b"PSTITLEIMG000000";
+
+/// Offset of the disc table in a `PSTITLEIMG` header.
+const PSTITLE_DISC_TABLE: u64 = 0x200;
 
-use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+/// Offset of the disc's serial ("_SLUS_00594") in a `PSISOIMG` header.
+const PSISO_DISC_ID: u64 = 0x400;
+
+/// Most discs a multi-disc PSone Classic can hold.
+const MAX_PS1_DISCS: usize = 5;
+
+/// PARAM.SFO categories, by code.
+const CATEGORIES: &[(&str, &str)] = &[
+    ("UG", "UMD game"),
+    ("UV", "UMD video"),
+    ("UA", "UMD audio"),
+    ("EG", "PSN game"),
+    ("MG", "Memory Stick game"),
+    ("ME", "PSone Classic"),
+    ("PG", "Game update"),
+    ("MA", "Application"),
+];
 
 /// Analyzer for PlayStation Portable disc images.
 #[derive(Debug, Default)]
 pub struct PspAnalyzer;
 
+impl PspAnalyzer {
+    /// Analyze a UMD ISO image.
+    fn analyze_iso(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let source = |lba| sony_disc::read_sector_data(reader, lba, DiscFormat::Iso2048);
+        let mut fs = Iso9660::open(source, &options.limits)?;
+
+        let pvd = fs.pvd().clone();
+        if pvd.system_identifier != PSP_SYSTEM_ID {
+            return Err(AnalysisError::invalid_format(format!(
+                "Not a UMD (system ID: '{}')",
+                pvd.system_identifier
+            )));
+        }
+
+        let mut id = RomIdentification::new().with_platform(Platform::Psp);
+        id.file_size = Some(file_size);
+        id.extra
+            .insert("format".into(), DiscFormat::Iso2048.name().into());
+        id.extra.insert(
+            "detected_extension".into(),
+            DiscFormat::Iso2048.extension().into(),
+        );
+        if !pvd.volume_identifier.is_empty() {
+            id.internal_name = Some(pvd.volume_identifier.clone());
+        }
+        id.build_date = pvd.creation_date;
+        id.expected_size = Some(pvd.image_size(DiscFormat::Iso2048.sector_size()));
+
+        // UMD_DATA.BIN: "ULUS-10041|<hash>|0001|G"
+        if let Ok(umd_data) = fs.read_path("UMD_DATA.BIN")
+            && let Some(code) = String::from_utf8_lossy(&umd_data).split('|').next()
+            && !code.trim().is_empty()
+        {
+            set_serial(&mut id, code);
+        }
+
+        let sfo = fs
+            .read_path("PSP_GAME/PARAM.SFO")
+            .or_else(|_| fs.read_path("UMD_VIDEO/PARAM.SFO"));
+        if let Ok(sfo) = sfo
+            && let Ok(sfo) = ParamSfo::parse(&sfo)
+        {
+            apply_sfo(&sfo, &mut id);
+        }
+        Ok(id)
+    }
+
+    /// Analyze a CSO/ZSO or DAX image as the ISO it decompresses to.
+    fn analyze_compressed(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+        format: DiscFormat,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let mut image = sony_disc::open_compressed_iso(reader, format, &options.limits)?;
+        let mut id = self.analyze_iso(&mut image, options)?;
+
+        // The PVD's size describes the ISO, not the compressed file
+        if let Some(iso_size) = id.file_size {
+            id.extra
+                .insert("uncompressed_size".into(), iso_size.to_string());
+        }
+        id.file_size = Some(file_size);
+        id.expected_size = None;
+        id.extra.insert("format".into(), format.name().into());
+        id.extra
+            .insert("detected_extension".into(), format.extension().into());
+        Ok(id)
+    }
+
+    /// Analyze an EBOOT.PBP package.
+    fn analyze_pbp(
+        &self,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let mut header = [0u8; PBP_HEADER_SIZE];
+        reader
+            .read_exact(&mut header)
+            .map_err(|_| AnalysisError::TooSmall {
+                expected: PBP_HEADER_SIZE as u64,
+                actual: file_size,
+            })?;
+        if &header[..4] != PBP_MAGIC {
+            return Err(AnalysisError::invalid_magic(0, PBP_MAGIC, &header[..4]));
+        }
+        let section = |i: usize| {
+            let at = 0x08 + 4 * i;
+            u64::from(u32::from_le_bytes(header[at..at + 4].try_into().unwrap()))
+        };
+        let (sfo_start, sfo_end, psar_start) = (section(0), section(1), section(7));
+        if sfo_end < sfo_start || sfo_end > file_size || psar_start > file_size {
+            return Err(AnalysisError::corrupted_header(
+                "PBP section offsets lie outside the file",
+            ));
+        }
+
+        let mut id = RomIdentification::new().with_platform(Platform::Psp);
+        id.file_size = Some(file_size);
+        id.extra.insert("format".into(), "PBP".into());
+        id.extra.insert("detected_extension".into(), "pbp".into());
+
+        let sfo_len = options
+            .limits
+            .check_alloc(sfo_end - sfo_start, "PBP PARAM.SFO")?;
+        let mut sfo = vec![0u8; sfo_len];
+        reader.seek(SeekFrom::Start(sfo_start))?;
+        reader.read_exact(&mut sfo)?;
+        let sfo = ParamSfo::parse(&sfo)?;
+        apply_sfo(&sfo, &mut id);
+
+        if sfo.get_str("CATEGORY") == Some("ME") {
+            let serials = read_ps1_disc_serials(reader, psar_start)?;
+            if !serials.is_empty() {
+                id.extra
+                    .insert("ps1_discs".into(), serials.len().to_string());
+                id.extra.insert("ps1_serials".into(), serials.join(", "));
+            }
+        }
+        Ok(id)
+    }
+}
+
 impl RomAnalyzer for PspAnalyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "PSP disc analysis not yet implemented",
-        ))
+        if has_pbp_magic(reader) {
+            return self.analyze_pbp(reader, options);
+        }
+        let format = sony_disc::detect_disc_format(reader)?;
+
+        match format {
+            DiscFormat::Iso2048 => self.analyze_iso(reader, options),
+            DiscFormat::Cso | DiscFormat::Zso | DiscFormat::Dax => {
+                self.analyze_compressed(reader, options, format)
+            }
+            DiscFormat::RawSector2352 | DiscFormat::Cue | DiscFormat::Chd => {
+                Err(AnalysisError::unsupported(format!(
+                    "{} images are not used for UMDs",
+                    format.name()
+                )))
+            }
+        }
     }
 
     fn platform(&self) -> Platform {
@@ -30,18 +228,186 @@ impl RomAnalyzer for PspAnalyzer {
     }
 
     fn file_extensions(&self) -> &'static [&'static str] {
-        &["iso", "cso", "pbp", "dax"]
+        &["iso", "cso", "zso", "pbp", "dax"]
+    }
+
+    fn sniff_len(&self) -> usize {
+        sony_disc::SNIFF_LEN
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        if has_pbp_magic(reader) {
+            return true;
+        }
+        let format = match sony_disc::detect_disc_format(reader) {
+            Ok(f) => f,
+            Err(_) => return false,
+        };
+
+        match format {
+            DiscFormat::Iso2048 => is_psp_image(reader),
+            // PS2 games share the compressed formats; only a UMD inside will do
+            DiscFormat::Cso | DiscFormat::Zso | DiscFormat::Dax => {
+                sony_disc::open_compressed_iso(reader, format, &ParseLimits::hardened())
+                    .is_ok_and(|mut image| is_psp_image(&mut image))
+            }
+            DiscFormat::RawSector2352 | DiscFormat::Cue | DiscFormat::Chd => false,
+        }
     }
 
     fn dat_source(&self) -> retro_junk_core::DatSource {
         retro_junk_core::DatSource::Redump
     }
 
+    fn compute_container_hashes(
+        &self,
+        reader: &mut dyn ReadSeek,
+        algorithms: HashAlgorithms,
+        _file_path: Option<&std::path::Path>,
+    ) -> Result<Option<FileHashes>, AnalysisError> {
+        if has_pbp_magic(reader) {
+            return Ok(None);
+        }
+        match sony_disc::detect_disc_format(reader)? {
+            format @ (DiscFormat::Cso | DiscFormat::Zso | DiscFormat::Dax) => Ok(Some(
+                sony_disc::hash_compressed_iso(reader, format, algorithms)?,
+            )),
+            _ => Ok(None),
+        }
+    }
+
     fn dat_names(&self) -> &'static [&'static str] {
         &["Sony - PlayStation Portable"]
     }
+
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(sony_disc::SERIAL_PATTERN)
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        sony_disc::normalize_serial(serial)
+    }
+
+    fn expects_serial(&self) -> bool {
+        true
+    }
+
+    fn extract_dat_game_code(&self, serial: &str) -> Option<String> {
+        // Redump DATs use the full product code (e.g., "ULUS-10041")
+        Some(serial.to_string())
+    }
+}
+
+/// Whether the file starts with the EBOOT.PBP magic. The reader is left at
+/// the start.
+fn has_pbp_magic(reader: &mut dyn ReadSeek) -> bool {
+    let mut magic = [0u8; 4];
+    let found = reader.seek(SeekFrom::Start(0)).is_ok()
+        && reader.read_exact(&mut magic).is_ok()
+        && &magic == PBP_MAGIC;
+    let _ = reader.seek(SeekFrom::Start(0));
+    found
+}
+
+/// Whether an ISO image is a UMD.
+fn is_psp_image(reader: &mut dyn ReadSeek) -> bool {
+    sony_disc::read_pvd(reader, DiscFormat::Iso2048)
+        .is_ok_and(|pvd| pvd.system_identifier == PSP_SYSTEM_ID)
+}
+
+/// Apply PARAM.SFO fields to the identification.
+fn apply_sfo(sfo: &ParamSfo, id: &mut RomIdentification) {
+    if let Some(title) = sfo.get_str("TITLE") {
+        id.internal_name = Some(title.to_string());
+    }
+    if let Some(code) = sfo.get_str("DISC_ID").or_else(|| sfo.get_str("TITLE_ID")) {
+        set_serial(id, code);
+    }
+    if let Some(version) = sfo
+        .get_str("DISC_VERSION")
+        .or_else(|| sfo.get_str("APP_VER"))
+    {
+        id.version = Some(version.to_string());
+    }
+    if let Some(code) = sfo.get_str("CATEGORY") {
+        let category = CATEGORIES
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map_or(code, |(_, name)| name);
+        id.extra.insert("category".into(), category.into());
+    }
+    if let Some(firmware) = sfo.get_str("PSP_SYSTEM_VER") {
+        id.extra
+            .insert("required_firmware".into(), firmware.to_string());
+    }
+    if let (Some(number), Some(total)) = (sfo.get_int("DISC_NUMBER"), sfo.get_int("DISC_TOTAL"))
+        && total > 1
+    {
+        id.extra
+            .insert("disc".into(), format!("{} of {}", number, total));
+    }
+    if let Some(level) = sfo.get_int("PARENTAL_LEVEL") {
+        id.extra.insert("parental_level".into(), level.to_string());
+    }
+}
+
+/// Record a product code as the serial, along with the region it implies.
+fn set_serial(id: &mut RomIdentification, code: &str) {
+    let serial = sfo::product_code_to_serial(code);
+    id.regions = serial_to_region(&serial).into_iter().collect();
+    id.serial_number = Some(serial);
+}
+
+/// Map a product code to a region. UMD ("ULUS") and PSN ("NPUH") codes carry
+/// it in their third letter; PSone Classics keep their PS1 serials.
+fn serial_to_region(serial: &str) -> Option<Region> {
+    match serial.as_bytes().first()? {
+        b'U' | b'N' => sfo::product_code_region(serial),
+        _ => sony_disc::serial_to_region(serial),
+    }
 }
+
+/// Read the serials of the PS1 discs in a PSone Classic's DATA.PSAR.
+fn read_ps1_disc_serials(
+    reader: &mut dyn ReadSeek,
+    psar_start: u64,
+) -> Result<Vec<String>, AnalysisError> {
+    let mut magic = [0u8; 16];
+    reader.seek(SeekFrom::Start(psar_start))?;
+    if reader.read_exact(&mut magic).is_err() {
+        return Ok(Vec::new());
+    }
+
+    let discs = if magic.starts_with(PSISO_MAGIC) {
+        vec![psar_start]
+    } else if &magic == PSTITLE_MAGIC {
+        let mut table = [0u8; 4 * MAX_PS1_DISCS];
+        reader.seek(SeekFrom::Start(psar_start + PSTITLE_DISC_TABLE))?;
+        reader.read_exact(&mut table)?;
+        table
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .take_while(|&offset| offset != 0)
+            .map(|offset| psar_start + u64::from(offset))
+            .collect()
+    } else {
+        return Ok(Vec::new());
+    };
+
+    let mut serials = Vec::new();
+    for disc in discs {
+        let mut disc_id = [0u8; 11];
+        reader.seek(SeekFrom::Start(disc + PSISO_DISC_ID))?;
+        reader.read_exact(&mut disc_id)?;
+        // "_SLUS_00594" names the disc's boot file
+        let disc_id = String::from_utf8_lossy(&disc_id);
+        if let Some(serial) = sony_disc::extract_serial(disc_id.trim_start_matches('_')) {
+            serials.push(serial);
+        }
+    }
+    Ok(serials)
+}
+
+#[cfg(test)]
+#[path = "tests/psp_tests.rs"]
+mod tests;
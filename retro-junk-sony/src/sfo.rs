@@ -0,0 +1,171 @@
+//! PARAM.SFO system file objects.
+//!
+//! Every PSP, PS3 and Vita title carries a PARAM.SFO: a small key/value table
+//! with the title, the product code (`DISC_ID` or `TITLE_ID`), the version
+//! and the content category. The 20-byte header gives the offsets of a key
+//! table (NUL-terminated names) and a data table, then the entry count; each
+//! 16-byte index entry gives its key's offset, the value's format, its used
+//! and reserved lengths and its offset in the data table.
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Region};
+
+/// PARAM.SFO magic.
+pub const SFO_MAGIC: &[u8; 4] = b"\0PSF";
+
+/// Largest PARAM.SFO read; real ones are about 1 KB.
+pub const MAX_SFO_SIZE: u64 = 0x1_0000;
+
+/// Size of the header before the index.
+const HEADER_SIZE: usize = 0x14;
+
+/// Size of one index entry.
+const INDEX_ENTRY_SIZE: usize = 0x10;
+
+/// Value formats.
+const FMT_UTF8_SPECIAL: u16 = 0x0004;
+const FMT_UTF8: u16 = 0x0204;
+const FMT_INT32: u16 = 0x0404;
+
+/// Check that a `size`-byte PARAM.SFO may be read before reading it.
+pub fn check_size(size: u64, options: &AnalysisOptions) -> Result<(), AnalysisError> {
+    if size > MAX_SFO_SIZE {
+        return Err(AnalysisError::corrupted_header(format!(
+            "PARAM.SFO is {size} bytes"
+        )));
+    }
+    options.limits.check_alloc(size, "PARAM.SFO")?;
+    Ok(())
+}
+
+/// A PARAM.SFO value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SfoValue {
+    Text(String),
+    Int(u32),
+}
+
+/// A parsed PARAM.SFO, keeping its entries in file order.
+#[derive(Debug, Clone, Default)]
+pub struct ParamSfo {
+    entries: Vec<(String, SfoValue)>,
+}
+
+impl ParamSfo {
+    /// Parse a whole PARAM.SFO file.
+    pub fn parse(data: &[u8]) -> Result<Self, AnalysisError> {
+        if data.len() < HEADER_SIZE {
+            return Err(AnalysisError::TooSmall {
+                expected: HEADER_SIZE as u64,
+                actual: data.len() as u64,
+            });
+        }
+        if &data[..4] != SFO_MAGIC {
+            return Err(AnalysisError::invalid_magic(0, SFO_MAGIC, &data[..4]));
+        }
+        let word = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap()) as usize;
+        let key_table = word(0x08);
+        let data_table = word(0x0C);
+        let count = word(0x10);
+
+        let index_end = count
+            .checked_mul(INDEX_ENTRY_SIZE)
+            .and_then(|len| len.checked_add(HEADER_SIZE))
+            .filter(|&end| end <= data.len())
+            .ok_or_else(|| {
+                AnalysisError::corrupted_header(format!(
+                    "PARAM.SFO index of {count} entries is truncated"
+                ))
+            })?;
+
+        let mut entries = Vec::with_capacity(count);
+        for entry in data[HEADER_SIZE..index_end].chunks_exact(INDEX_ENTRY_SIZE) {
+            let key_offset = u16::from_le_bytes([entry[0], entry[1]]) as usize;
+            let format = u16::from_le_bytes([entry[2], entry[3]]);
+            let len = u32::from_le_bytes(entry[4..8].try_into().unwrap()) as usize;
+            let data_offset = u32::from_le_bytes(entry[12..16].try_into().unwrap()) as usize;
+
+            let key = data
+                .get(key_table + key_offset..)
+                .and_then(|rest| rest.split(|&b| b == 0).next())
+                .ok_or_else(|| {
+                    AnalysisError::corrupted_header("PARAM.SFO key is outside the file")
+                })?;
+            let value = data
+                .get(data_table + data_offset..)
+                .and_then(|rest| rest.get(..len))
+                .ok_or_else(|| {
+                    AnalysisError::corrupted_header(format!(
+                        "PARAM.SFO value of {} is outside the file",
+                        String::from_utf8_lossy(key)
+                    ))
+                })?;
+            let value = match format {
+                FMT_UTF8 | FMT_UTF8_SPECIAL => {
+                    let text = value.split(|&b| b == 0).next().unwrap_or_default();
+                    SfoValue::Text(String::from_utf8_lossy(text).into_owned())
+                }
+                FMT_INT32 if len == 4 => {
+                    SfoValue::Int(u32::from_le_bytes(value.try_into().unwrap()))
+                }
+                _ => continue,
+            };
+            entries.push((String::from_utf8_lossy(key).into_owned(), value));
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&SfoValue> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// A text value, trimmed; blank values count as missing.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            SfoValue::Text(text) => Some(text.trim()).filter(|t| !t.is_empty()),
+            SfoValue::Int(_) => None,
+        }
+    }
+
+    pub fn get_int(&self, key: &str) -> Option<u32> {
+        match self.get(key)? {
+            SfoValue::Int(value) => Some(*value),
+            SfoValue::Text(_) => None,
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &SfoValue)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+/// Format a PARAM.SFO product code ("ULUS10041") the way Redump lists it
+/// ("ULUS-10041"). Codes that aren't four letters and five digits are
+/// returned as they are.
+pub fn product_code_to_serial(code: &str) -> String {
+    let code = code.trim();
+    let bytes = code.as_bytes();
+    if bytes.len() == 9
+        && bytes[..4].iter().all(u8::is_ascii_alphabetic)
+        && bytes[4..].iter().all(u8::is_ascii_digit)
+    {
+        format!("{}-{}", code[..4].to_ascii_uppercase(), &code[4..])
+    } else {
+        code.to_string()
+    }
+}
+
+/// The region named by the third letter of a UMD, Blu-ray or PSN product
+/// code ("ULUS", "BLES", "NPJB"). Asian codes have no matching region.
+pub fn product_code_region(code: &str) -> Option<Region> {
+    match code.as_bytes().get(2)? {
+        b'U' => Some(Region::Usa),
+        b'E' => Some(Region::Europe),
+        b'J' => Some(Region::Japan),
+        b'K' => Some(Region::Korea),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/sfo_tests.rs"]
+mod tests;
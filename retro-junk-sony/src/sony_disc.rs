@@ -1,40 +1,42 @@
 //! Sony disc parsing utilities.
 //!
-//! Handles ISO 9660 filesystem parsing, CD sector formats, SYSTEM.CNF extraction,
-//! serial/region detection, CUE sheet parsing, and CHD disc reading.
+//! Handles CD sector formats, SYSTEM.CNF extraction, serial/region detection,
+//! CUE sheet parsing, and CHD disc reading. The ISO 9660 filesystem itself is
+//! read with [`retro_junk_core::disc::iso9660`], over the sector readers here.
 //! Shared by PS1, PS2, and other Sony disc-based console analyzers.
 
-use std::io::SeekFrom;
+use std::io::{Read, Seek, SeekFrom};
 
-use retro_junk_core::{AnalysisError, Region};
+use retro_junk_core::disc::cd::{self, CHD_FRAME_SIZE, CHD_MAGIC, RAW_SECTOR_SIZE, SYNC_PATTERN};
+use retro_junk_core::disc::cue::{self, CueSheet};
+use retro_junk_core::disc::iso9660::Iso9660;
+use retro_junk_core::{AnalysisError, BlockImage, BlockSource, ParseLimits, Region};
+
+use crate::ciso::{self, CisoKind};
+use crate::dax::{self, DAX_MAGIC};
+
+pub use retro_junk_core::disc::iso9660::PrimaryVolumeDescriptor;
 
 // ---------------------------------------------------------------------------
 // Constants
 // ---------------------------------------------------------------------------
 
-/// CD sync pattern at the start of every raw (2352-byte) sector.
-pub const CD_SYNC_PATTERN: [u8; 12] = [
-    0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00,
-];
-
 /// Standard ISO 9660 sector size (user data only).
 const ISO_SECTOR_SIZE: u64 = 2048;
 
-/// Raw CD sector size (sync + header + subheader + data + EDC + ECC).
-const RAW_SECTOR_SIZE: u64 = 2352;
-
 /// Offset to user data within a Mode 2 Form 1 raw sector.
 /// 12 (sync) + 4 (header) + 8 (subheader) = 24.
 const MODE2_FORM1_DATA_OFFSET: u64 = 24;
 
-/// ISO 9660 Primary Volume Descriptor is always at sector 16.
-const PVD_SECTOR: u64 = 16;
+/// Offset to user data within a Mode 1 raw sector: 12 (sync) + 4 (header).
+const MODE1_DATA_OFFSET: u64 = 16;
 
-/// CHD file magic bytes.
-pub const CHD_MAGIC: &[u8; 8] = b"MComprHD";
+/// ISO 9660 Primary Volume Descriptor is always at sector 16.
+pub(crate) const PVD_SECTOR: u64 = 16;
 
-/// CD sector size within CHD: raw sector (2352) + subchannel (96) = 2448.
-const CHD_CD_SECTOR_SIZE: u32 = 2448;
+/// Bytes read when sniffing a disc image: the first 256 raw sectors, which
+/// covers the PVD, the root directory and SYSTEM.CNF on mastered discs.
+pub(crate) const SNIFF_LEN: usize = 256 * RAW_SECTOR_SIZE as usize;
 
 // ---------------------------------------------------------------------------
 // Disc format detection
@@ -51,6 +53,12 @@ pub enum DiscFormat {
     Cue,
     /// MAME Compressed Hunks of Data.
     Chd,
+    /// Deflate-compressed ISO blocks.
+    Cso,
+    /// LZ4-compressed ISO blocks.
+    Zso,
+    /// Zlib-compressed ISO frames.
+    Dax,
 }
 
 impl DiscFormat {
@@ -60,6 +68,18 @@ impl DiscFormat {
             Self::RawSector2352 => "Raw BIN (2352)",
             Self::Cue => "CUE Sheet",
             Self::Chd => "CHD",
+            Self::Cso => "CSO",
+            Self::Zso => "ZSO",
+            Self::Dax => "DAX",
+        }
+    }
+
+    /// Bytes per sector of the image, or of the ISO a compressed image
+    /// holds.
+    pub fn sector_size(&self) -> u64 {
+        match self {
+            Self::RawSector2352 => 2352,
+            _ => 2048,
         }
     }
 
@@ -69,6 +89,9 @@ impl DiscFormat {
             Self::RawSector2352 => "bin",
             Self::Cue => "cue",
             Self::Chd => "chd",
+            Self::Cso => "cso",
+            Self::Zso => "zso",
+            Self::Dax => "dax",
         }
     }
 }
@@ -95,13 +118,22 @@ pub fn detect_disc_format(
         return Ok(DiscFormat::Chd);
     }
 
+    match CisoKind::from_magic(&buf) {
+        Some(CisoKind::Cso) => return Ok(DiscFormat::Cso),
+        Some(CisoKind::Zso) => return Ok(DiscFormat::Zso),
+        None => {}
+    }
+
+    if buf[..4] == *DAX_MAGIC {
+        return Ok(DiscFormat::Dax);
+    }
+
     // Check raw sector sync pattern
-    if buf[..12] == CD_SYNC_PATTERN {
+    if buf[..12] == SYNC_PATTERN {
         return Ok(DiscFormat::RawSector2352);
     }
 
-    // Check for CUE sheet: scan for common CUE keywords in what looks like text
-    if looks_like_cue(reader)? {
+    if cue::sniff(reader)? {
         return Ok(DiscFormat::Cue);
     }
 
@@ -114,38 +146,19 @@ pub fn detect_disc_format(
         return Ok(DiscFormat::Iso2048);
     }
 
+    // A raw BIN whose first sectors were blanked or damaged still has the
+    // sync pattern on its PVD sector
+    if raw_sector_mode(reader, PVD_SECTOR)?.is_some() {
+        reader.seek(SeekFrom::Start(0))?;
+        return Ok(DiscFormat::RawSector2352);
+    }
+
     reader.seek(SeekFrom::Start(0))?;
     Err(AnalysisError::invalid_format(
         "Not a recognized disc format",
     ))
 }
 
-/// Check if reader content looks like a CUE sheet.
-fn looks_like_cue(reader: &mut dyn retro_junk_core::ReadSeek) -> Result<bool, AnalysisError> {
-    reader.seek(SeekFrom::Start(0))?;
-    let mut buf = [0u8; 512];
-    let n = reader.read(&mut buf)?;
-    reader.seek(SeekFrom::Start(0))?;
-
-    if n == 0 {
-        return Ok(false);
-    }
-
-    // CUE files are text; check for non-text bytes (ignoring common whitespace)
-    let slice = &buf[..n];
-    let has_binary = slice
-        .iter()
-        .any(|&b| b < 0x09 || (b > 0x0D && b < 0x20 && b != 0x1A));
-    if has_binary {
-        return Ok(false);
-    }
-
-    let text = String::from_utf8_lossy(slice).to_uppercase();
-    let has_file = text.contains("FILE ");
-    let has_track = text.contains("TRACK ");
-    Ok(has_file && has_track)
-}
-
 // ---------------------------------------------------------------------------
 // Sector reading
 // ---------------------------------------------------------------------------
@@ -158,10 +171,18 @@ pub fn read_sector_data(
 ) -> Result<[u8; 2048], AnalysisError> {
     let offset = match format {
         DiscFormat::Iso2048 => sector * ISO_SECTOR_SIZE,
-        DiscFormat::RawSector2352 => sector * RAW_SECTOR_SIZE + MODE2_FORM1_DATA_OFFSET,
+        // PlayStation discs are Mode 2, but a BIN without its CUE may be a
+        // Mode 1 dump; the sector header says which
+        DiscFormat::RawSector2352 => {
+            let data_offset = match raw_sector_mode(reader, sector)? {
+                Some(1) => MODE1_DATA_OFFSET,
+                _ => MODE2_FORM1_DATA_OFFSET,
+            };
+            sector * RAW_SECTOR_SIZE + data_offset
+        }
         _ => {
             return Err(AnalysisError::unsupported(
-                "Cannot read sectors directly from CUE/CHD format",
+                "Cannot read sectors directly from CUE/CHD/CSO/DAX format",
             ));
         }
     };
@@ -172,7 +193,7 @@ pub fn read_sector_data(
         if e.kind() == std::io::ErrorKind::UnexpectedEof {
             AnalysisError::corrupted_header(format!("Sector {} is beyond end of image", sector))
         } else {
-            AnalysisError::Io(e)
+            AnalysisError::io_at(e, offset)
         }
     })?;
     Ok(data)
@@ -182,197 +203,39 @@ pub fn read_sector_data(
 // ISO 9660 Primary Volume Descriptor
 // ---------------------------------------------------------------------------
 
-/// Parsed ISO 9660 Primary Volume Descriptor.
-#[derive(Debug, Clone)]
-pub struct PrimaryVolumeDescriptor {
-    /// System identifier (offset 8, 32 bytes). e.g. "PLAYSTATION"
-    pub system_identifier: String,
-    /// Volume identifier (offset 40, 32 bytes).
-    pub volume_identifier: String,
-    /// Volume space size in sectors (offset 80, LE u32).
-    pub volume_space_size: u32,
-    /// LBA of root directory extent (from root dir record at offset 156).
-    pub root_dir_extent_lba: u32,
-    /// Size of root directory data in bytes.
-    pub root_dir_data_length: u32,
-}
-
 /// Read and parse the ISO 9660 Primary Volume Descriptor from sector 16.
 pub fn read_pvd(
     reader: &mut dyn retro_junk_core::ReadSeek,
     format: DiscFormat,
 ) -> Result<PrimaryVolumeDescriptor, AnalysisError> {
-    let sector_data = read_sector_data(reader, PVD_SECTOR, format)?;
-
-    // Byte 0: type must be 0x01 (Primary Volume Descriptor)
-    if sector_data[0] != 0x01 {
-        return Err(AnalysisError::invalid_format(format!(
-            "Expected PVD type 0x01, got 0x{:02X}",
-            sector_data[0]
-        )));
-    }
-
-    // Bytes 1-5: "CD001"
-    if &sector_data[1..6] != b"CD001" {
-        return Err(AnalysisError::invalid_format(
-            "Missing CD001 signature in PVD",
-        ));
-    }
-
-    let system_identifier = read_str_a(&sector_data[8..40]);
-    let volume_identifier = read_str_a(&sector_data[40..72]);
-
-    // Volume space size: both-endian u32 at offset 80 (LE at 80, BE at 84)
-    let volume_space_size = u32::from_le_bytes([
-        sector_data[80],
-        sector_data[81],
-        sector_data[82],
-        sector_data[83],
-    ]);
-
-    // Root directory record at offset 156, 34 bytes
-    let root_record = &sector_data[156..190];
-    let root_dir_extent_lba = u32::from_le_bytes([
-        root_record[2],
-        root_record[3],
-        root_record[4],
-        root_record[5],
-    ]);
-    let root_dir_data_length = u32::from_le_bytes([
-        root_record[10],
-        root_record[11],
-        root_record[12],
-        root_record[13],
-    ]);
-
-    Ok(PrimaryVolumeDescriptor {
-        system_identifier,
-        volume_identifier,
-        volume_space_size,
-        root_dir_extent_lba,
-        root_dir_data_length,
-    })
-}
-
-/// Read a padded ISO 9660 string (strip trailing spaces).
-fn read_str_a(bytes: &[u8]) -> String {
-    let s = std::str::from_utf8(bytes).unwrap_or("");
-    s.trim_end().to_string()
+    PrimaryVolumeDescriptor::parse(&read_sector_data(reader, PVD_SECTOR, format)?)
 }
 
 // ---------------------------------------------------------------------------
-// ISO 9660 directory parsing
+// ISO 9660 directory lookup
 // ---------------------------------------------------------------------------
 
-/// A parsed ISO 9660 directory record.
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-pub struct DirectoryRecord {
-    pub extent_lba: u32,
-    pub data_length: u32,
-    pub file_flags: u8,
-    pub file_identifier: String,
-}
-
 /// Find a file by name in the root directory and return its contents.
+///
+/// The directory's sector count and the file's size come from the disc, so
+/// both are checked against `limits` before they're acted on.
 pub fn find_file_in_root(
     reader: &mut dyn retro_junk_core::ReadSeek,
     format: DiscFormat,
     pvd: &PrimaryVolumeDescriptor,
     filename: &str,
+    limits: &ParseLimits,
 ) -> Result<Vec<u8>, AnalysisError> {
-    let target_upper = filename.to_uppercase();
-
-    // Read root directory sectors
-    let dir_sectors = (pvd.root_dir_data_length as u64).div_ceil(2048);
-
-    for sector_offset in 0..dir_sectors {
-        let sector = pvd.root_dir_extent_lba as u64 + sector_offset;
-        let sector_data = read_sector_data(reader, sector, format)?;
-
-        let mut pos = 0;
-        while pos < 2048 {
-            let record_len = sector_data[pos] as usize;
-            if record_len == 0 {
-                break; // No more records in this sector
-            }
-            if pos + record_len > 2048 {
-                break;
-            }
-
-            let record = &sector_data[pos..pos + record_len];
-            if let Some(dir_rec) = parse_directory_record(record) {
-                // Compare filename (strip ";1" version suffix)
-                let id_upper = dir_rec.file_identifier.to_uppercase();
-                let id_stripped = id_upper.split(';').next().unwrap_or(&id_upper);
-
-                if id_stripped == target_upper {
-                    // Found it — read the file content
-                    return read_file_content(reader, format, &dir_rec);
-                }
-            }
-
-            pos += record_len;
-        }
-    }
-
-    Err(AnalysisError::other(format!(
-        "File '{}' not found in root directory",
-        filename
-    )))
-}
-
-/// Parse a single ISO 9660 directory record.
-fn parse_directory_record(data: &[u8]) -> Option<DirectoryRecord> {
-    let record_len = data[0] as usize;
-    if record_len < 33 {
-        return None;
-    }
-
-    let extent_lba = u32::from_le_bytes([data[2], data[3], data[4], data[5]]);
-    let data_length = u32::from_le_bytes([data[10], data[11], data[12], data[13]]);
-    let file_flags = data[25];
-    let id_len = data[32] as usize;
-
-    if 33 + id_len > record_len {
-        return None;
-    }
-
-    let file_identifier = if id_len == 1 && data[33] == 0x00 {
-        ".".to_string()
-    } else if id_len == 1 && data[33] == 0x01 {
-        "..".to_string()
-    } else {
-        String::from_utf8_lossy(&data[33..33 + id_len]).to_string()
-    };
-
-    Some(DirectoryRecord {
-        extent_lba,
-        data_length,
-        file_flags,
-        file_identifier,
-    })
-}
-
-/// Read the full content of a file given its directory record.
-fn read_file_content(
-    reader: &mut dyn retro_junk_core::ReadSeek,
-    format: DiscFormat,
-    record: &DirectoryRecord,
-) -> Result<Vec<u8>, AnalysisError> {
-    let mut result = Vec::with_capacity(record.data_length as usize);
-    let sectors_needed = (record.data_length as u64).div_ceil(2048);
-    let mut remaining = record.data_length as usize;
-
-    for i in 0..sectors_needed {
-        let sector = record.extent_lba as u64 + i;
-        let sector_data = read_sector_data(reader, sector, format)?;
-        let to_copy = remaining.min(2048);
-        result.extend_from_slice(&sector_data[..to_copy]);
-        remaining -= to_copy;
-    }
-
-    Ok(result)
+    let source = |lba| read_sector_data(reader, lba, format);
+    let mut fs = Iso9660::with_pvd(source, pvd.clone(), limits);
+    let entry = fs
+        .read_dir(&pvd.root())?
+        .into_iter()
+        .find(|e| e.matches(filename))
+        .ok_or_else(|| {
+            AnalysisError::other(format!("File '{}' not found in root directory", filename))
+        })?;
+    fs.read_file(&entry)
 }
 
 // ---------------------------------------------------------------------------
@@ -485,6 +348,34 @@ pub fn extract_serial(boot_path: &str) -> Option<String> {
     }
 }
 
+/// A canonical Sony serial: four letters, a hyphen and five digits, as
+/// Redump lists them ("SLUS-00594", "ULUS-10041", "BLES-00001").
+pub const SERIAL_PATTERN: &str = r"^[A-Z]{4}-\d{5}$";
+
+/// The canonical form of a Sony serial: "SLUS-00594".
+///
+/// Boot file names ("slus_005.94"), product codes ("ULUS10041") and spaced
+/// serials ("SLPS 00700") are all accepted, and shorter numbers are
+/// zero-padded to five digits. Anything else is only trimmed and uppercased.
+pub fn normalize_serial(serial: &str) -> String {
+    let serial = retro_junk_core::serial::normalize_basic(serial);
+    let Some((prefix, rest)) = serial.split_at_checked(4) else {
+        return serial;
+    };
+    let digits: String = rest
+        .trim_start_matches([' ', '_', '-'])
+        .chars()
+        .filter(|&c| c != '.')
+        .collect();
+    if !prefix.bytes().all(|b| b.is_ascii_uppercase())
+        || !(1..=5).contains(&digits.len())
+        || !digits.bytes().all(|b| b.is_ascii_digit())
+    {
+        return serial;
+    }
+    format!("{prefix}-{digits:0>5}")
+}
+
 /// Check if a 4-character prefix is a known Sony serial prefix.
 fn is_sony_serial_prefix(prefix: &str) -> bool {
     let upper = prefix.to_uppercase();
@@ -523,116 +414,21 @@ pub fn serial_to_region(serial: &str) -> Option<Region> {
 }
 
 // ---------------------------------------------------------------------------
-// CUE sheet parsing
+// CUE sheets
 // ---------------------------------------------------------------------------
 
-/// A parsed CUE sheet.
-#[derive(Debug, Clone)]
-pub struct CueSheet {
-    pub files: Vec<CueFile>,
-}
-
-/// A FILE entry in a CUE sheet.
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-pub struct CueFile {
-    pub filename: String,
-    pub file_type: String,
-    pub tracks: Vec<CueTrack>,
-}
-
-/// A TRACK entry in a CUE sheet.
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-pub struct CueTrack {
-    pub number: u8,
-    pub mode: String,
-}
-
-/// Parse a CUE sheet from its text content.
-pub fn parse_cue(content: &str) -> Result<CueSheet, AnalysisError> {
-    let mut files = Vec::new();
-    let mut current_file: Option<CueFile> = None;
-
-    for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-
-        let upper = line.to_uppercase();
-
-        if upper.starts_with("FILE ") {
-            // Save previous file entry
-            if let Some(f) = current_file.take() {
-                files.push(f);
-            }
-
-            // Parse: FILE "filename" TYPE
-            let (filename, file_type) = parse_cue_file_line(line)?;
-            current_file = Some(CueFile {
-                filename,
-                file_type,
-                tracks: Vec::new(),
-            });
-        } else if upper.starts_with("TRACK ")
-            && let Some(ref mut f) = current_file
-        {
-            let (number, mode) = parse_cue_track_line(line)?;
-            f.tracks.push(CueTrack { number, mode });
-        }
-        // Ignore INDEX, PREGAP, POSTGAP, REM, etc.
-    }
-
-    if let Some(f) = current_file.take() {
-        files.push(f);
-    }
-
-    if files.is_empty() {
+/// Read and parse a CUE sheet, requiring at least one `FILE` entry.
+pub(crate) fn read_cue_sheet(
+    reader: &mut dyn retro_junk_core::ReadSeek,
+    options: &retro_junk_core::AnalysisOptions,
+) -> Result<CueSheet, AnalysisError> {
+    let sheet = cue::parse(&options.limits.read_text(reader, "CUE sheet")?);
+    if sheet.files.is_empty() {
         return Err(AnalysisError::invalid_format(
             "CUE sheet contains no FILE entries",
         ));
     }
-
-    Ok(CueSheet { files })
-}
-
-/// Parse a FILE line: `FILE "filename.bin" BINARY`
-fn parse_cue_file_line(line: &str) -> Result<(String, String), AnalysisError> {
-    let rest = &line[5..]; // skip "FILE "
-
-    let (filename, remainder) = if let Some(after_quote) = rest.strip_prefix('"') {
-        // Quoted filename
-        let end_quote = after_quote
-            .find('"')
-            .ok_or_else(|| AnalysisError::invalid_format("Unterminated quote in CUE FILE line"))?;
-        let filename = after_quote[..end_quote].to_string();
-        let remainder = after_quote[end_quote + 1..].trim().to_string();
-        (filename, remainder)
-    } else {
-        // Unquoted filename (space-delimited)
-        let mut parts = rest.splitn(2, ' ');
-        let filename = parts.next().unwrap_or("").to_string();
-        let remainder = parts.next().unwrap_or("").trim().to_string();
-        (filename, remainder)
-    };
-
-    Ok((filename, remainder))
-}
-
-/// Parse a TRACK line: `TRACK 01 MODE2/2352`
-fn parse_cue_track_line(line: &str) -> Result<(u8, String), AnalysisError> {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() < 3 {
-        return Err(AnalysisError::invalid_format("Invalid TRACK line in CUE"));
-    }
-
-    let number: u8 = parts[1]
-        .parse()
-        .map_err(|_| AnalysisError::invalid_format("Invalid track number in CUE"))?;
-    let mode = parts[2].to_string();
-
-    Ok((number, mode))
+    Ok(sheet)
 }
 
 // ---------------------------------------------------------------------------
@@ -654,9 +450,9 @@ pub fn read_chd_sector(
 
     let hunk_size = chd.header().hunk_size() as u64;
 
-    // CHD CD images: each sector is CHD_CD_SECTOR_SIZE bytes (2448)
+    // CHD CD images: each sector is CHD_FRAME_SIZE bytes (2448)
     // The byte offset of our target sector within the logical data:
-    let sector_byte_offset = sector * CHD_CD_SECTOR_SIZE as u64;
+    let sector_byte_offset = sector * CHD_FRAME_SIZE;
 
     // Which hunk contains this offset?
     let hunk_num = sector_byte_offset / hunk_size;
@@ -696,6 +492,16 @@ pub struct ChdInfo {
     pub hunk_size: u32,
     pub total_hunks: u32,
     pub logical_size: u64,
+    /// Bytes per sector: 2448 for CDs (raw sector + subchannel), 2048 for
+    /// DVDs.
+    pub unit_bytes: u32,
+}
+
+impl ChdInfo {
+    /// Whether the image is a DVD, stored as plain 2048-byte sectors.
+    pub fn is_dvd(&self) -> bool {
+        u64::from(self.unit_bytes) == ISO_SECTOR_SIZE
+    }
 }
 
 /// Extract basic CHD file information without full decompression.
@@ -712,100 +518,118 @@ pub fn read_chd_info(reader: &mut dyn retro_junk_core::ReadSeek) -> Result<ChdIn
         hunk_size: header.hunk_size(),
         total_hunks: header.hunk_count(),
         logical_size: header.logical_bytes(),
+        unit_bytes: header.unit_bytes(),
     })
 }
 
-/// Find and read SYSTEM.CNF from a CHD disc image.
-pub fn read_system_cnf_from_chd(
-    reader: &mut dyn retro_junk_core::ReadSeek,
-) -> Result<Vec<u8>, AnalysisError> {
-    // Read PVD from sector 16
-    let pvd_data = read_chd_sector(reader, PVD_SECTOR)?;
+/// A DVD CHD image as its plain 2048-byte sectors, readable as an ISO
+/// through a [`BlockImage`].
+pub struct ChdDvdSource<R: Read + Seek> {
+    chd: chd::Chd<R>,
+    cmp_buf: Vec<u8>,
+}
 
-    // Verify PVD
-    if pvd_data[0] != 0x01 || &pvd_data[1..6] != b"CD001" {
-        return Err(AnalysisError::invalid_format(
-            "CHD: Missing PVD at sector 16",
-        ));
+impl<R: Read + Seek> ChdDvdSource<R> {
+    pub fn open(mut reader: R) -> Result<Self, AnalysisError> {
+        reader.seek(SeekFrom::Start(0))?;
+        let chd = chd::Chd::open(reader, None)
+            .map_err(|e| AnalysisError::other(format!("Failed to open CHD: {}", e)))?;
+        if u64::from(chd.header().unit_bytes()) != ISO_SECTOR_SIZE {
+            return Err(AnalysisError::invalid_format(
+                "CHD does not hold a DVD image",
+            ));
+        }
+        Ok(Self {
+            chd,
+            cmp_buf: Vec::new(),
+        })
     }
+}
 
-    let system_id = read_str_a(&pvd_data[8..40]);
-    if !system_id.starts_with("PLAYSTATION") {
-        return Err(AnalysisError::invalid_format(format!(
-            "Not a PlayStation disc (system ID: '{}')",
-            system_id,
-        )));
+impl<R: Read + Seek> BlockSource for ChdDvdSource<R> {
+    fn block_size(&self) -> usize {
+        self.chd.header().hunk_size() as usize
     }
 
-    // Parse root directory record from PVD
-    let root_record = &pvd_data[156..190];
-    let root_lba = u32::from_le_bytes([
-        root_record[2],
-        root_record[3],
-        root_record[4],
-        root_record[5],
-    ]);
-    let root_size = u32::from_le_bytes([
-        root_record[10],
-        root_record[11],
-        root_record[12],
-        root_record[13],
-    ]);
-
-    // Walk root directory to find SYSTEM.CNF
-    let dir_sectors = (root_size as u64).div_ceil(2048);
-
-    for sector_offset in 0..dir_sectors {
-        let sector = root_lba as u64 + sector_offset;
-        let sector_data = read_chd_sector(reader, sector)?;
-
-        let mut pos = 0;
-        while pos < 2048 {
-            let record_len = sector_data[pos] as usize;
-            if record_len == 0 {
-                break;
-            }
-            if pos + record_len > 2048 {
-                break;
-            }
+    fn image_len(&self) -> u64 {
+        self.chd.header().logical_bytes()
+    }
 
-            let record = &sector_data[pos..pos + record_len];
-            if let Some(dir_rec) = parse_directory_record(record) {
-                let id_upper = dir_rec.file_identifier.to_uppercase();
-                let id_stripped = id_upper.split(';').next().unwrap_or(&id_upper);
-                if id_stripped == "SYSTEM.CNF" {
-                    // Read the file
-                    return read_file_from_chd(reader, &dir_rec);
-                }
-            }
+    fn read_block(&mut self, index: u64, buf: &mut Vec<u8>) -> std::io::Result<()> {
+        let hunk_size = self.block_size();
+        buf.resize(hunk_size, 0);
+        let mut hunk = self
+            .chd
+            .hunk(index as u32)
+            .map_err(|e| std::io::Error::other(format!("CHD hunk {}: {}", index, e)))?;
+        hunk.read_hunk_in(&mut self.cmp_buf, buf)
+            .map_err(|e| std::io::Error::other(format!("CHD hunk {}: {}", index, e)))?;
+        let remaining = self.image_len() - index * hunk_size as u64;
+        buf.truncate(remaining.min(hunk_size as u64) as usize);
+        Ok(())
+    }
+}
 
-            pos += record_len;
+/// Open a DVD CHD image as a readable ISO.
+pub fn open_chd_dvd<R: Read + Seek>(
+    reader: R,
+) -> Result<BlockImage<ChdDvdSource<R>>, AnalysisError> {
+    Ok(BlockImage::new(ChdDvdSource::open(reader)?))
+}
+
+/// Open an image that compresses a plain ISO (CSO, ZSO, DAX or a DVD CHD)
+/// as that ISO.
+pub(crate) fn open_compressed_iso<'a>(
+    reader: &'a mut dyn retro_junk_core::ReadSeek,
+    format: DiscFormat,
+    limits: &ParseLimits,
+) -> Result<Box<dyn retro_junk_core::ReadSeek + 'a>, AnalysisError> {
+    Ok(match format {
+        DiscFormat::Cso | DiscFormat::Zso => Box::new(ciso::open_image(reader, limits)?),
+        DiscFormat::Dax => Box::new(dax::open_image(reader, limits)?),
+        DiscFormat::Chd => Box::new(open_chd_dvd(reader)?),
+        _ => {
+            return Err(AnalysisError::unsupported(format!(
+                "{} is not a compressed ISO",
+                format.name()
+            )));
         }
-    }
+    })
+}
 
-    Err(AnalysisError::other(
-        "SYSTEM.CNF not found in CHD root directory",
-    ))
+/// Hash the ISO a compressed image holds, which is what Redump lists.
+pub(crate) fn hash_compressed_iso(
+    reader: &mut dyn retro_junk_core::ReadSeek,
+    format: DiscFormat,
+    algorithms: retro_junk_core::HashAlgorithms,
+) -> Result<retro_junk_core::FileHashes, AnalysisError> {
+    let mut image = open_compressed_iso(reader, format, &ParseLimits::default())?;
+    let len = retro_junk_core::util::file_size(&mut image)?;
+    log::info!("Hashing the {} byte ISO in a {} image", len, format.name());
+    hash_leading_bytes(&mut image, algorithms, len)
 }
 
-/// Read file content from a CHD image given a directory record.
-fn read_file_from_chd(
+/// Find and read SYSTEM.CNF from a CHD disc image.
+pub fn read_system_cnf_from_chd(
     reader: &mut dyn retro_junk_core::ReadSeek,
-    record: &DirectoryRecord,
+    limits: &ParseLimits,
 ) -> Result<Vec<u8>, AnalysisError> {
-    let mut result = Vec::with_capacity(record.data_length as usize);
-    let sectors_needed = (record.data_length as u64).div_ceil(2048);
-    let mut remaining = record.data_length as usize;
-
-    for i in 0..sectors_needed {
-        let sector = record.extent_lba as u64 + i;
-        let sector_data = read_chd_sector(reader, sector)?;
-        let to_copy = remaining.min(2048);
-        result.extend_from_slice(&sector_data[..to_copy]);
-        remaining -= to_copy;
+    let mut fs = Iso9660::open(|lba| read_chd_sector(reader, lba), limits)?;
+
+    let system_id = &fs.pvd().system_identifier;
+    if !system_id.starts_with("PLAYSTATION") {
+        return Err(AnalysisError::invalid_format(format!(
+            "Not a PlayStation disc (system ID: '{}')",
+            system_id,
+        )));
     }
 
-    Ok(result)
+    match fs.lookup("SYSTEM.CNF")? {
+        Some(entry) if !entry.is_dir() => fs.read_file(&entry),
+        _ => Err(AnalysisError::other(
+            "SYSTEM.CNF not found in CHD root directory",
+        )),
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -868,11 +692,26 @@ fn is_data_sector(
     reader: &mut dyn retro_junk_core::ReadSeek,
     sector_index: u64,
 ) -> Result<bool, AnalysisError> {
+    Ok(raw_sector_mode(reader, sector_index)?.is_some())
+}
+
+/// The mode byte from the header of a raw 2352-byte sector, or `None` if
+/// the sector has no sync pattern (an audio sector, or past the end).
+pub fn raw_sector_mode(
+    reader: &mut dyn retro_junk_core::ReadSeek,
+    sector_index: u64,
+) -> Result<Option<u8>, AnalysisError> {
     let offset = sector_index * RAW_SECTOR_SIZE;
     reader.seek(SeekFrom::Start(offset))?;
-    let mut sync = [0u8; 12];
-    let n = reader.read(&mut sync)?;
-    Ok(n == 12 && sync == CD_SYNC_PATTERN)
+    let mut header = [0u8; 16];
+    let mut n = 0;
+    while n < header.len() {
+        match reader.read(&mut header[n..])? {
+            0 => break,
+            read => n += read,
+        }
+    }
+    Ok((n == header.len() && header[..12] == SYNC_PATTERN).then_some(header[15]))
 }
 
 // ---------------------------------------------------------------------------
@@ -887,8 +726,6 @@ pub fn hash_chd_raw_sectors(
     reader: &mut dyn retro_junk_core::ReadSeek,
     algorithms: retro_junk_core::HashAlgorithms,
 ) -> Result<retro_junk_core::FileHashes, AnalysisError> {
-    use sha1::Digest;
-
     reader.seek(SeekFrom::Start(0))?;
 
     let mut chd = chd::Chd::open(reader, None)
@@ -900,8 +737,8 @@ pub fn hash_chd_raw_sectors(
 
     let hunk_size = chd.header().hunk_size() as usize;
     let logical_bytes = chd.header().logical_bytes();
-    let total_disc_sectors = logical_bytes / CHD_CD_SECTOR_SIZE as u64;
-    let sectors_per_hunk = hunk_size / CHD_CD_SECTOR_SIZE as usize;
+    let total_disc_sectors = logical_bytes / CHD_FRAME_SIZE;
+    let sectors_per_hunk = hunk_size / CHD_FRAME_SIZE as usize;
     let total_hunks = chd.header().hunk_count();
 
     // Hash only Track 1 sectors. Fall back to all sectors if metadata unavailable.
@@ -921,21 +758,7 @@ pub fn hash_chd_raw_sectors(
         total_disc_sectors
     );
 
-    let mut crc = if algorithms.crc32() {
-        Some(crc32fast::Hasher::new())
-    } else {
-        None
-    };
-    let mut sha = if algorithms.sha1() {
-        Some(sha1::Sha1::new())
-    } else {
-        None
-    };
-    let mut md5_ctx = if algorithms.md5() {
-        Some(md5::Context::new())
-    } else {
-        None
-    };
+    let mut hasher = retro_junk_core::StreamHasher::new(algorithms);
 
     let mut hunk_buf = chd.get_hunksized_buffer();
     let mut cmp_buf = Vec::new();
@@ -958,38 +781,40 @@ pub fn hash_chd_raw_sectors(
         let sectors_in_hunk = sectors_remaining.min(sectors_per_hunk);
 
         for s in 0..sectors_in_hunk {
-            let offset = s * CHD_CD_SECTOR_SIZE as usize;
+            let offset = s * CHD_FRAME_SIZE as usize;
             let raw_sector = &hunk_buf[offset..offset + RAW_SECTOR_SIZE as usize];
 
-            if let Some(ref mut h) = crc {
-                h.update(raw_sector);
-            }
-            if let Some(ref mut h) = sha {
-                h.update(raw_sector);
-            }
-            if let Some(ref mut h) = md5_ctx {
-                h.consume(raw_sector);
-            }
+            hasher.update(raw_sector);
         }
 
         sectors_remaining -= sectors_in_hunk;
     }
 
     Ok(retro_junk_core::FileHashes {
-        crc32: crc
-            .map(|h| format!("{:08x}", h.finalize()))
-            .unwrap_or_default(),
-        sha1: sha.map(|h| format!("{:x}", h.finalize())),
-        md5: md5_ctx.map(|h| format!("{:x}", h.compute())),
         data_size,
+        ..hasher.finish()
+    })
+}
+
+/// Hash the first `data_size` bytes of a disc image: Track 1 of a raw
+/// 2352-byte sector BIN file, or a whole decompressed ISO.
+pub(crate) fn hash_leading_bytes(
+    reader: &mut dyn retro_junk_core::ReadSeek,
+    algorithms: retro_junk_core::HashAlgorithms,
+    data_size: u64,
+) -> Result<retro_junk_core::FileHashes, AnalysisError> {
+    reader.seek(SeekFrom::Start(0))?;
+    let hashes = retro_junk_core::hash_reader(&mut reader.take(data_size), algorithms)?;
+    Ok(retro_junk_core::FileHashes {
+        data_size,
+        ..hashes
     })
 }
 
 /// Parse CHD track metadata (CHTR or CHT2) to find the number of frames
 /// (sectors) in Track 1. Returns `None` if no track metadata is found.
 ///
-/// CHD CD-ROM track metadata is stored as text strings like:
-///   `TRACK:1 TYPE:MODE2_RAW SUBTYPE:NONE FRAMES:229020 PREFRAMES:150`
+/// The metadata is read with [`cd::parse_chd_track`].
 fn parse_chd_track1_frames<F: std::io::Read + std::io::Seek>(
     chd: &mut chd::Chd<F>,
 ) -> Result<Option<usize>, AnalysisError> {
@@ -1010,20 +835,17 @@ fn parse_chd_track1_frames<F: std::io::Read + std::io::Seek>(
             .map_err(|e| AnalysisError::other(format!("Failed to read CHD metadata: {}", e)))?;
 
         let text = String::from_utf8_lossy(&meta.value);
-
-        // Parse "TRACK:N ... FRAMES:N"
-        if let Some(track_num) = parse_meta_field(&text, "TRACK") {
-            if track_num == "1" {
-                if let Some(frames_str) = parse_meta_field(&text, "FRAMES") {
-                    let frames: usize = frames_str.parse().map_err(|_| {
-                        AnalysisError::other(format!(
-                            "Invalid FRAMES value in CHD metadata: {}",
-                            frames_str
-                        ))
-                    })?;
-                    log::info!("CHD track metadata: Track 1 has {} frames", frames);
-                    return Ok(Some(frames));
-                }
+        match cd::parse_chd_track(&text) {
+            Some(track) if track.number == 1 => {
+                log::info!("CHD track metadata: Track 1 has {} frames", track.frames);
+                return Ok(Some(track.frames as usize));
+            }
+            Some(_) => {}
+            None => {
+                return Err(AnalysisError::other(format!(
+                    "Invalid CHD track metadata: {}",
+                    text.trim_end_matches('\0')
+                )));
             }
         }
     }
@@ -1031,18 +853,6 @@ fn parse_chd_track1_frames<F: std::io::Read + std::io::Seek>(
     Ok(None)
 }
 
-/// Extract a field value from CHD metadata text (e.g., "FRAMES" from
-/// `"TRACK:1 TYPE:MODE2_RAW SUBTYPE:NONE FRAMES:229020"`).
-fn parse_meta_field<'a>(text: &'a str, field: &str) -> Option<&'a str> {
-    let prefix = format!("{}:", field);
-    for token in text.split_whitespace() {
-        if let Some(value) = token.strip_prefix(&prefix) {
-            return Some(value);
-        }
-    }
-    None
-}
-
 #[cfg(test)]
 #[path = "tests/sony_disc_tests.rs"]
 mod tests;
@@ -0,0 +1,496 @@
+//! Synthetic disc images for Sony platforms.
+//!
+//! Each builder produces the smallest image its analyzer fully accepts. The
+//! lower-level helpers are shared with the analyzers' unit tests.
+
+use retro_junk_core::disc::cd::SYNC_PATTERN;
+use retro_junk_core::{Platform, SyntheticRom};
+
+use crate::ciso::{CSO_MAGIC, CisoKind, ZSO_MAGIC};
+use crate::dax::{DAX_MAGIC, FRAME_SIZE};
+use crate::psp::PBP_MAGIC;
+use crate::sfo::{SFO_MAGIC, SfoValue};
+
+/// The synthetic image for `platform`, if this crate can build one.
+pub fn synthesize(platform: Platform) -> Option<SyntheticRom> {
+    match platform {
+        Platform::Ps1 => Some(ps1()),
+        Platform::Ps2 => Some(ps2()),
+        Platform::Psp => Some(psp()),
+        Platform::Vita => Some(vita()),
+        _ => None,
+    }
+}
+
+/// ISO whose SYSTEM.CNF boots `SLUS_000.00`.
+pub fn ps1() -> SyntheticRom {
+    let iso = make_iso_with_system_cnf("SLUS_000.00", "BOOT");
+    SyntheticRom::new(Platform::Ps1, "synthetic.iso", iso)
+}
+
+/// ISO whose SYSTEM.CNF boots `SLUS_200.00` via `BOOT2`.
+pub fn ps2() -> SyntheticRom {
+    let iso = make_iso_with_system_cnf("SLUS_200.00", "BOOT2");
+    SyntheticRom::new(Platform::Ps2, "synthetic.iso", iso)
+}
+
+/// UMD ISO whose PARAM.SFO names `ULUS-10000`.
+pub fn psp() -> SyntheticRom {
+    let iso = make_psp_iso("ULUS10000", "SYNTHETIC");
+    SyntheticRom::new(Platform::Psp, "synthetic.iso", iso)
+}
+
+/// VPK whose param.sfo names `PCSE-00000`.
+pub fn vita() -> SyntheticRom {
+    let vpk = make_vpk(&make_vita_sfo("PCSE00000", "SYNTHETIC"));
+    SyntheticRom::new(Platform::Vita, "synthetic.vpk", vpk)
+}
+
+/// Build a minimal 2048-byte PVD sector with a given system identifier.
+fn make_pvd_sector(system_id: &str) -> [u8; 2048] {
+    let mut sector = [0u8; 2048];
+    sector[0] = 0x01; // PVD type
+    sector[1..6].copy_from_slice(b"CD001"); // standard identifier
+    sector[6] = 0x01; // version
+
+    // System identifier at offset 8, 32 bytes padded with spaces
+    let id_bytes = system_id.as_bytes();
+    let len = id_bytes.len().min(32);
+    sector[8..8 + len].copy_from_slice(&id_bytes[..len]);
+    for i in len..32 {
+        sector[8 + i] = b' ';
+    }
+
+    // Volume identifier at offset 40, 32 bytes
+    let vol = b"TEST_VOLUME";
+    sector[40..40 + vol.len()].copy_from_slice(vol);
+    for i in vol.len()..32 {
+        sector[40 + i] = b' ';
+    }
+
+    // Volume space size at offset 80 (LE) — say 200 sectors
+    sector[80..84].copy_from_slice(&200u32.to_le_bytes());
+    sector[84..88].copy_from_slice(&200u32.to_be_bytes());
+
+    // Root directory record at offset 156 (34 bytes)
+    sector[156] = 34; // record length
+    // extent LBA at record+2 (LE) — sector 18
+    sector[158..162].copy_from_slice(&18u32.to_le_bytes());
+    // data length at record+10 (LE) — 2048 bytes (1 sector)
+    sector[166..170].copy_from_slice(&2048u32.to_le_bytes());
+
+    sector
+}
+
+/// Build a minimal ISO: 16 sectors of padding + PVD at sector 16.
+pub fn make_iso(system_id: &str) -> Vec<u8> {
+    let mut data = vec![0u8; 16 * 2048]; // 16 empty sectors
+    let pvd = make_pvd_sector(system_id);
+    data.extend_from_slice(&pvd);
+    data
+}
+
+/// Wrap 2048 bytes of user data into a raw 2352-byte sector: Mode 2 Form 1,
+/// or Mode 1 when `mode` is 1.
+fn make_raw_sector(user_data: &[u8], mode: u8) -> [u8; 2352] {
+    let mut sector = [0u8; 2352];
+    // 12 bytes sync
+    sector[0..12].copy_from_slice(&SYNC_PATTERN);
+    // 4 bytes header (MSF + mode) — just set the mode
+    sector[15] = mode;
+    // Mode 2: 8 bytes subheader (zeros are fine), then user data at 24.
+    // Mode 1: user data right after the header, at 16.
+    let data = if mode == 1 { 16 } else { 24 };
+    sector[data..data + 2048].copy_from_slice(user_data);
+    // Remaining bytes (EDC/ECC) left as zero
+    sector
+}
+
+/// Build a raw BIN: 16 raw empty sectors + raw PVD sector.
+pub fn make_raw_bin(system_id: &str) -> Vec<u8> {
+    let empty_user = [0u8; 2048];
+    let mut data = Vec::new();
+    for _ in 0..16 {
+        data.extend_from_slice(&make_raw_sector(&empty_user, 2));
+    }
+    let pvd = make_pvd_sector(system_id);
+    data.extend_from_slice(&make_raw_sector(&pvd, 2));
+    data
+}
+
+/// Convert a 2048-byte sector ISO into a raw BIN of `mode` sectors.
+pub fn make_raw_bin_from_iso(iso: &[u8], mode: u8) -> Vec<u8> {
+    iso.chunks(2048)
+        .flat_map(|sector| make_raw_sector(sector, mode))
+        .collect()
+}
+
+/// Build a directory record for a file.
+fn make_dir_record(filename: &str, extent_lba: u32, data_length: u32) -> Vec<u8> {
+    let id_bytes = filename.as_bytes();
+    let id_len = id_bytes.len();
+    let record_len = 33 + id_len + (id_len % 2); // pad to even
+    let mut record = vec![0u8; record_len];
+    record[0] = record_len as u8;
+    record[2..6].copy_from_slice(&extent_lba.to_le_bytes());
+    record[10..14].copy_from_slice(&data_length.to_le_bytes());
+    record[25] = 0; // file flags (regular file)
+    record[32] = id_len as u8;
+    record[33..33 + id_len].copy_from_slice(id_bytes);
+    record
+}
+
+/// Build a full ISO with a root directory containing SYSTEM.CNF.
+///
+/// `boot_key` controls whether SYSTEM.CNF uses `BOOT` (PS1) or `BOOT2` (PS2).
+/// `serial` is the boot executable filename (e.g., "SLUS_012.34").
+pub fn make_iso_with_system_cnf(serial: &str, boot_key: &str) -> Vec<u8> {
+    let cdrom_prefix = if boot_key == "BOOT2" {
+        "cdrom0:"
+    } else {
+        "cdrom:"
+    };
+    let system_cnf_content = format!(
+        "{} = {}\\{};1\r\nVMODE = NTSC\r\n",
+        boot_key, cdrom_prefix, serial
+    );
+    let cnf_bytes = system_cnf_content.as_bytes();
+
+    // Layout:
+    // Sectors 0-15: empty padding
+    // Sector 16: PVD (root dir at sector 18, 1 sector)
+    // Sector 17: empty (VD terminator)
+    // Sector 18: root directory (with "." ".." and "SYSTEM.CNF;1" entries)
+    // Sector 19: SYSTEM.CNF content
+
+    let mut data = vec![0u8; 16 * 2048]; // sectors 0-15
+
+    // Sector 16: PVD
+    let mut pvd = make_pvd_sector("PLAYSTATION");
+    // Point root dir to sector 18, size 2048
+    pvd[158..162].copy_from_slice(&18u32.to_le_bytes());
+    pvd[166..170].copy_from_slice(&2048u32.to_le_bytes());
+    data.extend_from_slice(&pvd);
+
+    // Sector 17: empty (VD set terminator would go here)
+    data.extend_from_slice(&[0u8; 2048]);
+
+    // Sector 18: root directory
+    let mut dir_sector = [0u8; 2048];
+    let mut pos = 0;
+
+    // "." entry (current directory)
+    let dot_record = make_dir_record("\0", 18, 2048);
+    dir_sector[pos..pos + dot_record.len()].copy_from_slice(&dot_record);
+    pos += dot_record.len();
+
+    // ".." entry (parent directory)
+    let dotdot_record = make_dir_record("\x01", 18, 2048);
+    dir_sector[pos..pos + dotdot_record.len()].copy_from_slice(&dotdot_record);
+    pos += dotdot_record.len();
+
+    // SYSTEM.CNF entry pointing to sector 19
+    let cnf_record = make_dir_record("SYSTEM.CNF;1", 19, cnf_bytes.len() as u32);
+    dir_sector[pos..pos + cnf_record.len()].copy_from_slice(&cnf_record);
+
+    data.extend_from_slice(&dir_sector);
+
+    // Sector 19: SYSTEM.CNF content
+    let mut cnf_sector = [0u8; 2048];
+    cnf_sector[..cnf_bytes.len()].copy_from_slice(cnf_bytes);
+    data.extend_from_slice(&cnf_sector);
+
+    data
+}
+
+/// Mark an ISO from [`make_iso_with_system_cnf`] as a UDF bridge DVD by
+/// writing a UDF NSR descriptor into its free sector 17.
+pub fn mark_udf_bridge(iso: &mut [u8]) {
+    let sector = &mut iso[17 * 2048..18 * 2048];
+    sector[1..6].copy_from_slice(b"NSR02");
+    sector[6] = 1;
+}
+
+/// Compress an ISO into a CSO (raw deflate) or ZSO (LZ4, literals only)
+/// image with 2 KB blocks. Every other block is stored uncompressed, so
+/// both kinds of index entry are covered.
+pub fn make_ciso(iso: &[u8], kind: CisoKind) -> Vec<u8> {
+    use std::io::Write;
+
+    const BLOCK: usize = 2048;
+    let blocks: Vec<&[u8]> = iso.chunks(BLOCK).collect();
+    let mut index = Vec::with_capacity(blocks.len() + 1);
+    let mut body = Vec::new();
+    let data_start = 0x18 + 4 * (blocks.len() as u32 + 1);
+
+    for (i, block) in blocks.iter().enumerate() {
+        let offset = data_start + body.len() as u32;
+        if i % 2 == 1 {
+            index.push(offset | 0x8000_0000);
+            body.extend_from_slice(block);
+            continue;
+        }
+        index.push(offset);
+        match kind {
+            CisoKind::Cso => {
+                let mut enc =
+                    flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+                enc.write_all(block).unwrap();
+                body.extend_from_slice(&enc.finish().unwrap());
+            }
+            CisoKind::Zso => {
+                // One sequence of literals: a length token, then the bytes
+                body.push(0xF0);
+                let mut rest = block.len() - 15;
+                while rest >= 255 {
+                    body.push(255);
+                    rest -= 255;
+                }
+                body.push(rest as u8);
+                body.extend_from_slice(block);
+            }
+        }
+    }
+    index.push(data_start + body.len() as u32);
+
+    let magic = match kind {
+        CisoKind::Cso => CSO_MAGIC,
+        CisoKind::Zso => ZSO_MAGIC,
+    };
+    let mut data = Vec::new();
+    data.extend_from_slice(magic);
+    data.extend_from_slice(&0x18u32.to_le_bytes());
+    data.extend_from_slice(&(iso.len() as u64).to_le_bytes());
+    data.extend_from_slice(&(BLOCK as u32).to_le_bytes());
+    data.extend_from_slice(&[1, 0, 0, 0]);
+    for entry in index {
+        data.extend_from_slice(&entry.to_le_bytes());
+    }
+    data.extend_from_slice(&body);
+    data
+}
+
+/// Build a PARAM.SFO holding `entries`, in order.
+pub fn make_param_sfo(entries: &[(&str, SfoValue)]) -> Vec<u8> {
+    let mut keys = Vec::new();
+    let mut values = Vec::new();
+    let mut index = Vec::new();
+    for (key, value) in entries {
+        let (format, bytes, max_len) = match value {
+            SfoValue::Text(text) => {
+                let mut bytes = text.as_bytes().to_vec();
+                bytes.push(0);
+                let max_len = bytes.len().next_multiple_of(4);
+                (0x0204u16, bytes, max_len)
+            }
+            SfoValue::Int(n) => (0x0404u16, n.to_le_bytes().to_vec(), 4),
+        };
+        index.extend_from_slice(&(keys.len() as u16).to_le_bytes());
+        index.extend_from_slice(&format.to_le_bytes());
+        index.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        index.extend_from_slice(&(max_len as u32).to_le_bytes());
+        index.extend_from_slice(&(values.len() as u32).to_le_bytes());
+        keys.extend_from_slice(key.as_bytes());
+        keys.push(0);
+        values.extend_from_slice(&bytes);
+        values.resize(values.len() + max_len - bytes.len(), 0);
+    }
+    keys.resize(keys.len().next_multiple_of(4), 0);
+
+    let key_table = 0x14 + index.len();
+    let data_table = key_table + keys.len();
+    let mut data = Vec::new();
+    data.extend_from_slice(SFO_MAGIC);
+    data.extend_from_slice(&0x0101u32.to_le_bytes());
+    data.extend_from_slice(&(key_table as u32).to_le_bytes());
+    data.extend_from_slice(&(data_table as u32).to_le_bytes());
+    data.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+    data.extend_from_slice(&index);
+    data.extend_from_slice(&keys);
+    data.extend_from_slice(&values);
+    data
+}
+
+/// The PARAM.SFO of a UMD game with product code `disc_id` ("ULUS10000").
+pub fn make_psp_game_sfo(disc_id: &str, title: &str) -> Vec<u8> {
+    let text = |s: &str| SfoValue::Text(s.to_string());
+    make_param_sfo(&[
+        ("CATEGORY", text("UG")),
+        ("DISC_ID", text(disc_id)),
+        ("DISC_NUMBER", SfoValue::Int(1)),
+        ("DISC_TOTAL", SfoValue::Int(1)),
+        ("DISC_VERSION", text("1.00")),
+        ("PARENTAL_LEVEL", SfoValue::Int(1)),
+        ("PSP_SYSTEM_VER", text("1.50")),
+        ("TITLE", text(title)),
+    ])
+}
+
+/// PARAM.SFO of a PS3 disc game.
+pub fn make_ps3_game_sfo(title_id: &str, title: &str) -> Vec<u8> {
+    let text = |s: &str| SfoValue::Text(s.to_string());
+    make_param_sfo(&[
+        ("APP_VER", text("01.02")),
+        ("CATEGORY", text("DG")),
+        ("PARENTAL_LEVEL", SfoValue::Int(5)),
+        ("PS3_SYSTEM_VER", text("02.5200")),
+        ("TITLE", text(title)),
+        ("TITLE_ID", text(title_id)),
+        ("VERSION", text("01.00")),
+    ])
+}
+
+/// param.sfo of a Vita game.
+pub fn make_vita_sfo(title_id: &str, title: &str) -> Vec<u8> {
+    let text = |s: &str| SfoValue::Text(s.to_string());
+    make_param_sfo(&[
+        ("APP_VER", text("01.00")),
+        ("CATEGORY", text("gd")),
+        (
+            "CONTENT_ID",
+            text(&format!("UP0000-{title_id}_00-0000000000000000")),
+        ),
+        ("PARENTAL_LEVEL", SfoValue::Int(1)),
+        ("PSP2_DISP_VER", text("03.600")),
+        ("TITLE", text(title)),
+        ("TITLE_ID", text(title_id)),
+        ("VERSION", text("00.00")),
+    ])
+}
+
+/// Build a VPK holding `sfo` as its `sce_sys/param.sfo`, after an
+/// `eboot.bin`.
+pub fn make_vpk(sfo: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    let options =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    zip.start_file("eboot.bin", options).unwrap();
+    zip.write_all(b"SCE\0").unwrap();
+    zip.start_file("sce_sys/param.sfo", options).unwrap();
+    zip.write_all(sfo).unwrap();
+    zip.finish().unwrap().into_inner()
+}
+
+/// Build a PSP UMD ISO: `UMD_DATA.BIN` in the root and `PARAM.SFO` in
+/// `PSP_GAME`, both naming `disc_id`.
+pub fn make_psp_iso(disc_id: &str, title: &str) -> Vec<u8> {
+    const ROOT: u32 = 18;
+    const GAME_DIR: u32 = 19;
+    const UMD_DATA: u32 = 20;
+    const SFO: u32 = 21;
+    const SECTORS: u32 = 22;
+
+    let serial = format!("{}-{}", &disc_id[..4], &disc_id[4..]);
+    let umd_data = format!("{serial}|0000000000000001|0001|G");
+    let sfo = make_psp_game_sfo(disc_id, title);
+
+    let sector_of = |records: &[Vec<u8>]| {
+        let mut sector = [0u8; 2048];
+        let mut pos = 0;
+        for record in records {
+            sector[pos..pos + record.len()].copy_from_slice(record);
+            pos += record.len();
+        }
+        sector
+    };
+    let dir_record = |name: &str, lba: u32| {
+        let mut record = make_dir_record(name, lba, 2048);
+        record[25] = 0x02; // directory
+        record
+    };
+
+    let mut data = vec![0u8; 16 * 2048];
+    let mut pvd = make_pvd_sector("PSP GAME");
+    pvd[40..72].copy_from_slice(&[b' '; 32]);
+    pvd[40..48].copy_from_slice(b"UMD_GAME");
+    pvd[80..84].copy_from_slice(&SECTORS.to_le_bytes());
+    pvd[84..88].copy_from_slice(&SECTORS.to_be_bytes());
+    pvd[158..162].copy_from_slice(&ROOT.to_le_bytes());
+    data.extend_from_slice(&pvd);
+    data.extend_from_slice(&[0u8; 2048]);
+
+    data.extend_from_slice(&sector_of(&[
+        dir_record("\0", ROOT),
+        dir_record("\x01", ROOT),
+        dir_record("PSP_GAME", GAME_DIR),
+        make_dir_record("UMD_DATA.BIN;1", UMD_DATA, umd_data.len() as u32),
+    ]));
+    data.extend_from_slice(&sector_of(&[
+        dir_record("\0", GAME_DIR),
+        dir_record("\x01", ROOT),
+        make_dir_record("PARAM.SFO;1", SFO, sfo.len() as u32),
+    ]));
+    for file in [umd_data.as_bytes(), &sfo] {
+        let mut sector = [0u8; 2048];
+        sector[..file.len()].copy_from_slice(file);
+        data.extend_from_slice(&sector);
+    }
+    data
+}
+
+/// Build an EBOOT.PBP holding `sfo` and `psar` as its PARAM.SFO and
+/// DATA.PSAR; the other sections are empty.
+pub fn make_pbp(sfo: &[u8], psar: &[u8]) -> Vec<u8> {
+    let sfo_offset = 0x28u32;
+    let psar_offset = sfo_offset + sfo.len() as u32;
+    let mut data = Vec::new();
+    data.extend_from_slice(PBP_MAGIC);
+    data.extend_from_slice(&0x0001_0000u32.to_le_bytes());
+    data.extend_from_slice(&sfo_offset.to_le_bytes());
+    for _ in 0..6 {
+        data.extend_from_slice(&psar_offset.to_le_bytes());
+    }
+    data.extend_from_slice(&psar_offset.to_le_bytes());
+    data.extend_from_slice(sfo);
+    data.extend_from_slice(psar);
+    data
+}
+
+/// Compress an ISO into a DAX image. Odd frames are zlib-compressed and
+/// even ones stored in one-frame NC areas, so both kinds are covered.
+pub fn make_dax(iso: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    let frames: Vec<&[u8]> = iso.chunks(FRAME_SIZE as usize).collect();
+    let mut offsets = Vec::new();
+    let mut lengths = Vec::new();
+    let mut nc_areas = Vec::new();
+    let mut body = Vec::new();
+    for (i, frame) in frames.iter().enumerate() {
+        offsets.push(body.len() as u32);
+        if i % 2 == 0 {
+            nc_areas.push((i as u32, 1u32));
+            lengths.push(FRAME_SIZE as u16);
+            body.extend_from_slice(frame);
+        } else {
+            let mut enc =
+                flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            enc.write_all(frame).unwrap();
+            let compressed = enc.finish().unwrap();
+            lengths.push(compressed.len() as u16);
+            body.extend_from_slice(&compressed);
+        }
+    }
+    let data_start = 0x20 + 6 * frames.len() + 8 * nc_areas.len();
+
+    let mut data = Vec::new();
+    data.extend_from_slice(DAX_MAGIC);
+    data.extend_from_slice(&(iso.len() as u32).to_le_bytes());
+    data.extend_from_slice(&1u32.to_le_bytes());
+    data.extend_from_slice(&(nc_areas.len() as u32).to_le_bytes());
+    data.extend_from_slice(&[0u8; 16]);
+    for offset in offsets {
+        data.extend_from_slice(&(data_start as u32 + offset).to_le_bytes());
+    }
+    for length in lengths {
+        data.extend_from_slice(&length.to_le_bytes());
+    }
+    for (frame, count) in nc_areas {
+        data.extend_from_slice(&frame.to_le_bytes());
+        data.extend_from_slice(&count.to_le_bytes());
+    }
+    data.extend_from_slice(&body);
+    data
+}
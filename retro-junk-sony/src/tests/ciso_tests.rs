@@ -0,0 +1,87 @@
+use super::*;
+use crate::synthetic::{make_ciso, make_iso_with_system_cnf};
+use std::io::Cursor;
+
+fn decompress(image: Vec<u8>) -> Vec<u8> {
+    let mut iso = Vec::new();
+    open_image(Cursor::new(image), &ParseLimits::default())
+        .unwrap()
+        .read_to_end(&mut iso)
+        .unwrap();
+    iso
+}
+
+#[test]
+fn test_cso_round_trip() {
+    let iso = make_iso_with_system_cnf("SLUS_200.62", "BOOT2");
+    let cso = make_ciso(&iso, CisoKind::Cso);
+    assert!(cso.len() < iso.len());
+    assert_eq!(decompress(cso), iso);
+}
+
+#[test]
+fn test_zso_round_trip() {
+    let iso = make_iso_with_system_cnf("SLUS_200.62", "BOOT2");
+    assert_eq!(decompress(make_ciso(&iso, CisoKind::Zso)), iso);
+}
+
+#[test]
+fn test_partial_last_block() {
+    let mut iso = make_iso_with_system_cnf("SLUS_200.62", "BOOT2");
+    iso.extend_from_slice(b"tail");
+    assert_eq!(decompress(make_ciso(&iso, CisoKind::Cso)), iso);
+}
+
+#[test]
+fn test_seek_into_a_block() {
+    let iso = make_iso_with_system_cnf("SLUS_200.62", "BOOT2");
+    let mut image = open_image(
+        Cursor::new(make_ciso(&iso, CisoKind::Zso)),
+        &ParseLimits::default(),
+    )
+    .unwrap();
+    assert_eq!(image.len(), iso.len() as u64);
+    image.seek(SeekFrom::Start(16 * 2048 + 1)).unwrap();
+    let mut magic = [0u8; 5];
+    image.read_exact(&mut magic).unwrap();
+    assert_eq!(&magic, b"CD001");
+}
+
+#[test]
+fn test_rejects_bad_headers() {
+    assert!(CisoSource::open(Cursor::new(b"CISO".to_vec()), &ParseLimits::default()).is_err());
+
+    let iso = make_iso_with_system_cnf("SLUS_200.62", "BOOT2");
+    let mut cso = make_ciso(&iso, CisoKind::Cso);
+    cso[0x10..0x14].copy_from_slice(&0u32.to_le_bytes());
+    assert!(CisoSource::open(Cursor::new(cso), &ParseLimits::default()).is_err());
+}
+
+#[test]
+fn test_index_allocation_is_limited() {
+    let iso = make_iso_with_system_cnf("SLUS_200.62", "BOOT2");
+    let tiny = ParseLimits {
+        max_alloc: 16,
+        ..ParseLimits::default()
+    };
+    let err = CisoSource::open(Cursor::new(make_ciso(&iso, CisoKind::Cso)), &tiny)
+        .err()
+        .unwrap();
+    assert_eq!(err.code(), "limit_exceeded");
+}
+
+#[test]
+fn test_lz4_match_copies_overlap() {
+    // "ab" as literals, then a 6-byte match at offset 2
+    let src = [0x22, b'a', b'b', 0x02, 0x00];
+    let mut out = Vec::new();
+    lz4_decompress_block(&src, &mut out, 8).unwrap();
+    assert_eq!(out, b"abababab");
+}
+
+#[test]
+fn test_lz4_rejects_bad_offsets() {
+    let src = [0x10, b'a', 0x05, 0x00];
+    let mut out = Vec::new();
+    assert!(lz4_decompress_block(&src, &mut out, 8).is_err());
+}
@@ -0,0 +1,56 @@
+use super::*;
+use crate::synthetic::{make_dax, make_psp_iso};
+use std::io::Cursor;
+
+fn decompress(image: Vec<u8>) -> Vec<u8> {
+    let mut iso = Vec::new();
+    open_image(Cursor::new(image), &ParseLimits::default())
+        .unwrap()
+        .read_to_end(&mut iso)
+        .unwrap();
+    iso
+}
+
+#[test]
+fn test_dax_round_trip() {
+    let iso = make_psp_iso("ULUS10041", "LUMINES");
+    let dax = make_dax(&iso);
+    assert_eq!(&dax[..4], DAX_MAGIC);
+    assert_eq!(decompress(dax), iso);
+}
+
+#[test]
+fn test_partial_last_frame() {
+    let mut iso = make_psp_iso("ULUS10041", "LUMINES");
+    iso.extend_from_slice(b"tail");
+    assert_eq!(decompress(make_dax(&iso)), iso);
+}
+
+#[test]
+fn test_rejects_bad_headers() {
+    assert!(DaxSource::open(Cursor::new(b"DAX\0".to_vec()), &ParseLimits::default()).is_err());
+
+    let iso = make_psp_iso("ULUS10041", "LUMINES");
+    let mut dax = make_dax(&iso);
+    dax[0] = b'X';
+    assert!(DaxSource::open(Cursor::new(dax), &ParseLimits::default()).is_err());
+
+    // Frame tables that run past the end of the file
+    let mut dax = make_dax(&iso);
+    dax[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(DaxSource::open(Cursor::new(dax), &ParseLimits::default()).is_err());
+}
+
+#[test]
+fn test_rejects_oversized_frames() {
+    let iso = make_psp_iso("ULUS10041", "LUMINES");
+    let mut dax = make_dax(&iso);
+    let frames = iso.len().div_ceil(FRAME_SIZE as usize);
+    // Frame 1 is compressed; claim it's bigger than any frame can be
+    let length_at = 0x20 + 4 * frames + 2;
+    dax[length_at..length_at + 2].copy_from_slice(&0xFFFFu16.to_le_bytes());
+    let mut image = open_image(Cursor::new(dax), &ParseLimits::default()).unwrap();
+    image.seek(SeekFrom::Start(u64::from(FRAME_SIZE))).unwrap();
+    let mut buf = [0u8; 1];
+    assert!(image.read_exact(&mut buf).is_err());
+}
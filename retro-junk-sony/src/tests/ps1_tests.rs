@@ -1,5 +1,5 @@
 use super::*;
-use crate::disc_test_helpers::{make_iso, make_iso_with_system_cnf, make_raw_bin};
+use crate::synthetic::{make_iso, make_iso_with_system_cnf, make_raw_bin, make_raw_bin_from_iso};
 use std::io::Cursor;
 
 // PS1 tests use "BOOT" key for SYSTEM.CNF
@@ -91,6 +91,54 @@ fn test_analyze_raw_bin_basic() {
     );
 }
 
+// -- Lone BIN files (no CUE) --
+
+#[test]
+fn test_analyze_lone_mode2_bin_extracts_serial() {
+    let data = make_raw_bin_from_iso(&make_ps1_iso_with_serial("SCUS_941.63"), 2);
+    let mut cursor = Cursor::new(data);
+    let analyzer = Ps1Analyzer;
+    assert!(analyzer.can_handle(&mut cursor));
+    let result = analyzer
+        .analyze(&mut cursor, &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.serial_number.as_deref(), Some("SCUS-94163"));
+    assert_eq!(
+        result.extra.get("sector_mode").map(|s| s.as_str()),
+        Some("Mode 2")
+    );
+}
+
+#[test]
+fn test_analyze_lone_mode1_bin_extracts_serial() {
+    let data = make_raw_bin_from_iso(&make_ps1_iso_with_serial("SLES_123.45"), 1);
+    let mut cursor = Cursor::new(data);
+    let analyzer = Ps1Analyzer;
+    assert!(analyzer.can_handle(&mut cursor));
+    let result = analyzer
+        .analyze(&mut cursor, &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.serial_number.as_deref(), Some("SLES-12345"));
+    assert_eq!(result.regions, vec![retro_junk_core::Region::Europe]);
+    assert_eq!(
+        result.extra.get("sector_mode").map(|s| s.as_str()),
+        Some("Mode 1")
+    );
+}
+
+#[test]
+fn test_analyze_bin_with_blank_first_sector() {
+    let mut data = make_raw_bin_from_iso(&make_ps1_iso_with_serial("SLUS_012.34"), 2);
+    data[..2352].fill(0);
+    let mut cursor = Cursor::new(data);
+    let analyzer = Ps1Analyzer;
+    assert!(analyzer.can_handle(&mut cursor));
+    let result = analyzer
+        .analyze(&mut cursor, &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.serial_number.as_deref(), Some("SLUS-01234"));
+}
+
 #[test]
 fn test_analyze_non_ps1_iso_rejected() {
     let data = make_iso("XBOX SYSTEM");
@@ -230,6 +278,15 @@ fn test_extract_dat_game_code() {
     );
 }
 
+#[test]
+fn test_serial_normalized_and_validated() {
+    let analyzer = Ps1Analyzer;
+    assert_eq!(analyzer.normalize_serial("slus_012.34"), "SLUS-01234");
+    assert!(analyzer.is_valid_serial("SLUS_012.34"));
+    assert!(!analyzer.is_valid_serial("SLUS-01234-0"));
+    assert!(!analyzer.is_valid_serial("HOMEBREW"));
+}
+
 #[test]
 fn test_extract_dat_game_code_multi_disc_fixups() {
     let analyzer = Ps1Analyzer;
@@ -1,5 +1,9 @@
 use super::*;
-use crate::disc_test_helpers::{make_iso, make_iso_with_system_cnf, make_raw_bin};
+use crate::ciso::CisoKind;
+use crate::synthetic::{
+    make_ciso, make_iso, make_iso_with_system_cnf, make_raw_bin, make_raw_bin_from_iso,
+    mark_udf_bridge,
+};
 use std::io::Cursor;
 
 // PS2 tests use "BOOT2" key for SYSTEM.CNF
@@ -163,6 +167,10 @@ fn test_analyze_cue_basic() {
         result.extra.get("total_tracks").map(|s| s.as_str()),
         Some("1")
     );
+    assert_eq!(
+        result.extra.get("media").map(|s| s.as_str()),
+        Some("CD-ROM")
+    );
 }
 
 // -- DAT methods --
@@ -185,16 +193,19 @@ fn test_file_extensions() {
     assert!(exts.contains(&"iso"));
     assert!(exts.contains(&"bin"));
     assert!(exts.contains(&"chd"));
+    assert!(exts.contains(&"cso"));
+    assert!(exts.contains(&"zso"));
     // cue excluded (matches PS1 convention)
     assert!(!exts.contains(&"cue"));
 }
 
-// -- DVD layer detection --
+// -- CD/DVD media and DVD layer detection --
 
 #[test]
 fn test_dvd_layer_detection_dvd5() {
-    // Small ISO → DVD-5
-    let data = make_ps2_iso_with_serial("SLUS_200.62");
+    // Small UDF bridge ISO → DVD-5
+    let mut data = make_ps2_iso_with_serial("SLUS_200.62");
+    mark_udf_bridge(&mut data);
     let mut cursor = Cursor::new(data);
     let analyzer = Ps2Analyzer;
     let options = AnalysisOptions::default();
@@ -203,6 +214,40 @@ fn test_dvd_layer_detection_dvd5() {
         result.extra.get("dvd_layer").map(|s| s.as_str()),
         Some("DVD-5")
     );
+    assert_eq!(
+        result.extra.get("media").map(|s| s.as_str()),
+        Some("DVD-ROM")
+    );
+}
+
+#[test]
+fn test_iso_without_udf_is_a_cd() {
+    let data = make_ps2_iso_with_serial("SLPS_200.01");
+    let result = Ps2Analyzer
+        .analyze(&mut Cursor::new(data), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(
+        result.extra.get("media").map(|s| s.as_str()),
+        Some("CD-ROM")
+    );
+    assert!(!result.extra.contains_key("dvd_layer"));
+}
+
+#[test]
+fn test_cd_bin_is_identified() {
+    for mode in [2, 1] {
+        let bin = make_raw_bin_from_iso(&make_ps2_iso_with_serial("SCUS_971.13"), mode);
+        assert!(Ps2Analyzer.can_handle(&mut Cursor::new(bin.clone())));
+        let result = Ps2Analyzer
+            .analyze(&mut Cursor::new(bin), &AnalysisOptions::default())
+            .unwrap();
+        assert_eq!(result.serial_number.as_deref(), Some("SCUS-97113"));
+        assert_eq!(
+            result.extra.get("media").map(|s| s.as_str()),
+            Some("CD-ROM")
+        );
+        assert!(!result.extra.contains_key("dvd_layer"));
+    }
 }
 
 // -- Platform and DAT metadata --
@@ -231,3 +276,54 @@ fn test_expects_serial() {
     let analyzer = Ps2Analyzer;
     assert!(analyzer.expects_serial());
 }
+
+// -- Compressed images --
+
+#[test]
+fn test_analyze_cso_and_zso() {
+    let iso = make_ps2_iso_with_serial("SLUS_200.62");
+    for kind in [CisoKind::Cso, CisoKind::Zso] {
+        let image = make_ciso(&iso, kind);
+        let image_len = image.len() as u64;
+        let mut cursor = Cursor::new(image);
+        let analyzer = Ps2Analyzer;
+        assert!(analyzer.can_handle(&mut cursor));
+
+        let result = analyzer
+            .analyze(&mut cursor, &AnalysisOptions::default())
+            .unwrap();
+        assert_eq!(result.serial_number.as_deref(), Some("SLUS-20062"));
+        assert_eq!(result.file_size, Some(image_len));
+        assert_eq!(result.expected_size, None);
+        assert_eq!(result.extra.get("format").unwrap(), kind.name());
+        assert_eq!(
+            result.extra.get("uncompressed_size").unwrap(),
+            &iso.len().to_string()
+        );
+    }
+}
+
+#[test]
+fn test_can_handle_rejects_ps1_cso() {
+    let iso = make_iso_with_system_cnf("SLUS_012.34", "BOOT");
+    let mut cursor = Cursor::new(make_ciso(&iso, CisoKind::Cso));
+    assert!(!Ps2Analyzer.can_handle(&mut cursor));
+}
+
+#[test]
+fn test_cso_hashes_match_the_iso() {
+    use sha1::Digest;
+
+    let iso = make_ps2_iso_with_serial("SLUS_200.62");
+    let mut cursor = Cursor::new(make_ciso(&iso, CisoKind::Zso));
+    let hashes = Ps2Analyzer
+        .compute_container_hashes(&mut cursor, HashAlgorithms::All, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(hashes.data_size, iso.len() as u64);
+    assert_eq!(hashes.crc32, format!("{:08x}", crc32fast::hash(&iso)));
+    assert_eq!(
+        hashes.sha1.unwrap(),
+        retro_junk_core::hash::hex(&sha1::Sha1::digest(&iso))
+    );
+}
@@ -0,0 +1,61 @@
+use super::*;
+use crate::synthetic::make_ps3_game_sfo;
+use retro_junk_core::Region;
+
+/// An extracted disc with `sfo` as its PARAM.SFO.
+fn game_dir(sfo: &[u8]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("PS3_GAME")).unwrap();
+    std::fs::write(dir.path().join("PS3_GAME").join("PARAM.SFO"), sfo).unwrap();
+    dir
+}
+
+#[test]
+fn test_analyze_folder() {
+    let dir = game_dir(&make_ps3_game_sfo("BLUS30001", "RESISTANCE"));
+    assert!(Ps3Analyzer.is_game_dir(dir.path()));
+
+    let id = Ps3Analyzer
+        .analyze_dir(dir.path(), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::Ps3));
+    assert_eq!(id.serial_number.as_deref(), Some("BLUS-30001"));
+    assert_eq!(id.internal_name.as_deref(), Some("RESISTANCE"));
+    assert_eq!(id.version.as_deref(), Some("01.00"));
+    assert_eq!(id.regions, vec![Region::Usa]);
+    assert_eq!(id.extra["format"], "Folder");
+    assert_eq!(id.extra["category"], "Disc game");
+    assert_eq!(id.extra["app_version"], "01.02");
+    assert_eq!(id.extra["required_firmware"], "02.5200");
+}
+
+#[test]
+fn test_region_from_title_id() {
+    let dir = game_dir(&make_ps3_game_sfo("BCES00001", "MOTORSTORM"));
+    let id = Ps3Analyzer
+        .analyze_dir(dir.path(), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.regions, vec![Region::Europe]);
+}
+
+#[test]
+fn test_non_game_folder() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(!Ps3Analyzer.is_game_dir(dir.path()));
+    assert!(
+        Ps3Analyzer
+            .analyze_dir(dir.path(), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_bad_param_sfo() {
+    let dir = game_dir(b"not an sfo");
+    assert!(Ps3Analyzer.is_game_dir(dir.path()));
+    assert!(
+        Ps3Analyzer
+            .analyze_dir(dir.path(), &AnalysisOptions::default())
+            .is_err()
+    );
+}
@@ -0,0 +1,234 @@
+use super::*;
+use crate::ciso::CisoKind;
+use crate::sfo::SfoValue;
+use crate::synthetic::{
+    make_ciso, make_dax, make_iso, make_iso_with_system_cnf, make_param_sfo, make_pbp,
+    make_psp_game_sfo, make_psp_iso,
+};
+use std::io::Cursor;
+
+/// A PSone Classic's PARAM.SFO.
+fn ps1_classic_sfo(disc_id: &str) -> Vec<u8> {
+    let text = |s: &str| SfoValue::Text(s.to_string());
+    make_param_sfo(&[
+        ("CATEGORY", text("ME")),
+        ("DISC_ID", text(disc_id)),
+        ("TITLE", text("Final Fantasy VII")),
+    ])
+}
+
+/// A `PSISOIMG` header naming its disc `disc_id`.
+fn psiso(disc_id: &str) -> Vec<u8> {
+    let mut data = vec![0u8; 0x800];
+    data[..12].copy_from_slice(PSISO_MAGIC);
+    data[0x400..0x400 + disc_id.len()].copy_from_slice(disc_id.as_bytes());
+    data
+}
+
+// -- ISO images --
+
+#[test]
+fn test_analyze_umd_iso() {
+    let iso = make_psp_iso("ULUS10041", "LUMINES");
+    let mut cursor = Cursor::new(iso.clone());
+    assert!(PspAnalyzer.can_handle(&mut cursor));
+
+    let result = PspAnalyzer
+        .analyze(&mut cursor, &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.platform, Some(Platform::Psp));
+    assert_eq!(result.serial_number.as_deref(), Some("ULUS-10041"));
+    assert_eq!(result.internal_name.as_deref(), Some("LUMINES"));
+    assert_eq!(result.version.as_deref(), Some("1.00"));
+    assert_eq!(result.regions, vec![Region::Usa]);
+    assert_eq!(result.expected_size, Some(iso.len() as u64));
+    assert_eq!(result.extra.get("category").unwrap(), "UMD game");
+    assert_eq!(result.extra.get("required_firmware").unwrap(), "1.50");
+    assert_eq!(result.extra.get("format").unwrap(), "ISO 9660");
+}
+
+#[test]
+fn test_umd_data_serial_without_param_sfo() {
+    let mut iso = make_psp_iso("ULES00151", "LUMINES");
+    // Blank out PARAM.SFO's magic
+    iso[21 * 2048] = 0xFF;
+    let result = PspAnalyzer
+        .analyze(&mut Cursor::new(iso), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.serial_number.as_deref(), Some("ULES-00151"));
+    assert_eq!(result.regions, vec![Region::Europe]);
+    assert_eq!(result.internal_name.as_deref(), Some("UMD_GAME"));
+}
+
+#[test]
+fn test_rejects_other_isos() {
+    for iso in [
+        make_iso("SOME_OTHER_SYS"),
+        make_iso_with_system_cnf("SLUS_200.62", "BOOT2"),
+    ] {
+        let mut cursor = Cursor::new(iso);
+        assert!(!PspAnalyzer.can_handle(&mut cursor));
+        assert!(
+            PspAnalyzer
+                .analyze(&mut cursor, &AnalysisOptions::default())
+                .is_err()
+        );
+    }
+}
+
+// -- Compressed images --
+
+#[test]
+fn test_analyze_compressed_images() {
+    let iso = make_psp_iso("ULJM05001", "LUMINES");
+    let images = [
+        ("CSO", make_ciso(&iso, CisoKind::Cso)),
+        ("ZSO", make_ciso(&iso, CisoKind::Zso)),
+        ("DAX", make_dax(&iso)),
+    ];
+    for (format, image) in images {
+        let image_len = image.len() as u64;
+        let mut cursor = Cursor::new(image);
+        assert!(PspAnalyzer.can_handle(&mut cursor), "{format}");
+
+        let result = PspAnalyzer
+            .analyze(&mut cursor, &AnalysisOptions::default())
+            .unwrap();
+        assert_eq!(result.serial_number.as_deref(), Some("ULJM-05001"));
+        assert_eq!(result.regions, vec![Region::Japan]);
+        assert_eq!(result.file_size, Some(image_len));
+        assert_eq!(result.expected_size, None);
+        assert_eq!(result.extra.get("format").unwrap(), format);
+        assert_eq!(
+            result.extra.get("uncompressed_size").unwrap(),
+            &iso.len().to_string()
+        );
+    }
+}
+
+#[test]
+fn test_can_handle_rejects_ps2_cso() {
+    let iso = make_iso_with_system_cnf("SLUS_200.62", "BOOT2");
+    let mut cursor = Cursor::new(make_ciso(&iso, CisoKind::Cso));
+    assert!(!PspAnalyzer.can_handle(&mut cursor));
+}
+
+#[test]
+fn test_compressed_hashes_match_the_iso() {
+    use sha1::Digest;
+
+    let iso = make_psp_iso("ULUS10041", "LUMINES");
+    for image in [make_ciso(&iso, CisoKind::Cso), make_dax(&iso)] {
+        let hashes = PspAnalyzer
+            .compute_container_hashes(&mut Cursor::new(image), HashAlgorithms::All, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(hashes.data_size, iso.len() as u64);
+        assert_eq!(hashes.crc32, format!("{:08x}", crc32fast::hash(&iso)));
+        assert_eq!(
+            hashes.sha1.unwrap(),
+            retro_junk_core::hash::hex(&sha1::Sha1::digest(&iso))
+        );
+    }
+}
+
+#[test]
+fn test_plain_iso_uses_default_hashing() {
+    let iso = make_psp_iso("ULUS10041", "LUMINES");
+    let hashes = PspAnalyzer
+        .compute_container_hashes(&mut Cursor::new(iso), HashAlgorithms::All, None)
+        .unwrap();
+    assert!(hashes.is_none());
+}
+
+// -- EBOOT.PBP --
+
+#[test]
+fn test_analyze_psn_eboot() {
+    let pbp = make_pbp(&make_psp_game_sfo("NPUH10001", "PixelJunk"), &[]);
+    let mut cursor = Cursor::new(pbp);
+    assert!(PspAnalyzer.can_handle(&mut cursor));
+
+    let result = PspAnalyzer
+        .analyze(&mut cursor, &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.serial_number.as_deref(), Some("NPUH-10001"));
+    assert_eq!(result.internal_name.as_deref(), Some("PixelJunk"));
+    assert_eq!(result.regions, vec![Region::Usa]);
+    assert_eq!(result.extra.get("format").unwrap(), "PBP");
+    assert!(!result.extra.contains_key("ps1_discs"));
+    assert!(
+        PspAnalyzer
+            .compute_container_hashes(&mut cursor, HashAlgorithms::All, None)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_analyze_single_disc_ps1_classic() {
+    let pbp = make_pbp(&ps1_classic_sfo("SCUS94163"), &psiso("_SCUS_94163"));
+    let result = PspAnalyzer
+        .analyze(&mut Cursor::new(pbp), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.serial_number.as_deref(), Some("SCUS-94163"));
+    assert_eq!(result.regions, vec![Region::Usa]);
+    assert_eq!(result.extra.get("category").unwrap(), "PSone Classic");
+    assert_eq!(result.extra.get("ps1_discs").unwrap(), "1");
+    assert_eq!(result.extra.get("ps1_serials").unwrap(), "SCUS-94163");
+}
+
+#[test]
+fn test_analyze_multi_disc_ps1_classic() {
+    let serials = ["_SCUS_94163", "_SCUS_94164", "_SCUS_94165"];
+    let mut psar = vec![0u8; 0x800];
+    psar[..16].copy_from_slice(PSTITLE_MAGIC);
+    for (i, serial) in serials.iter().enumerate() {
+        let offset = psar.len() as u32;
+        psar[0x200 + 4 * i..0x204 + 4 * i].copy_from_slice(&offset.to_le_bytes());
+        psar.extend_from_slice(&psiso(serial));
+    }
+
+    let pbp = make_pbp(&ps1_classic_sfo("SCUS94163"), &psar);
+    let result = PspAnalyzer
+        .analyze(&mut Cursor::new(pbp), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(result.extra.get("ps1_discs").unwrap(), "3");
+    assert_eq!(
+        result.extra.get("ps1_serials").unwrap(),
+        "SCUS-94163, SCUS-94164, SCUS-94165"
+    );
+}
+
+#[test]
+fn test_rejects_truncated_pbp() {
+    let mut pbp = make_pbp(&make_psp_game_sfo("NPUH10001", "PixelJunk"), &[]);
+    pbp[0x0C..0x10].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(
+        PspAnalyzer
+            .analyze(&mut Cursor::new(pbp), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
+// -- Serials --
+
+#[test]
+fn test_serial_to_region() {
+    assert_eq!(serial_to_region("ULUS-10041"), Some(Region::Usa));
+    assert_eq!(serial_to_region("UCES-00001"), Some(Region::Europe));
+    assert_eq!(serial_to_region("ULJM-05001"), Some(Region::Japan));
+    assert_eq!(serial_to_region("ULKS-46001"), Some(Region::Korea));
+    assert_eq!(serial_to_region("NPEH-00001"), Some(Region::Europe));
+    assert_eq!(serial_to_region("ULAS-42001"), None);
+    assert_eq!(serial_to_region("SLES-00001"), Some(Region::Europe));
+}
+
+#[test]
+fn test_extract_dat_game_code() {
+    assert!(PspAnalyzer.expects_serial());
+    assert_eq!(
+        PspAnalyzer.extract_dat_game_code("ULUS-10041").as_deref(),
+        Some("ULUS-10041")
+    );
+}
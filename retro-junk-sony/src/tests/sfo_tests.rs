@@ -0,0 +1,57 @@
+use super::*;
+use crate::synthetic::{make_param_sfo, make_psp_game_sfo};
+
+#[test]
+fn test_parse_text_and_int_values() {
+    let sfo = ParamSfo::parse(&make_psp_game_sfo("ULUS10041", "LUMINES")).unwrap();
+    assert_eq!(sfo.get_str("DISC_ID"), Some("ULUS10041"));
+    assert_eq!(sfo.get_str("TITLE"), Some("LUMINES"));
+    assert_eq!(sfo.get_int("DISC_TOTAL"), Some(1));
+    assert_eq!(sfo.get_int("TITLE"), None);
+    assert_eq!(sfo.get("MISSING"), None);
+    assert_eq!(sfo.entries().count(), 8);
+}
+
+#[test]
+fn test_blank_text_is_missing() {
+    let data = make_param_sfo(&[("TITLE", SfoValue::Text("  ".into()))]);
+    let sfo = ParamSfo::parse(&data).unwrap();
+    assert_eq!(sfo.get_str("TITLE"), None);
+}
+
+#[test]
+fn test_rejects_bad_files() {
+    assert!(ParamSfo::parse(b"\0PSF").is_err());
+
+    let mut data = make_psp_game_sfo("ULUS10041", "LUMINES");
+    data[0] = b'X';
+    let err = ParamSfo::parse(&data).unwrap_err();
+    assert_eq!(err.code(), "invalid_magic");
+
+    // More entries than the file has room for
+    let mut data = make_psp_game_sfo("ULUS10041", "LUMINES");
+    data[0x10..0x14].copy_from_slice(&1000u32.to_le_bytes());
+    assert!(ParamSfo::parse(&data).is_err());
+
+    // A value past the end of the file
+    let mut data = make_psp_game_sfo("ULUS10041", "LUMINES");
+    data[0x14 + 12..0x14 + 16].copy_from_slice(&0x1000u32.to_le_bytes());
+    assert!(ParamSfo::parse(&data).is_err());
+}
+
+#[test]
+fn test_product_code_to_serial() {
+    assert_eq!(product_code_to_serial("ULUS10041"), "ULUS-10041");
+    assert_eq!(product_code_to_serial("npuh10001"), "NPUH-10001");
+    assert_eq!(product_code_to_serial("ULUS-10041"), "ULUS-10041");
+    assert_eq!(product_code_to_serial("HOMEBREW"), "HOMEBREW");
+}
+
+#[test]
+fn test_product_code_region() {
+    assert_eq!(product_code_region("ULUS-10041"), Some(Region::Usa));
+    assert_eq!(product_code_region("BLES-00001"), Some(Region::Europe));
+    assert_eq!(product_code_region("NPJB-00001"), Some(Region::Japan));
+    assert_eq!(product_code_region("BCAS-20001"), None);
+    assert_eq!(product_code_region("BL"), None);
+}
@@ -1,5 +1,5 @@
 use super::*;
-use crate::disc_test_helpers::{make_iso, make_iso_with_system_cnf, make_raw_bin};
+use crate::synthetic::{make_iso, make_iso_with_system_cnf, make_raw_bin, make_raw_bin_from_iso};
 use std::io::Cursor;
 
 // sony_disc tests use "BOOT" key by default for SYSTEM.CNF
@@ -29,6 +29,39 @@ fn test_detect_raw_bin_format() {
     );
 }
 
+#[test]
+fn test_raw_sector_mode() {
+    let iso = make_boot_iso_with_system_cnf("SLUS_012.34");
+    let mut mode1 = Cursor::new(make_raw_bin_from_iso(&iso, 1));
+    let mut mode2 = Cursor::new(make_raw_bin_from_iso(&iso, 2));
+    assert_eq!(raw_sector_mode(&mut mode1, 16).unwrap(), Some(1));
+    assert_eq!(raw_sector_mode(&mut mode2, 16).unwrap(), Some(2));
+    // Past the end, and not raw at all
+    assert_eq!(raw_sector_mode(&mut mode2, 1000).unwrap(), None);
+    assert_eq!(raw_sector_mode(&mut Cursor::new(iso), 0).unwrap(), None);
+}
+
+#[test]
+fn test_read_sector_data_follows_the_sector_mode() {
+    let iso = make_boot_iso_with_system_cnf("SLUS_012.34");
+    for mode in [1, 2] {
+        let mut bin = Cursor::new(make_raw_bin_from_iso(&iso, mode));
+        let sector = read_sector_data(&mut bin, 16, DiscFormat::RawSector2352).unwrap();
+        assert_eq!(&sector[..], &iso[16 * 2048..17 * 2048]);
+    }
+}
+
+#[test]
+fn test_detect_raw_bin_with_blank_first_sector() {
+    let mut data = make_raw_bin("PLAYSTATION");
+    data[..2352].fill(0);
+    let mut cursor = Cursor::new(data);
+    assert_eq!(
+        detect_disc_format(&mut cursor).unwrap(),
+        DiscFormat::RawSector2352
+    );
+}
+
 #[test]
 fn test_detect_chd_magic() {
     let mut data = vec![0u8; 64];
@@ -74,6 +107,26 @@ fn test_read_pvd_raw_bin() {
     assert_eq!(pvd.volume_identifier, "TEST_VOLUME");
 }
 
+#[test]
+fn test_read_pvd_creation_date() {
+    let mut data = make_iso("PLAYSTATION");
+    let pvd_start = 16 * 2048;
+    data[pvd_start + 813..pvd_start + 830].copy_from_slice(b"1997013112000000\0");
+    let pvd = read_pvd(&mut Cursor::new(data), DiscFormat::Iso2048).unwrap();
+    assert_eq!(
+        pvd.creation_date.map(|d| d.to_string()).as_deref(),
+        Some("1997-01-31")
+    );
+
+    // The helper leaves the date unset
+    let pvd = read_pvd(
+        &mut Cursor::new(make_iso("PLAYSTATION")),
+        DiscFormat::Iso2048,
+    )
+    .unwrap();
+    assert!(pvd.creation_date.is_none());
+}
+
 #[test]
 fn test_pvd_non_playstation() {
     let data = make_iso("SOME_OTHER_SYS");
@@ -119,6 +172,18 @@ fn test_parse_system_cnf_missing_boot() {
 
 // -- Serial extraction tests --
 
+#[test]
+fn test_normalize_serial() {
+    assert_eq!(normalize_serial("SLUS-00594"), "SLUS-00594");
+    assert_eq!(normalize_serial(" slus_005.94 "), "SLUS-00594");
+    assert_eq!(normalize_serial("SLPS 00700"), "SLPS-00700");
+    assert_eq!(normalize_serial("ULUS10041"), "ULUS-10041");
+    assert_eq!(normalize_serial("SCES-594"), "SCES-00594");
+    // Not a Sony serial: only trimmed and uppercased
+    assert_eq!(normalize_serial("slus-01041gh"), "SLUS-01041GH");
+    assert_eq!(normalize_serial("T-00000"), "T-00000");
+}
+
 #[test]
 fn test_extract_serial_slus() {
     assert_eq!(
@@ -197,55 +262,6 @@ fn test_serial_to_region() {
     assert_eq!(serial_to_region("XXXX-01234"), None);
 }
 
-// -- CUE parsing tests --
-
-#[test]
-fn test_parse_cue_single_track() {
-    let cue = "FILE \"game.bin\" BINARY\n  TRACK 01 MODE2/2352\n    INDEX 01 00:00:00\n";
-    let sheet = parse_cue(cue).unwrap();
-    assert_eq!(sheet.files.len(), 1);
-    assert_eq!(sheet.files[0].filename, "game.bin");
-    assert_eq!(sheet.files[0].file_type, "BINARY");
-    assert_eq!(sheet.files[0].tracks.len(), 1);
-    assert_eq!(sheet.files[0].tracks[0].number, 1);
-    assert_eq!(sheet.files[0].tracks[0].mode, "MODE2/2352");
-}
-
-#[test]
-fn test_parse_cue_multi_track() {
-    let cue = r#"FILE "game.bin" BINARY
-  TRACK 01 MODE2/2352
-    INDEX 01 00:00:00
-  TRACK 02 AUDIO
-    INDEX 00 45:00:00
-    INDEX 01 45:02:00
-  TRACK 03 AUDIO
-    INDEX 00 50:30:00
-    INDEX 01 50:32:00
-"#;
-    let sheet = parse_cue(cue).unwrap();
-    assert_eq!(sheet.files.len(), 1);
-    assert_eq!(sheet.files[0].tracks.len(), 3);
-    assert_eq!(sheet.files[0].tracks[0].mode, "MODE2/2352");
-    assert_eq!(sheet.files[0].tracks[1].mode, "AUDIO");
-    assert_eq!(sheet.files[0].tracks[2].number, 3);
-}
-
-#[test]
-fn test_parse_cue_multiple_files() {
-    let cue = r#"FILE "game (Track 1).bin" BINARY
-  TRACK 01 MODE2/2352
-    INDEX 01 00:00:00
-FILE "game (Track 2).bin" BINARY
-  TRACK 02 AUDIO
-    INDEX 01 00:00:00
-"#;
-    let sheet = parse_cue(cue).unwrap();
-    assert_eq!(sheet.files.len(), 2);
-    assert_eq!(sheet.files[0].filename, "game (Track 1).bin");
-    assert_eq!(sheet.files[1].filename, "game (Track 2).bin");
-}
-
 // -- Full ISO analysis tests --
 
 #[test]
@@ -253,7 +269,14 @@ fn test_find_system_cnf_in_iso() {
     let data = make_boot_iso_with_system_cnf("SLUS_012.34");
     let mut cursor = Cursor::new(data);
     let pvd = read_pvd(&mut cursor, DiscFormat::Iso2048).unwrap();
-    let content = find_file_in_root(&mut cursor, DiscFormat::Iso2048, &pvd, "SYSTEM.CNF").unwrap();
+    let content = find_file_in_root(
+        &mut cursor,
+        DiscFormat::Iso2048,
+        &pvd,
+        "SYSTEM.CNF",
+        &ParseLimits::default(),
+    )
+    .unwrap();
     let text = String::from_utf8_lossy(&content);
     assert!(text.contains("SLUS_012.34"));
 }
@@ -263,7 +286,14 @@ fn test_full_iso_serial_extraction() {
     let data = make_boot_iso_with_system_cnf("SLUS_012.34");
     let mut cursor = Cursor::new(data);
     let pvd = read_pvd(&mut cursor, DiscFormat::Iso2048).unwrap();
-    let content = find_file_in_root(&mut cursor, DiscFormat::Iso2048, &pvd, "SYSTEM.CNF").unwrap();
+    let content = find_file_in_root(
+        &mut cursor,
+        DiscFormat::Iso2048,
+        &pvd,
+        "SYSTEM.CNF",
+        &ParseLimits::default(),
+    )
+    .unwrap();
     let text = String::from_utf8_lossy(&content);
     let cnf = parse_system_cnf(&text).unwrap();
     let serial = extract_serial(&cnf.boot_path).unwrap();
@@ -275,7 +305,51 @@ fn test_file_not_found_in_root() {
     let data = make_boot_iso_with_system_cnf("SLUS_012.34");
     let mut cursor = Cursor::new(data);
     let pvd = read_pvd(&mut cursor, DiscFormat::Iso2048).unwrap();
-    assert!(find_file_in_root(&mut cursor, DiscFormat::Iso2048, &pvd, "NONEXIST.TXT").is_err());
+    assert!(
+        find_file_in_root(
+            &mut cursor,
+            DiscFormat::Iso2048,
+            &pvd,
+            "NONEXIST.TXT",
+            &ParseLimits::default()
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_find_file_in_root_respects_limits() {
+    let data = make_boot_iso_with_system_cnf("SLUS_012.34");
+    let mut cursor = Cursor::new(data);
+    let pvd = read_pvd(&mut cursor, DiscFormat::Iso2048).unwrap();
+
+    let tiny_alloc = ParseLimits {
+        max_alloc: 8,
+        ..ParseLimits::default()
+    };
+    let err = find_file_in_root(
+        &mut cursor,
+        DiscFormat::Iso2048,
+        &pvd,
+        "SYSTEM.CNF",
+        &tiny_alloc,
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), "limit_exceeded");
+
+    let no_entries = ParseLimits {
+        max_entries: 0,
+        ..ParseLimits::default()
+    };
+    let err = find_file_in_root(
+        &mut cursor,
+        DiscFormat::Iso2048,
+        &pvd,
+        "SYSTEM.CNF",
+        &no_entries,
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), "limit_exceeded");
 }
 
 // ---------------------------------------------------------------------------
@@ -289,7 +363,7 @@ fn make_multi_track_bin(data_sectors: usize, audio_sectors: usize) -> Vec<u8> {
     let mut bin = Vec::with_capacity((data_sectors + audio_sectors) * RAW_SECTOR_SIZE as usize);
     for i in 0..data_sectors {
         let mut sector = [0u8; RAW_SECTOR_SIZE as usize];
-        sector[0..12].copy_from_slice(&CD_SYNC_PATTERN);
+        sector[0..12].copy_from_slice(&SYNC_PATTERN);
         sector[15] = 0x02; // Mode 2
         for (j, byte) in sector[24..2072].iter_mut().enumerate() {
             *byte = ((i * 251 + j * 97) & 0xFF) as u8;
@@ -319,7 +393,7 @@ fn reference_hashes(data: &[u8]) -> (String, String, String) {
     let sha1 = {
         let mut h = sha1::Sha1::new();
         h.update(data);
-        format!("{:x}", h.finalize())
+        retro_junk_core::hash::hex(&h.finalize())
     };
     let md5 = {
         let mut ctx = md5::Context::new();
@@ -393,32 +467,3 @@ fn test_single_track_bin_returns_none() {
 
     assert!(result.is_none(), "Single-track BIN should return None");
 }
-
-// ---------------------------------------------------------------------------
-// CHD metadata parsing tests
-// ---------------------------------------------------------------------------
-
-#[test]
-fn test_parse_meta_field_basic() {
-    let text = "TRACK:1 TYPE:MODE2_RAW SUBTYPE:NONE FRAMES:229020 PREFRAMES:150";
-    assert_eq!(parse_meta_field(text, "TRACK"), Some("1"));
-    assert_eq!(parse_meta_field(text, "TYPE"), Some("MODE2_RAW"));
-    assert_eq!(parse_meta_field(text, "FRAMES"), Some("229020"));
-    assert_eq!(parse_meta_field(text, "PREFRAMES"), Some("150"));
-    assert_eq!(parse_meta_field(text, "SUBTYPE"), Some("NONE"));
-}
-
-#[test]
-fn test_parse_meta_field_missing() {
-    let text = "TRACK:1 TYPE:AUDIO SUBTYPE:NONE FRAMES:18995";
-    assert_eq!(parse_meta_field(text, "POSTGAP"), None);
-    assert_eq!(parse_meta_field(text, "PREGAP"), None);
-}
-
-#[test]
-fn test_parse_meta_field_audio_track() {
-    let text = "TRACK:2 TYPE:AUDIO SUBTYPE:NONE FRAMES:18995 PREFRAMES:150";
-    assert_eq!(parse_meta_field(text, "TRACK"), Some("2"));
-    assert_eq!(parse_meta_field(text, "TYPE"), Some("AUDIO"));
-    assert_eq!(parse_meta_field(text, "FRAMES"), Some("18995"));
-}
@@ -0,0 +1,85 @@
+use super::*;
+use crate::synthetic::{make_vita_sfo, make_vpk};
+use std::io::Cursor;
+
+/// A NoNpDrm folder dump with `sfo` as its param.sfo.
+fn game_dir(sfo: &[u8]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("sce_sys")).unwrap();
+    std::fs::write(dir.path().join("sce_sys").join("param.sfo"), sfo).unwrap();
+    std::fs::write(dir.path().join("eboot.bin"), b"SCE\0").unwrap();
+    dir
+}
+
+#[test]
+fn test_analyze_vpk() {
+    let vpk = make_vpk(&make_vita_sfo("PCSE00120", "Persona 4 Golden"));
+    assert!(VitaAnalyzer.can_handle(&mut Cursor::new(&vpk)));
+
+    let id = VitaAnalyzer
+        .analyze(&mut Cursor::new(&vpk), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.platform, Some(Platform::Vita));
+    assert_eq!(id.serial_number.as_deref(), Some("PCSE-00120"));
+    assert_eq!(id.internal_name.as_deref(), Some("Persona 4 Golden"));
+    assert_eq!(id.version.as_deref(), Some("01.00"));
+    assert_eq!(id.regions, vec![Region::Usa]);
+    assert_eq!(id.file_size, Some(vpk.len() as u64));
+    assert_eq!(id.extra["format"], "VPK");
+    assert_eq!(id.extra["category"], "Game");
+    assert_eq!(
+        id.extra["content_id"],
+        "UP0000-PCSE00120_00-0000000000000000"
+    );
+    assert_eq!(id.extra["required_firmware"], "03.600");
+}
+
+#[test]
+fn test_analyze_folder() {
+    let dir = game_dir(&make_vita_sfo("PCSB00245", "Tearaway"));
+    assert!(VitaAnalyzer.is_game_dir(dir.path()));
+
+    let id = VitaAnalyzer
+        .analyze_dir(dir.path(), &AnalysisOptions::default())
+        .unwrap();
+    assert_eq!(id.serial_number.as_deref(), Some("PCSB-00245"));
+    assert_eq!(id.regions, vec![Region::Europe]);
+    assert_eq!(id.extra["format"], "Folder");
+}
+
+#[test]
+fn test_title_id_regions() {
+    assert_eq!(title_id_region("PCSA00001"), Some(Region::Usa));
+    assert_eq!(title_id_region("PCSF00001"), Some(Region::Europe));
+    assert_eq!(title_id_region("PCSG00001"), Some(Region::Japan));
+    assert_eq!(title_id_region("PCSC00001"), Some(Region::Japan));
+    assert_eq!(title_id_region("VLJM30001"), Some(Region::Japan));
+    assert_eq!(title_id_region("PCSH00001"), None);
+    assert_eq!(title_id_region("VCAS32001"), None);
+}
+
+#[test]
+fn test_rejects_other_zips() {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file("game.nes", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    let data = zip.finish().unwrap().into_inner();
+    assert!(!VitaAnalyzer.can_handle(&mut Cursor::new(&data)));
+    assert!(
+        VitaAnalyzer
+            .analyze(&mut Cursor::new(&data), &AnalysisOptions::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_non_game_folder() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("sce_sys")).unwrap();
+    std::fs::write(
+        dir.path().join("sce_sys").join("param.sfo"),
+        make_vita_sfo("PCSE00120", "Persona 4 Golden"),
+    )
+    .unwrap();
+    assert!(!VitaAnalyzer.is_game_dir(dir.path()));
+}
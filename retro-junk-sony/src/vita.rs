@@ -1,12 +1,37 @@
 //! PlayStation Vita ROM analyzer.
 //!
 //! Supports:
-//! - VPK files
-//! - Game card dumps
+//! - VPK files: ZIP archives of an installed app, holding its PARAM.SFO at
+//!   `sce_sys/param.sfo` (see [`crate::sfo`])
+//! - NoNpDrm folder dumps: the app's folder, with the same
+//!   `sce_sys/param.sfo` and its `eboot.bin`, through
+//!   [`RomAnalyzer::analyze_dir`]
+//!
+//! Game card dumps aren't analyzed yet.
+
+use std::io::{Read, SeekFrom};
+use std::path::Path;
 
 use retro_junk_core::ReadSeek;
 
-use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+use retro_junk_core::{
+    AnalysisError, AnalysisOptions, Platform, Region, RomAnalyzer, RomIdentification,
+};
+
+use crate::sfo::{self, ParamSfo};
+use crate::sony_disc;
+
+/// ZIP local file header magic, at the start of a VPK.
+const ZIP_MAGIC: &[u8; 4] = b"PK\x03\x04";
+
+/// The app's PARAM.SFO, relative to its root.
+const PARAM_SFO: &str = "sce_sys/param.sfo";
+
+/// The app's main executable, in its root.
+const EBOOT: &str = "eboot.bin";
+
+/// PARAM.SFO categories, by code.
+const CATEGORIES: &[(&str, &str)] = &[("gd", "Game"), ("gp", "Update")];
 
 /// Analyzer for PlayStation Vita ROMs.
 #[derive(Debug, Default)]
@@ -15,12 +40,17 @@ pub struct VitaAnalyzer;
 impl RomAnalyzer for VitaAnalyzer {
     fn analyze(
         &self,
-        _reader: &mut dyn ReadSeek,
-        _options: &AnalysisOptions,
+        reader: &mut dyn ReadSeek,
+        options: &AnalysisOptions,
     ) -> Result<RomIdentification, AnalysisError> {
-        Err(AnalysisError::other(
-            "PS Vita ROM analysis not yet implemented",
-        ))
+        let file_size = retro_junk_core::util::file_size(reader)?;
+        let sfo = ParamSfo::parse(&read_vpk_sfo(reader, options)?)?;
+
+        let mut id = RomIdentification::new().with_platform(Platform::Vita);
+        id.file_size = Some(file_size);
+        id.extra.insert("format".into(), "VPK".into());
+        apply_sfo(&sfo, &mut id);
+        Ok(id)
     }
 
     fn platform(&self) -> Platform {
@@ -31,11 +61,129 @@ impl RomAnalyzer for VitaAnalyzer {
         &["vpk"]
     }
 
-    fn can_handle(&self, _reader: &mut dyn ReadSeek) -> bool {
-        false // Not yet implemented
+    fn can_handle(&self, reader: &mut dyn ReadSeek) -> bool {
+        let mut magic = [0u8; 4];
+        if reader.seek(SeekFrom::Start(0)).is_err()
+            || reader.read_exact(&mut magic).is_err()
+            || &magic != ZIP_MAGIC
+        {
+            return false;
+        }
+        read_vpk_sfo(reader, &AnalysisOptions::default()).is_ok()
+    }
+
+    fn is_game_dir(&self, dir: &Path) -> bool {
+        dir.join(PARAM_SFO).is_file() && dir.join(EBOOT).is_file()
+    }
+
+    fn analyze_dir(
+        &self,
+        dir: &Path,
+        options: &AnalysisOptions,
+    ) -> Result<RomIdentification, AnalysisError> {
+        let path = dir.join(PARAM_SFO);
+        let size = std::fs::metadata(&path)?.len();
+        sfo::check_size(size, options)?;
+        let sfo = ParamSfo::parse(&std::fs::read(&path)?)?;
+
+        let mut id = RomIdentification::new().with_platform(Platform::Vita);
+        id.extra.insert("format".into(), "Folder".into());
+        apply_sfo(&sfo, &mut id);
+        Ok(id)
+    }
+
+    fn serial_pattern(&self) -> Option<&'static str> {
+        Some(sony_disc::SERIAL_PATTERN)
+    }
+
+    fn normalize_serial(&self, serial: &str) -> String {
+        sony_disc::normalize_serial(serial)
     }
 
     fn dat_names(&self) -> &'static [&'static str] {
         &["Sony - PlayStation Vita", "Sony - PlayStation Vita (PSN)"]
     }
 }
+
+/// Read `sce_sys/param.sfo` out of a VPK.
+fn read_vpk_sfo(
+    reader: &mut dyn ReadSeek,
+    options: &AnalysisOptions,
+) -> Result<Vec<u8>, AnalysisError> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut archive = zip::ZipArchive::new(reader)
+        .map_err(|e| AnalysisError::invalid_format(format!("Not a VPK: {e}")))?;
+    options
+        .limits
+        .check_entries(archive.len() as u64, "VPK entries")?;
+    let name = archive
+        .file_names()
+        .find(|name| name.eq_ignore_ascii_case(PARAM_SFO))
+        .map(str::to_string)
+        .ok_or_else(|| AnalysisError::invalid_format("No sce_sys/param.sfo in VPK"))?;
+    let entry = archive
+        .by_name(&name)
+        .map_err(|e| AnalysisError::corrupted_header(format!("Unreadable param.sfo: {e}")))?;
+    let size = entry.size();
+    sfo::check_size(size, options)?;
+
+    let mut data = Vec::with_capacity(size as usize);
+    entry
+        .take(size)
+        .read_to_end(&mut data)
+        .map_err(|e| AnalysisError::corrupted_header(format!("Unreadable param.sfo: {e}")))?;
+    Ok(data)
+}
+
+/// Apply PARAM.SFO fields to the identification.
+fn apply_sfo(sfo: &ParamSfo, id: &mut RomIdentification) {
+    if let Some(title) = sfo.get_str("TITLE") {
+        id.internal_name = Some(title.to_string());
+    }
+    if let Some(code) = sfo.get_str("TITLE_ID") {
+        id.regions = title_id_region(code).into_iter().collect();
+        id.serial_number = Some(sfo::product_code_to_serial(code));
+    }
+    // APP_VER is the app's version, raised by updates
+    if let Some(version) = sfo.get_str("APP_VER") {
+        id.version = Some(version.to_string());
+    }
+    if let Some(code) = sfo.get_str("CATEGORY") {
+        let category = CATEGORIES
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map_or(code, |(_, name)| name);
+        id.extra.insert("category".into(), category.into());
+    }
+    if let Some(content_id) = sfo.get_str("CONTENT_ID") {
+        id.extra.insert("content_id".into(), content_id.to_string());
+    }
+    if let Some(firmware) = sfo.get_str("PSP2_DISP_VER") {
+        id.extra
+            .insert("required_firmware".into(), firmware.to_string());
+    }
+    if let Some(level) = sfo.get_int("PARENTAL_LEVEL") {
+        id.extra.insert("parental_level".into(), level.to_string());
+    }
+}
+
+/// The region of a Vita title ID. Digital and most card titles use `PCS`
+/// and a letter per region and publisher; Japanese and Asian cards from
+/// other publishers use `VxJx` and `VxAx`. Asian titles have no matching
+/// region.
+fn title_id_region(code: &str) -> Option<Region> {
+    match code.to_ascii_uppercase().as_bytes() {
+        [b'P', b'C', b'S', letter, ..] => match letter {
+            b'A' | b'E' => Some(Region::Usa),
+            b'B' | b'F' => Some(Region::Europe),
+            b'C' | b'G' => Some(Region::Japan),
+            _ => None,
+        },
+        [b'V', _, b'J', ..] => Some(Region::Japan),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/vita_tests.rs"]
+mod tests;
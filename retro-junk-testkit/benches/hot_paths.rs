@@ -0,0 +1,23 @@
+//! Criterion benchmarks for the analysis hot paths.
+//!
+//! Run with `cargo bench -p retro-junk-testkit`; pass a workload name
+//! (e.g. `cargo bench -p retro-junk-testkit -- hashing`) to run just one.
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use retro_junk_testkit::bench::Workload;
+
+fn hot_paths(c: &mut Criterion) {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let mut group = c.benchmark_group("hot_paths");
+    for workload in Workload::ALL {
+        let prepared = workload
+            .prepare(&dir.path().join(workload.name()))
+            .expect("prepare workload");
+        group.throughput(Throughput::Bytes(prepared.input_len()));
+        group.bench_function(workload.name(), |b| b.iter(|| prepared.run()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, hot_paths);
+criterion_main!(benches);
@@ -0,0 +1,234 @@
+//! Representative workloads and time budgets for the analysis hot paths.
+//!
+//! Each [`Workload`] prepares its input once and can then be run any number
+//! of times, so the criterion benches (`cargo bench -p retro-junk-testkit`)
+//! and the budget test measure exactly the same thing. Budgets are generous
+//! upper bounds for an optimized build: they catch a hot path getting an
+//! order of magnitude slower, not a few percent. Timings depend on the
+//! machine and its load, so the budget test only runs when asked for
+//! (`cargo test --release -p retro-junk-testkit -- --ignored`).
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::hint::black_box;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use retro_junk_core::{AnalysisOptions, Platform};
+use retro_junk_dat::dat::parse_dat;
+use retro_junk_dat::matcher::DatIndex;
+use retro_junk_lib::{AnalysisContext, create_default_context};
+use retro_junk_nintendo::GameBoyAnalyzer;
+
+/// Bytes hashed by [`Workload::Hashing`]; large enough to take the
+/// parallel path.
+pub const HASH_INPUT_LEN: usize = 8 << 20;
+
+/// Games in the DAT built by [`Workload::DatIndex`], about the size of a
+/// cartridge system's No-Intro DAT.
+pub const DAT_GAMES: usize = 5_000;
+
+/// Tracks in the sheet parsed by [`Workload::CueParsing`], the most a CUE
+/// sheet can hold.
+pub const CUE_TRACKS: u8 = 99;
+
+/// A hot path worth keeping fast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Workload {
+    /// Analyze one synthetic image for every platform that has one.
+    HeaderAnalysis,
+    /// CRC32 and SHA1 of [`HASH_INPUT_LEN`] bytes, as `verify` and `rename`
+    /// hash ROMs.
+    Hashing,
+    /// Parse a [`DAT_GAMES`]-game DAT and index it.
+    DatIndex,
+    /// Parse a [`CUE_TRACKS`]-track CUE sheet.
+    CueParsing,
+}
+
+impl Workload {
+    pub const ALL: [Workload; 4] = [
+        Workload::HeaderAnalysis,
+        Workload::Hashing,
+        Workload::DatIndex,
+        Workload::CueParsing,
+    ];
+
+    /// Benchmark id.
+    pub fn name(self) -> &'static str {
+        match self {
+            Workload::HeaderAnalysis => "header_analysis",
+            Workload::Hashing => "hashing",
+            Workload::DatIndex => "dat_index",
+            Workload::CueParsing => "cue_parsing",
+        }
+    }
+
+    /// Upper bound for one run in an optimized build.
+    pub fn budget(self) -> Duration {
+        match self {
+            Workload::HeaderAnalysis => Duration::from_secs(1),
+            Workload::Hashing => Duration::from_secs(2),
+            Workload::DatIndex => Duration::from_millis(2500),
+            Workload::CueParsing => Duration::from_millis(50),
+        }
+    }
+
+    /// Build the workload's input. Files it needs are written into `dir`.
+    pub fn prepare(self, dir: &Path) -> std::io::Result<Prepared> {
+        let input = match self {
+            Workload::HeaderAnalysis => {
+                let mut roms = Vec::new();
+                for rom in crate::synthesize_all() {
+                    let rom_dir = dir.join(rom.platform.short_name());
+                    std::fs::create_dir_all(&rom_dir)?;
+                    roms.push((rom.platform, rom.write_to(&rom_dir)?));
+                }
+                Input::Roms {
+                    ctx: create_default_context(),
+                    roms,
+                }
+            }
+            Workload::Hashing => Input::Bytes(hash_input(HASH_INPUT_LEN)),
+            Workload::DatIndex => Input::Dat(sample_dat(DAT_GAMES)),
+            Workload::CueParsing => Input::Cue(sample_cue(CUE_TRACKS)),
+        };
+        Ok(Prepared {
+            workload: self,
+            input,
+        })
+    }
+}
+
+/// A workload with its input built, ready to [`run`](Prepared::run).
+pub struct Prepared {
+    workload: Workload,
+    input: Input,
+}
+
+enum Input {
+    Roms {
+        ctx: AnalysisContext,
+        roms: Vec<(Platform, PathBuf)>,
+    },
+    Bytes(Vec<u8>),
+    Dat(String),
+    Cue(String),
+}
+
+impl Prepared {
+    pub fn workload(&self) -> Workload {
+        self.workload
+    }
+
+    /// Bytes processed by one run, for throughput reporting.
+    pub fn input_len(&self) -> u64 {
+        match &self.input {
+            Input::Roms { roms, .. } => roms
+                .iter()
+                .filter_map(|(_, path)| std::fs::metadata(path).ok())
+                .map(|m| m.len())
+                .sum(),
+            Input::Bytes(bytes) => bytes.len() as u64,
+            Input::Dat(text) | Input::Cue(text) => text.len() as u64,
+        }
+    }
+
+    /// Run the workload once.
+    ///
+    /// # Panics
+    ///
+    /// If the hot path fails on its input, which is always valid.
+    pub fn run(&self) {
+        match &self.input {
+            Input::Roms { ctx, roms } => {
+                for (platform, path) in roms {
+                    let console = ctx.get_by_platform(*platform).expect("registered platform");
+                    let mut file = File::open(path).expect("open synthetic image");
+                    let options = AnalysisOptions::new().file_path(path.clone());
+                    let id = console
+                        .analyzer
+                        .analyze(&mut file, &options)
+                        .unwrap_or_else(|e| panic!("{platform:?}: {e}"));
+                    black_box(id);
+                }
+            }
+            Input::Bytes(bytes) => {
+                let hashes = retro_junk_lib::hasher::compute_crc32_sha1(
+                    &mut Cursor::new(bytes.as_slice()),
+                    &GameBoyAnalyzer,
+                    None,
+                )
+                .expect("hash in-memory input");
+                black_box(hashes);
+            }
+            Input::Dat(text) => {
+                let dat = parse_dat(text.as_bytes()).expect("parse sample DAT");
+                black_box(DatIndex::from_dat(dat));
+            }
+            Input::Cue(text) => {
+                black_box(retro_junk_core::disc::cue::parse(text));
+            }
+        }
+    }
+}
+
+/// `len` bytes of deterministic noise.
+pub fn hash_input(len: usize) -> Vec<u8> {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    (0..len)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 24) as u8
+        })
+        .collect()
+}
+
+/// A Logiqx XML DAT of `games` games with one ROM each. Every fourth game
+/// is a clone of the one before it.
+pub fn sample_dat(games: usize) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\"?>\n<datafile>\n\t<header>\n\t\t<name>Bench</name>\n\
+         \t\t<description>Bench</description>\n\t\t<version>1</version>\n\t</header>\n",
+    );
+    for i in 0..games {
+        let name = format!("Game {i:05} (USA)");
+        let clone_of = if i % 4 == 3 {
+            format!(" cloneof=\"Game {:05} (USA)\"", i - 1)
+        } else {
+            String::new()
+        };
+        let _ = write!(
+            xml,
+            "\t<game name=\"{name}\"{clone_of}>\n\t\t<description>{name}</description>\n\
+             \t\t<rom name=\"{name}.bin\" size=\"{size}\" crc=\"{i:08x}\" \
+             sha1=\"{i:040x}\" md5=\"{i:032x}\" serial=\"BENCH-{i:05}\"/>\n\t</game>\n",
+            size = 0x8000 * (i % 64 + 1),
+        );
+    }
+    xml.push_str("</datafile>\n");
+    xml
+}
+
+/// A CUE sheet of `tracks` tracks, one file each: a data track followed
+/// by audio.
+pub fn sample_cue(tracks: u8) -> String {
+    let mut cue = String::new();
+    for n in 1..=tracks {
+        let mode = if n == 1 { "MODE2/2352" } else { "AUDIO" };
+        let _ = write!(
+            cue,
+            "FILE \"Game (USA) (Track {n:02}).bin\" BINARY\n  TRACK {n:02} {mode}\n"
+        );
+        if n > 1 {
+            cue.push_str("    INDEX 00 00:00:00\n    INDEX 01 00:02:00\n");
+        } else {
+            cue.push_str("    INDEX 01 00:00:00\n");
+        }
+    }
+    cue
+}
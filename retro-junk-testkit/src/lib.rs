@@ -0,0 +1,58 @@
+//! Test support for code that embeds retro-junk analyzers.
+//!
+//! [`synthesize`] builds a minimal, valid image for any platform with a
+//! working analyzer, so integration tests can run real analysis without
+//! shipping copyrighted ROMs. [`snapshot`] compares the results against
+//! files checked in next to the tests. [`bench`] holds the workloads the
+//! benchmarks and performance budgets run.
+//!
+//! ```no_run
+//! use retro_junk_core::{Platform, RomAnalyzer};
+//! use retro_junk_nintendo::GameBoyAnalyzer;
+//!
+//! let rom = retro_junk_testkit::synthesize(Platform::GameBoy).unwrap();
+//! let dir = std::env::temp_dir();
+//! let id = retro_junk_testkit::analyze(&rom, &GameBoyAnalyzer, &dir).unwrap();
+//! assert_eq!(id.internal_name.as_deref(), Some("TESTGAME"));
+//! ```
+
+use std::path::Path;
+
+use retro_junk_core::{AnalysisError, AnalysisOptions, Platform, RomAnalyzer, RomIdentification};
+
+pub use retro_junk_core::SyntheticRom;
+
+pub mod bench;
+pub mod snapshot;
+
+/// A synthetic image for `platform`, or `None` when its analyzer isn't
+/// implemented yet.
+pub fn synthesize(platform: Platform) -> Option<SyntheticRom> {
+    retro_junk_nintendo::synthetic::synthesize(platform)
+        .or_else(|| retro_junk_sony::synthetic::synthesize(platform))
+        .or_else(|| retro_junk_sega::synthetic::synthesize(platform))
+        .or_else(|| retro_junk_atari::synthetic::synthesize(platform))
+        .or_else(|| retro_junk_nec::synthetic::synthesize(platform))
+        .or_else(|| retro_junk_microsoft::synthetic::synthesize(platform))
+}
+
+/// Synthetic images for every platform that has one.
+pub fn synthesize_all() -> Vec<SyntheticRom> {
+    Platform::all()
+        .iter()
+        .filter_map(|&p| synthesize(p))
+        .collect()
+}
+
+/// Write `rom` into `dir` and run `analyzer` on it, with
+/// [`AnalysisOptions::file_path`] set so sheet-based formats find their
+/// tracks.
+pub fn analyze(
+    rom: &SyntheticRom,
+    analyzer: &dyn RomAnalyzer,
+    dir: &Path,
+) -> Result<RomIdentification, AnalysisError> {
+    let path = rom.write_to(dir)?;
+    let mut file = std::fs::File::open(&path)?;
+    analyzer.analyze(&mut file, &AnalysisOptions::new().file_path(path))
+}
@@ -0,0 +1,52 @@
+//! Snapshot assertions for analysis results.
+//!
+//! A snapshot is the pretty-printed JSON of a [`RomIdentification`] with
+//! map keys sorted, stored as `<dir>/<name>.snap`. Set
+//! `RETRO_JUNK_UPDATE_SNAPSHOTS=1` to write new or changed snapshots
+//! instead of failing.
+
+use std::path::Path;
+
+use retro_junk_core::RomIdentification;
+
+/// Environment variable that makes [`assert_snapshot`] rewrite snapshots.
+pub const UPDATE_ENV: &str = "RETRO_JUNK_UPDATE_SNAPSHOTS";
+
+/// Render `id` as stable text: the same identification always produces the
+/// same bytes, whatever order its maps were filled in.
+pub fn render_identification(id: &RomIdentification) -> String {
+    // serde_json's Value keeps object keys sorted
+    let value = serde_json::to_value(id).expect("RomIdentification serializes");
+    let mut text = serde_json::to_string_pretty(&value).expect("Value serializes");
+    text.push('\n');
+    text
+}
+
+/// Compare `actual` with the snapshot `name` in `dir`.
+///
+/// # Panics
+///
+/// If the snapshot is missing or differs, unless [`UPDATE_ENV`] is set, in
+/// which case the snapshot is (re)written.
+pub fn assert_snapshot(dir: &Path, name: &str, actual: &str) {
+    let path = dir.join(format!("{name}.snap"));
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        std::fs::create_dir_all(dir).expect("create snapshot directory");
+        std::fs::write(&path, actual).expect("write snapshot");
+        return;
+    }
+
+    let expected = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(_) => panic!(
+            "Missing snapshot {}; run with {UPDATE_ENV}=1 to create it.\n{actual}",
+            path.display()
+        ),
+    };
+    if expected != actual {
+        panic!(
+            "Snapshot {} differs; run with {UPDATE_ENV}=1 to accept.\n--- expected\n{expected}\n--- actual\n{actual}",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::time::{Duration, Instant};
+
+use retro_junk_testkit::bench::Workload;
+
+/// Runs per workload; the fastest counts, to ride out scheduler noise.
+const RUNS: usize = 3;
+
+#[test]
+#[ignore = "timing-sensitive; run with `cargo test --release -p retro-junk-testkit -- --ignored`"]
+fn test_hot_paths_stay_within_budget() {
+    let dir = tempfile::tempdir().unwrap();
+    for workload in Workload::ALL {
+        let prepared = workload.prepare(&dir.path().join(workload.name())).unwrap();
+        let fastest = (0..RUNS)
+            .map(|_| {
+                let start = Instant::now();
+                prepared.run();
+                start.elapsed()
+            })
+            .min()
+            .unwrap_or(Duration::MAX);
+        let budget = workload.budget();
+        assert!(
+            fastest <= budget,
+            "{} took {fastest:?}, over its {budget:?} budget",
+            workload.name()
+        );
+    }
+}
+
+#[test]
+fn test_sample_inputs_are_well_formed() {
+    let dat = retro_junk_dat::dat::parse_dat(retro_junk_testkit::bench::sample_dat(8).as_bytes())
+        .unwrap();
+    assert_eq!(dat.games.len(), 8);
+    assert_eq!(dat.games[3].clone_of.as_deref(), Some("Game 00002 (USA)"));
+
+    let cue = retro_junk_core::disc::cue::parse(&retro_junk_testkit::bench::sample_cue(3));
+    assert_eq!(cue.files.len(), 3);
+    assert_eq!(cue.files[2].tracks[0].mode, "AUDIO");
+}
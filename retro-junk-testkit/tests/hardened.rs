@@ -0,0 +1,70 @@
+use std::io::Cursor;
+
+use retro_junk_lib::{AnalysisOptions, LimitedReader, ParseLimits, create_default_context};
+use retro_junk_testkit::{analyze, synthesize_all};
+
+#[test]
+fn test_synthetic_roms_analyze_within_hardened_limits() {
+    let ctx = create_default_context();
+    let dir = tempfile::tempdir().unwrap();
+    let options = AnalysisOptions::new().limits(ParseLimits::hardened());
+
+    for rom in synthesize_all() {
+        let rom_dir = dir.path().join(rom.platform.short_name());
+        std::fs::create_dir(&rom_dir).unwrap();
+        let path = rom.write_to(&rom_dir).unwrap();
+        let (platform, _) = ctx
+            .analyze_path_with(&path, &options)
+            .unwrap_or_else(|e| panic!("{:?}: {e}", rom.platform));
+        assert_eq!(platform, rom.platform);
+    }
+}
+
+#[test]
+fn test_damaged_roms_fail_cleanly_under_hardened_limits() {
+    let ctx = create_default_context();
+    let options = AnalysisOptions::new().limits(ParseLimits::hardened());
+
+    for rom in synthesize_all() {
+        let console = ctx.get_by_platform(rom.platform).unwrap();
+        let len = rom.data.len();
+        let mut variants: Vec<Vec<u8>> = [0, 1, 16, 0x100, len / 2, len.saturating_sub(1)]
+            .into_iter()
+            .filter(|&n| n < len)
+            .map(|n| rom.data[..n].to_vec())
+            .collect();
+        // Saturate every header-sized field near the start
+        let mut saturated = rom.data.clone();
+        for b in saturated.iter_mut().take(0x1000) {
+            *b = 0xFF;
+        }
+        variants.push(saturated);
+
+        for data in variants {
+            let mut reader = LimitedReader::new(Cursor::new(data), &options.limits);
+            // Any result is fine; panicking or running away is not
+            let _ = console.analyzer.analyze(&mut reader, &options);
+        }
+    }
+}
+
+#[test]
+fn test_analyze_honors_allocation_limit() {
+    let dir = tempfile::tempdir().unwrap();
+    let rom = retro_junk_testkit::synthesize(retro_junk_lib::Platform::Genesis).unwrap();
+    let ctx = create_default_context();
+    let console = ctx
+        .get_by_platform(retro_junk_lib::Platform::Genesis)
+        .unwrap();
+    assert!(analyze(&rom, console.analyzer.as_ref(), dir.path()).is_ok());
+
+    let tiny = AnalysisOptions::new().limits(ParseLimits {
+        max_alloc: 16,
+        ..ParseLimits::hardened()
+    });
+    let err = console
+        .analyzer
+        .analyze(&mut Cursor::new(rom.data), &tiny)
+        .unwrap_err();
+    assert_eq!(err.code(), "limit_exceeded");
+}
@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use retro_junk_lib::create_default_context;
+use retro_junk_testkit::snapshot::{assert_snapshot, render_identification};
+use retro_junk_testkit::{analyze, synthesize, synthesize_all};
+
+const SNAPSHOT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots");
+
+#[test]
+fn test_synthetic_roms_match_snapshots() {
+    let ctx = create_default_context();
+    let dir = tempfile::tempdir().unwrap();
+
+    for console in ctx.consoles() {
+        let platform = console.metadata.platform;
+        let Some(rom) = synthesize(platform) else {
+            continue;
+        };
+        let rom_dir = dir.path().join(platform.short_name());
+        std::fs::create_dir(&rom_dir).unwrap();
+        let id = analyze(&rom, console.analyzer.as_ref(), &rom_dir)
+            .unwrap_or_else(|e| panic!("{platform:?}: {e}"));
+        assert_eq!(id.platform, Some(platform));
+        assert_snapshot(
+            Path::new(SNAPSHOT_DIR),
+            platform.short_name(),
+            &render_identification(&id),
+        );
+    }
+}
+
+#[test]
+fn test_analyze_path_picks_the_synthesized_platform() {
+    let ctx = create_default_context();
+    let dir = tempfile::tempdir().unwrap();
+
+    for rom in synthesize_all() {
+        let rom_dir = dir.path().join(rom.platform.short_name());
+        std::fs::create_dir(&rom_dir).unwrap();
+        let path = rom.write_to(&rom_dir).unwrap();
+        let (platform, _) = ctx
+            .analyze_path(&path)
+            .unwrap_or_else(|e| panic!("{:?}: {e}", rom.platform));
+        assert_eq!(platform, rom.platform);
+    }
+}